@@ -6,7 +6,11 @@ use std::collections::BTreeMap;
 use std::fs::File;
 use std::path::PathBuf;
 
+use arch::numa::numa_distance;
+use arch::numa::numa_node_of_cpu;
 use arch::CpuSet;
+use arch::NumaMemoryRange;
+use arch::NumaNode;
 use arch::SERIAL_ADDR;
 use cros_fdt::Error;
 use cros_fdt::FdtWriter;
@@ -61,7 +65,15 @@ const IRQ_TYPE_EDGE_RISING: u32 = 0x00000001;
 const IRQ_TYPE_LEVEL_HIGH: u32 = 0x00000004;
 const IRQ_TYPE_LEVEL_LOW: u32 = 0x00000008;
 
-fn create_memory_node(fdt: &mut FdtWriter, guest_mem: &GuestMemory) -> Result<()> {
+fn create_memory_node(
+    fdt: &mut FdtWriter,
+    guest_mem: &GuestMemory,
+    numa_ranges: &[NumaMemoryRange],
+) -> Result<()> {
+    if !numa_ranges.is_empty() {
+        return create_numa_memory_nodes(fdt, guest_mem, numa_ranges);
+    }
+
     let mut mem_reg_prop = Vec::new();
     let mut previous_memory_region_end = None;
     let mut regions = guest_mem.guest_memory_regions();
@@ -94,6 +106,60 @@ fn create_memory_node(fdt: &mut FdtWriter, guest_mem: &GuestMemory) -> Result<()
     Ok(())
 }
 
+fn create_numa_memory_node(fdt: &mut FdtWriter, address: u64, size: u64, node: u32) -> Result<()> {
+    let memory_node = fdt.begin_node(&format!("memory@{:x}", address))?;
+    fdt.property_string("device_type", "memory")?;
+    fdt.property_array_u64("reg", &[address, size])?;
+    fdt.property_u32("numa-node-id", node)?;
+    fdt.end_node(memory_node)?;
+    Ok(())
+}
+
+// With NUMA, each memory range gets its own node to hold the id of the NUMA node it belongs to.
+fn create_numa_memory_nodes(
+    fdt: &mut FdtWriter,
+    guest_mem: &GuestMemory,
+    numa_ranges: &[NumaMemoryRange],
+) -> Result<()> {
+    for range in numa_ranges {
+        create_numa_memory_node(fdt, range.address.offset(), range.size, range.node as u32)?;
+    }
+
+    // The regions that aren't part of the RAM of the nodes, such as the swiotlb pool, go to the
+    // first node since Linux rejects the NUMA layout if some memory has no node.
+    for (address, size) in guest_mem.guest_memory_regions() {
+        let end = address.unchecked_add(size as u64);
+        if address.offset() == AARCH64_PROTECTED_VM_FW_START
+            || numa_ranges
+                .iter()
+                .any(|range| range.address >= address && range.address < end)
+        {
+            continue;
+        }
+        create_numa_memory_node(fdt, address.offset(), size as u64, 0)?;
+    }
+
+    Ok(())
+}
+
+fn create_distance_map_node(fdt: &mut FdtWriter, numa_nodes: &[NumaNode]) -> Result<()> {
+    let mut distance_matrix = Vec::new();
+    for from in 0..numa_nodes.len() {
+        for to in 0..numa_nodes.len() {
+            distance_matrix.push(from as u32);
+            distance_matrix.push(to as u32);
+            distance_matrix.push(numa_distance(numa_nodes, from, to) as u32);
+        }
+    }
+
+    let distance_map_node = fdt.begin_node("distance-map")?;
+    fdt.property_string("compatible", "numa-distance-map-v1")?;
+    fdt.property_array_u32("distance-matrix", &distance_matrix)?;
+    fdt.end_node(distance_map_node)?;
+
+    Ok(())
+}
+
 fn create_resv_memory_node(
     fdt: &mut FdtWriter,
     resv_addr_and_size: (Option<GuestAddress>, u64),
@@ -126,6 +192,7 @@ fn create_cpu_nodes(
     fdt: &mut FdtWriter,
     num_cpus: u32,
     cpu_clusters: Vec<CpuSet>,
+    numa_nodes: &[NumaNode],
     cpu_capacity: BTreeMap<usize, u32>,
     dynamic_power_coefficient: BTreeMap<usize, u32>,
 ) -> Result<()> {
@@ -150,6 +217,9 @@ fn create_cpu_nodes(
         if let Some(capacity) = cpu_capacity.get(&(cpu_id as usize)) {
             fdt.property_u32("capacity-dmips-mhz", *capacity)?;
         }
+        if let Some(node) = numa_node_of_cpu(numa_nodes, cpu_id as usize) {
+            fdt.property_u32("numa-node-id", node as u32)?;
+        }
 
         fdt.end_node(cpu_node)?;
     }
@@ -543,6 +613,8 @@ fn create_vmwdt_node(fdt: &mut FdtWriter, vmwdt_cfg: VmWdtConfig) -> Result<()>
 /// * `pci_cfg` - Location of the memory-mapped PCI configuration space.
 /// * `pci_ranges` - Memory ranges accessible via the PCI host controller.
 /// * `num_cpus` - Number of virtual CPUs the guest will have
+/// * `numa_nodes` - NUMA nodes of the guest, empty for a single node
+/// * `numa_ranges` - Guest memory ranges of the NUMA nodes
 /// * `fdt_address` - The offset into physical memory for the device tree
 /// * `cmdline` - The kernel commandline
 /// * `initrd` - An optional tuple of initrd guest physical address and size
@@ -563,6 +635,8 @@ pub fn create_fdt(
     num_cpus: u32,
     cpu_clusters: Vec<CpuSet>,
     cpu_capacity: BTreeMap<usize, u32>,
+    numa_nodes: &[NumaNode],
+    numa_ranges: &[NumaMemoryRange],
     fdt_address: GuestAddress,
     cmdline: &str,
     image: (GuestAddress, usize),
@@ -593,7 +667,7 @@ pub fn create_fdt(
     }
     create_chosen_node(&mut fdt, cmdline, initrd)?;
     create_config_node(&mut fdt, image)?;
-    create_memory_node(&mut fdt, guest_mem)?;
+    create_memory_node(&mut fdt, guest_mem, numa_ranges)?;
     let dma_pool_phandle = match swiotlb {
         Some(x) => {
             let phandle = create_resv_memory_node(&mut fdt, x)?;
@@ -606,9 +680,13 @@ pub fn create_fdt(
         &mut fdt,
         num_cpus,
         cpu_clusters,
+        numa_nodes,
         cpu_capacity,
         dynamic_power_coefficient,
     )?;
+    if !numa_nodes.is_empty() {
+        create_distance_map_node(&mut fdt, numa_nodes)?;
+    }
     create_gic_node(&mut fdt, is_gicv3, num_cpus as u64)?;
     create_timer_node(&mut fdt, num_cpus)?;
     if use_pmu {
@@ -35,6 +35,7 @@ use devices::PciConfigMmio;
 use devices::PciDevice;
 use devices::PciRootCommand;
 use devices::Serial;
+use devices::VcpuHotPlugState;
 #[cfg(feature = "gdb")]
 use gdbstub::arch::Arch;
 #[cfg(feature = "gdb")]
@@ -115,6 +116,18 @@ const PSR_I_BIT: u64 = 0x00000080;
 const PSR_A_BIT: u64 = 0x00000100;
 const PSR_D_BIT: u64 = 0x00000200;
 
+// PSCI functions and return values handled by the VMM when vCPU hotplug is enabled, from the Arm
+// Power State Coordination Interface specification.
+pub const PSCI_CPU_OFF: u32 = 0x8400_0002;
+pub const PSCI_CPU_ON_32: u32 = 0x8400_0003;
+pub const PSCI_CPU_ON_64: u32 = 0xc400_0003;
+pub const PSCI_SUCCESS: i32 = 0;
+pub const PSCI_NOT_SUPPORTED: i32 = -1;
+pub const PSCI_INVALID_PARAMETERS: i32 = -2;
+pub const PSCI_DENIED: i32 = -3;
+pub const PSCI_ALREADY_ON: i32 = -4;
+pub const PSCI_INTERNAL_FAILURE: i32 = -6;
+
 enum PayloadType {
     Bios {
         entry: GuestAddress,
@@ -211,6 +224,9 @@ pub enum Error {
     AllocateIrq,
     #[error("bios could not be loaded: {0}")]
     BiosLoadFailure(arch::LoadImageError),
+    #[cfg(unix)]
+    #[error("failed to bind guest memory to host NUMA node: {0}")]
+    BindNumaMemory(GuestMemoryError),
     #[error("failed to build arm pvtime memory: {0}")]
     BuildPvtimeError(base::MmapError),
     #[error("unable to clone an Event: {0}")]
@@ -245,6 +261,8 @@ pub enum Error {
     EnableSinglestep(base::Error),
     #[error("failed to finalize IRQ chip: {0}")]
     FinalizeIrqChip(base::Error),
+    #[error("failed to forward the PSCI calls to the VMM: {0}")]
+    ForwardPsciCalls(base::Error),
     #[error("failed to get HW breakpoint count: {0}")]
     GetMaxHwBreakPoint(base::Error),
     #[error("failed to get PSCI version: {0}")]
@@ -390,6 +408,14 @@ impl arch::LinuxArch for AArch64 {
         let has_bios = matches!(components.vm_image, VmImage::Bios(_));
         let mem = vm.get_memory().clone();
 
+        let numa_ranges = arch::numa::numa_memory_ranges(
+            &components.numa_nodes,
+            &[(GuestAddress(AARCH64_PHYS_MEM_START), components.memory_size)],
+        );
+        #[cfg(unix)]
+        arch::numa::bind_numa_memory(&mem, &components.numa_nodes, &numa_ranges)
+            .map_err(Error::BindNumaMemory)?;
+
         // separate out image loading from other setup to get a specific error for
         // image loading
         let mut initrd = None;
@@ -472,11 +498,26 @@ impl arch::LinuxArch for AArch64 {
         }
 
         // Initialize Vcpus after all Vcpu objects have been created.
-        for (vcpu_id, vcpu) in vcpus.iter().enumerate() {
-            vcpu.init(&Self::vcpu_features(vcpu_id, use_pmu))
-                .map_err(Error::VcpuInit)?;
+        for (vcpu_id, (vcpu, init)) in vcpus.iter().zip(vcpu_init.iter_mut()).enumerate() {
+            let features = Self::vcpu_features(vcpu_id, use_pmu);
+            vcpu.init(&features).map_err(Error::VcpuInit)?;
+            init.features = features;
         }
 
+        // With vCPU hotplug, the VMM handles the PSCI calls powering the vCPUs on and off so that
+        // only the plugged vCPUs can be started.
+        let vcpu_hotplug = match components.boot_vcpu_count {
+            Some(boot_vcpu_count) => {
+                vm.forward_smccc_calls(&[PSCI_CPU_OFF, PSCI_CPU_ON_32, PSCI_CPU_ON_64])
+                    .map_err(Error::ForwardPsciCalls)?;
+                Some(Arc::new(Mutex::new(VcpuHotPlugState::new(
+                    vcpu_count,
+                    boot_vcpu_count,
+                ))))
+            }
+            None => None,
+        };
+
         irq_chip.finalize().map_err(Error::FinalizeIrqChip)?;
 
         if has_pvtime {
@@ -684,6 +725,8 @@ impl arch::LinuxArch for AArch64 {
             vcpu_count as u32,
             components.cpu_clusters,
             components.cpu_capacity,
+            &components.numa_nodes,
+            &numa_ranges,
             fdt_offset,
             cmdline.as_str(),
             (payload.entry(), payload.size() as usize),
@@ -716,6 +759,7 @@ impl arch::LinuxArch for AArch64 {
         Ok(RunnableLinuxVm {
             vm,
             vcpu_count,
+            vcpu_hotplug,
             vcpus: Some(vcpus),
             vcpu_init,
             vcpu_affinity: components.vcpu_affinity,
@@ -988,7 +1032,10 @@ impl AArch64 {
             }
         }
 
-        VcpuInitAArch64 { regs }
+        VcpuInitAArch64 {
+            regs,
+            ..Default::default()
+        }
     }
 }
 
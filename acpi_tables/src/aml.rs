@@ -39,6 +39,7 @@ const OPREGIONOP: u8 = 0x80;
 const FIELDOP: u8 = 0x81;
 const DEVICEOP: u8 = 0x82;
 const POWERRESOURCEOP: u8 = 0x84;
+const THERMALZONEOP: u8 = 0x85;
 
 const LOCAL0OP: u8 = 0x60;
 const ARG0OP: u8 = 0x68;
@@ -80,6 +81,7 @@ const ONESOP: u8 = 0xff;
 // AML resouce data fields
 const IOPORTDESC: u8 = 0x47;
 const ENDTAG: u8 = 0x79;
+const GENERICREGDESC: u8 = 0x82;
 const MEMORY32FIXEDDESC: u8 = 0x86;
 const DWORDADDRSPACEDESC: u8 = 0x87;
 const WORDADDRSPACEDESC: u8 = 0x88;
@@ -711,6 +713,47 @@ impl Aml for Interrupt {
     }
 }
 
+/// Generic Register resource object, describing a register in an address space.
+pub struct GenericRegister {
+    space: OpRegionSpace,
+    bit_width: u8,
+    bit_offset: u8,
+    address: u64,
+    access_size: u8,
+}
+
+impl GenericRegister {
+    /// Create Generic Register object. `access_size` is 0 when undefined, or 1 to 4 for byte,
+    /// word, dword and qword accesses.
+    pub fn new(
+        space: OpRegionSpace,
+        bit_width: u8,
+        bit_offset: u8,
+        address: u64,
+        access_size: u8,
+    ) -> Self {
+        GenericRegister {
+            space,
+            bit_width,
+            bit_offset,
+            address,
+            access_size,
+        }
+    }
+}
+
+impl Aml for GenericRegister {
+    fn to_aml_bytes(&self, bytes: &mut Vec<u8>) {
+        bytes.push(GENERICREGDESC); /* Generic Register Descriptor */
+        bytes.append(&mut 12u16.to_le_bytes().to_vec());
+        bytes.push(self.space as u8);
+        bytes.push(self.bit_width);
+        bytes.push(self.bit_offset);
+        bytes.push(self.access_size);
+        bytes.append(&mut self.address.to_le_bytes().to_vec());
+    }
+}
+
 /// Device object with its device name and children objects in it.
 pub struct Device<'a> {
     path: Path,
@@ -1618,6 +1661,39 @@ impl<'a> Aml for PowerResource<'a> {
     }
 }
 
+/// Thermal Zone object with its name and children objects in it.
+pub struct ThermalZone<'a> {
+    path: Path,
+    children: Vec<&'a dyn Aml>,
+}
+
+impl<'a> ThermalZone<'a> {
+    /// Create Thermal Zone object
+    pub fn new(path: Path, children: Vec<&'a dyn Aml>) -> Self {
+        ThermalZone { path, children }
+    }
+}
+
+impl<'a> Aml for ThermalZone<'a> {
+    fn to_aml_bytes(&self, aml: &mut Vec<u8>) {
+        let mut bytes = Vec::new();
+        self.path.to_aml_bytes(&mut bytes);
+        for child in &self.children {
+            child.to_aml_bytes(&mut bytes);
+        }
+
+        let mut pkg_length = create_pkg_length(&bytes, true);
+        pkg_length.reverse();
+        for byte in pkg_length {
+            bytes.insert(0, byte);
+        }
+
+        bytes.insert(0, THERMALZONEOP);
+        bytes.insert(0, EXTOPPREFIX);
+        aml.append(&mut bytes)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2428,4 +2504,67 @@ mod tests {
         .to_aml_bytes(&mut aml);
         assert_eq!(aml, &buffer_data[..])
     }
+
+    #[test]
+    fn test_generic_register() {
+        /*
+        Name (_PCT, ResourceTemplate ()
+        {
+            Register (SystemIO,
+                0x20,               // Bit Width
+                0x00,               // Bit Offset
+                0x0000000000000B2C, // Address
+                0x03,               // Access Size
+                )
+        })
+        */
+        let register_data = [
+            0x08, 0x5F, 0x50, 0x43, 0x54, 0x11, 0x14, 0x0A, 0x11, 0x82, 0x0C, 0x00, 0x01, 0x20,
+            0x00, 0x03, 0x2C, 0x0B, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x79, 0x00,
+        ];
+        let mut aml = Vec::new();
+
+        Name::new(
+            "_PCT".into(),
+            &ResourceTemplate::new(vec![&GenericRegister::new(
+                OpRegionSpace::SystemIO,
+                32,
+                0,
+                0xb2c,
+                3,
+            )]),
+        )
+        .to_aml_bytes(&mut aml);
+        assert_eq!(aml, &register_data[..])
+    }
+
+    #[test]
+    fn test_thermal_zone() {
+        /*
+        ThermalZone (TZ00)
+        {
+            Method (_CRT, 0, NotSerialized)  // _CRT: Critical Temperature
+            {
+                Return (One)
+            }
+        }
+        */
+        let thermal_zone_data = [
+            0x5B, 0x85, 0x0E, 0x54, 0x5A, 0x30, 0x30, 0x14, 0x08, 0x5F, 0x43, 0x52, 0x54, 0x00,
+            0xA4, 0x01,
+        ];
+        let mut aml = Vec::new();
+
+        ThermalZone::new(
+            "TZ00".into(),
+            vec![&Method::new(
+                "_CRT".into(),
+                0,
+                false,
+                vec![&Return::new(&ONE)],
+            )],
+        )
+        .to_aml_bytes(&mut aml);
+        assert_eq!(aml, &thermal_zone_data[..])
+    }
 }
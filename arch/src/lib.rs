@@ -5,8 +5,10 @@
 //! Virtual machine architecture support code.
 
 pub mod android;
+pub mod numa;
 pub mod pstore;
 pub mod serial;
+pub mod smbios;
 
 pub mod sys;
 
@@ -53,6 +55,11 @@ use devices::PreferredIrq;
 use devices::ProxyDevice;
 use devices::SerialHardware;
 use devices::SerialParameters;
+#[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), unix))]
+use devices::ThermalConfig;
+#[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), unix))]
+use devices::ThermalState;
+use devices::VcpuHotPlugState;
 use devices::VirtioMmioDevice;
 #[cfg(feature = "gdb")]
 use gdbstub::arch::Arch;
@@ -62,6 +69,8 @@ use hypervisor::Vm;
 use jail::FakeMinijailStub as Minijail;
 #[cfg(unix)]
 use minijail::Minijail;
+pub use numa::NumaMemoryRange;
+pub use numa::NumaNode;
 use remain::sorted;
 #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
 use resources::AddressRange;
@@ -76,6 +85,7 @@ pub use serial::get_serial_cmdline;
 pub use serial::set_default_serial_parameters;
 pub use serial::GetSerialCmdlineError;
 pub use serial::SERIAL_ADDR;
+pub use smbios::SmbiosConfig;
 #[cfg(unix)]
 use sync::Condvar;
 use sync::Mutex;
@@ -314,6 +324,8 @@ pub struct VmComponents {
     pub ac_adapter: bool,
     pub acpi_sdts: Vec<SDT>,
     pub android_fstab: Option<File>,
+    /// Number of vCPUs online at boot when the others can be hot-plugged later.
+    pub boot_vcpu_count: Option<usize>,
     pub cpu_capacity: BTreeMap<usize, u32>,
     pub cpu_clusters: Vec<CpuSet>,
     pub delay_rt: bool,
@@ -337,6 +349,7 @@ pub struct VmComponents {
     pub no_i8042: bool,
     pub no_rtc: bool,
     pub no_smt: bool,
+    pub numa_nodes: Vec<NumaNode>,
     #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
     pub oem_strings: Vec<String>,
     #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
@@ -350,7 +363,11 @@ pub struct VmComponents {
     /// `hv_cfg.protection_type == ProtectionType::UnprotectedWithFirmware`.
     pub pvm_fw: Option<File>,
     pub rt_cpus: CpuSet,
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    pub smbios: SmbiosConfig,
     pub swiotlb: Option<u64>,
+    #[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), unix))]
+    pub thermal: Option<ThermalConfig>,
     pub vcpu_affinity: Option<VcpuAffinity>,
     pub vcpu_count: usize,
     pub vm_image: VmImage,
@@ -379,8 +396,13 @@ pub struct RunnableLinuxVm<V: VmArch, Vcpu: VcpuArch> {
     pub root_config: Arc<Mutex<PciRoot>>,
     pub rt_cpus: CpuSet,
     pub suspend_evt: Event,
+    /// State of the emulated thermal zone and processor performance states, if enabled.
+    #[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), unix))]
+    pub thermal: Option<Arc<Mutex<ThermalState>>>,
     pub vcpu_affinity: Option<VcpuAffinity>,
     pub vcpu_count: usize,
+    /// Plugged state of the vCPUs, if vCPU hotplug is enabled.
+    pub vcpu_hotplug: Option<Arc<Mutex<VcpuHotPlugState>>>,
     pub vcpu_init: Vec<VcpuInitArch>,
     /// If vcpus is None, then it's the responsibility of the vcpu thread to create vcpus.
     /// If it's Some, then `build_vm` already created the vcpus.
@@ -0,0 +1,189 @@
+// Copyright 2023 The ChromiumOS Authors
+// Use of this source code is governed by a BSD-style license that can be
+// found in the LICENSE file.
+
+//! NUMA topology of the guest, shared by the ACPI and device tree writers of each architecture.
+
+use vm_memory::GuestAddress;
+#[cfg(unix)]
+use vm_memory::GuestMemory;
+
+use crate::CpuSet;
+
+/// Distance of a node to itself, as defined by the ACPI SLIT.
+pub const NUMA_LOCAL_DISTANCE: u8 = 10;
+/// Distance between two different nodes when none is specified.
+pub const NUMA_REMOTE_DISTANCE: u8 = 20;
+
+/// A NUMA node of the guest. Nodes are identified by their index in `VmComponents::numa_nodes`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct NumaNode {
+    /// Indices of the vCPUs of the node.
+    pub cpus: CpuSet,
+    /// Amount of guest memory in the node, in bytes.
+    pub memory_size: u64,
+    /// Host NUMA node the guest memory of the node is allocated from.
+    pub host_node: Option<u32>,
+    /// Distance to each node, by node index. Empty to use the default distances.
+    pub distances: Vec<u8>,
+}
+
+/// A range of guest physical memory belonging to a NUMA node.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NumaMemoryRange {
+    /// Index of the node in `VmComponents::numa_nodes`.
+    pub node: usize,
+    pub address: GuestAddress,
+    pub size: u64,
+}
+
+/// Returns the distance from node `from` to node `to`.
+pub fn numa_distance(nodes: &[NumaNode], from: usize, to: usize) -> u8 {
+    match nodes[from].distances.get(to) {
+        Some(distance) => *distance,
+        None if from == to => NUMA_LOCAL_DISTANCE,
+        None => NUMA_REMOTE_DISTANCE,
+    }
+}
+
+/// Returns the index of the node owning vCPU `cpu`.
+pub fn numa_node_of_cpu(nodes: &[NumaNode], cpu: usize) -> Option<usize> {
+    nodes.iter().position(|node| node.cpus.contains(&cpu))
+}
+
+/// Splits the guest RAM made of `ram_regions` between `nodes`, giving each node the next
+/// `memory_size` bytes in guest physical address order. A node can end up with several ranges when
+/// its memory spans a hole of the address space.
+pub fn numa_memory_ranges(
+    nodes: &[NumaNode],
+    ram_regions: &[(GuestAddress, u64)],
+) -> Vec<NumaMemoryRange> {
+    let mut regions = ram_regions.to_vec();
+    regions.sort();
+
+    let mut ranges = Vec::new();
+    let mut regions = regions.into_iter();
+    let mut region = regions.next();
+    for (index, node) in nodes.iter().enumerate() {
+        let mut remaining = node.memory_size;
+        while remaining > 0 {
+            let (address, size) = match region {
+                Some(r) => r,
+                None => return ranges,
+            };
+            let range_size = remaining.min(size);
+            ranges.push(NumaMemoryRange {
+                node: index,
+                address,
+                size: range_size,
+            });
+            remaining -= range_size;
+            region = if range_size < size {
+                Some((address.unchecked_add(range_size), size - range_size))
+            } else {
+                regions.next()
+            };
+        }
+    }
+    ranges
+}
+
+/// Makes the host allocate the memory of each range from the host node of its NUMA node, if any.
+#[cfg(unix)]
+pub fn bind_numa_memory(
+    mem: &GuestMemory,
+    nodes: &[NumaNode],
+    ranges: &[NumaMemoryRange],
+) -> vm_memory::Result<()> {
+    for range in ranges {
+        if let Some(host_node) = nodes[range.node].host_node {
+            mem.bind_range_to_host_node(range.address, range.size, host_node)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(cpus: &[usize], memory_size: u64) -> NumaNode {
+        NumaNode {
+            cpus: CpuSet::new(cpus.iter().copied()),
+            memory_size,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn memory_ranges_single_region() {
+        let nodes = [node(&[0], 0x1000), node(&[1], 0x3000)];
+        let ranges = numa_memory_ranges(&nodes, &[(GuestAddress(0x10000), 0x4000)]);
+        assert_eq!(
+            ranges,
+            vec![
+                NumaMemoryRange {
+                    node: 0,
+                    address: GuestAddress(0x10000),
+                    size: 0x1000,
+                },
+                NumaMemoryRange {
+                    node: 1,
+                    address: GuestAddress(0x11000),
+                    size: 0x3000,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn memory_ranges_span_hole() {
+        let nodes = [node(&[0], 0x3000), node(&[1], 0x2000)];
+        // Regions out of order, the node 0 memory spans the hole between them.
+        let ranges = numa_memory_ranges(
+            &nodes,
+            &[
+                (GuestAddress(0x100000), 0x3000),
+                (GuestAddress(0x1000), 0x2000),
+            ],
+        );
+        assert_eq!(
+            ranges,
+            vec![
+                NumaMemoryRange {
+                    node: 0,
+                    address: GuestAddress(0x1000),
+                    size: 0x2000,
+                },
+                NumaMemoryRange {
+                    node: 0,
+                    address: GuestAddress(0x100000),
+                    size: 0x1000,
+                },
+                NumaMemoryRange {
+                    node: 1,
+                    address: GuestAddress(0x101000),
+                    size: 0x2000,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn distances() {
+        let mut nodes = vec![node(&[0, 1], 0x1000), node(&[2], 0x1000)];
+        assert_eq!(numa_distance(&nodes, 0, 0), NUMA_LOCAL_DISTANCE);
+        assert_eq!(numa_distance(&nodes, 0, 1), NUMA_REMOTE_DISTANCE);
+        nodes[1].distances = vec![32, 10];
+        assert_eq!(numa_distance(&nodes, 1, 0), 32);
+        assert_eq!(numa_distance(&nodes, 1, 1), NUMA_LOCAL_DISTANCE);
+    }
+
+    #[test]
+    fn node_of_cpu() {
+        let nodes = [node(&[0, 2], 0x1000), node(&[1, 3], 0x1000)];
+        assert_eq!(numa_node_of_cpu(&nodes, 2), Some(0));
+        assert_eq!(numa_node_of_cpu(&nodes, 3), Some(1));
+        assert_eq!(numa_node_of_cpu(&nodes, 4), None);
+    }
+}
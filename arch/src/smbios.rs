@@ -0,0 +1,178 @@
+// Copyright 2023 The ChromiumOS Authors
+// Use of this source code is governed by a BSD-style license that can be
+// found in the LICENSE file.
+
+//! SMBIOS structures given to the guest in addition to, or instead of, the ones crosvm generates.
+//! They are read from the JSON file passed to `--smbios`, and the string fields left out keep the
+//! value crosvm uses by default, or are left empty.
+
+use serde::Deserialize;
+use serde::Serialize;
+use uuid::Uuid;
+
+/// Version of the SMBIOS entry point structure.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub enum SmbiosEntryPoint {
+    /// 32-bit entry point (`_SM_`), for guests and firmware predating SMBIOS 3.0. The structure
+    /// table is limited to 64 KiB below 4 GiB.
+    #[serde(rename = "2.8")]
+    Smbios2,
+    /// 64-bit entry point (`_SM3_`).
+    #[default]
+    #[serde(rename = "3.0")]
+    Smbios3,
+}
+
+/// BIOS Information (type 0).
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+pub struct SmbiosBiosConfig {
+    pub vendor: Option<String>,
+    pub version: Option<String>,
+    pub release_date: Option<String>,
+}
+
+/// System Information (type 1).
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+pub struct SmbiosSystemConfig {
+    pub manufacturer: Option<String>,
+    pub product_name: Option<String>,
+    pub version: Option<String>,
+    pub serial_number: Option<String>,
+    pub uuid: Option<Uuid>,
+    pub sku: Option<String>,
+    pub family: Option<String>,
+}
+
+/// Baseboard Information (type 2).
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+pub struct SmbiosBaseboardConfig {
+    pub manufacturer: Option<String>,
+    pub product: Option<String>,
+    pub version: Option<String>,
+    pub serial_number: Option<String>,
+    pub asset_tag: Option<String>,
+}
+
+/// System Enclosure or Chassis (type 3).
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+pub struct SmbiosChassisConfig {
+    pub manufacturer: Option<String>,
+    /// Chassis type, as numbered by the SMBIOS specification. (default: 1, other)
+    pub chassis_type: Option<u8>,
+    pub version: Option<String>,
+    pub serial_number: Option<String>,
+    pub asset_tag: Option<String>,
+    pub sku: Option<String>,
+}
+
+/// Processor Information (type 4).
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+pub struct SmbiosProcessorConfig {
+    pub socket_designation: Option<String>,
+    /// Processor family, as numbered by the SMBIOS specification. (default: 2, unknown)
+    pub family: Option<u16>,
+    pub manufacturer: Option<String>,
+    pub version: Option<String>,
+    /// Maximum and current speed in MHz.
+    pub max_speed: Option<u16>,
+    pub current_speed: Option<u16>,
+    pub serial_number: Option<String>,
+    pub asset_tag: Option<String>,
+    pub part_number: Option<String>,
+    pub core_count: Option<u16>,
+    pub thread_count: Option<u16>,
+}
+
+/// Memory Device (type 17). crosvm adds the Physical Memory Array (type 16) the devices belong to.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+pub struct SmbiosMemoryDeviceConfig {
+    /// Size in MiB.
+    pub size: u32,
+    pub locator: Option<String>,
+    pub bank_locator: Option<String>,
+    /// Memory type, as numbered by the SMBIOS specification. (default: 2, unknown)
+    pub memory_type: Option<u8>,
+    /// Speed in MT/s.
+    pub speed: Option<u16>,
+    pub manufacturer: Option<String>,
+    pub serial_number: Option<String>,
+    pub asset_tag: Option<String>,
+    pub part_number: Option<String>,
+}
+
+/// A structure written as is, after a header with its type, length and handle.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+pub struct SmbiosRawStructure {
+    #[serde(rename = "type")]
+    pub typ: u8,
+    /// Formatted area following the header.
+    #[serde(default)]
+    pub data: Vec<u8>,
+    /// Strings referenced by index from `data`.
+    #[serde(default)]
+    pub strings: Vec<String>,
+}
+
+/// SMBIOS structures of the guest.
+///
+/// Raw structures of type 0 or 1 replace the BIOS and System Information crosvm generates, and the
+/// other structures are added to the table.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+pub struct SmbiosConfig {
+    #[serde(default)]
+    pub entry_point: SmbiosEntryPoint,
+    pub bios: Option<SmbiosBiosConfig>,
+    pub system: Option<SmbiosSystemConfig>,
+    pub baseboard: Option<SmbiosBaseboardConfig>,
+    pub chassis: Option<SmbiosChassisConfig>,
+    #[serde(default)]
+    pub processors: Vec<SmbiosProcessorConfig>,
+    /// Added to the strings given with `--oem-strings`.
+    #[serde(default)]
+    pub oem_strings: Vec<String>,
+    #[serde(default)]
+    pub memory_devices: Vec<SmbiosMemoryDeviceConfig>,
+    #[serde(default)]
+    pub raw: Vec<SmbiosRawStructure>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_smbios_config() {
+        let config: SmbiosConfig = serde_json::from_str(
+            r#"{
+                "entry-point": "2.8",
+                "system": {
+                    "product-name": "Test Machine",
+                    "uuid": "ecd3a8f4-2c7b-4dc7-8d6c-3c0f0c1b1a2e"
+                },
+                "processors": [{ "version": "Test CPU", "core-count": 4 }],
+                "memory-devices": [{ "size": 4096, "locator": "DIMM 0" }],
+                "raw": [{ "type": 200, "data": [1, 2], "strings": ["x"] }]
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(config.entry_point, SmbiosEntryPoint::Smbios2);
+        let system = config.system.unwrap();
+        assert_eq!(system.product_name.as_deref(), Some("Test Machine"));
+        assert_eq!(system.manufacturer, None);
+        assert_eq!(config.processors[0].core_count, Some(4));
+        assert_eq!(config.memory_devices[0].size, 4096);
+        assert_eq!(config.raw[0].typ, 200);
+
+        let config: SmbiosConfig = serde_json::from_str("{}").unwrap();
+        assert_eq!(config, SmbiosConfig::default());
+        assert!(serde_json::from_str::<SmbiosConfig>(r#"{ "bios": { "vendr": "" } }"#).is_err());
+    }
+}
@@ -2,8 +2,12 @@
 // Use of this source code is governed by a BSD-style license that can be
 // found in the LICENSE file.
 
+use std::cmp::min;
 use std::fs::File;
 use std::io::Error as IOError;
+use std::io::Read;
+use std::io::Seek;
+use std::io::SeekFrom;
 use std::slice;
 use std::sync::atomic::AtomicUsize;
 use std::sync::atomic::Ordering;
@@ -12,6 +16,8 @@ use std::time::Duration;
 use std::time::Instant;
 
 use async_trait::async_trait;
+use audio_streams::capture::AsyncCaptureBuffer;
+use audio_streams::capture::AsyncCaptureBufferStream;
 use audio_streams::AsyncBufferCommit;
 use audio_streams::AsyncPlaybackBuffer;
 use audio_streams::AsyncPlaybackBufferStream;
@@ -20,6 +26,7 @@ use audio_streams::BoxError;
 use audio_streams::NoopStreamControl;
 use audio_streams::SampleFormat;
 use audio_streams::StreamControl;
+use audio_streams::StreamEffect;
 use audio_streams::StreamSource;
 use audio_streams::StreamSourceGenerator;
 use base::warn;
@@ -29,12 +36,21 @@ use base::MemoryMappingBuilder;
 use base::MmapError;
 use thiserror::Error as ThisError;
 
+use crate::wav::WavHeader;
+use crate::wav::WAV_HEADER_LEN;
+
 #[derive(ThisError, Debug)]
 pub enum Error {
     #[error("Failed to build memory mapping: {0}")]
     BuildMemoryMapping(MmapError),
     #[error("Failed to clone file descriptor: {0}")]
     Clone(IOError),
+    #[error("Stream format ({0:?}) does not match the capture file ({1:?})")]
+    FormatMismatch(WavHeader, WavHeader),
+    #[error("Invalid WAV file: {0}")]
+    InvalidWavFile(&'static str),
+    #[error("Failed to read WAV file: {0}")]
+    ReadWavFile(IOError),
     #[error("Not implemented")]
     Unimplemented,
 }
@@ -46,10 +62,15 @@ pub enum Error {
 /// Note that `FileStream` also needs the mmap-ed file has allocated some spaces
 /// to be written. If the playback buffer exceeds the allocated spaces,
 /// it will invoke `panic!`
+///
+/// The samples are preceded by a WAV header describing the format of the stream, which is
+/// updated with the length of the written data as the playback goes.
 pub struct FileStream {
     /// A MemoryMapping that will hold the copy of the playback buffer.
     memory_mapping: AudioMemoryMapping,
-    /// Number of bytes that has been written.
+    /// Format of the stream, written at the start of the file.
+    wav_header: WavHeader,
+    /// Offset in the file of the next byte to be written.
     offset: Arc<AtomicUsize>,
     /// Number of bytes in a single audio frame.
     frame_size: usize,
@@ -69,14 +90,16 @@ pub struct FileStream {
 impl FileStream {
     fn new(
         memory_mapping: AudioMemoryMapping,
+        wav_header: WavHeader,
         offset: Arc<AtomicUsize>,
         frame_size: usize,
         buffer_mem_length: usize,
         interval_ms: Duration,
     ) -> Self {
         let max_offset = memory_mapping.size();
-        FileStream {
+        let stream = FileStream {
             memory_mapping,
+            wav_header,
             offset: offset.clone(),
             frame_size,
             buffer_mem_length,
@@ -89,10 +112,27 @@ impl FileStream {
                 offset,
                 max_offset,
             },
+        };
+        stream.update_wav_header();
+        stream
+    }
+
+    /// Writes the WAV header at the start of the file, accounting for the data written so far.
+    fn update_wav_header(&self) {
+        let data_len = self.offset.load(Ordering::Relaxed) - WAV_HEADER_LEN;
+        let header = self.wav_header.to_bytes(data_len as u32);
+        if let Err(e) = self.memory_mapping.write_slice(&header, 0) {
+            warn!("Failed to write WAV header: {}", e);
         }
     }
 }
 
+impl Drop for FileStream {
+    fn drop(&mut self) {
+        self.update_wav_header();
+    }
+}
+
 #[async_trait(?Send)]
 impl AsyncPlaybackBufferStream for FileStream {
     async fn next_playback_buffer<'a>(
@@ -110,6 +150,7 @@ impl AsyncPlaybackBufferStream for FileStream {
             self.next_frame = self.interval_ms;
         }
 
+        self.update_wav_header();
         let offset = self.offset.load(Ordering::Relaxed);
         let buffer = self
             .memory_mapping
@@ -161,6 +202,7 @@ impl StreamSource for FileStreamSource {
             Box::new(NoopStreamControl::new()),
             Box::new(FileStream::new(
                 memory_mapping,
+                WavHeader::new(num_channels, format, frame_rate),
                 self.offset.clone(),
                 frame_size,
                 buffer_mem_length,
@@ -193,13 +235,14 @@ pub struct FileStreamSourceGenerator {
     file: File,
     /// Size of the output file in bytes.
     file_size: usize,
-    /// Number of bytes that has been written to the file.
+    /// Offset in the file of the next byte to be written.
     offset: Arc<AtomicUsize>,
 }
 
 impl FileStreamSourceGenerator {
     /// Creates a new `FileStreamSourceGenerator` by given arguments.
-    /// It expects `file` has `file_size` of bytes allocated spaces.
+    /// It expects `file` has `file_size` of bytes allocated spaces, including the
+    /// `WAV_HEADER_LEN` bytes of the header.
     ///
     /// # Arguments
     ///
@@ -209,7 +252,7 @@ impl FileStreamSourceGenerator {
         FileStreamSourceGenerator {
             file,
             file_size,
-            offset: Arc::new(AtomicUsize::new(0)),
+            offset: Arc::new(AtomicUsize::new(WAV_HEADER_LEN)),
         }
     }
 }
@@ -262,7 +305,338 @@ impl AudioMemoryMapping {
         unsafe { slice::from_raw_parts_mut(self.memory_mapping.as_ptr().add(offset), len) }
     }
 
+    fn write_slice(&self, buf: &[u8], offset: usize) -> Result<usize, MmapError> {
+        self.memory_mapping.write_slice(buf, offset)
+    }
+
     fn size(&self) -> usize {
         self.memory_mapping.size()
     }
 }
+
+/// An audio stream that feeds the samples of a WAV file to a capture stream.
+///
+/// The capture is paced according to the frame rate of the stream. Once the end of the file is
+/// reached, the stream either starts over from the first sample or provides silence.
+struct FileCaptureStream {
+    file: File,
+    /// Offset of the samples in the file.
+    data_offset: u64,
+    /// Length of the samples in bytes, rounded down to a whole number of frames.
+    data_len: u64,
+    /// Position of the next sample to be read, relative to `data_offset`.
+    position: u64,
+    loop_at_eof: bool,
+    /// Buffer holding the samples returned to the user.
+    buffer: Vec<u8>,
+    /// Number of bytes in a single audio frame.
+    frame_size: usize,
+    /// Value of the bytes of a silent sample.
+    silence: u8,
+
+    frame_rate: u64,
+    /// Number of frames returned since the stream started.
+    frames: u64,
+    /// Timestamp that records when the stream starts.
+    start_time: Option<Instant>,
+    buffer_drop: FileCaptureBufferCommit,
+}
+
+impl FileCaptureStream {
+    /// Fills `self.buffer` with the next samples of the file.
+    fn fill_buffer(&mut self) -> Result<(), Error> {
+        let mut filled = 0;
+        while filled < self.buffer.len() {
+            if self.position == self.data_len {
+                if !self.loop_at_eof || self.data_len == 0 {
+                    break;
+                }
+                self.position = 0;
+            }
+
+            let len = min(
+                (self.buffer.len() - filled) as u64,
+                self.data_len - self.position,
+            ) as usize;
+            self.file
+                .seek(SeekFrom::Start(self.data_offset + self.position))
+                .map_err(Error::ReadWavFile)?;
+            self.file
+                .read_exact(&mut self.buffer[filled..filled + len])
+                .map_err(Error::ReadWavFile)?;
+            filled += len;
+            self.position += len as u64;
+        }
+
+        for b in &mut self.buffer[filled..] {
+            *b = self.silence;
+        }
+        Ok(())
+    }
+}
+
+/// Returns the duration of `frames` frames played at `frame_rate`.
+fn frames_duration(frames: u64, frame_rate: u64) -> Duration {
+    Duration::from_secs(frames / frame_rate)
+        + Duration::from_nanos((frames % frame_rate) * 1_000_000_000 / frame_rate)
+}
+
+#[async_trait(?Send)]
+impl AsyncCaptureBufferStream for FileCaptureStream {
+    async fn next_capture_buffer<'a>(
+        &'a mut self,
+        ex: &dyn AudioStreamsExecutor,
+    ) -> Result<AsyncCaptureBuffer<'a>, BoxError> {
+        match self.start_time {
+            Some(start_time) => {
+                // The deadline is computed from the start of the stream so that rounding errors
+                // don't add up.
+                let next_frame = frames_duration(self.frames, self.frame_rate);
+                let elapsed = start_time.elapsed();
+                if elapsed < next_frame {
+                    ex.delay(next_frame - elapsed).await?;
+                }
+            }
+            None => self.start_time = Some(Instant::now()),
+        }
+        self.frames += (self.buffer.len() / self.frame_size) as u64;
+
+        self.fill_buffer()?;
+        Ok(AsyncCaptureBuffer::new(
+            self.frame_size,
+            &mut self.buffer,
+            &mut self.buffer_drop,
+        )?)
+    }
+}
+
+struct FileCaptureBufferCommit;
+
+#[async_trait(?Send)]
+impl AsyncBufferCommit for FileCaptureBufferCommit {
+    async fn commit(&mut self, _nread: usize) {}
+}
+
+struct FileCaptureStreamSource {
+    file: File,
+    wav_header: WavHeader,
+    data_offset: u64,
+    data_len: u64,
+    loop_at_eof: bool,
+}
+
+impl StreamSource for FileCaptureStreamSource {
+    fn new_playback_stream(
+        &mut self,
+        _num_channels: usize,
+        _format: SampleFormat,
+        _frame_rate: u32,
+        _buffer_size: usize,
+    ) -> Result<
+        (
+            Box<dyn StreamControl>,
+            Box<dyn audio_streams::PlaybackBufferStream>,
+        ),
+        BoxError,
+    > {
+        Err(Box::new(Error::Unimplemented))
+    }
+
+    fn new_async_capture_stream(
+        &mut self,
+        num_channels: usize,
+        format: SampleFormat,
+        frame_rate: u32,
+        buffer_size: usize,
+        _effects: &[StreamEffect],
+        _ex: &dyn AudioStreamsExecutor,
+    ) -> Result<(Box<dyn StreamControl>, Box<dyn AsyncCaptureBufferStream>), BoxError> {
+        // Samples are passed through as-is, so the guest has to use the format of the file.
+        let stream_header = WavHeader::new(num_channels, format, frame_rate);
+        if stream_header != self.wav_header {
+            return Err(Box::new(Error::FormatMismatch(
+                stream_header,
+                self.wav_header,
+            )));
+        }
+
+        let frame_size = stream_header.frame_size();
+        Ok((
+            Box::new(NoopStreamControl::new()),
+            Box::new(FileCaptureStream {
+                file: self.file.try_clone().map_err(Error::Clone)?,
+                data_offset: self.data_offset,
+                data_len: self.data_len - self.data_len % frame_size as u64,
+                position: 0,
+                loop_at_eof: self.loop_at_eof,
+                buffer: vec![0; buffer_size * frame_size],
+                frame_size,
+                // Unsigned samples are centered on 0x80.
+                silence: if format == SampleFormat::U8 { 0x80 } else { 0 },
+                frame_rate: frame_rate as u64,
+                frames: 0,
+                start_time: None,
+                buffer_drop: FileCaptureBufferCommit,
+            }),
+        ))
+    }
+}
+
+/// `FileCaptureStreamSourceGenerator` is a struct that implements [`StreamSourceGenerator`]
+/// for capture streams reading their samples from a WAV file.
+pub struct FileCaptureStreamSourceGenerator {
+    /// WAV file providing the samples.
+    file: File,
+    wav_header: WavHeader,
+    data_offset: u64,
+    data_len: u64,
+    /// Whether to start over from the first sample once the end of the file is reached.
+    loop_at_eof: bool,
+}
+
+impl FileCaptureStreamSourceGenerator {
+    /// Creates a new `FileCaptureStreamSourceGenerator` by given arguments.
+    /// Fails if `file` is not a WAV file holding PCM samples.
+    ///
+    /// # Arguments
+    ///
+    /// * `file` - The WAV file from which the captured samples are read.
+    /// * `loop_at_eof` - Whether to loop over the file instead of providing silence once the
+    ///   end of the file is reached.
+    pub fn new(mut file: File, loop_at_eof: bool) -> Result<Self, Error> {
+        let (wav_header, data_offset, data_len) = WavHeader::parse(&mut file)?;
+        Ok(FileCaptureStreamSourceGenerator {
+            file,
+            wav_header,
+            data_offset,
+            data_len,
+            loop_at_eof,
+        })
+    }
+}
+
+impl StreamSourceGenerator for FileCaptureStreamSourceGenerator {
+    fn generate(&self) -> Result<Box<dyn StreamSource>, BoxError> {
+        Ok(Box::new(FileCaptureStreamSource {
+            file: self.file.try_clone().map_err(Error::Clone)?,
+            wav_header: self.wav_header,
+            data_offset: self.data_offset,
+            data_len: self.data_len,
+            loop_at_eof: self.loop_at_eof,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use base::SafeDescriptor;
+    use base::SharedMemory;
+    use cros_async::Executor;
+
+    use super::*;
+
+    /// Creates a WAV file holding `samples`.
+    fn wav_file(header: WavHeader, samples: &[u8]) -> File {
+        let shm = SharedMemory::new("wav", 0).unwrap();
+        let mut file = File::from(SafeDescriptor::from(shm));
+        file.write_all(&header.to_bytes(samples.len() as u32))
+            .unwrap();
+        file.write_all(samples).unwrap();
+        file.rewind().unwrap();
+        file
+    }
+
+    /// Captures `count` buffers of `buffer_size` frames from a WAV file holding `samples`.
+    fn capture(
+        format: SampleFormat,
+        samples: &[u8],
+        loop_at_eof: bool,
+        buffer_size: usize,
+        count: usize,
+    ) -> Vec<Vec<u8>> {
+        let header = WavHeader::new(1, format, 8000);
+        let generator =
+            FileCaptureStreamSourceGenerator::new(wav_file(header, samples), loop_at_eof).unwrap();
+        let mut source = generator.generate().unwrap();
+        let ex = Executor::new().unwrap();
+        let (_, mut stream) = source
+            .new_async_capture_stream(1, format, 8000, buffer_size, &[], &ex)
+            .unwrap();
+
+        ex.run_until(async {
+            let mut buffers = Vec::new();
+            for _ in 0..count {
+                let mut buffer = stream.next_capture_buffer(&ex).await.unwrap();
+                let mut data = Vec::new();
+                buffer
+                    .copy_cb(buffer_size * format.sample_bytes(), |samples| {
+                        data.extend_from_slice(samples)
+                    })
+                    .unwrap();
+                buffer.commit().await;
+                buffers.push(data);
+            }
+            buffers
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn capture_loops() {
+        assert_eq!(
+            capture(SampleFormat::U8, &[1, 2, 3], true, 4, 3),
+            [[1, 2, 3, 1], [2, 3, 1, 2], [3, 1, 2, 3]]
+        );
+    }
+
+    #[test]
+    fn capture_pads_with_silence() {
+        assert_eq!(
+            capture(SampleFormat::U8, &[1, 2, 3], false, 4, 2),
+            [[1, 2, 3, 0x80], [0x80, 0x80, 0x80, 0x80]]
+        );
+        // The trailing partial frame is dropped.
+        assert_eq!(
+            capture(SampleFormat::S16LE, &[1, 2, 3, 4, 5], false, 2, 2),
+            [[1, 2, 3, 4], [0, 0, 0, 0]]
+        );
+    }
+
+    #[test]
+    fn capture_empty_file() {
+        assert_eq!(
+            capture(SampleFormat::S16LE, &[], true, 2, 2),
+            [[0, 0, 0, 0], [0, 0, 0, 0]]
+        );
+    }
+
+    #[test]
+    fn capture_format_mismatch() {
+        let header = WavHeader::new(2, SampleFormat::S16LE, 48000);
+        let generator =
+            FileCaptureStreamSourceGenerator::new(wav_file(header, &[]), false).unwrap();
+        let mut source = generator.generate().unwrap();
+        let ex = Executor::new().unwrap();
+        assert!(source
+            .new_async_capture_stream(1, SampleFormat::S16LE, 48000, 480, &[], &ex)
+            .is_err());
+    }
+
+    #[test]
+    fn buffer_duration() {
+        assert_eq!(frames_duration(0, 44100), Duration::ZERO);
+        assert_eq!(frames_duration(44100, 44100), Duration::from_secs(1));
+        // 480 frames at 44.1kHz last 10.884ms, 1000 of them 10.884s and not 10s.
+        assert_eq!(
+            frames_duration(480 * 1000, 44100),
+            Duration::from_nanos(10_884_353_741)
+        );
+        // No overflow after days of capture.
+        assert_eq!(
+            frames_duration(48000 * 86400 * 30, 48000),
+            Duration::from_secs(86400 * 30)
+        );
+    }
+}
@@ -3,6 +3,15 @@
 // found in the LICENSE file.
 
 mod file_streams;
+#[cfg(all(unix, feature = "pulse"))]
+pub mod pulse;
+mod volume;
+mod wav;
 
 pub use file_streams::Error;
+pub use file_streams::FileCaptureStreamSourceGenerator;
 pub use file_streams::FileStreamSourceGenerator;
+pub use volume::SoftwareVolume;
+pub use volume::MAX_VOLUME;
+pub use wav::WavHeader;
+pub use wav::WAV_HEADER_LEN;
@@ -0,0 +1,87 @@
+// Copyright 2023 The ChromiumOS Authors
+// Use of this source code is governed by a BSD-style license that can be
+// found in the LICENSE file.
+
+//! Bindings for the subset of the PulseAudio simple API used by the pulse backend.
+//!
+//! See <pulse/simple.h>, <pulse/sample.h>, <pulse/def.h> and <pulse/error.h>.
+
+#![allow(non_camel_case_types)]
+
+use std::os::raw::c_char;
+use std::os::raw::c_int;
+use std::os::raw::c_void;
+
+#[link(name = "pulse-simple")]
+extern "C" {}
+
+#[link(name = "pulse")]
+extern "C" {}
+
+pub type pa_sample_format_t = c_int;
+pub const PA_SAMPLE_U8: pa_sample_format_t = 0;
+pub const PA_SAMPLE_S16LE: pa_sample_format_t = 3;
+pub const PA_SAMPLE_S32LE: pa_sample_format_t = 7;
+pub const PA_SAMPLE_S24_32LE: pa_sample_format_t = 11;
+
+pub type pa_stream_direction_t = c_int;
+pub const PA_STREAM_PLAYBACK: pa_stream_direction_t = 1;
+pub const PA_STREAM_RECORD: pa_stream_direction_t = 2;
+
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone)]
+pub struct pa_sample_spec {
+    pub format: pa_sample_format_t,
+    pub rate: u32,
+    pub channels: u8,
+}
+
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone)]
+pub struct pa_buffer_attr {
+    pub maxlength: u32,
+    pub tlength: u32,
+    pub prebuf: u32,
+    pub minreq: u32,
+    pub fragsize: u32,
+}
+
+/// Opaque channel map, only ever passed as NULL to get the default mapping.
+#[repr(C)]
+pub struct pa_channel_map {
+    _unused: [u8; 0],
+}
+
+#[repr(C)]
+pub struct pa_simple {
+    _unused: [u8; 0],
+}
+
+extern "C" {
+    pub fn pa_simple_new(
+        server: *const c_char,
+        name: *const c_char,
+        dir: pa_stream_direction_t,
+        dev: *const c_char,
+        stream_name: *const c_char,
+        ss: *const pa_sample_spec,
+        map: *const pa_channel_map,
+        attr: *const pa_buffer_attr,
+        error: *mut c_int,
+    ) -> *mut pa_simple;
+    pub fn pa_simple_free(s: *mut pa_simple);
+    pub fn pa_simple_write(
+        s: *mut pa_simple,
+        data: *const c_void,
+        bytes: usize,
+        error: *mut c_int,
+    ) -> c_int;
+    pub fn pa_simple_drain(s: *mut pa_simple, error: *mut c_int) -> c_int;
+    pub fn pa_simple_read(
+        s: *mut pa_simple,
+        data: *mut c_void,
+        bytes: usize,
+        error: *mut c_int,
+    ) -> c_int;
+    pub fn pa_strerror(error: c_int) -> *const c_char;
+}
@@ -0,0 +1,656 @@
+// Copyright 2023 The ChromiumOS Authors
+// Use of this source code is governed by a BSD-style license that can be
+// found in the LICENSE file.
+
+//! Audio streams backed by a PulseAudio server. This also works with PipeWire through its
+//! PulseAudio compatible server.
+//!
+//! Each stream is served by a dedicated thread doing blocking reads or writes through the
+//! PulseAudio simple API. Buffers are exchanged with the thread through channels, which also
+//! paces the streams to the rate at which the server consumes or produces samples.
+
+mod ffi;
+
+use std::ffi::CStr;
+use std::ffi::CString;
+use std::fmt;
+use std::io::Error as IOError;
+use std::mem;
+use std::os::raw::c_int;
+use std::path::Path;
+use std::path::PathBuf;
+use std::ptr::null;
+use std::sync::mpsc;
+use std::thread;
+
+use async_trait::async_trait;
+use audio_streams::capture::AsyncCaptureBuffer;
+use audio_streams::capture::AsyncCaptureBufferStream;
+use audio_streams::AsyncBufferCommit;
+use audio_streams::AsyncPlaybackBuffer;
+use audio_streams::AsyncPlaybackBufferStream;
+use audio_streams::AudioStreamsExecutor;
+use audio_streams::BoxError;
+use audio_streams::NoopStreamControl;
+use audio_streams::PlaybackBufferStream;
+use audio_streams::SampleFormat;
+use audio_streams::StreamControl;
+use audio_streams::StreamEffect;
+use audio_streams::StreamSource;
+use audio_streams::StreamSourceGenerator;
+use base::error;
+use base::warn;
+use futures::channel::mpsc::unbounded;
+use futures::channel::mpsc::UnboundedReceiver;
+use futures::channel::mpsc::UnboundedSender;
+use futures::StreamExt;
+use thiserror::Error as ThisError;
+
+/// Name of the client as shown by the PulseAudio server.
+const APP_NAME: &[u8] = b"crosvm\0";
+
+/// Number of buffers exchanged between a stream and its thread.
+const NUM_BUFFERS: usize = 2;
+
+/// An error code returned by the PulseAudio library.
+#[derive(Debug)]
+pub struct PulseError(c_int);
+
+impl fmt::Display for PulseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // Safe because `pa_strerror` returns either NULL or a static string.
+        let s = unsafe { ffi::pa_strerror(self.0) };
+        if s.is_null() {
+            write!(f, "unknown error {}", self.0)
+        } else {
+            // Safe because `s` is a valid zero-terminated C string.
+            write!(f, "{}", unsafe { CStr::from_ptr(s) }.to_string_lossy())
+        }
+    }
+}
+
+#[derive(ThisError, Debug)]
+pub enum Error {
+    #[error("Failed to connect to the PulseAudio server: {0}")]
+    Connect(PulseError),
+    #[error("Failed to drain PulseAudio stream: {0}")]
+    Drain(PulseError),
+    #[error("Failed to read from PulseAudio stream: {0}")]
+    Read(PulseError),
+    #[error("Failed to spawn PulseAudio stream thread: {0}")]
+    SpawnThread(IOError),
+    #[error("PulseAudio stream thread exited")]
+    StreamClosed,
+    #[error("Not implemented")]
+    Unimplemented,
+    #[error("Failed to write to PulseAudio stream: {0}")]
+    Write(PulseError),
+}
+
+/// Returns the PulseAudio description of the samples of a stream.
+fn sample_spec(num_channels: usize, format: SampleFormat, frame_rate: u32) -> ffi::pa_sample_spec {
+    ffi::pa_sample_spec {
+        format: match format {
+            SampleFormat::U8 => ffi::PA_SAMPLE_U8,
+            SampleFormat::S16LE => ffi::PA_SAMPLE_S16LE,
+            // 24-bit samples are stored in 32 bits.
+            SampleFormat::S24LE => ffi::PA_SAMPLE_S24_32LE,
+            SampleFormat::S32LE => ffi::PA_SAMPLE_S32LE,
+        },
+        rate: frame_rate,
+        channels: num_channels as u8,
+    }
+}
+
+/// Returns the server side buffering of a stream exchanging buffers of `buffer_bytes` bytes. It
+/// is kept close to what the guest asks for, and the server picks the other parameters (-1).
+fn buffer_attr(direction: ffi::pa_stream_direction_t, buffer_bytes: usize) -> ffi::pa_buffer_attr {
+    let buffer_bytes = buffer_bytes as u32;
+    if direction == ffi::PA_STREAM_PLAYBACK {
+        ffi::pa_buffer_attr {
+            maxlength: u32::MAX,
+            tlength: buffer_bytes * NUM_BUFFERS as u32,
+            prebuf: u32::MAX,
+            minreq: buffer_bytes,
+            fragsize: u32::MAX,
+        }
+    } else {
+        ffi::pa_buffer_attr {
+            maxlength: u32::MAX,
+            tlength: u32::MAX,
+            prebuf: u32::MAX,
+            minreq: u32::MAX,
+            fragsize: buffer_bytes,
+        }
+    }
+}
+
+/// A connection to the PulseAudio server for a single stream.
+struct PulseConnection(*mut ffi::pa_simple);
+
+// Safe because the connection is only ever used by the thread owning it.
+unsafe impl Send for PulseConnection {}
+
+impl PulseConnection {
+    fn new(
+        server: Option<&CStr>,
+        direction: ffi::pa_stream_direction_t,
+        stream_name: &CStr,
+        num_channels: usize,
+        format: SampleFormat,
+        frame_rate: u32,
+        buffer_bytes: usize,
+    ) -> Result<Self, Error> {
+        let spec = sample_spec(num_channels, format, frame_rate);
+        let attr = buffer_attr(direction, buffer_bytes);
+
+        let mut error = 0;
+        // Safe because all the pointers are valid for the duration of the call, and the result
+        // is checked.
+        let s = unsafe {
+            ffi::pa_simple_new(
+                server.map_or(null(), CStr::as_ptr),
+                APP_NAME.as_ptr() as *const _,
+                direction,
+                null(),
+                stream_name.as_ptr(),
+                &spec,
+                null(),
+                &attr,
+                &mut error,
+            )
+        };
+        if s.is_null() {
+            return Err(Error::Connect(PulseError(error)));
+        }
+        Ok(PulseConnection(s))
+    }
+
+    /// Writes `data` to the stream, blocking until the server has room for it.
+    fn write(&mut self, data: &[u8]) -> Result<(), Error> {
+        let mut error = 0;
+        // Safe because `data` is valid for reads of `data.len()` bytes.
+        let ret = unsafe {
+            ffi::pa_simple_write(self.0, data.as_ptr() as *const _, data.len(), &mut error)
+        };
+        if ret < 0 {
+            return Err(Error::Write(PulseError(error)));
+        }
+        Ok(())
+    }
+
+    /// Fills `data` with samples from the stream, blocking until enough of them are available.
+    fn read(&mut self, data: &mut [u8]) -> Result<(), Error> {
+        let mut error = 0;
+        // Safe because `data` is valid for writes of `data.len()` bytes.
+        let ret = unsafe {
+            ffi::pa_simple_read(self.0, data.as_mut_ptr() as *mut _, data.len(), &mut error)
+        };
+        if ret < 0 {
+            return Err(Error::Read(PulseError(error)));
+        }
+        Ok(())
+    }
+
+    /// Waits until all the written samples have been played.
+    fn drain(&mut self) -> Result<(), Error> {
+        let mut error = 0;
+        // Safe because `self.0` is a valid connection.
+        if unsafe { ffi::pa_simple_drain(self.0, &mut error) } < 0 {
+            return Err(Error::Drain(PulseError(error)));
+        }
+        Ok(())
+    }
+}
+
+impl Drop for PulseConnection {
+    fn drop(&mut self) {
+        // Safe because `self.0` is a valid connection that is not used afterwards.
+        unsafe { ffi::pa_simple_free(self.0) };
+    }
+}
+
+/// Writes the buffers received from `committed` to `connection`, then returns them through
+/// `free_buffers`.
+fn playback_thread(
+    mut connection: PulseConnection,
+    committed: mpsc::Receiver<(Vec<u8>, usize)>,
+    free_buffers: UnboundedSender<Vec<u8>>,
+) {
+    for (buffer, len) in committed.iter() {
+        if let Err(e) = connection.write(&buffer[..len]) {
+            error!("{}", e);
+            return;
+        }
+        // Keep playing the samples queued by a stream that is gone.
+        let _ = free_buffers.unbounded_send(buffer);
+    }
+
+    if let Err(e) = connection.drain() {
+        warn!("{}", e);
+    }
+}
+
+/// Fills the buffers received from `empty_buffers` with samples from `connection`, then passes
+/// them to `filled_buffers`.
+fn capture_thread(
+    mut connection: PulseConnection,
+    empty_buffers: mpsc::Receiver<Vec<u8>>,
+    filled_buffers: UnboundedSender<Vec<u8>>,
+) {
+    for mut buffer in empty_buffers.iter() {
+        if let Err(e) = connection.read(&mut buffer) {
+            error!("{}", e);
+            return;
+        }
+        if filled_buffers.unbounded_send(buffer).is_err() {
+            return;
+        }
+    }
+}
+
+struct PulsePlaybackBufferCommit {
+    /// Number of frames committed in the current buffer, if it has been committed.
+    nframes: Option<usize>,
+}
+
+#[async_trait(?Send)]
+impl AsyncBufferCommit for PulsePlaybackBufferCommit {
+    async fn commit(&mut self, nframes: usize) {
+        self.nframes = Some(nframes);
+    }
+}
+
+/// A playback stream writing its samples to a PulseAudio server.
+///
+/// Committed buffers are sent to the playback thread when the next buffer is requested.
+pub struct PulsePlaybackStream {
+    frame_size: usize,
+    /// Buffer handed out to the user.
+    buffer: Vec<u8>,
+    buffer_drop: PulsePlaybackBufferCommit,
+    /// Sends committed buffers and the number of bytes to play to the playback thread.
+    committed: mpsc::Sender<(Vec<u8>, usize)>,
+    /// Receives the buffers that have been played.
+    free_buffers: UnboundedReceiver<Vec<u8>>,
+}
+
+#[async_trait(?Send)]
+impl AsyncPlaybackBufferStream for PulsePlaybackStream {
+    async fn next_playback_buffer<'a>(
+        &'a mut self,
+        _ex: &dyn AudioStreamsExecutor,
+    ) -> Result<AsyncPlaybackBuffer<'a>, BoxError> {
+        if let Some(nframes) = self.buffer_drop.nframes.take() {
+            let free_buffer = self.free_buffers.next().await.ok_or(Error::StreamClosed)?;
+            let buffer = mem::replace(&mut self.buffer, free_buffer);
+            self.committed
+                .send((buffer, nframes * self.frame_size))
+                .map_err(|_| Error::StreamClosed)?;
+        }
+
+        Ok(AsyncPlaybackBuffer::new(
+            self.frame_size,
+            &mut self.buffer,
+            &mut self.buffer_drop,
+        )?)
+    }
+}
+
+impl Drop for PulsePlaybackStream {
+    fn drop(&mut self) {
+        // Send the last committed samples. The playback thread drains the stream and exits once
+        // the channel is closed.
+        if let Some(nframes) = self.buffer_drop.nframes.take() {
+            let buffer = mem::take(&mut self.buffer);
+            let _ = self.committed.send((buffer, nframes * self.frame_size));
+        }
+    }
+}
+
+struct PulseCaptureBufferCommit;
+
+#[async_trait(?Send)]
+impl AsyncBufferCommit for PulseCaptureBufferCommit {
+    async fn commit(&mut self, _nframes: usize) {}
+}
+
+/// A capture stream reading its samples from a PulseAudio server.
+pub struct PulseCaptureStream {
+    frame_size: usize,
+    /// Buffer handed out to the user.
+    buffer: Vec<u8>,
+    buffer_drop: PulseCaptureBufferCommit,
+    /// Sends the buffers that have been read by the user back to the capture thread.
+    empty_buffers: mpsc::Sender<Vec<u8>>,
+    /// Receives the buffers filled by the capture thread.
+    filled_buffers: UnboundedReceiver<Vec<u8>>,
+}
+
+#[async_trait(?Send)]
+impl AsyncCaptureBufferStream for PulseCaptureStream {
+    async fn next_capture_buffer<'a>(
+        &'a mut self,
+        _ex: &dyn AudioStreamsExecutor,
+    ) -> Result<AsyncCaptureBuffer<'a>, BoxError> {
+        let filled_buffer = self
+            .filled_buffers
+            .next()
+            .await
+            .ok_or(Error::StreamClosed)?;
+        let buffer = mem::replace(&mut self.buffer, filled_buffer);
+        // The first call has no previous buffer to return.
+        if !buffer.is_empty() {
+            self.empty_buffers
+                .send(buffer)
+                .map_err(|_| Error::StreamClosed)?;
+        }
+
+        Ok(AsyncCaptureBuffer::new(
+            self.frame_size,
+            &mut self.buffer,
+            &mut self.buffer_drop,
+        )?)
+    }
+}
+
+struct PulseStreamSource {
+    server: Option<CString>,
+    stream_name: CString,
+}
+
+impl StreamSource for PulseStreamSource {
+    fn new_playback_stream(
+        &mut self,
+        _num_channels: usize,
+        _format: SampleFormat,
+        _frame_rate: u32,
+        _buffer_size: usize,
+    ) -> Result<(Box<dyn StreamControl>, Box<dyn PlaybackBufferStream>), BoxError> {
+        Err(Box::new(Error::Unimplemented))
+    }
+
+    fn new_async_playback_stream(
+        &mut self,
+        num_channels: usize,
+        format: SampleFormat,
+        frame_rate: u32,
+        buffer_size: usize,
+        _ex: &dyn AudioStreamsExecutor,
+    ) -> Result<(Box<dyn StreamControl>, Box<dyn AsyncPlaybackBufferStream>), BoxError> {
+        let frame_size = format.sample_bytes() * num_channels;
+        let buffer_bytes = buffer_size * frame_size;
+        let connection = PulseConnection::new(
+            self.server.as_deref(),
+            ffi::PA_STREAM_PLAYBACK,
+            &self.stream_name,
+            num_channels,
+            format,
+            frame_rate,
+            buffer_bytes,
+        )?;
+
+        let (committed, committed_receiver) = mpsc::channel();
+        let (free_buffers_sender, free_buffers) = unbounded();
+        // One buffer is handed out to the user, the others are ready to replace it.
+        for _ in 1..NUM_BUFFERS {
+            free_buffers_sender
+                .unbounded_send(vec![0; buffer_bytes])
+                .map_err(|_| Error::StreamClosed)?;
+        }
+        thread::Builder::new()
+            .name("pulse_playback".to_string())
+            .spawn(move || playback_thread(connection, committed_receiver, free_buffers_sender))
+            .map_err(Error::SpawnThread)?;
+
+        Ok((
+            Box::new(NoopStreamControl::new()),
+            Box::new(PulsePlaybackStream {
+                frame_size,
+                buffer: vec![0; buffer_bytes],
+                buffer_drop: PulsePlaybackBufferCommit { nframes: None },
+                committed,
+                free_buffers,
+            }),
+        ))
+    }
+
+    fn new_async_capture_stream(
+        &mut self,
+        num_channels: usize,
+        format: SampleFormat,
+        frame_rate: u32,
+        buffer_size: usize,
+        _effects: &[StreamEffect],
+        _ex: &dyn AudioStreamsExecutor,
+    ) -> Result<(Box<dyn StreamControl>, Box<dyn AsyncCaptureBufferStream>), BoxError> {
+        let frame_size = format.sample_bytes() * num_channels;
+        let buffer_bytes = buffer_size * frame_size;
+        let connection = PulseConnection::new(
+            self.server.as_deref(),
+            ffi::PA_STREAM_RECORD,
+            &self.stream_name,
+            num_channels,
+            format,
+            frame_rate,
+            buffer_bytes,
+        )?;
+
+        let (empty_buffers, empty_buffers_receiver) = mpsc::channel();
+        let (filled_buffers_sender, filled_buffers) = unbounded();
+        for _ in 0..NUM_BUFFERS {
+            empty_buffers
+                .send(vec![0; buffer_bytes])
+                .map_err(|_| Error::StreamClosed)?;
+        }
+        thread::Builder::new()
+            .name("pulse_capture".to_string())
+            .spawn(move || {
+                capture_thread(connection, empty_buffers_receiver, filled_buffers_sender)
+            })
+            .map_err(Error::SpawnThread)?;
+
+        Ok((
+            Box::new(NoopStreamControl::new()),
+            Box::new(PulseCaptureStream {
+                frame_size,
+                buffer: Vec::new(),
+                buffer_drop: PulseCaptureBufferCommit,
+                empty_buffers,
+                filled_buffers,
+            }),
+        ))
+    }
+}
+
+/// Returns the unix socket of the PulseAudio server the streams connect to: the one named by
+/// `PULSE_SERVER` if it is a single unix socket, or else the socket of the per-user server in
+/// `XDG_RUNTIME_DIR`. Returns `None` if the server isn't reached through such a socket, in which
+/// case libpulse looks for it on its own.
+pub fn server_socket_path() -> Option<PathBuf> {
+    match std::env::var_os("PULSE_SERVER") {
+        Some(server) => {
+            let server = server.to_str()?;
+            let path = Path::new(server.strip_prefix("unix:").unwrap_or(server));
+            // A list of servers or a network address.
+            if server.contains(char::is_whitespace) || !path.is_absolute() {
+                return None;
+            }
+            Some(path.to_path_buf())
+        }
+        None => {
+            let runtime_dir = std::env::var_os("XDG_RUNTIME_DIR")?;
+            Some(Path::new(&runtime_dir).join("pulse").join("native"))
+        }
+    }
+}
+
+/// `PulseStreamSourceGenerator` is a struct that implements [`StreamSourceGenerator`]
+/// for streams played or captured by a PulseAudio server.
+///
+/// The server is the default one of the user, which can be overridden with the usual
+/// `PULSE_SERVER` environment variable. Its socket is given to libpulse explicitly, so that the
+/// runtime directory and configuration of the user aren't needed to find it.
+pub struct PulseStreamSourceGenerator {
+    /// Address of the server, if it has a unix socket.
+    server: Option<CString>,
+    /// Name of the streams as shown by the server.
+    stream_name: CString,
+}
+
+impl PulseStreamSourceGenerator {
+    /// Creates a new `PulseStreamSourceGenerator` for streams named `stream_name`.
+    pub fn new(stream_name: &str) -> Self {
+        PulseStreamSourceGenerator {
+            server: server_socket_path()
+                .and_then(|path| CString::new(format!("unix:{}", path.to_str()?)).ok()),
+            // Interior NUL bytes are not expected in stream names, drop them if there are any.
+            stream_name: CString::new(stream_name.replace('\0', "")).unwrap(),
+        }
+    }
+}
+
+impl StreamSourceGenerator for PulseStreamSourceGenerator {
+    fn generate(&self) -> Result<Box<dyn StreamSource>, BoxError> {
+        Ok(Box::new(PulseStreamSource {
+            server: self.server.clone(),
+            stream_name: self.stream_name.clone(),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+    use std::io::Write;
+
+    use cros_async::Executor;
+
+    use super::*;
+
+    #[test]
+    fn sample_formats() {
+        for (format, pa_format) in [
+            (SampleFormat::U8, ffi::PA_SAMPLE_U8),
+            (SampleFormat::S16LE, ffi::PA_SAMPLE_S16LE),
+            (SampleFormat::S24LE, ffi::PA_SAMPLE_S24_32LE),
+            (SampleFormat::S32LE, ffi::PA_SAMPLE_S32LE),
+        ] {
+            let spec = sample_spec(2, format, 44100);
+            assert_eq!(spec.format, pa_format);
+            assert_eq!(spec.channels, 2);
+            assert_eq!(spec.rate, 44100);
+        }
+        // The buffers hold 24-bit samples in 32 bits, as PulseAudio expects them.
+        assert_eq!(SampleFormat::S24LE.sample_bytes(), 4);
+    }
+
+    #[test]
+    fn buffer_attributes() {
+        let attr = buffer_attr(ffi::PA_STREAM_PLAYBACK, 1920);
+        assert_eq!(attr.tlength, 1920 * NUM_BUFFERS as u32);
+        assert_eq!(attr.minreq, 1920);
+        assert_eq!(attr.fragsize, u32::MAX);
+
+        let attr = buffer_attr(ffi::PA_STREAM_RECORD, 1920);
+        assert_eq!(attr.fragsize, 1920);
+        assert_eq!(attr.tlength, u32::MAX);
+        assert_eq!(attr.minreq, u32::MAX);
+    }
+
+    #[test]
+    fn playback_sends_committed_frames() {
+        let (committed, committed_receiver) = mpsc::channel();
+        let (free_buffers_sender, free_buffers) = unbounded();
+        free_buffers_sender.unbounded_send(vec![0; 8]).unwrap();
+        let mut stream = PulsePlaybackStream {
+            frame_size: 4,
+            buffer: vec![0; 8],
+            buffer_drop: PulsePlaybackBufferCommit { nframes: None },
+            committed,
+            free_buffers,
+        };
+
+        let ex = Executor::new().expect("failed to create executor");
+        ex.run_until(async {
+            let mut buffer = stream.next_playback_buffer(&ex).await.unwrap();
+            assert_eq!(buffer.frame_capacity(), 2);
+            buffer.write_all(&[1, 2, 3, 4]).unwrap();
+            buffer.commit().await;
+            // Nothing is sent before the next buffer is requested.
+            assert!(committed_receiver.try_recv().is_err());
+            stream.next_playback_buffer(&ex).await.unwrap();
+        })
+        .unwrap();
+        assert_eq!(
+            committed_receiver.try_recv().unwrap(),
+            (vec![1, 2, 3, 4, 0, 0, 0, 0], 4)
+        );
+    }
+
+    #[test]
+    fn capture_returns_read_buffers() {
+        let (empty_buffers, empty_buffers_receiver) = mpsc::channel();
+        let (filled_buffers_sender, filled_buffers) = unbounded();
+        filled_buffers_sender.unbounded_send(vec![1; 8]).unwrap();
+        filled_buffers_sender.unbounded_send(vec![2; 8]).unwrap();
+        let mut stream = PulseCaptureStream {
+            frame_size: 4,
+            buffer: Vec::new(),
+            buffer_drop: PulseCaptureBufferCommit,
+            empty_buffers,
+            filled_buffers,
+        };
+
+        let ex = Executor::new().expect("failed to create executor");
+        ex.run_until(async {
+            let mut data = [0; 8];
+            let mut buffer = stream.next_capture_buffer(&ex).await.unwrap();
+            buffer.read_exact(&mut data).unwrap();
+            assert_eq!(data, [1; 8]);
+            buffer.commit().await;
+            assert!(empty_buffers_receiver.try_recv().is_err());
+
+            let mut buffer = stream.next_capture_buffer(&ex).await.unwrap();
+            buffer.read_exact(&mut data).unwrap();
+            assert_eq!(data, [2; 8]);
+        })
+        .unwrap();
+        assert_eq!(empty_buffers_receiver.try_recv().unwrap(), vec![1; 8]);
+    }
+
+    // Needs a PulseAudio server, such as one started with
+    // `pulseaudio -n --daemonize --load=module-null-sink --load=module-native-protocol-unix`.
+    #[test]
+    #[ignore]
+    fn null_sink_playback_and_capture() {
+        let mut source = PulseStreamSourceGenerator::new("crosvm test")
+            .generate()
+            .unwrap();
+        let ex = Executor::new().expect("failed to create executor");
+        ex.run_until(async {
+            let (_, mut playback) = source
+                .new_async_playback_stream(2, SampleFormat::S16LE, 48000, 480, &ex)
+                .unwrap();
+            for _ in 0..10 {
+                let mut buffer = playback.next_playback_buffer(&ex).await.unwrap();
+                assert_eq!(buffer.frame_capacity(), 480);
+                buffer.write_all(&[0x55; 480 * 4]).unwrap();
+                buffer.commit().await;
+            }
+
+            // The monitor of the null sink is the default source.
+            let (_, mut capture) = source
+                .new_async_capture_stream(2, SampleFormat::S16LE, 48000, 480, &[], &ex)
+                .unwrap();
+            let mut data = vec![0; 480 * 4];
+            for _ in 0..10 {
+                let mut buffer = capture.next_capture_buffer(&ex).await.unwrap();
+                assert_eq!(buffer.frame_capacity(), 480);
+                buffer.read_exact(&mut data).unwrap();
+                buffer.commit().await;
+            }
+        })
+        .unwrap();
+    }
+}
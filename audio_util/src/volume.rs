@@ -0,0 +1,145 @@
+// Copyright 2023 The ChromiumOS Authors
+// Use of this source code is governed by a BSD-style license that can be
+// found in the LICENSE file.
+
+//! Volume and mute applied in software to PCM samples.
+
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicU32;
+use std::sync::atomic::Ordering;
+
+use audio_streams::SampleFormat;
+
+/// Volume at which samples are left untouched.
+pub const MAX_VOLUME: u32 = 100;
+
+/// Volume and mute state of a mixer control.
+///
+/// The state can be shared between the thread changing it and the threads processing the samples
+/// of the streams it applies to.
+#[derive(Debug)]
+pub struct SoftwareVolume {
+    volume: AtomicU32,
+    muted: AtomicBool,
+}
+
+impl Default for SoftwareVolume {
+    fn default() -> Self {
+        SoftwareVolume {
+            volume: AtomicU32::new(MAX_VOLUME),
+            muted: AtomicBool::new(false),
+        }
+    }
+}
+
+impl SoftwareVolume {
+    /// Returns the volume, from 0 to `MAX_VOLUME`.
+    pub fn volume(&self) -> u32 {
+        self.volume.load(Ordering::Relaxed)
+    }
+
+    /// Sets the volume. Values above `MAX_VOLUME` are clamped.
+    pub fn set_volume(&self, volume: u32) {
+        self.volume.store(volume.min(MAX_VOLUME), Ordering::Relaxed);
+    }
+
+    pub fn muted(&self) -> bool {
+        self.muted.load(Ordering::Relaxed)
+    }
+
+    pub fn set_muted(&self, muted: bool) {
+        self.muted.store(muted, Ordering::Relaxed);
+    }
+
+    /// Scales the samples of `format` in `buf` linearly by the current volume, or silences them
+    /// if muted.
+    ///
+    /// Trailing bytes that do not form a whole sample are left untouched.
+    pub fn apply(&self, format: SampleFormat, buf: &mut [u8]) {
+        let gain = if self.muted() {
+            0
+        } else {
+            self.volume() as i64
+        };
+        if gain == MAX_VOLUME as i64 {
+            return;
+        }
+        let scale = |sample: i64| sample * gain / MAX_VOLUME as i64;
+
+        match format {
+            SampleFormat::U8 => {
+                for sample in buf.iter_mut() {
+                    *sample = (scale(*sample as i64 - 0x80) + 0x80) as u8;
+                }
+            }
+            SampleFormat::S16LE => {
+                for sample in buf.chunks_exact_mut(2) {
+                    let value = i16::from_le_bytes([sample[0], sample[1]]);
+                    sample.copy_from_slice(&(scale(value as i64) as i16).to_le_bytes());
+                }
+            }
+            SampleFormat::S24LE | SampleFormat::S32LE => {
+                for sample in buf.chunks_exact_mut(4) {
+                    let mut value = i32::from_le_bytes(sample.try_into().unwrap());
+                    if format == SampleFormat::S24LE {
+                        // Sign-extend the 24 bit sample stored in the low bytes.
+                        value = (value << 8) >> 8;
+                    }
+                    sample.copy_from_slice(&(scale(value as i64) as i32).to_le_bytes());
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn full_volume_is_identity() {
+        let volume = SoftwareVolume::default();
+        let mut buf = [0x12, 0x34, 0x56, 0x78];
+        volume.apply(SampleFormat::S16LE, &mut buf);
+        assert_eq!(buf, [0x12, 0x34, 0x56, 0x78]);
+    }
+
+    #[test]
+    fn scale_samples() {
+        let volume = SoftwareVolume::default();
+        volume.set_volume(50);
+
+        let mut buf = [0x00, 0x80, 0xff];
+        volume.apply(SampleFormat::U8, &mut buf);
+        assert_eq!(buf, [0x40, 0x80, 0xbf]);
+
+        let mut buf = [1000i16.to_le_bytes(), (-1000i16).to_le_bytes()].concat();
+        volume.apply(SampleFormat::S16LE, &mut buf);
+        assert_eq!(
+            buf,
+            [500i16.to_le_bytes(), (-500i16).to_le_bytes()].concat()
+        );
+
+        // -2 in 24 bits, with the unused high byte cleared.
+        let mut buf = [0xfe, 0xff, 0xff, 0x00];
+        volume.apply(SampleFormat::S24LE, &mut buf);
+        assert_eq!(i32::from_le_bytes(buf), -1);
+
+        let mut buf = i32::MIN.to_le_bytes();
+        volume.apply(SampleFormat::S32LE, &mut buf);
+        assert_eq!(i32::from_le_bytes(buf), i32::MIN / 2);
+    }
+
+    #[test]
+    fn mute() {
+        let volume = SoftwareVolume::default();
+        volume.set_muted(true);
+        let mut buf = [0x00, 0xff, 0x12];
+        volume.apply(SampleFormat::U8, &mut buf);
+        assert_eq!(buf, [0x80, 0x80, 0x80]);
+
+        volume.set_muted(false);
+        volume.set_volume(1000);
+        assert_eq!(volume.volume(), MAX_VOLUME);
+    }
+}
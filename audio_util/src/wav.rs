@@ -0,0 +1,210 @@
+// Copyright 2023 The ChromiumOS Authors
+// Use of this source code is governed by a BSD-style license that can be
+// found in the LICENSE file.
+
+//! Minimal reader and writer for the headers of RIFF WAVE files containing PCM samples.
+
+use std::io::Read;
+use std::io::Seek;
+use std::io::SeekFrom;
+
+use audio_streams::SampleFormat;
+
+use crate::file_streams::Error;
+
+/// Size of the header written by [`WavHeader::to_bytes`].
+pub const WAV_HEADER_LEN: usize = 44;
+
+const WAVE_FORMAT_PCM: u16 = 1;
+const WAVE_FORMAT_EXTENSIBLE: u16 = 0xfffe;
+
+/// Format of the samples stored in a WAV file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WavHeader {
+    pub num_channels: u16,
+    pub frame_rate: u32,
+    /// Number of bytes of a single sample, including padding.
+    pub sample_bytes: u16,
+}
+
+impl WavHeader {
+    pub fn new(num_channels: usize, format: SampleFormat, frame_rate: u32) -> Self {
+        WavHeader {
+            num_channels: num_channels as u16,
+            frame_rate,
+            sample_bytes: format.sample_bytes() as u16,
+        }
+    }
+
+    /// Number of bytes of a frame, i.e. of one sample for each channel.
+    pub fn frame_size(&self) -> usize {
+        self.num_channels as usize * self.sample_bytes as usize
+    }
+
+    /// Serializes the header of a file holding `data_len` bytes of samples.
+    ///
+    /// S24LE samples are stored in 4 bytes and are therefore described as 32 bit samples.
+    pub fn to_bytes(self, data_len: u32) -> [u8; WAV_HEADER_LEN] {
+        let block_align = self.num_channels * self.sample_bytes;
+        let byte_rate = self.frame_rate * block_align as u32;
+
+        let mut header = [0u8; WAV_HEADER_LEN];
+        header[0..4].copy_from_slice(b"RIFF");
+        header[4..8].copy_from_slice(&(data_len.saturating_add(36)).to_le_bytes());
+        header[8..12].copy_from_slice(b"WAVE");
+        header[12..16].copy_from_slice(b"fmt ");
+        header[16..20].copy_from_slice(&16u32.to_le_bytes());
+        header[20..22].copy_from_slice(&WAVE_FORMAT_PCM.to_le_bytes());
+        header[22..24].copy_from_slice(&self.num_channels.to_le_bytes());
+        header[24..28].copy_from_slice(&self.frame_rate.to_le_bytes());
+        header[28..32].copy_from_slice(&byte_rate.to_le_bytes());
+        header[32..34].copy_from_slice(&block_align.to_le_bytes());
+        header[34..36].copy_from_slice(&(self.sample_bytes * 8).to_le_bytes());
+        header[36..40].copy_from_slice(b"data");
+        header[40..44].copy_from_slice(&data_len.to_le_bytes());
+        header
+    }
+
+    /// Parses the header of the WAV file read by `reader`.
+    ///
+    /// Returns the header along with the offset and length of the samples in the file.
+    pub fn parse<R: Read + Seek>(reader: &mut R) -> Result<(Self, u64, u64), Error> {
+        let mut riff = [0u8; 12];
+        reader.read_exact(&mut riff).map_err(Error::ReadWavFile)?;
+        if &riff[0..4] != b"RIFF" || &riff[8..12] != b"WAVE" {
+            return Err(Error::InvalidWavFile("not a RIFF WAVE file"));
+        }
+
+        let mut header = None;
+        loop {
+            let mut chunk = [0u8; 8];
+            reader.read_exact(&mut chunk).map_err(|e| {
+                if e.kind() == std::io::ErrorKind::UnexpectedEof {
+                    Error::InvalidWavFile("no data chunk")
+                } else {
+                    Error::ReadWavFile(e)
+                }
+            })?;
+            let chunk_len = u32::from_le_bytes(chunk[4..8].try_into().unwrap()) as u64;
+
+            match &chunk[0..4] {
+                b"fmt " => {
+                    if chunk_len < 16 {
+                        return Err(Error::InvalidWavFile("fmt chunk is too short"));
+                    }
+                    let mut fmt = vec![0u8; chunk_len as usize];
+                    reader.read_exact(&mut fmt).map_err(Error::ReadWavFile)?;
+                    header = Some(Self::parse_fmt(&fmt)?);
+                    // Chunks are padded to an even size.
+                    if chunk_len % 2 != 0 {
+                        reader
+                            .seek(SeekFrom::Current(1))
+                            .map_err(Error::ReadWavFile)?;
+                    }
+                }
+                b"data" => {
+                    let header = header.ok_or(Error::InvalidWavFile("data chunk before fmt"))?;
+                    let data_offset = reader.stream_position().map_err(Error::ReadWavFile)?;
+                    let file_len = reader.seek(SeekFrom::End(0)).map_err(Error::ReadWavFile)?;
+                    // Files that were not finalized may report a bogus length.
+                    let data_len = chunk_len.min(file_len - data_offset);
+                    return Ok((header, data_offset, data_len));
+                }
+                _ => {
+                    reader
+                        .seek(SeekFrom::Current((chunk_len + chunk_len % 2) as i64))
+                        .map_err(Error::ReadWavFile)?;
+                }
+            }
+        }
+    }
+
+    fn parse_fmt(fmt: &[u8]) -> Result<Self, Error> {
+        let read_u16 = |offset: usize| u16::from_le_bytes([fmt[offset], fmt[offset + 1]]);
+
+        let mut format_tag = read_u16(0);
+        if format_tag == WAVE_FORMAT_EXTENSIBLE {
+            if fmt.len() < 40 {
+                return Err(Error::InvalidWavFile("fmt chunk is too short"));
+            }
+            // The sub-format GUID starts with the format tag.
+            format_tag = read_u16(24);
+        }
+        if format_tag != WAVE_FORMAT_PCM {
+            return Err(Error::InvalidWavFile("samples are not PCM"));
+        }
+
+        let num_channels = read_u16(2);
+        let frame_rate = u32::from_le_bytes(fmt[4..8].try_into().unwrap());
+        let block_align = read_u16(12);
+        if num_channels == 0 || frame_rate == 0 || block_align % num_channels != 0 {
+            return Err(Error::InvalidWavFile("invalid fmt chunk"));
+        }
+
+        Ok(WavHeader {
+            num_channels,
+            frame_rate,
+            sample_bytes: block_align / num_channels,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+
+    #[test]
+    fn header_round_trip() {
+        let header = WavHeader::new(2, SampleFormat::S16LE, 48000);
+        let mut file = header.to_bytes(8).to_vec();
+        file.extend_from_slice(&[1, 2, 3, 4, 5, 6, 7, 8]);
+
+        let (parsed, data_offset, data_len) = WavHeader::parse(&mut Cursor::new(&file)).unwrap();
+        assert_eq!(parsed, header);
+        assert_eq!(parsed.frame_size(), 4);
+        assert_eq!(data_offset, WAV_HEADER_LEN as u64);
+        assert_eq!(data_len, 8);
+    }
+
+    #[test]
+    fn parse_skips_unknown_chunks() {
+        let header = WavHeader::new(1, SampleFormat::U8, 8000);
+        let bytes = header.to_bytes(2);
+        let mut file = bytes[..36].to_vec();
+        // Odd-sized LIST chunk, followed by its padding byte.
+        file.extend_from_slice(b"LIST");
+        file.extend_from_slice(&3u32.to_le_bytes());
+        file.extend_from_slice(&[0, 0, 0, 0]);
+        file.extend_from_slice(&bytes[36..]);
+        file.extend_from_slice(&[0x80, 0x80]);
+
+        let (parsed, data_offset, data_len) = WavHeader::parse(&mut Cursor::new(&file)).unwrap();
+        assert_eq!(parsed, header);
+        assert_eq!(data_offset, WAV_HEADER_LEN as u64 + 12);
+        assert_eq!(data_len, 2);
+    }
+
+    #[test]
+    fn parse_truncated_data() {
+        let header = WavHeader::new(2, SampleFormat::S32LE, 44100);
+        let mut file = header.to_bytes(u32::MAX).to_vec();
+        file.extend_from_slice(&[0; 16]);
+
+        let (_, _, data_len) = WavHeader::parse(&mut Cursor::new(&file)).unwrap();
+        assert_eq!(data_len, 16);
+    }
+
+    #[test]
+    fn parse_invalid() {
+        assert!(WavHeader::parse(&mut Cursor::new(b"RIFF\0\0\0\0AVI ")).is_err());
+
+        // Floating point samples.
+        let mut file = WavHeader::new(2, SampleFormat::S32LE, 48000)
+            .to_bytes(0)
+            .to_vec();
+        file[20] = 3;
+        assert!(WavHeader::parse(&mut Cursor::new(&file)).is_err());
+    }
+}
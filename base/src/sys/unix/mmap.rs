@@ -52,6 +52,9 @@ pub enum Error {
 }
 pub type Result<T> = std::result::Result<T, Error>;
 
+// Flag of mbind(2) to also move the pages already allocated outside of the new policy.
+const MPOL_MF_MOVE: libc::c_uint = 1 << 1;
+
 /// Validates that `offset`..`offset+range_size` lies within the bounds of a memory mapping of
 /// `mmap_size` bytes.  Also checks for any overflow.
 fn validate_includes_range(mmap_size: usize, offset: usize, range_size: usize) -> Result<()> {
@@ -516,6 +519,42 @@ impl MemoryMapping {
         }
     }
 
+    /// Sets the NUMA memory policy of the range so that its pages are allocated from host node
+    /// `node`, moving the pages already allocated elsewhere.
+    ///
+    /// # Arguments
+    ///
+    /// * `mem_offset` - The offset of the head of the range.
+    /// * `count` - The size in bytes of the range.
+    /// * `node` - The host NUMA node to allocate the pages from.
+    pub fn bind_to_host_node(&self, mem_offset: usize, count: usize, node: u32) -> Result<()> {
+        self.range_end(mem_offset, count)
+            .map_err(|_| Error::InvalidRange(mem_offset, count, self.size()))?;
+        let mut nodemask = vec![0u64; node as usize / 64 + 1];
+        nodemask[node as usize / 64] |= 1 << (node % 64);
+        // The kernel drops the last bit of the node mask, so one more bit than the mask holds must
+        // be passed for the last node to be taken into account.
+        let maxnode = nodemask.len() * 64 + 1;
+        // Safe because the memory policy only affects where the kernel allocates the pages, and
+        // the node mask is a valid buffer of the given number of bits.
+        let ret = unsafe {
+            libc::syscall(
+                libc::SYS_mbind,
+                (self.addr as usize + mem_offset) as *mut c_void,
+                count,
+                libc::MPOL_BIND,
+                nodemask.as_ptr(),
+                maxnode,
+                MPOL_MF_MOVE,
+            )
+        };
+        if ret < 0 {
+            Err(Error::SystemCallFailed(super::Error::last()))
+        } else {
+            Ok(())
+        }
+    }
+
     /// Tell the kernel to readahead the range.
     ///
     /// This does not block the thread by I/O wait from reading the backed file. This does not
@@ -908,6 +947,8 @@ impl CrateMemoryMapping {
 pub trait Unix {
     /// Remove the specified range from the mapping.
     fn remove_range(&self, mem_offset: usize, count: usize) -> Result<()>;
+    /// Allocate the pages of the range from the given host NUMA node.
+    fn bind_to_host_node(&self, mem_offset: usize, count: usize, node: u32) -> Result<()>;
     /// Tell the kernel to readahead the range.
     fn async_prefetch(&self, mem_offset: usize, count: usize) -> Result<()>;
     /// Tell the kernel to drop the page cache.
@@ -924,6 +965,9 @@ impl Unix for CrateMemoryMapping {
     fn remove_range(&self, mem_offset: usize, count: usize) -> Result<()> {
         self.mapping.remove_range(mem_offset, count)
     }
+    fn bind_to_host_node(&self, mem_offset: usize, count: usize, node: u32) -> Result<()> {
+        self.mapping.bind_to_host_node(mem_offset, count, node)
+    }
     fn async_prefetch(&self, mem_offset: usize, count: usize) -> Result<()> {
         self.mapping.async_prefetch(mem_offset, count)
     }
@@ -1101,6 +1145,22 @@ mod tests {
         }
     }
 
+    #[test]
+    fn bind_to_host_node() {
+        let m = MemoryMappingBuilder::new(pagesize() * 4).build().unwrap();
+        m.write_obj(0x55u8, 0).unwrap();
+        // Every host has a node 0, even without NUMA.
+        m.bind_to_host_node(0, pagesize() * 4, 0).unwrap();
+        assert_eq!(m.read_obj::<u8>(0).unwrap(), 0x55);
+        match m
+            .bind_to_host_node(pagesize(), pagesize() * 4, 0)
+            .unwrap_err()
+        {
+            Error::InvalidRange(..) => {}
+            e => panic!("unexpected error: {}", e),
+        }
+    }
+
     #[test]
     fn arena_new() {
         let m = MemoryMappingArena::new(0x40000).unwrap();
@@ -24,6 +24,7 @@ use libc::c_char;
 use libc::ssize_t;
 pub use swap::SwapStatus;
 use vm_control::client::*;
+use vm_control::gpu::GpuControlResult;
 use vm_control::BalloonControlCommand;
 use vm_control::BalloonStats;
 use vm_control::BalloonWSS;
@@ -860,3 +861,54 @@ pub unsafe extern "C" fn crosvm_client_balloon_wss_config(
     })
     .unwrap_or(false)
 }
+
+/// Saves the current contents of display `display_id` of the crosvm instance whose control socket
+/// is listening on `socket_path` as a PNG image at `out_path`.
+///
+/// The parameters `out_width` and `out_height` are optional and will only be written to if they
+/// are non-null.
+///
+/// The function returns true on success or false if an error occured.
+///
+/// # Safety
+///
+/// Function is unsafe due to raw pointer usage - a null pointer could be passed in. Usage of
+/// !raw_pointer.is_null() checks should prevent unsafe behavior but the caller should ensure no
+/// null pointers are passed.
+#[no_mangle]
+pub unsafe extern "C" fn crosvm_client_gpu_screenshot(
+    socket_path: *const c_char,
+    display_id: u32,
+    out_path: *const c_char,
+    out_width: *mut u32,
+    out_height: *mut u32,
+) -> bool {
+    catch_unwind(|| {
+        if let Some(socket_path) = validate_socket_path(socket_path) {
+            if out_path.is_null() {
+                return false;
+            }
+            // SAFETY: just checked that `out_path` is not null.
+            let out_path = Path::new(unsafe { CStr::from_ptr(out_path) }.to_str().unwrap_or(""));
+
+            if let Ok(GpuControlResult::ScreenshotTaken { width, height }) =
+                do_gpu_screenshot(socket_path, display_id, out_path)
+            {
+                if !out_width.is_null() {
+                    // SAFETY: just checked that `out_width` is not null.
+                    unsafe { *out_width = width };
+                }
+                if !out_height.is_null() {
+                    // SAFETY: just checked that `out_height` is not null.
+                    unsafe { *out_height = height };
+                }
+                true
+            } else {
+                false
+            }
+        } else {
+            false
+        }
+    })
+    .unwrap_or(false)
+}
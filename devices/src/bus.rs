@@ -181,12 +181,14 @@ pub trait BusResumeDevice: Send {
 
 /// The key to identify hotplug device from host view.
 /// like host sysfs path for vfio pci device, host disk file
-/// path for virtio block device
+/// path for virtio block device. Emulated virtio devices have no host address, so they are keyed
+/// by the guest address they were hotplugged at.
 #[derive(Copy, Clone, PartialEq, Eq, Debug)]
 pub enum HostHotPlugKey {
     UpstreamPort { host_addr: PciAddress },
     DownstreamPort { host_addr: PciAddress },
     Vfio { host_addr: PciAddress },
+    Virtio { guest_addr: PciAddress },
 }
 
 /// Trait for devices that notify hotplug event into guest
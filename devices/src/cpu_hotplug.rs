@@ -0,0 +1,567 @@
+// Copyright 2023 The ChromiumOS Authors
+// Use of this source code is governed by a BSD-style license that can be
+// found in the LICENSE file.
+
+// vCPU hotplug. `VcpuHotPlugState` tracks which of the configured vCPUs are plugged into the guest
+// and is shared by the VMM control loop and the architecture specific code. On x86, the ACPI
+// processor devices (ACPI0007) described by `CpuHotPlugController` report the vCPU presence via
+// _STA and the controller's GPE makes the guest rescan them, following ACPI specification section
+// 8.4 and the generic hot-plug model of section 6.3. A removal is completed once the guest ejects
+// the vCPU with _EJ0.
+
+use std::sync::Arc;
+
+use acpi_tables::aml;
+use acpi_tables::aml::Aml;
+use base::warn;
+use remain::sorted;
+use sync::Mutex;
+use thiserror::Error;
+
+use crate::pci::CrosvmDeviceId;
+use crate::BusAccessInfo;
+use crate::BusDevice;
+use crate::DeviceId;
+use crate::Suspendable;
+
+#[sorted]
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum VcpuHotPlugError {
+    #[error("vCPU {0} is already plugged")]
+    AlreadyPlugged(usize),
+    #[error("vCPU {0} is the boot vCPU and can't be unplugged")]
+    BootVcpu(usize),
+    #[error("vCPU {0} doesn't exist")]
+    InvalidVcpu(usize),
+    #[error("vCPU {0} is not plugged")]
+    NotPlugged(usize),
+}
+
+pub type Result<T> = std::result::Result<T, VcpuHotPlugError>;
+
+#[derive(Clone, Copy, Default)]
+struct VcpuSlot {
+    plugged: bool,
+    // Insertion not yet acknowledged by the guest.
+    inserting: bool,
+    // Removal not yet acknowledged by the guest.
+    removing: bool,
+}
+
+/// Plugged state of the vCPUs of the VM.
+pub struct VcpuHotPlugState {
+    vcpus: Vec<VcpuSlot>,
+    gpe: Option<u32>,
+}
+
+impl VcpuHotPlugState {
+    /// Creates the state of `vcpu_count` vCPUs, of which the first `boot_vcpu_count` are plugged.
+    pub fn new(vcpu_count: usize, boot_vcpu_count: usize) -> Self {
+        let vcpus = (0..vcpu_count)
+            .map(|cpu| VcpuSlot {
+                plugged: cpu < boot_vcpu_count,
+                ..Default::default()
+            })
+            .collect();
+        VcpuHotPlugState { vcpus, gpe: None }
+    }
+
+    /// Returns the number of configured vCPUs, plugged or not.
+    pub fn vcpu_count(&self) -> usize {
+        self.vcpus.len()
+    }
+
+    /// Returns whether vCPU `cpu` is plugged.
+    pub fn is_plugged(&self, cpu: usize) -> bool {
+        self.vcpus.get(cpu).map_or(false, |slot| slot.plugged)
+    }
+
+    /// Returns the GPE to raise to notify the guest of a change, if the guest is notified.
+    pub fn gpe(&self) -> Option<u32> {
+        self.gpe
+    }
+
+    pub fn set_gpe(&mut self, gpe: u32) {
+        self.gpe = Some(gpe);
+    }
+
+    /// Plugs vCPU `cpu`.
+    pub fn plug(&mut self, cpu: usize) -> Result<()> {
+        let slot = self
+            .vcpus
+            .get_mut(cpu)
+            .ok_or(VcpuHotPlugError::InvalidVcpu(cpu))?;
+        if slot.plugged {
+            return Err(VcpuHotPlugError::AlreadyPlugged(cpu));
+        }
+        slot.plugged = true;
+        slot.inserting = true;
+        slot.removing = false;
+        Ok(())
+    }
+
+    /// Requests the removal of vCPU `cpu`. When the guest is notified of hotplug events the vCPU
+    /// stays plugged until the guest ejects it, otherwise it is unplugged right away.
+    pub fn unplug(&mut self, cpu: usize) -> Result<()> {
+        if cpu == 0 {
+            return Err(VcpuHotPlugError::BootVcpu(cpu));
+        }
+        let notify = self.gpe.is_some();
+        let slot = self
+            .vcpus
+            .get_mut(cpu)
+            .ok_or(VcpuHotPlugError::InvalidVcpu(cpu))?;
+        if !slot.plugged {
+            return Err(VcpuHotPlugError::NotPlugged(cpu));
+        }
+        if notify {
+            slot.removing = true;
+            slot.inserting = false;
+        } else {
+            slot.plugged = false;
+        }
+        Ok(())
+    }
+
+    fn eject(&mut self, cpu: usize) {
+        if let Some(slot) = self.vcpus.get_mut(cpu) {
+            *slot = VcpuSlot::default();
+        }
+    }
+}
+
+pub const CPU_HOTPLUG_MMIO_SIZE: u64 = 0x10;
+
+/// Offset of the vCPU selector register.
+const CPU_HOTPLUG_CSEL: u64 = 0;
+/// Offset of the status register of the selected vCPU.
+const CPU_HOTPLUG_STATUS: u64 = 4;
+
+/// Status register bits.
+const CPU_HOTPLUG_CPEN: u8 = 1 << 0;
+const CPU_HOTPLUG_CINS: u8 = 1 << 1;
+const CPU_HOTPLUG_CRMV: u8 = 1 << 2;
+const CPU_HOTPLUG_CEJ0: u8 = 1 << 3;
+
+/// MMIO registers and ACPI description of the hot-pluggable vCPUs.
+///
+/// The guest writes the index of a vCPU to CSEL, then reads its status: CPEN is set while the vCPU
+/// is plugged, CINS and CRMV while an insertion or a removal is pending. Writing CINS or CRMV
+/// acknowledges the event and writing CEJ0 completes the removal.
+pub struct CpuHotPlugController {
+    state: Arc<Mutex<VcpuHotPlugState>>,
+    mmio_base: u64,
+    gpe_nr: u32,
+    selected: u32,
+}
+
+impl CpuHotPlugController {
+    pub fn new(state: Arc<Mutex<VcpuHotPlugState>>, mmio_base: u64, gpe_nr: u32) -> Self {
+        CpuHotPlugController {
+            state,
+            mmio_base,
+            gpe_nr,
+            selected: 0,
+        }
+    }
+
+    fn status(&self) -> u8 {
+        let state = self.state.lock();
+        let slot = match state.vcpus.get(self.selected as usize) {
+            Some(slot) => slot,
+            None => return 0,
+        };
+        let mut status = 0;
+        if slot.plugged {
+            status |= CPU_HOTPLUG_CPEN;
+        }
+        if slot.inserting {
+            status |= CPU_HOTPLUG_CINS;
+        }
+        if slot.removing {
+            status |= CPU_HOTPLUG_CRMV;
+        }
+        status
+    }
+
+    fn write_status(&mut self, status: u8) {
+        let cpu = self.selected as usize;
+        let mut state = self.state.lock();
+        if status & CPU_HOTPLUG_CEJ0 != 0 {
+            state.eject(cpu);
+            return;
+        }
+        if let Some(slot) = state.vcpus.get_mut(cpu) {
+            if status & CPU_HOTPLUG_CINS != 0 {
+                slot.inserting = false;
+            }
+            if status & CPU_HOTPLUG_CRMV != 0 {
+                slot.removing = false;
+            }
+        }
+    }
+}
+
+impl BusDevice for CpuHotPlugController {
+    fn device_id(&self) -> DeviceId {
+        CrosvmDeviceId::CpuHotPlug.into()
+    }
+
+    fn debug_label(&self) -> String {
+        "CpuHotPlugController".to_owned()
+    }
+
+    fn read(&mut self, info: BusAccessInfo, data: &mut [u8]) {
+        match (info.offset, data.len()) {
+            (CPU_HOTPLUG_CSEL, 4) => data.copy_from_slice(&self.selected.to_le_bytes()),
+            (CPU_HOTPLUG_STATUS, 1) => data[0] = self.status(),
+            _ => warn!(
+                "{}: unsupported read of {} bytes at {}",
+                self.debug_label(),
+                data.len(),
+                info
+            ),
+        }
+    }
+
+    fn write(&mut self, info: BusAccessInfo, data: &[u8]) {
+        match (info.offset, data.len()) {
+            (CPU_HOTPLUG_CSEL, 4) => {
+                // Unwrap is safe: the length of data was checked above.
+                self.selected = u32::from_le_bytes(data.try_into().unwrap());
+            }
+            (CPU_HOTPLUG_STATUS, 1) => self.write_status(data[0]),
+            _ => warn!(
+                "{}: unsupported write of {} bytes at {}",
+                self.debug_label(),
+                data.len(),
+                info
+            ),
+        }
+    }
+}
+
+/// Name of the ACPI processor device of vCPU `cpu`.
+pub(crate) fn cpu_device_name(cpu: usize) -> String {
+    format!("C{:03X}", cpu)
+}
+
+/// MADT Processor Local APIC structure of vCPU `cpu`, returned by its _MAT. The APIC ID is
+/// `cpu`, so hotplug is limited to 255 vCPUs.
+fn cpu_local_apic(cpu: usize) -> Vec<u8> {
+    const MADT_TYPE_LOCAL_APIC: u8 = 0;
+    const MADT_ENABLED: u32 = 1;
+    let mut entry = vec![MADT_TYPE_LOCAL_APIC, 8, cpu as u8, cpu as u8];
+    entry.extend_from_slice(&MADT_ENABLED.to_le_bytes());
+    entry
+}
+
+impl Aml for CpuHotPlugController {
+    fn to_aml_bytes(&self, bytes: &mut Vec<u8>) {
+        let cpus: Vec<usize> = (0..self.state.lock().vcpu_count()).collect();
+        let lock_timeout = 0xffff;
+        let mmio_size = CPU_HOTPLUG_MMIO_SIZE as u32;
+
+        // Body of CTFY(cpu, event), which Notify()'s processor device `cpu`.
+        let cpu_paths: Vec<aml::Path> = cpus
+            .iter()
+            .map(|cpu| cpu_device_name(*cpu).as_str().into())
+            .collect();
+        let notifies: Vec<aml::Notify> = cpu_paths
+            .iter()
+            .map(|path| aml::Notify::new(path, &aml::Arg(1)))
+            .collect();
+        let is_cpu: Vec<aml::Equal> = cpus
+            .iter()
+            .map(|cpu| aml::Equal::new(&aml::Arg(0), cpu))
+            .collect();
+        let notify_ifs: Vec<aml::If> = is_cpu
+            .iter()
+            .zip(notifies.iter())
+            .map(|(predicate, notify)| aml::If::new(predicate, vec![notify]))
+            .collect();
+
+        // The processor devices, whose _UID matches the ACPI processor UID of the MADT.
+        let hid = aml::Name::new("_HID".into(), &"ACPI0007");
+        let uids: Vec<aml::Name> = cpus
+            .iter()
+            .map(|cpu| aml::Name::new("_UID".into(), cpu))
+            .collect();
+        let mats: Vec<aml::Name> = cpus
+            .iter()
+            .map(|cpu| aml::Name::new("_MAT".into(), &aml::BufferData::new(cpu_local_apic(*cpu))))
+            .collect();
+        let sta_calls: Vec<aml::MethodCall> = cpus
+            .iter()
+            .map(|cpu| aml::MethodCall::new("CSTA".into(), vec![cpu]))
+            .collect();
+        let sta_returns: Vec<aml::Return> = sta_calls
+            .iter()
+            .map(|call| aml::Return::new(call))
+            .collect();
+        let stas: Vec<aml::Method> = sta_returns
+            .iter()
+            .map(|ret| aml::Method::new("_STA".into(), 0, false, vec![ret]))
+            .collect();
+        let ej0_calls: Vec<aml::MethodCall> = cpus
+            .iter()
+            .map(|cpu| aml::MethodCall::new("CEJC".into(), vec![cpu]))
+            .collect();
+        let ej0s: Vec<aml::Method> = ej0_calls
+            .iter()
+            .map(|call| aml::Method::new("_EJ0".into(), 1, false, vec![call]))
+            .collect();
+        let devices: Vec<aml::Device> = cpus
+            .iter()
+            .map(|&cpu| {
+                let mut children: Vec<&dyn Aml> = vec![&hid, &uids[cpu], &stas[cpu], &mats[cpu]];
+                // The boot vCPU can't be removed.
+                if cpu != 0 {
+                    children.push(&ej0s[cpu]);
+                }
+                aml::Device::new(cpu_device_name(cpu).as_str().into(), children)
+            })
+            .collect();
+
+        aml::Device::new(
+            "\\_SB_.CPUS".into(),
+            vec![
+                &aml::Name::new("_HID".into(), &"ACPI0010"),
+                &aml::OpRegion::new(
+                    "CPRG".into(),
+                    aml::OpRegionSpace::SystemMemory,
+                    &self.mmio_base,
+                    &mmio_size,
+                ),
+                &aml::Field::new(
+                    "CPRG".into(),
+                    aml::FieldAccessType::DWord,
+                    aml::FieldLockRule::NoLock,
+                    aml::FieldUpdateRule::Preserve,
+                    vec![aml::FieldEntry::Named(*b"CSEL", 32)],
+                ),
+                &aml::Field::new(
+                    "CPRG".into(),
+                    aml::FieldAccessType::Byte,
+                    aml::FieldLockRule::NoLock,
+                    aml::FieldUpdateRule::WriteAsZeroes,
+                    vec![
+                        aml::FieldEntry::Reserved(32),
+                        aml::FieldEntry::Named(*b"CPEN", 1),
+                        aml::FieldEntry::Named(*b"CINS", 1),
+                        aml::FieldEntry::Named(*b"CRMV", 1),
+                        aml::FieldEntry::Named(*b"CEJ0", 1),
+                    ],
+                ),
+                &aml::Mutex::new("CPLK".into(), 0),
+                // CSTA(cpu) returns the _STA of processor device `cpu`.
+                &aml::Method::new(
+                    "CSTA".into(),
+                    1,
+                    false,
+                    vec![
+                        &aml::Acquire::new("CPLK".into(), lock_timeout),
+                        &aml::Store::new(&aml::Path::new("CSEL"), &aml::Arg(0)),
+                        &aml::Store::new(&aml::Local(0), &aml::ZERO),
+                        &aml::If::new(
+                            &aml::Equal::new(&aml::Path::new("CPEN"), &aml::ONE),
+                            vec![&aml::Store::new(&aml::Local(0), &0xfu8)],
+                        ),
+                        &aml::Release::new("CPLK".into()),
+                        &aml::Return::new(&aml::Local(0)),
+                    ],
+                ),
+                // CEJC(cpu) ejects processor device `cpu`.
+                &aml::Method::new(
+                    "CEJC".into(),
+                    1,
+                    false,
+                    vec![
+                        &aml::Acquire::new("CPLK".into(), lock_timeout),
+                        &aml::Store::new(&aml::Path::new("CSEL"), &aml::Arg(0)),
+                        &aml::Store::new(&aml::Path::new("CEJ0"), &aml::ONE),
+                        &aml::Release::new("CPLK".into()),
+                    ],
+                ),
+                // CTFY(cpu, event) Notify()'s processor device `cpu`.
+                &aml::Method::new(
+                    "CTFY".into(),
+                    2,
+                    false,
+                    notify_ifs.iter().map(|i| i as &dyn Aml).collect(),
+                ),
+                // CSCN() notifies the guest of the pending insertions and removals and
+                // acknowledges them.
+                &aml::Method::new(
+                    "CSCN".into(),
+                    0,
+                    false,
+                    vec![
+                        &aml::Acquire::new("CPLK".into(), lock_timeout),
+                        &aml::Store::new(&aml::Local(0), &aml::ZERO),
+                        &aml::While::new(
+                            &aml::LessThan::new(&aml::Local(0), &cpus.len()),
+                            vec![
+                                &aml::Store::new(&aml::Path::new("CSEL"), &aml::Local(0)),
+                                &aml::If::new(
+                                    &aml::Equal::new(&aml::Path::new("CINS"), &aml::ONE),
+                                    vec![
+                                        // Device Check
+                                        &aml::MethodCall::new(
+                                            "CTFY".into(),
+                                            vec![&aml::Local(0), &1u8],
+                                        ),
+                                        &aml::Store::new(&aml::Path::new("CINS"), &aml::ONE),
+                                    ],
+                                ),
+                                &aml::If::new(
+                                    &aml::Equal::new(&aml::Path::new("CRMV"), &aml::ONE),
+                                    vec![
+                                        // Eject Request
+                                        &aml::MethodCall::new(
+                                            "CTFY".into(),
+                                            vec![&aml::Local(0), &3u8],
+                                        ),
+                                        &aml::Store::new(&aml::Path::new("CRMV"), &aml::ONE),
+                                    ],
+                                ),
+                                &aml::Add::new(&aml::Local(0), &aml::Local(0), &aml::ONE),
+                            ],
+                        ),
+                        &aml::Release::new("CPLK".into()),
+                    ],
+                ),
+            ],
+        )
+        .to_aml_bytes(bytes);
+
+        // The processor devices are added once the methods they call are declared, so that the
+        // guest knows the argument count of the methods when parsing the calls.
+        aml::Scope::new(
+            "\\_SB_.CPUS".into(),
+            devices.iter().map(|device| device as &dyn Aml).collect(),
+        )
+        .to_aml_bytes(bytes);
+
+        aml::Scope::new(
+            "_GPE".into(),
+            vec![&aml::Method::new(
+                format!("_E{:02X}", self.gpe_nr).as_str().into(),
+                0,
+                false,
+                vec![&aml::MethodCall::new("\\_SB_.CPUS.CSCN".into(), vec![])],
+            )],
+        )
+        .to_aml_bytes(bytes);
+    }
+}
+
+impl Suspendable for CpuHotPlugController {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn access(offset: u64) -> BusAccessInfo {
+        BusAccessInfo {
+            offset,
+            address: offset,
+            id: 0,
+        }
+    }
+
+    #[test]
+    fn plug_unplug() {
+        let mut state = VcpuHotPlugState::new(4, 2);
+        assert!(state.is_plugged(1));
+        assert!(!state.is_plugged(2));
+        assert_eq!(state.plug(1), Err(VcpuHotPlugError::AlreadyPlugged(1)));
+        assert_eq!(state.plug(4), Err(VcpuHotPlugError::InvalidVcpu(4)));
+        assert_eq!(state.unplug(0), Err(VcpuHotPlugError::BootVcpu(0)));
+        assert_eq!(state.unplug(3), Err(VcpuHotPlugError::NotPlugged(3)));
+
+        state.plug(2).unwrap();
+        assert!(state.is_plugged(2));
+        // Without a GPE to notify the guest, the removal is immediate.
+        state.unplug(2).unwrap();
+        assert!(!state.is_plugged(2));
+    }
+
+    #[test]
+    fn guest_handshake() {
+        let state = Arc::new(Mutex::new(VcpuHotPlugState::new(4, 1)));
+        state.lock().set_gpe(5);
+        let mut controller = CpuHotPlugController::new(state.clone(), 0x1000, 5);
+
+        let mut status = [0u8];
+        controller.write(access(CPU_HOTPLUG_CSEL), &3u32.to_le_bytes());
+        controller.read(access(CPU_HOTPLUG_STATUS), &mut status);
+        assert_eq!(status[0], 0);
+
+        state.lock().plug(3).unwrap();
+        controller.read(access(CPU_HOTPLUG_STATUS), &mut status);
+        assert_eq!(status[0], CPU_HOTPLUG_CPEN | CPU_HOTPLUG_CINS);
+        controller.write(access(CPU_HOTPLUG_STATUS), &[CPU_HOTPLUG_CINS]);
+        controller.read(access(CPU_HOTPLUG_STATUS), &mut status);
+        assert_eq!(status[0], CPU_HOTPLUG_CPEN);
+
+        // The vCPU stays plugged until the guest ejects it.
+        state.lock().unplug(3).unwrap();
+        assert!(state.lock().is_plugged(3));
+        controller.read(access(CPU_HOTPLUG_STATUS), &mut status);
+        assert_eq!(status[0], CPU_HOTPLUG_CPEN | CPU_HOTPLUG_CRMV);
+        controller.write(access(CPU_HOTPLUG_STATUS), &[CPU_HOTPLUG_CRMV]);
+        controller.write(access(CPU_HOTPLUG_STATUS), &[CPU_HOTPLUG_CEJ0]);
+        assert!(!state.lock().is_plugged(3));
+    }
+
+    fn count(haystack: &[u8], needle: &[u8]) -> usize {
+        haystack
+            .windows(needle.len())
+            .filter(|window| *window == needle)
+            .count()
+    }
+
+    fn position(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+        haystack
+            .windows(needle.len())
+            .position(|window| window == needle)
+    }
+
+    #[test]
+    fn aml() {
+        let state = Arc::new(Mutex::new(VcpuHotPlugState::new(4, 1)));
+        let controller = CpuHotPlugController::new(state, 0x1000, 5);
+        let mut bytes = Vec::new();
+        controller.to_aml_bytes(&mut bytes);
+
+        // A processor device per configured vCPU, all of them ejectable but the boot vCPU.
+        for cpu in 0..4 {
+            assert!(position(&bytes, cpu_device_name(cpu).as_bytes()).is_some());
+        }
+        assert_eq!(count(&bytes, b"ACPI0007"), 4);
+        assert_eq!(count(&bytes, b"_STA"), 4);
+        assert_eq!(count(&bytes, b"_EJ0"), 3);
+
+        // The registers are at the MMIO base of the controller.
+        let mut region = Vec::new();
+        aml::OpRegion::new(
+            "CPRG".into(),
+            aml::OpRegionSpace::SystemMemory,
+            &0x1000u64,
+            &(CPU_HOTPLUG_MMIO_SIZE as u32),
+        )
+        .to_aml_bytes(&mut region);
+        assert_eq!(count(&bytes, &region), 1);
+
+        // The processor devices come after the methods they call.
+        let cscn = position(&bytes, b"CSCN").unwrap();
+        let sta = position(&bytes, b"_STA").unwrap();
+        assert!(cscn < sta);
+
+        // The GPE handler rescans the vCPUs.
+        let gpe = position(&bytes, b"_E05").unwrap();
+        assert!(sta < gpe);
+        assert!(position(&bytes[gpe..], b"CSCN").is_some());
+    }
+}
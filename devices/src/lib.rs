@@ -13,6 +13,7 @@ mod bus;
 #[cfg(feature = "stats")]
 mod bus_stats;
 pub mod cmos;
+pub mod cpu_hotplug;
 #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
 mod debugcon;
 #[cfg(feature = "direct")]
@@ -32,6 +33,7 @@ pub mod serial_device;
 mod software_tpm;
 mod suspendable;
 mod sys;
+pub mod thermal;
 pub mod virtio;
 #[cfg(all(feature = "vtpm", target_arch = "x86_64"))]
 mod vtpm_proxy;
@@ -78,6 +80,9 @@ pub use self::bus::HostHotPlugKey;
 pub use self::bus::HotPlugBus;
 #[cfg(feature = "stats")]
 pub use self::bus_stats::BusStatistics;
+pub use self::cpu_hotplug::CpuHotPlugController;
+pub use self::cpu_hotplug::VcpuHotPlugError;
+pub use self::cpu_hotplug::VcpuHotPlugState;
 #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
 pub use self::debugcon::Debugcon;
 #[cfg(feature = "direct")]
@@ -128,6 +133,10 @@ pub use self::serial_device::SerialType;
 pub use self::software_tpm::SoftwareTpm;
 pub use self::suspendable::DeviceState;
 pub use self::suspendable::Suspendable;
+pub use self::thermal::ThermalConfig;
+pub use self::thermal::ThermalController;
+pub use self::thermal::ThermalError;
+pub use self::thermal::ThermalState;
 pub use self::virtio::VirtioMmioDevice;
 pub use self::virtio::VirtioPciDevice;
 #[cfg(all(feature = "vtpm", target_arch = "x86_64"))]
@@ -139,6 +148,7 @@ cfg_if::cfg_if! {
         mod proxy;
         pub mod vmwdt;
         pub mod vfio;
+        pub mod vfio_user;
         #[cfg(feature = "usb")]
         #[macro_use]
         mod register_space;
@@ -148,9 +158,9 @@ cfg_if::cfg_if! {
         mod utils;
 
         pub use self::pci::{
-            CoIommuDev, CoIommuParameters, CoIommuUnpinPolicy, PciBridge, PcieDownstreamPort,
-            PcieHostPort, PcieRootPort, PcieUpstreamPort, PvPanicCode, PvPanicPciDevice,
-            VfioPciDevice,
+            has_pcie_aer_root_ports, inject_aer_error, CoIommuDev, CoIommuParameters,
+            CoIommuUnpinPolicy, PciBridge, PcieDownstreamPort, PcieHostPort, PcieRootPort,
+            PcieUpstreamPort, PvPanicCode, PvPanicPciDevice, VfioPciDevice, VfioUserPciDevice,
         };
         pub use self::platform::VfioPlatformDevice;
         pub use self::ac_adapter::AcAdapter;
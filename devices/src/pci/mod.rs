@@ -28,6 +28,8 @@ mod pvpanic;
 mod stub;
 #[cfg(unix)]
 mod vfio_pci;
+#[cfg(unix)]
+mod vfio_user_pci;
 
 use libc::EINVAL;
 use serde::Deserialize;
@@ -79,6 +81,10 @@ pub use self::pci_root::PciRoot;
 pub use self::pci_root::PciRootCommand;
 pub use self::pci_root::PciVirtualConfigMmio;
 #[cfg(unix)]
+pub use self::pcie::has_pcie_aer_root_ports;
+#[cfg(unix)]
+pub use self::pcie::inject_aer_error;
+#[cfg(unix)]
 pub use self::pcie::PciBridge;
 #[cfg(unix)]
 pub use self::pcie::PcieDownstreamPort;
@@ -94,6 +100,8 @@ pub use self::stub::StubPciDevice;
 pub use self::stub::StubPciParameters;
 #[cfg(unix)]
 pub use self::vfio_pci::VfioPciDevice;
+#[cfg(unix)]
+pub use self::vfio_user_pci::VfioUserPciDevice;
 
 /// PCI has four interrupt pins A->D.
 #[derive(Copy, Clone, Ord, PartialOrd, PartialEq, Eq)]
@@ -137,6 +145,8 @@ pub enum CrosvmDeviceId {
     VirtioMmio = 19,
     AcAdapter = 20,
     VirtualPmc = 21,
+    CpuHotPlug = 22,
+    Thermal = 23,
 }
 
 impl TryFrom<u16> for CrosvmDeviceId {
@@ -165,6 +175,8 @@ impl TryFrom<u16> for CrosvmDeviceId {
             19 => Ok(CrosvmDeviceId::VirtioMmio),
             20 => Ok(CrosvmDeviceId::AcAdapter),
             21 => Ok(CrosvmDeviceId::VirtualPmc),
+            22 => Ok(CrosvmDeviceId::CpuHotPlug),
+            23 => Ok(CrosvmDeviceId::Thermal),
             _ => Err(base::Error::new(EINVAL)),
         }
     }
@@ -108,6 +108,8 @@ pub enum Error {
     /// PCI Bus window allocation failure.
     #[error("failed to allocate window for PCI bus: {0}")]
     PciBusWindowAllocationFailure(String),
+    #[error("no PCIe root port for the device with requester ID {0:#06x}")]
+    PcieRootPortNotFound(u16),
     /// Size of zero encountered
     #[error("Size of zero detected")]
     SizeZero,
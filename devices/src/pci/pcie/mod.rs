@@ -3,6 +3,7 @@
 // found in the LICENSE file.
 
 mod pci_bridge;
+mod pcie_aer;
 mod pcie_device;
 mod pcie_host;
 mod pcie_port;
@@ -11,6 +12,8 @@ mod pcie_switch;
 
 pub use pci_bridge::PciBridge;
 pub use pcie_host::PcieHostPort;
+pub use pcie_port::has_pcie_aer_root_ports;
+pub use pcie_port::inject_aer_error;
 pub use pcie_rp::PcieRootPort;
 pub use pcie_switch::PcieDownstreamPort;
 pub use pcie_switch::PcieUpstreamPort;
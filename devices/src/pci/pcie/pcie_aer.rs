@@ -0,0 +1,263 @@
+// Copyright 2023 The ChromiumOS Authors
+// Use of this source code is governed by a BSD-style license that can be
+// found in the LICENSE file.
+
+//! Advanced Error Reporting extended capability of the emulated PCIe root ports.
+//!
+//! Errors are injected from the VMM and logged in the AER registers of the root port, which then
+//! signals them to the guest with its MSI, as a root port does for the link errors of its
+//! downstream devices.
+
+use base::warn;
+use vm_control::PcieAerErrorType;
+use zerocopy::FromBytes;
+
+/// Offset of the AER capability in the configuration space, as the first extended capability.
+pub const PCIE_AER_CAP_OFFSET: usize = 0x100;
+/// Length of the AER capability of a root port, including the root error registers.
+pub const PCIE_AER_CAP_LEN: usize = 0x48;
+
+const PCI_EXT_CAP_ID_ERR: u32 = 0x1;
+const PCIE_AER_CAP_VERSION: u32 = 0x2;
+const PCIE_AER_CAP_VERSION_SHIFT: u32 = 16;
+
+const PCIE_AER_UNCOR_STATUS: usize = 0x04;
+const PCIE_AER_UNCOR_MASK: usize = 0x08;
+const PCIE_AER_UNCOR_SEVER: usize = 0x0C;
+const PCIE_AER_COR_STATUS: usize = 0x10;
+const PCIE_AER_COR_MASK: usize = 0x14;
+const PCIE_AER_CAP_CONTROL: usize = 0x18;
+const PCIE_AER_ROOT_COMMAND: usize = 0x2C;
+const PCIE_AER_ROOT_STATUS: usize = 0x30;
+const PCIE_AER_ERR_SRC_ID: usize = 0x34;
+
+// Uncorrectable errors defined by the PCIe base specification, bits 4 to 26.
+const PCIE_AER_UNC_MASK: u32 = 0x07FF_F030;
+const PCIE_AER_UNC_DLP: u32 = 0x0000_0010; // Data Link Protocol Error, fatal by default
+const PCIE_AER_UNC_COMP_TIME: u32 = 0x0000_4000; // Completion Timeout, non-fatal by default
+const PCIE_AER_UNC_SEVER_DEFAULT: u32 = 0x0046_2030;
+
+// Correctable errors defined by the PCIe base specification.
+const PCIE_AER_COR_MASK_BITS: u32 = 0x0000_F1C1;
+const PCIE_AER_COR_RCVR: u32 = 0x0000_0001; // Receiver Error
+const PCIE_AER_COR_ADV_NFAT: u32 = 0x0000_2000; // Advisory Non-Fatal Error, masked by default
+
+const PCIE_AER_ROOT_CMD_COR_EN: u32 = 0x1;
+const PCIE_AER_ROOT_CMD_NONFATAL_EN: u32 = 0x2;
+const PCIE_AER_ROOT_CMD_FATAL_EN: u32 = 0x4;
+const PCIE_AER_ROOT_CMD_MASK: u32 = 0x7;
+
+const PCIE_AER_ROOT_COR_RCV: u32 = 0x01; // ERR_COR received
+const PCIE_AER_ROOT_MULTI_COR_RCV: u32 = 0x02; // Multiple ERR_COR received
+const PCIE_AER_ROOT_UNCOR_RCV: u32 = 0x04; // ERR_FATAL/NONFATAL received
+const PCIE_AER_ROOT_MULTI_UNCOR_RCV: u32 = 0x08; // Multiple ERR_FATAL/NONFATAL received
+const PCIE_AER_ROOT_FIRST_FATAL: u32 = 0x10; // First uncorrectable is fatal
+const PCIE_AER_ROOT_NONFATAL_RCV: u32 = 0x20; // Non-fatal error messages received
+const PCIE_AER_ROOT_FATAL_RCV: u32 = 0x40; // Fatal error messages received
+const PCIE_AER_ROOT_STATUS_MASK: u32 = 0x7F;
+
+/// State of the AER capability of a root port.
+pub struct PcieAerCap {
+    uncor_status: u32,
+    uncor_mask: u32,
+    uncor_severity: u32,
+    cor_status: u32,
+    cor_mask: u32,
+    first_error_pointer: u32,
+    root_command: u32,
+    root_status: u32,
+    error_source_id: u32,
+}
+
+impl PcieAerCap {
+    pub fn new() -> Self {
+        PcieAerCap {
+            uncor_status: 0,
+            uncor_mask: 0,
+            uncor_severity: PCIE_AER_UNC_SEVER_DEFAULT,
+            cor_status: 0,
+            cor_mask: PCIE_AER_COR_ADV_NFAT,
+            first_error_pointer: 0,
+            root_command: 0,
+            root_status: 0,
+            error_source_id: 0,
+        }
+    }
+
+    /// Reads the register at `offset` in the capability.
+    pub fn read(&self, offset: usize) -> u32 {
+        match offset {
+            0 => PCI_EXT_CAP_ID_ERR | (PCIE_AER_CAP_VERSION << PCIE_AER_CAP_VERSION_SHIFT),
+            PCIE_AER_UNCOR_STATUS => self.uncor_status,
+            PCIE_AER_UNCOR_MASK => self.uncor_mask,
+            PCIE_AER_UNCOR_SEVER => self.uncor_severity,
+            PCIE_AER_COR_STATUS => self.cor_status,
+            PCIE_AER_COR_MASK => self.cor_mask,
+            PCIE_AER_CAP_CONTROL => self.first_error_pointer,
+            PCIE_AER_ROOT_COMMAND => self.root_command,
+            PCIE_AER_ROOT_STATUS => self.root_status,
+            PCIE_AER_ERR_SRC_ID => self.error_source_id,
+            // The header and TLP prefix logs are always empty, as the injected errors carry no
+            // TLP.
+            _ => 0,
+        }
+    }
+
+    /// Writes `data` to the register at `offset` in the capability. Returns true if the write
+    /// enabled the interrupt of an error that is already logged.
+    pub fn write(&mut self, offset: usize, data: &[u8]) -> bool {
+        let value = match u32::read_from(data) {
+            Some(v) => v,
+            None => {
+                warn!("write AER register isn't dword, len: {}", data.len());
+                return false;
+            }
+        };
+        match offset {
+            PCIE_AER_UNCOR_STATUS => self.uncor_status &= !value,
+            PCIE_AER_UNCOR_MASK => self.uncor_mask = value & PCIE_AER_UNC_MASK,
+            PCIE_AER_UNCOR_SEVER => self.uncor_severity = value & PCIE_AER_UNC_MASK,
+            PCIE_AER_COR_STATUS => self.cor_status &= !value,
+            PCIE_AER_COR_MASK => self.cor_mask = value & PCIE_AER_COR_MASK_BITS,
+            PCIE_AER_ROOT_COMMAND => {
+                let was_pending = self.interrupt_pending();
+                self.root_command = value & PCIE_AER_ROOT_CMD_MASK;
+                return !was_pending && self.interrupt_pending();
+            }
+            // The Error Source Identification register is read-only and keeps the last sources.
+            PCIE_AER_ROOT_STATUS => self.root_status &= !(value & PCIE_AER_ROOT_STATUS_MASK),
+            _ => (),
+        }
+        false
+    }
+
+    /// Logs an error of type `error` reported by the device with `requester_id`. Returns true if
+    /// the root port must signal its interrupt.
+    pub fn inject(&mut self, error: PcieAerErrorType, requester_id: u16) -> bool {
+        match error {
+            PcieAerErrorType::Correctable => {
+                if self.cor_mask & PCIE_AER_COR_RCVR != 0 {
+                    return false;
+                }
+                self.cor_status |= PCIE_AER_COR_RCVR;
+                if self.root_status & PCIE_AER_ROOT_COR_RCV != 0 {
+                    self.root_status |= PCIE_AER_ROOT_MULTI_COR_RCV;
+                } else {
+                    self.root_status |= PCIE_AER_ROOT_COR_RCV;
+                    self.error_source_id =
+                        (self.error_source_id & 0xFFFF_0000) | requester_id as u32;
+                }
+                self.root_command & PCIE_AER_ROOT_CMD_COR_EN != 0
+            }
+            PcieAerErrorType::NonFatal | PcieAerErrorType::Fatal => {
+                let status = match error {
+                    PcieAerErrorType::Fatal => PCIE_AER_UNC_DLP,
+                    _ => PCIE_AER_UNC_COMP_TIME,
+                };
+                if self.uncor_mask & status != 0 {
+                    return false;
+                }
+                // As on hardware, the severity is the one programmed for the error by the guest.
+                let fatal = self.uncor_severity & status != 0;
+                if self.uncor_status & !self.uncor_mask == 0 {
+                    self.first_error_pointer = status.trailing_zeros();
+                }
+                self.uncor_status |= status;
+                if self.root_status & PCIE_AER_ROOT_UNCOR_RCV != 0 {
+                    self.root_status |= PCIE_AER_ROOT_MULTI_UNCOR_RCV;
+                } else {
+                    self.root_status |= PCIE_AER_ROOT_UNCOR_RCV;
+                    if fatal {
+                        self.root_status |= PCIE_AER_ROOT_FIRST_FATAL;
+                    }
+                    self.error_source_id =
+                        (self.error_source_id & 0x0000_FFFF) | ((requester_id as u32) << 16);
+                }
+                if fatal {
+                    self.root_status |= PCIE_AER_ROOT_FATAL_RCV;
+                    self.root_command & PCIE_AER_ROOT_CMD_FATAL_EN != 0
+                } else {
+                    self.root_status |= PCIE_AER_ROOT_NONFATAL_RCV;
+                    self.root_command & PCIE_AER_ROOT_CMD_NONFATAL_EN != 0
+                }
+            }
+        }
+    }
+
+    fn interrupt_pending(&self) -> bool {
+        (self.root_command & PCIE_AER_ROOT_CMD_COR_EN != 0
+            && self.root_status & PCIE_AER_ROOT_COR_RCV != 0)
+            || (self.root_command & PCIE_AER_ROOT_CMD_NONFATAL_EN != 0
+                && self.root_status & PCIE_AER_ROOT_NONFATAL_RCV != 0)
+            || (self.root_command & PCIE_AER_ROOT_CMD_FATAL_EN != 0
+                && self.root_status & PCIE_AER_ROOT_FATAL_RCV != 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn correctable() {
+        let mut aer = PcieAerCap::new();
+        // Not signaled until the guest enables the interrupt.
+        assert!(!aer.inject(PcieAerErrorType::Correctable, 0x0100));
+        assert!(aer.write(PCIE_AER_ROOT_COMMAND, &0x7u32.to_le_bytes()));
+        assert_eq!(aer.read(PCIE_AER_COR_STATUS), PCIE_AER_COR_RCVR);
+        assert_eq!(aer.read(PCIE_AER_ROOT_STATUS), PCIE_AER_ROOT_COR_RCV);
+        assert_eq!(aer.read(PCIE_AER_ERR_SRC_ID), 0x0100);
+
+        assert!(aer.inject(PcieAerErrorType::Correctable, 0x0100));
+        assert_eq!(
+            aer.read(PCIE_AER_ROOT_STATUS),
+            PCIE_AER_ROOT_COR_RCV | PCIE_AER_ROOT_MULTI_COR_RCV
+        );
+
+        // The status registers are write-1-to-clear.
+        aer.write(PCIE_AER_COR_STATUS, &PCIE_AER_COR_RCVR.to_le_bytes());
+        aer.write(
+            PCIE_AER_ROOT_STATUS,
+            &PCIE_AER_ROOT_STATUS_MASK.to_le_bytes(),
+        );
+        assert_eq!(aer.read(PCIE_AER_COR_STATUS), 0);
+        assert_eq!(aer.read(PCIE_AER_ROOT_STATUS), 0);
+        assert_eq!(aer.read(PCIE_AER_ERR_SRC_ID), 0x0100);
+    }
+
+    #[test]
+    fn uncorrectable() {
+        let mut aer = PcieAerCap::new();
+        aer.write(PCIE_AER_ROOT_COMMAND, &0x7u32.to_le_bytes());
+
+        assert!(aer.inject(PcieAerErrorType::Fatal, 0x0208));
+        assert_eq!(aer.read(PCIE_AER_UNCOR_STATUS), PCIE_AER_UNC_DLP);
+        assert_eq!(aer.read(PCIE_AER_CAP_CONTROL), 4);
+        assert_eq!(
+            aer.read(PCIE_AER_ROOT_STATUS),
+            PCIE_AER_ROOT_UNCOR_RCV | PCIE_AER_ROOT_FIRST_FATAL | PCIE_AER_ROOT_FATAL_RCV
+        );
+        assert_eq!(aer.read(PCIE_AER_ERR_SRC_ID), 0x0208 << 16);
+
+        // The guest made completion timeouts fatal.
+        aer.write(
+            PCIE_AER_UNCOR_SEVER,
+            &(PCIE_AER_UNC_SEVER_DEFAULT | PCIE_AER_UNC_COMP_TIME).to_le_bytes(),
+        );
+        assert!(aer.inject(PcieAerErrorType::NonFatal, 0x0208));
+        assert_eq!(
+            aer.read(PCIE_AER_ROOT_STATUS),
+            PCIE_AER_ROOT_UNCOR_RCV
+                | PCIE_AER_ROOT_MULTI_UNCOR_RCV
+                | PCIE_AER_ROOT_FIRST_FATAL
+                | PCIE_AER_ROOT_FATAL_RCV
+        );
+
+        // Masked errors are neither logged nor signaled.
+        let mut aer = PcieAerCap::new();
+        aer.write(PCIE_AER_UNCOR_MASK, &PCIE_AER_UNC_COMP_TIME.to_le_bytes());
+        assert!(!aer.inject(PcieAerErrorType::NonFatal, 0x0208));
+        assert_eq!(aer.read(PCIE_AER_UNCOR_STATUS), 0);
+        assert_eq!(aer.read(PCIE_AER_ROOT_STATUS), 0);
+    }
+}
@@ -10,10 +10,14 @@ use once_cell::sync::Lazy;
 use resources::Alloc;
 use resources::SystemAllocator;
 use sync::Mutex;
+use vm_control::PcieAerErrorType;
 use zerocopy::FromBytes;
 
 use crate::pci::pci_configuration::PciCapabilityID;
 use crate::pci::pcie::pci_bridge::PciBridgeBusRange;
+use crate::pci::pcie::pcie_aer::PcieAerCap;
+use crate::pci::pcie::pcie_aer::PCIE_AER_CAP_LEN;
+use crate::pci::pcie::pcie_aer::PCIE_AER_CAP_OFFSET;
 use crate::pci::pcie::pcie_host::PcieHostPort;
 use crate::pci::pcie::*;
 use crate::pci::pm::PciDevicePower;
@@ -45,6 +49,10 @@ struct PcieRootCap {
     status: u32,
     pme_pending_requester_id: Option<u16>,
 
+    // Requester ID of the root port, reported as the source of the AER errors it logs.
+    requester_id: Option<u16>,
+    aer: PcieAerCap,
+
     msi_config: Option<Arc<Mutex<MsiConfig>>>,
 }
 
@@ -56,6 +64,8 @@ impl PcieRootCap {
             control: 0,
             status: 0,
             pme_pending_requester_id: None,
+            requester_id: None,
+            aer: PcieAerCap::new(),
             msi_config: None,
         }
     }
@@ -80,6 +90,42 @@ fn push_pcie_root_cap(root_cap: Arc<Mutex<PcieRootCap>>) {
     PCIE_ROOTS_CAP.lock().push(root_cap);
 }
 
+/// Injects a PCIe AER error of type `error` at the device with `requester_id`. The error is logged
+/// by the root port the device is behind, or by the root port itself if `requester_id` is the one
+/// of a root port, and signaled to the guest if it enabled the reporting of such errors.
+pub fn inject_aer_error(
+    requester_id: u16,
+    error: PcieAerErrorType,
+) -> std::result::Result<(), PciDeviceError> {
+    let root_port = PCIE_ROOTS_CAP
+        .lock()
+        .iter()
+        .find(|root_cap| root_cap.lock().requester_id == Some(requester_id))
+        .cloned();
+    let root_cap = match root_port {
+        Some(root_cap) => root_cap,
+        None => get_pcie_root_cap((requester_id >> 8) as u8)
+            .ok_or(PciDeviceError::PcieRootPortNotFound(requester_id))?,
+    };
+
+    let mut root_cap = root_cap.lock();
+    // The guest only gets the details of errors logged by devices with an AER capability, so the
+    // root port reports the errors as its own.
+    let source_id = root_cap
+        .requester_id
+        .ok_or(PciDeviceError::PcieRootPortNotFound(requester_id))?;
+    if root_cap.aer.inject(error, source_id) {
+        trigger_interrupt(&root_cap.msi_config);
+    }
+    Ok(())
+}
+
+/// Returns true if there are root ports, which all implement the AER capability. The guest can
+/// then be given native control of AER.
+pub fn has_pcie_aer_root_ports() -> bool {
+    !PCIE_ROOTS_CAP.lock().is_empty()
+}
+
 fn get_pcie_root_cap(bus_num: u8) -> Option<Arc<Mutex<PcieRootCap>>> {
     for root_cap in PCIE_ROOTS_CAP.lock().iter() {
         let root_cap_lock = root_cap.lock();
@@ -267,6 +313,11 @@ impl PciePort {
                 }
             }
         }
+        if self.is_root_port {
+            if let Some(address) = self.pci_address {
+                self.root_cap.lock().requester_id = Some(address.pme_requester_id());
+            }
+        }
         self.pci_address.ok_or(PciDeviceError::PciAllocationFailed)
     }
 
@@ -405,6 +456,9 @@ impl PciePort {
                 self.pm_config.read(data);
             }
         }
+        if let Some(offset) = self.aer_offset(reg_idx) {
+            *data = self.root_cap.lock().aer.read(offset);
+        }
         if let Some(host) = &self.pcie_host {
             host.read_config(reg_idx, data);
         }
@@ -433,11 +487,28 @@ impl PciePort {
                 }
             }
         }
+        if let Some(aer_offset) = self.aer_offset(reg_idx) {
+            let mut r = self.root_cap.lock();
+            if r.aer.write(aer_offset + offset as usize, data) {
+                trigger_interrupt(&r.msi_config);
+            }
+        }
         if let Some(host) = self.pcie_host.as_mut() {
             host.write_config(reg_idx, offset, data);
         }
     }
 
+    // Returns the offset in the AER capability of the register `reg_idx`, if it is one of them.
+    // Only root ports implement the capability.
+    fn aer_offset(&self, reg_idx: usize) -> Option<usize> {
+        let offset = (reg_idx * 4).checked_sub(PCIE_AER_CAP_OFFSET)?;
+        if self.is_root_port && offset < PCIE_AER_CAP_LEN {
+            Some(offset)
+        } else {
+            None
+        }
+    }
+
     pub fn set_capability_reg_idx(&mut self, id: PciCapabilityID, reg_idx: usize) {
         match id {
             PciCapabilityID::PciExpress => self.pcie_cap_reg_idx = Some(reg_idx),
@@ -551,3 +622,95 @@ impl PciePort {
         self.prepare_hotplug = true;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use resources::AddressRange;
+    use resources::SystemAllocatorConfig;
+
+    use super::*;
+
+    // Registers of the AER capability.
+    const AER_UNCOR_STATUS: usize = 0x04;
+    const AER_COR_STATUS: usize = 0x10;
+    const AER_ROOT_COMMAND: usize = 0x2C;
+    const AER_ROOT_STATUS: usize = 0x30;
+    const AER_ERR_SRC_ID: usize = 0x34;
+
+    fn read_aer(port: &PciePort, offset: usize) -> u32 {
+        let mut data = 0;
+        port.read_config((PCIE_AER_CAP_OFFSET + offset) / 4, &mut data);
+        data
+    }
+
+    fn write_aer(port: &mut PciePort, offset: usize, value: u32) {
+        port.write_config((PCIE_AER_CAP_OFFSET + offset) / 4, 0, &value.to_le_bytes());
+    }
+
+    #[test]
+    fn inject_aer_error_through_config_space() {
+        let mut allocator = SystemAllocator::new(
+            SystemAllocatorConfig {
+                io: None,
+                low_mmio: AddressRange {
+                    start: 0x2000_0000,
+                    end: 0x2fff_ffff,
+                },
+                high_mmio: AddressRange {
+                    start: 0x1_0000_0000,
+                    end: 0x1_0fff_ffff,
+                },
+                platform_mmio: None,
+                first_irq: 5,
+            },
+            None,
+            &[],
+        )
+        .unwrap();
+
+        // Root ports are global, so use a bus and an address that no other test uses.
+        let mut port = PciePort::new(0x3420, "pcie_rp".to_string(), 0, 0xe0, false, true);
+        port.preferred_address = Some(PciAddress {
+            bus: 0,
+            dev: 0x1d,
+            func: 7,
+        });
+        let requester_id = port
+            .allocate_address(&mut allocator)
+            .unwrap()
+            .pme_requester_id();
+        assert!(has_pcie_aer_root_ports());
+
+        // The capability is the first extended capability of the root port.
+        assert_eq!(read_aer(&port, 0) & 0xffff, 0x1);
+
+        write_aer(&mut port, AER_ROOT_COMMAND, 0x7);
+        assert_eq!(read_aer(&port, AER_ROOT_COMMAND), 0x7);
+
+        // Errors of the devices behind the root port are reported by the root port.
+        inject_aer_error(0xe008, PcieAerErrorType::NonFatal).unwrap();
+        assert_ne!(read_aer(&port, AER_UNCOR_STATUS), 0);
+        assert_eq!(read_aer(&port, AER_ERR_SRC_ID), (requester_id as u32) << 16);
+        inject_aer_error(requester_id, PcieAerErrorType::Correctable).unwrap();
+        assert_ne!(read_aer(&port, AER_COR_STATUS), 0);
+        assert_eq!(
+            read_aer(&port, AER_ERR_SRC_ID),
+            ((requester_id as u32) << 16) | requester_id as u32
+        );
+
+        // The guest clears the errors it handled.
+        write_aer(&mut port, AER_UNCOR_STATUS, 0xffff_ffff);
+        write_aer(&mut port, AER_COR_STATUS, 0xffff_ffff);
+        write_aer(&mut port, AER_ROOT_STATUS, 0x7f);
+        assert_eq!(read_aer(&port, AER_UNCOR_STATUS), 0);
+        assert_eq!(read_aer(&port, AER_COR_STATUS), 0);
+        assert_eq!(read_aer(&port, AER_ROOT_STATUS), 0);
+        assert_eq!(
+            read_aer(&port, AER_ERR_SRC_ID),
+            ((requester_id as u32) << 16) | requester_id as u32
+        );
+
+        // There is no root port for bus 0xef.
+        assert!(inject_aer_error(0xef00, PcieAerErrorType::Fatal).is_err());
+    }
+}
@@ -0,0 +1,995 @@
+// Copyright 2023 The ChromiumOS Authors
+// Use of this source code is governed by a BSD-style license that can be
+// found in the LICENSE file.
+
+//! A PCI device emulated by a vfio-user server running in another process.
+//!
+//! Config space and BAR accesses of the guest are forwarded to the server, except for the BAR
+//! registers and the MSI/MSI-X structures which are emulated here so that interrupts can be routed
+//! to irqfds. The whole guest memory is mapped for DMA by the server, so the device can't be put
+//! behind a virtual IOMMU.
+
+use std::path::Path;
+use std::time::Duration;
+
+use base::error;
+use base::warn;
+use base::AsRawDescriptor;
+use base::AsRawDescriptors;
+use base::Event;
+use base::EventWaitResult;
+use base::RawDescriptor;
+use base::Tube;
+use resources::Alloc;
+use resources::AllocOptions;
+use resources::SystemAllocator;
+use vfio_sys::*;
+use vm_memory::GuestMemory;
+use zerocopy::AsBytes;
+use zerocopy::FromBytes;
+
+use crate::pci::msi::MsiConfig;
+use crate::pci::msi::MsiStatus;
+use crate::pci::msi::PCI_MSI_FLAGS;
+use crate::pci::msi::PCI_MSI_FLAGS_64BIT;
+use crate::pci::msi::PCI_MSI_FLAGS_MASKBIT;
+use crate::pci::msi::PCI_MSI_NEXT_POINTER;
+use crate::pci::msix::MsixConfig;
+use crate::pci::msix::MsixStatus;
+use crate::pci::msix::BITS_PER_PBA_ENTRY;
+use crate::pci::msix::MSIX_PBA_ENTRIES_MODULO;
+use crate::pci::msix::MSIX_TABLE_ENTRIES_MODULO;
+use crate::pci::pci_device::BarRange;
+use crate::pci::pci_device::Error as PciDeviceError;
+use crate::pci::pci_device::PciDevice;
+use crate::pci::pci_device::PreferredIrq;
+use crate::pci::PciAddress;
+use crate::pci::PciBarConfiguration;
+use crate::pci::PciBarPrefetchable;
+use crate::pci::PciBarRegionType;
+use crate::pci::PciId;
+use crate::pci::PciInterruptPin;
+use crate::vfio_user::VfioUserClient;
+use crate::vfio_user::VfioUserError;
+use crate::IrqLevelEvent;
+use crate::Suspendable;
+
+const PCI_VENDOR_ID: u32 = 0x0;
+const PCI_DEVICE_ID: u32 = 0x2;
+const PCI_CAPABILITY_LIST: u32 = 0x34;
+const PCI_CAP_ID_MSI: u8 = 0x05;
+const PCI_CAP_ID_MSIX: u8 = 0x11;
+
+const PCI_BAR0_REG: usize = 4;
+const PCI_ROM_REG: usize = 12;
+const PCI_INTERRUPT_REG: usize = 15;
+const PCI_INTERRUPT_PIN: u32 = 0x3D;
+const NUM_BAR_REGS: usize = 6;
+// Flag bits of a memory BAR register.
+const PCI_BAR_MEM_FLAGS: u32 = 0xf;
+const PCI_BAR_IO_SPACE: u32 = 0x1;
+const PCI_BAR_MEM_64BIT: u32 = 0x4;
+const PCI_BAR_MEM_PREFETCH: u32 = 0x8;
+
+// Upper bound on the length of the capability list, in case the server returns a looping one.
+const MAX_CAPABILITIES: usize = 48;
+
+// MSI-X registers in MSI-X capability
+const PCI_MSIX_FLAGS: u32 = 0x02; // Message Control
+const PCI_MSIX_FLAGS_QSIZE: u16 = 0x07FF; // Table size
+const PCI_MSIX_TABLE: u32 = 0x04; // Table offset
+const PCI_MSIX_PBA: u32 = 0x08; // Pending bit Array offset
+const PCI_MSIX_BIR: u32 = 0x07; // BAR index
+const PCI_MSIX_OFFSET: u32 = 0xFFFFFFF8; // Offset into specified BAR
+
+struct MsiCap {
+    config: MsiConfig,
+    offset: u32,
+}
+
+struct MsixCap {
+    config: MsixConfig,
+    offset: u32,
+    table_bar: u32,
+    table_offset: u64,
+    table_size_bytes: u64,
+    pba_bar: u32,
+    pba_offset: u64,
+    pba_size_bytes: u64,
+    // Events given to the server for vectors that are masked or not routed yet. They are polled
+    // to set the pending bits of the vectors.
+    masked_evts: Vec<Event>,
+}
+
+impl MsixCap {
+    fn is_control_reg(&self, offset: u32, size: u32) -> bool {
+        let control_start = self.offset + PCI_MSIX_FLAGS;
+        offset < control_start + 2 && offset + size > control_start
+    }
+
+    fn is_table(&self, bar: u32, offset: u64) -> bool {
+        bar == self.table_bar
+            && offset >= self.table_offset
+            && offset < self.table_offset + self.table_size_bytes
+    }
+
+    fn is_pba(&self, bar: u32, offset: u64) -> bool {
+        bar == self.pba_bar
+            && offset >= self.pba_offset
+            && offset < self.pba_offset + self.pba_size_bytes
+    }
+
+    fn vector_masked(&self, index: usize) -> bool {
+        !self.config.enabled() || self.config.masked() || self.config.table_masked(index)
+    }
+
+    // Returns the event the server should signal for vector `index`.
+    fn vector_evt(&self, index: usize) -> &Event {
+        match self.config.get_irqfd(index) {
+            Some(irqfd) if !self.vector_masked(index) => irqfd,
+            _ => &self.masked_evts[index],
+        }
+    }
+
+    // Marks the vectors that were signaled while masked as pending.
+    fn sync_pending(&mut self) {
+        for (index, evt) in self.masked_evts.iter().enumerate() {
+            if let Ok(EventWaitResult::Signaled) = evt.wait_timeout(Duration::ZERO) {
+                self.config.trigger(index as u16);
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum IrqType {
+    Intx,
+    Msi,
+    Msix,
+}
+
+/// A PCI device implemented by a vfio-user server.
+pub struct VfioUserPciDevice {
+    client: VfioUserClient,
+    name: String,
+    preferred_address: Option<PciAddress>,
+    pci_address: Option<PciAddress>,
+    interrupt_evt: Option<IrqLevelEvent>,
+    // Interrupt line and pin assigned to the device, as in the low half of register 15.
+    interrupt_reg: Option<u16>,
+    irq_type: Option<IrqType>,
+    bars: Vec<PciBarConfiguration>,
+    bar_regs: [u32; NUM_BAR_REGS],
+    msi_cap: Option<MsiCap>,
+    msix_cap: Option<MsixCap>,
+}
+
+impl VfioUserPciDevice {
+    /// Connects to the vfio-user server listening at `path` and lets it access `mem` through DMA.
+    pub fn new(
+        path: &Path,
+        guest_address: Option<PciAddress>,
+        mem: &GuestMemory,
+        msi_tube: Tube,
+        msix_tube: Tube,
+    ) -> Result<Self, VfioUserError> {
+        let client = VfioUserClient::connect(path)?;
+        Self::with_client(
+            client,
+            path.display().to_string(),
+            guest_address,
+            mem,
+            msi_tube,
+            msix_tube,
+        )
+    }
+
+    fn with_client(
+        client: VfioUserClient,
+        name: String,
+        guest_address: Option<PciAddress>,
+        mem: &GuestMemory,
+        msi_tube: Tube,
+        msix_tube: Tube,
+    ) -> Result<Self, VfioUserError> {
+        mem.with_regions(|region| {
+            client.dma_map(
+                region.guest_addr.offset(),
+                region.size as u64,
+                region.shm,
+                region.shm_offset,
+            )
+        })?;
+
+        let mut device = VfioUserPciDevice {
+            client,
+            name,
+            preferred_address: guest_address,
+            pci_address: None,
+            interrupt_evt: None,
+            interrupt_reg: None,
+            irq_type: None,
+            bars: Vec::new(),
+            bar_regs: [0; NUM_BAR_REGS],
+            msi_cap: None,
+            msix_cap: None,
+        };
+        device.collect_bars();
+        device.find_capabilities(msi_tube, msix_tube)?;
+        Ok(device)
+    }
+
+    fn config_read<T: AsBytes + FromBytes + Default>(&self, offset: u32) -> T {
+        self.client
+            .region_read_obj(VFIO_PCI_CONFIG_REGION_INDEX, offset.into())
+            .unwrap_or_else(|e| {
+                error!(
+                    "{} failed to read config at {:#x}: {}",
+                    self.name, offset, e
+                );
+                T::default()
+            })
+    }
+
+    fn config_write(&self, offset: u64, data: &[u8]) {
+        if let Err(e) = self
+            .client
+            .region_write(VFIO_PCI_CONFIG_REGION_INDEX, offset, data)
+        {
+            error!(
+                "{} failed to write config at {:#x}: {}",
+                self.name, offset, e
+            );
+        }
+    }
+
+    // Finds the memory BARs from the regions of the device and the flags of its BAR registers.
+    fn collect_bars(&mut self) {
+        let mut index = VFIO_PCI_BAR0_REGION_INDEX;
+        while index <= VFIO_PCI_BAR5_REGION_INDEX {
+            let size = self.client.region_size(index);
+            let reg: u32 = self.config_read(0x10 + index * 4);
+            let is_64bit = reg & PCI_BAR_IO_SPACE == 0 && reg & PCI_BAR_MEM_64BIT != 0;
+            if size != 0 {
+                if reg & PCI_BAR_IO_SPACE != 0 {
+                    warn!("{} ignoring unsupported I/O BAR {}", self.name, index);
+                } else {
+                    let region_type = if is_64bit {
+                        PciBarRegionType::Memory64BitRegion
+                    } else {
+                        PciBarRegionType::Memory32BitRegion
+                    };
+                    let prefetch = if reg & PCI_BAR_MEM_PREFETCH != 0 {
+                        PciBarPrefetchable::Prefetchable
+                    } else {
+                        PciBarPrefetchable::NotPrefetchable
+                    };
+                    self.bars.push(PciBarConfiguration::new(
+                        index as usize,
+                        size.next_power_of_two(),
+                        region_type,
+                        prefetch,
+                    ));
+                    self.bar_regs[index as usize] = reg & PCI_BAR_MEM_FLAGS;
+                }
+            }
+            index += if is_64bit { 2 } else { 1 };
+        }
+    }
+
+    fn find_capabilities(&mut self, msi_tube: Tube, msix_tube: Tube) -> Result<(), VfioUserError> {
+        let vendor_id: u16 = self.config_read(PCI_VENDOR_ID);
+        let device_id: u16 = self.config_read(PCI_DEVICE_ID);
+        let pci_id: u32 = PciId::new(vendor_id, device_id).into();
+        let mut msi_tube = Some(msi_tube);
+        let mut msix_tube = Some(msix_tube);
+
+        let mut cap_next: u32 = self.config_read::<u8>(PCI_CAPABILITY_LIST).into();
+        for _ in 0..MAX_CAPABILITIES {
+            if cap_next == 0 {
+                break;
+            }
+            let cap_id: u8 = self.config_read(cap_next);
+            if cap_id == PCI_CAP_ID_MSI {
+                if let Some(tube) = msi_tube.take() {
+                    let msi_ctl: u16 = self.config_read(cap_next + PCI_MSI_FLAGS);
+                    self.msi_cap = Some(MsiCap {
+                        config: MsiConfig::new(
+                            msi_ctl & PCI_MSI_FLAGS_64BIT != 0,
+                            msi_ctl & PCI_MSI_FLAGS_MASKBIT != 0,
+                            tube,
+                            pci_id,
+                            self.name.clone(),
+                        ),
+                        offset: cap_next,
+                    });
+                }
+            } else if cap_id == PCI_CAP_ID_MSIX {
+                if let Some(tube) = msix_tube.take() {
+                    self.msix_cap = Some(self.msix_cap_at(cap_next, tube, pci_id)?);
+                }
+            }
+            cap_next = self
+                .config_read::<u8>(cap_next + PCI_MSI_NEXT_POINTER)
+                .into();
+        }
+        Ok(())
+    }
+
+    fn msix_cap_at(&self, offset: u32, tube: Tube, pci_id: u32) -> Result<MsixCap, VfioUserError> {
+        let msix_ctl: u16 = self.config_read(offset + PCI_MSIX_FLAGS);
+        let table: u32 = self.config_read(offset + PCI_MSIX_TABLE);
+        let pba: u32 = self.config_read(offset + PCI_MSIX_PBA);
+
+        let table_size = (msix_ctl & PCI_MSIX_FLAGS_QSIZE) as u64 + 1;
+        let table_size_bytes = table_size * MSIX_TABLE_ENTRIES_MODULO;
+        let pba_size_bytes = ((table_size + BITS_PER_PBA_ENTRY as u64 - 1)
+            / BITS_PER_PBA_ENTRY as u64)
+            * MSIX_PBA_ENTRIES_MODULO;
+        let masked_evts = (0..table_size)
+            .map(|_| Event::new().map_err(VfioUserError::CreateEvent))
+            .collect::<Result<_, _>>()?;
+
+        Ok(MsixCap {
+            config: MsixConfig::new(table_size as u16, tube, pci_id, self.name.clone()),
+            offset,
+            table_bar: table & PCI_MSIX_BIR,
+            table_offset: (table & PCI_MSIX_OFFSET) as u64,
+            table_size_bytes,
+            pba_bar: pba & PCI_MSIX_BIR,
+            pba_offset: (pba & PCI_MSIX_OFFSET) as u64,
+            pba_size_bytes,
+            masked_evts,
+        })
+    }
+
+    fn find_bar(&self, addr: u64) -> Option<PciBarConfiguration> {
+        self.bars
+            .iter()
+            .find(|bar| addr >= bar.address() && addr < bar.address() + bar.size())
+            .copied()
+    }
+
+    // Emulates a write to BAR register `reg`, keeping the bits that aren't part of the size of the
+    // BAR owning it at 0 so that the guest can size it.
+    fn write_bar_reg(&mut self, reg: usize, value: u32) {
+        for bar in self.bars.iter_mut() {
+            let size_mask = !(bar.size() - 1);
+            let index = bar.bar_index();
+            if index == reg {
+                self.bar_regs[reg] = (value & size_mask as u32 & !PCI_BAR_MEM_FLAGS)
+                    | (self.bar_regs[reg] & PCI_BAR_MEM_FLAGS);
+            } else if bar.is_64bit_memory() && index + 1 == reg {
+                self.bar_regs[reg] = value & (size_mask >> 32) as u32;
+            } else {
+                continue;
+            }
+            let mut addr = (self.bar_regs[index] & !PCI_BAR_MEM_FLAGS) as u64;
+            if bar.is_64bit_memory() {
+                addr |= (self.bar_regs[index + 1] as u64) << 32;
+            }
+            *bar = bar.set_address(addr);
+            return;
+        }
+    }
+
+    fn enable_intx(&mut self) {
+        if let Some(interrupt_evt) = &self.interrupt_evt {
+            if let Err(e) =
+                self.client
+                    .irq_enable(&[interrupt_evt.get_trigger()], VFIO_PCI_INTX_IRQ_INDEX, 0)
+            {
+                error!("{} Intx enable failed: {}", self.name, e);
+                return;
+            }
+            // Servers can signal INTx as an edge, so not being able to get resample events is fine.
+            if let Err(e) = self
+                .client
+                .resample_enable(interrupt_evt.get_resample(), VFIO_PCI_INTX_IRQ_INDEX)
+            {
+                warn!("{} resample enable failed: {}", self.name, e);
+            }
+            self.irq_type = Some(IrqType::Intx);
+        }
+    }
+
+    fn disable_irqs(&mut self) {
+        let index = match self.irq_type.take() {
+            Some(IrqType::Intx) => VFIO_PCI_INTX_IRQ_INDEX,
+            Some(IrqType::Msi) => VFIO_PCI_MSI_IRQ_INDEX,
+            Some(IrqType::Msix) => VFIO_PCI_MSIX_IRQ_INDEX,
+            None => return,
+        };
+        if let Err(e) = self.client.irq_disable(index) {
+            error!("{} failed to disable irq {}: {}", self.name, index, e);
+        }
+    }
+
+    fn enable_msi(&mut self) {
+        self.disable_irqs();
+        let irqfd = match self.msi_cap.as_ref().and_then(|cap| cap.config.get_irqfd()) {
+            Some(irqfd) => irqfd,
+            None => {
+                self.enable_intx();
+                return;
+            }
+        };
+        if let Err(e) = self.client.irq_enable(&[irqfd], VFIO_PCI_MSI_IRQ_INDEX, 0) {
+            error!("{} failed to enable msi: {}", self.name, e);
+            self.enable_intx();
+            return;
+        }
+        self.irq_type = Some(IrqType::Msi);
+    }
+
+    fn enable_msix(&mut self) {
+        self.disable_irqs();
+        if let Err(e) = self.msix_vectors_update() {
+            error!("{} failed to enable msix: {}", self.name, e);
+            self.enable_intx();
+            return;
+        }
+        self.irq_type = Some(IrqType::Msix);
+    }
+
+    fn msix_vectors_update(&self) -> Result<(), VfioUserError> {
+        if let Some(cap) = &self.msix_cap {
+            let evts: Vec<&Event> = (0..cap.masked_evts.len())
+                .map(|i| cap.vector_evt(i))
+                .collect();
+            self.client.irq_enable(&evts, VFIO_PCI_MSIX_IRQ_INDEX, 0)?;
+        }
+        Ok(())
+    }
+
+    fn msix_vector_update(&self, index: usize) {
+        if let Some(cap) = &self.msix_cap {
+            if let Err(e) = self.client.irq_enable(
+                &[cap.vector_evt(index)],
+                VFIO_PCI_MSIX_IRQ_INDEX,
+                index as u32,
+            ) {
+                error!(
+                    "{} failed to update msix vector {}: {}",
+                    self.name, index, e
+                );
+            }
+        }
+    }
+}
+
+impl PciDevice for VfioUserPciDevice {
+    fn debug_label(&self) -> String {
+        format!("vfio-user {} device", self.name)
+    }
+
+    fn preferred_address(&self) -> Option<PciAddress> {
+        self.preferred_address
+    }
+
+    fn allocate_address(
+        &mut self,
+        resources: &mut SystemAllocator,
+    ) -> Result<PciAddress, PciDeviceError> {
+        if self.pci_address.is_none() {
+            if let Some(address) = self.preferred_address {
+                if !resources.reserve_pci(
+                    Alloc::PciBar {
+                        bus: address.bus,
+                        dev: address.dev,
+                        func: address.func,
+                        bar: 0,
+                    },
+                    self.debug_label(),
+                ) {
+                    return Err(PciDeviceError::PciAllocationFailed);
+                }
+                self.pci_address = Some(address);
+            } else {
+                self.pci_address = match resources.allocate_pci(0, self.debug_label()) {
+                    Some(Alloc::PciBar {
+                        bus,
+                        dev,
+                        func,
+                        bar: _,
+                    }) => Some(PciAddress { bus, dev, func }),
+                    _ => None,
+                }
+            }
+        }
+        self.pci_address.ok_or(PciDeviceError::PciAllocationFailed)
+    }
+
+    fn keep_rds(&self) -> Vec<RawDescriptor> {
+        let mut rds = vec![self.client.as_raw_descriptor()];
+        if let Some(interrupt_evt) = &self.interrupt_evt {
+            rds.extend(interrupt_evt.as_raw_descriptors());
+        }
+        if let Some(msi_cap) = &self.msi_cap {
+            rds.push(msi_cap.config.get_msi_socket());
+        }
+        if let Some(msix_cap) = &self.msix_cap {
+            rds.push(msix_cap.config.as_raw_descriptor());
+            rds.extend(msix_cap.masked_evts.iter().map(|e| e.as_raw_descriptor()));
+        }
+        rds
+    }
+
+    fn preferred_irq(&self) -> PreferredIrq {
+        match self.config_read::<u8>(PCI_INTERRUPT_PIN) {
+            1..=4 => PreferredIrq::Any,
+            _ => PreferredIrq::None,
+        }
+    }
+
+    fn assign_irq(&mut self, irq_evt: IrqLevelEvent, pin: PciInterruptPin, irq_num: u32) {
+        self.interrupt_evt = Some(irq_evt);
+        self.interrupt_reg = Some(((pin.to_mask() as u16 + 1) << 8) | irq_num as u8 as u16);
+        self.enable_intx();
+    }
+
+    fn allocate_io_bars(
+        &mut self,
+        resources: &mut SystemAllocator,
+    ) -> Result<Vec<BarRange>, PciDeviceError> {
+        let address = self
+            .pci_address
+            .expect("allocate_address must be called prior to allocate_io_bars");
+        let mut ranges = Vec::new();
+        for bar in self.bars.clone() {
+            let size = bar.size();
+            let bar_addr = resources
+                .allocate_mmio(
+                    size,
+                    Alloc::PciBar {
+                        bus: address.bus,
+                        dev: address.dev,
+                        func: address.func,
+                        bar: bar.bar_index() as u8,
+                    },
+                    "vfio_user_bar".to_string(),
+                    AllocOptions::new()
+                        .prefetchable(bar.is_prefetchable())
+                        .max_address(if bar.is_64bit_memory() {
+                            u64::MAX
+                        } else {
+                            u32::MAX.into()
+                        })
+                        .align(size),
+                )
+                .map_err(|e| PciDeviceError::IoAllocationFailed(size, e))?;
+            ranges.push(BarRange {
+                addr: bar_addr,
+                size,
+                prefetchable: bar.is_prefetchable(),
+            });
+            let reg = bar.bar_index();
+            self.write_bar_reg(reg, bar_addr as u32);
+            if bar.is_64bit_memory() {
+                self.write_bar_reg(reg + 1, (bar_addr >> 32) as u32);
+            }
+        }
+        Ok(ranges)
+    }
+
+    fn get_bar_configuration(&self, bar_num: usize) -> Option<PciBarConfiguration> {
+        self.bars
+            .iter()
+            .find(|bar| bar.bar_index() == bar_num)
+            .copied()
+    }
+
+    fn read_config_register(&self, reg_idx: usize) -> u32 {
+        if (PCI_BAR0_REG..PCI_BAR0_REG + NUM_BAR_REGS).contains(&reg_idx) {
+            return self.bar_regs[reg_idx - PCI_BAR0_REG];
+        }
+        // Expansion ROMs aren't supported.
+        if reg_idx == PCI_ROM_REG {
+            return 0;
+        }
+
+        let reg = (reg_idx * 4) as u32;
+        if reg as u64 + 4 > self.client.region_size(VFIO_PCI_CONFIG_REGION_INDEX) {
+            return 0;
+        }
+        let mut config: u32 = self.config_read(reg);
+
+        if reg_idx == PCI_INTERRUPT_REG {
+            if let Some(interrupt_reg) = self.interrupt_reg {
+                config = (config & 0xffff_0000) | interrupt_reg as u32;
+            }
+        } else if let Some(msix_cap) = self.msix_cap.as_ref().filter(|c| c.is_control_reg(reg, 4)) {
+            config = msix_cap.config.read_msix_capability(config);
+        } else if let Some(msi_cap) = &self.msi_cap {
+            if msi_cap.config.is_msi_reg(msi_cap.offset, reg as u64, 4) {
+                config = msi_cap
+                    .config
+                    .read_msi_capability(reg - msi_cap.offset, config);
+            }
+        }
+        config
+    }
+
+    fn write_config_register(&mut self, reg_idx: usize, offset: u64, data: &[u8]) {
+        if (PCI_BAR0_REG..PCI_BAR0_REG + NUM_BAR_REGS).contains(&reg_idx) {
+            let reg = reg_idx - PCI_BAR0_REG;
+            let mut value = self.bar_regs[reg].to_le_bytes();
+            if let Some(bytes) = value.get_mut(offset as usize..offset as usize + data.len()) {
+                bytes.copy_from_slice(data);
+            }
+            self.write_bar_reg(reg, u32::from_le_bytes(value));
+            return;
+        }
+        if reg_idx == PCI_ROM_REG {
+            return;
+        }
+
+        let start = (reg_idx * 4) as u64 + offset;
+
+        let mut msi_status = MsiStatus::NothingToDo;
+        if let Some(msi_cap) = self.msi_cap.as_mut() {
+            if msi_cap.config.is_msi_reg(msi_cap.offset, start, data.len()) {
+                msi_status = msi_cap
+                    .config
+                    .write_msi_capability(start as u32 - msi_cap.offset, data);
+            }
+        }
+        match msi_status {
+            MsiStatus::Enabled => self.enable_msi(),
+            MsiStatus::Disabled => {
+                self.disable_irqs();
+                self.enable_intx();
+            }
+            MsiStatus::NothingToDo => (),
+        }
+
+        let mut msix_change = None;
+        if let Some(msix_cap) = self.msix_cap.as_mut() {
+            if msix_cap.is_control_reg(start as u32, data.len() as u32) {
+                msix_cap.sync_pending();
+                let old_enabled = msix_cap.config.enabled();
+                let old_masked = msix_cap.config.masked();
+                msix_cap
+                    .config
+                    .write_msix_capability(PCI_MSIX_FLAGS.into(), data);
+                msix_change = Some((
+                    old_enabled,
+                    old_masked,
+                    msix_cap.config.enabled(),
+                    msix_cap.config.masked(),
+                ));
+            }
+        }
+        match msix_change {
+            Some((false, _, true, _)) => self.enable_msix(),
+            Some((true, _, false, _)) => {
+                self.disable_irqs();
+                self.enable_intx();
+            }
+            Some((_, old_masked, true, new_masked)) if old_masked != new_masked => {
+                if let Err(e) = self.msix_vectors_update() {
+                    error!("{} failed to update msix vectors: {}", self.name, e);
+                }
+            }
+            _ => (),
+        }
+
+        self.config_write(start, data);
+    }
+
+    fn read_bar(&mut self, addr: u64, data: &mut [u8]) {
+        if let Some(bar) = self.find_bar(addr) {
+            let offset = addr - bar.address();
+            let index = bar.bar_index() as u32;
+            if let Some(msix_cap) = self.msix_cap.as_mut() {
+                if msix_cap.is_table(index, offset) {
+                    msix_cap
+                        .config
+                        .read_msix_table(offset - msix_cap.table_offset, data);
+                    return;
+                } else if msix_cap.is_pba(index, offset) {
+                    msix_cap.sync_pending();
+                    msix_cap
+                        .config
+                        .read_pba_entries(offset - msix_cap.pba_offset, data);
+                    return;
+                }
+            }
+            if let Err(e) = self.client.region_read(index, offset, data) {
+                error!("{} failed to read bar {}: {}", self.name, index, e);
+            }
+        }
+    }
+
+    fn write_bar(&mut self, addr: u64, data: &[u8]) {
+        if let Some(bar) = self.find_bar(addr) {
+            let offset = addr - bar.address();
+            let index = bar.bar_index() as u32;
+            if let Some(msix_cap) = self.msix_cap.as_mut() {
+                if msix_cap.is_table(index, offset) {
+                    msix_cap.sync_pending();
+                    let status = msix_cap
+                        .config
+                        .write_msix_table(offset - msix_cap.table_offset, data);
+                    if let MsixStatus::EntryChanged(vector) = status {
+                        self.msix_vector_update(vector);
+                    }
+                    return;
+                } else if msix_cap.is_pba(index, offset) {
+                    msix_cap
+                        .config
+                        .write_pba_entries(offset - msix_cap.pba_offset, data);
+                    return;
+                }
+            }
+            if let Err(e) = self.client.region_write(index, offset, data) {
+                error!("{} failed to write bar {}: {}", self.name, index, e);
+            }
+        }
+    }
+
+    fn destroy_device(&mut self) {
+        self.disable_irqs();
+        if let Some(msi_cap) = self.msi_cap.as_mut() {
+            msi_cap.config.destroy();
+        }
+        if let Some(msix_cap) = self.msix_cap.as_mut() {
+            msix_cap.config.destroy();
+        }
+    }
+}
+
+impl Suspendable for VfioUserPciDevice {
+    fn sleep(&mut self) -> anyhow::Result<()> {
+        // There are no workers to sleep/wake.
+        Ok(())
+    }
+
+    fn wake(&mut self) -> anyhow::Result<()> {
+        // There are no workers to sleep/wake.
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::os::unix::net::UnixStream;
+    use std::sync::mpsc;
+    use std::thread;
+
+    use vm_control::VmIrqRequest;
+    use vm_control::VmIrqResponse;
+    use vm_memory::GuestAddress;
+
+    use super::*;
+    use crate::vfio_user::test_server;
+
+    const BAR0_ADDR: u64 = 0xe000_0000;
+    const BAR1_ADDR: u64 = 0x1_0000_0000;
+
+    // Answers the irq requests of the device on `tube`, sending the irqfds it allocates to
+    // `irqfds`.
+    fn irqchip(tube: Tube, irqfds: mpsc::Sender<Event>) {
+        let mut gsi = 32;
+        while let Ok(request) = tube.recv::<VmIrqRequest>() {
+            let response = match request {
+                VmIrqRequest::AllocateOneMsi { irqfd, .. } => {
+                    let _ = irqfds.send(irqfd);
+                    gsi += 1;
+                    VmIrqResponse::AllocateOneMsi { gsi }
+                }
+                _ => VmIrqResponse::Ok,
+            };
+            tube.send(&response).unwrap();
+        }
+    }
+
+    struct TestDevice {
+        device: VfioUserPciDevice,
+        msi_irqfds: mpsc::Receiver<Event>,
+        msix_irqfds: mpsc::Receiver<Event>,
+    }
+
+    fn create_device() -> TestDevice {
+        let (client, server) = UnixStream::pair().unwrap();
+        thread::spawn(move || test_server::run(server));
+        let client = VfioUserClient::new(client).unwrap();
+        let mem = GuestMemory::new(&[(GuestAddress(0), 0x10000)]).unwrap();
+
+        let (msi_tube, msi_irqchip_tube) = Tube::pair().unwrap();
+        let (msix_tube, msix_irqchip_tube) = Tube::pair().unwrap();
+        let (msi_sender, msi_irqfds) = mpsc::channel();
+        let (msix_sender, msix_irqfds) = mpsc::channel();
+        thread::spawn(move || irqchip(msi_irqchip_tube, msi_sender));
+        thread::spawn(move || irqchip(msix_irqchip_tube, msix_sender));
+
+        let device = VfioUserPciDevice::with_client(
+            client,
+            "test".to_string(),
+            None,
+            &mem,
+            msi_tube,
+            msix_tube,
+        )
+        .unwrap();
+        TestDevice {
+            device,
+            msi_irqfds,
+            msix_irqfds,
+        }
+    }
+
+    fn write_config<T: AsBytes>(device: &mut VfioUserPciDevice, offset: u32, value: T) {
+        device.write_config_register((offset / 4) as usize, (offset % 4).into(), value.as_bytes());
+    }
+
+    // Places BAR0 and BAR1 at `BAR0_ADDR` and `BAR1_ADDR`.
+    fn map_bars(device: &mut VfioUserPciDevice) {
+        write_config(device, 0x10, BAR0_ADDR as u32);
+        write_config(device, 0x14, BAR1_ADDR as u32);
+        write_config(device, 0x18, (BAR1_ADDR >> 32) as u32);
+    }
+
+    // Makes the test server signal vector `vector` of interrupt type `index`.
+    fn trigger(device: &mut VfioUserPciDevice, index: u32, vector: u32) {
+        device.write_bar(
+            BAR0_ADDR + test_server::REG_IRQ,
+            &((index << 16) | vector).to_le_bytes(),
+        );
+    }
+
+    fn signaled(evt: &Event) -> bool {
+        evt.wait_timeout(Duration::from_secs(5)).unwrap() == EventWaitResult::Signaled
+    }
+
+    fn not_signaled(evt: &Event) -> bool {
+        evt.wait_timeout(Duration::from_millis(1)).unwrap() == EventWaitResult::TimedOut
+    }
+
+    #[test]
+    fn bar_sizing() {
+        let TestDevice { mut device, .. } = create_device();
+        assert_eq!(device.read_config_register(PCI_BAR0_REG), 0);
+        assert_eq!(device.read_config_register(PCI_BAR0_REG + 1), 0x4);
+
+        // The bits below the size of a BAR stay 0, the flags are kept.
+        for reg in 0..NUM_BAR_REGS {
+            write_config(&mut device, 0x10 + reg as u32 * 4, u32::MAX);
+        }
+        assert_eq!(
+            device.read_config_register(PCI_BAR0_REG),
+            !(test_server::BAR0_SIZE as u32 - 1)
+        );
+        assert_eq!(
+            device.read_config_register(PCI_BAR0_REG + 1),
+            !(test_server::BAR1_SIZE as u32 - 1) | 0x4
+        );
+        assert_eq!(device.read_config_register(PCI_BAR0_REG + 2), u32::MAX);
+        // The device has no other BAR.
+        for reg in 3..NUM_BAR_REGS {
+            assert_eq!(device.read_config_register(PCI_BAR0_REG + reg), 0);
+        }
+
+        map_bars(&mut device);
+        assert_eq!(device.read_config_register(PCI_BAR0_REG), BAR0_ADDR as u32);
+        assert_eq!(
+            device.get_bar_configuration(0).unwrap().address(),
+            BAR0_ADDR
+        );
+        assert_eq!(
+            device.get_bar_configuration(1).unwrap().address(),
+            BAR1_ADDR
+        );
+
+        // BAR accesses at the new addresses reach the server.
+        device.write_bar(
+            BAR0_ADDR + test_server::REG_SCRATCH,
+            &0x1234u32.to_le_bytes(),
+        );
+        let mut data = [0u8; 4];
+        device.read_bar(BAR0_ADDR + test_server::REG_SCRATCH, &mut data);
+        assert_eq!(u32::from_le_bytes(data), 0x1234);
+    }
+
+    #[test]
+    fn config_register() {
+        let TestDevice { mut device, .. } = create_device();
+        assert_eq!(
+            device.read_config_register(0),
+            (test_server::DEVICE_ID as u32) << 16 | test_server::VENDOR_ID as u32
+        );
+        // Expansion ROM and registers past the end of the config region read as 0.
+        assert_eq!(device.read_config_register(PCI_ROM_REG), 0);
+        assert_eq!(
+            device.read_config_register(test_server::CONFIG_SIZE as usize / 4),
+            0
+        );
+
+        // The interrupt line and pin assigned to the device are reported.
+        assert_eq!(
+            device.read_config_register(PCI_INTERRUPT_REG) & 0xffff,
+            0x0100
+        );
+        device.assign_irq(IrqLevelEvent::new().unwrap(), PciInterruptPin::IntA, 5);
+        assert_eq!(
+            device.read_config_register(PCI_INTERRUPT_REG) & 0xffff,
+            0x0105
+        );
+
+        // Other registers are forwarded to the server.
+        write_config(&mut device, 0x80, 0xcafe_u32);
+        assert_eq!(device.read_config_register(0x80 / 4), 0xcafe);
+    }
+
+    #[test]
+    fn msi() {
+        let TestDevice {
+            mut device,
+            msi_irqfds,
+            ..
+        } = create_device();
+        map_bars(&mut device);
+        let offset = test_server::MSI_CAP_OFFSET;
+        let reg = offset as usize / 4;
+        assert_eq!(device.read_config_register(reg) >> 16, 0);
+
+        write_config(&mut device, offset + 4, 0xfee0_0000u32);
+        write_config(&mut device, offset + 8, 0x30u16);
+        write_config(&mut device, offset + PCI_MSI_FLAGS, 1u16);
+        assert_eq!(device.read_config_register(reg) >> 16, 1);
+        let irqfd = msi_irqfds.recv().unwrap();
+
+        trigger(&mut device, VFIO_PCI_MSI_IRQ_INDEX, 0);
+        assert!(signaled(&irqfd));
+
+        // Disabling MSI takes the interrupt back from the server.
+        write_config(&mut device, offset + PCI_MSI_FLAGS, 0u16);
+        assert_eq!(device.read_config_register(reg) >> 16, 0);
+        trigger(&mut device, VFIO_PCI_MSI_IRQ_INDEX, 0);
+        assert!(not_signaled(&irqfd));
+    }
+
+    #[test]
+    fn msix() {
+        let TestDevice {
+            mut device,
+            msix_irqfds,
+            ..
+        } = create_device();
+        map_bars(&mut device);
+        let offset = test_server::MSIX_CAP_OFFSET;
+        let reg = offset as usize / 4;
+        let vector = 1;
+        let entry = BAR1_ADDR + vector as u64 * MSIX_TABLE_ENTRIES_MODULO;
+        let pba = BAR1_ADDR + test_server::MSIX_PBA_OFFSET;
+        let read_pba = |device: &mut VfioUserPciDevice| {
+            let mut data = [0u8; 8];
+            device.read_bar(pba, &mut data);
+            u64::from_le_bytes(data)
+        };
+
+        write_config(&mut device, offset + PCI_MSIX_FLAGS, 0x8000u16);
+        assert_eq!((device.read_config_register(reg) >> 16) & 0x8000, 0x8000);
+
+        // The vector is masked: the server signals an event of the device, which sets the pending
+        // bit of the vector.
+        trigger(&mut device, VFIO_PCI_MSIX_IRQ_INDEX, vector);
+        assert_eq!(read_pba(&mut device), 1 << vector);
+
+        // Once unmasked, the server signals the irqfd of the vector.
+        device.write_bar(entry, &0xfee0_0000u32.to_le_bytes());
+        device.write_bar(entry + 8, &0x31u32.to_le_bytes());
+        device.write_bar(entry + 12, &0u32.to_le_bytes());
+        let irqfd = msix_irqfds.recv().unwrap();
+        trigger(&mut device, VFIO_PCI_MSIX_IRQ_INDEX, vector);
+        assert!(signaled(&irqfd));
+
+        // Masking it again makes interrupts pending, which are delivered when it is unmasked.
+        device.write_bar(entry + 12, &1u32.to_le_bytes());
+        trigger(&mut device, VFIO_PCI_MSIX_IRQ_INDEX, vector);
+        assert!(not_signaled(&irqfd));
+        device.write_bar(entry + 12, &0u32.to_le_bytes());
+        assert!(signaled(&irqfd));
+        assert_eq!(read_pba(&mut device), 0);
+    }
+}
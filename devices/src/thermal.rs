@@ -0,0 +1,589 @@
+// Copyright 2023 The ChromiumOS Authors
+// Use of this source code is governed by a BSD-style license that can be
+// found in the LICENSE file.
+
+// Emulated ACPI thermal zone and processor performance states. `ThermalState` holds the
+// temperature, the trip points and the frequency limit set by the host, and is shared by the VMM
+// control loop and `ThermalController`, which exposes them to the guest through I/O ports. The
+// thermal zone follows ACPI specification section 11 (_TMP, _PSV, _CRT and the passive cooling
+// objects), and the processors get the performance control objects of section 8.4.5 (_PCT, _PSS,
+// _PPC and _PSD). The controller's GPE notifies the guest when the host changes the state.
+
+use std::sync::Arc;
+
+use acpi_tables::aml;
+use acpi_tables::aml::Aml;
+use base::warn;
+use remain::sorted;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_keyvalue::FromKeyValues;
+use sync::Mutex;
+use thiserror::Error;
+
+use crate::cpu_hotplug::cpu_device_name;
+use crate::pci::CrosvmDeviceId;
+use crate::BusAccessInfo;
+use crate::BusDevice;
+use crate::DeviceId;
+use crate::Suspendable;
+
+#[sorted]
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum ThermalError {
+    #[error("frequencies must be non-zero and in decreasing order")]
+    InvalidFrequencies,
+    #[error("invalid temperature {0}°C")]
+    InvalidTemperature(i32),
+    #[error("passive trip point {passive}°C must be below critical trip point {critical}°C")]
+    InvalidTripPoints { passive: i32, critical: i32 },
+    #[error("no performance states")]
+    NoPerformanceStates,
+}
+
+pub type Result<T> = std::result::Result<T, ThermalError>;
+
+fn thermal_default_temperature() -> i32 {
+    40
+}
+
+fn thermal_default_passive() -> i32 {
+    90
+}
+
+fn thermal_default_critical() -> i32 {
+    105
+}
+
+/// Initial state of the emulated thermal zone.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, FromKeyValues)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+pub struct ThermalConfig {
+    /// Temperature of the thermal zone, in degrees Celsius.
+    #[serde(default = "thermal_default_temperature")]
+    pub temperature: i32,
+    /// Temperature above which the guest should throttle the processors, in degrees Celsius.
+    #[serde(default = "thermal_default_passive")]
+    pub passive: i32,
+    /// Temperature above which the guest should shut down, in degrees Celsius.
+    #[serde(default = "thermal_default_critical")]
+    pub critical: i32,
+    /// Frequencies of the processor performance states in MHz, from the fastest. Without them,
+    /// the processors don't report performance states and passive cooling is unavailable.
+    #[serde(default)]
+    pub frequencies: Vec<u32>,
+}
+
+impl Default for ThermalConfig {
+    fn default() -> Self {
+        ThermalConfig {
+            temperature: thermal_default_temperature(),
+            passive: thermal_default_passive(),
+            critical: thermal_default_critical(),
+            frequencies: Vec::new(),
+        }
+    }
+}
+
+/// Converts `celsius` to tenths of Kelvin, the unit of the ACPI thermal objects.
+fn deci_kelvin(celsius: i32) -> Result<u32> {
+    celsius
+        .checked_mul(10)
+        .and_then(|t| t.checked_add(2732))
+        .and_then(|t| u32::try_from(t).ok())
+        .ok_or(ThermalError::InvalidTemperature(celsius))
+}
+
+/// Pending notification bits, read and cleared by the guest from the EVT register.
+const THERMAL_EVT_TEMPERATURE: u32 = 1 << 0;
+const THERMAL_EVT_TRIP_POINTS: u32 = 1 << 1;
+const THERMAL_EVT_PERFORMANCE: u32 = 1 << 2;
+
+/// State of the thermal zone and the processor performance states of the VM.
+pub struct ThermalState {
+    // Temperatures in tenths of Kelvin.
+    temperature: u32,
+    passive: u32,
+    critical: u32,
+    frequencies: Vec<u32>,
+    vcpu_count: usize,
+    // Index of the fastest performance state the guest may use.
+    ppc: u32,
+    // Notifications not yet delivered to the guest.
+    events: u32,
+    gpe: Option<u32>,
+}
+
+impl ThermalState {
+    pub fn new(config: &ThermalConfig, vcpu_count: usize) -> Result<Self> {
+        if config.frequencies.contains(&0) || config.frequencies.windows(2).any(|f| f[0] <= f[1]) {
+            return Err(ThermalError::InvalidFrequencies);
+        }
+        let mut state = ThermalState {
+            temperature: deci_kelvin(config.temperature)?,
+            passive: 0,
+            critical: 0,
+            frequencies: config.frequencies.clone(),
+            vcpu_count,
+            ppc: 0,
+            events: 0,
+            gpe: None,
+        };
+        state.set_trip_points(config.passive, config.critical)?;
+        state.events = 0;
+        Ok(state)
+    }
+
+    /// Returns the GPE to raise to notify the guest of a change, if the guest is notified.
+    pub fn gpe(&self) -> Option<u32> {
+        self.gpe
+    }
+
+    pub fn set_gpe(&mut self, gpe: u32) {
+        self.gpe = Some(gpe);
+    }
+
+    /// Returns whether the guest has changes to be notified of.
+    pub fn has_pending_events(&self) -> bool {
+        self.events != 0
+    }
+
+    pub fn set_temperature(&mut self, celsius: i32) -> Result<()> {
+        let temperature = deci_kelvin(celsius)?;
+        if temperature != self.temperature {
+            self.temperature = temperature;
+            self.events |= THERMAL_EVT_TEMPERATURE;
+        }
+        Ok(())
+    }
+
+    pub fn set_trip_points(&mut self, passive: i32, critical: i32) -> Result<()> {
+        if passive >= critical {
+            return Err(ThermalError::InvalidTripPoints { passive, critical });
+        }
+        let (passive, critical) = (deci_kelvin(passive)?, deci_kelvin(critical)?);
+        if (passive, critical) != (self.passive, self.critical) {
+            self.passive = passive;
+            self.critical = critical;
+            self.events |= THERMAL_EVT_TRIP_POINTS;
+        }
+        Ok(())
+    }
+
+    /// Limits the processors to the performance states running at `mhz` at most, or to the
+    /// slowest one if they are all faster. Returns the frequency of the fastest state allowed.
+    pub fn set_frequency_limit(&mut self, mhz: u32) -> Result<u32> {
+        let slowest = self
+            .frequencies
+            .len()
+            .checked_sub(1)
+            .ok_or(ThermalError::NoPerformanceStates)?;
+        let ppc = self
+            .frequencies
+            .iter()
+            .position(|f| *f <= mhz)
+            .unwrap_or(slowest);
+        if ppc as u32 != self.ppc {
+            self.ppc = ppc as u32;
+            self.events |= THERMAL_EVT_PERFORMANCE;
+        }
+        Ok(self.frequencies[ppc])
+    }
+}
+
+pub const THERMAL_IO_SIZE: u64 = 0x20;
+
+/// Offsets of the registers, read by the ACPI methods.
+const THERMAL_TMP: u64 = 0x0;
+const THERMAL_PSV: u64 = 0x4;
+const THERMAL_CRT: u64 = 0x8;
+const THERMAL_PPC: u64 = 0xc;
+const THERMAL_EVT: u64 = 0x10;
+/// Offsets of the performance control and status registers described by _PCT.
+const THERMAL_PERF_CTL: u64 = 0x14;
+const THERMAL_PERF_STS: u64 = 0x18;
+
+/// Latency of the performance state transitions reported in _PSS, in microseconds.
+const PSTATE_LATENCY_US: u32 = 10;
+
+/// I/O registers and ACPI description of the thermal zone and of the processor performance
+/// states.
+///
+/// The guest reads the temperature and the trip points in tenths of Kelvin, and the index of the
+/// fastest allowed performance state from PPC. Reading EVT returns and clears the pending
+/// notifications. A performance state is selected by writing its index to PERF_CTL, and PERF_STS
+/// reports the state in effect once limited by PPC. All the processors share one performance
+/// domain.
+pub struct ThermalController {
+    state: Arc<Mutex<ThermalState>>,
+    io_base: u64,
+    gpe_nr: u32,
+    // Whether the processor devices are declared by the vCPU hotplug controller.
+    processors_declared: bool,
+    perf_control: u32,
+}
+
+impl ThermalController {
+    pub fn new(
+        state: Arc<Mutex<ThermalState>>,
+        io_base: u64,
+        gpe_nr: u32,
+        processors_declared: bool,
+    ) -> Self {
+        ThermalController {
+            state,
+            io_base,
+            gpe_nr,
+            processors_declared,
+            perf_control: 0,
+        }
+    }
+
+    fn perf_status(&self) -> u32 {
+        let state = self.state.lock();
+        let slowest = state.frequencies.len().saturating_sub(1) as u32;
+        self.perf_control.max(state.ppc).min(slowest)
+    }
+}
+
+impl BusDevice for ThermalController {
+    fn device_id(&self) -> DeviceId {
+        CrosvmDeviceId::Thermal.into()
+    }
+
+    fn debug_label(&self) -> String {
+        "ThermalController".to_owned()
+    }
+
+    fn read(&mut self, info: BusAccessInfo, data: &mut [u8]) {
+        if data.len() != std::mem::size_of::<u32>() {
+            warn!(
+                "{}: unsupported read length {}, only support 4bytes read",
+                self.debug_label(),
+                data.len()
+            );
+            return;
+        }
+
+        let val = match info.offset {
+            THERMAL_TMP => self.state.lock().temperature,
+            THERMAL_PSV => self.state.lock().passive,
+            THERMAL_CRT => self.state.lock().critical,
+            THERMAL_PPC => self.state.lock().ppc,
+            THERMAL_EVT => std::mem::replace(&mut self.state.lock().events, 0),
+            THERMAL_PERF_CTL => self.perf_control,
+            THERMAL_PERF_STS => self.perf_status(),
+            _ => {
+                warn!("{}: unsupported read address {}", self.debug_label(), info);
+                return;
+            }
+        };
+        data.copy_from_slice(&val.to_le_bytes());
+    }
+
+    fn write(&mut self, info: BusAccessInfo, data: &[u8]) {
+        match (info.offset, data.len()) {
+            (THERMAL_PERF_CTL, 4) => {
+                // Unwrap is safe: the length of data is matched above.
+                self.perf_control = u32::from_le_bytes(data.try_into().unwrap());
+            }
+            _ => warn!(
+                "{}: unsupported write of {} bytes at {}",
+                self.debug_label(),
+                data.len(),
+                info
+            ),
+        }
+    }
+}
+
+/// Path of the ACPI processor device of vCPU `cpu`.
+fn cpu_path(cpu: usize) -> String {
+    format!("\\_SB_.CPUS.{}", cpu_device_name(cpu))
+}
+
+impl Aml for ThermalController {
+    fn to_aml_bytes(&self, bytes: &mut Vec<u8>) {
+        let (frequencies, vcpu_count) = {
+            let state = self.state.lock();
+            (state.frequencies.clone(), state.vcpu_count)
+        };
+        let io_size = THERMAL_IO_SIZE as u32;
+        let (tmp, psv, crt) = (
+            aml::Path::new("TTMP"),
+            aml::Path::new("TPSV"),
+            aml::Path::new("TCRT"),
+        );
+        let (tmp, psv, crt) = (
+            aml::Return::new(&tmp),
+            aml::Return::new(&psv),
+            aml::Return::new(&crt),
+        );
+        let tmp = aml::Method::new("_TMP".into(), 0, false, vec![&tmp]);
+        let psv = aml::Method::new("_PSV".into(), 0, false, vec![&psv]);
+        let crt = aml::Method::new("_CRT".into(), 0, false, vec![&crt]);
+        let mut zone_children: Vec<&dyn Aml> = Vec::new();
+        let region = aml::OpRegion::new(
+            "TREG".into(),
+            aml::OpRegionSpace::SystemIO,
+            &self.io_base,
+            &io_size,
+        );
+        let fields = aml::Field::new(
+            "TREG".into(),
+            aml::FieldAccessType::DWord,
+            aml::FieldLockRule::NoLock,
+            aml::FieldUpdateRule::Preserve,
+            vec![
+                aml::FieldEntry::Named(*b"TTMP", 32),
+                aml::FieldEntry::Named(*b"TPSV", 32),
+                aml::FieldEntry::Named(*b"TCRT", 32),
+                aml::FieldEntry::Named(*b"TPPC", 32),
+                aml::FieldEntry::Named(*b"TEVT", 32),
+            ],
+        );
+        zone_children.extend_from_slice(&[&region, &fields, &tmp, &psv, &crt]);
+
+        // Passive cooling throttles the processors listed in _PSL through their performance
+        // states. _TC1 and _TC2 are the constants of the passive cooling formula, and the guest
+        // samples the temperature every _TSP tenths of second.
+        let cpu_paths: Vec<aml::Path> = (0..vcpu_count)
+            .map(|cpu| cpu_path(cpu).as_str().into())
+            .collect();
+        let psl = aml::Package::new(cpu_paths.iter().map(|p| p as &dyn Aml).collect());
+        let psl = aml::Name::new("_PSL".into(), &psl);
+        let tc1 = aml::Name::new("_TC1".into(), &2u8);
+        let tc2 = aml::Name::new("_TC2".into(), &5u8);
+        let tsp = aml::Name::new("_TSP".into(), &10u8);
+        if !frequencies.is_empty() {
+            zone_children.extend_from_slice(&[&psl, &tc1, &tc2, &tsp]);
+        }
+        aml::ThermalZone::new("\\_TZ_.TZ00".into(), zone_children).to_aml_bytes(bytes);
+
+        // The processor objects only exist with performance states.
+        let cpu_notifies: Vec<aml::Notify> = if frequencies.is_empty() {
+            Vec::new()
+        } else {
+            cpu_paths
+                .iter()
+                .map(|path| aml::Notify::new(path, &0x80u8))
+                .collect()
+        };
+        let zone = aml::Path::new("\\_TZ_.TZ00");
+        let events = aml::Path::new("\\_TZ_.TZ00.TEVT");
+        let temperature_changed =
+            aml::And::new(&aml::ZERO, &aml::Local(0), &THERMAL_EVT_TEMPERATURE);
+        let trip_points_changed =
+            aml::And::new(&aml::ZERO, &aml::Local(0), &THERMAL_EVT_TRIP_POINTS);
+        let performance_changed =
+            aml::And::new(&aml::ZERO, &aml::Local(0), &THERMAL_EVT_PERFORMANCE);
+        aml::Scope::new(
+            "_GPE".into(),
+            vec![&aml::Method::new(
+                format!("_E{:02X}", self.gpe_nr).as_str().into(),
+                0,
+                false,
+                vec![
+                    &aml::Store::new(&aml::Local(0), &events),
+                    // Thermal Zone Status Change
+                    &aml::If::new(
+                        &temperature_changed,
+                        vec![&aml::Notify::new(&zone, &0x80u8)],
+                    ),
+                    // Thermal Zone Trip Points Change
+                    &aml::If::new(
+                        &trip_points_changed,
+                        vec![&aml::Notify::new(&zone, &0x81u8)],
+                    ),
+                    // Performance Present Capabilities Change
+                    &aml::If::new(
+                        &performance_changed,
+                        cpu_notifies.iter().map(|n| n as &dyn Aml).collect(),
+                    ),
+                ],
+            )],
+        )
+        .to_aml_bytes(bytes);
+
+        if frequencies.is_empty() {
+            return;
+        }
+
+        if !self.processors_declared {
+            let hid = aml::Name::new("_HID".into(), &"ACPI0007");
+            let uids: Vec<aml::Name> = (0..vcpu_count)
+                .map(|cpu| aml::Name::new("_UID".into(), &cpu))
+                .collect();
+            let devices: Vec<aml::Device> = uids
+                .iter()
+                .enumerate()
+                .map(|(cpu, uid)| {
+                    aml::Device::new(cpu_device_name(cpu).as_str().into(), vec![&hid, uid])
+                })
+                .collect();
+            let container_hid = aml::Name::new("_HID".into(), &"ACPI0010");
+            let mut children: Vec<&dyn Aml> = vec![&container_hid];
+            children.extend(devices.iter().map(|d| d as &dyn Aml));
+            aml::Device::new("\\_SB_.CPUS".into(), children).to_aml_bytes(bytes);
+        }
+
+        let control = aml::GenericRegister::new(
+            aml::OpRegionSpace::SystemIO,
+            32,
+            0,
+            self.io_base + THERMAL_PERF_CTL,
+            3,
+        );
+        let status = aml::GenericRegister::new(
+            aml::OpRegionSpace::SystemIO,
+            32,
+            0,
+            self.io_base + THERMAL_PERF_STS,
+            3,
+        );
+        let control = aml::ResourceTemplate::new(vec![&control]);
+        let status = aml::ResourceTemplate::new(vec![&status]);
+        let pct = aml::Name::new("_PCT".into(), &aml::Package::new(vec![&control, &status]));
+
+        // Each state is described by its frequency, power, transition and bus master latencies,
+        // and the values of the control and status registers, which are the index of the state.
+        let pss_values: Vec<[u32; 6]> = frequencies
+            .iter()
+            .enumerate()
+            .map(|(i, mhz)| {
+                [
+                    *mhz,
+                    0,
+                    PSTATE_LATENCY_US,
+                    PSTATE_LATENCY_US,
+                    i as u32,
+                    i as u32,
+                ]
+            })
+            .collect();
+        let pss_states: Vec<aml::Package> = pss_values
+            .iter()
+            .map(|values| aml::Package::new(values.iter().map(|v| v as &dyn Aml).collect()))
+            .collect();
+        let pss = aml::Name::new(
+            "_PSS".into(),
+            &aml::Package::new(pss_states.iter().map(|p| p as &dyn Aml).collect()),
+        );
+
+        let ppc = aml::Path::new("\\_TZ_.TZ00.TPPC");
+        let ppc = aml::Return::new(&ppc);
+        let ppc = aml::Method::new("_PPC".into(), 0, false, vec![&ppc]);
+
+        // A single domain of all the processors, coordinated by the guest (SW_ALL).
+        let domain_size = vcpu_count as u32;
+        let psd = aml::Name::new(
+            "_PSD".into(),
+            &aml::Package::new(vec![&aml::Package::new(vec![
+                &5u8,
+                &0u8,
+                &0u32,
+                &0xfcu32,
+                &domain_size,
+            ])]),
+        );
+
+        for cpu in 0..vcpu_count {
+            aml::Scope::new(cpu_path(cpu).as_str().into(), vec![&pct, &pss, &ppc, &psd])
+                .to_aml_bytes(bytes);
+        }
+    }
+}
+
+impl Suspendable for ThermalController {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn access(offset: u64) -> BusAccessInfo {
+        BusAccessInfo {
+            offset,
+            address: offset,
+            id: 0,
+        }
+    }
+
+    fn read_register(controller: &mut ThermalController, offset: u64) -> u32 {
+        let mut data = [0u8; 4];
+        controller.read(access(offset), &mut data);
+        u32::from_le_bytes(data)
+    }
+
+    #[test]
+    fn state_updates() {
+        let config = ThermalConfig {
+            frequencies: vec![3000, 2000, 1000],
+            ..Default::default()
+        };
+        let mut state = ThermalState::new(&config, 2).unwrap();
+        assert_eq!(state.temperature, 3132);
+        assert!(!state.has_pending_events());
+
+        assert_eq!(
+            state.set_temperature(-274),
+            Err(ThermalError::InvalidTemperature(-274))
+        );
+        assert_eq!(
+            state.set_trip_points(90, 90),
+            Err(ThermalError::InvalidTripPoints {
+                passive: 90,
+                critical: 90
+            })
+        );
+        state.set_temperature(40).unwrap();
+        assert!(!state.has_pending_events());
+
+        assert_eq!(state.set_frequency_limit(2500), Ok(2000));
+        assert_eq!(state.ppc, 1);
+        assert_eq!(state.set_frequency_limit(500), Ok(1000));
+        assert_eq!(state.ppc, 2);
+        assert_eq!(state.set_frequency_limit(u32::MAX), Ok(3000));
+        assert_eq!(state.ppc, 0);
+        assert_eq!(state.events, THERMAL_EVT_PERFORMANCE);
+
+        let config = ThermalConfig {
+            frequencies: vec![1000, 2000],
+            ..Default::default()
+        };
+        assert!(ThermalState::new(&config, 2).is_err());
+        let mut state = ThermalState::new(&Default::default(), 2).unwrap();
+        assert_eq!(
+            state.set_frequency_limit(1000),
+            Err(ThermalError::NoPerformanceStates)
+        );
+    }
+
+    #[test]
+    fn guest_registers() {
+        let config = ThermalConfig {
+            frequencies: vec![3000, 2000, 1000],
+            ..Default::default()
+        };
+        let state = Arc::new(Mutex::new(ThermalState::new(&config, 2).unwrap()));
+        let mut controller = ThermalController::new(state.clone(), 0x600, 3, false);
+
+        state.lock().set_temperature(95).unwrap();
+        state.lock().set_frequency_limit(2000).unwrap();
+        assert_eq!(read_register(&mut controller, THERMAL_TMP), 3682);
+        assert_eq!(read_register(&mut controller, THERMAL_PSV), 3632);
+        assert_eq!(read_register(&mut controller, THERMAL_CRT), 3782);
+        assert_eq!(read_register(&mut controller, THERMAL_PPC), 1);
+        assert_eq!(
+            read_register(&mut controller, THERMAL_EVT),
+            THERMAL_EVT_TEMPERATURE | THERMAL_EVT_PERFORMANCE
+        );
+        assert_eq!(read_register(&mut controller, THERMAL_EVT), 0);
+        assert!(!state.lock().has_pending_events());
+
+        // The state in effect is limited by PPC.
+        controller.write(access(THERMAL_PERF_CTL), &0u32.to_le_bytes());
+        assert_eq!(read_register(&mut controller, THERMAL_PERF_STS), 1);
+        controller.write(access(THERMAL_PERF_CTL), &2u32.to_le_bytes());
+        assert_eq!(read_register(&mut controller, THERMAL_PERF_STS), 2);
+    }
+}
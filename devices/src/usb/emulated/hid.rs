@@ -0,0 +1,807 @@
+// Copyright 2023 The ChromiumOS Authors
+// Use of this source code is governed by a BSD-style license that can be
+// found in the LICENSE file.
+
+//! USB HID keyboard, mouse and tablet devices, fed by the same event sources as virtio-input.
+
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+use anyhow::Context;
+use base::warn;
+use base::AsRawDescriptor;
+use base::RawDescriptor;
+use linux_input_sys::virtio_input_event;
+use sync::Mutex;
+use usb_util::ConfigDescriptor;
+use usb_util::ControlRequestRecipient;
+use usb_util::ControlRequestType;
+use usb_util::DescriptorType;
+use usb_util::DeviceDescriptor;
+use usb_util::EndpointDescriptor;
+use usb_util::InterfaceDescriptor;
+use usb_util::StandardControlRequest;
+use usb_util::TransferStatus;
+use usb_util::UsbRequestSetup;
+use vm_control::UsbHidKind;
+
+use super::complete_transfer;
+use super::config_descriptor;
+use super::descriptor;
+use super::EmulatedDevice;
+use super::PendingTransfers;
+use super::UsbDescriptors;
+use super::UsbFunction;
+use super::EMULATED_VENDOR_ID;
+use super::STRING_MANUFACTURER;
+use super::STRING_PRODUCT;
+use super::STRING_SERIAL_NUMBER;
+use crate::usb::host_backend::error::Error;
+use crate::usb::host_backend::error::Result;
+use crate::usb::xhci::scatter_gather_buffer::ScatterGatherBuffer;
+use crate::usb::xhci::xhci_transfer::TransferDirection;
+use crate::usb::xhci::xhci_transfer::XhciTransfer;
+use crate::utils::AsyncJobQueue;
+use crate::utils::EventHandler;
+use crate::virtio::input::constants::*;
+use crate::virtio::EventSource;
+
+const KEYBOARD_PRODUCT_ID: u16 = 0x0101;
+const MOUSE_PRODUCT_ID: u16 = 0x0102;
+const TABLET_PRODUCT_ID: u16 = 0x0103;
+
+const INTERRUPT_IN_ENDPOINT: u8 = 1;
+
+const CLASS_HID: u8 = 0x03;
+const SUBCLASS_BOOT: u8 = 0x01;
+const PROTOCOL_KEYBOARD: u8 = 0x01;
+const PROTOCOL_MOUSE: u8 = 0x02;
+
+const DESCRIPTOR_TYPE_HID: u8 = 0x21;
+const DESCRIPTOR_TYPE_REPORT: u8 = 0x22;
+
+// Class specific requests, see the HID specification 7.2.
+const GET_REPORT: u8 = 0x01;
+const GET_IDLE: u8 = 0x02;
+const GET_PROTOCOL: u8 = 0x03;
+const SET_REPORT: u8 = 0x09;
+const SET_IDLE: u8 = 0x0a;
+const SET_PROTOCOL: u8 = 0x0b;
+
+const BOOT_PROTOCOL: u8 = 0;
+const REPORT_PROTOCOL: u8 = 1;
+
+// Reports not yet read by the guest are dropped past this limit, oldest first.
+const MAX_QUEUED_REPORTS: usize = 64;
+
+// Range of the absolute coordinates reported by the tablet.
+const TABLET_MAX_COORDINATE: i64 = 0x7fff;
+
+// Boot keyboard with LED output report, HID specification appendix B.1.
+const KEYBOARD_REPORT_DESCRIPTOR: &[u8] = &[
+    0x05, 0x01, // Usage Page (Generic Desktop)
+    0x09, 0x06, // Usage (Keyboard)
+    0xa1, 0x01, // Collection (Application)
+    0x75, 0x01, //   Report Size (1)
+    0x95, 0x08, //   Report Count (8)
+    0x05, 0x07, //   Usage Page (Keyboard)
+    0x19, 0xe0, //   Usage Minimum (Left Control)
+    0x29, 0xe7, //   Usage Maximum (Right GUI)
+    0x15, 0x00, //   Logical Minimum (0)
+    0x25, 0x01, //   Logical Maximum (1)
+    0x81, 0x02, //   Input (Data, Variable, Absolute)
+    0x95, 0x01, //   Report Count (1)
+    0x75, 0x08, //   Report Size (8)
+    0x81, 0x01, //   Input (Constant)
+    0x95, 0x05, //   Report Count (5)
+    0x75, 0x01, //   Report Size (1)
+    0x05, 0x08, //   Usage Page (LEDs)
+    0x19, 0x01, //   Usage Minimum (Num Lock)
+    0x29, 0x05, //   Usage Maximum (Kana)
+    0x91, 0x02, //   Output (Data, Variable, Absolute)
+    0x95, 0x01, //   Report Count (1)
+    0x75, 0x03, //   Report Size (3)
+    0x91, 0x01, //   Output (Constant)
+    0x95, 0x06, //   Report Count (6)
+    0x75, 0x08, //   Report Size (8)
+    0x15, 0x00, //   Logical Minimum (0)
+    0x26, 0xff, 0x00, //   Logical Maximum (255)
+    0x05, 0x07, //   Usage Page (Keyboard)
+    0x19, 0x00, //   Usage Minimum (0)
+    0x29, 0xff, //   Usage Maximum (255)
+    0x81, 0x00, //   Input (Data, Array)
+    0xc0, // End Collection
+];
+
+// Five buttons and relative X, Y and wheel. The first three bytes match the boot mouse report.
+const MOUSE_REPORT_DESCRIPTOR: &[u8] = &[
+    0x05, 0x01, // Usage Page (Generic Desktop)
+    0x09, 0x02, // Usage (Mouse)
+    0xa1, 0x01, // Collection (Application)
+    0x09, 0x01, //   Usage (Pointer)
+    0xa1, 0x00, //   Collection (Physical)
+    0x05, 0x09, //     Usage Page (Button)
+    0x19, 0x01, //     Usage Minimum (1)
+    0x29, 0x05, //     Usage Maximum (5)
+    0x15, 0x00, //     Logical Minimum (0)
+    0x25, 0x01, //     Logical Maximum (1)
+    0x95, 0x05, //     Report Count (5)
+    0x75, 0x01, //     Report Size (1)
+    0x81, 0x02, //     Input (Data, Variable, Absolute)
+    0x95, 0x01, //     Report Count (1)
+    0x75, 0x03, //     Report Size (3)
+    0x81, 0x01, //     Input (Constant)
+    0x05, 0x01, //     Usage Page (Generic Desktop)
+    0x09, 0x30, //     Usage (X)
+    0x09, 0x31, //     Usage (Y)
+    0x09, 0x38, //     Usage (Wheel)
+    0x15, 0x81, //     Logical Minimum (-127)
+    0x25, 0x7f, //     Logical Maximum (127)
+    0x75, 0x08, //     Report Size (8)
+    0x95, 0x03, //     Report Count (3)
+    0x81, 0x06, //     Input (Data, Variable, Relative)
+    0xc0, //   End Collection
+    0xc0, // End Collection
+];
+
+// Five buttons, absolute X and Y, and relative wheel.
+const TABLET_REPORT_DESCRIPTOR: &[u8] = &[
+    0x05, 0x01, // Usage Page (Generic Desktop)
+    0x09, 0x02, // Usage (Mouse)
+    0xa1, 0x01, // Collection (Application)
+    0x09, 0x01, //   Usage (Pointer)
+    0xa1, 0x00, //   Collection (Physical)
+    0x05, 0x09, //     Usage Page (Button)
+    0x19, 0x01, //     Usage Minimum (1)
+    0x29, 0x05, //     Usage Maximum (5)
+    0x15, 0x00, //     Logical Minimum (0)
+    0x25, 0x01, //     Logical Maximum (1)
+    0x95, 0x05, //     Report Count (5)
+    0x75, 0x01, //     Report Size (1)
+    0x81, 0x02, //     Input (Data, Variable, Absolute)
+    0x95, 0x01, //     Report Count (1)
+    0x75, 0x03, //     Report Size (3)
+    0x81, 0x01, //     Input (Constant)
+    0x05, 0x01, //     Usage Page (Generic Desktop)
+    0x09, 0x30, //     Usage (X)
+    0x09, 0x31, //     Usage (Y)
+    0x15, 0x00, //     Logical Minimum (0)
+    0x26, 0xff, 0x7f, //     Logical Maximum (32767)
+    0x75, 0x10, //     Report Size (16)
+    0x95, 0x02, //     Report Count (2)
+    0x81, 0x02, //     Input (Data, Variable, Absolute)
+    0x09, 0x38, //     Usage (Wheel)
+    0x15, 0x81, //     Logical Minimum (-127)
+    0x25, 0x7f, //     Logical Maximum (127)
+    0x75, 0x08, //     Report Size (8)
+    0x95, 0x01, //     Report Count (1)
+    0x81, 0x06, //     Input (Data, Variable, Relative)
+    0xc0, //   End Collection
+    0xc0, // End Collection
+];
+
+// Linux key codes indexed by HID keyboard usage, as in the kernel's usbkbd driver.
+#[rustfmt::skip]
+const USAGE_TO_KEY: [u16; 0x74] = [
+      0,   0,   0,   0,  30,  48,  46,  32,  18,  33,  34,  35,  23,  36,  37,  38,
+     50,  49,  24,  25,  16,  19,  31,  20,  22,  47,  17,  45,  21,  44,   2,   3,
+      4,   5,   6,   7,   8,   9,  10,  11,  28,   1,  14,  15,  57,  12,  13,  26,
+     27,  43,  43,  39,  40,  41,  51,  52,  53,  58,  59,  60,  61,  62,  63,  64,
+     65,  66,  67,  68,  87,  88,  99,  70, 119, 110, 102, 104, 111, 107, 109, 106,
+    105, 108, 103,  69,  98,  55,  74,  78,  96,  79,  80,  81,  75,  76,  77,  71,
+     72,  73,  82,  83,  86, 127, 116, 117, 183, 184, 185, 186, 187, 188, 189, 190,
+    191, 192, 193, 194,
+];
+
+// Modifier keys, in the order of their bits in the keyboard report.
+const MODIFIER_KEYS: [u16; 8] = [
+    KEY_LEFTCTRL,
+    KEY_LEFTSHIFT,
+    KEY_LEFTALT,
+    KEY_LEFTMETA,
+    KEY_RIGHTCTRL,
+    KEY_RIGHTSHIFT,
+    KEY_RIGHTALT,
+    KEY_RIGHTMETA,
+];
+
+// Returns the HID usage of the Linux key `code`, if it has one.
+fn key_to_usage(code: u16) -> Option<u8> {
+    if code == KEY_RESERVED {
+        return None;
+    }
+    USAGE_TO_KEY
+        .iter()
+        .position(|k| *k == code)
+        .map(|usage| usage as u8)
+}
+
+fn button_bit(code: u16) -> Option<u8> {
+    match code {
+        BTN_LEFT | BTN_TOUCH => Some(0),
+        BTN_RIGHT => Some(1),
+        BTN_MIDDLE => Some(2),
+        BTN_SIDE => Some(3),
+        BTN_EXTRA => Some(4),
+        _ => None,
+    }
+}
+
+/// Input state of a HID device, updated by input events and turned into reports.
+enum InputState {
+    Keyboard {
+        modifiers: u8,
+        // Usages of the pressed keys, in the order they were pressed.
+        keys: Vec<u8>,
+    },
+    Mouse {
+        buttons: u8,
+        dx: i32,
+        dy: i32,
+        wheel: i32,
+    },
+    Tablet {
+        width: u32,
+        height: u32,
+        buttons: u8,
+        x: u16,
+        y: u16,
+        wheel: i32,
+    },
+}
+
+impl InputState {
+    fn new(kind: UsbHidKind) -> InputState {
+        match kind {
+            UsbHidKind::Keyboard => InputState::Keyboard {
+                modifiers: 0,
+                keys: Vec::new(),
+            },
+            UsbHidKind::Mouse => InputState::Mouse {
+                buttons: 0,
+                dx: 0,
+                dy: 0,
+                wheel: 0,
+            },
+            UsbHidKind::Tablet { width, height } => InputState::Tablet {
+                width,
+                height,
+                buttons: 0,
+                x: 0,
+                y: 0,
+                wheel: 0,
+            },
+        }
+    }
+
+    fn handle_event(&mut self, event: &virtio_input_event) {
+        let type_ = event.type_.to_native();
+        let code = event.code.to_native();
+        let value = event.value.to_native();
+        match self {
+            InputState::Keyboard { modifiers, keys } if type_ == EV_KEY => {
+                if let Some(bit) = MODIFIER_KEYS.iter().position(|k| *k == code) {
+                    if value != 0 {
+                        *modifiers |= 1 << bit;
+                    } else {
+                        *modifiers &= !(1 << bit);
+                    }
+                } else if let Some(usage) = key_to_usage(code) {
+                    // Auto-repeat events don't change the state.
+                    if value == 0 {
+                        keys.retain(|k| *k != usage);
+                    } else if !keys.contains(&usage) {
+                        keys.push(usage);
+                    }
+                }
+            }
+            InputState::Mouse { buttons, .. } | InputState::Tablet { buttons, .. }
+                if type_ == EV_KEY =>
+            {
+                if let Some(bit) = button_bit(code) {
+                    if value != 0 {
+                        *buttons |= 1 << bit;
+                    } else {
+                        *buttons &= !(1 << bit);
+                    }
+                }
+            }
+            InputState::Mouse { dx, dy, wheel, .. } if type_ == EV_REL => match code {
+                REL_X => *dx = dx.saturating_add(value),
+                REL_Y => *dy = dy.saturating_add(value),
+                REL_WHEEL => *wheel = wheel.saturating_add(value),
+                _ => {}
+            },
+            InputState::Tablet { wheel, .. } if type_ == EV_REL && code == REL_WHEEL => {
+                *wheel = wheel.saturating_add(value);
+            }
+            InputState::Tablet {
+                width,
+                height,
+                x,
+                y,
+                ..
+            } if type_ == EV_ABS => match code {
+                ABS_X => *x = scale_coordinate(value, *width),
+                ABS_Y => *y = scale_coordinate(value, *height),
+                _ => {}
+            },
+            _ => {}
+        }
+    }
+
+    /// Returns the report describing the current state, consuming relative motion as far as it
+    /// fits in the report.
+    fn report(&mut self) -> Vec<u8> {
+        match self {
+            InputState::Keyboard { modifiers, keys } => {
+                let mut report = vec![*modifiers, 0];
+                if keys.len() > 6 {
+                    // Error roll over, too many keys are pressed.
+                    report.extend_from_slice(&[0x01; 6]);
+                } else {
+                    report.extend_from_slice(keys);
+                    report.resize(8, 0);
+                }
+                report
+            }
+            InputState::Mouse {
+                buttons,
+                dx,
+                dy,
+                wheel,
+            } => vec![
+                *buttons,
+                take_relative(dx) as u8,
+                take_relative(dy) as u8,
+                take_relative(wheel) as u8,
+            ],
+            InputState::Tablet {
+                buttons,
+                x,
+                y,
+                wheel,
+                ..
+            } => {
+                let mut report = vec![*buttons];
+                report.extend_from_slice(&x.to_le_bytes());
+                report.extend_from_slice(&y.to_le_bytes());
+                report.push(take_relative(wheel) as u8);
+                report
+            }
+        }
+    }
+
+    fn has_relative_motion(&self) -> bool {
+        match self {
+            InputState::Keyboard { .. } => false,
+            InputState::Mouse { dx, dy, wheel, .. } => *dx != 0 || *dy != 0 || *wheel != 0,
+            InputState::Tablet { wheel, .. } => *wheel != 0,
+        }
+    }
+}
+
+// Scales `value` in `0..size` to the range of the tablet report.
+fn scale_coordinate(value: i32, size: u32) -> u16 {
+    let max = (size as i64 - 1).max(1);
+    ((value as i64).clamp(0, max) * TABLET_MAX_COORDINATE / max) as u16
+}
+
+// Takes the part of the relative motion `value` that fits in a report.
+fn take_relative(value: &mut i32) -> i8 {
+    let taken = (*value).clamp(-127, 127);
+    *value -= taken;
+    taken as i8
+}
+
+struct HidState {
+    input: InputState,
+    // Reports generated from input events, not yet read by the guest.
+    reports: VecDeque<Vec<u8>>,
+    last_report: Vec<u8>,
+    pending_in: PendingTransfers,
+    protocol: u8,
+    idle: u8,
+}
+
+impl HidState {
+    fn new(kind: UsbHidKind, job_queue: Arc<AsyncJobQueue>) -> HidState {
+        let mut input = InputState::new(kind);
+        let last_report = input.report();
+        HidState {
+            input,
+            reports: VecDeque::new(),
+            last_report,
+            pending_in: PendingTransfers::new(job_queue),
+            protocol: REPORT_PROTOCOL,
+            idle: 0,
+        }
+    }
+
+    fn handle_event(&mut self, event: &virtio_input_event) {
+        if event.type_.to_native() != EV_SYN || event.code.to_native() != SYN_REPORT {
+            self.input.handle_event(event);
+            return;
+        }
+        loop {
+            // Reports carrying relative motion are always sent, even if they repeat the last one.
+            let relative = self.input.has_relative_motion();
+            let report = self.input.report();
+            if relative || report != self.last_report {
+                if self.reports.len() == MAX_QUEUED_REPORTS {
+                    warn!("usb hid report queue full, dropping report");
+                    self.reports.pop_front();
+                }
+                self.reports.push_back(report.clone());
+                self.last_report = report;
+            }
+            if !self.input.has_relative_motion() {
+                break;
+            }
+        }
+    }
+
+    // Completes pending interrupt transfers with the queued reports.
+    fn send_reports(&mut self) -> Result<()> {
+        while !self.reports.is_empty() {
+            let (transfer, buffer) = match self.pending_in.pop() {
+                Some(t) => t,
+                None => break,
+            };
+            let mut report = self.reports.pop_front().unwrap();
+            if self.protocol == BOOT_PROTOCOL {
+                if let InputState::Mouse { .. } = self.input {
+                    // The boot mouse report stops after X and Y.
+                    report.truncate(3);
+                }
+            }
+            let len = buffer.write(&report).map_err(Error::WriteBuffer)?;
+            complete_transfer(&transfer, TransferStatus::Completed, len)?;
+        }
+        Ok(())
+    }
+}
+
+/// The HID function of an emulated device.
+pub struct Hid {
+    state: Arc<Mutex<HidState>>,
+    report_descriptor: &'static [u8],
+}
+
+impl Hid {
+    /// Creates a HID device of `kind`, together with the handler feeding it with the events
+    /// from `source`. The handler needs to be registered on an event loop for the source.
+    pub fn new_device(
+        job_queue: Arc<AsyncJobQueue>,
+        kind: UsbHidKind,
+        mut source: Box<dyn EventSource + Send>,
+    ) -> anyhow::Result<(EmulatedDevice<Hid>, Arc<HidEventHandler>)> {
+        source
+            .init()
+            .context("failed to initialize hid event source")?;
+        let state = Arc::new(Mutex::new(HidState::new(kind, job_queue)));
+        let report_descriptor = match kind {
+            UsbHidKind::Keyboard => KEYBOARD_REPORT_DESCRIPTOR,
+            UsbHidKind::Mouse => MOUSE_REPORT_DESCRIPTOR,
+            UsbHidKind::Tablet { .. } => TABLET_REPORT_DESCRIPTOR,
+        };
+        let hid = Hid {
+            state: state.clone(),
+            report_descriptor,
+        };
+        let handler = Arc::new(HidEventHandler {
+            source: Mutex::new(source),
+            state,
+        });
+        Ok((
+            EmulatedDevice::new(descriptors(kind, report_descriptor), hid),
+            handler,
+        ))
+    }
+}
+
+impl UsbFunction for Hid {
+    fn control_request(&mut self, setup: &UsbRequestSetup, _data: &[u8]) -> Option<Vec<u8>> {
+        if setup.get_recipient() != ControlRequestRecipient::Interface {
+            return None;
+        }
+        if setup.get_standard_request() == Some(StandardControlRequest::GetDescriptor) {
+            return match (setup.value >> 8) as u8 {
+                DESCRIPTOR_TYPE_HID => Some(hid_descriptor(self.report_descriptor)),
+                DESCRIPTOR_TYPE_REPORT => Some(self.report_descriptor.to_vec()),
+                _ => None,
+            };
+        }
+        if setup.get_type() != ControlRequestType::Class {
+            return None;
+        }
+        let mut state = self.state.lock();
+        match setup.request {
+            GET_REPORT => Some(state.last_report.clone()),
+            GET_IDLE => Some(vec![state.idle]),
+            GET_PROTOCOL => Some(vec![state.protocol]),
+            // The only output report is the keyboard LEDs, which have nowhere to go.
+            SET_REPORT => Some(Vec::new()),
+            SET_IDLE => {
+                // Reports are only sent on changes, whatever the idle rate.
+                state.idle = (setup.value >> 8) as u8;
+                Some(Vec::new())
+            }
+            SET_PROTOCOL => {
+                state.protocol = setup.value as u8;
+                Some(Vec::new())
+            }
+            _ => None,
+        }
+    }
+
+    fn submit_transfer(
+        &mut self,
+        transfer: Arc<XhciTransfer>,
+        buffer: ScatterGatherBuffer,
+    ) -> Result<()> {
+        if transfer.get_endpoint_number() != INTERRUPT_IN_ENDPOINT
+            || transfer.get_transfer_dir() != TransferDirection::In
+        {
+            warn!("usb hid transfer on unknown endpoint");
+            return complete_transfer(&transfer, TransferStatus::Error, 0);
+        }
+        let mut state = self.state.lock();
+        state.pending_in.push(transfer, buffer)?;
+        state.send_reports()
+    }
+
+    fn reset(&mut self) -> Result<()> {
+        let mut state = self.state.lock();
+        state.reports.clear();
+        state.protocol = REPORT_PROTOCOL;
+        state.idle = 0;
+        state.pending_in.cancel_all()
+    }
+}
+
+/// Reads the events of a HID device's event source and turns them into reports.
+pub struct HidEventHandler {
+    source: Mutex<Box<dyn EventSource + Send>>,
+    state: Arc<Mutex<HidState>>,
+}
+
+impl AsRawDescriptor for HidEventHandler {
+    fn as_raw_descriptor(&self) -> RawDescriptor {
+        self.source.lock().as_raw_descriptor()
+    }
+}
+
+impl EventHandler for HidEventHandler {
+    fn on_event(&self) -> anyhow::Result<()> {
+        let mut source = self.source.lock();
+        source
+            .receive_events()
+            .context("failed to receive hid input events")?;
+        let mut state = self.state.lock();
+        while let Some(event) = source.pop_available_event() {
+            state.handle_event(&event);
+        }
+        state
+            .send_reports()
+            .context("failed to send hid input reports")
+    }
+}
+
+fn hid_descriptor(report_descriptor: &[u8]) -> Vec<u8> {
+    let mut body = vec![
+        0x11,
+        0x01, // bcdHID 1.11
+        0x00, // bCountryCode
+        0x01, // bNumDescriptors
+        DESCRIPTOR_TYPE_REPORT,
+    ];
+    body.extend_from_slice(&(report_descriptor.len() as u16).to_le_bytes());
+    descriptor(DESCRIPTOR_TYPE_HID, body.as_slice())
+}
+
+fn descriptors(kind: UsbHidKind, report_descriptor: &[u8]) -> UsbDescriptors {
+    let (product_id, product, subclass, protocol, max_packet_size) = match kind {
+        UsbHidKind::Keyboard => (
+            KEYBOARD_PRODUCT_ID,
+            "USB Keyboard",
+            SUBCLASS_BOOT,
+            PROTOCOL_KEYBOARD,
+            8,
+        ),
+        UsbHidKind::Mouse => (
+            MOUSE_PRODUCT_ID,
+            "USB Mouse",
+            SUBCLASS_BOOT,
+            PROTOCOL_MOUSE,
+            4,
+        ),
+        UsbHidKind::Tablet { .. } => (TABLET_PRODUCT_ID, "USB Tablet", 0, 0, 6),
+    };
+    let device = DeviceDescriptor {
+        bcdUSB: 0x0200,
+        bMaxPacketSize0: 64,
+        idVendor: EMULATED_VENDOR_ID,
+        idProduct: product_id,
+        bcdDevice: 0x0100,
+        iManufacturer: STRING_MANUFACTURER,
+        iProduct: STRING_PRODUCT,
+        iSerialNumber: STRING_SERIAL_NUMBER,
+        bNumConfigurations: 1,
+        ..Default::default()
+    };
+    let config = ConfigDescriptor {
+        bNumInterfaces: 1,
+        // Self powered.
+        bmAttributes: 0xc0,
+        ..Default::default()
+    };
+    let interface = InterfaceDescriptor {
+        bNumEndpoints: 1,
+        bInterfaceClass: CLASS_HID,
+        bInterfaceSubClass: subclass,
+        bInterfaceProtocol: protocol,
+        ..Default::default()
+    };
+    let interrupt_in = EndpointDescriptor {
+        bEndpointAddress: 0x80 | INTERRUPT_IN_ENDPOINT,
+        bmAttributes: 0x03,
+        wMaxPacketSize: max_packet_size,
+        // 2^(4-1) microframes, 1ms.
+        bInterval: 4,
+    };
+    UsbDescriptors {
+        device,
+        config: config_descriptor(
+            config,
+            &[
+                descriptor(DescriptorType::Interface as u8, &interface),
+                hid_descriptor(report_descriptor),
+                descriptor(DescriptorType::Endpoint as u8, &interrupt_in),
+            ],
+        ),
+        strings: vec![
+            "crosvm".to_string(),
+            product.to_string(),
+            "000000000001".to_string(),
+        ],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::mem;
+
+    use data_model::Le16;
+    use data_model::SLe32;
+
+    use super::*;
+    use crate::utils::EventLoop;
+
+    fn event(type_: u16, code: u16, value: i32) -> virtio_input_event {
+        virtio_input_event {
+            type_: Le16::from(type_),
+            code: Le16::from(code),
+            value: SLe32::from(value),
+        }
+    }
+
+    fn send(input: &mut InputState, events: &[virtio_input_event]) -> Vec<u8> {
+        for e in events {
+            input.handle_event(e);
+        }
+        input.report()
+    }
+
+    #[test]
+    fn keyboard_report() {
+        let mut input = InputState::new(UsbHidKind::Keyboard);
+        let report = send(
+            &mut input,
+            &[
+                event(EV_KEY, KEY_LEFTSHIFT, 1),
+                event(EV_KEY, KEY_A, 1),
+                event(EV_KEY, KEY_ENTER, 1),
+                // Auto-repeat.
+                event(EV_KEY, KEY_A, 2),
+            ],
+        );
+        assert_eq!(report, vec![0x02, 0, 0x04, 0x28, 0, 0, 0, 0]);
+
+        let report = send(&mut input, &[event(EV_KEY, KEY_A, 0)]);
+        assert_eq!(report, vec![0x02, 0, 0x28, 0, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn keyboard_roll_over() {
+        let mut input = InputState::new(UsbHidKind::Keyboard);
+        let keys = [KEY_A, KEY_B, KEY_C, KEY_D, KEY_E, KEY_F, KEY_G];
+        let events: Vec<_> = keys.iter().map(|k| event(EV_KEY, *k, 1)).collect();
+        assert_eq!(send(&mut input, &events), vec![0, 0, 1, 1, 1, 1, 1, 1]);
+    }
+
+    #[test]
+    fn mouse_motion_split_across_reports() {
+        let mut input = InputState::new(UsbHidKind::Mouse);
+        let report = send(
+            &mut input,
+            &[
+                event(EV_KEY, BTN_RIGHT, 1),
+                event(EV_REL, REL_X, 200),
+                event(EV_REL, REL_Y, -3),
+            ],
+        );
+        assert_eq!(report, vec![0x02, 127, (-3i8) as u8, 0]);
+        assert!(input.has_relative_motion());
+        assert_eq!(input.report(), vec![0x02, 73, 0, 0]);
+        assert!(!input.has_relative_motion());
+    }
+
+    #[test]
+    fn hid_state_queues_changed_reports() {
+        let (event_loop, join_handle) = EventLoop::start("test".to_string(), None).unwrap();
+        let job_queue = AsyncJobQueue::init(&event_loop).unwrap();
+        let syn = event(EV_SYN, SYN_REPORT, 0);
+
+        let mut state = HidState::new(UsbHidKind::Mouse, job_queue.clone());
+        state.handle_event(&event(EV_REL, REL_X, 300));
+        state.handle_event(&syn);
+        assert_eq!(
+            Vec::from(mem::take(&mut state.reports)),
+            vec![vec![0, 127, 0, 0], vec![0, 127, 0, 0], vec![0, 46, 0, 0]]
+        );
+        state.handle_event(&event(EV_REL, REL_X, 46));
+        state.handle_event(&syn);
+        assert_eq!(
+            Vec::from(mem::take(&mut state.reports)),
+            vec![vec![0, 46, 0, 0]]
+        );
+        // The motion stopping is reported once.
+        state.handle_event(&syn);
+        state.handle_event(&syn);
+        assert_eq!(
+            Vec::from(mem::take(&mut state.reports)),
+            vec![vec![0, 0, 0, 0]]
+        );
+
+        let mut state = HidState::new(UsbHidKind::Keyboard, job_queue);
+        state.handle_event(&event(EV_KEY, KEY_A, 1));
+        state.handle_event(&syn);
+        state.handle_event(&event(EV_KEY, KEY_A, 2));
+        state.handle_event(&syn);
+        assert_eq!(
+            Vec::from(mem::take(&mut state.reports)),
+            vec![vec![0, 0, 0x04, 0, 0, 0, 0, 0]]
+        );
+
+        event_loop.stop();
+        join_handle.join().unwrap();
+    }
+
+    #[test]
+    fn scale_coordinate_bounds() {
+        assert_eq!(scale_coordinate(-1, 100), 0);
+        assert_eq!(scale_coordinate(99, 100), 0x7fff);
+        assert_eq!(scale_coordinate(1000, 100), 0x7fff);
+        // Sizes past i32::MAX don't wrap to negative bounds.
+        assert_eq!(scale_coordinate(i32::MAX, u32::MAX), 0x3fff);
+        assert_eq!(scale_coordinate(-1, u32::MAX), 0);
+    }
+
+    #[test]
+    fn tablet_scaled_coordinates() {
+        let mut input = InputState::new(UsbHidKind::Tablet {
+            width: 1025,
+            height: 101,
+        });
+        let report = send(
+            &mut input,
+            &[
+                event(EV_ABS, ABS_X, 512),
+                event(EV_ABS, ABS_Y, 1000),
+                event(EV_KEY, BTN_TOUCH, 1),
+            ],
+        );
+        assert_eq!(report, vec![0x01, 0xff, 0x3f, 0xff, 0x7f, 0]);
+    }
+}
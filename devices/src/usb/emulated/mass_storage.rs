@@ -0,0 +1,674 @@
+// Copyright 2023 The ChromiumOS Authors
+// Use of this source code is governed by a BSD-style license that can be
+// found in the LICENSE file.
+
+//! USB mass storage device using the Bulk-Only Transport and the SCSI transparent command set.
+
+use std::cmp::min;
+use std::convert::TryInto;
+use std::sync::Arc;
+
+use base::error;
+use base::warn;
+use disk::DiskFile;
+use usb_util::ConfigDescriptor;
+use usb_util::ControlRequestRecipient;
+use usb_util::ControlRequestType;
+use usb_util::DescriptorType;
+use usb_util::DeviceDescriptor;
+use usb_util::EndpointDescriptor;
+use usb_util::InterfaceDescriptor;
+use usb_util::TransferStatus;
+use usb_util::UsbRequestSetup;
+
+use super::complete_transfer;
+use super::config_descriptor;
+use super::descriptor;
+use super::scsi::DataPhase;
+use super::scsi::ScsiDisk;
+use super::EmulatedDevice;
+use super::PendingTransfers;
+use super::UsbDescriptors;
+use super::UsbFunction;
+use super::EMULATED_VENDOR_ID;
+use super::STRING_MANUFACTURER;
+use super::STRING_PRODUCT;
+use super::STRING_SERIAL_NUMBER;
+use crate::usb::host_backend::error::Error;
+use crate::usb::host_backend::error::Result;
+use crate::usb::xhci::scatter_gather_buffer::ScatterGatherBuffer;
+use crate::usb::xhci::xhci_transfer::TransferDirection;
+use crate::usb::xhci::xhci_transfer::XhciTransfer;
+use crate::utils::AsyncJobQueue;
+
+const PRODUCT_ID: u16 = 0x0100;
+
+const BULK_IN_ENDPOINT: u8 = 1;
+const BULK_OUT_ENDPOINT: u8 = 2;
+const MAX_PACKET_SIZE: u16 = 512;
+
+const CLASS_MASS_STORAGE: u8 = 0x08;
+const SUBCLASS_SCSI_TRANSPARENT: u8 = 0x06;
+const PROTOCOL_BULK_ONLY: u8 = 0x50;
+
+// Class specific requests, see the Bulk-Only Transport specification 3.
+const BULK_ONLY_MASS_STORAGE_RESET: u8 = 0xff;
+const GET_MAX_LUN: u8 = 0xfe;
+
+const CBW_SIGNATURE: u32 = 0x43425355;
+const CBW_LEN: usize = 31;
+const CBW_FLAG_DATA_IN: u8 = 0x80;
+const CSW_SIGNATURE: u32 = 0x53425355;
+
+const CSW_STATUS_PASSED: u8 = 0;
+const CSW_STATUS_FAILED: u8 = 1;
+const CSW_STATUS_PHASE_ERROR: u8 = 2;
+
+/// Command Block Wrapper sent by the host on the bulk OUT endpoint.
+struct Cbw {
+    tag: u32,
+    data_transfer_length: u32,
+    data_in: bool,
+    cdb: Vec<u8>,
+}
+
+impl Cbw {
+    fn parse(bytes: &[u8]) -> Option<Cbw> {
+        if bytes.len() != CBW_LEN
+            || u32::from_le_bytes(bytes[0..4].try_into().unwrap()) != CBW_SIGNATURE
+        {
+            return None;
+        }
+        // Only a single logical unit is exposed.
+        if bytes[13] & 0x0f != 0 {
+            return None;
+        }
+        let cdb_len = bytes[14] as usize & 0x1f;
+        if cdb_len == 0 || cdb_len > 16 {
+            return None;
+        }
+        Some(Cbw {
+            tag: u32::from_le_bytes(bytes[4..8].try_into().unwrap()),
+            data_transfer_length: u32::from_le_bytes(bytes[8..12].try_into().unwrap()),
+            data_in: bytes[12] & CBW_FLAG_DATA_IN != 0,
+            cdb: bytes[15..15 + cdb_len].to_vec(),
+        })
+    }
+}
+
+/// Where the device is in the command, data and status sequence of the Bulk-Only Transport.
+enum Stage {
+    /// Waiting for a CBW on the bulk OUT endpoint.
+    Command,
+    /// Sending `data` from `offset` on the bulk IN endpoint.
+    DataIn { data: Vec<u8>, offset: usize },
+    /// Sending the `len` bytes of a read command on the bulk IN endpoint, `offset` bytes so far.
+    /// The data is read from the disk one transfer at a time.
+    Read { len: usize, offset: usize },
+    /// Receiving the data of a write command on the bulk OUT endpoint, `received` bytes so far.
+    /// The data is written to the disk one transfer at a time. When `write` is false, the command
+    /// failed and the data is discarded.
+    DataOut { received: usize, write: bool },
+    /// Sending the CSW on the bulk IN endpoint.
+    Status,
+}
+
+/// The mass storage function of an emulated device, exposing a single logical unit.
+pub struct MassStorage {
+    disk: ScsiDisk,
+    pending_in: PendingTransfers,
+    stage: Stage,
+    // The command being executed.
+    cbw: Cbw,
+    status: u8,
+    residue: u32,
+    in_halted: bool,
+    out_halted: bool,
+    // Set by an invalid CBW. The bulk endpoints then stay halted until the host runs the Reset
+    // Recovery of the Bulk-Only Transport 5.3.4: a bulk-only mass storage reset, then clearing
+    // the halt of both endpoints.
+    reset_required: bool,
+}
+
+impl MassStorage {
+    /// Creates a mass storage device backed by `disk`.
+    pub fn new_device(
+        job_queue: Arc<AsyncJobQueue>,
+        disk: Box<dyn DiskFile>,
+        read_only: bool,
+    ) -> std::io::Result<EmulatedDevice<MassStorage>> {
+        let function = MassStorage::new(job_queue, ScsiDisk::new(disk, read_only)?);
+        Ok(EmulatedDevice::new(descriptors(), function))
+    }
+
+    fn new(job_queue: Arc<AsyncJobQueue>, disk: ScsiDisk) -> MassStorage {
+        MassStorage {
+            disk,
+            pending_in: PendingTransfers::new(job_queue),
+            stage: Stage::Command,
+            cbw: Cbw {
+                tag: 0,
+                data_transfer_length: 0,
+                data_in: false,
+                cdb: Vec::new(),
+            },
+            status: CSW_STATUS_PASSED,
+            residue: 0,
+            in_halted: false,
+            out_halted: false,
+            reset_required: false,
+        }
+    }
+
+    fn handle_out(
+        &mut self,
+        transfer: Arc<XhciTransfer>,
+        buffer: ScatterGatherBuffer,
+    ) -> Result<()> {
+        let len = buffer.len().map_err(Error::BufferLen)?;
+        let mut bytes = vec![0u8; len];
+        let len = buffer.read(&mut bytes).map_err(Error::ReadBuffer)?;
+        bytes.truncate(len);
+
+        let status = self.receive(&mut bytes);
+        let len = if status == TransferStatus::Completed {
+            len
+        } else {
+            0
+        };
+        complete_transfer(&transfer, status, len)?;
+        self.send_pending()
+    }
+
+    // Handles the data of a bulk OUT transfer. Returns the status to complete the transfer with.
+    fn receive(&mut self, bytes: &mut [u8]) -> TransferStatus {
+        if self.out_halted {
+            return TransferStatus::Stalled;
+        }
+        match &mut self.stage {
+            Stage::Command => match Cbw::parse(bytes) {
+                Some(cbw) => self.execute(cbw),
+                None => {
+                    warn!("usb storage received an invalid CBW, halting until reset");
+                    self.in_halted = true;
+                    self.out_halted = true;
+                    self.reset_required = true;
+                    return TransferStatus::Stalled;
+                }
+            },
+            Stage::DataOut { received, write } => {
+                // Only the data needed by the command is written, the host may send more.
+                let needed = (self.cbw.data_transfer_length - self.residue) as usize;
+                let take = needed.saturating_sub(*received).min(bytes.len());
+                if *write
+                    && take > 0
+                    && self
+                        .disk
+                        .write(&self.cbw.cdb, *received, &mut bytes[..take])
+                        .is_err()
+                {
+                    *write = false;
+                    self.status = CSW_STATUS_FAILED;
+                    self.residue = self.cbw.data_transfer_length;
+                }
+                *received += bytes.len();
+                if *received >= self.cbw.data_transfer_length as usize {
+                    self.stage = Stage::Status;
+                }
+            }
+            _ => {
+                error!("usb storage received data while sending");
+                return TransferStatus::Error;
+            }
+        }
+        TransferStatus::Completed
+    }
+
+    fn execute(&mut self, cbw: Cbw) {
+        usb_debug!("usb storage executing command {:x?}", cbw.cdb);
+        let expected_len = cbw.data_transfer_length as usize;
+        self.status = CSW_STATUS_PASSED;
+        self.residue = cbw.data_transfer_length;
+        self.stage = match self.disk.execute(&cbw.cdb, expected_len) {
+            Ok(DataPhase::In(mut data)) if cbw.data_in || data.is_empty() => {
+                data.truncate(expected_len);
+                self.residue -= data.len() as u32;
+                data_stage(&cbw, data)
+            }
+            Ok(DataPhase::Read(len)) if cbw.data_in => {
+                self.residue -= len as u32;
+                if len == 0 {
+                    data_stage(&cbw, Vec::new())
+                } else {
+                    Stage::Read { len, offset: 0 }
+                }
+            }
+            Ok(DataPhase::Out(len)) if !cbw.data_in => {
+                self.residue -= len as u32;
+                if expected_len == 0 {
+                    Stage::Status
+                } else {
+                    Stage::DataOut {
+                        received: 0,
+                        write: true,
+                    }
+                }
+            }
+            Ok(_) => {
+                // The host and the command disagree on the direction of the data.
+                self.status = CSW_STATUS_PHASE_ERROR;
+                data_stage(&cbw, Vec::new())
+            }
+            Err(sense) => {
+                usb_debug!("usb storage command failed: {:?}", sense);
+                self.status = CSW_STATUS_FAILED;
+                data_stage(&cbw, Vec::new())
+            }
+        };
+        self.cbw = cbw;
+    }
+
+    // Returns whether a bulk IN transfer can be completed now.
+    fn can_send(&self) -> bool {
+        self.in_halted
+            || matches!(
+                self.stage,
+                Stage::DataIn { .. } | Stage::Read { .. } | Stage::Status
+            )
+    }
+
+    // Returns the data of the next bulk IN transfer, of at most `max_len` bytes, and the status to
+    // complete it with. Must only be called when `can_send` is true.
+    fn send(&mut self, max_len: usize) -> (TransferStatus, Vec<u8>) {
+        if self.in_halted {
+            return (TransferStatus::Stalled, Vec::new());
+        }
+        let data = match &mut self.stage {
+            Stage::DataIn { data, offset } => {
+                let end = min(data.len(), *offset + max_len);
+                let sent = data[*offset..end].to_vec();
+                *offset = end;
+                // A short transfer also ends the data stage.
+                if *offset == data.len() || sent.len() < max_len {
+                    self.stage = Stage::Status;
+                }
+                sent
+            }
+            Stage::Read { len, offset } => {
+                let mut data = vec![0u8; min(*len - *offset, max_len)];
+                if self.disk.read(&self.cbw.cdb, *offset, &mut data).is_err() {
+                    // End the data stage with what was sent so far.
+                    self.status = CSW_STATUS_FAILED;
+                    self.residue = self.cbw.data_transfer_length - *offset as u32;
+                    *len = *offset;
+                    data.clear();
+                }
+                *offset += data.len();
+                if *offset == *len || data.len() < max_len {
+                    self.stage = Stage::Status;
+                }
+                data
+            }
+            Stage::Status => {
+                let mut csw = Vec::with_capacity(13);
+                csw.extend_from_slice(&CSW_SIGNATURE.to_le_bytes());
+                csw.extend_from_slice(&self.cbw.tag.to_le_bytes());
+                csw.extend_from_slice(&self.residue.to_le_bytes());
+                csw.push(self.status);
+                self.stage = Stage::Command;
+                csw
+            }
+            _ => {
+                error!("usb storage has nothing to send");
+                return (TransferStatus::Error, Vec::new());
+            }
+        };
+        (TransferStatus::Completed, data)
+    }
+
+    // Completes pending IN transfers with whatever the current stage has to send.
+    fn send_pending(&mut self) -> Result<()> {
+        while self.can_send() {
+            let (transfer, buffer) = match self.pending_in.pop() {
+                Some(t) => t,
+                None => break,
+            };
+            let max_len = buffer.len().map_err(Error::BufferLen)?;
+            let (status, data) = self.send(max_len);
+            let len = buffer.write(&data).map_err(Error::WriteBuffer)?;
+            complete_transfer(&transfer, status, len)?;
+        }
+        Ok(())
+    }
+}
+
+impl UsbFunction for MassStorage {
+    fn control_request(&mut self, setup: &UsbRequestSetup, _data: &[u8]) -> Option<Vec<u8>> {
+        if setup.get_type() != ControlRequestType::Class
+            || setup.get_recipient() != ControlRequestRecipient::Interface
+        {
+            return None;
+        }
+        match setup.request {
+            BULK_ONLY_MASS_STORAGE_RESET => {
+                usb_debug!("usb storage bulk-only reset");
+                // The halt of the endpoints is left alone, the host clears it next.
+                self.stage = Stage::Command;
+                self.reset_required = false;
+                Some(Vec::new())
+            }
+            GET_MAX_LUN => Some(vec![0]),
+            _ => None,
+        }
+    }
+
+    fn submit_transfer(
+        &mut self,
+        transfer: Arc<XhciTransfer>,
+        buffer: ScatterGatherBuffer,
+    ) -> Result<()> {
+        match (transfer.get_endpoint_number(), transfer.get_transfer_dir()) {
+            (BULK_OUT_ENDPOINT, TransferDirection::Out) => self.handle_out(transfer, buffer),
+            (BULK_IN_ENDPOINT, TransferDirection::In) => {
+                self.pending_in.push(transfer, buffer)?;
+                self.send_pending()
+            }
+            _ => {
+                warn!("usb storage transfer on unknown endpoint");
+                complete_transfer(&transfer, TransferStatus::Error, 0)
+            }
+        }
+    }
+
+    fn reset(&mut self) -> Result<()> {
+        self.stage = Stage::Command;
+        self.in_halted = false;
+        self.out_halted = false;
+        self.reset_required = false;
+        self.pending_in.cancel_all()
+    }
+
+    fn set_halt(&mut self, endpoint: u8, halt: bool) -> Result<()> {
+        if !halt && self.reset_required {
+            usb_debug!("usb storage keeps endpoint {} halted until reset", endpoint);
+            return Ok(());
+        }
+        match endpoint {
+            BULK_IN_ENDPOINT => self.in_halted = halt,
+            BULK_OUT_ENDPOINT => self.out_halted = halt,
+            _ => warn!("usb storage halting unknown endpoint {}", endpoint),
+        }
+        // Pending IN transfers stall, or resume, with the endpoint.
+        self.send_pending()
+    }
+
+    fn halted(&self, endpoint: u8) -> bool {
+        match endpoint {
+            BULK_IN_ENDPOINT => self.in_halted,
+            BULK_OUT_ENDPOINT => self.out_halted,
+            _ => false,
+        }
+    }
+}
+
+// Returns the stage following the command in `cbw`, which produced `data`. The host runs the data
+// stage it announced even if the command has less data or failed, in which case nothing useful is
+// transferred.
+fn data_stage(cbw: &Cbw, data: Vec<u8>) -> Stage {
+    if cbw.data_transfer_length == 0 {
+        Stage::Status
+    } else if cbw.data_in {
+        Stage::DataIn { data, offset: 0 }
+    } else {
+        Stage::DataOut {
+            received: 0,
+            write: false,
+        }
+    }
+}
+
+fn descriptors() -> UsbDescriptors {
+    let device = DeviceDescriptor {
+        bcdUSB: 0x0200,
+        bMaxPacketSize0: 64,
+        idVendor: EMULATED_VENDOR_ID,
+        idProduct: PRODUCT_ID,
+        bcdDevice: 0x0100,
+        iManufacturer: STRING_MANUFACTURER,
+        iProduct: STRING_PRODUCT,
+        iSerialNumber: STRING_SERIAL_NUMBER,
+        bNumConfigurations: 1,
+        ..Default::default()
+    };
+    let config = ConfigDescriptor {
+        bNumInterfaces: 1,
+        // Self powered.
+        bmAttributes: 0xc0,
+        ..Default::default()
+    };
+    let interface = InterfaceDescriptor {
+        bNumEndpoints: 2,
+        bInterfaceClass: CLASS_MASS_STORAGE,
+        bInterfaceSubClass: SUBCLASS_SCSI_TRANSPARENT,
+        bInterfaceProtocol: PROTOCOL_BULK_ONLY,
+        ..Default::default()
+    };
+    let bulk_in = EndpointDescriptor {
+        bEndpointAddress: 0x80 | BULK_IN_ENDPOINT,
+        bmAttributes: 0x02,
+        wMaxPacketSize: MAX_PACKET_SIZE,
+        bInterval: 0,
+    };
+    let bulk_out = EndpointDescriptor {
+        bEndpointAddress: BULK_OUT_ENDPOINT,
+        ..bulk_in
+    };
+    UsbDescriptors {
+        device,
+        config: config_descriptor(
+            config,
+            &[
+                descriptor(DescriptorType::Interface as u8, &interface),
+                descriptor(DescriptorType::Endpoint as u8, &bulk_in),
+                descriptor(DescriptorType::Endpoint as u8, &bulk_out),
+            ],
+        ),
+        strings: vec![
+            "crosvm".to_string(),
+            "USB Mass Storage".to_string(),
+            "000000000001".to_string(),
+        ],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+    use std::io::Read;
+    use std::io::Seek;
+    use std::io::SeekFrom;
+    use std::io::Write;
+
+    use tempfile::tempfile;
+
+    use super::*;
+    use crate::usb::emulated::scsi::BLOCK_SIZE;
+    use crate::utils::EventLoop;
+
+    const TEST_UNIT_READY: u8 = 0x00;
+    const READ_10: u8 = 0x28;
+    const WRITE_10: u8 = 0x2a;
+
+    // Runs `test` on a mass storage function backed by a disk of `blocks` blocks, each filled
+    // with its index.
+    fn with_storage(blocks: u64, test: impl FnOnce(&mut MassStorage, &mut File)) {
+        let (event_loop, join_handle) = EventLoop::start("test".to_string(), None).unwrap();
+        let job_queue = AsyncJobQueue::init(&event_loop).unwrap();
+        let mut f = tempfile().unwrap();
+        for i in 0..blocks {
+            f.write_all(&[i as u8; BLOCK_SIZE as usize]).unwrap();
+        }
+        let disk = ScsiDisk::new(Box::new(f.try_clone().unwrap()), false).unwrap();
+        test(&mut MassStorage::new(job_queue, disk), &mut f);
+        event_loop.stop();
+        join_handle.join().unwrap();
+    }
+
+    fn cbw(tag: u32, data_transfer_length: u32, data_in: bool, cdb: &[u8]) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(CBW_LEN);
+        bytes.extend_from_slice(&CBW_SIGNATURE.to_le_bytes());
+        bytes.extend_from_slice(&tag.to_le_bytes());
+        bytes.extend_from_slice(&data_transfer_length.to_le_bytes());
+        bytes.push(if data_in { CBW_FLAG_DATA_IN } else { 0 });
+        bytes.push(0);
+        bytes.push(cdb.len() as u8);
+        bytes.extend_from_slice(cdb);
+        bytes.resize(CBW_LEN, 0);
+        bytes
+    }
+
+    fn rw_10(opcode: u8, lba: u32, blocks: u16) -> Vec<u8> {
+        let mut cdb = vec![opcode, 0];
+        cdb.extend_from_slice(&lba.to_be_bytes());
+        cdb.push(0);
+        cdb.extend_from_slice(&blocks.to_be_bytes());
+        cdb.push(0);
+        cdb
+    }
+
+    fn send_cbw(storage: &mut MassStorage, mut cbw: Vec<u8>) -> TransferStatus {
+        storage.receive(&mut cbw)
+    }
+
+    // Reads the CSW, checking its signature, and returns its tag, residue and status.
+    fn read_csw(storage: &mut MassStorage) -> (u32, u32, u8) {
+        assert!(storage.can_send());
+        let (status, csw) = storage.send(13);
+        assert_eq!(status, TransferStatus::Completed);
+        assert_eq!(csw.len(), 13);
+        assert_eq!(&csw[0..4], &CSW_SIGNATURE.to_le_bytes());
+        (
+            u32::from_le_bytes(csw[4..8].try_into().unwrap()),
+            u32::from_le_bytes(csw[8..12].try_into().unwrap()),
+            csw[12],
+        )
+    }
+
+    #[test]
+    fn read_command() {
+        with_storage(8, |storage, _f| {
+            // Nothing to send before a command.
+            assert!(!storage.can_send());
+            let cbw = cbw(1, 1024, true, &rw_10(READ_10, 2, 2));
+            assert_eq!(send_cbw(storage, cbw), TransferStatus::Completed);
+
+            let (status, data) = storage.send(512);
+            assert_eq!(status, TransferStatus::Completed);
+            assert_eq!(data, vec![2; 512]);
+            let (status, data) = storage.send(4096);
+            assert_eq!(status, TransferStatus::Completed);
+            assert_eq!(data, vec![3; 512]);
+
+            assert_eq!(read_csw(storage), (1, 0, CSW_STATUS_PASSED));
+            assert!(!storage.can_send());
+        });
+    }
+
+    #[test]
+    fn write_command() {
+        with_storage(8, |storage, f| {
+            let cbw = cbw(2, 1024, false, &rw_10(WRITE_10, 1, 2));
+            assert_eq!(send_cbw(storage, cbw), TransferStatus::Completed);
+            // The CSW waits for the data stage.
+            assert!(!storage.can_send());
+            assert_eq!(storage.receive(&mut [0xaa; 512]), TransferStatus::Completed);
+            assert!(!storage.can_send());
+            assert_eq!(storage.receive(&mut [0xbb; 512]), TransferStatus::Completed);
+            assert_eq!(read_csw(storage), (2, 0, CSW_STATUS_PASSED));
+
+            let mut blocks = [0u8; 1024];
+            f.seek(SeekFrom::Start(BLOCK_SIZE)).unwrap();
+            f.read_exact(&mut blocks).unwrap();
+            assert!(blocks[..512].iter().all(|b| *b == 0xaa));
+            assert!(blocks[512..].iter().all(|b| *b == 0xbb));
+        });
+    }
+
+    #[test]
+    fn failed_command_pads_data_stage() {
+        with_storage(8, |storage, _f| {
+            let cbw = cbw(3, 1024, true, &rw_10(READ_10, 7, 2));
+            assert_eq!(send_cbw(storage, cbw), TransferStatus::Completed);
+            // The data stage announced by the host ends with a short transfer.
+            let (status, data) = storage.send(1024);
+            assert_eq!(status, TransferStatus::Completed);
+            assert!(data.is_empty());
+            assert_eq!(read_csw(storage), (3, 1024, CSW_STATUS_FAILED));
+        });
+    }
+
+    #[test]
+    fn phase_error() {
+        with_storage(8, |storage, f| {
+            // The host announces data to send for a command that reads.
+            let cbw = cbw(4, 512, false, &rw_10(READ_10, 0, 1));
+            assert_eq!(send_cbw(storage, cbw), TransferStatus::Completed);
+            assert!(!storage.can_send());
+            assert_eq!(storage.receive(&mut [0xcc; 512]), TransferStatus::Completed);
+            let (tag, _, status) = read_csw(storage);
+            assert_eq!((tag, status), (4, CSW_STATUS_PHASE_ERROR));
+
+            // The data was discarded.
+            let mut block = [0u8; 512];
+            f.seek(SeekFrom::Start(0)).unwrap();
+            f.read_exact(&mut block).unwrap();
+            assert!(block.iter().all(|b| *b == 0));
+
+            // The host expects data from a command that writes.
+            let cbw = cbw(5, 512, true, &rw_10(WRITE_10, 0, 1));
+            assert_eq!(send_cbw(storage, cbw), TransferStatus::Completed);
+            let (status, data) = storage.send(512);
+            assert_eq!(status, TransferStatus::Completed);
+            assert!(data.is_empty());
+            let (tag, _, status) = read_csw(storage);
+            assert_eq!((tag, status), (5, CSW_STATUS_PHASE_ERROR));
+        });
+    }
+
+    #[test]
+    fn invalid_cbw_stalls_until_reset_recovery() {
+        with_storage(8, |storage, _f| {
+            let mut bad = cbw(6, 0, false, &[TEST_UNIT_READY; 6]);
+            bad[0] ^= 0xff;
+            assert_eq!(send_cbw(storage, bad), TransferStatus::Stalled);
+            assert!(storage.halted(BULK_IN_ENDPOINT));
+            assert!(storage.halted(BULK_OUT_ENDPOINT));
+
+            // Both endpoints stall, even for a valid CBW.
+            assert!(storage.can_send());
+            assert_eq!(storage.send(13).0, TransferStatus::Stalled);
+            let valid = cbw(7, 0, false, &[TEST_UNIT_READY; 6]);
+            assert_eq!(send_cbw(storage, valid.clone()), TransferStatus::Stalled);
+
+            // Clearing the halts is not enough without a bulk-only reset first.
+            storage.set_halt(BULK_IN_ENDPOINT, false).unwrap();
+            storage.set_halt(BULK_OUT_ENDPOINT, false).unwrap();
+            assert!(storage.halted(BULK_IN_ENDPOINT));
+            assert!(storage.halted(BULK_OUT_ENDPOINT));
+
+            let reset = UsbRequestSetup::new(0x21, BULK_ONLY_MASS_STORAGE_RESET, 0, 0, 0);
+            assert_eq!(storage.control_request(&reset, &[]), Some(Vec::new()));
+            // The reset leaves the halts to the host.
+            assert!(storage.halted(BULK_IN_ENDPOINT));
+            assert_eq!(send_cbw(storage, valid.clone()), TransferStatus::Stalled);
+            storage.set_halt(BULK_IN_ENDPOINT, false).unwrap();
+            storage.set_halt(BULK_OUT_ENDPOINT, false).unwrap();
+            assert!(!storage.halted(BULK_IN_ENDPOINT));
+            assert!(!storage.halted(BULK_OUT_ENDPOINT));
+
+            assert!(!storage.can_send());
+            assert_eq!(send_cbw(storage, valid), TransferStatus::Completed);
+            assert_eq!(read_csw(storage), (7, 0, CSW_STATUS_PASSED));
+        });
+    }
+}
@@ -0,0 +1,497 @@
+// Copyright 2023 The ChromiumOS Authors
+// Use of this source code is governed by a BSD-style license that can be
+// found in the LICENSE file.
+
+//! Emulated USB devices. Unlike host devices, these are implemented entirely in crosvm and don't
+//! require any hardware on the host.
+
+pub mod hid;
+pub mod mass_storage;
+pub mod scsi;
+
+use std::collections::VecDeque;
+use std::mem;
+use std::sync::Arc;
+
+use base::error;
+use base::warn;
+use usb_util::ConfigDescriptor;
+use usb_util::ControlRequestDataPhaseTransferDirection;
+use usb_util::ControlRequestRecipient;
+use usb_util::DescriptorType;
+use usb_util::DeviceDescriptor;
+use usb_util::DeviceSpeed;
+use usb_util::StandardControlRequest;
+use usb_util::TransferStatus;
+use usb_util::UsbRequestSetup;
+use zerocopy::AsBytes;
+
+pub use self::hid::Hid;
+pub use self::hid::HidEventHandler;
+pub use self::mass_storage::MassStorage;
+use crate::usb::host_backend::error::Error;
+use crate::usb::host_backend::error::Result;
+use crate::usb::host_backend::host_device::ControlEndpointState;
+use crate::usb::xhci::scatter_gather_buffer::ScatterGatherBuffer;
+use crate::usb::xhci::xhci_backend_device::BackendType;
+use crate::usb::xhci::xhci_backend_device::UsbDeviceAddress;
+use crate::usb::xhci::xhci_backend_device::XhciBackendDevice;
+use crate::usb::xhci::xhci_transfer::XhciTransfer;
+use crate::usb::xhci::xhci_transfer::XhciTransferState;
+use crate::usb::xhci::xhci_transfer::XhciTransferType;
+use crate::utils::AsyncJobQueue;
+
+/// Vendor ID reported by emulated devices.
+pub const EMULATED_VENDOR_ID: u16 = 0x18d1;
+
+/// Index of the manufacturer string in `UsbDescriptors::strings`.
+pub const STRING_MANUFACTURER: u8 = 1;
+/// Index of the product string in `UsbDescriptors::strings`.
+pub const STRING_PRODUCT: u8 = 2;
+/// Index of the serial number string in `UsbDescriptors::strings`.
+pub const STRING_SERIAL_NUMBER: u8 = 3;
+
+// Descriptor types missing from `usb_util::DescriptorType`.
+const DESCRIPTOR_TYPE_STRING: u8 = 0x03;
+const DESCRIPTOR_TYPE_DEVICE_QUALIFIER: u8 = 0x06;
+
+// US English, the only language supported for string descriptors.
+const LANGID_EN_US: u16 = 0x0409;
+
+// Feature selector of Set and Clear Feature requests to endpoints.
+const FEATURE_ENDPOINT_HALT: u16 = 0;
+const ENDPOINT_NUMBER_MASK: u8 = 0x0f;
+
+const CONFIGURATION_VALUE: u8 = 1;
+
+/// Descriptors exposed by an emulated device through standard Get Descriptor requests.
+pub struct UsbDescriptors {
+    pub device: DeviceDescriptor,
+    /// The configuration descriptor, followed by all its interface, endpoint and class specific
+    /// descriptors, as built by `config_descriptor`.
+    pub config: Vec<u8>,
+    /// String descriptors, starting with index 1.
+    pub strings: Vec<String>,
+}
+
+/// Serializes `body` with a standard descriptor header of type `descriptor_type`.
+pub fn descriptor<T: AsBytes + ?Sized>(descriptor_type: u8, body: &T) -> Vec<u8> {
+    let body = body.as_bytes();
+    let mut bytes = vec![(body.len() + 2) as u8, descriptor_type];
+    bytes.extend_from_slice(body);
+    bytes
+}
+
+/// Builds the full configuration descriptor made of `config` followed by `descriptors`, filling
+/// in its total length.
+pub fn config_descriptor(mut config: ConfigDescriptor, descriptors: &[Vec<u8>]) -> Vec<u8> {
+    let len = descriptors.iter().map(Vec::len).sum::<usize>() + mem::size_of_val(&config) + 2;
+    config.wTotalLength = len as u16;
+    config.bConfigurationValue = CONFIGURATION_VALUE;
+    let mut bytes = descriptor(DescriptorType::Configuration as u8, &config);
+    for d in descriptors {
+        bytes.extend_from_slice(d);
+    }
+    bytes
+}
+
+/// Completes `transfer` with `status` and `bytes` transferred.
+pub fn complete_transfer(
+    transfer: &XhciTransfer,
+    status: TransferStatus,
+    bytes: usize,
+) -> Result<()> {
+    transfer
+        .on_transfer_complete(&status, bytes as u32)
+        .map_err(Error::TransferComplete)
+}
+
+/// Transfers that can't be completed until the device has data to send, such as bulk or
+/// interrupt IN transfers. They stay cancellable by the xHCI controller while queued.
+pub struct PendingTransfers {
+    job_queue: Arc<AsyncJobQueue>,
+    transfers: VecDeque<(Arc<XhciTransfer>, ScatterGatherBuffer)>,
+}
+
+impl PendingTransfers {
+    pub fn new(job_queue: Arc<AsyncJobQueue>) -> PendingTransfers {
+        PendingTransfers {
+            job_queue,
+            transfers: VecDeque::new(),
+        }
+    }
+
+    /// Queues `transfer`, which will be filled through `buffer` once it is popped.
+    pub fn push(&mut self, transfer: Arc<XhciTransfer>, buffer: ScatterGatherBuffer) -> Result<()> {
+        let mut state = transfer.state().lock();
+        match mem::replace(&mut *state, XhciTransferState::Cancelled) {
+            XhciTransferState::Created => {
+                // The controller cancels transfers while holding its own locks, so the
+                // completion is deferred to the job queue.
+                let weak_transfer = Arc::downgrade(&transfer);
+                let job_queue = self.job_queue.clone();
+                let cancel_callback = Box::new(move || {
+                    let weak_transfer = weak_transfer.clone();
+                    let result = job_queue.queue_job(move || {
+                        if let Some(transfer) = weak_transfer.upgrade() {
+                            if let Err(e) = cancel_transfer(&transfer) {
+                                error!("failed to cancel pending transfer: {}", e);
+                            }
+                        }
+                    });
+                    if let Err(e) = result {
+                        error!("failed to queue transfer cancellation: {}", e);
+                    }
+                });
+                *state = XhciTransferState::Submitted { cancel_callback };
+            }
+            XhciTransferState::Cancelled => {
+                warn!("Transfer is already cancelled");
+                drop(state);
+                return complete_transfer(&transfer, TransferStatus::Cancelled, 0);
+            }
+            _ => {
+                error!("xhci trasfer state is invalid");
+                return Err(Error::BadXhciTransferState);
+            }
+        }
+        drop(state);
+        self.transfers.push_back((transfer, buffer));
+        Ok(())
+    }
+
+    /// Returns the oldest transfer that has not been cancelled, if any. The caller is responsible
+    /// for completing it.
+    pub fn pop(&mut self) -> Option<(Arc<XhciTransfer>, ScatterGatherBuffer)> {
+        while let Some((transfer, buffer)) = self.transfers.pop_front() {
+            let mut state = transfer.state().lock();
+            if let XhciTransferState::Submitted { .. } = *state {
+                *state = XhciTransferState::Completed;
+                drop(state);
+                return Some((transfer, buffer));
+            }
+            // The state lock must be released before the transfer is dropped.
+            drop(state);
+        }
+        None
+    }
+
+    /// Completes all queued transfers as cancelled.
+    pub fn cancel_all(&mut self) -> Result<()> {
+        for (transfer, _) in mem::take(&mut self.transfers) {
+            cancel_transfer(&transfer)?;
+        }
+        Ok(())
+    }
+}
+
+fn cancel_transfer(transfer: &XhciTransfer) -> Result<()> {
+    let mut state = transfer.state().lock();
+    match *state {
+        XhciTransferState::Submitted { .. } | XhciTransferState::Cancelling => {
+            *state = XhciTransferState::Cancelled;
+            drop(state);
+            complete_transfer(transfer, TransferStatus::Cancelled, 0)
+        }
+        // Already completed, or cancelled through another path.
+        _ => Ok(()),
+    }
+}
+
+/// The device specific part of an emulated device. Standard requests on the default control
+/// endpoint are handled by `EmulatedDevice`.
+pub trait UsbFunction: Send {
+    /// Handles a control request that isn't a standard device request, such as class or vendor
+    /// requests. `data` holds the data stage of host to device requests. Returns the data to send
+    /// back for device to host requests, or `None` if the request isn't supported.
+    fn control_request(&mut self, setup: &UsbRequestSetup, data: &[u8]) -> Option<Vec<u8>>;
+    /// Handles a transfer on a non-default endpoint, whose data is in `buffer`.
+    fn submit_transfer(
+        &mut self,
+        transfer: Arc<XhciTransfer>,
+        buffer: ScatterGatherBuffer,
+    ) -> Result<()>;
+    /// Resets the function, cancelling any pending transfer.
+    fn reset(&mut self) -> Result<()>;
+    /// Halts `endpoint`, or clears its halt, on a Set or Clear Feature ENDPOINT_HALT request.
+    /// Functions whose endpoints never halt can ignore it.
+    fn set_halt(&mut self, _endpoint: u8, _halt: bool) -> Result<()> {
+        Ok(())
+    }
+    /// Returns whether `endpoint` is halted, reported through Get Status.
+    fn halted(&self, _endpoint: u8) -> bool {
+        false
+    }
+}
+
+/// An emulated USB 2.0 high-speed device.
+pub struct EmulatedDevice<F: UsbFunction> {
+    descriptors: UsbDescriptors,
+    function: F,
+    ctl_ep_state: ControlEndpointState,
+    control_request_setup: UsbRequestSetup,
+    executed: bool,
+    configuration: u8,
+}
+
+impl<F: UsbFunction> EmulatedDevice<F> {
+    pub fn new(descriptors: UsbDescriptors, function: F) -> EmulatedDevice<F> {
+        EmulatedDevice {
+            descriptors,
+            function,
+            ctl_ep_state: ControlEndpointState::SetupStage,
+            control_request_setup: UsbRequestSetup::new(0, 0, 0, 0, 0),
+            executed: false,
+            configuration: 0,
+        }
+    }
+
+    fn get_descriptor(&self) -> Option<Vec<u8>> {
+        let setup = &self.control_request_setup;
+        let descriptor_type = (setup.value >> 8) as u8;
+        let index = setup.value as u8;
+        match descriptor_type {
+            t if t == DescriptorType::Device as u8 => Some(descriptor(t, &self.descriptors.device)),
+            t if t == DescriptorType::Configuration as u8 && index == 0 => {
+                Some(self.descriptors.config.clone())
+            }
+            DESCRIPTOR_TYPE_STRING if index == 0 => {
+                Some(descriptor(DESCRIPTOR_TYPE_STRING, &LANGID_EN_US))
+            }
+            DESCRIPTOR_TYPE_STRING => {
+                let string = self.descriptors.strings.get(index as usize - 1)?;
+                let utf16: Vec<u16> = string.encode_utf16().collect();
+                Some(descriptor(DESCRIPTOR_TYPE_STRING, utf16.as_slice()))
+            }
+            DESCRIPTOR_TYPE_DEVICE_QUALIFIER => {
+                let device = &self.descriptors.device;
+                let bcd_usb = device.bcdUSB;
+                let mut qualifier = bcd_usb.to_le_bytes().to_vec();
+                qualifier.extend_from_slice(&[
+                    device.bDeviceClass,
+                    device.bDeviceSubClass,
+                    device.bDeviceProtocol,
+                    device.bMaxPacketSize0,
+                    device.bNumConfigurations,
+                    0,
+                ]);
+                Some(descriptor(
+                    DESCRIPTOR_TYPE_DEVICE_QUALIFIER,
+                    qualifier.as_slice(),
+                ))
+            }
+            _ => None,
+        }
+    }
+
+    fn set_endpoint_halt(&mut self, halt: bool) -> Option<Vec<u8>> {
+        let endpoint = self.control_request_setup.index as u8 & ENDPOINT_NUMBER_MASK;
+        match self.function.set_halt(endpoint, halt) {
+            Ok(()) => Some(Vec::new()),
+            Err(e) => {
+                error!("failed to set halt of endpoint {}: {}", endpoint, e);
+                None
+            }
+        }
+    }
+
+    // Handles standard requests. Returns `None` for requests that should be passed on to the
+    // function.
+    fn standard_request(&mut self) -> Option<Option<Vec<u8>>> {
+        let setup = self.control_request_setup;
+        let request = setup.get_standard_request()?;
+        let response = match (request, setup.get_recipient()) {
+            (StandardControlRequest::GetDescriptor, ControlRequestRecipient::Device) => {
+                self.get_descriptor()
+            }
+            (StandardControlRequest::SetAddress, ControlRequestRecipient::Device) => {
+                self.set_address(setup.value as u32);
+                Some(Vec::new())
+            }
+            (StandardControlRequest::GetConfiguration, ControlRequestRecipient::Device) => {
+                Some(vec![self.configuration])
+            }
+            (StandardControlRequest::SetConfiguration, ControlRequestRecipient::Device) => {
+                match setup.value as u8 {
+                    c @ (0 | CONFIGURATION_VALUE) => {
+                        usb_debug!("emulated device set configuration {}", c);
+                        self.configuration = c;
+                        Some(Vec::new())
+                    }
+                    _ => None,
+                }
+            }
+            // Only the default alternate setting is supported.
+            (StandardControlRequest::GetInterface, ControlRequestRecipient::Interface) => {
+                Some(vec![0])
+            }
+            (StandardControlRequest::SetInterface, ControlRequestRecipient::Interface)
+                if setup.value == 0 =>
+            {
+                Some(Vec::new())
+            }
+            (StandardControlRequest::GetStatus, ControlRequestRecipient::Endpoint) => {
+                let halted = self
+                    .function
+                    .halted(setup.index as u8 & ENDPOINT_NUMBER_MASK);
+                Some(vec![halted as u8, 0])
+            }
+            (StandardControlRequest::GetStatus, _) => Some(vec![0, 0]),
+            (StandardControlRequest::ClearFeature, ControlRequestRecipient::Endpoint)
+                if setup.value == FEATURE_ENDPOINT_HALT =>
+            {
+                self.set_endpoint_halt(false)
+            }
+            (StandardControlRequest::SetFeature, ControlRequestRecipient::Endpoint)
+                if setup.value == FEATURE_ENDPOINT_HALT =>
+            {
+                self.set_endpoint_halt(true)
+            }
+            // Remote wakeup is not supported, so other features have no effect.
+            (StandardControlRequest::ClearFeature, _) | (StandardControlRequest::SetFeature, _) => {
+                Some(Vec::new())
+            }
+            // Everything else, such as class specific descriptors, goes to the function.
+            _ => return None,
+        };
+        Some(response)
+    }
+
+    fn execute_control_transfer(
+        &mut self,
+        transfer: &XhciTransfer,
+        buffer: Option<ScatterGatherBuffer>,
+    ) -> Result<()> {
+        let setup = self.control_request_setup;
+        let direction = setup.get_direction();
+        let mut data = vec![0u8; setup.length as usize];
+        if direction == ControlRequestDataPhaseTransferDirection::HostToDevice {
+            if let Some(buffer) = &buffer {
+                let len = buffer.read(&mut data).map_err(Error::ReadBuffer)?;
+                data.truncate(len);
+            }
+        }
+
+        let response = match self.standard_request() {
+            Some(response) => response,
+            None => self.function.control_request(&setup, &data),
+        };
+        let response = match response {
+            Some(response) => response,
+            None => {
+                warn!("unsupported control request {:?}", setup);
+                return complete_transfer(transfer, TransferStatus::Error, 0);
+            }
+        };
+
+        let mut bytes = 0;
+        if direction == ControlRequestDataPhaseTransferDirection::DeviceToHost {
+            if let Some(buffer) = &buffer {
+                let len = response.len().min(setup.length as usize);
+                bytes = buffer.write(&response[..len]).map_err(Error::WriteBuffer)?;
+            }
+        }
+        complete_transfer(transfer, TransferStatus::Completed, bytes)
+    }
+
+    fn handle_control_transfer(&mut self, transfer: XhciTransfer) -> Result<()> {
+        let transfer_type = transfer
+            .get_transfer_type()
+            .map_err(Error::GetXhciTransferType)?;
+        match transfer_type {
+            XhciTransferType::SetupStage(setup) => {
+                if self.ctl_ep_state != ControlEndpointState::SetupStage {
+                    error!("Control endpoint is in an inconsistant state");
+                    return Ok(());
+                }
+                usb_debug!("setup stage setup buffer: {:?}", setup);
+                self.control_request_setup = setup;
+                complete_transfer(&transfer, TransferStatus::Completed, 0)?;
+                self.ctl_ep_state = ControlEndpointState::DataStage;
+            }
+            XhciTransferType::DataStage(buffer) => {
+                if self.ctl_ep_state != ControlEndpointState::DataStage {
+                    error!("Control endpoint is in an inconsistant state");
+                    return Ok(());
+                }
+                self.execute_control_transfer(&transfer, Some(buffer))?;
+                self.executed = true;
+                self.ctl_ep_state = ControlEndpointState::StatusStage;
+            }
+            XhciTransferType::StatusStage => {
+                if self.ctl_ep_state == ControlEndpointState::SetupStage {
+                    error!("Control endpoint is in an inconsistant state");
+                    return Ok(());
+                }
+                if self.executed {
+                    complete_transfer(&transfer, TransferStatus::Completed, 0)?;
+                } else {
+                    self.execute_control_transfer(&transfer, None)?;
+                }
+                self.executed = false;
+                self.ctl_ep_state = ControlEndpointState::SetupStage;
+            }
+            _ => {
+                error!(
+                    "Non control {} transfer sent to control endpoint.",
+                    transfer_type,
+                );
+                complete_transfer(&transfer, TransferStatus::Completed, 0)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<F: UsbFunction> XhciBackendDevice for EmulatedDevice<F> {
+    fn get_backend_type(&self) -> BackendType {
+        BackendType::Usb2
+    }
+
+    fn get_vid(&self) -> u16 {
+        self.descriptors.device.idVendor
+    }
+
+    fn get_pid(&self) -> u16 {
+        self.descriptors.device.idProduct
+    }
+
+    fn submit_transfer(&mut self, transfer: XhciTransfer) -> Result<()> {
+        if transfer.get_endpoint_number() == 0 {
+            return self.handle_control_transfer(transfer);
+        }
+        match transfer
+            .get_transfer_type()
+            .map_err(Error::GetXhciTransferType)?
+        {
+            XhciTransferType::Normal(buffer) => {
+                self.function.submit_transfer(Arc::new(transfer), buffer)
+            }
+            XhciTransferType::Noop => complete_transfer(&transfer, TransferStatus::Completed, 0),
+            transfer_type => {
+                warn!("unsupported {} transfer on emulated device", transfer_type);
+                complete_transfer(&transfer, TransferStatus::Error, 0)
+            }
+        }
+    }
+
+    fn set_address(&mut self, _address: UsbDeviceAddress) {
+        // The address is managed by the xHCI controller, see set address command ring trb.
+        usb_debug!(
+            "Set address control transfer is received with address: {}",
+            _address
+        );
+    }
+
+    fn reset(&mut self) -> Result<()> {
+        usb_debug!("resetting emulated device");
+        self.ctl_ep_state = ControlEndpointState::SetupStage;
+        self.executed = false;
+        self.configuration = 0;
+        self.function.reset()
+    }
+
+    fn get_speed(&self) -> Option<DeviceSpeed> {
+        Some(DeviceSpeed::High)
+    }
+}
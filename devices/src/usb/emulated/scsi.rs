@@ -0,0 +1,418 @@
+// Copyright 2023 The ChromiumOS Authors
+// Use of this source code is governed by a BSD-style license that can be
+// found in the LICENSE file.
+
+//! Minimal SCSI direct-access block device, implementing the subset of SPC/SBC commands used by
+//! the guest USB storage stacks over the transparent command set.
+
+use std::cmp::min;
+use std::convert::TryInto;
+
+use base::error;
+use data_model::VolatileSlice;
+use disk::DiskFile;
+
+/// Size of a logical block, in bytes.
+pub const BLOCK_SIZE: u64 = 512;
+
+const TEST_UNIT_READY: u8 = 0x00;
+const REQUEST_SENSE: u8 = 0x03;
+const INQUIRY: u8 = 0x12;
+const MODE_SENSE_6: u8 = 0x1a;
+const START_STOP_UNIT: u8 = 0x1b;
+const PREVENT_ALLOW_MEDIUM_REMOVAL: u8 = 0x1e;
+const READ_FORMAT_CAPACITIES: u8 = 0x23;
+const READ_CAPACITY_10: u8 = 0x25;
+const READ_10: u8 = 0x28;
+const WRITE_10: u8 = 0x2a;
+const VERIFY_10: u8 = 0x2f;
+const SYNCHRONIZE_CACHE_10: u8 = 0x35;
+const MODE_SENSE_10: u8 = 0x5a;
+const READ_16: u8 = 0x88;
+const WRITE_16: u8 = 0x8a;
+const SERVICE_ACTION_IN_16: u8 = 0x9e;
+
+const READ_CAPACITY_16_SERVICE_ACTION: u8 = 0x10;
+
+/// Sense data reported through REQUEST SENSE after a command fails.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Sense {
+    pub key: u8,
+    pub asc: u8,
+    pub ascq: u8,
+}
+
+impl Sense {
+    pub const NO_SENSE: Sense = Sense::new(0x00, 0x00, 0x00);
+    pub const UNRECOVERED_READ_ERROR: Sense = Sense::new(0x03, 0x11, 0x00);
+    pub const WRITE_ERROR: Sense = Sense::new(0x03, 0x0c, 0x00);
+    pub const INVALID_COMMAND: Sense = Sense::new(0x05, 0x20, 0x00);
+    pub const LBA_OUT_OF_RANGE: Sense = Sense::new(0x05, 0x21, 0x00);
+    pub const INVALID_FIELD_IN_CDB: Sense = Sense::new(0x05, 0x24, 0x00);
+    pub const WRITE_PROTECTED: Sense = Sense::new(0x07, 0x27, 0x00);
+
+    const fn new(key: u8, asc: u8, ascq: u8) -> Sense {
+        Sense { key, asc, ascq }
+    }
+}
+
+pub type Result<T> = std::result::Result<T, Sense>;
+
+/// Data phase required by a command.
+#[derive(Debug, PartialEq, Eq)]
+pub enum DataPhase {
+    /// The command has completed and the data, possibly empty, must be sent to the host.
+    In(Vec<u8>),
+    /// The command reads this many bytes from the disk, to be fetched with `ScsiDisk::read` as the
+    /// host asks for them.
+    Read(usize),
+    /// The command needs this many bytes from the host, to be given to `ScsiDisk::write` as they
+    /// arrive.
+    Out(usize),
+}
+
+/// A SCSI disk backed by a `DiskFile`.
+pub struct ScsiDisk {
+    disk: Box<dyn DiskFile>,
+    read_only: bool,
+    block_count: u64,
+    sense: Sense,
+}
+
+impl ScsiDisk {
+    pub fn new(disk: Box<dyn DiskFile>, read_only: bool) -> std::io::Result<ScsiDisk> {
+        let block_count = disk.get_len()? / BLOCK_SIZE;
+        Ok(ScsiDisk {
+            disk,
+            read_only,
+            block_count,
+            sense: Sense::NO_SENSE,
+        })
+    }
+
+    /// Executes the command in `cdb`. `max_len` is the amount of data the host is prepared to
+    /// transfer. On failure, the returned sense is also reported by the next REQUEST SENSE.
+    pub fn execute(&mut self, cdb: &[u8], max_len: usize) -> Result<DataPhase> {
+        let result = self.execute_inner(cdb, max_len);
+        if let Err(sense) = result {
+            self.sense = sense;
+        }
+        result
+    }
+
+    /// Reads the data at `offset` in the range of a read command for which `execute` returned
+    /// `DataPhase::Read`.
+    pub fn read(&mut self, cdb: &[u8], offset: usize, data: &mut [u8]) -> Result<()> {
+        let (lba, _) = lba_and_blocks(cdb);
+        if let Err(e) = self
+            .disk
+            .read_exact_at_volatile(VolatileSlice::new(data), lba * BLOCK_SIZE + offset as u64)
+        {
+            error!("failed to read from usb storage image: {}", e);
+            self.sense = Sense::UNRECOVERED_READ_ERROR;
+            return Err(Sense::UNRECOVERED_READ_ERROR);
+        }
+        Ok(())
+    }
+
+    /// Writes the data at `offset` in the range of a write command for which `execute` returned
+    /// `DataPhase::Out`.
+    pub fn write(&mut self, cdb: &[u8], offset: usize, data: &mut [u8]) -> Result<()> {
+        let (lba, _) = lba_and_blocks(cdb);
+        if let Err(e) = self
+            .disk
+            .write_all_at_volatile(VolatileSlice::new(data), lba * BLOCK_SIZE + offset as u64)
+        {
+            error!("failed to write to usb storage image: {}", e);
+            self.sense = Sense::WRITE_ERROR;
+            return Err(Sense::WRITE_ERROR);
+        }
+        Ok(())
+    }
+
+    fn execute_inner(&mut self, cdb: &[u8], max_len: usize) -> Result<DataPhase> {
+        let opcode = *cdb.first().ok_or(Sense::INVALID_COMMAND)?;
+        if cdb.len() < command_len(opcode) {
+            return Err(Sense::INVALID_FIELD_IN_CDB);
+        }
+        let data = match opcode {
+            TEST_UNIT_READY | START_STOP_UNIT | PREVENT_ALLOW_MEDIUM_REMOVAL => Vec::new(),
+            SYNCHRONIZE_CACHE_10 => {
+                if let Err(e) = self.disk.fsync() {
+                    error!("failed to flush usb storage image: {}", e);
+                    return Err(Sense::WRITE_ERROR);
+                }
+                Vec::new()
+            }
+            REQUEST_SENSE => {
+                let sense = std::mem::replace(&mut self.sense, Sense::NO_SENSE);
+                // Fixed format sense data, SPC-4 4.5.3.
+                let mut data = vec![0u8; 18];
+                data[0] = 0x70;
+                data[2] = sense.key;
+                data[7] = 10;
+                data[12] = sense.asc;
+                data[13] = sense.ascq;
+                truncate(data, cdb[4] as usize)
+            }
+            INQUIRY => self.inquiry(cdb)?,
+            MODE_SENSE_6 => {
+                let data = vec![3, 0, self.device_specific_parameter(), 0];
+                truncate(data, cdb[4] as usize)
+            }
+            MODE_SENSE_10 => {
+                let data = vec![0, 6, 0, self.device_specific_parameter(), 0, 0, 0, 0];
+                truncate(data, be16(&cdb[7..9]) as usize)
+            }
+            READ_FORMAT_CAPACITIES => {
+                let mut data = vec![0, 0, 0, 8];
+                data.extend_from_slice(
+                    &(min(self.block_count, u32::MAX as u64) as u32).to_be_bytes(),
+                );
+                // Formatted media, followed by the 24-bit block length.
+                data.push(0x02);
+                data.extend_from_slice(&(BLOCK_SIZE as u32).to_be_bytes()[1..]);
+                truncate(data, be16(&cdb[7..9]) as usize)
+            }
+            READ_CAPACITY_10 => {
+                let last_lba = min(self.block_count.saturating_sub(1), u32::MAX as u64) as u32;
+                let mut data = last_lba.to_be_bytes().to_vec();
+                data.extend_from_slice(&(BLOCK_SIZE as u32).to_be_bytes());
+                data
+            }
+            SERVICE_ACTION_IN_16 if cdb[1] & 0x1f == READ_CAPACITY_16_SERVICE_ACTION => {
+                let mut data = vec![0u8; 32];
+                data[0..8].copy_from_slice(&self.block_count.saturating_sub(1).to_be_bytes());
+                data[8..12].copy_from_slice(&(BLOCK_SIZE as u32).to_be_bytes());
+                truncate(data, be32(&cdb[10..14]) as usize)
+            }
+            READ_10 | READ_16 => {
+                let (lba, blocks) = lba_and_blocks(cdb);
+                let len = self.check_range(lba, blocks, max_len)?;
+                return Ok(DataPhase::Read(len));
+            }
+            VERIFY_10 => {
+                let (lba, blocks) = lba_and_blocks(cdb);
+                // Nothing is transferred unless BYTCHK is set, which is not supported.
+                if cdb[1] & 0x02 != 0 {
+                    return Err(Sense::INVALID_FIELD_IN_CDB);
+                }
+                self.check_range(lba, blocks, usize::MAX)?;
+                Vec::new()
+            }
+            WRITE_10 | WRITE_16 => {
+                if self.read_only {
+                    return Err(Sense::WRITE_PROTECTED);
+                }
+                let (lba, blocks) = lba_and_blocks(cdb);
+                let len = self.check_range(lba, blocks, max_len)?;
+                return Ok(DataPhase::Out(len));
+            }
+            _ => return Err(Sense::INVALID_COMMAND),
+        };
+        Ok(DataPhase::In(data))
+    }
+
+    fn inquiry(&self, cdb: &[u8]) -> Result<Vec<u8>> {
+        let alloc_len = be16(&cdb[3..5]) as usize;
+        // Vital product data pages.
+        if cdb[1] & 0x01 != 0 {
+            // Only the list of supported pages, which is empty apart from itself.
+            return match cdb[2] {
+                0x00 => Ok(truncate(vec![0, 0, 0, 1, 0], alloc_len)),
+                _ => Err(Sense::INVALID_FIELD_IN_CDB),
+            };
+        }
+        if cdb[2] != 0 {
+            return Err(Sense::INVALID_FIELD_IN_CDB);
+        }
+        // Standard inquiry data for a removable direct-access device, SPC-4 6.4.2.
+        let mut data = vec![0x00, 0x80, 0x04, 0x02, 31, 0, 0, 0];
+        data.extend_from_slice(b"CROSVM  ");
+        data.extend_from_slice(b"USB Mass Storage");
+        data.extend_from_slice(b"1.0 ");
+        Ok(truncate(data, alloc_len))
+    }
+
+    fn device_specific_parameter(&self) -> u8 {
+        // WP bit of the mode parameter header.
+        if self.read_only {
+            0x80
+        } else {
+            0
+        }
+    }
+
+    /// Checks that the range is on the disk and fits in `max_len` bytes, and returns its length.
+    fn check_range(&self, lba: u64, blocks: u64, max_len: usize) -> Result<usize> {
+        match lba.checked_add(blocks) {
+            Some(end) if end <= self.block_count => {}
+            _ => return Err(Sense::LBA_OUT_OF_RANGE),
+        }
+        match (blocks * BLOCK_SIZE).try_into() {
+            Ok(len) if len <= max_len => Ok(len),
+            _ => Err(Sense::INVALID_FIELD_IN_CDB),
+        }
+    }
+}
+
+fn command_len(opcode: u8) -> usize {
+    // The group code in the top three bits of the opcode gives the CDB length.
+    match opcode >> 5 {
+        0 => 6,
+        1 | 2 => 10,
+        4 => 16,
+        5 => 12,
+        _ => 6,
+    }
+}
+
+fn lba_and_blocks(cdb: &[u8]) -> (u64, u64) {
+    match cdb[0] {
+        READ_16 | WRITE_16 => (be64(&cdb[2..10]), be32(&cdb[10..14]) as u64),
+        _ => (be32(&cdb[2..6]) as u64, be16(&cdb[7..9]) as u64),
+    }
+}
+
+fn truncate(mut data: Vec<u8>, len: usize) -> Vec<u8> {
+    data.truncate(len);
+    data
+}
+
+fn be16(b: &[u8]) -> u16 {
+    u16::from_be_bytes(b.try_into().unwrap())
+}
+
+fn be32(b: &[u8]) -> u32 {
+    u32::from_be_bytes(b.try_into().unwrap())
+}
+
+fn be64(b: &[u8]) -> u64 {
+    u64::from_be_bytes(b.try_into().unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+    use std::io::Read;
+    use std::io::Seek;
+    use std::io::SeekFrom;
+    use std::io::Write;
+
+    use tempfile::tempfile;
+
+    use super::*;
+
+    fn new_disk(blocks: u64, read_only: bool) -> (ScsiDisk, File) {
+        let mut f = tempfile().unwrap();
+        for i in 0..blocks {
+            f.write_all(&[i as u8; BLOCK_SIZE as usize]).unwrap();
+        }
+        let disk = ScsiDisk::new(Box::new(f.try_clone().unwrap()), read_only).unwrap();
+        (disk, f)
+    }
+
+    fn read_10(lba: u32, blocks: u16) -> Vec<u8> {
+        let mut cdb = vec![READ_10, 0];
+        cdb.extend_from_slice(&lba.to_be_bytes());
+        cdb.push(0);
+        cdb.extend_from_slice(&blocks.to_be_bytes());
+        cdb.push(0);
+        cdb
+    }
+
+    #[test]
+    fn read_capacity() {
+        let (mut disk, _f) = new_disk(8, false);
+        let cdb = [READ_CAPACITY_10, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+        assert_eq!(
+            disk.execute(&cdb, 8),
+            Ok(DataPhase::In(vec![0, 0, 0, 7, 0, 0, 2, 0]))
+        );
+    }
+
+    #[test]
+    fn read_blocks() {
+        let (mut disk, _f) = new_disk(8, false);
+        let cdb = read_10(2, 3);
+        assert_eq!(disk.execute(&cdb, 3 * 512), Ok(DataPhase::Read(3 * 512)));
+        // The data is read in pieces, as the host asks for it.
+        let mut data = [0u8; 1024];
+        disk.read(&cdb, 0, &mut data).unwrap();
+        assert!(data[..512].iter().all(|b| *b == 2));
+        assert!(data[512..].iter().all(|b| *b == 3));
+        disk.read(&cdb, 1024, &mut data[..512]).unwrap();
+        assert!(data[..512].iter().all(|b| *b == 4));
+    }
+
+    #[test]
+    fn read_out_of_range_sets_sense() {
+        let (mut disk, _f) = new_disk(8, false);
+        assert_eq!(
+            disk.execute(&read_10(7, 2), 2 * 512),
+            Err(Sense::LBA_OUT_OF_RANGE)
+        );
+        let request_sense = [REQUEST_SENSE, 0, 0, 0, 18, 0];
+        let data = match disk.execute(&request_sense, 18).unwrap() {
+            DataPhase::In(data) => data,
+            phase => panic!("unexpected data phase {:?}", phase),
+        };
+        assert_eq!((data[2], data[12], data[13]), (0x05, 0x21, 0x00));
+        // Sense is cleared once reported.
+        assert_eq!(
+            disk.execute(&request_sense, 18),
+            Ok(DataPhase::In(vec![
+                0x70, 0, 0, 0, 0, 0, 0, 10, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0
+            ]))
+        );
+    }
+
+    #[test]
+    fn write_blocks() {
+        let (mut disk, mut f) = new_disk(8, false);
+        let mut cdb = read_10(1, 1);
+        cdb[0] = WRITE_10;
+        cdb[8] = 2;
+        assert_eq!(disk.execute(&cdb, 1024), Ok(DataPhase::Out(1024)));
+        disk.write(&cdb, 0, &mut [0xaa; 512]).unwrap();
+        disk.write(&cdb, 512, &mut [0xbb; 512]).unwrap();
+        let sync = [SYNCHRONIZE_CACHE_10, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+        assert_eq!(disk.execute(&sync, 0), Ok(DataPhase::In(Vec::new())));
+
+        let mut block = [0u8; 512];
+        f.seek(SeekFrom::Start(512)).unwrap();
+        f.read_exact(&mut block).unwrap();
+        assert!(block.iter().all(|b| *b == 0xaa));
+        f.read_exact(&mut block).unwrap();
+        assert!(block.iter().all(|b| *b == 0xbb));
+    }
+
+    #[test]
+    fn write_protected() {
+        let (mut disk, _f) = new_disk(8, true);
+        let mut cdb = read_10(1, 1);
+        cdb[0] = WRITE_10;
+        assert_eq!(disk.execute(&cdb, 512), Err(Sense::WRITE_PROTECTED));
+        let mode_sense = [MODE_SENSE_6, 0, 0x3f, 0, 4, 0];
+        assert_eq!(
+            disk.execute(&mode_sense, 4),
+            Ok(DataPhase::In(vec![3, 0, 0x80, 0]))
+        );
+    }
+
+    #[test]
+    fn inquiry_truncated_to_allocation_length() {
+        let (mut disk, _f) = new_disk(8, false);
+        let cdb = [INQUIRY, 0, 0, 0, 5, 0];
+        assert_eq!(
+            disk.execute(&cdb, 36),
+            Ok(DataPhase::In(vec![0x00, 0x80, 0x04, 0x02, 31]))
+        );
+    }
+
+    #[test]
+    fn unknown_command() {
+        let (mut disk, _f) = new_disk(8, false);
+        assert_eq!(disk.execute(&[0xff; 16], 0), Err(Sense::INVALID_COMMAND));
+    }
+}
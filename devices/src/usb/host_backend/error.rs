@@ -7,6 +7,7 @@ use remain::sorted;
 use thiserror::Error;
 use usb_util::Error as UsbUtilError;
 
+use crate::usb::usbip::protocol::Error as UsbipError;
 use crate::usb::xhci::scatter_gather_buffer::Error as BufferError;
 use crate::usb::xhci::xhci_transfer::Error as XhciTransferError;
 use crate::utils::Error as UtilsError;
@@ -24,6 +25,8 @@ pub enum Error {
     BufferLen(BufferError),
     #[error("failed to clear halt: {0}")]
     ClearHalt(UsbUtilError),
+    #[error("failed to clone usbip connection: {0}")]
+    CloneUsbipConnection(std::io::Error),
     #[error("failed to create contro tube: {0}")]
     CreateControlTube(TubeError),
     #[error("failed to create libusb context: {0}")]
@@ -63,8 +66,12 @@ pub enum Error {
     SetupControlTube(TubeError),
     #[error("failed to start async job queue: {0}")]
     StartAsyncJobQueue(UtilsError),
+    #[error("failed to start usbip reader thread: {0}")]
+    StartUsbipReader(std::io::Error),
     #[error("xhci transfer completed: {0}")]
     TransferComplete(XhciTransferError),
+    #[error("usbip error: {0}")]
+    Usbip(UsbipError),
     #[error("failed to write buffer: {0}")]
     WriteBuffer(BufferError),
     #[error("failed to write control tube: {0}")]
@@ -5,30 +5,42 @@
 use std::collections::HashMap;
 use std::fs::File;
 use std::mem;
+use std::net::TcpStream;
+use std::path::Path;
 use std::sync::Arc;
 use std::time::Duration;
 
 use anyhow::Context;
 use base::error;
 use base::AsRawDescriptor;
+use base::Descriptor;
 use base::EventType;
 use base::RawDescriptor;
+use base::SafeDescriptor;
 use base::Tube;
 use sync::Mutex;
 use usb_util::Device;
 use vm_control::UsbControlAttachedDevice;
 use vm_control::UsbControlCommand;
 use vm_control::UsbControlResult;
+use vm_control::UsbHidKind;
 use vm_control::USB_CONTROL_MAX_PORTS;
 
 use super::error::*;
 use super::host_device::HostDevice;
+use crate::usb::emulated::Hid;
+use crate::usb::emulated::MassStorage;
+use crate::usb::usbip::UsbipDevice;
 use crate::usb::xhci::usb_hub::UsbHub;
+use crate::usb::xhci::xhci_backend_device::XhciBackendDevice;
 use crate::usb::xhci::xhci_backend_device_provider::XhciBackendDeviceProvider;
 use crate::utils::AsyncJobQueue;
 use crate::utils::EventHandler;
 use crate::utils::EventLoop;
 use crate::utils::FailHandle;
+use crate::virtio::EvdevEventSource;
+use crate::virtio::EventSource;
+use crate::virtio::SocketEventSource;
 
 const SOCKET_TIMEOUT_MS: u64 = 2000;
 
@@ -134,6 +146,8 @@ pub struct ProviderInner {
 
     // Map of USB hub port number to per-device context.
     devices: Mutex<HashMap<u8, HostDeviceContext>>,
+    // Map of USB hub port number to the event handler of a device that isn't a host device.
+    backend_handlers: Mutex<HashMap<u8, BackendEventHandler>>,
 }
 
 struct HostDeviceContext {
@@ -141,6 +155,13 @@ struct HostDeviceContext {
     device: Arc<Mutex<Device>>,
 }
 
+struct BackendEventHandler {
+    // The event loop only holds a weak reference to the handler.
+    _handler: Arc<dyn EventHandler>,
+    // The descriptor the handler is registered for on the event loop.
+    descriptor: Descriptor,
+}
+
 impl ProviderInner {
     fn new(
         fail_handle: Arc<dyn FailHandle>,
@@ -156,6 +177,7 @@ impl ProviderInner {
             control_tube,
             usb_hub,
             devices: Mutex::new(HashMap::new()),
+            backend_handlers: Mutex::new(HashMap::new()),
         }
     }
 
@@ -221,6 +243,111 @@ impl ProviderInner {
         }
     }
 
+    /// Create an emulated mass storage device backed by the disk image in `file`.
+    fn handle_attach_storage(&self, file: File, read_only: bool) -> UsbControlResult {
+        let disk = match disk::create_disk_file(file, false, disk::MAX_NESTING_DEPTH, Path::new(""))
+        {
+            Ok(d) => d,
+            Err(e) => {
+                error!("could not open USB storage image: {}", e);
+                return UsbControlResult::FailedToOpenDevice;
+            }
+        };
+
+        let device = match MassStorage::new_device(self.job_queue.clone(), disk, read_only) {
+            Ok(device) => Box::new(device),
+            Err(e) => {
+                error!("failed to initialize USB storage device: {}", e);
+                return UsbControlResult::FailedToInitHostDevice;
+            }
+        };
+
+        match self.usb_hub.connect_backend(device) {
+            Ok(port) => UsbControlResult::Ok { port },
+            Err(e) => {
+                error!("failed to connect device to hub: {}", e);
+                UsbControlResult::NoAvailablePort
+            }
+        }
+    }
+
+    /// Create an emulated HID device of `kind`, fed by the input events read from `source`.
+    /// `source` is an evdev device if `evdev` is set, and a socket otherwise.
+    fn handle_attach_hid(&self, kind: UsbHidKind, source: File, evdev: bool) -> UsbControlResult {
+        let source: Box<dyn EventSource + Send> = if evdev {
+            Box::new(EvdevEventSource::new(source))
+        } else {
+            Box::new(SocketEventSource::new(source))
+        };
+        let (device, handler) = match Hid::new_device(self.job_queue.clone(), kind, source) {
+            Ok(d) => d,
+            Err(e) => {
+                error!("failed to initialize USB HID device: {:#}", e);
+                return UsbControlResult::FailedToInitHostDevice;
+            }
+        };
+
+        self.connect_backend_with_handler(Box::new(device), handler)
+    }
+
+    /// Import the device `busid` from the usbip server connected to `socket`.
+    fn handle_attach_usbip(&self, socket: File, busid: &str) -> UsbControlResult {
+        let stream = TcpStream::from(SafeDescriptor::from(socket));
+        let device = match UsbipDevice::new(stream, busid, self.job_queue.clone()) {
+            Ok(device) => Box::new(device),
+            Err(e) => {
+                error!("failed to import usbip device {}: {}", busid, e);
+                return UsbControlResult::FailedToInitHostDevice;
+            }
+        };
+
+        match self.usb_hub.connect_backend(device) {
+            Ok(port) => UsbControlResult::Ok { port },
+            Err(e) => {
+                error!("failed to connect device to hub: {}", e);
+                UsbControlResult::NoAvailablePort
+            }
+        }
+    }
+
+    // Connects `device` to the hub, with `handler` processing the events of the device on the
+    // event loop while it is attached.
+    fn connect_backend_with_handler<H: EventHandler + AsRawDescriptor + 'static>(
+        &self,
+        device: Box<dyn XhciBackendDevice>,
+        handler: Arc<H>,
+    ) -> UsbControlResult {
+        let descriptor = Descriptor(handler.as_raw_descriptor());
+        let handler: Arc<dyn EventHandler> = handler;
+        if let Err(e) =
+            self.event_loop
+                .add_event(&descriptor, EventType::Read, Arc::downgrade(&handler))
+        {
+            error!("failed to add USB device event handler: {}", e);
+            return UsbControlResult::FailedToOpenDevice;
+        }
+
+        match self.usb_hub.connect_backend(device) {
+            Ok(port) => {
+                self.backend_handlers.lock().insert(
+                    port,
+                    BackendEventHandler {
+                        _handler: handler,
+                        descriptor,
+                    },
+                );
+                UsbControlResult::Ok { port }
+            }
+            Err(e) => {
+                error!("failed to connect device to hub: {}", e);
+                if let Err(e) = self.event_loop.remove_event_for_descriptor(&descriptor) {
+                    error!("failed to remove USB device event handler: {}", e);
+                }
+                UsbControlResult::NoAvailablePort
+            }
+        }
+    }
+
     fn handle_detach_device(&self, port: u8) -> UsbControlResult {
         match self.usb_hub.disconnect_port(port) {
             Ok(()) => {
@@ -236,6 +363,14 @@ impl ProviderInner {
                         );
                     }
                 }
+                if let Some(handler) = self.backend_handlers.lock().remove(&port) {
+                    if let Err(e) = self
+                        .event_loop
+                        .remove_event_for_descriptor(&handler.descriptor)
+                    {
+                        error!("failed to remove USB device event handler: {}", e);
+                    }
+                }
                 UsbControlResult::Ok { port }
             }
             Err(e) => {
@@ -271,6 +406,17 @@ impl ProviderInner {
         let cmd = tube.recv().map_err(Error::ReadControlTube)?;
         let result = match cmd {
             UsbControlCommand::AttachDevice { file } => self.handle_attach_device(file),
+            UsbControlCommand::AttachStorage { file, read_only } => {
+                self.handle_attach_storage(file, read_only)
+            }
+            UsbControlCommand::AttachHid {
+                kind,
+                source,
+                evdev,
+            } => self.handle_attach_hid(kind, source, evdev),
+            UsbControlCommand::AttachUsbip { socket, busid } => {
+                self.handle_attach_usbip(socket, &busid)
+            }
             UsbControlCommand::DetachDevice { port } => self.handle_detach_device(port),
             UsbControlCommand::ListDevice { ports } => self.handle_list_devices(ports),
         };
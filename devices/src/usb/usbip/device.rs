@@ -0,0 +1,689 @@
+// Copyright 2023 The ChromiumOS Authors
+// Use of this source code is governed by a BSD-style license that can be
+// found in the LICENSE file.
+
+use std::collections::HashMap;
+use std::mem;
+use std::net::Shutdown;
+use std::net::TcpStream;
+use std::sync::Arc;
+use std::thread;
+use std::thread::JoinHandle;
+
+use base::error;
+use base::warn;
+use sync::Mutex;
+use usb_util::ControlRequestDataPhaseTransferDirection;
+use usb_util::ControlRequestRecipient;
+use usb_util::DescriptorType;
+use usb_util::DeviceSpeed;
+use usb_util::StandardControlRequest;
+use usb_util::TransferStatus;
+use usb_util::UsbRequestSetup;
+use zerocopy::AsBytes;
+
+use super::protocol::import_device;
+use super::protocol::read_reply;
+use super::protocol::write_unlink;
+use super::protocol::Direction;
+use super::protocol::Reply;
+use super::protocol::Submit;
+use super::protocol::UsbipDeviceInfo;
+use crate::usb::host_backend::error::Error;
+use crate::usb::host_backend::error::Result;
+use crate::usb::host_backend::host_device::ControlEndpointState;
+use crate::usb::xhci::scatter_gather_buffer::ScatterGatherBuffer;
+use crate::usb::xhci::xhci_backend_device::BackendType;
+use crate::usb::xhci::xhci_backend_device::UsbDeviceAddress;
+use crate::usb::xhci::xhci_backend_device::XhciBackendDevice;
+use crate::usb::xhci::xhci_transfer::TransferDirection;
+use crate::usb::xhci::xhci_transfer::XhciTransfer;
+use crate::usb::xhci::xhci_transfer::XhciTransferState;
+use crate::usb::xhci::xhci_transfer::XhciTransferType;
+use crate::utils::AsyncJobQueue;
+
+// Linux errno values reported in URB status.
+const ENOENT: i32 = 2;
+const ENODEV: i32 = 19;
+const ECONNRESET: i32 = 104;
+const ESHUTDOWN: i32 = 108;
+
+// Values of the Linux kernel's `enum usb_device_speed`.
+const USB_SPEED_LOW: u32 = 1;
+const USB_SPEED_FULL: u32 = 2;
+const USB_SPEED_HIGH: u32 = 3;
+const USB_SPEED_SUPER: u32 = 5;
+const USB_SPEED_SUPER_PLUS: u32 = 6;
+
+const ENDPOINT_ATTRIBUTES_TYPE_MASK: u8 = 0x03;
+const ENDPOINT_TYPE_INTERRUPT: u8 = 0x03;
+
+// A hub class SET_FEATURE(PORT_RESET) request, which usbip servers handle by resetting the
+// exported device.
+const PORT_RESET_SETUP: [u8; 8] = [0x23, 0x03, 0x04, 0x00, 0x00, 0x00, 0x00, 0x00];
+
+/// A submitted URB, waiting for the reply of the server.
+struct Urb {
+    transfer: Arc<XhciTransfer>,
+    // Buffer receiving the data of IN transfers.
+    buffer: Option<ScatterGatherBuffer>,
+    in_length: Option<usize>,
+    // Whether the URB reads a configuration descriptor, whose endpoints are recorded.
+    config_descriptor: bool,
+}
+
+/// State of the connection to the usbip server shared by the device and the reply reader.
+struct Connection {
+    writer: TcpStream,
+    devid: u32,
+    next_seqnum: u32,
+    urbs: HashMap<u32, Urb>,
+    // Maps sequence numbers of unlink commands to the URB they cancel.
+    unlinks: HashMap<u32, u32>,
+    // bInterval of the interrupt endpoints of the device, by endpoint address.
+    intervals: HashMap<u8, u8>,
+    // Set once the connection is shut down or lost, no reply will arrive anymore.
+    closed: bool,
+}
+
+impl Connection {
+    fn next_seqnum(&mut self) -> u32 {
+        self.next_seqnum = self.next_seqnum.wrapping_add(1);
+        self.next_seqnum
+    }
+
+    fn unlink(&mut self, urb_seqnum: u32) -> Result<()> {
+        let seqnum = self.next_seqnum();
+        write_unlink(&mut self.writer, seqnum, self.devid, urb_seqnum).map_err(Error::Usbip)?;
+        self.unlinks.insert(seqnum, urb_seqnum);
+        Ok(())
+    }
+}
+
+/// A device imported from a usbip server.
+pub struct UsbipDevice {
+    info: UsbipDeviceInfo,
+    connection: Arc<Mutex<Connection>>,
+    reader_thread: Option<JoinHandle<()>>,
+    ctl_ep_state: ControlEndpointState,
+    control_request_setup: UsbRequestSetup,
+    executed: bool,
+}
+
+impl UsbipDevice {
+    /// Imports the device `busid` from the usbip server connected to `stream`. The replies of
+    /// the server are read on a dedicated thread, which completes the transfers submitted to the
+    /// device. If the connection is lost, the pending transfers are failed through `job_queue`.
+    pub fn new(
+        mut stream: TcpStream,
+        busid: &str,
+        job_queue: Arc<AsyncJobQueue>,
+    ) -> Result<UsbipDevice> {
+        let info = import_device(&mut stream, busid).map_err(Error::Usbip)?;
+        if let Err(e) = stream.set_nodelay(true) {
+            warn!(
+                "failed to disable nagle algorithm for usbip connection: {}",
+                e
+            );
+        }
+        let reader = stream.try_clone().map_err(Error::CloneUsbipConnection)?;
+        let connection = Arc::new(Mutex::new(Connection {
+            writer: stream,
+            devid: info.devid(),
+            next_seqnum: 0,
+            urbs: HashMap::new(),
+            unlinks: HashMap::new(),
+            intervals: HashMap::new(),
+            closed: false,
+        }));
+        let reader_connection = connection.clone();
+        let reader_thread = thread::Builder::new()
+            .name(format!("usbip {}", busid))
+            .spawn(move || read_replies(reader, reader_connection, job_queue))
+            .map_err(Error::StartUsbipReader)?;
+        Ok(UsbipDevice {
+            info,
+            connection,
+            reader_thread: Some(reader_thread),
+            ctl_ep_state: ControlEndpointState::SetupStage,
+            control_request_setup: UsbRequestSetup::new(0, 0, 0, 0, 0),
+            executed: false,
+        })
+    }
+
+    // Returns the URB polling interval of `endpoint`. Servers reject interrupt transfers without
+    // one, and ignore it for bulk transfers.
+    fn interval(&self, endpoint: u8, direction: Direction) -> i32 {
+        let address = match direction {
+            Direction::In => endpoint | 0x80,
+            Direction::Out => endpoint,
+        };
+        match self.connection.lock().intervals.get(&address) {
+            Some(b_interval) => urb_interval(*b_interval, self.info.speed.to_native()),
+            None => 1,
+        }
+    }
+
+    // Sends `submit` for `urb`. The transfer is completed by the reply handler.
+    fn submit_urb(&mut self, urb: Urb, mut submit: Submit) -> Result<()> {
+        let transfer = urb.transfer.clone();
+        let mut state = transfer.state().lock();
+        match mem::replace(&mut *state, XhciTransferState::Cancelled) {
+            XhciTransferState::Created => {}
+            XhciTransferState::Cancelled => {
+                warn!("Transfer is already cancelled");
+                drop(state);
+                return transfer
+                    .on_transfer_complete(&TransferStatus::Cancelled, 0)
+                    .map_err(Error::TransferComplete);
+            }
+            _ => {
+                error!("xhci trasfer state is invalid");
+                return Err(Error::BadXhciTransferState);
+            }
+        }
+
+        let mut connection = self.connection.lock();
+        submit.seqnum = connection.next_seqnum();
+        submit.devid = connection.devid;
+        if connection.closed {
+            usb_debug!("usbip connection is closed, failing transfer");
+            *state = XhciTransferState::Completed;
+            drop(connection);
+            drop(state);
+            return transfer
+                .on_transfer_complete(&TransferStatus::Error, 0)
+                .map_err(Error::TransferComplete);
+        }
+        if let Err(e) = submit.write_to(&mut connection.writer) {
+            error!("failed to submit usbip transfer: {}", e);
+            *state = XhciTransferState::Completed;
+            drop(connection);
+            drop(state);
+            return transfer
+                .on_transfer_complete(&TransferStatus::Error, 0)
+                .map_err(Error::TransferComplete);
+        }
+        let seqnum = submit.seqnum;
+        connection.urbs.insert(seqnum, urb);
+        drop(connection);
+
+        let connection = self.connection.clone();
+        let cancel_callback = Box::new(move || {
+            usb_debug!("unlinking usbip urb {}", seqnum);
+            if let Err(e) = connection.lock().unlink(seqnum) {
+                error!("failed to cancel usbip transfer: {}", e);
+            }
+        });
+        *state = XhciTransferState::Submitted { cancel_callback };
+        Ok(())
+    }
+
+    fn execute_control_transfer(
+        &mut self,
+        transfer: Arc<XhciTransfer>,
+        buffer: Option<ScatterGatherBuffer>,
+    ) -> Result<()> {
+        let setup = self.control_request_setup;
+        let direction = setup.get_direction();
+        // The address is assigned by the controller, it is never sent to the device.
+        if setup.get_standard_request() == Some(StandardControlRequest::SetAddress)
+            && setup.get_recipient() == ControlRequestRecipient::Device
+        {
+            usb_debug!("usbip device handling set address");
+            return transfer
+                .on_transfer_complete(&TransferStatus::Completed, 0)
+                .map_err(Error::TransferComplete);
+        }
+
+        let length = setup.length as usize;
+        let mut setup_bytes = [0u8; 8];
+        setup_bytes.copy_from_slice(setup.as_bytes());
+        if direction == ControlRequestDataPhaseTransferDirection::HostToDevice {
+            let mut data = vec![0u8; length];
+            if let Some(buffer) = &buffer {
+                let len = buffer.read(&mut data).map_err(Error::ReadBuffer)?;
+                data.truncate(len);
+            } else {
+                data.clear();
+            }
+            let urb = Urb {
+                transfer,
+                buffer: None,
+                in_length: None,
+                config_descriptor: false,
+            };
+            self.submit_urb(
+                urb,
+                Submit {
+                    seqnum: 0,
+                    devid: 0,
+                    direction: Direction::Out,
+                    endpoint: 0,
+                    setup: setup_bytes,
+                    interval: 0,
+                    length: data.len(),
+                    data: &data,
+                },
+            )
+        } else {
+            let config_descriptor = setup.get_standard_request()
+                == Some(StandardControlRequest::GetDescriptor)
+                && setup.get_recipient() == ControlRequestRecipient::Device
+                && (setup.value >> 8) as u8 == DescriptorType::Configuration as u8;
+            let urb = Urb {
+                transfer,
+                buffer,
+                in_length: Some(length),
+                config_descriptor,
+            };
+            self.submit_urb(
+                urb,
+                Submit {
+                    seqnum: 0,
+                    devid: 0,
+                    direction: Direction::In,
+                    endpoint: 0,
+                    setup: setup_bytes,
+                    interval: 0,
+                    length,
+                    data: &[],
+                },
+            )
+        }
+    }
+
+    fn handle_control_transfer(&mut self, transfer: XhciTransfer) -> Result<()> {
+        let transfer = Arc::new(transfer);
+        let transfer_type = transfer
+            .get_transfer_type()
+            .map_err(Error::GetXhciTransferType)?;
+        match transfer_type {
+            XhciTransferType::SetupStage(setup) => {
+                if self.ctl_ep_state != ControlEndpointState::SetupStage {
+                    error!("Control endpoint is in an inconsistant state");
+                    return Ok(());
+                }
+                usb_debug!("setup stage setup buffer: {:?}", setup);
+                self.control_request_setup = setup;
+                transfer
+                    .on_transfer_complete(&TransferStatus::Completed, 0)
+                    .map_err(Error::TransferComplete)?;
+                self.ctl_ep_state = ControlEndpointState::DataStage;
+            }
+            XhciTransferType::DataStage(buffer) => {
+                if self.ctl_ep_state != ControlEndpointState::DataStage {
+                    error!("Control endpoint is in an inconsistant state");
+                    return Ok(());
+                }
+                self.execute_control_transfer(transfer, Some(buffer))?;
+                self.executed = true;
+                self.ctl_ep_state = ControlEndpointState::StatusStage;
+            }
+            XhciTransferType::StatusStage => {
+                if self.ctl_ep_state == ControlEndpointState::SetupStage {
+                    error!("Control endpoint is in an inconsistant state");
+                    return Ok(());
+                }
+                if self.executed {
+                    transfer
+                        .on_transfer_complete(&TransferStatus::Completed, 0)
+                        .map_err(Error::TransferComplete)?;
+                } else {
+                    self.execute_control_transfer(transfer, None)?;
+                }
+                self.executed = false;
+                self.ctl_ep_state = ControlEndpointState::SetupStage;
+            }
+            _ => {
+                error!(
+                    "Non control {} transfer sent to control endpoint.",
+                    transfer_type,
+                );
+                transfer
+                    .on_transfer_complete(&TransferStatus::Completed, 0)
+                    .map_err(Error::TransferComplete)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn handle_data_transfer(&mut self, transfer: XhciTransfer) -> Result<()> {
+        let buffer = match transfer
+            .get_transfer_type()
+            .map_err(Error::GetXhciTransferType)?
+        {
+            XhciTransferType::Normal(buffer) => buffer,
+            XhciTransferType::Noop => {
+                return transfer
+                    .on_transfer_complete(&TransferStatus::Completed, 0)
+                    .map_err(Error::TransferComplete);
+            }
+            _ => {
+                error!("unhandled xhci transfer type by usbip device");
+                return transfer
+                    .on_transfer_complete(&TransferStatus::Error, 0)
+                    .map_err(Error::TransferComplete);
+            }
+        };
+        let endpoint = transfer.get_endpoint_number();
+        let length = buffer.len().map_err(Error::BufferLen)?;
+        let transfer = Arc::new(transfer);
+        if transfer.get_transfer_dir() == TransferDirection::In {
+            let urb = Urb {
+                transfer,
+                buffer: Some(buffer),
+                in_length: Some(length),
+                config_descriptor: false,
+            };
+            let interval = self.interval(endpoint, Direction::In);
+            self.submit_urb(
+                urb,
+                Submit {
+                    seqnum: 0,
+                    devid: 0,
+                    direction: Direction::In,
+                    endpoint,
+                    setup: [0; 8],
+                    interval,
+                    length,
+                    data: &[],
+                },
+            )
+        } else {
+            let mut data = vec![0u8; length];
+            buffer.read(&mut data).map_err(Error::ReadBuffer)?;
+            let urb = Urb {
+                transfer,
+                buffer: None,
+                in_length: None,
+                config_descriptor: false,
+            };
+            let interval = self.interval(endpoint, Direction::Out);
+            self.submit_urb(
+                urb,
+                Submit {
+                    seqnum: 0,
+                    devid: 0,
+                    direction: Direction::Out,
+                    endpoint,
+                    setup: [0; 8],
+                    interval,
+                    length,
+                    data: &data,
+                },
+            )
+        }
+    }
+}
+
+impl XhciBackendDevice for UsbipDevice {
+    fn get_backend_type(&self) -> BackendType {
+        match self.info.speed.to_native() {
+            USB_SPEED_SUPER | USB_SPEED_SUPER_PLUS => BackendType::Usb3,
+            _ => BackendType::Usb2,
+        }
+    }
+
+    fn get_vid(&self) -> u16 {
+        self.info.id_vendor.to_native()
+    }
+
+    fn get_pid(&self) -> u16 {
+        self.info.id_product.to_native()
+    }
+
+    fn submit_transfer(&mut self, transfer: XhciTransfer) -> Result<()> {
+        if transfer.get_endpoint_number() == 0 {
+            self.handle_control_transfer(transfer)
+        } else {
+            self.handle_data_transfer(transfer)
+        }
+    }
+
+    fn set_address(&mut self, _address: UsbDeviceAddress) {
+        usb_debug!(
+            "Set address control transfer is received with address: {}",
+            _address
+        );
+    }
+
+    fn reset(&mut self) -> Result<()> {
+        usb_debug!("resetting usbip device");
+        self.ctl_ep_state = ControlEndpointState::SetupStage;
+        self.executed = false;
+        // The reply isn't tracked, the handler ignores it.
+        let mut connection = self.connection.lock();
+        let submit = Submit {
+            seqnum: connection.next_seqnum(),
+            devid: connection.devid,
+            direction: Direction::Out,
+            endpoint: 0,
+            setup: PORT_RESET_SETUP,
+            interval: 0,
+            length: 0,
+            data: &[],
+        };
+        submit
+            .write_to(&mut connection.writer)
+            .map_err(Error::Usbip)
+    }
+
+    fn get_speed(&self) -> Option<DeviceSpeed> {
+        match self.info.speed.to_native() {
+            USB_SPEED_LOW => Some(DeviceSpeed::Low),
+            USB_SPEED_FULL => Some(DeviceSpeed::Full),
+            USB_SPEED_HIGH => Some(DeviceSpeed::High),
+            USB_SPEED_SUPER => Some(DeviceSpeed::Super),
+            USB_SPEED_SUPER_PLUS => Some(DeviceSpeed::SuperPlus),
+            _ => None,
+        }
+    }
+}
+
+impl Drop for UsbipDevice {
+    fn drop(&mut self) {
+        // Shutting the connection down stops the reader. It isn't joined from its own thread,
+        // which drops the device when a reply reports it gone.
+        let mut connection = self.connection.lock();
+        connection.closed = true;
+        if let Err(e) = connection.writer.shutdown(Shutdown::Both) {
+            warn!("failed to shut usbip connection down: {}", e);
+        }
+        drop(connection);
+        if let Some(reader_thread) = self.reader_thread.take() {
+            if reader_thread.thread().id() != thread::current().id()
+                && reader_thread.join().is_err()
+            {
+                error!("usbip reader thread panicked");
+            }
+        }
+    }
+}
+
+// Reads the replies of the usbip server and completes the corresponding transfers, until the
+// connection is shut down or lost.
+fn read_replies(
+    mut reader: TcpStream,
+    connection: Arc<Mutex<Connection>>,
+    job_queue: Arc<AsyncJobQueue>,
+) {
+    loop {
+        if let Err(e) = handle_reply(&mut reader, &connection) {
+            if let Error::Usbip(_) = e {
+                if !connection.lock().closed {
+                    error!("usbip connection lost: {}", e);
+                }
+                break;
+            }
+            error!("failed to handle usbip reply: {}", e);
+        }
+    }
+
+    let (urbs, lost) = {
+        let mut connection = connection.lock();
+        let lost = !mem::replace(&mut connection.closed, true);
+        (mem::take(&mut connection.urbs), lost)
+    };
+    if urbs.is_empty() {
+        return;
+    }
+    // When the connection is lost, so is the device. Detaching it takes locks held by the
+    // controller while it drops the device, which waits for this thread, so the transfers are
+    // completed on the job queue instead. They are simply cancelled when the device is dropped.
+    let status = if lost { -ENODEV } else { -ECONNRESET };
+    let result = job_queue.queue_job(move || {
+        for (_, urb) in urbs {
+            if let Err(e) = complete_urb(urb, status, 0, &[]) {
+                error!("failed to complete usbip transfer: {}", e);
+            }
+        }
+    });
+    if let Err(e) = result {
+        error!("failed to queue usbip transfer completions: {}", e);
+    }
+}
+
+fn handle_reply(reader: &mut TcpStream, connection: &Mutex<Connection>) -> Result<()> {
+    let reply = read_reply(reader, |seqnum| {
+        connection
+            .lock()
+            .urbs
+            .get(&seqnum)
+            .and_then(|urb| urb.in_length)
+    })
+    .map_err(Error::Usbip)?;
+    match reply {
+        Reply::Submit {
+            seqnum,
+            status,
+            actual_length,
+            data,
+        } => {
+            let mut connection = connection.lock();
+            let urb = match connection.urbs.remove(&seqnum) {
+                Some(urb) => urb,
+                // Replies to untracked requests, such as resets.
+                None => return Ok(()),
+            };
+            if urb.config_descriptor && status == 0 {
+                connection.intervals.extend(interrupt_intervals(&data));
+            }
+            drop(connection);
+            complete_urb(urb, status, actual_length, &data)
+        }
+        Reply::Unlink { seqnum, status } => {
+            let urb = {
+                let mut connection = connection.lock();
+                connection
+                    .unlinks
+                    .remove(&seqnum)
+                    .and_then(|urb_seqnum| connection.urbs.remove(&urb_seqnum))
+            };
+            // If the URB completed before it could be unlinked, it was already answered.
+            match urb {
+                Some(urb) => complete_urb(urb, status, 0, &[]),
+                None => Ok(()),
+            }
+        }
+    }
+}
+
+// Returns the address and bInterval of the interrupt endpoints in the configuration descriptor
+// `config`, which may be truncated.
+fn interrupt_intervals(config: &[u8]) -> Vec<(u8, u8)> {
+    let mut intervals = Vec::new();
+    let mut rest = config;
+    while rest.len() >= 2 {
+        let len = rest[0] as usize;
+        if len < 2 || len > rest.len() {
+            break;
+        }
+        let descriptor = &rest[..len];
+        if descriptor[1] == DescriptorType::Endpoint as u8
+            && len >= 7
+            && descriptor[3] & ENDPOINT_ATTRIBUTES_TYPE_MASK == ENDPOINT_TYPE_INTERRUPT
+        {
+            intervals.push((descriptor[2], descriptor[6]));
+        }
+        rest = &rest[len..];
+    }
+    intervals
+}
+
+// Converts the bInterval of an interrupt endpoint to an URB interval, in frames for low and full
+// speed devices and in microframes for faster ones. See USB 2.0 9.6.6.
+fn urb_interval(b_interval: u8, speed: u32) -> i32 {
+    match speed {
+        USB_SPEED_LOW | USB_SPEED_FULL => b_interval.max(1) as i32,
+        _ => 1 << (b_interval.clamp(1, 16) - 1),
+    }
+}
+
+fn transfer_status(status: i32) -> TransferStatus {
+    match -status {
+        0 => TransferStatus::Completed,
+        ENOENT | ECONNRESET => TransferStatus::Cancelled,
+        ENODEV | ESHUTDOWN => TransferStatus::NoDevice,
+        _ => TransferStatus::Error,
+    }
+}
+
+fn complete_urb(urb: Urb, status: i32, actual_length: usize, data: &[u8]) -> Result<()> {
+    let mut state = urb.transfer.state().lock();
+    match *state {
+        XhciTransferState::Submitted { .. } => {
+            *state = XhciTransferState::Completed;
+        }
+        XhciTransferState::Cancelling => {
+            *state = XhciTransferState::Cancelled;
+            drop(state);
+            return urb
+                .transfer
+                .on_transfer_complete(&TransferStatus::Cancelled, 0)
+                .map_err(Error::TransferComplete);
+        }
+        _ => {
+            error!("xhci trasfer state is invalid");
+            return Err(Error::BadXhciTransferState);
+        }
+    }
+    drop(state);
+
+    let status = transfer_status(status);
+    let length = match &urb.buffer {
+        Some(buffer) => buffer.write(data).map_err(Error::WriteBuffer)?,
+        None => actual_length,
+    };
+    urb.transfer
+        .on_transfer_complete(&status, length as u32)
+        .map_err(Error::TransferComplete)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn interrupt_endpoint_intervals() {
+        // A configuration with a HID interface.
+        let mut config = vec![9, 2, 39, 0, 1, 1, 0, 0xa0, 50];
+        config.extend_from_slice(&[9, 4, 0, 0, 3, 3, 1, 1, 0]);
+        config.extend_from_slice(&[9, 0x21, 0x11, 0x01, 0, 1, 0x22, 63, 0]);
+        // Interrupt IN.
+        config.extend_from_slice(&[7, 5, 0x81, 0x03, 8, 0, 4]);
+        // Bulk OUT.
+        config.extend_from_slice(&[7, 5, 0x02, 0x02, 0, 2, 1]);
+        // Interrupt OUT, truncated.
+        config.extend_from_slice(&[7, 5, 0x03, 0x03]);
+        assert_eq!(interrupt_intervals(&config), vec![(0x81, 4)]);
+        assert!(interrupt_intervals(&[0, 5, 0x81, 0x03, 8, 0, 4]).is_empty());
+
+        assert_eq!(urb_interval(10, USB_SPEED_FULL), 10);
+        assert_eq!(urb_interval(0, USB_SPEED_LOW), 1);
+        assert_eq!(urb_interval(4, USB_SPEED_HIGH), 8);
+        assert_eq!(urb_interval(1, USB_SPEED_SUPER), 1);
+        assert_eq!(urb_interval(0, USB_SPEED_HIGH), 1);
+        assert_eq!(urb_interval(255, USB_SPEED_HIGH), 1 << 15);
+    }
+}
@@ -0,0 +1,10 @@
+// Copyright 2023 The ChromiumOS Authors
+// Use of this source code is governed by a BSD-style license that can be
+// found in the LICENSE file.
+
+//! USB devices imported from a remote machine through the USB/IP protocol.
+
+mod device;
+pub mod protocol;
+
+pub use self::device::UsbipDevice;
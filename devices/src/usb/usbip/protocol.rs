@@ -0,0 +1,602 @@
+// Copyright 2023 The ChromiumOS Authors
+// Use of this source code is governed by a BSD-style license that can be
+// found in the LICENSE file.
+
+//! Client side of the USB/IP protocol, see the Linux kernel's Documentation/usb/usbip_protocol.rst.
+//! All fields are big-endian on the wire.
+
+use std::io;
+use std::io::Read;
+use std::io::Write;
+
+use data_model::Be16;
+use data_model::Be32;
+use data_model::SBe32;
+use remain::sorted;
+use thiserror::Error;
+use zerocopy::AsBytes;
+use zerocopy::FromBytes;
+
+const USBIP_VERSION: u16 = 0x0111;
+
+const OP_REQ_IMPORT: u16 = 0x8003;
+const OP_REP_IMPORT: u16 = 0x0003;
+
+const USBIP_CMD_SUBMIT: u32 = 0x0001;
+const USBIP_CMD_UNLINK: u32 = 0x0002;
+const USBIP_RET_SUBMIT: u32 = 0x0003;
+const USBIP_RET_UNLINK: u32 = 0x0004;
+
+const BUSID_SIZE: usize = 32;
+// Value of `number_of_packets` for non isochronous transfers.
+const NON_ISO_PACKETS: i32 = -1;
+
+#[sorted]
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("usbip server refused to export {0}: status {1}")]
+    ImportRejected(String, u32),
+    #[error("invalid usbip bus id {0}")]
+    InvalidBusId(String),
+    #[error("usbip server returned {0} isochronous packets for a non isochronous transfer")]
+    InvalidPacketCount(i32),
+    #[error("failed to read from usbip server: {0}")]
+    Read(io::Error),
+    #[error("usbip server returned {0} bytes for a {1} bytes transfer")]
+    ReplyTooLong(usize, usize),
+    #[error("unexpected usbip reply {0:#x}")]
+    UnexpectedReply(u32),
+    #[error("failed to write to usbip server: {0}")]
+    Write(io::Error),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Direction of an URB, as in the `direction` field of USB/IP commands.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Direction {
+    Out = 0,
+    In = 1,
+}
+
+#[repr(C, packed)]
+#[derive(Copy, Clone, Default, FromBytes, AsBytes)]
+struct OpHeader {
+    version: Be16,
+    code: Be16,
+    status: Be32,
+}
+
+#[repr(C, packed)]
+#[derive(Copy, Clone, FromBytes, AsBytes)]
+struct OpImportRequest {
+    header: OpHeader,
+    busid: [u8; BUSID_SIZE],
+}
+
+/// Description of an exported device, sent by the server when it is imported.
+#[repr(C, packed)]
+#[derive(Copy, Clone, FromBytes, AsBytes)]
+pub struct UsbipDeviceInfo {
+    pub path: [u8; 256],
+    pub busid: [u8; BUSID_SIZE],
+    pub busnum: Be32,
+    pub devnum: Be32,
+    /// One of the Linux kernel's `enum usb_device_speed` values.
+    pub speed: Be32,
+    pub id_vendor: Be16,
+    pub id_product: Be16,
+    pub bcd_device: Be16,
+    pub device_class: u8,
+    pub device_subclass: u8,
+    pub device_protocol: u8,
+    pub configuration_value: u8,
+    pub num_configurations: u8,
+    pub num_interfaces: u8,
+}
+
+impl UsbipDeviceInfo {
+    /// Identifier of the device in URB commands.
+    pub fn devid(&self) -> u32 {
+        (self.busnum.to_native() << 16) | (self.devnum.to_native() & 0xffff)
+    }
+}
+
+#[repr(C, packed)]
+#[derive(Copy, Clone, Default, FromBytes, AsBytes)]
+struct HeaderBasic {
+    command: Be32,
+    seqnum: Be32,
+    devid: Be32,
+    direction: Be32,
+    ep: Be32,
+}
+
+#[repr(C, packed)]
+#[derive(Copy, Clone, Default, FromBytes, AsBytes)]
+struct CmdSubmit {
+    base: HeaderBasic,
+    transfer_flags: Be32,
+    transfer_buffer_length: SBe32,
+    start_frame: SBe32,
+    number_of_packets: SBe32,
+    interval: SBe32,
+    setup: [u8; 8],
+}
+
+#[repr(C, packed)]
+#[derive(Copy, Clone, Default, FromBytes, AsBytes)]
+struct RetSubmit {
+    base: HeaderBasic,
+    status: SBe32,
+    actual_length: SBe32,
+    start_frame: SBe32,
+    number_of_packets: SBe32,
+    error_count: SBe32,
+    padding: [u8; 8],
+}
+
+#[repr(C, packed)]
+#[derive(Copy, Clone, Default, FromBytes, AsBytes)]
+struct CmdUnlink {
+    base: HeaderBasic,
+    unlink_seqnum: Be32,
+    padding: [u8; 24],
+}
+
+#[repr(C, packed)]
+#[derive(Copy, Clone, Default, FromBytes, AsBytes)]
+struct RetUnlink {
+    base: HeaderBasic,
+    status: SBe32,
+    padding: [u8; 24],
+}
+
+// All URB commands and replies have the same header size.
+const URB_HEADER_SIZE: usize = std::mem::size_of::<CmdSubmit>();
+
+/// Asks the server to export the device at `busid`, and returns its description. The connection
+/// carries URBs for the device once this succeeds.
+pub fn import_device<S: Read + Write>(stream: &mut S, busid: &str) -> Result<UsbipDeviceInfo> {
+    // The bus id is a NUL terminated string.
+    if busid.is_empty() || busid.len() >= BUSID_SIZE {
+        return Err(Error::InvalidBusId(busid.to_string()));
+    }
+    let mut request = OpImportRequest {
+        header: OpHeader {
+            version: USBIP_VERSION.into(),
+            code: OP_REQ_IMPORT.into(),
+            status: 0.into(),
+        },
+        busid: [0; BUSID_SIZE],
+    };
+    request.busid[..busid.len()].copy_from_slice(busid.as_bytes());
+    stream.write_all(request.as_bytes()).map_err(Error::Write)?;
+
+    let mut header = OpHeader::default();
+    stream
+        .read_exact(header.as_bytes_mut())
+        .map_err(Error::Read)?;
+    if header.code.to_native() != OP_REP_IMPORT {
+        return Err(Error::UnexpectedReply(header.code.to_native().into()));
+    }
+    if header.status.to_native() != 0 {
+        return Err(Error::ImportRejected(
+            busid.to_string(),
+            header.status.to_native(),
+        ));
+    }
+    let mut info = UsbipDeviceInfo::new_zeroed();
+    stream
+        .read_exact(info.as_bytes_mut())
+        .map_err(Error::Read)?;
+    Ok(info)
+}
+
+/// An URB submission.
+pub struct Submit<'a> {
+    pub seqnum: u32,
+    pub devid: u32,
+    pub direction: Direction,
+    pub endpoint: u8,
+    /// Setup packet of control transfers, ignored for other endpoints.
+    pub setup: [u8; 8],
+    /// Polling interval of interrupt transfers, in frames or microframes depending on the speed
+    /// of the device. Servers reject interrupt transfers without one.
+    pub interval: i32,
+    /// Length of the transfer. OUT transfers send it from `data`.
+    pub length: usize,
+    pub data: &'a [u8],
+}
+
+impl Submit<'_> {
+    pub fn write_to<W: Write>(&self, writer: &mut W) -> Result<()> {
+        let command = CmdSubmit {
+            base: HeaderBasic {
+                command: USBIP_CMD_SUBMIT.into(),
+                seqnum: self.seqnum.into(),
+                devid: self.devid.into(),
+                direction: (self.direction as u32).into(),
+                ep: (self.endpoint as u32).into(),
+            },
+            transfer_buffer_length: (self.length as i32).into(),
+            number_of_packets: NON_ISO_PACKETS.into(),
+            interval: self.interval.into(),
+            setup: self.setup,
+            ..Default::default()
+        };
+        let mut message = command.as_bytes().to_vec();
+        if self.direction == Direction::Out {
+            message.extend_from_slice(&self.data[..self.length.min(self.data.len())]);
+        }
+        writer.write_all(&message).map_err(Error::Write)
+    }
+}
+
+/// Asks the server to cancel the URB `unlink_seqnum`.
+pub fn write_unlink<W: Write>(
+    writer: &mut W,
+    seqnum: u32,
+    devid: u32,
+    unlink_seqnum: u32,
+) -> Result<()> {
+    let command = CmdUnlink {
+        base: HeaderBasic {
+            command: USBIP_CMD_UNLINK.into(),
+            seqnum: seqnum.into(),
+            devid: devid.into(),
+            ..Default::default()
+        },
+        unlink_seqnum: unlink_seqnum.into(),
+        ..Default::default()
+    };
+    writer.write_all(command.as_bytes()).map_err(Error::Write)
+}
+
+/// A reply from the server.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Reply {
+    /// Completion of the submission `seqnum`, with a Linux errno `status`. `data` holds what was
+    /// read by IN transfers.
+    Submit {
+        seqnum: u32,
+        status: i32,
+        actual_length: usize,
+        data: Vec<u8>,
+    },
+    /// Result of the unlink command `seqnum`.
+    Unlink { seqnum: u32, status: i32 },
+}
+
+/// Reads the next reply. `in_length` returns the length of the IN submission with the given
+/// sequence number, or `None` if it isn't a pending IN submission: the server doesn't repeat the
+/// direction in its replies.
+pub fn read_reply<R: Read>(
+    reader: &mut R,
+    in_length: impl FnOnce(u32) -> Option<usize>,
+) -> Result<Reply> {
+    let mut header = [0u8; URB_HEADER_SIZE];
+    reader.read_exact(&mut header).map_err(Error::Read)?;
+    let base = HeaderBasic::read_from_prefix(&header[..]).unwrap();
+    match base.command.to_native() {
+        USBIP_RET_SUBMIT => {
+            let ret = RetSubmit::read_from(&header[..]).unwrap();
+            // Submissions never carry isochronous packets, and neither can their replies: the
+            // descriptors would follow the data.
+            let packets = ret.number_of_packets.to_native();
+            if packets != 0 && packets != NON_ISO_PACKETS {
+                return Err(Error::InvalidPacketCount(packets));
+            }
+            let seqnum = base.seqnum.to_native();
+            let actual_length = ret.actual_length.to_native().max(0) as usize;
+            let mut data = Vec::new();
+            if let Some(length) = in_length(seqnum) {
+                if actual_length > length {
+                    return Err(Error::ReplyTooLong(actual_length, length));
+                }
+                data.resize(actual_length, 0);
+                reader.read_exact(&mut data).map_err(Error::Read)?;
+            }
+            Ok(Reply::Submit {
+                seqnum,
+                status: ret.status.to_native(),
+                actual_length,
+                data,
+            })
+        }
+        USBIP_RET_UNLINK => {
+            let ret = RetUnlink::read_from(&header[..]).unwrap();
+            Ok(Reply::Unlink {
+                seqnum: base.seqnum.to_native(),
+                status: ret.status.to_native(),
+            })
+        }
+        command => Err(Error::UnexpectedReply(command)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::TcpListener;
+    use std::net::TcpStream;
+    use std::thread;
+
+    use super::*;
+
+    const EXPORTED_BUSID: &str = "1-1";
+    const DEVID: u32 = (1 << 16) | 2;
+    const INTERRUPT_ENDPOINT: u32 = 3;
+    // -EINVAL
+    const INVALID_URB: i32 = -22;
+
+    // Minimal usbip server exporting a single device. It echoes the data of OUT transfers back
+    // to the following IN transfer, and answers unlinks as if the URB was still pending. Like
+    // Linux, it rejects interrupt transfers without a polling interval.
+    fn stub_server(mut stream: TcpStream) {
+        let mut request = OpImportRequest::new_zeroed();
+        stream.read_exact(request.as_bytes_mut()).unwrap();
+        assert_eq!(request.header.code.to_native(), OP_REQ_IMPORT);
+        let busid = request.busid.split(|b| *b == 0).next().unwrap();
+        let exported = busid == EXPORTED_BUSID.as_bytes();
+        let header = OpHeader {
+            version: USBIP_VERSION.into(),
+            code: OP_REP_IMPORT.into(),
+            status: (!exported as u32).into(),
+        };
+        stream.write_all(header.as_bytes()).unwrap();
+        if !exported {
+            return;
+        }
+        let mut info = UsbipDeviceInfo::new_zeroed();
+        info.busid[..busid.len()].copy_from_slice(busid);
+        info.busnum = 1.into();
+        info.devnum = 2.into();
+        info.speed = 3.into();
+        info.id_vendor = 0x18d1.into();
+        info.id_product = 0x4e11.into();
+        stream.write_all(info.as_bytes()).unwrap();
+
+        let mut echo = Vec::new();
+        loop {
+            let mut command = CmdSubmit::default();
+            if stream.read_exact(command.as_bytes_mut()).is_err() {
+                return;
+            }
+            let base = command.base;
+            assert_eq!(base.devid.to_native(), DEVID);
+            let seqnum = base.seqnum.to_native();
+            match base.command.to_native() {
+                USBIP_CMD_SUBMIT => {
+                    assert_eq!(command.number_of_packets.to_native(), NON_ISO_PACKETS);
+                    let length = command.transfer_buffer_length.to_native() as usize;
+                    let out = base.direction.to_native() == Direction::Out as u32;
+                    let status = if base.ep.to_native() == INTERRUPT_ENDPOINT
+                        && command.interval.to_native() <= 0
+                    {
+                        INVALID_URB
+                    } else {
+                        0
+                    };
+                    let (data, actual_length) = if status != 0 {
+                        if out {
+                            stream.read_exact(&mut vec![0; length]).unwrap();
+                        }
+                        (Vec::new(), 0)
+                    } else if out {
+                        echo = vec![0; length];
+                        stream.read_exact(&mut echo).unwrap();
+                        (Vec::new(), length)
+                    } else {
+                        echo.truncate(length);
+                        let data = std::mem::take(&mut echo);
+                        let actual_length = data.len();
+                        (data, actual_length)
+                    };
+                    let ret = RetSubmit {
+                        base: HeaderBasic {
+                            command: USBIP_RET_SUBMIT.into(),
+                            seqnum: seqnum.into(),
+                            ..Default::default()
+                        },
+                        status: status.into(),
+                        actual_length: (actual_length as i32).into(),
+                        ..Default::default()
+                    };
+                    stream.write_all(ret.as_bytes()).unwrap();
+                    stream.write_all(&data).unwrap();
+                }
+                USBIP_CMD_UNLINK => {
+                    let ret = RetUnlink {
+                        base: HeaderBasic {
+                            command: USBIP_RET_UNLINK.into(),
+                            seqnum: seqnum.into(),
+                            ..Default::default()
+                        },
+                        // -ECONNRESET
+                        status: (-104).into(),
+                        ..Default::default()
+                    };
+                    stream.write_all(ret.as_bytes()).unwrap();
+                }
+                command => panic!("unexpected usbip command {}", command),
+            }
+        }
+    }
+
+    fn connect() -> (TcpStream, thread::JoinHandle<()>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = thread::spawn(move || stub_server(listener.accept().unwrap().0));
+        (TcpStream::connect(addr).unwrap(), server)
+    }
+
+    #[test]
+    fn import() {
+        let (mut stream, server) = connect();
+        let info = import_device(&mut stream, EXPORTED_BUSID).unwrap();
+        assert_eq!(info.devid(), DEVID);
+        assert_eq!(info.speed.to_native(), 3);
+        assert_eq!(info.id_vendor.to_native(), 0x18d1);
+        assert_eq!(info.id_product.to_native(), 0x4e11);
+        drop(stream);
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn import_rejected() {
+        let (mut stream, server) = connect();
+        match import_device(&mut stream, "2-1") {
+            Err(Error::ImportRejected(busid, 1)) => assert_eq!(busid, "2-1"),
+            r => panic!("unexpected import result {:?}", r.map(|i| i.devid())),
+        }
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn invalid_busid() {
+        let mut stream = io::Cursor::new(Vec::new());
+        assert!(matches!(
+            import_device(&mut stream, &"1".repeat(BUSID_SIZE)),
+            Err(Error::InvalidBusId(_))
+        ));
+        assert!(stream.get_ref().is_empty());
+    }
+
+    #[test]
+    fn submit_and_unlink() {
+        let (mut stream, server) = connect();
+        import_device(&mut stream, EXPORTED_BUSID).unwrap();
+
+        Submit {
+            seqnum: 1,
+            devid: DEVID,
+            direction: Direction::Out,
+            endpoint: 2,
+            setup: [0; 8],
+            interval: 0,
+            length: 4,
+            data: &[1, 2, 3, 4],
+        }
+        .write_to(&mut stream)
+        .unwrap();
+        let reply = read_reply(&mut stream, |_| None).unwrap();
+        assert_eq!(
+            reply,
+            Reply::Submit {
+                seqnum: 1,
+                status: 0,
+                actual_length: 4,
+                data: Vec::new(),
+            }
+        );
+
+        Submit {
+            seqnum: 2,
+            devid: DEVID,
+            direction: Direction::In,
+            endpoint: 1,
+            setup: [0; 8],
+            interval: 0,
+            length: 512,
+            data: &[],
+        }
+        .write_to(&mut stream)
+        .unwrap();
+        let reply = read_reply(&mut stream, |seqnum| {
+            assert_eq!(seqnum, 2);
+            Some(512)
+        })
+        .unwrap();
+        assert_eq!(
+            reply,
+            Reply::Submit {
+                seqnum: 2,
+                status: 0,
+                actual_length: 4,
+                data: vec![1, 2, 3, 4],
+            }
+        );
+
+        write_unlink(&mut stream, 3, DEVID, 2).unwrap();
+        let reply = read_reply(&mut stream, |_| None).unwrap();
+        assert_eq!(
+            reply,
+            Reply::Unlink {
+                seqnum: 3,
+                status: -104,
+            }
+        );
+
+        drop(stream);
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn interrupt_interval() {
+        let (mut stream, server) = connect();
+        import_device(&mut stream, EXPORTED_BUSID).unwrap();
+
+        let mut submit = Submit {
+            seqnum: 1,
+            devid: DEVID,
+            direction: Direction::In,
+            endpoint: INTERRUPT_ENDPOINT as u8,
+            setup: [0; 8],
+            interval: 0,
+            length: 8,
+            data: &[],
+        };
+        submit.write_to(&mut stream).unwrap();
+        let reply = read_reply(&mut stream, |_| Some(8)).unwrap();
+        assert_eq!(
+            reply,
+            Reply::Submit {
+                seqnum: 1,
+                status: INVALID_URB,
+                actual_length: 0,
+                data: Vec::new(),
+            }
+        );
+
+        submit.seqnum = 2;
+        submit.interval = 8;
+        submit.write_to(&mut stream).unwrap();
+        let reply = read_reply(&mut stream, |_| Some(8)).unwrap();
+        assert_eq!(
+            reply,
+            Reply::Submit {
+                seqnum: 2,
+                status: 0,
+                actual_length: 0,
+                data: Vec::new(),
+            }
+        );
+
+        drop(stream);
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn invalid_packet_count() {
+        let reply = |number_of_packets: i32| {
+            let ret = RetSubmit {
+                base: HeaderBasic {
+                    command: USBIP_RET_SUBMIT.into(),
+                    seqnum: 1.into(),
+                    ..Default::default()
+                },
+                number_of_packets: number_of_packets.into(),
+                ..Default::default()
+            };
+            read_reply(&mut io::Cursor::new(ret.as_bytes()), |_| None)
+        };
+        assert!(reply(0).is_ok());
+        assert!(reply(NON_ISO_PACKETS).is_ok());
+        assert!(matches!(reply(1), Err(Error::InvalidPacketCount(1))));
+        assert!(matches!(
+            reply(i32::MAX),
+            Err(Error::InvalidPacketCount(i32::MAX))
+        ));
+        assert!(matches!(reply(-2), Err(Error::InvalidPacketCount(-2))));
+    }
+}
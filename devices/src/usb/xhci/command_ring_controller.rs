@@ -32,6 +32,7 @@ use super::xhci_abi::DisableSlotCommandTrb;
 use super::xhci_abi::Error as TrbError;
 use super::xhci_abi::EvaluateContextCommandTrb;
 use super::xhci_abi::ResetDeviceCommandTrb;
+use super::xhci_abi::ResetEndpointCommandTrb;
 use super::xhci_abi::SetTRDequeuePointerCommandTrb;
 use super::xhci_abi::StopEndpointCommandTrb;
 use super::xhci_abi::TransferDescriptor;
@@ -317,6 +318,35 @@ impl CommandRingTrbHandler {
         }
     }
 
+    fn reset_endpoint(&self, atrb: &AddressedTrb, event: Event) -> Result<()> {
+        let trb = atrb
+            .trb
+            .cast::<ResetEndpointCommandTrb>()
+            .map_err(Error::CastTrb)?;
+        let slot_id = trb.get_slot_id();
+        // Stalls are reported on the transfers they end, they don't halt the transfer ring, so
+        // there is nothing to reset. The device keeps stalling until its endpoint halt is cleared
+        // with a control request.
+        let completion_code = if valid_slot_id(slot_id) {
+            usb_debug!(
+                "reset endpoint {} of slot {}",
+                trb.get_endpoint_id(),
+                slot_id
+            );
+            TrbCompletionCode::Success
+        } else {
+            error!("reset endpoint trb has invalid slot id {}", slot_id);
+            TrbCompletionCode::TrbError
+        };
+        CommandRingTrbHandler::command_completion_callback(
+            &self.interrupter,
+            completion_code,
+            slot_id,
+            atrb.gpa,
+            &event,
+        )
+    }
+
     fn set_tr_dequeue_ptr(&self, atrb: &AddressedTrb, event: Event) -> Result<()> {
         let trb = atrb
             .trb
@@ -369,19 +399,7 @@ impl TransferDescriptorHandler for CommandRingTrbHandler {
                 atrb.gpa,
                 &complete_event,
             ),
-            Ok(TrbType::ResetEndpointCommand) => {
-                error!(
-                    "Receiving reset endpoint command. \
-                     It should only happen when cmd ring stall"
-                );
-                CommandRingTrbHandler::command_completion_callback(
-                    &self.interrupter,
-                    TrbCompletionCode::TrbError,
-                    0,
-                    atrb.gpa,
-                    &complete_event,
-                )
-            }
+            Ok(TrbType::ResetEndpointCommand) => self.reset_endpoint(atrb, complete_event),
             Ok(TrbType::StopEndpointCommand) => self.stop_endpoint(atrb, complete_event),
             Ok(TrbType::SetTRDequeuePointerCommand) => {
                 self.set_tr_dequeue_ptr(atrb, complete_event)
@@ -69,6 +69,7 @@ pub enum TrbCompletionCode {
     Success = 1,
     TransactionError = 4,
     TrbError = 5,
+    StallError = 6,
     NoSlotsAvailableError = 9,
     SlotNotEnabledError = 11,
     ShortPacket = 13,
@@ -332,6 +332,12 @@ impl XhciTransfer {
                     .signal()
                     .map_err(Error::WriteCompletionEvent)?;
             }
+            TransferStatus::Stalled => {
+                self.transfer_completion_event
+                    .signal()
+                    .map_err(Error::WriteCompletionEvent)?;
+                return self.send_stall_event(bytes_transferred);
+            }
             _ => {
                 // Transfer failed, we are not handling this correctly yet. Guest kernel might see
                 // short packets for in transfer and might think control transfer is successful. It
@@ -408,6 +414,32 @@ impl XhciTransfer {
         Ok(())
     }
 
+    // A stall ends the transfer descriptor on the TRB it happened in, whatever its flags. See
+    // xHCI spec 4.10.2.1.
+    fn send_stall_event(&self, bytes_transferred: u32) -> Result<()> {
+        let last = self.transfer_trbs.len() - 1;
+        let mut edtla: u32 = 0;
+        for (i, atrb) in self.transfer_trbs.iter().enumerate() {
+            edtla += atrb.trb.transfer_length().map_err(Error::TransferLength)?;
+            if edtla > bytes_transferred || i == last {
+                usb_debug!("on transfer complete stall");
+                return self
+                    .interrupter
+                    .lock()
+                    .send_transfer_event_trb(
+                        TrbCompletionCode::StallError,
+                        atrb.gpa,
+                        edtla.saturating_sub(bytes_transferred),
+                        false,
+                        self.slot_id,
+                        self.endpoint_id,
+                    )
+                    .map_err(Error::SendInterrupt);
+            }
+        }
+        Ok(())
+    }
+
     /// Send this transfer to backend if it's a valid transfer.
     pub fn send_to_backend_if_valid(self) -> Result<()> {
         if self.validate_transfer()? {
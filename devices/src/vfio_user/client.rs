@@ -0,0 +1,640 @@
+// Copyright 2023 The ChromiumOS Authors
+// Use of this source code is governed by a BSD-style license that can be
+// found in the LICENSE file.
+
+use std::collections::HashMap;
+use std::io;
+use std::mem::size_of;
+use std::net::Shutdown;
+use std::os::unix::net::UnixStream;
+use std::path::Path;
+use std::time::Duration;
+
+use base::AsRawDescriptor;
+use base::Event;
+use base::RawDescriptor;
+use data_model::Le32;
+use data_model::Le64;
+use serde::Deserialize;
+use sync::Mutex;
+use vfio_sys::*;
+use zerocopy::AsBytes;
+use zerocopy::FromBytes;
+
+use super::protocol::*;
+
+// Number of file descriptors a server accepts in a message if it doesn't say otherwise.
+const DEFAULT_MAX_MSG_FDS: usize = 1;
+// Time the server has to take a message and to reply to a command.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Deserialize)]
+struct ServerCapabilities {
+    #[serde(default = "default_max_msg_fds")]
+    max_msg_fds: usize,
+}
+
+fn default_max_msg_fds() -> usize {
+    DEFAULT_MAX_MSG_FDS
+}
+
+#[derive(Deserialize)]
+struct ServerVersion {
+    capabilities: Option<ServerCapabilities>,
+}
+
+/// Connection to a vfio-user server emulating a single device.
+pub struct VfioUserClient {
+    socket: UnixStream,
+    // Id of the next command.
+    next_msg_id: Mutex<u16>,
+    // Held while sending a message so that concurrent messages don't interleave.
+    send_lock: Mutex<()>,
+    // Held by the caller reading from the socket. Replies it reads to other commands are left in
+    // `replies` for their callers.
+    recv_lock: Mutex<()>,
+    replies: Mutex<HashMap<u16, Message>>,
+    max_msg_fds: usize,
+    regions: Vec<RegionInfo>,
+    num_irqs: u32,
+}
+
+impl VfioUserClient {
+    /// Negotiates the protocol version with the server at the other end of `socket` and queries
+    /// the regions of its device.
+    pub fn new(socket: UnixStream) -> Result<Self> {
+        socket
+            .set_read_timeout(Some(REQUEST_TIMEOUT))
+            .map_err(Error::SetTimeout)?;
+        socket
+            .set_write_timeout(Some(REQUEST_TIMEOUT))
+            .map_err(Error::SetTimeout)?;
+        let mut client = VfioUserClient {
+            socket,
+            next_msg_id: Mutex::new(0),
+            send_lock: Mutex::new(()),
+            recv_lock: Mutex::new(()),
+            replies: Mutex::new(HashMap::new()),
+            max_msg_fds: DEFAULT_MAX_MSG_FDS,
+            regions: Vec::new(),
+            num_irqs: 0,
+        };
+        client.max_msg_fds = client.negotiate_version()?;
+
+        let info = DeviceInfo {
+            argsz: Le32::from(size_of::<DeviceInfo>() as u32),
+            ..Default::default()
+        };
+        let info: DeviceInfo = client
+            .request(VFIO_USER_DEVICE_GET_INFO, &[info.as_bytes()], &[])?
+            .payload_as()
+            .ok_or(Error::MalformedReply(VFIO_USER_DEVICE_GET_INFO))?;
+        client.num_irqs = info.num_irqs.to_native();
+        for index in 0..info.num_regions.to_native() {
+            let region = client.get_region_info(index)?;
+            client.regions.push(region);
+        }
+
+        Ok(client)
+    }
+
+    /// Connects to the vfio-user server listening on the unix socket at `path`.
+    pub fn connect(path: &Path) -> Result<Self> {
+        let socket = UnixStream::connect(path).map_err(Error::Connect)?;
+        Self::new(socket)
+    }
+
+    // Returns the maximum number of file descriptors the server accepts in a message.
+    fn negotiate_version(&self) -> Result<usize> {
+        let version = Version {
+            major: VFIO_USER_MAJOR_VERSION.into(),
+            minor: VFIO_USER_MINOR_VERSION.into(),
+        };
+        let capabilities = format!(
+            "{{\"capabilities\":{{\"max_msg_fds\":{}}}}}\0",
+            base::SCM_SOCKET_MAX_FD_COUNT
+        );
+        let reply = self.request(
+            VFIO_USER_VERSION,
+            &[version.as_bytes(), capabilities.as_bytes()],
+            &[],
+        )?;
+        let version: Version = reply
+            .payload_as()
+            .ok_or(Error::MalformedReply(VFIO_USER_VERSION))?;
+        if version.major.to_native() != VFIO_USER_MAJOR_VERSION {
+            return Err(Error::UnsupportedVersion(
+                version.major.to_native(),
+                version.minor.to_native(),
+            ));
+        }
+
+        // The JSON capabilities are optional and NUL-terminated.
+        let json = &reply.payload[size_of::<Version>()..];
+        let json = json.split(|&c| c == 0).next().unwrap_or_default();
+        if json.is_empty() {
+            return Ok(DEFAULT_MAX_MSG_FDS);
+        }
+        let server: ServerVersion =
+            serde_json::from_slice(json).map_err(|_| Error::MalformedReply(VFIO_USER_VERSION))?;
+        Ok(server
+            .capabilities
+            .map_or(DEFAULT_MAX_MSG_FDS, |c| c.max_msg_fds.max(1)))
+    }
+
+    fn get_region_info(&self, index: u32) -> Result<RegionInfo> {
+        let info = RegionInfo {
+            argsz: Le32::from(size_of::<RegionInfo>() as u32),
+            index: index.into(),
+            ..Default::default()
+        };
+        self.request(VFIO_USER_DEVICE_GET_REGION_INFO, &[info.as_bytes()], &[])?
+            .payload_as()
+            .ok_or(Error::MalformedReply(VFIO_USER_DEVICE_GET_REGION_INFO))
+    }
+
+    // Sends `command` and waits for its reply.
+    fn request(&self, command: u16, payload: &[&[u8]], fds: &[RawDescriptor]) -> Result<Message> {
+        let msg_id = {
+            let mut next_msg_id = self.next_msg_id.lock();
+            let msg_id = *next_msg_id;
+            *next_msg_id = msg_id.wrapping_add(1);
+            msg_id
+        };
+
+        let header = Header {
+            msg_id: msg_id.into(),
+            command: command.into(),
+            flags: VFIO_USER_F_TYPE_COMMAND.into(),
+            ..Default::default()
+        };
+        {
+            let _send = self.send_lock.lock();
+            send_message(&self.socket, header, payload, fds)
+                .map_err(|e| self.check_timeout(command, e))?;
+        }
+
+        let reply = self
+            .recv_reply(msg_id)
+            .map_err(|e| self.check_timeout(command, e))?;
+        let header = reply.header;
+        let flags = header.flags.to_native();
+        if header.msg_id.to_native() != msg_id
+            || header.command.to_native() != command
+            || flags & VFIO_USER_F_TYPE_MASK != VFIO_USER_F_TYPE_REPLY
+        {
+            return Err(Error::UnexpectedMessage(
+                header.msg_id.to_native(),
+                header.command.to_native(),
+            ));
+        }
+        if flags & VFIO_USER_F_ERROR != 0 {
+            return Err(Error::CommandFailed(
+                command,
+                base::Error::new(header.error_no.to_native()),
+            ));
+        }
+        Ok(reply)
+    }
+
+    // Receives messages until the reply with `msg_id` arrives.
+    fn recv_reply(&self, msg_id: u16) -> Result<Message> {
+        loop {
+            if let Some(reply) = self.replies.lock().remove(&msg_id) {
+                return Ok(reply);
+            }
+            let _recv = self.recv_lock.lock();
+            // Another caller may have received the reply while this one waited for the lock.
+            if let Some(reply) = self.replies.lock().remove(&msg_id) {
+                return Ok(reply);
+            }
+            let reply = recv_message(&self.socket)?;
+            let header = reply.header;
+            if header.msg_id.to_native() == msg_id {
+                return Ok(reply);
+            }
+            if header.flags.to_native() & VFIO_USER_F_TYPE_MASK != VFIO_USER_F_TYPE_REPLY {
+                return Err(Error::UnexpectedMessage(
+                    header.msg_id.to_native(),
+                    header.command.to_native(),
+                ));
+            }
+            self.replies.lock().insert(header.msg_id.to_native(), reply);
+        }
+    }
+
+    // Turns a socket timeout during `command` into `Error::Timeout`. The connection is shut down
+    // then, since the socket may be left in the middle of a message.
+    fn check_timeout(&self, command: u16, e: Error) -> Error {
+        match &e {
+            Error::Recv(err) | Error::Send(err)
+                if matches!(
+                    err.kind(),
+                    io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut
+                ) =>
+            {
+                let _ = self.socket.shutdown(Shutdown::Both);
+                Error::Timeout(command)
+            }
+            _ => e,
+        }
+    }
+
+    /// Returns the number of regions of the device.
+    pub fn num_regions(&self) -> u32 {
+        self.regions.len() as u32
+    }
+
+    /// Returns the size of region `index`, 0 if the device doesn't have it.
+    pub fn region_size(&self, index: u32) -> u64 {
+        self.regions
+            .get(index as usize)
+            .map_or(0, |r| r.size.to_native())
+    }
+
+    /// Returns the `VFIO_REGION_INFO_FLAG_*` flags of region `index`.
+    pub fn region_flags(&self, index: u32) -> u32 {
+        self.regions
+            .get(index as usize)
+            .map_or(0, |r| r.flags.to_native())
+    }
+
+    /// Returns the number of interrupts of type `index` supported by the device.
+    pub fn irq_count(&self, index: u32) -> Result<u32> {
+        if index >= self.num_irqs {
+            return Ok(0);
+        }
+        let info = IrqInfo {
+            argsz: Le32::from(size_of::<IrqInfo>() as u32),
+            index: index.into(),
+            ..Default::default()
+        };
+        let info: IrqInfo = self
+            .request(VFIO_USER_DEVICE_GET_IRQ_INFO, &[info.as_bytes()], &[])?
+            .payload_as()
+            .ok_or(Error::MalformedReply(VFIO_USER_DEVICE_GET_IRQ_INFO))?;
+        Ok(info.count.to_native())
+    }
+
+    /// Lets the device access `size` bytes of `descriptor` at `offset` through DMA at `address`.
+    pub fn dma_map(
+        &self,
+        address: u64,
+        size: u64,
+        descriptor: &dyn AsRawDescriptor,
+        offset: u64,
+    ) -> Result<()> {
+        let map = DmaMap {
+            argsz: Le32::from(size_of::<DmaMap>() as u32),
+            flags: Le32::from(VFIO_USER_F_DMA_REGION_READ | VFIO_USER_F_DMA_REGION_WRITE),
+            offset: offset.into(),
+            address: address.into(),
+            size: size.into(),
+        };
+        self.request(
+            VFIO_USER_DMA_MAP,
+            &[map.as_bytes()],
+            &[descriptor.as_raw_descriptor()],
+        )?;
+        Ok(())
+    }
+
+    /// Removes a mapping added by `dma_map`.
+    pub fn dma_unmap(&self, address: u64, size: u64) -> Result<()> {
+        let unmap = DmaUnmap {
+            argsz: Le32::from(size_of::<DmaUnmap>() as u32),
+            flags: Le32::from(0),
+            address: Le64::from(address),
+            size: Le64::from(size),
+        };
+        self.request(VFIO_USER_DMA_UNMAP, &[unmap.as_bytes()], &[])?;
+        Ok(())
+    }
+
+    fn set_irqs(
+        &self,
+        index: u32,
+        flags: u32,
+        start: u32,
+        count: u32,
+        fds: &[RawDescriptor],
+    ) -> Result<()> {
+        if index >= self.num_irqs {
+            return Err(Error::InvalidIrqIndex(index));
+        }
+        let irq_set = IrqSet {
+            argsz: Le32::from(size_of::<IrqSet>() as u32),
+            flags: flags.into(),
+            index: index.into(),
+            start: start.into(),
+            count: count.into(),
+        };
+        self.request(VFIO_USER_DEVICE_SET_IRQS, &[irq_set.as_bytes()], fds)?;
+        Ok(())
+    }
+
+    /// Makes the device signal `events[i]` for the interrupt `start + i` of type `index`.
+    pub fn irq_enable(&self, events: &[&Event], index: u32, start: u32) -> Result<()> {
+        let fds: Vec<RawDescriptor> = events.iter().map(|e| e.as_raw_descriptor()).collect();
+        // A single message can't carry more descriptors than the server accepts.
+        for (i, chunk) in fds.chunks(self.max_msg_fds).enumerate() {
+            self.set_irqs(
+                index,
+                VFIO_IRQ_SET_DATA_EVENTFD | VFIO_IRQ_SET_ACTION_TRIGGER,
+                start + (i * self.max_msg_fds) as u32,
+                chunk.len() as u32,
+                chunk,
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Makes the device unmask its level triggered interrupt of type `index` when `event` is
+    /// signaled.
+    pub fn resample_enable(&self, event: &Event, index: u32) -> Result<()> {
+        self.set_irqs(
+            index,
+            VFIO_IRQ_SET_DATA_EVENTFD | VFIO_IRQ_SET_ACTION_UNMASK,
+            0,
+            1,
+            &[event.as_raw_descriptor()],
+        )
+    }
+
+    /// Stops the device from signaling interrupts of type `index`.
+    pub fn irq_disable(&self, index: u32) -> Result<()> {
+        self.set_irqs(
+            index,
+            VFIO_IRQ_SET_DATA_NONE | VFIO_IRQ_SET_ACTION_TRIGGER,
+            0,
+            0,
+            &[],
+        )
+    }
+
+    fn check_region_access(&self, index: u32, offset: u64, len: usize) -> Result<()> {
+        match offset.checked_add(len as u64) {
+            Some(end) if end <= self.region_size(index) => Ok(()),
+            _ => Err(Error::InvalidRegionAccess(index, offset, len)),
+        }
+    }
+
+    /// Reads `data.len()` bytes at `offset` of region `index`.
+    pub fn region_read(&self, index: u32, offset: u64, data: &mut [u8]) -> Result<()> {
+        self.check_region_access(index, offset, data.len())?;
+        let access = RegionAccess {
+            offset: offset.into(),
+            region: index.into(),
+            count: Le32::from(data.len() as u32),
+        };
+        let reply = self.request(VFIO_USER_REGION_READ, &[access.as_bytes()], &[])?;
+        let reply_data = reply
+            .payload
+            .get(size_of::<RegionAccess>()..)
+            .filter(|d| d.len() == data.len())
+            .ok_or(Error::MalformedReply(VFIO_USER_REGION_READ))?;
+        data.copy_from_slice(reply_data);
+        Ok(())
+    }
+
+    /// Reads a `T` at `offset` of region `index`.
+    pub fn region_read_obj<T: AsBytes + FromBytes + Default>(
+        &self,
+        index: u32,
+        offset: u64,
+    ) -> Result<T> {
+        let mut val = T::default();
+        self.region_read(index, offset, val.as_bytes_mut())?;
+        Ok(val)
+    }
+
+    /// Writes `data` at `offset` of region `index`.
+    pub fn region_write(&self, index: u32, offset: u64, data: &[u8]) -> Result<()> {
+        self.check_region_access(index, offset, data.len())?;
+        let access = RegionAccess {
+            offset: offset.into(),
+            region: index.into(),
+            count: Le32::from(data.len() as u32),
+        };
+        self.request(VFIO_USER_REGION_WRITE, &[access.as_bytes(), data], &[])?;
+        Ok(())
+    }
+
+    /// Resets the device.
+    pub fn reset(&self) -> Result<()> {
+        self.request(VFIO_USER_DEVICE_RESET, &[], &[])?;
+        Ok(())
+    }
+}
+
+impl AsRawDescriptor for VfioUserClient {
+    fn as_raw_descriptor(&self) -> RawDescriptor {
+        self.socket.as_raw_descriptor()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+    use std::io::Seek;
+    use std::io::SeekFrom;
+    use std::os::unix::net::UnixStream;
+    use std::thread;
+    use std::time::Duration;
+
+    use base::Event;
+    use base::EventWaitResult;
+    use base::SharedMemory;
+
+    use super::*;
+    use crate::vfio_user::test_server;
+
+    fn connect() -> (VfioUserClient, thread::JoinHandle<()>) {
+        let (client, server) = UnixStream::pair().unwrap();
+        let server = thread::spawn(move || test_server::run(server));
+        (VfioUserClient::new(client).unwrap(), server)
+    }
+
+    #[test]
+    fn device_info() {
+        let (client, server) = connect();
+        assert_eq!(client.num_regions(), VFIO_PCI_NUM_REGIONS);
+        assert_eq!(
+            client.region_size(VFIO_PCI_BAR0_REGION_INDEX),
+            test_server::BAR0_SIZE
+        );
+        assert_eq!(client.region_size(VFIO_PCI_BAR2_REGION_INDEX), 0);
+        assert_eq!(
+            client.region_size(VFIO_PCI_CONFIG_REGION_INDEX),
+            test_server::CONFIG_SIZE
+        );
+        assert_eq!(client.irq_count(VFIO_PCI_INTX_IRQ_INDEX).unwrap(), 1);
+        assert_eq!(
+            client.irq_count(VFIO_PCI_MSIX_IRQ_INDEX).unwrap(),
+            test_server::MSIX_VECTORS
+        );
+
+        let vendor: u16 = client
+            .region_read_obj(VFIO_PCI_CONFIG_REGION_INDEX, 0)
+            .unwrap();
+        assert_eq!(vendor, test_server::VENDOR_ID);
+
+        drop(client);
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn region_access() {
+        let (client, server) = connect();
+        client
+            .region_write(
+                VFIO_PCI_BAR0_REGION_INDEX,
+                test_server::REG_SCRATCH,
+                &0xdeadbeefu32.to_le_bytes(),
+            )
+            .unwrap();
+        let scratch: u32 = client
+            .region_read_obj(VFIO_PCI_BAR0_REGION_INDEX, test_server::REG_SCRATCH)
+            .unwrap();
+        assert_eq!(scratch, 0xdeadbeef);
+
+        // Accesses past the end of a region aren't sent to the server.
+        assert!(client
+            .region_read_obj::<u32>(VFIO_PCI_BAR0_REGION_INDEX, test_server::BAR0_SIZE - 2)
+            .is_err());
+        // Errors reported by the server are returned.
+        assert!(matches!(
+            client.region_write(VFIO_PCI_BAR0_REGION_INDEX, 0x100, &[0]),
+            Err(Error::CommandFailed(VFIO_USER_REGION_WRITE, _))
+        ));
+
+        drop(client);
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn concurrent_requests() {
+        let (client, server) = connect();
+        thread::scope(|s| {
+            for i in 0..4u64 {
+                let client = &client;
+                s.spawn(move || {
+                    let offset = test_server::REG_SCRATCH + i * 4;
+                    for _ in 0..100 {
+                        let value: u32 = client
+                            .region_read_obj(VFIO_PCI_CONFIG_REGION_INDEX, 0)
+                            .unwrap();
+                        assert_eq!(value as u16, test_server::VENDOR_ID);
+                        client
+                            .region_write(
+                                VFIO_PCI_BAR0_REGION_INDEX,
+                                offset,
+                                &(i as u32).to_le_bytes(),
+                            )
+                            .unwrap();
+                    }
+                });
+            }
+        });
+        for i in 0..4u64 {
+            let value: u32 = client
+                .region_read_obj(VFIO_PCI_BAR0_REGION_INDEX, test_server::REG_SCRATCH + i * 4)
+                .unwrap();
+            assert_eq!(value, i as u32);
+        }
+
+        drop(client);
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn timeout() {
+        // The server never replies.
+        let (client, _server) = UnixStream::pair().unwrap();
+        assert!(matches!(
+            VfioUserClient::new(client),
+            Err(Error::Timeout(VFIO_USER_VERSION))
+        ));
+    }
+
+    #[test]
+    fn dma() {
+        let (client, server) = connect();
+        let mut shm = SharedMemory::new("vfio_user_dma", 0x2000).unwrap();
+        client.dma_map(0x10000, 0x1000, &shm, 0x1000).unwrap();
+
+        // Ask the device to write to guest address 0x10010, which is at 0x1010 in `shm`.
+        client
+            .region_write(
+                VFIO_PCI_BAR0_REGION_INDEX,
+                test_server::REG_DMA_ADDR,
+                &0x10010u64.to_le_bytes(),
+            )
+            .unwrap();
+        client
+            .region_write(
+                VFIO_PCI_BAR0_REGION_INDEX,
+                test_server::REG_DMA_DATA,
+                &0x12345678u32.to_le_bytes(),
+            )
+            .unwrap();
+        let mut data = [0u8; 4];
+        shm.seek(SeekFrom::Start(0x1010)).unwrap();
+        shm.read_exact(&mut data).unwrap();
+        assert_eq!(u32::from_le_bytes(data), 0x12345678);
+
+        client.dma_unmap(0x10000, 0x1000).unwrap();
+        assert!(client
+            .region_write(
+                VFIO_PCI_BAR0_REGION_INDEX,
+                test_server::REG_DMA_DATA,
+                &0u32.to_le_bytes(),
+            )
+            .is_err());
+
+        drop(client);
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn interrupts() {
+        let (client, server) = connect();
+        let events: Vec<Event> = (0..test_server::MSIX_VECTORS)
+            .map(|_| Event::new().unwrap())
+            .collect();
+        let event_refs: Vec<&Event> = events.iter().collect();
+        // The test server only takes one descriptor per message, so this needs several.
+        client
+            .irq_enable(&event_refs, VFIO_PCI_MSIX_IRQ_INDEX, 0)
+            .unwrap();
+
+        let trigger = (VFIO_PCI_MSIX_IRQ_INDEX << 16) | 2;
+        client
+            .region_write(
+                VFIO_PCI_BAR0_REGION_INDEX,
+                test_server::REG_IRQ,
+                &trigger.to_le_bytes(),
+            )
+            .unwrap();
+        assert_eq!(
+            events[2].wait_timeout(Duration::from_secs(5)).unwrap(),
+            EventWaitResult::Signaled
+        );
+        assert_eq!(
+            events[1].wait_timeout(Duration::from_millis(1)).unwrap(),
+            EventWaitResult::TimedOut
+        );
+
+        client.irq_disable(VFIO_PCI_MSIX_IRQ_INDEX).unwrap();
+        assert!(client
+            .region_write(
+                VFIO_PCI_BAR0_REGION_INDEX,
+                test_server::REG_IRQ,
+                &trigger.to_le_bytes(),
+            )
+            .is_err());
+
+        drop(client);
+        server.join().unwrap();
+    }
+}
@@ -0,0 +1,14 @@
+// Copyright 2023 The ChromiumOS Authors
+// Use of this source code is governed by a BSD-style license that can be
+// found in the LICENSE file.
+
+//! Client of the vfio-user protocol, used to drive PCI devices emulated by another process over a
+//! unix socket.
+
+mod client;
+pub mod protocol;
+#[cfg(test)]
+pub(crate) mod test_server;
+
+pub use self::client::VfioUserClient;
+pub use self::protocol::Error as VfioUserError;
@@ -0,0 +1,262 @@
+// Copyright 2023 The ChromiumOS Authors
+// Use of this source code is governed by a BSD-style license that can be
+// found in the LICENSE file.
+
+//! Messages of the vfio-user protocol, see docs/devel/vfio-user.rst in the QEMU tree.
+//! Every message starts with a `Header` and is sent over a unix stream socket, with file
+//! descriptors attached as SCM_RIGHTS ancillary data. All fields are little-endian.
+
+use std::fs::File;
+use std::io;
+use std::io::IoSlice;
+use std::io::IoSliceMut;
+use std::io::Read;
+use std::io::Write;
+use std::mem::size_of;
+use std::os::unix::net::UnixStream;
+
+use base::FromRawDescriptor;
+use base::RawDescriptor;
+use base::ScmSocket;
+use base::SCM_SOCKET_MAX_FD_COUNT;
+use data_model::Le16;
+use data_model::Le32;
+use data_model::Le64;
+use remain::sorted;
+use thiserror::Error;
+use zerocopy::AsBytes;
+use zerocopy::FromBytes;
+
+pub const VFIO_USER_MAJOR_VERSION: u16 = 0;
+pub const VFIO_USER_MINOR_VERSION: u16 = 1;
+
+pub const VFIO_USER_VERSION: u16 = 1;
+pub const VFIO_USER_DMA_MAP: u16 = 2;
+pub const VFIO_USER_DMA_UNMAP: u16 = 3;
+pub const VFIO_USER_DEVICE_GET_INFO: u16 = 4;
+pub const VFIO_USER_DEVICE_GET_REGION_INFO: u16 = 5;
+pub const VFIO_USER_DEVICE_GET_IRQ_INFO: u16 = 7;
+pub const VFIO_USER_DEVICE_SET_IRQS: u16 = 8;
+pub const VFIO_USER_REGION_READ: u16 = 9;
+pub const VFIO_USER_REGION_WRITE: u16 = 10;
+pub const VFIO_USER_DEVICE_RESET: u16 = 13;
+
+pub const VFIO_USER_F_TYPE_MASK: u32 = 0xf;
+pub const VFIO_USER_F_TYPE_COMMAND: u32 = 0;
+pub const VFIO_USER_F_TYPE_REPLY: u32 = 1;
+pub const VFIO_USER_F_NO_REPLY: u32 = 0x10;
+pub const VFIO_USER_F_ERROR: u32 = 0x20;
+
+pub const VFIO_USER_F_DMA_REGION_READ: u32 = 1;
+pub const VFIO_USER_F_DMA_REGION_WRITE: u32 = 2;
+
+/// Largest message accepted from the peer. Region accesses done by the guest are at most a few
+/// bytes, so this only needs to fit the replies to the informational commands.
+pub const MAX_MESSAGE_SIZE: usize = 1 << 20;
+
+#[sorted]
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("vfio-user command {0} failed: {1}")]
+    CommandFailed(u16, base::Error),
+    #[error("failed to connect to vfio-user server: {0}")]
+    Connect(io::Error),
+    #[error("failed to create event: {0}")]
+    CreateEvent(base::Error),
+    #[error("vfio-user peer closed the connection")]
+    Disconnected,
+    #[error("invalid vfio-user irq index {0}")]
+    InvalidIrqIndex(u32),
+    #[error("invalid vfio-user region access: region {0}, offset {1:#x}, size {2:#x}")]
+    InvalidRegionAccess(u32, u64, usize),
+    #[error("malformed vfio-user reply to command {0}")]
+    MalformedReply(u16),
+    #[error("vfio-user message of {0} bytes is too large")]
+    MessageTooLarge(usize),
+    #[error("failed to receive vfio-user message: {0}")]
+    Recv(io::Error),
+    #[error("failed to send vfio-user message: {0}")]
+    Send(io::Error),
+    #[error("failed to set the vfio-user socket timeout: {0}")]
+    SetTimeout(io::Error),
+    #[error("vfio-user command {0} timed out")]
+    Timeout(u16),
+    #[error("unexpected vfio-user message {1} with id {0}")]
+    UnexpectedMessage(u16, u16),
+    #[error("unsupported vfio-user version {0}.{1}")]
+    UnsupportedVersion(u16, u16),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default, FromBytes, AsBytes)]
+pub struct Header {
+    pub msg_id: Le16,
+    pub command: Le16,
+    /// Size of the whole message, including this header.
+    pub msg_size: Le32,
+    pub flags: Le32,
+    /// errno of a failed command, only valid in replies with `VFIO_USER_F_ERROR` set.
+    pub error_no: Le32,
+}
+
+/// Payload of `VFIO_USER_VERSION`, followed by a NUL-terminated JSON string of capabilities.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default, FromBytes, AsBytes)]
+pub struct Version {
+    pub major: Le16,
+    pub minor: Le16,
+}
+
+/// Payload of `VFIO_USER_DMA_MAP`. The file descriptor backing the mapping is attached to the
+/// message.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default, FromBytes, AsBytes)]
+pub struct DmaMap {
+    pub argsz: Le32,
+    pub flags: Le32,
+    /// Offset of the mapping in the attached file.
+    pub offset: Le64,
+    /// DMA address of the mapping, as seen by the device.
+    pub address: Le64,
+    pub size: Le64,
+}
+
+/// Payload of `VFIO_USER_DMA_UNMAP`.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default, FromBytes, AsBytes)]
+pub struct DmaUnmap {
+    pub argsz: Le32,
+    pub flags: Le32,
+    pub address: Le64,
+    pub size: Le64,
+}
+
+/// Payload of `VFIO_USER_DEVICE_GET_INFO`, mirroring `struct vfio_device_info`.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default, FromBytes, AsBytes)]
+pub struct DeviceInfo {
+    pub argsz: Le32,
+    pub flags: Le32,
+    pub num_regions: Le32,
+    pub num_irqs: Le32,
+}
+
+/// Payload of `VFIO_USER_DEVICE_GET_REGION_INFO`, mirroring `struct vfio_region_info`.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default, FromBytes, AsBytes)]
+pub struct RegionInfo {
+    pub argsz: Le32,
+    pub flags: Le32,
+    pub index: Le32,
+    pub cap_offset: Le32,
+    pub size: Le64,
+    pub offset: Le64,
+}
+
+/// Payload of `VFIO_USER_DEVICE_GET_IRQ_INFO`, mirroring `struct vfio_irq_info`.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default, FromBytes, AsBytes)]
+pub struct IrqInfo {
+    pub argsz: Le32,
+    pub flags: Le32,
+    pub index: Le32,
+    pub count: Le32,
+}
+
+/// Payload of `VFIO_USER_DEVICE_SET_IRQS`, mirroring `struct vfio_irq_set`. Eventfds are
+/// attached to the message instead of following the header.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default, FromBytes, AsBytes)]
+pub struct IrqSet {
+    pub argsz: Le32,
+    pub flags: Le32,
+    pub index: Le32,
+    pub start: Le32,
+    pub count: Le32,
+}
+
+/// Payload of `VFIO_USER_REGION_READ` and `VFIO_USER_REGION_WRITE`, followed by the data for
+/// write requests and read replies.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default, FromBytes, AsBytes)]
+pub struct RegionAccess {
+    pub offset: Le64,
+    pub region: Le32,
+    pub count: Le32,
+}
+
+/// A message received from the peer.
+pub struct Message {
+    pub header: Header,
+    pub payload: Vec<u8>,
+    pub files: Vec<File>,
+}
+
+impl Message {
+    /// Parses the start of the payload as a `T`.
+    pub fn payload_as<T: FromBytes>(&self) -> Option<T> {
+        T::read_from_prefix(self.payload.as_slice())
+    }
+}
+
+/// Sends a message made of `header`, with its `msg_size` filled in, followed by `payload`.
+pub fn send_message(
+    socket: &UnixStream,
+    mut header: Header,
+    payload: &[&[u8]],
+    fds: &[RawDescriptor],
+) -> Result<()> {
+    let size = size_of::<Header>() + payload.iter().map(|p| p.len()).sum::<usize>();
+    header.msg_size = Le32::from(size as u32);
+
+    let mut message = Vec::with_capacity(size);
+    message.extend_from_slice(header.as_bytes());
+    for p in payload {
+        message.extend_from_slice(p);
+    }
+
+    let sent = socket
+        .send_bufs_with_fds(&[IoSlice::new(&message)], fds)
+        .map_err(|e| Error::Send(e.into()))?;
+    // The file descriptors went with the first chunk, a stream socket may need more writes for
+    // the rest of the message.
+    let mut socket = socket;
+    socket.write_all(&message[sent..]).map_err(Error::Send)
+}
+
+/// Receives a message and the file descriptors attached to it.
+pub fn recv_message(socket: &UnixStream) -> Result<Message> {
+    let mut header = Header::default();
+    let mut fds = [0; SCM_SOCKET_MAX_FD_COUNT];
+    let (read, fd_count) = socket
+        .recv_with_fds(IoSliceMut::new(header.as_bytes_mut()), &mut fds)
+        .map_err(|e| Error::Recv(e.into()))?;
+    let files: Vec<File> = fds[..fd_count]
+        .iter()
+        // Safe because the descriptors were just received and are owned by nobody else.
+        .map(|&fd| unsafe { File::from_raw_descriptor(fd) })
+        .collect();
+    if read == 0 {
+        return Err(Error::Disconnected);
+    }
+
+    let mut socket = socket;
+    socket
+        .read_exact(&mut header.as_bytes_mut()[read..])
+        .map_err(Error::Recv)?;
+
+    let size = header.msg_size.to_native() as usize;
+    if size > MAX_MESSAGE_SIZE {
+        return Err(Error::MessageTooLarge(size));
+    }
+    let mut payload = vec![0; size.saturating_sub(size_of::<Header>())];
+    socket.read_exact(&mut payload).map_err(Error::Recv)?;
+
+    Ok(Message {
+        header,
+        payload,
+        files,
+    })
+}
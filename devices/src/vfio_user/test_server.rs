@@ -0,0 +1,305 @@
+// Copyright 2023 The ChromiumOS Authors
+// Use of this source code is governed by a BSD-style license that can be
+// found in the LICENSE file.
+
+//! A small vfio-user server emulating a PCI device, to test the client without a device model
+//! running in another process.
+//!
+//! The device has a register BAR at BAR0, its MSI-X table in BAR1, a 64-bit BAR, and an MSI
+//! capability. BAR0 registers:
+//!  - `REG_SCRATCH`: read/write scratch register.
+//!  - `REG_DMA_ADDR`: DMA address used by `REG_DMA_DATA`.
+//!  - `REG_DMA_DATA`: writing a u32 stores it at `REG_DMA_ADDR` through DMA.
+//!  - `REG_IRQ`: writing `index << 16 | vector` signals that interrupt.
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::Write;
+use std::mem::size_of;
+use std::os::unix::fs::FileExt;
+use std::os::unix::net::UnixStream;
+
+use data_model::Le32;
+use libc::EFAULT;
+use libc::EINVAL;
+use libc::EIO;
+use vfio_sys::*;
+use zerocopy::AsBytes;
+
+use super::protocol::*;
+
+pub const VENDOR_ID: u16 = 0x1234;
+pub const DEVICE_ID: u16 = 0xabcd;
+pub const CONFIG_SIZE: u64 = 0x100;
+pub const BAR0_SIZE: u64 = 0x1000;
+pub const BAR1_SIZE: u64 = 0x1000;
+pub const MSIX_VECTORS: u32 = 4;
+
+pub const REG_SCRATCH: u64 = 0x0;
+pub const REG_DMA_ADDR: u64 = 0x8;
+pub const REG_DMA_DATA: u64 = 0x10;
+pub const REG_IRQ: u64 = 0x18;
+// Registers past this offset of BAR0 don't exist and fail to be accessed.
+const BAR0_REGS_END: u64 = 0x100;
+
+pub const MSIX_CAP_OFFSET: u32 = 0x40;
+pub const MSIX_PBA_OFFSET: u64 = 0x800;
+pub const MSI_CAP_OFFSET: u32 = 0x50;
+
+type ReplyResult = std::result::Result<Vec<u8>, i32>;
+
+struct DmaMapping {
+    address: u64,
+    size: u64,
+    file: File,
+    offset: u64,
+}
+
+struct TestDevice {
+    config: Vec<u8>,
+    bar0: Vec<u8>,
+    bar1: Vec<u8>,
+    dma: Vec<DmaMapping>,
+    // Eventfds of the interrupts, by irq index and vector.
+    irqs: HashMap<(u32, u32), File>,
+}
+
+impl TestDevice {
+    fn new() -> Self {
+        let mut config = vec![0u8; CONFIG_SIZE as usize];
+        config[0x00..0x02].copy_from_slice(&VENDOR_ID.to_le_bytes());
+        config[0x02..0x04].copy_from_slice(&DEVICE_ID.to_le_bytes());
+        // Capabilities list present.
+        config[0x06] = 0x10;
+        // Class "other".
+        config[0x0b] = 0xff;
+        // BAR1 is a 64-bit memory BAR.
+        config[0x14] = 0x4;
+        config[0x34] = MSIX_CAP_OFFSET as u8;
+        // INTA.
+        config[0x3d] = 1;
+
+        let msix = &mut config[MSIX_CAP_OFFSET as usize..];
+        msix[0] = 0x11;
+        msix[1] = MSI_CAP_OFFSET as u8;
+        msix[2..4].copy_from_slice(&(MSIX_VECTORS as u16 - 1).to_le_bytes());
+        // Table at offset 0 of BAR1, followed by the PBA.
+        msix[4..8].copy_from_slice(&1u32.to_le_bytes());
+        msix[8..12].copy_from_slice(&(MSIX_PBA_OFFSET as u32 | 1).to_le_bytes());
+
+        // A single 32-bit vector without masking.
+        config[MSI_CAP_OFFSET as usize] = 0x05;
+
+        TestDevice {
+            config,
+            bar0: vec![0; BAR0_SIZE as usize],
+            bar1: vec![0; BAR1_SIZE as usize],
+            dma: Vec::new(),
+            irqs: HashMap::new(),
+        }
+    }
+
+    fn irq_count(index: u32) -> u32 {
+        match index {
+            VFIO_PCI_INTX_IRQ_INDEX | VFIO_PCI_MSI_IRQ_INDEX => 1,
+            VFIO_PCI_MSIX_IRQ_INDEX => MSIX_VECTORS,
+            _ => 0,
+        }
+    }
+
+    fn region(&mut self, index: u32) -> Option<&mut Vec<u8>> {
+        match index {
+            VFIO_PCI_BAR0_REGION_INDEX => Some(&mut self.bar0),
+            VFIO_PCI_BAR1_REGION_INDEX => Some(&mut self.bar1),
+            VFIO_PCI_CONFIG_REGION_INDEX => Some(&mut self.config),
+            _ => None,
+        }
+    }
+
+    fn handle(&mut self, message: Message) -> ReplyResult {
+        match message.header.command.to_native() {
+            VFIO_USER_VERSION => {
+                let version = Version {
+                    major: VFIO_USER_MAJOR_VERSION.into(),
+                    minor: VFIO_USER_MINOR_VERSION.into(),
+                };
+                let mut reply = version.as_bytes().to_vec();
+                reply.extend_from_slice(b"{\"capabilities\":{\"max_msg_fds\":1}}\0");
+                Ok(reply)
+            }
+            VFIO_USER_DMA_MAP => {
+                let map: DmaMap = message.payload_as().ok_or(EINVAL)?;
+                let file = message.files.into_iter().next().ok_or(EINVAL)?;
+                self.dma.push(DmaMapping {
+                    address: map.address.to_native(),
+                    size: map.size.to_native(),
+                    file,
+                    offset: map.offset.to_native(),
+                });
+                Ok(Vec::new())
+            }
+            VFIO_USER_DMA_UNMAP => {
+                let unmap: DmaUnmap = message.payload_as().ok_or(EINVAL)?;
+                let index = self
+                    .dma
+                    .iter()
+                    .position(|m| {
+                        m.address == unmap.address.to_native() && m.size == unmap.size.to_native()
+                    })
+                    .ok_or(EINVAL)?;
+                self.dma.remove(index);
+                Ok(Vec::new())
+            }
+            VFIO_USER_DEVICE_GET_INFO => {
+                let info = DeviceInfo {
+                    argsz: Le32::from(size_of::<DeviceInfo>() as u32),
+                    flags: Le32::from(VFIO_DEVICE_FLAGS_PCI),
+                    num_regions: Le32::from(VFIO_PCI_NUM_REGIONS),
+                    num_irqs: Le32::from(VFIO_PCI_NUM_IRQS),
+                };
+                Ok(info.as_bytes().to_vec())
+            }
+            VFIO_USER_DEVICE_GET_REGION_INFO => {
+                let mut info: RegionInfo = message.payload_as().ok_or(EINVAL)?;
+                let size = self
+                    .region(info.index.to_native())
+                    .map_or(0, |r| r.len() as u64);
+                info.size = size.into();
+                info.flags = Le32::from(if size != 0 {
+                    VFIO_REGION_INFO_FLAG_READ | VFIO_REGION_INFO_FLAG_WRITE
+                } else {
+                    0
+                });
+                Ok(info.as_bytes().to_vec())
+            }
+            VFIO_USER_DEVICE_GET_IRQ_INFO => {
+                let mut info: IrqInfo = message.payload_as().ok_or(EINVAL)?;
+                info.count = Self::irq_count(info.index.to_native()).into();
+                Ok(info.as_bytes().to_vec())
+            }
+            VFIO_USER_DEVICE_SET_IRQS => {
+                let irq_set: IrqSet = message.payload_as().ok_or(EINVAL)?;
+                self.set_irqs(irq_set, message.files)?;
+                Ok(Vec::new())
+            }
+            VFIO_USER_REGION_READ => {
+                let access: RegionAccess = message.payload_as().ok_or(EINVAL)?;
+                let (index, offset, count) = (
+                    access.region.to_native(),
+                    access.offset.to_native() as usize,
+                    access.count.to_native() as usize,
+                );
+                if index == VFIO_PCI_BAR0_REGION_INDEX && offset as u64 >= BAR0_REGS_END {
+                    return Err(EIO);
+                }
+                let region = self.region(index).ok_or(EINVAL)?;
+                let data = region.get(offset..offset + count).ok_or(EINVAL)?;
+                let mut reply = access.as_bytes().to_vec();
+                reply.extend_from_slice(data);
+                Ok(reply)
+            }
+            VFIO_USER_REGION_WRITE => {
+                let access: RegionAccess = message.payload_as().ok_or(EINVAL)?;
+                let (index, offset, count) = (
+                    access.region.to_native(),
+                    access.offset.to_native() as usize,
+                    access.count.to_native() as usize,
+                );
+                let data = message
+                    .payload
+                    .get(size_of::<RegionAccess>()..)
+                    .filter(|d| d.len() == count)
+                    .ok_or(EINVAL)?;
+                if index == VFIO_PCI_BAR0_REGION_INDEX {
+                    self.write_bar0(offset as u64, data)?;
+                }
+                let region = self.region(index).ok_or(EINVAL)?;
+                region
+                    .get_mut(offset..offset + count)
+                    .ok_or(EINVAL)?
+                    .copy_from_slice(data);
+                Ok(access.as_bytes().to_vec())
+            }
+            VFIO_USER_DEVICE_RESET => {
+                self.bar0.iter_mut().for_each(|b| *b = 0);
+                self.irqs.clear();
+                Ok(Vec::new())
+            }
+            _ => Err(EINVAL),
+        }
+    }
+
+    fn set_irqs(&mut self, irq_set: IrqSet, files: Vec<File>) -> std::result::Result<(), i32> {
+        let (flags, index, start, count) = (
+            irq_set.flags.to_native(),
+            irq_set.index.to_native(),
+            irq_set.start.to_native(),
+            irq_set.count.to_native(),
+        );
+        if start + count > Self::irq_count(index) || files.len() > 1 {
+            return Err(EINVAL);
+        }
+        match flags {
+            f if f == VFIO_IRQ_SET_DATA_NONE | VFIO_IRQ_SET_ACTION_TRIGGER && count == 0 => {
+                self.irqs.retain(|(i, _), _| *i != index);
+            }
+            f if f == VFIO_IRQ_SET_DATA_EVENTFD | VFIO_IRQ_SET_ACTION_TRIGGER => {
+                if files.len() != count as usize {
+                    return Err(EINVAL);
+                }
+                for (vector, file) in (start..).zip(files) {
+                    self.irqs.insert((index, vector), file);
+                }
+            }
+            // Interrupts are edge triggered, there is nothing to unmask.
+            f if f == VFIO_IRQ_SET_DATA_EVENTFD | VFIO_IRQ_SET_ACTION_UNMASK => (),
+            _ => return Err(EINVAL),
+        }
+        Ok(())
+    }
+
+    fn write_bar0(&mut self, offset: u64, data: &[u8]) -> std::result::Result<(), i32> {
+        match offset {
+            REG_DMA_DATA => {
+                let mut addr = [0u8; 8];
+                addr.copy_from_slice(&self.bar0[REG_DMA_ADDR as usize..REG_DMA_ADDR as usize + 8]);
+                let addr = u64::from_le_bytes(addr);
+                let mapping = self
+                    .dma
+                    .iter()
+                    .find(|m| addr >= m.address && addr + data.len() as u64 <= m.address + m.size)
+                    .ok_or(EFAULT)?;
+                mapping
+                    .file
+                    .write_all_at(data, mapping.offset + addr - mapping.address)
+                    .map_err(|_| EFAULT)
+            }
+            REG_IRQ => {
+                let mut irq = [0u8; 4];
+                irq[..data.len().min(4)].copy_from_slice(&data[..data.len().min(4)]);
+                let irq = u32::from_le_bytes(irq);
+                let mut file = self.irqs.get(&(irq >> 16, irq & 0xffff)).ok_or(EINVAL)?;
+                file.write_all(&1u64.to_ne_bytes()).map_err(|_| EIO)
+            }
+            o if o >= BAR0_REGS_END => Err(EIO),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Serves the device on `socket` until the client disconnects.
+pub fn run(socket: UnixStream) {
+    let mut device = TestDevice::new();
+    while let Ok(message) = recv_message(&socket) {
+        let mut header = message.header;
+        let (flags, error_no, reply) = match device.handle(message) {
+            Ok(reply) => (VFIO_USER_F_TYPE_REPLY, 0, reply),
+            Err(e) => (VFIO_USER_F_TYPE_REPLY | VFIO_USER_F_ERROR, e, Vec::new()),
+        };
+        header.flags = flags.into();
+        header.error_no = Le32::from(error_no as u32);
+        if send_message(&socket, header, &[&reply], &[]).is_err() {
+            return;
+        }
+    }
+}
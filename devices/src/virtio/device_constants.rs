@@ -136,6 +136,7 @@ pub mod snd {
         pub jacks: Le32,
         pub streams: Le32,
         pub chmaps: Le32,
+        pub controls: Le32,
     }
 }
 
@@ -46,12 +46,14 @@ mod caps;
 mod expiring_map;
 mod multikey;
 pub mod passthrough;
+mod policy;
 mod read_dir;
 mod worker;
 
 use fuse::Server;
 use passthrough::PassthroughFs;
 pub use worker::process_fs_queue;
+pub use worker::BlockingRequests;
 use worker::Worker;
 
 const FS_BAR_NUM: u8 = 4;
@@ -62,6 +64,9 @@ const FS_BAR_SIZE: u64 = 1 << 33;
 #[sorted]
 #[derive(Error, Debug)]
 pub enum Error {
+    /// Failed to create an event.
+    #[error("failed to create event: {0}")]
+    CreateEvent(SysError),
     /// Failed to create the file system.
     #[error("failed to create file system: {0}")]
     CreateFs(io::Error),
@@ -89,6 +94,9 @@ pub enum Error {
     /// Failed to signal the virio used queue.
     #[error("failed to signal used queue: {0}")]
     SignalUsedQueue(SysError),
+    /// Failed to spawn a thread for a request that may block.
+    #[error("failed to spawn thread for blocking request: {0}")]
+    SpawnBlockingThread(io::Error),
     /// The tag for the Fs device was too long to fit in the config space.
     #[error("Fs device tag is too long: len = {0}, max = {}", FS_MAX_TAG_LEN)]
     TagTooLong(usize),
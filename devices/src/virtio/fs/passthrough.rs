@@ -8,6 +8,8 @@ use std::collections::btree_map;
 use std::collections::BTreeMap;
 use std::ffi::CStr;
 use std::ffi::CString;
+use std::ffi::OsStr;
+use std::ffi::OsString;
 use std::fs::File;
 use std::io;
 use std::mem;
@@ -15,6 +17,10 @@ use std::mem::size_of;
 use std::mem::MaybeUninit;
 use std::os::raw::c_int;
 use std::os::raw::c_long;
+use std::os::unix::ffi::OsStrExt;
+use std::os::unix::ffi::OsStringExt;
+use std::path::Path;
+use std::path::PathBuf;
 use std::ptr::addr_of;
 use std::ptr::addr_of_mut;
 use std::str::FromStr;
@@ -32,7 +38,10 @@ use base::ioctl_iowr_nr;
 use base::ioctl_with_mut_ptr;
 use base::ioctl_with_ptr;
 use base::syscall;
+use base::warn;
 use base::AsRawDescriptor;
+use base::Event;
+use base::EventWaitResult;
 use base::FileFlags;
 use base::FromRawDescriptor;
 use base::RawDescriptor;
@@ -41,6 +50,7 @@ use data_model::DataInit;
 use fuse::filesystem::Context;
 use fuse::filesystem::DirectoryIterator;
 use fuse::filesystem::Entry;
+use fuse::filesystem::FileLock;
 use fuse::filesystem::FileSystem;
 use fuse::filesystem::FsOptions;
 use fuse::filesystem::GetxattrReply;
@@ -79,6 +89,7 @@ use crate::virtio::fs::caps::Set as CapSet;
 use crate::virtio::fs::caps::Value as CapValue;
 use crate::virtio::fs::expiring_map::ExpiringMap;
 use crate::virtio::fs::multikey::MultikeyBTreeMap;
+use crate::virtio::fs::policy::PathPolicy;
 use crate::virtio::fs::read_dir::ReadDir;
 
 const EMPTY_CSTR: &[u8] = b"\0";
@@ -99,6 +110,11 @@ const FS_PROJINHERIT_FL: c_int = 0x20000000;
 #[cfg(feature = "arc_quota")]
 const DEFAULT_DBUS_TIMEOUT: Duration = Duration::from_secs(25);
 
+// A lock request waiting in the kernel can only be cancelled with a signal, which would race with
+// the request entering the kernel. Waiting locks are retried instead, at intervals growing up to
+// this one, so that the wait can be interrupted.
+const MAX_LOCK_RETRY_INTERVAL: Duration = Duration::from_millis(100);
+
 #[repr(C)]
 #[derive(Clone, Copy, AsBytes, FromBytes)]
 struct fscrypt_policy_v1 {
@@ -220,6 +236,8 @@ struct InodeData {
     file: Mutex<(File, libc::c_int)>,
     refcount: AtomicU64,
     filetype: FileType,
+    // Host files holding the POSIX locks of each lock owner of the guest, keyed by the owner.
+    posix_locks: Mutex<BTreeMap<u64, Arc<File>>>,
 }
 
 impl AsRawDescriptor for InodeData {
@@ -409,6 +427,14 @@ fn stat<F: AsRawDescriptor + ?Sized>(f: &F) -> io::Result<libc::stat64> {
     Ok(unsafe { st.assume_init() })
 }
 
+// Returns `path` relative to `root`, the host path of the shared directory. Files that were moved
+// out of the shared directory on the host have no such path and are inaccessible.
+fn share_relative_path(root: &Path, path: &Path) -> io::Result<PathBuf> {
+    path.strip_prefix(root)
+        .map(Path::to_path_buf)
+        .map_err(|_| io::Error::from_raw_os_error(libc::EACCES))
+}
+
 fn statat<D: AsRawDescriptor>(dir: &D, name: &CStr) -> io::Result<libc::stat64> {
     let mut st = MaybeUninit::<libc::stat64>::zeroed();
 
@@ -427,6 +453,47 @@ fn statat<D: AsRawDescriptor>(dir: &D, name: &CStr) -> io::Result<libc::stat64>
     Ok(unsafe { st.assume_init() })
 }
 
+// The value of `FileLock::end` for locks that extend to the end of the file.
+const LOCK_OFFSET_MAX: u64 = libc::off64_t::MAX as u64;
+
+// Converts a lock from the FUSE protocol, where the range is given by inclusive start and end
+// offsets, into a host lock.
+fn host_lock(lock: &FileLock) -> io::Result<libc::flock> {
+    if lock.start > LOCK_OFFSET_MAX || lock.end > LOCK_OFFSET_MAX || lock.end < lock.start {
+        return Err(io::Error::from_raw_os_error(libc::EINVAL));
+    }
+    let len = if lock.end == LOCK_OFFSET_MAX {
+        0
+    } else {
+        lock.end - lock.start + 1
+    };
+
+    // Safe because `libc::flock` only contains integers, for which all-zeroes is a valid value.
+    let mut fl: libc::flock = unsafe { mem::zeroed() };
+    fl.l_type = lock.type_ as libc::c_short;
+    fl.l_whence = libc::SEEK_SET as libc::c_short;
+    fl.l_start = lock.start as libc::off_t;
+    fl.l_len = len as libc::off_t;
+    Ok(fl)
+}
+
+// Converts a host lock returned by `F_OFD_GETLK` back into a lock for the FUSE protocol.
+fn fuse_lock(fl: &libc::flock) -> FileLock {
+    let start = fl.l_start as u64;
+    let end = if fl.l_len == 0 {
+        LOCK_OFFSET_MAX
+    } else {
+        start + fl.l_len as u64 - 1
+    };
+    FileLock {
+        start,
+        end,
+        type_: fl.l_type as u32,
+        // Open file description locks aren't owned by a process.
+        pid: 0,
+    }
+}
+
 /// The caching policy that the file system should report to the FUSE client. By default the FUSE
 /// protocol uses close-to-open consistency. This means that any cached contents of the file are
 /// invalidated the next time that file is opened.
@@ -462,6 +529,93 @@ impl FromStr for CachePolicy {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+struct IdMapRange {
+    guest: u32,
+    host: u32,
+    count: u32,
+}
+
+/// A translation table between uids or gids in the guest and the ids used by the file system on
+/// the host.
+///
+/// It is parsed from a comma-separated list of "guest host count" ranges, the same format as the
+/// user namespace id maps. Ids that are not covered by any range are passed through unchanged.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IdMap(Vec<IdMapRange>);
+
+impl IdMap {
+    /// Translates an id used by the guest into the id used on the host.
+    pub fn to_host(&self, id: u32) -> u32 {
+        self.0
+            .iter()
+            .find_map(|r| {
+                id.checked_sub(r.guest)
+                    .filter(|offset| *offset < r.count)
+                    .map(|offset| r.host + offset)
+            })
+            .unwrap_or(id)
+    }
+
+    /// Translates an id used on the host into the id seen by the guest.
+    pub fn to_guest(&self, id: u32) -> u32 {
+        self.0
+            .iter()
+            .find_map(|r| {
+                id.checked_sub(r.host)
+                    .filter(|offset| *offset < r.count)
+                    .map(|offset| r.guest + offset)
+            })
+            .unwrap_or(id)
+    }
+}
+
+impl FromStr for IdMap {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut ranges: Vec<IdMapRange> = Vec::new();
+        for range in s.split(',') {
+            let mut fields = range.split_whitespace().map(|f| {
+                f.parse::<u32>()
+                    .map_err(|_| "id map fields must be unsigned integers")
+            });
+            let mut next_field = || {
+                fields
+                    .next()
+                    .ok_or("id map ranges must be `guest host count`")
+            };
+            let guest = next_field()??;
+            let host = next_field()??;
+            let count = next_field()??;
+            if fields.next().is_some() {
+                return Err("id map ranges must be `guest host count`");
+            }
+
+            if count == 0
+                || guest.checked_add(count - 1).is_none()
+                || host.checked_add(count - 1).is_none()
+            {
+                return Err("invalid id map range");
+            }
+            // Overlapping ranges would make the translation ambiguous in one direction.
+            let overlaps = |start: u32, other_start: u32, other_count: u32| {
+                u64::from(start) < u64::from(other_start) + u64::from(other_count)
+                    && u64::from(other_start) < u64::from(start) + u64::from(count)
+            };
+            if ranges
+                .iter()
+                .any(|r| overlaps(guest, r.guest, r.count) || overlaps(host, r.host, r.count))
+            {
+                return Err("id map ranges must not overlap");
+            }
+
+            ranges.push(IdMapRange { guest, host, count });
+        }
+        Ok(IdMap(ranges))
+    }
+}
+
 /// Options that configure the behavior of the file system.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
@@ -537,6 +691,38 @@ pub struct Config {
     ///
     /// The default value for this option is `true`.
     pub posix_acl: bool,
+
+    /// Translation of uids between the guest and the host.
+    ///
+    /// Applied to the credentials of guest requests, to ownership changes, and to the attributes
+    /// returned to the guest. This allows sharing a directory owned by an unprivileged host user
+    /// with a guest that uses arbitrary uids.
+    ///
+    /// The default value for this option is an empty map, i.e. uids are passed through unchanged.
+    pub uid_map: IdMap,
+
+    /// Translation of gids between the guest and the host. See `uid_map` for details.
+    ///
+    /// The default value for this option is an empty map, i.e. gids are passed through unchanged.
+    pub gid_map: IdMap,
+
+    /// Reject every request that would modify the shared directory with `EROFS`.
+    ///
+    /// The default value for this option is `false`.
+    pub read_only: bool,
+
+    /// Paths, relative to the root of the shared directory, that the guest may access. When this
+    /// is not empty, everything outside of these paths is hidden from the guest except for the
+    /// directories leading to them.
+    ///
+    /// The default value for this option is an empty list, i.e. every path is accessible.
+    pub allow_paths: Vec<PathBuf>,
+
+    /// Paths, relative to the root of the shared directory, that the guest may not access. Takes
+    /// precedence over `allow_paths`.
+    ///
+    /// The default value for this option is an empty list.
+    pub deny_paths: Vec<PathBuf>,
 }
 
 impl Default for Config {
@@ -552,6 +738,11 @@ impl Default for Config {
             privileged_quota_uids: Default::default(),
             use_dax: false,
             posix_acl: true,
+            uid_map: Default::default(),
+            gid_map: Default::default(),
+            read_only: false,
+            allow_paths: Vec::new(),
+            deny_paths: Vec::new(),
         }
     }
 }
@@ -613,6 +804,18 @@ impl FromStr for Config {
                     let posix_acl = value.parse().map_err(|_| "`posix_acl` must be a boolean")?;
                     cfg.posix_acl = posix_acl;
                 }
+                "guest_uid_map" => {
+                    cfg.uid_map = value.parse()?;
+                }
+                "guest_gid_map" => {
+                    cfg.gid_map = value.parse()?;
+                }
+                "read_only" => {
+                    let read_only = value.parse().map_err(|_| "`read_only` must be a boolean")?;
+                    cfg.read_only = read_only;
+                }
+                "allow" => cfg.allow_paths.push(PathBuf::from(value)),
+                "deny" => cfg.deny_paths.push(PathBuf::from(value)),
                 _ => return Err("unrecognized option for virtio-fs config"),
             }
         }
@@ -726,9 +929,8 @@ impl ExpiringCasefoldLookupCaches {
 pub struct PassthroughFs {
     // Mutex that must be acquired before executing a process-wide operation such as fchdir.
     process_lock: Mutex<()>,
-    // virtio-fs tag that the guest uses when mounting. This is only used for debugging
-    // when tracing is enabled.
-    #[cfg_attr(not(feature = "trace_marker"), allow(dead_code))]
+    // virtio-fs tag that the guest uses when mounting. This is used for debugging when tracing
+    // is enabled and in the logs of requests rejected by `path_policy`.
     tag: String,
 
     // File descriptors for various points in the file system tree.
@@ -770,6 +972,12 @@ pub struct PassthroughFs {
     // if we use PassthroughFs in multi-threaded environments.
     expiring_casefold_lookup_caches: Option<Mutex<ExpiringCasefoldLookupCaches>>,
 
+    // Access rules built from `cfg.allow_paths` and `cfg.deny_paths`.
+    path_policy: PathPolicy,
+    // Host path of the root of the shared directory, which the paths matched against
+    // `path_policy` are made relative to. Set by `init`.
+    root_path: Mutex<PathBuf>,
+
     cfg: Config,
 }
 
@@ -829,6 +1037,8 @@ impl PassthroughFs {
             None
         };
 
+        let path_policy = PathPolicy::new(&cfg.allow_paths, &cfg.deny_paths);
+
         let passthroughfs = PassthroughFs {
             process_lock: Mutex::new(()),
             tag: tag.to_string(),
@@ -849,6 +1059,8 @@ impl PassthroughFs {
             #[cfg(feature = "arc_quota")]
             dbus_fd,
             expiring_casefold_lookup_caches,
+            path_policy,
+            root_path: Mutex::new(PathBuf::from("/")),
             cfg,
         };
 
@@ -860,6 +1072,202 @@ impl PassthroughFs {
         &self.cfg
     }
 
+    // Switches the thread credentials to the host ids corresponding to the caller in `ctx`.
+    fn set_guest_creds(&self, ctx: &Context) -> io::Result<(Option<ScopedUid>, Option<ScopedGid>)> {
+        set_creds(
+            self.cfg.uid_map.to_host(ctx.uid),
+            self.cfg.gid_map.to_host(ctx.gid),
+        )
+    }
+
+    // Translates the ownership of a host `stat` result into the ids seen by the guest.
+    fn stat_to_guest(&self, mut st: libc::stat64) -> libc::stat64 {
+        st.st_uid = self.cfg.uid_map.to_guest(st.st_uid);
+        st.st_gid = self.cfg.gid_map.to_guest(st.st_gid);
+        st
+    }
+
+    // Returns the host path of `fd`, as seen by this process.
+    fn fd_path(&self, fd: RawDescriptor) -> io::Result<PathBuf> {
+        let pathname = CString::new(format!("self/fd/{}", fd))
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        let mut buf = vec![0; libc::PATH_MAX as usize];
+
+        // Safe because this will only modify the contents of `buf` and we check the return value.
+        let res = syscall!(unsafe {
+            libc::readlinkat(
+                self.proc.as_raw_descriptor(),
+                pathname.as_ptr(),
+                buf.as_mut_ptr() as *mut libc::c_char,
+                buf.len(),
+            )
+        })?;
+
+        buf.truncate(res as usize);
+        Ok(PathBuf::from(OsString::from_vec(buf)))
+    }
+
+    // Returns the path of `inode` relative to the root of the shared directory.
+    fn inode_path(&self, inode: &InodeData) -> io::Result<PathBuf> {
+        let path = self.fd_path(inode.as_raw_descriptor())?;
+        share_relative_path(&self.root_path.lock(), &path)
+    }
+
+    // Returns the path of `name` in `parent`, or of `parent` itself if there is no `name`.
+    fn entry_path(&self, parent: &InodeData, name: Option<&CStr>) -> io::Result<PathBuf> {
+        let path = self.inode_path(parent)?;
+        Ok(match name {
+            Some(name) => path.join(OsStr::from_bytes(name.to_bytes())),
+            None => path,
+        })
+    }
+
+    // Logs a request rejected by the access rules and returns the error for the guest.
+    fn deny_access(&self, op: &str, path: &Path, errno: i32) -> io::Error {
+        let err = io::Error::from_raw_os_error(errno);
+        warn!("{}: denied {} of {}: {}", self.tag, op, path.display(), err);
+        err
+    }
+
+    // Checks that the guest may look up `name` in `parent`.
+    fn check_lookup(&self, parent: &InodeData, name: &CStr) -> io::Result<()> {
+        if self.path_policy.is_empty() {
+            return Ok(());
+        }
+
+        let path = self.entry_path(parent, Some(name))?;
+        if self.path_policy.can_traverse(&path) {
+            Ok(())
+        } else {
+            Err(self.deny_access("lookup", &path, libc::EACCES))
+        }
+    }
+
+    // Checks that the guest may perform `op`, which modifies `name` in `parent` or `parent`
+    // itself if there is no `name`.
+    fn check_modify(&self, op: &str, parent: &InodeData, name: Option<&CStr>) -> io::Result<()> {
+        if self.cfg.read_only {
+            // The path is only used for logging so don't fail the request if it can't be found.
+            let path = self.entry_path(parent, name).unwrap_or_default();
+            return Err(self.deny_access(op, &path, libc::EROFS));
+        }
+        if self.path_policy.is_empty() {
+            return Ok(());
+        }
+
+        let path = self.entry_path(parent, name)?;
+        if self.path_policy.can_access(&path) {
+            Ok(())
+        } else {
+            Err(self.deny_access(op, &path, libc::EACCES))
+        }
+    }
+
+    // Returns the host file on which the BSD style locks for `inode` and `handle` are placed.
+    fn lock_file(&self, inode: Inode, handle: Handle) -> io::Result<Arc<dyn AsRawDescriptor>> {
+        if self.zero_message_open.load(Ordering::Relaxed) {
+            Ok(self.find_inode(inode)?)
+        } else {
+            Ok(self.find_handle(handle, inode)?)
+        }
+    }
+
+    // Returns the host file on which the POSIX locks of `owner` for `inode` are placed, opening it
+    // through `handle` if the owner doesn't hold any lock on the inode yet.
+    //
+    // POSIX locks belong to a process rather than to an open file, so each lock owner of the guest
+    // gets its own open file description on the host: the locks of different owners conflict even
+    // when they go through the same handle, and the locks of one owner are shared by all of its
+    // handles. The file is closed, releasing the locks, when the owner flushes the inode.
+    fn posix_lock_file(&self, inode: Inode, handle: Handle, owner: u64) -> io::Result<Arc<File>> {
+        let data = self.find_inode(inode)?;
+        let mut posix_locks = data.posix_locks.lock();
+        if let Some(file) = posix_locks.get(&owner) {
+            return Ok(Arc::clone(file));
+        }
+
+        let file = self.lock_file(inode, handle)?;
+        // Prefer a file open for both reading and writing so that the owner can later take both
+        // kinds of locks, even through a handle other than the one it used first.
+        let file = match self.open_fd(file.as_raw_descriptor(), libc::O_RDWR) {
+            Ok(f) => f,
+            Err(_) => {
+                // Safe because this doesn't modify any memory and we check the return value.
+                let flags =
+                    syscall!(unsafe { libc::fcntl(file.as_raw_descriptor(), libc::F_GETFL) })?;
+                self.open_fd(file.as_raw_descriptor(), flags & libc::O_ACCMODE)?
+            }
+        };
+        let file = Arc::new(file);
+        posix_locks.insert(owner, Arc::clone(&file));
+        Ok(file)
+    }
+
+    // Guest locks are forwarded to open file description locks on the host so that they conflict
+    // with the locks of other owners and of host processes. BSD style locks are placed on the host
+    // file of the handle and are released when it is closed.
+    //
+    // With `interrupt`, waits for the conflicting locks to be released until `interrupt` is
+    // signaled.
+    fn do_setlk(
+        &self,
+        inode: Inode,
+        handle: Handle,
+        owner: u64,
+        lock: FileLock,
+        flock: bool,
+        interrupt: Option<&Event>,
+    ) -> io::Result<()> {
+        let mut retry_interval = Duration::from_millis(1);
+        loop {
+            match self.try_setlk(inode, handle, owner, &lock, flock) {
+                Err(e) if matches!(e.raw_os_error(), Some(libc::EAGAIN) | Some(libc::EACCES)) => {
+                    let interrupt = match interrupt {
+                        Some(interrupt) => interrupt,
+                        None => return Err(e),
+                    };
+                    if let EventWaitResult::Signaled = interrupt.wait_timeout(retry_interval)? {
+                        return Err(io::Error::from_raw_os_error(libc::EINTR));
+                    }
+                    retry_interval = cmp::min(retry_interval * 2, MAX_LOCK_RETRY_INTERVAL);
+                }
+                res => return res,
+            }
+        }
+    }
+
+    // Places `lock`, failing with `EAGAIN` or `EACCES` if it conflicts with another lock.
+    fn try_setlk(
+        &self,
+        inode: Inode,
+        handle: Handle,
+        owner: u64,
+        lock: &FileLock,
+        flock: bool,
+    ) -> io::Result<()> {
+        if flock {
+            let file = self.lock_file(inode, handle)?;
+            let operation = match lock.type_ as libc::c_int {
+                libc::F_RDLCK => libc::LOCK_SH,
+                libc::F_WRLCK => libc::LOCK_EX,
+                libc::F_UNLCK => libc::LOCK_UN,
+                _ => return Err(io::Error::from_raw_os_error(libc::EINVAL)),
+            };
+
+            // Safe because this doesn't modify any memory and we check the return value.
+            syscall!(unsafe { libc::flock(file.as_raw_descriptor(), operation | libc::LOCK_NB) })?;
+        } else {
+            let file = self.posix_lock_file(inode, handle, owner)?;
+            let fl = host_lock(lock)?;
+
+            // Safe because this doesn't modify any memory and we check the return value.
+            syscall!(unsafe { libc::fcntl(file.as_raw_descriptor(), libc::F_OFD_SETLK, &fl) })?;
+        }
+
+        Ok(())
+    }
+
     pub fn keep_rds(&self) -> Vec<RawDescriptor> {
         #[cfg_attr(not(feature = "arc_quota"), allow(unused_mut))]
         let mut keep_rds = vec![self.proc.as_raw_descriptor()];
@@ -979,6 +1387,7 @@ impl PassthroughFs {
                     file: Mutex::new((f, open_flags)),
                     refcount: AtomicU64::new(1),
                     filetype: st.st_mode.into(),
+                    posix_locks: Mutex::new(BTreeMap::new()),
                 }),
             );
 
@@ -988,7 +1397,7 @@ impl PassthroughFs {
         Entry {
             inode,
             generation: 0,
-            attr: st,
+            attr: self.stat_to_guest(st),
             attr_timeout: self.cfg.attr_timeout,
             entry_timeout: self.cfg.entry_timeout,
         }
@@ -1033,6 +1442,8 @@ impl PassthroughFs {
     }
 
     fn do_lookup(&self, parent: &InodeData, name: &CStr) -> io::Result<Entry> {
+        self.check_lookup(parent, name)?;
+
         let st = statat(parent, name)?;
 
         let altkey = InodeAltKey {
@@ -1046,7 +1457,7 @@ impl PassthroughFs {
             return Ok(Entry {
                 inode: self.increase_inode_refcount(data),
                 generation: 0,
-                attr: st,
+                attr: self.stat_to_guest(st),
                 attr_timeout: self.cfg.attr_timeout,
                 entry_timeout: self.cfg.entry_timeout,
             });
@@ -1150,7 +1561,7 @@ impl PassthroughFs {
     fn do_getattr(&self, inode: &InodeData) -> io::Result<(libc::stat64, Duration)> {
         let st = stat(inode)?;
 
-        Ok((st, self.cfg.attr_timeout))
+        Ok((self.stat_to_guest(st), self.cfg.attr_timeout))
     }
 
     fn do_unlink(&self, parent: &InodeData, name: &CStr, flags: libc::c_int) -> io::Result<()> {
@@ -1317,7 +1728,7 @@ impl PassthroughFs {
         let in_attr: fsxattr = zerocopy_from_reader(r)?;
 
         #[cfg(feature = "arc_quota")]
-        let st = stat(&*data)?;
+        let st = self.stat_to_guest(stat(&*data)?);
 
         // Changing quota project ID requires CAP_FOWNER or being file owner.
         // Here we use privileged_quota_uids because we cannot perform a CAP_FOWNER check.
@@ -1408,7 +1819,7 @@ impl PassthroughFs {
         let in_flags: c_int = zerocopy_from_reader(r)?;
 
         #[cfg(feature = "arc_quota")]
-        let st = stat(&*data)?;
+        let st = self.stat_to_guest(stat(&*data)?);
 
         // Only privleged uid can perform FS_IOC_SETFLAGS through cryptohome.
         #[cfg(feature = "arc_quota")]
@@ -1723,6 +2134,9 @@ impl FileSystem for PassthroughFs {
         let f = unsafe { File::from_raw_descriptor(raw_descriptor) };
 
         let st = stat(&f)?;
+        if !self.path_policy.is_empty() {
+            *self.root_path.lock() = self.fd_path(f.as_raw_descriptor())?;
+        }
 
         // Safe because this doesn't modify any memory and there is no need to check the return
         // value because this system call always succeeds. We need to clear the umask here because
@@ -1743,6 +2157,7 @@ impl FileSystem for PassthroughFs {
                 file: Mutex::new((f, flags)),
                 refcount: AtomicU64::new(2),
                 filetype: st.st_mode.into(),
+                posix_locks: Mutex::new(BTreeMap::new()),
             }),
         );
 
@@ -1767,6 +2182,12 @@ impl FileSystem for PassthroughFs {
                 self.zero_message_opendir.store(true, Ordering::Relaxed);
             }
         }
+        // BSD style locks are placed on the host file backing each handle, and the files holding
+        // POSIX locks are opened through it, so locks can't be supported when every open of an
+        // inode shares a single host file.
+        if !self.zero_message_open.load(Ordering::Relaxed) {
+            opts |= capable & (FsOptions::POSIX_LOCKS | FsOptions::FLOCK_LOCKS);
+        }
         Ok(opts)
     }
 
@@ -1876,8 +2297,9 @@ impl FileSystem for PassthroughFs {
             name
         );
         let data = self.find_inode(parent)?;
+        self.check_modify("mkdir", &data, Some(name))?;
 
-        let (_uid, _gid) = set_creds(ctx.uid, ctx.gid)?;
+        let (_uid, _gid) = self.set_guest_creds(&ctx)?;
         {
             let casefold_cache = self.lock_casefold_lookup_caches();
             let _scoped_umask = ScopedUmask::new(umask);
@@ -1894,6 +2316,7 @@ impl FileSystem for PassthroughFs {
     fn rmdir(&self, _ctx: Context, parent: Inode, name: &CStr) -> io::Result<()> {
         cros_tracing::trace_simple_print!("{}: rmdir: inode={parent}, name={:?}", self.tag, name);
         let data = self.find_inode(parent)?;
+        self.check_modify("rmdir", &data, Some(name))?;
         let casefold_cache = self.lock_casefold_lookup_caches();
         // TODO(b/278691962): If ascii_casefold is enabled, we need to call
         // `lookup_case_unfolded_name()` to get the actual name to be unlinked.
@@ -1916,17 +2339,43 @@ impl FileSystem for PassthroughFs {
             "{}: readdir: inode={inode}, handle={handle}, size={size}, offset={offset}",
             self.tag
         );
-        let buf = vec![0; size as usize].into_boxed_slice();
+        let read_dir = |offset: libc::off64_t| {
+            let buf = vec![0; size as usize].into_boxed_slice();
 
-        if self.zero_message_opendir.load(Ordering::Relaxed) {
-            let data = self.find_inode(inode)?;
-            ReadDir::new(&*data, offset as libc::off64_t, buf)
-        } else {
-            let data = self.find_handle(handle, inode)?;
+            if self.zero_message_opendir.load(Ordering::Relaxed) {
+                let data = self.find_inode(inode)?;
+                ReadDir::new(&*data, offset, buf)
+            } else {
+                let data = self.find_handle(handle, inode)?;
+
+                let dir = data.file.lock();
+
+                ReadDir::new(&*dir, offset, buf)
+            }
+        };
 
-            let dir = data.file.lock();
+        let mut offset = offset as libc::off64_t;
+        if self.path_policy.is_empty() {
+            return read_dir(offset);
+        }
 
-            ReadDir::new(&*dir, offset as libc::off64_t, buf)
+        // Hide the entries that the guest isn't allowed to look up. If that removes every entry
+        // that was read, keep reading so that an empty reply isn't mistaken for the end of the
+        // directory.
+        let dir_path = self.inode_path(&*self.find_inode(inode)?)?;
+        loop {
+            let mut entries = read_dir(offset)?;
+            let next_offset = entries.retain(|name| {
+                let name = name.to_bytes();
+                name == b"." || name == b".." || {
+                    self.path_policy
+                        .can_traverse(&dir_path.join(OsStr::from_bytes(name)))
+                }
+            });
+            match next_offset {
+                Some(next_offset) if entries.remaining() == 0 => offset = next_offset,
+                _ => return Ok(entries),
+            }
         }
     }
 
@@ -1944,6 +2393,10 @@ impl FileSystem for PassthroughFs {
             Err(io::Error::from_raw_os_error(libc::ENOSYS))
         } else {
             cros_tracing::trace_simple_print!("{}: open: inode={inode}, flags={flags}", self.tag);
+            let flags_i32 = flags as i32;
+            if flags_i32 & libc::O_ACCMODE != libc::O_RDONLY || flags_i32 & libc::O_TRUNC != 0 {
+                self.check_modify("open", &*self.find_inode(inode)?, None)?;
+            }
             self.do_open(inode, flags)
         }
     }
@@ -1985,8 +2438,9 @@ impl FileSystem for PassthroughFs {
             self.tag
         );
         let data = self.find_inode(parent)?;
+        self.check_modify("tmpfile", &data, None)?;
 
-        let (_uid, _gid) = set_creds(ctx.uid, ctx.gid)?;
+        let (_uid, _gid) = self.set_guest_creds(&ctx)?;
 
         let tmpflags = libc::O_RDWR | libc::O_TMPFILE | libc::O_CLOEXEC | libc::O_NOFOLLOW;
 
@@ -2030,7 +2484,8 @@ impl FileSystem for PassthroughFs {
             name
         );
         let data = self.find_inode(parent)?;
-        let (_uid, _gid) = set_creds(ctx.uid, ctx.gid)?;
+        self.check_modify("create", &data, Some(name))?;
+        let (_uid, _gid) = self.set_guest_creds(&ctx)?;
 
         let create_flags =
             (flags as i32 | libc::O_CREAT | libc::O_CLOEXEC | libc::O_NOFOLLOW) & !libc::O_DIRECT;
@@ -2078,6 +2533,7 @@ impl FileSystem for PassthroughFs {
     fn unlink(&self, _ctx: Context, parent: Inode, name: &CStr) -> io::Result<()> {
         cros_tracing::trace_simple_print!("{}: unlink: inode={parent}, name={:?}", self.tag, name);
         let data = self.find_inode(parent)?;
+        self.check_modify("unlink", &data, Some(name))?;
         let casefold_cache = self.lock_casefold_lookup_caches();
         // TODO(b/278691962): If ascii_casefold is enabled, we need to call
         // `lookup_case_unfolded_name()` to get the actual name to be unlinked.
@@ -2158,6 +2614,8 @@ impl FileSystem for PassthroughFs {
             );
 
             let data = self.find_inode(inode)?;
+            // Without an explicit open, nothing checked that the file may be written yet.
+            self.check_modify("write", &data, None)?;
 
             let mut file = data.file.lock();
             let mut flags = file.1;
@@ -2214,6 +2672,7 @@ impl FileSystem for PassthroughFs {
             handle
         );
         let inode_data = self.find_inode(inode)?;
+        self.check_modify("setattr", &inode_data, None)?;
 
         enum Data {
             Handle(Arc<HandleData>, RawDescriptor),
@@ -2246,13 +2705,13 @@ impl FileSystem for PassthroughFs {
 
         if valid.intersects(SetattrValid::UID | SetattrValid::GID) {
             let uid = if valid.contains(SetattrValid::UID) {
-                attr.st_uid
+                self.cfg.uid_map.to_host(attr.st_uid)
             } else {
                 // Cannot use -1 here because these are unsigned values.
                 ::std::u32::MAX
             };
             let gid = if valid.contains(SetattrValid::GID) {
-                attr.st_gid
+                self.cfg.gid_map.to_host(attr.st_gid)
             } else {
                 // Cannot use -1 here because these are unsigned values.
                 ::std::u32::MAX
@@ -2343,6 +2802,8 @@ impl FileSystem for PassthroughFs {
 
         let old_inode = self.find_inode(olddir)?;
         let new_inode = self.find_inode(newdir)?;
+        self.check_modify("rename", &old_inode, Some(oldname))?;
+        self.check_modify("rename", &new_inode, Some(newname))?;
         {
             let casefold_cache = self.lock_casefold_lookup_caches();
 
@@ -2383,8 +2844,9 @@ impl FileSystem for PassthroughFs {
             name
         );
         let data = self.find_inode(parent)?;
+        self.check_modify("mknod", &data, Some(name))?;
 
-        let (_uid, _gid) = set_creds(ctx.uid, ctx.gid)?;
+        let (_uid, _gid) = self.set_guest_creds(&ctx)?;
         {
             let _scoped_umask = ScopedUmask::new(umask);
             let casefold_cache = self.lock_casefold_lookup_caches();
@@ -2420,6 +2882,9 @@ impl FileSystem for PassthroughFs {
         );
         let data = self.find_inode(inode)?;
         let new_inode = self.find_inode(newparent)?;
+        self.check_modify("link", &new_inode, Some(newname))?;
+        // A file that the guest can't access must not be made accessible through a new name.
+        self.check_modify("link", &data, None)?;
 
         let path = CString::new(format!("self/fd/{}", data.as_raw_descriptor()))
             .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
@@ -2458,8 +2923,9 @@ impl FileSystem for PassthroughFs {
             name
         );
         let data = self.find_inode(parent)?;
+        self.check_modify("symlink", &data, Some(name))?;
 
-        let (_uid, _gid) = set_creds(ctx.uid, ctx.gid)?;
+        let (_uid, _gid) = self.set_guest_creds(&ctx)?;
         {
             let casefold_cache = self.lock_casefold_lookup_caches();
             // Safe because this doesn't modify any memory and we check the return value.
@@ -2502,9 +2968,14 @@ impl FileSystem for PassthroughFs {
         _ctx: Context,
         inode: Inode,
         handle: Handle,
-        _lock_owner: u64,
+        lock_owner: u64,
     ) -> io::Result<()> {
         cros_tracing::trace_simple_print!("{}: flush: inode={inode}, handle={handle}", self.tag);
+        // Closing any file of the inode releases all the POSIX locks of the process on it.
+        if let Ok(data) = self.find_inode(inode) {
+            data.posix_locks.lock().remove(&lock_owner);
+        }
+
         let data: Arc<dyn AsRawDescriptor> = if self.zero_message_open.load(Ordering::Relaxed) {
             self.find_inode(inode)?
         } else {
@@ -2572,11 +3043,75 @@ impl FileSystem for PassthroughFs {
         }
     }
 
+    fn getlk(
+        &self,
+        _ctx: Context,
+        inode: Inode,
+        handle: Handle,
+        owner: u64,
+        lock: FileLock,
+        flock: bool,
+    ) -> io::Result<FileLock> {
+        cros_tracing::trace_simple_print!(
+            "{}: getlk: inode={inode}, handle={handle}, lock={:?}, flock={flock}",
+            self.tag,
+            lock
+        );
+        // The client tests BSD style locks itself.
+        if flock {
+            return Err(io::Error::from_raw_os_error(libc::EINVAL));
+        }
+
+        let file = self.posix_lock_file(inode, handle, owner)?;
+        let mut fl = host_lock(&lock)?;
+
+        // Safe because the kernel will only write data in `fl` and we check the return value.
+        syscall!(unsafe { libc::fcntl(file.as_raw_descriptor(), libc::F_OFD_GETLK, &mut fl) })?;
+
+        Ok(fuse_lock(&fl))
+    }
+
+    fn setlk(
+        &self,
+        _ctx: Context,
+        inode: Inode,
+        handle: Handle,
+        owner: u64,
+        lock: FileLock,
+        flock: bool,
+    ) -> io::Result<()> {
+        cros_tracing::trace_simple_print!(
+            "{}: setlk: inode={inode}, handle={handle}, lock={:?}, flock={flock}",
+            self.tag,
+            lock
+        );
+        self.do_setlk(inode, handle, owner, lock, flock, None)
+    }
+
+    fn setlkw(
+        &self,
+        _ctx: Context,
+        inode: Inode,
+        handle: Handle,
+        owner: u64,
+        lock: FileLock,
+        flock: bool,
+        interrupt: &Event,
+    ) -> io::Result<()> {
+        cros_tracing::trace_simple_print!(
+            "{}: setlkw: inode={inode}, handle={handle}, lock={:?}, flock={flock}",
+            self.tag,
+            lock
+        );
+        self.do_setlk(inode, handle, owner, lock, flock, Some(interrupt))
+    }
+
     fn access(&self, ctx: Context, inode: Inode, mask: u32) -> io::Result<()> {
         cros_tracing::trace_simple_print!("{}: access: inode={inode}, mask={mask}", self.tag);
         let data = self.find_inode(inode)?;
 
-        let st = stat(&*data)?;
+        // Permissions are checked against the ids seen by the guest.
+        let st = self.stat_to_guest(stat(&*data)?);
         let mode = mask as i32 & (libc::R_OK | libc::W_OK | libc::X_OK);
 
         if mode == libc::F_OK {
@@ -2639,6 +3174,7 @@ impl FileSystem for PassthroughFs {
         }
 
         let data = self.find_inode(inode)?;
+        self.check_modify("setxattr", &data, None)?;
         let name = self.rewrite_xattr_name(name);
         let file = data.file.lock();
         let o_path_file = (file.1 & libc::O_PATH) != 0;
@@ -2770,6 +3306,7 @@ impl FileSystem for PassthroughFs {
         }
 
         let data = self.find_inode(inode)?;
+        self.check_modify("removexattr", &data, None)?;
         let name = self.rewrite_xattr_name(name);
 
         let file = data.file.lock();
@@ -2810,6 +3347,7 @@ impl FileSystem for PassthroughFs {
 
         let data: Arc<dyn AsRawDescriptor> = if self.zero_message_open.load(Ordering::Relaxed) {
             let data = self.find_inode(inode)?;
+            self.check_modify("fallocate", &data, None)?;
 
             {
                 // fallocate needs a writable fd
@@ -2875,6 +3413,10 @@ impl FileSystem for PassthroughFs {
         const ENABLE_VERITY: u32 = FS_IOC_ENABLE_VERITY() as u32;
         const MEASURE_VERITY: u32 = FS_IOC_MEASURE_VERITY() as u32;
 
+        if matches!(cmd, SET_FSXATTR | SET_FLAGS32 | SET_FLAGS64 | ENABLE_VERITY) {
+            self.check_modify("ioctl", &*self.find_inode(inode)?, None)?;
+        }
+
         match cmd {
             GET_ENCRYPTION_POLICY_EX => self.get_encryption_policy_ex(inode, handle, r),
             GET_FSXATTR => {
@@ -2943,10 +3485,12 @@ impl FileSystem for PassthroughFs {
         );
         // We need to change credentials during a write so that the kernel will remove setuid or
         // setgid bits from the file if it was written to by someone other than the owner.
-        let (_uid, _gid) = set_creds(ctx.uid, ctx.gid)?;
+        let (_uid, _gid) = self.set_guest_creds(&ctx)?;
         let (src_data, dst_data): (Arc<dyn AsRawDescriptor>, Arc<dyn AsRawDescriptor>) =
             if self.zero_message_open.load(Ordering::Relaxed) {
-                (self.find_inode(inode_src)?, self.find_inode(inode_dst)?)
+                let dst = self.find_inode(inode_dst)?;
+                self.check_modify("copy_file_range", &dst, None)?;
+                (self.find_inode(inode_src)?, dst)
             } else {
                 (
                     self.find_handle(handle_src, inode_src)?,
@@ -2999,6 +3543,9 @@ impl FileSystem for PassthroughFs {
         };
 
         let data = self.find_inode(inode)?;
+        if write {
+            self.check_modify("mmap", &data, None)?;
+        }
 
         if self.zero_message_open.load(Ordering::Relaxed) {
             let mut file = data.file.lock();
@@ -3046,7 +3593,8 @@ impl FileSystem for PassthroughFs {
 mod tests {
     use super::*;
 
-    use std::path::Path;
+    use std::os::unix::fs::MetadataExt;
+    use std::thread;
 
     use named_lock::NamedLock;
     use tempfile::TempDir;
@@ -3230,6 +3778,293 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parse_id_map() {
+        let map: IdMap = "0 1000 1,1000 2000 10".parse().unwrap();
+        assert_eq!(map.to_host(0), 1000);
+        assert_eq!(map.to_host(1005), 2005);
+        assert_eq!(map.to_host(1010), 1010);
+        assert_eq!(map.to_guest(1000), 0);
+        assert_eq!(map.to_guest(2009), 1009);
+        assert_eq!(map.to_guest(5), 5);
+
+        assert!("0 1000".parse::<IdMap>().is_err());
+        assert!("0 1000 1 2".parse::<IdMap>().is_err());
+        assert!("0 1000 0".parse::<IdMap>().is_err());
+        assert!("a 1000 1".parse::<IdMap>().is_err());
+        assert!("4294967295 0 2".parse::<IdMap>().is_err());
+        // Overlapping guest ranges.
+        assert!("0 1000 10,5 2000 1".parse::<IdMap>().is_err());
+        // Overlapping host ranges.
+        assert!("0 1000 10,100 1009 1".parse::<IdMap>().is_err());
+    }
+
+    #[test]
+    fn lookup_and_create_with_id_map() {
+        // Since PassthroughFs may executes process-wide operations such as `fchdir`, acquire
+        // `NamedLock` before starting each unit test creating a `PassthroughFs` instance.
+        let lock = NamedLock::create(UNITTEST_LOCK_NAME).expect("create named lock");
+        let _guard = lock.lock().expect("acquire named lock");
+
+        const GUEST_UID: libc::uid_t = 4242;
+        const GUEST_GID: libc::gid_t = 4343;
+
+        let temp_dir = TempDir::new().unwrap();
+        create_test_data(&temp_dir, &[], &["a.txt"]);
+
+        let host_ctx = get_context();
+        let cfg = Config {
+            uid_map: format!("{} {} 1", GUEST_UID, host_ctx.uid).parse().unwrap(),
+            gid_map: format!("{} {} 1", GUEST_GID, host_ctx.gid).parse().unwrap(),
+            ..Default::default()
+        };
+        let fs = PassthroughFs::new("tag", cfg).unwrap();
+
+        let capable = FsOptions::empty();
+        fs.init(capable).unwrap();
+
+        // Files owned by the host user appear to be owned by the mapped guest ids.
+        let inode = lookup(&fs, &temp_dir.path().join("a.txt")).expect("a.txt must be found");
+        let (st, _) = fs.getattr(host_ctx, inode, None).unwrap();
+        assert_eq!(st.st_uid, GUEST_UID);
+        assert_eq!(st.st_gid, GUEST_GID);
+
+        // Files created by the guest ids are owned by the host user.
+        let guest_ctx = Context {
+            uid: GUEST_UID,
+            gid: GUEST_GID,
+            pid: host_ctx.pid,
+        };
+        let parent = lookup(&fs, temp_dir.path()).expect("lookup temp_dir");
+        let name = CString::new("b.txt").unwrap();
+        let (entry, _, _) = fs
+            .create(guest_ctx, parent, &name, 0o666, libc::O_RDWR as u32, 0)
+            .expect("create b.txt");
+        assert_eq!(entry.attr.st_uid, GUEST_UID);
+        assert_eq!(entry.attr.st_gid, GUEST_GID);
+
+        let host_st = std::fs::metadata(temp_dir.path().join("b.txt")).unwrap();
+        assert_eq!(host_st.uid(), host_ctx.uid);
+        assert_eq!(host_st.gid(), host_ctx.gid);
+    }
+
+    #[test]
+    fn create_read_only() {
+        // Since PassthroughFs may executes process-wide operations such as `fchdir`, acquire
+        // `NamedLock` before starting each unit test creating a `PassthroughFs` instance.
+        let lock = NamedLock::create(UNITTEST_LOCK_NAME).expect("create named lock");
+        let _guard = lock.lock().expect("acquire named lock");
+
+        let temp_dir = TempDir::new().unwrap();
+        create_test_data(&temp_dir, &[], &["a.txt"]);
+
+        let cfg = Config {
+            read_only: true,
+            ..Default::default()
+        };
+        let fs = PassthroughFs::new("tag", cfg).unwrap();
+
+        let capable = FsOptions::empty();
+        fs.init(capable).unwrap();
+
+        assert!(lookup(&fs, &temp_dir.path().join("a.txt")).is_ok());
+        assert_eq!(
+            create(&fs, &temp_dir.path().join("b.txt"))
+                .expect_err("file must not be created")
+                .raw_os_error(),
+            Some(libc::EROFS)
+        );
+        assert_eq!(
+            unlink(&fs, &temp_dir.path().join("a.txt"))
+                .expect_err("file must not be removed")
+                .raw_os_error(),
+            Some(libc::EROFS)
+        );
+        assert!(temp_dir.path().join("a.txt").exists());
+    }
+
+    #[test]
+    fn posix_and_flock_locks() {
+        // Since PassthroughFs may executes process-wide operations such as `fchdir`, acquire
+        // `NamedLock` before starting each unit test creating a `PassthroughFs` instance.
+        let lock = NamedLock::create(UNITTEST_LOCK_NAME).expect("create named lock");
+        let _guard = lock.lock().expect("acquire named lock");
+
+        let temp_dir = TempDir::new().unwrap();
+        create_test_data(&temp_dir, &[], &["a.txt"]);
+
+        let cfg = Default::default();
+        let fs = PassthroughFs::new("tag", cfg).unwrap();
+
+        let capable = FsOptions::POSIX_LOCKS | FsOptions::FLOCK_LOCKS;
+        assert!(fs.init(capable).unwrap().contains(capable));
+
+        let ctx = get_context();
+        let inode = lookup(&fs, &temp_dir.path().join("a.txt")).unwrap();
+        let open = || {
+            fs.open(ctx, inode, libc::O_RDWR as u32)
+                .unwrap()
+                .0
+                .expect("missing handle")
+        };
+        let (h1, h2, h3) = (open(), open(), open());
+
+        let write_lock = FileLock {
+            start: 0,
+            end: LOCK_OFFSET_MAX,
+            type_: libc::F_WRLCK as u32,
+            pid: 0,
+        };
+        fs.setlk(ctx, inode, h1, 1, write_lock, false).unwrap();
+        assert_eq!(
+            fs.getlk(ctx, inode, h1, 1, write_lock, false)
+                .unwrap()
+                .type_,
+            libc::F_UNLCK as u32
+        );
+        // The locks of an owner are shared by all of its handles.
+        assert_eq!(
+            fs.getlk(ctx, inode, h2, 1, write_lock, false)
+                .unwrap()
+                .type_,
+            libc::F_UNLCK as u32
+        );
+        // The locks of different owners conflict, even through the same handle.
+        let conflict = fs.getlk(ctx, inode, h1, 2, write_lock, false).unwrap();
+        assert_eq!(conflict.type_, libc::F_WRLCK as u32);
+        assert_eq!(conflict.end, LOCK_OFFSET_MAX);
+        assert_eq!(
+            fs.setlk(ctx, inode, h1, 2, write_lock, false)
+                .expect_err("owners must conflict")
+                .raw_os_error(),
+            Some(libc::EAGAIN)
+        );
+
+        // Closing a file of the inode releases the locks of the owner, even if it was open
+        // through another handle.
+        fs.flush(ctx, inode, h2, 1).unwrap();
+        fs.setlk(ctx, inode, h1, 2, write_lock, false).unwrap();
+
+        fs.setlk(ctx, inode, h2, 2, write_lock, true).unwrap();
+        assert_eq!(
+            fs.setlk(ctx, inode, h3, 3, write_lock, true)
+                .expect_err("flock must conflict")
+                .raw_os_error(),
+            Some(libc::EWOULDBLOCK)
+        );
+
+        // Waiting for a lock ends once the conflicting lock is released.
+        let unlock = FileLock {
+            type_: libc::F_UNLCK as u32,
+            ..write_lock
+        };
+        let interrupt = Event::new().unwrap();
+        thread::scope(|s| {
+            let waiter = s.spawn(|| fs.setlkw(ctx, inode, h3, 3, write_lock, false, &interrupt));
+            thread::sleep(Duration::from_millis(10));
+            fs.setlk(ctx, inode, h1, 2, unlock, false).unwrap();
+            waiter.join().unwrap().unwrap();
+        });
+        // Or once it is interrupted.
+        thread::scope(|s| {
+            let waiter = s.spawn(|| fs.setlkw(ctx, inode, h1, 2, write_lock, false, &interrupt));
+            thread::sleep(Duration::from_millis(10));
+            interrupt.signal().unwrap();
+            assert_eq!(
+                waiter
+                    .join()
+                    .unwrap()
+                    .expect_err("wait must be interrupted")
+                    .raw_os_error(),
+                Some(libc::EINTR)
+            );
+        });
+    }
+
+    #[test]
+    fn lookup_with_path_policy() {
+        // Since PassthroughFs may executes process-wide operations such as `fchdir`, acquire
+        // `NamedLock` before starting each unit test creating a `PassthroughFs` instance.
+        let lock = NamedLock::create(UNITTEST_LOCK_NAME).expect("create named lock");
+        let _guard = lock.lock().expect("acquire named lock");
+
+        let temp_dir = TempDir::new().unwrap();
+        create_test_data(
+            &temp_dir,
+            &["public", "public/secret", "private"],
+            &["a.txt", "public/b.txt", "public/secret/c.txt"],
+        );
+        // The paths seen by the file system are resolved, so the rules must be too. They are
+        // relative to the root of the file system, which is `/` in tests.
+        let root = temp_dir.path().canonicalize().unwrap();
+        let rules = root.strip_prefix("/").unwrap();
+
+        let cfg = Config {
+            allow_paths: vec![rules.join("public")],
+            deny_paths: vec![rules.join("public/secret")],
+            ..Default::default()
+        };
+        let fs = PassthroughFs::new("tag", cfg).unwrap();
+
+        let capable = FsOptions::empty();
+        fs.init(capable).unwrap();
+
+        assert!(lookup(&fs, &root.join("public/b.txt")).is_ok());
+        for denied in ["a.txt", "private", "public/secret"] {
+            assert_eq!(
+                lookup(&fs, &root.join(denied))
+                    .expect_err("path must be hidden")
+                    .raw_os_error(),
+                Some(libc::EACCES)
+            );
+        }
+
+        // Ancestors of allowed paths can be traversed but not modified.
+        assert_eq!(
+            create(&fs, &root.join("d.txt"))
+                .expect_err("file must not be created")
+                .raw_os_error(),
+            Some(libc::EACCES)
+        );
+        assert!(create(&fs, &root.join("public/d.txt")).is_ok());
+
+        // Files that are denied, here because they were moved on the host after the guest looked
+        // them up, can't be linked into allowed directories.
+        let ctx = get_context();
+        let public = lookup(&fs, &root.join("public")).unwrap();
+        let b = lookup(&fs, &root.join("public/b.txt")).unwrap();
+        let name = CString::new("e.txt").unwrap();
+        fs.link(ctx, b, public, &name).unwrap();
+        std::fs::rename(root.join("public/b.txt"), root.join("private/b.txt")).unwrap();
+        let name = CString::new("f.txt").unwrap();
+        assert_eq!(
+            fs.link(ctx, b, public, &name)
+                .expect_err("denied file must not be linked")
+                .raw_os_error(),
+            Some(libc::EACCES)
+        );
+    }
+
+    #[test]
+    fn share_relative_paths() {
+        let root = Path::new("/srv/share");
+        assert_eq!(
+            share_relative_path(root, Path::new("/srv/share/a/b")).unwrap(),
+            Path::new("a/b")
+        );
+        assert_eq!(share_relative_path(root, root).unwrap(), Path::new(""));
+        assert_eq!(
+            share_relative_path(Path::new("/"), Path::new("/a")).unwrap(),
+            Path::new("a")
+        );
+        assert_eq!(
+            share_relative_path(root, Path::new("/srv/shared/a"))
+                .unwrap_err()
+                .raw_os_error(),
+            Some(libc::EACCES)
+        );
+    }
+
     #[test]
     fn lookup_files_ascii_casefold() {
         // Since PassthroughFs may executes process-wide operations such as `fchdir`, acquire
@@ -0,0 +1,102 @@
+// Copyright 2023 The ChromiumOS Authors
+// Use of this source code is governed by a BSD-style license that can be
+// found in the LICENSE file.
+
+use std::path::Component;
+use std::path::Path;
+use std::path::PathBuf;
+
+/// Path-based access rules for a shared directory.
+///
+/// All paths are relative to the root of the shared directory. A path is accessible if it is not
+/// inside any of the denied paths and, when there are allowed paths, if it is inside one of them.
+/// Ancestors of allowed paths can be traversed so that the guest is able to reach the allowed
+/// paths, but are otherwise inaccessible.
+#[derive(Debug, Default)]
+pub struct PathPolicy {
+    allow: Vec<PathBuf>,
+    deny: Vec<PathBuf>,
+}
+
+// Makes `path` relative to the root and resolves `.` and `..` components lexically so that
+// `/a/./b`, `a/c/../b` and `a/b` compare equal.
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::Normal(c) => normalized.push(c),
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            Component::RootDir | Component::CurDir | Component::Prefix(_) => {}
+        }
+    }
+    normalized
+}
+
+impl PathPolicy {
+    pub fn new(allow: &[PathBuf], deny: &[PathBuf]) -> Self {
+        PathPolicy {
+            allow: allow.iter().map(|p| normalize(p)).collect(),
+            deny: deny.iter().map(|p| normalize(p)).collect(),
+        }
+    }
+
+    /// Whether there are no rules, i.e. every path is accessible.
+    pub fn is_empty(&self) -> bool {
+        self.allow.is_empty() && self.deny.is_empty()
+    }
+
+    /// Whether the guest may access and modify `path`.
+    pub fn can_access(&self, path: &Path) -> bool {
+        let path = normalize(path);
+        if self.deny.iter().any(|d| path.starts_with(d)) {
+            return false;
+        }
+        self.allow.is_empty() || self.allow.iter().any(|a| path.starts_with(a))
+    }
+
+    /// Whether the guest may look up `path`. This is the case if it is accessible or if it is
+    /// an ancestor of an allowed path.
+    pub fn can_traverse(&self, path: &Path) -> bool {
+        if self.can_access(path) {
+            return true;
+        }
+        let path = normalize(path);
+        !self.deny.iter().any(|d| path.starts_with(d))
+            && self.allow.iter().any(|a| a.starts_with(&path))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn access_rules() {
+        let policy = PathPolicy::new(
+            &[PathBuf::from("/a/b"), PathBuf::from("c")],
+            &[PathBuf::from("a/b/secret")],
+        );
+
+        assert!(policy.can_access(Path::new("a/b")));
+        assert!(policy.can_access(Path::new("/a/b/file")));
+        assert!(policy.can_access(Path::new("c/d/../e")));
+        assert!(!policy.can_access(Path::new("a")));
+        assert!(!policy.can_access(Path::new("a/bc")));
+        assert!(!policy.can_access(Path::new("a/b/secret/file")));
+        assert!(!policy.can_access(Path::new("a/b/../../d")));
+
+        assert!(policy.can_traverse(Path::new("/")));
+        assert!(policy.can_traverse(Path::new("a")));
+        assert!(!policy.can_traverse(Path::new("d")));
+        assert!(!policy.can_traverse(Path::new("a/b/secret")));
+    }
+
+    #[test]
+    fn empty_policy() {
+        let policy = PathPolicy::default();
+        assert!(policy.is_empty());
+        assert!(policy.can_access(Path::new("any/path")));
+    }
+}
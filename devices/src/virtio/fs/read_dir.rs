@@ -57,6 +57,31 @@ impl<P: DerefMut<Target = [u8]>> ReadDir<P> {
             end: res as usize,
         })
     }
+
+    /// Removes the entries whose name doesn't satisfy `f` from the internal buffer.
+    ///
+    /// Returns the offset of the entry following the last examined entry, which can be used to
+    /// read more entries if all of them were removed.
+    pub fn retain<F: FnMut(&CStr) -> bool>(&mut self, mut f: F) -> Option<libc::off64_t> {
+        let mut next_offset = None;
+        let mut read = self.current;
+        let mut write = self.current;
+        while read < self.end {
+            let (front, back) = self.buf[read..self.end].split_at(size_of::<LinuxDirent64>());
+            let dirent64 =
+                LinuxDirent64::read_from(front).expect("unable to get LinuxDirent64 from slice");
+            let reclen = dirent64.d_reclen as usize;
+            let keep = f(strip_padding(&back[..reclen - size_of::<LinuxDirent64>()]));
+            if keep {
+                self.buf.copy_within(read..read + reclen, write);
+                write += reclen;
+            }
+            next_offset = Some(dirent64.d_off);
+            read += reclen;
+        }
+        self.end = write;
+        next_offset
+    }
 }
 
 impl<P> ReadDir<P> {
@@ -8,6 +8,8 @@ use std::fs::File;
 use std::io;
 use std::os::unix::io::AsRawFd;
 use std::sync::Arc;
+use std::thread;
+use std::thread::JoinHandle;
 
 use base::error;
 use base::syscall;
@@ -140,28 +142,128 @@ impl fuse::Mapper for Mapper {
     }
 }
 
+// Maximum number of requests that may block, like waiting for a file lock, handled at the same
+// time. Each of them takes a thread, so further ones fail with `EAGAIN` to keep the guest from
+// exhausting the threads of the host.
+const MAX_BLOCKING_REQUESTS: usize = 64;
+
+/// Threads handling the requests of a queue that may block, see `process_fs_queue`.
+///
+/// Dropping it interrupts the requests and waits for the threads to complete them, so that the
+/// threads don't use the queue once it is reset or handed back.
+#[derive(Default)]
+pub struct BlockingRequests {
+    // Each thread with the event interrupting its request.
+    threads: Vec<(JoinHandle<()>, Event)>,
+}
+
+impl BlockingRequests {
+    // Returns the number of requests still being handled.
+    fn count(&mut self) -> usize {
+        self.threads.retain(|(thread, _)| !thread.is_finished());
+        self.threads.len()
+    }
+
+    /// Interrupts the requests and waits for their threads to exit.
+    pub fn stop(&mut self) {
+        for (_, interrupt) in &self.threads {
+            if let Err(e) = interrupt.signal() {
+                error!("failed to interrupt virtio-fs blocking request: {}", e);
+            }
+        }
+        for (thread, _) in self.threads.drain(..) {
+            if thread.join().is_err() {
+                error!("virtio-fs blocking request thread panicked");
+            }
+        }
+    }
+}
+
+impl Drop for BlockingRequests {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
 pub struct Worker<F: FileSystem + Sync> {
     mem: GuestMemory,
-    queue: Queue,
+    queue: Arc<Mutex<Queue>>,
+    blocking_requests: BlockingRequests,
     server: Arc<fuse::Server<F>>,
     irq: Interrupt,
     tube: Arc<Mutex<Tube>>,
     slot: u32,
 }
 
-pub fn process_fs_queue<I: SignalableInterrupt, F: FileSystem + Sync>(
+pub fn process_fs_queue<
+    I: SignalableInterrupt + Send + 'static,
+    F: FileSystem + Send + Sync + 'static,
+>(
     mem: &GuestMemory,
     interrupt: &I,
-    queue: &mut Queue,
+    queue: &Arc<Mutex<Queue>>,
+    blocking_requests: &mut BlockingRequests,
     server: &Arc<fuse::Server<F>>,
     tube: &Arc<Mutex<Tube>>,
     slot: u32,
 ) -> Result<()> {
     let mapper = Mapper::new(Arc::clone(tube), slot);
-    while let Some(mut avail_desc) = queue.pop(mem) {
+    loop {
+        let mut avail_desc = match queue.lock().pop(mem) {
+            Some(avail_desc) => avail_desc,
+            None => break,
+        };
+
+        // Requests like waiting for a file lock can take arbitrarily long, so handle them on a
+        // separate thread instead of holding up every other request in the queue.
+        if server.may_block(avail_desc.reader.clone()) {
+            if blocking_requests.count() >= MAX_BLOCKING_REQUESTS {
+                let total = server.reject_message(
+                    &mut avail_desc.reader,
+                    &mut avail_desc.writer,
+                    io::Error::from_raw_os_error(libc::EAGAIN),
+                )?;
+
+                let mut queue = queue.lock();
+                queue.add_used(mem, avail_desc, total as u32);
+                queue.trigger_interrupt(mem, interrupt);
+                continue;
+            }
+
+            let request_interrupt = Event::new().map_err(Error::CreateEvent)?;
+            let thread_request_interrupt =
+                request_interrupt.try_clone().map_err(Error::CreateEvent)?;
+            let mem = mem.clone();
+            let interrupt = interrupt.clone();
+            let queue = Arc::clone(queue);
+            let server = Arc::clone(server);
+            let mapper = Mapper::new(Arc::clone(tube), slot);
+            let thread = thread::Builder::new()
+                .name("v_fs_blocking".to_string())
+                .spawn(move || {
+                    match server.handle_blocking_message(
+                        &mut avail_desc.reader,
+                        &mut avail_desc.writer,
+                        &mapper,
+                        &thread_request_interrupt,
+                    ) {
+                        Ok(total) => {
+                            let mut queue = queue.lock();
+                            queue.add_used(&mem, avail_desc, total as u32);
+                            queue.trigger_interrupt(&mem, &interrupt);
+                        }
+                        Err(e) => error!("virtio-fs transport error: {}", e),
+                    }
+                })
+                .map_err(Error::SpawnBlockingThread)?;
+            blocking_requests.threads.push((thread, request_interrupt));
+            continue;
+        }
+
         let total =
             server.handle_message(&mut avail_desc.reader, &mut avail_desc.writer, &mapper)?;
 
+        let mut queue = queue.lock();
         queue.add_used(mem, avail_desc, total as u32);
         queue.trigger_interrupt(mem, interrupt);
     }
@@ -169,7 +271,7 @@ pub fn process_fs_queue<I: SignalableInterrupt, F: FileSystem + Sync>(
     Ok(())
 }
 
-impl<F: FileSystem + Sync> Worker<F> {
+impl<F: FileSystem + Send + Sync + 'static> Worker<F> {
     pub fn new(
         mem: GuestMemory,
         queue: Queue,
@@ -180,7 +282,8 @@ impl<F: FileSystem + Sync> Worker<F> {
     ) -> Worker<F> {
         Worker {
             mem,
-            queue,
+            queue: Arc::new(Mutex::new(queue)),
+            blocking_requests: BlockingRequests::default(),
             server,
             irq,
             tube,
@@ -245,7 +348,8 @@ impl<F: FileSystem + Sync> Worker<F> {
                         if let Err(e) = process_fs_queue(
                             &self.mem,
                             &self.irq,
-                            &mut self.queue,
+                            &self.queue,
+                            &mut self.blocking_requests,
                             &self.server,
                             &self.tube,
                             self.slot,
@@ -257,7 +361,10 @@ impl<F: FileSystem + Sync> Worker<F> {
                     Token::InterruptResample => {
                         self.irq.interrupt_resample();
                     }
-                    Token::Kill => return Ok(()),
+                    Token::Kill => {
+                        self.blocking_requests.stop();
+                        return Ok(());
+                    }
                 }
             }
         }
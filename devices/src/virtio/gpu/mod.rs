@@ -4,13 +4,25 @@
 
 mod edid;
 mod parameters;
+mod png;
 mod protocol;
+mod recorder;
 mod virtio_gpu;
 
 use std::cell::RefCell;
 use std::collections::BTreeMap;
 use std::collections::VecDeque;
+#[cfg(unix)]
+use std::io;
 use std::io::Read;
+#[cfg(unix)]
+use std::net::Ipv4Addr;
+#[cfg(unix)]
+use std::net::TcpListener;
+#[cfg(unix)]
+use std::os::unix::net::UnixListener;
+#[cfg(unix)]
+use std::path::Path;
 use std::path::PathBuf;
 use std::rc::Rc;
 use std::sync::atomic::AtomicBool;
@@ -1028,12 +1040,40 @@ pub enum DisplayBackend {
     X(Option<String>),
     /// Emulate a display without actually displaying it.
     Stub,
+    #[cfg(unix)]
+    /// Serve the scanouts to VNC clients, scanout `i` on the `i`-th listener.
+    Vnc(Arc<Vec<TcpListener>>),
+    #[cfg(unix)]
+    /// Keep the scanouts in memory, and let the clients of the listener read them and inject input.
+    Headless(Arc<UnixListener>),
     #[cfg(windows)]
     /// Open a window using WinAPI.
     WinApi(WinDisplayProperties),
 }
 
 impl DisplayBackend {
+    /// Returns a VNC backend serving the first `num_scanouts` scanouts on consecutive local host
+    /// ports, starting at `port`.
+    #[cfg(unix)]
+    pub fn vnc(port: u16, num_scanouts: usize) -> io::Result<DisplayBackend> {
+        let listeners = (0..num_scanouts)
+            .map(|i| {
+                let port = u16::try_from(usize::from(port) + i).map_err(|_| {
+                    io::Error::new(io::ErrorKind::InvalidInput, "VNC port is out of range")
+                })?;
+                TcpListener::bind((Ipv4Addr::LOCALHOST, port))
+            })
+            .collect::<io::Result<Vec<_>>>()?;
+        Ok(DisplayBackend::Vnc(Arc::new(listeners)))
+    }
+
+    /// Returns a headless backend controlled over a unix socket bound at `path`.
+    #[cfg(unix)]
+    pub fn headless<P: AsRef<Path>>(path: P) -> io::Result<DisplayBackend> {
+        let listener = UnixListener::bind(path)?;
+        Ok(DisplayBackend::Headless(Arc::new(listener)))
+    }
+
     fn build(
         &self,
         #[cfg(windows)] wndproc_thread: &mut Option<WindowProcedureThread>,
@@ -1044,6 +1084,15 @@ impl DisplayBackend {
             #[cfg(unix)]
             DisplayBackend::X(display) => GpuDisplay::open_x(display.as_ref()),
             DisplayBackend::Stub => GpuDisplay::open_stub(),
+            #[cfg(unix)]
+            DisplayBackend::Vnc(listeners) => GpuDisplay::open_vnc(
+                listeners
+                    .iter()
+                    .map(TcpListener::try_clone)
+                    .collect::<io::Result<_>>()?,
+            ),
+            #[cfg(unix)]
+            DisplayBackend::Headless(listener) => GpuDisplay::open_headless(listener.try_clone()?),
             #[cfg(windows)]
             DisplayBackend::WinApi(display_properties) => match wndproc_thread.take() {
                 Some(wndproc_thread) => GpuDisplay::open_winapi(
@@ -1295,6 +1344,17 @@ impl VirtioDevice for Gpu {
             resource_bridges.append_raw_descriptors(&mut keep_rds);
         }
 
+        #[cfg(unix)]
+        for display_backend in &self.display_backends {
+            match display_backend {
+                DisplayBackend::Vnc(listeners) => {
+                    keep_rds.extend(listeners.iter().map(|l| l.as_raw_descriptor()));
+                }
+                DisplayBackend::Headless(listener) => keep_rds.push(listener.as_raw_descriptor()),
+                _ => {}
+            }
+        }
+
         keep_rds
     }
 
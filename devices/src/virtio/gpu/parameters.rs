@@ -6,6 +6,7 @@
 
 #[cfg(windows)]
 use std::marker::PhantomData;
+use std::path::PathBuf;
 
 use rutabaga_gfx::RutabagaWsi;
 use serde::Deserialize;
@@ -64,6 +65,10 @@ pub struct GpuParameters {
     pub pci_bar_size: u64,
     #[serde(rename = "context-types", with = "serde_capset_mask")]
     pub capset_mask: u64,
+    /// Serve the displays over VNC, starting on this local host port.
+    pub vnc_port: Option<u16>,
+    /// Keep the displays in memory, and serve their contents and accept input on this socket.
+    pub headless_socket: Option<PathBuf>,
 }
 
 impl Default for GpuParameters {
@@ -84,6 +89,8 @@ impl Default for GpuParameters {
             pci_bar_size: (1 << 33),
             udmabuf: false,
             capset_mask: 0,
+            vnc_port: None,
+            headless_socket: None,
         }
     }
 }
@@ -0,0 +1,252 @@
+// Copyright 2023 The ChromiumOS Authors
+// Use of this source code is governed by a BSD-style license that can be
+// found in the LICENSE file.
+
+//! Minimal PNG and APNG encoders used for display screenshots and recordings.
+
+use std::io;
+use std::io::Seek;
+use std::io::SeekFrom;
+use std::io::Write;
+
+use flate2::write::ZlibEncoder;
+use flate2::Compression;
+
+const PNG_SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+// Color type 2 is truecolor without alpha.
+const COLOR_TYPE_RGB: u8 = 2;
+
+fn write_chunk<W: Write>(w: &mut W, kind: &[u8; 4], data: &[u8]) -> io::Result<()> {
+    let mut crc = crc32fast::Hasher::new();
+    crc.update(kind);
+    crc.update(data);
+    w.write_all(&(data.len() as u32).to_be_bytes())?;
+    w.write_all(kind)?;
+    w.write_all(data)?;
+    w.write_all(&crc.finalize().to_be_bytes())
+}
+
+fn check_dimensions(width: u32, height: u32, pixels: &[u8]) -> io::Result<()> {
+    if width == 0 || height == 0 || pixels.len() < width as usize * 4 * height as usize {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "pixel buffer does not match image dimensions",
+        ));
+    }
+    Ok(())
+}
+
+fn ihdr(width: u32, height: u32) -> Vec<u8> {
+    let mut ihdr = Vec::with_capacity(13);
+    ihdr.extend_from_slice(&width.to_be_bytes());
+    ihdr.extend_from_slice(&height.to_be_bytes());
+    // 8 bits per sample, RGB, deflate compression, adaptive filtering, no interlace.
+    ihdr.extend_from_slice(&[8, COLOR_TYPE_RGB, 0, 0, 0]);
+    ihdr
+}
+
+/// Converts B8G8R8X8 pixels to the zlib compressed, filtered RGB rows of a PNG image.
+fn image_data(width: u32, height: u32, pixels: &[u8]) -> io::Result<Vec<u8>> {
+    let row_bytes = width as usize * 4;
+    // Favor speed since frames are encoded while recording.
+    let mut encoder = ZlibEncoder::new(Vec::new(), Compression::fast());
+    let mut raw = Vec::with_capacity(width as usize * 3 + 1);
+    for row in pixels.chunks_exact(row_bytes).take(height as usize) {
+        raw.clear();
+        // Each row starts with a filter type byte, 0 meaning no filtering.
+        raw.push(0);
+        for px in row.chunks_exact(4) {
+            raw.extend_from_slice(&[px[2], px[1], px[0]]);
+        }
+        encoder.write_all(&raw)?;
+    }
+    encoder.finish()
+}
+
+/// Writes a `width` by `height` image to `w` as a PNG.
+///
+/// `pixels` holds tightly packed rows of 32-bit pixels in the B8G8R8X8 layout used by scanouts
+/// (DRM_FORMAT_XRGB8888 on a little-endian host). The unused byte is dropped.
+pub fn write_png<W: Write>(w: &mut W, width: u32, height: u32, pixels: &[u8]) -> io::Result<()> {
+    check_dimensions(width, height, pixels)?;
+    w.write_all(&PNG_SIGNATURE)?;
+    write_chunk(w, b"IHDR", &ihdr(width, height))?;
+    write_chunk(w, b"IDAT", &image_data(width, height, pixels)?)?;
+    write_chunk(w, b"IEND", &[])
+}
+
+/// Writes a sequence of full size frames as an animated PNG.
+///
+/// The number of frames is only known once the animation is finished, so the writer must be
+/// seekable to fill it in.
+pub struct ApngWriter<W: Write + Seek> {
+    w: W,
+    width: u32,
+    height: u32,
+    actl_offset: u64,
+    frames: u32,
+    sequence: u32,
+}
+
+impl<W: Write + Seek> ApngWriter<W> {
+    /// Writes the headers of a `width` by `height` animation to `w`.
+    pub fn new(mut w: W, width: u32, height: u32) -> io::Result<ApngWriter<W>> {
+        w.write_all(&PNG_SIGNATURE)?;
+        write_chunk(&mut w, b"IHDR", &ihdr(width, height))?;
+        let actl_offset = w.stream_position()?;
+        write_chunk(&mut w, b"acTL", &actl(0))?;
+        Ok(ApngWriter {
+            w,
+            width,
+            height,
+            actl_offset,
+            frames: 0,
+            sequence: 0,
+        })
+    }
+
+    /// Appends a frame that is shown for `delay_ms` milliseconds.
+    ///
+    /// `pixels` uses the same layout as for [`write_png`].
+    pub fn write_frame(&mut self, pixels: &[u8], delay_ms: u16) -> io::Result<()> {
+        check_dimensions(self.width, self.height, pixels)?;
+
+        let mut fctl = Vec::with_capacity(26);
+        fctl.extend_from_slice(&self.sequence.to_be_bytes());
+        fctl.extend_from_slice(&self.width.to_be_bytes());
+        fctl.extend_from_slice(&self.height.to_be_bytes());
+        // X and Y offsets of the frame.
+        fctl.extend_from_slice(&[0; 8]);
+        fctl.extend_from_slice(&delay_ms.to_be_bytes());
+        fctl.extend_from_slice(&1000u16.to_be_bytes());
+        // Leave the frame as is when done and overwrite the output buffer with it.
+        fctl.extend_from_slice(&[0, 0]);
+        write_chunk(&mut self.w, b"fcTL", &fctl)?;
+        self.sequence += 1;
+
+        let data = image_data(self.width, self.height, pixels)?;
+        if self.frames == 0 {
+            // The first frame doubles as the default image shown by decoders without APNG support.
+            write_chunk(&mut self.w, b"IDAT", &data)?;
+        } else {
+            let mut fdat = Vec::with_capacity(data.len() + 4);
+            fdat.extend_from_slice(&self.sequence.to_be_bytes());
+            fdat.extend_from_slice(&data);
+            write_chunk(&mut self.w, b"fdAT", &fdat)?;
+            self.sequence += 1;
+        }
+        self.frames += 1;
+        Ok(())
+    }
+
+    /// Returns the number of frames written so far.
+    pub fn frames(&self) -> u32 {
+        self.frames
+    }
+
+    /// Ends the animation and fills in the number of frames.
+    ///
+    /// At least one frame must have been written for the image to be valid.
+    pub fn finish(mut self) -> io::Result<W> {
+        write_chunk(&mut self.w, b"IEND", &[])?;
+        let end = self.w.stream_position()?;
+        self.w.seek(SeekFrom::Start(self.actl_offset))?;
+        write_chunk(&mut self.w, b"acTL", &actl(self.frames))?;
+        self.w.seek(SeekFrom::Start(end))?;
+        self.w.flush()?;
+        Ok(self.w)
+    }
+}
+
+fn actl(frames: u32) -> [u8; 8] {
+    let mut actl = [0; 8];
+    actl[..4].copy_from_slice(&frames.to_be_bytes());
+    // The remaining bytes are the number of plays, 0 meaning to loop forever.
+    actl
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+    use std::io::Read;
+
+    use flate2::read::ZlibDecoder;
+
+    use super::*;
+
+    #[test]
+    fn encode_image() {
+        // A 2x1 image with one red and one blue pixel.
+        let pixels = [0x00, 0x00, 0xff, 0x00, 0xff, 0x00, 0x00, 0x00];
+        let mut out = Vec::new();
+        write_png(&mut out, 2, 1, &pixels).unwrap();
+
+        assert_eq!(&out[..8], &PNG_SIGNATURE);
+        assert_eq!(&out[8..16], &[0, 0, 0, 13, b'I', b'H', b'D', b'R']);
+        assert_eq!(&out[16..24], &[0, 0, 0, 2, 0, 0, 0, 1]);
+        assert_eq!(&out[24..29], &[8, COLOR_TYPE_RGB, 0, 0, 0]);
+
+        // The IDAT chunk starts after the IHDR CRC.
+        let idat = &out[33..];
+        assert_eq!(&idat[4..8], b"IDAT");
+        let data = &idat[8..8 + u32::from_be_bytes(idat[..4].try_into().unwrap()) as usize];
+        let mut raw = Vec::new();
+        ZlibDecoder::new(data).read_to_end(&mut raw).unwrap();
+        // The filtered row.
+        assert_eq!(raw, [0, 0xff, 0x00, 0x00, 0x00, 0x00, 0xff]);
+        let crc = crc32fast::hash(&idat[4..8 + data.len()]);
+        assert_eq!(&idat[8 + data.len()..12 + data.len()], &crc.to_be_bytes());
+
+        // IEND with its well-known CRC.
+        assert_eq!(
+            &out[out.len() - 12..],
+            &[0, 0, 0, 0, b'I', b'E', b'N', b'D', 0xae, 0x42, 0x60, 0x82]
+        );
+    }
+
+    #[test]
+    fn encode_animation() {
+        let mut apng = ApngWriter::new(Cursor::new(Vec::new()), 1, 1).unwrap();
+        apng.write_frame(&[0, 0, 0xff, 0], 100).unwrap();
+        apng.write_frame(&[0xff, 0, 0, 0], 2000).unwrap();
+        assert_eq!(apng.frames(), 2);
+        let out = apng.finish().unwrap().into_inner();
+
+        let mut chunks = Vec::new();
+        let mut pos = PNG_SIGNATURE.len();
+        while pos < out.len() {
+            let len = u32::from_be_bytes(out[pos..pos + 4].try_into().unwrap()) as usize;
+            chunks.push((&out[pos + 4..pos + 8], &out[pos + 8..pos + 8 + len]));
+            pos += len + 12;
+        }
+        let kinds: Vec<&[u8]> = chunks.iter().map(|(kind, _)| *kind).collect();
+        assert_eq!(
+            kinds,
+            [
+                &b"IHDR"[..],
+                b"acTL",
+                b"fcTL",
+                b"IDAT",
+                b"fcTL",
+                b"fdAT",
+                b"IEND"
+            ]
+        );
+
+        // Two frames, looping forever.
+        assert_eq!(chunks[1].1, &[0, 0, 0, 2, 0, 0, 0, 0]);
+        // Sequence numbers and delays.
+        assert_eq!(&chunks[2].1[..4], &[0, 0, 0, 0]);
+        assert_eq!(&chunks[2].1[20..24], &[0, 100, 0x03, 0xe8]);
+        assert_eq!(&chunks[4].1[..4], &[0, 0, 0, 1]);
+        assert_eq!(&chunks[4].1[20..24], &[0x07, 0xd0, 0x03, 0xe8]);
+        assert_eq!(&chunks[5].1[..4], &[0, 0, 0, 2]);
+    }
+
+    #[test]
+    fn short_buffer() {
+        let mut out = Vec::new();
+        assert!(write_png(&mut out, 2, 2, &[0; 8]).is_err());
+        assert!(out.is_empty());
+    }
+}
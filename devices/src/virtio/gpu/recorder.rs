@@ -0,0 +1,662 @@
+// Copyright 2023 The ChromiumOS Authors
+// Use of this source code is governed by a BSD-style license that can be
+// found in the LICENSE file.
+
+//! Recording of the frames flushed to a scanout into a file.
+
+use std::fs::File;
+use std::io::BufWriter;
+use std::sync::mpsc;
+use std::sync::mpsc::Receiver;
+use std::sync::mpsc::SyncSender;
+use std::thread;
+use std::thread::JoinHandle;
+use std::time::Duration;
+use std::time::Instant;
+
+use anyhow::Context;
+use anyhow::Result;
+use base::error;
+use base::info;
+use base::warn;
+use vm_control::gpu::RecordingFormat;
+
+use super::png::ApngWriter;
+
+/// A sink for the frames of a recording.
+trait FrameSink {
+    /// Adds a frame of B8G8R8X8 pixels that was shown `timestamp` after the recording started.
+    fn write_frame(&mut self, timestamp: Duration, pixels: &[u8]) -> Result<()>;
+
+    /// Writes any pending data and finalizes the file. `timestamp` is the end of the recording.
+    fn finish(self: Box<Self>, timestamp: Duration) -> Result<()>;
+}
+
+/// Records a scanout as an animated PNG.
+struct ApngSink {
+    writer: ApngWriter<BufWriter<File>>,
+    // The last frame, which is only written once the next frame tells how long it was shown.
+    pending: Option<(Duration, Vec<u8>)>,
+}
+
+impl ApngSink {
+    fn new(file: File, width: u32, height: u32) -> Result<ApngSink> {
+        Ok(ApngSink {
+            writer: ApngWriter::new(BufWriter::new(file), width, height)
+                .context("failed to write APNG header")?,
+            pending: None,
+        })
+    }
+
+    fn write_pending(&mut self, timestamp: Duration) -> Result<()> {
+        if let Some((start, pixels)) = self.pending.take() {
+            let delay_ms = (timestamp - start).as_millis().min(u16::MAX as u128) as u16;
+            self.writer
+                .write_frame(&pixels, delay_ms)
+                .context("failed to write APNG frame")?;
+        }
+        Ok(())
+    }
+}
+
+impl FrameSink for ApngSink {
+    fn write_frame(&mut self, timestamp: Duration, pixels: &[u8]) -> Result<()> {
+        self.write_pending(timestamp)?;
+        self.pending = Some((timestamp, pixels.to_vec()));
+        Ok(())
+    }
+
+    fn finish(mut self: Box<Self>, timestamp: Duration) -> Result<()> {
+        self.write_pending(timestamp)?;
+        if self.writer.frames() == 0 {
+            anyhow::bail!("no frames were recorded");
+        }
+        self.writer.finish().context("failed to finish APNG")?;
+        Ok(())
+    }
+}
+
+#[cfg(all(unix, feature = "ffmpeg"))]
+mod ivf {
+    use std::fs::File;
+    use std::io::BufWriter;
+    use std::io::Seek;
+    use std::io::SeekFrom;
+    use std::io::Write;
+    use std::time::Duration;
+
+    use anyhow::anyhow;
+    use anyhow::Context;
+    use anyhow::Result;
+    use ffmpeg::avcodec::AvBuffer;
+    use ffmpeg::avcodec::AvBufferSource;
+    use ffmpeg::avcodec::AvCodec;
+    use ffmpeg::avcodec::AvCodecContext;
+    use ffmpeg::avcodec::AvCodecIterator;
+    use ffmpeg::avcodec::AvFrame;
+    use ffmpeg::avcodec::AvPacket;
+    use ffmpeg::avcodec::AvPixelFormat;
+    use ffmpeg::avcodec::Dimensions;
+    use ffmpeg::avcodec::PlaneDescriptor;
+    use ffmpeg::avcodec::TryReceiveResult;
+    use ffmpeg::swscale::SwConverter;
+    use ffmpeg::AVPixelFormat_AV_PIX_FMT_BGR0;
+    use ffmpeg::AVPixelFormat_AV_PIX_FMT_YUV420P;
+    use ffmpeg::AVRational;
+
+    use super::FrameSink;
+
+    const IVF_HEADER_SIZE: u16 = 32;
+    const IVF_FRAME_COUNT_OFFSET: u64 = 24;
+    // Timestamps are expressed in milliseconds.
+    const TIME_BASE: AVRational = AVRational { num: 1, den: 1000 };
+
+    struct VecBufferSource(Vec<u8>);
+
+    impl AvBufferSource for VecBufferSource {
+        fn as_ptr(&self) -> *const u8 {
+            self.0.as_ptr()
+        }
+
+        fn len(&self) -> usize {
+            self.0.len()
+        }
+    }
+
+    fn new_frame(
+        width: u32,
+        height: u32,
+        format: AvPixelFormat,
+        data: Vec<u8>,
+        strides: &[usize],
+    ) -> Result<AvFrame> {
+        let mut builder = AvFrame::builder()?;
+        builder.set_dimensions(Dimensions { width, height })?;
+        builder.set_format(format)?;
+        let sizes = format.plane_sizes(strides.iter().map(|s| *s as u32), height)?;
+        let mut offset = 0;
+        let planes = strides
+            .iter()
+            .zip(sizes)
+            .map(|(&stride, size)| {
+                let plane = PlaneDescriptor {
+                    buffer_index: 0,
+                    offset,
+                    stride,
+                };
+                offset += size;
+                plane
+            })
+            .collect::<Vec<_>>();
+        let buffer = AvBuffer::new(VecBufferSource(data))
+            .ok_or_else(|| anyhow!("failed to allocate frame buffer"))?;
+        Ok(builder.build_owned([buffer], planes)?)
+    }
+
+    fn vp8_encoder() -> Option<AvCodec> {
+        AvCodecIterator::new().find(|codec| codec.is_encoder() && codec.name() == "libvpx")
+    }
+
+    /// Returns whether ffmpeg provides a VP8 encoder.
+    pub(super) fn is_supported() -> bool {
+        vp8_encoder().is_some()
+    }
+
+    pub(super) fn new_sink(file: File, width: u32, height: u32) -> Result<Box<dyn FrameSink>> {
+        let codec = vp8_encoder().context("ffmpeg has no VP8 encoder")?;
+        Ok(Box::new(IvfSink::new(codec, file, width, height)?))
+    }
+
+    /// Records a scanout as a VP8 stream in an IVF container using libvpx from ffmpeg.
+    struct IvfSink {
+        writer: BufWriter<File>,
+        context: AvCodecContext,
+        converter: SwConverter,
+        width: u32,
+        height: u32,
+        yuv_format: AvPixelFormat,
+        yuv_strides: Vec<usize>,
+        yuv_size: usize,
+        last_pts: Option<i64>,
+        frames: u32,
+    }
+
+    impl IvfSink {
+        fn new(codec: AvCodec, file: File, width: u32, height: u32) -> Result<IvfSink> {
+            let yuv_format = AvPixelFormat::try_from(AVPixelFormat_AV_PIX_FMT_YUV420P)
+                .map_err(|_| anyhow!("YUV420P is not supported"))?;
+            let mut builder = codec.build_encoder()?;
+            builder.set_dimensions(Dimensions { width, height });
+            builder.set_time_base(TIME_BASE);
+            builder.set_pix_fmt(yuv_format);
+            let context = builder.build()?;
+
+            let converter = SwConverter::new(
+                width as usize,
+                height as usize,
+                AVPixelFormat_AV_PIX_FMT_BGR0,
+                AVPixelFormat_AV_PIX_FMT_YUV420P,
+            )?;
+
+            let yuv_strides = (0..3)
+                .map(|plane| yuv_format.line_size(width, plane))
+                .collect::<Result<Vec<_>, _>>()?;
+            let yuv_size = yuv_format
+                .plane_sizes(yuv_strides.iter().map(|s| *s as u32), height)?
+                .iter()
+                .sum();
+
+            let mut writer = BufWriter::new(file);
+            writer.write_all(b"DKIF")?;
+            writer.write_all(&0u16.to_le_bytes())?;
+            writer.write_all(&IVF_HEADER_SIZE.to_le_bytes())?;
+            writer.write_all(b"VP80")?;
+            writer.write_all(&(width as u16).to_le_bytes())?;
+            writer.write_all(&(height as u16).to_le_bytes())?;
+            writer.write_all(&(TIME_BASE.den as u32).to_le_bytes())?;
+            writer.write_all(&(TIME_BASE.num as u32).to_le_bytes())?;
+            // Frame count, filled in when the recording is finished, and an unused field.
+            writer.write_all(&[0; 8])?;
+
+            Ok(IvfSink {
+                writer,
+                context,
+                converter,
+                width,
+                height,
+                yuv_format,
+                yuv_strides,
+                yuv_size,
+                last_pts: None,
+                frames: 0,
+            })
+        }
+
+        /// Writes all the packets the encoder has ready. Returns `true` once a flush completed.
+        fn drain_packets(&mut self) -> Result<bool> {
+            loop {
+                let mut packet = AvPacket::empty();
+                match self.context.try_receive_packet(&mut packet)? {
+                    TryReceiveResult::Received => {
+                        let packet = packet.as_ref();
+                        // Safe because the encoder filled `packet` with `size` bytes of data.
+                        let data = unsafe {
+                            std::slice::from_raw_parts(packet.data, packet.size as usize)
+                        };
+                        self.writer.write_all(&(data.len() as u32).to_le_bytes())?;
+                        self.writer.write_all(&(packet.pts as u64).to_le_bytes())?;
+                        self.writer.write_all(data)?;
+                        self.frames += 1;
+                    }
+                    TryReceiveResult::TryAgain => return Ok(false),
+                    TryReceiveResult::FlushCompleted => return Ok(true),
+                }
+            }
+        }
+    }
+
+    impl FrameSink for IvfSink {
+        fn write_frame(&mut self, timestamp: Duration, pixels: &[u8]) -> Result<()> {
+            let bgr_format = AvPixelFormat::try_from(AVPixelFormat_AV_PIX_FMT_BGR0)
+                .map_err(|_| anyhow!("BGR0 is not supported"))?;
+            let src = new_frame(
+                self.width,
+                self.height,
+                bgr_format,
+                pixels.to_vec(),
+                &[self.width as usize * 4],
+            )?;
+            let mut dst = new_frame(
+                self.width,
+                self.height,
+                self.yuv_format,
+                vec![0; self.yuv_size],
+                &self.yuv_strides,
+            )?;
+            self.converter.convert(&src, &mut dst)?;
+            // The encoder rejects frames that do not advance the timestamp.
+            let pts = match self.last_pts {
+                Some(last) => (timestamp.as_millis() as i64).max(last + 1),
+                None => timestamp.as_millis() as i64,
+            };
+            self.last_pts = Some(pts);
+            dst.set_pts(pts);
+
+            while !self
+                .context
+                .try_send_frame(&dst)
+                .context("failed to send frame")?
+            {
+                self.drain_packets()?;
+            }
+            self.drain_packets()?;
+            Ok(())
+        }
+
+        fn finish(mut self: Box<Self>, _timestamp: Duration) -> Result<()> {
+            self.context.flush_encoder()?;
+            while !self.drain_packets()? {}
+            self.writer
+                .seek(SeekFrom::Start(IVF_FRAME_COUNT_OFFSET))
+                .and_then(|_| self.writer.write_all(&self.frames.to_le_bytes()))
+                .and_then(|_| self.writer.flush())
+                .context("failed to finish IVF file")?;
+            Ok(())
+        }
+    }
+}
+
+#[cfg(not(all(unix, feature = "ffmpeg")))]
+mod ivf {
+    use std::fs::File;
+
+    use anyhow::Result;
+
+    use super::FrameSink;
+
+    pub(super) fn is_supported() -> bool {
+        false
+    }
+
+    pub(super) fn new_sink(_file: File, _width: u32, _height: u32) -> Result<Box<dyn FrameSink>> {
+        anyhow::bail!("IVF recordings require ffmpeg support")
+    }
+}
+
+fn new_sink(
+    file: File,
+    width: u32,
+    height: u32,
+    format: RecordingFormat,
+) -> Result<Box<dyn FrameSink>> {
+    Ok(match format {
+        RecordingFormat::Apng => Box::new(ApngSink::new(file, width, height)?),
+        RecordingFormat::Ivf => ivf::new_sink(file, width, height)?,
+    })
+}
+
+// Number of frame buffers of a recording. One of them holds the last recorded frame and the others
+// can be queued for encoding at the same time.
+const FRAME_BUFFERS: usize = 4;
+
+struct Frame {
+    timestamp: Duration,
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+}
+
+impl Frame {
+    fn same_contents(&self, other: &Frame) -> bool {
+        self.width == other.width && self.height == other.height && self.pixels == other.pixels
+    }
+
+    /// Copies the frame into the top left corner of a `width` by `height` canvas, cropping it or
+    /// filling the rest of the canvas with black as needed.
+    fn fit_to(&self, width: u32, height: u32, canvas: &mut [u8]) {
+        canvas.fill(0);
+        let row_bytes = self.width.min(width) as usize * 4;
+        for (dst, src) in canvas
+            .chunks_exact_mut(width as usize * 4)
+            .take(height as usize)
+            .zip(self.pixels.chunks_exact(self.width as usize * 4))
+        {
+            dst[..row_bytes].copy_from_slice(&src[..row_bytes]);
+        }
+    }
+}
+
+enum Message {
+    Frame(Frame),
+    /// Ends the recording at the given timestamp.
+    Finish(Duration),
+}
+
+/// Writes the frames received from `messages` to `sink` until the recording is finished, then
+/// returns the number of recorded frames. The buffers of the frames are handed back through
+/// `buffers`.
+fn record(
+    mut sink: Box<dyn FrameSink>,
+    width: u32,
+    height: u32,
+    messages: Receiver<Message>,
+    buffers: SyncSender<Vec<u8>>,
+) -> Result<u64> {
+    let mut canvas = Vec::new();
+    let mut last_frame: Option<Frame> = None;
+    let mut frames = 0;
+    for message in messages {
+        let frame = match message {
+            Message::Frame(frame) => frame,
+            Message::Finish(timestamp) => {
+                sink.finish(timestamp)?;
+                return Ok(frames);
+            }
+        };
+
+        // Identical frames are skipped, which only extends how long the previous frame is shown.
+        if last_frame
+            .as_ref()
+            .map_or(false, |last| last.same_contents(&frame))
+        {
+            let _ = buffers.try_send(frame.pixels);
+            continue;
+        }
+        let (last_width, last_height) = last_frame
+            .as_ref()
+            .map_or((width, height), |last| (last.width, last.height));
+        if (frame.width, frame.height) != (last_width, last_height) {
+            info!(
+                "recorded display resized to {}x{}, fitting frames to {}x{}",
+                frame.width, frame.height, width, height
+            );
+        }
+
+        if (frame.width, frame.height) == (width, height) {
+            sink.write_frame(frame.timestamp, &frame.pixels)?;
+        } else {
+            canvas.resize(width as usize * height as usize * 4, 0);
+            frame.fit_to(width, height, &mut canvas);
+            sink.write_frame(frame.timestamp, &canvas)?;
+        }
+        frames += 1;
+
+        if let Some(last) = last_frame.replace(frame) {
+            let _ = buffers.try_send(last.pixels);
+        }
+    }
+    anyhow::bail!("recording ended without being finished")
+}
+
+fn join(worker: JoinHandle<Result<u64>>) -> Result<u64> {
+    worker
+        .join()
+        .map_err(|_| anyhow::anyhow!("recorder thread panicked"))?
+}
+
+/// An ongoing recording of a scanout.
+///
+/// Frames are encoded by a worker thread so that recording does not hold up the device. When the
+/// worker falls behind, new frames are dropped until one of its buffers is free again.
+pub struct Recording {
+    format: RecordingFormat,
+    start: Instant,
+    messages: SyncSender<Message>,
+    buffers: Receiver<Vec<u8>>,
+    worker: Option<JoinHandle<Result<u64>>>,
+    dropped: u64,
+}
+
+impl Recording {
+    /// Starts recording `width` by `height` frames to `file`. Frames of a different size are
+    /// cropped or padded to that size.
+    ///
+    /// When no `format` is requested, the recording is encoded as VP8 if ffmpeg supports it and
+    /// falls back to APNG otherwise.
+    pub fn new(
+        file: File,
+        width: u32,
+        height: u32,
+        format: Option<RecordingFormat>,
+    ) -> Result<Recording> {
+        let format = format.unwrap_or_else(|| {
+            if ivf::is_supported() {
+                RecordingFormat::Ivf
+            } else {
+                RecordingFormat::Apng
+            }
+        });
+
+        // At most `FRAME_BUFFERS` frames exist at once, so neither channel ever blocks for long.
+        let (messages_tx, messages_rx) = mpsc::sync_channel(FRAME_BUFFERS);
+        let (buffers_tx, buffers_rx) = mpsc::sync_channel(FRAME_BUFFERS);
+        for _ in 0..FRAME_BUFFERS {
+            buffers_tx.send(Vec::new())?;
+        }
+        let (ready_tx, ready_rx) = mpsc::channel();
+        let worker = thread::Builder::new()
+            .name("v_gpu_recorder".to_string())
+            .spawn(move || {
+                // The encoders are not `Send`, so the sink is created by the worker itself.
+                let sink = new_sink(file, width, height, format)?;
+                let _ = ready_tx.send(());
+                record(sink, width, height, messages_rx, buffers_tx)
+            })
+            .context("failed to spawn recorder thread")?;
+        if ready_rx.recv().is_err() {
+            join(worker)?;
+            anyhow::bail!("recorder thread exited");
+        }
+
+        Ok(Recording {
+            format,
+            start: Instant::now(),
+            messages: messages_tx,
+            buffers: buffers_rx,
+            worker: Some(worker),
+            dropped: 0,
+        })
+    }
+
+    /// Returns the format of the file being written.
+    pub fn format(&self) -> RecordingFormat {
+        self.format
+    }
+
+    /// Returns a buffer to read the next frame into, or `None` if the frame should be dropped
+    /// because all the buffers are still queued for encoding.
+    pub fn buffer(&mut self) -> Option<Vec<u8>> {
+        match self.buffers.try_recv() {
+            Ok(buffer) => Some(buffer),
+            Err(_) => {
+                self.dropped += 1;
+                None
+            }
+        }
+    }
+
+    /// Queues a `width` by `height` frame of B8G8R8X8 `pixels` for encoding. The buffer should
+    /// come from [`Recording::buffer`], frames allocated otherwise may block until the worker
+    /// catches up.
+    pub fn add_frame(&mut self, width: u32, height: u32, pixels: Vec<u8>) -> Result<()> {
+        let frame = Frame {
+            timestamp: self.start.elapsed(),
+            width,
+            height,
+            pixels,
+        };
+        if self.messages.send(Message::Frame(frame)).is_err() {
+            // The worker only stops early when it fails, joining it tells why.
+            return Err(self
+                .stop()
+                .err()
+                .unwrap_or_else(|| anyhow::anyhow!("recorder thread exited")));
+        }
+        Ok(())
+    }
+
+    /// Finalizes the file and returns the number of recorded frames.
+    pub fn finish(mut self) -> Result<u64> {
+        self.stop()
+    }
+
+    fn stop(&mut self) -> Result<u64> {
+        let worker = match self.worker.take() {
+            Some(worker) => worker,
+            None => anyhow::bail!("recording already stopped"),
+        };
+        // This fails if the worker already exited, `join` returns its error then.
+        let _ = self.messages.send(Message::Finish(self.start.elapsed()));
+        let frames = join(worker)?;
+        if self.dropped > 0 {
+            warn!(
+                "{} frames were dropped while the recorder was busy",
+                self.dropped
+            );
+        }
+        Ok(frames)
+    }
+}
+
+impl Drop for Recording {
+    fn drop(&mut self) {
+        if self.worker.is_some() {
+            if let Err(e) = self.stop() {
+                error!("failed to finish recording: {:#}", e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+    use std::io::Seek;
+    use std::io::SeekFrom;
+
+    use super::*;
+
+    fn read_file(file: &mut File) -> Vec<u8> {
+        let mut data = Vec::new();
+        file.seek(SeekFrom::Start(0)).unwrap();
+        file.read_to_end(&mut data).unwrap();
+        data
+    }
+
+    #[test]
+    fn apng_recording() {
+        let mut file = tempfile::tempfile().unwrap();
+        let mut recording =
+            Recording::new(file.try_clone().unwrap(), 1, 1, Some(RecordingFormat::Apng)).unwrap();
+        assert_eq!(recording.format(), RecordingFormat::Apng);
+
+        let mut buffer = recording.buffer().unwrap();
+        buffer.extend_from_slice(&[0, 0, 0, 0]);
+        recording.add_frame(1, 1, buffer).unwrap();
+        // Identical frames are skipped.
+        recording.add_frame(1, 1, vec![0, 0, 0, 0]).unwrap();
+        recording
+            .add_frame(1, 1, vec![0xff, 0xff, 0xff, 0])
+            .unwrap();
+        assert_eq!(recording.finish().unwrap(), 2);
+
+        let data = read_file(&mut file);
+        // The acTL chunk follows the signature and the IHDR chunk and holds the frame count.
+        assert_eq!(&data[37..41], b"acTL");
+        assert_eq!(&data[41..45], &[0, 0, 0, 2]);
+        assert_eq!(&data[data.len() - 8..data.len() - 4], b"IEND");
+    }
+
+    #[test]
+    fn resized_recording() {
+        let mut file = tempfile::tempfile().unwrap();
+        let mut recording =
+            Recording::new(file.try_clone().unwrap(), 2, 2, Some(RecordingFormat::Apng)).unwrap();
+        recording.add_frame(2, 2, vec![0x10; 16]).unwrap();
+        recording.add_frame(1, 1, vec![0x20; 4]).unwrap();
+        recording.add_frame(3, 3, vec![0x30; 36]).unwrap();
+        assert_eq!(recording.finish().unwrap(), 3);
+
+        let data = read_file(&mut file);
+        // The size of the animation is the initial one.
+        assert_eq!(&data[16..24], &[0, 0, 0, 2, 0, 0, 0, 2]);
+        assert_eq!(&data[41..45], &[0, 0, 0, 3]);
+    }
+
+    #[test]
+    fn fit_frame() {
+        let frame = Frame {
+            timestamp: Duration::ZERO,
+            width: 2,
+            height: 1,
+            pixels: vec![1, 1, 1, 1, 2, 2, 2, 2],
+        };
+        let mut canvas = vec![0xff; 12];
+        // Padded on the right.
+        frame.fit_to(3, 1, &mut canvas[..12]);
+        assert_eq!(canvas, [1, 1, 1, 1, 2, 2, 2, 2, 0, 0, 0, 0]);
+        // Cropped on the right and padded at the bottom.
+        frame.fit_to(1, 2, &mut canvas[..8]);
+        assert_eq!(&canvas[..8], &[1, 1, 1, 1, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn dropped_frames() {
+        let file = tempfile::tempfile().unwrap();
+        let mut recording = Recording::new(file, 1, 1, Some(RecordingFormat::Apng)).unwrap();
+        // Buffers that are not handed back to the recording are not available for new frames.
+        let buffers: Vec<Vec<u8>> = (0..FRAME_BUFFERS)
+            .map(|_| recording.buffer().unwrap())
+            .collect();
+        assert!(recording.buffer().is_none());
+        assert_eq!(buffers.len(), FRAME_BUFFERS);
+        assert_eq!(recording.dropped, 1);
+    }
+
+    #[test]
+    fn empty_recording() {
+        let file = tempfile::tempfile().unwrap();
+        let recording = Recording::new(file, 1, 1, Some(RecordingFormat::Apng)).unwrap();
+        assert!(recording.finish().is_err());
+    }
+}
@@ -5,6 +5,9 @@
 use std::cell::RefCell;
 use std::collections::BTreeMap as Map;
 use std::collections::BTreeSet as Set;
+use std::fs::File;
+use std::io::BufWriter;
+use std::io::Write;
 use std::num::NonZeroU32;
 use std::rc::Rc;
 use std::result::Result;
@@ -20,6 +23,7 @@ use base::SafeDescriptor;
 use data_model::VolatileSlice;
 use gpu_display::*;
 use libc::c_void;
+use rutabaga_gfx::DrmFormat;
 use rutabaga_gfx::ResourceCreate3D;
 use rutabaga_gfx::ResourceCreateBlob;
 use rutabaga_gfx::Rutabaga;
@@ -37,20 +41,27 @@ use rutabaga_gfx::Transfer3D;
 use rutabaga_gfx::RUTABAGA_MEM_HANDLE_TYPE_DMABUF;
 use rutabaga_gfx::RUTABAGA_MEM_HANDLE_TYPE_OPAQUE_FD;
 use vm_control::gpu::DisplayParameters;
+use vm_control::gpu::DisplayRecording;
+use vm_control::gpu::DisplayScreenshot;
 use vm_control::gpu::GpuControlCommand;
 use vm_control::gpu::GpuControlResult;
+use vm_control::gpu::RecordingFormat;
 use vm_control::VmMemorySource;
 use vm_memory::udmabuf::UdmabufDriver;
 use vm_memory::udmabuf::UdmabufDriverTrait;
 use vm_memory::GuestAddress;
 use vm_memory::GuestMemory;
 
+use super::png::write_png;
 use super::protocol::GpuResponse;
 use super::protocol::GpuResponse::*;
 use super::protocol::GpuResponsePlaneInfo;
 use super::protocol::VirtioGpuResult;
 use super::protocol::VIRTIO_GPU_BLOB_FLAG_CREATE_GUEST_HANDLE;
 use super::protocol::VIRTIO_GPU_BLOB_MEM_HOST3D;
+use super::protocol::VIRTIO_GPU_FORMAT_B8G8R8A8_UNORM;
+use super::protocol::VIRTIO_GPU_FORMAT_B8G8R8X8_UNORM;
+use super::recorder::Recording;
 use super::VirtioScanoutBlobData;
 use crate::virtio::gpu::edid::DisplayInfo;
 use crate::virtio::gpu::edid::EdidBytes;
@@ -81,6 +92,8 @@ struct VirtioGpuResource {
     scanout_data: Option<VirtioScanoutBlobData>,
     display_import: Option<u32>,
     rutabaga_external_mapping: bool,
+    // The virtio-gpu format of resources created with `resource_create_3d`.
+    format: Option<u32>,
 }
 
 impl VirtioGpuResource {
@@ -96,6 +109,23 @@ impl VirtioGpuResource {
             scanout_data: None,
             display_import: None,
             rutabaga_external_mapping: false,
+            format: None,
+        }
+    }
+
+    /// Returns whether the resource holds 32-bit pixels laid out as B8G8R8X8 in memory, alpha
+    /// being ignored if any.
+    fn is_bgrx(&self) -> bool {
+        match (&self.scanout_data, self.format) {
+            (Some(data), _) => {
+                data.drm_format == DrmFormat::new(b'X', b'R', b'2', b'4')
+                    || data.drm_format == DrmFormat::new(b'A', b'R', b'2', b'4')
+            }
+            (None, Some(format)) => matches!(
+                format,
+                VIRTIO_GPU_FORMAT_B8G8R8X8_UNORM | VIRTIO_GPU_FORMAT_B8G8R8A8_UNORM
+            ),
+            (None, None) => false,
         }
     }
 }
@@ -249,6 +279,42 @@ impl VirtioGpuScanout {
         Ok(OkNoData)
     }
 
+    /// Reads back the visible part of the resource currently scanned out into `pixels`, as
+    /// tightly packed B8G8R8X8 pixels, and returns its width and height. This is smaller than the
+    /// scanout if the resource is.
+    fn read_pixels(
+        &self,
+        resources: &Map<u32, VirtioGpuResource>,
+        rutabaga: &mut Rutabaga,
+        pixels: &mut Vec<u8>,
+    ) -> Result<(u32, u32), String> {
+        let resource_id = match self.resource_id {
+            Some(id) => id.get(),
+            None => return Err("display has no scanout resource".to_string()),
+        };
+        let (width, height) = match resources.get(&resource_id) {
+            Some(resource) if resource.is_bgrx() => match &resource.scanout_data {
+                Some(data) => (data.width, data.height),
+                None => (resource.width, resource.height),
+            },
+            Some(_) => return Err(format!("unsupported format of resource {}", resource_id)),
+            None => return Err(format!("no such resource {}", resource_id)),
+        };
+        let (width, height) = (width.min(self.width), height.min(self.height));
+        if width == 0 || height == 0 {
+            return Err(format!("resource {} is empty", resource_id));
+        }
+
+        let stride = width * 4;
+        pixels.resize(stride as usize * height as usize, 0);
+        let mut transfer = Transfer3D::new_2d(0, 0, width, height);
+        transfer.stride = stride;
+        rutabaga
+            .transfer_read(0, resource_id, transfer, Some(VolatileSlice::new(pixels)))
+            .map_err(|e| format!("failed to read resource {}: {}", resource_id, e))?;
+        Ok((width, height))
+    }
+
     fn import_resource_to_display(
         display: &Rc<RefCell<GpuDisplay>>,
         resource: &mut VirtioGpuResource,
@@ -308,6 +374,8 @@ pub struct VirtioGpu {
     resources: Map<u32, VirtioGpuResource>,
     external_blob: bool,
     udmabuf_driver: Option<UdmabufDriver>,
+    // Maps scanout ids to their ongoing recordings.
+    recordings: Map<u32, Recording>,
 }
 
 fn sglist_to_rutabaga_iovecs(
@@ -391,6 +459,7 @@ impl VirtioGpu {
             resources: Default::default(),
             external_blob,
             udmabuf_driver,
+            recordings: Default::default(),
         };
 
         for event_device in event_devices {
@@ -497,6 +566,14 @@ impl VirtioGpu {
                     })?;
 
                 self.scanouts.remove(display_id);
+                if let Some(recording) = self.recordings.remove(display_id) {
+                    if let Err(e) = recording.finish() {
+                        error!(
+                            "failed to finish recording of display {}: {:#}",
+                            display_id, e
+                        );
+                    }
+                }
 
                 Ok(())
             })
@@ -507,12 +584,134 @@ impl VirtioGpu {
             })
     }
 
+    /// Reads back the resource currently scanned out to the display and writes it to `file` as a
+    /// PNG image.
+    fn screenshot(&mut self, display_id: u32, file: File) -> GpuControlResult {
+        let scanout = match self.scanouts.get(&display_id) {
+            Some(scanout) => scanout,
+            None => return GpuControlResult::NoSuchDisplay { display_id },
+        };
+        let mut pixels = Vec::new();
+        let (width, height) =
+            match scanout.read_pixels(&self.resources, &mut self.rutabaga, &mut pixels) {
+                Ok(size) => size,
+                Err(e) => return GpuControlResult::ScreenshotFailed(e),
+            };
+
+        let mut writer = BufWriter::new(file);
+        if let Err(e) = write_png(&mut writer, width, height, &pixels).and_then(|_| writer.flush())
+        {
+            return GpuControlResult::ScreenshotFailed(format!("failed to write image: {}", e));
+        }
+
+        GpuControlResult::ScreenshotTaken { width, height }
+    }
+
+    /// Starts recording every frame flushed to the display to `file`.
+    fn start_recording(
+        &mut self,
+        display_id: u32,
+        file: File,
+        format: Option<RecordingFormat>,
+    ) -> GpuControlResult {
+        let scanout = match self.scanouts.get(&display_id) {
+            Some(scanout) => scanout,
+            None => return GpuControlResult::NoSuchDisplay { display_id },
+        };
+        if self.recordings.contains_key(&display_id) {
+            return GpuControlResult::RecordingFailed(format!(
+                "display {} is already being recorded",
+                display_id
+            ));
+        }
+
+        let mut recording = match Recording::new(file, scanout.width, scanout.height, format) {
+            Ok(recording) => recording,
+            Err(e) => return GpuControlResult::RecordingFailed(format!("{:#}", e)),
+        };
+        // Start with what is currently shown, if anything.
+        let mut pixels = Vec::new();
+        if let Ok((width, height)) =
+            scanout.read_pixels(&self.resources, &mut self.rutabaga, &mut pixels)
+        {
+            if let Err(e) = recording.add_frame(width, height, pixels) {
+                return GpuControlResult::RecordingFailed(format!("{:#}", e));
+            }
+        }
+
+        let format = recording.format();
+        self.recordings.insert(display_id, recording);
+        GpuControlResult::RecordingStarted { format }
+    }
+
+    /// Stops the recording of the display and finalizes its file.
+    fn stop_recording(&mut self, display_id: u32) -> GpuControlResult {
+        match self.recordings.remove(&display_id) {
+            Some(mut recording) => {
+                // End with what is currently shown, in case the last update was dropped.
+                if let Some(scanout) = self.scanouts.get(&display_id) {
+                    let mut pixels = Vec::new();
+                    if let Ok((width, height)) =
+                        scanout.read_pixels(&self.resources, &mut self.rutabaga, &mut pixels)
+                    {
+                        // A failure is reported when finishing.
+                        let _ = recording.add_frame(width, height, pixels);
+                    }
+                }
+                match recording.finish() {
+                    Ok(frames) => GpuControlResult::RecordingStopped { frames },
+                    Err(e) => GpuControlResult::RecordingFailed(format!("{:#}", e)),
+                }
+            }
+            None => GpuControlResult::RecordingFailed(format!(
+                "display {} is not being recorded",
+                display_id
+            )),
+        }
+    }
+
+    /// Queues the current contents of the scanouts showing `resource_id` for their recordings.
+    /// Frames are dropped while a recording is busy encoding previous ones.
+    fn record_resource(&mut self, resource_id: NonZeroU32) {
+        let rutabaga = &mut self.rutabaga;
+        let resources = &self.resources;
+        let scanouts = &self.scanouts;
+        self.recordings.retain(|scanout_id, recording| {
+            let scanout = match scanouts.get(scanout_id) {
+                Some(scanout) if scanout.resource_id == Some(resource_id) => scanout,
+                _ => return true,
+            };
+            let mut pixels = match recording.buffer() {
+                Some(pixels) => pixels,
+                None => return true,
+            };
+            let result = scanout
+                .read_pixels(resources, rutabaga, &mut pixels)
+                .map_err(anyhow::Error::msg)
+                .and_then(|(width, height)| recording.add_frame(width, height, pixels));
+            if let Err(e) = result {
+                error!("stopping recording of display {}: {:#}", scanout_id, e);
+                return false;
+            }
+            true
+        });
+    }
+
     /// Performs the given command to interact with or modify the device.
     pub fn process_gpu_control_command(&mut self, cmd: GpuControlCommand) -> GpuControlResult {
         match cmd {
             GpuControlCommand::AddDisplays { displays } => self.add_displays(displays),
             GpuControlCommand::ListDisplays => self.list_displays(),
             GpuControlCommand::RemoveDisplays { display_ids } => self.remove_displays(display_ids),
+            GpuControlCommand::Screenshot(DisplayScreenshot { display_id, file }) => {
+                self.screenshot(display_id, file)
+            }
+            GpuControlCommand::StartRecording(DisplayRecording {
+                display_id,
+                file,
+                format,
+            }) => self.start_recording(display_id, file, format),
+            GpuControlCommand::StopRecording { display_id } => self.stop_recording(display_id),
         }
     }
 
@@ -585,6 +784,9 @@ impl VirtioGpu {
             self.cursor_scanout
                 .flush(&self.display, resource, &mut self.rutabaga)?;
         }
+        if let Some(resource_id) = resource_id {
+            self.record_resource(resource_id);
+        }
 
         Ok(OkNoData)
     }
@@ -722,12 +924,13 @@ impl VirtioGpu {
         self.rutabaga
             .resource_create_3d(resource_id, resource_create_3d)?;
 
-        let resource = VirtioGpuResource::new(
+        let mut resource = VirtioGpuResource::new(
             resource_id,
             resource_create_3d.width,
             resource_create_3d.height,
             0,
         );
+        resource.format = Some(resource_create_3d.format);
 
         // Rely on rutabaga to check for duplicate resource ids.
         self.resources.insert(resource_id, resource);
@@ -93,6 +93,20 @@ pub fn new_multi_touch_config(idx: u32, width: u32, height: u32) -> VirtioInputC
     )
 }
 
+/// Instantiates a VirtioInputConfig object with the default configuration for a gamepad. It
+/// supports the standard face, shoulder and menu buttons, two analog sticks, two analog triggers
+/// and a d-pad reported as a hat switch.
+pub fn new_gamepad_config(idx: u32) -> VirtioInputConfig {
+    VirtioInputConfig::new(
+        virtio_input_device_ids::new(0, 0, 0, 0),
+        name_with_index(b"Crosvm Virtio Gamepad ", idx),
+        name_with_index(b"virtio-gamepad-", idx),
+        virtio_input_bitmap::new([0u8; 128]),
+        default_gamepad_events(),
+        default_gamepad_absinfo(),
+    )
+}
+
 fn default_touchscreen_absinfo(width: u32, height: u32) -> BTreeMap<u16, virtio_input_absinfo> {
     let mut absinfo: BTreeMap<u16, virtio_input_absinfo> = BTreeMap::new();
     absinfo.insert(ABS_X, virtio_input_absinfo::new(0, width, 0, 0));
@@ -296,6 +310,44 @@ fn default_keyboard_events() -> BTreeMap<u16, virtio_input_bitmap> {
     supported_events
 }
 
+fn default_gamepad_absinfo() -> BTreeMap<u16, virtio_input_absinfo> {
+    // Sticks are signed 16-bit values centered on zero, triggers range from released (0) to fully
+    // pressed (255) and the hat reports -1, 0 or 1 on each axis. The absinfo fields are s32 in the
+    // virtio spec, so negative minimums are passed in their two's complement form.
+    let mut absinfo: BTreeMap<u16, virtio_input_absinfo> = BTreeMap::new();
+    for axis in [ABS_X, ABS_Y, ABS_RX, ABS_RY] {
+        absinfo.insert(
+            axis,
+            virtio_input_absinfo::new(i16::MIN as i32 as u32, i16::MAX as u32, 16, 128),
+        );
+    }
+    for axis in [ABS_Z, ABS_RZ] {
+        absinfo.insert(axis, virtio_input_absinfo::new(0, 255, 0, 0));
+    }
+    for axis in [ABS_HAT0X, ABS_HAT0Y] {
+        absinfo.insert(axis, virtio_input_absinfo::new(-1i32 as u32, 1, 0, 0));
+    }
+    absinfo
+}
+
+fn default_gamepad_events() -> BTreeMap<u16, virtio_input_bitmap> {
+    let mut supported_events: BTreeMap<u16, virtio_input_bitmap> = BTreeMap::new();
+    supported_events.insert(
+        EV_KEY,
+        virtio_input_bitmap::from_bits(&[
+            BTN_SOUTH, BTN_EAST, BTN_NORTH, BTN_WEST, BTN_TL, BTN_TR, BTN_SELECT, BTN_START,
+            BTN_MODE, BTN_THUMBL, BTN_THUMBR,
+        ]),
+    );
+    supported_events.insert(
+        EV_ABS,
+        virtio_input_bitmap::from_bits(&[
+            ABS_X, ABS_Y, ABS_Z, ABS_RX, ABS_RY, ABS_RZ, ABS_HAT0X, ABS_HAT0Y,
+        ]),
+    );
+    supported_events
+}
+
 fn default_switch_events() -> BTreeMap<u16, virtio_input_bitmap> {
     let mut supported_events: BTreeMap<u16, virtio_input_bitmap> = BTreeMap::new();
     supported_events.insert(
@@ -344,4 +396,24 @@ mod tests {
         expected_bitmap[2] = 0b1u8;
         assert_eq!(events[&EV_SW].bitmap, expected_bitmap);
     }
+
+    #[test]
+    fn test_new_gamepad_config() {
+        let config = new_gamepad_config(1);
+        assert_eq!(config.serial_name, b"virtio-gamepad-1".to_vec());
+
+        let events = config.supported_events;
+        assert_eq!(events.len(), 2);
+        // BTN_SOUTH..BTN_THUMBR live in bytes 38 and 39 of the bitmap; BTN_C, BTN_Z, BTN_TL2 and
+        // BTN_TR2 are not reported.
+        assert_eq!(events[&EV_KEY].bitmap[38], 0b11011011u8);
+        assert_eq!(events[&EV_KEY].bitmap[39], 0b01111100u8);
+
+        let absinfo = config.axis_info;
+        assert_eq!(absinfo.len(), 8);
+        assert_eq!(absinfo[&ABS_X].min.to_native() as i32, -32768);
+        assert_eq!(absinfo[&ABS_X].max.to_native(), 32767);
+        assert_eq!(absinfo[&ABS_HAT0Y].min.to_native() as i32, -1);
+        assert_eq!(absinfo[&ABS_RZ].max.to_native(), 255);
+    }
 }
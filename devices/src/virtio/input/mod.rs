@@ -3,7 +3,7 @@
 // found in the LICENSE file.
 
 #[allow(dead_code)]
-mod constants;
+pub mod constants;
 mod defaults;
 mod evdev;
 mod event_source;
@@ -33,9 +33,9 @@ use zerocopy::AsBytes;
 use zerocopy::FromBytes;
 
 use self::constants::*;
-use self::event_source::EvdevEventSource;
-use self::event_source::EventSource;
-use self::event_source::SocketEventSource;
+pub use self::event_source::EvdevEventSource;
+pub use self::event_source::EventSource;
+pub use self::event_source::SocketEventSource;
 use super::copy_config;
 use super::virtio_device::Error as VirtioError;
 use super::DescriptorChain;
@@ -746,3 +746,21 @@ where
         virtio_features,
     })
 }
+
+/// Creates a new virtio gamepad device which supports the standard face, shoulder and menu
+/// buttons, two analog sticks, two analog triggers and a d-pad.
+pub fn new_gamepad<T>(
+    idx: u32,
+    source: T,
+    virtio_features: u64,
+) -> Result<Input<SocketEventSource<T>>>
+where
+    T: Read + Write + AsRawDescriptor + Send + 'static,
+{
+    Ok(Input {
+        worker_thread: None,
+        config: defaults::new_gamepad_config(idx),
+        source: Some(SocketEventSource::new(source)),
+        virtio_features,
+    })
+}
@@ -11,6 +11,7 @@ pub(crate) mod sys;
 use std::cell::RefCell;
 use std::collections::btree_map::Entry;
 use std::collections::BTreeMap;
+use std::collections::BTreeSet;
 use std::io;
 use std::io::Write;
 use std::mem::size_of;
@@ -26,7 +27,6 @@ use anyhow::Context;
 use base::debug;
 use base::error;
 use base::pagesize;
-#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
 use base::warn;
 use base::AsRawDescriptor;
 use base::Error as SysError;
@@ -70,7 +70,6 @@ use crate::virtio::Queue;
 use crate::virtio::Reader;
 use crate::virtio::SignalableInterrupt;
 use crate::virtio::VirtioDevice;
-#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
 use crate::virtio::Writer;
 use crate::Suspendable;
 
@@ -78,9 +77,23 @@ const QUEUE_SIZE: u16 = 256;
 const NUM_QUEUES: usize = 2;
 const QUEUE_SIZES: &[u16] = &[QUEUE_SIZE; NUM_QUEUES];
 
+// Size of struct virtio_iommu_probe_resv_mem
+const IOMMU_PROBE_RESV_MEM_SIZE: usize = size_of::<virtio_iommu_probe_resv_mem>();
 // Size of struct virtio_iommu_probe_property
+const IOMMU_PROBE_PROPERTY_SIZE: usize = size_of::<virtio_iommu_probe_property>();
+
 #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
-const IOMMU_PROBE_SIZE: usize = size_of::<virtio_iommu_probe_resv_mem>();
+const X86_MSI_IOVA_START: u64 = 0xfee0_0000;
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+const X86_MSI_IOVA_END: u64 = 0xfeef_ffff;
+
+// Offset of the driver-writable `bypass` field, which is followed by 3 reserved bytes at the end
+// of struct virtio_iommu_config.
+const VIRTIO_IOMMU_CONFIG_BYPASS_OFFSET: u64 = (size_of::<virtio_iommu_config>() - 4) as u64;
+
+// Range of domain IDs supported by the device.
+const DOMAIN_ID_START: u32 = 0;
+const DOMAIN_ID_END: u32 = u32::MAX;
 
 #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
 const VIRTIO_IOMMU_VIOT_NODE_PCI_RANGE: u8 = 1;
@@ -157,9 +170,18 @@ pub enum IommuError {
     WriteBufferTooSmall,
 }
 
+struct DomainEntry {
+    // Number of endpoints attached to the domain
+    refs: u32,
+    mapper: Arc<Mutex<Box<dyn MemoryMapperTrait>>>,
+    // Whether the domain was created with VIRTIO_IOMMU_ATTACH_F_BYPASS, in which case
+    // all of guest memory is identity mapped and MAP/UNMAP requests are rejected.
+    identity: bool,
+}
+
 // key: domain ID
-// value: reference counter and MemoryMapperTrait
-type DomainMap = BTreeMap<u32, (u32, Arc<Mutex<Box<dyn MemoryMapperTrait>>>)>;
+// value: reference counter, MemoryMapperTrait and domain type
+type DomainMap = BTreeMap<u32, DomainEntry>;
 
 struct DmabufRegionEntry {
     mmap: MemoryMapping,
@@ -171,6 +193,15 @@ struct DmabufRegionEntry {
 struct State {
     mem: GuestMemory,
     page_mask: u64,
+    // Size in bytes of the properties buffer of PROBE requests
+    probe_size: usize,
+    // Features acked by the driver
+    acked_features: u64,
+    // Value of the `bypass` config field. When set, accesses from unattached endpoints are
+    // identity mapped.
+    bypass: bool,
+    // Unattached endpoints whose mappers currently hold identity mappings because of `bypass`
+    bypassed_endpoints: BTreeSet<u32>,
     // Hot-pluggable PCI endpoints ranges
     // RangeInclusive: (start endpoint PCI address .. =end endpoint PCI address)
     #[cfg_attr(windows, allow(dead_code))]
@@ -190,7 +221,116 @@ struct State {
     dmabuf_mem: BTreeMap<u64, DmabufRegionEntry>,
 }
 
+// Maps all of guest memory into `mapper` with iova == gpa. Regions which are already mapped
+// (e.g. because the mapper is shared with another identity mapped endpoint) are left as is.
+fn identity_map(mem: &GuestMemory, mapper: &mut dyn MemoryMapperTrait) -> Result<()> {
+    for (gpa, size) in mem.guest_memory_regions() {
+        let res = mapper
+            .add_map(MappingInfo {
+                iova: gpa.offset(),
+                gpa,
+                size: size as u64,
+                prot: Protection::read_write(),
+            })
+            .map_err(IommuError::MemoryMapper)?;
+        if res == AddMapResult::OverlapFailure {
+            debug!("identity mapping for {} already exists", gpa);
+        }
+    }
+    Ok(())
+}
+
+// Removes the mappings created by `identity_map`. If exported memory is affected, returns an
+// event that will be signaled once all exported memory is released.
+fn identity_unmap(
+    mem: &GuestMemory,
+    mapper: &mut dyn MemoryMapperTrait,
+) -> Result<Option<EventAsync>> {
+    let mut fault_resolved_event = None;
+    for (gpa, size) in mem.guest_memory_regions() {
+        match mapper
+            .remove_map(gpa.offset(), size as u64)
+            .map_err(IommuError::MemoryMapper)?
+        {
+            RemoveMapResult::Success(evt) => {
+                if fault_resolved_event.is_none() {
+                    fault_resolved_event = evt;
+                }
+            }
+            RemoveMapResult::OverlapFailure => {
+                return Err(IommuError::MemoryMapper(anyhow!(
+                    "identity mapping for {} was split",
+                    gpa
+                )));
+            }
+        }
+    }
+    Ok(fault_resolved_event)
+}
+
+fn resv_mem_property(subtype: u32, start: u64, end: u64) -> virtio_iommu_probe_resv_mem {
+    virtio_iommu_probe_resv_mem {
+        head: virtio_iommu_probe_property {
+            type_: (VIRTIO_IOMMU_PROBE_T_RESV_MEM as u16).into(),
+            length: ((IOMMU_PROBE_RESV_MEM_SIZE - IOMMU_PROBE_PROPERTY_SIZE) as u16).into(),
+        },
+        subtype: subtype as u8,
+        start: start.into(),
+        end: end.into(),
+        ..Default::default()
+    }
+}
+
+// Returns the RESV_MEM probe properties of an endpoint behind `mapper`.
+fn resv_mem_properties(mapper: &dyn MemoryMapperTrait) -> Vec<virtio_iommu_probe_resv_mem> {
+    let mut properties = Vec::new();
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    properties.push(resv_mem_property(
+        VIRTIO_IOMMU_RESV_MEM_T_MSI,
+        X86_MSI_IOVA_START,
+        X86_MSI_IOVA_END,
+    ));
+    match mapper.reserved_regions() {
+        Ok(regions) => {
+            for region in regions {
+                properties.push(resv_mem_property(
+                    VIRTIO_IOMMU_RESV_MEM_T_RESERVED,
+                    region.start,
+                    region.end,
+                ));
+            }
+        }
+        Err(e) => warn!("vIOMMU: failed to get reserved regions: {:#}", e),
+    }
+    properties
+}
+
 impl State {
+    // Identity maps all of the endpoints which are not attached to a domain. This is called
+    // when the device is activated with the `bypass` config field set.
+    fn bypass_unattached_endpoints(&mut self) -> Result<()> {
+        for (endpoint, mapper) in self.endpoints.iter() {
+            if self.endpoint_map.contains_key(endpoint) {
+                continue;
+            }
+            identity_map(&self.mem, &mut **mapper.lock())?;
+            self.bypassed_endpoints.insert(*endpoint);
+        }
+        Ok(())
+    }
+
+    // Removes the bypass identity mappings of an endpoint which is about to be attached to a
+    // domain. Returns an event to wait on if exported memory was affected.
+    fn stop_bypass(&mut self, endpoint: u32) -> Result<Option<EventAsync>> {
+        if !self.bypassed_endpoints.remove(&endpoint) {
+            return Ok(None);
+        }
+        match self.endpoints.get(&endpoint) {
+            Some(mapper) => identity_unmap(&self.mem, &mut **mapper.lock()),
+            None => Ok(None),
+        }
+    }
+
     // Detach the given endpoint if possible, and return whether or not the endpoint
     // was actually detached. If a successfully detached endpoint has exported
     // memory, returns an event that will be signaled once all exported memory is released.
@@ -210,7 +350,7 @@ impl State {
         if let Some(attached_domain) = endpoint_map.get(&endpoint) {
             // Remove the entry or update the domain reference count
             if let Entry::Occupied(o) = domain_map.entry(*attached_domain) {
-                let (refs, mapper) = o.get();
+                let DomainEntry { refs, mapper, .. } = o.get();
                 if !mapper.lock().supports_detach() {
                     return (false, None);
                 }
@@ -265,9 +405,36 @@ impl State {
             return Ok((0, None));
         }
 
+        // If the device doesn’t recognize a flags bit, it MUST reject the
+        // request and set status to VIRTIO_IOMMU_S_INVAL. The bypass flag is
+        // only valid when VIRTIO_IOMMU_F_BYPASS_CONFIG is negotiated.
+        let flags: u32 = req.flags.into();
+        let supported_flags = if self.acked_features & (1 << VIRTIO_IOMMU_F_BYPASS_CONFIG) != 0 {
+            VIRTIO_IOMMU_ATTACH_F_BYPASS
+        } else {
+            0
+        };
+        if flags & !supported_flags != 0 {
+            tail.status = VIRTIO_IOMMU_S_INVAL;
+            return Ok((0, None));
+        }
+        let identity = flags & VIRTIO_IOMMU_ATTACH_F_BYPASS != 0;
+
+        // Every domain ID is within domain_range, so there is no need to check
+        // for VIRTIO_IOMMU_S_RANGE.
         let domain: u32 = req.domain.into();
         let endpoint: u32 = req.endpoint.into();
 
+        // If the domain already exists but was created with different flags,
+        // the device SHOULD reject the request and set status to
+        // VIRTIO_IOMMU_S_INVAL.
+        if let Some(entry) = self.domain_map.get(&domain) {
+            if entry.identity != identity {
+                tail.status = VIRTIO_IOMMU_S_INVAL;
+                return Ok((0, None));
+            }
+        }
+
         if let Some(mapper) = self.endpoints.get(&endpoint) {
             // The same mapper can't be used for two domains at the same time,
             // since that would result in conflicts/permission leaks between
@@ -311,15 +478,31 @@ impl State {
                     return Ok((0, None));
                 }
                 fault_resolved_event = evt;
+            } else {
+                // An unattached endpoint may be identity mapped because of the
+                // global bypass, which no longer applies once it is attached.
+                fault_resolved_event = self.stop_bypass(endpoint)?;
+            }
+
+            let mapper = self.endpoints[&endpoint].clone();
+            if identity {
+                identity_map(&self.mem, &mut **mapper.lock())?;
             }
 
             let new_ref = match self.domain_map.get(&domain) {
                 None => 1,
-                Some(val) => val.0 + 1,
+                Some(val) => val.refs + 1,
             };
 
             self.endpoint_map.insert(endpoint, domain);
-            self.domain_map.insert(domain, (new_ref, mapper.clone()));
+            self.domain_map.insert(
+                domain,
+                DomainEntry {
+                    refs: new_ref,
+                    mapper,
+                    identity,
+                },
+            );
         } else {
             // If the endpoint identified by endpoint doesn’t exist,
             // the device MUST reject the request and set status to
@@ -351,6 +534,10 @@ impl State {
             Self::detach_endpoint(&mut self.endpoint_map, &mut self.domain_map, endpoint);
         if !detached {
             tail.status = VIRTIO_IOMMU_S_UNSUPP;
+        } else if self.bypass && !self.bypassed_endpoints.contains(&endpoint) {
+            // Unattached endpoints fall back to the global bypass.
+            identity_map(&self.mem, &mut **self.endpoints[&endpoint].lock())?;
+            self.bypassed_endpoints.insert(endpoint);
         }
         Ok((0, evt))
     }
@@ -381,11 +568,20 @@ impl State {
         }
 
         let domain: u32 = req.domain.into();
-        if !self.domain_map.contains_key(&domain) {
+        match self.domain_map.get(&domain) {
             // If domain does not exist, the device SHOULD reject
             // the request and set status to VIRTIO_IOMMU_S_NOENT.
-            tail.status = VIRTIO_IOMMU_S_NOENT;
-            return Ok(0);
+            None => {
+                tail.status = VIRTIO_IOMMU_S_NOENT;
+                return Ok(0);
+            }
+            // If the domain is a bypass domain, the device SHOULD reject
+            // the request and set status to VIRTIO_IOMMU_S_INVAL.
+            Some(entry) if entry.identity => {
+                tail.status = VIRTIO_IOMMU_S_INVAL;
+                return Ok(0);
+            }
+            Some(_) => (),
         }
 
         // The device MUST NOT allow writes to a range mapped
@@ -416,11 +612,11 @@ impl State {
                 // Safe because [dmabuf_map, dmabuf_map + size) refers to an external mmap'ed region.
                 Some(dmabuf_map) => unsafe {
                     mapper
-                        .1
+                        .mapper
                         .lock()
                         .vfio_dma_map(req.virt_start.into(), dmabuf_map, size, prot)
                 },
-                None => mapper.1.lock().add_map(MappingInfo {
+                None => mapper.mapper.lock().add_map(MappingInfo {
                     iova: req.virt_start.into(),
                     gpa: GuestAddress(req.phys_start.into()),
                     size,
@@ -452,9 +648,15 @@ impl State {
 
         let domain: u32 = req.domain.into();
         let fault_resolved_event = if let Some(mapper) = self.domain_map.get(&domain) {
+            if mapper.identity {
+                // If the domain is a bypass domain, the device SHOULD reject
+                // the request and set status to VIRTIO_IOMMU_S_INVAL.
+                tail.status = VIRTIO_IOMMU_S_INVAL;
+                return Ok((0, None));
+            }
             let size = u64::from(req.virt_end) - u64::from(req.virt_start) + 1;
             let res = mapper
-                .1
+                .mapper
                 .lock()
                 .remove_map(u64::from(req.virt_start), size)
                 .map_err(IommuError::MemoryMapper)?;
@@ -479,7 +681,6 @@ impl State {
         Ok((0, fault_resolved_event))
     }
 
-    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
     fn process_probe_request(
         &mut self,
         reader: &mut Reader,
@@ -492,39 +693,38 @@ impl State {
         // If the endpoint identified by endpoint doesn’t exist,
         // then the device SHOULD reject the request and set status
         // to VIRTIO_IOMMU_S_NOENT.
-        if !self.endpoints.contains_key(&endpoint) {
-            tail.status = VIRTIO_IOMMU_S_NOENT;
-        }
+        let properties = match self.endpoints.get(&endpoint) {
+            Some(mapper) => resv_mem_properties(&**mapper.lock()),
+            None => {
+                tail.status = VIRTIO_IOMMU_S_NOENT;
+                Vec::new()
+            }
+        };
 
         let properties_size = writer.available_bytes() - size_of::<virtio_iommu_req_tail>();
 
         // It's OK if properties_size is larger than probe_size
         // We are good even if properties_size is 0
-        if properties_size < IOMMU_PROBE_SIZE {
+        if properties_size < self.probe_size {
             // If the properties list is smaller than probe_size, the device
             // SHOULD NOT write any property. It SHOULD reject the request
             // and set status to VIRTIO_IOMMU_S_INVAL.
             tail.status = VIRTIO_IOMMU_S_INVAL;
         } else if tail.status == VIRTIO_IOMMU_S_OK {
-            const VIRTIO_IOMMU_PROBE_T_RESV_MEM: u16 = 1;
-            const VIRTIO_IOMMU_RESV_MEM_T_MSI: u8 = 1;
-            const PROBE_PROPERTY_SIZE: u16 = 4;
-            const X86_MSI_IOVA_START: u64 = 0xfee0_0000;
-            const X86_MSI_IOVA_END: u64 = 0xfeef_ffff;
-
-            let properties = virtio_iommu_probe_resv_mem {
-                head: virtio_iommu_probe_property {
-                    type_: VIRTIO_IOMMU_PROBE_T_RESV_MEM.into(),
-                    length: (IOMMU_PROBE_SIZE as u16 - PROBE_PROPERTY_SIZE).into(),
-                },
-                subtype: VIRTIO_IOMMU_RESV_MEM_T_MSI,
-                start: X86_MSI_IOVA_START.into(),
-                end: X86_MSI_IOVA_END.into(),
-                ..Default::default()
-            };
-            writer
-                .write_all(properties.as_bytes())
-                .map_err(IommuError::GuestMemoryWrite)?;
+            let max_properties = properties_size / IOMMU_PROBE_RESV_MEM_SIZE;
+            if properties.len() > max_properties {
+                warn!(
+                    "vIOMMU: endpoint {:#x} has {} reserved regions, only reporting {}",
+                    endpoint,
+                    properties.len(),
+                    max_properties
+                );
+            }
+            for property in properties.iter().take(max_properties) {
+                writer
+                    .write_all(property.as_bytes())
+                    .map_err(IommuError::GuestMemoryWrite)?;
+            }
         }
 
         // If the device doesn’t fill all probe_size bytes with properties,
@@ -566,7 +766,6 @@ impl State {
             VIRTIO_IOMMU_T_DETACH => self.process_detach_request(reader, &mut tail)?,
             VIRTIO_IOMMU_T_MAP => (self.process_dma_map_request(reader, &mut tail)?, None),
             VIRTIO_IOMMU_T_UNMAP => self.process_dma_unmap_request(reader, &mut tail)?,
-            #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
             VIRTIO_IOMMU_T_PROBE => (self.process_probe_request(reader, writer, &mut tail)?, None),
             _ => return Err(IommuError::UnexpectedDescriptor),
         };
@@ -684,6 +883,7 @@ pub struct Iommu {
     worker_thread: Option<WorkerThread<()>>,
     config: virtio_iommu_config,
     avail_features: u64,
+    acked_features: u64,
     // Attached endpoints
     // key: endpoint PCI address
     // value: reference counter and MemoryMapperTrait
@@ -719,25 +919,44 @@ impl Iommu {
             return Err(SysError::new(libc::EIO));
         }
 
+        // The probe buffer needs to hold the reserved regions of the endpoint
+        // with the most of them.
+        let mut max_properties = 0;
+        for (_, container) in endpoints.iter() {
+            max_properties = max_properties.max(resv_mem_properties(&**container.lock()).len());
+        }
+        if cfg!(any(target_arch = "x86", target_arch = "x86_64")) {
+            // Hot-plugged endpoints always have at least the MSI region.
+            max_properties = max_properties.max(1);
+        }
+        let probe_size = max_properties * IOMMU_PROBE_RESV_MEM_SIZE;
+
         let input_range = virtio_iommu_range_64 {
             start: Le64::from(0),
             end: iova_max_addr.into(),
         };
 
+        let domain_range = virtio_iommu_range_32 {
+            start: DOMAIN_ID_START.into(),
+            end: DOMAIN_ID_END.into(),
+        };
+
         let config = virtio_iommu_config {
             page_size_mask: page_size_mask.into(),
             input_range,
-            #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
-            probe_size: (IOMMU_PROBE_SIZE as u32).into(),
+            domain_range,
+            probe_size: (probe_size as u32).into(),
             ..Default::default()
         };
 
         let mut avail_features: u64 = base_features;
         avail_features |= 1 << VIRTIO_IOMMU_F_MAP_UNMAP
             | 1 << VIRTIO_IOMMU_F_INPUT_RANGE
-            | 1 << VIRTIO_IOMMU_F_MMIO;
+            | 1 << VIRTIO_IOMMU_F_DOMAIN_RANGE
+            | 1 << VIRTIO_IOMMU_F_MMIO
+            | 1 << VIRTIO_IOMMU_F_BYPASS_CONFIG;
 
-        if cfg!(any(target_arch = "x86", target_arch = "x86_64")) {
+        if probe_size > 0 {
             avail_features |= 1 << VIRTIO_IOMMU_F_PROBE;
         }
 
@@ -745,6 +964,7 @@ impl Iommu {
             worker_thread: None,
             config,
             avail_features,
+            acked_features: 0,
             endpoints,
             hp_endpoints_ranges,
             translate_response_senders,
@@ -789,12 +1009,42 @@ impl VirtioDevice for Iommu {
         self.avail_features
     }
 
+    fn ack_features(&mut self, value: u64) {
+        self.acked_features |= value & self.avail_features;
+    }
+
     fn read_config(&self, offset: u64, data: &mut [u8]) {
         let mut config: Vec<u8> = Vec::new();
         config.extend_from_slice(self.config.as_bytes());
         copy_config(data, 0, config.as_slice(), offset);
     }
 
+    fn write_config(&mut self, offset: u64, data: &[u8]) {
+        // `bypass` is the only field the driver may write, and only if
+        // VIRTIO_IOMMU_F_BYPASS_CONFIG has been negotiated.
+        if self.acked_features & (1 << VIRTIO_IOMMU_F_BYPASS_CONFIG) == 0
+            || offset != VIRTIO_IOMMU_CONFIG_BYPASS_OFFSET
+            || data.len() != 1
+        {
+            warn!(
+                "vIOMMU: ignoring config write of {} bytes at {:#x}",
+                data.len(),
+                offset
+            );
+            return;
+        }
+        if self.worker_thread.is_some() {
+            // The bypass state is handed to the worker when the device is activated, so the
+            // write is rejected and the field keeps reporting the state in effect.
+            error!(
+                "vIOMMU: rejecting bypass config write of {} after activation, bypass stays {}",
+                data[0], self.config.bypass
+            );
+            return;
+        }
+        self.config.bypass = (data[0] != 0).into();
+    }
+
     fn activate(
         &mut self,
         mem: GuestMemory,
@@ -812,6 +1062,9 @@ impl VirtioDevice for Iommu {
         // The least significant bit of page_size_masks defines the page
         // granularity of IOMMU mappings
         let page_mask = (1u64 << u64::from(self.config.page_size_mask).trailing_zeros()) - 1;
+        let probe_size = u32::from(self.config.probe_size) as usize;
+        let acked_features = self.acked_features;
+        let bypass = self.config.bypass != 0;
         let eps = self.endpoints.clone();
         let hp_endpoints_ranges = self.hp_endpoints_ranges.to_owned();
 
@@ -824,15 +1077,25 @@ impl VirtioDevice for Iommu {
             .context("failed to start virtio-iommu worker: No control tube")?;
 
         self.worker_thread = Some(WorkerThread::start("v_iommu", move |kill_evt| {
-            let state = State {
+            let mut state = State {
                 mem,
                 page_mask,
+                probe_size,
+                acked_features,
+                bypass,
+                bypassed_endpoints: BTreeSet::new(),
                 hp_endpoints_ranges,
                 endpoint_map: BTreeMap::new(),
                 domain_map: BTreeMap::new(),
                 endpoints: eps,
                 dmabuf_mem: BTreeMap::new(),
             };
+            if bypass {
+                if let Err(e) = state.bypass_unattached_endpoints() {
+                    error!("virtio-iommu failed to bypass endpoints: {}", e);
+                    return;
+                }
+            }
             let result = run(
                 state,
                 iommu_device_tube,
@@ -927,3 +1190,266 @@ impl VirtioDevice for Iommu {
 }
 
 impl Suspendable for Iommu {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::virtio::descriptor_utils::create_descriptor_chain;
+    use crate::virtio::descriptor_utils::DescriptorType;
+
+    const MEM_SIZE: u64 = 0x10000;
+    const REQ_ADDR: u64 = 0x1000;
+
+    fn new_state(bypass: bool) -> State {
+        let mut endpoints: BTreeMap<u32, Arc<Mutex<Box<dyn MemoryMapperTrait>>>> = BTreeMap::new();
+        for endpoint in [1, 2] {
+            endpoints.insert(
+                endpoint,
+                Arc::new(Mutex::new(Box::new(BasicMemoryMapper::new(!0xfff)))),
+            );
+        }
+        State {
+            mem: GuestMemory::new(&[(GuestAddress(0), MEM_SIZE)]).unwrap(),
+            page_mask: 0xfff,
+            probe_size: 0,
+            acked_features: 1 << VIRTIO_IOMMU_F_BYPASS_CONFIG,
+            bypass,
+            bypassed_endpoints: BTreeSet::new(),
+            hp_endpoints_ranges: Vec::new(),
+            endpoint_map: BTreeMap::new(),
+            domain_map: BTreeMap::new(),
+            endpoints,
+            dmabuf_mem: BTreeMap::new(),
+        }
+    }
+
+    // Sends a request of type `type_` with body `req` and returns the status of the reply.
+    fn request<T: AsBytes>(state: &mut State, type_: u8, req: T) -> u8 {
+        let mem = state.mem.clone();
+        let head = virtio_iommu_req_head {
+            type_,
+            ..Default::default()
+        };
+        let mut bytes = head.as_bytes().to_vec();
+        bytes.extend_from_slice(req.as_bytes());
+        mem.write_all_at_addr(&bytes, GuestAddress(REQ_ADDR))
+            .unwrap();
+
+        let mut avail_desc = create_descriptor_chain(
+            &mem,
+            GuestAddress(0x100),
+            GuestAddress(REQ_ADDR),
+            vec![
+                (DescriptorType::Readable, bytes.len() as u32),
+                (
+                    DescriptorType::Writable,
+                    size_of::<virtio_iommu_req_tail>() as u32,
+                ),
+            ],
+            0,
+        )
+        .unwrap();
+        state.execute_request(&mut avail_desc).unwrap();
+
+        let tail: virtio_iommu_req_tail = mem
+            .read_obj_from_addr(GuestAddress(REQ_ADDR + bytes.len() as u64))
+            .unwrap();
+        tail.status
+    }
+
+    fn attach(state: &mut State, domain: u32, endpoint: u32, flags: u32) -> u8 {
+        let req = virtio_iommu_req_attach {
+            domain: domain.into(),
+            endpoint: endpoint.into(),
+            flags: flags.into(),
+            ..Default::default()
+        };
+        request(state, VIRTIO_IOMMU_T_ATTACH, req)
+    }
+
+    fn detach(state: &mut State, domain: u32, endpoint: u32) -> u8 {
+        let req = virtio_iommu_req_detach {
+            domain: domain.into(),
+            endpoint: endpoint.into(),
+            ..Default::default()
+        };
+        request(state, VIRTIO_IOMMU_T_DETACH, req)
+    }
+
+    fn map(state: &mut State, domain: u32, iova: u64, size: u64, gpa: u64) -> u8 {
+        let req = virtio_iommu_req_map {
+            domain: domain.into(),
+            virt_start: iova.into(),
+            virt_end: (iova + size - 1).into(),
+            phys_start: gpa.into(),
+            flags: (VIRTIO_IOMMU_MAP_F_READ | VIRTIO_IOMMU_MAP_F_WRITE).into(),
+        };
+        request(state, VIRTIO_IOMMU_T_MAP, req)
+    }
+
+    fn unmap(state: &mut State, domain: u32, iova: u64, size: u64) -> u8 {
+        let req = virtio_iommu_req_unmap {
+            domain: domain.into(),
+            virt_start: iova.into(),
+            virt_end: (iova + size - 1).into(),
+            ..Default::default()
+        };
+        request(state, VIRTIO_IOMMU_T_UNMAP, req)
+    }
+
+    // Returns whether the page at `iova` is mapped in the mapper of `endpoint`.
+    fn is_mapped(state: &State, endpoint: u32, iova: u64) -> bool {
+        let mut mapper = state.endpoints[&endpoint].lock();
+        let probe = MappingInfo {
+            iova,
+            gpa: GuestAddress(iova),
+            size: 0x1000,
+            prot: Protection::read(),
+        };
+        match mapper.add_map(probe).unwrap() {
+            AddMapResult::OverlapFailure => true,
+            AddMapResult::Ok => {
+                mapper.remove_map(iova, 0x1000).unwrap();
+                false
+            }
+        }
+    }
+
+    #[test]
+    fn attach_detach() {
+        let mut state = new_state(false);
+        assert_eq!(attach(&mut state, 1, 1, 0), VIRTIO_IOMMU_S_OK);
+        assert_eq!(state.endpoint_map.get(&1), Some(&1));
+        assert_eq!(attach(&mut state, 1, 5, 0), VIRTIO_IOMMU_S_NOENT);
+
+        let req = virtio_iommu_req_attach {
+            domain: 2.into(),
+            endpoint: 2.into(),
+            reserved: [1, 0, 0, 0],
+            ..Default::default()
+        };
+        assert_eq!(
+            request(&mut state, VIRTIO_IOMMU_T_ATTACH, req),
+            VIRTIO_IOMMU_S_INVAL
+        );
+
+        // Attaching to another domain detaches from the previous one first.
+        assert_eq!(attach(&mut state, 2, 1, 0), VIRTIO_IOMMU_S_OK);
+        assert_eq!(state.endpoint_map.get(&1), Some(&2));
+        assert!(!state.domain_map.contains_key(&1));
+
+        assert_eq!(detach(&mut state, 2, 1), VIRTIO_IOMMU_S_OK);
+        assert!(state.endpoint_map.is_empty());
+        assert!(state.domain_map.is_empty());
+        assert_eq!(detach(&mut state, 2, 5), VIRTIO_IOMMU_S_NOENT);
+    }
+
+    #[test]
+    fn map_unmap() {
+        let mut state = new_state(false);
+        assert_eq!(attach(&mut state, 1, 1, 0), VIRTIO_IOMMU_S_OK);
+
+        assert_eq!(
+            map(&mut state, 1, 0x4000, 0x2000, 0x8000),
+            VIRTIO_IOMMU_S_OK
+        );
+        assert!(is_mapped(&state, 1, 0x5000));
+        assert!(!is_mapped(&state, 2, 0x5000));
+        assert_eq!(
+            map(&mut state, 1, 0x5000, 0x1000, 0x8000),
+            VIRTIO_IOMMU_S_INVAL
+        );
+        assert_eq!(
+            map(&mut state, 1, 0x8000, 0x800, 0x8000),
+            VIRTIO_IOMMU_S_RANGE
+        );
+        assert_eq!(
+            map(&mut state, 2, 0x8000, 0x1000, 0x8000),
+            VIRTIO_IOMMU_S_NOENT
+        );
+
+        // Unmapping part of a mapping would split it.
+        assert_eq!(unmap(&mut state, 1, 0x4000, 0x1000), VIRTIO_IOMMU_S_RANGE);
+        assert!(is_mapped(&state, 1, 0x4000));
+        assert_eq!(unmap(&mut state, 1, 0x4000, 0x2000), VIRTIO_IOMMU_S_OK);
+        assert!(!is_mapped(&state, 1, 0x4000));
+        assert_eq!(unmap(&mut state, 2, 0x4000, 0x2000), VIRTIO_IOMMU_S_NOENT);
+    }
+
+    #[test]
+    fn identity_domain() {
+        let mut state = new_state(false);
+        assert_eq!(
+            attach(&mut state, 1, 1, VIRTIO_IOMMU_ATTACH_F_BYPASS),
+            VIRTIO_IOMMU_S_OK
+        );
+        assert!(is_mapped(&state, 1, 0));
+        assert!(is_mapped(&state, 1, MEM_SIZE - 0x1000));
+        assert!(!is_mapped(&state, 1, MEM_SIZE));
+
+        // Identity domains can't be modified, nor be joined without the bypass flag.
+        assert_eq!(
+            map(&mut state, 1, MEM_SIZE, 0x1000, 0),
+            VIRTIO_IOMMU_S_INVAL
+        );
+        assert_eq!(unmap(&mut state, 1, 0, MEM_SIZE), VIRTIO_IOMMU_S_INVAL);
+        assert_eq!(attach(&mut state, 1, 2, 0), VIRTIO_IOMMU_S_INVAL);
+
+        assert_eq!(detach(&mut state, 1, 1), VIRTIO_IOMMU_S_OK);
+        assert!(!is_mapped(&state, 1, 0));
+
+        // The flag is only valid if VIRTIO_IOMMU_F_BYPASS_CONFIG was negotiated.
+        state.acked_features = 0;
+        assert_eq!(
+            attach(&mut state, 1, 1, VIRTIO_IOMMU_ATTACH_F_BYPASS),
+            VIRTIO_IOMMU_S_INVAL
+        );
+    }
+
+    #[test]
+    fn global_bypass() {
+        let mut state = new_state(true);
+        state.bypass_unattached_endpoints().unwrap();
+        assert!(is_mapped(&state, 1, 0));
+        assert!(is_mapped(&state, 2, 0));
+
+        // Attached endpoints only see the mappings of their domain.
+        assert_eq!(attach(&mut state, 1, 1, 0), VIRTIO_IOMMU_S_OK);
+        assert!(!is_mapped(&state, 1, 0));
+        assert!(is_mapped(&state, 2, 0));
+
+        assert_eq!(detach(&mut state, 1, 1), VIRTIO_IOMMU_S_OK);
+        assert!(is_mapped(&state, 1, 0));
+    }
+
+    #[test]
+    fn bypass_config() {
+        let mut endpoints: BTreeMap<u32, Arc<Mutex<Box<dyn MemoryMapperTrait>>>> = BTreeMap::new();
+        endpoints.insert(
+            1,
+            Arc::new(Mutex::new(Box::new(BasicMemoryMapper::new(!0xfff)))),
+        );
+        let mut iommu = Iommu::new(0, endpoints, u64::MAX, Vec::new(), None, None, None).unwrap();
+        let read_bypass = |iommu: &Iommu| {
+            let mut bypass = [0xff];
+            iommu.read_config(VIRTIO_IOMMU_CONFIG_BYPASS_OFFSET, &mut bypass);
+            bypass[0]
+        };
+
+        // Writes are ignored until the feature is negotiated.
+        iommu.write_config(VIRTIO_IOMMU_CONFIG_BYPASS_OFFSET, &[1]);
+        assert_eq!(read_bypass(&iommu), 0);
+
+        iommu.ack_features(1 << VIRTIO_IOMMU_F_BYPASS_CONFIG);
+        iommu.write_config(VIRTIO_IOMMU_CONFIG_BYPASS_OFFSET, &[1]);
+        assert_eq!(read_bypass(&iommu), 1);
+        // Other fields are read-only.
+        iommu.write_config(0, &[0]);
+        assert_ne!(u64::from(iommu.config.page_size_mask), 0);
+
+        // Once activated, the state in effect keeps being reported.
+        iommu.worker_thread = Some(WorkerThread::start("v_iommu_test", |_| {}));
+        iommu.write_config(VIRTIO_IOMMU_CONFIG_BYPASS_OFFSET, &[0]);
+        assert_eq!(read_bypass(&iommu), 1);
+    }
+}
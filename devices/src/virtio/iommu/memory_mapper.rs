@@ -185,6 +185,12 @@ pub trait MemoryMapper: Send {
 
     fn get_mask(&self) -> Result<u64>;
 
+    /// Returns the IOVA ranges which can't be used for DMA by endpoints behind
+    /// this mapper. These are reported to the guest as reserved regions.
+    fn reserved_regions(&self) -> Result<Vec<AddressRange>> {
+        Ok(Vec::new())
+    }
+
     /// Whether or not endpoints can be safely detached from this mapper.
     fn supports_detach(&self) -> bool;
     /// Resets the mapper's domain back into its initial state. Only necessary
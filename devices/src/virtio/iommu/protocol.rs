@@ -39,6 +39,7 @@ pub const VIRTIO_IOMMU_F_MAP_UNMAP: u32 = 2;
 pub const VIRTIO_IOMMU_F_BYPASS: u32 = 3;
 pub const VIRTIO_IOMMU_F_PROBE: u32 = 4;
 pub const VIRTIO_IOMMU_F_MMIO: u32 = 5;
+pub const VIRTIO_IOMMU_F_BYPASS_CONFIG: u32 = 6;
 pub const VIRTIO_IOMMU_T_ATTACH: u8 = 1;
 pub const VIRTIO_IOMMU_T_DETACH: u8 = 2;
 pub const VIRTIO_IOMMU_T_MAP: u8 = 3;
@@ -53,6 +54,7 @@ pub const VIRTIO_IOMMU_S_RANGE: u8 = 5;
 pub const VIRTIO_IOMMU_S_NOENT: u8 = 6;
 pub const VIRTIO_IOMMU_S_FAULT: u8 = 7;
 pub const VIRTIO_IOMMU_S_NOMEM: u8 = 8;
+pub const VIRTIO_IOMMU_ATTACH_F_BYPASS: u32 = 1;
 pub const VIRTIO_IOMMU_MAP_F_READ: u32 = 1;
 pub const VIRTIO_IOMMU_MAP_F_WRITE: u32 = 2;
 pub const VIRTIO_IOMMU_MAP_F_MMIO: u32 = 4;
@@ -91,6 +93,8 @@ pub struct virtio_iommu_config {
     pub input_range: virtio_iommu_range_64,
     pub domain_range: virtio_iommu_range_32,
     pub probe_size: Le32,
+    pub bypass: u8,
+    pub reserved: [u8; 3usize],
 }
 
 #[repr(C, packed)]
@@ -112,7 +116,8 @@ pub struct virtio_iommu_req_tail {
 pub struct virtio_iommu_req_attach {
     pub domain: Le32,
     pub endpoint: Le32,
-    pub reserved: [u8; 8usize],
+    pub flags: Le32,
+    pub reserved: [u8; 4usize],
 }
 
 #[repr(C, packed)]
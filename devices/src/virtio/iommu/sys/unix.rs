@@ -23,8 +23,10 @@ use vm_control::VirtioIOMMUVfioCommand;
 use vm_control::VirtioIOMMUVfioResult;
 
 use self::vfio_wrapper::VfioWrapper;
+use crate::virtio::iommu::identity_map;
 use crate::virtio::iommu::ipc_memory_mapper::IommuRequest;
 use crate::virtio::iommu::ipc_memory_mapper::IommuResponse;
+use crate::virtio::iommu::memory_mapper::MemoryMapperTrait;
 use crate::virtio::iommu::DmabufRegionEntry;
 use crate::virtio::iommu::Result;
 use crate::virtio::iommu::State;
@@ -52,8 +54,17 @@ impl State {
             return VirtioIOMMUVfioResult::NotInPCIRanges;
         }
 
-        self.endpoints
-            .insert(endpoint_addr, Arc::new(Mutex::new(Box::new(wrapper))));
+        let mapper: Arc<Mutex<Box<dyn MemoryMapperTrait>>> =
+            Arc::new(Mutex::new(Box::new(wrapper)));
+        if self.bypass {
+            // Until the guest attaches it, the new endpoint is subject to the global bypass.
+            if let Err(e) = identity_map(&self.mem, &mut **mapper.lock()) {
+                error!("failed to identity map endpoint {}: {}", endpoint_addr, e);
+                return VirtioIOMMUVfioResult::InvalidParam;
+            }
+            self.bypassed_endpoints.insert(endpoint_addr);
+        }
+        self.endpoints.insert(endpoint_addr, mapper);
         VirtioIOMMUVfioResult::Ok
     }
 
@@ -65,6 +76,7 @@ impl State {
             error!("There is no vfio container of {}", pci_address);
             return VirtioIOMMUVfioResult::NoSuchDevice;
         }
+        self.bypassed_endpoints.remove(&pci_address);
         if let Some(domain) = self.endpoint_map.remove(&pci_address) {
             self.domain_map.remove(&domain);
         }
@@ -11,6 +11,7 @@ use base::AsRawDescriptor;
 use base::AsRawDescriptors;
 use base::Protection;
 use base::RawDescriptor;
+use resources::AddressRange;
 use sync::Mutex;
 use vm_memory::GuestAddress;
 use vm_memory::GuestMemory;
@@ -114,6 +115,33 @@ impl MemoryMapper for VfioWrapper {
             .context("vfio get mask error")
     }
 
+    fn reserved_regions(&self) -> anyhow::Result<Vec<AddressRange>> {
+        let mut iova_ranges = self
+            .container
+            .lock()
+            .vfio_iommu_iova_get_iova_ranges()
+            .context("vfio get iova ranges error")?;
+        iova_ranges.sort_by_key(|r| r.start);
+
+        // Everything outside of the usable IOVA ranges is reserved.
+        let mut reserved = Vec::new();
+        let mut next_start = 0;
+        for range in iova_ranges {
+            if range.start > next_start {
+                reserved.push(AddressRange::from_start_and_end(
+                    next_start,
+                    range.start - 1,
+                ));
+            }
+            next_start = match range.end.checked_add(1) {
+                Some(start) => start,
+                None => return Ok(reserved),
+            };
+        }
+        reserved.push(AddressRange::from_start_and_end(next_start, u64::MAX));
+        Ok(reserved)
+    }
+
     fn supports_detach(&self) -> bool {
         // A few reasons why we don't support detach:
         //
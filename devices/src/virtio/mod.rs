@@ -11,7 +11,6 @@ mod balloon;
 mod descriptor_chain;
 mod descriptor_utils;
 pub mod device_constants;
-mod input;
 mod interrupt;
 mod iommu;
 #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
@@ -32,6 +31,7 @@ pub mod block;
 pub mod console;
 #[cfg(feature = "gpu")]
 pub mod gpu;
+pub mod input;
 pub mod resource_bridge;
 #[cfg(feature = "audio")]
 pub mod snd;
@@ -6,6 +6,7 @@ use std::fmt;
 use std::io;
 use std::io::Read;
 use std::io::Write;
+use std::pin::Pin;
 use std::rc::Rc;
 
 use async_trait::async_trait;
@@ -13,22 +14,28 @@ use audio_streams::capture::AsyncCaptureBuffer;
 use audio_streams::AsyncPlaybackBuffer;
 use base::debug;
 use base::error;
+use base::Error as SysError;
 use cros_async::sync::Condvar;
 use cros_async::sync::Mutex as AsyncMutex;
+use cros_async::AsyncTube;
 use cros_async::EventAsync;
 use cros_async::Executor;
 use futures::channel::mpsc;
 use futures::channel::oneshot;
 use futures::pin_mut;
 use futures::select;
+use futures::stream::Peekable;
 use futures::FutureExt;
 use futures::SinkExt;
 use futures::StreamExt;
 use thiserror::Error as ThisError;
+use vm_control::SndControlCommand;
+use vm_control::SndControlResult;
 use vm_memory::GuestMemory;
 #[cfg(windows)]
 use win_audio::AudioSharedFormat;
 use zerocopy::AsBytes;
+use zerocopy::FromBytes;
 
 use super::Error;
 use super::SndData;
@@ -38,6 +45,7 @@ use crate::virtio::snd::common_backend::stream_info::SetParams;
 use crate::virtio::snd::common_backend::stream_info::StreamInfo;
 use crate::virtio::snd::common_backend::DirectionalStream;
 use crate::virtio::snd::common_backend::PcmResponse;
+use crate::virtio::snd::common_backend::StreamVolume;
 use crate::virtio::snd::constants::*;
 use crate::virtio::snd::layout::*;
 use crate::virtio::DescriptorChain;
@@ -64,16 +72,27 @@ pub trait PlaybackBufferWriter {
     /// Returns the period of the endpoint device.
     fn endpoint_period_bytes(&self) -> usize;
 
-    /// Read audio samples from the tx virtqueue.
+    /// Read audio samples from the tx virtqueue, applying `volume` to them.
     fn copy_to_buffer(
         &mut self,
         dst_buf: &mut AsyncPlaybackBuffer<'_>,
         reader: &mut Reader,
+        volume: &StreamVolume,
     ) -> Result<usize, Error> {
-        dst_buf.copy_from(reader).map_err(Error::Io)
+        let mut read = Ok(0);
+        dst_buf
+            .copy_cb(self.endpoint_period_bytes(), |buf| {
+                read = reader.read(buf);
+                // Play silence rather than the stale content of the buffer after a short read.
+                let len = *read.as_ref().unwrap_or(&0);
+                buf[len..].fill(0);
+                volume.apply(&mut buf[..len]);
+            })
+            .map_err(Error::Io)?;
+        read.map_err(Error::Io)
     }
     /// Check to see if an additional read from the tx virtqueue is needed during a playback
-    /// loop. If so, read from the virtqueue.
+    /// loop. If so, read from the virtqueue, applying `volume` to the samples.
     ///
     /// Prefill will happen, for example, if the endpoint buffer requires a 513 frame period, but
     /// each tx virtqueue read only produces 480 frames.
@@ -82,6 +101,7 @@ pub trait PlaybackBufferWriter {
         &mut self,
         desc_receiver: &mut mpsc::UnboundedReceiver<DescriptorChain>,
         sender: &mut mpsc::UnboundedSender<PcmResponse>,
+        volume: &StreamVolume,
     ) -> Result<(), Error>;
 }
 
@@ -225,9 +245,10 @@ async fn write_data(
     mut dst_buf: AsyncPlaybackBuffer<'_>,
     reader: Option<&mut Reader>,
     buffer_writer: &mut Box<dyn PlaybackBufferWriter>,
+    volume: &StreamVolume,
 ) -> Result<u32, Error> {
     let transferred = match reader {
-        Some(reader) => buffer_writer.copy_to_buffer(&mut dst_buf, reader)?,
+        Some(reader) => buffer_writer.copy_to_buffer(&mut dst_buf, reader, volume)?,
         None => dst_buf
             .copy_from(&mut io::repeat(0).take(buffer_writer.endpoint_period_bytes() as u64))
             .map_err(Error::Io)?,
@@ -246,13 +267,44 @@ async fn write_data(
     }
 }
 
+// Writes `samples` to `writer` with `volume` applied to them, through a buffer on the stack. Its
+// size is a multiple of every sample size.
+fn write_with_volume(
+    writer: &mut Writer,
+    samples: &[u8],
+    volume: &StreamVolume,
+) -> io::Result<usize> {
+    let mut scaled = [0u8; 512];
+    let mut written = 0;
+    for chunk in samples.chunks(scaled.len()) {
+        let scaled = &mut scaled[..chunk.len()];
+        scaled.copy_from_slice(chunk);
+        volume.apply(scaled);
+        let len = writer.write(scaled)?;
+        written += len;
+        if len < chunk.len() {
+            break;
+        }
+    }
+    Ok(written)
+}
+
 async fn read_data<'a>(
     mut src_buf: AsyncCaptureBuffer<'a>,
     writer: Option<&mut Writer>,
     period_bytes: usize,
+    volume: &StreamVolume,
 ) -> Result<u32, Error> {
     let transferred = match writer {
-        Some(writer) => src_buf.copy_to(writer),
+        Some(writer) => {
+            let mut written = Ok(0);
+            src_buf
+                .copy_cb(period_bytes, |buf| {
+                    written = write_with_volume(writer, buf, volume);
+                })
+                .map_err(Error::Io)?;
+            written
+        }
         None => src_buf.copy_to(&mut io::sink()),
     }
     .map_err(Error::Io)?;
@@ -352,7 +404,7 @@ async fn pcm_worker_loop(
 ) -> Result<(), Error> {
     match dstream {
         #[allow(unused_mut)]
-        DirectionalStream::Output(mut stream, mut buffer_writer) => loop {
+        DirectionalStream::Output(mut stream, mut buffer_writer, volume) => loop {
             let dst_buf = stream
                 .next_playback_buffer(&ex)
                 .await
@@ -361,30 +413,30 @@ async fn pcm_worker_loop(
             match *worker_status {
                 WorkerStatus::Quit => {
                     drain_desc_receiver(desc_receiver, sender).await?;
-                    if let Err(e) = write_data(dst_buf, None, &mut buffer_writer).await {
+                    if let Err(e) = write_data(dst_buf, None, &mut buffer_writer, &volume).await {
                         error!("Error on write_data after worker quit: {}", e)
                     }
                     break Ok(());
                 }
                 WorkerStatus::Pause => {
-                    write_data(dst_buf, None, &mut buffer_writer).await?;
+                    write_data(dst_buf, None, &mut buffer_writer, &volume).await?;
                 }
                 WorkerStatus::Running => {
                     // TODO(b/246601226): Remove once a generic audio_stream solution that can
                     // accpet arbitrarily size buffers
                     #[cfg(windows)]
                     buffer_writer
-                        .check_and_prefill(desc_receiver, sender)
+                        .check_and_prefill(desc_receiver, sender, &volume)
                         .await?;
 
                     match desc_receiver.try_next() {
                         Err(e) => {
                             error!("Underrun. No new DescriptorChain while running: {}", e);
-                            write_data(dst_buf, None, &mut buffer_writer).await?;
+                            write_data(dst_buf, None, &mut buffer_writer, &volume).await?;
                         }
                         Ok(None) => {
                             error!("Unreachable. status should be Quit when the channel is closed");
-                            write_data(dst_buf, None, &mut buffer_writer).await?;
+                            write_data(dst_buf, None, &mut buffer_writer, &volume).await?;
                             return Err(Error::InvalidPCMWorkerState);
                         }
                         Ok(Some(mut desc_chain)) => {
@@ -393,6 +445,7 @@ async fn pcm_worker_loop(
                                 dst_buf,
                                 Some(&mut desc_chain.reader),
                                 &mut buffer_writer,
+                                &volume,
                             )
                             .await
                             .into();
@@ -409,7 +462,7 @@ async fn pcm_worker_loop(
                 }
             }
         },
-        DirectionalStream::Input(mut stream, period_bytes) => loop {
+        DirectionalStream::Input(mut stream, period_bytes, volume) => loop {
             let src_buf = stream
                 .next_capture_buffer(&ex)
                 .await
@@ -419,28 +472,29 @@ async fn pcm_worker_loop(
             match *worker_status {
                 WorkerStatus::Quit => {
                     drain_desc_receiver(desc_receiver, sender).await?;
-                    if let Err(e) = read_data(src_buf, None, period_bytes).await {
+                    if let Err(e) = read_data(src_buf, None, period_bytes, &volume).await {
                         error!("Error on read_data after worker quit: {}", e)
                     }
                     break Ok(());
                 }
                 WorkerStatus::Pause => {
-                    read_data(src_buf, None, period_bytes).await?;
+                    read_data(src_buf, None, period_bytes, &volume).await?;
                 }
                 WorkerStatus::Running => match desc_receiver.try_next() {
                     Err(e) => {
                         error!("Overrun. No new DescriptorChain while running: {}", e);
-                        read_data(src_buf, None, period_bytes).await?;
+                        read_data(src_buf, None, period_bytes, &volume).await?;
                     }
                     Ok(None) => {
                         error!("Unreachable. status should be Quit when the channel is closed");
-                        read_data(src_buf, None, period_bytes).await?;
+                        read_data(src_buf, None, period_bytes, &volume).await?;
                         return Err(Error::InvalidPCMWorkerState);
                     }
                     Ok(Some(mut desc_chain)) => {
-                        let status = read_data(src_buf, Some(&mut desc_chain.writer), period_bytes)
-                            .await
-                            .into();
+                        let status =
+                            read_data(src_buf, Some(&mut desc_chain.writer), period_bytes, &volume)
+                                .await
+                                .into();
                         sender
                             .send(PcmResponse {
                                 desc_chain,
@@ -694,7 +748,7 @@ pub async fn handle_ctrl_queue<I: SignalableInterrupt>(
                         .map_err(Error::WriteResponse)?;
                     for i in start_id..(start_id + count) {
                         writer
-                            .write_all(snd_data.jack_info[i].as_bytes())
+                            .write_all(snd_data.jack_info(i).as_bytes())
                             .map_err(Error::WriteResponse)?;
                     }
                     Ok(())
@@ -760,7 +814,84 @@ pub async fn handle_ctrl_queue<I: SignalableInterrupt>(
                     Ok(())
                 }
                 VIRTIO_SND_R_JACK_REMAP => {
-                    unreachable!("remap is unsupported");
+                    error!("Jack remapping is not supported");
+                    return writer
+                        .write_obj(VIRTIO_SND_S_NOT_SUPP)
+                        .map_err(Error::WriteResponse);
+                }
+                VIRTIO_SND_R_CTL_INFO => {
+                    let query_info: virtio_snd_query_info =
+                        reader.read_obj().map_err(Error::ReadMessage)?;
+                    let start_id: usize = u32::from(query_info.start_id) as usize;
+                    let count: usize = u32::from(query_info.count) as usize;
+                    if start_id + count > snd_data.controls.len() {
+                        error!(
+                            "start_id({}) + count({}) must be smaller than \
+                            the number of controls ({})",
+                            start_id,
+                            count,
+                            snd_data.controls.len()
+                        );
+                        return writer
+                            .write_obj(VIRTIO_SND_S_BAD_MSG)
+                            .map_err(Error::WriteResponse);
+                    }
+                    writer
+                        .write_obj(VIRTIO_SND_S_OK)
+                        .map_err(Error::WriteResponse)?;
+                    for i in start_id..(start_id + count) {
+                        writer
+                            .write_all(snd_data.controls[i].info.as_bytes())
+                            .map_err(Error::WriteResponse)?;
+                    }
+                    Ok(())
+                }
+                VIRTIO_SND_R_CTL_READ | VIRTIO_SND_R_CTL_WRITE => {
+                    let hdr: virtio_snd_ctl_hdr = reader.read_obj().map_err(Error::ReadMessage)?;
+                    let control_id: usize = u32::from(hdr.control_id) as usize;
+                    let control = match snd_data.controls.get(control_id) {
+                        Some(control) => control,
+                        None => {
+                            error!(
+                                "control_id {} < controls {}",
+                                control_id,
+                                snd_data.controls.len()
+                            );
+                            return writer
+                                .write_obj(VIRTIO_SND_S_BAD_MSG)
+                                .map_err(Error::WriteResponse);
+                        }
+                    };
+                    if code == VIRTIO_SND_R_CTL_READ {
+                        let mut value = virtio_snd_ctl_value::new_zeroed();
+                        value.integer[0] = control.value().into();
+                        writer
+                            .write_obj(VIRTIO_SND_S_OK)
+                            .map_err(Error::WriteResponse)?;
+                        return writer.write_obj(value).map_err(Error::WriteResponse);
+                    }
+                    let value: virtio_snd_ctl_value =
+                        reader.read_obj().map_err(Error::ReadMessage)?;
+                    let value: u32 = value.integer[0].into();
+                    if !control.set_value(value) {
+                        error!("Invalid value {} for control {}", value, control_id);
+                        return writer
+                            .write_obj(VIRTIO_SND_S_BAD_MSG)
+                            .map_err(Error::WriteResponse);
+                    }
+                    writer
+                        .write_obj(VIRTIO_SND_S_OK)
+                        .map_err(Error::WriteResponse)
+                }
+                VIRTIO_SND_R_CTL_ENUM_ITEMS
+                | VIRTIO_SND_R_CTL_TLV_READ
+                | VIRTIO_SND_R_CTL_TLV_WRITE
+                | VIRTIO_SND_R_CTL_TLV_COMMAND => {
+                    // None of the controls is enumerated or has TLV access.
+                    error!("Control request {} is not supported", code);
+                    return writer
+                        .write_obj(VIRTIO_SND_S_NOT_SUPP)
+                        .map_err(Error::WriteResponse);
                 }
                 VIRTIO_SND_R_PCM_SET_PARAMS => {
                     // Raise VIRTIO_SND_S_BAD_MSG or IO error?
@@ -877,21 +1008,130 @@ pub async fn handle_ctrl_queue<I: SignalableInterrupt>(
     Ok(())
 }
 
-/// Send events to the audio driver.
+/// Send the events of `event_receiver` to the audio driver.
 pub async fn handle_event_queue<I: SignalableInterrupt>(
     mem: &GuestMemory,
-    mut queue: Queue,
-    mut queue_event: EventAsync,
+    queue: &mut Queue,
+    queue_event: &mut EventAsync,
     interrupt: I,
+    event_receiver: &mut Peekable<mpsc::UnboundedReceiver<virtio_snd_event>>,
+    reset_signal: Option<&(AsyncMutex<bool>, Condvar)>,
 ) -> Result<(), Error> {
+    let on_reset = await_reset_signal(reset_signal).fuse();
+    pin_mut!(on_reset);
+
     loop {
-        let desc_chain = queue
-            .next_async(mem, &mut queue_event)
-            .await
-            .map_err(Error::Async)?;
+        // Events and descriptors are only peeked at until both are available, so that neither is
+        // lost on reset.
+        let event = {
+            let next_event = Pin::new(&mut *event_receiver).peek().fuse();
+            pin_mut!(next_event);
+
+            select! {
+                _ = on_reset => break,
+                event = next_event => event.copied(),
+            }
+        };
+        let event = match event {
+            Some(event) => event,
+            None => {
+                debug!("Event channel is closed.");
+                break;
+            }
+        };
+
+        let mut desc_chain = {
+            let next_async = async {
+                loop {
+                    if let Some(chain) = queue.peek(mem) {
+                        return Ok(chain);
+                    }
+                    queue_event.next_val().await?;
+                }
+            }
+            .fuse();
+            pin_mut!(next_async);
 
-        // TODO(woodychow): Poll and forward events from cras asynchronously (API to be added)
-        queue.add_used(mem, desc_chain, 0);
+            select! {
+                _ = on_reset => break,
+                res = next_async => res.map_err(Error::Async)?,
+            }
+        };
+        queue.pop_peeked(mem);
+        event_receiver.next().now_or_never();
+
+        desc_chain
+            .writer
+            .write_obj(event)
+            .map_err(Error::WriteResponse)?;
+        let len = desc_chain.writer.bytes_written() as u32;
+        queue.add_used(mem, desc_chain, len);
         queue.trigger_interrupt(mem, &interrupt);
     }
+    Ok(())
+}
+
+/// Handle the commands sent by the host through `control_tube`, forwarding the events they cause
+/// to the event queue worker.
+pub async fn handle_control_tube(
+    control_tube: &Option<AsyncTube>,
+    snd_data: &SndData,
+    event_sender: &mpsc::UnboundedSender<virtio_snd_event>,
+    reset_signal: Option<&(AsyncMutex<bool>, Condvar)>,
+) -> Result<(), Error> {
+    let on_reset = await_reset_signal(reset_signal).fuse();
+    pin_mut!(on_reset);
+
+    let control_tube = match control_tube {
+        Some(control_tube) => control_tube,
+        None => {
+            on_reset.await;
+            return Ok(());
+        }
+    };
+
+    loop {
+        let command = {
+            let next_command = control_tube.next::<SndControlCommand>().fuse();
+            pin_mut!(next_command);
+
+            select! {
+                _ = on_reset => break,
+                res = next_command => res,
+            }
+        };
+        let command = match command {
+            Ok(command) => command,
+            Err(e) => {
+                // Don't request a reset that would not fix the tube.
+                error!("Failed to receive snd control command: {}", e);
+                (&mut on_reset).await;
+                break;
+            }
+        };
+
+        let result = match command {
+            SndControlCommand::SetJackConnected { jack_id, connected } => {
+                match snd_data.set_jack_connected(jack_id as usize, connected) {
+                    Ok(event) => {
+                        if let Some(event) = event {
+                            event_sender
+                                .unbounded_send(event)
+                                .map_err(|e| Error::MpscSend(e.into_send_error()))?;
+                        }
+                        SndControlResult::Ok
+                    }
+                    Err(e) => {
+                        error!("Failed to set jack state: {}", e);
+                        SndControlResult::Err(SysError::new(libc::ENODEV))
+                    }
+                }
+            }
+        };
+        control_tube
+            .send(result)
+            .await
+            .map_err(Error::SendControlResult)?;
+    }
+    Ok(())
 }
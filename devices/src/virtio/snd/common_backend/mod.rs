@@ -6,11 +6,16 @@
 
 use std::io;
 use std::rc::Rc;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
 
 use anyhow::anyhow;
 use anyhow::Context;
 use audio_streams::BoxError;
+use audio_streams::SampleFormat;
+use audio_util::SoftwareVolume;
+use audio_util::MAX_VOLUME;
 use base::debug;
 use base::error;
 use base::warn;
@@ -19,11 +24,14 @@ use base::Descriptor;
 use base::Error as SysError;
 use base::Event;
 use base::RawDescriptor;
+use base::Tube;
+use base::TubeError;
 use base::WorkerThread;
 use cros_async::block_on;
 use cros_async::sync::Condvar;
 use cros_async::sync::Mutex as AsyncMutex;
 use cros_async::AsyncError;
+use cros_async::AsyncTube;
 use cros_async::EventAsync;
 use cros_async::Executor;
 use futures::channel::mpsc;
@@ -33,11 +41,14 @@ use futures::future::FusedFuture;
 use futures::join;
 use futures::pin_mut;
 use futures::select;
+use futures::stream::Peekable;
 use futures::Future;
 use futures::FutureExt;
+use futures::StreamExt;
 use thiserror::Error as ThisError;
 use vm_memory::GuestMemory;
 use zerocopy::AsBytes;
+use zerocopy::FromBytes;
 
 use crate::virtio::async_utils;
 use crate::virtio::copy_config;
@@ -50,6 +61,7 @@ use crate::virtio::snd::file_backend::create_file_stream_source_generators;
 use crate::virtio::snd::file_backend::Error as FileError;
 use crate::virtio::snd::layout::*;
 use crate::virtio::snd::null_backend::create_null_stream_source_generators;
+use crate::virtio::snd::parameters::JackState;
 use crate::virtio::snd::parameters::Parameters;
 use crate::virtio::snd::parameters::StreamSourceBackend;
 use crate::virtio::snd::sys::create_stream_source_generators as sys_create_stream_source_generators;
@@ -114,6 +126,9 @@ pub enum Error {
     /// Fetch buffer error
     #[error("Failed to get buffer from CRAS: {0}")]
     FetchBuffer(BoxError),
+    /// Jack not found.
+    #[error("jack id ({0}) >= num_jacks ({1})")]
+    JackNotFound(usize, usize),
     /// Invalid buffer size
     #[error("Invalid buffer size")]
     InvalidBufferSize,
@@ -132,6 +147,9 @@ pub enum Error {
     // Invalid backend.
     #[error("Backend is not implemented")]
     InvalidBackend,
+    /// Failed to send the result of a control command.
+    #[error("Failed to send control result: {0}")]
+    SendControlResult(TubeError),
     // Failed to generate StreamSource
     #[error("Failed to generate stream source: {0}")]
     GenerateStreamSource(BoxError),
@@ -144,13 +162,28 @@ pub enum DirectionalStream {
     Input(
         Box<dyn audio_streams::capture::AsyncCaptureBufferStream>,
         usize, // `period_size` in `usize`
+        StreamVolume,
     ),
     Output(
         Box<dyn audio_streams::AsyncPlaybackBufferStream>,
         Box<dyn PlaybackBufferWriter>,
+        StreamVolume,
     ),
 }
 
+/// Software volume applied to the samples of a stream.
+#[derive(Clone)]
+pub struct StreamVolume {
+    pub(crate) volume: Arc<SoftwareVolume>,
+    pub(crate) format: SampleFormat,
+}
+
+impl StreamVolume {
+    pub fn apply(&self, buf: &mut [u8]) {
+        self.volume.apply(self.format, buf);
+    }
+}
+
 #[derive(Copy, Clone, std::cmp::PartialEq, Eq)]
 pub enum WorkerStatus {
     Pause = 0,
@@ -158,12 +191,44 @@ pub enum WorkerStatus {
     Quit = 2,
 }
 
-// Stores constant data
+/// A control element of a PCM device, backed by the software volume of the device's streams.
+#[derive(Clone)]
+pub struct SndControl {
+    pub(crate) info: virtio_snd_ctl_info,
+    pub(crate) direction: u8, // VIRTIO_SND_D_*
+    pub(crate) volume: Arc<SoftwareVolume>,
+}
+
+impl SndControl {
+    /// Returns the value of the control, as reported to the driver.
+    pub(crate) fn value(&self) -> u32 {
+        match u32::from(self.info.role) {
+            VIRTIO_SND_CTL_ROLE_MUTE => !self.volume.muted() as u32,
+            _ => self.volume.volume(),
+        }
+    }
+
+    /// Sets the value of the control. Returns false if `value` is out of range.
+    pub(crate) fn set_value(&self, value: u32) -> bool {
+        match u32::from(self.info.role) {
+            VIRTIO_SND_CTL_ROLE_MUTE if value <= 1 => self.volume.set_muted(value == 0),
+            VIRTIO_SND_CTL_ROLE_VOLUME if value <= MAX_VOLUME => self.volume.set_volume(value),
+            _ => return false,
+        }
+        true
+    }
+}
+
+// Stores constant data, along with the state of the jacks and controls that is shared by every
+// clone.
 #[derive(Clone)]
 pub struct SndData {
     pub(crate) jack_info: Vec<virtio_snd_jack_info>,
     pub(crate) pcm_info: Vec<virtio_snd_pcm_info>,
     pub(crate) chmap_info: Vec<virtio_snd_chmap_info>,
+    pub(crate) controls: Vec<SndControl>,
+    // Current state of each jack of `jack_info`, which may be changed by the host.
+    pub(crate) jack_connected: Arc<Vec<AtomicBool>>,
 }
 
 impl SndData {
@@ -174,6 +239,50 @@ impl SndData {
     pub fn pcm_info_iter(&self) -> std::slice::Iter<'_, virtio_snd_pcm_info> {
         self.pcm_info.iter()
     }
+
+    /// Returns the information of the jack `jack_id` with its current connection state.
+    pub(crate) fn jack_info(&self, jack_id: usize) -> virtio_snd_jack_info {
+        let mut info = self.jack_info[jack_id];
+        info.connected = self.jack_connected[jack_id].load(Ordering::Relaxed) as u8;
+        info
+    }
+
+    /// Plugs or unplugs the jack `jack_id`.
+    ///
+    /// Returns the event to send to the driver if the state of the jack changed.
+    pub(crate) fn set_jack_connected(
+        &self,
+        jack_id: usize,
+        connected: bool,
+    ) -> Result<Option<virtio_snd_event>, Error> {
+        let jack = self
+            .jack_connected
+            .get(jack_id)
+            .ok_or(Error::JackNotFound(jack_id, self.jack_connected.len()))?;
+        if jack.swap(connected, Ordering::Relaxed) == connected {
+            return Ok(None);
+        }
+        let code = if connected {
+            VIRTIO_SND_EVT_JACK_CONNECTED
+        } else {
+            VIRTIO_SND_EVT_JACK_DISCONNECTED
+        };
+        Ok(Some(virtio_snd_event {
+            hdr: virtio_snd_hdr { code: code.into() },
+            data: (jack_id as u32).into(),
+        }))
+    }
+
+    /// Returns the volume applied to the streams of the PCM device of `pcm_info`.
+    pub(crate) fn pcm_volume(&self, pcm_info: &virtio_snd_pcm_info) -> Option<Arc<SoftwareVolume>> {
+        self.controls
+            .iter()
+            .find(|control| {
+                control.info.hdr.hda_fn_nid == pcm_info.hdr.hda_fn_nid
+                    && control.direction == pcm_info.direction
+            })
+            .map(|control| control.volume.clone())
+    }
 }
 
 const SUPPORTED_FORMATS: u64 = 1 << VIRTIO_SND_PCM_FMT_U8
@@ -202,19 +311,29 @@ pub struct VirtioSnd {
     avail_features: u64,
     acked_features: u64,
     queue_sizes: Box<[u16]>,
-    worker_thread: Option<WorkerThread<()>>,
+    worker_thread: Option<WorkerThread<Option<Tube>>>,
     keep_rds: Vec<Descriptor>,
+    control_tube: Option<Tube>,
 }
 
 impl VirtioSnd {
-    pub fn new(base_features: u64, params: Parameters) -> Result<VirtioSnd, Error> {
+    /// Creates a sound device. The host can plug and unplug its jacks by sending
+    /// `SndControlCommand`s to `control_tube`.
+    pub fn new(
+        base_features: u64,
+        params: Parameters,
+        control_tube: Option<Tube>,
+    ) -> Result<VirtioSnd, Error> {
         let params = resize_parameters_pcm_device_config(params);
-        let cfg = hardcoded_virtio_snd_config(&params);
         let snd_data = hardcoded_snd_data(&params);
-        let avail_features = base_features;
+        let cfg = hardcoded_virtio_snd_config(&snd_data);
+        let avail_features = base_features | 1 << VIRTIO_SND_F_CTLS;
         let mut keep_rds: Vec<RawDescriptor> = Vec::new();
 
         let stream_info_builders = create_stream_info_builders(&params, &snd_data, &mut keep_rds)?;
+        if let Some(control_tube) = &control_tube {
+            keep_rds.push(control_tube.as_raw_descriptor());
+        }
 
         Ok(VirtioSnd {
             cfg,
@@ -225,6 +344,7 @@ impl VirtioSnd {
             queue_sizes: vec![MAX_VRING_LEN; MAX_QUEUE_NUM].into_boxed_slice(),
             worker_thread: None,
             keep_rds: keep_rds.iter().map(|rd| Descriptor(*rd)).collect(),
+            control_tube,
         })
     }
 }
@@ -261,28 +381,165 @@ pub(crate) fn create_stream_info_builders(
         .zip(snd_data.pcm_info_iter())
         .map(|(generator, pcm_info)| {
             let device_params = params.get_device_params(pcm_info).unwrap_or_default();
-            StreamInfo::builder(generator).effects(device_params.effects.unwrap_or_default())
+            let builder =
+                StreamInfo::builder(generator).effects(device_params.effects.unwrap_or_default());
+            match snd_data.pcm_volume(pcm_info) {
+                Some(volume) => builder.volume(volume),
+                None => builder,
+            }
         })
         .collect())
 }
 
 // To be used with hardcoded_snd_data
-pub fn hardcoded_virtio_snd_config(params: &Parameters) -> virtio_snd_config {
+pub fn hardcoded_virtio_snd_config(snd_data: &SndData) -> virtio_snd_config {
     virtio_snd_config {
-        jacks: 0.into(),
-        streams: params.get_total_streams().into(),
-        chmaps: (params.num_output_devices * 3 + params.num_input_devices).into(),
+        jacks: (snd_data.jack_info.len() as u32).into(),
+        streams: (snd_data.pcm_info.len() as u32).into(),
+        chmaps: (snd_data.chmap_info.len() as u32).into(),
+        controls: (snd_data.controls.len() as u32).into(),
     }
 }
 
+// Positions of the channels of the streams of each supported channel count.
+fn chmap_positions(channels: u8) -> [u8; VIRTIO_SND_CHMAP_MAX_SIZE] {
+    let layout: &[u8] = match channels {
+        1 => &[VIRTIO_SND_CHMAP_MONO],
+        2 => &[VIRTIO_SND_CHMAP_FL, VIRTIO_SND_CHMAP_FR],
+        3 => &[
+            VIRTIO_SND_CHMAP_FL,
+            VIRTIO_SND_CHMAP_FR,
+            VIRTIO_SND_CHMAP_FC,
+        ],
+        4 => &[
+            VIRTIO_SND_CHMAP_FL,
+            VIRTIO_SND_CHMAP_FR,
+            VIRTIO_SND_CHMAP_RL,
+            VIRTIO_SND_CHMAP_RR,
+        ],
+        5 => &[
+            VIRTIO_SND_CHMAP_FL,
+            VIRTIO_SND_CHMAP_FR,
+            VIRTIO_SND_CHMAP_FC,
+            VIRTIO_SND_CHMAP_RL,
+            VIRTIO_SND_CHMAP_RR,
+        ],
+        6 => &[
+            VIRTIO_SND_CHMAP_FL,
+            VIRTIO_SND_CHMAP_FR,
+            VIRTIO_SND_CHMAP_FC,
+            VIRTIO_SND_CHMAP_LFE,
+            VIRTIO_SND_CHMAP_RL,
+            VIRTIO_SND_CHMAP_RR,
+        ],
+        _ => &[],
+    };
+    let mut positions = [VIRTIO_SND_CHMAP_NONE; VIRTIO_SND_CHMAP_MAX_SIZE];
+    positions[..layout.len()].copy_from_slice(layout);
+    positions
+}
+
+fn pcm_device_jack_info(dev: u32, direction: u8, state: JackState) -> virtio_snd_jack_info {
+    let (device, caps) = match direction {
+        VIRTIO_SND_D_OUTPUT => (HDA_DEFCONF_DEVICE_HP_OUT, HDA_PINCAP_OUT),
+        _ => (HDA_DEFCONF_DEVICE_MIC_IN, HDA_PINCAP_IN),
+    };
+    virtio_snd_jack_info {
+        hdr: virtio_snd_info {
+            hda_fn_nid: dev.into(),
+        },
+        features: 0.into(),
+        hda_reg_defconf: ((device << HDA_DEFCONF_DEVICE_SHIFT)
+            | (HDA_DEFCONF_CONN_TYPE_1_8 << HDA_DEFCONF_CONN_TYPE_SHIFT))
+            .into(),
+        hda_reg_caps: (HDA_PINCAP_PRES_DETECT | caps).into(),
+        connected: (state == JackState::Connected) as u8,
+        padding: [0; 7],
+    }
+}
+
+fn ctl_info(
+    dev: u32,
+    role: u32,
+    type_: u32,
+    name: &str,
+    integer: virtio_snd_ctl_info_integer,
+) -> virtio_snd_ctl_info {
+    let mut info = virtio_snd_ctl_info::new_zeroed();
+    info.hdr.hda_fn_nid = dev.into();
+    info.role = role.into();
+    info.type_ = type_.into();
+    info.access = (1 << VIRTIO_SND_CTL_ACCESS_READ | 1 << VIRTIO_SND_CTL_ACCESS_WRITE).into();
+    info.count = 1.into();
+    info.index = dev.into();
+    info.name[..name.len()].copy_from_slice(name.as_bytes());
+    info.integer = integer;
+    info
+}
+
+// Creates the volume and switch controls of a PCM device. Like ALSA switches, the switch is on
+// when the device is not muted.
+fn pcm_device_controls(dev: u32, direction: u8) -> [SndControl; 2] {
+    let prefix = match direction {
+        VIRTIO_SND_D_OUTPUT => "Master Playback",
+        _ => "Capture",
+    };
+    let volume = Arc::new(SoftwareVolume::default());
+    [
+        SndControl {
+            info: ctl_info(
+                dev,
+                VIRTIO_SND_CTL_ROLE_VOLUME,
+                VIRTIO_SND_CTL_TYPE_INTEGER,
+                &format!("{} Volume", prefix),
+                virtio_snd_ctl_info_integer {
+                    min: 0.into(),
+                    max: MAX_VOLUME.into(),
+                    step: 1.into(),
+                },
+            ),
+            direction,
+            volume: volume.clone(),
+        },
+        SndControl {
+            info: ctl_info(
+                dev,
+                VIRTIO_SND_CTL_ROLE_MUTE,
+                VIRTIO_SND_CTL_TYPE_BOOLEAN,
+                &format!("{} Switch", prefix),
+                Default::default(),
+            ),
+            direction,
+            volume,
+        },
+    ]
+}
+
 // To be used with hardcoded_virtio_snd_config
 pub fn hardcoded_snd_data(params: &Parameters) -> SndData {
-    let jack_info: Vec<virtio_snd_jack_info> = Vec::new();
+    let mut jack_info: Vec<virtio_snd_jack_info> = Vec::new();
     let mut pcm_info: Vec<virtio_snd_pcm_info> = Vec::new();
     let mut chmap_info: Vec<virtio_snd_chmap_info> = Vec::new();
+    let mut controls: Vec<SndControl> = Vec::new();
 
-    for dev in 0..params.num_output_devices {
-        for _ in 0..params.num_output_streams {
+    let devices = (0..params.num_output_devices)
+        .map(|dev| (dev, VIRTIO_SND_D_OUTPUT, params.num_output_streams, 6))
+        .chain(
+            (0..params.num_input_devices)
+                .map(|dev| (dev, VIRTIO_SND_D_INPUT, params.num_input_streams, 2)),
+        );
+    for (dev, direction, num_streams, channels_max) in devices {
+        let device_config = match direction {
+            VIRTIO_SND_D_OUTPUT => &params.output_device_config,
+            _ => &params.input_device_config,
+        };
+        if let Some(state) = device_config
+            .get(dev as usize)
+            .and_then(|config| config.jack)
+        {
+            jack_info.push(pcm_device_jack_info(dev, direction, state));
+        }
+        for _ in 0..num_streams {
             pcm_info.push(virtio_snd_pcm_info {
                 hdr: virtio_snd_info {
                     hda_fn_nid: dev.into(),
@@ -290,84 +547,36 @@ pub fn hardcoded_snd_data(params: &Parameters) -> SndData {
                 features: 0.into(), /* 1 << VIRTIO_SND_PCM_F_XXX */
                 formats: SUPPORTED_FORMATS.into(),
                 rates: SUPPORTED_FRAME_RATES.into(),
-                direction: VIRTIO_SND_D_OUTPUT,
+                direction,
                 channels_min: 1,
-                channels_max: 6,
+                channels_max,
                 padding: [0; 5],
             });
         }
-    }
-    for dev in 0..params.num_input_devices {
-        for _ in 0..params.num_input_streams {
-            pcm_info.push(virtio_snd_pcm_info {
+        for channels in 1..=channels_max {
+            chmap_info.push(virtio_snd_chmap_info {
                 hdr: virtio_snd_info {
                     hda_fn_nid: dev.into(),
                 },
-                features: 0.into(), /* 1 << VIRTIO_SND_PCM_F_XXX */
-                formats: SUPPORTED_FORMATS.into(),
-                rates: SUPPORTED_FRAME_RATES.into(),
-                direction: VIRTIO_SND_D_INPUT,
-                channels_min: 1,
-                channels_max: 2,
-                padding: [0; 5],
+                direction,
+                channels,
+                positions: chmap_positions(channels),
             });
         }
+        controls.extend(pcm_device_controls(dev, direction));
     }
-    // Use stereo channel map.
-    let mut positions = [VIRTIO_SND_CHMAP_NONE; VIRTIO_SND_CHMAP_MAX_SIZE];
-    positions[0] = VIRTIO_SND_CHMAP_FL;
-    positions[1] = VIRTIO_SND_CHMAP_FR;
-    for dev in 0..params.num_output_devices {
-        chmap_info.push(virtio_snd_chmap_info {
-            hdr: virtio_snd_info {
-                hda_fn_nid: dev.into(),
-            },
-            direction: VIRTIO_SND_D_OUTPUT,
-            channels: 2,
-            positions,
-        });
-    }
-    for dev in 0..params.num_input_devices {
-        chmap_info.push(virtio_snd_chmap_info {
-            hdr: virtio_snd_info {
-                hda_fn_nid: dev.into(),
-            },
-            direction: VIRTIO_SND_D_INPUT,
-            channels: 2,
-            positions,
-        });
-    }
-    positions[2] = VIRTIO_SND_CHMAP_RL;
-    positions[3] = VIRTIO_SND_CHMAP_RR;
-    for dev in 0..params.num_output_devices {
-        chmap_info.push(virtio_snd_chmap_info {
-            hdr: virtio_snd_info {
-                hda_fn_nid: dev.into(),
-            },
-            direction: VIRTIO_SND_D_OUTPUT,
-            channels: 4,
-            positions,
-        });
-    }
-    positions[2] = VIRTIO_SND_CHMAP_FC;
-    positions[3] = VIRTIO_SND_CHMAP_LFE;
-    positions[4] = VIRTIO_SND_CHMAP_RL;
-    positions[5] = VIRTIO_SND_CHMAP_RR;
-    for dev in 0..params.num_output_devices {
-        chmap_info.push(virtio_snd_chmap_info {
-            hdr: virtio_snd_info {
-                hda_fn_nid: dev.into(),
-            },
-            direction: VIRTIO_SND_D_OUTPUT,
-            channels: 6,
-            positions,
-        });
-    }
+
+    let jack_connected = jack_info
+        .iter()
+        .map(|info| AtomicBool::new(info.connected != 0))
+        .collect();
 
     SndData {
         jack_info,
         pcm_info,
         chmap_info,
+        controls,
+        jack_connected: Arc::new(jack_connected),
     }
 }
 
@@ -441,6 +650,7 @@ impl VirtioDevice for VirtioSnd {
 
         let snd_data = self.snd_data.clone();
         let stream_info_builders = self.stream_info_builders.to_vec();
+        let mut control_tube = self.control_tube.take();
 
         self.worker_thread = Some(WorkerThread::start("v_snd_common", move |kill_evt| {
             let _thread_priority_handle = set_audio_thread_priority();
@@ -454,9 +664,11 @@ impl VirtioDevice for VirtioSnd {
                 snd_data,
                 kill_evt,
                 stream_info_builders,
+                &mut control_tube,
             ) {
                 error!("{}", err_string);
             }
+            control_tube
         }));
 
         Ok(())
@@ -464,7 +676,7 @@ impl VirtioDevice for VirtioSnd {
 
     fn reset(&mut self) -> bool {
         if let Some(worker_thread) = self.worker_thread.take() {
-            worker_thread.stop();
+            self.control_tube = worker_thread.stop();
         }
 
         true
@@ -486,6 +698,7 @@ fn run_worker(
     snd_data: SndData,
     kill_evt: Event,
     stream_info_builders: Vec<StreamInfoBuilder>,
+    control_tube_slot: &mut Option<Tube>,
 ) -> Result<(), String> {
     let ex = Executor::new().expect("Failed to create an executor");
 
@@ -514,7 +727,7 @@ fn run_worker(
         .collect();
 
     let (mut ctrl_queue, mut ctrl_queue_evt) = queues.remove(0);
-    let (_event_queue, _event_queue_evt) = queues.remove(0);
+    let (mut event_queue, mut event_queue_evt) = queues.remove(0);
     let (tx_queue, tx_queue_evt) = queues.remove(0);
     let (rx_queue, rx_queue_evt) = queues.remove(0);
 
@@ -523,6 +736,12 @@ fn run_worker(
 
     let (tx_send, mut tx_recv) = mpsc::unbounded();
     let (rx_send, mut rx_recv) = mpsc::unbounded();
+    let (event_send, event_recv) = mpsc::unbounded();
+    let mut event_recv = event_recv.peekable();
+
+    let control_tube = control_tube_slot
+        .take()
+        .map(|tube| AsyncTube::new(&ex, tube).expect("Failed to create async tube"));
 
     let f_resample = async_utils::handle_irq_resample(&ex, interrupt.clone()).fuse();
 
@@ -542,6 +761,11 @@ fn run_worker(
             &mut f_resample,
             &mut ctrl_queue,
             &mut ctrl_queue_evt,
+            &mut event_queue,
+            &mut event_queue_evt,
+            &event_send,
+            &mut event_recv,
+            &control_tube,
             &tx_queue,
             &tx_queue_evt,
             tx_send.clone(),
@@ -570,6 +794,7 @@ fn run_worker(
         }
     }
 
+    *control_tube_slot = control_tube.map(Tube::from);
     Ok(())
 }
 
@@ -596,6 +821,11 @@ fn run_worker_once(
     mut f_resample: &mut (impl Future<Output = anyhow::Result<()>> + FusedFuture + Unpin),
     ctrl_queue: &mut Queue,
     ctrl_queue_evt: &mut EventAsync,
+    event_queue: &mut Queue,
+    event_queue_evt: &mut EventAsync,
+    event_send: &mpsc::UnboundedSender<virtio_snd_event>,
+    event_recv: &mut Peekable<mpsc::UnboundedReceiver<virtio_snd_event>>,
+    control_tube: &Option<AsyncTube>,
     tx_queue: &Rc<AsyncMutex<Queue>>,
     tx_queue_evt: &EventAsync,
     tx_send: mpsc::UnboundedSender<PcmResponse>,
@@ -624,14 +854,17 @@ fn run_worker_once(
     )
     .fuse();
 
-    // TODO(woodychow): Enable this when libcras sends jack connect/disconnect evts
-    // let f_event = handle_event_queue(
-    //     &mem,
-    //     snd_state,
-    //     event_queue,
-    //     event_queue_evt,
-    //     interrupt,
-    // );
+    let f_event = handle_event_queue(
+        mem,
+        event_queue,
+        event_queue_evt,
+        interrupt.clone(),
+        event_recv,
+        Some(&reset_signal),
+    )
+    .fuse();
+    let f_control =
+        handle_control_tube(control_tube, snd_data, event_send, Some(&reset_signal)).fuse();
     let f_tx = handle_pcm_queue(
         mem,
         streams,
@@ -661,11 +894,21 @@ fn run_worker_once(
     let f_rx_response =
         send_pcm_response_worker(mem, rx_queue, interrupt, rx_recv, Some(&reset_signal)).fuse();
 
-    pin_mut!(f_ctrl, f_tx, f_tx_response, f_rx, f_rx_response);
+    pin_mut!(
+        f_ctrl,
+        f_event,
+        f_control,
+        f_tx,
+        f_tx_response,
+        f_rx,
+        f_rx_response
+    );
 
     let done = async {
         select! {
             res = f_ctrl => (res.context("error in handling ctrl queue"), LoopState::Continue),
+            res = f_event => (res.context("error in handling event queue"), LoopState::Continue),
+            res = f_control => (res.context("error in handling control tube"), LoopState::Continue),
             res = f_tx => (res.context("error in handling tx queue"), LoopState::Continue),
             res = f_tx_response => (res.context("error in handling tx response"), LoopState::Continue),
             res = f_rx => (res.context("error in handling rx queue"), LoopState::Continue),
@@ -698,6 +941,8 @@ fn run_worker_once(
         loop {
             let (res, worker_name) = select!(
                 res = f_ctrl => (res, "f_ctrl"),
+                res = f_event => (res, "f_event"),
+                res = f_control => (res, "f_control"),
                 res = f_tx => (res, "f_tx"),
                 res = f_tx_response => (res, "f_tx_response"),
                 res = f_rx => (res, "f_rx"),
@@ -813,17 +1058,19 @@ mod tests {
             ..Default::default()
         };
 
-        let res = VirtioSnd::new(123, params).unwrap();
+        let res = VirtioSnd::new(123, params, None).unwrap();
 
         // Default values
         assert_eq!(res.snd_data.jack_info.len(), 0);
         assert_eq!(res.acked_features, 0);
         assert_eq!(res.worker_thread.is_none(), true);
 
-        assert_eq!(res.avail_features, 123); // avail_features must be equal to the input
+        // avail_features must be the input plus the control elements feature
+        assert_eq!(res.avail_features, 123 | 1 << VIRTIO_SND_F_CTLS);
         assert_eq!(res.cfg.jacks.to_native(), 0);
         assert_eq!(res.cfg.streams.to_native(), 13); // (Output = 3*3) + (Input = 2*2)
-        assert_eq!(res.cfg.chmaps.to_native(), 11); // (Output = 3*3) + (Input = 2*1)
+        assert_eq!(res.cfg.chmaps.to_native(), 22); // (Output = 3*6) + (Input = 2*2)
+        assert_eq!(res.cfg.controls.to_native(), 10); // (Output = 3*2) + (Input = 2*2)
 
         // Check snd_data.pcm_info
         assert_eq!(res.snd_data.pcm_info.len(), 13);
@@ -855,8 +1102,10 @@ mod tests {
         }
 
         // Check snd_data.chmap_info
-        assert_eq!(res.snd_data.chmap_info.len(), 11);
-        let expected_hda_fn_nid = vec![0, 1, 2, 0, 1, 0, 1, 2, 0, 1, 2];
+        assert_eq!(res.snd_data.chmap_info.len(), 22);
+        let expected_hda_fn_nid = vec![
+            0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 1, 1, 2, 2, 2, 2, 2, 2, 0, 0, 1, 1,
+        ];
         // Check hda_fn_nid (PCM Device number)
         for (i, chmap_info) in res.snd_data.chmap_info.iter().enumerate() {
             assert_eq!(
@@ -868,6 +1117,69 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_jacks() {
+        let params = Parameters {
+            num_output_devices: 2,
+            num_input_devices: 1,
+            output_device_config: vec![
+                PCMDeviceParameters::default(),
+                PCMDeviceParameters {
+                    jack: Some(JackState::Connected),
+                    ..PCMDeviceParameters::default()
+                },
+            ],
+            input_device_config: vec![PCMDeviceParameters {
+                jack: Some(JackState::Disconnected),
+                ..PCMDeviceParameters::default()
+            }],
+            ..Default::default()
+        };
+        let snd_data = hardcoded_snd_data(&resize_parameters_pcm_device_config(params));
+
+        assert_eq!(hardcoded_virtio_snd_config(&snd_data).jacks.to_native(), 2);
+        assert_eq!(snd_data.jack_info(0).hdr.hda_fn_nid.to_native(), 1);
+        assert_eq!(snd_data.jack_info(0).connected, 1);
+        assert_eq!(snd_data.jack_info(1).hdr.hda_fn_nid.to_native(), 0);
+        assert_eq!(snd_data.jack_info(1).connected, 0);
+
+        // Setting the current state does not notify the driver.
+        assert!(snd_data.set_jack_connected(0, true).unwrap().is_none());
+        let event = snd_data.set_jack_connected(0, false).unwrap().unwrap();
+        assert_eq!(event.hdr.code.to_native(), VIRTIO_SND_EVT_JACK_DISCONNECTED);
+        assert_eq!(event.data.to_native(), 0);
+        assert_eq!(snd_data.clone().jack_info(0).connected, 0);
+
+        assert!(snd_data.set_jack_connected(2, true).is_err());
+    }
+
+    #[test]
+    fn test_controls() {
+        let params = Parameters {
+            num_output_devices: 1,
+            num_input_devices: 1,
+            ..Default::default()
+        };
+        let snd_data = hardcoded_snd_data(&params);
+
+        let volume = snd_data.pcm_volume(&snd_data.pcm_info[0]).unwrap();
+        let output_controls = &snd_data.controls[..2];
+        assert_eq!(output_controls[0].value(), MAX_VOLUME);
+        assert_eq!(output_controls[1].value(), 1);
+
+        assert!(output_controls[0].set_value(20));
+        assert!(!output_controls[0].set_value(MAX_VOLUME + 1));
+        assert_eq!(volume.volume(), 20);
+        assert!(output_controls[1].set_value(0));
+        assert!(!output_controls[1].set_value(2));
+        assert!(volume.muted());
+
+        // Input controls are independent from output ones.
+        let input_volume = snd_data.pcm_volume(&snd_data.pcm_info[1]).unwrap();
+        assert_eq!(input_volume.volume(), MAX_VOLUME);
+        assert!(!input_volume.muted());
+    }
+
     #[test]
     fn test_resize_parameters_pcm_device_config_truncate() {
         // If pcm_device_config is larger than number of devices, it will be truncated
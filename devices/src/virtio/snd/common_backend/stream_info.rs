@@ -8,6 +8,7 @@ use std::sync::Arc;
 
 use audio_streams::SampleFormat;
 use audio_streams::StreamEffect;
+use audio_util::SoftwareVolume;
 use base::error;
 use cros_async::sync::Mutex as AsyncMutex;
 use cros_async::Executor;
@@ -21,6 +22,7 @@ use super::WorkerStatus;
 use crate::virtio::snd::common::*;
 use crate::virtio::snd::common_backend::async_funcs::*;
 use crate::virtio::snd::common_backend::DirectionalStream;
+use crate::virtio::snd::common_backend::StreamVolume;
 use crate::virtio::snd::common_backend::SysAsyncStreamObjects;
 use crate::virtio::snd::common_backend::SysBufferWriter;
 use crate::virtio::snd::constants::*;
@@ -47,6 +49,7 @@ pub struct SetParams {
 pub struct StreamInfoBuilder {
     stream_source_generator: Arc<SysAudioStreamSourceGenerator>,
     effects: Vec<StreamEffect>,
+    volume: Arc<SoftwareVolume>,
 }
 
 impl StreamInfoBuilder {
@@ -57,6 +60,7 @@ impl StreamInfoBuilder {
         StreamInfoBuilder {
             stream_source_generator,
             effects: vec![],
+            volume: Default::default(),
         }
     }
 
@@ -67,6 +71,14 @@ impl StreamInfoBuilder {
         self
     }
 
+    /// Set the [`SoftwareVolume`] applied to the samples of the stream, which is usually shared
+    /// with the control elements of its PCM device. The default value is a full volume that is
+    /// not shared.
+    pub fn volume(mut self, volume: Arc<SoftwareVolume>) -> Self {
+        self.volume = volume;
+        self
+    }
+
     /// Builds a [`StreamInfo`].
     pub fn build(self) -> StreamInfo {
         self.into()
@@ -88,6 +100,8 @@ pub struct StreamInfo {
     pub state: u32, // VIRTIO_SND_R_PCM_SET_PARAMS -> VIRTIO_SND_R_PCM_STOP, or 0 (uninitialized)
     // Stream effects to use when creating a new stream on [`prepare()`].
     effects: Vec<StreamEffect>,
+    // Volume applied to the samples of the stream.
+    volume: Arc<SoftwareVolume>,
 
     // just_reset set to true after reset. Make invalid state transition return Ok. Set to false
     // after a valid state transition to SET_PARAMS or PREPARE.
@@ -149,6 +163,7 @@ impl From<StreamInfoBuilder> for StreamInfo {
             direction: 0,
             state: 0,
             effects: builder.effects,
+            volume: builder.volume,
             just_reset: false,
             status_mutex: Rc::new(AsyncMutex::new(WorkerStatus::Pause)),
             sender: None,
@@ -239,6 +254,10 @@ impl StreamInfo {
                     .map_err(Error::GenerateStreamSource)?,
             );
         }
+        let volume = StreamVolume {
+            volume: self.volume.clone(),
+            format: self.format,
+        };
         let SysAsyncStreamObjects { stream, pcm_sender } = match self.direction {
             VIRTIO_SND_D_OUTPUT => {
                 let sys_async_stream = self.set_up_async_playback_stream(frame_size, ex).await?;
@@ -258,6 +277,7 @@ impl StreamInfo {
                     stream: DirectionalStream::Output(
                         sys_async_stream.async_playback_buffer_stream,
                         Box::new(buffer_writer),
+                        volume,
                     ),
                     pcm_sender: tx_send.clone(),
                 }
@@ -279,7 +299,7 @@ impl StreamInfo {
                     .map_err(Error::CreateStream)?
                     .1;
                 SysAsyncStreamObjects {
-                    stream: DirectionalStream::Input(async_stream, self.period_bytes),
+                    stream: DirectionalStream::Input(async_stream, self.period_bytes, volume),
                     pcm_sender: rx_send.clone(),
                 }
             }
@@ -689,5 +709,17 @@ mod tests {
 
         let stream = builder.build();
         assert_eq!(stream.effects, vec![StreamEffect::EchoCancellation]);
+        assert_eq!(stream.volume.volume(), audio_util::MAX_VOLUME);
+    }
+
+    #[test]
+    fn test_stream_info_builder_volume() {
+        let volume = Arc::new(SoftwareVolume::default());
+        let stream = StreamInfo::builder(Arc::new(Box::new(NoopStreamSourceGenerator::new())))
+            .volume(volume.clone())
+            .build();
+
+        volume.set_volume(10);
+        assert_eq!(stream.volume.volume(), 10);
     }
 }
@@ -11,7 +11,9 @@ use std::io::Write;
 use std::path::Path;
 
 use audio_streams::NoopStreamSourceGenerator;
+use audio_util::FileCaptureStreamSourceGenerator;
 use audio_util::FileStreamSourceGenerator;
+use audio_util::WAV_HEADER_LEN;
 use base::error;
 use base::open_file;
 use base::AsRawDescriptor;
@@ -27,6 +29,8 @@ use crate::virtio::snd::sys::SysAudioStreamSourceGenerator;
 pub enum Error {
     #[error("Failed to allocate space: {0}")]
     AllocateSpace(IOError),
+    #[error("Failed to create capture stream source: {0}")]
+    CreateCaptureSource(audio_util::Error),
     #[error("Failed to open file: {0}")]
     OpenFile(base::Error),
 }
@@ -51,6 +55,13 @@ fn open_playback_file(dir_path: &String, stream_id: usize) -> Result<File, Error
     Ok(file)
 }
 
+fn open_capture_file(dir_path: &String, stream_id: usize) -> Result<File, Error> {
+    let file_name = format!("stream-{}.wav", stream_id);
+    let file_path = Path::new(dir_path).join(file_name);
+    let file = open_file(file_path, OpenOptions::new().read(true)).map_err(Error::OpenFile)?;
+    Ok(file)
+}
+
 pub(crate) fn create_file_stream_source_generators(
     params: &Parameters,
     snd_data: &SndData,
@@ -61,13 +72,22 @@ pub(crate) fn create_file_stream_source_generators(
     for (stream, pcm_info) in snd_data.pcm_info.iter().enumerate() {
         let generator: SysAudioStreamSourceGenerator = if pcm_info.direction == VIRTIO_SND_D_OUTPUT
         {
+            // The samples are preceded by a WAV header.
+            let file_size = params.playback_size + WAV_HEADER_LEN;
             let file = open_playback_file(&params.playback_path, stream)?;
-            allocate_space(&file, params.playback_size)?;
+            allocate_space(&file, file_size)?;
+            keep_rds.push(file.as_raw_descriptor());
+
+            Box::new(FileStreamSourceGenerator::new(file, file_size))
+        } else if !params.capture_path.is_empty() {
+            let file = open_capture_file(&params.capture_path, stream)?;
             keep_rds.push(file.as_raw_descriptor());
 
-            Box::new(FileStreamSourceGenerator::new(file, params.playback_size))
+            Box::new(
+                FileCaptureStreamSourceGenerator::new(file, params.capture_loop)
+                    .map_err(Error::CreateCaptureSource)?,
+            )
         } else {
-            // Capture is not supported yet
             Box::new(NoopStreamSourceGenerator::new())
         };
 
@@ -9,6 +9,8 @@ use zerocopy::FromBytes;
 
 use crate::virtio::snd::constants::StatusCode;
 use crate::virtio::snd::constants::VIRTIO_SND_CHMAP_MAX_SIZE;
+use crate::virtio::snd::constants::VIRTIO_SND_CTL_NAME_MAX;
+use crate::virtio::snd::constants::VIRTIO_SND_CTL_VALUE_MAX;
 
 #[derive(Copy, Clone, Default, AsBytes, FromBytes)]
 #[repr(C)]
@@ -128,3 +130,41 @@ pub struct virtio_snd_chmap_info {
     pub channels: u8,
     pub positions: [u8; VIRTIO_SND_CHMAP_MAX_SIZE],
 }
+
+#[derive(Copy, Clone, Default, AsBytes, FromBytes)]
+#[repr(C)]
+pub struct virtio_snd_ctl_hdr {
+    pub hdr: virtio_snd_hdr,
+    pub control_id: Le32,
+}
+
+#[derive(Copy, Clone, Default, AsBytes, FromBytes)]
+#[repr(C)]
+pub struct virtio_snd_ctl_info_integer {
+    pub min: Le32,
+    pub max: Le32,
+    pub step: Le32,
+}
+
+#[derive(Copy, Clone, AsBytes, FromBytes)]
+#[repr(C)]
+pub struct virtio_snd_ctl_info {
+    pub hdr: virtio_snd_info,
+    pub role: Le32,   /* VIRTIO_SND_CTL_ROLE_XXX */
+    pub type_: Le32,  /* VIRTIO_SND_CTL_TYPE_XXX */
+    pub access: Le32, /* 1 << VIRTIO_SND_CTL_ACCESS_XXX */
+    pub count: Le32,
+    pub index: Le32,
+    pub name: [u8; VIRTIO_SND_CTL_NAME_MAX],
+    pub padding: [u8; 4],
+    // Only the integer member of the value union is used, the 64 bit ones make it 24 bytes long.
+    pub integer: virtio_snd_ctl_info_integer,
+    pub padding_value: [u8; 12],
+}
+
+#[derive(Copy, Clone, AsBytes, FromBytes)]
+#[repr(C)]
+pub struct virtio_snd_ctl_value {
+    // The value union is only accessed as an array of 32 bit integers.
+    pub integer: [Le32; VIRTIO_SND_CTL_VALUE_MAX],
+}
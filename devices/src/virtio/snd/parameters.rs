@@ -77,6 +77,14 @@ impl TryFrom<&str> for StreamSourceBackend {
     }
 }
 
+/// Initial state of the jack of a PCM device.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum JackState {
+    Connected,
+    Disconnected,
+}
+
 /// Holds the parameters for each PCM device
 #[derive(Debug, Clone, Default, Deserialize, Serialize, FromKeyValues, PartialEq, Eq)]
 #[serde(deny_unknown_fields, default)]
@@ -86,6 +94,9 @@ pub struct PCMDeviceParameters {
     #[cfg(all(unix, feature = "audio_cras"))]
     pub stream_type: Option<CrasStreamType>,
     pub effects: Option<Vec<StreamEffect>>,
+    /// Exposes a jack for the device, a headphone for output devices and a microphone for input
+    /// devices, that can be plugged and unplugged by the host.
+    pub jack: Option<JackState>,
 }
 
 /// Holds the parameters for a cras sound device
@@ -100,6 +111,8 @@ pub struct Parameters {
     pub num_input_streams: u32,
     pub playback_path: String,
     pub playback_size: usize,
+    pub capture_path: String,
+    pub capture_loop: bool,
     #[cfg(all(unix, feature = "audio_cras"))]
     #[serde(deserialize_with = "libcras::deserialize_cras_client_type")]
     pub client_type: CrasClientType,
@@ -120,6 +133,8 @@ impl Default for Parameters {
             num_input_streams: 1,
             playback_path: "".to_string(),
             playback_size: 0,
+            capture_path: "".to_string(),
+            capture_loop: false,
             #[cfg(all(unix, feature = "audio_cras"))]
             client_type: CrasClientType::CRAS_CLIENT_TYPE_CROSVM,
             #[cfg(all(unix, feature = "audio_cras"))]
@@ -288,8 +303,47 @@ mod tests {
             ],
         );
 
+        check_success(
+            "output_device_config=[[jack=connected]],input_device_config=[[jack=disconnected]]",
+            false,
+            StreamSourceBackend::NULL,
+            1,
+            1,
+            1,
+            1,
+            vec![PCMDeviceParameters {
+                jack: Some(JackState::Connected),
+                ..Default::default()
+            }],
+            vec![PCMDeviceParameters {
+                jack: Some(JackState::Disconnected),
+                ..Default::default()
+            }],
+        );
+
         // Invalid effect in device config
         check_failure("output_device_config=[[effects=[none]]]");
+        // Invalid jack state in device config
+        check_failure("output_device_config=[[jack=unplugged]]");
+    }
+
+    #[test]
+    fn file_parameters_fromstr() {
+        let params: Parameters = serde_keyvalue::from_key_values(
+            "backend=file,playback_path=/tmp/out,playback_size=1024,\
+            capture_path=/tmp/in,capture_loop=true",
+        )
+        .expect("parse should have succeded");
+        assert_eq!(params.backend, StreamSourceBackend::FILE);
+        assert_eq!(params.playback_path, "/tmp/out");
+        assert_eq!(params.playback_size, 1024);
+        assert_eq!(params.capture_path, "/tmp/in");
+        assert!(params.capture_loop);
+
+        let params: Parameters =
+            serde_keyvalue::from_key_values("backend=file").expect("parse should have succeded");
+        assert_eq!(params.capture_path, "");
+        assert!(!params.capture_loop);
     }
 
     #[test]
@@ -363,11 +417,13 @@ mod tests {
                     client_type: Some(CrasClientType::CRAS_CLIENT_TYPE_CROSVM),
                     stream_type: None,
                     effects: None,
+                    jack: None,
                 },
                 PCMDeviceParameters{
                     client_type: Some(CrasClientType::CRAS_CLIENT_TYPE_ARCVM),
                     stream_type: Some(CrasStreamType::CRAS_STREAM_TYPE_PRO_AUDIO),
                     effects: None,
+                    jack: None,
                 },
                 Default::default(),
                 ],
@@ -384,16 +440,19 @@ mod tests {
                     client_type: Some(CrasClientType::CRAS_CLIENT_TYPE_CROSVM),
                     stream_type: None,
                     effects: None,
+                    jack: None,
                 },
                 PCMDeviceParameters{
                     client_type: Some(CrasClientType::CRAS_CLIENT_TYPE_ARCVM),
                     stream_type: Some(CrasStreamType::CRAS_STREAM_TYPE_PRO_AUDIO),
                     effects: Some(vec![StreamEffect::EchoCancellation]),
+                    jack: None,
                 },
                 PCMDeviceParameters{
                     client_type: None,
                     stream_type: None,
                     effects: Some(vec![StreamEffect::EchoCancellation]),
+                    jack: None,
                 },
                 Default::default(),
                 ],
@@ -4,8 +4,12 @@
 
 use async_trait::async_trait;
 use audio_streams::AsyncPlaybackBufferStream;
+#[cfg(feature = "audio_pulse")]
+use audio_streams::NoopStreamSourceGenerator;
 use audio_streams::StreamSource;
 use audio_streams::StreamSourceGenerator;
+#[cfg(feature = "audio_pulse")]
+use audio_util::pulse::PulseStreamSourceGenerator;
 #[cfg(feature = "audio_cras")]
 use base::error;
 use base::set_rt_prio_limit;
@@ -25,9 +29,14 @@ use crate::virtio::snd::common_backend::stream_info::StreamInfo;
 use crate::virtio::snd::common_backend::DirectionalStream;
 use crate::virtio::snd::common_backend::Error;
 use crate::virtio::snd::common_backend::SndData;
+#[cfg(feature = "audio_pulse")]
+use crate::virtio::snd::constants::VIRTIO_SND_D_INPUT;
 use crate::virtio::snd::parameters::Error as ParametersError;
 use crate::virtio::snd::parameters::Parameters;
 
+#[cfg(feature = "audio_pulse")]
+pub use audio_util::pulse::server_socket_path as pulse_server_socket_path;
+
 const AUDIO_THREAD_RTPRIO: u16 = 10; // Matches other cros audio clients.
 
 pub(crate) type SysAudioStreamSourceGenerator = Box<dyn StreamSourceGenerator>;
@@ -47,6 +56,8 @@ pub(crate) struct SysAsyncStreamObjects {
 pub enum StreamSourceBackend {
     #[cfg(feature = "audio_cras")]
     CRAS,
+    #[cfg(feature = "audio_pulse")]
+    PULSE,
 }
 
 // Implemented to make backend serialization possible, since we deserialize from str.
@@ -55,6 +66,8 @@ impl From<StreamSourceBackend> for String {
         match backend {
             #[cfg(feature = "audio_cras")]
             StreamSourceBackend::CRAS => "cras".to_owned(),
+            #[cfg(feature = "audio_pulse")]
+            StreamSourceBackend::PULSE => "pulse".to_owned(),
         }
     }
 }
@@ -66,6 +79,8 @@ impl TryFrom<&str> for StreamSourceBackend {
         match s {
             #[cfg(feature = "audio_cras")]
             "cras" => Ok(StreamSourceBackend::CRAS),
+            #[cfg(feature = "audio_pulse")]
+            "pulse" => Ok(StreamSourceBackend::PULSE),
             _ => Err(ParametersError::InvalidBackend),
         }
     }
@@ -95,6 +110,27 @@ pub(crate) fn create_cras_stream_source_generators(
     generators
 }
 
+#[cfg(feature = "audio_pulse")]
+pub(crate) fn create_pulse_stream_source_generators(
+    params: &Parameters,
+    snd_data: &SndData,
+) -> Vec<Box<dyn StreamSourceGenerator>> {
+    snd_data
+        .pcm_info_iter()
+        .enumerate()
+        .map(|(stream_id, pcm_info)| -> Box<dyn StreamSourceGenerator> {
+            if pcm_info.direction == VIRTIO_SND_D_INPUT && !params.capture {
+                Box::new(NoopStreamSourceGenerator::new())
+            } else {
+                Box::new(PulseStreamSourceGenerator::new(&format!(
+                    "virtio-snd stream {}",
+                    stream_id
+                )))
+            }
+        })
+        .collect()
+}
+
 #[allow(unused_variables)]
 pub(crate) fn create_stream_source_generators(
     backend: StreamSourceBackend,
@@ -104,6 +140,8 @@ pub(crate) fn create_stream_source_generators(
     match backend {
         #[cfg(feature = "audio_cras")]
         StreamSourceBackend::CRAS => create_cras_stream_source_generators(params, snd_data),
+        #[cfg(feature = "audio_pulse")]
+        StreamSourceBackend::PULSE => create_pulse_stream_source_generators(params, snd_data),
     }
 }
 
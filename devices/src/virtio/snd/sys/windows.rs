@@ -33,6 +33,7 @@ use crate::virtio::snd::common_backend::DirectionalStream;
 use crate::virtio::snd::common_backend::Error;
 use crate::virtio::snd::common_backend::PcmResponse;
 use crate::virtio::snd::common_backend::SndData;
+use crate::virtio::snd::common_backend::StreamVolume;
 use crate::virtio::snd::constants::StatusCode;
 use crate::virtio::snd::layout::virtio_snd_pcm_status;
 use crate::virtio::snd::parameters::Error as ParametersError;
@@ -139,14 +140,20 @@ impl WinBufferWriter {
                 * self.guest_num_channels
     }
 
-    fn write_to_resampler_buffer(&mut self, reader: &mut Reader) -> Result<usize, Error> {
+    fn write_to_resampler_buffer(
+        &mut self,
+        reader: &mut Reader,
+        volume: &StreamVolume,
+    ) -> Result<usize, Error> {
         let written = reader.read_to_cb(
             |iovs| {
                 let mut written = 0;
                 for iov in iovs {
                     let buffer_slice = unsafe { slice::from_raw_parts(iov.as_ptr(), iov.size()) };
-                    self.intermediate_resampler_buffer
-                        .convert_and_add(buffer_slice);
+                    // The guest memory must not be modified, so the volume is applied to a copy.
+                    let mut samples = buffer_slice.to_vec();
+                    volume.apply(&mut samples);
+                    self.intermediate_resampler_buffer.convert_and_add(&samples);
                     written += iov.size();
                 }
                 written
@@ -201,8 +208,9 @@ impl PlaybackBufferWriter for WinBufferWriter {
         &mut self,
         dst_buf: &mut AsyncPlaybackBuffer<'_>,
         reader: &mut Reader,
+        volume: &StreamVolume,
     ) -> Result<usize, Error> {
-        self.write_to_resampler_buffer(reader)?;
+        self.write_to_resampler_buffer(reader, volume)?;
 
         if let Some(next_period) = self.intermediate_resampler_buffer.get_next_period() {
             dst_buf
@@ -220,6 +228,7 @@ impl PlaybackBufferWriter for WinBufferWriter {
         &mut self,
         desc_receiver: &mut UnboundedReceiver<DescriptorChain>,
         sender: &mut UnboundedSender<PcmResponse>,
+        volume: &StreamVolume,
     ) -> Result<(), Error> {
         if !self.needs_prefill() {
             return Ok(());
@@ -237,7 +246,7 @@ impl PlaybackBufferWriter for WinBufferWriter {
                 return Err(Error::InvalidPCMWorkerState);
             }
             Ok(Some(mut desc_chain)) => {
-                self.write_to_resampler_buffer(&mut desc_chain.reader)?;
+                self.write_to_resampler_buffer(&mut desc_chain.reader, volume)?;
 
                 sender
                     .send(PcmResponse {
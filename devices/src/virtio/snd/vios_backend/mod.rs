@@ -188,6 +188,8 @@ pub fn new_sound<P: AsRef<Path>>(path: P, virtio_features: u64) -> Result<Sound>
             jacks: Le32::from(vios_client.num_jacks()),
             streams: Le32::from(vios_client.num_streams()),
             chmaps: Le32::from(vios_client.num_chmaps()),
+            // VIRTIO_SND_F_CTLS is not offered, so the field is ignored by the driver.
+            controls: Le32::from(0),
         },
         virtio_features,
         worker_thread: None,
@@ -38,13 +38,14 @@ use crate::virtio::device_constants::fs::FS_MAX_TAG_LEN;
 use crate::virtio::fs::passthrough::Config;
 use crate::virtio::fs::passthrough::PassthroughFs;
 use crate::virtio::fs::process_fs_queue;
+use crate::virtio::fs::BlockingRequests;
 use crate::virtio::vhost::user::device::handler::sys::Doorbell;
 use crate::virtio::vhost::user::device::handler::VhostUserBackend;
 
 const MAX_QUEUE_NUM: usize = 2; /* worker queue and high priority queue */
 
 async fn handle_fs_queue(
-    mut queue: virtio::Queue,
+    queue: virtio::Queue,
     mem: GuestMemory,
     doorbell: Doorbell,
     kick_evt: EventAsync,
@@ -53,13 +54,24 @@ async fn handle_fs_queue(
 ) {
     // Slot is always going to be 0 because we do not support DAX
     let slot: u32 = 0;
+    let queue = Arc::new(Mutex::new(queue));
+    // Dropped, and so stopped, when the handler is aborted.
+    let mut blocking_requests = BlockingRequests::default();
 
     loop {
         if let Err(e) = kick_evt.next_val().await {
             error!("Failed to read kick event for fs queue: {}", e);
             break;
         }
-        if let Err(e) = process_fs_queue(&mem, &doorbell, &mut queue, &server, &tube, slot) {
+        if let Err(e) = process_fs_queue(
+            &mem,
+            &doorbell,
+            &queue,
+            &mut blocking_requests,
+            &server,
+            &tube,
+            slot,
+        ) {
             error!("Process FS queue failed: {}", e);
             break;
         }
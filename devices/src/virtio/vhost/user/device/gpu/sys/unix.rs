@@ -232,6 +232,17 @@ pub fn run_gpu_device(opts: Options) -> anyhow::Result<()> {
     if let Some(p) = wayland_paths.get("") {
         display_backends.insert(0, virtio::DisplayBackend::Wayland(Some(p.to_owned())));
     }
+    if let Some(port) = gpu_parameters.vnc_port {
+        let backend = virtio::DisplayBackend::vnc(port, gpu_parameters.display_params.len())
+            .with_context(|| format!("failed to bind VNC server to port {}", port))?;
+        display_backends.insert(0, backend);
+    }
+    if let Some(path) = &gpu_parameters.headless_socket {
+        let backend = virtio::DisplayBackend::headless(path).with_context(|| {
+            format!("failed to bind headless display socket {}", path.display())
+        })?;
+        display_backends.insert(0, backend);
+    }
 
     // These are only used when there is an input device.
     let event_devices = Vec::new();
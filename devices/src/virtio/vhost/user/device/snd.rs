@@ -41,6 +41,7 @@ use crate::virtio::snd::common_backend::stream_info::StreamInfoBuilder;
 use crate::virtio::snd::common_backend::PcmResponse;
 use crate::virtio::snd::common_backend::SndData;
 use crate::virtio::snd::common_backend::MAX_QUEUE_NUM;
+use crate::virtio::snd::constants::VIRTIO_SND_F_CTLS;
 use crate::virtio::snd::parameters::Parameters;
 use crate::virtio::vhost::user::device::handler::sys::Doorbell;
 use crate::virtio::vhost::user::device::handler::DeviceRequestHandler;
@@ -72,11 +73,12 @@ struct SndBackend {
 
 impl SndBackend {
     pub fn new(params: Parameters) -> anyhow::Result<Self> {
-        let cfg = hardcoded_virtio_snd_config(&params);
+        let snd_data = hardcoded_snd_data(&params);
+        let cfg = hardcoded_virtio_snd_config(&snd_data);
         let avail_features = virtio::base_features(ProtectionType::Unprotected)
+            | 1 << VIRTIO_SND_F_CTLS
             | VhostUserVirtioFeatures::PROTOCOL_FEATURES.bits();
 
-        let snd_data = hardcoded_snd_data(&params);
         let mut keep_rds = Vec::new();
         let builders = create_stream_info_builders(&params, &snd_data, &mut keep_rds)?;
 
@@ -326,13 +326,19 @@ impl VaapiDecoder {
                 bitrates: Default::default(),
             };
 
+            // Main Still Picture streams are decoded with the Main profile.
+            let mut new_profiles = vec![profile];
+            if profile == Profile::HevcMain {
+                new_profiles.push(Profile::HevcMainStillPicture);
+            }
+
             let coded_format = profile.to_format();
             match profiles_map.entry(coded_format) {
                 Entry::Vacant(e) => {
-                    e.insert(vec![profile]);
+                    e.insert(new_profiles);
                 }
                 Entry::Occupied(mut ps) => {
-                    ps.get_mut().push(profile);
+                    ps.get_mut().extend(new_profiles);
                 }
             }
 
@@ -1038,6 +1044,13 @@ impl DecoderBackend for VaapiDecoder {
                 )
                 .map_err(|e| VideoError::BackendFailure(anyhow!(e)))?,
             ),
+            Format::Hevc => Box::new(
+                cros_codecs::decoders::h265::decoder::Decoder::new_vaapi(
+                    display,
+                    cros_codecs::decoders::BlockingMode::NonBlocking,
+                )
+                .map_err(|e| VideoError::BackendFailure(anyhow!(e)))?,
+            ),
             _ => return Err(VideoError::InvalidFormat),
         };
 
@@ -1058,6 +1071,20 @@ mod tests {
     use super::super::tests::*;
     use super::*;
 
+    #[test]
+    fn test_va_profile_conversion() {
+        assert_eq!(
+            Profile::try_from(libva::VAProfile::VAProfileHEVCMain).unwrap(),
+            Profile::HevcMain
+        );
+        assert_eq!(
+            Profile::try_from(libva::VAProfile::VAProfileHEVCMain10).unwrap(),
+            Profile::HevcMain10
+        );
+        assert!(Profile::try_from(libva::VAProfile::VAProfileAV1Profile0).is_err());
+        assert!(Profile::try_from(libva::VAProfile::VAProfileJPEGBaseline).is_err());
+    }
+
     #[test]
     // Ignore this test by default as it requires libva-compatible hardware.
     #[ignore]
@@ -22,11 +22,18 @@ use ffmpeg::avcodec::AvCodecIterator;
 use ffmpeg::avcodec::AvFrame;
 use ffmpeg::avcodec::AvPacket;
 use ffmpeg::avcodec::Dimensions;
+use ffmpeg::avcodec::EncoderContextBuilder;
 use ffmpeg::avcodec::TryReceiveResult;
 use ffmpeg::max_buffer_alignment;
 use ffmpeg::AVPictureType_AV_PICTURE_TYPE_I;
 use ffmpeg::AVRational;
 use ffmpeg::AV_PKT_FLAG_KEY;
+use ffmpeg::FF_PROFILE_H264_BASELINE;
+use ffmpeg::FF_PROFILE_H264_HIGH;
+use ffmpeg::FF_PROFILE_H264_MAIN;
+use ffmpeg::FF_PROFILE_HEVC_MAIN;
+use ffmpeg::FF_PROFILE_HEVC_MAIN_STILL_PICTURE;
+use ffmpeg::FF_PROFILE_VP9_0;
 
 use crate::virtio::video::encoder::backend::Encoder;
 use crate::virtio::video::encoder::backend::EncoderSession;
@@ -43,6 +50,7 @@ use crate::virtio::video::format::Format;
 use crate::virtio::video::format::FormatDesc;
 use crate::virtio::video::format::FormatRange;
 use crate::virtio::video::format::FrameFormat;
+use crate::virtio::video::format::Level;
 use crate::virtio::video::format::Profile;
 use crate::virtio::video::resource::BufferHandle;
 use crate::virtio::video::resource::GuestResource;
@@ -50,6 +58,123 @@ use crate::virtio::video::resource::GuestResourceHandle;
 use crate::virtio::video::utils::EventQueue;
 use crate::virtio::video::utils::SyncEventQueue;
 
+/// Duration of a group of pictures, i.e. the maximum time between two keyframes. virtio-video has
+/// no control for the GOP length, so it is derived from the frame rate.
+const GOP_DURATION_SECS: u32 = 2;
+
+/// Returns the libavcodec profile corresponding to `profile`, along with its name for the
+/// encoders that take it as a private option instead.
+fn ffmpeg_profile(profile: Profile) -> Option<(u32, &'static str)> {
+    match profile {
+        Profile::H264Baseline => Some((FF_PROFILE_H264_BASELINE, "baseline")),
+        Profile::H264Main => Some((FF_PROFILE_H264_MAIN, "main")),
+        Profile::H264High => Some((FF_PROFILE_H264_HIGH, "high")),
+        Profile::HevcMain => Some((FF_PROFILE_HEVC_MAIN, "main")),
+        Profile::HevcMainStillPicture => {
+            Some((FF_PROFILE_HEVC_MAIN_STILL_PICTURE, "mainstillpicture"))
+        }
+        // libvpx takes the VP8 profile number directly.
+        Profile::VP8Profile0 => Some((0, "0")),
+        Profile::VP8Profile1 => Some((1, "1")),
+        Profile::VP8Profile2 => Some((2, "2")),
+        Profile::VP8Profile3 => Some((3, "3")),
+        Profile::VP9Profile0 => Some((FF_PROFILE_VP9_0, "0")),
+        _ => None,
+    }
+}
+
+/// Returns the profiles supported for `format`. Only the 8-bit 4:2:0 profiles are listed since
+/// these are the only input formats we accept. virtio-video only has level controls for H.264.
+fn supported_profiles(format: Format) -> Vec<Profile> {
+    match format {
+        Format::H264 => vec![Profile::H264Baseline, Profile::H264Main, Profile::H264High],
+        Format::Hevc => vec![Profile::HevcMain, Profile::HevcMainStillPicture],
+        Format::VP8 => vec![
+            Profile::VP8Profile0,
+            Profile::VP8Profile1,
+            Profile::VP8Profile2,
+            Profile::VP8Profile3,
+        ],
+        Format::VP9 => vec![Profile::VP9Profile0],
+        _ => vec![],
+    }
+}
+
+/// Returns the level_idc of an H.264 `level`, which is what libavcodec expects.
+fn h264_level_idc(level: Level) -> u32 {
+    match level {
+        Level::H264_1_0 => 10,
+        Level::H264_1_1 => 11,
+        Level::H264_1_2 => 12,
+        Level::H264_1_3 => 13,
+        Level::H264_2_0 => 20,
+        Level::H264_2_1 => 21,
+        Level::H264_2_2 => 22,
+        Level::H264_3_0 => 30,
+        Level::H264_3_1 => 31,
+        Level::H264_3_2 => 32,
+        Level::H264_4_0 => 40,
+        Level::H264_4_1 => 41,
+        Level::H264_4_2 => 42,
+        Level::H264_5_0 => 50,
+        Level::H264_5_1 => 51,
+    }
+}
+
+/// Returns the (average, min, max) bit rates and the rate control buffer size to use for
+/// `bitrate`. A constant bit rate is requested by setting all three rates to the target.
+fn rate_control_params(bitrate: Bitrate) -> (u64, u64, u64, u32) {
+    match bitrate {
+        // Use a buffer of one second worth of data.
+        Bitrate::Cbr { target } => (target as u64, target as u64, target as u64, target),
+        Bitrate::Vbr { target, peak } => (target as u64, 0, peak as u64, peak),
+    }
+}
+
+/// Applies the stream parameters of `config` that are not common to all encoders.
+fn configure_encoder(
+    builder: &mut EncoderContextBuilder,
+    codec_name: &str,
+    config: &SessionConfig,
+) -> anyhow::Result<()> {
+    let (bit_rate, min_bit_rate, max_bit_rate, rc_buffer_size) =
+        rate_control_params(config.dst_bitrate);
+    builder.set_bit_rate(bit_rate);
+    builder.set_min_bit_rate(min_bit_rate);
+    builder.set_max_bit_rate(max_bit_rate);
+    builder.set_rc_buffer_size(rc_buffer_size);
+
+    builder.set_gop_size(config.frame_rate.max(1) * GOP_DURATION_SECS);
+    // Frames are returned to the guest in the order they have been queued.
+    builder.set_max_b_frames(0);
+
+    let (profile, profile_name) = ffmpeg_profile(config.dst_profile)
+        .ok_or_else(|| anyhow!("unsupported profile {:?}", config.dst_profile))?;
+    builder.set_profile(profile);
+    if let Some(level) = config.dst_h264_level {
+        builder.set_level(h264_level_idc(level));
+    }
+
+    match codec_name {
+        "libx264" | "libx265" => {
+            // These encoders only look at the profile given as a private option.
+            builder.set_option("profile", profile_name)?;
+            // Keyframes forced by the guest must be usable as stream entry points.
+            builder.set_option("forced-idr", "1")?;
+            builder.set_option("tune", "zerolatency")?;
+            if codec_name == "libx264" && matches!(config.dst_bitrate, Bitrate::Cbr { .. }) {
+                builder.set_option("nal-hrd", "cbr")?;
+            }
+        }
+        "libvpx" | "libvpx-vp9" => {
+            builder.set_option("deadline", "realtime")?;
+        }
+        _ => (),
+    }
+
+    Ok(())
+}
+
 /// Structure wrapping a backing memory mapping for an input frame that can be used as a libavcodec
 /// buffer source. It also sends a `ProcessedInputBuffer` event when dropped.
 struct InputBuffer {
@@ -304,15 +429,11 @@ impl EncoderSession for FfmpegEncoderSession {
         bitrate: Bitrate,
         framerate: u32,
     ) -> VideoResult<()> {
-        match bitrate {
-            Bitrate::Cbr { target } => {
-                self.context.set_bit_rate(target as u64);
-            }
-            Bitrate::Vbr { target, peak } => {
-                self.context.set_bit_rate(target as u64);
-                self.context.set_max_bit_rate(peak as u64);
-            }
-        }
+        let (bit_rate, min_bit_rate, max_bit_rate, rc_buffer_size) = rate_control_params(bitrate);
+        self.context.set_bit_rate(bit_rate);
+        self.context.set_min_bit_rate(min_bit_rate);
+        self.context.set_max_bit_rate(max_bit_rate);
+        self.context.set_rc_buffer_size(rc_buffer_size);
         // TODO(b/241492607): support fractional frame rates.
         self.context.set_time_base(AVRational {
             num: 1,
@@ -433,23 +554,9 @@ impl Encoder for FfmpegEncoder {
                 }
             })
             .collect();
-        // TODO(ishitatsuyuki): right now we haven't plumbed the profile handling yet and will use
-        //                      a hard coded set of profiles. Make this support more profiles when
-        //                      we implement the conversion between virtio and ffmpeg profiles.
         let coded_format_profiles = codecs
             .iter()
-            .map(|(&format, _codec)| {
-                (
-                    format,
-                    match format {
-                        Format::H264 => vec![Profile::H264Baseline],
-                        Format::Hevc => vec![Profile::HevcMain],
-                        Format::VP8 => vec![Profile::VP8Profile0],
-                        Format::VP9 => vec![Profile::VP9Profile0],
-                        _ => vec![],
-                    },
-                )
-            })
+            .map(|(&format, _codec)| (format, supported_profiles(format)))
             .collect();
         let caps = EncoderCapabilities {
             input_format_descs,
@@ -477,6 +584,7 @@ impl Encoder for FfmpegEncoder {
             .map_err(|_| VideoError::InvalidFormat)?;
         let context = codec
             .build_encoder()
+            .map_err(anyhow::Error::from)
             .and_then(|mut b| {
                 b.set_pix_fmt(pix_fmt);
                 b.set_dimensions(Dimensions {
@@ -487,7 +595,8 @@ impl Encoder for FfmpegEncoder {
                     num: 1,
                     den: config.frame_rate as _,
                 });
-                b.build()
+                configure_encoder(&mut b, codec.name(), &config)?;
+                Ok(b.build()?)
             })
             .context("while creating new session")
             .map_err(VideoError::BackendFailure)?;
@@ -523,3 +632,98 @@ impl Encoder for FfmpegEncoder {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rate_control() {
+        let cases = [
+            (
+                Bitrate::Cbr { target: 1_000_000 },
+                (1_000_000, 1_000_000, 1_000_000, 1_000_000),
+            ),
+            (
+                Bitrate::Vbr {
+                    target: 1_000_000,
+                    peak: 3_000_000,
+                },
+                (1_000_000, 0, 3_000_000, 3_000_000),
+            ),
+            (
+                Bitrate::Vbr {
+                    target: 500_000,
+                    peak: 500_000,
+                },
+                (500_000, 0, 500_000, 500_000),
+            ),
+        ];
+        for (bitrate, expected) in cases {
+            assert_eq!(rate_control_params(bitrate), expected, "{:?}", bitrate);
+        }
+    }
+
+    #[test]
+    fn profiles() {
+        let cases = [
+            (
+                Profile::H264Baseline,
+                Some((FF_PROFILE_H264_BASELINE, "baseline")),
+            ),
+            (Profile::H264Main, Some((FF_PROFILE_H264_MAIN, "main"))),
+            (Profile::H264High, Some((FF_PROFILE_H264_HIGH, "high"))),
+            (Profile::H264High10, None),
+            (Profile::H264Extended, None),
+            (Profile::HevcMain, Some((FF_PROFILE_HEVC_MAIN, "main"))),
+            (
+                Profile::HevcMainStillPicture,
+                Some((FF_PROFILE_HEVC_MAIN_STILL_PICTURE, "mainstillpicture")),
+            ),
+            (Profile::HevcMain10, None),
+            (Profile::VP8Profile0, Some((0, "0"))),
+            (Profile::VP8Profile3, Some((3, "3"))),
+            (Profile::VP9Profile0, Some((FF_PROFILE_VP9_0, "0"))),
+            (Profile::VP9Profile2, None),
+        ];
+        for (profile, expected) in cases {
+            assert_eq!(ffmpeg_profile(profile), expected, "{:?}", profile);
+        }
+    }
+
+    #[test]
+    fn supported_profiles_are_mapped() {
+        for format in [Format::H264, Format::Hevc, Format::VP8, Format::VP9] {
+            let profiles = supported_profiles(format);
+            assert!(!profiles.is_empty(), "{:?}", format);
+            for profile in profiles {
+                assert_eq!(profile.to_format(), format);
+                assert!(ffmpeg_profile(profile).is_some(), "{:?}", profile);
+            }
+        }
+    }
+
+    #[test]
+    fn h264_levels() {
+        let cases = [
+            (Level::H264_1_0, 10),
+            (Level::H264_1_1, 11),
+            (Level::H264_1_2, 12),
+            (Level::H264_1_3, 13),
+            (Level::H264_2_0, 20),
+            (Level::H264_2_1, 21),
+            (Level::H264_2_2, 22),
+            (Level::H264_3_0, 30),
+            (Level::H264_3_1, 31),
+            (Level::H264_3_2, 32),
+            (Level::H264_4_0, 40),
+            (Level::H264_4_1, 41),
+            (Level::H264_4_2, 42),
+            (Level::H264_5_0, 50),
+            (Level::H264_5_1, 51),
+        ];
+        for (level, idc) in cases {
+            assert_eq!(h264_level_idc(level), idc, "{:?}", level);
+        }
+    }
+}
@@ -460,6 +460,13 @@ impl VirtioPciDevice {
             && self.common_config.driver_status & VIRTIO_CONFIG_S_FAILED as u8 == 0
     }
 
+    /// Overrides the PCI address requested by the underlying virtio device. This is used to place
+    /// hotplugged devices behind a hotplug capable root port. It has no effect once the address
+    /// has been allocated.
+    pub fn set_preferred_address(&mut self, address: PciAddress) {
+        self.preferred_address = Some(address);
+    }
+
     /// Determines if the driver has requested the device reset itself
     fn is_reset_requested(&self) -> bool {
         self.common_config.driver_status == DEVICE_RESET as u8
@@ -4,10 +4,14 @@
 
 //! This module implements the virtio vsock device.
 //!
-//! Currently, this is only implemented for Windows.
-//! For Linux, please use the vhost-vsock device, which delegates the vsock
-//! implementation to the kernel.
+//! On Windows, the device is implemented in userspace on top of named pipes.
+//! On Linux, the vhost-vsock device delegates the vsock implementation to the
+//! kernel, and `UserspaceVsock` forwards guest connections to unix domain
+//! sockets for hosts where vhost-vsock is not available.
 
+pub mod protocol;
 mod sys;
+#[cfg(unix)]
+pub use sys::UserspaceVsock;
 pub use sys::Vsock;
 pub use sys::VsockConfig;
@@ -6,6 +6,7 @@ cfg_if::cfg_if! {
     if #[cfg(unix)] {
         mod unix;
         use unix as platform;
+        pub use unix::UserspaceVsock;
         pub use crate::virtio::vhost::Vsock;
     } else if #[cfg(windows)] {
         mod windows;
@@ -2,12 +2,15 @@
 // Use of this source code is governed by a BSD-style license that can be
 // found in the LICENSE file.
 
+mod vsock;
+
 use std::path::Path;
 use std::path::PathBuf;
 
 use serde::Deserialize;
 use serde::Serialize;
 use serde_keyvalue::FromKeyValues;
+pub use vsock::UserspaceVsock;
 
 static VHOST_VSOCK_DEFAULT_PATH: &str = "/dev/vhost-vsock";
 
@@ -25,6 +28,11 @@ pub struct VsockConfig {
     /// Path to the vhost-vsock device.
     #[serde(default = "default_vsock_path", rename = "device")]
     pub vhost_device: PathBuf,
+    /// Path of the unix socket to forward connections to. If set, a userspace device is used
+    /// instead of vhost-vsock: guest connections to host port `P` go to `<uds_path>_<P>`, and host
+    /// applications reach the guest by connecting to `uds_path` and writing `CONNECT <port>\n`.
+    #[serde(default)]
+    pub uds_path: Option<PathBuf>,
 }
 
 impl VsockConfig {
@@ -37,6 +45,7 @@ impl VsockConfig {
             vhost_device: vhost_device
                 .map(|p| PathBuf::from(p.as_ref()))
                 .unwrap_or_else(|| PathBuf::from(VHOST_VSOCK_DEFAULT_PATH)),
+            uds_path: None,
         }
     }
 }
@@ -61,6 +70,7 @@ mod tests {
             VsockConfig {
                 vhost_device: VHOST_VSOCK_DEFAULT_PATH.into(),
                 cid: 56,
+                uds_path: None,
             }
         );
 
@@ -80,6 +90,7 @@ mod tests {
                 #[cfg(unix)]
                 vhost_device: VHOST_VSOCK_DEFAULT_PATH.into(),
                 cid: 78,
+                uds_path: None,
             }
         );
 
@@ -97,7 +108,7 @@ mod tests {
             from_vsock_arg("invalid=foo").unwrap_err(),
             ParseError {
                 kind: ErrorKind::SerdeError(
-                    "unknown field `invalid`, expected `cid` or `device`".into()
+                    "unknown field `invalid`, expected one of `cid`, `device`, `uds_path`".into()
                 ),
                 pos: 0,
             }
@@ -109,6 +120,7 @@ mod tests {
             VsockConfig {
                 vhost_device: "/some/path".into(),
                 cid: 56,
+                uds_path: None,
             }
         );
 
@@ -118,6 +130,7 @@ mod tests {
             VsockConfig {
                 vhost_device: "/some/path".into(),
                 cid: 56,
+                uds_path: None,
             }
         );
 
@@ -130,6 +143,16 @@ mod tests {
             }
         );
 
+        // Userspace device
+        assert_eq!(
+            from_vsock_arg("cid=56,uds_path=/run/vm/vsock.sock").unwrap(),
+            VsockConfig {
+                vhost_device: VHOST_VSOCK_DEFAULT_PATH.into(),
+                cid: 56,
+                uds_path: Some("/run/vm/vsock.sock".into()),
+            }
+        );
+
         // Device passed twice
         assert_eq!(
             from_vsock_arg("cid=56,device=42,device=/some/path").unwrap_err(),
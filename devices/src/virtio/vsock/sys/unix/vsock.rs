@@ -0,0 +1,1121 @@
+// Copyright 2023 The ChromiumOS Authors
+// Use of this source code is governed by a BSD-style license that can be
+// found in the LICENSE file.
+
+//! Userspace virtio-vsock device that proxies guest stream connections to unix domain sockets on
+//! the host, following the hybrid vsock scheme used by Firecracker:
+//!
+//! * A guest connection to the host (CID 2) on port `P` is forwarded to the unix socket at
+//!   `<uds_path>_<P>`, which must be bound by a host application.
+//! * A host application connects to the unix socket at `<uds_path>` and writes
+//!   `CONNECT <port>\n`. Once the guest accepts the connection on `port`, the device replies with
+//!   `OK <local port>\n` and the socket carries the stream from then on.
+//!
+//! Unlike vhost-vsock, this needs neither `/dev/vhost-vsock` nor a host-wide CID, so it can be
+//! used inside containers.
+
+use std::cmp::min;
+use std::collections::BTreeMap;
+use std::collections::VecDeque;
+use std::ffi::OsString;
+use std::io;
+use std::io::Read;
+use std::io::Write;
+use std::mem::size_of;
+use std::net::Shutdown;
+use std::num::Wrapping;
+use std::os::unix::net::UnixListener;
+use std::os::unix::net::UnixStream;
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::anyhow;
+use anyhow::Context;
+use base::error;
+use base::info;
+use base::warn;
+use base::AsRawDescriptor;
+use base::Event;
+use base::EventToken;
+use base::EventType;
+use base::RawDescriptor;
+use base::WaitContext;
+use base::WorkerThread;
+use data_model::Le16;
+use data_model::Le32;
+use data_model::Le64;
+use vm_memory::GuestMemory;
+use zerocopy::AsBytes;
+
+use crate::virtio::copy_config;
+use crate::virtio::device_constants::vsock::QUEUE_SIZES;
+use crate::virtio::vsock::protocol::virtio_vsock_config;
+use crate::virtio::vsock::protocol::virtio_vsock_hdr;
+use crate::virtio::vsock::protocol::vsock_op;
+use crate::virtio::vsock::protocol::TYPE_STREAM_SOCKET;
+use crate::virtio::DeviceType;
+use crate::virtio::Interrupt;
+use crate::virtio::Queue;
+use crate::virtio::SignalableInterrupt;
+use crate::virtio::VirtioDevice;
+use crate::Suspendable;
+
+/// CID of the host, the only peer guest connections can be forwarded to.
+const HOST_CID: u64 = 2;
+/// Receive buffer space advertised to the guest for each connection.
+const CONN_BUF_ALLOC: u32 = 256 * 1024;
+/// Maximum payload of a single packet sent to the guest.
+const MAX_PKT_BUF_SIZE: usize = 64 * 1024;
+/// First local port used for connections initiated by the host.
+const FIRST_HOST_PORT: u32 = 1 << 30;
+/// Maximum length of the `CONNECT <port>` line sent by host applications.
+const MAX_CONNECT_CMD_LEN: usize = 32;
+
+const VSOCK_FLAGS_SHUTDOWN_RCV: u32 = 1;
+const VSOCK_FLAGS_SHUTDOWN_SEND: u32 = 2;
+
+#[derive(EventToken)]
+enum Token {
+    RxQueue,
+    TxQueue,
+    InterruptResample,
+    Kill,
+    Listener,
+    Pending { id: u32 },
+    Connection { id: u32 },
+}
+
+/// Identifies a connection by its host (local) and guest (peer) ports.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct PortPair {
+    local: u32,
+    peer: u32,
+}
+
+/// Returns the path of the unix socket guest connections to `port` are forwarded to.
+fn port_path(uds_path: &Path, port: u32) -> PathBuf {
+    let mut path = OsString::from(uds_path.as_os_str());
+    path.push(format!("_{}", port));
+    PathBuf::from(path)
+}
+
+/// Parses the `CONNECT <port>` command sent by host applications, without the trailing newline.
+fn parse_connect_command(cmd: &[u8]) -> Option<u32> {
+    let cmd = std::str::from_utf8(cmd).ok()?;
+    let port = cmd.trim_end_matches('\r').strip_prefix("CONNECT ")?;
+    port.trim().parse().ok()
+}
+
+/// Returns a stream packet header from the host to the guest.
+fn packet_header(guest_cid: u64, ports: PortPair, op: u16) -> virtio_vsock_hdr {
+    virtio_vsock_hdr {
+        src_cid: Le64::from(HOST_CID),
+        dst_cid: Le64::from(guest_cid),
+        src_port: Le32::from(ports.local),
+        dst_port: Le32::from(ports.peer),
+        r#type: Le16::from(TYPE_STREAM_SOCKET),
+        op: Le16::from(op),
+        ..Default::default()
+    }
+}
+
+/// A host application that has connected to the listening socket but has not sent its
+/// `CONNECT` command yet.
+struct PendingConnection {
+    stream: UnixStream,
+    cmd: Vec<u8>,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum ConnState {
+    /// Connection initiated by the host, waiting for the guest's response.
+    Connecting,
+    Connected,
+}
+
+struct Connection {
+    stream: UnixStream,
+    ports: PortPair,
+    state: ConnState,
+    /// Guest data that has not been written to the host socket yet.
+    tx_buf: VecDeque<u8>,
+    /// Number of guest bytes written to the host socket.
+    fwd_cnt: Wrapping<u32>,
+    /// Value of `fwd_cnt` last reported to the guest.
+    last_fwd_cnt: Wrapping<u32>,
+    /// Number of host bytes sent to the guest.
+    rx_cnt: Wrapping<u32>,
+    peer_buf_alloc: u32,
+    peer_fwd_cnt: Wrapping<u32>,
+    /// The host socket has been closed; waiting for the guest to reset the connection.
+    host_eof: bool,
+    /// The guest will not send any more data.
+    guest_shut_send: bool,
+    /// Events currently registered for `stream`.
+    events: EventType,
+}
+
+impl Connection {
+    fn new(stream: UnixStream, ports: PortPair, state: ConnState) -> Self {
+        Connection {
+            stream,
+            ports,
+            state,
+            tx_buf: VecDeque::new(),
+            fwd_cnt: Wrapping(0),
+            last_fwd_cnt: Wrapping(0),
+            rx_cnt: Wrapping(0),
+            peer_buf_alloc: 0,
+            peer_fwd_cnt: Wrapping(0),
+            host_eof: false,
+            guest_shut_send: false,
+            events: EventType::None,
+        }
+    }
+
+    /// Returns a packet header for this connection carrying our current credit.
+    fn header(&mut self, guest_cid: u64, op: u16) -> virtio_vsock_hdr {
+        let mut hdr = packet_header(guest_cid, self.ports, op);
+        hdr.buf_alloc = Le32::from(CONN_BUF_ALLOC);
+        hdr.fwd_cnt = Le32::from(self.fwd_cnt.0);
+        self.last_fwd_cnt = self.fwd_cnt;
+        hdr
+    }
+
+    /// Number of bytes the guest is able to receive.
+    fn peer_credit(&self) -> u32 {
+        let in_flight = (self.rx_cnt - self.peer_fwd_cnt).0;
+        self.peer_buf_alloc.saturating_sub(in_flight)
+    }
+
+    /// Whether the guest is running low on credit and should be told about freed buffer space.
+    fn needs_credit_update(&self) -> bool {
+        let unacked = self.fwd_cnt - self.last_fwd_cnt;
+        let in_flight = unacked + Wrapping(self.tx_buf.len() as u32);
+        unacked.0 > 0 && CONN_BUF_ALLOC.saturating_sub(in_flight.0) < CONN_BUF_ALLOC / 2
+    }
+
+    fn wanted_events(&self, rx_ready: bool) -> EventType {
+        let read = rx_ready
+            && self.state == ConnState::Connected
+            && !self.host_eof
+            && self.peer_credit() > 0;
+        let write = !self.tx_buf.is_empty();
+        match (read, write) {
+            (true, true) => EventType::ReadWrite,
+            (true, false) => EventType::Read,
+            (false, true) => EventType::Write,
+            (false, false) => EventType::None,
+        }
+    }
+
+    /// Writes as much buffered guest data as possible to the host socket.
+    fn flush_tx(&mut self) -> io::Result<()> {
+        while !self.tx_buf.is_empty() {
+            let (data, _) = self.tx_buf.as_slices();
+            match self.stream.write(data) {
+                Ok(0) => return Err(io::Error::from(io::ErrorKind::WriteZero)),
+                Ok(n) => {
+                    self.tx_buf.drain(..n);
+                    self.fwd_cnt += Wrapping(n as u32);
+                }
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => return Ok(()),
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+        if self.guest_shut_send {
+            // The host application may already have closed its end; nothing to do then.
+            let _ = self.stream.shutdown(Shutdown::Write);
+        }
+        Ok(())
+    }
+}
+
+struct Worker {
+    mem: GuestMemory,
+    interrupt: Interrupt,
+    rx_queue: Queue,
+    rx_queue_evt: Event,
+    tx_queue: Queue,
+    tx_queue_evt: Event,
+    guest_cid: u64,
+    uds_path: PathBuf,
+    listener: UnixListener,
+    wait_ctx: WaitContext<Token>,
+    next_id: u32,
+    next_host_port: u32,
+    pending: BTreeMap<u32, PendingConnection>,
+    connections: BTreeMap<u32, Connection>,
+    ports: BTreeMap<PortPair, u32>,
+    /// Control packets waiting for rx descriptors, sent before any data. Guest packets are not
+    /// processed while it holds as many packets as the rx queue has descriptors.
+    rx_pending: VecDeque<virtio_vsock_hdr>,
+    /// Set when the guest ran out of rx descriptors.
+    rx_stalled: bool,
+    rx_buf: Vec<u8>,
+    needs_rx_interrupt: bool,
+    needs_tx_interrupt: bool,
+}
+
+impl Worker {
+    #[allow(clippy::too_many_arguments)]
+    fn new(
+        mem: GuestMemory,
+        interrupt: Interrupt,
+        (rx_queue, rx_queue_evt): (Queue, Event),
+        (tx_queue, tx_queue_evt): (Queue, Event),
+        guest_cid: u64,
+        uds_path: PathBuf,
+        listener: UnixListener,
+        wait_ctx: WaitContext<Token>,
+    ) -> Self {
+        Worker {
+            mem,
+            interrupt,
+            rx_queue,
+            rx_queue_evt,
+            tx_queue,
+            tx_queue_evt,
+            guest_cid,
+            uds_path,
+            listener,
+            wait_ctx,
+            next_id: 0,
+            next_host_port: FIRST_HOST_PORT,
+            pending: BTreeMap::new(),
+            connections: BTreeMap::new(),
+            ports: BTreeMap::new(),
+            rx_pending: VecDeque::new(),
+            rx_stalled: false,
+            rx_buf: vec![0; MAX_PKT_BUF_SIZE],
+            needs_rx_interrupt: false,
+            needs_tx_interrupt: false,
+        }
+    }
+
+    fn rx_ready(&self) -> bool {
+        !self.rx_stalled && self.rx_pending.is_empty()
+    }
+
+    fn alloc_id(&mut self) -> u32 {
+        loop {
+            let id = self.next_id;
+            self.next_id = self.next_id.wrapping_add(1);
+            if !self.pending.contains_key(&id) && !self.connections.contains_key(&id) {
+                return id;
+            }
+        }
+    }
+
+    fn alloc_host_port(&mut self, peer: u32) -> u32 {
+        loop {
+            let local = self.next_host_port;
+            self.next_host_port = self
+                .next_host_port
+                .checked_add(1)
+                .unwrap_or(FIRST_HOST_PORT);
+            if !self.ports.contains_key(&PortPair { local, peer }) {
+                return local;
+            }
+        }
+    }
+
+    fn add_connection(&mut self, conn: Connection) -> u32 {
+        let id = self.alloc_id();
+        self.ports.insert(conn.ports, id);
+        self.connections.insert(id, conn);
+        id
+    }
+
+    fn remove_connection(&mut self, id: u32) -> anyhow::Result<Option<Connection>> {
+        let conn = match self.connections.remove(&id) {
+            Some(conn) => conn,
+            None => return Ok(None),
+        };
+        self.ports.remove(&conn.ports);
+        if conn.events != EventType::None {
+            self.wait_ctx
+                .delete(&conn.stream)
+                .context("failed to remove connection from WaitContext")?;
+        }
+        Ok(Some(conn))
+    }
+
+    /// Closes the host side of connection `id` and tells the guest about it.
+    fn reset_connection(&mut self, id: u32) -> anyhow::Result<()> {
+        if let Some(conn) = self.remove_connection(id)? {
+            self.send_rst(conn.ports);
+        }
+        Ok(())
+    }
+
+    fn send_rst(&mut self, ports: PortPair) {
+        let hdr = packet_header(self.guest_cid, ports, vsock_op::VIRTIO_VSOCK_OP_RST);
+        self.send_control(hdr);
+    }
+
+    /// Sends a header-only packet to the guest, or queues it until rx descriptors are available.
+    /// A queued credit update replaces the one already queued for the same connection, if any.
+    fn send_control(&mut self, hdr: virtio_vsock_hdr) {
+        if u16::from(hdr.op) == vsock_op::VIRTIO_VSOCK_OP_CREDIT_UPDATE {
+            if let Some(pending) = self.rx_pending.iter_mut().find(|pending| {
+                u16::from(pending.op) == vsock_op::VIRTIO_VSOCK_OP_CREDIT_UPDATE
+                    && u32::from(pending.src_port) == u32::from(hdr.src_port)
+                    && u32::from(pending.dst_port) == u32::from(hdr.dst_port)
+            }) {
+                *pending = hdr;
+                return;
+            }
+        }
+        self.rx_pending.push_back(hdr);
+        self.flush_rx_pending();
+    }
+
+    fn flush_rx_pending(&mut self) {
+        while let Some(hdr) = self.rx_pending.front() {
+            let mut desc = match self.rx_queue.pop(&self.mem) {
+                Some(desc) => desc,
+                None => {
+                    self.rx_stalled = true;
+                    return;
+                }
+            };
+            if let Err(e) = desc.writer.write_obj(*hdr) {
+                error!("vsock: failed to write packet to the guest: {}", e);
+            }
+            let len = desc.writer.bytes_written() as u32;
+            self.rx_queue.add_used(&self.mem, desc, len);
+            self.needs_rx_interrupt = true;
+            self.rx_pending.pop_front();
+        }
+    }
+
+    /// Registers the events connection `id` is currently interested in.
+    fn update_events(&mut self, id: u32) -> anyhow::Result<()> {
+        let rx_ready = self.rx_ready();
+        let conn = match self.connections.get_mut(&id) {
+            Some(conn) => conn,
+            None => return Ok(()),
+        };
+        let events = conn.wanted_events(rx_ready);
+        if events == conn.events {
+            return Ok(());
+        }
+        let token = Token::Connection { id };
+        // Descriptors are removed rather than set to `EventType::None` so that a hung up socket
+        // does not keep waking the worker while it cannot be serviced.
+        match (conn.events, events) {
+            (_, EventType::None) => self.wait_ctx.delete(&conn.stream),
+            (EventType::None, events) => self.wait_ctx.add_for_event(&conn.stream, events, token),
+            (_, events) => self.wait_ctx.modify(&conn.stream, events, token),
+        }
+        .context("failed to update connection events")?;
+        conn.events = events;
+        Ok(())
+    }
+
+    fn process_rx_queue(&mut self) -> anyhow::Result<()> {
+        self.rx_stalled = false;
+        self.flush_rx_pending();
+        let ids: Vec<u32> = self.connections.keys().copied().collect();
+        for id in ids {
+            self.update_events(id)?;
+        }
+        // Guest packets may have been left in the tx queue while replies were piling up.
+        self.process_tx_queue()
+    }
+
+    fn process_tx_queue(&mut self) -> anyhow::Result<()> {
+        // Like vhost-vsock, stop taking guest packets while there are as many replies waiting for
+        // rx descriptors as the rx queue can hold, so that a guest that doesn't provide rx
+        // descriptors can't make the device queue an unbounded number of them.
+        while self.rx_pending.len() < self.rx_queue.size() as usize {
+            let mut desc = match self.tx_queue.pop(&self.mem) {
+                Some(desc) => desc,
+                None => break,
+            };
+            let reader = &mut desc.reader;
+            let packet = reader.read_obj::<virtio_vsock_hdr>().and_then(|hdr| {
+                let len = min(u32::from(hdr.len) as usize, reader.available_bytes());
+                let mut data = vec![0; len];
+                reader.read_exact(&mut data)?;
+                Ok((hdr, data))
+            });
+            self.tx_queue.add_used(&self.mem, desc, 0);
+            self.needs_tx_interrupt = true;
+
+            match packet {
+                Ok((hdr, data)) => self.handle_guest_packet(hdr, data)?,
+                Err(e) => error!("vsock: failed to read packet from the guest: {}", e),
+            }
+        }
+        Ok(())
+    }
+
+    fn handle_guest_packet(&mut self, hdr: virtio_vsock_hdr, data: Vec<u8>) -> anyhow::Result<()> {
+        let op = u16::from(hdr.op);
+        let ports = PortPair {
+            local: hdr.dst_port.into(),
+            peer: hdr.src_port.into(),
+        };
+
+        // Only stream connections to the host are forwarded.
+        if u64::from(hdr.dst_cid) != HOST_CID || u16::from(hdr.r#type) != TYPE_STREAM_SOCKET {
+            if op != vsock_op::VIRTIO_VSOCK_OP_RST {
+                self.send_rst(ports);
+            }
+            return Ok(());
+        }
+
+        let id = match self.ports.get(&ports) {
+            Some(id) => *id,
+            None => {
+                match op {
+                    vsock_op::VIRTIO_VSOCK_OP_REQUEST => self.connect_to_host(ports, &hdr)?,
+                    vsock_op::VIRTIO_VSOCK_OP_RST => {}
+                    _ => self.send_rst(ports),
+                }
+                return Ok(());
+            }
+        };
+        let guest_cid = self.guest_cid;
+        let conn = match self.connections.get_mut(&id) {
+            Some(conn) => conn,
+            None => return Ok(()),
+        };
+        conn.peer_buf_alloc = hdr.buf_alloc.into();
+        conn.peer_fwd_cnt = Wrapping(hdr.fwd_cnt.into());
+
+        match op {
+            vsock_op::VIRTIO_VSOCK_OP_RESPONSE if conn.state == ConnState::Connecting => {
+                conn.state = ConnState::Connected;
+                let reply = format!("OK {}\n", ports.local);
+                if let Err(e) = conn.stream.write_all(reply.as_bytes()) {
+                    warn!("vsock: failed to reply to host connection: {}", e);
+                    return self.reset_connection(id);
+                }
+            }
+            vsock_op::VIRTIO_VSOCK_OP_RW
+                if conn.state == ConnState::Connected && !conn.guest_shut_send =>
+            {
+                if conn.tx_buf.len() + data.len() > CONN_BUF_ALLOC as usize {
+                    warn!("vsock: guest exceeded its credit on port {}", ports.local);
+                    return self.reset_connection(id);
+                }
+                conn.tx_buf.extend(data);
+                return self.flush_tx(id);
+            }
+            vsock_op::VIRTIO_VSOCK_OP_SHUTDOWN => {
+                let flags = u32::from(hdr.flags);
+                let both = VSOCK_FLAGS_SHUTDOWN_RCV | VSOCK_FLAGS_SHUTDOWN_SEND;
+                if flags & both == both {
+                    // The guest closed its socket and waits for us to reset the connection. Data
+                    // the host application has not accepted by now is dropped.
+                    let _ = conn.flush_tx();
+                    return self.reset_connection(id);
+                }
+                if flags & VSOCK_FLAGS_SHUTDOWN_SEND != 0 {
+                    conn.guest_shut_send = true;
+                    return self.flush_tx(id);
+                }
+            }
+            vsock_op::VIRTIO_VSOCK_OP_RST => {
+                self.remove_connection(id)?;
+                return Ok(());
+            }
+            vsock_op::VIRTIO_VSOCK_OP_CREDIT_UPDATE => {}
+            vsock_op::VIRTIO_VSOCK_OP_CREDIT_REQUEST => {
+                let hdr = conn.header(guest_cid, vsock_op::VIRTIO_VSOCK_OP_CREDIT_UPDATE);
+                self.send_control(hdr);
+            }
+            _ => {
+                warn!("vsock: unexpected operation {} on port {}", op, ports.local);
+                return self.reset_connection(id);
+            }
+        }
+        self.update_events(id)
+    }
+
+    /// Handles a guest connection request by connecting to the matching host socket.
+    fn connect_to_host(&mut self, ports: PortPair, hdr: &virtio_vsock_hdr) -> anyhow::Result<()> {
+        let path = port_path(&self.uds_path, ports.local);
+        let stream = match UnixStream::connect(&path).and_then(|stream| {
+            stream.set_nonblocking(true)?;
+            Ok(stream)
+        }) {
+            Ok(stream) => stream,
+            Err(e) => {
+                info!("vsock: failed to connect to {}: {}", path.display(), e);
+                self.send_rst(ports);
+                return Ok(());
+            }
+        };
+
+        let mut conn = Connection::new(stream, ports, ConnState::Connected);
+        conn.peer_buf_alloc = hdr.buf_alloc.into();
+        conn.peer_fwd_cnt = Wrapping(hdr.fwd_cnt.into());
+        let response = conn.header(self.guest_cid, vsock_op::VIRTIO_VSOCK_OP_RESPONSE);
+        let id = self.add_connection(conn);
+        self.send_control(response);
+        self.update_events(id)
+    }
+
+    fn flush_tx(&mut self, id: u32) -> anyhow::Result<()> {
+        let guest_cid = self.guest_cid;
+        let conn = match self.connections.get_mut(&id) {
+            Some(conn) => conn,
+            None => return Ok(()),
+        };
+        if let Err(e) = conn.flush_tx() {
+            warn!(
+                "vsock: failed to write to host socket for port {}: {}",
+                conn.ports.local, e
+            );
+            return self.reset_connection(id);
+        }
+        if conn.needs_credit_update() {
+            let hdr = conn.header(guest_cid, vsock_op::VIRTIO_VSOCK_OP_CREDIT_UPDATE);
+            self.send_control(hdr);
+        }
+        self.update_events(id)
+    }
+
+    /// Forwards data from the host socket of connection `id` to the guest.
+    fn forward_to_guest(&mut self, id: u32) -> anyhow::Result<()> {
+        let guest_cid = self.guest_cid;
+        while self.rx_ready() {
+            let conn = match self.connections.get_mut(&id) {
+                Some(conn) => conn,
+                None => return Ok(()),
+            };
+            let credit = conn.peer_credit() as usize;
+            if conn.state != ConnState::Connected || conn.host_eof || credit == 0 {
+                break;
+            }
+            // Only consume the descriptor once there is data to put in it.
+            let mut desc = match self.rx_queue.peek(&self.mem) {
+                Some(desc) => desc,
+                None => {
+                    self.rx_stalled = true;
+                    break;
+                }
+            };
+            let hdr_len = size_of::<virtio_vsock_hdr>();
+            let avail = desc.writer.available_bytes();
+            if avail <= hdr_len {
+                error!("vsock: rx descriptor too small: {} bytes", avail);
+                self.rx_queue.pop_peeked(&self.mem);
+                self.rx_queue.add_used(&self.mem, desc, 0);
+                self.needs_rx_interrupt = true;
+                continue;
+            }
+            let len = min(MAX_PKT_BUF_SIZE, min(credit, avail - hdr_len));
+
+            match conn.stream.read(&mut self.rx_buf[..len]) {
+                Ok(0) => {
+                    // The host application closed the connection, ask the guest to do the same.
+                    conn.host_eof = true;
+                    let mut hdr = conn.header(guest_cid, vsock_op::VIRTIO_VSOCK_OP_SHUTDOWN);
+                    hdr.flags = Le32::from(VSOCK_FLAGS_SHUTDOWN_RCV | VSOCK_FLAGS_SHUTDOWN_SEND);
+                    self.send_control(hdr);
+                    break;
+                }
+                Ok(n) => {
+                    let mut hdr = conn.header(guest_cid, vsock_op::VIRTIO_VSOCK_OP_RW);
+                    hdr.len = Le32::from(n as u32);
+                    conn.rx_cnt += Wrapping(n as u32);
+                    self.rx_queue.pop_peeked(&self.mem);
+                    let writer = &mut desc.writer;
+                    if let Err(e) = writer
+                        .write_obj(hdr)
+                        .and_then(|_| writer.write_all(&self.rx_buf[..n]))
+                    {
+                        error!("vsock: failed to write packet to the guest: {}", e);
+                    }
+                    let written = desc.writer.bytes_written() as u32;
+                    self.rx_queue.add_used(&self.mem, desc, written);
+                    self.needs_rx_interrupt = true;
+                }
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => break,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => {
+                    warn!(
+                        "vsock: failed to read from host socket for port {}: {}",
+                        conn.ports.local, e
+                    );
+                    return self.reset_connection(id);
+                }
+            }
+        }
+        self.update_events(id)
+    }
+
+    fn accept_connections(&mut self) -> anyhow::Result<()> {
+        loop {
+            let stream = match self.listener.accept() {
+                Ok((stream, _)) => stream,
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => return Ok(()),
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => {
+                    warn!("vsock: failed to accept host connection: {}", e);
+                    return Ok(());
+                }
+            };
+            if let Err(e) = stream.set_nonblocking(true) {
+                warn!("vsock: failed to set host connection non-blocking: {}", e);
+                continue;
+            }
+            let id = self.alloc_id();
+            self.wait_ctx
+                .add(&stream, Token::Pending { id })
+                .context("failed to add host connection to WaitContext")?;
+            self.pending.insert(
+                id,
+                PendingConnection {
+                    stream,
+                    cmd: Vec::new(),
+                },
+            );
+        }
+    }
+
+    /// Reads the `CONNECT` command of a pending host connection and forwards the request to the
+    /// guest once it is complete.
+    fn read_connect_command(&mut self, id: u32) -> anyhow::Result<()> {
+        let pending = match self.pending.get_mut(&id) {
+            Some(pending) => pending,
+            None => return Ok(()),
+        };
+        // Read one byte at a time so that no stream data following the command is consumed.
+        let mut byte = [0u8];
+        let complete = loop {
+            match pending.stream.read(&mut byte) {
+                Ok(0) => break false,
+                Ok(_) if byte[0] == b'\n' => break true,
+                Ok(_) if pending.cmd.len() < MAX_CONNECT_CMD_LEN => pending.cmd.push(byte[0]),
+                Ok(_) => {
+                    warn!("vsock: host connect command is too long");
+                    break false;
+                }
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => return Ok(()),
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => {
+                    warn!("vsock: failed to read host connect command: {}", e);
+                    break false;
+                }
+            }
+        };
+
+        let pending = match self.pending.remove(&id) {
+            Some(pending) => pending,
+            None => return Ok(()),
+        };
+        self.wait_ctx
+            .delete(&pending.stream)
+            .context("failed to remove host connection from WaitContext")?;
+        if !complete {
+            return Ok(());
+        }
+        let peer = match parse_connect_command(&pending.cmd) {
+            Some(port) => port,
+            None => {
+                warn!(
+                    "vsock: invalid host connect command: {:?}",
+                    String::from_utf8_lossy(&pending.cmd)
+                );
+                return Ok(());
+            }
+        };
+
+        let local = self.alloc_host_port(peer);
+        let mut conn = Connection::new(
+            pending.stream,
+            PortPair { local, peer },
+            ConnState::Connecting,
+        );
+        let request = conn.header(self.guest_cid, vsock_op::VIRTIO_VSOCK_OP_REQUEST);
+        self.add_connection(conn);
+        self.send_control(request);
+        Ok(())
+    }
+
+    fn run(&mut self, kill_evt: &Event) -> anyhow::Result<()> {
+        self.wait_ctx
+            .add_many(&[
+                (&self.rx_queue_evt, Token::RxQueue),
+                (&self.tx_queue_evt, Token::TxQueue),
+                (kill_evt, Token::Kill),
+                (&self.listener, Token::Listener),
+            ])
+            .context("failed to add events to WaitContext")?;
+        if let Some(resample_evt) = self.interrupt.get_resample_evt() {
+            self.wait_ctx
+                .add(resample_evt, Token::InterruptResample)
+                .context("failed to add resample event to WaitContext")?;
+        }
+
+        loop {
+            let events = self.wait_ctx.wait().context("failed polling for events")?;
+            for event in events.iter() {
+                match event.token {
+                    Token::RxQueue => {
+                        self.rx_queue_evt
+                            .wait()
+                            .context("failed reading rx queue Event")?;
+                        self.process_rx_queue()?;
+                    }
+                    Token::TxQueue => {
+                        self.tx_queue_evt
+                            .wait()
+                            .context("failed reading tx queue Event")?;
+                        self.process_tx_queue()?;
+                    }
+                    Token::InterruptResample => {
+                        self.interrupt.interrupt_resample();
+                    }
+                    Token::Kill => return Ok(()),
+                    Token::Listener => self.accept_connections()?,
+                    Token::Pending { id } => self.read_connect_command(id)?,
+                    Token::Connection { id } => {
+                        if event.is_writable {
+                            self.flush_tx(id)?;
+                        }
+                        if event.is_readable || event.is_hungup {
+                            self.forward_to_guest(id)?;
+                        }
+                    }
+                }
+            }
+            if self.needs_rx_interrupt {
+                self.needs_rx_interrupt = false;
+                self.rx_queue.trigger_interrupt(&self.mem, &self.interrupt);
+            }
+            if self.needs_tx_interrupt {
+                self.needs_tx_interrupt = false;
+                self.tx_queue.trigger_interrupt(&self.mem, &self.interrupt);
+            }
+        }
+    }
+}
+
+/// Virtio vsock device forwarding guest connections to unix domain sockets on the host.
+pub struct UserspaceVsock {
+    guest_cid: u64,
+    uds_path: PathBuf,
+    features: u64,
+    listener: Option<UnixListener>,
+    worker_thread: Option<WorkerThread<UnixListener>>,
+}
+
+impl UserspaceVsock {
+    /// Creates a vsock device for a guest with CID `guest_cid`. Host applications connect to the
+    /// guest through the unix socket bound at `uds_path`, and guest connections to port `P` are
+    /// forwarded to `<uds_path>_<P>`.
+    pub fn new(base_features: u64, guest_cid: u64, uds_path: &Path) -> anyhow::Result<Self> {
+        let listener = UnixListener::bind(uds_path)
+            .with_context(|| format!("failed to bind vsock socket {}", uds_path.display()))?;
+        listener
+            .set_nonblocking(true)
+            .context("failed to set vsock socket non-blocking")?;
+
+        Ok(UserspaceVsock {
+            guest_cid,
+            uds_path: uds_path.to_path_buf(),
+            features: base_features,
+            listener: Some(listener),
+            worker_thread: None,
+        })
+    }
+}
+
+impl VirtioDevice for UserspaceVsock {
+    fn keep_rds(&self) -> Vec<RawDescriptor> {
+        self.listener
+            .iter()
+            .map(|listener| listener.as_raw_descriptor())
+            .collect()
+    }
+
+    fn device_type(&self) -> DeviceType {
+        DeviceType::Vsock
+    }
+
+    fn queue_max_sizes(&self) -> &[u16] {
+        QUEUE_SIZES
+    }
+
+    fn features(&self) -> u64 {
+        self.features
+    }
+
+    fn read_config(&self, offset: u64, data: &mut [u8]) {
+        let config = virtio_vsock_config {
+            guest_cid: Le64::from(self.guest_cid),
+        };
+        copy_config(data, 0, config.as_bytes(), offset);
+    }
+
+    fn activate(
+        &mut self,
+        mem: GuestMemory,
+        interrupt: Interrupt,
+        mut queues: Vec<(Queue, Event)>,
+    ) -> anyhow::Result<()> {
+        if queues.len() != QUEUE_SIZES.len() {
+            return Err(anyhow!(
+                "expected {} queues, got {}",
+                QUEUE_SIZES.len(),
+                queues.len()
+            ));
+        }
+        let wait_ctx = WaitContext::new().context("failed to create WaitContext")?;
+        let listener = self
+            .listener
+            .take()
+            .context("vsock socket is in use by another worker")?;
+
+        // The event queue is only used for transport resets, which this device never issues.
+        let rx_queue = queues.remove(0);
+        let tx_queue = queues.remove(0);
+        let guest_cid = self.guest_cid;
+        let uds_path = self.uds_path.clone();
+
+        self.worker_thread = Some(WorkerThread::start("v_vsock", move |kill_evt| {
+            let mut worker = Worker::new(
+                mem, interrupt, rx_queue, tx_queue, guest_cid, uds_path, listener, wait_ctx,
+            );
+            if let Err(e) = worker.run(&kill_evt) {
+                error!("vsock worker failed: {:#}", e);
+            }
+            worker.listener
+        }));
+
+        Ok(())
+    }
+
+    fn reset(&mut self) -> bool {
+        if let Some(worker_thread) = self.worker_thread.take() {
+            self.listener = Some(worker_thread.stop());
+            return true;
+        }
+        false
+    }
+}
+
+impl Suspendable for UserspaceVsock {}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::tempdir;
+    use vm_memory::GuestAddress;
+
+    use super::*;
+    use crate::virtio::VIRTIO_MSI_NO_VECTOR;
+    use crate::IrqLevelEvent;
+
+    const QUEUE_SIZE: u16 = 64;
+    // Layout of a test queue in guest memory, from the start of the queue.
+    const DESC_TABLE: u64 = 0x0;
+    const AVAIL_RING: u64 = 0x1000;
+    const USED_RING: u64 = 0x2000;
+    const BUFFERS: u64 = 0x3000;
+    const BUFFER_SIZE: u64 = 0x100;
+    const RX_QUEUE: u64 = 0x0;
+    const TX_QUEUE: u64 = 0x10000;
+    const GUEST_CID: u64 = 3;
+
+    fn test_queue(base: u64) -> Queue {
+        let mut queue = Queue::new(QUEUE_SIZE);
+        queue.set_desc_table(GuestAddress(base + DESC_TABLE));
+        queue.set_avail_ring(GuestAddress(base + AVAIL_RING));
+        queue.set_used_ring(GuestAddress(base + USED_RING));
+        queue.set_ready(true);
+        queue
+    }
+
+    // Makes the `avail_idx`th buffer of the queue at `base` available to the device. The buffer
+    // holds `data` if there is some, or is writable by the device otherwise.
+    fn add_buffer(mem: &GuestMemory, base: u64, avail_idx: u16, data: Option<&[u8]>) {
+        let slot = avail_idx % QUEUE_SIZE;
+        let addr = base + BUFFERS + slot as u64 * BUFFER_SIZE;
+        let (len, flags) = match data {
+            Some(data) => {
+                mem.write_all_at_addr(data, GuestAddress(addr)).unwrap();
+                (data.len() as u32, 0u16)
+            }
+            // VIRTQ_DESC_F_WRITE
+            None => (BUFFER_SIZE as u32, 2u16),
+        };
+        let desc = base + DESC_TABLE + slot as u64 * 16;
+        mem.write_obj_at_addr(Le64::from(addr), GuestAddress(desc))
+            .unwrap();
+        mem.write_obj_at_addr(Le32::from(len), GuestAddress(desc + 8))
+            .unwrap();
+        mem.write_obj_at_addr(Le16::from(flags), GuestAddress(desc + 12))
+            .unwrap();
+        mem.write_obj_at_addr(
+            Le16::from(slot),
+            GuestAddress(base + AVAIL_RING + 4 + slot as u64 * 2),
+        )
+        .unwrap();
+        mem.write_obj_at_addr(
+            Le16::from(avail_idx.wrapping_add(1)),
+            GuestAddress(base + AVAIL_RING + 2),
+        )
+        .unwrap();
+    }
+
+    fn guest_packet(local: u32, peer: u32, op: u16) -> virtio_vsock_hdr {
+        virtio_vsock_hdr {
+            src_cid: Le64::from(GUEST_CID),
+            dst_cid: Le64::from(HOST_CID),
+            src_port: Le32::from(peer),
+            dst_port: Le32::from(local),
+            r#type: Le16::from(TYPE_STREAM_SOCKET),
+            op: Le16::from(op),
+            buf_alloc: Le32::from(CONN_BUF_ALLOC),
+            ..Default::default()
+        }
+    }
+
+    fn pending_ops(worker: &Worker, op: u16) -> usize {
+        worker
+            .rx_pending
+            .iter()
+            .filter(|hdr| u16::from(hdr.op) == op)
+            .count()
+    }
+
+    #[test]
+    fn connect_command() {
+        assert_eq!(parse_connect_command(b"CONNECT 1234"), Some(1234));
+        assert_eq!(parse_connect_command(b"CONNECT 52\r"), Some(52));
+        assert_eq!(parse_connect_command(b"CONNECT"), None);
+        assert_eq!(parse_connect_command(b"CONNECT foo"), None);
+        assert_eq!(parse_connect_command(b"connect 1234"), None);
+        assert_eq!(parse_connect_command(b"CONNECT 4294967296"), None);
+    }
+
+    #[test]
+    fn guest_port_path() {
+        assert_eq!(
+            port_path(Path::new("/run/vm/vsock.sock"), 52),
+            PathBuf::from("/run/vm/vsock.sock_52")
+        );
+    }
+
+    #[test]
+    fn credit() {
+        let (stream, _peer) = UnixStream::pair().unwrap();
+        let mut conn =
+            Connection::new(stream, PortPair { local: 2, peer: 3 }, ConnState::Connected);
+        assert_eq!(conn.peer_credit(), 0);
+        assert_eq!(conn.wanted_events(true), EventType::None);
+
+        conn.peer_buf_alloc = 4096;
+        conn.rx_cnt = Wrapping(1024);
+        assert_eq!(conn.peer_credit(), 3072);
+        assert_eq!(conn.wanted_events(true), EventType::Read);
+        assert_eq!(conn.wanted_events(false), EventType::None);
+
+        conn.peer_fwd_cnt = Wrapping(1024);
+        assert_eq!(conn.peer_credit(), 4096);
+
+        // Counters wrap around.
+        conn.rx_cnt = Wrapping(10);
+        conn.peer_fwd_cnt = Wrapping(u32::MAX - 9);
+        assert_eq!(conn.peer_credit(), 4076);
+    }
+
+    #[test]
+    fn credit_update() {
+        let (stream, mut peer) = UnixStream::pair().unwrap();
+        stream.set_nonblocking(true).unwrap();
+        let mut conn =
+            Connection::new(stream, PortPair { local: 2, peer: 3 }, ConnState::Connected);
+
+        conn.tx_buf.extend(vec![0xa5; 1024]);
+        conn.flush_tx().unwrap();
+        assert!(conn.tx_buf.is_empty());
+        assert_eq!(conn.fwd_cnt, Wrapping(1024));
+        assert!(!conn.needs_credit_update());
+
+        let mut buf = vec![0; 1024];
+        peer.read_exact(&mut buf).unwrap();
+        assert!(buf.iter().all(|b| *b == 0xa5));
+
+        conn.fwd_cnt += Wrapping(CONN_BUF_ALLOC / 2);
+        assert!(conn.needs_credit_update());
+        let hdr = conn.header(3, vsock_op::VIRTIO_VSOCK_OP_CREDIT_UPDATE);
+        assert_eq!(u32::from(hdr.fwd_cnt), conn.fwd_cnt.0);
+        assert!(!conn.needs_credit_update());
+    }
+
+    #[test]
+    fn stalled_rx_queue() {
+        let mem = GuestMemory::new(&[(GuestAddress(0), 0x20000)]).unwrap();
+        let dir = tempdir().unwrap();
+        let uds_path = dir.path().join("vsock.sock");
+        let listener = UnixListener::bind(&uds_path).unwrap();
+        let mut worker = Worker::new(
+            mem.clone(),
+            Interrupt::new(IrqLevelEvent::new().unwrap(), None, VIRTIO_MSI_NO_VECTOR),
+            (test_queue(RX_QUEUE), Event::new().unwrap()),
+            (test_queue(TX_QUEUE), Event::new().unwrap()),
+            GUEST_CID,
+            uds_path,
+            listener,
+            WaitContext::new().unwrap(),
+        );
+        let (stream, _peer) = UnixStream::pair().unwrap();
+        let ports = PortPair {
+            local: 1024,
+            peer: 2048,
+        };
+        worker.add_connection(Connection::new(stream, ports, ConnState::Connected));
+
+        // The guest provides no rx descriptors. Its credit requests get a single credit update,
+        // and its packets to unknown ports a reset each.
+        let mut tx_idx = 0;
+        for _ in 0..20 {
+            let hdr = guest_packet(
+                ports.local,
+                ports.peer,
+                vsock_op::VIRTIO_VSOCK_OP_CREDIT_REQUEST,
+            );
+            add_buffer(&mem, TX_QUEUE, tx_idx, Some(hdr.as_bytes()));
+            tx_idx += 1;
+        }
+        for port in 0..44 {
+            let hdr = guest_packet(port, ports.peer, vsock_op::VIRTIO_VSOCK_OP_RW);
+            add_buffer(&mem, TX_QUEUE, tx_idx, Some(hdr.as_bytes()));
+            tx_idx += 1;
+        }
+        worker.process_tx_queue().unwrap();
+        assert_eq!(worker.rx_pending.len(), 45);
+        assert_eq!(
+            pending_ops(&worker, vsock_op::VIRTIO_VSOCK_OP_CREDIT_UPDATE),
+            1
+        );
+
+        // Guest packets are left in the tx queue once the replies fill the rx queue.
+        for port in 44..74 {
+            let hdr = guest_packet(port, ports.peer, vsock_op::VIRTIO_VSOCK_OP_RW);
+            add_buffer(&mem, TX_QUEUE, tx_idx, Some(hdr.as_bytes()));
+            tx_idx += 1;
+        }
+        worker.process_tx_queue().unwrap();
+        assert_eq!(worker.rx_pending.len(), QUEUE_SIZE as usize);
+        assert!(worker.tx_queue.peek(&mem).is_some());
+
+        // They are processed once the guest provides rx descriptors.
+        for rx_idx in 0..QUEUE_SIZE {
+            add_buffer(&mem, RX_QUEUE, rx_idx, None);
+        }
+        worker.process_rx_queue().unwrap();
+        assert!(worker.tx_queue.peek(&mem).is_none());
+        assert_eq!(worker.rx_pending.len(), 11);
+        assert_eq!(pending_ops(&worker, vsock_op::VIRTIO_VSOCK_OP_RST), 11);
+    }
+}
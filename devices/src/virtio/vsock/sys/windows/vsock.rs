@@ -53,11 +53,11 @@ use zerocopy::FromBytes;
 
 use crate::virtio::async_utils;
 use crate::virtio::copy_config;
-use crate::virtio::vsock::sys::windows::protocol::virtio_vsock_config;
-use crate::virtio::vsock::sys::windows::protocol::virtio_vsock_event;
-use crate::virtio::vsock::sys::windows::protocol::virtio_vsock_hdr;
-use crate::virtio::vsock::sys::windows::protocol::vsock_op;
-use crate::virtio::vsock::sys::windows::protocol::TYPE_STREAM_SOCKET;
+use crate::virtio::vsock::protocol::virtio_vsock_config;
+use crate::virtio::vsock::protocol::virtio_vsock_event;
+use crate::virtio::vsock::protocol::virtio_vsock_hdr;
+use crate::virtio::vsock::protocol::vsock_op;
+use crate::virtio::vsock::protocol::TYPE_STREAM_SOCKET;
 use crate::virtio::DescriptorChain;
 use crate::virtio::DeviceType;
 use crate::virtio::Interrupt;
@@ -19,6 +19,7 @@ use base::AsRawDescriptor;
 use base::FileAllocate;
 use base::FileReadWriteAtVolatile;
 use base::FileSetLen;
+use base::FileSync;
 use base::RawDescriptor;
 use cros_async::BackingMemory;
 use cros_async::Executor;
@@ -247,6 +248,13 @@ impl FileSetLen for AndroidSparse {
     }
 }
 
+impl FileSync for AndroidSparse {
+    fn fsync(&mut self) -> io::Result<()> {
+        // Do nothing because it's read-only.
+        Ok(())
+    }
+}
+
 impl AsRawDescriptor for AndroidSparse {
     fn as_raw_descriptor(&self) -> RawDescriptor {
         self.file.as_raw_descriptor()
@@ -27,6 +27,7 @@ use base::AsRawDescriptors;
 use base::FileAllocate;
 use base::FileReadWriteAtVolatile;
 use base::FileSetLen;
+use base::FileSync;
 use base::RawDescriptor;
 use crc32fast::Hasher;
 use cros_async::BackingMemory;
@@ -305,6 +306,18 @@ impl FileSetLen for CompositeDiskFile {
     }
 }
 
+impl FileSync for CompositeDiskFile {
+    fn fsync(&mut self) -> io::Result<()> {
+        for disk in self.component_disks.iter_mut() {
+            if disk.needs_fsync {
+                disk.file.fsync()?;
+                disk.needs_fsync = false;
+            }
+        }
+        Ok(())
+    }
+}
+
 // Implements Read and Write targeting volatile storage for composite disks.
 //
 // Note that reads and writes will return early if crossing component disk boundaries.
@@ -21,6 +21,7 @@ use base::AsRawDescriptors;
 use base::FileAllocate;
 use base::FileReadWriteAtVolatile;
 use base::FileSetLen;
+use base::FileSync;
 use base::PunchHole;
 use cros_async::AllocateMode;
 use cros_async::BackingMemory;
@@ -161,7 +162,14 @@ impl<T: PunchHole> PunchHoleMut for T {
 
 /// The prerequisites necessary to support a block device.
 pub trait DiskFile:
-    FileSetLen + DiskGetLen + FileReadWriteAtVolatile + ToAsyncDisk + Send + AsRawDescriptors + Debug
+    FileSetLen
+    + DiskGetLen
+    + FileSync
+    + FileReadWriteAtVolatile
+    + ToAsyncDisk
+    + Send
+    + AsRawDescriptors
+    + Debug
 {
     /// Creates a new DiskFile instance that shares the same underlying disk file image. IO
     /// operations to a DiskFile should affect all DiskFile instances with the same underlying disk
@@ -55,8 +55,8 @@ fn do_playback() {
 fn compare_files(temp_dir: TempDir, golden_file_name: &str, output_file_name: &str) -> bool {
     // 1 second, 2 channels, 16 bit (2 byte) format, 48000 frame rate.
     const BYTES_TO_COMPARE: usize = 1 * 2 * 2 * 48000;
-    // Skip the first buffer-size bytes as it's 0 pads.
-    const SKIP_OFFSET: usize = 48000;
+    // Skip the 44 bytes of the WAV header and the first buffer-size bytes as it's 0 pads.
+    const SKIP_OFFSET: usize = 44 + 48000;
 
     // Open the second file for reading
     let buf1 = fs::read(temp_dir.path().join(golden_file_name)).unwrap();
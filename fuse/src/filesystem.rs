@@ -9,8 +9,11 @@ use std::io;
 use std::mem;
 use std::time::Duration;
 
+use base::Event;
+
 use crate::server::Mapper;
 use crate::sys;
+pub use crate::sys::FileLock;
 pub use crate::sys::FsOptions;
 pub use crate::sys::IoctlFlags;
 pub use crate::sys::IoctlIovec;
@@ -1096,18 +1099,69 @@ pub trait FileSystem {
         Err(io::Error::from_raw_os_error(libc::ENOSYS))
     }
 
-    /// TODO: support this
-    fn getlk(&self) -> io::Result<()> {
+    /// Test for a file lock.
+    ///
+    /// Returns a lock held by a different owner that conflicts with `lock`, or `lock` with its
+    /// `type_` set to `F_UNLCK` if there is no such lock.
+    ///
+    /// `handle` is the `Handle` returned by the file system from the `open` method, if any. If the
+    /// file system did not return a `Handle` from `open` then the contents of `handle` are
+    /// undefined.
+    ///
+    /// `owner` identifies the owner of the lock in the client. `flock` is true for BSD style locks
+    /// created with `flock()` rather than POSIX record locks.
+    ///
+    /// This method is only called if the `FsOptions::POSIX_LOCKS` feature is enabled.
+    fn getlk(
+        &self,
+        ctx: Context,
+        inode: Self::Inode,
+        handle: Self::Handle,
+        owner: u64,
+        lock: FileLock,
+        flock: bool,
+    ) -> io::Result<FileLock> {
         Err(io::Error::from_raw_os_error(libc::ENOSYS))
     }
 
-    /// TODO: support this
-    fn setlk(&self) -> io::Result<()> {
+    /// Acquire, modify, or release a file lock.
+    ///
+    /// If `lock` conflicts with a lock held by a different owner, this method must fail with
+    /// `EAGAIN` rather than wait for the conflicting lock to be released. See `getlk` for the
+    /// meaning of the other arguments.
+    ///
+    /// This method is only called if the `FsOptions::POSIX_LOCKS` feature is enabled or, for BSD
+    /// style locks, if the `FsOptions::FLOCK_LOCKS` feature is enabled.
+    fn setlk(
+        &self,
+        ctx: Context,
+        inode: Self::Inode,
+        handle: Self::Handle,
+        owner: u64,
+        lock: FileLock,
+        flock: bool,
+    ) -> io::Result<()> {
         Err(io::Error::from_raw_os_error(libc::ENOSYS))
     }
 
-    /// TODO: support this
-    fn setlkw(&self) -> io::Result<()> {
+    /// Acquire, modify, or release a file lock, waiting for conflicting locks to be released.
+    ///
+    /// This is the same as `setlk` except that it may block for an arbitrary amount of time.
+    /// Users of `Server` that want to keep handling other requests in the meantime can use
+    /// `Server::may_block` to find the messages that should be handled on a separate thread.
+    ///
+    /// The wait must be abandoned with `EINTR` once `interrupt` is signaled, which happens when the
+    /// client interrupts the request or when the server stops.
+    fn setlkw(
+        &self,
+        ctx: Context,
+        inode: Self::Inode,
+        handle: Self::Handle,
+        owner: u64,
+        lock: FileLock,
+        flock: bool,
+        interrupt: &Event,
+    ) -> io::Result<()> {
         Err(io::Error::from_raw_os_error(libc::ENOSYS))
     }
 
@@ -4,6 +4,7 @@
 
 use std::cmp::max;
 use std::cmp::min;
+use std::collections::BTreeMap;
 use std::convert::TryInto;
 use std::ffi::CStr;
 use std::io;
@@ -14,7 +15,9 @@ use std::time::Duration;
 
 use base::error;
 use base::pagesize;
+use base::Event;
 use data_model::zerocopy_from_reader;
+use sync::Mutex;
 use zerocopy::AsBytes;
 
 use crate::filesystem::Context;
@@ -134,18 +137,75 @@ impl<'a, M: Mapper> Mapper for &'a M {
 
 pub struct Server<F: FileSystem + Sync> {
     fs: F,
+    // Events interrupting the blocking requests being handled, by `unique` of the request.
+    interrupts: Mutex<BTreeMap<u64, Event>>,
 }
 
 impl<F: FileSystem + Sync> Server<F> {
     pub fn new(fs: F) -> Server<F> {
-        Server { fs }
+        Server {
+            fs,
+            interrupts: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    /// Returns true if handling the message read from `r` may block for an arbitrary amount of
+    /// time, e.g. because it waits for a file lock to be released.
+    ///
+    /// This consumes the header of the message so `r` should be a copy of the reader that is
+    /// later passed to `handle_message`.
+    pub fn may_block<R: Reader>(&self, mut r: R) -> bool {
+        match zerocopy_from_reader::<_, InHeader>(&mut r) {
+            Ok(in_header) => matches!(Opcode::n(in_header.opcode), Some(Opcode::Setlkw)),
+            Err(_) => false,
+        }
+    }
+
+    /// Replies to the message read from `r` with the error `e` without handling it.
+    pub fn reject_message<R: Reader, W: Writer>(
+        &self,
+        mut r: R,
+        w: W,
+        e: io::Error,
+    ) -> Result<usize> {
+        let in_header: InHeader = zerocopy_from_reader(&mut r).map_err(Error::DecodeMessage)?;
+        reply_error(e, in_header.unique, w)
     }
 
     pub fn handle_message<R: Reader + ZeroCopyReader, W: Writer + ZeroCopyWriter, M: Mapper>(
+        &self,
+        r: R,
+        w: W,
+        mapper: M,
+    ) -> Result<usize> {
+        self.do_handle_message(r, w, mapper, None)
+    }
+
+    /// Handles a message for which `may_block` returned true, like `handle_message`.
+    ///
+    /// Signaling `interrupt` makes the message fail with `EINTR` if it is still waiting, like a
+    /// `FUSE_INTERRUPT` request for the message does. This lets the caller wake up the thread
+    /// handling the message when it stops processing requests.
+    pub fn handle_blocking_message<
+        R: Reader + ZeroCopyReader,
+        W: Writer + ZeroCopyWriter,
+        M: Mapper,
+    >(
+        &self,
+        r: R,
+        w: W,
+        mapper: M,
+        interrupt: &Event,
+    ) -> Result<usize> {
+        self.do_handle_message(r, w, mapper, Some(interrupt))
+    }
+
+    fn do_handle_message<R: Reader + ZeroCopyReader, W: Writer + ZeroCopyWriter, M: Mapper>(
         &self,
         mut r: R,
         w: W,
         mapper: M,
+        interrupt: Option<&Event>,
     ) -> Result<usize> {
         let in_header: InHeader = zerocopy_from_reader(&mut r).map_err(Error::DecodeMessage)?;
         cros_tracing::trace_simple_print!("fuse server: handle_message: in_header={:?}", in_header);
@@ -190,10 +250,10 @@ impl<F: FileSystem + Sync> Server<F> {
             Some(Opcode::Fsyncdir) => self.fsyncdir(in_header, r, w),
             Some(Opcode::Getlk) => self.getlk(in_header, r, w),
             Some(Opcode::Setlk) => self.setlk(in_header, r, w),
-            Some(Opcode::Setlkw) => self.setlkw(in_header, r, w),
+            Some(Opcode::Setlkw) => self.setlkw(in_header, r, w, interrupt),
             Some(Opcode::Access) => self.access(in_header, r, w),
             Some(Opcode::Create) => self.create(in_header, r, w),
-            Some(Opcode::Interrupt) => self.interrupt(in_header),
+            Some(Opcode::Interrupt) => self.interrupt(in_header, r, w),
             Some(Opcode::Bmap) => self.bmap(in_header, r, w),
             Some(Opcode::Destroy) => self.destroy(),
             Some(Opcode::Ioctl) => self.ioctl(in_header, r, w),
@@ -1251,27 +1311,89 @@ impl<F: FileSystem + Sync> Server<F> {
         }
     }
 
-    fn getlk<R: Reader, W: Writer>(&self, in_header: InHeader, mut _r: R, w: W) -> Result<usize> {
-        if let Err(e) = self.fs.getlk() {
-            reply_error(e, in_header.unique, w)
-        } else {
-            Ok(0)
+    fn getlk<R: Reader, W: Writer>(&self, in_header: InHeader, mut r: R, w: W) -> Result<usize> {
+        let LkIn {
+            fh,
+            owner,
+            lk,
+            lk_flags,
+            ..
+        } = zerocopy_from_reader(&mut r).map_err(Error::DecodeMessage)?;
+
+        match self.fs.getlk(
+            Context::from(in_header),
+            in_header.nodeid.into(),
+            fh.into(),
+            owner,
+            lk,
+            lk_flags & LK_FLOCK != 0,
+        ) {
+            Ok(lk) => reply_ok(Some(LkOut { lk }), None, in_header.unique, w),
+            Err(e) => reply_error(e, in_header.unique, w),
         }
     }
 
-    fn setlk<R: Reader, W: Writer>(&self, in_header: InHeader, mut _r: R, w: W) -> Result<usize> {
-        if let Err(e) = self.fs.setlk() {
-            reply_error(e, in_header.unique, w)
-        } else {
-            Ok(0)
-        }
+    fn setlk<R: Reader, W: Writer>(&self, in_header: InHeader, r: R, w: W) -> Result<usize> {
+        self.do_setlk(in_header, r, w, None)
     }
 
-    fn setlkw<R: Reader, W: Writer>(&self, in_header: InHeader, mut _r: R, w: W) -> Result<usize> {
-        if let Err(e) = self.fs.setlkw() {
-            reply_error(e, in_header.unique, w)
+    fn setlkw<R: Reader, W: Writer>(
+        &self,
+        in_header: InHeader,
+        r: R,
+        w: W,
+        interrupt: Option<&Event>,
+    ) -> Result<usize> {
+        let local_interrupt;
+        let interrupt = match interrupt {
+            Some(interrupt) => interrupt,
+            None => {
+                local_interrupt = match Event::new() {
+                    Ok(event) => event,
+                    Err(e) => return reply_error(e.into(), in_header.unique, w),
+                };
+                &local_interrupt
+            }
+        };
+        let registered = match interrupt.try_clone() {
+            Ok(event) => event,
+            Err(e) => return reply_error(e.into(), in_header.unique, w),
+        };
+
+        self.interrupts.lock().insert(in_header.unique, registered);
+        let res = self.do_setlk(in_header, r, w, Some(interrupt));
+        self.interrupts.lock().remove(&in_header.unique);
+        res
+    }
+
+    fn do_setlk<R: Reader, W: Writer>(
+        &self,
+        in_header: InHeader,
+        mut r: R,
+        w: W,
+        interrupt: Option<&Event>,
+    ) -> Result<usize> {
+        let LkIn {
+            fh,
+            owner,
+            lk,
+            lk_flags,
+            ..
+        } = zerocopy_from_reader(&mut r).map_err(Error::DecodeMessage)?;
+
+        let ctx = Context::from(in_header);
+        let inode = in_header.nodeid.into();
+        let flock = lk_flags & LK_FLOCK != 0;
+        let res = if let Some(interrupt) = interrupt {
+            self.fs
+                .setlkw(ctx, inode, fh.into(), owner, lk, flock, interrupt)
         } else {
-            Ok(0)
+            self.fs.setlk(ctx, inode, fh.into(), owner, lk, flock)
+        };
+
+        match res {
+            Ok(()) => reply_ok(None::<u8>, None, in_header.unique, w),
+            Err(e) => reply_error(e, in_header.unique, w),
         }
     }
 
@@ -1343,9 +1465,28 @@ impl<F: FileSystem + Sync> Server<F> {
         }
     }
 
-    #[allow(clippy::unnecessary_wraps)]
-    fn interrupt(&self, _in_header: InHeader) -> Result<usize> {
-        Ok(0)
+    fn interrupt<R: Reader, W: Writer>(
+        &self,
+        in_header: InHeader,
+        mut r: R,
+        w: W,
+    ) -> Result<usize> {
+        let InterruptIn { unique } = zerocopy_from_reader(&mut r).map_err(Error::DecodeMessage)?;
+
+        match self.interrupts.lock().get(&unique) {
+            // The interrupted request replies with `EINTR`, the interrupt itself gets no reply.
+            Some(interrupt) => match interrupt.signal() {
+                Ok(()) => Ok(0),
+                Err(e) => reply_error(e.into(), in_header.unique, w),
+            },
+            // The request may not have been seen yet, so ask the client to send the interrupt
+            // again later. The client drops it once the request is answered.
+            None => reply_error(
+                io::Error::from_raw_os_error(libc::EAGAIN),
+                in_header.unique,
+                w,
+            ),
+        }
     }
 
     fn bmap<R: Reader, W: Writer>(&self, in_header: InHeader, mut _r: R, w: W) -> Result<usize> {
@@ -1873,7 +2014,14 @@ where
 
 #[cfg(test)]
 mod tests {
+    use std::fs::File;
+    use std::io::Cursor;
+    use std::thread;
+
+    use zerocopy::FromBytes;
+
     use super::*;
+    use crate::filesystem::FileLock;
 
     #[test]
     fn split_inclusive_basic() {
@@ -1903,4 +2051,180 @@ mod tests {
         assert_eq!(iter.next().unwrap(), &slice);
         assert!(iter.next().is_none());
     }
+
+    // A file system whose lock requests wait until they are interrupted.
+    struct LockFs;
+
+    impl FileSystem for LockFs {
+        type Inode = u64;
+        type Handle = u64;
+        type DirIter = NullIter;
+
+        fn setlkw(
+            &self,
+            _ctx: Context,
+            _inode: u64,
+            _handle: u64,
+            _owner: u64,
+            _lock: FileLock,
+            _flock: bool,
+            interrupt: &Event,
+        ) -> io::Result<()> {
+            interrupt.wait()?;
+            Err(io::Error::from_raw_os_error(libc::EINTR))
+        }
+    }
+
+    struct NullIter;
+
+    impl DirectoryIterator for NullIter {
+        fn next(&mut self) -> Option<DirEntry> {
+            None
+        }
+    }
+
+    struct NullMapper;
+
+    impl Mapper for NullMapper {
+        fn map(
+            &self,
+            _mem_offset: u64,
+            _size: usize,
+            _fd: &dyn AsRawFd,
+            _file_offset: u64,
+            _prot: u32,
+        ) -> io::Result<()> {
+            Err(io::Error::from_raw_os_error(libc::EOPNOTSUPP))
+        }
+
+        fn unmap(&self, _offset: u64, _size: u64) -> io::Result<()> {
+            Err(io::Error::from_raw_os_error(libc::EOPNOTSUPP))
+        }
+    }
+
+    impl Reader for Cursor<Vec<u8>> {}
+
+    impl ZeroCopyReader for Cursor<Vec<u8>> {
+        fn read_to(&mut self, _f: &mut File, _count: usize, _off: u64) -> io::Result<usize> {
+            Err(io::Error::from_raw_os_error(libc::EOPNOTSUPP))
+        }
+    }
+
+    impl Writer for Vec<u8> {
+        type ClosureWriter = Self;
+
+        fn write_at<F>(&mut self, _offset: usize, f: F) -> io::Result<usize>
+        where
+            F: Fn(&mut Self) -> io::Result<usize>,
+        {
+            f(self)
+        }
+
+        fn has_sufficient_buffer(&self, _size: u32) -> bool {
+            true
+        }
+    }
+
+    impl ZeroCopyWriter for Vec<u8> {
+        fn write_from(&mut self, _f: &mut File, _count: usize, _off: u64) -> io::Result<usize> {
+            Err(io::Error::from_raw_os_error(libc::EOPNOTSUPP))
+        }
+    }
+
+    // Handles the message made of `opcode` and `arg` and returns the error of the reply, if any.
+    fn request<T: AsBytes>(
+        server: &Server<LockFs>,
+        opcode: Opcode,
+        unique: u64,
+        arg: T,
+        interrupt: Option<&Event>,
+    ) -> Option<i32> {
+        let in_header = InHeader {
+            len: (size_of::<InHeader>() + size_of::<T>()) as u32,
+            opcode: opcode as u32,
+            unique,
+            ..Default::default()
+        };
+        let mut message = in_header.as_bytes().to_vec();
+        message.extend_from_slice(arg.as_bytes());
+
+        let mut reply = Vec::new();
+        match interrupt {
+            Some(interrupt) => server.handle_blocking_message(
+                Cursor::new(message),
+                &mut reply,
+                NullMapper,
+                interrupt,
+            ),
+            None => server.handle_message(Cursor::new(message), &mut reply, NullMapper),
+        }
+        .unwrap();
+        OutHeader::read_from_prefix(&reply[..]).map(|out_header| {
+            assert_eq!(out_header.unique, unique);
+            out_header.error
+        })
+    }
+
+    #[test]
+    fn interrupt_setlkw() {
+        let server = Server::new(LockFs);
+        let lock_in = LkIn {
+            lk: FileLock {
+                type_: libc::F_WRLCK as u32,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        thread::scope(|s| {
+            let waiter = s.spawn(|| request(&server, Opcode::Setlkw, 7, lock_in, None));
+            // Until the lock request is seen, the client is asked to send the interrupt again.
+            loop {
+                match request(
+                    &server,
+                    Opcode::Interrupt,
+                    8,
+                    InterruptIn { unique: 7 },
+                    None,
+                ) {
+                    None => break,
+                    Some(error) => assert_eq!(error, -libc::EAGAIN),
+                }
+                thread::yield_now();
+            }
+            assert_eq!(waiter.join().unwrap(), Some(-libc::EINTR));
+        });
+
+        // The request is forgotten once answered.
+        assert_eq!(
+            request(
+                &server,
+                Opcode::Interrupt,
+                9,
+                InterruptIn { unique: 7 },
+                None
+            ),
+            Some(-libc::EAGAIN)
+        );
+    }
+
+    #[test]
+    fn stop_setlkw() {
+        let server = Server::new(LockFs);
+        let interrupt = Event::new().unwrap();
+
+        thread::scope(|s| {
+            let waiter = s.spawn(|| {
+                request(
+                    &server,
+                    Opcode::Setlkw,
+                    7,
+                    LkIn::default(),
+                    Some(&interrupt),
+                )
+            });
+            interrupt.signal().unwrap();
+            assert_eq!(waiter.join().unwrap(), Some(-libc::EINTR));
+        });
+    }
 }
@@ -0,0 +1,700 @@
+// Copyright 2023 The ChromiumOS Authors
+// Use of this source code is governed by a BSD-style license that can be
+// found in the LICENSE file.
+
+//! Headless display backend controlled over a unix socket.
+//!
+//! The latest framebuffer of every scanout is kept in memory, and clients of the control socket can
+//! wait for new frames, read their pixels and inject keyboard, mouse and touch input. This lets
+//! test harnesses drive graphical guests without a compositor.
+//!
+//! Requests are single text lines of space separated words, and every request gets exactly one
+//! response line, starting with `ok` or `error`, in the order the requests were sent:
+//!
+//! - `frame SCANOUT [SERIAL]`: waits until the scanout shows a frame newer than `SERIAL`, or any
+//!   frame if it is omitted, and replies `ok SERIAL WIDTH HEIGHT`.
+//! - `pixels SCANOUT`: replies `ok SERIAL WIDTH HEIGHT SIZE` followed by `SIZE` bytes of pixels in
+//!   the XRGB8888 format (B, G, R, X in memory), row by row.
+//! - `key SCANOUT CODE 0|1`: releases or presses the key with the given Linux key code.
+//! - `mouse-move SCANOUT DX DY`: moves the mouse by a relative amount.
+//! - `mouse-button SCANOUT CODE 0|1`: releases or presses a mouse button (e.g. 272 for BTN_LEFT).
+//! - `touch SCANOUT X Y 0|1`: moves a single touch to `X`,`Y`, or lifts it.
+//!
+//! Requests aren't read while a client has `MAX_PENDING_OUTPUT` bytes of responses or more left to
+//! read, so a client sending `pixels` requests without reading the responses can't make the
+//! server buffer an unbounded number of frames.
+
+use std::collections::BTreeMap;
+use std::collections::VecDeque;
+use std::io;
+use std::io::Read;
+use std::io::Write;
+use std::os::unix::net::UnixListener;
+use std::os::unix::net::UnixStream;
+use std::sync::Arc;
+
+use anyhow::Context;
+use base::error;
+use base::Event;
+use base::EventToken;
+use base::EventType;
+use base::WaitContext;
+use linux_input_sys::virtio_input_event;
+
+use crate::scanout_display::ScanoutDisplay;
+use crate::scanout_display::ScanoutState;
+use crate::scanout_display::Shared;
+use crate::EventDeviceKind;
+use crate::GpuDisplayEvents;
+use crate::GpuDisplayResult;
+
+const MAX_REQUEST_SIZE: usize = 4096;
+const MAX_PENDING_OUTPUT: usize = 16 << 20;
+
+/// A parsed control request.
+#[derive(Debug, PartialEq, Eq)]
+enum Request {
+    Frame {
+        scanout_id: u32,
+        after: Option<u64>,
+    },
+    Pixels {
+        scanout_id: u32,
+    },
+    Key {
+        scanout_id: u32,
+        code: u16,
+        pressed: bool,
+    },
+    MouseMove {
+        scanout_id: u32,
+        dx: i32,
+        dy: i32,
+    },
+    MouseButton {
+        scanout_id: u32,
+        code: u16,
+        pressed: bool,
+    },
+    Touch {
+        scanout_id: u32,
+        x: i32,
+        y: i32,
+        down: bool,
+    },
+}
+
+fn parse_request(line: &str) -> Result<Request, String> {
+    let mut words = line.split_whitespace();
+    let command = words.next().ok_or("empty request")?;
+    let args: Vec<&str> = words.collect();
+
+    fn arg<T: std::str::FromStr>(args: &[&str], index: usize, name: &str) -> Result<T, String> {
+        args.get(index)
+            .ok_or_else(|| format!("missing {}", name))?
+            .parse()
+            .map_err(|_| format!("invalid {}", name))
+    }
+    fn state(args: &[&str], index: usize) -> Result<bool, String> {
+        match args.get(index) {
+            Some(&"0") => Ok(false),
+            Some(&"1") => Ok(true),
+            Some(_) => Err("state must be 0 or 1".to_string()),
+            None => Err("missing state".to_string()),
+        }
+    }
+
+    let (request, num_args) = match command {
+        "frame" => {
+            let after = match args.get(1) {
+                Some(_) => Some(arg(&args, 1, "serial")?),
+                None => None,
+            };
+            (
+                Request::Frame {
+                    scanout_id: arg(&args, 0, "scanout")?,
+                    after,
+                },
+                1 + after.is_some() as usize,
+            )
+        }
+        "pixels" => (
+            Request::Pixels {
+                scanout_id: arg(&args, 0, "scanout")?,
+            },
+            1,
+        ),
+        "key" => (
+            Request::Key {
+                scanout_id: arg(&args, 0, "scanout")?,
+                code: arg(&args, 1, "key code")?,
+                pressed: state(&args, 2)?,
+            },
+            3,
+        ),
+        "mouse-move" => (
+            Request::MouseMove {
+                scanout_id: arg(&args, 0, "scanout")?,
+                dx: arg(&args, 1, "x motion")?,
+                dy: arg(&args, 2, "y motion")?,
+            },
+            3,
+        ),
+        "mouse-button" => (
+            Request::MouseButton {
+                scanout_id: arg(&args, 0, "scanout")?,
+                code: arg(&args, 1, "button code")?,
+                pressed: state(&args, 2)?,
+            },
+            3,
+        ),
+        "touch" => (
+            Request::Touch {
+                scanout_id: arg(&args, 0, "scanout")?,
+                x: arg(&args, 1, "x")?,
+                y: arg(&args, 2, "y")?,
+                down: state(&args, 3)?,
+            },
+            4,
+        ),
+        _ => return Err(format!("unknown request `{}`", command)),
+    };
+    if args.len() > num_args {
+        return Err("too many arguments".to_string());
+    }
+    Ok(request)
+}
+
+struct Client {
+    stream: UnixStream,
+    in_buf: Vec<u8>,
+    out_buf: VecDeque<u8>,
+    /// A `frame` request waiting for a new frame, which holds back the following requests.
+    waiting: Option<(u32, Option<u64>)>,
+    tracking_id: i32,
+    touching: bool,
+    /// Events the client is registered for.
+    registered_events: EventType,
+}
+
+impl Client {
+    fn new(stream: UnixStream) -> Client {
+        Client {
+            stream,
+            in_buf: Vec::new(),
+            out_buf: VecDeque::new(),
+            waiting: None,
+            tracking_id: 0,
+            touching: false,
+            registered_events: EventType::Read,
+        }
+    }
+
+    /// Whether the client must read its responses before more requests are processed.
+    fn is_full(&self) -> bool {
+        self.out_buf.len() >= MAX_PENDING_OUTPUT
+    }
+
+    /// Reads whatever the client sent. Returns an error once the client hung up.
+    fn read(&mut self) -> io::Result<()> {
+        let mut buf = [0u8; 4096];
+        loop {
+            match self.stream.read(&mut buf) {
+                Ok(0) => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::UnexpectedEof,
+                        "client hung up",
+                    ))
+                }
+                Ok(n) => self.in_buf.extend_from_slice(&buf[..n]),
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => return Ok(()),
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            }
+            if self.in_buf.len() > MAX_REQUEST_SIZE && !self.in_buf.contains(&b'\n') {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "request is too long",
+                ));
+            }
+        }
+    }
+
+    /// Writes as much of the pending output as the socket accepts.
+    fn flush(&mut self) -> io::Result<()> {
+        while !self.out_buf.is_empty() {
+            let (front, _) = self.out_buf.as_slices();
+            match self.stream.write(front) {
+                Ok(0) => return Err(io::Error::from(io::ErrorKind::WriteZero)),
+                Ok(n) => {
+                    self.out_buf.drain(..n);
+                }
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => return Ok(()),
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(())
+    }
+
+    fn reply(&mut self, line: &str) {
+        self.out_buf.extend(line.as_bytes());
+        self.out_buf.push_back(b'\n');
+    }
+
+    /// Answers the pending `frame` request if the scanout has a new enough frame.
+    fn check_waiting(&mut self, state: &ScanoutState) {
+        let (scanout_id, after) = match self.waiting {
+            Some(waiting) => waiting,
+            None => return,
+        };
+        if let Some(scanout) = state.scanouts.get(&scanout_id) {
+            if scanout.serial > after.unwrap_or(0) {
+                self.reply(&format!(
+                    "ok {} {} {}",
+                    scanout.serial, scanout.width, scanout.height
+                ));
+                self.waiting = None;
+            }
+        }
+    }
+
+    fn touch_event(&mut self, x: i32, y: i32, down: bool) -> Vec<virtio_input_event> {
+        // The touch event *must* be first per the Linux input subsystem's guidance.
+        let mut events = vec![virtio_input_event::multitouch_slot(0)];
+        if down {
+            if !self.touching {
+                self.tracking_id = self.tracking_id.wrapping_add(1).max(0);
+            }
+            events.push(virtio_input_event::multitouch_tracking_id(self.tracking_id));
+            events.push(virtio_input_event::multitouch_absolute_x(x));
+            events.push(virtio_input_event::multitouch_absolute_y(y));
+        } else {
+            events.push(virtio_input_event::multitouch_tracking_id(-1));
+        }
+        self.touching = down;
+        events
+    }
+
+    /// Processes the complete requests received so far, queueing injected input in `state`.
+    /// Returns whether any input was queued.
+    fn process_requests(&mut self, state: &mut ScanoutState) -> bool {
+        let mut queued_input = false;
+        self.check_waiting(state);
+        while self.waiting.is_none() && !self.is_full() {
+            let end = match self.in_buf.iter().position(|&b| b == b'\n') {
+                Some(end) => end,
+                None => break,
+            };
+            let line: Vec<u8> = self.in_buf.drain(..=end).collect();
+            let line = String::from_utf8_lossy(&line);
+            let request = match parse_request(&line) {
+                Ok(request) => request,
+                Err(e) => {
+                    self.reply(&format!("error {}", e));
+                    continue;
+                }
+            };
+
+            let (scanout_id, events, device_type) = match request {
+                Request::Frame { scanout_id, after } => {
+                    self.waiting = Some((scanout_id, after));
+                    self.check_waiting(state);
+                    continue;
+                }
+                Request::Pixels { scanout_id } => {
+                    match state.scanouts.get(&scanout_id) {
+                        Some(scanout) => {
+                            self.reply(&format!(
+                                "ok {} {} {} {}",
+                                scanout.serial,
+                                scanout.width,
+                                scanout.height,
+                                scanout.pixels.len()
+                            ));
+                            self.out_buf.extend(&scanout.pixels);
+                        }
+                        None => self.reply(&format!("error no scanout {}", scanout_id)),
+                    }
+                    continue;
+                }
+                Request::Key {
+                    scanout_id,
+                    code,
+                    pressed,
+                } => (
+                    scanout_id,
+                    vec![virtio_input_event::key(code, pressed)],
+                    EventDeviceKind::Keyboard,
+                ),
+                Request::MouseMove { scanout_id, dx, dy } => (
+                    scanout_id,
+                    vec![
+                        virtio_input_event::relative_x(dx),
+                        virtio_input_event::relative_y(dy),
+                    ],
+                    EventDeviceKind::Mouse,
+                ),
+                Request::MouseButton {
+                    scanout_id,
+                    code,
+                    pressed,
+                } => (
+                    scanout_id,
+                    vec![virtio_input_event::key(code, pressed)],
+                    EventDeviceKind::Mouse,
+                ),
+                Request::Touch {
+                    scanout_id,
+                    x,
+                    y,
+                    down,
+                } => {
+                    if !state.scanouts.contains_key(&scanout_id) {
+                        self.reply(&format!("error no scanout {}", scanout_id));
+                        continue;
+                    }
+                    (
+                        scanout_id,
+                        self.touch_event(x, y, down),
+                        EventDeviceKind::Touchscreen,
+                    )
+                }
+            };
+
+            match state.scanouts.get(&scanout_id) {
+                Some(scanout) => {
+                    let descriptor = u64::from(scanout.surface_id);
+                    state.events.push_back((
+                        descriptor,
+                        GpuDisplayEvents {
+                            events,
+                            device_type,
+                        },
+                    ));
+                    queued_input = true;
+                    self.reply("ok");
+                }
+                None => self.reply(&format!("error no scanout {}", scanout_id)),
+            }
+        }
+        queued_input
+    }
+}
+
+#[derive(EventToken)]
+enum Token {
+    Kill,
+    Frame,
+    Listener,
+    Client { id: u32 },
+}
+
+/// Serves the control socket of the headless display.
+struct Server {
+    shared: Arc<Shared>,
+    listener: UnixListener,
+    wait_ctx: WaitContext<Token>,
+    clients: BTreeMap<u32, Client>,
+    next_client_id: u32,
+}
+
+impl Server {
+    fn accept(&mut self) -> anyhow::Result<()> {
+        loop {
+            let stream = match self.listener.accept() {
+                Ok((stream, _)) => stream,
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => return Ok(()),
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => {
+                    error!("headless display: failed to accept client: {}", e);
+                    return Ok(());
+                }
+            };
+            if let Err(e) = stream.set_nonblocking(true) {
+                error!(
+                    "headless display: failed to set client socket non-blocking: {}",
+                    e
+                );
+                continue;
+            }
+            let id = self.next_client_id;
+            self.next_client_id = self.next_client_id.wrapping_add(1);
+            self.wait_ctx
+                .add(&stream, Token::Client { id })
+                .context("failed to add client to WaitContext")?;
+            self.clients.insert(id, Client::new(stream));
+        }
+    }
+
+    /// Services client `id`, and disconnects it on errors.
+    fn handle_client(&mut self, id: u32, readable: bool, writable: bool) -> anyhow::Result<()> {
+        let client = match self.clients.get_mut(&id) {
+            Some(client) => client,
+            None => return Ok(()),
+        };
+        let mut result = Ok(());
+        if writable {
+            result = client.flush();
+        }
+        if readable && result.is_ok() && !client.is_full() {
+            result = client.read();
+        }
+        if result.is_ok() {
+            let queued_input = client.process_requests(&mut self.shared.state.lock());
+            if queued_input {
+                self.shared
+                    .input_evt
+                    .signal()
+                    .context("failed to signal input event")?;
+            }
+        }
+        self.finish_client(id, result)
+    }
+
+    /// Flushes the output of client `id` and updates its registered events, or disconnects the
+    /// client if `result` is an error.
+    fn finish_client(&mut self, id: u32, result: io::Result<()>) -> anyhow::Result<()> {
+        let client = match self.clients.get_mut(&id) {
+            Some(client) => client,
+            None => return Ok(()),
+        };
+        if result.and_then(|_| client.flush()).is_err() {
+            self.wait_ctx
+                .delete(&client.stream)
+                .context("failed to remove client from WaitContext")?;
+            self.clients.remove(&id);
+            return Ok(());
+        }
+
+        let events = if client.is_full() {
+            EventType::Write
+        } else if !client.out_buf.is_empty() {
+            EventType::ReadWrite
+        } else {
+            EventType::Read
+        };
+        if events != client.registered_events {
+            self.wait_ctx
+                .modify(&client.stream, events, Token::Client { id })
+                .context("failed to update client events")?;
+            client.registered_events = events;
+        }
+        Ok(())
+    }
+
+    /// Answers the `frame` requests waiting for the scanouts to change.
+    fn update_clients(&mut self) -> anyhow::Result<()> {
+        let ids: Vec<u32> = self.clients.keys().copied().collect();
+        for id in ids {
+            let queued_input = match self.clients.get_mut(&id) {
+                Some(client) if client.waiting.is_some() => {
+                    client.process_requests(&mut self.shared.state.lock())
+                }
+                _ => continue,
+            };
+            if queued_input {
+                self.shared
+                    .input_evt
+                    .signal()
+                    .context("failed to signal input event")?;
+            }
+            self.finish_client(id, Ok(()))?;
+        }
+        Ok(())
+    }
+
+    fn run(&mut self, kill_evt: &Event) -> anyhow::Result<()> {
+        self.wait_ctx
+            .add_many(&[
+                (kill_evt, Token::Kill),
+                (&self.shared.frame_evt, Token::Frame),
+                (&self.listener, Token::Listener),
+            ])
+            .context("failed to add events to WaitContext")?;
+
+        loop {
+            let events = self.wait_ctx.wait().context("failed polling for events")?;
+            for event in events.iter() {
+                match event.token {
+                    Token::Kill => return Ok(()),
+                    Token::Frame => {
+                        self.shared
+                            .frame_evt
+                            .wait()
+                            .context("failed reading frame event")?;
+                        self.update_clients()?;
+                    }
+                    Token::Listener => self.accept()?,
+                    Token::Client { id } => self.handle_client(
+                        id,
+                        event.is_readable || event.is_hungup,
+                        event.is_writable,
+                    )?,
+                }
+            }
+        }
+    }
+}
+
+/// Creates a headless display controlled by the clients connecting to `listener`.
+pub fn new_display(listener: UnixListener) -> GpuDisplayResult<ScanoutDisplay> {
+    listener.set_nonblocking(true)?;
+    let wait_ctx = WaitContext::new()?;
+    ScanoutDisplay::new(
+        "headless display",
+        "headless_display",
+        u32::MAX,
+        move |shared, kill_evt| {
+            Server {
+                shared,
+                listener,
+                wait_ctx,
+                clients: BTreeMap::new(),
+                next_client_id: 0,
+            }
+            .run(kill_evt)
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::BufRead;
+    use std::io::BufReader;
+    use std::time::Duration;
+
+    use super::*;
+    use crate::scanout_display::Scanout;
+    use crate::DisplayT;
+    use crate::SurfaceType;
+
+    #[test]
+    fn parse_requests() {
+        assert_eq!(
+            parse_request("frame 0\n"),
+            Ok(Request::Frame {
+                scanout_id: 0,
+                after: None
+            })
+        );
+        assert_eq!(
+            parse_request("frame 1 7"),
+            Ok(Request::Frame {
+                scanout_id: 1,
+                after: Some(7)
+            })
+        );
+        assert_eq!(
+            parse_request("key 0 30 1"),
+            Ok(Request::Key {
+                scanout_id: 0,
+                code: 30,
+                pressed: true
+            })
+        );
+        assert_eq!(
+            parse_request("mouse-move 0 -5 3"),
+            Ok(Request::MouseMove {
+                scanout_id: 0,
+                dx: -5,
+                dy: 3
+            })
+        );
+        assert_eq!(
+            parse_request("touch 0 10 20 0"),
+            Ok(Request::Touch {
+                scanout_id: 0,
+                x: 10,
+                y: 20,
+                down: false
+            })
+        );
+        assert!(parse_request("").is_err());
+        assert!(parse_request("explode").is_err());
+        assert!(parse_request("key 0 30").is_err());
+        assert!(parse_request("key 0 30 2").is_err());
+        assert!(parse_request("pixels 0 1").is_err());
+        assert!(parse_request("touch 0 x 20 1").is_err());
+    }
+
+    fn read_line(reader: &mut BufReader<UnixStream>) -> String {
+        let mut line = String::new();
+        reader.read_line(&mut line).unwrap();
+        line.trim_end().to_string()
+    }
+
+    #[test]
+    fn control_socket() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("display.sock");
+        let mut display = new_display(UnixListener::bind(&path).unwrap()).unwrap();
+        let mut surface = display
+            .create_surface(None, 1, 2, 1, SurfaceType::Scanout)
+            .unwrap();
+        surface.set_scanout_id(0);
+
+        let stream = UnixStream::connect(&path).unwrap();
+        stream
+            .set_read_timeout(Some(Duration::from_secs(10)))
+            .unwrap();
+        let mut writer = stream.try_clone().unwrap();
+        let mut reader = BufReader::new(stream);
+
+        // Nothing was flipped yet, so the request waits for the first frame.
+        writer.write_all(b"frame 0\n").unwrap();
+        surface
+            .framebuffer()
+            .unwrap()
+            .as_volatile_slice()
+            .copy_from(&[1u8, 2, 3, 0, 4, 5, 6, 0]);
+        surface.flip();
+        assert_eq!(read_line(&mut reader), "ok 1 2 1");
+
+        writer.write_all(b"pixels 0\n").unwrap();
+        assert_eq!(read_line(&mut reader), "ok 1 2 1 8");
+        let mut pixels = [0u8; 8];
+        reader.read_exact(&mut pixels).unwrap();
+        assert_eq!(pixels, [1, 2, 3, 0, 4, 5, 6, 0]);
+
+        writer.write_all(b"pixels 3\nkey 0 30 1\n").unwrap();
+        assert_eq!(read_line(&mut reader), "error no scanout 3");
+        assert_eq!(read_line(&mut reader), "ok");
+        assert!(display.pending_events());
+        assert_eq!(display.next_event().unwrap(), 1);
+        let events = display.handle_next_event(&mut surface).unwrap();
+        assert_eq!(events.device_type, EventDeviceKind::Keyboard);
+        assert_eq!(events.events, vec![virtio_input_event::key(30, true)]);
+        assert!(!display.pending_events());
+    }
+
+    #[test]
+    fn bounded_output() {
+        let (stream, _peer) = UnixStream::pair().unwrap();
+        let mut client = Client::new(stream);
+        let mut state = ScanoutState::default();
+        state.scanouts.insert(
+            0,
+            Scanout {
+                surface_id: 1,
+                width: 1024,
+                height: 1024,
+                pixels: vec![0; MAX_PENDING_OUTPUT / 2],
+                serial: 1,
+            },
+        );
+
+        client
+            .in_buf
+            .extend_from_slice(b"pixels 0\npixels 0\npixels 0\n");
+        client.process_requests(&mut state);
+        // The third request waits for the client to read the first two frames.
+        assert!(client.is_full());
+        assert_eq!(client.in_buf, b"pixels 0\n");
+
+        client.out_buf.clear();
+        client.process_requests(&mut state);
+        assert!(client.in_buf.is_empty());
+        assert!(!client.is_full());
+    }
+}
@@ -0,0 +1,961 @@
+// Copyright 2023 The ChromiumOS Authors
+// Use of this source code is governed by a BSD-style license that can be
+// found in the LICENSE file.
+
+//! VNC (RFB) server display backend.
+//!
+//! Every scanout surface is served to VNC clients on its own TCP listener, and keyboard and
+//! pointer input from the clients is reported through the display's event devices. Only the
+//! `None` security type and the raw encoding are supported, so the listeners are meant to be bound
+//! to the local host.
+
+use std::collections::BTreeMap;
+use std::collections::VecDeque;
+use std::io;
+use std::io::Read;
+use std::io::Write;
+use std::net::TcpListener;
+use std::net::TcpStream;
+use std::sync::Arc;
+
+use anyhow::Context;
+use base::error;
+use base::info;
+use base::Event;
+use base::EventToken;
+use base::EventType;
+use base::WaitContext;
+use linux_input_sys::virtio_input_event;
+
+use crate::scanout_display::Scanout;
+use crate::scanout_display::ScanoutDisplay;
+use crate::scanout_display::Shared;
+use crate::scanout_display::BYTES_PER_PIXEL;
+use crate::EventDeviceKind;
+use crate::GpuDisplayEvents;
+use crate::GpuDisplayResult;
+
+const RFB_VERSION: &[u8; 12] = b"RFB 003.008\n";
+const SECURITY_TYPE_NONE: u8 = 1;
+const ENCODING_RAW: i32 = 0;
+const ENCODING_DESKTOP_SIZE: i32 = -223;
+const DESKTOP_NAME: &[u8] = b"crosvm";
+/// Largest client message accepted, only cut text messages can get this long.
+const MAX_CLIENT_MESSAGE_SIZE: usize = 1 << 20;
+
+const CLIENT_SET_PIXEL_FORMAT: u8 = 0;
+const CLIENT_SET_ENCODINGS: u8 = 2;
+const CLIENT_FRAMEBUFFER_UPDATE_REQUEST: u8 = 3;
+const CLIENT_KEY_EVENT: u8 = 4;
+const CLIENT_POINTER_EVENT: u8 = 5;
+const CLIENT_CUT_TEXT: u8 = 6;
+const SERVER_FRAMEBUFFER_UPDATE: u8 = 0;
+
+/// Maps X11 keysyms, as sent by VNC clients, to Linux keycodes.
+const KEYSYM_MAP: &[(u32, u16)] = &[
+    // Letters, in both cases.
+    (b'q' as u32, 16),
+    (b'w' as u32, 17),
+    (b'e' as u32, 18),
+    (b'r' as u32, 19),
+    (b't' as u32, 20),
+    (b'y' as u32, 21),
+    (b'u' as u32, 22),
+    (b'i' as u32, 23),
+    (b'o' as u32, 24),
+    (b'p' as u32, 25),
+    (b'a' as u32, 30),
+    (b's' as u32, 31),
+    (b'd' as u32, 32),
+    (b'f' as u32, 33),
+    (b'g' as u32, 34),
+    (b'h' as u32, 35),
+    (b'j' as u32, 36),
+    (b'k' as u32, 37),
+    (b'l' as u32, 38),
+    (b'z' as u32, 44),
+    (b'x' as u32, 45),
+    (b'c' as u32, 46),
+    (b'v' as u32, 47),
+    (b'b' as u32, 48),
+    (b'n' as u32, 49),
+    (b'm' as u32, 50),
+    // Digits and their shifted symbols on a US layout.
+    (b'1' as u32, 2),
+    (b'!' as u32, 2),
+    (b'2' as u32, 3),
+    (b'@' as u32, 3),
+    (b'3' as u32, 4),
+    (b'#' as u32, 4),
+    (b'4' as u32, 5),
+    (b'$' as u32, 5),
+    (b'5' as u32, 6),
+    (b'%' as u32, 6),
+    (b'6' as u32, 7),
+    (b'^' as u32, 7),
+    (b'7' as u32, 8),
+    (b'&' as u32, 8),
+    (b'8' as u32, 9),
+    (b'*' as u32, 9),
+    (b'9' as u32, 10),
+    (b'(' as u32, 10),
+    (b'0' as u32, 11),
+    (b')' as u32, 11),
+    // Punctuation.
+    (b'-' as u32, 12),
+    (b'_' as u32, 12),
+    (b'=' as u32, 13),
+    (b'+' as u32, 13),
+    (b'[' as u32, 26),
+    (b'{' as u32, 26),
+    (b']' as u32, 27),
+    (b'}' as u32, 27),
+    (b';' as u32, 39),
+    (b':' as u32, 39),
+    (b'\'' as u32, 40),
+    (b'"' as u32, 40),
+    (b'`' as u32, 41),
+    (b'~' as u32, 41),
+    (b'\\' as u32, 43),
+    (b'|' as u32, 43),
+    (b',' as u32, 51),
+    (b'<' as u32, 51),
+    (b'.' as u32, 52),
+    (b'>' as u32, 52),
+    (b'/' as u32, 53),
+    (b'?' as u32, 53),
+    (b' ' as u32, 57),
+    // Editing and navigation.
+    (0xff08, 14),  // BackSpace
+    (0xff09, 15),  // Tab
+    (0xfe20, 15),  // ISO_Left_Tab
+    (0xff0d, 28),  // Return
+    (0xff1b, 1),   // Escape
+    (0xffff, 111), // Delete
+    (0xff50, 102), // Home
+    (0xff51, 105), // Left
+    (0xff52, 103), // Up
+    (0xff53, 106), // Right
+    (0xff54, 108), // Down
+    (0xff55, 104), // Page_Up
+    (0xff56, 109), // Page_Down
+    (0xff57, 107), // End
+    (0xff63, 110), // Insert
+    (0xff61, 99),  // Print
+    (0xff13, 119), // Pause
+    (0xff67, 127), // Menu
+    (0xff8d, 96),  // KP_Enter
+    // Function keys.
+    (0xffbe, 59), // F1
+    (0xffbf, 60), // F2
+    (0xffc0, 61), // F3
+    (0xffc1, 62), // F4
+    (0xffc2, 63), // F5
+    (0xffc3, 64), // F6
+    (0xffc4, 65), // F7
+    (0xffc5, 66), // F8
+    (0xffc6, 67), // F9
+    (0xffc7, 68), // F10
+    (0xffc8, 87), // F11
+    (0xffc9, 88), // F12
+    // Modifiers and locks.
+    (0xffe1, 42),  // Shift_L
+    (0xffe2, 54),  // Shift_R
+    (0xffe3, 29),  // Control_L
+    (0xffe4, 97),  // Control_R
+    (0xffe5, 58),  // Caps_Lock
+    (0xffe7, 125), // Meta_L
+    (0xffe8, 126), // Meta_R
+    (0xffe9, 56),  // Alt_L
+    (0xffea, 100), // Alt_R
+    (0xffeb, 125), // Super_L
+    (0xffec, 126), // Super_R
+    (0xfe03, 100), // ISO_Level3_Shift
+    (0xff7f, 69),  // Num_Lock
+    (0xff14, 70),  // Scroll_Lock
+];
+
+/// Translates an X11 keysym into a Linux keycode.
+fn keysym_to_keycode(keysym: u32) -> Option<u16> {
+    // Upper case letters are reported with their own keysyms, but map to the same keys.
+    let keysym = match keysym {
+        0x41..=0x5a => keysym + 0x20,
+        _ => keysym,
+    };
+    KEYSYM_MAP
+        .iter()
+        .find(|(sym, _)| *sym == keysym)
+        .map(|(_, code)| *code)
+}
+
+/// An RFB pixel format, describing how pixels are sent to a client.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+struct PixelFormat {
+    bits_per_pixel: u8,
+    depth: u8,
+    big_endian: bool,
+    true_colour: bool,
+    red_max: u16,
+    green_max: u16,
+    blue_max: u16,
+    red_shift: u8,
+    green_shift: u8,
+    blue_shift: u8,
+}
+
+impl PixelFormat {
+    /// Format of the surface buffers: XRGB8888 stored in little endian.
+    const XRGB8888: PixelFormat = PixelFormat {
+        bits_per_pixel: 32,
+        depth: 24,
+        big_endian: false,
+        true_colour: true,
+        red_max: 255,
+        green_max: 255,
+        blue_max: 255,
+        red_shift: 16,
+        green_shift: 8,
+        blue_shift: 0,
+    };
+
+    fn from_bytes(b: &[u8; 16]) -> PixelFormat {
+        PixelFormat {
+            bits_per_pixel: b[0],
+            depth: b[1],
+            big_endian: b[2] != 0,
+            true_colour: b[3] != 0,
+            red_max: u16::from_be_bytes([b[4], b[5]]),
+            green_max: u16::from_be_bytes([b[6], b[7]]),
+            blue_max: u16::from_be_bytes([b[8], b[9]]),
+            red_shift: b[10],
+            green_shift: b[11],
+            blue_shift: b[12],
+        }
+    }
+
+    fn to_bytes(self) -> [u8; 16] {
+        let mut b = [0u8; 16];
+        b[0] = self.bits_per_pixel;
+        b[1] = self.depth;
+        b[2] = self.big_endian as u8;
+        b[3] = self.true_colour as u8;
+        b[4..6].copy_from_slice(&self.red_max.to_be_bytes());
+        b[6..8].copy_from_slice(&self.green_max.to_be_bytes());
+        b[8..10].copy_from_slice(&self.blue_max.to_be_bytes());
+        b[10] = self.red_shift;
+        b[11] = self.green_shift;
+        b[12] = self.blue_shift;
+        b
+    }
+
+    /// Returns true if pixels can be converted to this format.
+    fn is_supported(&self) -> bool {
+        let bits = u32::from(self.bits_per_pixel);
+        self.true_colour
+            && matches!(bits, 8 | 16 | 32)
+            && [self.red_shift, self.green_shift, self.blue_shift]
+                .iter()
+                .all(|shift| u32::from(*shift) < bits)
+    }
+
+    /// Appends `pixels`, which are in the surfaces' XRGB8888 format, to `out` in this format.
+    fn convert(&self, pixels: &[u8], out: &mut VecDeque<u8>) {
+        if *self == Self::XRGB8888 {
+            out.extend(pixels);
+            return;
+        }
+        let scale = |value: u8, max: u16| u32::from(value) * u32::from(max) / 255;
+        for px in pixels.chunks_exact(BYTES_PER_PIXEL as usize) {
+            let value = scale(px[2], self.red_max) << self.red_shift
+                | scale(px[1], self.green_max) << self.green_shift
+                | scale(px[0], self.blue_max) << self.blue_shift;
+            match (self.bits_per_pixel, self.big_endian) {
+                (8, _) => out.push_back(value as u8),
+                (16, false) => out.extend((value as u16).to_le_bytes()),
+                (16, true) => out.extend((value as u16).to_be_bytes()),
+                (_, false) => out.extend(value.to_le_bytes()),
+                (_, true) => out.extend(value.to_be_bytes()),
+            }
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum ClientState {
+    /// Waiting for the client's protocol version.
+    Version,
+    /// Waiting for the client to pick a security type.
+    Security,
+    /// Waiting for the client's initialisation message.
+    Init,
+    /// Waiting for the scanout to have a surface before sending the server initialisation.
+    WaitingForScanout,
+    Active,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum UpdateRequest {
+    /// Only send a new frame once the scanout changes.
+    Incremental,
+    Full,
+}
+
+struct Client {
+    stream: TcpStream,
+    scanout_id: u32,
+    state: ClientState,
+    /// Minor version of the negotiated RFB protocol.
+    minor_version: u8,
+    in_buf: Vec<u8>,
+    out_buf: VecDeque<u8>,
+    pixel_format: PixelFormat,
+    desktop_size: bool,
+    /// Framebuffer size as known by the client.
+    width: u32,
+    height: u32,
+    /// Serial of the last frame sent to the client.
+    sent_serial: Option<u64>,
+    update_request: Option<UpdateRequest>,
+    buttons: u8,
+    tracking_id: i32,
+    /// Whether the client is registered for writable events.
+    write_registered: bool,
+}
+
+impl Client {
+    fn new(stream: TcpStream, scanout_id: u32) -> Client {
+        Client {
+            stream,
+            scanout_id,
+            state: ClientState::Version,
+            minor_version: 0,
+            in_buf: Vec::new(),
+            out_buf: RFB_VERSION.iter().copied().collect(),
+            pixel_format: PixelFormat::XRGB8888,
+            desktop_size: false,
+            width: 0,
+            height: 0,
+            sent_serial: None,
+            update_request: None,
+            buttons: 0,
+            tracking_id: -1,
+            write_registered: false,
+        }
+    }
+
+    /// Reads everything the client sent so far. Returns an error once the client disconnects.
+    fn read(&mut self) -> io::Result<()> {
+        let mut buf = [0u8; 4096];
+        loop {
+            match self.stream.read(&mut buf) {
+                Ok(0) => return Err(io::Error::from(io::ErrorKind::UnexpectedEof)),
+                Ok(n) => self.in_buf.extend_from_slice(&buf[..n]),
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => return Ok(()),
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// Writes as much buffered output as the socket accepts.
+    fn flush(&mut self) -> io::Result<()> {
+        while !self.out_buf.is_empty() {
+            let (data, _) = self.out_buf.as_slices();
+            match self.stream.write(data) {
+                Ok(0) => return Err(io::Error::from(io::ErrorKind::WriteZero)),
+                Ok(n) => {
+                    self.out_buf.drain(..n);
+                }
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => return Ok(()),
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(())
+    }
+
+    /// Handles the complete messages received from the client, appending input events to
+    /// `events`.
+    fn process_messages(&mut self, events: &mut Vec<GpuDisplayEvents>) -> io::Result<()> {
+        loop {
+            let consumed = match self.state {
+                ClientState::Version => self.process_version()?,
+                ClientState::Security => self.process_security()?,
+                ClientState::Init => {
+                    if self.in_buf.is_empty() {
+                        0
+                    } else {
+                        // The shared flag is ignored, all clients share the scanout.
+                        self.state = ClientState::WaitingForScanout;
+                        1
+                    }
+                }
+                // Messages are only processed once the client knows the framebuffer size.
+                ClientState::WaitingForScanout => 0,
+                ClientState::Active => self.process_message(events)?,
+            };
+            if consumed == 0 {
+                return Ok(());
+            }
+            self.in_buf.drain(..consumed);
+        }
+    }
+
+    fn process_version(&mut self) -> io::Result<usize> {
+        if self.in_buf.len() < RFB_VERSION.len() {
+            return Ok(0);
+        }
+        self.minor_version = match &self.in_buf[..RFB_VERSION.len()] {
+            b"RFB 003.003\n" => 3,
+            b"RFB 003.007\n" => 7,
+            b"RFB 003.008\n" => 8,
+            _ => return Err(protocol_error("unsupported protocol version")),
+        };
+        if self.minor_version == 3 {
+            // Version 3.3 has the server decide on the security type.
+            self.out_buf
+                .extend(u32::from(SECURITY_TYPE_NONE).to_be_bytes());
+            self.state = ClientState::Init;
+        } else {
+            self.out_buf.extend([1, SECURITY_TYPE_NONE]);
+            self.state = ClientState::Security;
+        }
+        Ok(RFB_VERSION.len())
+    }
+
+    fn process_security(&mut self) -> io::Result<usize> {
+        let security_type = match self.in_buf.first() {
+            Some(t) => *t,
+            None => return Ok(0),
+        };
+        if security_type != SECURITY_TYPE_NONE {
+            return Err(protocol_error("unsupported security type"));
+        }
+        // Only version 3.8 sends a security result for the `None` security type.
+        if self.minor_version >= 8 {
+            self.out_buf.extend(0u32.to_be_bytes());
+        }
+        self.state = ClientState::Init;
+        Ok(1)
+    }
+
+    fn process_message(&mut self, events: &mut Vec<GpuDisplayEvents>) -> io::Result<usize> {
+        let buf = &self.in_buf;
+        let message_type = match buf.first() {
+            Some(t) => *t,
+            None => return Ok(0),
+        };
+        let len = match message_type {
+            CLIENT_SET_PIXEL_FORMAT => 20,
+            CLIENT_SET_ENCODINGS => {
+                if buf.len() < 4 {
+                    return Ok(0);
+                }
+                4 + 4 * u16::from_be_bytes([buf[2], buf[3]]) as usize
+            }
+            CLIENT_FRAMEBUFFER_UPDATE_REQUEST => 10,
+            CLIENT_KEY_EVENT => 8,
+            CLIENT_POINTER_EVENT => 6,
+            CLIENT_CUT_TEXT => {
+                if buf.len() < 8 {
+                    return Ok(0);
+                }
+                let text_len = u32::from_be_bytes([buf[4], buf[5], buf[6], buf[7]]) as usize;
+                8 + text_len
+            }
+            _ => return Err(protocol_error("unknown message type")),
+        };
+        if len > MAX_CLIENT_MESSAGE_SIZE {
+            return Err(protocol_error("message is too long"));
+        }
+        if buf.len() < len {
+            return Ok(0);
+        }
+        let msg = &buf[..len];
+
+        match message_type {
+            CLIENT_SET_PIXEL_FORMAT => {
+                let mut format = [0u8; 16];
+                format.copy_from_slice(&msg[4..20]);
+                let format = PixelFormat::from_bytes(&format);
+                if !format.is_supported() {
+                    return Err(protocol_error("unsupported pixel format"));
+                }
+                self.pixel_format = format;
+            }
+            CLIENT_SET_ENCODINGS => {
+                self.desktop_size = msg[4..]
+                    .chunks_exact(4)
+                    .any(|e| i32::from_be_bytes([e[0], e[1], e[2], e[3]]) == ENCODING_DESKTOP_SIZE);
+            }
+            CLIENT_FRAMEBUFFER_UPDATE_REQUEST => {
+                let request = if msg[1] != 0 {
+                    UpdateRequest::Incremental
+                } else {
+                    UpdateRequest::Full
+                };
+                if self.update_request != Some(UpdateRequest::Full) {
+                    self.update_request = Some(request);
+                }
+            }
+            CLIENT_KEY_EVENT => {
+                let down = msg[1] != 0;
+                let keysym = u32::from_be_bytes([msg[4], msg[5], msg[6], msg[7]]);
+                if let Some(keycode) = keysym_to_keycode(keysym) {
+                    events.push(GpuDisplayEvents {
+                        events: vec![virtio_input_event::key(keycode, down)],
+                        device_type: EventDeviceKind::Keyboard,
+                    });
+                }
+            }
+            CLIENT_POINTER_EVENT => {
+                let buttons = msg[1];
+                let x = i32::from(u16::from_be_bytes([msg[2], msg[3]]));
+                let y = i32::from(u16::from_be_bytes([msg[4], msg[5]]));
+                if let Some(event) = self.pointer_event(buttons, x, y) {
+                    events.push(event);
+                }
+            }
+            // The guest clipboard is not shared.
+            _ => {}
+        }
+        Ok(len)
+    }
+
+    /// Converts pointer events into touchscreen events. Like the X backend, only the left button
+    /// is reported, as a single touch.
+    fn pointer_event(&mut self, buttons: u8, x: i32, y: i32) -> Option<GpuDisplayEvents> {
+        let pressed = buttons & 1 != 0;
+        let was_pressed = self.buttons & 1 != 0;
+        self.buttons = buttons;
+
+        // The touch event *must* be first per the Linux input subsystem's guidance.
+        let mut events = vec![virtio_input_event::multitouch_slot(0)];
+        if pressed {
+            if !was_pressed {
+                self.tracking_id = self.tracking_id.wrapping_add(1).max(0);
+            }
+            events.push(virtio_input_event::multitouch_tracking_id(self.tracking_id));
+            events.push(virtio_input_event::multitouch_absolute_x(x));
+            events.push(virtio_input_event::multitouch_absolute_y(y));
+        } else if was_pressed {
+            events.push(virtio_input_event::multitouch_tracking_id(-1));
+        } else {
+            return None;
+        }
+
+        Some(GpuDisplayEvents {
+            events,
+            device_type: EventDeviceKind::Touchscreen,
+        })
+    }
+
+    fn send_server_init(&mut self, scanout: &Scanout) {
+        self.width = scanout.width;
+        self.height = scanout.height;
+        self.out_buf.extend((scanout.width as u16).to_be_bytes());
+        self.out_buf.extend((scanout.height as u16).to_be_bytes());
+        self.out_buf.extend(self.pixel_format.to_bytes());
+        self.out_buf
+            .extend((DESKTOP_NAME.len() as u32).to_be_bytes());
+        self.out_buf.extend(DESKTOP_NAME);
+        self.state = ClientState::Active;
+    }
+
+    fn send_rect_header(&mut self, width: u32, height: u32, encoding: i32) {
+        self.out_buf.extend([SERVER_FRAMEBUFFER_UPDATE, 0]);
+        self.out_buf.extend(1u16.to_be_bytes());
+        self.out_buf.extend(0u16.to_be_bytes());
+        self.out_buf.extend(0u16.to_be_bytes());
+        self.out_buf.extend((width as u16).to_be_bytes());
+        self.out_buf.extend((height as u16).to_be_bytes());
+        self.out_buf.extend(encoding.to_be_bytes());
+    }
+
+    /// Sends the scanout contents if the client asked for them and they changed.
+    fn update(&mut self, scanout: Option<&Scanout>) -> io::Result<()> {
+        let scanout = match scanout {
+            Some(scanout) => scanout,
+            None => return Ok(()),
+        };
+        match self.state {
+            ClientState::WaitingForScanout => self.send_server_init(scanout),
+            ClientState::Active => {}
+            _ => return Ok(()),
+        }
+        // Wait for the previous update to be sent to avoid queueing up frames.
+        if !self.out_buf.is_empty() {
+            return Ok(());
+        }
+        let request = match self.update_request {
+            Some(request) => request,
+            None => return Ok(()),
+        };
+
+        if (scanout.width, scanout.height) != (self.width, self.height) {
+            if !self.desktop_size {
+                return Err(io::Error::new(
+                    io::ErrorKind::Other,
+                    "scanout was resized but the client does not support resizing",
+                ));
+            }
+            self.send_rect_header(scanout.width, scanout.height, ENCODING_DESKTOP_SIZE);
+            self.width = scanout.width;
+            self.height = scanout.height;
+            self.sent_serial = None;
+            self.update_request = None;
+            return Ok(());
+        }
+
+        if request == UpdateRequest::Incremental && self.sent_serial == Some(scanout.serial) {
+            return Ok(());
+        }
+        self.send_rect_header(scanout.width, scanout.height, ENCODING_RAW);
+        self.pixel_format
+            .convert(&scanout.pixels, &mut self.out_buf);
+        self.sent_serial = Some(scanout.serial);
+        self.update_request = None;
+        Ok(())
+    }
+}
+
+fn protocol_error(msg: &'static str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+#[derive(EventToken)]
+enum Token {
+    Kill,
+    Frame,
+    Listener { index: u32 },
+    Client { id: u32 },
+}
+
+/// Serves the scanouts to VNC clients, the listener at index `i` serving scanout `i`.
+struct Server {
+    shared: Arc<Shared>,
+    listeners: Vec<TcpListener>,
+    wait_ctx: WaitContext<Token>,
+    clients: BTreeMap<u32, Client>,
+    next_client_id: u32,
+}
+
+impl Server {
+    fn accept(&mut self, index: u32) -> anyhow::Result<()> {
+        loop {
+            let stream = match self.listeners[index as usize].accept() {
+                Ok((stream, addr)) => {
+                    info!("vnc: client {} connected to scanout {}", addr, index);
+                    stream
+                }
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => return Ok(()),
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => {
+                    error!("vnc: failed to accept client: {}", e);
+                    return Ok(());
+                }
+            };
+            if let Err(e) = stream.set_nonblocking(true) {
+                error!("vnc: failed to set client socket non-blocking: {}", e);
+                continue;
+            }
+            let id = self.next_client_id;
+            self.next_client_id = self.next_client_id.wrapping_add(1);
+            // The protocol version is sent as soon as the client connects.
+            self.wait_ctx
+                .add_for_event(&stream, EventType::ReadWrite, Token::Client { id })
+                .context("failed to add client to WaitContext")?;
+            let mut client = Client::new(stream, index);
+            client.write_registered = true;
+            self.clients.insert(id, client);
+        }
+    }
+
+    /// Services client `id`, and disconnects it on errors.
+    fn handle_client(&mut self, id: u32, readable: bool, writable: bool) -> anyhow::Result<()> {
+        let client = match self.clients.get_mut(&id) {
+            Some(client) => client,
+            None => return Ok(()),
+        };
+        let mut events = Vec::new();
+        let mut result = Ok(());
+        if writable {
+            result = client.flush();
+        }
+        if readable && result.is_ok() {
+            result = client
+                .read()
+                .and_then(|_| client.process_messages(&mut events));
+        }
+        if result.is_ok() {
+            let mut state = self.shared.state.lock();
+            result = client.update(state.scanouts.get(&client.scanout_id));
+            if !events.is_empty() {
+                if let Some(scanout) = state.scanouts.get(&client.scanout_id) {
+                    let descriptor = u64::from(scanout.surface_id);
+                    state
+                        .events
+                        .extend(events.into_iter().map(|events| (descriptor, events)));
+                    self.shared
+                        .input_evt
+                        .signal()
+                        .context("failed to signal input event")?;
+                }
+            }
+        }
+        self.finish_client(id, result)
+    }
+
+    /// Flushes the output of client `id` and updates its registered events, or disconnects the
+    /// client if `result` is an error.
+    fn finish_client(&mut self, id: u32, result: io::Result<()>) -> anyhow::Result<()> {
+        let client = match self.clients.get_mut(&id) {
+            Some(client) => client,
+            None => return Ok(()),
+        };
+        if let Err(e) = result.and_then(|_| client.flush()) {
+            info!(
+                "vnc: client of scanout {} disconnected: {}",
+                client.scanout_id, e
+            );
+            self.wait_ctx
+                .delete(&client.stream)
+                .context("failed to remove client from WaitContext")?;
+            self.clients.remove(&id);
+            return Ok(());
+        }
+
+        let write = !client.out_buf.is_empty();
+        if write != client.write_registered {
+            let events = if write {
+                EventType::ReadWrite
+            } else {
+                EventType::Read
+            };
+            self.wait_ctx
+                .modify(&client.stream, events, Token::Client { id })
+                .context("failed to update client events")?;
+            client.write_registered = write;
+        }
+        Ok(())
+    }
+
+    fn update_clients(&mut self) -> anyhow::Result<()> {
+        let ids: Vec<u32> = self.clients.keys().copied().collect();
+        for id in ids {
+            let result = {
+                let state = self.shared.state.lock();
+                match self.clients.get_mut(&id) {
+                    Some(client) => client.update(state.scanouts.get(&client.scanout_id)),
+                    None => continue,
+                }
+            };
+            self.finish_client(id, result)?;
+        }
+        Ok(())
+    }
+
+    fn run(&mut self, kill_evt: &Event) -> anyhow::Result<()> {
+        self.wait_ctx
+            .add_many(&[
+                (kill_evt, Token::Kill),
+                (&self.shared.frame_evt, Token::Frame),
+            ])
+            .context("failed to add events to WaitContext")?;
+        for (index, listener) in self.listeners.iter().enumerate() {
+            self.wait_ctx
+                .add(
+                    listener,
+                    Token::Listener {
+                        index: index as u32,
+                    },
+                )
+                .context("failed to add listener to WaitContext")?;
+        }
+
+        loop {
+            let events = self.wait_ctx.wait().context("failed polling for events")?;
+            for event in events.iter() {
+                match event.token {
+                    Token::Kill => return Ok(()),
+                    Token::Frame => {
+                        self.shared
+                            .frame_evt
+                            .wait()
+                            .context("failed reading frame event")?;
+                        self.update_clients()?;
+                    }
+                    Token::Listener { index } => self.accept(index)?,
+                    Token::Client { id } => self.handle_client(
+                        id,
+                        event.is_readable || event.is_hungup,
+                        event.is_writable,
+                    )?,
+                }
+            }
+        }
+    }
+}
+
+/// Creates a display serving the scanout `i` to VNC clients connecting to `listeners[i]`.
+pub fn new_display(listeners: Vec<TcpListener>) -> GpuDisplayResult<ScanoutDisplay> {
+    for listener in &listeners {
+        listener.set_nonblocking(true)?;
+    }
+    let wait_ctx = WaitContext::new()?;
+    // The RFB protocol limits framebuffer dimensions to 16 bits.
+    ScanoutDisplay::new(
+        "vnc",
+        "vnc_server",
+        u32::from(u16::MAX),
+        move |shared, kill_evt| {
+            Server {
+                shared,
+                listeners,
+                wait_ctx,
+                clients: BTreeMap::new(),
+                next_client_id: 0,
+            }
+            .run(kill_evt)
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::DisplayT;
+    use crate::SurfaceType;
+
+    fn read_bytes(stream: &mut TcpStream, len: usize) -> Vec<u8> {
+        let mut buf = vec![0; len];
+        stream.read_exact(&mut buf).unwrap();
+        buf
+    }
+
+    #[test]
+    fn keysyms() {
+        assert_eq!(keysym_to_keycode(b'a' as u32), Some(30));
+        assert_eq!(keysym_to_keycode(b'A' as u32), Some(30));
+        assert_eq!(keysym_to_keycode(b'!' as u32), Some(2));
+        assert_eq!(keysym_to_keycode(0xff0d), Some(28));
+        assert_eq!(keysym_to_keycode(0x20ac), None);
+    }
+
+    #[test]
+    fn convert_pixel_format() {
+        let pixels = [0x30, 0x20, 0x10, 0x00, 0xff, 0xff, 0xff, 0x00];
+
+        let mut out = VecDeque::new();
+        PixelFormat::XRGB8888.convert(&pixels, &mut out);
+        assert!(out.iter().eq(pixels.iter()));
+
+        let rgb565 = PixelFormat {
+            bits_per_pixel: 16,
+            depth: 16,
+            big_endian: true,
+            true_colour: true,
+            red_max: 31,
+            green_max: 63,
+            blue_max: 31,
+            red_shift: 11,
+            green_shift: 5,
+            blue_shift: 0,
+        };
+        assert!(rgb565.is_supported());
+        assert_eq!(PixelFormat::from_bytes(&rgb565.to_bytes()), rgb565);
+        let mut out = VecDeque::new();
+        rgb565.convert(&pixels, &mut out);
+        assert!(out.iter().eq([0x08, 0xe5, 0xff, 0xff].iter()));
+
+        let palette = PixelFormat {
+            true_colour: false,
+            ..PixelFormat::XRGB8888
+        };
+        assert!(!palette.is_supported());
+    }
+
+    #[test]
+    fn serve_scanout() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let mut display = new_display(vec![listener]).unwrap();
+
+        let mut surface = display
+            .create_surface(None, 1, 4, 2, SurfaceType::Scanout)
+            .unwrap();
+        surface.set_scanout_id(0);
+        let pixels: Vec<u8> = (0..32).collect();
+        surface
+            .framebuffer()
+            .unwrap()
+            .as_volatile_slice()
+            .copy_from(&pixels);
+        surface.flip();
+
+        let mut client = TcpStream::connect(addr).unwrap();
+        client
+            .set_read_timeout(Some(Duration::from_secs(10)))
+            .unwrap();
+        assert_eq!(read_bytes(&mut client, 12), RFB_VERSION);
+        client.write_all(RFB_VERSION).unwrap();
+        assert_eq!(read_bytes(&mut client, 2), [1, SECURITY_TYPE_NONE]);
+        client.write_all(&[SECURITY_TYPE_NONE]).unwrap();
+        assert_eq!(read_bytes(&mut client, 4), [0, 0, 0, 0]);
+        client.write_all(&[1]).unwrap();
+
+        let server_init = read_bytes(&mut client, 24);
+        assert_eq!(server_init[0..4], [0, 4, 0, 2]);
+        assert_eq!(server_init[4..20], PixelFormat::XRGB8888.to_bytes());
+        let name_len = u32::from_be_bytes(server_init[20..24].try_into().unwrap());
+        assert_eq!(read_bytes(&mut client, name_len as usize), DESKTOP_NAME);
+
+        client
+            .write_all(&[CLIENT_FRAMEBUFFER_UPDATE_REQUEST, 0, 0, 0, 0, 0, 0, 4, 0, 2])
+            .unwrap();
+        assert_eq!(
+            read_bytes(&mut client, 16),
+            [
+                SERVER_FRAMEBUFFER_UPDATE,
+                0,
+                0,
+                1,
+                0,
+                0,
+                0,
+                0,
+                0,
+                4,
+                0,
+                2,
+                0,
+                0,
+                0,
+                0
+            ]
+        );
+        assert_eq!(read_bytes(&mut client, pixels.len()), pixels);
+
+        // Press 'a' and check that it is reported for the surface.
+        client
+            .write_all(&[CLIENT_KEY_EVENT, 1, 0, 0, 0, 0, 0, b'a'])
+            .unwrap();
+        let wait_ctx = WaitContext::build_with(&[(&display, ())]).unwrap();
+        assert_eq!(
+            wait_ctx
+                .wait_timeout(Duration::from_secs(10))
+                .unwrap()
+                .len(),
+            1
+        );
+        display.flush();
+        assert!(display.pending_events());
+        assert_eq!(display.next_event().unwrap(), 1);
+        let events = display.handle_next_event(&mut surface).unwrap();
+        assert_eq!(events.device_type, EventDeviceKind::Keyboard);
+        assert_eq!(events.events, vec![virtio_input_event::key(30, true)]);
+        assert!(!display.pending_events());
+    }
+}
@@ -3,7 +3,7 @@
 // found in the LICENSE file.
 
 //! Crate for displaying simple surfaces and GPU buffers over a low-level display backend such as
-//! Wayland or X.
+//! Wayland, X, a VNC server or an in-memory display controlled over a unix socket.
 
 use std::collections::BTreeMap;
 use std::io::Error as IoError;
@@ -19,7 +19,11 @@ use remain::sorted;
 use thiserror::Error;
 
 mod event_device;
+#[cfg(unix)]
+mod gpu_display_headless;
 mod gpu_display_stub;
+#[cfg(unix)]
+mod gpu_display_vnc;
 #[cfg(windows)]
 mod gpu_display_win;
 #[cfg(unix)]
@@ -28,6 +32,8 @@ mod gpu_display_wl;
 mod gpu_display_x;
 #[cfg(feature = "x")]
 mod keycode_converter;
+#[cfg(unix)]
+mod scanout_display;
 mod sys;
 
 pub use event_device::EventDevice;
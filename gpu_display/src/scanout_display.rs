@@ -0,0 +1,243 @@
+// Copyright 2023 The ChromiumOS Authors
+// Use of this source code is governed by a BSD-style license that can be
+// found in the LICENSE file.
+
+//! Display keeping the framebuffers of its scanouts in memory, for a server thread to serve them
+//! to its clients and to inject their input. This is what the VNC and headless backends have in
+//! common.
+
+use std::collections::BTreeMap;
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+use base::error;
+use base::AsRawDescriptor;
+use base::Event;
+use base::RawDescriptor;
+use base::WorkerThread;
+use data_model::VolatileSlice;
+use sync::Mutex;
+
+use crate::DisplayT;
+use crate::GpuDisplayError;
+use crate::GpuDisplayEvents;
+use crate::GpuDisplayFramebuffer;
+use crate::GpuDisplayResult;
+use crate::GpuDisplaySurface;
+use crate::SurfaceType;
+use crate::SysDisplayT;
+
+pub(crate) const BYTES_PER_PIXEL: u32 = 4;
+
+/// Contents of a scanout, shared between its surface and the server.
+pub(crate) struct Scanout {
+    pub surface_id: u32,
+    pub width: u32,
+    pub height: u32,
+    /// Pixels of the last flipped framebuffer, in XRGB8888 format.
+    pub pixels: Vec<u8>,
+    /// Incremented every time the surface is flipped, starting at 0 before the first frame.
+    pub serial: u64,
+}
+
+#[derive(Default)]
+pub(crate) struct ScanoutState {
+    pub scanouts: BTreeMap<u32, Scanout>,
+    /// Input events from clients along with the descriptor of the surface they target.
+    pub events: VecDeque<(u64, GpuDisplayEvents)>,
+}
+
+pub(crate) struct Shared {
+    /// Name of the backend in log messages.
+    pub name: &'static str,
+    pub state: Mutex<ScanoutState>,
+    /// Signaled when a scanout is created, flipped or released.
+    pub frame_evt: Event,
+    /// Signaled when input events are queued in `state`.
+    pub input_evt: Event,
+}
+
+struct ScanoutSurface {
+    surface_id: u32,
+    width: u32,
+    height: u32,
+    scanout_id: Option<u32>,
+    buffer: Vec<u8>,
+    shared: Arc<Shared>,
+}
+
+impl ScanoutSurface {
+    fn notify_server(&self) {
+        if let Err(e) = self.shared.frame_evt.signal() {
+            error!("{}: failed to signal frame event: {}", self.shared.name, e);
+        }
+    }
+}
+
+impl GpuDisplaySurface for ScanoutSurface {
+    fn surface_descriptor(&self) -> u64 {
+        u64::from(self.surface_id)
+    }
+
+    fn framebuffer(&mut self) -> Option<GpuDisplayFramebuffer> {
+        Some(GpuDisplayFramebuffer::new(
+            VolatileSlice::new(&mut self.buffer),
+            self.width * BYTES_PER_PIXEL,
+            BYTES_PER_PIXEL,
+        ))
+    }
+
+    fn flip(&mut self) {
+        let scanout_id = match self.scanout_id {
+            Some(scanout_id) => scanout_id,
+            None => return,
+        };
+        {
+            let mut state = self.shared.state.lock();
+            match state.scanouts.get_mut(&scanout_id) {
+                Some(scanout) if scanout.surface_id == self.surface_id => {
+                    scanout.pixels.copy_from_slice(&self.buffer);
+                    scanout.serial = scanout.serial.wrapping_add(1);
+                }
+                _ => return,
+            }
+        }
+        self.notify_server();
+    }
+
+    fn set_scanout_id(&mut self, scanout_id: u32) {
+        self.scanout_id = Some(scanout_id);
+        self.shared.state.lock().scanouts.insert(
+            scanout_id,
+            Scanout {
+                surface_id: self.surface_id,
+                width: self.width,
+                height: self.height,
+                pixels: self.buffer.clone(),
+                serial: 0,
+            },
+        );
+        self.notify_server();
+    }
+}
+
+impl Drop for ScanoutSurface {
+    fn drop(&mut self) {
+        if let Some(scanout_id) = self.scanout_id {
+            let mut state = self.shared.state.lock();
+            if let Some(scanout) = state.scanouts.get(&scanout_id) {
+                if scanout.surface_id == self.surface_id {
+                    state.scanouts.remove(&scanout_id);
+                }
+            }
+        }
+    }
+}
+
+pub struct ScanoutDisplay {
+    shared: Arc<Shared>,
+    current_event: Option<GpuDisplayEvents>,
+    /// Largest width and height of the surfaces.
+    max_size: u32,
+    _server: WorkerThread<()>,
+}
+
+impl ScanoutDisplay {
+    /// Creates a display with surfaces up to `max_size` pixels wide and high, served by `server`
+    /// in a thread named `thread_name` until its kill event is signaled.
+    pub(crate) fn new<F>(
+        name: &'static str,
+        thread_name: &str,
+        max_size: u32,
+        server: F,
+    ) -> GpuDisplayResult<ScanoutDisplay>
+    where
+        F: FnOnce(Arc<Shared>, &Event) -> anyhow::Result<()> + Send + 'static,
+    {
+        let shared = Arc::new(Shared {
+            name,
+            state: Mutex::new(Default::default()),
+            frame_evt: Event::new().map_err(|_| GpuDisplayError::CreateEvent)?,
+            input_evt: Event::new().map_err(|_| GpuDisplayError::CreateEvent)?,
+        });
+        let server_shared = shared.clone();
+        let server = WorkerThread::start(thread_name, move |kill_evt| {
+            if let Err(e) = server(server_shared, &kill_evt) {
+                error!("{} server failed: {:#}", name, e);
+            }
+        });
+
+        Ok(ScanoutDisplay {
+            shared,
+            current_event: None,
+            max_size,
+            _server: server,
+        })
+    }
+}
+
+impl DisplayT for ScanoutDisplay {
+    fn pending_events(&self) -> bool {
+        !self.shared.state.lock().events.is_empty()
+    }
+
+    fn flush(&self) {
+        // Events queued after this point signal the event again.
+        if let Err(e) = self.shared.input_evt.reset() {
+            error!("{}: failed to reset input event: {}", self.shared.name, e);
+        }
+    }
+
+    fn next_event(&mut self) -> GpuDisplayResult<u64> {
+        // Surface IDs are non-zero, so 0 matches no surface.
+        Ok(match self.shared.state.lock().events.pop_front() {
+            Some((descriptor, events)) => {
+                self.current_event = Some(events);
+                descriptor
+            }
+            None => 0,
+        })
+    }
+
+    fn handle_next_event(
+        &mut self,
+        _surface: &mut Box<dyn GpuDisplaySurface>,
+    ) -> Option<GpuDisplayEvents> {
+        self.current_event.take()
+    }
+
+    fn create_surface(
+        &mut self,
+        parent_surface_id: Option<u32>,
+        surface_id: u32,
+        width: u32,
+        height: u32,
+        surf_type: SurfaceType,
+    ) -> GpuDisplayResult<Box<dyn GpuDisplaySurface>> {
+        // Only scanouts are served, the clients draw their own cursor if any.
+        if parent_surface_id.is_some() || surf_type != SurfaceType::Scanout {
+            return Err(GpuDisplayError::Unsupported);
+        }
+        if width > self.max_size || height > self.max_size {
+            return Err(GpuDisplayError::Unsupported);
+        }
+
+        let size = width as usize * height as usize * BYTES_PER_PIXEL as usize;
+        Ok(Box::new(ScanoutSurface {
+            surface_id,
+            width,
+            height,
+            scanout_id: None,
+            buffer: vec![0; size],
+            shared: self.shared.clone(),
+        }))
+    }
+}
+
+impl SysDisplayT for ScanoutDisplay {}
+
+impl AsRawDescriptor for ScanoutDisplay {
+    fn as_raw_descriptor(&self) -> RawDescriptor {
+        self.shared.input_evt.as_raw_descriptor()
+    }
+}
@@ -2,12 +2,16 @@
 // Use of this source code is governed by a BSD-style license that can be
 // found in the LICENSE file.
 
+use std::net::TcpListener;
+use std::os::unix::net::UnixListener;
 use std::path::Path;
 
 use base::AsRawDescriptor;
 use base::RawDescriptor;
 use base::WaitContext;
 
+use crate::gpu_display_headless;
+use crate::gpu_display_vnc;
 use crate::gpu_display_wl::DisplayWl;
 use crate::DisplayEventToken;
 use crate::DisplayT;
@@ -38,6 +42,13 @@ impl GpuDisplayExt for GpuDisplay {
 pub trait UnixGpuDisplayExt {
     /// Opens a fresh connection to the compositor.
     fn open_wayland<P: AsRef<Path>>(wayland_path: Option<P>) -> GpuDisplayResult<GpuDisplay>;
+
+    /// Starts a VNC server serving scanout `i` to the clients connecting to `listeners[i]`.
+    fn open_vnc(listeners: Vec<TcpListener>) -> GpuDisplayResult<GpuDisplay>;
+
+    /// Keeps the scanouts in memory and lets the clients connecting to `listener` read them and
+    /// inject input.
+    fn open_headless(listener: UnixListener) -> GpuDisplayResult<GpuDisplay>;
 }
 
 impl UnixGpuDisplayExt for GpuDisplay {
@@ -60,6 +71,40 @@ impl UnixGpuDisplayExt for GpuDisplay {
             is_x: false,
         })
     }
+
+    fn open_vnc(listeners: Vec<TcpListener>) -> GpuDisplayResult<GpuDisplay> {
+        let display = gpu_display_vnc::new_display(listeners)?;
+
+        let wait_ctx = WaitContext::new()?;
+        wait_ctx.add(&display, DisplayEventToken::Display)?;
+
+        Ok(GpuDisplay {
+            inner: Box::new(display),
+            next_id: 1,
+            event_devices: Default::default(),
+            surfaces: Default::default(),
+            imports: Default::default(),
+            wait_ctx,
+            is_x: false,
+        })
+    }
+
+    fn open_headless(listener: UnixListener) -> GpuDisplayResult<GpuDisplay> {
+        let display = gpu_display_headless::new_display(listener)?;
+
+        let wait_ctx = WaitContext::new()?;
+        wait_ctx.add(&display, DisplayEventToken::Display)?;
+
+        Ok(GpuDisplay {
+            inner: Box::new(display),
+            next_id: 1,
+            event_devices: Default::default(),
+            surfaces: Default::default(),
+            imports: Default::default(),
+            wait_ctx,
+            is_x: false,
+        })
+    }
 }
 
 impl AsRawDescriptor for GpuDisplay {
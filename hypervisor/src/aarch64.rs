@@ -92,6 +92,10 @@ pub trait VmAArch64: Vm {
         fdt_address: GuestAddress,
         fdt_size: usize,
     ) -> Result<()>;
+
+    /// Makes the SMCCC calls to the functions `function_ids` exit to the VMM with
+    /// `VcpuExit::Hypercall` instead of being handled by the hypervisor.
+    fn forward_smccc_calls(&self, function_ids: &[u32]) -> Result<()>;
 }
 
 /// A wrapper around creating and using a VCPU on aarch64.
@@ -121,6 +125,15 @@ pub trait VcpuAArch64: Vcpu {
     /// Gets the current PSCI version.
     fn get_psci_version(&self) -> Result<PsciVersion>;
 
+    /// Handles a `VcpuExit::Hypercall` for an SMCCC call forwarded by
+    /// `VmAArch64::forward_smccc_calls`. `handle_fn` is called with the function ID and the first
+    /// three arguments of the call, and returns the value for the guest's x0.
+    fn handle_smccc_call(&self, handle_fn: &mut dyn FnMut(u32, [u64; 3]) -> u64) -> Result<()>;
+
+    /// Powers the VCPU on or off, as the PSCI CPU_ON and CPU_OFF functions do. A powered off VCPU
+    /// must not be run.
+    fn set_power_state(&self, on: bool) -> Result<()>;
+
     #[cfg(feature = "gdb")]
     /// Sets up debug registers and configure vcpu for handling guest debug events.
     fn set_guest_debug(&self, addrs: &[GuestAddress], enable_singlestep: bool) -> Result<()>;
@@ -166,12 +179,14 @@ pub struct VcpuSnapshot {
 
 impl_downcast!(VcpuAArch64);
 
-/// Initial register state for AArch64 VCPUs.
+/// Initial state for AArch64 VCPUs.
 #[derive(Clone, Default)]
 pub struct VcpuInitAArch64 {
     /// Initial register state as a map of register name to value pairs. Registers that do not have
     /// a value specified in this map will retain the original value provided by the hypervisor.
     pub regs: BTreeMap<VcpuRegAArch64, u64>,
+    /// Features the VCPU was initialized with, needed to reset it again.
+    pub features: Vec<VcpuFeature>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
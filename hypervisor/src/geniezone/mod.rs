@@ -201,6 +201,10 @@ impl VmAArch64 for GeniezoneVm {
     ) -> Result<()> {
         Ok(())
     }
+
+    fn forward_smccc_calls(&self, _function_ids: &[u32]) -> Result<()> {
+        Err(Error::new(ENOTSUP))
+    }
 }
 
 impl GeniezoneVcpu {
@@ -442,6 +446,14 @@ impl VcpuAArch64 for GeniezoneVcpu {
         Ok(PSCI_0_2)
     }
 
+    fn handle_smccc_call(&self, _handle_fn: &mut dyn FnMut(u32, [u64; 3]) -> u64) -> Result<()> {
+        Err(Error::new(ENOTSUP))
+    }
+
+    fn set_power_state(&self, _on: bool) -> Result<()> {
+        Err(Error::new(ENOTSUP))
+    }
+
     #[cfg(feature = "gdb")]
     fn get_max_hw_bps(&self) -> Result<usize> {
         // TODO: Geniezone not support gdb currently
@@ -220,6 +220,10 @@ impl VmAArch64 for GunyahVm {
 
         Ok(())
     }
+
+    fn forward_smccc_calls(&self, _function_ids: &[u32]) -> Result<()> {
+        Err(Error::new(ENOTSUP))
+    }
 }
 
 impl VcpuAArch64 for GunyahVcpu {
@@ -251,6 +255,14 @@ impl VcpuAArch64 for GunyahVcpu {
         Ok(PSCI_0_2)
     }
 
+    fn handle_smccc_call(&self, _handle_fn: &mut dyn FnMut(u32, [u64; 3]) -> u64) -> Result<()> {
+        Err(Error::new(ENOTSUP))
+    }
+
+    fn set_power_state(&self, _on: bool) -> Result<()> {
+        Err(Error::new(ENOTSUP))
+    }
+
     #[cfg(feature = "gdb")]
     fn set_guest_debug(&self, _addrs: &[GuestAddress], _enable_singlestep: bool) -> Result<()> {
         Err(Error::new(ENOTSUP))
@@ -177,6 +177,19 @@ struct KvmProtectedVmInfo {
     reserved: [u64; 7],
 }
 
+// SMCCC filter of the VM, from the linux/kvm.h of Linux 6.4 which is newer than the bindings.
+const KVM_ARM_VM_SMCCC_CTRL: u32 = 0;
+const KVM_ARM_VM_SMCCC_FILTER: u64 = 0;
+const KVM_SMCCC_FILTER_FWD_TO_USER: u8 = 2;
+
+#[repr(C)]
+struct KvmSmcccFilter {
+    base: u32,
+    nr_functions: u32,
+    action: u8,
+    pad: [u8; 15],
+}
+
 impl VmAArch64 for KvmVm {
     fn get_hypervisor(&self) -> &dyn Hypervisor {
         &self.kvm
@@ -220,6 +233,30 @@ impl VmAArch64 for KvmVm {
     ) -> Result<()> {
         Ok(())
     }
+
+    fn forward_smccc_calls(&self, function_ids: &[u32]) -> Result<()> {
+        for &function_id in function_ids {
+            let filter = KvmSmcccFilter {
+                base: function_id,
+                nr_functions: 1,
+                action: KVM_SMCCC_FILTER_FWD_TO_USER,
+                pad: [0; 15],
+            };
+            let filter_attr = kvm_device_attr {
+                group: KVM_ARM_VM_SMCCC_CTRL,
+                attr: KVM_ARM_VM_SMCCC_FILTER,
+                addr: &filter as *const KvmSmcccFilter as u64,
+                flags: 0,
+            };
+            // Safe because we allocated the struct and we know the kernel will read exactly the
+            // size of the struct.
+            let ret = unsafe { ioctl_with_ref(self, KVM_SET_DEVICE_ATTR(), &filter_attr) };
+            if ret < 0 {
+                return errno_result();
+            }
+        }
+        Ok(())
+    }
 }
 
 impl KvmVcpu {
@@ -681,6 +718,37 @@ impl VcpuAArch64 for KvmVcpu {
         }
     }
 
+    fn handle_smccc_call(&self, handle_fn: &mut dyn FnMut(u32, [u64; 3]) -> u64) -> Result<()> {
+        // Safe because we know we mapped enough memory to hold the kvm_run struct because the
+        // kernel told us how large it was.
+        let run = unsafe { &*(self.run_mmap.as_ptr() as *const kvm_run) };
+        // Verify that the handler is called in the right context.
+        assert!(run.exit_reason == KVM_EXIT_HYPERCALL);
+        // Safe because the exit_reason (which comes from the kernel) told us which
+        // union field to use.
+        let function_id = unsafe { run.__bindgen_anon_1.hypercall.nr } as u32;
+        // KVM leaves the arguments in the registers and already moved the PC past the HVC or SMC
+        // instruction.
+        let args = [
+            self.get_one_reg(VcpuRegAArch64::X(1))?,
+            self.get_one_reg(VcpuRegAArch64::X(2))?,
+            self.get_one_reg(VcpuRegAArch64::X(3))?,
+        ];
+        let ret = handle_fn(function_id, args);
+        self.set_one_reg(VcpuRegAArch64::X(0), ret)
+    }
+
+    fn set_power_state(&self, on: bool) -> Result<()> {
+        let mp_state = kvm_mp_state {
+            mp_state: if on {
+                KVM_MP_STATE_RUNNABLE
+            } else {
+                KVM_MP_STATE_STOPPED
+            },
+        };
+        self.set_mp_state(&mp_state)
+    }
+
     #[cfg(feature = "gdb")]
     fn get_max_hw_bps(&self) -> Result<usize> {
         // Safe because the kernel will only return the result of the ioctl.
@@ -13,7 +13,9 @@ use thiserror::Error;
 use crate::DecodedFormat;
 use crate::Resolution;
 
+pub mod av1;
 pub mod h264;
+pub mod h265;
 pub mod vp8;
 pub mod vp9;
 
@@ -0,0 +1,60 @@
+// Copyright 2023 The ChromiumOS Authors
+// Use of this source code is governed by a BSD-style license that can be
+// found in the LICENSE file.
+
+use crate::decoders::av1::parser::FrameHeader;
+use crate::decoders::av1::parser::SequenceHeader;
+use crate::decoders::av1::parser::TileGroup;
+use crate::decoders::av1::parser::NUM_REF_FRAMES;
+use crate::decoders::BlockingMode;
+use crate::decoders::VideoDecoderBackend;
+
+#[cfg(test)]
+pub mod dummy;
+#[cfg(feature = "vaapi")]
+pub mod vaapi;
+
+pub type Result<T> = crate::decoders::StatelessBackendResult<T>;
+
+/// Trait for stateless decoder backends. The decoder will call into the backend
+/// to request decode operations. The backend can operate in blocking mode,
+/// where it will wait until the current decode finishes, or in non-blocking
+/// mode, where it should return immediately with any previously decoded frames
+/// that happen to be ready.
+pub(crate) trait StatelessDecoderBackend: VideoDecoderBackend {
+    /// Called when a new sequence header is parsed.
+    fn new_sequence(&mut self, sequence: &SequenceHeader) -> Result<()>;
+
+    /// Called when the decoder determines that a new frame was found, i.e. for
+    /// every frame header that is not a show_existing_frame.
+    fn new_picture(
+        &mut self,
+        sequence: &SequenceHeader,
+        picture: &FrameHeader,
+        timestamp: u64,
+        reference_frames: &[Option<Self::Handle>; NUM_REF_FRAMES],
+    ) -> Result<()>;
+
+    /// Called to dispatch a decode operation to the backend for every tile
+    /// group of the current frame.
+    fn decode_tile_group(&mut self, tile_group: &TileGroup) -> Result<()>;
+
+    /// Called when the decoder wants the backend to finish the decoding
+    /// operations for `picture`. At this point, `decode_tile_group` has been
+    /// called for all tile groups. The argument `block` dictates whether this
+    /// call should wait until the current decode finishes, or whether it should
+    /// return immediately.
+    ///
+    /// This call will assign the ownership of the BackendHandle to the Picture
+    /// and then assign the ownership of the Picture to the Handle.
+    fn submit_picture(
+        &mut self,
+        picture: &FrameHeader,
+        block: BlockingMode,
+    ) -> Result<Self::Handle>;
+
+    /// Get the test parameters for the backend. The caller is reponsible for
+    /// downcasting them to the correct type, which is backend-dependent.
+    #[cfg(test)]
+    fn get_test_params(&self) -> &dyn std::any::Any;
+}
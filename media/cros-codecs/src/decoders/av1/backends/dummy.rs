@@ -0,0 +1,57 @@
+// Copyright 2023 The ChromiumOS Authors
+// Use of this source code is governed by a BSD-style license that can be
+// found in the LICENSE file.
+
+//! This file contains a dummy backend whose only purpose is to let the decoder
+//! run so we can test it in isolation.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::decoders::av1::backends::StatelessDecoderBackend;
+use crate::decoders::av1::decoder::Decoder;
+use crate::decoders::av1::parser::FrameHeader;
+use crate::decoders::av1::parser::SequenceHeader;
+use crate::decoders::av1::parser::TileGroup;
+use crate::decoders::av1::parser::NUM_REF_FRAMES;
+use crate::decoders::BlockingMode;
+use crate::utils::dummy::*;
+
+impl StatelessDecoderBackend for Backend {
+    fn new_sequence(&mut self, _: &SequenceHeader) -> super::Result<()> {
+        Ok(())
+    }
+
+    fn new_picture(
+        &mut self,
+        _: &SequenceHeader,
+        _: &FrameHeader,
+        _: u64,
+        _: &[Option<Self::Handle>; NUM_REF_FRAMES],
+    ) -> super::Result<()> {
+        Ok(())
+    }
+
+    fn decode_tile_group(&mut self, _: &TileGroup) -> super::Result<()> {
+        Ok(())
+    }
+
+    fn submit_picture(&mut self, _: &FrameHeader, _: BlockingMode) -> super::Result<Self::Handle> {
+        Ok(Handle {
+            handle: Rc::new(RefCell::new(BackendHandle)),
+        })
+    }
+
+    #[cfg(test)]
+    fn get_test_params(&self) -> &dyn std::any::Any {
+        // There are no test parameters for the dummy backend.
+        unimplemented!()
+    }
+}
+
+impl Decoder<Handle> {
+    // Creates a new instance of the decoder using the dummy backend.
+    pub fn new_dummy(blocking_mode: BlockingMode) -> anyhow::Result<Self> {
+        Self::new(Box::new(Backend::new()), blocking_mode)
+    }
+}
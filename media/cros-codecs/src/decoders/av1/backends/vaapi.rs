@@ -0,0 +1,750 @@
+// Copyright 2023 The ChromiumOS Authors
+// Use of this source code is governed by a BSD-style license that can be
+// found in the LICENSE file.
+
+use std::collections::VecDeque;
+use std::rc::Rc;
+
+use anyhow::anyhow;
+use anyhow::Result;
+use libva::AV1FilmGrainInfoFields;
+use libva::AV1LoopFilterInfoFields;
+use libva::AV1LoopRestorationFields;
+use libva::AV1ModeControlFields;
+use libva::AV1PicInfoFields;
+use libva::AV1QMatrixFields;
+use libva::AV1SegmentInfoFields;
+use libva::AV1SeqInfoFields;
+use libva::BufferType;
+use libva::Display;
+use libva::FilmGrainStructAV1;
+use libva::Picture as VaPicture;
+use libva::PictureNew;
+use libva::PictureParameter;
+use libva::PictureParameterBufferAV1;
+use libva::SegmentationStructAV1;
+use libva::SliceParameter;
+use libva::SliceParameterBufferAV1;
+use libva::WarpedMotionParamsAV1;
+use log::debug;
+
+use crate::decoders::av1::backends::Result as StatelessBackendResult;
+use crate::decoders::av1::backends::StatelessDecoderBackend;
+use crate::decoders::av1::decoder::Decoder;
+use crate::decoders::av1::parser::FrameHeader;
+use crate::decoders::av1::parser::Profile;
+use crate::decoders::av1::parser::SequenceHeader;
+use crate::decoders::av1::parser::TileGroup;
+use crate::decoders::av1::parser::WarpModelType;
+use crate::decoders::av1::parser::MAX_SEGMENTS;
+use crate::decoders::av1::parser::NUM_REF_FRAMES;
+use crate::decoders::av1::parser::REFS_PER_FRAME;
+use crate::decoders::av1::parser::SEG_LVL_MAX;
+use crate::decoders::av1::parser::WARPEDMODEL_PREC_BITS;
+use crate::decoders::BlockingMode;
+use crate::decoders::DecodedHandle;
+use crate::decoders::Result as DecoderResult;
+use crate::decoders::StatelessBackendError;
+use crate::decoders::VideoDecoderBackend;
+use crate::utils::vaapi::DecodedHandle as VADecodedHandle;
+use crate::utils::vaapi::NegotiationStatus;
+use crate::utils::vaapi::StreamInfo;
+use crate::utils::vaapi::VaapiBackend;
+use crate::DecodedFormat;
+use crate::Resolution;
+
+/// The number of entries of the `width_in_sbs_minus_1` and `height_in_sbs_minus_1` arrays of
+/// `VADecPictureParameterBufferAV1`.
+const NUM_VA_TILE_SIZES: usize = 63;
+
+#[cfg(test)]
+#[derive(Default)]
+struct TestParams {
+    pic_param: Option<BufferType>,
+    slice_param: Option<BufferType>,
+    slice_data: Option<BufferType>,
+}
+
+#[cfg(test)]
+impl TestParams {
+    fn save_pic_params(&mut self, pic_param: BufferType) {
+        self.pic_param = Some(pic_param);
+    }
+
+    fn save_slice_params(&mut self, slice_param: BufferType, slice_data: BufferType) {
+        self.slice_param = Some(slice_param);
+        self.slice_data = Some(slice_data);
+    }
+}
+
+impl StreamInfo for &SequenceHeader {
+    fn va_profile(&self) -> anyhow::Result<i32> {
+        match self.seq_profile() {
+            Profile::Profile0 => Ok(libva::VAProfile::VAProfileAV1Profile0),
+            Profile::Profile1 => Ok(libva::VAProfile::VAProfileAV1Profile1),
+            Profile::Profile2 => Err(anyhow!("Unsupported seq_profile 2")),
+        }
+    }
+
+    fn rt_format(&self) -> anyhow::Result<u32> {
+        let bit_depth = self.bit_depth();
+        let color_config = self.color_config();
+
+        match bit_depth {
+            8 => match (color_config.subsampling_x(), color_config.subsampling_y()) {
+                (true, true) => Ok(libva::constants::VA_RT_FORMAT_YUV420),
+                (subsampling_x, subsampling_y) => Err(anyhow!(
+                    "Unsupported subsampling: subsampling_x {} subsampling_y {}",
+                    subsampling_x,
+                    subsampling_y
+                )),
+            },
+            _ => Err(anyhow!("Unsupported bit depth: {}", bit_depth)),
+        }
+    }
+
+    fn min_num_surfaces(&self) -> usize {
+        NUM_REF_FRAMES + 4
+    }
+
+    fn coded_size(&self) -> (u32, u32) {
+        (self.max_frame_width(), self.max_frame_height())
+    }
+
+    fn visible_rect(&self) -> ((u32, u32), (u32, u32)) {
+        ((0, 0), self.coded_size())
+    }
+}
+
+/// Returns the entry `f` of the Div_Lut table of the specification, i.e. the reciprocal of
+/// `(1 << DIV_LUT_BITS) + f` with DIV_LUT_PREC_BITS of precision, rounded to the nearest.
+fn div_lut(f: i64) -> i64 {
+    const DIV_LUT_BITS: u32 = 8;
+    const DIV_LUT_PREC_BITS: u32 = 14;
+
+    let divisor = (1 << DIV_LUT_BITS) + f;
+    ((1 << (DIV_LUT_BITS + DIV_LUT_PREC_BITS)) + divisor / 2) / divisor
+}
+
+/// Whether the global motion `gm_params` yield a valid warp, i.e. warpValid as computed by the
+/// setup shear process, see 7.11.3.6. VA-API needs invalid global motion to be flagged.
+fn shear_params_valid(gm_params: &[i32; 6]) -> bool {
+    const DIV_LUT_BITS: u32 = 8;
+    const DIV_LUT_PREC_BITS: u32 = 14;
+    const WARP_PARAM_REDUCE_BITS: u32 = 6;
+
+    fn round2_signed(x: i64, n: u32) -> i64 {
+        if n == 0 {
+            return x;
+        }
+
+        let round2 = |x: i64| (x + (1 << (n - 1))) >> n;
+        if x >= 0 {
+            round2(x)
+        } else {
+            -round2(-x)
+        }
+    }
+
+    let clip = |x: i64| x.clamp(i16::MIN.into(), i16::MAX.into());
+    let reduce = |x: i64| round2_signed(x, WARP_PARAM_REDUCE_BITS) << WARP_PARAM_REDUCE_BITS;
+
+    let d = i64::from(gm_params[2]);
+    if d == 0 {
+        return false;
+    }
+
+    // resolveDivisor(d).
+    let n = 63 - d.unsigned_abs().leading_zeros();
+    let e = d.abs() - (1 << n);
+    let f = if n > DIV_LUT_BITS {
+        round2_signed(e, n - DIV_LUT_BITS)
+    } else {
+        e << (DIV_LUT_BITS - n)
+    };
+    let div_shift = n + DIV_LUT_PREC_BITS;
+    let div_factor = if d < 0 { -div_lut(f) } else { div_lut(f) };
+
+    let one = 1i64 << WARPEDMODEL_PREC_BITS;
+    let alpha = reduce(clip(d - one));
+    let beta = reduce(clip(i64::from(gm_params[3])));
+    let v = i64::from(gm_params[4]) << WARPEDMODEL_PREC_BITS;
+    let gamma = reduce(clip(round2_signed(v * div_factor, div_shift)));
+    let w = i64::from(gm_params[3]) * i64::from(gm_params[4]);
+    let delta = reduce(clip(
+        i64::from(gm_params[5]) - round2_signed(w * div_factor, div_shift) - one,
+    ));
+
+    4 * alpha.abs() + 7 * beta.abs() < one && 4 * gamma.abs() + 4 * delta.abs() < one
+}
+
+/// AV1 stateless decoder backend for VA-API.
+struct Backend {
+    backend: VaapiBackend<SequenceHeader>,
+
+    /// The current picture being worked on.
+    current_picture: Option<VaPicture<PictureNew>>,
+
+    #[cfg(test)]
+    /// Test params. Saves the metadata sent to VA-API for the purposes of
+    /// testing.
+    test_params: TestParams,
+}
+
+impl Backend {
+    /// Creates a new codec backend for AV1.
+    fn new(display: Rc<libva::Display>) -> Result<Self> {
+        Ok(Self {
+            backend: VaapiBackend::new(display),
+            current_picture: Default::default(),
+
+            #[cfg(test)]
+            test_params: Default::default(),
+        })
+    }
+
+    /// Gets the VASurfaceID for the given `handle`.
+    fn surface_id(handle: &VADecodedHandle) -> libva::VASurfaceID {
+        handle.handle().surface_id()
+    }
+
+    /// Builds the sizes in superblocks of the tiles that start at `mi_starts`, as expected by
+    /// VA-API. Both the uniform and the explicit tile spacing are covered this way.
+    fn tile_sizes_in_sbs_minus_1(
+        mi_starts: &[u32],
+        num_tiles: u32,
+        sb_shift: u32,
+    ) -> Result<[u16; NUM_VA_TILE_SIZES]> {
+        let mut sizes = [0; NUM_VA_TILE_SIZES];
+
+        for (i, size) in sizes.iter_mut().enumerate().take(num_tiles as usize) {
+            let size_in_sbs = (mi_starts[i + 1] - mi_starts[i] + (1 << sb_shift) - 1) >> sb_shift;
+            *size = u16::try_from(size_in_sbs - 1)?;
+        }
+
+        Ok(sizes)
+    }
+
+    /// Builds the segmentation parameters of `hdr`.
+    fn build_segmentation(hdr: &FrameHeader) -> SegmentationStructAV1 {
+        let seg = hdr.segmentation_params();
+
+        let segment_info_fields = AV1SegmentInfoFields::new(
+            seg.segmentation_enabled() as u32,
+            seg.segmentation_update_map() as u32,
+            seg.segmentation_temporal_update() as u32,
+            seg.segmentation_update_data() as u32,
+        );
+
+        let mut feature_mask = [0u8; MAX_SEGMENTS];
+        for (mask, enabled) in feature_mask.iter_mut().zip(seg.feature_enabled()) {
+            for (j, enabled) in enabled.iter().enumerate().take(SEG_LVL_MAX) {
+                *mask |= (*enabled as u8) << j;
+            }
+        }
+
+        SegmentationStructAV1::new(&segment_info_fields, *seg.feature_data(), feature_mask)
+    }
+
+    /// Builds the film grain parameters. Applying the film grain requires a display surface
+    /// separate from the reference one, which this backend does not allocate, so frames are
+    /// output without it.
+    fn build_film_grain() -> FilmGrainStructAV1 {
+        let film_grain_info_fields = AV1FilmGrainInfoFields::new(0, 0, 0, 0, 0, 0, 0, 0);
+
+        FilmGrainStructAV1::new(
+            &film_grain_info_fields,
+            0,
+            0,
+            Default::default(),
+            Default::default(),
+            0,
+            Default::default(),
+            Default::default(),
+            0,
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            [0; 25],
+            [0; 25],
+            0,
+            0,
+            0,
+            0,
+            0,
+            0,
+        )
+    }
+
+    /// Builds the global motion parameters of the references of `hdr`.
+    fn build_warped_motion(hdr: &FrameHeader) -> [WarpedMotionParamsAV1; REFS_PER_FRAME] {
+        let gm = hdr.global_motion_params();
+
+        std::array::from_fn(|i| {
+            // Indexed by reference frame type, starting at LAST_FRAME.
+            let ref_frame = i + 1;
+            let wmtype = match gm.gm_type()[ref_frame] {
+                WarpModelType::Identity => {
+                    libva::VAAV1TransformationType::VAAV1TransformationIdentity
+                }
+                WarpModelType::Translation => {
+                    libva::VAAV1TransformationType::VAAV1TransformationTranslation
+                }
+                WarpModelType::RotZoom => {
+                    libva::VAAV1TransformationType::VAAV1TransformationRotzoom
+                }
+                WarpModelType::Affine => libva::VAAV1TransformationType::VAAV1TransformationAffine,
+            };
+
+            let gm_params = &gm.gm_params()[ref_frame];
+            let mut wmmat = [0; 8];
+            wmmat[..6].copy_from_slice(gm_params);
+
+            WarpedMotionParamsAV1::new(wmtype, wmmat, !shear_params_valid(gm_params) as u8)
+        })
+    }
+
+    /// Builds the CDEF strengths of VA-API from their primary and secondary strengths. The
+    /// parser stores the secondary strengths as used by the decoding process, where 3 means 4.
+    fn build_cdef_strengths(pri_strengths: &[u8], sec_strengths: &[u8]) -> [u8; 8] {
+        let mut strengths = [0; 8];
+
+        for ((strength, pri), sec) in strengths.iter_mut().zip(pri_strengths).zip(sec_strengths) {
+            let sec = if *sec == 4 { 3 } else { *sec };
+            *strength = (pri << 2) | sec;
+        }
+
+        strengths
+    }
+
+    fn build_pic_param(
+        seq: &SequenceHeader,
+        hdr: &FrameHeader,
+        current_frame: libva::VASurfaceID,
+        reference_frames: &[Option<VADecodedHandle>; NUM_REF_FRAMES],
+    ) -> Result<BufferType> {
+        let cc = seq.color_config();
+
+        let bit_depth_idx = match seq.bit_depth() {
+            8 => 0,
+            10 => 1,
+            12 => 2,
+            bit_depth => return Err(anyhow!("Invalid bit depth {}", bit_depth)),
+        };
+
+        let seq_info_fields = AV1SeqInfoFields::new(
+            seq.still_picture() as u32,
+            seq.use_128x128_superblock() as u32,
+            seq.enable_filter_intra() as u32,
+            seq.enable_intra_edge_filter() as u32,
+            seq.enable_interintra_compound() as u32,
+            seq.enable_masked_compound() as u32,
+            seq.enable_dual_filter() as u32,
+            seq.enable_order_hint() as u32,
+            seq.enable_jnt_comp() as u32,
+            seq.enable_cdef() as u32,
+            cc.mono_chrome() as u32,
+            cc.color_range() as u32,
+            cc.subsampling_x() as u32,
+            cc.subsampling_y() as u32,
+            cc.chroma_sample_position() as u32,
+            seq.film_grain_params_present() as u32,
+        );
+
+        let ref_frame_map = reference_frames.clone().map(|handle| match handle {
+            Some(handle) => Self::surface_id(&handle),
+            None => libva::constants::VA_INVALID_SURFACE,
+        });
+
+        let ti = hdr.tile_info();
+        let sb_shift = if seq.use_128x128_superblock() { 5 } else { 4 };
+        let width_in_sbs_minus_1 =
+            Self::tile_sizes_in_sbs_minus_1(ti.mi_col_starts(), ti.tile_cols(), sb_shift)?;
+        let height_in_sbs_minus_1 =
+            Self::tile_sizes_in_sbs_minus_1(ti.mi_row_starts(), ti.tile_rows(), sb_shift)?;
+
+        let pic_info_fields = AV1PicInfoFields::new(
+            hdr.frame_type() as u32,
+            hdr.show_frame() as u32,
+            hdr.showable_frame() as u32,
+            hdr.error_resilient_mode() as u32,
+            hdr.disable_cdf_update() as u32,
+            hdr.allow_screen_content_tools() as u32,
+            hdr.force_integer_mv() as u32,
+            hdr.allow_intrabc() as u32,
+            hdr.use_superres() as u32,
+            hdr.allow_high_precision_mv() as u32,
+            hdr.is_motion_mode_switchable() as u32,
+            hdr.use_ref_frame_mvs() as u32,
+            hdr.disable_frame_end_update_cdf() as u32,
+            ti.uniform_tile_spacing_flag() as u32,
+            hdr.allow_warped_motion() as u32,
+            0, /* large_scale_tile */
+        );
+
+        let lf = hdr.loop_filter_params();
+        let loop_filter_info_fields = AV1LoopFilterInfoFields::new(
+            lf.loop_filter_sharpness(),
+            lf.loop_filter_delta_enabled() as u8,
+            lf.loop_filter_delta_update() as u8,
+        );
+
+        let qp = hdr.quantization_params();
+        let qmatrix_fields = AV1QMatrixFields::new(
+            qp.using_qmatrix() as u16,
+            u16::from(qp.qm_y()),
+            u16::from(qp.qm_u()),
+            u16::from(qp.qm_v()),
+        );
+
+        let mode_control_fields = AV1ModeControlFields::new(
+            qp.delta_q_present() as u32,
+            u32::from(qp.delta_q_res()),
+            lf.delta_lf_present() as u32,
+            u32::from(lf.delta_lf_res()),
+            lf.delta_lf_multi() as u32,
+            hdr.tx_mode() as u32,
+            hdr.reference_select() as u32,
+            hdr.reduced_tx_set() as u32,
+            hdr.skip_mode_present() as u32,
+        );
+
+        let cdef = hdr.cdef_params();
+        let lr = hdr.loop_restoration_params();
+        let loop_restoration_fields = AV1LoopRestorationFields::new(
+            lr.frame_restoration_type()[0] as u16,
+            lr.frame_restoration_type()[1] as u16,
+            lr.frame_restoration_type()[2] as u16,
+            u16::from(lr.lr_unit_shift()),
+            u16::from(lr.lr_uv_shift()),
+        );
+
+        let pic_param = PictureParameterBufferAV1::new(
+            seq.seq_profile() as u8,
+            seq.order_hint_bits().saturating_sub(1),
+            bit_depth_idx,
+            cc.matrix_coefficients() as u8,
+            &seq_info_fields,
+            current_frame,
+            current_frame,
+            u16::try_from(hdr.frame_width() - 1)?,
+            u16::try_from(hdr.frame_height() - 1)?,
+            ref_frame_map,
+            *hdr.ref_frame_idx(),
+            hdr.primary_ref_frame(),
+            hdr.order_hint() as u8,
+            &Self::build_segmentation(hdr),
+            &Self::build_film_grain(),
+            ti.tile_cols() as u8,
+            ti.tile_rows() as u8,
+            width_in_sbs_minus_1,
+            height_in_sbs_minus_1,
+            u16::try_from(ti.tile_cols() * ti.tile_rows() - 1)?,
+            u16::try_from(ti.context_update_tile_id())?,
+            &pic_info_fields,
+            hdr.superres_denom() as u8,
+            hdr.interpolation_filter() as u8,
+            [lf.loop_filter_level()[0], lf.loop_filter_level()[1]],
+            lf.loop_filter_level()[2],
+            lf.loop_filter_level()[3],
+            &loop_filter_info_fields,
+            *lf.loop_filter_ref_deltas(),
+            *lf.loop_filter_mode_deltas(),
+            qp.base_q_idx(),
+            qp.delta_q_y_dc(),
+            qp.delta_q_u_dc(),
+            qp.delta_q_u_ac(),
+            qp.delta_q_v_dc(),
+            qp.delta_q_v_ac(),
+            &qmatrix_fields,
+            &mode_control_fields,
+            cdef.cdef_damping() - 3,
+            cdef.cdef_bits(),
+            Self::build_cdef_strengths(cdef.cdef_y_pri_strength(), cdef.cdef_y_sec_strength()),
+            Self::build_cdef_strengths(cdef.cdef_uv_pri_strength(), cdef.cdef_uv_sec_strength()),
+            &loop_restoration_fields,
+            Self::build_warped_motion(hdr),
+        );
+
+        Ok(BufferType::PictureParameter(PictureParameter::AV1(
+            pic_param,
+        )))
+    }
+
+    fn build_slice_param(tile_group: &TileGroup, tile_num: usize) -> Result<BufferType> {
+        let tile = &tile_group.tiles[tile_num];
+
+        let slice_param = SliceParameterBufferAV1::new(
+            tile.tile_size,
+            0,
+            libva::constants::VA_SLICE_DATA_FLAG_ALL,
+            u16::try_from(tile.tile_row)?,
+            u16::try_from(tile.tile_col)?,
+            u16::try_from(tile_group.tg_start)?,
+            u16::try_from(tile_group.tg_end)?,
+            0,
+            0,
+        );
+
+        Ok(BufferType::SliceParameter(SliceParameter::AV1(slice_param)))
+    }
+
+    /// Returns the data of tile `tile_num` of `tile_group`.
+    fn build_slice_data(tile_group: &TileGroup, tile_num: usize) -> BufferType {
+        let tile = &tile_group.tiles[tile_num];
+        let start = tile.tile_offset as usize;
+        let end = start + tile.tile_size as usize;
+
+        BufferType::SliceData(Vec::from(&tile_group.data[start..end]))
+    }
+}
+
+impl VideoDecoderBackend for Backend {
+    type Handle = VADecodedHandle;
+
+    fn num_resources_total(&self) -> usize {
+        self.backend.num_resources_total()
+    }
+
+    fn num_resources_left(&self) -> usize {
+        self.backend.num_resources_left()
+    }
+
+    fn format(&self) -> Option<DecodedFormat> {
+        self.backend.format()
+    }
+
+    fn try_format(&mut self, format: DecodedFormat) -> DecoderResult<()> {
+        self.backend.try_format(format)
+    }
+
+    fn coded_resolution(&self) -> Option<Resolution> {
+        self.backend.coded_resolution()
+    }
+
+    fn display_resolution(&self) -> Option<Resolution> {
+        self.backend.display_resolution()
+    }
+
+    fn poll(&mut self, blocking_mode: BlockingMode) -> DecoderResult<VecDeque<Self::Handle>> {
+        self.backend.poll(blocking_mode)
+    }
+
+    fn handle_is_ready(&self, handle: &Self::Handle) -> bool {
+        self.backend.handle_is_ready(handle)
+    }
+
+    fn block_on_handle(&mut self, handle: &Self::Handle) -> StatelessBackendResult<()> {
+        self.backend.block_on_handle(handle)
+    }
+}
+
+impl StatelessDecoderBackend for Backend {
+    fn new_sequence(&mut self, sequence: &SequenceHeader) -> StatelessBackendResult<()> {
+        self.backend.metadata_state.open(sequence, None)?;
+        self.backend.negotiation_status = NegotiationStatus::Possible(Box::new(sequence.clone()));
+
+        Ok(())
+    }
+
+    fn new_picture(
+        &mut self,
+        sequence: &SequenceHeader,
+        picture: &FrameHeader,
+        timestamp: u64,
+        reference_frames: &[Option<Self::Handle>; NUM_REF_FRAMES],
+    ) -> StatelessBackendResult<()> {
+        debug!("Va-API backend: new_picture for timestamp {:?}", timestamp);
+
+        self.backend.negotiation_status = NegotiationStatus::Negotiated;
+
+        let metadata = self.backend.metadata_state.get_parsed_mut()?;
+
+        let surface = metadata
+            .surface_pool
+            .get_surface()
+            .ok_or(StatelessBackendError::OutOfResources)?;
+
+        let mut va_pic = VaPicture::new(timestamp, Rc::clone(&metadata.context), surface);
+        let surface_id = va_pic.surface().id();
+
+        let pic_param = metadata.context.create_buffer(Backend::build_pic_param(
+            sequence,
+            picture,
+            surface_id,
+            reference_frames,
+        )?)?;
+        va_pic.add_buffer(pic_param);
+
+        #[cfg(test)]
+        self.test_params.save_pic_params(Backend::build_pic_param(
+            sequence,
+            picture,
+            surface_id,
+            reference_frames,
+        )?);
+
+        self.current_picture = Some(va_pic);
+
+        Ok(())
+    }
+
+    fn decode_tile_group(&mut self, tile_group: &TileGroup) -> StatelessBackendResult<()> {
+        let metadata = self.backend.metadata_state.get_parsed()?;
+        let context = &metadata.context;
+
+        let cur_va_pic = self.current_picture.as_mut().unwrap();
+
+        for tile_num in 0..tile_group.tiles.len() {
+            let slice_param =
+                context.create_buffer(Backend::build_slice_param(tile_group, tile_num)?)?;
+            let slice_data =
+                context.create_buffer(Backend::build_slice_data(tile_group, tile_num))?;
+
+            cur_va_pic.add_buffer(slice_param);
+            cur_va_pic.add_buffer(slice_data);
+        }
+
+        #[cfg(test)]
+        if let Some(tile_num) = tile_group.tiles.len().checked_sub(1) {
+            self.test_params.save_slice_params(
+                Backend::build_slice_param(tile_group, tile_num)?,
+                Backend::build_slice_data(tile_group, tile_num),
+            );
+        }
+
+        Ok(())
+    }
+
+    fn submit_picture(
+        &mut self,
+        _: &FrameHeader,
+        block: BlockingMode,
+    ) -> StatelessBackendResult<Self::Handle> {
+        let current_picture = self.current_picture.take().unwrap();
+
+        self.backend.process_picture(current_picture, block)
+    }
+
+    #[cfg(test)]
+    fn get_test_params(&self) -> &dyn std::any::Any {
+        &self.test_params
+    }
+}
+
+impl Decoder<VADecodedHandle> {
+    // Creates a new instance of the decoder using the VAAPI backend.
+    pub fn new_vaapi(display: Rc<Display>, blocking_mode: BlockingMode) -> Result<Self> {
+        Self::new(Box::new(Backend::new(display)?), blocking_mode)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use libva::Display;
+
+    use crate::decoders::av1::backends::vaapi::div_lut;
+    use crate::decoders::av1::backends::vaapi::shear_params_valid;
+    use crate::decoders::av1::backends::vaapi::TestParams;
+    use crate::decoders::av1::backends::StatelessDecoderBackend;
+    use crate::decoders::av1::decoder::tests::inter_frame;
+    use crate::decoders::av1::decoder::tests::process_ready_frames;
+    use crate::decoders::av1::decoder::tests::run_decoding_loop;
+    use crate::decoders::av1::decoder::tests::temporal_unit;
+    use crate::decoders::av1::decoder::Decoder;
+    use crate::decoders::av1::parser::tests::frame;
+    use crate::decoders::av1::parser::tests::sequence_header;
+    use crate::decoders::av1::parser::FrameType;
+    use crate::decoders::av1::parser::ObuType;
+    use crate::decoders::av1::parser::REFS_PER_FRAME;
+    use crate::decoders::BlockingMode;
+    use crate::decoders::DecodedHandle;
+    use crate::decoders::DynHandle;
+    use crate::utils::vaapi::DecodedHandle as VADecodedHandle;
+
+    fn get_test_params(
+        backend: &dyn StatelessDecoderBackend<Handle = VADecodedHandle>,
+    ) -> &TestParams {
+        backend
+            .get_test_params()
+            .downcast_ref::<TestParams>()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_div_lut() {
+        // A few entries of the Div_Lut table of the specification.
+        assert_eq!(div_lut(0), 16384);
+        assert_eq!(div_lut(1), 16320);
+        assert_eq!(div_lut(2), 16257);
+        assert_eq!(div_lut(128), 10923);
+        assert_eq!(div_lut(255), 8208);
+        assert_eq!(div_lut(256), 8192);
+    }
+
+    #[test]
+    fn test_shear_params_valid() {
+        let one = 1 << 16;
+
+        // The identity and translations are always valid.
+        assert!(shear_params_valid(&[0, 0, one, 0, 0, one]));
+        assert!(shear_params_valid(&[1 << 10, -(1 << 10), one, 0, 0, one]));
+        // A small rotation.
+        assert!(shear_params_valid(&[0, 0, one, 1 << 10, -(1 << 10), one]));
+        // Too much horizontal shear.
+        assert!(!shear_params_valid(&[0, 0, one, 1 << 14, 0, one]));
+        // Too much vertical shear.
+        assert!(!shear_params_valid(&[0, 0, one, 0, 1 << 15, one]));
+        // A degenerate model.
+        assert!(!shear_params_valid(&[0, 0, 0, 0, 0, one]));
+    }
+
+    #[test]
+    // Ignore this test by default as it requires libva-compatible hardware.
+    #[ignore]
+    fn test_64x64_key_and_inter() {
+        // This test is the same as av1::decoder::tests::test_64x64_key_and_inter, but with an
+        // actual backend to test whether the backend specific logic works.
+        let key_frame = frame(
+            ObuType::Frame,
+            FrameType::KeyFrame,
+            true,
+            0,
+            0xff,
+            [0; REFS_PER_FRAME],
+        );
+
+        let temporal_units = vec![
+            temporal_unit(&[sequence_header(), key_frame]),
+            temporal_unit(&[inter_frame(true, 1, 0x01)]),
+            temporal_unit(&[inter_frame(true, 2, 0x01)]),
+            temporal_unit(&[inter_frame(true, 3, 0x01)]),
+        ];
+
+        let blocking_modes = [BlockingMode::Blocking, BlockingMode::NonBlocking];
+
+        for blocking_mode in blocking_modes {
+            let mut frame_num = 0;
+            let display = Display::open().unwrap();
+            let mut decoder = Decoder::new_vaapi(display, blocking_mode).unwrap();
+
+            run_decoding_loop(&mut decoder, &temporal_units, |decoder| {
+                process_ready_frames(decoder, &mut |decoder, handle| {
+                    let params = get_test_params(decoder.backend());
+                    assert!(params.pic_param.is_some());
+                    assert!(params.slice_param.is_some());
+                    assert!(params.slice_data.is_some());
+
+                    let mut picture = handle.handle_mut();
+                    let mut backend_handle = picture.dyn_mappable_handle_mut();
+                    let mut nv12 = vec![0; backend_handle.image_size()];
+                    backend_handle.read(&mut nv12).unwrap();
+
+                    frame_num += 1;
+                });
+            });
+
+            assert_eq!(frame_num, temporal_units.len());
+        }
+    }
+}
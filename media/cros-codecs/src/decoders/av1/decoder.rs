@@ -0,0 +1,657 @@
+// Copyright 2023 The ChromiumOS Authors
+// Use of this source code is governed by a BSD-style license that can be
+// found in the LICENSE file.
+
+use anyhow::anyhow;
+use anyhow::Result;
+use log::debug;
+
+use crate::decoders::av1::backends::StatelessDecoderBackend;
+use crate::decoders::av1::parser::FrameHeader;
+use crate::decoders::av1::parser::FrameType;
+use crate::decoders::av1::parser::ObuType;
+use crate::decoders::av1::parser::Parser;
+use crate::decoders::av1::parser::SequenceHeader;
+use crate::decoders::av1::parser::NUM_REF_FRAMES;
+use crate::decoders::BlockingMode;
+use crate::decoders::DecodedHandle;
+use crate::decoders::DynDecodedHandle;
+use crate::decoders::Error as VideoDecoderError;
+use crate::decoders::Result as VideoDecoderResult;
+use crate::decoders::StatelessBackendError;
+use crate::decoders::VideoDecoder;
+use crate::Resolution;
+
+#[cfg(test)]
+struct TestParams<T> {
+    ready_pics: Vec<T>,
+}
+
+#[cfg(test)]
+impl<T> TestParams<T> {
+    fn save_ready_pics(&mut self, ready_pics: Vec<T>) {
+        self.ready_pics.extend(ready_pics);
+    }
+}
+
+#[cfg(test)]
+impl<T> Default for TestParams<T> {
+    fn default() -> Self {
+        Self {
+            ready_pics: Default::default(),
+        }
+    }
+}
+
+/// Represents where we are in the negotiation status. We assume ownership of
+/// the incoming buffers in this special case so that clients do not have to do
+/// the bookkeeping themselves.
+enum NegotiationStatus {
+    /// Still waiting for a sequence header. Any incoming buffers are being
+    /// queued in order.
+    NonNegotiated { queued_buffers: Vec<(u64, Vec<u8>)> },
+    /// Saw a sequence header. Negotiation is possible until the next call to
+    /// decode()
+    Possible { queued_buffers: Vec<(u64, Vec<u8>)> },
+    /// Processing the queued buffers.
+    DrainingQueuedBuffers,
+    /// Negotiated. Locks in the format until a new sequence header changes the
+    /// stream parameters.
+    Negotiated,
+}
+
+impl Default for NegotiationStatus {
+    fn default() -> Self {
+        Self::NonNegotiated {
+            queued_buffers: Default::default(),
+        }
+    }
+}
+
+pub struct Decoder<T: DecodedHandle> {
+    /// A parser to extract bitstream data and build frame data in turn
+    parser: Parser,
+
+    /// Whether the decoder should block on decode operations.
+    blocking_mode: BlockingMode,
+
+    /// The backend used for hardware acceleration.
+    backend: Box<dyn StatelessDecoderBackend<Handle = T>>,
+
+    /// Keeps track of whether the decoded format has been negotiated with the
+    /// backend.
+    negotiation_status: NegotiationStatus,
+
+    /// The current resolution
+    coded_resolution: Resolution,
+
+    /// The bit depth of the current sequence.
+    bit_depth: u8,
+
+    /// A queue with the pictures that are ready to be sent to the client.
+    ready_queue: Vec<T>,
+
+    /// A monotonically increasing counter used to tag pictures in display
+    /// order
+    current_display_order: u64,
+
+    /// The reference frames in use.
+    reference_frames: [Option<T>; NUM_REF_FRAMES],
+
+    /// The header of the frame being decoded, if its tile groups have not all
+    /// been received yet.
+    cur_frame: Option<FrameHeader>,
+
+    #[cfg(test)]
+    test_params: TestParams<T>,
+}
+
+impl<T: DecodedHandle + DynDecodedHandle + 'static> Decoder<T> {
+    /// Create a new codec backend for AV1.
+    #[cfg(any(feature = "vaapi", test))]
+    pub(crate) fn new(
+        backend: Box<dyn StatelessDecoderBackend<Handle = T>>,
+        blocking_mode: BlockingMode,
+    ) -> Result<Self> {
+        Ok(Self {
+            backend,
+            blocking_mode,
+            parser: Default::default(),
+            negotiation_status: Default::default(),
+            coded_resolution: Default::default(),
+            bit_depth: Default::default(),
+            ready_queue: Default::default(),
+            current_display_order: Default::default(),
+            reference_frames: Default::default(),
+            cur_frame: Default::default(),
+
+            #[cfg(test)]
+            test_params: Default::default(),
+        })
+    }
+
+    fn update_references(
+        reference_frames: &mut [Option<T>; NUM_REF_FRAMES],
+        picture: &T,
+        refresh_frame_flags: u8,
+    ) {
+        for (i, reference) in reference_frames.iter_mut().enumerate() {
+            if refresh_frame_flags & (1 << i) != 0 {
+                debug!("Replacing reference frame {}", i);
+                *reference = Some(picture.clone());
+            }
+        }
+    }
+
+    /// Queues `handle` for output, tagging it with the next display order.
+    fn output_picture(&mut self, mut handle: T) {
+        let order = self.current_display_order;
+        handle.set_display_order(order);
+        self.current_display_order += 1;
+
+        self.ready_queue.push(handle);
+    }
+
+    fn negotiation_possible(&self, sequence: &SequenceHeader) -> bool {
+        let width = sequence.max_frame_width();
+        let height = sequence.max_frame_height();
+
+        width != self.coded_resolution.width
+            || height != self.coded_resolution.height
+            || sequence.bit_depth() != self.bit_depth
+    }
+
+    /// Returns the first sequence header in `bitstream`, if any.
+    fn peek_sequence_header(parser: &mut Parser, mut bitstream: &[u8]) -> Option<SequenceHeader> {
+        while !bitstream.is_empty() {
+            let obu = parser.parse_obu(bitstream).ok()?;
+
+            if matches!(obu.header.obu_type(), ObuType::SequenceHeader) {
+                let sequence = parser.parse_sequence_header_obu(&obu).ok()?;
+                return Some(sequence.clone());
+            }
+
+            bitstream = &bitstream[obu.bytes_used..];
+        }
+
+        None
+    }
+
+    /// Handles a frame header, starting the decoding of a new frame unless it
+    /// is a show_existing_frame.
+    fn handle_frame_header(&mut self, hdr: FrameHeader, timestamp: u64) -> Result<()> {
+        if self.cur_frame.is_some() {
+            return Err(anyhow!(
+                "Broken data: new frame header before the end of the current frame"
+            ));
+        }
+
+        if hdr.show_existing_frame() {
+            let idx = usize::from(hdr.frame_to_show_map_idx());
+            let handle = self.reference_frames[idx].clone().ok_or(anyhow!(
+                "Broken data: frame_to_show_map_idx {} is empty",
+                idx
+            ))?;
+
+            // Showing an existing key frame refreshes all the reference frames, see 7.21.
+            if matches!(hdr.frame_type(), FrameType::KeyFrame) {
+                Self::update_references(
+                    &mut self.reference_frames,
+                    &handle,
+                    hdr.refresh_frame_flags(),
+                );
+            }
+
+            self.output_picture(handle);
+
+            return Ok(());
+        }
+
+        let sequence = self.parser.sequence_header().ok_or(anyhow!(
+            "Broken data: frame header without a sequence header"
+        ))?;
+
+        self.backend
+            .new_picture(sequence, &hdr, timestamp, &self.reference_frames)?;
+        self.cur_frame = Some(hdr);
+
+        Ok(())
+    }
+
+    /// Parses a tile group and sends it to the backend, finishing the current
+    /// frame if it was its last tile group.
+    fn handle_tile_group(&mut self, data: &[u8]) -> Result<()> {
+        let tile_group = self.parser.parse_tile_group(data)?;
+
+        let is_last = match &self.cur_frame {
+            Some(hdr) => tile_group.is_last(hdr.tile_info()),
+            None => return Err(anyhow!("Broken data: tile group without a frame header")),
+        };
+
+        self.backend.decode_tile_group(&tile_group)?;
+
+        if is_last {
+            self.finish_frame()?;
+        }
+
+        Ok(())
+    }
+
+    /// Submits the current frame to the accelerator and updates the reference
+    /// frames.
+    fn finish_frame(&mut self) -> Result<()> {
+        let hdr = self.cur_frame.take().unwrap();
+
+        let block = if matches!(self.blocking_mode, BlockingMode::Blocking)
+            || matches!(
+                self.negotiation_status,
+                NegotiationStatus::DrainingQueuedBuffers
+            ) {
+            BlockingMode::Blocking
+        } else {
+            BlockingMode::NonBlocking
+        };
+
+        let handle = self.backend.submit_picture(&hdr, block)?;
+
+        Self::update_references(
+            &mut self.reference_frames,
+            &handle,
+            hdr.refresh_frame_flags(),
+        );
+
+        if hdr.show_frame() {
+            self.output_picture(handle);
+        }
+
+        Ok(())
+    }
+
+    /// Decodes a temporal unit, i.e. all the OBUs with the same presentation
+    /// time.
+    fn decode_temporal_unit(
+        &mut self,
+        timestamp: u64,
+        mut bitstream: &[u8],
+    ) -> VideoDecoderResult<()> {
+        if self.backend.num_resources_left() == 0 {
+            return Err(VideoDecoderError::StatelessBackendError(
+                StatelessBackendError::OutOfResources,
+            ));
+        }
+
+        while !bitstream.is_empty() {
+            let obu = self.parser.parse_obu(bitstream)?;
+            bitstream = &bitstream[obu.bytes_used..];
+
+            if self.parser.should_drop(&obu.header) {
+                debug!("Dropping OBU outside of the operating point");
+                continue;
+            }
+
+            match obu.header.obu_type() {
+                ObuType::TemporalDelimiter => {
+                    self.parser.temporal_delimiter();
+                }
+
+                ObuType::SequenceHeader => {
+                    self.parser.parse_sequence_header_obu(&obu)?;
+                }
+
+                ObuType::FrameHeader | ObuType::RedundantFrameHeader | ObuType::Frame => {
+                    let hdr = match self.parser.parse_frame_header_obu(&obu)? {
+                        Some(hdr) => hdr,
+                        // Redundant copy of the current frame header.
+                        None => continue,
+                    };
+
+                    let header_bytes = hdr.header_bytes();
+                    self.handle_frame_header(hdr, timestamp)?;
+
+                    if matches!(obu.header.obu_type(), ObuType::Frame) {
+                        self.handle_tile_group(&obu.data[header_bytes..])?;
+                    }
+                }
+
+                ObuType::TileGroup => {
+                    self.handle_tile_group(obu.data)?;
+                }
+
+                other => {
+                    debug!("Unsupported OBU type {:?}", other);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn block_on_one(&mut self) -> VideoDecoderResult<()> {
+        for handle in &self.ready_queue {
+            if !self.backend.handle_is_ready(handle) {
+                return self
+                    .backend
+                    .block_on_handle(handle)
+                    .map_err(VideoDecoderError::StatelessBackendError);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the ready handles.
+    fn get_ready_frames(&mut self) -> Vec<T> {
+        let ready = self
+            .ready_queue
+            .iter()
+            .filter(|&handle| self.backend.handle_is_ready(handle))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        self.ready_queue
+            .retain(|handle| !self.backend.handle_is_ready(handle));
+
+        ready
+    }
+
+    #[cfg(test)]
+    fn steal_pics_for_test(&mut self) {
+        let frames = self.get_ready_frames();
+        self.test_params.save_ready_pics(frames);
+    }
+
+    #[cfg(test)]
+    #[allow(dead_code)]
+    pub(crate) fn backend(&self) -> &dyn StatelessDecoderBackend<Handle = T> {
+        self.backend.as_ref()
+    }
+}
+
+impl<T: DecodedHandle + DynDecodedHandle + 'static> VideoDecoder for Decoder<T> {
+    fn decode(
+        &mut self,
+        timestamp: u64,
+        bitstream: &[u8],
+    ) -> VideoDecoderResult<Vec<Box<dyn DynDecodedHandle>>> {
+        let sequence = Self::peek_sequence_header(&mut self.parser, bitstream);
+
+        if let Some(sequence) = &sequence {
+            if self.negotiation_possible(sequence)
+                && matches!(self.negotiation_status, NegotiationStatus::Negotiated)
+            {
+                self.negotiation_status = NegotiationStatus::NonNegotiated {
+                    queued_buffers: Default::default(),
+                }
+            }
+        }
+
+        let queued_buffers = match &mut self.negotiation_status {
+            NegotiationStatus::NonNegotiated { queued_buffers } => {
+                let buffer = Vec::from(bitstream);
+                queued_buffers.push((timestamp, buffer));
+
+                if let Some(sequence) = &sequence {
+                    self.backend.poll(BlockingMode::Blocking)?;
+                    self.backend.new_sequence(sequence)?;
+
+                    self.coded_resolution = Resolution {
+                        width: sequence.max_frame_width(),
+                        height: sequence.max_frame_height(),
+                    };
+                    self.bit_depth = sequence.bit_depth();
+
+                    self.negotiation_status = NegotiationStatus::Possible {
+                        queued_buffers: queued_buffers.clone(),
+                    }
+                }
+
+                return Ok(vec![]);
+            }
+
+            NegotiationStatus::Possible { queued_buffers } => {
+                let queued_buffers = queued_buffers.clone();
+                self.negotiation_status = NegotiationStatus::DrainingQueuedBuffers;
+                Some(queued_buffers)
+            }
+
+            NegotiationStatus::DrainingQueuedBuffers | NegotiationStatus::Negotiated => None,
+        };
+
+        if let Some(queued_buffers) = queued_buffers {
+            for (timestamp, buffer) in queued_buffers {
+                self.decode_temporal_unit(timestamp, &buffer)?;
+            }
+
+            self.negotiation_status = NegotiationStatus::Negotiated;
+        }
+
+        self.decode_temporal_unit(timestamp, bitstream)?;
+
+        if self.backend.num_resources_left() == 0 {
+            self.block_on_one()?;
+        }
+
+        self.backend.poll(self.blocking_mode)?;
+
+        #[cfg(test)]
+        self.steal_pics_for_test();
+
+        let ready_frames = self.get_ready_frames();
+
+        Ok(ready_frames
+            .into_iter()
+            .map(|h| Box::new(h) as Box<dyn DynDecodedHandle>)
+            .collect())
+    }
+
+    fn flush(&mut self) -> VideoDecoderResult<Vec<Box<dyn DynDecodedHandle>>> {
+        // Decode whatever is pending using the default format. Mainly covers
+        // the rare case where only one buffer is sent.
+        if let NegotiationStatus::Possible { queued_buffers } = &self.negotiation_status {
+            for (timestamp, buffer) in queued_buffers.clone() {
+                self.decode_temporal_unit(timestamp, &buffer)?;
+            }
+        }
+
+        self.backend.poll(BlockingMode::Blocking)?;
+
+        #[cfg(test)]
+        self.steal_pics_for_test();
+
+        let pics = self.get_ready_frames();
+
+        Ok(pics
+            .into_iter()
+            .map(|h| Box::new(h) as Box<dyn DynDecodedHandle>)
+            .collect())
+    }
+
+    fn negotiation_possible(&self) -> bool {
+        matches!(self.negotiation_status, NegotiationStatus::Possible { .. })
+    }
+
+    fn num_resources_left(&self) -> Option<usize> {
+        if matches!(
+            self.negotiation_status,
+            NegotiationStatus::NonNegotiated { .. }
+        ) {
+            return None;
+        }
+
+        let left_in_the_backend = self.backend.num_resources_left();
+
+        if let NegotiationStatus::Possible { queued_buffers } = &self.negotiation_status {
+            Some(left_in_the_backend - queued_buffers.len())
+        } else {
+            Some(left_in_the_backend)
+        }
+    }
+
+    fn num_resources_total(&self) -> usize {
+        self.backend.num_resources_total()
+    }
+
+    fn coded_resolution(&self) -> Option<Resolution> {
+        self.backend.coded_resolution()
+    }
+
+    fn poll(
+        &mut self,
+        blocking_mode: BlockingMode,
+    ) -> VideoDecoderResult<Vec<Box<dyn DynDecodedHandle>>> {
+        let handles = self.backend.poll(blocking_mode)?;
+
+        Ok(handles
+            .into_iter()
+            .map(|h| Box::new(h) as Box<dyn DynDecodedHandle>)
+            .collect())
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use crate::decoders::av1::decoder::Decoder;
+    use crate::decoders::av1::parser::tests::frame;
+    use crate::decoders::av1::parser::tests::sequence_header;
+    use crate::decoders::av1::parser::tests::show_existing_frame;
+    use crate::decoders::av1::parser::tests::temporal_delimiter;
+    use crate::decoders::av1::parser::tests::tile_group;
+    use crate::decoders::av1::parser::FrameType;
+    use crate::decoders::av1::parser::ObuType;
+    use crate::decoders::av1::parser::REFS_PER_FRAME;
+    use crate::decoders::BlockingMode;
+    use crate::decoders::DecodedHandle;
+    use crate::decoders::DynDecodedHandle;
+    use crate::decoders::VideoDecoder;
+
+    pub fn process_ready_frames<Handle>(
+        decoder: &mut Decoder<Handle>,
+        action: &mut dyn FnMut(&mut Decoder<Handle>, &Handle),
+    ) where
+        Handle: DecodedHandle + DynDecodedHandle,
+    {
+        let ready_pics = decoder.test_params.ready_pics.drain(..).collect::<Vec<_>>();
+
+        for handle in ready_pics {
+            action(decoder, &handle);
+        }
+    }
+
+    /// Decodes `temporal_units`, calling `on_new_iteration` after each of them
+    /// and for every frame returned by the final flush.
+    pub fn run_decoding_loop<Handle, F>(
+        decoder: &mut Decoder<Handle>,
+        temporal_units: &[Vec<u8>],
+        mut on_new_iteration: F,
+    ) where
+        Handle: DecodedHandle + DynDecodedHandle + 'static,
+        F: FnMut(&mut Decoder<Handle>),
+    {
+        for (frame_num, temporal_unit) in temporal_units.iter().enumerate() {
+            decoder.decode(frame_num as u64, temporal_unit).unwrap();
+            on_new_iteration(decoder);
+        }
+
+        decoder.flush().unwrap();
+        let n_flushed = decoder.test_params.ready_pics.len();
+
+        for _ in 0..n_flushed {
+            on_new_iteration(decoder);
+        }
+    }
+
+    fn count_frames(temporal_units: &[Vec<u8>]) -> usize {
+        let blocking_modes = [BlockingMode::Blocking, BlockingMode::NonBlocking];
+        let mut counts = vec![];
+
+        for blocking_mode in blocking_modes {
+            let mut frame_num = 0;
+            let mut decoder = Decoder::new_dummy(blocking_mode).unwrap();
+
+            run_decoding_loop(&mut decoder, temporal_units, |decoder| {
+                process_ready_frames(decoder, &mut |_, _| frame_num += 1);
+            });
+
+            counts.push(frame_num);
+        }
+
+        assert_eq!(counts[0], counts[1]);
+        counts[0]
+    }
+
+    /// Builds a temporal unit out of `obus`.
+    pub fn temporal_unit(obus: &[Vec<u8>]) -> Vec<u8> {
+        let mut data = temporal_delimiter();
+        for obu in obus {
+            data.extend_from_slice(obu);
+        }
+        data
+    }
+
+    pub fn inter_frame(show_frame: bool, order_hint: u8, refresh_frame_flags: u8) -> Vec<u8> {
+        frame(
+            ObuType::Frame,
+            FrameType::InterFrame,
+            show_frame,
+            order_hint,
+            refresh_frame_flags,
+            [0, 0, 0, 0, 1, 1, 1],
+        )
+    }
+
+    #[test]
+    fn test_64x64_key_and_inter() {
+        let key_frame = frame(
+            ObuType::Frame,
+            FrameType::KeyFrame,
+            true,
+            0,
+            0xff,
+            [0; REFS_PER_FRAME],
+        );
+
+        let temporal_units = vec![
+            temporal_unit(&[sequence_header(), key_frame]),
+            temporal_unit(&[inter_frame(true, 1, 0x01)]),
+            temporal_unit(&[inter_frame(true, 2, 0x01)]),
+            temporal_unit(&[inter_frame(true, 3, 0x01)]),
+        ];
+
+        assert_eq!(count_frames(&temporal_units), 4);
+    }
+
+    #[test]
+    fn test_64x64_hidden_frame_and_show_existing_frame() {
+        let key_frame = frame(
+            ObuType::Frame,
+            FrameType::KeyFrame,
+            true,
+            0,
+            0xff,
+            [0; REFS_PER_FRAME],
+        );
+        // The same frame, split into a frame header and a tile group OBU.
+        let split_inter_frame = vec![
+            frame(
+                ObuType::FrameHeader,
+                FrameType::InterFrame,
+                true,
+                2,
+                0x01,
+                [0, 0, 0, 0, 1, 1, 1],
+            ),
+            tile_group(),
+        ];
+
+        let temporal_units = vec![
+            temporal_unit(&[sequence_header(), key_frame]),
+            // An alternate reference frame is decoded in slot 1 but not shown.
+            temporal_unit(&[inter_frame(false, 4, 0x02), inter_frame(true, 1, 0x01)]),
+            temporal_unit(&split_inter_frame),
+            temporal_unit(&[inter_frame(true, 3, 0x01)]),
+            temporal_unit(&[show_existing_frame(1)]),
+        ];
+
+        assert_eq!(count_frames(&temporal_units), 5);
+    }
+}
@@ -0,0 +1,3859 @@
+// Copyright 2023 The ChromiumOS Authors
+// Use of this source code is governed by a BSD-style license that can be
+// found in the LICENSE file.
+
+use anyhow::anyhow;
+use anyhow::Context;
+use anyhow::Result;
+use bitreader::BitReader;
+use enumn::N;
+
+pub const REFS_PER_FRAME: usize = 7;
+pub const TOTAL_REFS_PER_FRAME: usize = 8;
+pub const NUM_REF_FRAMES: usize = 8;
+pub const PRIMARY_REF_NONE: u8 = 7;
+
+pub const MAX_OPERATING_POINTS: usize = 32;
+pub const MAX_SEGMENTS: usize = 8;
+pub const SEG_LVL_MAX: usize = 8;
+pub const SEG_LVL_REF_FRAME: usize = 5;
+pub const MAX_LOOP_FILTER: i32 = 63;
+pub const MAX_CDEF_STRENGTHS: usize = 8;
+pub const MAX_NUM_PLANES: usize = 3;
+
+pub const MAX_TILE_WIDTH: u32 = 4096;
+pub const MAX_TILE_AREA: u32 = 4096 * 2304;
+pub const MAX_TILE_ROWS: usize = 64;
+pub const MAX_TILE_COLS: usize = 64;
+
+pub const SUPERRES_NUM: u32 = 8;
+pub const SUPERRES_DENOM_MIN: u32 = 9;
+pub const SUPERRES_DENOM_BITS: u8 = 3;
+
+pub const RESTORATION_TILESIZE_MAX: u32 = 256;
+
+pub const WARPEDMODEL_PREC_BITS: u32 = 16;
+const GM_ABS_ALPHA_BITS: u32 = 12;
+const GM_ALPHA_PREC_BITS: u32 = 15;
+const GM_ABS_TRANS_ONLY_BITS: u32 = 9;
+const GM_TRANS_ONLY_PREC_BITS: u32 = 3;
+const GM_ABS_TRANS_BITS: u32 = 12;
+const GM_TRANS_PREC_BITS: u32 = 6;
+
+pub const MAX_NUM_Y_POINTS: usize = 14;
+pub const MAX_NUM_CB_POINTS: usize = 10;
+pub const MAX_NUM_CR_POINTS: usize = 10;
+pub const MAX_NUM_POS_LUMA: usize = 24;
+pub const MAX_NUM_POS_CHROMA: usize = 25;
+
+/// Value of seq_force_screen_content_tools and seq_force_integer_mv meaning that the frame
+/// header decides.
+pub const SELECT_SCREEN_CONTENT_TOOLS: u8 = 2;
+pub const SELECT_INTEGER_MV: u8 = 2;
+
+const SEGMENTATION_FEATURE_BITS: [u8; SEG_LVL_MAX] = [8, 6, 6, 6, 6, 3, 0, 0];
+const SEGMENTATION_FEATURE_SIGNED: [bool; SEG_LVL_MAX] =
+    [true, true, true, true, true, false, false, false];
+const SEGMENTATION_FEATURE_MAX: [i32; SEG_LVL_MAX] = [
+    255,
+    MAX_LOOP_FILTER,
+    MAX_LOOP_FILTER,
+    MAX_LOOP_FILTER,
+    MAX_LOOP_FILTER,
+    7,
+    0,
+    0,
+];
+
+/// The default loop filter deltas for each reference frame, see setup_past_independence().
+const DEFAULT_LOOP_FILTER_REF_DELTAS: [i8; TOTAL_REFS_PER_FRAME] = [1, 0, 0, 0, -1, 0, -1, -1];
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, N)]
+pub enum ObuType {
+    #[default]
+    Reserved = 0,
+    SequenceHeader = 1,
+    TemporalDelimiter = 2,
+    FrameHeader = 3,
+    TileGroup = 4,
+    Metadata = 5,
+    Frame = 6,
+    RedundantFrameHeader = 7,
+    TileList = 8,
+    Reserved9 = 9,
+    Reserved10 = 10,
+    Reserved11 = 11,
+    Reserved12 = 12,
+    Reserved13 = 13,
+    Reserved14 = 14,
+    Padding = 15,
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, N)]
+pub enum Profile {
+    #[default]
+    Profile0 = 0,
+    Profile1 = 1,
+    Profile2 = 2,
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, N)]
+pub enum FrameType {
+    #[default]
+    KeyFrame = 0,
+    InterFrame = 1,
+    IntraOnlyFrame = 2,
+    SwitchFrame = 3,
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, N)]
+pub enum InterpolationFilter {
+    #[default]
+    EightTap = 0,
+    EightTapSmooth = 1,
+    EightTapSharp = 2,
+    Bilinear = 3,
+    Switchable = 4,
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, N)]
+pub enum TxMode {
+    #[default]
+    Only4x4 = 0,
+    Largest = 1,
+    Select = 2,
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, N)]
+pub enum FrameRestorationType {
+    #[default]
+    None = 0,
+    Wiener = 1,
+    Sgrproj = 2,
+    Switchable = 3,
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, N)]
+pub enum WarpModelType {
+    #[default]
+    Identity = 0,
+    Translation = 1,
+    RotZoom = 2,
+    Affine = 3,
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, N)]
+pub enum ReferenceFrameType {
+    #[default]
+    Intra = 0,
+    Last = 1,
+    Last2 = 2,
+    Last3 = 3,
+    Golden = 4,
+    BwdRef = 5,
+    AltRef2 = 6,
+    AltRef = 7,
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, N)]
+pub enum ColorPrimaries {
+    Bt709 = 1,
+    #[default]
+    Unspecified = 2,
+    Bt470M = 4,
+    Bt470BG = 5,
+    Bt601 = 6,
+    Smpte240 = 7,
+    GenericFilm = 8,
+    Bt2020 = 9,
+    Xyz = 10,
+    Smpte431 = 11,
+    Smpte432 = 12,
+    Ebu3213 = 22,
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, N)]
+pub enum TransferCharacteristics {
+    Reserved0 = 0,
+    Bt709 = 1,
+    #[default]
+    Unspecified = 2,
+    Reserved3 = 3,
+    Bt470M = 4,
+    Bt470BG = 5,
+    Bt601 = 6,
+    Smpte240 = 7,
+    Linear = 8,
+    Log100 = 9,
+    Log100Sqrt10 = 10,
+    Iec61966 = 11,
+    Bt1361 = 12,
+    Srgb = 13,
+    Bt2020Ten = 14,
+    Bt2020Twelve = 15,
+    Smpte2084 = 16,
+    Smpte428 = 17,
+    Hlg = 18,
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, N)]
+pub enum MatrixCoefficients {
+    Identity = 0,
+    Bt709 = 1,
+    #[default]
+    Unspecified = 2,
+    Reserved3 = 3,
+    Fcc = 4,
+    Bt470BG = 5,
+    Bt601 = 6,
+    Smpte240 = 7,
+    SmpteYcgco = 8,
+    Bt2020Ncl = 9,
+    Bt2020Cl = 10,
+    Smpte2085 = 11,
+    ChromatNcl = 12,
+    ChromatCl = 13,
+    Ictcp = 14,
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, N)]
+pub enum ChromaSamplePosition {
+    #[default]
+    Unknown = 0,
+    Vertical = 1,
+    Colocated = 2,
+}
+
+/// The header of an OBU, see 5.3.2.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ObuHeader {
+    obu_type: ObuType,
+    extension_flag: bool,
+    has_size_field: bool,
+    temporal_id: u8,
+    spatial_id: u8,
+}
+
+impl ObuHeader {
+    pub fn obu_type(&self) -> ObuType {
+        self.obu_type
+    }
+
+    pub fn extension_flag(&self) -> bool {
+        self.extension_flag
+    }
+
+    pub fn has_size_field(&self) -> bool {
+        self.has_size_field
+    }
+
+    pub fn temporal_id(&self) -> u8 {
+        self.temporal_id
+    }
+
+    pub fn spatial_id(&self) -> u8 {
+        self.spatial_id
+    }
+}
+
+/// An OBU and its payload.
+#[derive(Clone, Debug)]
+pub struct Obu<'a> {
+    /// The OBU header.
+    pub header: ObuHeader,
+    /// The payload of the OBU, excluding its header and size field.
+    pub data: &'a [u8],
+    /// The number of bytes used by the OBU in the bitstream, including its header.
+    pub bytes_used: usize,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct OperatingPoint {
+    idc: u16,
+    seq_level_idx: u8,
+    seq_tier: u8,
+    decoder_model_present_for_this_op: bool,
+    decoder_buffer_delay: u32,
+    encoder_buffer_delay: u32,
+    low_delay_mode_flag: bool,
+    initial_display_delay_present_for_this_op: bool,
+    initial_display_delay_minus_1: u8,
+}
+
+impl OperatingPoint {
+    pub fn idc(&self) -> u16 {
+        self.idc
+    }
+
+    pub fn seq_level_idx(&self) -> u8 {
+        self.seq_level_idx
+    }
+
+    pub fn seq_tier(&self) -> u8 {
+        self.seq_tier
+    }
+
+    pub fn decoder_model_present_for_this_op(&self) -> bool {
+        self.decoder_model_present_for_this_op
+    }
+
+    pub fn decoder_buffer_delay(&self) -> u32 {
+        self.decoder_buffer_delay
+    }
+
+    pub fn encoder_buffer_delay(&self) -> u32 {
+        self.encoder_buffer_delay
+    }
+
+    pub fn low_delay_mode_flag(&self) -> bool {
+        self.low_delay_mode_flag
+    }
+
+    pub fn initial_display_delay_present_for_this_op(&self) -> bool {
+        self.initial_display_delay_present_for_this_op
+    }
+
+    pub fn initial_display_delay_minus_1(&self) -> u8 {
+        self.initial_display_delay_minus_1
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TimingInfo {
+    num_units_in_display_tick: u32,
+    time_scale: u32,
+    equal_picture_interval: bool,
+    num_ticks_per_picture_minus_1: u32,
+}
+
+impl TimingInfo {
+    pub fn num_units_in_display_tick(&self) -> u32 {
+        self.num_units_in_display_tick
+    }
+
+    pub fn time_scale(&self) -> u32 {
+        self.time_scale
+    }
+
+    pub fn equal_picture_interval(&self) -> bool {
+        self.equal_picture_interval
+    }
+
+    pub fn num_ticks_per_picture_minus_1(&self) -> u32 {
+        self.num_ticks_per_picture_minus_1
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DecoderModelInfo {
+    buffer_delay_length_minus_1: u8,
+    num_units_in_decoding_tick: u32,
+    buffer_removal_time_length_minus_1: u8,
+    frame_presentation_time_length_minus_1: u8,
+}
+
+impl DecoderModelInfo {
+    pub fn buffer_delay_length_minus_1(&self) -> u8 {
+        self.buffer_delay_length_minus_1
+    }
+
+    pub fn num_units_in_decoding_tick(&self) -> u32 {
+        self.num_units_in_decoding_tick
+    }
+
+    pub fn buffer_removal_time_length_minus_1(&self) -> u8 {
+        self.buffer_removal_time_length_minus_1
+    }
+
+    pub fn frame_presentation_time_length_minus_1(&self) -> u8 {
+        self.frame_presentation_time_length_minus_1
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ColorConfig {
+    high_bitdepth: bool,
+    twelve_bit: bool,
+    bit_depth: u8,
+    mono_chrome: bool,
+    num_planes: u8,
+    color_description_present_flag: bool,
+    color_primaries: ColorPrimaries,
+    transfer_characteristics: TransferCharacteristics,
+    matrix_coefficients: MatrixCoefficients,
+    color_range: bool,
+    subsampling_x: bool,
+    subsampling_y: bool,
+    chroma_sample_position: ChromaSamplePosition,
+    separate_uv_delta_q: bool,
+}
+
+impl ColorConfig {
+    pub fn high_bitdepth(&self) -> bool {
+        self.high_bitdepth
+    }
+
+    pub fn twelve_bit(&self) -> bool {
+        self.twelve_bit
+    }
+
+    pub fn bit_depth(&self) -> u8 {
+        self.bit_depth
+    }
+
+    pub fn mono_chrome(&self) -> bool {
+        self.mono_chrome
+    }
+
+    pub fn num_planes(&self) -> u8 {
+        self.num_planes
+    }
+
+    pub fn color_description_present_flag(&self) -> bool {
+        self.color_description_present_flag
+    }
+
+    pub fn color_primaries(&self) -> ColorPrimaries {
+        self.color_primaries
+    }
+
+    pub fn transfer_characteristics(&self) -> TransferCharacteristics {
+        self.transfer_characteristics
+    }
+
+    pub fn matrix_coefficients(&self) -> MatrixCoefficients {
+        self.matrix_coefficients
+    }
+
+    pub fn color_range(&self) -> bool {
+        self.color_range
+    }
+
+    pub fn subsampling_x(&self) -> bool {
+        self.subsampling_x
+    }
+
+    pub fn subsampling_y(&self) -> bool {
+        self.subsampling_y
+    }
+
+    pub fn chroma_sample_position(&self) -> ChromaSamplePosition {
+        self.chroma_sample_position
+    }
+
+    pub fn separate_uv_delta_q(&self) -> bool {
+        self.separate_uv_delta_q
+    }
+}
+
+/// The sequence header OBU, see 5.5.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SequenceHeader {
+    seq_profile: Profile,
+    still_picture: bool,
+    reduced_still_picture_header: bool,
+    timing_info_present_flag: bool,
+    timing_info: TimingInfo,
+    decoder_model_info_present_flag: bool,
+    decoder_model_info: DecoderModelInfo,
+    initial_display_delay_present_flag: bool,
+    operating_points_cnt_minus_1: u8,
+    operating_points: [OperatingPoint; MAX_OPERATING_POINTS],
+    frame_width_bits_minus_1: u8,
+    frame_height_bits_minus_1: u8,
+    max_frame_width_minus_1: u16,
+    max_frame_height_minus_1: u16,
+    frame_id_numbers_present_flag: bool,
+    delta_frame_id_length_minus_2: u8,
+    additional_frame_id_length_minus_1: u8,
+    use_128x128_superblock: bool,
+    enable_filter_intra: bool,
+    enable_intra_edge_filter: bool,
+    enable_interintra_compound: bool,
+    enable_masked_compound: bool,
+    enable_warped_motion: bool,
+    enable_dual_filter: bool,
+    enable_order_hint: bool,
+    enable_jnt_comp: bool,
+    enable_ref_frame_mvs: bool,
+    seq_choose_screen_content_tools: bool,
+    seq_force_screen_content_tools: u8,
+    seq_choose_integer_mv: bool,
+    seq_force_integer_mv: u8,
+    order_hint_bits_minus_1: u8,
+    /// OrderHintBits in the specification.
+    order_hint_bits: u8,
+    enable_superres: bool,
+    enable_cdef: bool,
+    enable_restoration: bool,
+    color_config: ColorConfig,
+    film_grain_params_present: bool,
+}
+
+impl SequenceHeader {
+    pub fn seq_profile(&self) -> Profile {
+        self.seq_profile
+    }
+
+    pub fn still_picture(&self) -> bool {
+        self.still_picture
+    }
+
+    pub fn reduced_still_picture_header(&self) -> bool {
+        self.reduced_still_picture_header
+    }
+
+    pub fn timing_info_present_flag(&self) -> bool {
+        self.timing_info_present_flag
+    }
+
+    pub fn timing_info(&self) -> &TimingInfo {
+        &self.timing_info
+    }
+
+    pub fn decoder_model_info_present_flag(&self) -> bool {
+        self.decoder_model_info_present_flag
+    }
+
+    pub fn decoder_model_info(&self) -> &DecoderModelInfo {
+        &self.decoder_model_info
+    }
+
+    pub fn initial_display_delay_present_flag(&self) -> bool {
+        self.initial_display_delay_present_flag
+    }
+
+    pub fn operating_points_cnt_minus_1(&self) -> u8 {
+        self.operating_points_cnt_minus_1
+    }
+
+    pub fn operating_points(&self) -> &[OperatingPoint; MAX_OPERATING_POINTS] {
+        &self.operating_points
+    }
+
+    pub fn frame_width_bits_minus_1(&self) -> u8 {
+        self.frame_width_bits_minus_1
+    }
+
+    pub fn frame_height_bits_minus_1(&self) -> u8 {
+        self.frame_height_bits_minus_1
+    }
+
+    pub fn max_frame_width_minus_1(&self) -> u16 {
+        self.max_frame_width_minus_1
+    }
+
+    pub fn max_frame_height_minus_1(&self) -> u16 {
+        self.max_frame_height_minus_1
+    }
+
+    pub fn frame_id_numbers_present_flag(&self) -> bool {
+        self.frame_id_numbers_present_flag
+    }
+
+    pub fn delta_frame_id_length_minus_2(&self) -> u8 {
+        self.delta_frame_id_length_minus_2
+    }
+
+    pub fn additional_frame_id_length_minus_1(&self) -> u8 {
+        self.additional_frame_id_length_minus_1
+    }
+
+    pub fn use_128x128_superblock(&self) -> bool {
+        self.use_128x128_superblock
+    }
+
+    pub fn enable_filter_intra(&self) -> bool {
+        self.enable_filter_intra
+    }
+
+    pub fn enable_intra_edge_filter(&self) -> bool {
+        self.enable_intra_edge_filter
+    }
+
+    pub fn enable_interintra_compound(&self) -> bool {
+        self.enable_interintra_compound
+    }
+
+    pub fn enable_masked_compound(&self) -> bool {
+        self.enable_masked_compound
+    }
+
+    pub fn enable_warped_motion(&self) -> bool {
+        self.enable_warped_motion
+    }
+
+    pub fn enable_dual_filter(&self) -> bool {
+        self.enable_dual_filter
+    }
+
+    pub fn enable_order_hint(&self) -> bool {
+        self.enable_order_hint
+    }
+
+    pub fn enable_jnt_comp(&self) -> bool {
+        self.enable_jnt_comp
+    }
+
+    pub fn enable_ref_frame_mvs(&self) -> bool {
+        self.enable_ref_frame_mvs
+    }
+
+    pub fn seq_choose_screen_content_tools(&self) -> bool {
+        self.seq_choose_screen_content_tools
+    }
+
+    pub fn seq_force_screen_content_tools(&self) -> u8 {
+        self.seq_force_screen_content_tools
+    }
+
+    pub fn seq_choose_integer_mv(&self) -> bool {
+        self.seq_choose_integer_mv
+    }
+
+    pub fn seq_force_integer_mv(&self) -> u8 {
+        self.seq_force_integer_mv
+    }
+
+    pub fn order_hint_bits_minus_1(&self) -> u8 {
+        self.order_hint_bits_minus_1
+    }
+
+    pub fn order_hint_bits(&self) -> u8 {
+        self.order_hint_bits
+    }
+
+    pub fn enable_superres(&self) -> bool {
+        self.enable_superres
+    }
+
+    pub fn enable_cdef(&self) -> bool {
+        self.enable_cdef
+    }
+
+    pub fn enable_restoration(&self) -> bool {
+        self.enable_restoration
+    }
+
+    pub fn color_config(&self) -> &ColorConfig {
+        &self.color_config
+    }
+
+    pub fn film_grain_params_present(&self) -> bool {
+        self.film_grain_params_present
+    }
+
+    /// The width of the largest frames of the sequence.
+    pub fn max_frame_width(&self) -> u32 {
+        u32::from(self.max_frame_width_minus_1) + 1
+    }
+
+    /// The height of the largest frames of the sequence.
+    pub fn max_frame_height(&self) -> u32 {
+        u32::from(self.max_frame_height_minus_1) + 1
+    }
+
+    /// The bit depth of the samples of the sequence.
+    pub fn bit_depth(&self) -> u8 {
+        self.color_config.bit_depth
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TemporalPointInfo {
+    frame_presentation_time: u32,
+}
+
+impl TemporalPointInfo {
+    pub fn frame_presentation_time(&self) -> u32 {
+        self.frame_presentation_time
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LoopFilterParams {
+    loop_filter_level: [u8; 4],
+    loop_filter_sharpness: u8,
+    loop_filter_delta_enabled: bool,
+    loop_filter_delta_update: bool,
+    update_ref_delta: [bool; TOTAL_REFS_PER_FRAME],
+    loop_filter_ref_deltas: [i8; TOTAL_REFS_PER_FRAME],
+    update_mode_delta: [bool; 2],
+    loop_filter_mode_deltas: [i8; 2],
+    delta_lf_present: bool,
+    delta_lf_res: u8,
+    delta_lf_multi: bool,
+}
+
+impl Default for LoopFilterParams {
+    fn default() -> Self {
+        Self {
+            loop_filter_level: Default::default(),
+            loop_filter_sharpness: Default::default(),
+            loop_filter_delta_enabled: true,
+            loop_filter_delta_update: Default::default(),
+            update_ref_delta: Default::default(),
+            loop_filter_ref_deltas: DEFAULT_LOOP_FILTER_REF_DELTAS,
+            update_mode_delta: Default::default(),
+            loop_filter_mode_deltas: Default::default(),
+            delta_lf_present: Default::default(),
+            delta_lf_res: Default::default(),
+            delta_lf_multi: Default::default(),
+        }
+    }
+}
+
+impl LoopFilterParams {
+    pub fn loop_filter_level(&self) -> &[u8; 4] {
+        &self.loop_filter_level
+    }
+
+    pub fn loop_filter_sharpness(&self) -> u8 {
+        self.loop_filter_sharpness
+    }
+
+    pub fn loop_filter_delta_enabled(&self) -> bool {
+        self.loop_filter_delta_enabled
+    }
+
+    pub fn loop_filter_delta_update(&self) -> bool {
+        self.loop_filter_delta_update
+    }
+
+    pub fn update_ref_delta(&self) -> &[bool; TOTAL_REFS_PER_FRAME] {
+        &self.update_ref_delta
+    }
+
+    pub fn loop_filter_ref_deltas(&self) -> &[i8; TOTAL_REFS_PER_FRAME] {
+        &self.loop_filter_ref_deltas
+    }
+
+    pub fn update_mode_delta(&self) -> &[bool; 2] {
+        &self.update_mode_delta
+    }
+
+    pub fn loop_filter_mode_deltas(&self) -> &[i8; 2] {
+        &self.loop_filter_mode_deltas
+    }
+
+    pub fn delta_lf_present(&self) -> bool {
+        self.delta_lf_present
+    }
+
+    pub fn delta_lf_res(&self) -> u8 {
+        self.delta_lf_res
+    }
+
+    pub fn delta_lf_multi(&self) -> bool {
+        self.delta_lf_multi
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct QuantizationParams {
+    base_q_idx: u8,
+    diff_uv_delta: bool,
+    delta_q_y_dc: i8,
+    delta_q_u_dc: i8,
+    delta_q_u_ac: i8,
+    delta_q_v_dc: i8,
+    delta_q_v_ac: i8,
+    using_qmatrix: bool,
+    qm_y: u8,
+    qm_u: u8,
+    qm_v: u8,
+    delta_q_present: bool,
+    delta_q_res: u8,
+}
+
+impl QuantizationParams {
+    pub fn base_q_idx(&self) -> u8 {
+        self.base_q_idx
+    }
+
+    pub fn diff_uv_delta(&self) -> bool {
+        self.diff_uv_delta
+    }
+
+    pub fn delta_q_y_dc(&self) -> i8 {
+        self.delta_q_y_dc
+    }
+
+    pub fn delta_q_u_dc(&self) -> i8 {
+        self.delta_q_u_dc
+    }
+
+    pub fn delta_q_u_ac(&self) -> i8 {
+        self.delta_q_u_ac
+    }
+
+    pub fn delta_q_v_dc(&self) -> i8 {
+        self.delta_q_v_dc
+    }
+
+    pub fn delta_q_v_ac(&self) -> i8 {
+        self.delta_q_v_ac
+    }
+
+    pub fn using_qmatrix(&self) -> bool {
+        self.using_qmatrix
+    }
+
+    pub fn qm_y(&self) -> u8 {
+        self.qm_y
+    }
+
+    pub fn qm_u(&self) -> u8 {
+        self.qm_u
+    }
+
+    pub fn qm_v(&self) -> u8 {
+        self.qm_v
+    }
+
+    pub fn delta_q_present(&self) -> bool {
+        self.delta_q_present
+    }
+
+    pub fn delta_q_res(&self) -> u8 {
+        self.delta_q_res
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SegmentationParams {
+    segmentation_enabled: bool,
+    segmentation_update_map: bool,
+    segmentation_temporal_update: bool,
+    segmentation_update_data: bool,
+    feature_enabled: [[bool; SEG_LVL_MAX]; MAX_SEGMENTS],
+    feature_data: [[i16; SEG_LVL_MAX]; MAX_SEGMENTS],
+    seg_id_pre_skip: bool,
+    last_active_seg_id: u8,
+}
+
+impl SegmentationParams {
+    pub fn segmentation_enabled(&self) -> bool {
+        self.segmentation_enabled
+    }
+
+    pub fn segmentation_update_map(&self) -> bool {
+        self.segmentation_update_map
+    }
+
+    pub fn segmentation_temporal_update(&self) -> bool {
+        self.segmentation_temporal_update
+    }
+
+    pub fn segmentation_update_data(&self) -> bool {
+        self.segmentation_update_data
+    }
+
+    pub fn feature_enabled(&self) -> &[[bool; SEG_LVL_MAX]; MAX_SEGMENTS] {
+        &self.feature_enabled
+    }
+
+    pub fn feature_data(&self) -> &[[i16; SEG_LVL_MAX]; MAX_SEGMENTS] {
+        &self.feature_data
+    }
+
+    pub fn seg_id_pre_skip(&self) -> bool {
+        self.seg_id_pre_skip
+    }
+
+    pub fn last_active_seg_id(&self) -> u8 {
+        self.last_active_seg_id
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TileInfo {
+    uniform_tile_spacing_flag: bool,
+    tile_cols_log2: u8,
+    tile_rows_log2: u8,
+    tile_cols: u32,
+    tile_rows: u32,
+    mi_col_starts: [u32; MAX_TILE_COLS + 1],
+    mi_row_starts: [u32; MAX_TILE_ROWS + 1],
+    width_in_sbs_minus_1: [u32; MAX_TILE_COLS],
+    height_in_sbs_minus_1: [u32; MAX_TILE_ROWS],
+    context_update_tile_id: u32,
+    tile_size_bytes: u8,
+}
+
+impl Default for TileInfo {
+    fn default() -> Self {
+        Self {
+            uniform_tile_spacing_flag: Default::default(),
+            tile_cols_log2: Default::default(),
+            tile_rows_log2: Default::default(),
+            tile_cols: Default::default(),
+            tile_rows: Default::default(),
+            mi_col_starts: [0; MAX_TILE_COLS + 1],
+            mi_row_starts: [0; MAX_TILE_ROWS + 1],
+            width_in_sbs_minus_1: [0; MAX_TILE_COLS],
+            height_in_sbs_minus_1: [0; MAX_TILE_ROWS],
+            context_update_tile_id: Default::default(),
+            tile_size_bytes: Default::default(),
+        }
+    }
+}
+
+impl TileInfo {
+    pub fn uniform_tile_spacing_flag(&self) -> bool {
+        self.uniform_tile_spacing_flag
+    }
+
+    pub fn tile_cols_log2(&self) -> u8 {
+        self.tile_cols_log2
+    }
+
+    pub fn tile_rows_log2(&self) -> u8 {
+        self.tile_rows_log2
+    }
+
+    pub fn tile_cols(&self) -> u32 {
+        self.tile_cols
+    }
+
+    pub fn tile_rows(&self) -> u32 {
+        self.tile_rows
+    }
+
+    pub fn mi_col_starts(&self) -> &[u32; MAX_TILE_COLS + 1] {
+        &self.mi_col_starts
+    }
+
+    pub fn mi_row_starts(&self) -> &[u32; MAX_TILE_ROWS + 1] {
+        &self.mi_row_starts
+    }
+
+    pub fn width_in_sbs_minus_1(&self) -> &[u32; MAX_TILE_COLS] {
+        &self.width_in_sbs_minus_1
+    }
+
+    pub fn height_in_sbs_minus_1(&self) -> &[u32; MAX_TILE_ROWS] {
+        &self.height_in_sbs_minus_1
+    }
+
+    pub fn context_update_tile_id(&self) -> u32 {
+        self.context_update_tile_id
+    }
+
+    pub fn tile_size_bytes(&self) -> u8 {
+        self.tile_size_bytes
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CdefParams {
+    cdef_damping: u8,
+    cdef_bits: u8,
+    cdef_y_pri_strength: [u8; MAX_CDEF_STRENGTHS],
+    cdef_y_sec_strength: [u8; MAX_CDEF_STRENGTHS],
+    cdef_uv_pri_strength: [u8; MAX_CDEF_STRENGTHS],
+    cdef_uv_sec_strength: [u8; MAX_CDEF_STRENGTHS],
+}
+
+impl CdefParams {
+    pub fn cdef_damping(&self) -> u8 {
+        self.cdef_damping
+    }
+
+    pub fn cdef_bits(&self) -> u8 {
+        self.cdef_bits
+    }
+
+    pub fn cdef_y_pri_strength(&self) -> &[u8; MAX_CDEF_STRENGTHS] {
+        &self.cdef_y_pri_strength
+    }
+
+    pub fn cdef_y_sec_strength(&self) -> &[u8; MAX_CDEF_STRENGTHS] {
+        &self.cdef_y_sec_strength
+    }
+
+    pub fn cdef_uv_pri_strength(&self) -> &[u8; MAX_CDEF_STRENGTHS] {
+        &self.cdef_uv_pri_strength
+    }
+
+    pub fn cdef_uv_sec_strength(&self) -> &[u8; MAX_CDEF_STRENGTHS] {
+        &self.cdef_uv_sec_strength
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LoopRestorationParams {
+    frame_restoration_type: [FrameRestorationType; MAX_NUM_PLANES],
+    uses_lr: bool,
+    uses_chroma_lr: bool,
+    lr_unit_shift: u8,
+    lr_uv_shift: u8,
+    loop_restoration_size: [u32; MAX_NUM_PLANES],
+}
+
+impl LoopRestorationParams {
+    pub fn frame_restoration_type(&self) -> &[FrameRestorationType; MAX_NUM_PLANES] {
+        &self.frame_restoration_type
+    }
+
+    pub fn uses_lr(&self) -> bool {
+        self.uses_lr
+    }
+
+    pub fn uses_chroma_lr(&self) -> bool {
+        self.uses_chroma_lr
+    }
+
+    pub fn lr_unit_shift(&self) -> u8 {
+        self.lr_unit_shift
+    }
+
+    pub fn lr_uv_shift(&self) -> u8 {
+        self.lr_uv_shift
+    }
+
+    pub fn loop_restoration_size(&self) -> &[u32; MAX_NUM_PLANES] {
+        &self.loop_restoration_size
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GlobalMotionParams {
+    gm_type: [WarpModelType; TOTAL_REFS_PER_FRAME],
+    gm_params: [[i32; 6]; TOTAL_REFS_PER_FRAME],
+}
+
+impl Default for GlobalMotionParams {
+    fn default() -> Self {
+        let mut identity = [0; 6];
+        identity[2] = 1 << WARPEDMODEL_PREC_BITS;
+        identity[5] = 1 << WARPEDMODEL_PREC_BITS;
+
+        Self {
+            gm_type: Default::default(),
+            gm_params: [identity; TOTAL_REFS_PER_FRAME],
+        }
+    }
+}
+
+impl GlobalMotionParams {
+    pub fn gm_type(&self) -> &[WarpModelType; TOTAL_REFS_PER_FRAME] {
+        &self.gm_type
+    }
+
+    pub fn gm_params(&self) -> &[[i32; 6]; TOTAL_REFS_PER_FRAME] {
+        &self.gm_params
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FilmGrainParams {
+    apply_grain: bool,
+    grain_seed: u16,
+    update_grain: bool,
+    film_grain_params_ref_idx: u8,
+    num_y_points: u8,
+    point_y_value: [u8; MAX_NUM_Y_POINTS],
+    point_y_scaling: [u8; MAX_NUM_Y_POINTS],
+    chroma_scaling_from_luma: bool,
+    num_cb_points: u8,
+    point_cb_value: [u8; MAX_NUM_CB_POINTS],
+    point_cb_scaling: [u8; MAX_NUM_CB_POINTS],
+    num_cr_points: u8,
+    point_cr_value: [u8; MAX_NUM_CR_POINTS],
+    point_cr_scaling: [u8; MAX_NUM_CR_POINTS],
+    grain_scaling_minus_8: u8,
+    ar_coeff_lag: u8,
+    ar_coeffs_y_plus_128: [u8; MAX_NUM_POS_LUMA],
+    ar_coeffs_cb_plus_128: [u8; MAX_NUM_POS_CHROMA],
+    ar_coeffs_cr_plus_128: [u8; MAX_NUM_POS_CHROMA],
+    ar_coeff_shift_minus_6: u8,
+    grain_scale_shift: u8,
+    cb_mult: u8,
+    cb_luma_mult: u8,
+    cb_offset: u16,
+    cr_mult: u8,
+    cr_luma_mult: u8,
+    cr_offset: u16,
+    overlap_flag: bool,
+    clip_to_restricted_range: bool,
+}
+
+impl FilmGrainParams {
+    pub fn apply_grain(&self) -> bool {
+        self.apply_grain
+    }
+
+    pub fn grain_seed(&self) -> u16 {
+        self.grain_seed
+    }
+
+    pub fn update_grain(&self) -> bool {
+        self.update_grain
+    }
+
+    pub fn film_grain_params_ref_idx(&self) -> u8 {
+        self.film_grain_params_ref_idx
+    }
+
+    pub fn num_y_points(&self) -> u8 {
+        self.num_y_points
+    }
+
+    pub fn point_y_value(&self) -> &[u8; MAX_NUM_Y_POINTS] {
+        &self.point_y_value
+    }
+
+    pub fn point_y_scaling(&self) -> &[u8; MAX_NUM_Y_POINTS] {
+        &self.point_y_scaling
+    }
+
+    pub fn chroma_scaling_from_luma(&self) -> bool {
+        self.chroma_scaling_from_luma
+    }
+
+    pub fn num_cb_points(&self) -> u8 {
+        self.num_cb_points
+    }
+
+    pub fn point_cb_value(&self) -> &[u8; MAX_NUM_CB_POINTS] {
+        &self.point_cb_value
+    }
+
+    pub fn point_cb_scaling(&self) -> &[u8; MAX_NUM_CB_POINTS] {
+        &self.point_cb_scaling
+    }
+
+    pub fn num_cr_points(&self) -> u8 {
+        self.num_cr_points
+    }
+
+    pub fn point_cr_value(&self) -> &[u8; MAX_NUM_CR_POINTS] {
+        &self.point_cr_value
+    }
+
+    pub fn point_cr_scaling(&self) -> &[u8; MAX_NUM_CR_POINTS] {
+        &self.point_cr_scaling
+    }
+
+    pub fn grain_scaling_minus_8(&self) -> u8 {
+        self.grain_scaling_minus_8
+    }
+
+    pub fn ar_coeff_lag(&self) -> u8 {
+        self.ar_coeff_lag
+    }
+
+    pub fn ar_coeffs_y_plus_128(&self) -> &[u8; MAX_NUM_POS_LUMA] {
+        &self.ar_coeffs_y_plus_128
+    }
+
+    pub fn ar_coeffs_cb_plus_128(&self) -> &[u8; MAX_NUM_POS_CHROMA] {
+        &self.ar_coeffs_cb_plus_128
+    }
+
+    pub fn ar_coeffs_cr_plus_128(&self) -> &[u8; MAX_NUM_POS_CHROMA] {
+        &self.ar_coeffs_cr_plus_128
+    }
+
+    pub fn ar_coeff_shift_minus_6(&self) -> u8 {
+        self.ar_coeff_shift_minus_6
+    }
+
+    pub fn grain_scale_shift(&self) -> u8 {
+        self.grain_scale_shift
+    }
+
+    pub fn cb_mult(&self) -> u8 {
+        self.cb_mult
+    }
+
+    pub fn cb_luma_mult(&self) -> u8 {
+        self.cb_luma_mult
+    }
+
+    pub fn cb_offset(&self) -> u16 {
+        self.cb_offset
+    }
+
+    pub fn cr_mult(&self) -> u8 {
+        self.cr_mult
+    }
+
+    pub fn cr_luma_mult(&self) -> u8 {
+        self.cr_luma_mult
+    }
+
+    pub fn cr_offset(&self) -> u16 {
+        self.cr_offset
+    }
+
+    pub fn overlap_flag(&self) -> bool {
+        self.overlap_flag
+    }
+
+    pub fn clip_to_restricted_range(&self) -> bool {
+        self.clip_to_restricted_range
+    }
+}
+
+/// The uncompressed frame header, see 5.9.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FrameHeader {
+    show_existing_frame: bool,
+    frame_to_show_map_idx: u8,
+    temporal_point_info: TemporalPointInfo,
+    display_frame_id: u32,
+    frame_type: FrameType,
+    /// FrameIsIntra in the specification.
+    frame_is_intra: bool,
+    show_frame: bool,
+    showable_frame: bool,
+    error_resilient_mode: bool,
+    disable_cdf_update: bool,
+    allow_screen_content_tools: bool,
+    force_integer_mv: bool,
+    current_frame_id: u32,
+    frame_size_override_flag: bool,
+    order_hint: u32,
+    primary_ref_frame: u8,
+    buffer_removal_time_present_flag: bool,
+    buffer_removal_time: [u32; MAX_OPERATING_POINTS],
+    refresh_frame_flags: u8,
+    ref_order_hint: [u32; NUM_REF_FRAMES],
+    allow_intrabc: bool,
+    frame_refs_short_signaling: bool,
+    last_frame_idx: u8,
+    gold_frame_idx: u8,
+    ref_frame_idx: [u8; REFS_PER_FRAME],
+    allow_high_precision_mv: bool,
+    is_motion_mode_switchable: bool,
+    use_ref_frame_mvs: bool,
+    /// OrderHints in the specification, indexed by reference frame type.
+    order_hints: [u32; TOTAL_REFS_PER_FRAME],
+    ref_frame_sign_bias: [bool; TOTAL_REFS_PER_FRAME],
+    disable_frame_end_update_cdf: bool,
+    /// FrameWidth in the specification.
+    frame_width: u32,
+    /// FrameHeight in the specification.
+    frame_height: u32,
+    /// UpscaledWidth in the specification.
+    upscaled_width: u32,
+    render_and_frame_size_different: bool,
+    render_width: u32,
+    render_height: u32,
+    use_superres: bool,
+    /// SuperresDenom in the specification.
+    superres_denom: u32,
+    mi_cols: u32,
+    mi_rows: u32,
+    interpolation_filter: InterpolationFilter,
+    tile_info: TileInfo,
+    quantization_params: QuantizationParams,
+    segmentation_params: SegmentationParams,
+    loop_filter_params: LoopFilterParams,
+    coded_lossless: bool,
+    all_lossless: bool,
+    lossless_array: [bool; MAX_SEGMENTS],
+    seg_qm_level: [[u8; MAX_SEGMENTS]; MAX_NUM_PLANES],
+    cdef_params: CdefParams,
+    loop_restoration_params: LoopRestorationParams,
+    tx_mode: TxMode,
+    reference_select: bool,
+    skip_mode_present: bool,
+    skip_mode_frame: [u8; 2],
+    allow_warped_motion: bool,
+    reduced_tx_set: bool,
+    global_motion_params: GlobalMotionParams,
+    film_grain_params: FilmGrainParams,
+    /// The size in bytes of the frame header OBU, including the trailing byte_alignment() when
+    /// it is part of a frame OBU.
+    header_bytes: usize,
+}
+
+impl FrameHeader {
+    pub fn show_existing_frame(&self) -> bool {
+        self.show_existing_frame
+    }
+
+    pub fn frame_to_show_map_idx(&self) -> u8 {
+        self.frame_to_show_map_idx
+    }
+
+    pub fn temporal_point_info(&self) -> &TemporalPointInfo {
+        &self.temporal_point_info
+    }
+
+    pub fn display_frame_id(&self) -> u32 {
+        self.display_frame_id
+    }
+
+    pub fn frame_type(&self) -> FrameType {
+        self.frame_type
+    }
+
+    pub fn frame_is_intra(&self) -> bool {
+        self.frame_is_intra
+    }
+
+    pub fn show_frame(&self) -> bool {
+        self.show_frame
+    }
+
+    pub fn showable_frame(&self) -> bool {
+        self.showable_frame
+    }
+
+    pub fn error_resilient_mode(&self) -> bool {
+        self.error_resilient_mode
+    }
+
+    pub fn disable_cdf_update(&self) -> bool {
+        self.disable_cdf_update
+    }
+
+    pub fn allow_screen_content_tools(&self) -> bool {
+        self.allow_screen_content_tools
+    }
+
+    pub fn force_integer_mv(&self) -> bool {
+        self.force_integer_mv
+    }
+
+    pub fn current_frame_id(&self) -> u32 {
+        self.current_frame_id
+    }
+
+    pub fn frame_size_override_flag(&self) -> bool {
+        self.frame_size_override_flag
+    }
+
+    pub fn order_hint(&self) -> u32 {
+        self.order_hint
+    }
+
+    pub fn primary_ref_frame(&self) -> u8 {
+        self.primary_ref_frame
+    }
+
+    pub fn buffer_removal_time_present_flag(&self) -> bool {
+        self.buffer_removal_time_present_flag
+    }
+
+    pub fn buffer_removal_time(&self) -> &[u32; MAX_OPERATING_POINTS] {
+        &self.buffer_removal_time
+    }
+
+    pub fn refresh_frame_flags(&self) -> u8 {
+        self.refresh_frame_flags
+    }
+
+    pub fn ref_order_hint(&self) -> &[u32; NUM_REF_FRAMES] {
+        &self.ref_order_hint
+    }
+
+    pub fn allow_intrabc(&self) -> bool {
+        self.allow_intrabc
+    }
+
+    pub fn frame_refs_short_signaling(&self) -> bool {
+        self.frame_refs_short_signaling
+    }
+
+    pub fn last_frame_idx(&self) -> u8 {
+        self.last_frame_idx
+    }
+
+    pub fn gold_frame_idx(&self) -> u8 {
+        self.gold_frame_idx
+    }
+
+    pub fn ref_frame_idx(&self) -> &[u8; REFS_PER_FRAME] {
+        &self.ref_frame_idx
+    }
+
+    pub fn allow_high_precision_mv(&self) -> bool {
+        self.allow_high_precision_mv
+    }
+
+    pub fn is_motion_mode_switchable(&self) -> bool {
+        self.is_motion_mode_switchable
+    }
+
+    pub fn use_ref_frame_mvs(&self) -> bool {
+        self.use_ref_frame_mvs
+    }
+
+    pub fn order_hints(&self) -> &[u32; TOTAL_REFS_PER_FRAME] {
+        &self.order_hints
+    }
+
+    pub fn ref_frame_sign_bias(&self) -> &[bool; TOTAL_REFS_PER_FRAME] {
+        &self.ref_frame_sign_bias
+    }
+
+    pub fn disable_frame_end_update_cdf(&self) -> bool {
+        self.disable_frame_end_update_cdf
+    }
+
+    pub fn frame_width(&self) -> u32 {
+        self.frame_width
+    }
+
+    pub fn frame_height(&self) -> u32 {
+        self.frame_height
+    }
+
+    pub fn upscaled_width(&self) -> u32 {
+        self.upscaled_width
+    }
+
+    pub fn render_and_frame_size_different(&self) -> bool {
+        self.render_and_frame_size_different
+    }
+
+    pub fn render_width(&self) -> u32 {
+        self.render_width
+    }
+
+    pub fn render_height(&self) -> u32 {
+        self.render_height
+    }
+
+    pub fn use_superres(&self) -> bool {
+        self.use_superres
+    }
+
+    pub fn superres_denom(&self) -> u32 {
+        self.superres_denom
+    }
+
+    pub fn mi_cols(&self) -> u32 {
+        self.mi_cols
+    }
+
+    pub fn mi_rows(&self) -> u32 {
+        self.mi_rows
+    }
+
+    pub fn interpolation_filter(&self) -> InterpolationFilter {
+        self.interpolation_filter
+    }
+
+    pub fn tile_info(&self) -> &TileInfo {
+        &self.tile_info
+    }
+
+    pub fn quantization_params(&self) -> &QuantizationParams {
+        &self.quantization_params
+    }
+
+    pub fn segmentation_params(&self) -> &SegmentationParams {
+        &self.segmentation_params
+    }
+
+    pub fn loop_filter_params(&self) -> &LoopFilterParams {
+        &self.loop_filter_params
+    }
+
+    pub fn coded_lossless(&self) -> bool {
+        self.coded_lossless
+    }
+
+    pub fn all_lossless(&self) -> bool {
+        self.all_lossless
+    }
+
+    pub fn lossless_array(&self) -> &[bool; MAX_SEGMENTS] {
+        &self.lossless_array
+    }
+
+    pub fn seg_qm_level(&self) -> &[[u8; MAX_SEGMENTS]; MAX_NUM_PLANES] {
+        &self.seg_qm_level
+    }
+
+    pub fn cdef_params(&self) -> &CdefParams {
+        &self.cdef_params
+    }
+
+    pub fn loop_restoration_params(&self) -> &LoopRestorationParams {
+        &self.loop_restoration_params
+    }
+
+    pub fn tx_mode(&self) -> TxMode {
+        self.tx_mode
+    }
+
+    pub fn reference_select(&self) -> bool {
+        self.reference_select
+    }
+
+    pub fn skip_mode_present(&self) -> bool {
+        self.skip_mode_present
+    }
+
+    pub fn skip_mode_frame(&self) -> &[u8; 2] {
+        &self.skip_mode_frame
+    }
+
+    pub fn allow_warped_motion(&self) -> bool {
+        self.allow_warped_motion
+    }
+
+    pub fn reduced_tx_set(&self) -> bool {
+        self.reduced_tx_set
+    }
+
+    pub fn global_motion_params(&self) -> &GlobalMotionParams {
+        &self.global_motion_params
+    }
+
+    pub fn film_grain_params(&self) -> &FilmGrainParams {
+        &self.film_grain_params
+    }
+
+    pub fn header_bytes(&self) -> usize {
+        self.header_bytes
+    }
+}
+
+/// A tile inside a tile group, see 5.11.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Tile {
+    /// The offset of the tile data inside the tile group OBU payload.
+    pub tile_offset: u32,
+    /// The size of the tile data in bytes.
+    pub tile_size: u32,
+    pub tile_row: u32,
+    pub tile_col: u32,
+}
+
+/// A tile group OBU, see 5.11.1.
+#[derive(Clone, Debug)]
+pub struct TileGroup<'a> {
+    /// The payload of the tile group OBU.
+    pub data: &'a [u8],
+    pub tile_start_and_end_present_flag: bool,
+    pub tg_start: u32,
+    pub tg_end: u32,
+    pub tiles: Vec<Tile>,
+}
+
+impl<'a> TileGroup<'a> {
+    /// Whether this tile group contains the last tile of the frame.
+    pub fn is_last(&self, tile_info: &TileInfo) -> bool {
+        self.tg_end == tile_info.tile_cols * tile_info.tile_rows - 1
+    }
+}
+
+/// The state saved for each reference frame slot by the reference frame update process, see
+/// 7.20.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+struct ReferenceFrameInfo {
+    ref_valid: bool,
+    ref_frame_id: u32,
+    ref_upscaled_width: u32,
+    ref_frame_width: u32,
+    ref_frame_height: u32,
+    ref_render_width: u32,
+    ref_render_height: u32,
+    ref_mi_cols: u32,
+    ref_mi_rows: u32,
+    ref_frame_type: FrameType,
+    ref_order_hint: u32,
+    saved_order_hints: [u32; TOTAL_REFS_PER_FRAME],
+    saved_gm_params: GlobalMotionParams,
+    saved_loop_filter_ref_deltas: [i8; TOTAL_REFS_PER_FRAME],
+    saved_loop_filter_mode_deltas: [i8; 2],
+    saved_feature_enabled: [[bool; SEG_LVL_MAX]; MAX_SEGMENTS],
+    saved_feature_data: [[i16; SEG_LVL_MAX]; MAX_SEGMENTS],
+    saved_film_grain_params: FilmGrainParams,
+}
+
+/// An AV1 bitstream parser.
+#[derive(Debug, Default)]
+pub struct Parser {
+    sequence_header: Option<SequenceHeader>,
+    /// The operating point selected for decoding, see 7.1.
+    operating_point: usize,
+    /// Whether a frame header has been seen for the current frame. Redundant frame headers are
+    /// ignored while this is set.
+    seen_frame_header: bool,
+    /// The frame header of the frame being decoded.
+    last_frame_header: Option<FrameHeader>,
+    /// Where the tile groups of the current frame are at.
+    tile_num: u32,
+    ref_info: [ReferenceFrameInfo; NUM_REF_FRAMES],
+}
+
+impl Parser {
+    /// Reads a value using leb128() encoding, see 4.10.5.
+    fn read_leb128(data: &[u8]) -> Result<(u64, usize)> {
+        let mut value = 0u64;
+
+        for i in 0..8 {
+            let byte = *data
+                .get(i)
+                .ok_or(anyhow!("Broken data: truncated leb128 value"))?;
+            value |= u64::from(byte & 0x7f) << (i * 7);
+
+            if byte & 0x80 == 0 {
+                return Ok((value, i + 1));
+            }
+        }
+
+        Err(anyhow!("Broken data: leb128 value is too long"))
+    }
+
+    /// Reads a variable length unsigned value, see uvlc() in 4.10.3.
+    fn read_uvlc(r: &mut BitReader) -> Result<u32> {
+        let mut leading_zeros = 0;
+
+        while !r.read_bool()? {
+            leading_zeros += 1;
+        }
+
+        if leading_zeros >= 32 {
+            return Ok(u32::MAX);
+        }
+
+        let value = r.read_u32(leading_zeros)?;
+        Ok(value + ((1 << leading_zeros) - 1))
+    }
+
+    /// Reads a signed value of `n` bits, see su() in 4.10.6.
+    fn read_su(r: &mut BitReader, n: u8) -> Result<i32> {
+        let mut value = r.read_u32(n)? as i32;
+        let sign_mask = 1 << (n - 1);
+
+        if value & sign_mask != 0 {
+            value -= 2 * sign_mask;
+        }
+
+        Ok(value)
+    }
+
+    /// Reads a non-symmetric unsigned value in the range 0..n, see ns() in 4.10.7.
+    fn read_ns(r: &mut BitReader, n: u32) -> Result<u32> {
+        let w = Self::floor_log2(n) + 1;
+        let m = (1 << w) - n;
+        let v = r.read_u32(w as u8 - 1)?;
+
+        if v < m {
+            return Ok(v);
+        }
+
+        let extra_bit = r.read_u32(1)?;
+        Ok((v << 1) - m + extra_bit)
+    }
+
+    /// Reads a little-endian value of `n` bytes, see le() in 4.10.4.
+    fn read_le(data: &[u8], n: usize) -> Result<u32> {
+        let bytes = data
+            .get(..n)
+            .ok_or(anyhow!("Broken data: truncated tile size"))?;
+
+        Ok(bytes
+            .iter()
+            .enumerate()
+            .fold(0, |value, (i, &byte)| value | (u32::from(byte) << (i * 8))))
+    }
+
+    fn floor_log2(x: u32) -> u32 {
+        31 - x.leading_zeros()
+    }
+
+    /// Returns the smallest `k` such that `blk_size << k` is at least `target`, see 7.3.
+    fn tile_log2(blk_size: u32, target: u32) -> u32 {
+        let mut k = 0;
+
+        while (blk_size << k) < target {
+            k += 1;
+        }
+
+        k
+    }
+
+    /// Skips to the next byte boundary, see byte_alignment() in 5.3.5.
+    fn byte_alignment(r: &mut BitReader) -> Result<()> {
+        let padding = (8 - r.position() % 8) % 8;
+        r.skip(padding)?;
+        Ok(())
+    }
+
+    /// Reads the next OBU in `data`, see 5.3.
+    pub fn parse_obu<'a>(&self, data: &'a [u8]) -> Result<Obu<'a>> {
+        let mut r = BitReader::new(data);
+
+        if r.read_bool()? {
+            return Err(anyhow!("Broken data: obu_forbidden_bit is set"));
+        }
+
+        let obu_type = r.read_u8(4)?;
+        let mut header = ObuHeader {
+            obu_type: ObuType::n(obu_type)
+                .with_context(|| format!("Invalid OBU type {}", obu_type))?,
+            extension_flag: r.read_bool()?,
+            has_size_field: r.read_bool()?,
+            ..Default::default()
+        };
+
+        // obu_reserved_1bit
+        r.skip(1)?;
+
+        if header.extension_flag {
+            header.temporal_id = r.read_u8(3)?;
+            header.spatial_id = r.read_u8(2)?;
+            // extension_header_reserved_3bits
+            r.skip(3)?;
+        }
+
+        let header_size = r.position() as usize / 8;
+
+        let (obu_size, size_len) = if header.has_size_field {
+            let (obu_size, size_len) = Self::read_leb128(&data[header_size..])?;
+            (usize::try_from(obu_size)?, size_len)
+        } else {
+            (data.len() - header_size, 0)
+        };
+
+        let start = header_size + size_len;
+        let end = start + obu_size;
+
+        if end > data.len() {
+            return Err(anyhow!(
+                "Broken data: OBU size {} exceeds the available data",
+                obu_size
+            ));
+        }
+
+        Ok(Obu {
+            header,
+            data: &data[start..end],
+            bytes_used: end,
+        })
+    }
+
+    /// Whether the OBU is not part of the selected operating point and must be dropped, see 7.5.
+    pub fn should_drop(&self, header: &ObuHeader) -> bool {
+        if matches!(
+            header.obu_type,
+            ObuType::SequenceHeader | ObuType::TemporalDelimiter | ObuType::Padding
+        ) || !header.extension_flag
+        {
+            return false;
+        }
+
+        let idc = match &self.sequence_header {
+            Some(seq) => seq.operating_points[self.operating_point].idc,
+            None => return false,
+        };
+
+        if idc == 0 {
+            return false;
+        }
+
+        let in_temporal_layer = (idc >> header.temporal_id) & 1 != 0;
+        let in_spatial_layer = (idc >> (header.spatial_id + 8)) & 1 != 0;
+
+        !in_temporal_layer || !in_spatial_layer
+    }
+
+    fn parse_color_config(r: &mut BitReader, seq: &mut SequenceHeader) -> Result<()> {
+        let cc = &mut seq.color_config;
+
+        cc.high_bitdepth = r.read_bool()?;
+        if matches!(seq.seq_profile, Profile::Profile2) && cc.high_bitdepth {
+            cc.twelve_bit = r.read_bool()?;
+            cc.bit_depth = if cc.twelve_bit { 12 } else { 10 };
+        } else {
+            cc.bit_depth = if cc.high_bitdepth { 10 } else { 8 };
+        }
+
+        if !matches!(seq.seq_profile, Profile::Profile1) {
+            cc.mono_chrome = r.read_bool()?;
+        }
+
+        cc.num_planes = if cc.mono_chrome { 1 } else { 3 };
+
+        cc.color_description_present_flag = r.read_bool()?;
+        if cc.color_description_present_flag {
+            let color_primaries = r.read_u8(8)?;
+            cc.color_primaries = ColorPrimaries::n(color_primaries)
+                .with_context(|| format!("Invalid color primaries {}", color_primaries))?;
+            let transfer_characteristics = r.read_u8(8)?;
+            cc.transfer_characteristics = TransferCharacteristics::n(transfer_characteristics)
+                .with_context(|| {
+                    format!(
+                        "Invalid transfer characteristics {}",
+                        transfer_characteristics
+                    )
+                })?;
+            let matrix_coefficients = r.read_u8(8)?;
+            cc.matrix_coefficients = MatrixCoefficients::n(matrix_coefficients)
+                .with_context(|| format!("Invalid matrix coefficients {}", matrix_coefficients))?;
+        } else {
+            cc.color_primaries = ColorPrimaries::Unspecified;
+            cc.transfer_characteristics = TransferCharacteristics::Unspecified;
+            cc.matrix_coefficients = MatrixCoefficients::Unspecified;
+        }
+
+        if cc.mono_chrome {
+            cc.color_range = r.read_bool()?;
+            cc.subsampling_x = true;
+            cc.subsampling_y = true;
+            cc.chroma_sample_position = ChromaSamplePosition::Unknown;
+            cc.separate_uv_delta_q = false;
+            return Ok(());
+        } else if matches!(cc.color_primaries, ColorPrimaries::Bt709)
+            && matches!(cc.transfer_characteristics, TransferCharacteristics::Srgb)
+            && matches!(cc.matrix_coefficients, MatrixCoefficients::Identity)
+        {
+            cc.color_range = true;
+            cc.subsampling_x = false;
+            cc.subsampling_y = false;
+        } else {
+            cc.color_range = r.read_bool()?;
+
+            match seq.seq_profile {
+                Profile::Profile0 => {
+                    cc.subsampling_x = true;
+                    cc.subsampling_y = true;
+                }
+                Profile::Profile1 => {
+                    cc.subsampling_x = false;
+                    cc.subsampling_y = false;
+                }
+                Profile::Profile2 => {
+                    if cc.bit_depth == 12 {
+                        cc.subsampling_x = r.read_bool()?;
+                        cc.subsampling_y = cc.subsampling_x && r.read_bool()?;
+                    } else {
+                        cc.subsampling_x = true;
+                        cc.subsampling_y = false;
+                    }
+                }
+            }
+
+            if cc.subsampling_x && cc.subsampling_y {
+                let chroma_sample_position = r.read_u8(2)?;
+                cc.chroma_sample_position = ChromaSamplePosition::n(chroma_sample_position)
+                    .with_context(|| {
+                        format!("Invalid chroma sample position {}", chroma_sample_position)
+                    })?;
+            }
+        }
+
+        cc.separate_uv_delta_q = r.read_bool()?;
+
+        Ok(())
+    }
+
+    /// Parses a sequence header OBU, see 5.5.
+    pub fn parse_sequence_header_obu(&mut self, obu: &Obu) -> Result<&SequenceHeader> {
+        if !matches!(obu.header.obu_type, ObuType::SequenceHeader) {
+            return Err(anyhow!("Not a sequence header OBU"));
+        }
+
+        let mut r = BitReader::new(obu.data);
+        let mut seq = SequenceHeader::default();
+
+        let seq_profile = r.read_u8(3)?;
+        seq.seq_profile = Profile::n(seq_profile)
+            .with_context(|| format!("Invalid sequence profile {}", seq_profile))?;
+        seq.still_picture = r.read_bool()?;
+        seq.reduced_still_picture_header = r.read_bool()?;
+
+        if seq.reduced_still_picture_header {
+            seq.operating_points[0].seq_level_idx = r.read_u8(5)?;
+        } else {
+            seq.timing_info_present_flag = r.read_bool()?;
+            if seq.timing_info_present_flag {
+                let ti = &mut seq.timing_info;
+                ti.num_units_in_display_tick = r.read_u32(32)?;
+                ti.time_scale = r.read_u32(32)?;
+                ti.equal_picture_interval = r.read_bool()?;
+                if ti.equal_picture_interval {
+                    ti.num_ticks_per_picture_minus_1 = Self::read_uvlc(&mut r)?;
+                }
+
+                seq.decoder_model_info_present_flag = r.read_bool()?;
+                if seq.decoder_model_info_present_flag {
+                    let dmi = &mut seq.decoder_model_info;
+                    dmi.buffer_delay_length_minus_1 = r.read_u8(5)?;
+                    dmi.num_units_in_decoding_tick = r.read_u32(32)?;
+                    dmi.buffer_removal_time_length_minus_1 = r.read_u8(5)?;
+                    dmi.frame_presentation_time_length_minus_1 = r.read_u8(5)?;
+                }
+            }
+
+            seq.initial_display_delay_present_flag = r.read_bool()?;
+            seq.operating_points_cnt_minus_1 = r.read_u8(5)?;
+
+            let buffer_delay_length = seq.decoder_model_info.buffer_delay_length_minus_1 + 1;
+            for op in &mut seq.operating_points[..=usize::from(seq.operating_points_cnt_minus_1)] {
+                op.idc = r.read_u16(12)?;
+                op.seq_level_idx = r.read_u8(5)?;
+                if op.seq_level_idx > 7 {
+                    op.seq_tier = r.read_u8(1)?;
+                }
+
+                if seq.decoder_model_info_present_flag {
+                    op.decoder_model_present_for_this_op = r.read_bool()?;
+                    if op.decoder_model_present_for_this_op {
+                        op.decoder_buffer_delay = r.read_u32(buffer_delay_length)?;
+                        op.encoder_buffer_delay = r.read_u32(buffer_delay_length)?;
+                        op.low_delay_mode_flag = r.read_bool()?;
+                    }
+                }
+
+                if seq.initial_display_delay_present_flag {
+                    op.initial_display_delay_present_for_this_op = r.read_bool()?;
+                    if op.initial_display_delay_present_for_this_op {
+                        op.initial_display_delay_minus_1 = r.read_u8(4)?;
+                    }
+                }
+            }
+        }
+
+        seq.frame_width_bits_minus_1 = r.read_u8(4)?;
+        seq.frame_height_bits_minus_1 = r.read_u8(4)?;
+        seq.max_frame_width_minus_1 = r.read_u16(seq.frame_width_bits_minus_1 + 1)?;
+        seq.max_frame_height_minus_1 = r.read_u16(seq.frame_height_bits_minus_1 + 1)?;
+
+        if !seq.reduced_still_picture_header {
+            seq.frame_id_numbers_present_flag = r.read_bool()?;
+        }
+
+        if seq.frame_id_numbers_present_flag {
+            seq.delta_frame_id_length_minus_2 = r.read_u8(4)?;
+            seq.additional_frame_id_length_minus_1 = r.read_u8(3)?;
+        }
+
+        seq.use_128x128_superblock = r.read_bool()?;
+        seq.enable_filter_intra = r.read_bool()?;
+        seq.enable_intra_edge_filter = r.read_bool()?;
+
+        if seq.reduced_still_picture_header {
+            seq.seq_force_screen_content_tools = SELECT_SCREEN_CONTENT_TOOLS;
+            seq.seq_force_integer_mv = SELECT_INTEGER_MV;
+        } else {
+            seq.enable_interintra_compound = r.read_bool()?;
+            seq.enable_masked_compound = r.read_bool()?;
+            seq.enable_warped_motion = r.read_bool()?;
+            seq.enable_dual_filter = r.read_bool()?;
+            seq.enable_order_hint = r.read_bool()?;
+
+            if seq.enable_order_hint {
+                seq.enable_jnt_comp = r.read_bool()?;
+                seq.enable_ref_frame_mvs = r.read_bool()?;
+            }
+
+            seq.seq_choose_screen_content_tools = r.read_bool()?;
+            seq.seq_force_screen_content_tools = if seq.seq_choose_screen_content_tools {
+                SELECT_SCREEN_CONTENT_TOOLS
+            } else {
+                r.read_u8(1)?
+            };
+
+            if seq.seq_force_screen_content_tools > 0 {
+                seq.seq_choose_integer_mv = r.read_bool()?;
+                seq.seq_force_integer_mv = if seq.seq_choose_integer_mv {
+                    SELECT_INTEGER_MV
+                } else {
+                    r.read_u8(1)?
+                };
+            } else {
+                seq.seq_force_integer_mv = SELECT_INTEGER_MV;
+            }
+
+            if seq.enable_order_hint {
+                seq.order_hint_bits_minus_1 = r.read_u8(3)?;
+                seq.order_hint_bits = seq.order_hint_bits_minus_1 + 1;
+            }
+        }
+
+        seq.enable_superres = r.read_bool()?;
+        seq.enable_cdef = r.read_bool()?;
+        seq.enable_restoration = r.read_bool()?;
+        Self::parse_color_config(&mut r, &mut seq)?;
+        seq.film_grain_params_present = r.read_bool()?;
+
+        // Select the first operating point, as recommended by 7.1 when there is no external
+        // means to choose one.
+        self.operating_point = 0;
+        self.sequence_header = Some(seq);
+
+        Ok(self.sequence_header.as_ref().unwrap())
+    }
+
+    /// Computes the relative distance between two order hints, see 7.12.3.
+    fn get_relative_dist(seq: &SequenceHeader, a: u32, b: u32) -> i32 {
+        if !seq.enable_order_hint {
+            return 0;
+        }
+
+        let diff = a as i32 - b as i32;
+        let m = 1 << (seq.order_hint_bits - 1);
+
+        (diff & (m - 1)) - (diff & m)
+    }
+
+    fn parse_temporal_point_info(
+        r: &mut BitReader,
+        seq: &SequenceHeader,
+        hdr: &mut FrameHeader,
+    ) -> Result<()> {
+        let n = seq
+            .decoder_model_info
+            .frame_presentation_time_length_minus_1
+            + 1;
+        hdr.temporal_point_info.frame_presentation_time = r.read_u32(n)?;
+        Ok(())
+    }
+
+    fn parse_superres_params(
+        r: &mut BitReader,
+        seq: &SequenceHeader,
+        hdr: &mut FrameHeader,
+    ) -> Result<()> {
+        if seq.enable_superres {
+            hdr.use_superres = r.read_bool()?;
+        }
+
+        hdr.superres_denom = if hdr.use_superres {
+            r.read_u32(SUPERRES_DENOM_BITS)? + SUPERRES_DENOM_MIN
+        } else {
+            SUPERRES_NUM
+        };
+
+        hdr.upscaled_width = hdr.frame_width;
+        hdr.frame_width =
+            (hdr.upscaled_width * SUPERRES_NUM + hdr.superres_denom / 2) / hdr.superres_denom;
+
+        Ok(())
+    }
+
+    fn compute_image_size(hdr: &mut FrameHeader) {
+        hdr.mi_cols = 2 * ((hdr.frame_width + 7) >> 3);
+        hdr.mi_rows = 2 * ((hdr.frame_height + 7) >> 3);
+    }
+
+    fn parse_frame_size(
+        r: &mut BitReader,
+        seq: &SequenceHeader,
+        hdr: &mut FrameHeader,
+    ) -> Result<()> {
+        if hdr.frame_size_override_flag {
+            hdr.frame_width = r.read_u32(seq.frame_width_bits_minus_1 + 1)? + 1;
+            hdr.frame_height = r.read_u32(seq.frame_height_bits_minus_1 + 1)? + 1;
+        } else {
+            hdr.frame_width = seq.max_frame_width();
+            hdr.frame_height = seq.max_frame_height();
+        }
+
+        Self::parse_superres_params(r, seq, hdr)?;
+        Self::compute_image_size(hdr);
+
+        Ok(())
+    }
+
+    fn parse_render_size(r: &mut BitReader, hdr: &mut FrameHeader) -> Result<()> {
+        hdr.render_and_frame_size_different = r.read_bool()?;
+
+        if hdr.render_and_frame_size_different {
+            hdr.render_width = r.read_u32(16)? + 1;
+            hdr.render_height = r.read_u32(16)? + 1;
+        } else {
+            hdr.render_width = hdr.upscaled_width;
+            hdr.render_height = hdr.frame_height;
+        }
+
+        Ok(())
+    }
+
+    fn parse_frame_size_with_refs(
+        &self,
+        r: &mut BitReader,
+        seq: &SequenceHeader,
+        hdr: &mut FrameHeader,
+    ) -> Result<()> {
+        for i in 0..REFS_PER_FRAME {
+            let found_ref = r.read_bool()?;
+
+            if found_ref {
+                let ref_info = &self.ref_info[usize::from(hdr.ref_frame_idx[i])];
+
+                hdr.upscaled_width = ref_info.ref_upscaled_width;
+                hdr.frame_width = hdr.upscaled_width;
+                hdr.frame_height = ref_info.ref_frame_height;
+                hdr.render_width = ref_info.ref_render_width;
+                hdr.render_height = ref_info.ref_render_height;
+
+                Self::parse_superres_params(r, seq, hdr)?;
+                Self::compute_image_size(hdr);
+
+                return Ok(());
+            }
+        }
+
+        Self::parse_frame_size(r, seq, hdr)?;
+        Self::parse_render_size(r, hdr)
+    }
+
+    /// Sets up the reference frame indices when frame_refs_short_signaling is used, see 7.8.
+    fn set_frame_refs(&self, seq: &SequenceHeader, hdr: &mut FrameHeader) -> Result<()> {
+        const LAST_FRAME: usize = ReferenceFrameType::Last as usize;
+        const GOLDEN_FRAME: usize = ReferenceFrameType::Golden as usize;
+        const ALTREF_FRAME: usize = ReferenceFrameType::AltRef as usize;
+        const REF_FRAME_LIST: [usize; REFS_PER_FRAME - 2] = [
+            ReferenceFrameType::Last2 as usize,
+            ReferenceFrameType::Last3 as usize,
+            ReferenceFrameType::BwdRef as usize,
+            ReferenceFrameType::AltRef2 as usize,
+            ReferenceFrameType::AltRef as usize,
+        ];
+
+        let mut ref_frame_idx = [-1i32; TOTAL_REFS_PER_FRAME];
+        ref_frame_idx[LAST_FRAME] = i32::from(hdr.last_frame_idx);
+        ref_frame_idx[GOLDEN_FRAME] = i32::from(hdr.gold_frame_idx);
+
+        let mut used_frame = [false; NUM_REF_FRAMES];
+        used_frame[usize::from(hdr.last_frame_idx)] = true;
+        used_frame[usize::from(hdr.gold_frame_idx)] = true;
+
+        let cur_frame_hint = 1 << (seq.order_hint_bits - 1);
+        let mut shifted_order_hints = [0i32; NUM_REF_FRAMES];
+
+        for (i, shifted_order_hint) in shifted_order_hints.iter_mut().enumerate() {
+            *shifted_order_hint = cur_frame_hint
+                + Self::get_relative_dist(seq, self.ref_info[i].ref_order_hint, hdr.order_hint);
+        }
+
+        let last_order_hint = shifted_order_hints[usize::from(hdr.last_frame_idx)];
+        let gold_order_hint = shifted_order_hints[usize::from(hdr.gold_frame_idx)];
+
+        if last_order_hint >= cur_frame_hint || gold_order_hint >= cur_frame_hint {
+            return Err(anyhow!(
+                "Broken data: invalid references for frame_refs_short_signaling"
+            ));
+        }
+
+        // Finds the unused reference frame with the largest or smallest order hint matching the
+        // given condition.
+        let find_ref = |used_frame: &[bool; NUM_REF_FRAMES],
+                        want_latest: bool,
+                        condition: &dyn Fn(i32) -> bool| {
+            let mut result = None;
+            let mut best_hint = 0;
+
+            for i in 0..NUM_REF_FRAMES {
+                let hint = shifted_order_hints[i];
+                if !used_frame[i]
+                    && condition(hint)
+                    && (result.is_none()
+                        || (want_latest && hint >= best_hint)
+                        || (!want_latest && hint < best_hint))
+                {
+                    result = Some(i);
+                    best_hint = hint;
+                }
+            }
+
+            result
+        };
+
+        // The ALTREF_FRAME is the backward reference with the latest order hint.
+        if let Some(i) = find_ref(&used_frame, true, &|hint| hint >= cur_frame_hint) {
+            ref_frame_idx[ALTREF_FRAME] = i as i32;
+            used_frame[i] = true;
+        }
+
+        // The BWDREF_FRAME is the backward reference with the earliest order hint.
+        if let Some(i) = find_ref(&used_frame, false, &|hint| hint >= cur_frame_hint) {
+            ref_frame_idx[ReferenceFrameType::BwdRef as usize] = i as i32;
+            used_frame[i] = true;
+        }
+
+        // The ALTREF2_FRAME is the next backward reference with the earliest order hint.
+        if let Some(i) = find_ref(&used_frame, false, &|hint| hint >= cur_frame_hint) {
+            ref_frame_idx[ReferenceFrameType::AltRef2 as usize] = i as i32;
+            used_frame[i] = true;
+        }
+
+        // The remaining references are the forward references, latest first.
+        for ref_frame in REF_FRAME_LIST {
+            if ref_frame_idx[ref_frame] < 0 {
+                if let Some(i) = find_ref(&used_frame, true, &|hint| hint < cur_frame_hint) {
+                    ref_frame_idx[ref_frame] = i as i32;
+                    used_frame[i] = true;
+                }
+            }
+        }
+
+        // Any remaining references are set to the reference frame with the smallest order hint.
+        let mut earliest = 0;
+        for i in 0..NUM_REF_FRAMES {
+            if shifted_order_hints[i] < shifted_order_hints[earliest] {
+                earliest = i;
+            }
+        }
+
+        for (i, idx) in hdr.ref_frame_idx.iter_mut().enumerate() {
+            let ref_idx = ref_frame_idx[LAST_FRAME + i];
+            *idx = if ref_idx < 0 {
+                earliest as u8
+            } else {
+                ref_idx as u8
+            };
+        }
+
+        Ok(())
+    }
+
+    fn parse_interpolation_filter(r: &mut BitReader, hdr: &mut FrameHeader) -> Result<()> {
+        let is_filter_switchable = r.read_bool()?;
+
+        hdr.interpolation_filter = if is_filter_switchable {
+            InterpolationFilter::Switchable
+        } else {
+            let filter = r.read_u8(2)?;
+            InterpolationFilter::n(filter)
+                .with_context(|| format!("Invalid interpolation filter {}", filter))?
+        };
+
+        Ok(())
+    }
+
+    fn parse_tile_info(
+        r: &mut BitReader,
+        seq: &SequenceHeader,
+        hdr: &mut FrameHeader,
+    ) -> Result<()> {
+        let ti = &mut hdr.tile_info;
+
+        let (sb_cols, sb_rows, sb_shift) = if seq.use_128x128_superblock {
+            ((hdr.mi_cols + 31) >> 5, (hdr.mi_rows + 31) >> 5, 5)
+        } else {
+            ((hdr.mi_cols + 15) >> 4, (hdr.mi_rows + 15) >> 4, 4)
+        };
+
+        let sb_size = sb_shift + 2;
+        let max_tile_width_sb = MAX_TILE_WIDTH >> sb_size;
+        let mut max_tile_area_sb = MAX_TILE_AREA >> (2 * sb_size);
+        let min_log2_tile_cols = Self::tile_log2(max_tile_width_sb, sb_cols);
+        let max_log2_tile_cols = Self::tile_log2(1, sb_cols.min(MAX_TILE_COLS as u32));
+        let max_log2_tile_rows = Self::tile_log2(1, sb_rows.min(MAX_TILE_ROWS as u32));
+        let min_log2_tiles =
+            min_log2_tile_cols.max(Self::tile_log2(max_tile_area_sb, sb_rows * sb_cols));
+
+        ti.uniform_tile_spacing_flag = r.read_bool()?;
+
+        if ti.uniform_tile_spacing_flag {
+            let mut tile_cols_log2 = min_log2_tile_cols;
+            while tile_cols_log2 < max_log2_tile_cols && r.read_bool()? {
+                tile_cols_log2 += 1;
+            }
+
+            let tile_width_sb = (sb_cols + (1 << tile_cols_log2) - 1) >> tile_cols_log2;
+            let mut i = 0;
+            let mut start_sb = 0;
+            while start_sb < sb_cols {
+                ti.mi_col_starts[i] = start_sb << sb_shift;
+                i += 1;
+                start_sb += tile_width_sb;
+            }
+            ti.mi_col_starts[i] = hdr.mi_cols;
+            ti.tile_cols = i as u32;
+
+            let min_log2_tile_rows = min_log2_tiles.saturating_sub(tile_cols_log2);
+            let mut tile_rows_log2 = min_log2_tile_rows;
+            while tile_rows_log2 < max_log2_tile_rows && r.read_bool()? {
+                tile_rows_log2 += 1;
+            }
+
+            let tile_height_sb = (sb_rows + (1 << tile_rows_log2) - 1) >> tile_rows_log2;
+            let mut i = 0;
+            let mut start_sb = 0;
+            while start_sb < sb_rows {
+                ti.mi_row_starts[i] = start_sb << sb_shift;
+                i += 1;
+                start_sb += tile_height_sb;
+            }
+            ti.mi_row_starts[i] = hdr.mi_rows;
+            ti.tile_rows = i as u32;
+
+            ti.tile_cols_log2 = tile_cols_log2 as u8;
+            ti.tile_rows_log2 = tile_rows_log2 as u8;
+        } else {
+            let mut widest_tile_sb = 0;
+            let mut start_sb = 0;
+            let mut i = 0;
+            while start_sb < sb_cols {
+                if i >= MAX_TILE_COLS {
+                    return Err(anyhow!("Broken data: too many tile columns"));
+                }
+
+                ti.mi_col_starts[i] = start_sb << sb_shift;
+                let max_width = (sb_cols - start_sb).min(max_tile_width_sb);
+                ti.width_in_sbs_minus_1[i] = Self::read_ns(r, max_width)?;
+                let size_sb = ti.width_in_sbs_minus_1[i] + 1;
+                widest_tile_sb = widest_tile_sb.max(size_sb);
+                start_sb += size_sb;
+                i += 1;
+            }
+            ti.mi_col_starts[i] = hdr.mi_cols;
+            ti.tile_cols = i as u32;
+            ti.tile_cols_log2 = Self::tile_log2(1, ti.tile_cols) as u8;
+
+            if min_log2_tiles > 0 {
+                max_tile_area_sb = (sb_rows * sb_cols) >> (min_log2_tiles + 1);
+            } else {
+                max_tile_area_sb = sb_rows * sb_cols;
+            }
+
+            let max_tile_height_sb = (max_tile_area_sb / widest_tile_sb).max(1);
+
+            let mut start_sb = 0;
+            let mut i = 0;
+            while start_sb < sb_rows {
+                if i >= MAX_TILE_ROWS {
+                    return Err(anyhow!("Broken data: too many tile rows"));
+                }
+
+                ti.mi_row_starts[i] = start_sb << sb_shift;
+                let max_height = (sb_rows - start_sb).min(max_tile_height_sb);
+                ti.height_in_sbs_minus_1[i] = Self::read_ns(r, max_height)?;
+                let size_sb = ti.height_in_sbs_minus_1[i] + 1;
+                start_sb += size_sb;
+                i += 1;
+            }
+            ti.mi_row_starts[i] = hdr.mi_rows;
+            ti.tile_rows = i as u32;
+            ti.tile_rows_log2 = Self::tile_log2(1, ti.tile_rows) as u8;
+        }
+
+        if ti.tile_cols_log2 > 0 || ti.tile_rows_log2 > 0 {
+            ti.context_update_tile_id = r.read_u32(ti.tile_rows_log2 + ti.tile_cols_log2)?;
+            ti.tile_size_bytes = r.read_u8(2)? + 1;
+        } else {
+            ti.context_update_tile_id = 0;
+        }
+
+        Ok(())
+    }
+
+    fn read_delta_q(r: &mut BitReader) -> Result<i8> {
+        if r.read_bool()? {
+            Ok(Self::read_su(r, 7)? as i8)
+        } else {
+            Ok(0)
+        }
+    }
+
+    fn parse_quantization_params(
+        r: &mut BitReader,
+        seq: &SequenceHeader,
+        hdr: &mut FrameHeader,
+    ) -> Result<()> {
+        let qp = &mut hdr.quantization_params;
+        let cc = &seq.color_config;
+
+        qp.base_q_idx = r.read_u8(8)?;
+        qp.delta_q_y_dc = Self::read_delta_q(r)?;
+
+        if cc.num_planes > 1 {
+            if cc.separate_uv_delta_q {
+                qp.diff_uv_delta = r.read_bool()?;
+            }
+
+            qp.delta_q_u_dc = Self::read_delta_q(r)?;
+            qp.delta_q_u_ac = Self::read_delta_q(r)?;
+
+            if qp.diff_uv_delta {
+                qp.delta_q_v_dc = Self::read_delta_q(r)?;
+                qp.delta_q_v_ac = Self::read_delta_q(r)?;
+            } else {
+                qp.delta_q_v_dc = qp.delta_q_u_dc;
+                qp.delta_q_v_ac = qp.delta_q_u_ac;
+            }
+        }
+
+        qp.using_qmatrix = r.read_bool()?;
+        if qp.using_qmatrix {
+            qp.qm_y = r.read_u8(4)?;
+            qp.qm_u = r.read_u8(4)?;
+            qp.qm_v = if cc.separate_uv_delta_q {
+                r.read_u8(4)?
+            } else {
+                qp.qm_u
+            };
+        }
+
+        Ok(())
+    }
+
+    fn parse_segmentation_params(r: &mut BitReader, hdr: &mut FrameHeader) -> Result<()> {
+        let seg = &mut hdr.segmentation_params;
+
+        seg.segmentation_enabled = r.read_bool()?;
+
+        if seg.segmentation_enabled {
+            if hdr.primary_ref_frame == PRIMARY_REF_NONE {
+                seg.segmentation_update_map = true;
+                seg.segmentation_temporal_update = false;
+                seg.segmentation_update_data = true;
+            } else {
+                seg.segmentation_update_map = r.read_bool()?;
+                if seg.segmentation_update_map {
+                    seg.segmentation_temporal_update = r.read_bool()?;
+                }
+                seg.segmentation_update_data = r.read_bool()?;
+            }
+
+            if seg.segmentation_update_data {
+                for i in 0..MAX_SEGMENTS {
+                    for j in 0..SEG_LVL_MAX {
+                        let feature_enabled = r.read_bool()?;
+                        let mut clipped_value = 0;
+
+                        if feature_enabled {
+                            let bits_to_read = SEGMENTATION_FEATURE_BITS[j];
+                            let limit = SEGMENTATION_FEATURE_MAX[j];
+
+                            clipped_value = if SEGMENTATION_FEATURE_SIGNED[j] {
+                                Self::read_su(r, 1 + bits_to_read)?.clamp(-limit, limit)
+                            } else {
+                                (r.read_u32(bits_to_read)? as i32).clamp(0, limit)
+                            };
+                        }
+
+                        seg.feature_enabled[i][j] = feature_enabled;
+                        seg.feature_data[i][j] = clipped_value as i16;
+                    }
+                }
+            }
+        } else {
+            seg.feature_enabled = Default::default();
+            seg.feature_data = Default::default();
+        }
+
+        seg.seg_id_pre_skip = false;
+        seg.last_active_seg_id = 0;
+        for i in 0..MAX_SEGMENTS {
+            for j in 0..SEG_LVL_MAX {
+                if seg.feature_enabled[i][j] {
+                    seg.last_active_seg_id = i as u8;
+                    if j >= SEG_LVL_REF_FRAME {
+                        seg.seg_id_pre_skip = true;
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn parse_delta_params(r: &mut BitReader, hdr: &mut FrameHeader) -> Result<()> {
+        let qp = &mut hdr.quantization_params;
+        let lf = &mut hdr.loop_filter_params;
+
+        qp.delta_q_res = 0;
+        qp.delta_q_present = false;
+        if qp.base_q_idx > 0 {
+            qp.delta_q_present = r.read_bool()?;
+        }
+        if qp.delta_q_present {
+            qp.delta_q_res = r.read_u8(2)?;
+        }
+
+        lf.delta_lf_present = false;
+        lf.delta_lf_res = 0;
+        lf.delta_lf_multi = false;
+        if qp.delta_q_present {
+            if !hdr.allow_intrabc {
+                lf.delta_lf_present = r.read_bool()?;
+            }
+            if lf.delta_lf_present {
+                lf.delta_lf_res = r.read_u8(2)?;
+                lf.delta_lf_multi = r.read_bool()?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Computes the quantizer index of a segment, see get_qindex() in 7.12.2.
+    fn get_qindex(hdr: &FrameHeader, segment_id: usize) -> i32 {
+        let seg = &hdr.segmentation_params;
+        let base_q_idx = i32::from(hdr.quantization_params.base_q_idx);
+
+        if seg.segmentation_enabled && seg.feature_enabled[segment_id][0] {
+            (base_q_idx + i32::from(seg.feature_data[segment_id][0])).clamp(0, 255)
+        } else {
+            base_q_idx
+        }
+    }
+
+    fn compute_lossless(hdr: &mut FrameHeader) {
+        let qp = hdr.quantization_params.clone();
+
+        hdr.coded_lossless = true;
+        for segment_id in 0..MAX_SEGMENTS {
+            let qindex = Self::get_qindex(hdr, segment_id);
+            let lossless = qindex == 0
+                && qp.delta_q_y_dc == 0
+                && qp.delta_q_u_ac == 0
+                && qp.delta_q_u_dc == 0
+                && qp.delta_q_v_ac == 0
+                && qp.delta_q_v_dc == 0;
+
+            hdr.lossless_array[segment_id] = lossless;
+            if !lossless {
+                hdr.coded_lossless = false;
+            }
+
+            if qp.using_qmatrix {
+                let levels = if lossless {
+                    [15, 15, 15]
+                } else {
+                    [qp.qm_y, qp.qm_u, qp.qm_v]
+                };
+
+                for (plane, level) in levels.into_iter().enumerate() {
+                    hdr.seg_qm_level[plane][segment_id] = level;
+                }
+            }
+        }
+
+        hdr.all_lossless = hdr.coded_lossless && hdr.frame_width == hdr.upscaled_width;
+    }
+
+    fn parse_loop_filter_params(
+        r: &mut BitReader,
+        seq: &SequenceHeader,
+        hdr: &mut FrameHeader,
+    ) -> Result<()> {
+        let lf = &mut hdr.loop_filter_params;
+
+        if hdr.coded_lossless || hdr.allow_intrabc {
+            lf.loop_filter_level[0] = 0;
+            lf.loop_filter_level[1] = 0;
+            lf.loop_filter_ref_deltas = DEFAULT_LOOP_FILTER_REF_DELTAS;
+            lf.loop_filter_mode_deltas = Default::default();
+            return Ok(());
+        }
+
+        lf.loop_filter_level[0] = r.read_u8(6)?;
+        lf.loop_filter_level[1] = r.read_u8(6)?;
+
+        if seq.color_config.num_planes > 1
+            && (lf.loop_filter_level[0] != 0 || lf.loop_filter_level[1] != 0)
+        {
+            lf.loop_filter_level[2] = r.read_u8(6)?;
+            lf.loop_filter_level[3] = r.read_u8(6)?;
+        }
+
+        lf.loop_filter_sharpness = r.read_u8(3)?;
+        lf.loop_filter_delta_enabled = r.read_bool()?;
+
+        if lf.loop_filter_delta_enabled {
+            lf.loop_filter_delta_update = r.read_bool()?;
+
+            if lf.loop_filter_delta_update {
+                for i in 0..TOTAL_REFS_PER_FRAME {
+                    lf.update_ref_delta[i] = r.read_bool()?;
+                    if lf.update_ref_delta[i] {
+                        lf.loop_filter_ref_deltas[i] = Self::read_su(r, 7)? as i8;
+                    }
+                }
+
+                for i in 0..2 {
+                    lf.update_mode_delta[i] = r.read_bool()?;
+                    if lf.update_mode_delta[i] {
+                        lf.loop_filter_mode_deltas[i] = Self::read_su(r, 7)? as i8;
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn parse_cdef_params(
+        r: &mut BitReader,
+        seq: &SequenceHeader,
+        hdr: &mut FrameHeader,
+    ) -> Result<()> {
+        let cdef = &mut hdr.cdef_params;
+
+        if hdr.coded_lossless || hdr.allow_intrabc || !seq.enable_cdef {
+            cdef.cdef_bits = 0;
+            cdef.cdef_y_pri_strength[0] = 0;
+            cdef.cdef_y_sec_strength[0] = 0;
+            cdef.cdef_uv_pri_strength[0] = 0;
+            cdef.cdef_uv_sec_strength[0] = 0;
+            cdef.cdef_damping = 3;
+            return Ok(());
+        }
+
+        cdef.cdef_damping = r.read_u8(2)? + 3;
+        cdef.cdef_bits = r.read_u8(2)?;
+
+        for i in 0..1 << cdef.cdef_bits {
+            cdef.cdef_y_pri_strength[i] = r.read_u8(4)?;
+            cdef.cdef_y_sec_strength[i] = r.read_u8(2)?;
+            if cdef.cdef_y_sec_strength[i] == 3 {
+                cdef.cdef_y_sec_strength[i] += 1;
+            }
+
+            if seq.color_config.num_planes > 1 {
+                cdef.cdef_uv_pri_strength[i] = r.read_u8(4)?;
+                cdef.cdef_uv_sec_strength[i] = r.read_u8(2)?;
+                if cdef.cdef_uv_sec_strength[i] == 3 {
+                    cdef.cdef_uv_sec_strength[i] += 1;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn parse_lr_params(
+        r: &mut BitReader,
+        seq: &SequenceHeader,
+        hdr: &mut FrameHeader,
+    ) -> Result<()> {
+        const REMAP_LR_TYPE: [FrameRestorationType; 4] = [
+            FrameRestorationType::None,
+            FrameRestorationType::Switchable,
+            FrameRestorationType::Wiener,
+            FrameRestorationType::Sgrproj,
+        ];
+
+        let lr = &mut hdr.loop_restoration_params;
+        let cc = &seq.color_config;
+
+        lr.frame_restoration_type = Default::default();
+        lr.uses_lr = false;
+        lr.uses_chroma_lr = false;
+
+        if hdr.all_lossless || hdr.allow_intrabc || !seq.enable_restoration {
+            return Ok(());
+        }
+
+        for i in 0..usize::from(cc.num_planes) {
+            let lr_type = r.read_u8(2)?;
+            lr.frame_restoration_type[i] = REMAP_LR_TYPE[usize::from(lr_type)];
+
+            if !matches!(lr.frame_restoration_type[i], FrameRestorationType::None) {
+                lr.uses_lr = true;
+                if i > 0 {
+                    lr.uses_chroma_lr = true;
+                }
+            }
+        }
+
+        if lr.uses_lr {
+            if seq.use_128x128_superblock {
+                lr.lr_unit_shift = r.read_u8(1)? + 1;
+            } else {
+                lr.lr_unit_shift = r.read_u8(1)?;
+                if lr.lr_unit_shift != 0 {
+                    lr.lr_unit_shift += r.read_u8(1)?;
+                }
+            }
+
+            lr.loop_restoration_size[0] = RESTORATION_TILESIZE_MAX >> (2 - lr.lr_unit_shift);
+
+            lr.lr_uv_shift = if cc.subsampling_x && cc.subsampling_y && lr.uses_chroma_lr {
+                r.read_u8(1)?
+            } else {
+                0
+            };
+
+            lr.loop_restoration_size[1] = lr.loop_restoration_size[0] >> lr.lr_uv_shift;
+            lr.loop_restoration_size[2] = lr.loop_restoration_size[0] >> lr.lr_uv_shift;
+        }
+
+        Ok(())
+    }
+
+    fn parse_skip_mode_params(
+        &self,
+        r: &mut BitReader,
+        seq: &SequenceHeader,
+        hdr: &mut FrameHeader,
+    ) -> Result<()> {
+        let mut skip_mode_allowed = false;
+
+        if !hdr.frame_is_intra && hdr.reference_select && seq.enable_order_hint {
+            let mut forward: Option<(usize, u32)> = None;
+            let mut backward: Option<(usize, u32)> = None;
+
+            for i in 0..REFS_PER_FRAME {
+                let ref_hint = self.ref_info[usize::from(hdr.ref_frame_idx[i])].ref_order_hint;
+                let dist = Self::get_relative_dist(seq, ref_hint, hdr.order_hint);
+
+                if dist < 0 {
+                    if forward.map_or(true, |(_, forward_hint)| {
+                        Self::get_relative_dist(seq, ref_hint, forward_hint) > 0
+                    }) {
+                        forward = Some((i, ref_hint));
+                    }
+                } else if dist > 0
+                    && backward.map_or(true, |(_, backward_hint)| {
+                        Self::get_relative_dist(seq, ref_hint, backward_hint) < 0
+                    })
+                {
+                    backward = Some((i, ref_hint));
+                }
+            }
+
+            let second = match (forward, backward) {
+                (None, _) => None,
+                (Some(_), Some((backward_idx, _))) => Some(backward_idx),
+                (Some((_, forward_hint)), None) => {
+                    let mut second_forward: Option<(usize, u32)> = None;
+
+                    for i in 0..REFS_PER_FRAME {
+                        let ref_hint =
+                            self.ref_info[usize::from(hdr.ref_frame_idx[i])].ref_order_hint;
+
+                        if Self::get_relative_dist(seq, ref_hint, forward_hint) < 0
+                            && second_forward.map_or(true, |(_, second_hint)| {
+                                Self::get_relative_dist(seq, ref_hint, second_hint) > 0
+                            })
+                        {
+                            second_forward = Some((i, ref_hint));
+                        }
+                    }
+
+                    second_forward.map(|(i, _)| i)
+                }
+            };
+
+            if let (Some((forward_idx, _)), Some(second_idx)) = (forward, second) {
+                skip_mode_allowed = true;
+                hdr.skip_mode_frame[0] =
+                    ReferenceFrameType::Last as u8 + forward_idx.min(second_idx) as u8;
+                hdr.skip_mode_frame[1] =
+                    ReferenceFrameType::Last as u8 + forward_idx.max(second_idx) as u8;
+            }
+        }
+
+        hdr.skip_mode_present = skip_mode_allowed && r.read_bool()?;
+
+        Ok(())
+    }
+
+    /// Decodes a subexponential value, see decode_subexp() in 5.9.26.
+    fn decode_subexp(r: &mut BitReader, num_syms: i32) -> Result<i32> {
+        let mut i = 0;
+        let mut mk = 0;
+        let k = 3;
+
+        loop {
+            let b2 = if i != 0 { k + i - 1 } else { k };
+            let a = 1 << b2;
+
+            if num_syms <= mk + 3 * a {
+                let subexp_final_bits = Self::read_ns(r, (num_syms - mk) as u32)? as i32;
+                return Ok(subexp_final_bits + mk);
+            }
+
+            let subexp_more_bits = r.read_bool()?;
+            if subexp_more_bits {
+                i += 1;
+                mk += a;
+            } else {
+                let subexp_bits = r.read_u32(b2 as u8)? as i32;
+                return Ok(subexp_bits + mk);
+            }
+        }
+    }
+
+    fn inverse_recenter(r: i32, v: i32) -> i32 {
+        if v > 2 * r {
+            v
+        } else if v & 1 != 0 {
+            r - ((v + 1) >> 1)
+        } else {
+            r + (v >> 1)
+        }
+    }
+
+    fn decode_unsigned_subexp_with_ref(r: &mut BitReader, mx: i32, reference: i32) -> Result<i32> {
+        let v = Self::decode_subexp(r, mx)?;
+
+        if (reference << 1) <= mx {
+            Ok(Self::inverse_recenter(reference, v))
+        } else {
+            Ok(mx - 1 - Self::inverse_recenter(mx - 1 - reference, v))
+        }
+    }
+
+    fn decode_signed_subexp_with_ref(
+        r: &mut BitReader,
+        low: i32,
+        high: i32,
+        reference: i32,
+    ) -> Result<i32> {
+        let x = Self::decode_unsigned_subexp_with_ref(r, high - low, reference - low)?;
+        Ok(x + low)
+    }
+
+    fn read_global_param(
+        r: &mut BitReader,
+        hdr: &mut FrameHeader,
+        prev_gm_params: &GlobalMotionParams,
+        type_: WarpModelType,
+        ref_frame: usize,
+        idx: usize,
+    ) -> Result<()> {
+        let mut abs_bits = GM_ABS_ALPHA_BITS;
+        let mut prec_bits = GM_ALPHA_PREC_BITS;
+
+        if idx < 2 {
+            if matches!(type_, WarpModelType::Translation) {
+                let not_hp = u32::from(!hdr.allow_high_precision_mv);
+                abs_bits = GM_ABS_TRANS_ONLY_BITS - not_hp;
+                prec_bits = GM_TRANS_ONLY_PREC_BITS - not_hp;
+            } else {
+                abs_bits = GM_ABS_TRANS_BITS;
+                prec_bits = GM_TRANS_PREC_BITS;
+            }
+        }
+
+        let prec_diff = WARPEDMODEL_PREC_BITS - prec_bits;
+        let (round, sub) = if idx % 3 == 2 {
+            (1 << WARPEDMODEL_PREC_BITS, 1 << prec_bits)
+        } else {
+            (0, 0)
+        };
+
+        let mx = 1 << abs_bits;
+        let reference = (prev_gm_params.gm_params[ref_frame][idx] >> prec_diff) - sub;
+
+        hdr.global_motion_params.gm_params[ref_frame][idx] =
+            (Self::decode_signed_subexp_with_ref(r, -mx, mx + 1, reference)? << prec_diff) + round;
+
+        Ok(())
+    }
+
+    fn parse_global_motion_params(
+        r: &mut BitReader,
+        hdr: &mut FrameHeader,
+        prev_gm_params: &GlobalMotionParams,
+    ) -> Result<()> {
+        hdr.global_motion_params = Default::default();
+
+        if hdr.frame_is_intra {
+            return Ok(());
+        }
+
+        for ref_frame in ReferenceFrameType::Last as usize..=ReferenceFrameType::AltRef as usize {
+            let is_global = r.read_bool()?;
+
+            let type_ = if is_global {
+                let is_rot_zoom = r.read_bool()?;
+                if is_rot_zoom {
+                    WarpModelType::RotZoom
+                } else {
+                    let is_translation = r.read_bool()?;
+                    if is_translation {
+                        WarpModelType::Translation
+                    } else {
+                        WarpModelType::Affine
+                    }
+                }
+            } else {
+                WarpModelType::Identity
+            };
+
+            hdr.global_motion_params.gm_type[ref_frame] = type_;
+
+            if type_ >= WarpModelType::RotZoom {
+                Self::read_global_param(r, hdr, prev_gm_params, type_, ref_frame, 2)?;
+                Self::read_global_param(r, hdr, prev_gm_params, type_, ref_frame, 3)?;
+
+                if matches!(type_, WarpModelType::Affine) {
+                    Self::read_global_param(r, hdr, prev_gm_params, type_, ref_frame, 4)?;
+                    Self::read_global_param(r, hdr, prev_gm_params, type_, ref_frame, 5)?;
+                } else {
+                    let params = &mut hdr.global_motion_params.gm_params[ref_frame];
+                    params[4] = -params[3];
+                    params[5] = params[2];
+                }
+            }
+
+            if type_ >= WarpModelType::Translation {
+                Self::read_global_param(r, hdr, prev_gm_params, type_, ref_frame, 0)?;
+                Self::read_global_param(r, hdr, prev_gm_params, type_, ref_frame, 1)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn parse_film_grain_params(
+        &self,
+        r: &mut BitReader,
+        seq: &SequenceHeader,
+        hdr: &mut FrameHeader,
+    ) -> Result<()> {
+        hdr.film_grain_params = Default::default();
+
+        if !seq.film_grain_params_present || (!hdr.show_frame && !hdr.showable_frame) {
+            return Ok(());
+        }
+
+        let fg = &mut hdr.film_grain_params;
+        let cc = &seq.color_config;
+
+        fg.apply_grain = r.read_bool()?;
+        if !fg.apply_grain {
+            *fg = Default::default();
+            return Ok(());
+        }
+
+        fg.grain_seed = r.read_u16(16)?;
+        fg.update_grain = if matches!(hdr.frame_type, FrameType::InterFrame) {
+            r.read_bool()?
+        } else {
+            true
+        };
+
+        if !fg.update_grain {
+            let film_grain_params_ref_idx = r.read_u8(3)?;
+            let grain_seed = fg.grain_seed;
+
+            // load_grain_params()
+            *fg = self.ref_info[usize::from(film_grain_params_ref_idx)]
+                .saved_film_grain_params
+                .clone();
+            fg.grain_seed = grain_seed;
+            fg.film_grain_params_ref_idx = film_grain_params_ref_idx;
+            return Ok(());
+        }
+
+        fg.num_y_points = r.read_u8(4)?;
+        if usize::from(fg.num_y_points) > MAX_NUM_Y_POINTS {
+            return Err(anyhow!(
+                "Broken data: invalid num_y_points {}",
+                fg.num_y_points
+            ));
+        }
+        for i in 0..usize::from(fg.num_y_points) {
+            fg.point_y_value[i] = r.read_u8(8)?;
+            fg.point_y_scaling[i] = r.read_u8(8)?;
+        }
+
+        if !cc.mono_chrome {
+            fg.chroma_scaling_from_luma = r.read_bool()?;
+        }
+
+        if cc.mono_chrome
+            || fg.chroma_scaling_from_luma
+            || (cc.subsampling_x && cc.subsampling_y && fg.num_y_points == 0)
+        {
+            fg.num_cb_points = 0;
+            fg.num_cr_points = 0;
+        } else {
+            fg.num_cb_points = r.read_u8(4)?;
+            if usize::from(fg.num_cb_points) > MAX_NUM_CB_POINTS {
+                return Err(anyhow!(
+                    "Broken data: invalid num_cb_points {}",
+                    fg.num_cb_points
+                ));
+            }
+            for i in 0..usize::from(fg.num_cb_points) {
+                fg.point_cb_value[i] = r.read_u8(8)?;
+                fg.point_cb_scaling[i] = r.read_u8(8)?;
+            }
+
+            fg.num_cr_points = r.read_u8(4)?;
+            if usize::from(fg.num_cr_points) > MAX_NUM_CR_POINTS {
+                return Err(anyhow!(
+                    "Broken data: invalid num_cr_points {}",
+                    fg.num_cr_points
+                ));
+            }
+            for i in 0..usize::from(fg.num_cr_points) {
+                fg.point_cr_value[i] = r.read_u8(8)?;
+                fg.point_cr_scaling[i] = r.read_u8(8)?;
+            }
+        }
+
+        fg.grain_scaling_minus_8 = r.read_u8(2)?;
+        fg.ar_coeff_lag = r.read_u8(2)?;
+
+        let num_pos_luma = 2 * usize::from(fg.ar_coeff_lag) * (usize::from(fg.ar_coeff_lag) + 1);
+        let num_pos_chroma = if fg.num_y_points != 0 {
+            for i in 0..num_pos_luma {
+                fg.ar_coeffs_y_plus_128[i] = r.read_u8(8)?;
+            }
+            num_pos_luma + 1
+        } else {
+            num_pos_luma
+        };
+
+        if fg.chroma_scaling_from_luma || fg.num_cb_points != 0 {
+            for i in 0..num_pos_chroma {
+                fg.ar_coeffs_cb_plus_128[i] = r.read_u8(8)?;
+            }
+        }
+
+        if fg.chroma_scaling_from_luma || fg.num_cr_points != 0 {
+            for i in 0..num_pos_chroma {
+                fg.ar_coeffs_cr_plus_128[i] = r.read_u8(8)?;
+            }
+        }
+
+        fg.ar_coeff_shift_minus_6 = r.read_u8(2)?;
+        fg.grain_scale_shift = r.read_u8(2)?;
+
+        if fg.num_cb_points != 0 {
+            fg.cb_mult = r.read_u8(8)?;
+            fg.cb_luma_mult = r.read_u8(8)?;
+            fg.cb_offset = r.read_u16(9)?;
+        }
+
+        if fg.num_cr_points != 0 {
+            fg.cr_mult = r.read_u8(8)?;
+            fg.cr_luma_mult = r.read_u8(8)?;
+            fg.cr_offset = r.read_u16(9)?;
+        }
+
+        fg.overlap_flag = r.read_bool()?;
+        fg.clip_to_restricted_range = r.read_bool()?;
+
+        Ok(())
+    }
+
+    /// Saves the state of the current frame into the reference slots it refreshes, see 7.20.
+    fn update_reference_frames(&mut self, hdr: &FrameHeader) {
+        for (i, ref_info) in self.ref_info.iter_mut().enumerate() {
+            if hdr.refresh_frame_flags & (1 << i) == 0 {
+                continue;
+            }
+
+            *ref_info = ReferenceFrameInfo {
+                ref_valid: true,
+                ref_frame_id: hdr.current_frame_id,
+                ref_upscaled_width: hdr.upscaled_width,
+                ref_frame_width: hdr.frame_width,
+                ref_frame_height: hdr.frame_height,
+                ref_render_width: hdr.render_width,
+                ref_render_height: hdr.render_height,
+                ref_mi_cols: hdr.mi_cols,
+                ref_mi_rows: hdr.mi_rows,
+                ref_frame_type: hdr.frame_type,
+                ref_order_hint: hdr.order_hint,
+                saved_order_hints: hdr.order_hints,
+                saved_gm_params: hdr.global_motion_params.clone(),
+                saved_loop_filter_ref_deltas: hdr.loop_filter_params.loop_filter_ref_deltas,
+                saved_loop_filter_mode_deltas: hdr.loop_filter_params.loop_filter_mode_deltas,
+                saved_feature_enabled: hdr.segmentation_params.feature_enabled,
+                saved_feature_data: hdr.segmentation_params.feature_data,
+                saved_film_grain_params: hdr.film_grain_params.clone(),
+            };
+        }
+    }
+
+    /// Parses the frame header of a show_existing_frame, see 5.9.2.
+    fn parse_show_existing_frame(
+        &mut self,
+        r: &mut BitReader,
+        seq: &SequenceHeader,
+        mut hdr: FrameHeader,
+    ) -> Result<FrameHeader> {
+        hdr.frame_to_show_map_idx = r.read_u8(3)?;
+
+        if seq.decoder_model_info_present_flag && !seq.timing_info.equal_picture_interval {
+            Self::parse_temporal_point_info(r, seq, &mut hdr)?;
+        }
+
+        hdr.refresh_frame_flags = 0;
+
+        if seq.frame_id_numbers_present_flag {
+            let id_len =
+                seq.additional_frame_id_length_minus_1 + seq.delta_frame_id_length_minus_2 + 3;
+            hdr.display_frame_id = r.read_u32(id_len)?;
+        }
+
+        let ref_info = &self.ref_info[usize::from(hdr.frame_to_show_map_idx)];
+        if !ref_info.ref_valid {
+            return Err(anyhow!(
+                "Broken data: frame_to_show_map_idx {} refers to an invalid frame",
+                hdr.frame_to_show_map_idx
+            ));
+        }
+
+        hdr.frame_type = ref_info.ref_frame_type;
+        hdr.upscaled_width = ref_info.ref_upscaled_width;
+        hdr.frame_width = ref_info.ref_frame_width;
+        hdr.frame_height = ref_info.ref_frame_height;
+        hdr.render_width = ref_info.ref_render_width;
+        hdr.render_height = ref_info.ref_render_height;
+        hdr.mi_cols = ref_info.ref_mi_cols;
+        hdr.mi_rows = ref_info.ref_mi_rows;
+        hdr.show_frame = true;
+
+        if seq.film_grain_params_present {
+            hdr.film_grain_params = ref_info.saved_film_grain_params.clone();
+        }
+
+        if matches!(hdr.frame_type, FrameType::KeyFrame) {
+            // The frame loading process of 7.21, followed by the reference frame update process.
+            hdr.refresh_frame_flags = 0xff;
+            hdr.current_frame_id = ref_info.ref_frame_id;
+            hdr.order_hint = ref_info.ref_order_hint;
+            hdr.order_hints = ref_info.saved_order_hints;
+            hdr.global_motion_params = ref_info.saved_gm_params.clone();
+            hdr.loop_filter_params.loop_filter_ref_deltas = ref_info.saved_loop_filter_ref_deltas;
+            hdr.loop_filter_params.loop_filter_mode_deltas = ref_info.saved_loop_filter_mode_deltas;
+            hdr.segmentation_params.feature_enabled = ref_info.saved_feature_enabled;
+            hdr.segmentation_params.feature_data = ref_info.saved_feature_data;
+
+            self.update_reference_frames(&hdr);
+        }
+
+        Ok(hdr)
+    }
+
+    fn parse_uncompressed_header(&mut self, r: &mut BitReader) -> Result<FrameHeader> {
+        let seq = self
+            .sequence_header
+            .clone()
+            .context("Broken data: frame header without a sequence header")?;
+
+        let mut hdr = FrameHeader::default();
+        let id_len = if seq.frame_id_numbers_present_flag {
+            seq.additional_frame_id_length_minus_1 + seq.delta_frame_id_length_minus_2 + 3
+        } else {
+            0
+        };
+        let all_frames = (1u16 << NUM_REF_FRAMES) - 1;
+
+        if seq.reduced_still_picture_header {
+            hdr.show_existing_frame = false;
+            hdr.frame_type = FrameType::KeyFrame;
+            hdr.frame_is_intra = true;
+            hdr.show_frame = true;
+            hdr.showable_frame = false;
+        } else {
+            hdr.show_existing_frame = r.read_bool()?;
+            if hdr.show_existing_frame {
+                return self.parse_show_existing_frame(r, &seq, hdr);
+            }
+
+            let frame_type = r.read_u8(2)?;
+            hdr.frame_type = FrameType::n(frame_type)
+                .with_context(|| format!("Invalid frame type {}", frame_type))?;
+            hdr.frame_is_intra = matches!(
+                hdr.frame_type,
+                FrameType::IntraOnlyFrame | FrameType::KeyFrame
+            );
+
+            hdr.show_frame = r.read_bool()?;
+            if hdr.show_frame
+                && seq.decoder_model_info_present_flag
+                && !seq.timing_info.equal_picture_interval
+            {
+                Self::parse_temporal_point_info(r, &seq, &mut hdr)?;
+            }
+
+            hdr.showable_frame = if hdr.show_frame {
+                !matches!(hdr.frame_type, FrameType::KeyFrame)
+            } else {
+                r.read_bool()?
+            };
+
+            hdr.error_resilient_mode = if matches!(hdr.frame_type, FrameType::SwitchFrame)
+                || (matches!(hdr.frame_type, FrameType::KeyFrame) && hdr.show_frame)
+            {
+                true
+            } else {
+                r.read_bool()?
+            };
+        }
+
+        if matches!(hdr.frame_type, FrameType::KeyFrame) && hdr.show_frame {
+            for ref_info in &mut self.ref_info {
+                ref_info.ref_valid = false;
+                ref_info.ref_order_hint = 0;
+            }
+        }
+
+        hdr.disable_cdf_update = r.read_bool()?;
+
+        hdr.allow_screen_content_tools =
+            if seq.seq_force_screen_content_tools == SELECT_SCREEN_CONTENT_TOOLS {
+                r.read_bool()?
+            } else {
+                seq.seq_force_screen_content_tools != 0
+            };
+
+        if hdr.allow_screen_content_tools {
+            hdr.force_integer_mv = if seq.seq_force_integer_mv == SELECT_INTEGER_MV {
+                r.read_bool()?
+            } else {
+                seq.seq_force_integer_mv != 0
+            };
+        }
+
+        if hdr.frame_is_intra {
+            hdr.force_integer_mv = true;
+        }
+
+        if seq.frame_id_numbers_present_flag {
+            hdr.current_frame_id = r.read_u32(id_len)?;
+        }
+
+        hdr.frame_size_override_flag = if matches!(hdr.frame_type, FrameType::SwitchFrame) {
+            true
+        } else if seq.reduced_still_picture_header {
+            false
+        } else {
+            r.read_bool()?
+        };
+
+        hdr.order_hint = r.read_u32(seq.order_hint_bits)?;
+
+        hdr.primary_ref_frame = if hdr.frame_is_intra || hdr.error_resilient_mode {
+            PRIMARY_REF_NONE
+        } else {
+            r.read_u8(3)?
+        };
+
+        if seq.decoder_model_info_present_flag {
+            hdr.buffer_removal_time_present_flag = r.read_bool()?;
+
+            if hdr.buffer_removal_time_present_flag {
+                let n = seq.decoder_model_info.buffer_removal_time_length_minus_1 + 1;
+
+                for (op_num, op) in seq.operating_points
+                    [..=usize::from(seq.operating_points_cnt_minus_1)]
+                    .iter()
+                    .enumerate()
+                {
+                    if op.decoder_model_present_for_this_op {
+                        // The temporal and spatial IDs of the frame header OBU are not available
+                        // here, so all the operating points are assumed to include this frame.
+                        hdr.buffer_removal_time[op_num] = r.read_u32(n)?;
+                    }
+                }
+            }
+        }
+
+        hdr.allow_high_precision_mv = false;
+        hdr.use_ref_frame_mvs = false;
+        hdr.allow_intrabc = false;
+
+        hdr.refresh_frame_flags = if matches!(hdr.frame_type, FrameType::SwitchFrame)
+            || (matches!(hdr.frame_type, FrameType::KeyFrame) && hdr.show_frame)
+        {
+            all_frames as u8
+        } else {
+            r.read_u8(8)?
+        };
+
+        if (!hdr.frame_is_intra || u16::from(hdr.refresh_frame_flags) != all_frames)
+            && hdr.error_resilient_mode
+            && seq.enable_order_hint
+        {
+            for i in 0..NUM_REF_FRAMES {
+                hdr.ref_order_hint[i] = r.read_u32(seq.order_hint_bits)?;
+
+                if hdr.ref_order_hint[i] != self.ref_info[i].ref_order_hint {
+                    self.ref_info[i].ref_valid = false;
+                    self.ref_info[i].ref_order_hint = hdr.ref_order_hint[i];
+                }
+            }
+        }
+
+        if hdr.frame_is_intra {
+            Self::parse_frame_size(r, &seq, &mut hdr)?;
+            Self::parse_render_size(r, &mut hdr)?;
+
+            if hdr.allow_screen_content_tools && hdr.upscaled_width == hdr.frame_width {
+                hdr.allow_intrabc = r.read_bool()?;
+            }
+        } else {
+            if seq.enable_order_hint {
+                hdr.frame_refs_short_signaling = r.read_bool()?;
+                if hdr.frame_refs_short_signaling {
+                    hdr.last_frame_idx = r.read_u8(3)?;
+                    hdr.gold_frame_idx = r.read_u8(3)?;
+                    self.set_frame_refs(&seq, &mut hdr)?;
+                }
+            }
+
+            for i in 0..REFS_PER_FRAME {
+                if !hdr.frame_refs_short_signaling {
+                    hdr.ref_frame_idx[i] = r.read_u8(3)?;
+                }
+
+                if seq.frame_id_numbers_present_flag {
+                    // delta_frame_id_minus_1
+                    r.skip(u64::from(seq.delta_frame_id_length_minus_2) + 2)?;
+                }
+            }
+
+            if hdr.frame_size_override_flag && !hdr.error_resilient_mode {
+                self.parse_frame_size_with_refs(r, &seq, &mut hdr)?;
+            } else {
+                Self::parse_frame_size(r, &seq, &mut hdr)?;
+                Self::parse_render_size(r, &mut hdr)?;
+            }
+
+            hdr.allow_high_precision_mv = if hdr.force_integer_mv {
+                false
+            } else {
+                r.read_bool()?
+            };
+
+            Self::parse_interpolation_filter(r, &mut hdr)?;
+            hdr.is_motion_mode_switchable = r.read_bool()?;
+
+            hdr.use_ref_frame_mvs = if hdr.error_resilient_mode || !seq.enable_ref_frame_mvs {
+                false
+            } else {
+                r.read_bool()?
+            };
+
+            for i in 0..REFS_PER_FRAME {
+                let ref_frame = ReferenceFrameType::Last as usize + i;
+                let hint = self.ref_info[usize::from(hdr.ref_frame_idx[i])].ref_order_hint;
+
+                hdr.order_hints[ref_frame] = hint;
+                hdr.ref_frame_sign_bias[ref_frame] =
+                    Self::get_relative_dist(&seq, hint, hdr.order_hint) > 0;
+            }
+        }
+
+        hdr.disable_frame_end_update_cdf =
+            if seq.reduced_still_picture_header || hdr.disable_cdf_update {
+                true
+            } else {
+                r.read_bool()?
+            };
+
+        // setup_past_independence() or load_previous().
+        let prev_gm_params = if hdr.primary_ref_frame == PRIMARY_REF_NONE {
+            hdr.loop_filter_params = Default::default();
+            hdr.segmentation_params.feature_enabled = Default::default();
+            hdr.segmentation_params.feature_data = Default::default();
+            GlobalMotionParams::default()
+        } else {
+            let prev_frame = usize::from(hdr.ref_frame_idx[usize::from(hdr.primary_ref_frame)]);
+            let ref_info = &self.ref_info[prev_frame];
+
+            hdr.loop_filter_params.loop_filter_ref_deltas = ref_info.saved_loop_filter_ref_deltas;
+            hdr.loop_filter_params.loop_filter_mode_deltas = ref_info.saved_loop_filter_mode_deltas;
+            hdr.segmentation_params.feature_enabled = ref_info.saved_feature_enabled;
+            hdr.segmentation_params.feature_data = ref_info.saved_feature_data;
+            ref_info.saved_gm_params.clone()
+        };
+
+        Self::parse_tile_info(r, &seq, &mut hdr)?;
+        Self::parse_quantization_params(r, &seq, &mut hdr)?;
+        Self::parse_segmentation_params(r, &mut hdr)?;
+        Self::parse_delta_params(r, &mut hdr)?;
+        Self::compute_lossless(&mut hdr);
+        Self::parse_loop_filter_params(r, &seq, &mut hdr)?;
+        Self::parse_cdef_params(r, &seq, &mut hdr)?;
+        Self::parse_lr_params(r, &seq, &mut hdr)?;
+
+        hdr.tx_mode = if hdr.coded_lossless {
+            TxMode::Only4x4
+        } else if r.read_bool()? {
+            TxMode::Select
+        } else {
+            TxMode::Largest
+        };
+
+        hdr.reference_select = if hdr.frame_is_intra {
+            false
+        } else {
+            r.read_bool()?
+        };
+
+        self.parse_skip_mode_params(r, &seq, &mut hdr)?;
+
+        hdr.allow_warped_motion =
+            if hdr.frame_is_intra || hdr.error_resilient_mode || !seq.enable_warped_motion {
+                false
+            } else {
+                r.read_bool()?
+            };
+
+        hdr.reduced_tx_set = r.read_bool()?;
+        Self::parse_global_motion_params(r, &mut hdr, &prev_gm_params)?;
+        self.parse_film_grain_params(r, &seq, &mut hdr)?;
+
+        Ok(hdr)
+    }
+
+    /// Parses a frame header OBU, or the frame header part of a frame OBU, see 5.9 and 5.10.
+    /// Returns `None` for redundant copies of the frame header of the current frame.
+    pub fn parse_frame_header_obu(&mut self, obu: &Obu) -> Result<Option<FrameHeader>> {
+        if !matches!(
+            obu.header.obu_type,
+            ObuType::FrameHeader | ObuType::Frame | ObuType::RedundantFrameHeader
+        ) {
+            return Err(anyhow!("Not a frame header OBU"));
+        }
+
+        if self.seen_frame_header {
+            if matches!(obu.header.obu_type, ObuType::Frame) {
+                return Err(anyhow!(
+                    "Broken data: frame OBU while a frame is being decoded"
+                ));
+            }
+
+            // frame_header_copy()
+            return Ok(None);
+        }
+
+        if matches!(obu.header.obu_type, ObuType::RedundantFrameHeader) {
+            return Err(anyhow!(
+                "Broken data: redundant frame header without a frame header"
+            ));
+        }
+
+        let mut r = BitReader::new(obu.data);
+        let mut hdr = self.parse_uncompressed_header(&mut r)?;
+
+        if matches!(obu.header.obu_type, ObuType::Frame) {
+            Self::byte_alignment(&mut r)?;
+        }
+
+        hdr.header_bytes = (r.position() as usize + 7) / 8;
+
+        if hdr.show_existing_frame {
+            self.seen_frame_header = false;
+            self.last_frame_header = None;
+        } else {
+            self.seen_frame_header = true;
+            self.tile_num = 0;
+            self.update_reference_frames(&hdr);
+            self.last_frame_header = Some(hdr.clone());
+        }
+
+        Ok(Some(hdr))
+    }
+
+    /// Parses a tile group OBU payload, see 5.11.1. For frame OBUs, `data` must start right
+    /// after the frame header.
+    pub fn parse_tile_group<'a>(&mut self, data: &'a [u8]) -> Result<TileGroup<'a>> {
+        let hdr = self
+            .last_frame_header
+            .as_ref()
+            .context("Broken data: tile group without a frame header")?;
+        let ti = &hdr.tile_info;
+
+        let mut r = BitReader::new(data);
+        let num_tiles = ti.tile_cols * ti.tile_rows;
+
+        let mut tile_start_and_end_present_flag = false;
+        if num_tiles > 1 {
+            tile_start_and_end_present_flag = r.read_bool()?;
+        }
+
+        let (tg_start, tg_end) = if num_tiles == 1 || !tile_start_and_end_present_flag {
+            (0, num_tiles - 1)
+        } else {
+            let tile_bits = ti.tile_cols_log2 + ti.tile_rows_log2;
+            (r.read_u32(tile_bits)?, r.read_u32(tile_bits)?)
+        };
+
+        if tg_start != self.tile_num || tg_end < tg_start || tg_end >= num_tiles {
+            return Err(anyhow!(
+                "Broken data: invalid tile group range {}..={}",
+                tg_start,
+                tg_end
+            ));
+        }
+
+        Self::byte_alignment(&mut r)?;
+
+        let mut offset = r.position() as usize / 8;
+        let mut tiles = vec![];
+
+        for tile_num in tg_start..=tg_end {
+            let tile_size = if tile_num == tg_end {
+                data.len()
+                    .checked_sub(offset)
+                    .context("Broken data: truncated tile group")?
+            } else {
+                let tile_size_bytes = usize::from(ti.tile_size_bytes);
+                let tile_size = Self::read_le(&data[offset..], tile_size_bytes)? as usize + 1;
+                offset += tile_size_bytes;
+                tile_size
+            };
+
+            if offset + tile_size > data.len() {
+                return Err(anyhow!("Broken data: truncated tile {}", tile_num));
+            }
+
+            tiles.push(Tile {
+                tile_offset: offset as u32,
+                tile_size: tile_size as u32,
+                tile_row: tile_num / ti.tile_cols,
+                tile_col: tile_num % ti.tile_cols,
+            });
+
+            offset += tile_size;
+        }
+
+        self.tile_num = tg_end + 1;
+        if self.tile_num == num_tiles {
+            // The frame is complete, the next frame header won't be a copy.
+            self.seen_frame_header = false;
+        }
+
+        Ok(TileGroup {
+            data,
+            tile_start_and_end_present_flag,
+            tg_start,
+            tg_end,
+            tiles,
+        })
+    }
+
+    /// Resets the state that is scoped to a temporal unit, to be called when a temporal
+    /// delimiter OBU is seen.
+    pub fn temporal_delimiter(&mut self) {
+        self.seen_frame_header = false;
+    }
+
+    /// Returns the active sequence header, if any.
+    pub fn sequence_header(&self) -> Option<&SequenceHeader> {
+        self.sequence_header.as_ref()
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use crate::decoders::av1::parser::FrameType;
+    use crate::decoders::av1::parser::InterpolationFilter;
+    use crate::decoders::av1::parser::ObuType;
+    use crate::decoders::av1::parser::Parser;
+    use crate::decoders::av1::parser::TxMode;
+    use crate::decoders::av1::parser::PRIMARY_REF_NONE;
+    use crate::decoders::av1::parser::REFS_PER_FRAME;
+    use crate::utils::bitwriter::BitWriter;
+
+    /// The dummy data used as the payload of every tile.
+    const TILE_DATA: [u8; 4] = [0xab; 4];
+
+    /// Wraps `payload` into an OBU with a size field.
+    pub fn obu(obu_type: ObuType, payload: &[u8]) -> Vec<u8> {
+        let mut data = vec![(obu_type as u8) << 3 | 0b010];
+
+        let mut size = payload.len();
+        loop {
+            let byte = (size & 0x7f) as u8;
+            size >>= 7;
+
+            if size == 0 {
+                data.push(byte);
+                break;
+            }
+
+            data.push(byte | 0x80);
+        }
+
+        data.extend_from_slice(payload);
+        data
+    }
+
+    pub fn temporal_delimiter() -> Vec<u8> {
+        obu(ObuType::TemporalDelimiter, &[])
+    }
+
+    /// A 64x64 8-bit 4:2:0 sequence header with 7-bit order hints.
+    pub fn sequence_header() -> Vec<u8> {
+        let mut w = BitWriter::default();
+
+        // seq_profile, still_picture, reduced_still_picture_header
+        w.write_bits(0, 3);
+        w.write_bit(false);
+        w.write_bit(false);
+        // timing_info_present_flag, initial_display_delay_present_flag
+        w.write_bit(false);
+        w.write_bit(false);
+        // operating_points_cnt_minus_1, operating_point_idc[0], seq_level_idx[0], seq_tier[0]
+        w.write_bits(0, 5);
+        w.write_bits(0, 12);
+        w.write_bits(8, 5);
+        w.write_bit(false);
+        // frame_width_bits_minus_1, frame_height_bits_minus_1
+        w.write_bits(6, 4);
+        w.write_bits(6, 4);
+        // max_frame_width_minus_1, max_frame_height_minus_1
+        w.write_bits(63, 7);
+        w.write_bits(63, 7);
+        // frame_id_numbers_present_flag
+        w.write_bit(false);
+        // use_128x128_superblock, enable_filter_intra, enable_intra_edge_filter
+        w.write_bits(0, 3);
+        // enable_interintra_compound, enable_masked_compound, enable_warped_motion,
+        // enable_dual_filter
+        w.write_bits(0, 4);
+        // enable_order_hint, enable_jnt_comp, enable_ref_frame_mvs
+        w.write_bit(true);
+        w.write_bit(false);
+        w.write_bit(false);
+        // seq_choose_screen_content_tools, seq_force_screen_content_tools
+        w.write_bit(false);
+        w.write_bit(false);
+        // order_hint_bits_minus_1
+        w.write_bits(6, 3);
+        // enable_superres, enable_cdef, enable_restoration
+        w.write_bits(0, 3);
+        // high_bitdepth, mono_chrome, color_description_present_flag, color_range
+        w.write_bits(0, 4);
+        // chroma_sample_position, separate_uv_delta_q
+        w.write_bits(0, 2);
+        w.write_bit(false);
+        // film_grain_params_present
+        w.write_bit(false);
+        w.write_trailing_bits();
+
+        obu(ObuType::SequenceHeader, &w.into_bytes())
+    }
+
+    /// A frame using the sequence header above. If `obu_type` is `ObuType::Frame`, the single
+    /// tile of the frame is included in the OBU.
+    pub fn frame(
+        obu_type: ObuType,
+        frame_type: FrameType,
+        show_frame: bool,
+        order_hint: u8,
+        refresh_frame_flags: u8,
+        ref_frame_idx: [u8; REFS_PER_FRAME],
+    ) -> Vec<u8> {
+        let mut w = BitWriter::default();
+        let key_frame = matches!(frame_type, FrameType::KeyFrame);
+        let intra = key_frame || matches!(frame_type, FrameType::IntraOnlyFrame);
+
+        // show_existing_frame
+        w.write_bit(false);
+        w.write_bits(frame_type as u64, 2);
+        w.write_bit(show_frame);
+        if !show_frame {
+            // showable_frame
+            w.write_bit(true);
+        }
+        if !(key_frame && show_frame) {
+            // error_resilient_mode
+            w.write_bit(false);
+        }
+        // disable_cdf_update, frame_size_override_flag
+        w.write_bit(false);
+        w.write_bit(false);
+        w.write_bits(u64::from(order_hint), 7);
+        if !intra {
+            w.write_bits(u64::from(PRIMARY_REF_NONE), 3);
+        }
+        if !(key_frame && show_frame) {
+            w.write_bits(u64::from(refresh_frame_flags), 8);
+        }
+
+        if intra {
+            // render_and_frame_size_different
+            w.write_bit(false);
+        } else {
+            // frame_refs_short_signaling
+            w.write_bit(false);
+            for idx in ref_frame_idx {
+                w.write_bits(u64::from(idx), 3);
+            }
+            // render_and_frame_size_different, allow_high_precision_mv
+            w.write_bit(false);
+            w.write_bit(false);
+            // is_filter_switchable, is_motion_mode_switchable
+            w.write_bit(true);
+            w.write_bit(false);
+        }
+
+        // disable_frame_end_update_cdf, uniform_tile_spacing_flag
+        w.write_bit(true);
+        w.write_bit(true);
+        // base_q_idx, DeltaQYDc, DeltaQUDc, DeltaQUAc, using_qmatrix
+        w.write_bits(100, 8);
+        w.write_bits(0, 4);
+        // segmentation_enabled, delta_q_present
+        w.write_bit(false);
+        w.write_bit(false);
+        // loop_filter_level[0..2], loop_filter_sharpness, loop_filter_delta_enabled
+        w.write_bits(0, 12);
+        w.write_bits(0, 3);
+        w.write_bit(false);
+        // tx_mode_select
+        w.write_bit(true);
+        if !intra {
+            // reference_select
+            w.write_bit(false);
+        }
+        // reduced_tx_set
+        w.write_bit(false);
+        if !intra {
+            // is_global
+            w.write_bits(0, REFS_PER_FRAME);
+        }
+
+        if matches!(obu_type, ObuType::Frame) {
+            w.align_with_zeros();
+            let mut data = w.into_bytes();
+            data.extend_from_slice(&TILE_DATA);
+            obu(obu_type, &data)
+        } else {
+            w.write_trailing_bits();
+            obu(obu_type, &w.into_bytes())
+        }
+    }
+
+    /// A tile group OBU with the single tile of a frame.
+    pub fn tile_group() -> Vec<u8> {
+        obu(ObuType::TileGroup, &TILE_DATA)
+    }
+
+    pub fn show_existing_frame(frame_to_show_map_idx: u8) -> Vec<u8> {
+        let mut w = BitWriter::default();
+
+        w.write_bit(true);
+        w.write_bits(u64::from(frame_to_show_map_idx), 3);
+        w.write_trailing_bits();
+
+        obu(ObuType::FrameHeader, &w.into_bytes())
+    }
+
+    #[test]
+    fn parse_synthetic_stream() {
+        let mut stream = temporal_delimiter();
+        stream.extend(sequence_header());
+        stream.extend(frame(
+            ObuType::Frame,
+            FrameType::KeyFrame,
+            true,
+            0,
+            0xff,
+            [0; REFS_PER_FRAME],
+        ));
+        // A hidden frame in slot 1, followed by a frame referencing it.
+        stream.extend(frame(
+            ObuType::Frame,
+            FrameType::InterFrame,
+            false,
+            4,
+            0x02,
+            [0; REFS_PER_FRAME],
+        ));
+        stream.extend(frame(
+            ObuType::FrameHeader,
+            FrameType::InterFrame,
+            true,
+            1,
+            0x04,
+            [0, 0, 0, 0, 1, 1, 1],
+        ));
+        stream.extend(tile_group());
+        stream.extend(show_existing_frame(1));
+
+        let mut parser = Parser::default();
+        let mut data = stream.as_slice();
+        let mut headers = vec![];
+        let mut num_tile_groups = 0;
+
+        while !data.is_empty() {
+            let obu = parser.parse_obu(data).unwrap();
+            assert!(!parser.should_drop(&obu.header));
+
+            match obu.header.obu_type() {
+                ObuType::TemporalDelimiter => parser.temporal_delimiter(),
+                ObuType::SequenceHeader => {
+                    let seq = parser.parse_sequence_header_obu(&obu).unwrap();
+                    assert_eq!(seq.max_frame_width(), 64);
+                    assert_eq!(seq.max_frame_height(), 64);
+                    assert_eq!(seq.bit_depth(), 8);
+                    assert_eq!(seq.order_hint_bits(), 7);
+                    assert!(seq.color_config().subsampling_x());
+                    assert!(seq.color_config().subsampling_y());
+                }
+                ObuType::Frame | ObuType::FrameHeader => {
+                    let hdr = parser.parse_frame_header_obu(&obu).unwrap().unwrap();
+
+                    if matches!(obu.header.obu_type(), ObuType::Frame) {
+                        let tg = parser.parse_tile_group(&obu.data[hdr.header_bytes()..]);
+                        let tg = tg.unwrap();
+                        assert_eq!(tg.tiles.len(), 1);
+                        assert_eq!(tg.tiles[0].tile_size, 4);
+                        assert!(tg.is_last(hdr.tile_info()));
+                        num_tile_groups += 1;
+                    }
+
+                    headers.push(hdr);
+                }
+                ObuType::TileGroup => {
+                    let tg = parser.parse_tile_group(obu.data).unwrap();
+                    assert_eq!(tg.tiles[0].tile_offset, 0);
+                    assert_eq!(tg.tiles[0].tile_size, 4);
+                    num_tile_groups += 1;
+                }
+                other => panic!("Unexpected OBU type {:?}", other),
+            }
+
+            data = &data[obu.bytes_used..];
+        }
+
+        assert_eq!(headers.len(), 4);
+        assert_eq!(num_tile_groups, 3);
+
+        let key = &headers[0];
+        assert!(matches!(key.frame_type(), FrameType::KeyFrame));
+        assert!(key.show_frame());
+        assert_eq!(key.refresh_frame_flags(), 0xff);
+        assert_eq!(key.frame_width(), 64);
+        assert_eq!(key.frame_height(), 64);
+        assert_eq!(key.upscaled_width(), 64);
+        assert_eq!(key.mi_cols(), 16);
+        assert_eq!(key.mi_rows(), 16);
+        assert_eq!(key.tile_info().tile_cols(), 1);
+        assert_eq!(key.tile_info().tile_rows(), 1);
+        assert_eq!(key.quantization_params().base_q_idx(), 100);
+        assert!(matches!(key.tx_mode(), TxMode::Select));
+        assert!(!key.coded_lossless());
+
+        let hidden = &headers[1];
+        assert!(matches!(hidden.frame_type(), FrameType::InterFrame));
+        assert!(!hidden.show_frame());
+        assert!(hidden.showable_frame());
+        assert_eq!(hidden.order_hint(), 4);
+        assert_eq!(hidden.refresh_frame_flags(), 0x02);
+        assert!(matches!(
+            hidden.interpolation_filter(),
+            InterpolationFilter::Switchable
+        ));
+
+        // The last four references point to the hidden frame, which comes later in display
+        // order.
+        let inter = &headers[2];
+        assert_eq!(inter.order_hint(), 1);
+        assert_eq!(inter.ref_frame_idx(), &[0, 0, 0, 0, 1, 1, 1]);
+        assert_eq!(inter.order_hints()[1..], [0, 0, 0, 0, 4, 4, 4]);
+        assert_eq!(
+            inter.ref_frame_sign_bias()[1..],
+            [false, false, false, false, true, true, true]
+        );
+
+        let existing = &headers[3];
+        assert!(existing.show_existing_frame());
+        assert_eq!(existing.frame_to_show_map_idx(), 1);
+        assert!(matches!(existing.frame_type(), FrameType::InterFrame));
+        assert_eq!(existing.frame_width(), 64);
+    }
+}
@@ -0,0 +1,9 @@
+// Copyright 2023 The ChromiumOS Authors
+// Use of this source code is governed by a BSD-style license that can be
+// found in the LICENSE file.
+
+pub mod backends;
+pub mod decoder;
+pub mod dpb;
+pub mod parser;
+pub mod picture;
@@ -0,0 +1,79 @@
+// Copyright 2023 The ChromiumOS Authors
+// Use of this source code is governed by a BSD-style license that can be
+// found in the LICENSE file.
+
+use crate::decoders::h265::decoder::RefPicSet;
+use crate::decoders::h265::dpb::Dpb;
+use crate::decoders::h265::dpb::DpbEntry;
+use crate::decoders::h265::parser::Pps;
+use crate::decoders::h265::parser::Slice;
+use crate::decoders::h265::parser::Sps;
+use crate::decoders::h265::picture::PictureData;
+use crate::decoders::BlockingMode;
+use crate::decoders::VideoDecoderBackend;
+
+pub type Result<T> = crate::decoders::StatelessBackendResult<T>;
+
+#[cfg(test)]
+pub mod dummy;
+#[cfg(feature = "vaapi")]
+pub mod vaapi;
+
+/// Trait for stateless decoder backends. The decoder will call into the backend
+/// to request decode operations. The backend can operate in blocking mode,
+/// where it will wait until the current decode finishes, or in non-blocking
+/// mode, where it should return immediately with any previously decoded frames
+/// that happen to be ready.
+pub(crate) trait StatelessDecoderBackend: VideoDecoderBackend {
+    /// Called when a new SPS is parsed.
+    fn new_sequence(&mut self, sps: &Sps) -> Result<()>;
+
+    /// Called when the decoder determines that a frame was found.
+    fn new_picture(&mut self, picture: &PictureData, timestamp: u64) -> Result<()>;
+
+    /// Called by the decoder for every frame found, once its reference picture set has been
+    /// derived.
+    #[allow(clippy::too_many_arguments)]
+    fn handle_picture(
+        &mut self,
+        picture: &PictureData,
+        timestamp: u64,
+        sps: &Sps,
+        pps: &Pps,
+        dpb: &Dpb<Self::Handle>,
+        rps: &RefPicSet<Self::Handle>,
+        slice: &Slice<&[u8]>,
+    ) -> Result<()>;
+
+    /// Called to dispatch a decode operation to the backend. The reference
+    /// picture lists contain `None` for the entries whose picture is missing
+    /// from the DPB.
+    fn decode_slice(
+        &mut self,
+        slice: &Slice<&[u8]>,
+        sps: &Sps,
+        pps: &Pps,
+        dpb: &Dpb<Self::Handle>,
+        ref_pic_list0: &[Option<DpbEntry<Self::Handle>>],
+        ref_pic_list1: &[Option<DpbEntry<Self::Handle>>],
+    ) -> Result<()>;
+
+    /// Called when the decoder wants the backend to finish the decoding
+    /// operations for `picture`. At this point, `decode_slice` has been called
+    /// for all slices. The argument `block` dictates whether this call should
+    /// wait until the current decode finishes, or whether it should return
+    /// immediately.
+    ///
+    /// This call will assign the ownership of the BackendHandle to the Picture
+    /// and then assign the ownership of the Picture to the Handle.
+    fn submit_picture(
+        &mut self,
+        picture: &PictureData,
+        block: BlockingMode,
+    ) -> Result<Self::Handle>;
+
+    /// Get the test parameters for the backend. The caller is reponsible for
+    /// downcasting them to the correct type, which is backend-dependent.
+    #[cfg(test)]
+    fn get_test_params(&self) -> &dyn std::any::Any;
+}
@@ -0,0 +1,80 @@
+// Copyright 2023 The ChromiumOS Authors
+// Use of this source code is governed by a BSD-style license that can be
+// found in the LICENSE file.
+
+//! This file contains a dummy backend whose only purpose is to let the decoder
+//! run so we can test it in isolation.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::decoders::h265::backends::Result as StatelessBackendResult;
+use crate::decoders::h265::backends::StatelessDecoderBackend;
+use crate::decoders::h265::decoder::Decoder;
+use crate::decoders::h265::decoder::RefPicSet;
+use crate::decoders::h265::dpb::Dpb;
+use crate::decoders::h265::dpb::DpbEntry;
+use crate::decoders::h265::parser::Pps;
+use crate::decoders::h265::parser::Slice;
+use crate::decoders::h265::parser::Sps;
+use crate::decoders::h265::picture::PictureData;
+use crate::decoders::BlockingMode;
+use crate::utils::dummy::*;
+
+impl StatelessDecoderBackend for Backend {
+    fn new_sequence(&mut self, _: &Sps) -> StatelessBackendResult<()> {
+        Ok(())
+    }
+
+    fn new_picture(&mut self, _: &PictureData, _: u64) -> StatelessBackendResult<()> {
+        Ok(())
+    }
+
+    fn handle_picture(
+        &mut self,
+        _: &PictureData,
+        _: u64,
+        _: &Sps,
+        _: &Pps,
+        _: &Dpb<Self::Handle>,
+        _: &RefPicSet<Self::Handle>,
+        _: &Slice<&[u8]>,
+    ) -> StatelessBackendResult<()> {
+        Ok(())
+    }
+
+    fn decode_slice(
+        &mut self,
+        _: &Slice<&[u8]>,
+        _: &Sps,
+        _: &Pps,
+        _: &Dpb<Self::Handle>,
+        _: &[Option<DpbEntry<Self::Handle>>],
+        _: &[Option<DpbEntry<Self::Handle>>],
+    ) -> StatelessBackendResult<()> {
+        Ok(())
+    }
+
+    fn submit_picture(
+        &mut self,
+        _: &PictureData,
+        _: BlockingMode,
+    ) -> StatelessBackendResult<Self::Handle> {
+        Ok(Handle {
+            handle: Rc::new(RefCell::new(BackendHandle)),
+        })
+    }
+
+    #[cfg(test)]
+    fn get_test_params(&self) -> &dyn std::any::Any {
+        // There are no test parameters for the dummy backend.
+        unimplemented!()
+    }
+}
+
+impl Decoder<Handle> {
+    // Creates a new instance of the decoder using the dummy backend.
+    pub fn new_dummy(blocking_mode: BlockingMode) -> anyhow::Result<Self> {
+        Self::new(Box::new(Backend::new()), blocking_mode)
+    }
+}
@@ -0,0 +1,887 @@
+// Copyright 2023 The ChromiumOS Authors
+// Use of this source code is governed by a BSD-style license that can be
+// found in the LICENSE file.
+
+use std::collections::VecDeque;
+use std::rc::Rc;
+
+use anyhow::anyhow;
+use anyhow::Result;
+use libva::BufferType;
+use libva::Display;
+use libva::HEVCLongSliceFlags;
+use libva::HEVCPicFields;
+use libva::HEVCSliceParsingFields;
+use libva::IQMatrix;
+use libva::IQMatrixBufferHEVC;
+use libva::Picture as VaPicture;
+use libva::PictureHEVC;
+use libva::PictureNew;
+use libva::PictureParameter;
+use libva::PictureParameterBufferHEVC;
+use libva::SliceParameter;
+use libva::SliceParameterBufferHEVC;
+use log::debug;
+
+use crate::decoders::h265::backends::Result as StatelessBackendResult;
+use crate::decoders::h265::backends::StatelessDecoderBackend;
+use crate::decoders::h265::decoder::Decoder;
+use crate::decoders::h265::decoder::RefPicSet;
+use crate::decoders::h265::dpb::Dpb;
+use crate::decoders::h265::dpb::DpbEntry;
+use crate::decoders::h265::parser::Pps;
+use crate::decoders::h265::parser::Slice;
+use crate::decoders::h265::parser::Sps;
+use crate::decoders::h265::picture::PictureData;
+use crate::decoders::h265::picture::Reference;
+use crate::decoders::BlockingMode;
+use crate::decoders::DecodedHandle;
+use crate::decoders::Result as DecoderResult;
+use crate::decoders::StatelessBackendError;
+use crate::decoders::VideoDecoderBackend;
+use crate::utils::vaapi::DecodedHandle as VADecodedHandle;
+use crate::utils::vaapi::NegotiationStatus;
+use crate::utils::vaapi::StreamInfo;
+use crate::utils::vaapi::VaapiBackend;
+use crate::DecodedFormat;
+use crate::Resolution;
+
+/// The number of entries of the `ReferenceFrames` array of `VAPictureParameterBufferHEVC`.
+const NUM_VA_REFERENCE_FRAMES: usize = 15;
+
+#[cfg(test)]
+#[derive(Default)]
+struct TestParams {
+    pic_param: Option<BufferType>,
+    iq_matrix: Option<BufferType>,
+    slice_param: Option<BufferType>,
+    slice_data: Option<BufferType>,
+}
+
+#[cfg(test)]
+impl TestParams {
+    fn save_pic_params(&mut self, pic_param: BufferType, iq_matrix: Option<BufferType>) {
+        self.pic_param = Some(pic_param);
+        self.iq_matrix = iq_matrix;
+    }
+
+    fn save_slice_params(&mut self, slice_param: BufferType) {
+        self.slice_param = Some(slice_param);
+    }
+
+    fn save_slice_data(&mut self, slice_data: BufferType) {
+        self.slice_data = Some(slice_data);
+    }
+}
+
+impl StreamInfo for &Sps {
+    fn va_profile(&self) -> anyhow::Result<i32> {
+        let profile_idc = self.profile_tier_level().general_profile_idc();
+
+        // Main Still Picture streams are decodable as Main.
+        match profile_idc {
+            1 | 3 => Ok(libva::VAProfile::VAProfileHEVCMain),
+            2 => Ok(libva::VAProfile::VAProfileHEVCMain10),
+            _ => Err(anyhow!("Unsupported general_profile_idc {}", profile_idc)),
+        }
+    }
+
+    fn rt_format(&self) -> anyhow::Result<u32> {
+        let bit_depth_luma = self.bit_depth_luma();
+        let chroma_format_idc = self.chroma_format_idc();
+
+        match bit_depth_luma {
+            8 => match chroma_format_idc {
+                0 | 1 => Ok(libva::constants::VA_RT_FORMAT_YUV420),
+                _ => Err(anyhow!(
+                    "Unsupported chroma_format_idc: {}",
+                    chroma_format_idc
+                )),
+            },
+            _ => Err(anyhow!("Unsupported bit depth: {}", bit_depth_luma)),
+        }
+    }
+
+    fn min_num_surfaces(&self) -> usize {
+        self.max_dpb_size() + 4
+    }
+
+    fn coded_size(&self) -> (u32, u32) {
+        (self.width(), self.height())
+    }
+
+    fn visible_rect(&self) -> ((u32, u32), (u32, u32)) {
+        let (x, y, width, height) = self.visible_rectangle();
+
+        ((x, y), (x + width, y + height))
+    }
+}
+
+/// A slice parameter buffer and its data, kept until the next slice tells whether it is the last
+/// one of the picture.
+struct PendingSlice {
+    slice_param: SliceParameterBufferHEVC,
+    slice_data: Vec<u8>,
+}
+
+/// H.265 stateless decoder backend for VA-API.
+struct Backend {
+    backend: VaapiBackend<Sps>,
+
+    /// The current picture being worked on.
+    current_picture: Option<VaPicture<PictureNew>>,
+
+    /// The surfaces of the `ReferenceFrames` of the current picture, in order, which the
+    /// reference picture lists of its slices index into.
+    reference_surfaces: Vec<libva::VASurfaceID>,
+
+    /// The last slice of the current picture seen so far. VA-API needs the last slice of a
+    /// picture to be flagged, which is only known once the next slice or the end of the picture
+    /// is reached.
+    pending_slice: Option<PendingSlice>,
+
+    #[cfg(test)]
+    /// Test params. Saves the metadata sent to VA-API for the purposes of
+    /// testing.
+    test_params: TestParams,
+}
+
+impl Backend {
+    /// Creates a new codec backend for H.265.
+    fn new(display: Rc<libva::Display>) -> Result<Self> {
+        Ok(Self {
+            backend: VaapiBackend::new(display),
+            current_picture: Default::default(),
+            reference_surfaces: Default::default(),
+            pending_slice: Default::default(),
+
+            #[cfg(test)]
+            test_params: Default::default(),
+        })
+    }
+
+    /// Gets the VASurfaceID for the given `handle`.
+    fn surface_id(handle: &VADecodedHandle) -> libva::VASurfaceID {
+        handle.handle().surface_id()
+    }
+
+    /// Builds an invalid VAPictureHEVC. These pictures are used to fill empty
+    /// array slots there is no data to fill them with.
+    fn build_invalid_va_hevc_pic() -> PictureHEVC {
+        PictureHEVC::new(
+            libva::constants::VA_INVALID_ID,
+            0,
+            libva::constants::VA_PICTURE_HEVC_INVALID,
+        )
+    }
+
+    /// Returns the VA-API flags of the reference `entry`, given the reference picture set of the
+    /// current picture.
+    fn reference_flags(entry: &DpbEntry<VADecodedHandle>, rps: &RefPicSet<VADecodedHandle>) -> u32 {
+        let contains = |set: &[Option<DpbEntry<VADecodedHandle>>]| {
+            set.iter()
+                .flatten()
+                .any(|other| Rc::ptr_eq(&other.0, &entry.0))
+        };
+
+        let mut flags = 0;
+
+        if matches!(entry.0.borrow().reference(), Reference::LongTerm) {
+            flags |= libva::constants::VA_PICTURE_HEVC_LONG_TERM_REFERENCE;
+        }
+
+        if contains(&rps.st_curr_before) {
+            flags |= libva::constants::VA_PICTURE_HEVC_RPS_ST_CURR_BEFORE;
+        } else if contains(&rps.st_curr_after) {
+            flags |= libva::constants::VA_PICTURE_HEVC_RPS_ST_CURR_AFTER;
+        } else if contains(&rps.lt_curr) {
+            flags |= libva::constants::VA_PICTURE_HEVC_RPS_LT_CURR;
+        }
+
+        flags
+    }
+
+    /// Returns the (x, y) positions of the up-right diagonal scan of a square block of
+    /// `blk_size`, see 6.5.3.
+    fn up_right_diagonal_scan(blk_size: usize) -> Vec<(usize, usize)> {
+        let mut scan = Vec::with_capacity(blk_size * blk_size);
+        let mut x = 0;
+        let mut y = 0;
+
+        while scan.len() < blk_size * blk_size {
+            loop {
+                if x < blk_size && y < blk_size {
+                    scan.push((x, y));
+                }
+
+                if y == 0 {
+                    break;
+                }
+
+                y -= 1;
+                x += 1;
+            }
+
+            y = x + 1;
+            x = 0;
+        }
+
+        scan
+    }
+
+    /// Converts a scaling list from the up-right diagonal order it is coded in to the raster order
+    /// VA-API expects.
+    fn get_raster_from_up_right_diagonal(src: &[u8], dst: &mut [u8], blk_size: usize) {
+        for (i, (x, y)) in Backend::up_right_diagonal_scan(blk_size)
+            .into_iter()
+            .enumerate()
+        {
+            dst[y * blk_size + x] = src[i];
+        }
+    }
+
+    fn build_iq_matrix(pps: &Pps) -> BufferType {
+        let scaling_lists = pps.scaling_lists();
+
+        let mut scaling_list4x4 = [[0; 16]; 6];
+        let mut scaling_list8x8 = [[0; 64]; 6];
+        let mut scaling_list16x16 = [[0; 64]; 6];
+        let mut scaling_list32x32 = [[0; 64]; 2];
+        let mut scaling_list_dc16x16 = [0; 6];
+        let mut scaling_list_dc32x32 = [0; 2];
+
+        for i in 0..6 {
+            Backend::get_raster_from_up_right_diagonal(
+                &scaling_lists.scaling_list_4x4()[i],
+                &mut scaling_list4x4[i],
+                4,
+            );
+            Backend::get_raster_from_up_right_diagonal(
+                &scaling_lists.scaling_list_8x8()[i],
+                &mut scaling_list8x8[i],
+                8,
+            );
+            Backend::get_raster_from_up_right_diagonal(
+                &scaling_lists.scaling_list_16x16()[i],
+                &mut scaling_list16x16[i],
+                8,
+            );
+        }
+
+        scaling_list_dc16x16.copy_from_slice(&scaling_lists.scaling_list_dc_coef_16x16());
+
+        // Only the luma intra and inter 32x32 lists (matrixId 0 and 3) are used for 4:2:0.
+        for i in 0..2 {
+            Backend::get_raster_from_up_right_diagonal(
+                &scaling_lists.scaling_list_32x32()[i * 3],
+                &mut scaling_list32x32[i],
+                8,
+            );
+            scaling_list_dc32x32[i] = scaling_lists.scaling_list_dc_coef_32x32()[i * 3];
+        }
+
+        BufferType::IQMatrix(IQMatrix::HEVC(IQMatrixBufferHEVC::new(
+            scaling_list4x4,
+            scaling_list8x8,
+            scaling_list16x16,
+            scaling_list32x32,
+            scaling_list_dc16x16,
+            scaling_list_dc32x32,
+        )))
+    }
+
+    /// Builds the picture parameters of `current_picture`, along with the surfaces of its
+    /// `ReferenceFrames`.
+    fn build_pic_param(
+        slice: &Slice<impl AsRef<[u8]>>,
+        current_picture: &PictureData,
+        current_surface_id: libva::VASurfaceID,
+        dpb: &Dpb<VADecodedHandle>,
+        rps: &RefPicSet<VADecodedHandle>,
+        sps: &Sps,
+        pps: &Pps,
+    ) -> Result<(BufferType, Vec<libva::VASurfaceID>)> {
+        let curr_pic = PictureHEVC::new(current_surface_id, current_picture.pic_order_cnt_val, 0);
+
+        let mut va_refs = vec![];
+        let mut reference_surfaces = vec![];
+
+        for entry in dpb.entries() {
+            if !entry.0.borrow().is_ref() {
+                continue;
+            }
+
+            if va_refs.len() == NUM_VA_REFERENCE_FRAMES {
+                return Err(anyhow!("Too many reference pictures in the DPB"));
+            }
+
+            let surface_id = Backend::surface_id(&entry.1);
+            let flags = Backend::reference_flags(entry, rps);
+
+            va_refs.push(PictureHEVC::new(
+                surface_id,
+                entry.0.borrow().pic_order_cnt_val,
+                flags,
+            ));
+            reference_surfaces.push(surface_id);
+        }
+
+        for _ in va_refs.len()..NUM_VA_REFERENCE_FRAMES {
+            va_refs.push(Backend::build_invalid_va_hevc_pic());
+        }
+
+        let va_refs = match va_refs.try_into() {
+            Ok(va_refs) => va_refs,
+            Err(_) => {
+                panic!("Bug: wrong number of references, expected 15");
+            }
+        };
+
+        let pic_fields = HEVCPicFields::new(
+            u32::from(sps.chroma_format_idc()),
+            sps.separate_colour_plane_flag() as u32,
+            sps.pcm_enabled_flag() as u32,
+            sps.scaling_list_enabled_flag() as u32,
+            pps.transform_skip_enabled_flag() as u32,
+            sps.amp_enabled_flag() as u32,
+            sps.strong_intra_smoothing_enabled_flag() as u32,
+            pps.sign_data_hiding_enabled_flag() as u32,
+            pps.constrained_intra_pred_flag() as u32,
+            pps.cu_qp_delta_enabled_flag() as u32,
+            pps.weighted_pred_flag() as u32,
+            pps.weighted_bipred_flag() as u32,
+            pps.transquant_bypass_enabled_flag() as u32,
+            pps.tiles_enabled_flag() as u32,
+            pps.entropy_coding_sync_enabled_flag() as u32,
+            pps.loop_filter_across_slices_enabled_flag() as u32,
+            pps.loop_filter_across_tiles_enabled_flag() as u32,
+            sps.pcm_loop_filter_disabled_flag() as u32,
+            (sps.max_num_reorder_pics() == 0) as u32,
+            0, /* NoBiPredFlag is not derived */
+        );
+
+        let nalu_type = current_picture.nalu_type;
+        let slice_parsing_fields = HEVCSliceParsingFields::new(
+            pps.lists_modification_present_flag() as u32,
+            sps.long_term_ref_pics_present_flag() as u32,
+            sps.temporal_mvp_enabled_flag() as u32,
+            pps.cabac_init_present_flag() as u32,
+            pps.output_flag_present_flag() as u32,
+            pps.dependent_slice_segments_enabled_flag() as u32,
+            pps.slice_chroma_qp_offsets_present_flag() as u32,
+            sps.sample_adaptive_offset_enabled_flag() as u32,
+            pps.deblocking_filter_override_enabled_flag() as u32,
+            pps.deblocking_filter_disabled_flag() as u32,
+            pps.slice_segment_header_extension_present_flag() as u32,
+            nalu_type.is_irap() as u32,
+            nalu_type.is_idr() as u32,
+            nalu_type.is_irap() as u32,
+        );
+
+        let mut column_width_minus1 = [0; 19];
+        column_width_minus1.copy_from_slice(&pps.column_width_minus1()[..19]);
+        let mut row_height_minus1 = [0; 21];
+        row_height_minus1.copy_from_slice(&pps.row_height_minus1()[..21]);
+
+        let sps_max_dec_pic_buffering_minus1 = u8::try_from(sps.max_dpb_size() - 1)?;
+
+        let pic_param = PictureParameterBufferHEVC::new(
+            curr_pic,
+            va_refs,
+            sps.pic_width_in_luma_samples(),
+            sps.pic_height_in_luma_samples(),
+            &pic_fields,
+            sps_max_dec_pic_buffering_minus1,
+            sps.bit_depth_luma_minus8(),
+            sps.bit_depth_chroma_minus8(),
+            sps.pcm_sample_bit_depth_luma_minus1(),
+            sps.pcm_sample_bit_depth_chroma_minus1(),
+            sps.log2_min_luma_coding_block_size_minus3(),
+            sps.log2_diff_max_min_luma_coding_block_size(),
+            sps.log2_min_luma_transform_block_size_minus2(),
+            sps.log2_diff_max_min_luma_transform_block_size(),
+            sps.log2_min_pcm_luma_coding_block_size_minus3(),
+            sps.log2_diff_max_min_pcm_luma_coding_block_size(),
+            sps.max_transform_hierarchy_depth_intra(),
+            sps.max_transform_hierarchy_depth_inter(),
+            pps.init_qp_minus26(),
+            pps.diff_cu_qp_delta_depth(),
+            pps.cb_qp_offset(),
+            pps.cr_qp_offset(),
+            pps.log2_parallel_merge_level_minus2(),
+            pps.num_tile_columns_minus1(),
+            pps.num_tile_rows_minus1(),
+            column_width_minus1,
+            row_height_minus1,
+            &slice_parsing_fields,
+            sps.log2_max_pic_order_cnt_lsb_minus4(),
+            u8::try_from(sps.short_term_ref_pic_sets().len())?,
+            sps.num_long_term_ref_pics_sps(),
+            pps.num_ref_idx_l0_default_active_minus1(),
+            pps.num_ref_idx_l1_default_active_minus1(),
+            pps.beta_offset_div2(),
+            pps.tc_offset_div2(),
+            pps.num_extra_slice_header_bits(),
+            slice.header().st_rps_bits(),
+        );
+
+        Ok((
+            BufferType::PictureParameter(PictureParameter::HEVC(pic_param)),
+            reference_surfaces,
+        ))
+    }
+
+    /// Fills a `RefPicList` entry of the slice parameters, i.e. the indices of the pictures of
+    /// `ref_pic_list` in the `ReferenceFrames` of the picture parameters.
+    fn fill_ref_pic_list(
+        ref_pic_list: &[Option<DpbEntry<VADecodedHandle>>],
+        reference_surfaces: &[libva::VASurfaceID],
+    ) -> [u8; 15] {
+        let mut va_list = [0xff; 15];
+
+        for (va_entry, entry) in va_list.iter_mut().zip(ref_pic_list) {
+            let index = entry.as_ref().and_then(|entry| {
+                let surface_id = Backend::surface_id(&entry.1);
+                reference_surfaces.iter().position(|&id| id == surface_id)
+            });
+
+            if let Some(index) = index {
+                *va_entry = index as u8;
+            }
+        }
+
+        va_list
+    }
+
+    fn build_slice_param(
+        slice: &Slice<impl AsRef<[u8]>>,
+        ref_pic_list0: &[Option<DpbEntry<VADecodedHandle>>],
+        ref_pic_list1: &[Option<DpbEntry<VADecodedHandle>>],
+        reference_surfaces: &[libva::VASurfaceID],
+        sps: &Sps,
+        pps: &Pps,
+    ) -> Result<SliceParameterBufferHEVC> {
+        let hdr = slice.header();
+        let nalu = slice.nalu();
+        let pwt = hdr.pred_weight_table();
+
+        let ref_pic_list = [
+            Backend::fill_ref_pic_list(ref_pic_list0, reference_surfaces),
+            Backend::fill_ref_pic_list(ref_pic_list1, reference_surfaces),
+        ];
+
+        let long_slice_flags = HEVCLongSliceFlags::new(
+            0, /* set on the last slice once known */
+            hdr.dependent_slice_segment_flag() as u32,
+            hdr.slice_type() as u32,
+            u32::from(hdr.colour_plane_id()),
+            hdr.sao_luma_flag() as u32,
+            hdr.sao_chroma_flag() as u32,
+            hdr.mvd_l1_zero_flag() as u32,
+            hdr.cabac_init_flag() as u32,
+            hdr.temporal_mvp_enabled_flag() as u32,
+            hdr.deblocking_filter_disabled_flag() as u32,
+            hdr.collocated_from_l0_flag() as u32,
+            hdr.loop_filter_across_slices_enabled_flag() as u32,
+        );
+
+        let collocated_ref_idx = if hdr.temporal_mvp_enabled_flag() {
+            hdr.collocated_ref_idx()
+        } else {
+            0xff
+        };
+
+        let mut delta_luma_weight_l0 = [0i8; 15];
+        let mut luma_offset_l0 = [0i8; 15];
+        let mut delta_chroma_weight_l0 = [[0i8; 2]; 15];
+        let mut chroma_offset_l0 = [[0i8; 2]; 15];
+        let mut delta_luma_weight_l1 = [0i8; 15];
+        let mut luma_offset_l1 = [0i8; 15];
+        let mut delta_chroma_weight_l1 = [[0i8; 2]; 15];
+        let mut chroma_offset_l1 = [[0i8; 2]; 15];
+
+        let slice_type = hdr.slice_type();
+        let fill_l0 = (pps.weighted_pred_flag() && slice_type.is_p())
+            || (pps.weighted_bipred_flag() && slice_type.is_b());
+        let fill_l1 = pps.weighted_bipred_flag() && slice_type.is_b();
+
+        // ChromaOffsetLX as derived in 7.4.7.3, which VA-API expects instead of
+        // delta_chroma_offset_lX. High precision offsets are not supported.
+        let chroma_log2_weight_denom = i32::from(pwt.luma_log2_weight_denom())
+            + i32::from(pwt.delta_chroma_log2_weight_denom());
+        let wp_offset_half_range_c = 1 << (sps.bit_depth_chroma() - 1);
+        let chroma_offset = |delta_chroma_weight: i8, delta_chroma_offset: i16| {
+            let chroma_weight = (1 << chroma_log2_weight_denom) + i32::from(delta_chroma_weight);
+            let offset = wp_offset_half_range_c
+                - ((wp_offset_half_range_c * chroma_weight) >> chroma_log2_weight_denom)
+                + i32::from(delta_chroma_offset);
+
+            offset.clamp(-wp_offset_half_range_c, wp_offset_half_range_c - 1) as i8
+        };
+
+        if fill_l0 {
+            for i in 0..=usize::from(hdr.num_ref_idx_l0_active_minus1()) {
+                delta_luma_weight_l0[i] = pwt.delta_luma_weight_l0()[i];
+                luma_offset_l0[i] = pwt.luma_offset_l0()[i] as i8;
+
+                if sps.chroma_array_type() != 0 {
+                    for j in 0..2 {
+                        delta_chroma_weight_l0[i][j] = pwt.delta_chroma_weight_l0()[i][j];
+                        chroma_offset_l0[i][j] = chroma_offset(
+                            pwt.delta_chroma_weight_l0()[i][j],
+                            pwt.delta_chroma_offset_l0()[i][j],
+                        );
+                    }
+                }
+            }
+        }
+
+        if fill_l1 {
+            for i in 0..=usize::from(hdr.num_ref_idx_l1_active_minus1()) {
+                delta_luma_weight_l1[i] = pwt.delta_luma_weight_l1()[i];
+                luma_offset_l1[i] = pwt.luma_offset_l1()[i] as i8;
+
+                if sps.chroma_array_type() != 0 {
+                    for j in 0..2 {
+                        delta_chroma_weight_l1[i][j] = pwt.delta_chroma_weight_l1()[i][j];
+                        chroma_offset_l1[i][j] = chroma_offset(
+                            pwt.delta_chroma_weight_l1()[i][j],
+                            pwt.delta_chroma_offset_l1()[i][j],
+                        );
+                    }
+                }
+            }
+        }
+
+        // The header size does not account for the emulation prevention bytes, but the offset is
+        // in the raw slice data.
+        let slice_data_byte_offset = hdr.header_bit_size() / 8 + hdr.n_emulation_prevention_bytes();
+
+        Ok(SliceParameterBufferHEVC::new(
+            nalu.size() as u32,
+            0,
+            libva::constants::VA_SLICE_DATA_FLAG_ALL,
+            u32::try_from(slice_data_byte_offset)?,
+            hdr.segment_address(),
+            ref_pic_list,
+            &long_slice_flags,
+            collocated_ref_idx,
+            hdr.num_ref_idx_l0_active_minus1(),
+            hdr.num_ref_idx_l1_active_minus1(),
+            hdr.qp_delta(),
+            hdr.cb_qp_offset(),
+            hdr.cr_qp_offset(),
+            hdr.beta_offset_div2(),
+            hdr.tc_offset_div2(),
+            pwt.luma_log2_weight_denom(),
+            pwt.delta_chroma_log2_weight_denom(),
+            delta_luma_weight_l0,
+            luma_offset_l0,
+            delta_chroma_weight_l0,
+            chroma_offset_l0,
+            delta_luma_weight_l1,
+            luma_offset_l1,
+            delta_chroma_weight_l1,
+            chroma_offset_l1,
+            hdr.five_minus_max_num_merge_cand(),
+            u16::try_from(hdr.num_entry_point_offsets())?,
+            0,
+            u16::try_from(hdr.n_emulation_prevention_bytes())?,
+        ))
+    }
+
+    /// Adds the buffers of `pending_slice` to the current picture.
+    fn submit_slice(&mut self, pending_slice: PendingSlice) -> StatelessBackendResult<()> {
+        let metadata = self.backend.metadata_state.get_parsed()?;
+        let context = &metadata.context;
+
+        let slice_param = context.create_buffer(BufferType::SliceParameter(
+            SliceParameter::HEVC(pending_slice.slice_param),
+        ))?;
+        let slice_data = context.create_buffer(BufferType::SliceData(pending_slice.slice_data))?;
+
+        let cur_va_pic = self.current_picture.as_mut().unwrap();
+        cur_va_pic.add_buffer(slice_param);
+        cur_va_pic.add_buffer(slice_data);
+
+        Ok(())
+    }
+}
+
+impl VideoDecoderBackend for Backend {
+    type Handle = VADecodedHandle;
+
+    fn num_resources_total(&self) -> usize {
+        self.backend.num_resources_total()
+    }
+
+    fn num_resources_left(&self) -> usize {
+        self.backend.num_resources_left()
+    }
+
+    fn format(&self) -> Option<DecodedFormat> {
+        self.backend.format()
+    }
+
+    fn try_format(&mut self, format: DecodedFormat) -> DecoderResult<()> {
+        self.backend.try_format(format)
+    }
+
+    fn coded_resolution(&self) -> Option<Resolution> {
+        self.backend.coded_resolution()
+    }
+
+    fn display_resolution(&self) -> Option<Resolution> {
+        self.backend.display_resolution()
+    }
+
+    fn poll(&mut self, blocking_mode: BlockingMode) -> DecoderResult<VecDeque<Self::Handle>> {
+        self.backend.poll(blocking_mode)
+    }
+
+    fn handle_is_ready(&self, handle: &Self::Handle) -> bool {
+        self.backend.handle_is_ready(handle)
+    }
+
+    fn block_on_handle(&mut self, handle: &Self::Handle) -> StatelessBackendResult<()> {
+        self.backend.block_on_handle(handle)
+    }
+}
+
+impl StatelessDecoderBackend for Backend {
+    fn new_sequence(&mut self, sps: &Sps) -> StatelessBackendResult<()> {
+        self.backend.metadata_state.open(sps, None)?;
+        self.backend.negotiation_status = NegotiationStatus::Possible(Box::new(sps.clone()));
+
+        Ok(())
+    }
+
+    fn new_picture(&mut self, _: &PictureData, timestamp: u64) -> StatelessBackendResult<()> {
+        let metadata = self.backend.metadata_state.get_parsed_mut()?;
+
+        let surface = metadata
+            .surface_pool
+            .get_surface()
+            .ok_or(StatelessBackendError::OutOfResources)?;
+
+        let va_pic = VaPicture::new(timestamp, Rc::clone(&metadata.context), surface);
+
+        self.current_picture = Some(va_pic);
+        self.pending_slice = None;
+
+        Ok(())
+    }
+
+    fn handle_picture(
+        &mut self,
+        picture: &PictureData,
+        timestamp: u64,
+        sps: &Sps,
+        pps: &Pps,
+        dpb: &Dpb<Self::Handle>,
+        rps: &RefPicSet<Self::Handle>,
+        slice: &Slice<&[u8]>,
+    ) -> StatelessBackendResult<()> {
+        debug!(
+            "Va-API backend: handle_picture for timestamp {:?}",
+            timestamp
+        );
+
+        self.backend.negotiation_status = NegotiationStatus::Negotiated;
+
+        let metadata = self.backend.metadata_state.get_parsed()?;
+        let context = &metadata.context;
+
+        let va_pic = self.current_picture.as_mut().unwrap();
+        let surface_id = va_pic.surface().id();
+
+        let (pic_param, reference_surfaces) =
+            Backend::build_pic_param(slice, picture, surface_id, dpb, rps, sps, pps)?;
+        let pic_param = context.create_buffer(pic_param)?;
+        va_pic.add_buffer(pic_param);
+
+        if sps.scaling_list_enabled_flag() {
+            let iq_matrix = context.create_buffer(Backend::build_iq_matrix(pps))?;
+            va_pic.add_buffer(iq_matrix);
+        }
+
+        #[cfg(test)]
+        self.test_params.save_pic_params(
+            Backend::build_pic_param(slice, picture, surface_id, dpb, rps, sps, pps)?.0,
+            sps.scaling_list_enabled_flag()
+                .then(|| Backend::build_iq_matrix(pps)),
+        );
+
+        self.reference_surfaces = reference_surfaces;
+
+        Ok(())
+    }
+
+    fn decode_slice(
+        &mut self,
+        slice: &Slice<&[u8]>,
+        sps: &Sps,
+        pps: &Pps,
+        _: &Dpb<Self::Handle>,
+        ref_pic_list0: &[Option<DpbEntry<Self::Handle>>],
+        ref_pic_list1: &[Option<DpbEntry<Self::Handle>>],
+    ) -> StatelessBackendResult<()> {
+        if let Some(pending_slice) = self.pending_slice.take() {
+            self.submit_slice(pending_slice)?;
+        }
+
+        let slice_param = Backend::build_slice_param(
+            slice,
+            ref_pic_list0,
+            ref_pic_list1,
+            &self.reference_surfaces,
+            sps,
+            pps,
+        )?;
+
+        #[cfg(test)]
+        {
+            self.test_params
+                .save_slice_params(BufferType::SliceParameter(SliceParameter::HEVC(
+                    Backend::build_slice_param(
+                        slice,
+                        ref_pic_list0,
+                        ref_pic_list1,
+                        &self.reference_surfaces,
+                        sps,
+                        pps,
+                    )?,
+                )));
+            self.test_params
+                .save_slice_data(BufferType::SliceData(Vec::from(slice.nalu().as_ref())));
+        }
+
+        self.pending_slice = Some(PendingSlice {
+            slice_param,
+            slice_data: Vec::from(slice.nalu().as_ref()),
+        });
+
+        Ok(())
+    }
+
+    fn submit_picture(
+        &mut self,
+        _: &PictureData,
+        block: BlockingMode,
+    ) -> StatelessBackendResult<Self::Handle> {
+        if let Some(mut pending_slice) = self.pending_slice.take() {
+            pending_slice.slice_param.set_as_last();
+            self.submit_slice(pending_slice)?;
+        }
+
+        let current_picture = self.current_picture.take().unwrap();
+
+        self.backend.process_picture(current_picture, block)
+    }
+
+    #[cfg(test)]
+    fn get_test_params(&self) -> &dyn std::any::Any {
+        &self.test_params
+    }
+}
+
+impl Decoder<VADecodedHandle> {
+    // Creates a new instance of the decoder using the VAAPI backend.
+    pub fn new_vaapi(display: Rc<Display>, blocking_mode: BlockingMode) -> Result<Self> {
+        Self::new(Box::new(Backend::new(display)?), blocking_mode)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use libva::Display;
+
+    use crate::decoders::h265::backends::vaapi::Backend;
+    use crate::decoders::h265::backends::vaapi::TestParams;
+    use crate::decoders::h265::backends::StatelessDecoderBackend;
+    use crate::decoders::h265::decoder::tests::process_ready_frames;
+    use crate::decoders::h265::decoder::tests::run_decoding_loop;
+    use crate::decoders::h265::decoder::Decoder;
+    use crate::decoders::h265::parser::tests::pps;
+    use crate::decoders::h265::parser::tests::slice;
+    use crate::decoders::h265::parser::tests::sps;
+    use crate::decoders::h265::parser::tests::vps;
+    use crate::decoders::h265::parser::NaluType;
+    use crate::decoders::h265::parser::SliceType;
+    use crate::decoders::BlockingMode;
+    use crate::decoders::DecodedHandle;
+    use crate::decoders::DynHandle;
+    use crate::utils::vaapi::DecodedHandle as VADecodedHandle;
+
+    fn get_test_params(
+        backend: &dyn StatelessDecoderBackend<Handle = VADecodedHandle>,
+    ) -> &TestParams {
+        backend
+            .get_test_params()
+            .downcast_ref::<TestParams>()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_up_right_diagonal_scan() {
+        // The 4x4 up-right diagonal scan of 6.5.3, in raster order.
+        let diagonal = (0..16).collect::<Vec<u8>>();
+        let mut raster = [0u8; 16];
+
+        Backend::get_raster_from_up_right_diagonal(&diagonal, &mut raster, 4);
+
+        assert_eq!(
+            raster,
+            [0, 2, 5, 9, 1, 4, 8, 12, 3, 7, 11, 14, 6, 10, 13, 15]
+        );
+    }
+
+    #[test]
+    // Ignore this test by default as it requires libva-compatible hardware.
+    #[ignore]
+    fn test_64x64_i_and_p() {
+        // This test is the same as h265::decoder::tests::test_64x64_i_and_p, but with an actual
+        // backend to test whether the backend specific logic works.
+        let mut first_access_unit = vps();
+        first_access_unit.extend(sps());
+        first_access_unit.extend(pps());
+        first_access_unit.extend(slice(NaluType::IdrWRadl, SliceType::I, 0, &[], &[]));
+
+        let mut access_units = vec![first_access_unit];
+        for poc in 1..4 {
+            access_units.push(slice(
+                NaluType::TrailR,
+                SliceType::P,
+                poc,
+                &[(-1, true)],
+                &[],
+            ));
+        }
+
+        let blocking_modes = [BlockingMode::Blocking, BlockingMode::NonBlocking];
+
+        for blocking_mode in blocking_modes {
+            let mut frame_num = 0;
+            let display = Display::open().unwrap();
+            let mut decoder = Decoder::new_vaapi(display, blocking_mode).unwrap();
+
+            run_decoding_loop(&mut decoder, &access_units, |decoder| {
+                process_ready_frames(decoder, &mut |decoder, handle| {
+                    let params = get_test_params(decoder.backend());
+                    assert!(params.pic_param.is_some());
+                    assert!(params.slice_param.is_some());
+                    assert!(params.slice_data.is_some());
+
+                    let mut picture = handle.handle_mut();
+                    let mut backend_handle = picture.dyn_mappable_handle_mut();
+                    let mut nv12 = vec![0; backend_handle.image_size()];
+                    backend_handle.read(&mut nv12).unwrap();
+
+                    frame_num += 1;
+                });
+            });
+
+            assert_eq!(frame_num, 4);
+        }
+    }
+}
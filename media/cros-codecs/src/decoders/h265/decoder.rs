@@ -0,0 +1,1086 @@
+// Copyright 2023 The ChromiumOS Authors
+// Use of this source code is governed by a BSD-style license that can be
+// found in the LICENSE file.
+
+use std::cell::RefCell;
+use std::io::Cursor;
+use std::rc::Rc;
+
+use anyhow::Context;
+use anyhow::Result;
+use log::debug;
+
+use crate::decoders::h265::backends::StatelessDecoderBackend;
+use crate::decoders::h265::dpb::Dpb;
+use crate::decoders::h265::dpb::DpbEntry;
+use crate::decoders::h265::parser::Nalu;
+use crate::decoders::h265::parser::NaluType;
+use crate::decoders::h265::parser::Parser;
+use crate::decoders::h265::parser::Slice;
+use crate::decoders::h265::parser::Sps;
+use crate::decoders::h265::picture::PictureData;
+use crate::decoders::h265::picture::Reference;
+use crate::decoders::BlockingMode;
+use crate::decoders::DecodedHandle;
+use crate::decoders::DynDecodedHandle;
+use crate::decoders::Error as VideoDecoderError;
+use crate::decoders::Result as VideoDecoderResult;
+use crate::decoders::StatelessBackendError;
+use crate::decoders::VideoDecoder;
+use crate::Resolution;
+
+#[cfg(test)]
+struct Params<T> {
+    ready_pics: Vec<T>,
+}
+
+#[cfg(test)]
+impl<T> Params<T> {
+    fn save_ready_pics(&mut self, ready_pics: Vec<T>) {
+        self.ready_pics.extend(ready_pics);
+    }
+}
+
+#[cfg(test)]
+impl<T> Default for Params<T> {
+    fn default() -> Self {
+        Self {
+            ready_pics: Default::default(),
+        }
+    }
+}
+
+/// Represents where we are in the negotiation status. We assume ownership of
+/// the incoming buffers in this special case so that clients do not have to do
+/// the bookkeeping themselves.
+enum NegotiationStatus {
+    /// Still waiting for a SPS. Any incoming buffers are being queued in order.
+    NonNegotiated { queued_buffers: Vec<(u64, Vec<u8>)> },
+    /// Saw an SPS. Negotiation is possible until the next call to decode()
+    Possible { queued_buffers: Vec<(u64, Vec<u8>)> },
+    /// Processing the queued buffers.
+    DrainingQueuedBuffers,
+    /// Negotiated. Locks in the format until a new SPS is seen.
+    Negotiated,
+}
+
+impl Default for NegotiationStatus {
+    fn default() -> Self {
+        Self::NonNegotiated {
+            queued_buffers: Default::default(),
+        }
+    }
+}
+
+/// The reference picture set of the current picture, as derived in 8.3.2. Entries are `None` when
+/// the corresponding picture is missing from the DPB.
+pub struct RefPicSet<T: DecodedHandle> {
+    /// RefPicSetStCurrBefore in the specification.
+    pub st_curr_before: Vec<Option<DpbEntry<T>>>,
+    /// RefPicSetStCurrAfter in the specification.
+    pub st_curr_after: Vec<Option<DpbEntry<T>>>,
+    /// RefPicSetStFoll in the specification.
+    pub st_foll: Vec<Option<DpbEntry<T>>>,
+    /// RefPicSetLtCurr in the specification.
+    pub lt_curr: Vec<Option<DpbEntry<T>>>,
+    /// RefPicSetLtFoll in the specification.
+    pub lt_foll: Vec<Option<DpbEntry<T>>>,
+}
+
+impl<T: DecodedHandle> RefPicSet<T> {
+    fn clear(&mut self) {
+        self.st_curr_before.clear();
+        self.st_curr_after.clear();
+        self.st_foll.clear();
+        self.lt_curr.clear();
+        self.lt_foll.clear();
+    }
+}
+
+impl<T: DecodedHandle> Default for RefPicSet<T> {
+    fn default() -> Self {
+        // See https://github.com/rust-lang/rust/issues/26925 on why this can't
+        // be derived.
+        Self {
+            st_curr_before: Default::default(),
+            st_curr_after: Default::default(),
+            st_foll: Default::default(),
+            lt_curr: Default::default(),
+            lt_foll: Default::default(),
+        }
+    }
+}
+
+pub struct Decoder<T>
+where
+    T: DecodedHandle + DynDecodedHandle,
+{
+    /// A parser to extract bitstream metadata
+    parser: Parser,
+
+    /// Whether the decoder should block on decode operations.
+    blocking_mode: BlockingMode,
+
+    /// The backend used for hardware acceleration.
+    backend: Box<dyn StatelessDecoderBackend<Handle = T>>,
+
+    /// Keeps track of whether the decoded format has been negotiated with the
+    /// backend.
+    negotiation_status: NegotiationStatus,
+
+    /// The current coded resolution
+    coded_resolution: Resolution,
+
+    /// A queue with the handles of pictures that are ready to be sent to the
+    /// DecoderSession, in output order.
+    ready_queue: Vec<T>,
+
+    /// A monotonically increasing counter used to tag pictures in display
+    /// order
+    current_display_order: u64,
+
+    /// The decoded picture buffer
+    dpb: Dpb<T>,
+
+    /// The current active SPS id.
+    cur_sps_id: u8,
+    /// The current active PPS id.
+    cur_pps_id: u8,
+
+    /// Whether the next picture is the first one of the bitstream or follows an end of sequence
+    /// NAL unit, in which case it starts a new coded video sequence.
+    first_picture_in_cvs: bool,
+    /// The NoRaslOutputFlag of the last IRAP picture, which determines whether its associated RASL
+    /// pictures are decoded.
+    irap_no_rasl_output_flag: bool,
+    /// PicOrderCntVal of prevTid0Pic, the previous picture with TemporalId equal to 0 that is not
+    /// a RASL, RADL or SLNR picture.
+    prev_tid0_pic_poc: i32,
+
+    /// The current picture being worked on.
+    cur_pic: Option<PictureData>,
+    /// Whether the slices of the current picture are being skipped, as happens for RASL pictures
+    /// associated with an IRAP picture starting a coded video sequence.
+    skipping_cur_pic: bool,
+
+    /// The reference picture set of the current picture.
+    rps: RefPicSet<T>,
+
+    /// Equivalent to RefPicList0 in the specification. Computed for every
+    /// slice, points to the pictures in the DPB.
+    ref_pic_list0: Vec<Option<DpbEntry<T>>>,
+    /// Equivalent to RefPicList1 in the specification. Computed for every
+    /// slice, points to the pictures in the DPB.
+    ref_pic_list1: Vec<Option<DpbEntry<T>>>,
+
+    #[cfg(test)]
+    params: Params<T>,
+}
+
+impl<T> Decoder<T>
+where
+    T: DecodedHandle + DynDecodedHandle + 'static,
+{
+    // Creates a new instance of the decoder.
+    #[cfg(any(feature = "vaapi", test))]
+    pub(crate) fn new(
+        backend: Box<dyn StatelessDecoderBackend<Handle = T>>,
+        blocking_mode: BlockingMode,
+    ) -> Result<Self> {
+        Ok(Self {
+            backend,
+            blocking_mode,
+            parser: Default::default(),
+            coded_resolution: Default::default(),
+            negotiation_status: Default::default(),
+            dpb: Default::default(),
+            current_display_order: Default::default(),
+            cur_sps_id: Default::default(),
+            cur_pps_id: Default::default(),
+            first_picture_in_cvs: true,
+            irap_no_rasl_output_flag: Default::default(),
+            prev_tid0_pic_poc: Default::default(),
+            cur_pic: Default::default(),
+            skipping_cur_pic: Default::default(),
+            ready_queue: Default::default(),
+            rps: Default::default(),
+            ref_pic_list0: Default::default(),
+            ref_pic_list1: Default::default(),
+
+            #[cfg(test)]
+            params: Default::default(),
+        })
+    }
+
+    fn negotiation_possible(sps: &Sps, dpb: &Dpb<T>, current_resolution: Resolution) -> bool {
+        let resolution = Resolution {
+            width: sps.width(),
+            height: sps.height(),
+        };
+
+        current_resolution != resolution || dpb.max_num_pics() != sps.max_dpb_size()
+    }
+
+    fn process_sps(&mut self, nalu: &Nalu<impl AsRef<[u8]>>) -> Result<()> {
+        let sps = self.parser.parse_sps(nalu)?;
+
+        if Self::negotiation_possible(sps, &self.dpb, self.coded_resolution) {
+            let max_dpb_size = sps.max_dpb_size();
+            let resolution = Resolution {
+                width: sps.width(),
+                height: sps.height(),
+            };
+
+            self.drain()?;
+
+            self.coded_resolution = resolution;
+            self.dpb.set_max_num_pics(max_dpb_size);
+        }
+
+        Ok(())
+    }
+
+    /// Decoding process for picture order count, see 8.3.1.
+    fn compute_pic_order_count(&mut self, pic: &mut PictureData, sps: &Sps) {
+        let max_pic_order_cnt_lsb = sps.max_pic_order_cnt_lsb();
+
+        pic.pic_order_cnt_msb = if pic.starts_cvs() {
+            0
+        } else {
+            let prev_pic_order_cnt_lsb = self.prev_tid0_pic_poc & (max_pic_order_cnt_lsb - 1);
+            let prev_pic_order_cnt_msb = self.prev_tid0_pic_poc - prev_pic_order_cnt_lsb;
+
+            if pic.pic_order_cnt_lsb < prev_pic_order_cnt_lsb
+                && prev_pic_order_cnt_lsb - pic.pic_order_cnt_lsb >= max_pic_order_cnt_lsb / 2
+            {
+                prev_pic_order_cnt_msb + max_pic_order_cnt_lsb
+            } else if pic.pic_order_cnt_lsb > prev_pic_order_cnt_lsb
+                && pic.pic_order_cnt_lsb - prev_pic_order_cnt_lsb > max_pic_order_cnt_lsb / 2
+            {
+                prev_pic_order_cnt_msb - max_pic_order_cnt_lsb
+            } else {
+                prev_pic_order_cnt_msb
+            }
+        };
+
+        pic.pic_order_cnt_val = pic.pic_order_cnt_msb + pic.pic_order_cnt_lsb;
+
+        if pic.temporal_id == 0
+            && !pic.nalu_type.is_radl()
+            && !pic.nalu_type.is_rasl()
+            && !pic.nalu_type.is_sub_layer_non_ref()
+        {
+            self.prev_tid0_pic_poc = pic.pic_order_cnt_val;
+        }
+    }
+
+    /// Decoding process for the reference picture set, see 8.3.2. Marks the pictures of the DPB
+    /// and fills `self.rps`.
+    fn decode_rps(&mut self, pic: &PictureData, slice: &Slice<&[u8]>, sps: &Sps) {
+        self.rps.clear();
+
+        if pic.starts_cvs() {
+            self.dpb.mark_all_as_unused_for_ref();
+        }
+
+        if pic.nalu_type.is_idr() {
+            return;
+        }
+
+        let hdr = slice.header();
+        let st_rps = hdr.curr_short_term_ref_pic_set(sps);
+        let poc = pic.pic_order_cnt_val;
+        let max_pic_order_cnt_lsb = sps.max_pic_order_cnt_lsb();
+
+        let mut poc_st_curr_before = vec![];
+        let mut poc_st_curr_after = vec![];
+        let mut poc_st_foll = vec![];
+
+        for i in 0..usize::from(st_rps.num_negative_pics()) {
+            let delta_poc = poc + st_rps.delta_poc_s0()[i];
+            if st_rps.used_by_curr_pic_s0()[i] {
+                poc_st_curr_before.push(delta_poc);
+            } else {
+                poc_st_foll.push(delta_poc);
+            }
+        }
+
+        for i in 0..usize::from(st_rps.num_positive_pics()) {
+            let delta_poc = poc + st_rps.delta_poc_s1()[i];
+            if st_rps.used_by_curr_pic_s1()[i] {
+                poc_st_curr_after.push(delta_poc);
+            } else {
+                poc_st_foll.push(delta_poc);
+            }
+        }
+
+        // The long-term entries, along with whether their full POC is known.
+        let mut poc_lt_curr = vec![];
+        let mut poc_lt_foll = vec![];
+
+        let num_long_term =
+            usize::from(hdr.num_long_term_sps()) + usize::from(hdr.num_long_term_pics());
+        for i in 0..num_long_term {
+            let mut poc_lt = i32::from(hdr.poc_lsb_lt()[i]);
+            let msb_present = hdr.delta_poc_msb_present_flag()[i];
+
+            if msb_present {
+                poc_lt += poc
+                    - hdr.delta_poc_msb_cycle_lt()[i] as i32 * max_pic_order_cnt_lsb
+                    - (poc & (max_pic_order_cnt_lsb - 1));
+            }
+
+            if hdr.used_by_curr_pic_lt()[i] {
+                poc_lt_curr.push((poc_lt, msb_present));
+            } else {
+                poc_lt_foll.push((poc_lt, msb_present));
+            }
+        }
+
+        let find_lt = |dpb: &Dpb<T>, (poc_lt, msb_present): (i32, bool)| {
+            let mask = if msb_present {
+                None
+            } else {
+                Some(max_pic_order_cnt_lsb - 1)
+            };
+            dpb.find_ref_by_poc(poc_lt, mask)
+        };
+
+        self.rps.lt_curr = poc_lt_curr
+            .into_iter()
+            .map(|lt| find_lt(&self.dpb, lt))
+            .collect();
+        self.rps.lt_foll = poc_lt_foll
+            .into_iter()
+            .map(|lt| find_lt(&self.dpb, lt))
+            .collect();
+
+        for entry in self.rps.lt_curr.iter().chain(&self.rps.lt_foll).flatten() {
+            entry.0.borrow_mut().set_reference(Reference::LongTerm);
+        }
+
+        self.rps.st_curr_before = poc_st_curr_before
+            .into_iter()
+            .map(|poc| self.dpb.find_short_term_ref_by_poc(poc))
+            .collect();
+        self.rps.st_curr_after = poc_st_curr_after
+            .into_iter()
+            .map(|poc| self.dpb.find_short_term_ref_by_poc(poc))
+            .collect();
+        self.rps.st_foll = poc_st_foll
+            .into_iter()
+            .map(|poc| self.dpb.find_short_term_ref_by_poc(poc))
+            .collect();
+
+        // All the reference pictures that are not included in the RPS are marked as unused for
+        // reference.
+        let in_rps = |pic: &Rc<RefCell<PictureData>>| {
+            self.rps
+                .st_curr_before
+                .iter()
+                .chain(&self.rps.st_curr_after)
+                .chain(&self.rps.st_foll)
+                .chain(&self.rps.lt_curr)
+                .chain(&self.rps.lt_foll)
+                .flatten()
+                .any(|entry| Rc::ptr_eq(&entry.0, pic))
+        };
+
+        for entry in self.dpb.entries() {
+            if !in_rps(&entry.0) {
+                entry.0.borrow_mut().set_reference(Reference::None);
+            }
+        }
+
+        for (name, list) in [
+            ("RefPicSetStCurrBefore", &self.rps.st_curr_before),
+            ("RefPicSetStCurrAfter", &self.rps.st_curr_after),
+            ("RefPicSetLtCurr", &self.rps.lt_curr),
+        ] {
+            if list.iter().any(Option::is_none) {
+                log::warn!("Missing reference picture in {} for POC {}", name, poc);
+            }
+        }
+    }
+
+    /// Output and removal of pictures from the DPB before the decoding of the current picture,
+    /// see C.5.2.2.
+    fn output_and_remove_pictures(&mut self, pic: &PictureData, sps: &Sps) {
+        if pic.starts_cvs() && !self.first_picture_in_cvs {
+            // CRA pictures always discard the pictures preceding them when they start a coded
+            // video sequence.
+            let no_output_of_prior_pics_flag =
+                pic.nalu_type.is_cra() || pic.no_output_of_prior_pics_flag;
+
+            if no_output_of_prior_pics_flag {
+                self.dpb.clear();
+            } else {
+                self.bump_all();
+                self.dpb.clear();
+            }
+        } else {
+            self.dpb.remove_unused();
+
+            while self.dpb.needs_bumping(sps, true) {
+                match self.dpb.bump(false) {
+                    Some(entry) => self.ready_queue.push(entry.1),
+                    None => break,
+                }
+            }
+        }
+    }
+
+    /// Bumps all the pictures waiting for output into the ready queue.
+    fn bump_all(&mut self) {
+        let pics = self.dpb.drain();
+        self.ready_queue
+            .extend(pics.into_iter().map(|entry| entry.1));
+    }
+
+    /// Returns whether the picture should be decoded. RASL pictures associated with an IRAP
+    /// picture that starts a coded video sequence are skipped, see 8.1.3.
+    fn handle_picture(&mut self, timestamp: u64, slice: &Slice<&[u8]>) -> Result<bool> {
+        let nalu_type = *slice.nalu().header().nalu_type();
+
+        if nalu_type.is_irap() {
+            self.irap_no_rasl_output_flag =
+                nalu_type.is_idr() || nalu_type.is_bla() || self.first_picture_in_cvs;
+        } else if self.first_picture_in_cvs {
+            debug!("Skipping non-IRAP picture at the start of the coded video sequence");
+            return Ok(false);
+        }
+
+        if nalu_type.is_rasl() && self.irap_no_rasl_output_flag {
+            debug!("Skipping RASL picture");
+            return Ok(false);
+        }
+
+        self.cur_pps_id = slice.header().pic_parameter_set_id();
+
+        let pps = self
+            .parser
+            .get_pps(self.cur_pps_id)
+            .context("Invalid PPS in handle_picture")?;
+
+        self.cur_sps_id = pps.seq_parameter_set_id();
+
+        let sps = self
+            .parser
+            .get_sps(self.cur_sps_id)
+            .context("Invalid SPS in handle_picture")?
+            .clone();
+
+        let mut pic = PictureData::new_from_slice(slice, &sps, timestamp);
+        if nalu_type.is_irap() {
+            pic.no_rasl_output_flag = self.irap_no_rasl_output_flag;
+        }
+
+        self.compute_pic_order_count(&mut pic, &sps);
+        self.decode_rps(&pic, slice, &sps);
+        self.output_and_remove_pictures(&pic, &sps);
+
+        self.first_picture_in_cvs = false;
+
+        debug!("Decode picture POC {:?}", pic.pic_order_cnt_val);
+
+        self.backend.new_picture(&pic, timestamp)?;
+        self.backend.handle_picture(
+            &pic,
+            timestamp,
+            &sps,
+            self.parser
+                .get_pps(self.cur_pps_id)
+                .context("invalid PPS in handle_picture")?,
+            &self.dpb,
+            &self.rps,
+            slice,
+        )?;
+
+        self.cur_pic = Some(pic);
+
+        Ok(true)
+    }
+
+    /// Builds RefPicList0 and RefPicList1 for the current slice, see 8.3.4.
+    fn build_ref_pic_lists(&mut self, slice: &Slice<&[u8]>) {
+        self.ref_pic_list0.clear();
+        self.ref_pic_list1.clear();
+
+        let hdr = slice.header();
+
+        if hdr.slice_type().is_i() {
+            return;
+        }
+
+        let num_pic_total_curr = hdr.num_pic_total_curr() as usize;
+        let build = |first: &[Option<DpbEntry<T>>],
+                     second: &[Option<DpbEntry<T>>],
+                     lt: &[Option<DpbEntry<T>>],
+                     num_active: usize,
+                     modification: bool,
+                     list_entry: &[u8]| {
+            let num_rps_curr_temp_list = std::cmp::max(num_active, num_pic_total_curr);
+            let temp = first
+                .iter()
+                .chain(second)
+                .chain(lt)
+                .cycle()
+                .take(num_rps_curr_temp_list)
+                .cloned()
+                .collect::<Vec<_>>();
+
+            if temp.is_empty() {
+                return vec![];
+            }
+
+            (0..num_active)
+                .map(|i| {
+                    let idx = if modification {
+                        usize::from(list_entry[i])
+                    } else {
+                        i
+                    };
+                    temp.get(idx).cloned().flatten()
+                })
+                .collect::<Vec<_>>()
+        };
+
+        self.ref_pic_list0 = build(
+            &self.rps.st_curr_before,
+            &self.rps.st_curr_after,
+            &self.rps.lt_curr,
+            usize::from(hdr.num_ref_idx_l0_active_minus1()) + 1,
+            hdr.ref_pic_list_modification_flag_l0(),
+            &hdr.list_entry_l0(),
+        );
+
+        if hdr.slice_type().is_b() {
+            self.ref_pic_list1 = build(
+                &self.rps.st_curr_after,
+                &self.rps.st_curr_before,
+                &self.rps.lt_curr,
+                usize::from(hdr.num_ref_idx_l1_active_minus1()) + 1,
+                hdr.ref_pic_list_modification_flag_l1(),
+                &hdr.list_entry_l1(),
+            );
+        }
+    }
+
+    fn handle_slice(&mut self, slice: &Slice<&[u8]>) -> Result<()> {
+        self.build_ref_pic_lists(slice);
+
+        let sps = self
+            .parser
+            .get_sps(self.cur_sps_id)
+            .context("Invalid SPS in handle_slice")?;
+
+        let pps = self
+            .parser
+            .get_pps(self.cur_pps_id)
+            .context("Invalid PPS in handle_slice")?;
+
+        self.backend.decode_slice(
+            slice,
+            sps,
+            pps,
+            &self.dpb,
+            &self.ref_pic_list0,
+            &self.ref_pic_list1,
+        )?;
+
+        Ok(())
+    }
+
+    /// Submits the picture to the accelerator.
+    fn submit_picture(&mut self) -> VideoDecoderResult<(PictureData, T)> {
+        let picture = self.cur_pic.take().unwrap();
+
+        let block = if matches!(self.blocking_mode, BlockingMode::Blocking)
+            || matches!(
+                self.negotiation_status,
+                NegotiationStatus::DrainingQueuedBuffers
+            ) {
+            BlockingMode::Blocking
+        } else {
+            BlockingMode::NonBlocking
+        };
+
+        let handle = self
+            .backend
+            .submit_picture(&picture, block)
+            .map_err(VideoDecoderError::StatelessBackendError)?;
+
+        Ok((picture, handle))
+    }
+
+    /// Marks the decoded picture and stores it in the DPB, bumping pictures as described in
+    /// C.5.2.3.
+    fn finish_picture(&mut self, mut pic: PictureData, handle: T) -> Result<()> {
+        debug!("Finishing picture POC {:?}", pic.pic_order_cnt_val);
+
+        let sps = self
+            .parser
+            .get_sps(self.cur_sps_id)
+            .context("Invalid SPS in finish_picture")?;
+
+        for mut dpb_pic in self.dpb.pictures_mut() {
+            if dpb_pic.needed_for_output {
+                dpb_pic.pic_latency_count += 1;
+            }
+        }
+
+        pic.needed_for_output = pic.pic_output_flag;
+        pic.pic_latency_count = 0;
+        pic.set_reference(Reference::ShortTerm);
+
+        self.ref_pic_list0.clear();
+        self.ref_pic_list1.clear();
+        self.rps.clear();
+
+        self.dpb.store_picture(Rc::new(RefCell::new(pic)), handle)?;
+
+        while self.dpb.needs_bumping(sps, false) {
+            match self.dpb.bump(false) {
+                Some(entry) => self.ready_queue.push(entry.1),
+                None => break,
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Get the DecodedFrameHandles for the pictures in the ready queue, in display order.
+    fn get_ready_frames(&mut self) -> Vec<T> {
+        let (ready, retained): (Vec<_>, Vec<_>) = std::mem::take(&mut self.ready_queue)
+            .into_iter()
+            // Assign display order to frames that don't have one yet.
+            .map(|mut handle| {
+                if DecodedHandle::display_order(&handle).is_none() {
+                    let order = self.current_display_order;
+                    handle.set_display_order(order);
+                    self.current_display_order += 1;
+                }
+                handle
+            })
+            .partition(|handle| self.backend.handle_is_ready(handle));
+
+        // Keep non-ready frames in the ready queue.
+        self.ready_queue = retained;
+
+        ready
+    }
+
+    /// Drain the decoder, processing all pending frames.
+    fn drain(&mut self) -> Result<()> {
+        debug!("Draining the decoder.");
+        self.backend.poll(BlockingMode::Blocking)?;
+
+        // Pics in the DPB have undergone `finish_picture` already, we can
+        // just mark them as ready.
+        self.bump_all();
+
+        self.ref_pic_list0.clear();
+        self.ref_pic_list1.clear();
+        self.rps.clear();
+        self.dpb.clear();
+
+        Ok(())
+    }
+
+    fn peek_sps(parser: &mut Parser, bitstream: &[u8]) -> Option<Sps> {
+        let mut cursor = Cursor::new(bitstream);
+
+        while let Ok(Some(nalu)) = Nalu::next(&mut cursor, bitstream) {
+            if matches!(nalu.header().nalu_type(), NaluType::SpsNut) {
+                let sps = parser.parse_sps(&nalu).ok()?;
+                return Some(sps.clone());
+            }
+        }
+
+        None
+    }
+
+    #[cfg(test)]
+    fn steal_pics_for_test(&mut self) {
+        let frames = self.get_ready_frames();
+        self.params.save_ready_pics(frames);
+    }
+
+    #[cfg(test)]
+    #[allow(dead_code)]
+    pub(crate) fn backend(&self) -> &dyn StatelessDecoderBackend<Handle = T> {
+        self.backend.as_ref()
+    }
+
+    /// Finishes the current picture, if any.
+    fn finish_current_picture(&mut self) -> VideoDecoderResult<()> {
+        if self.cur_pic.is_some() {
+            let (picture, handle) = self.submit_picture()?;
+            self.finish_picture(picture, handle)?;
+        }
+
+        Ok(())
+    }
+
+    fn decode_access_unit(&mut self, timestamp: u64, bitstream: &[u8]) -> VideoDecoderResult<()> {
+        if self.backend.num_resources_left() == 0 {
+            return Err(VideoDecoderError::StatelessBackendError(
+                StatelessBackendError::OutOfResources,
+            ));
+        }
+
+        let mut cursor = Cursor::new(bitstream);
+
+        while let Ok(Some(nalu)) = Nalu::next(&mut cursor, bitstream) {
+            match nalu.header().nalu_type() {
+                NaluType::VpsNut => {
+                    self.parser.parse_vps(&nalu)?;
+                }
+
+                NaluType::SpsNut => {
+                    self.process_sps(&nalu)?;
+                }
+
+                NaluType::PpsNut => {
+                    self.parser.parse_pps(&nalu)?;
+                }
+
+                NaluType::EosNut | NaluType::EobNut => {
+                    self.finish_current_picture()?;
+                    self.first_picture_in_cvs = true;
+                }
+
+                // Reserved VCL NAL unit types are ignored.
+                nalu_type if matches!(*nalu_type as u32, 0..=9 | 16..=21) => {
+                    let slice = self.parser.parse_slice_header(nalu)?;
+
+                    if slice.header().first_slice_segment_in_pic_flag() {
+                        self.finish_current_picture()?;
+                        self.skipping_cur_pic = !self.handle_picture(timestamp, &slice)?;
+                    }
+
+                    if !self.skipping_cur_pic && self.cur_pic.is_some() {
+                        self.handle_slice(&slice)?;
+                    }
+                }
+
+                other => {
+                    debug!("Unsupported NAL unit type {:?}", other,);
+                }
+            }
+        }
+
+        self.finish_current_picture()?;
+
+        Ok(())
+    }
+
+    fn block_on_one(&mut self) -> VideoDecoderResult<()> {
+        for handle in &self.ready_queue {
+            if !self.backend.handle_is_ready(handle) {
+                return self
+                    .backend
+                    .block_on_handle(handle)
+                    .map_err(VideoDecoderError::StatelessBackendError);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<T> VideoDecoder for Decoder<T>
+where
+    T: DecodedHandle + DynDecodedHandle + 'static,
+{
+    fn decode(
+        &mut self,
+        timestamp: u64,
+        bitstream: &[u8],
+    ) -> VideoDecoderResult<Vec<Box<dyn DynDecodedHandle>>> {
+        let sps = Self::peek_sps(&mut self.parser, bitstream);
+
+        if let Some(sps) = &sps {
+            if Self::negotiation_possible(sps, &self.dpb, self.coded_resolution)
+                && matches!(self.negotiation_status, NegotiationStatus::Negotiated)
+            {
+                self.negotiation_status = NegotiationStatus::NonNegotiated {
+                    queued_buffers: Default::default(),
+                }
+            }
+        }
+
+        let queued_buffers = match &mut self.negotiation_status {
+            NegotiationStatus::NonNegotiated { queued_buffers } => {
+                let buffer = Vec::from(bitstream);
+                queued_buffers.push((timestamp, buffer));
+
+                if let Some(sps) = &sps {
+                    self.backend.poll(BlockingMode::Blocking)?;
+                    self.backend.new_sequence(sps)?;
+
+                    self.negotiation_status = NegotiationStatus::Possible {
+                        queued_buffers: queued_buffers.clone(),
+                    }
+                }
+
+                return Ok(vec![]);
+            }
+
+            NegotiationStatus::Possible { queued_buffers } => {
+                let queued_buffers = queued_buffers.clone();
+                self.negotiation_status = NegotiationStatus::DrainingQueuedBuffers;
+                Some(queued_buffers)
+            }
+
+            NegotiationStatus::DrainingQueuedBuffers | NegotiationStatus::Negotiated => None,
+        };
+
+        if let Some(queued_buffers) = queued_buffers {
+            for (timestamp, buffer) in queued_buffers {
+                self.decode_access_unit(timestamp, &buffer)?;
+            }
+
+            self.negotiation_status = NegotiationStatus::Negotiated;
+        }
+
+        self.decode_access_unit(timestamp, bitstream)?;
+
+        if self.backend.num_resources_left() == 0 {
+            self.block_on_one()?;
+        }
+
+        self.backend.poll(self.blocking_mode)?;
+
+        #[cfg(test)]
+        self.steal_pics_for_test();
+
+        let ready_frames = self.get_ready_frames();
+
+        Ok(ready_frames
+            .into_iter()
+            .map(|h| Box::new(h) as Box<dyn DynDecodedHandle>)
+            .collect())
+    }
+
+    fn flush(&mut self) -> VideoDecoderResult<Vec<Box<dyn DynDecodedHandle>>> {
+        // Decode whatever is pending using the default format. Mainly covers
+        // the rare case where only one buffer is sent.
+        match &self.negotiation_status {
+            NegotiationStatus::NonNegotiated { queued_buffers }
+            | NegotiationStatus::Possible { queued_buffers } => {
+                for (timestamp, buffer) in queued_buffers.clone() {
+                    self.decode_access_unit(timestamp, &buffer)?;
+                }
+            }
+            _ => {}
+        }
+
+        self.drain()?;
+
+        #[cfg(test)]
+        self.steal_pics_for_test();
+
+        let pics = self.get_ready_frames();
+
+        Ok(pics
+            .into_iter()
+            .map(|h| Box::new(h) as Box<dyn DynDecodedHandle>)
+            .collect())
+    }
+
+    fn negotiation_possible(&self) -> bool {
+        matches!(self.negotiation_status, NegotiationStatus::Possible { .. })
+    }
+
+    fn num_resources_left(&self) -> Option<usize> {
+        if matches!(
+            self.negotiation_status,
+            NegotiationStatus::NonNegotiated { .. }
+        ) {
+            return None;
+        }
+
+        let left_in_the_backend = self.backend.num_resources_left();
+
+        if let NegotiationStatus::Possible { queued_buffers } = &self.negotiation_status {
+            Some(left_in_the_backend - queued_buffers.len())
+        } else {
+            Some(left_in_the_backend)
+        }
+    }
+
+    fn num_resources_total(&self) -> usize {
+        self.backend.num_resources_total()
+    }
+
+    fn coded_resolution(&self) -> Option<Resolution> {
+        self.backend.coded_resolution()
+    }
+
+    fn poll(
+        &mut self,
+        blocking_mode: BlockingMode,
+    ) -> VideoDecoderResult<Vec<Box<dyn DynDecodedHandle>>> {
+        let handles = self.backend.poll(blocking_mode)?;
+
+        Ok(handles
+            .into_iter()
+            .map(|h| Box::new(h) as Box<dyn DynDecodedHandle>)
+            .collect())
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use crate::decoders::h265::decoder::Decoder;
+    use crate::decoders::h265::parser::tests::pps;
+    use crate::decoders::h265::parser::tests::slice;
+    use crate::decoders::h265::parser::tests::sps;
+    use crate::decoders::h265::parser::tests::vps;
+    use crate::decoders::h265::parser::NaluType;
+    use crate::decoders::h265::parser::SliceType;
+    use crate::decoders::BlockingMode;
+    use crate::decoders::DecodedHandle;
+    use crate::decoders::DynDecodedHandle;
+    use crate::decoders::VideoDecoder;
+
+    pub fn process_ready_frames<Handle>(
+        decoder: &mut Decoder<Handle>,
+        action: &mut dyn FnMut(&mut Decoder<Handle>, &Handle),
+    ) where
+        Handle: DecodedHandle + DynDecodedHandle,
+    {
+        let ready_pics = decoder.params.ready_pics.drain(..).collect::<Vec<_>>();
+
+        for handle in ready_pics {
+            action(decoder, &handle);
+        }
+    }
+
+    /// Decodes `access_units`, calling `on_new_iteration` after each of them and for every
+    /// frame returned by the final flush.
+    pub fn run_decoding_loop<Handle, F>(
+        decoder: &mut Decoder<Handle>,
+        access_units: &[Vec<u8>],
+        mut on_new_iteration: F,
+    ) where
+        Handle: DecodedHandle + DynDecodedHandle + 'static,
+        F: FnMut(&mut Decoder<Handle>),
+    {
+        for (frame_num, access_unit) in access_units.iter().enumerate() {
+            decoder.decode(frame_num as u64, access_unit).unwrap();
+            on_new_iteration(decoder);
+        }
+
+        decoder.flush().unwrap();
+        let n_flushed = decoder.params.ready_pics.len();
+
+        for _ in 0..n_flushed {
+            on_new_iteration(decoder);
+        }
+    }
+
+    /// Prepends the parameter sets to the first access unit of a test stream.
+    fn with_parameter_sets(first_slice: Vec<u8>) -> Vec<u8> {
+        let mut access_unit = vps();
+        access_unit.extend(sps());
+        access_unit.extend(pps());
+        access_unit.extend(first_slice);
+        access_unit
+    }
+
+    fn count_frames(access_units: &[Vec<u8>]) -> usize {
+        let blocking_modes = [BlockingMode::Blocking, BlockingMode::NonBlocking];
+        let mut counts = vec![];
+
+        for blocking_mode in blocking_modes {
+            let mut frame_num = 0;
+            let mut decoder = Decoder::new_dummy(blocking_mode).unwrap();
+
+            run_decoding_loop(&mut decoder, access_units, |decoder| {
+                process_ready_frames(decoder, &mut |_, _| frame_num += 1);
+            });
+
+            counts.push(frame_num);
+        }
+
+        assert_eq!(counts[0], counts[1]);
+        counts[0]
+    }
+
+    #[test]
+    fn test_64x64_i_and_p() {
+        // IDR picture followed by three P pictures, each referencing the previous one.
+        let mut access_units = vec![with_parameter_sets(slice(
+            NaluType::IdrWRadl,
+            SliceType::I,
+            0,
+            &[],
+            &[],
+        ))];
+
+        for poc in 1..4 {
+            access_units.push(slice(
+                NaluType::TrailR,
+                SliceType::P,
+                poc,
+                &[(-1, true)],
+                &[],
+            ));
+        }
+
+        assert_eq!(count_frames(&access_units), 4);
+    }
+
+    #[test]
+    fn test_64x64_i_p_b_b_b() {
+        // A hierarchical GOP, in decoding order: I0 P4 B2 B1 B3.
+        let access_units = vec![
+            with_parameter_sets(slice(NaluType::IdrWRadl, SliceType::I, 0, &[], &[])),
+            slice(NaluType::TrailR, SliceType::P, 4, &[(-4, true)], &[]),
+            slice(
+                NaluType::TrailR,
+                SliceType::B,
+                2,
+                &[(-2, true)],
+                &[(2, true)],
+            ),
+            slice(
+                NaluType::TrailN,
+                SliceType::B,
+                1,
+                &[(-1, true)],
+                &[(1, true), (3, false)],
+            ),
+            slice(
+                NaluType::TrailN,
+                SliceType::B,
+                3,
+                &[(-1, true)],
+                &[(1, true)],
+            ),
+        ];
+
+        assert_eq!(count_frames(&access_units), 5);
+    }
+
+    #[test]
+    fn test_64x64_cra_skips_rasl() {
+        // A stream starting with a CRA picture: its leading RASL picture references a picture
+        // that precedes the CRA and must be skipped.
+        let access_units = vec![
+            with_parameter_sets(slice(NaluType::CraNut, SliceType::I, 8, &[], &[])),
+            slice(
+                NaluType::RaslN,
+                SliceType::B,
+                6,
+                &[(-2, true)],
+                &[(2, true)],
+            ),
+            slice(NaluType::TrailR, SliceType::P, 16, &[(-8, true)], &[]),
+        ];
+
+        assert_eq!(count_frames(&access_units), 2);
+    }
+}
@@ -0,0 +1,238 @@
+// Copyright 2023 The ChromiumOS Authors
+// Use of this source code is governed by a BSD-style license that can be
+// found in the LICENSE file.
+
+use std::cell::Ref;
+use std::cell::RefCell;
+use std::cell::RefMut;
+use std::rc::Rc;
+
+use anyhow::anyhow;
+use anyhow::Result;
+use log::debug;
+
+use crate::decoders::h265::parser::Sps;
+use crate::decoders::h265::picture::PictureData;
+use crate::decoders::h265::picture::Reference;
+use crate::decoders::DecodedHandle;
+
+// Shortcut to refer to a DPB entry.
+//
+// The first member of the tuple is the `PictureData` for the frame.
+//
+// The second member is the backend handle of the frame.
+#[derive(Clone)]
+pub struct DpbEntry<T: DecodedHandle>(pub Rc<RefCell<PictureData>>, pub T);
+
+pub struct Dpb<T: DecodedHandle> {
+    /// List of `PictureData` and backend handles to decoded pictures.
+    entries: Vec<DpbEntry<T>>,
+    /// The maximum number of pictures that can be stored.
+    max_num_pics: usize,
+}
+
+impl<T: DecodedHandle> Dpb<T> {
+    /// Returns an iterator over the underlying H265 pictures stored in the
+    /// DPB.
+    pub fn pictures(&self) -> impl Iterator<Item = Ref<'_, PictureData>> {
+        self.entries.iter().map(|h| h.0.borrow())
+    }
+
+    /// Returns a mutable iterator over the underlying H265 pictures stored in
+    /// the DPB.
+    pub fn pictures_mut(&mut self) -> impl Iterator<Item = RefMut<'_, PictureData>> {
+        self.entries.iter().map(|h| h.0.borrow_mut())
+    }
+
+    /// Returns the length of the DPB.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Get a reference to the whole DPB entries.
+    pub fn entries(&self) -> &Vec<DpbEntry<T>> {
+        &self.entries
+    }
+
+    /// Set the dpb's max num pics.
+    pub fn set_max_num_pics(&mut self, max_num_pics: usize) {
+        self.max_num_pics = max_num_pics;
+    }
+
+    /// Get a reference to the dpb's max num pics.
+    pub fn max_num_pics(&self) -> usize {
+        self.max_num_pics
+    }
+
+    /// Mark all pictures in the DPB as unused for reference.
+    pub fn mark_all_as_unused_for_ref(&mut self) {
+        for mut picture in self.pictures_mut() {
+            picture.set_reference(Reference::None);
+        }
+    }
+
+    /// Remove unused pictures from the DPB. A picture is not going to be used
+    /// anymore if it's a) not a reference and b) not needed for output
+    pub fn remove_unused(&mut self) {
+        self.entries.retain(|handle| {
+            let pic = handle.0.borrow();
+            let discard = !pic.is_ref() && !pic.needed_for_output;
+
+            if discard {
+                log::debug!("Removing unused picture POC {}", pic.pic_order_cnt_val);
+            }
+
+            !discard
+        });
+    }
+
+    /// Find a reference picture with the given `poc`. If `lsb_mask` is given, only the bits of
+    /// the POC in the mask are compared, as done for long-term references without
+    /// delta_poc_msb_present_flag.
+    pub fn find_ref_by_poc(&self, poc: i32, lsb_mask: Option<i32>) -> Option<DpbEntry<T>> {
+        let mask = lsb_mask.unwrap_or(-1);
+
+        self.entries
+            .iter()
+            .find(|handle| {
+                let pic = handle.0.borrow();
+                pic.is_ref() && (pic.pic_order_cnt_val & mask) == (poc & mask)
+            })
+            .cloned()
+    }
+
+    /// Find a short-term reference picture with the given `poc`.
+    pub fn find_short_term_ref_by_poc(&self, poc: i32) -> Option<DpbEntry<T>> {
+        self.entries
+            .iter()
+            .find(|handle| {
+                let pic = handle.0.borrow();
+                matches!(pic.reference(), Reference::ShortTerm) && pic.pic_order_cnt_val == poc
+            })
+            .cloned()
+    }
+
+    /// Store a picture and its backend handle in the DPB.
+    pub fn store_picture(&mut self, picture: Rc<RefCell<PictureData>>, handle: T) -> Result<()> {
+        if self.entries.len() >= self.max_num_pics {
+            return Err(anyhow!("Can't add a picture to the DPB: DPB is full."));
+        }
+
+        debug!(
+            "Stored picture POC {:?}, the DPB length is {:?}",
+            picture.borrow().pic_order_cnt_val,
+            self.entries.len()
+        );
+
+        self.entries.push(DpbEntry(picture, handle));
+
+        Ok(())
+    }
+
+    /// Returns the number of pictures marked as needed for output.
+    pub fn num_needed_for_output(&self) -> usize {
+        self.pictures().filter(|pic| pic.needed_for_output).count()
+    }
+
+    /// Whether the DPB needs bumping, as described by the conditions of C.5.2.2 and C.5.2.3.
+    /// When `storing` is set, the DPB is also bumped when full so the current picture can be
+    /// stored.
+    pub fn needs_bumping(&self, sps: &Sps, storing: bool) -> bool {
+        let num_needed_for_output = self.num_needed_for_output();
+
+        if num_needed_for_output == 0 {
+            return false;
+        }
+
+        if num_needed_for_output > sps.max_num_reorder_pics() as usize {
+            return true;
+        }
+
+        if let Some(max_latency_pictures) = sps.max_latency_pictures() {
+            if self
+                .pictures()
+                .any(|pic| pic.needed_for_output && pic.pic_latency_count >= max_latency_pictures)
+            {
+                return true;
+            }
+        }
+
+        storing && self.entries.len() >= sps.max_dpb_size()
+    }
+
+    /// Bump the dpb, returning a picture as per the bumping process described in C.5.2.4. The
+    /// picture is removed from the DPB unless it is still used for reference.
+    pub fn bump(&mut self, flush: bool) -> Option<DpbEntry<T>> {
+        let position = self
+            .entries
+            .iter()
+            .enumerate()
+            .filter(|(_, handle)| handle.0.borrow().needed_for_output)
+            .min_by_key(|(_, handle)| handle.0.borrow().pic_order_cnt_val)
+            .map(|(position, _)| position)?;
+
+        let handle = self.entries[position].clone();
+        let mut pic = handle.0.borrow_mut();
+
+        debug!("Bumping picture POC {} from the dpb", pic.pic_order_cnt_val);
+
+        pic.needed_for_output = false;
+
+        if !pic.is_ref() || flush {
+            self.entries.remove(position);
+        }
+
+        drop(pic);
+        Some(handle)
+    }
+
+    /// Drains the DPB by continuously invoking the bumping process.
+    pub fn drain(&mut self) -> Vec<DpbEntry<T>> {
+        debug!("Draining the DPB.");
+
+        let mut pics = vec![];
+
+        while let Some(pic) = self.bump(true) {
+            pics.push(pic);
+        }
+
+        pics
+    }
+
+    /// Clears the DPB, dropping all the pictures.
+    pub fn clear(&mut self) {
+        debug!("Clearing the DPB");
+
+        let max_num_pics = self.max_num_pics;
+
+        *self = Default::default();
+
+        self.max_num_pics = max_num_pics;
+    }
+}
+
+impl<T: DecodedHandle> Default for Dpb<T> {
+    fn default() -> Self {
+        // See https://github.com/rust-lang/rust/issues/26925 on why this can't
+        // be derived.
+        Self {
+            entries: Default::default(),
+            max_num_pics: Default::default(),
+        }
+    }
+}
+
+impl<T: DecodedHandle> std::fmt::Debug for Dpb<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let pics = self
+            .entries
+            .iter()
+            .map(|h| &h.0)
+            .enumerate()
+            .collect::<Vec<_>>();
+        f.debug_struct("Dpb")
+            .field("pictures", &pics)
+            .field("max_num_pics", &self.max_num_pics)
+            .finish()
+    }
+}
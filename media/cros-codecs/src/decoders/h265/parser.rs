@@ -0,0 +1,3773 @@
+// Copyright 2023 The ChromiumOS Authors
+// Use of this source code is governed by a BSD-style license that can be
+// found in the LICENSE file.
+
+use std::collections::BTreeMap;
+use std::io::Cursor;
+
+use anyhow::anyhow;
+use anyhow::Context;
+use anyhow::Result;
+use bytes::Buf;
+use enumn::N;
+
+use crate::decoders::h264::nalu_reader::NaluReader;
+
+const MAX_VPS_COUNT: u32 = 16;
+const MAX_SPS_COUNT: u32 = 16;
+const MAX_PPS_COUNT: u32 = 64;
+
+/// The maximum number of temporal sub-layers in a coded video sequence.
+pub const MAX_SUB_LAYERS: usize = 7;
+/// The maximum number of pictures in the DPB, see A.4.2.
+pub const MAX_DPB_SIZE: usize = 16;
+/// The maximum number of short-term reference picture sets in a SPS.
+pub const MAX_SHORT_TERM_REF_PIC_SETS: usize = 64;
+/// The maximum number of long-term reference pictures signalled in a SPS.
+pub const MAX_LONG_TERM_REF_PICS_SPS: usize = 32;
+/// The maximum number of entries in a short-term reference picture set.
+pub const MAX_SHORT_TERM_REF_PICS: usize = 16;
+/// The maximum number of long-term reference pictures signalled in a slice header.
+pub const MAX_LONG_TERM_REF_PICS: usize = 16;
+/// The maximum number of entries in a reference picture list.
+pub const MAX_REF_IDX_ACTIVE: usize = 15;
+/// The maximum number of tile columns, see Table A.8.
+pub const MAX_TILE_COLUMNS: usize = 20;
+/// The maximum number of tile rows, see Table A.8.
+pub const MAX_TILE_ROWS: usize = 22;
+/// The maximum number of entries in the chroma QP offset lists.
+pub const MAX_CHROMA_QP_OFFSET_LIST_LEN: usize = 6;
+
+const EXTENDED_SAR: u8 = 255;
+
+// Table 7-5 and 7-6.
+const DEFAULT_SCALING_LIST_INTRA: [u8; 64] = [
+    16, 16, 16, 16, 16, 16, 16, 16, 16, 16, 17, 16, 17, 16, 17, 18, 17, 18, 18, 17, 18, 21, 19, 20,
+    21, 20, 19, 21, 24, 22, 22, 24, 24, 22, 22, 24, 25, 25, 27, 30, 27, 25, 25, 29, 31, 35, 35, 31,
+    29, 36, 41, 44, 41, 36, 47, 54, 54, 47, 65, 70, 65, 88, 88, 115,
+];
+
+const DEFAULT_SCALING_LIST_INTER: [u8; 64] = [
+    16, 16, 16, 16, 16, 16, 16, 16, 16, 16, 17, 17, 17, 17, 17, 18, 18, 18, 18, 18, 18, 20, 20, 20,
+    20, 20, 20, 20, 24, 24, 24, 24, 24, 24, 24, 24, 25, 25, 25, 25, 25, 25, 25, 28, 28, 28, 28, 28,
+    28, 33, 33, 33, 33, 33, 41, 41, 41, 41, 54, 54, 54, 71, 71, 91,
+];
+
+/// Table 7-1 – NAL unit type codes and NAL unit type classes
+#[derive(N, Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum NaluType {
+    #[default]
+    TrailN = 0,
+    TrailR = 1,
+    TsaN = 2,
+    TsaR = 3,
+    StsaN = 4,
+    StsaR = 5,
+    RadlN = 6,
+    RadlR = 7,
+    RaslN = 8,
+    RaslR = 9,
+    RsvVclN10 = 10,
+    RsvVclR11 = 11,
+    RsvVclN12 = 12,
+    RsvVclR13 = 13,
+    RsvVclN14 = 14,
+    RsvVclR15 = 15,
+    BlaWLp = 16,
+    BlaWRadl = 17,
+    BlaNLp = 18,
+    IdrWRadl = 19,
+    IdrNLp = 20,
+    CraNut = 21,
+    RsvIrapVcl22 = 22,
+    RsvIrapVcl23 = 23,
+    RsvVcl24 = 24,
+    RsvVcl25 = 25,
+    RsvVcl26 = 26,
+    RsvVcl27 = 27,
+    RsvVcl28 = 28,
+    RsvVcl29 = 29,
+    RsvVcl30 = 30,
+    RsvVcl31 = 31,
+    VpsNut = 32,
+    SpsNut = 33,
+    PpsNut = 34,
+    AudNut = 35,
+    EosNut = 36,
+    EobNut = 37,
+    FdNut = 38,
+    PrefixSeiNut = 39,
+    SuffixSeiNut = 40,
+    RsvNvcl41 = 41,
+    RsvNvcl42 = 42,
+    RsvNvcl43 = 43,
+    RsvNvcl44 = 44,
+    RsvNvcl45 = 45,
+    RsvNvcl46 = 46,
+    RsvNvcl47 = 47,
+    Unspec48 = 48,
+    Unspec49 = 49,
+    Unspec50 = 50,
+    Unspec51 = 51,
+    Unspec52 = 52,
+    Unspec53 = 53,
+    Unspec54 = 54,
+    Unspec55 = 55,
+    Unspec56 = 56,
+    Unspec57 = 57,
+    Unspec58 = 58,
+    Unspec59 = 59,
+    Unspec60 = 60,
+    Unspec61 = 61,
+    Unspec62 = 62,
+    Unspec63 = 63,
+}
+
+impl NaluType {
+    /// Whether this is a VCL NAL unit, i.e. one containing a slice segment.
+    pub fn is_vcl(&self) -> bool {
+        (*self as u32) < 32
+    }
+
+    /// Whether this NAL unit starts an intra random access point (IRAP) picture.
+    pub fn is_irap(&self) -> bool {
+        (16..=23).contains(&(*self as u32))
+    }
+
+    /// Whether this is an instantaneous decoding refresh (IDR) picture.
+    pub fn is_idr(&self) -> bool {
+        matches!(self, NaluType::IdrWRadl | NaluType::IdrNLp)
+    }
+
+    /// Whether this is a broken link access (BLA) picture.
+    pub fn is_bla(&self) -> bool {
+        matches!(
+            self,
+            NaluType::BlaWLp | NaluType::BlaWRadl | NaluType::BlaNLp
+        )
+    }
+
+    /// Whether this is a clean random access (CRA) picture.
+    pub fn is_cra(&self) -> bool {
+        matches!(self, NaluType::CraNut)
+    }
+
+    /// Whether this is a random access decodable leading (RADL) picture.
+    pub fn is_radl(&self) -> bool {
+        matches!(self, NaluType::RadlN | NaluType::RadlR)
+    }
+
+    /// Whether this is a random access skipped leading (RASL) picture.
+    pub fn is_rasl(&self) -> bool {
+        matches!(self, NaluType::RaslN | NaluType::RaslR)
+    }
+
+    /// Whether this is a sub-layer non-reference picture, i.e. one that cannot be used for the
+    /// inter prediction of pictures of the same sub-layer.
+    pub fn is_sub_layer_non_ref(&self) -> bool {
+        (*self as u32) <= 14 && (*self as u32) % 2 == 0
+    }
+}
+
+/// Table 7-7 – Name association to slice_type
+#[derive(N, Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum SliceType {
+    B = 0,
+    P = 1,
+    #[default]
+    I = 2,
+}
+
+impl SliceType {
+    /// Whether this is a B slice.
+    pub fn is_b(&self) -> bool {
+        matches!(self, SliceType::B)
+    }
+
+    /// Whether this is a P slice.
+    pub fn is_p(&self) -> bool {
+        matches!(self, SliceType::P)
+    }
+
+    /// Whether this is an I slice.
+    pub fn is_i(&self) -> bool {
+        matches!(self, SliceType::I)
+    }
+}
+
+/// Profile, tier and level information, see 7.3.3. Only the general values are kept, those of the
+/// sub-layers are skipped.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ProfileTierLevel {
+    /// Specifies the context for the interpretation of general_profile_idc.
+    general_profile_space: u8,
+    /// Specifies the tier context for the interpretation of general_level_idc.
+    general_tier_flag: bool,
+    /// Indicates the profile to which the coded video sequence conforms, see Annex A.
+    general_profile_idc: u8,
+    /// Bit `j` indicates whether the coded video sequence conforms to the profile indicated by
+    /// general_profile_idc equal to `j`.
+    general_profile_compatibility_flags: u32,
+    /// Retains the same meaning as in the specification.
+    general_progressive_source_flag: bool,
+    /// Retains the same meaning as in the specification.
+    general_interlaced_source_flag: bool,
+    /// Retains the same meaning as in the specification.
+    general_non_packed_constraint_flag: bool,
+    /// Retains the same meaning as in the specification.
+    general_frame_only_constraint_flag: bool,
+    /// Indicates the level to which the coded video sequence conforms, as 30 times the level
+    /// number.
+    general_level_idc: u8,
+}
+
+impl ProfileTierLevel {
+    pub fn general_profile_space(&self) -> u8 {
+        self.general_profile_space
+    }
+
+    pub fn general_tier_flag(&self) -> bool {
+        self.general_tier_flag
+    }
+
+    pub fn general_profile_idc(&self) -> u8 {
+        self.general_profile_idc
+    }
+
+    pub fn general_profile_compatibility_flags(&self) -> u32 {
+        self.general_profile_compatibility_flags
+    }
+
+    pub fn general_progressive_source_flag(&self) -> bool {
+        self.general_progressive_source_flag
+    }
+
+    pub fn general_interlaced_source_flag(&self) -> bool {
+        self.general_interlaced_source_flag
+    }
+
+    pub fn general_non_packed_constraint_flag(&self) -> bool {
+        self.general_non_packed_constraint_flag
+    }
+
+    pub fn general_frame_only_constraint_flag(&self) -> bool {
+        self.general_frame_only_constraint_flag
+    }
+
+    pub fn general_level_idc(&self) -> u8 {
+        self.general_level_idc
+    }
+}
+
+/// A short-term reference picture set, see 7.3.7 and 7.4.8. The values derived from another set
+/// through inter RPS prediction are stored as well.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ShortTermRefPicSet {
+    /// Whether this set is predicted from another one.
+    inter_ref_pic_set_prediction_flag: bool,
+    /// Specifies the index of the predicting set, when parsed in a slice header.
+    delta_idx_minus1: u8,
+    /// Specifies the sign of deltaRps.
+    delta_rps_sign: bool,
+    /// Specifies the absolute value of deltaRps minus one.
+    abs_delta_rps_minus1: u16,
+    /// The number of entries with a negative POC delta.
+    num_negative_pics: u8,
+    /// The number of entries with a positive POC delta.
+    num_positive_pics: u8,
+    /// The POC deltas of the entries preceding the current picture, in decreasing order.
+    delta_poc_s0: [i32; MAX_SHORT_TERM_REF_PICS],
+    /// Whether the entries preceding the current picture are used for its inter prediction.
+    used_by_curr_pic_s0: [bool; MAX_SHORT_TERM_REF_PICS],
+    /// The POC deltas of the entries following the current picture, in increasing order.
+    delta_poc_s1: [i32; MAX_SHORT_TERM_REF_PICS],
+    /// Whether the entries following the current picture are used for its inter prediction.
+    used_by_curr_pic_s1: [bool; MAX_SHORT_TERM_REF_PICS],
+}
+
+impl ShortTermRefPicSet {
+    pub fn inter_ref_pic_set_prediction_flag(&self) -> bool {
+        self.inter_ref_pic_set_prediction_flag
+    }
+
+    pub fn delta_idx_minus1(&self) -> u8 {
+        self.delta_idx_minus1
+    }
+
+    pub fn delta_rps_sign(&self) -> bool {
+        self.delta_rps_sign
+    }
+
+    pub fn abs_delta_rps_minus1(&self) -> u16 {
+        self.abs_delta_rps_minus1
+    }
+
+    pub fn num_negative_pics(&self) -> u8 {
+        self.num_negative_pics
+    }
+
+    pub fn num_positive_pics(&self) -> u8 {
+        self.num_positive_pics
+    }
+
+    pub fn delta_poc_s0(&self) -> [i32; MAX_SHORT_TERM_REF_PICS] {
+        self.delta_poc_s0
+    }
+
+    pub fn used_by_curr_pic_s0(&self) -> [bool; MAX_SHORT_TERM_REF_PICS] {
+        self.used_by_curr_pic_s0
+    }
+
+    pub fn delta_poc_s1(&self) -> [i32; MAX_SHORT_TERM_REF_PICS] {
+        self.delta_poc_s1
+    }
+
+    pub fn used_by_curr_pic_s1(&self) -> [bool; MAX_SHORT_TERM_REF_PICS] {
+        self.used_by_curr_pic_s1
+    }
+    /// NumDeltaPocs in the specification.
+    pub fn num_delta_pocs(&self) -> u32 {
+        u32::from(self.num_negative_pics) + u32::from(self.num_positive_pics)
+    }
+
+    /// The number of pictures of this set used by the current picture.
+    pub fn num_used_by_curr_pic(&self) -> u32 {
+        let s0 = &self.used_by_curr_pic_s0[..usize::from(self.num_negative_pics)];
+        let s1 = &self.used_by_curr_pic_s1[..usize::from(self.num_positive_pics)];
+        s0.iter().chain(s1).filter(|&&used| used).count() as u32
+    }
+}
+
+/// Scaling lists, see 7.3.4. The coefficients are stored in the up-right diagonal scan order they
+/// are coded in.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ScalingLists {
+    /// Scaling lists for 4x4 transform blocks.
+    scaling_list_4x4: [[u8; 16]; 6],
+    /// Scaling lists for 8x8 transform blocks.
+    scaling_list_8x8: [[u8; 64]; 6],
+    /// Scaling lists for 16x16 transform blocks, upsampled by the decoding process.
+    scaling_list_16x16: [[u8; 64]; 6],
+    /// Scaling lists for 32x32 transform blocks, upsampled by the decoding process. Only the
+    /// entries 0 and 3 are coded, the others are used for 4:4:4 chroma blocks.
+    scaling_list_32x32: [[u8; 64]; 6],
+    /// scaling_list_dc_coef_minus8 + 8 for 16x16 transform blocks.
+    scaling_list_dc_coef_16x16: [u8; 6],
+    /// scaling_list_dc_coef_minus8 + 8 for 32x32 transform blocks.
+    scaling_list_dc_coef_32x32: [u8; 6],
+}
+
+impl ScalingLists {
+    pub fn scaling_list_4x4(&self) -> [[u8; 16]; 6] {
+        self.scaling_list_4x4
+    }
+
+    pub fn scaling_list_8x8(&self) -> [[u8; 64]; 6] {
+        self.scaling_list_8x8
+    }
+
+    pub fn scaling_list_16x16(&self) -> [[u8; 64]; 6] {
+        self.scaling_list_16x16
+    }
+
+    pub fn scaling_list_32x32(&self) -> [[u8; 64]; 6] {
+        self.scaling_list_32x32
+    }
+
+    pub fn scaling_list_dc_coef_16x16(&self) -> [u8; 6] {
+        self.scaling_list_dc_coef_16x16
+    }
+
+    pub fn scaling_list_dc_coef_32x32(&self) -> [u8; 6] {
+        self.scaling_list_dc_coef_32x32
+    }
+}
+
+impl Default for ScalingLists {
+    /// Returns flat scaling lists, which are equivalent to no scaling lists.
+    fn default() -> Self {
+        Self {
+            scaling_list_4x4: [[16; 16]; 6],
+            scaling_list_8x8: [[16; 64]; 6],
+            scaling_list_16x16: [[16; 64]; 6],
+            scaling_list_32x32: [[16; 64]; 6],
+            scaling_list_dc_coef_16x16: [16; 6],
+            scaling_list_dc_coef_32x32: [16; 6],
+        }
+    }
+}
+
+impl ScalingLists {
+    /// Returns the default scaling lists of Table 7-5 and Table 7-6.
+    pub fn default_lists() -> Self {
+        let lists = [
+            DEFAULT_SCALING_LIST_INTRA,
+            DEFAULT_SCALING_LIST_INTRA,
+            DEFAULT_SCALING_LIST_INTRA,
+            DEFAULT_SCALING_LIST_INTER,
+            DEFAULT_SCALING_LIST_INTER,
+            DEFAULT_SCALING_LIST_INTER,
+        ];
+
+        Self {
+            scaling_list_4x4: [[16; 16]; 6],
+            scaling_list_8x8: lists,
+            scaling_list_16x16: lists,
+            scaling_list_32x32: lists,
+            scaling_list_dc_coef_16x16: [16; 6],
+            scaling_list_dc_coef_32x32: [16; 6],
+        }
+    }
+
+    /// Returns the coefficients for `size_id` and `matrix_id`, as in ScalingList in the
+    /// specification.
+    fn list_mut(&mut self, size_id: usize, matrix_id: usize) -> &mut [u8] {
+        match size_id {
+            0 => &mut self.scaling_list_4x4[matrix_id],
+            1 => &mut self.scaling_list_8x8[matrix_id],
+            2 => &mut self.scaling_list_16x16[matrix_id],
+            _ => &mut self.scaling_list_32x32[matrix_id],
+        }
+    }
+
+    fn dc_coef_mut(&mut self, size_id: usize, matrix_id: usize) -> Option<&mut u8> {
+        match size_id {
+            2 => Some(&mut self.scaling_list_dc_coef_16x16[matrix_id]),
+            3 => Some(&mut self.scaling_list_dc_coef_32x32[matrix_id]),
+            _ => None,
+        }
+    }
+}
+
+/// Sub-layer ordering information, see 7.4.3.2.1.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct SubLayerOrderingInfo {
+    /// The maximum required size of the DPB in units of picture storage buffers, minus one.
+    max_dec_pic_buffering_minus1: u8,
+    /// The maximum allowed number of pictures preceding any picture in decoding order and
+    /// following it in output order.
+    max_num_reorder_pics: u8,
+    /// Used to compute SpsMaxLatencyPictures, the maximum number of pictures that can precede any
+    /// picture in output order and follow it in decoding order. Zero means no limit.
+    max_latency_increase_plus1: u32,
+}
+
+impl SubLayerOrderingInfo {
+    pub fn max_dec_pic_buffering_minus1(&self) -> u8 {
+        self.max_dec_pic_buffering_minus1
+    }
+
+    pub fn max_num_reorder_pics(&self) -> u8 {
+        self.max_num_reorder_pics
+    }
+
+    pub fn max_latency_increase_plus1(&self) -> u32 {
+        self.max_latency_increase_plus1
+    }
+}
+
+/// A H.265 Video Parameter Set, see 7.3.2.1. Only the parts relevant to the decoding of the base
+/// layer are parsed.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Vps {
+    /// Identifies the VPS for reference by other syntax elements.
+    video_parameter_set_id: u8,
+    /// Retains the same meaning as in the specification.
+    base_layer_internal_flag: bool,
+    /// Retains the same meaning as in the specification.
+    base_layer_available_flag: bool,
+    /// The maximum number of layers in the coded video sequences referring to this VPS, minus one.
+    max_layers_minus1: u8,
+    /// The maximum number of temporal sub-layers, minus one.
+    max_sub_layers_minus1: u8,
+    /// Whether inter prediction is additionally restricted for the coded video sequences.
+    temporal_id_nesting_flag: bool,
+    /// The profile, tier and level of the coded video sequences.
+    profile_tier_level: ProfileTierLevel,
+    /// The DPB requirements of each sub-layer.
+    sub_layer_ordering_info: [SubLayerOrderingInfo; MAX_SUB_LAYERS],
+}
+
+impl Vps {
+    pub fn video_parameter_set_id(&self) -> u8 {
+        self.video_parameter_set_id
+    }
+
+    pub fn base_layer_internal_flag(&self) -> bool {
+        self.base_layer_internal_flag
+    }
+
+    pub fn base_layer_available_flag(&self) -> bool {
+        self.base_layer_available_flag
+    }
+
+    pub fn max_layers_minus1(&self) -> u8 {
+        self.max_layers_minus1
+    }
+
+    pub fn max_sub_layers_minus1(&self) -> u8 {
+        self.max_sub_layers_minus1
+    }
+
+    pub fn temporal_id_nesting_flag(&self) -> bool {
+        self.temporal_id_nesting_flag
+    }
+
+    pub fn profile_tier_level(&self) -> &ProfileTierLevel {
+        &self.profile_tier_level
+    }
+
+    pub fn sub_layer_ordering_info(&self) -> &[SubLayerOrderingInfo; MAX_SUB_LAYERS] {
+        &self.sub_layer_ordering_info
+    }
+}
+
+/// Video usability information, see E.2.1. The hypothetical reference decoder parameters are
+/// skipped.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct VuiParams {
+    /// Retains the same meaning as in the specification.
+    aspect_ratio_info_present_flag: bool,
+    /// Specifies the sample aspect ratio of the luma samples, see Table E.1.
+    aspect_ratio_idc: u8,
+    /// The horizontal size of the sample aspect ratio, when aspect_ratio_idc is EXTENDED_SAR.
+    sar_width: u16,
+    /// The vertical size of the sample aspect ratio, when aspect_ratio_idc is EXTENDED_SAR.
+    sar_height: u16,
+    /// Retains the same meaning as in the specification.
+    overscan_info_present_flag: bool,
+    /// Retains the same meaning as in the specification.
+    overscan_appropriate_flag: bool,
+    /// Retains the same meaning as in the specification.
+    video_signal_type_present_flag: bool,
+    /// Indicates the representation of the pictures, see Table E.2.
+    video_format: u8,
+    /// Indicates the black level and range of the samples.
+    video_full_range_flag: bool,
+    /// Retains the same meaning as in the specification.
+    colour_description_present_flag: bool,
+    /// Indicates the chromaticity coordinates of the source primaries, see Table E.3.
+    colour_primaries: u8,
+    /// Indicates the opto-electronic transfer characteristic, see Table E.4.
+    transfer_characteristics: u8,
+    /// Describes the matrix coefficients used to derive the luma and chroma signals, see Table
+    /// E.5.
+    matrix_coeffs: u8,
+    /// Retains the same meaning as in the specification.
+    chroma_loc_info_present_flag: bool,
+    /// Specifies the location of chroma samples for the top field.
+    chroma_sample_loc_type_top_field: u32,
+    /// Specifies the location of chroma samples for the bottom field.
+    chroma_sample_loc_type_bottom_field: u32,
+    /// Whether all the chroma samples are equal to 1 << (BitDepthC - 1).
+    neutral_chroma_indication_flag: bool,
+    /// Whether the coded video sequence conveys pictures representing fields.
+    field_seq_flag: bool,
+    /// Whether picture timing SEI messages carry frame-field information.
+    frame_field_info_present_flag: bool,
+    /// Whether the default display window parameters follow.
+    default_display_window_flag: bool,
+    /// Retains the same meaning as in the specification.
+    def_disp_win_left_offset: u32,
+    /// Retains the same meaning as in the specification.
+    def_disp_win_right_offset: u32,
+    /// Retains the same meaning as in the specification.
+    def_disp_win_top_offset: u32,
+    /// Retains the same meaning as in the specification.
+    def_disp_win_bottom_offset: u32,
+    /// Retains the same meaning as in the specification.
+    timing_info_present_flag: bool,
+    /// The number of time units of a clock operating at time_scale Hz that corresponds to one
+    /// increment of a clock tick counter.
+    num_units_in_tick: u32,
+    /// The number of time units that pass in one second.
+    time_scale: u32,
+    /// Whether the picture order count is proportional to the output time.
+    poc_proportional_to_timing_flag: bool,
+    /// The number of clock ticks corresponding to a difference of picture order count values equal
+    /// to 1, minus one.
+    num_ticks_poc_diff_one_minus1: u32,
+    /// Whether hypothetical reference decoder parameters are present.
+    hrd_parameters_present_flag: bool,
+    /// Whether the bitstream restriction parameters follow.
+    bitstream_restriction_flag: bool,
+    /// Retains the same meaning as in the specification.
+    tiles_fixed_structure_flag: bool,
+    /// Retains the same meaning as in the specification.
+    motion_vectors_over_pic_boundaries_flag: bool,
+    /// Retains the same meaning as in the specification.
+    restricted_ref_pic_lists_flag: bool,
+    /// Retains the same meaning as in the specification.
+    min_spatial_segmentation_idc: u32,
+    /// Retains the same meaning as in the specification.
+    max_bytes_per_pic_denom: u32,
+    /// Retains the same meaning as in the specification.
+    max_bits_per_min_cu_denom: u32,
+    /// Retains the same meaning as in the specification.
+    log2_max_mv_length_horizontal: u32,
+    /// Retains the same meaning as in the specification.
+    log2_max_mv_length_vertical: u32,
+}
+
+impl VuiParams {
+    pub fn aspect_ratio_info_present_flag(&self) -> bool {
+        self.aspect_ratio_info_present_flag
+    }
+
+    pub fn aspect_ratio_idc(&self) -> u8 {
+        self.aspect_ratio_idc
+    }
+
+    pub fn sar_width(&self) -> u16 {
+        self.sar_width
+    }
+
+    pub fn sar_height(&self) -> u16 {
+        self.sar_height
+    }
+
+    pub fn overscan_info_present_flag(&self) -> bool {
+        self.overscan_info_present_flag
+    }
+
+    pub fn overscan_appropriate_flag(&self) -> bool {
+        self.overscan_appropriate_flag
+    }
+
+    pub fn video_signal_type_present_flag(&self) -> bool {
+        self.video_signal_type_present_flag
+    }
+
+    pub fn video_format(&self) -> u8 {
+        self.video_format
+    }
+
+    pub fn video_full_range_flag(&self) -> bool {
+        self.video_full_range_flag
+    }
+
+    pub fn colour_description_present_flag(&self) -> bool {
+        self.colour_description_present_flag
+    }
+
+    pub fn colour_primaries(&self) -> u8 {
+        self.colour_primaries
+    }
+
+    pub fn transfer_characteristics(&self) -> u8 {
+        self.transfer_characteristics
+    }
+
+    pub fn matrix_coeffs(&self) -> u8 {
+        self.matrix_coeffs
+    }
+
+    pub fn chroma_loc_info_present_flag(&self) -> bool {
+        self.chroma_loc_info_present_flag
+    }
+
+    pub fn chroma_sample_loc_type_top_field(&self) -> u32 {
+        self.chroma_sample_loc_type_top_field
+    }
+
+    pub fn chroma_sample_loc_type_bottom_field(&self) -> u32 {
+        self.chroma_sample_loc_type_bottom_field
+    }
+
+    pub fn neutral_chroma_indication_flag(&self) -> bool {
+        self.neutral_chroma_indication_flag
+    }
+
+    pub fn field_seq_flag(&self) -> bool {
+        self.field_seq_flag
+    }
+
+    pub fn frame_field_info_present_flag(&self) -> bool {
+        self.frame_field_info_present_flag
+    }
+
+    pub fn default_display_window_flag(&self) -> bool {
+        self.default_display_window_flag
+    }
+
+    pub fn def_disp_win_left_offset(&self) -> u32 {
+        self.def_disp_win_left_offset
+    }
+
+    pub fn def_disp_win_right_offset(&self) -> u32 {
+        self.def_disp_win_right_offset
+    }
+
+    pub fn def_disp_win_top_offset(&self) -> u32 {
+        self.def_disp_win_top_offset
+    }
+
+    pub fn def_disp_win_bottom_offset(&self) -> u32 {
+        self.def_disp_win_bottom_offset
+    }
+
+    pub fn timing_info_present_flag(&self) -> bool {
+        self.timing_info_present_flag
+    }
+
+    pub fn num_units_in_tick(&self) -> u32 {
+        self.num_units_in_tick
+    }
+
+    pub fn time_scale(&self) -> u32 {
+        self.time_scale
+    }
+
+    pub fn poc_proportional_to_timing_flag(&self) -> bool {
+        self.poc_proportional_to_timing_flag
+    }
+
+    pub fn num_ticks_poc_diff_one_minus1(&self) -> u32 {
+        self.num_ticks_poc_diff_one_minus1
+    }
+
+    pub fn hrd_parameters_present_flag(&self) -> bool {
+        self.hrd_parameters_present_flag
+    }
+
+    pub fn bitstream_restriction_flag(&self) -> bool {
+        self.bitstream_restriction_flag
+    }
+
+    pub fn tiles_fixed_structure_flag(&self) -> bool {
+        self.tiles_fixed_structure_flag
+    }
+
+    pub fn motion_vectors_over_pic_boundaries_flag(&self) -> bool {
+        self.motion_vectors_over_pic_boundaries_flag
+    }
+
+    pub fn restricted_ref_pic_lists_flag(&self) -> bool {
+        self.restricted_ref_pic_lists_flag
+    }
+
+    pub fn min_spatial_segmentation_idc(&self) -> u32 {
+        self.min_spatial_segmentation_idc
+    }
+
+    pub fn max_bytes_per_pic_denom(&self) -> u32 {
+        self.max_bytes_per_pic_denom
+    }
+
+    pub fn max_bits_per_min_cu_denom(&self) -> u32 {
+        self.max_bits_per_min_cu_denom
+    }
+
+    pub fn log2_max_mv_length_horizontal(&self) -> u32 {
+        self.log2_max_mv_length_horizontal
+    }
+
+    pub fn log2_max_mv_length_vertical(&self) -> u32 {
+        self.log2_max_mv_length_vertical
+    }
+}
+
+/// The SPS range extension, see 7.3.2.2.2.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SpsRangeExtension {
+    /// Retains the same meaning as in the specification.
+    transform_skip_rotation_enabled_flag: bool,
+    /// Retains the same meaning as in the specification.
+    transform_skip_context_enabled_flag: bool,
+    /// Retains the same meaning as in the specification.
+    implicit_rdpcm_enabled_flag: bool,
+    /// Retains the same meaning as in the specification.
+    explicit_rdpcm_enabled_flag: bool,
+    /// Retains the same meaning as in the specification.
+    extended_precision_processing_flag: bool,
+    /// Retains the same meaning as in the specification.
+    intra_smoothing_disabled_flag: bool,
+    /// Retains the same meaning as in the specification.
+    high_precision_offsets_enabled_flag: bool,
+    /// Retains the same meaning as in the specification.
+    persistent_rice_adaptation_enabled_flag: bool,
+    /// Retains the same meaning as in the specification.
+    cabac_bypass_alignment_enabled_flag: bool,
+}
+
+impl SpsRangeExtension {
+    pub fn transform_skip_rotation_enabled_flag(&self) -> bool {
+        self.transform_skip_rotation_enabled_flag
+    }
+
+    pub fn transform_skip_context_enabled_flag(&self) -> bool {
+        self.transform_skip_context_enabled_flag
+    }
+
+    pub fn implicit_rdpcm_enabled_flag(&self) -> bool {
+        self.implicit_rdpcm_enabled_flag
+    }
+
+    pub fn explicit_rdpcm_enabled_flag(&self) -> bool {
+        self.explicit_rdpcm_enabled_flag
+    }
+
+    pub fn extended_precision_processing_flag(&self) -> bool {
+        self.extended_precision_processing_flag
+    }
+
+    pub fn intra_smoothing_disabled_flag(&self) -> bool {
+        self.intra_smoothing_disabled_flag
+    }
+
+    pub fn high_precision_offsets_enabled_flag(&self) -> bool {
+        self.high_precision_offsets_enabled_flag
+    }
+
+    pub fn persistent_rice_adaptation_enabled_flag(&self) -> bool {
+        self.persistent_rice_adaptation_enabled_flag
+    }
+
+    pub fn cabac_bypass_alignment_enabled_flag(&self) -> bool {
+        self.cabac_bypass_alignment_enabled_flag
+    }
+}
+
+/// A H.265 Sequence Parameter Set, see 7.3.2.2. A syntax structure containing syntax elements that
+/// apply to zero or more entire coded layer video sequences.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Sps {
+    /// The VPS referred to by this SPS.
+    video_parameter_set_id: u8,
+    /// The maximum number of temporal sub-layers, minus one.
+    max_sub_layers_minus1: u8,
+    /// Whether inter prediction is additionally restricted for the coded video sequences.
+    temporal_id_nesting_flag: bool,
+    /// The profile, tier and level of the coded video sequences.
+    profile_tier_level: ProfileTierLevel,
+    /// Identifies the SPS for reference by other syntax elements.
+    seq_parameter_set_id: u8,
+    /// Specifies the chroma sampling relative to the luma sampling, see 6.2.
+    chroma_format_idc: u8,
+    /// Specifies whether the three colour components of the 4:4:4 chroma format are coded
+    /// separately.
+    separate_colour_plane_flag: bool,
+    /// The width of each decoded picture in units of luma samples.
+    pic_width_in_luma_samples: u16,
+    /// The height of each decoded picture in units of luma samples.
+    pic_height_in_luma_samples: u16,
+    /// Whether the conformance cropping window offsets follow.
+    conformance_window_flag: bool,
+    /// The left offset of the conformance window, in chroma sample units.
+    conf_win_left_offset: u32,
+    /// The right offset of the conformance window, in chroma sample units.
+    conf_win_right_offset: u32,
+    /// The top offset of the conformance window, in chroma sample units.
+    conf_win_top_offset: u32,
+    /// The bottom offset of the conformance window, in chroma sample units.
+    conf_win_bottom_offset: u32,
+    /// Specifies the bit depth of the samples of the luma array, minus 8.
+    bit_depth_luma_minus8: u8,
+    /// Specifies the bit depth of the samples of the chroma arrays, minus 8.
+    bit_depth_chroma_minus8: u8,
+    /// Specifies MaxPicOrderCntLsb = 2 ^ (log2_max_pic_order_cnt_lsb_minus4 + 4).
+    log2_max_pic_order_cnt_lsb_minus4: u8,
+    /// Whether the ordering information is signalled for every sub-layer.
+    sub_layer_ordering_info_present_flag: bool,
+    /// The DPB requirements of each sub-layer.
+    sub_layer_ordering_info: [SubLayerOrderingInfo; MAX_SUB_LAYERS],
+    /// The minimum luma coding block size, as log2 minus 3.
+    log2_min_luma_coding_block_size_minus3: u8,
+    /// The difference between the maximum and minimum luma coding block sizes, as log2.
+    log2_diff_max_min_luma_coding_block_size: u8,
+    /// The minimum luma transform block size, as log2 minus 2.
+    log2_min_luma_transform_block_size_minus2: u8,
+    /// The difference between the maximum and minimum luma transform block sizes, as log2.
+    log2_diff_max_min_luma_transform_block_size: u8,
+    /// The maximum hierarchy depth for transform units of inter coded coding units.
+    max_transform_hierarchy_depth_inter: u8,
+    /// The maximum hierarchy depth for transform units of intra coded coding units.
+    max_transform_hierarchy_depth_intra: u8,
+    /// Whether a scaling list is used for the scaling of transform coefficients.
+    scaling_list_enabled_flag: bool,
+    /// Whether the scaling lists are coded in the SPS, as opposed to using the default ones.
+    scaling_list_data_present_flag: bool,
+    /// The scaling lists, when scaling_list_enabled_flag is set.
+    scaling_lists: ScalingLists,
+    /// Whether asymmetric motion partitions may be used.
+    amp_enabled_flag: bool,
+    /// Whether the sample adaptive offset process is applied after the deblocking filter.
+    sample_adaptive_offset_enabled_flag: bool,
+    /// Whether PCM sample data may be present.
+    pcm_enabled_flag: bool,
+    /// The bit depth of the PCM luma samples, minus one.
+    pcm_sample_bit_depth_luma_minus1: u8,
+    /// The bit depth of the PCM chroma samples, minus one.
+    pcm_sample_bit_depth_chroma_minus1: u8,
+    /// The minimum size of coding blocks with PCM samples, as log2 minus 3.
+    log2_min_pcm_luma_coding_block_size_minus3: u8,
+    /// The difference between the maximum and minimum sizes of coding blocks with PCM samples.
+    log2_diff_max_min_pcm_luma_coding_block_size: u8,
+    /// Whether the in-loop filters are disabled for PCM samples.
+    pcm_loop_filter_disabled_flag: bool,
+    /// The short-term reference picture sets of the SPS.
+    short_term_ref_pic_sets: Vec<ShortTermRefPicSet>,
+    /// Whether long-term reference pictures may be used.
+    long_term_ref_pics_present_flag: bool,
+    /// The number of candidate long-term reference pictures specified in the SPS.
+    num_long_term_ref_pics_sps: u8,
+    /// The POC modulo MaxPicOrderCntLsb of the candidate long-term reference pictures.
+    lt_ref_pic_poc_lsb_sps: [u16; MAX_LONG_TERM_REF_PICS_SPS],
+    /// Whether the candidate long-term reference pictures are used by the current picture.
+    used_by_curr_pic_lt_sps_flag: [bool; MAX_LONG_TERM_REF_PICS_SPS],
+    /// Whether slice_temporal_mvp_enabled_flag is present in the slice headers.
+    temporal_mvp_enabled_flag: bool,
+    /// Whether bi-linear interpolation is conditionally used in the intra smoothing filter.
+    strong_intra_smoothing_enabled_flag: bool,
+    /// Whether the VUI parameters are present.
+    vui_parameters_present_flag: bool,
+    /// The VUI parameters.
+    vui_parameters: VuiParams,
+    /// Whether the SPS extensions are present.
+    extension_present_flag: bool,
+    /// Whether the range extension is present.
+    range_extension_flag: bool,
+    /// The range extension.
+    range_extension: SpsRangeExtension,
+}
+
+impl Sps {
+    pub fn video_parameter_set_id(&self) -> u8 {
+        self.video_parameter_set_id
+    }
+
+    pub fn max_sub_layers_minus1(&self) -> u8 {
+        self.max_sub_layers_minus1
+    }
+
+    pub fn temporal_id_nesting_flag(&self) -> bool {
+        self.temporal_id_nesting_flag
+    }
+
+    pub fn profile_tier_level(&self) -> &ProfileTierLevel {
+        &self.profile_tier_level
+    }
+
+    pub fn seq_parameter_set_id(&self) -> u8 {
+        self.seq_parameter_set_id
+    }
+
+    pub fn chroma_format_idc(&self) -> u8 {
+        self.chroma_format_idc
+    }
+
+    pub fn separate_colour_plane_flag(&self) -> bool {
+        self.separate_colour_plane_flag
+    }
+
+    pub fn pic_width_in_luma_samples(&self) -> u16 {
+        self.pic_width_in_luma_samples
+    }
+
+    pub fn pic_height_in_luma_samples(&self) -> u16 {
+        self.pic_height_in_luma_samples
+    }
+
+    pub fn conformance_window_flag(&self) -> bool {
+        self.conformance_window_flag
+    }
+
+    pub fn conf_win_left_offset(&self) -> u32 {
+        self.conf_win_left_offset
+    }
+
+    pub fn conf_win_right_offset(&self) -> u32 {
+        self.conf_win_right_offset
+    }
+
+    pub fn conf_win_top_offset(&self) -> u32 {
+        self.conf_win_top_offset
+    }
+
+    pub fn conf_win_bottom_offset(&self) -> u32 {
+        self.conf_win_bottom_offset
+    }
+
+    pub fn bit_depth_luma_minus8(&self) -> u8 {
+        self.bit_depth_luma_minus8
+    }
+
+    pub fn bit_depth_chroma_minus8(&self) -> u8 {
+        self.bit_depth_chroma_minus8
+    }
+
+    pub fn log2_max_pic_order_cnt_lsb_minus4(&self) -> u8 {
+        self.log2_max_pic_order_cnt_lsb_minus4
+    }
+
+    pub fn sub_layer_ordering_info_present_flag(&self) -> bool {
+        self.sub_layer_ordering_info_present_flag
+    }
+
+    pub fn sub_layer_ordering_info(&self) -> &[SubLayerOrderingInfo; MAX_SUB_LAYERS] {
+        &self.sub_layer_ordering_info
+    }
+
+    pub fn log2_min_luma_coding_block_size_minus3(&self) -> u8 {
+        self.log2_min_luma_coding_block_size_minus3
+    }
+
+    pub fn log2_diff_max_min_luma_coding_block_size(&self) -> u8 {
+        self.log2_diff_max_min_luma_coding_block_size
+    }
+
+    pub fn log2_min_luma_transform_block_size_minus2(&self) -> u8 {
+        self.log2_min_luma_transform_block_size_minus2
+    }
+
+    pub fn log2_diff_max_min_luma_transform_block_size(&self) -> u8 {
+        self.log2_diff_max_min_luma_transform_block_size
+    }
+
+    pub fn max_transform_hierarchy_depth_inter(&self) -> u8 {
+        self.max_transform_hierarchy_depth_inter
+    }
+
+    pub fn max_transform_hierarchy_depth_intra(&self) -> u8 {
+        self.max_transform_hierarchy_depth_intra
+    }
+
+    pub fn scaling_list_enabled_flag(&self) -> bool {
+        self.scaling_list_enabled_flag
+    }
+
+    pub fn scaling_list_data_present_flag(&self) -> bool {
+        self.scaling_list_data_present_flag
+    }
+
+    pub fn scaling_lists(&self) -> &ScalingLists {
+        &self.scaling_lists
+    }
+
+    pub fn amp_enabled_flag(&self) -> bool {
+        self.amp_enabled_flag
+    }
+
+    pub fn sample_adaptive_offset_enabled_flag(&self) -> bool {
+        self.sample_adaptive_offset_enabled_flag
+    }
+
+    pub fn pcm_enabled_flag(&self) -> bool {
+        self.pcm_enabled_flag
+    }
+
+    pub fn pcm_sample_bit_depth_luma_minus1(&self) -> u8 {
+        self.pcm_sample_bit_depth_luma_minus1
+    }
+
+    pub fn pcm_sample_bit_depth_chroma_minus1(&self) -> u8 {
+        self.pcm_sample_bit_depth_chroma_minus1
+    }
+
+    pub fn log2_min_pcm_luma_coding_block_size_minus3(&self) -> u8 {
+        self.log2_min_pcm_luma_coding_block_size_minus3
+    }
+
+    pub fn log2_diff_max_min_pcm_luma_coding_block_size(&self) -> u8 {
+        self.log2_diff_max_min_pcm_luma_coding_block_size
+    }
+
+    pub fn pcm_loop_filter_disabled_flag(&self) -> bool {
+        self.pcm_loop_filter_disabled_flag
+    }
+
+    pub fn short_term_ref_pic_sets(&self) -> &Vec<ShortTermRefPicSet> {
+        &self.short_term_ref_pic_sets
+    }
+
+    pub fn long_term_ref_pics_present_flag(&self) -> bool {
+        self.long_term_ref_pics_present_flag
+    }
+
+    pub fn num_long_term_ref_pics_sps(&self) -> u8 {
+        self.num_long_term_ref_pics_sps
+    }
+
+    pub fn lt_ref_pic_poc_lsb_sps(&self) -> [u16; MAX_LONG_TERM_REF_PICS_SPS] {
+        self.lt_ref_pic_poc_lsb_sps
+    }
+
+    pub fn used_by_curr_pic_lt_sps_flag(&self) -> [bool; MAX_LONG_TERM_REF_PICS_SPS] {
+        self.used_by_curr_pic_lt_sps_flag
+    }
+
+    pub fn temporal_mvp_enabled_flag(&self) -> bool {
+        self.temporal_mvp_enabled_flag
+    }
+
+    pub fn strong_intra_smoothing_enabled_flag(&self) -> bool {
+        self.strong_intra_smoothing_enabled_flag
+    }
+
+    pub fn vui_parameters_present_flag(&self) -> bool {
+        self.vui_parameters_present_flag
+    }
+
+    pub fn vui_parameters(&self) -> &VuiParams {
+        &self.vui_parameters
+    }
+
+    pub fn extension_present_flag(&self) -> bool {
+        self.extension_present_flag
+    }
+
+    pub fn range_extension_flag(&self) -> bool {
+        self.range_extension_flag
+    }
+
+    pub fn range_extension(&self) -> &SpsRangeExtension {
+        &self.range_extension
+    }
+    /// ChromaArrayType in the specification.
+    pub fn chroma_array_type(&self) -> u8 {
+        if self.separate_colour_plane_flag {
+            0
+        } else {
+            self.chroma_format_idc
+        }
+    }
+
+    /// The width of the decoded pictures, in luma samples.
+    pub fn width(&self) -> u32 {
+        u32::from(self.pic_width_in_luma_samples)
+    }
+
+    /// The height of the decoded pictures, in luma samples.
+    pub fn height(&self) -> u32 {
+        u32::from(self.pic_height_in_luma_samples)
+    }
+
+    /// SubWidthC and SubHeightC of Table 6-1.
+    fn chroma_subsampling(&self) -> (u32, u32) {
+        match self.chroma_array_type() {
+            1 => (2, 2),
+            2 => (2, 1),
+            _ => (1, 1),
+        }
+    }
+
+    /// The visible area of the decoded pictures, i.e. the conformance cropping window, as
+    /// `(x, y, width, height)` in luma samples.
+    pub fn visible_rectangle(&self) -> (u32, u32, u32, u32) {
+        if !self.conformance_window_flag {
+            return (0, 0, self.width(), self.height());
+        }
+
+        let (sub_width_c, sub_height_c) = self.chroma_subsampling();
+        let left = sub_width_c * self.conf_win_left_offset;
+        let right = sub_width_c * self.conf_win_right_offset;
+        let top = sub_height_c * self.conf_win_top_offset;
+        let bottom = sub_height_c * self.conf_win_bottom_offset;
+
+        (
+            left,
+            top,
+            self.width().saturating_sub(left + right),
+            self.height().saturating_sub(top + bottom),
+        )
+    }
+
+    /// BitDepthY in the specification.
+    pub fn bit_depth_luma(&self) -> u32 {
+        u32::from(self.bit_depth_luma_minus8) + 8
+    }
+
+    /// BitDepthC in the specification.
+    pub fn bit_depth_chroma(&self) -> u32 {
+        u32::from(self.bit_depth_chroma_minus8) + 8
+    }
+
+    /// MaxPicOrderCntLsb in the specification.
+    pub fn max_pic_order_cnt_lsb(&self) -> i32 {
+        1 << (self.log2_max_pic_order_cnt_lsb_minus4 + 4)
+    }
+
+    /// MinCbLog2SizeY in the specification.
+    pub fn min_cb_log2_size_y(&self) -> u32 {
+        u32::from(self.log2_min_luma_coding_block_size_minus3) + 3
+    }
+
+    /// CtbLog2SizeY in the specification.
+    pub fn ctb_log2_size_y(&self) -> u32 {
+        self.min_cb_log2_size_y() + u32::from(self.log2_diff_max_min_luma_coding_block_size)
+    }
+
+    /// PicWidthInCtbsY in the specification.
+    pub fn pic_width_in_ctbs_y(&self) -> u32 {
+        let ctb_size_y = 1 << self.ctb_log2_size_y();
+        (self.width() + ctb_size_y - 1) / ctb_size_y
+    }
+
+    /// PicHeightInCtbsY in the specification.
+    pub fn pic_height_in_ctbs_y(&self) -> u32 {
+        let ctb_size_y = 1 << self.ctb_log2_size_y();
+        (self.height() + ctb_size_y - 1) / ctb_size_y
+    }
+
+    /// PicSizeInCtbsY in the specification.
+    pub fn pic_size_in_ctbs_y(&self) -> u32 {
+        self.pic_width_in_ctbs_y() * self.pic_height_in_ctbs_y()
+    }
+
+    /// HighestTid, assuming all the temporal sub-layers are decoded.
+    pub fn highest_tid(&self) -> usize {
+        usize::from(self.max_sub_layers_minus1)
+    }
+
+    /// The number of picture storage buffers the DPB needs for decoding all the sub-layers.
+    pub fn max_dpb_size(&self) -> usize {
+        usize::from(self.sub_layer_ordering_info[self.highest_tid()].max_dec_pic_buffering_minus1)
+            + 1
+    }
+
+    /// sps_max_num_reorder_pics[HighestTid] in the specification.
+    pub fn max_num_reorder_pics(&self) -> u32 {
+        u32::from(self.sub_layer_ordering_info[self.highest_tid()].max_num_reorder_pics)
+    }
+
+    /// SpsMaxLatencyPictures[HighestTid] in the specification, or `None` if the latency is not
+    /// limited.
+    pub fn max_latency_pictures(&self) -> Option<u32> {
+        let info = &self.sub_layer_ordering_info[self.highest_tid()];
+        match info.max_latency_increase_plus1 {
+            0 => None,
+            plus1 => Some(u32::from(info.max_num_reorder_pics) + plus1 - 1),
+        }
+    }
+}
+
+/// The PPS range extension, see 7.3.2.3.2.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PpsRangeExtension {
+    /// The maximum transform block size for which transform_skip_flag may be present, as log2
+    /// minus 2.
+    log2_max_transform_skip_block_size_minus2: u8,
+    /// Whether log2_res_scale_abs_plus1 and res_scale_sign_flag may be present.
+    cross_component_prediction_enabled_flag: bool,
+    /// Whether cu_chroma_qp_offset_flag may be present.
+    chroma_qp_offset_list_enabled_flag: bool,
+    /// The difference between the luma CTB size and the minimum luma coding block size of the
+    /// coding units conveying cu_chroma_qp_offset_flag.
+    diff_cu_chroma_qp_offset_depth: u8,
+    /// The number of entries in the chroma QP offset lists, minus one.
+    chroma_qp_offset_list_len_minus1: u8,
+    /// The offsets used in the derivation of Qp'Cb.
+    cb_qp_offset_list: [i8; MAX_CHROMA_QP_OFFSET_LIST_LEN],
+    /// The offsets used in the derivation of Qp'Cr.
+    cr_qp_offset_list: [i8; MAX_CHROMA_QP_OFFSET_LIST_LEN],
+    /// The scaling of the luma sample adaptive offsets, as log2.
+    log2_sao_offset_scale_luma: u8,
+    /// The scaling of the chroma sample adaptive offsets, as log2.
+    log2_sao_offset_scale_chroma: u8,
+}
+
+impl PpsRangeExtension {
+    pub fn log2_max_transform_skip_block_size_minus2(&self) -> u8 {
+        self.log2_max_transform_skip_block_size_minus2
+    }
+
+    pub fn cross_component_prediction_enabled_flag(&self) -> bool {
+        self.cross_component_prediction_enabled_flag
+    }
+
+    pub fn chroma_qp_offset_list_enabled_flag(&self) -> bool {
+        self.chroma_qp_offset_list_enabled_flag
+    }
+
+    pub fn diff_cu_chroma_qp_offset_depth(&self) -> u8 {
+        self.diff_cu_chroma_qp_offset_depth
+    }
+
+    pub fn chroma_qp_offset_list_len_minus1(&self) -> u8 {
+        self.chroma_qp_offset_list_len_minus1
+    }
+
+    pub fn cb_qp_offset_list(&self) -> [i8; MAX_CHROMA_QP_OFFSET_LIST_LEN] {
+        self.cb_qp_offset_list
+    }
+
+    pub fn cr_qp_offset_list(&self) -> [i8; MAX_CHROMA_QP_OFFSET_LIST_LEN] {
+        self.cr_qp_offset_list
+    }
+
+    pub fn log2_sao_offset_scale_luma(&self) -> u8 {
+        self.log2_sao_offset_scale_luma
+    }
+
+    pub fn log2_sao_offset_scale_chroma(&self) -> u8 {
+        self.log2_sao_offset_scale_chroma
+    }
+}
+
+/// A H.265 Picture Parameter Set, see 7.3.2.3. A syntax structure containing syntax elements that
+/// apply to zero or more entire coded pictures.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Pps {
+    /// Identifies the PPS for reference by other syntax elements.
+    pic_parameter_set_id: u8,
+    /// The SPS referred to by this PPS.
+    seq_parameter_set_id: u8,
+    /// Whether dependent_slice_segment_flag is present in the slice segment headers.
+    dependent_slice_segments_enabled_flag: bool,
+    /// Whether pic_output_flag is present in the slice headers.
+    output_flag_present_flag: bool,
+    /// The number of extra slice header bits.
+    num_extra_slice_header_bits: u8,
+    /// Whether sign bit hiding is enabled.
+    sign_data_hiding_enabled_flag: bool,
+    /// Whether cabac_init_flag is present in the slice headers.
+    cabac_init_present_flag: bool,
+    /// The default number of active entries of reference picture list 0, minus one.
+    num_ref_idx_l0_default_active_minus1: u8,
+    /// The default number of active entries of reference picture list 1, minus one.
+    num_ref_idx_l1_default_active_minus1: u8,
+    /// The initial value of SliceQpY, minus 26.
+    init_qp_minus26: i8,
+    /// Whether constrained intra prediction is used.
+    constrained_intra_pred_flag: bool,
+    /// Whether transform_skip_flag may be present.
+    transform_skip_enabled_flag: bool,
+    /// Whether cu_qp_delta_abs may be present.
+    cu_qp_delta_enabled_flag: bool,
+    /// The difference between the luma CTB size and the minimum luma coding block size of the
+    /// coding units conveying cu_qp_delta_abs.
+    diff_cu_qp_delta_depth: u8,
+    /// The offset to the luma quantization parameter used for deriving Qp'Cb.
+    cb_qp_offset: i8,
+    /// The offset to the luma quantization parameter used for deriving Qp'Cr.
+    cr_qp_offset: i8,
+    /// Whether slice_cb_qp_offset and slice_cr_qp_offset are present in the slice headers.
+    slice_chroma_qp_offsets_present_flag: bool,
+    /// Whether weighted prediction is applied to P slices.
+    weighted_pred_flag: bool,
+    /// Whether weighted prediction is applied to B slices.
+    weighted_bipred_flag: bool,
+    /// Whether cu_transquant_bypass_flag may be present.
+    transquant_bypass_enabled_flag: bool,
+    /// Whether there is more than one tile in each picture.
+    tiles_enabled_flag: bool,
+    /// Whether entropy coding synchronization is used.
+    entropy_coding_sync_enabled_flag: bool,
+    /// The number of tile columns, minus one.
+    num_tile_columns_minus1: u8,
+    /// The number of tile rows, minus one.
+    num_tile_rows_minus1: u8,
+    /// Whether the tile boundaries are distributed uniformly across the picture.
+    uniform_spacing_flag: bool,
+    /// The width of each tile column in units of CTBs, minus one. Also derived when
+    /// uniform_spacing_flag is set.
+    column_width_minus1: [u16; MAX_TILE_COLUMNS],
+    /// The height of each tile row in units of CTBs, minus one. Also derived when
+    /// uniform_spacing_flag is set.
+    row_height_minus1: [u16; MAX_TILE_ROWS],
+    /// Whether the in-loop filters may be applied across tile boundaries.
+    loop_filter_across_tiles_enabled_flag: bool,
+    /// Whether the in-loop filters may be applied across slice boundaries.
+    loop_filter_across_slices_enabled_flag: bool,
+    /// Whether the deblocking filter control syntax elements are present.
+    deblocking_filter_control_present_flag: bool,
+    /// Whether deblocking_filter_override_flag is present in the slice headers.
+    deblocking_filter_override_enabled_flag: bool,
+    /// Whether the deblocking filter is disabled by default.
+    deblocking_filter_disabled_flag: bool,
+    /// The default deblocking parameter offset for beta, divided by 2.
+    beta_offset_div2: i8,
+    /// The default deblocking parameter offset for tC, divided by 2.
+    tc_offset_div2: i8,
+    /// Whether the scaling lists are coded in the PPS, as opposed to using those of the SPS.
+    scaling_list_data_present_flag: bool,
+    /// The scaling lists of the pictures referring to this PPS.
+    scaling_lists: ScalingLists,
+    /// Whether ref_pic_lists_modification may be present in the slice headers.
+    lists_modification_present_flag: bool,
+    /// Log2ParMrgLevel minus 2.
+    log2_parallel_merge_level_minus2: u8,
+    /// Whether the slice segment header extensions may be present.
+    slice_segment_header_extension_present_flag: bool,
+    /// Whether the PPS extensions are present.
+    extension_present_flag: bool,
+    /// Whether the range extension is present.
+    range_extension_flag: bool,
+    /// The range extension.
+    range_extension: PpsRangeExtension,
+}
+
+impl Pps {
+    pub fn pic_parameter_set_id(&self) -> u8 {
+        self.pic_parameter_set_id
+    }
+
+    pub fn seq_parameter_set_id(&self) -> u8 {
+        self.seq_parameter_set_id
+    }
+
+    pub fn dependent_slice_segments_enabled_flag(&self) -> bool {
+        self.dependent_slice_segments_enabled_flag
+    }
+
+    pub fn output_flag_present_flag(&self) -> bool {
+        self.output_flag_present_flag
+    }
+
+    pub fn num_extra_slice_header_bits(&self) -> u8 {
+        self.num_extra_slice_header_bits
+    }
+
+    pub fn sign_data_hiding_enabled_flag(&self) -> bool {
+        self.sign_data_hiding_enabled_flag
+    }
+
+    pub fn cabac_init_present_flag(&self) -> bool {
+        self.cabac_init_present_flag
+    }
+
+    pub fn num_ref_idx_l0_default_active_minus1(&self) -> u8 {
+        self.num_ref_idx_l0_default_active_minus1
+    }
+
+    pub fn num_ref_idx_l1_default_active_minus1(&self) -> u8 {
+        self.num_ref_idx_l1_default_active_minus1
+    }
+
+    pub fn init_qp_minus26(&self) -> i8 {
+        self.init_qp_minus26
+    }
+
+    pub fn constrained_intra_pred_flag(&self) -> bool {
+        self.constrained_intra_pred_flag
+    }
+
+    pub fn transform_skip_enabled_flag(&self) -> bool {
+        self.transform_skip_enabled_flag
+    }
+
+    pub fn cu_qp_delta_enabled_flag(&self) -> bool {
+        self.cu_qp_delta_enabled_flag
+    }
+
+    pub fn diff_cu_qp_delta_depth(&self) -> u8 {
+        self.diff_cu_qp_delta_depth
+    }
+
+    pub fn cb_qp_offset(&self) -> i8 {
+        self.cb_qp_offset
+    }
+
+    pub fn cr_qp_offset(&self) -> i8 {
+        self.cr_qp_offset
+    }
+
+    pub fn slice_chroma_qp_offsets_present_flag(&self) -> bool {
+        self.slice_chroma_qp_offsets_present_flag
+    }
+
+    pub fn weighted_pred_flag(&self) -> bool {
+        self.weighted_pred_flag
+    }
+
+    pub fn weighted_bipred_flag(&self) -> bool {
+        self.weighted_bipred_flag
+    }
+
+    pub fn transquant_bypass_enabled_flag(&self) -> bool {
+        self.transquant_bypass_enabled_flag
+    }
+
+    pub fn tiles_enabled_flag(&self) -> bool {
+        self.tiles_enabled_flag
+    }
+
+    pub fn entropy_coding_sync_enabled_flag(&self) -> bool {
+        self.entropy_coding_sync_enabled_flag
+    }
+
+    pub fn num_tile_columns_minus1(&self) -> u8 {
+        self.num_tile_columns_minus1
+    }
+
+    pub fn num_tile_rows_minus1(&self) -> u8 {
+        self.num_tile_rows_minus1
+    }
+
+    pub fn uniform_spacing_flag(&self) -> bool {
+        self.uniform_spacing_flag
+    }
+
+    pub fn column_width_minus1(&self) -> [u16; MAX_TILE_COLUMNS] {
+        self.column_width_minus1
+    }
+
+    pub fn row_height_minus1(&self) -> [u16; MAX_TILE_ROWS] {
+        self.row_height_minus1
+    }
+
+    pub fn loop_filter_across_tiles_enabled_flag(&self) -> bool {
+        self.loop_filter_across_tiles_enabled_flag
+    }
+
+    pub fn loop_filter_across_slices_enabled_flag(&self) -> bool {
+        self.loop_filter_across_slices_enabled_flag
+    }
+
+    pub fn deblocking_filter_control_present_flag(&self) -> bool {
+        self.deblocking_filter_control_present_flag
+    }
+
+    pub fn deblocking_filter_override_enabled_flag(&self) -> bool {
+        self.deblocking_filter_override_enabled_flag
+    }
+
+    pub fn deblocking_filter_disabled_flag(&self) -> bool {
+        self.deblocking_filter_disabled_flag
+    }
+
+    pub fn beta_offset_div2(&self) -> i8 {
+        self.beta_offset_div2
+    }
+
+    pub fn tc_offset_div2(&self) -> i8 {
+        self.tc_offset_div2
+    }
+
+    pub fn scaling_list_data_present_flag(&self) -> bool {
+        self.scaling_list_data_present_flag
+    }
+
+    pub fn scaling_lists(&self) -> &ScalingLists {
+        &self.scaling_lists
+    }
+
+    pub fn lists_modification_present_flag(&self) -> bool {
+        self.lists_modification_present_flag
+    }
+
+    pub fn log2_parallel_merge_level_minus2(&self) -> u8 {
+        self.log2_parallel_merge_level_minus2
+    }
+
+    pub fn slice_segment_header_extension_present_flag(&self) -> bool {
+        self.slice_segment_header_extension_present_flag
+    }
+
+    pub fn extension_present_flag(&self) -> bool {
+        self.extension_present_flag
+    }
+
+    pub fn range_extension_flag(&self) -> bool {
+        self.range_extension_flag
+    }
+
+    pub fn range_extension(&self) -> &PpsRangeExtension {
+        &self.range_extension
+    }
+}
+
+/// The prediction weight table of a slice, see 7.3.6.3. The delta values are kept as coded.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PredWeightTable {
+    /// The base 2 logarithm of the denominator for all luma weighting factors.
+    luma_log2_weight_denom: u8,
+    /// The difference of the base 2 logarithm of the denominator for all chroma weighting factors.
+    delta_chroma_log2_weight_denom: i8,
+    /// Whether the luma weighting factors are present for the entries of reference picture list 0.
+    luma_weight_l0_flag: [bool; MAX_REF_IDX_ACTIVE],
+    /// Whether the chroma weighting factors are present for the entries of reference picture list
+    /// 0.
+    chroma_weight_l0_flag: [bool; MAX_REF_IDX_ACTIVE],
+    /// The difference of the luma weighting factors for reference picture list 0.
+    delta_luma_weight_l0: [i8; MAX_REF_IDX_ACTIVE],
+    /// The additive luma offsets for reference picture list 0.
+    luma_offset_l0: [i16; MAX_REF_IDX_ACTIVE],
+    /// The difference of the chroma weighting factors for reference picture list 0.
+    delta_chroma_weight_l0: [[i8; 2]; MAX_REF_IDX_ACTIVE],
+    /// The difference of the additive chroma offsets for reference picture list 0.
+    delta_chroma_offset_l0: [[i16; 2]; MAX_REF_IDX_ACTIVE],
+    /// Whether the luma weighting factors are present for the entries of reference picture list 1.
+    luma_weight_l1_flag: [bool; MAX_REF_IDX_ACTIVE],
+    /// Whether the chroma weighting factors are present for the entries of reference picture list
+    /// 1.
+    chroma_weight_l1_flag: [bool; MAX_REF_IDX_ACTIVE],
+    /// The difference of the luma weighting factors for reference picture list 1.
+    delta_luma_weight_l1: [i8; MAX_REF_IDX_ACTIVE],
+    /// The additive luma offsets for reference picture list 1.
+    luma_offset_l1: [i16; MAX_REF_IDX_ACTIVE],
+    /// The difference of the chroma weighting factors for reference picture list 1.
+    delta_chroma_weight_l1: [[i8; 2]; MAX_REF_IDX_ACTIVE],
+    /// The difference of the additive chroma offsets for reference picture list 1.
+    delta_chroma_offset_l1: [[i16; 2]; MAX_REF_IDX_ACTIVE],
+}
+
+impl PredWeightTable {
+    pub fn luma_log2_weight_denom(&self) -> u8 {
+        self.luma_log2_weight_denom
+    }
+
+    pub fn delta_chroma_log2_weight_denom(&self) -> i8 {
+        self.delta_chroma_log2_weight_denom
+    }
+
+    pub fn luma_weight_l0_flag(&self) -> [bool; MAX_REF_IDX_ACTIVE] {
+        self.luma_weight_l0_flag
+    }
+
+    pub fn chroma_weight_l0_flag(&self) -> [bool; MAX_REF_IDX_ACTIVE] {
+        self.chroma_weight_l0_flag
+    }
+
+    pub fn delta_luma_weight_l0(&self) -> [i8; MAX_REF_IDX_ACTIVE] {
+        self.delta_luma_weight_l0
+    }
+
+    pub fn luma_offset_l0(&self) -> [i16; MAX_REF_IDX_ACTIVE] {
+        self.luma_offset_l0
+    }
+
+    pub fn delta_chroma_weight_l0(&self) -> [[i8; 2]; MAX_REF_IDX_ACTIVE] {
+        self.delta_chroma_weight_l0
+    }
+
+    pub fn delta_chroma_offset_l0(&self) -> [[i16; 2]; MAX_REF_IDX_ACTIVE] {
+        self.delta_chroma_offset_l0
+    }
+
+    pub fn luma_weight_l1_flag(&self) -> [bool; MAX_REF_IDX_ACTIVE] {
+        self.luma_weight_l1_flag
+    }
+
+    pub fn chroma_weight_l1_flag(&self) -> [bool; MAX_REF_IDX_ACTIVE] {
+        self.chroma_weight_l1_flag
+    }
+
+    pub fn delta_luma_weight_l1(&self) -> [i8; MAX_REF_IDX_ACTIVE] {
+        self.delta_luma_weight_l1
+    }
+
+    pub fn luma_offset_l1(&self) -> [i16; MAX_REF_IDX_ACTIVE] {
+        self.luma_offset_l1
+    }
+
+    pub fn delta_chroma_weight_l1(&self) -> [[i8; 2]; MAX_REF_IDX_ACTIVE] {
+        self.delta_chroma_weight_l1
+    }
+
+    pub fn delta_chroma_offset_l1(&self) -> [[i16; 2]; MAX_REF_IDX_ACTIVE] {
+        self.delta_chroma_offset_l1
+    }
+}
+
+/// A H.265 slice segment header, see 7.3.6.1. The values of dependent slice segments are taken
+/// from the preceding independent slice segment.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SliceHeader {
+    /// Whether this is the first slice segment of the picture in decoding order.
+    first_slice_segment_in_pic_flag: bool,
+    /// Affects the output of the previously decoded pictures after the decoding of an IDR or BLA
+    /// picture.
+    no_output_of_prior_pics_flag: bool,
+    /// The PPS in use.
+    pic_parameter_set_id: u8,
+    /// Whether the values of the slice segment header syntax elements are inferred from the
+    /// preceding independent slice segment.
+    dependent_slice_segment_flag: bool,
+    /// The address of the first CTB in the slice segment, in the coding tree block raster scan of
+    /// the picture.
+    segment_address: u32,
+    /// The coding type of the slice.
+    slice_type: SliceType,
+    /// Whether the picture is output.
+    pic_output_flag: bool,
+    /// The colour plane of the slice, when separate_colour_plane_flag is set.
+    colour_plane_id: u8,
+    /// The picture order count modulo MaxPicOrderCntLsb of the current picture.
+    pic_order_cnt_lsb: u16,
+    /// Whether the short-term reference picture set is one of the SPS.
+    short_term_ref_pic_set_sps_flag: bool,
+    /// The short-term reference picture set coded in the slice header, if any.
+    short_term_ref_pic_set: ShortTermRefPicSet,
+    /// The index of the SPS short-term reference picture set in use.
+    short_term_ref_pic_set_idx: u8,
+    /// The number of bits used by the short-term reference picture set coded in the slice header.
+    st_rps_bits: u32,
+    /// The number of long-term reference pictures derived from the SPS candidates.
+    num_long_term_sps: u8,
+    /// The number of long-term reference pictures signalled in the slice header.
+    num_long_term_pics: u8,
+    /// PocLsbLt in the specification.
+    poc_lsb_lt: [u16; MAX_LONG_TERM_REF_PICS],
+    /// UsedByCurrPicLt in the specification.
+    used_by_curr_pic_lt: [bool; MAX_LONG_TERM_REF_PICS],
+    /// Whether delta_poc_msb_cycle_lt is present.
+    delta_poc_msb_present_flag: [bool; MAX_LONG_TERM_REF_PICS],
+    /// DeltaPocMsbCycleLt in the specification.
+    delta_poc_msb_cycle_lt: [u32; MAX_LONG_TERM_REF_PICS],
+    /// Whether temporal motion vector predictors may be used.
+    temporal_mvp_enabled_flag: bool,
+    /// Whether the sample adaptive offset is enabled for the luma component.
+    sao_luma_flag: bool,
+    /// Whether the sample adaptive offset is enabled for the chroma components.
+    sao_chroma_flag: bool,
+    /// Whether the number of active reference indices is coded in the slice header.
+    num_ref_idx_active_override_flag: bool,
+    /// The number of active entries of reference picture list 0, minus one.
+    num_ref_idx_l0_active_minus1: u8,
+    /// The number of active entries of reference picture list 1, minus one.
+    num_ref_idx_l1_active_minus1: u8,
+    /// Whether reference picture list 0 is explicitly specified.
+    ref_pic_list_modification_flag_l0: bool,
+    /// The entries of reference picture list 0, as indices into RefPicListTemp0.
+    list_entry_l0: [u8; MAX_REF_IDX_ACTIVE],
+    /// Whether reference picture list 1 is explicitly specified.
+    ref_pic_list_modification_flag_l1: bool,
+    /// The entries of reference picture list 1, as indices into RefPicListTemp1.
+    list_entry_l1: [u8; MAX_REF_IDX_ACTIVE],
+    /// Whether the list 1 motion vector difference syntax structure is not parsed.
+    mvd_l1_zero_flag: bool,
+    /// The method for determining the initialization table used in the context variables
+    /// initialization process.
+    cabac_init_flag: bool,
+    /// Whether the collocated picture is derived from reference picture list 0.
+    collocated_from_l0_flag: bool,
+    /// The reference index of the collocated picture.
+    collocated_ref_idx: u8,
+    /// The prediction weight table.
+    pred_weight_table: PredWeightTable,
+    /// The maximum number of merging motion vector prediction candidates subtracted from 5.
+    five_minus_max_num_merge_cand: u8,
+    /// The initial value of QpY for the slice, relative to 26 + init_qp_minus26.
+    qp_delta: i8,
+    /// The difference to be added to pps_cb_qp_offset.
+    cb_qp_offset: i8,
+    /// The difference to be added to pps_cr_qp_offset.
+    cr_qp_offset: i8,
+    /// Whether cu_chroma_qp_offset_flag may be present.
+    cu_chroma_qp_offset_enabled_flag: bool,
+    /// Whether the deblocking parameters are present in the slice header.
+    deblocking_filter_override_flag: bool,
+    /// Whether the deblocking filter is disabled for the slice.
+    deblocking_filter_disabled_flag: bool,
+    /// The deblocking parameter offset for beta, divided by 2.
+    beta_offset_div2: i8,
+    /// The deblocking parameter offset for tC, divided by 2.
+    tc_offset_div2: i8,
+    /// Whether the in-loop filters may be applied across the left and upper slice boundaries.
+    loop_filter_across_slices_enabled_flag: bool,
+    /// The number of entry point offsets.
+    num_entry_point_offsets: u32,
+    /// The length of the entry point offsets, in bits, minus one.
+    offset_len_minus1: u8,
+    /// The entry point offsets, in bytes, minus one.
+    entry_point_offset_minus1: Vec<u32>,
+    /// NumPicTotalCurr in the specification.
+    num_pic_total_curr: u32,
+    /// The size of the slice segment header, including the NAL unit header, in bits.
+    header_bit_size: usize,
+    /// The number of emulation prevention bytes in the slice segment header.
+    n_emulation_prevention_bytes: usize,
+}
+
+impl SliceHeader {
+    pub fn first_slice_segment_in_pic_flag(&self) -> bool {
+        self.first_slice_segment_in_pic_flag
+    }
+
+    pub fn no_output_of_prior_pics_flag(&self) -> bool {
+        self.no_output_of_prior_pics_flag
+    }
+
+    pub fn pic_parameter_set_id(&self) -> u8 {
+        self.pic_parameter_set_id
+    }
+
+    pub fn dependent_slice_segment_flag(&self) -> bool {
+        self.dependent_slice_segment_flag
+    }
+
+    pub fn segment_address(&self) -> u32 {
+        self.segment_address
+    }
+
+    pub fn slice_type(&self) -> SliceType {
+        self.slice_type
+    }
+
+    pub fn pic_output_flag(&self) -> bool {
+        self.pic_output_flag
+    }
+
+    pub fn colour_plane_id(&self) -> u8 {
+        self.colour_plane_id
+    }
+
+    pub fn pic_order_cnt_lsb(&self) -> u16 {
+        self.pic_order_cnt_lsb
+    }
+
+    pub fn short_term_ref_pic_set_sps_flag(&self) -> bool {
+        self.short_term_ref_pic_set_sps_flag
+    }
+
+    pub fn short_term_ref_pic_set(&self) -> &ShortTermRefPicSet {
+        &self.short_term_ref_pic_set
+    }
+
+    pub fn short_term_ref_pic_set_idx(&self) -> u8 {
+        self.short_term_ref_pic_set_idx
+    }
+
+    pub fn st_rps_bits(&self) -> u32 {
+        self.st_rps_bits
+    }
+
+    pub fn num_long_term_sps(&self) -> u8 {
+        self.num_long_term_sps
+    }
+
+    pub fn num_long_term_pics(&self) -> u8 {
+        self.num_long_term_pics
+    }
+
+    pub fn poc_lsb_lt(&self) -> [u16; MAX_LONG_TERM_REF_PICS] {
+        self.poc_lsb_lt
+    }
+
+    pub fn used_by_curr_pic_lt(&self) -> [bool; MAX_LONG_TERM_REF_PICS] {
+        self.used_by_curr_pic_lt
+    }
+
+    pub fn delta_poc_msb_present_flag(&self) -> [bool; MAX_LONG_TERM_REF_PICS] {
+        self.delta_poc_msb_present_flag
+    }
+
+    pub fn delta_poc_msb_cycle_lt(&self) -> [u32; MAX_LONG_TERM_REF_PICS] {
+        self.delta_poc_msb_cycle_lt
+    }
+
+    pub fn temporal_mvp_enabled_flag(&self) -> bool {
+        self.temporal_mvp_enabled_flag
+    }
+
+    pub fn sao_luma_flag(&self) -> bool {
+        self.sao_luma_flag
+    }
+
+    pub fn sao_chroma_flag(&self) -> bool {
+        self.sao_chroma_flag
+    }
+
+    pub fn num_ref_idx_active_override_flag(&self) -> bool {
+        self.num_ref_idx_active_override_flag
+    }
+
+    pub fn num_ref_idx_l0_active_minus1(&self) -> u8 {
+        self.num_ref_idx_l0_active_minus1
+    }
+
+    pub fn num_ref_idx_l1_active_minus1(&self) -> u8 {
+        self.num_ref_idx_l1_active_minus1
+    }
+
+    pub fn ref_pic_list_modification_flag_l0(&self) -> bool {
+        self.ref_pic_list_modification_flag_l0
+    }
+
+    pub fn list_entry_l0(&self) -> [u8; MAX_REF_IDX_ACTIVE] {
+        self.list_entry_l0
+    }
+
+    pub fn ref_pic_list_modification_flag_l1(&self) -> bool {
+        self.ref_pic_list_modification_flag_l1
+    }
+
+    pub fn list_entry_l1(&self) -> [u8; MAX_REF_IDX_ACTIVE] {
+        self.list_entry_l1
+    }
+
+    pub fn mvd_l1_zero_flag(&self) -> bool {
+        self.mvd_l1_zero_flag
+    }
+
+    pub fn cabac_init_flag(&self) -> bool {
+        self.cabac_init_flag
+    }
+
+    pub fn collocated_from_l0_flag(&self) -> bool {
+        self.collocated_from_l0_flag
+    }
+
+    pub fn collocated_ref_idx(&self) -> u8 {
+        self.collocated_ref_idx
+    }
+
+    pub fn pred_weight_table(&self) -> &PredWeightTable {
+        &self.pred_weight_table
+    }
+
+    pub fn five_minus_max_num_merge_cand(&self) -> u8 {
+        self.five_minus_max_num_merge_cand
+    }
+
+    pub fn qp_delta(&self) -> i8 {
+        self.qp_delta
+    }
+
+    pub fn cb_qp_offset(&self) -> i8 {
+        self.cb_qp_offset
+    }
+
+    pub fn cr_qp_offset(&self) -> i8 {
+        self.cr_qp_offset
+    }
+
+    pub fn cu_chroma_qp_offset_enabled_flag(&self) -> bool {
+        self.cu_chroma_qp_offset_enabled_flag
+    }
+
+    pub fn deblocking_filter_override_flag(&self) -> bool {
+        self.deblocking_filter_override_flag
+    }
+
+    pub fn deblocking_filter_disabled_flag(&self) -> bool {
+        self.deblocking_filter_disabled_flag
+    }
+
+    pub fn beta_offset_div2(&self) -> i8 {
+        self.beta_offset_div2
+    }
+
+    pub fn tc_offset_div2(&self) -> i8 {
+        self.tc_offset_div2
+    }
+
+    pub fn loop_filter_across_slices_enabled_flag(&self) -> bool {
+        self.loop_filter_across_slices_enabled_flag
+    }
+
+    pub fn num_entry_point_offsets(&self) -> u32 {
+        self.num_entry_point_offsets
+    }
+
+    pub fn offset_len_minus1(&self) -> u8 {
+        self.offset_len_minus1
+    }
+
+    pub fn entry_point_offset_minus1(&self) -> &Vec<u32> {
+        &self.entry_point_offset_minus1
+    }
+
+    pub fn num_pic_total_curr(&self) -> u32 {
+        self.num_pic_total_curr
+    }
+
+    pub fn header_bit_size(&self) -> usize {
+        self.header_bit_size
+    }
+
+    pub fn n_emulation_prevention_bytes(&self) -> usize {
+        self.n_emulation_prevention_bytes
+    }
+    /// The short-term reference picture set of the current picture, which is either coded in the
+    /// slice header or one of the SPS.
+    pub fn curr_short_term_ref_pic_set<'a>(&'a self, sps: &'a Sps) -> &'a ShortTermRefPicSet {
+        if self.short_term_ref_pic_set_sps_flag {
+            &sps.short_term_ref_pic_sets[usize::from(self.short_term_ref_pic_set_idx)]
+        } else {
+            &self.short_term_ref_pic_set
+        }
+    }
+}
+
+/// A H.265 slice segment. An integer number of CTUs contained in a single NAL unit.
+#[derive(Debug)]
+pub struct Slice<T> {
+    /// The slice segment header.
+    header: SliceHeader,
+    /// The NAL unit backing this slice segment.
+    nalu: Nalu<T>,
+}
+
+impl<T> Slice<T> {
+    /// Get a reference to the slice's header.
+    pub fn header(&self) -> &SliceHeader {
+        &self.header
+    }
+
+    /// Get a reference to the slice's nalu.
+    pub fn nalu(&self) -> &Nalu<T> {
+        &self.nalu
+    }
+}
+
+/// A H.265 NAL unit header, see 7.3.1.2.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NaluHeader {
+    type_: NaluType,
+    nuh_layer_id: u8,
+    nuh_temporal_id_plus1: u8,
+    header_bytes: usize,
+}
+
+impl NaluHeader {
+    /// Get a reference to the nalu header's type.
+    pub fn nalu_type(&self) -> &NaluType {
+        &self.type_
+    }
+
+    /// Get the nalu header's layer id.
+    pub fn nuh_layer_id(&self) -> u8 {
+        self.nuh_layer_id
+    }
+
+    /// Get the nalu header's temporal id plus one.
+    pub fn nuh_temporal_id_plus1(&self) -> u8 {
+        self.nuh_temporal_id_plus1
+    }
+
+    /// TemporalId in the specification.
+    pub fn temporal_id(&self) -> u8 {
+        self.nuh_temporal_id_plus1.saturating_sub(1)
+    }
+
+    /// Get a reference to the nalu header's header bytes.
+    pub fn header_bytes(&self) -> usize {
+        self.header_bytes
+    }
+}
+
+#[derive(Debug)]
+pub struct Nalu<T> {
+    header: NaluHeader,
+    /// The mapping that backs this NALU. Possibly shared with the other NALUs
+    /// in the Access Unit.
+    data: T,
+
+    size: usize,
+    offset: usize,
+    sc_offset: usize,
+}
+
+impl<T: AsRef<[u8]>> Nalu<T> {
+    fn find_start_code(data: &mut Cursor<T>, offset: usize) -> Option<usize> {
+        // discard all zeroes until the start code pattern is found
+        data.get_ref().as_ref()[offset..]
+            .windows(3)
+            .position(|window| window == [0x00, 0x00, 0x01])
+    }
+
+    fn parse_header(data: &mut Cursor<T>) -> Result<NaluHeader> {
+        if data.remaining() < 2 {
+            return Err(anyhow!("Broken Data"));
+        }
+
+        let header = u16::from_be_bytes([data.chunk()[0], data.chunk()[1]]);
+
+        if header & 0x8000 != 0 {
+            return Err(anyhow!("Broken Data: forbidden_zero_bit is set"));
+        }
+
+        let type_ = NaluType::n((header >> 9) & 0x3f).ok_or(anyhow!("Broken Data"))?;
+        let nuh_layer_id = ((header >> 3) & 0x3f) as u8;
+        let nuh_temporal_id_plus1 = (header & 0x7) as u8;
+
+        if nuh_temporal_id_plus1 == 0 {
+            return Err(anyhow!("Broken Data: nuh_temporal_id_plus1 is zero"));
+        }
+
+        Ok(NaluHeader {
+            type_,
+            nuh_layer_id,
+            nuh_temporal_id_plus1,
+            header_bytes: 2,
+        })
+    }
+
+    /// Find the next Annex B encoded NAL unit.
+    pub fn next(cursor: &mut Cursor<T>, bitstream: T) -> Result<Option<Nalu<T>>> {
+        let pos = usize::try_from(cursor.position())?;
+
+        // Find the start code for this NALU
+        let current_nalu_offset = match Nalu::find_start_code(cursor, pos) {
+            Some(offset) => offset,
+            None => return Err(anyhow!("No NAL found")),
+        };
+
+        let mut start_code_offset = pos + current_nalu_offset;
+
+        // If the preceding byte is 00, then we actually have a four byte SC,
+        // i.e. 00 00 00 01 Where the first 00 is the "zero_byte()"
+        if start_code_offset > 0 && cursor.get_ref().as_ref()[start_code_offset - 1] == 00 {
+            start_code_offset -= 1;
+        }
+
+        // The NALU offset is its offset + 3 bytes to skip the start code.
+        let nalu_offset = pos + current_nalu_offset + 3;
+
+        // Set the bitstream position to the start of the current NALU
+        cursor.set_position(u64::try_from(nalu_offset)?);
+
+        let hdr = Nalu::parse_header(cursor)?;
+
+        // Find the start of the subsequent NALU.
+        let mut next_nalu_offset = match Nalu::find_start_code(cursor, nalu_offset) {
+            Some(offset) => offset,
+            None => cursor.chunk().len(), // Whatever data is left must be part of the current NALU
+        };
+
+        while next_nalu_offset > 0
+            && cursor.get_ref().as_ref()[nalu_offset + next_nalu_offset - 1] == 00
+        {
+            // Discard trailing_zero_8bits
+            next_nalu_offset -= 1;
+        }
+
+        let nal_size = match hdr.type_ {
+            NaluType::EosNut | NaluType::EobNut => hdr.header_bytes,
+            _ => next_nalu_offset,
+        };
+
+        Ok(Some(Nalu {
+            header: hdr,
+            data: bitstream,
+            size: nal_size,
+            offset: nalu_offset,
+            sc_offset: start_code_offset,
+        }))
+    }
+
+    /// Get a reference to the nalu's header.
+    pub fn header(&self) -> &NaluHeader {
+        &self.header
+    }
+
+    /// Get a reference to the nalu's data.
+    pub fn data(&self) -> &T {
+        &self.data
+    }
+
+    /// Get a reference to the nalu's size.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Get a reference to the nalu's offset.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Get a reference to the nalu's sc offset.
+    pub fn sc_offset(&self) -> usize {
+        self.sc_offset
+    }
+}
+
+impl<T: AsRef<[u8]>> AsRef<[u8]> for Nalu<T> {
+    fn as_ref(&self) -> &[u8] {
+        let data = self.data.as_ref();
+        &data[self.offset..self.offset + self.size]
+    }
+}
+
+/// Ceil(Log2(`value`)), which is used to size several fixed-length syntax elements.
+fn ceil_log2(value: u32) -> usize {
+    if value <= 1 {
+        0
+    } else {
+        (32 - (value - 1).leading_zeros()) as usize
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Parser {
+    active_vpses: BTreeMap<u8, Vps>,
+    active_spses: BTreeMap<u8, Sps>,
+    active_ppses: BTreeMap<u8, Pps>,
+    /// The header of the last independent slice segment, from which the dependent slice segments
+    /// inherit their values.
+    last_independent_slice_header: Option<SliceHeader>,
+}
+
+impl Parser {
+    /// Reads a 32-bit value, which `NaluReader` cannot do in a single read.
+    fn read_u32<T: AsRef<[u8]>>(r: &mut NaluReader<T>) -> Result<u32> {
+        let high = r.read_bits::<u32>(16)?;
+        let low = r.read_bits::<u32>(16)?;
+        Ok((high << 16) | low)
+    }
+
+    fn parse_profile_tier_level<T: AsRef<[u8]>>(
+        r: &mut NaluReader<T>,
+        ptl: &mut ProfileTierLevel,
+        max_sub_layers_minus1: u8,
+    ) -> Result<()> {
+        ptl.general_profile_space = r.read_bits(2)?;
+        ptl.general_tier_flag = r.read_bit()?;
+        ptl.general_profile_idc = r.read_bits(5)?;
+        ptl.general_profile_compatibility_flags = Parser::read_u32(r)?;
+        ptl.general_progressive_source_flag = r.read_bit()?;
+        ptl.general_interlaced_source_flag = r.read_bit()?;
+        ptl.general_non_packed_constraint_flag = r.read_bit()?;
+        ptl.general_frame_only_constraint_flag = r.read_bit()?;
+        // The constraint flags and reserved bits, followed by general_inbld_flag.
+        r.skip_bits(22)?;
+        r.skip_bits(22)?;
+        ptl.general_level_idc = r.read_bits(8)?;
+
+        let num_sub_layers = usize::from(max_sub_layers_minus1);
+        let mut sub_layer_profile_present_flag = [false; MAX_SUB_LAYERS];
+        let mut sub_layer_level_present_flag = [false; MAX_SUB_LAYERS];
+
+        for i in 0..num_sub_layers {
+            sub_layer_profile_present_flag[i] = r.read_bit()?;
+            sub_layer_level_present_flag[i] = r.read_bit()?;
+        }
+
+        if num_sub_layers > 0 {
+            for _ in num_sub_layers..8 {
+                // reserved_zero_2bits
+                r.skip_bits(2)?;
+            }
+        }
+
+        for i in 0..num_sub_layers {
+            if sub_layer_profile_present_flag[i] {
+                // The sub-layer profile space, tier, profile, compatibility and constraint flags.
+                for _ in 0..4 {
+                    r.skip_bits(22)?;
+                }
+            }
+
+            if sub_layer_level_present_flag[i] {
+                r.skip_bits(8)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn parse_sub_layer_ordering_info<T: AsRef<[u8]>>(
+        r: &mut NaluReader<T>,
+        info: &mut [SubLayerOrderingInfo; MAX_SUB_LAYERS],
+        max_sub_layers_minus1: u8,
+        present_flag: bool,
+    ) -> Result<()> {
+        let max_sub_layers_minus1 = usize::from(max_sub_layers_minus1);
+        let first = if present_flag {
+            0
+        } else {
+            max_sub_layers_minus1
+        };
+
+        for i in first..=max_sub_layers_minus1 {
+            let max_dec_pic_buffering_minus1 = r.read_ue_max(MAX_DPB_SIZE as u32 - 1)?;
+            info[i] = SubLayerOrderingInfo {
+                max_dec_pic_buffering_minus1,
+                max_num_reorder_pics: r.read_ue_max(u32::from(max_dec_pic_buffering_minus1))?,
+                max_latency_increase_plus1: r.read_ue_max(u32::MAX - 1)?,
+            };
+        }
+
+        // When not present, the values of the lower sub-layers are inferred to be those of the
+        // highest one.
+        for i in 0..first {
+            info[i] = info[max_sub_layers_minus1];
+        }
+
+        Ok(())
+    }
+
+    fn parse_scaling_list_data<T: AsRef<[u8]>>(
+        r: &mut NaluReader<T>,
+        lists: &mut ScalingLists,
+    ) -> Result<()> {
+        let mut defaults = ScalingLists::default_lists();
+
+        for size_id in 0..4 {
+            let step = if size_id == 3 { 3 } else { 1 };
+
+            for matrix_id in (0..6).step_by(step) {
+                let scaling_list_pred_mode_flag = r.read_bit()?;
+
+                if !scaling_list_pred_mode_flag {
+                    let delta: usize = r.read_ue_max((matrix_id / step) as u32)?;
+
+                    if delta == 0 {
+                        let default = defaults.list_mut(size_id, matrix_id).to_vec();
+                        lists.list_mut(size_id, matrix_id).copy_from_slice(&default);
+                        if let Some(dc) = lists.dc_coef_mut(size_id, matrix_id) {
+                            *dc = 16;
+                        }
+                    } else {
+                        let ref_matrix_id = matrix_id - delta * step;
+                        let list = lists.list_mut(size_id, ref_matrix_id).to_vec();
+                        lists.list_mut(size_id, matrix_id).copy_from_slice(&list);
+                        if let Some(ref_dc) = lists.dc_coef_mut(size_id, ref_matrix_id) {
+                            let ref_dc = *ref_dc;
+                            *lists.dc_coef_mut(size_id, matrix_id).unwrap() = ref_dc;
+                        }
+                    }
+                } else {
+                    let mut next_coef = 8i32;
+
+                    if size_id > 1 {
+                        let dc_coef_minus8: i32 = r.read_se_bounded(-7, 247)?;
+                        next_coef = dc_coef_minus8 + 8;
+                        *lists.dc_coef_mut(size_id, matrix_id).unwrap() = next_coef as u8;
+                    }
+
+                    for coef in lists.list_mut(size_id, matrix_id).iter_mut() {
+                        let delta: i32 = r.read_se_bounded(-128, 127)?;
+                        next_coef = (next_coef + delta + 256) % 256;
+                        *coef = next_coef as u8;
+                    }
+                }
+            }
+        }
+
+        // The 32x32 chroma lists, which are used with ChromaArrayType == 3, are inferred from the
+        // 16x16 ones, see 7.4.5.
+        for matrix_id in [1, 2, 4, 5] {
+            lists.scaling_list_32x32[matrix_id] = lists.scaling_list_16x16[matrix_id];
+            lists.scaling_list_dc_coef_32x32[matrix_id] =
+                lists.scaling_list_dc_coef_16x16[matrix_id];
+        }
+
+        Ok(())
+    }
+
+    fn push_short_term_ref_pic<'a>(
+        pics: (
+            &'a mut [i32; MAX_SHORT_TERM_REF_PICS],
+            &'a mut [bool; MAX_SHORT_TERM_REF_PICS],
+        ),
+        count: &mut usize,
+        delta_poc: i32,
+        used_by_curr_pic: bool,
+    ) -> Result<()> {
+        if *count >= MAX_SHORT_TERM_REF_PICS {
+            return Err(anyhow!(
+                "Broken Data: too many pictures in the short-term RPS"
+            ));
+        }
+
+        pics.0[*count] = delta_poc;
+        pics.1[*count] = used_by_curr_pic;
+        *count += 1;
+
+        Ok(())
+    }
+
+    /// Parses the short-term reference picture set of index `idx`, possibly predicting it from
+    /// one of `sets`. `idx` is equal to `sets.len()` for the set coded in a slice header.
+    fn parse_short_term_ref_pic_set<T: AsRef<[u8]>>(
+        r: &mut NaluReader<T>,
+        sets: &[ShortTermRefPicSet],
+        idx: usize,
+        max_dec_pic_buffering_minus1: u8,
+    ) -> Result<ShortTermRefPicSet> {
+        let mut rps = ShortTermRefPicSet::default();
+
+        if idx != 0 {
+            rps.inter_ref_pic_set_prediction_flag = r.read_bit()?;
+        }
+
+        if rps.inter_ref_pic_set_prediction_flag {
+            if idx == sets.len() {
+                rps.delta_idx_minus1 = r.read_ue_max(idx as u32 - 1)?;
+            }
+
+            rps.delta_rps_sign = r.read_bit()?;
+            rps.abs_delta_rps_minus1 = r.read_ue_max((1 << 15) - 1)?;
+
+            let ref_rps = &sets[idx - (usize::from(rps.delta_idx_minus1) + 1)];
+            let delta_rps =
+                (1 - 2 * i32::from(rps.delta_rps_sign)) * (i32::from(rps.abs_delta_rps_minus1) + 1);
+
+            let ref_num_negative = usize::from(ref_rps.num_negative_pics);
+            let ref_num_positive = usize::from(ref_rps.num_positive_pics);
+            let ref_num_delta_pocs = ref_num_negative + ref_num_positive;
+
+            let mut used_by_curr_pic_flag = [false; MAX_SHORT_TERM_REF_PICS + 1];
+            let mut use_delta_flag = [true; MAX_SHORT_TERM_REF_PICS + 1];
+
+            for j in 0..=ref_num_delta_pocs {
+                used_by_curr_pic_flag[j] = r.read_bit()?;
+                if !used_by_curr_pic_flag[j] {
+                    use_delta_flag[j] = r.read_bit()?;
+                }
+            }
+
+            // Equation 7-61.
+            let mut i = 0;
+            for j in (0..ref_num_positive).rev() {
+                let d_poc = ref_rps.delta_poc_s1[j] + delta_rps;
+                if d_poc < 0 && use_delta_flag[ref_num_negative + j] {
+                    Parser::push_short_term_ref_pic(
+                        (&mut rps.delta_poc_s0, &mut rps.used_by_curr_pic_s0),
+                        &mut i,
+                        d_poc,
+                        used_by_curr_pic_flag[ref_num_negative + j],
+                    )?;
+                }
+            }
+            if delta_rps < 0 && use_delta_flag[ref_num_delta_pocs] {
+                Parser::push_short_term_ref_pic(
+                    (&mut rps.delta_poc_s0, &mut rps.used_by_curr_pic_s0),
+                    &mut i,
+                    delta_rps,
+                    used_by_curr_pic_flag[ref_num_delta_pocs],
+                )?;
+            }
+            for j in 0..ref_num_negative {
+                let d_poc = ref_rps.delta_poc_s0[j] + delta_rps;
+                if d_poc < 0 && use_delta_flag[j] {
+                    Parser::push_short_term_ref_pic(
+                        (&mut rps.delta_poc_s0, &mut rps.used_by_curr_pic_s0),
+                        &mut i,
+                        d_poc,
+                        used_by_curr_pic_flag[j],
+                    )?;
+                }
+            }
+            rps.num_negative_pics = i as u8;
+
+            // Equation 7-62.
+            let mut i = 0;
+            for j in (0..ref_num_negative).rev() {
+                let d_poc = ref_rps.delta_poc_s0[j] + delta_rps;
+                if d_poc > 0 && use_delta_flag[j] {
+                    Parser::push_short_term_ref_pic(
+                        (&mut rps.delta_poc_s1, &mut rps.used_by_curr_pic_s1),
+                        &mut i,
+                        d_poc,
+                        used_by_curr_pic_flag[j],
+                    )?;
+                }
+            }
+            if delta_rps > 0 && use_delta_flag[ref_num_delta_pocs] {
+                Parser::push_short_term_ref_pic(
+                    (&mut rps.delta_poc_s1, &mut rps.used_by_curr_pic_s1),
+                    &mut i,
+                    delta_rps,
+                    used_by_curr_pic_flag[ref_num_delta_pocs],
+                )?;
+            }
+            for j in 0..ref_num_positive {
+                let d_poc = ref_rps.delta_poc_s1[j] + delta_rps;
+                if d_poc > 0 && use_delta_flag[ref_num_negative + j] {
+                    Parser::push_short_term_ref_pic(
+                        (&mut rps.delta_poc_s1, &mut rps.used_by_curr_pic_s1),
+                        &mut i,
+                        d_poc,
+                        used_by_curr_pic_flag[ref_num_negative + j],
+                    )?;
+                }
+            }
+            rps.num_positive_pics = i as u8;
+        } else {
+            let max = u32::from(max_dec_pic_buffering_minus1);
+            rps.num_negative_pics = r.read_ue_max(max)?;
+            rps.num_positive_pics = r.read_ue_max(max - u32::from(rps.num_negative_pics))?;
+
+            let mut poc = 0;
+            for i in 0..usize::from(rps.num_negative_pics) {
+                let delta_poc_s0_minus1: i32 = r.read_ue_max((1 << 15) - 1)?;
+                poc -= delta_poc_s0_minus1 + 1;
+                rps.delta_poc_s0[i] = poc;
+                rps.used_by_curr_pic_s0[i] = r.read_bit()?;
+            }
+
+            let mut poc = 0;
+            for i in 0..usize::from(rps.num_positive_pics) {
+                let delta_poc_s1_minus1: i32 = r.read_ue_max((1 << 15) - 1)?;
+                poc += delta_poc_s1_minus1 + 1;
+                rps.delta_poc_s1[i] = poc;
+                rps.used_by_curr_pic_s1[i] = r.read_bit()?;
+            }
+        }
+
+        Ok(rps)
+    }
+
+    fn parse_sub_layer_hrd_parameters<T: AsRef<[u8]>>(
+        r: &mut NaluReader<T>,
+        cpb_cnt_minus1: u32,
+        sub_pic_hrd_params_present_flag: bool,
+    ) -> Result<()> {
+        for _ in 0..=cpb_cnt_minus1 {
+            // bit_rate_value_minus1 and cpb_size_value_minus1.
+            r.read_ue::<u32>()?;
+            r.read_ue::<u32>()?;
+            if sub_pic_hrd_params_present_flag {
+                // cpb_size_du_value_minus1 and bit_rate_du_value_minus1.
+                r.read_ue::<u32>()?;
+                r.read_ue::<u32>()?;
+            }
+            // cbr_flag
+            r.skip_bits(1)?;
+        }
+
+        Ok(())
+    }
+
+    /// Parses hrd_parameters() as in E.2.2 in order to skip over it, since the decoding process
+    /// does not use it.
+    fn parse_hrd_parameters<T: AsRef<[u8]>>(
+        r: &mut NaluReader<T>,
+        max_sub_layers_minus1: u8,
+    ) -> Result<()> {
+        let nal_hrd_parameters_present_flag = r.read_bit()?;
+        let vcl_hrd_parameters_present_flag = r.read_bit()?;
+        let mut sub_pic_hrd_params_present_flag = false;
+
+        if nal_hrd_parameters_present_flag || vcl_hrd_parameters_present_flag {
+            sub_pic_hrd_params_present_flag = r.read_bit()?;
+            if sub_pic_hrd_params_present_flag {
+                // tick_divisor_minus2, du_cpb_removal_delay_increment_length_minus1,
+                // sub_pic_cpb_params_in_pic_timing_sei_flag and
+                // dpb_output_delay_du_length_minus1.
+                r.skip_bits(19)?;
+            }
+            // bit_rate_scale and cpb_size_scale.
+            r.skip_bits(8)?;
+            if sub_pic_hrd_params_present_flag {
+                // cpb_size_du_scale
+                r.skip_bits(4)?;
+            }
+            // initial_cpb_removal_delay_length_minus1, au_cpb_removal_delay_length_minus1 and
+            // dpb_output_delay_length_minus1.
+            r.skip_bits(15)?;
+        }
+
+        for _ in 0..=max_sub_layers_minus1 {
+            let fixed_pic_rate_general_flag = r.read_bit()?;
+            let fixed_pic_rate_within_cvs_flag = if !fixed_pic_rate_general_flag {
+                r.read_bit()?
+            } else {
+                true
+            };
+
+            let mut low_delay_hrd_flag = false;
+            if fixed_pic_rate_within_cvs_flag {
+                // elemental_duration_in_tc_minus1
+                r.read_ue_max::<u32>(2047)?;
+            } else {
+                low_delay_hrd_flag = r.read_bit()?;
+            }
+
+            let mut cpb_cnt_minus1 = 0;
+            if !low_delay_hrd_flag {
+                cpb_cnt_minus1 = r.read_ue_max(31)?;
+            }
+
+            if nal_hrd_parameters_present_flag {
+                Parser::parse_sub_layer_hrd_parameters(
+                    r,
+                    cpb_cnt_minus1,
+                    sub_pic_hrd_params_present_flag,
+                )?;
+            }
+
+            if vcl_hrd_parameters_present_flag {
+                Parser::parse_sub_layer_hrd_parameters(
+                    r,
+                    cpb_cnt_minus1,
+                    sub_pic_hrd_params_present_flag,
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn parse_vui_parameters<T: AsRef<[u8]>>(
+        r: &mut NaluReader<T>,
+        vui: &mut VuiParams,
+        max_sub_layers_minus1: u8,
+    ) -> Result<()> {
+        vui.aspect_ratio_info_present_flag = r.read_bit()?;
+        if vui.aspect_ratio_info_present_flag {
+            vui.aspect_ratio_idc = r.read_bits(8)?;
+            if vui.aspect_ratio_idc == EXTENDED_SAR {
+                vui.sar_width = r.read_bits(16)?;
+                vui.sar_height = r.read_bits(16)?;
+            }
+        }
+
+        vui.overscan_info_present_flag = r.read_bit()?;
+        if vui.overscan_info_present_flag {
+            vui.overscan_appropriate_flag = r.read_bit()?;
+        }
+
+        vui.video_signal_type_present_flag = r.read_bit()?;
+        if vui.video_signal_type_present_flag {
+            vui.video_format = r.read_bits(3)?;
+            vui.video_full_range_flag = r.read_bit()?;
+            vui.colour_description_present_flag = r.read_bit()?;
+            if vui.colour_description_present_flag {
+                vui.colour_primaries = r.read_bits(8)?;
+                vui.transfer_characteristics = r.read_bits(8)?;
+                vui.matrix_coeffs = r.read_bits(8)?;
+            }
+        }
+
+        vui.chroma_loc_info_present_flag = r.read_bit()?;
+        if vui.chroma_loc_info_present_flag {
+            vui.chroma_sample_loc_type_top_field = r.read_ue_max(5)?;
+            vui.chroma_sample_loc_type_bottom_field = r.read_ue_max(5)?;
+        }
+
+        vui.neutral_chroma_indication_flag = r.read_bit()?;
+        vui.field_seq_flag = r.read_bit()?;
+        vui.frame_field_info_present_flag = r.read_bit()?;
+        vui.default_display_window_flag = r.read_bit()?;
+        if vui.default_display_window_flag {
+            vui.def_disp_win_left_offset = r.read_ue()?;
+            vui.def_disp_win_right_offset = r.read_ue()?;
+            vui.def_disp_win_top_offset = r.read_ue()?;
+            vui.def_disp_win_bottom_offset = r.read_ue()?;
+        }
+
+        vui.timing_info_present_flag = r.read_bit()?;
+        if vui.timing_info_present_flag {
+            vui.num_units_in_tick = Parser::read_u32(r)?;
+            vui.time_scale = Parser::read_u32(r)?;
+            vui.poc_proportional_to_timing_flag = r.read_bit()?;
+            if vui.poc_proportional_to_timing_flag {
+                vui.num_ticks_poc_diff_one_minus1 = r.read_ue_max(u32::MAX - 1)?;
+            }
+            vui.hrd_parameters_present_flag = r.read_bit()?;
+            if vui.hrd_parameters_present_flag {
+                Parser::parse_hrd_parameters(r, max_sub_layers_minus1)?;
+            }
+        }
+
+        vui.bitstream_restriction_flag = r.read_bit()?;
+        if vui.bitstream_restriction_flag {
+            vui.tiles_fixed_structure_flag = r.read_bit()?;
+            vui.motion_vectors_over_pic_boundaries_flag = r.read_bit()?;
+            vui.restricted_ref_pic_lists_flag = r.read_bit()?;
+            vui.min_spatial_segmentation_idc = r.read_ue_max(4095)?;
+            vui.max_bytes_per_pic_denom = r.read_ue_max(16)?;
+            vui.max_bits_per_min_cu_denom = r.read_ue_max(16)?;
+            vui.log2_max_mv_length_horizontal = r.read_ue_max(15)?;
+            vui.log2_max_mv_length_vertical = r.read_ue_max(15)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn parse_vps<T: AsRef<[u8]>>(&mut self, nalu: &Nalu<T>) -> Result<&Vps> {
+        let data = nalu.as_ref();
+        // Skip the header
+        let mut r = NaluReader::new(&data[nalu.header.header_bytes..]);
+
+        let mut vps = Vps {
+            video_parameter_set_id: r.read_bits(4)?,
+            base_layer_internal_flag: r.read_bit()?,
+            base_layer_available_flag: r.read_bit()?,
+            max_layers_minus1: r.read_bits(6)?,
+            max_sub_layers_minus1: r.read_bits(3)?,
+            temporal_id_nesting_flag: r.read_bit()?,
+            ..Default::default()
+        };
+
+        if usize::from(vps.max_sub_layers_minus1) >= MAX_SUB_LAYERS {
+            return Err(anyhow!(
+                "Broken Data: invalid vps_max_sub_layers_minus1 {}",
+                vps.max_sub_layers_minus1
+            ));
+        }
+
+        // vps_reserved_0xffff_16bits
+        r.skip_bits(16)?;
+
+        Parser::parse_profile_tier_level(
+            &mut r,
+            &mut vps.profile_tier_level,
+            vps.max_sub_layers_minus1,
+        )?;
+
+        let sub_layer_ordering_info_present_flag = r.read_bit()?;
+        Parser::parse_sub_layer_ordering_info(
+            &mut r,
+            &mut vps.sub_layer_ordering_info,
+            vps.max_sub_layers_minus1,
+            sub_layer_ordering_info_present_flag,
+        )?;
+
+        // The layer sets, timing information and extensions are not needed by the decoding
+        // process of the base layer.
+
+        let key = vps.video_parameter_set_id;
+        self.active_vpses.insert(key, vps);
+
+        if self.active_vpses.keys().len() > MAX_VPS_COUNT as usize {
+            return Err(anyhow!(
+                "Broken data: Number of active VPSs > MAX_VPS_COUNT"
+            ));
+        }
+
+        Ok(self.get_vps(key).unwrap())
+    }
+
+    pub fn parse_sps<T: AsRef<[u8]>>(&mut self, nalu: &Nalu<T>) -> Result<&Sps> {
+        let data = nalu.as_ref();
+        // Skip the header
+        let mut r = NaluReader::new(&data[nalu.header.header_bytes..]);
+
+        let mut sps = Sps {
+            video_parameter_set_id: r.read_bits(4)?,
+            max_sub_layers_minus1: r.read_bits(3)?,
+            temporal_id_nesting_flag: r.read_bit()?,
+            ..Default::default()
+        };
+
+        if usize::from(sps.max_sub_layers_minus1) >= MAX_SUB_LAYERS {
+            return Err(anyhow!(
+                "Broken Data: invalid sps_max_sub_layers_minus1 {}",
+                sps.max_sub_layers_minus1
+            ));
+        }
+
+        Parser::parse_profile_tier_level(
+            &mut r,
+            &mut sps.profile_tier_level,
+            sps.max_sub_layers_minus1,
+        )?;
+
+        sps.seq_parameter_set_id = r.read_ue_max(MAX_SPS_COUNT - 1)?;
+        sps.chroma_format_idc = r.read_ue_max(3)?;
+
+        if sps.chroma_format_idc == 3 {
+            sps.separate_colour_plane_flag = r.read_bit()?;
+        }
+
+        sps.pic_width_in_luma_samples = r.read_ue_max(u32::from(u16::MAX))?;
+        sps.pic_height_in_luma_samples = r.read_ue_max(u32::from(u16::MAX))?;
+
+        if sps.pic_width_in_luma_samples == 0 || sps.pic_height_in_luma_samples == 0 {
+            return Err(anyhow!("Broken Data: invalid picture dimensions"));
+        }
+
+        sps.conformance_window_flag = r.read_bit()?;
+        if sps.conformance_window_flag {
+            sps.conf_win_left_offset = r.read_ue()?;
+            sps.conf_win_right_offset = r.read_ue()?;
+            sps.conf_win_top_offset = r.read_ue()?;
+            sps.conf_win_bottom_offset = r.read_ue()?;
+        }
+
+        sps.bit_depth_luma_minus8 = r.read_ue_max(8)?;
+        sps.bit_depth_chroma_minus8 = r.read_ue_max(8)?;
+        sps.log2_max_pic_order_cnt_lsb_minus4 = r.read_ue_max(12)?;
+
+        sps.sub_layer_ordering_info_present_flag = r.read_bit()?;
+        Parser::parse_sub_layer_ordering_info(
+            &mut r,
+            &mut sps.sub_layer_ordering_info,
+            sps.max_sub_layers_minus1,
+            sps.sub_layer_ordering_info_present_flag,
+        )?;
+
+        sps.log2_min_luma_coding_block_size_minus3 = r.read_ue_max(3)?;
+        sps.log2_diff_max_min_luma_coding_block_size = r.read_ue_max(3)?;
+        sps.log2_min_luma_transform_block_size_minus2 = r.read_ue_max(3)?;
+        sps.log2_diff_max_min_luma_transform_block_size = r.read_ue_max(3)?;
+        sps.max_transform_hierarchy_depth_inter = r.read_ue_max(4)?;
+        sps.max_transform_hierarchy_depth_intra = r.read_ue_max(4)?;
+
+        sps.scaling_list_enabled_flag = r.read_bit()?;
+        if sps.scaling_list_enabled_flag {
+            sps.scaling_lists = ScalingLists::default_lists();
+            sps.scaling_list_data_present_flag = r.read_bit()?;
+            if sps.scaling_list_data_present_flag {
+                Parser::parse_scaling_list_data(&mut r, &mut sps.scaling_lists)?;
+            }
+        }
+
+        sps.amp_enabled_flag = r.read_bit()?;
+        sps.sample_adaptive_offset_enabled_flag = r.read_bit()?;
+        sps.pcm_enabled_flag = r.read_bit()?;
+
+        if sps.pcm_enabled_flag {
+            sps.pcm_sample_bit_depth_luma_minus1 = r.read_bits(4)?;
+            sps.pcm_sample_bit_depth_chroma_minus1 = r.read_bits(4)?;
+            sps.log2_min_pcm_luma_coding_block_size_minus3 = r.read_ue_max(2)?;
+            sps.log2_diff_max_min_pcm_luma_coding_block_size = r.read_ue_max(2)?;
+            sps.pcm_loop_filter_disabled_flag = r.read_bit()?;
+        }
+
+        let num_short_term_ref_pic_sets: usize =
+            r.read_ue_max(MAX_SHORT_TERM_REF_PIC_SETS as u32)?;
+        let max_dec_pic_buffering_minus1 =
+            sps.sub_layer_ordering_info[sps.highest_tid()].max_dec_pic_buffering_minus1;
+
+        for i in 0..num_short_term_ref_pic_sets {
+            let rps = Parser::parse_short_term_ref_pic_set(
+                &mut r,
+                &sps.short_term_ref_pic_sets,
+                i,
+                max_dec_pic_buffering_minus1,
+            )?;
+            sps.short_term_ref_pic_sets.push(rps);
+        }
+
+        sps.long_term_ref_pics_present_flag = r.read_bit()?;
+        if sps.long_term_ref_pics_present_flag {
+            sps.num_long_term_ref_pics_sps = r.read_ue_max(MAX_LONG_TERM_REF_PICS_SPS as u32)?;
+            for i in 0..usize::from(sps.num_long_term_ref_pics_sps) {
+                sps.lt_ref_pic_poc_lsb_sps[i] =
+                    r.read_bits(usize::from(sps.log2_max_pic_order_cnt_lsb_minus4) + 4)?;
+                sps.used_by_curr_pic_lt_sps_flag[i] = r.read_bit()?;
+            }
+        }
+
+        sps.temporal_mvp_enabled_flag = r.read_bit()?;
+        sps.strong_intra_smoothing_enabled_flag = r.read_bit()?;
+
+        sps.vui_parameters_present_flag = r.read_bit()?;
+        if sps.vui_parameters_present_flag {
+            Parser::parse_vui_parameters(
+                &mut r,
+                &mut sps.vui_parameters,
+                sps.max_sub_layers_minus1,
+            )?;
+        }
+
+        sps.extension_present_flag = r.read_bit()?;
+        if sps.extension_present_flag {
+            sps.range_extension_flag = r.read_bit()?;
+            // The multilayer, 3D, screen content coding and future extensions are not supported
+            // and are ignored.
+            r.skip_bits(7)?;
+
+            if sps.range_extension_flag {
+                let ext = &mut sps.range_extension;
+                ext.transform_skip_rotation_enabled_flag = r.read_bit()?;
+                ext.transform_skip_context_enabled_flag = r.read_bit()?;
+                ext.implicit_rdpcm_enabled_flag = r.read_bit()?;
+                ext.explicit_rdpcm_enabled_flag = r.read_bit()?;
+                ext.extended_precision_processing_flag = r.read_bit()?;
+                ext.intra_smoothing_disabled_flag = r.read_bit()?;
+                ext.high_precision_offsets_enabled_flag = r.read_bit()?;
+                ext.persistent_rice_adaptation_enabled_flag = r.read_bit()?;
+                ext.cabac_bypass_alignment_enabled_flag = r.read_bit()?;
+            }
+        }
+
+        let key = sps.seq_parameter_set_id;
+        self.active_spses.insert(key, sps);
+
+        if self.active_spses.keys().len() > MAX_SPS_COUNT as usize {
+            return Err(anyhow!(
+                "Broken data: Number of active SPSs > MAX_SPS_COUNT"
+            ));
+        }
+
+        Ok(self.get_sps(key).unwrap())
+    }
+
+    pub fn parse_pps<T: AsRef<[u8]>>(&mut self, nalu: &Nalu<T>) -> Result<&Pps> {
+        let data = nalu.as_ref();
+        // Skip the header
+        let mut r = NaluReader::new(&data[nalu.header.header_bytes..]);
+
+        let mut pps = Pps {
+            pic_parameter_set_id: r.read_ue_max(MAX_PPS_COUNT - 1)?,
+            seq_parameter_set_id: r.read_ue_max(MAX_SPS_COUNT - 1)?,
+            loop_filter_across_tiles_enabled_flag: true,
+            ..Default::default()
+        };
+
+        let sps = self.get_sps(pps.seq_parameter_set_id).context(
+            "Broken stream: stream references a SPS that has not been successfully parsed",
+        )?;
+
+        pps.dependent_slice_segments_enabled_flag = r.read_bit()?;
+        pps.output_flag_present_flag = r.read_bit()?;
+        pps.num_extra_slice_header_bits = r.read_bits(3)?;
+        pps.sign_data_hiding_enabled_flag = r.read_bit()?;
+        pps.cabac_init_present_flag = r.read_bit()?;
+        pps.num_ref_idx_l0_default_active_minus1 = r.read_ue_max(14)?;
+        pps.num_ref_idx_l1_default_active_minus1 = r.read_ue_max(14)?;
+
+        let qp_bd_offset_y = 6 * i32::from(sps.bit_depth_luma_minus8);
+        pps.init_qp_minus26 = r.read_se_bounded(-(26 + qp_bd_offset_y), 25)?;
+
+        pps.constrained_intra_pred_flag = r.read_bit()?;
+        pps.transform_skip_enabled_flag = r.read_bit()?;
+        pps.cu_qp_delta_enabled_flag = r.read_bit()?;
+
+        if pps.cu_qp_delta_enabled_flag {
+            pps.diff_cu_qp_delta_depth =
+                r.read_ue_max(u32::from(sps.log2_diff_max_min_luma_coding_block_size))?;
+        }
+
+        pps.cb_qp_offset = r.read_se_bounded(-12, 12)?;
+        pps.cr_qp_offset = r.read_se_bounded(-12, 12)?;
+        pps.slice_chroma_qp_offsets_present_flag = r.read_bit()?;
+        pps.weighted_pred_flag = r.read_bit()?;
+        pps.weighted_bipred_flag = r.read_bit()?;
+        pps.transquant_bypass_enabled_flag = r.read_bit()?;
+        pps.tiles_enabled_flag = r.read_bit()?;
+        pps.entropy_coding_sync_enabled_flag = r.read_bit()?;
+
+        if pps.tiles_enabled_flag {
+            let pic_width_in_ctbs_y = sps.pic_width_in_ctbs_y();
+            let pic_height_in_ctbs_y = sps.pic_height_in_ctbs_y();
+
+            pps.num_tile_columns_minus1 =
+                r.read_ue_max((MAX_TILE_COLUMNS as u32).min(pic_width_in_ctbs_y) - 1)?;
+            pps.num_tile_rows_minus1 =
+                r.read_ue_max((MAX_TILE_ROWS as u32).min(pic_height_in_ctbs_y) - 1)?;
+            pps.uniform_spacing_flag = r.read_bit()?;
+
+            let num_columns = u32::from(pps.num_tile_columns_minus1) + 1;
+            let num_rows = u32::from(pps.num_tile_rows_minus1) + 1;
+
+            if pps.uniform_spacing_flag {
+                // Equations 6-3 and 6-4.
+                for i in 0..num_columns {
+                    pps.column_width_minus1[i as usize] = (((i + 1) * pic_width_in_ctbs_y)
+                        / num_columns
+                        - (i * pic_width_in_ctbs_y) / num_columns
+                        - 1) as u16;
+                }
+                for j in 0..num_rows {
+                    pps.row_height_minus1[j as usize] = (((j + 1) * pic_height_in_ctbs_y)
+                        / num_rows
+                        - (j * pic_height_in_ctbs_y) / num_rows
+                        - 1) as u16;
+                }
+            } else {
+                let mut remaining = pic_width_in_ctbs_y;
+                for i in 0..usize::from(pps.num_tile_columns_minus1) {
+                    pps.column_width_minus1[i] = r.read_ue_max(remaining.saturating_sub(2))?;
+                    remaining -= u32::from(pps.column_width_minus1[i]) + 1;
+                }
+                pps.column_width_minus1[usize::from(pps.num_tile_columns_minus1)] =
+                    remaining.saturating_sub(1) as u16;
+
+                let mut remaining = pic_height_in_ctbs_y;
+                for j in 0..usize::from(pps.num_tile_rows_minus1) {
+                    pps.row_height_minus1[j] = r.read_ue_max(remaining.saturating_sub(2))?;
+                    remaining -= u32::from(pps.row_height_minus1[j]) + 1;
+                }
+                pps.row_height_minus1[usize::from(pps.num_tile_rows_minus1)] =
+                    remaining.saturating_sub(1) as u16;
+            }
+
+            pps.loop_filter_across_tiles_enabled_flag = r.read_bit()?;
+        }
+
+        pps.loop_filter_across_slices_enabled_flag = r.read_bit()?;
+        pps.deblocking_filter_control_present_flag = r.read_bit()?;
+
+        if pps.deblocking_filter_control_present_flag {
+            pps.deblocking_filter_override_enabled_flag = r.read_bit()?;
+            pps.deblocking_filter_disabled_flag = r.read_bit()?;
+            if !pps.deblocking_filter_disabled_flag {
+                pps.beta_offset_div2 = r.read_se_bounded(-6, 6)?;
+                pps.tc_offset_div2 = r.read_se_bounded(-6, 6)?;
+            }
+        }
+
+        pps.scaling_list_data_present_flag = r.read_bit()?;
+        if pps.scaling_list_data_present_flag {
+            pps.scaling_lists = ScalingLists::default_lists();
+            Parser::parse_scaling_list_data(&mut r, &mut pps.scaling_lists)?;
+        } else {
+            pps.scaling_lists = sps.scaling_lists.clone();
+        }
+
+        pps.lists_modification_present_flag = r.read_bit()?;
+        pps.log2_parallel_merge_level_minus2 = r.read_ue_max(sps.ctb_log2_size_y() - 2)?;
+        pps.slice_segment_header_extension_present_flag = r.read_bit()?;
+
+        pps.extension_present_flag = r.read_bit()?;
+        if pps.extension_present_flag {
+            pps.range_extension_flag = r.read_bit()?;
+            // The multilayer, 3D, screen content coding and future extensions are not supported
+            // and are ignored.
+            r.skip_bits(7)?;
+
+            if pps.range_extension_flag {
+                let ext = &mut pps.range_extension;
+                if pps.transform_skip_enabled_flag {
+                    ext.log2_max_transform_skip_block_size_minus2 = r.read_ue_max(3)?;
+                }
+
+                ext.cross_component_prediction_enabled_flag = r.read_bit()?;
+                ext.chroma_qp_offset_list_enabled_flag = r.read_bit()?;
+
+                if ext.chroma_qp_offset_list_enabled_flag {
+                    ext.diff_cu_chroma_qp_offset_depth =
+                        r.read_ue_max(u32::from(sps.log2_diff_max_min_luma_coding_block_size))?;
+                    ext.chroma_qp_offset_list_len_minus1 =
+                        r.read_ue_max(MAX_CHROMA_QP_OFFSET_LIST_LEN as u32 - 1)?;
+
+                    for i in 0..=usize::from(ext.chroma_qp_offset_list_len_minus1) {
+                        ext.cb_qp_offset_list[i] = r.read_se_bounded(-12, 12)?;
+                        ext.cr_qp_offset_list[i] = r.read_se_bounded(-12, 12)?;
+                    }
+                }
+
+                let max_log2_sao_offset_scale_luma = sps.bit_depth_luma().saturating_sub(10);
+                let max_log2_sao_offset_scale_chroma = sps.bit_depth_chroma().saturating_sub(10);
+                ext.log2_sao_offset_scale_luma = r.read_ue_max(max_log2_sao_offset_scale_luma)?;
+                ext.log2_sao_offset_scale_chroma =
+                    r.read_ue_max(max_log2_sao_offset_scale_chroma)?;
+            }
+        }
+
+        let key = pps.pic_parameter_set_id;
+        self.active_ppses.insert(key, pps);
+
+        if self.active_ppses.keys().len() > MAX_PPS_COUNT as usize {
+            return Err(anyhow!(
+                "Broken Data: number of active PPSs > MAX_PPS_COUNT"
+            ));
+        }
+
+        Ok(self.get_pps(key).unwrap())
+    }
+
+    fn parse_pred_weight_table<T: AsRef<[u8]>>(
+        r: &mut NaluReader<T>,
+        sps: &Sps,
+        header: &mut SliceHeader,
+    ) -> Result<()> {
+        let pwt = &mut header.pred_weight_table;
+        let chroma_present = sps.chroma_array_type() != 0;
+
+        pwt.luma_log2_weight_denom = r.read_ue_max(7)?;
+        if chroma_present {
+            let denom = i32::from(pwt.luma_log2_weight_denom);
+            pwt.delta_chroma_log2_weight_denom = r.read_se_bounded(-denom, 7 - denom)?;
+        }
+
+        let wp_offset_half_range_y: i32 = if sps.range_extension.high_precision_offsets_enabled_flag
+        {
+            1 << (sps.bit_depth_luma() - 1)
+        } else {
+            1 << 7
+        };
+        let wp_offset_half_range_c: i32 = if sps.range_extension.high_precision_offsets_enabled_flag
+        {
+            1 << (sps.bit_depth_chroma() - 1)
+        } else {
+            1 << 7
+        };
+
+        let num_lists = if header.slice_type.is_b() { 2 } else { 1 };
+
+        for list in 0..num_lists {
+            let (
+                num_refs,
+                luma_flag,
+                chroma_flag,
+                luma_weight,
+                luma_offset,
+                chroma_weight,
+                chroma_offset,
+            ) = if list == 0 {
+                (
+                    usize::from(header.num_ref_idx_l0_active_minus1) + 1,
+                    &mut pwt.luma_weight_l0_flag,
+                    &mut pwt.chroma_weight_l0_flag,
+                    &mut pwt.delta_luma_weight_l0,
+                    &mut pwt.luma_offset_l0,
+                    &mut pwt.delta_chroma_weight_l0,
+                    &mut pwt.delta_chroma_offset_l0,
+                )
+            } else {
+                (
+                    usize::from(header.num_ref_idx_l1_active_minus1) + 1,
+                    &mut pwt.luma_weight_l1_flag,
+                    &mut pwt.chroma_weight_l1_flag,
+                    &mut pwt.delta_luma_weight_l1,
+                    &mut pwt.luma_offset_l1,
+                    &mut pwt.delta_chroma_weight_l1,
+                    &mut pwt.delta_chroma_offset_l1,
+                )
+            };
+
+            // Without the screen content coding extension, the reference pictures never have the
+            // same POC as the current picture, so all the flags are present.
+            for flag in luma_flag.iter_mut().take(num_refs) {
+                *flag = r.read_bit()?;
+            }
+
+            if chroma_present {
+                for flag in chroma_flag.iter_mut().take(num_refs) {
+                    *flag = r.read_bit()?;
+                }
+            }
+
+            for i in 0..num_refs {
+                if luma_flag[i] {
+                    luma_weight[i] = r.read_se_bounded(-128, 127)?;
+                    luma_offset[i] =
+                        r.read_se_bounded(-wp_offset_half_range_y, wp_offset_half_range_y - 1)?;
+                }
+
+                if chroma_flag[i] {
+                    for j in 0..2 {
+                        chroma_weight[i][j] = r.read_se_bounded(-128, 127)?;
+                        chroma_offset[i][j] = r.read_se_bounded(
+                            -4 * wp_offset_half_range_c,
+                            4 * wp_offset_half_range_c - 1,
+                        )?;
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn parse_slice_header<T: AsRef<[u8]>>(&mut self, nalu: Nalu<T>) -> Result<Slice<T>> {
+        let data = nalu.as_ref();
+        // Skip the header
+        let mut r = NaluReader::new(&data[nalu.header.header_bytes..]);
+        let nalu_type = nalu.header.type_;
+
+        if !nalu_type.is_vcl() {
+            return Err(anyhow!("Broken Data: not a slice segment NAL unit"));
+        }
+
+        let first_slice_segment_in_pic_flag = r.read_bit()?;
+        let mut no_output_of_prior_pics_flag = false;
+        if nalu_type.is_irap() {
+            no_output_of_prior_pics_flag = r.read_bit()?;
+        }
+
+        let pic_parameter_set_id = r.read_ue_max(MAX_PPS_COUNT - 1)?;
+
+        let pps = self.get_pps(pic_parameter_set_id).context(
+            "Broken stream: slice references PPS that has not been successfully parsed.",
+        )?;
+
+        let sps = self.get_sps(pps.seq_parameter_set_id).context(
+            "Broken stream: slice's PPS references SPS that has not been successfully parsed.",
+        )?;
+
+        let mut dependent_slice_segment_flag = false;
+        let mut segment_address = 0;
+        if !first_slice_segment_in_pic_flag {
+            if pps.dependent_slice_segments_enabled_flag {
+                dependent_slice_segment_flag = r.read_bit()?;
+            }
+            segment_address = r.read_bits(ceil_log2(sps.pic_size_in_ctbs_y()))?;
+            if segment_address >= sps.pic_size_in_ctbs_y() {
+                return Err(anyhow!(
+                    "Broken Data: invalid slice_segment_address {}",
+                    segment_address
+                ));
+            }
+        }
+
+        let mut header = if dependent_slice_segment_flag {
+            self.last_independent_slice_header
+                .clone()
+                .context("Broken stream: dependent slice segment without independent one.")?
+        } else {
+            SliceHeader {
+                pic_output_flag: true,
+                ..Default::default()
+            }
+        };
+
+        header.first_slice_segment_in_pic_flag = first_slice_segment_in_pic_flag;
+        header.no_output_of_prior_pics_flag = no_output_of_prior_pics_flag;
+        header.pic_parameter_set_id = pic_parameter_set_id;
+        header.dependent_slice_segment_flag = dependent_slice_segment_flag;
+        header.segment_address = segment_address;
+
+        if !dependent_slice_segment_flag {
+            r.skip_bits(usize::from(pps.num_extra_slice_header_bits))?;
+
+            let slice_type: u8 = r.read_ue_max(2)?;
+            header.slice_type = SliceType::n(slice_type)
+                .with_context(|| format!("Invalid slice type {}", slice_type))?;
+
+            if pps.output_flag_present_flag {
+                header.pic_output_flag = r.read_bit()?;
+            }
+
+            if sps.separate_colour_plane_flag {
+                header.colour_plane_id = r.read_bits(2)?;
+            }
+
+            if !nalu_type.is_idr() {
+                header.pic_order_cnt_lsb =
+                    r.read_bits(usize::from(sps.log2_max_pic_order_cnt_lsb_minus4) + 4)?;
+                header.short_term_ref_pic_set_sps_flag = r.read_bit()?;
+
+                let num_short_term_ref_pic_sets = sps.short_term_ref_pic_sets.len();
+                if !header.short_term_ref_pic_set_sps_flag {
+                    let bits_left = r.num_bits_left();
+                    header.short_term_ref_pic_set = Parser::parse_short_term_ref_pic_set(
+                        &mut r,
+                        &sps.short_term_ref_pic_sets,
+                        num_short_term_ref_pic_sets,
+                        sps.sub_layer_ordering_info[sps.highest_tid()].max_dec_pic_buffering_minus1,
+                    )?;
+                    header.st_rps_bits = (bits_left - r.num_bits_left()) as u32;
+                } else {
+                    if num_short_term_ref_pic_sets == 0 {
+                        return Err(anyhow!("Broken Data: no short-term RPS in the SPS"));
+                    }
+                    if num_short_term_ref_pic_sets > 1 {
+                        header.short_term_ref_pic_set_idx =
+                            r.read_bits(ceil_log2(num_short_term_ref_pic_sets as u32))?;
+                    }
+                    if usize::from(header.short_term_ref_pic_set_idx) >= num_short_term_ref_pic_sets
+                    {
+                        return Err(anyhow!(
+                            "Broken Data: invalid short_term_ref_pic_set_idx {}",
+                            header.short_term_ref_pic_set_idx
+                        ));
+                    }
+                }
+
+                if sps.long_term_ref_pics_present_flag {
+                    if sps.num_long_term_ref_pics_sps > 0 {
+                        header.num_long_term_sps =
+                            r.read_ue_max(u32::from(sps.num_long_term_ref_pics_sps))?;
+                    }
+
+                    header.num_long_term_pics = r.read_ue_max(MAX_LONG_TERM_REF_PICS as u32)?;
+
+                    let num_long_term = usize::from(header.num_long_term_sps)
+                        + usize::from(header.num_long_term_pics);
+                    if num_long_term > MAX_LONG_TERM_REF_PICS {
+                        return Err(anyhow!(
+                            "Broken Data: too many long-term reference pictures"
+                        ));
+                    }
+
+                    for i in 0..num_long_term {
+                        if i < usize::from(header.num_long_term_sps) {
+                            let mut lt_idx_sps = 0;
+                            if sps.num_long_term_ref_pics_sps > 1 {
+                                lt_idx_sps = r.read_bits::<usize>(ceil_log2(u32::from(
+                                    sps.num_long_term_ref_pics_sps,
+                                )))?;
+                            }
+                            header.poc_lsb_lt[i] = sps.lt_ref_pic_poc_lsb_sps[lt_idx_sps];
+                            header.used_by_curr_pic_lt[i] =
+                                sps.used_by_curr_pic_lt_sps_flag[lt_idx_sps];
+                        } else {
+                            header.poc_lsb_lt[i] = r.read_bits(
+                                usize::from(sps.log2_max_pic_order_cnt_lsb_minus4) + 4,
+                            )?;
+                            header.used_by_curr_pic_lt[i] = r.read_bit()?;
+                        }
+
+                        header.delta_poc_msb_present_flag[i] = r.read_bit()?;
+                        if header.delta_poc_msb_present_flag[i] {
+                            header.delta_poc_msb_cycle_lt[i] = r.read_ue()?;
+                        }
+
+                        // Equation 7-52.
+                        if i != 0 && i != usize::from(header.num_long_term_sps) {
+                            header.delta_poc_msb_cycle_lt[i] +=
+                                header.delta_poc_msb_cycle_lt[i - 1];
+                        }
+                    }
+                }
+
+                if sps.temporal_mvp_enabled_flag {
+                    header.temporal_mvp_enabled_flag = r.read_bit()?;
+                }
+            }
+
+            if sps.sample_adaptive_offset_enabled_flag {
+                header.sao_luma_flag = r.read_bit()?;
+                if sps.chroma_array_type() != 0 {
+                    header.sao_chroma_flag = r.read_bit()?;
+                }
+            }
+
+            // Equation 7-55.
+            let num_long_term =
+                usize::from(header.num_long_term_sps) + usize::from(header.num_long_term_pics);
+            header.num_pic_total_curr = if nalu_type.is_idr() {
+                0
+            } else {
+                header
+                    .curr_short_term_ref_pic_set(sps)
+                    .num_used_by_curr_pic()
+                    + header.used_by_curr_pic_lt[..num_long_term]
+                        .iter()
+                        .filter(|&&used| used)
+                        .count() as u32
+            };
+
+            if header.slice_type.is_p() || header.slice_type.is_b() {
+                header.num_ref_idx_active_override_flag = r.read_bit()?;
+                if header.num_ref_idx_active_override_flag {
+                    header.num_ref_idx_l0_active_minus1 =
+                        r.read_ue_max(MAX_REF_IDX_ACTIVE as u32 - 1)?;
+                    if header.slice_type.is_b() {
+                        header.num_ref_idx_l1_active_minus1 =
+                            r.read_ue_max(MAX_REF_IDX_ACTIVE as u32 - 1)?;
+                    }
+                } else {
+                    header.num_ref_idx_l0_active_minus1 = pps.num_ref_idx_l0_default_active_minus1;
+                    header.num_ref_idx_l1_active_minus1 = pps.num_ref_idx_l1_default_active_minus1;
+                }
+
+                if header.num_pic_total_curr == 0 {
+                    return Err(anyhow!(
+                        "Broken Data: inter slice without reference pictures"
+                    ));
+                }
+
+                if pps.lists_modification_present_flag && header.num_pic_total_curr > 1 {
+                    let entry_bits = ceil_log2(header.num_pic_total_curr);
+
+                    header.ref_pic_list_modification_flag_l0 = r.read_bit()?;
+                    if header.ref_pic_list_modification_flag_l0 {
+                        for i in 0..=usize::from(header.num_ref_idx_l0_active_minus1) {
+                            header.list_entry_l0[i] = r.read_bits(entry_bits)?;
+                        }
+                    }
+
+                    if header.slice_type.is_b() {
+                        header.ref_pic_list_modification_flag_l1 = r.read_bit()?;
+                        if header.ref_pic_list_modification_flag_l1 {
+                            for i in 0..=usize::from(header.num_ref_idx_l1_active_minus1) {
+                                header.list_entry_l1[i] = r.read_bits(entry_bits)?;
+                            }
+                        }
+                    }
+                }
+
+                if header.slice_type.is_b() {
+                    header.mvd_l1_zero_flag = r.read_bit()?;
+                }
+
+                if pps.cabac_init_present_flag {
+                    header.cabac_init_flag = r.read_bit()?;
+                }
+
+                if header.temporal_mvp_enabled_flag {
+                    header.collocated_from_l0_flag = true;
+                    if header.slice_type.is_b() {
+                        header.collocated_from_l0_flag = r.read_bit()?;
+                    }
+
+                    let num_ref_idx_active_minus1 = if header.collocated_from_l0_flag {
+                        header.num_ref_idx_l0_active_minus1
+                    } else {
+                        header.num_ref_idx_l1_active_minus1
+                    };
+
+                    if num_ref_idx_active_minus1 > 0 {
+                        header.collocated_ref_idx =
+                            r.read_ue_max(u32::from(num_ref_idx_active_minus1))?;
+                    }
+                }
+
+                if (pps.weighted_pred_flag && header.slice_type.is_p())
+                    || (pps.weighted_bipred_flag && header.slice_type.is_b())
+                {
+                    Parser::parse_pred_weight_table(&mut r, sps, &mut header)?;
+                }
+
+                header.five_minus_max_num_merge_cand = r.read_ue_max(4)?;
+            }
+
+            let qp_bd_offset_y = 6 * i32::from(sps.bit_depth_luma_minus8);
+            let init_qp = 26 + i32::from(pps.init_qp_minus26);
+            header.qp_delta = r.read_se_bounded(-qp_bd_offset_y - init_qp, 51 - init_qp)?;
+
+            if pps.slice_chroma_qp_offsets_present_flag {
+                header.cb_qp_offset = r.read_se_bounded(-12, 12)?;
+                header.cr_qp_offset = r.read_se_bounded(-12, 12)?;
+            }
+
+            if pps.range_extension.chroma_qp_offset_list_enabled_flag {
+                header.cu_chroma_qp_offset_enabled_flag = r.read_bit()?;
+            }
+
+            if pps.deblocking_filter_override_enabled_flag {
+                header.deblocking_filter_override_flag = r.read_bit()?;
+            }
+
+            if header.deblocking_filter_override_flag {
+                header.deblocking_filter_disabled_flag = r.read_bit()?;
+                if !header.deblocking_filter_disabled_flag {
+                    header.beta_offset_div2 = r.read_se_bounded(-6, 6)?;
+                    header.tc_offset_div2 = r.read_se_bounded(-6, 6)?;
+                }
+            } else {
+                header.deblocking_filter_disabled_flag = pps.deblocking_filter_disabled_flag;
+                header.beta_offset_div2 = pps.beta_offset_div2;
+                header.tc_offset_div2 = pps.tc_offset_div2;
+            }
+
+            header.loop_filter_across_slices_enabled_flag =
+                pps.loop_filter_across_slices_enabled_flag;
+            if pps.loop_filter_across_slices_enabled_flag
+                && (header.sao_luma_flag
+                    || header.sao_chroma_flag
+                    || !header.deblocking_filter_disabled_flag)
+            {
+                header.loop_filter_across_slices_enabled_flag = r.read_bit()?;
+            }
+        }
+
+        header.num_entry_point_offsets = 0;
+        header.offset_len_minus1 = 0;
+        header.entry_point_offset_minus1.clear();
+
+        if pps.tiles_enabled_flag || pps.entropy_coding_sync_enabled_flag {
+            header.num_entry_point_offsets = r.read_ue_max(sps.pic_size_in_ctbs_y())?;
+            if header.num_entry_point_offsets > 0 {
+                header.offset_len_minus1 = r.read_ue_max(31)?;
+                for _ in 0..header.num_entry_point_offsets {
+                    let offset = if header.offset_len_minus1 == 31 {
+                        Parser::read_u32(&mut r)?
+                    } else {
+                        r.read_bits(usize::from(header.offset_len_minus1) + 1)?
+                    };
+                    header.entry_point_offset_minus1.push(offset);
+                }
+            }
+        }
+
+        if pps.slice_segment_header_extension_present_flag {
+            let slice_segment_header_extension_length: u32 = r.read_ue_max(256)?;
+            for _ in 0..slice_segment_header_extension_length {
+                r.skip_bits(8)?;
+            }
+        }
+
+        // byte_alignment(): the alignment_bit_equal_to_one, followed by zero bits up to the next
+        // byte boundary.
+        if !r.read_bit()? {
+            return Err(anyhow!("Broken Data: invalid slice header alignment"));
+        }
+        let padding = r.num_bits_left() % 8;
+        r.skip_bits(padding)?;
+
+        let epb = r.num_epb();
+        header.header_bit_size = (nalu.size - epb) * 8 - r.num_bits_left();
+
+        header.n_emulation_prevention_bytes = epb;
+
+        if !dependent_slice_segment_flag {
+            self.last_independent_slice_header = Some(header.clone());
+        }
+
+        Ok(Slice { header, nalu })
+    }
+
+    pub fn get_vps(&self, vps_id: u8) -> Option<&Vps> {
+        self.active_vpses.get(&vps_id)
+    }
+
+    pub fn get_sps(&self, sps_id: u8) -> Option<&Sps> {
+        self.active_spses.get(&sps_id)
+    }
+
+    pub fn get_pps(&self, pps_id: u8) -> Option<&Pps> {
+        self.active_ppses.get(&pps_id)
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use std::io::Cursor;
+
+    use crate::decoders::h265::parser::Nalu;
+    use crate::decoders::h265::parser::NaluType;
+    use crate::decoders::h265::parser::Parser;
+    use crate::decoders::h265::parser::SliceType;
+    use crate::utils::bitwriter::BitWriter;
+
+    /// Wraps `rbsp` into an Annex B NAL unit of type `nalu_type`, inserting the emulation
+    /// prevention bytes.
+    pub fn nalu(nalu_type: NaluType, rbsp: &[u8]) -> Vec<u8> {
+        let mut data = vec![0, 0, 0, 1, (nalu_type as u8) << 1, 1];
+        let mut num_zeros = 0;
+
+        for &byte in rbsp {
+            if num_zeros == 2 && byte <= 3 {
+                data.push(3);
+                num_zeros = 0;
+            }
+
+            data.push(byte);
+            num_zeros = if byte == 0 { num_zeros + 1 } else { 0 };
+        }
+
+        data
+    }
+
+    fn write_profile_tier_level(w: &mut BitWriter) {
+        // general_profile_space, general_tier_flag and general_profile_idc (Main).
+        w.write_bits(0, 2);
+        w.write_bit(false);
+        w.write_bits(1, 5);
+        // general_profile_compatibility_flag[1] and [2].
+        w.write_bits(0x6000_0000, 32);
+        // progressive_source, interlaced_source, non_packed_constraint, frame_only_constraint.
+        w.write_bits(0b1001, 4);
+        // The constraint flags and general_inbld_flag.
+        w.write_bits(0, 44);
+        // general_level_idc (level 3).
+        w.write_bits(90, 8);
+    }
+
+    /// Returns a VPS for the test streams.
+    pub fn vps() -> Vec<u8> {
+        let mut w = BitWriter::default();
+        // vps_video_parameter_set_id, base_layer_internal/available flags, max_layers_minus1.
+        w.write_bits(0, 4);
+        w.write_bits(0b11, 2);
+        w.write_bits(0, 6);
+        // vps_max_sub_layers_minus1 and vps_temporal_id_nesting_flag.
+        w.write_bits(0, 3);
+        w.write_bit(true);
+        w.write_bits(0xffff, 16);
+        write_profile_tier_level(&mut w);
+        // vps_sub_layer_ordering_info_present_flag.
+        w.write_bit(true);
+        w.write_ue(4);
+        w.write_ue(2);
+        w.write_ue(0);
+        // vps_max_layer_id, vps_num_layer_sets_minus1, vps_timing_info_present_flag and
+        // vps_extension_flag.
+        w.write_bits(0, 6);
+        w.write_ue(0);
+        w.write_bit(false);
+        w.write_bit(false);
+        w.write_trailing_bits();
+
+        nalu(NaluType::VpsNut, &w.into_bytes())
+    }
+
+    /// Returns a SPS for a 64x64 4:2:0 stream with a DPB of 5 pictures, up to 2 reordered
+    /// pictures and 8-bit POC LSBs.
+    pub fn sps() -> Vec<u8> {
+        let mut w = BitWriter::default();
+        // sps_video_parameter_set_id, sps_max_sub_layers_minus1 and sps_temporal_id_nesting_flag.
+        w.write_bits(0, 4);
+        w.write_bits(0, 3);
+        w.write_bit(true);
+        write_profile_tier_level(&mut w);
+        // sps_seq_parameter_set_id and chroma_format_idc.
+        w.write_ue(0);
+        w.write_ue(1);
+        // pic_width_in_luma_samples, pic_height_in_luma_samples and conformance_window_flag.
+        w.write_ue(64);
+        w.write_ue(64);
+        w.write_bit(false);
+        // bit_depth_luma_minus8, bit_depth_chroma_minus8 and log2_max_pic_order_cnt_lsb_minus4.
+        w.write_ue(0);
+        w.write_ue(0);
+        w.write_ue(4);
+        // sps_sub_layer_ordering_info_present_flag.
+        w.write_bit(true);
+        w.write_ue(4);
+        w.write_ue(2);
+        w.write_ue(0);
+        // 8x8 to 16x16 coding blocks, 4x4 to 16x16 transform blocks.
+        w.write_ue(0);
+        w.write_ue(1);
+        w.write_ue(0);
+        w.write_ue(2);
+        // max_transform_hierarchy_depth_inter and max_transform_hierarchy_depth_intra.
+        w.write_ue(0);
+        w.write_ue(0);
+        // scaling_list_enabled_flag, amp_enabled_flag, sample_adaptive_offset_enabled_flag and
+        // pcm_enabled_flag.
+        w.write_bits(0, 4);
+        // num_short_term_ref_pic_sets.
+        w.write_ue(0);
+        // long_term_ref_pics_present_flag, sps_temporal_mvp_enabled_flag,
+        // strong_intra_smoothing_enabled_flag, vui_parameters_present_flag and
+        // sps_extension_present_flag.
+        w.write_bits(0, 5);
+        w.write_trailing_bits();
+
+        nalu(NaluType::SpsNut, &w.into_bytes())
+    }
+
+    /// Returns a PPS using the default values for all its fields.
+    pub fn pps() -> Vec<u8> {
+        let mut w = BitWriter::default();
+        // pps_pic_parameter_set_id and pps_seq_parameter_set_id.
+        w.write_ue(0);
+        w.write_ue(0);
+        // dependent_slice_segments_enabled_flag, output_flag_present_flag,
+        // num_extra_slice_header_bits, sign_data_hiding_enabled_flag and
+        // cabac_init_present_flag.
+        w.write_bits(0, 7);
+        // num_ref_idx_l0_default_active_minus1, num_ref_idx_l1_default_active_minus1 and
+        // init_qp_minus26.
+        w.write_ue(0);
+        w.write_ue(0);
+        w.write_se(0);
+        // constrained_intra_pred_flag, transform_skip_enabled_flag and cu_qp_delta_enabled_flag.
+        w.write_bits(0, 3);
+        // pps_cb_qp_offset and pps_cr_qp_offset.
+        w.write_se(0);
+        w.write_se(0);
+        // pps_slice_chroma_qp_offsets_present_flag, weighted_pred_flag, weighted_bipred_flag,
+        // transquant_bypass_enabled_flag, tiles_enabled_flag, entropy_coding_sync_enabled_flag,
+        // pps_loop_filter_across_slices_enabled_flag, deblocking_filter_control_present_flag,
+        // pps_scaling_list_data_present_flag and lists_modification_present_flag.
+        w.write_bits(0, 10);
+        // log2_parallel_merge_level_minus2, slice_segment_header_extension_present_flag and
+        // pps_extension_present_flag.
+        w.write_ue(0);
+        w.write_bits(0, 2);
+        w.write_trailing_bits();
+
+        nalu(NaluType::PpsNut, &w.into_bytes())
+    }
+
+    /// Returns a slice segment covering a whole picture. The short-term RPS is coded in the
+    /// slice header and made of the POC deltas in `negative` and `positive`, along with their
+    /// used_by_curr_pic flags.
+    pub fn slice(
+        nalu_type: NaluType,
+        slice_type: SliceType,
+        pic_order_cnt_lsb: u8,
+        negative: &[(i32, bool)],
+        positive: &[(i32, bool)],
+    ) -> Vec<u8> {
+        let mut w = BitWriter::default();
+        // first_slice_segment_in_pic_flag.
+        w.write_bit(true);
+        if nalu_type.is_irap() {
+            // no_output_of_prior_pics_flag.
+            w.write_bit(false);
+        }
+        // slice_pic_parameter_set_id and slice_type.
+        w.write_ue(0);
+        w.write_ue(slice_type as u32);
+
+        if !nalu_type.is_idr() {
+            w.write_bits(u64::from(pic_order_cnt_lsb), 8);
+            // short_term_ref_pic_set_sps_flag.
+            w.write_bit(false);
+            w.write_ue(negative.len() as u32);
+            w.write_ue(positive.len() as u32);
+
+            let mut prev = 0;
+            for &(delta_poc, used) in negative {
+                w.write_ue((prev - delta_poc - 1) as u32);
+                w.write_bit(used);
+                prev = delta_poc;
+            }
+
+            let mut prev = 0;
+            for &(delta_poc, used) in positive {
+                w.write_ue((delta_poc - prev - 1) as u32);
+                w.write_bit(used);
+                prev = delta_poc;
+            }
+        }
+
+        if !slice_type.is_i() {
+            // num_ref_idx_active_override_flag.
+            w.write_bit(false);
+            if slice_type.is_b() {
+                // mvd_l1_zero_flag.
+                w.write_bit(false);
+            }
+            // five_minus_max_num_merge_cand.
+            w.write_ue(0);
+        }
+
+        // slice_qp_delta.
+        w.write_se(0);
+        // byte_alignment().
+        w.write_trailing_bits();
+
+        // Some slice data.
+        let mut data = w.into_bytes();
+        data.extend([0xab; 4]);
+
+        nalu(nalu_type, &data)
+    }
+
+    #[test]
+    fn parse_synthetic_stream() {
+        let mut stream = vec![];
+        stream.extend(vps());
+        stream.extend(sps());
+        stream.extend(pps());
+        stream.extend(slice(NaluType::IdrWRadl, SliceType::I, 0, &[], &[]));
+        stream.extend(slice(
+            NaluType::TrailR,
+            SliceType::B,
+            3,
+            &[(-1, true), (-3, false)],
+            &[(2, true)],
+        ));
+
+        let stream = stream.as_slice();
+        let mut cursor = Cursor::new(stream);
+        let mut parser = Parser::default();
+
+        let nalu = Nalu::next(&mut cursor, stream).unwrap().unwrap();
+        assert_eq!(*nalu.header().nalu_type(), NaluType::VpsNut);
+        let vps = parser.parse_vps(&nalu).unwrap();
+        assert_eq!(vps.sub_layer_ordering_info()[0].max_num_reorder_pics(), 2);
+
+        let nalu = Nalu::next(&mut cursor, stream).unwrap().unwrap();
+        assert_eq!(*nalu.header().nalu_type(), NaluType::SpsNut);
+        let sps = parser.parse_sps(&nalu).unwrap();
+        assert_eq!(sps.profile_tier_level().general_profile_idc(), 1);
+        assert_eq!(sps.profile_tier_level().general_level_idc(), 90);
+        assert_eq!(sps.width(), 64);
+        assert_eq!(sps.height(), 64);
+        assert_eq!(sps.max_pic_order_cnt_lsb(), 256);
+        assert_eq!(sps.ctb_log2_size_y(), 4);
+        assert_eq!(sps.pic_size_in_ctbs_y(), 16);
+        assert_eq!(sps.max_dpb_size(), 5);
+        assert_eq!(sps.max_num_reorder_pics(), 2);
+
+        let nalu = Nalu::next(&mut cursor, stream).unwrap().unwrap();
+        assert_eq!(*nalu.header().nalu_type(), NaluType::PpsNut);
+        let pps = parser.parse_pps(&nalu).unwrap();
+        assert_eq!(pps.seq_parameter_set_id(), 0);
+        assert!(pps.loop_filter_across_tiles_enabled_flag());
+
+        let nalu = Nalu::next(&mut cursor, stream).unwrap().unwrap();
+        assert_eq!(*nalu.header().nalu_type(), NaluType::IdrWRadl);
+        let slice = parser.parse_slice_header(nalu).unwrap();
+        let hdr = slice.header();
+        assert!(hdr.first_slice_segment_in_pic_flag());
+        assert!(hdr.slice_type().is_i());
+        assert_eq!(hdr.num_pic_total_curr(), 0);
+        assert_eq!(hdr.header_bit_size() % 8, 0);
+
+        let nalu = Nalu::next(&mut cursor, stream).unwrap().unwrap();
+        assert_eq!(*nalu.header().nalu_type(), NaluType::TrailR);
+        let slice = parser.parse_slice_header(nalu).unwrap();
+        let hdr = slice.header();
+        assert!(hdr.slice_type().is_b());
+        assert_eq!(hdr.pic_order_cnt_lsb(), 3);
+
+        let rps = hdr.short_term_ref_pic_set();
+        assert_eq!(rps.num_negative_pics(), 2);
+        assert_eq!(rps.num_positive_pics(), 1);
+        assert_eq!(rps.delta_poc_s0()[..2], [-1, -3]);
+        assert_eq!(rps.used_by_curr_pic_s0()[..2], [true, false]);
+        assert_eq!(rps.delta_poc_s1()[0], 2);
+        assert_eq!(hdr.num_pic_total_curr(), 2);
+        assert!(!hdr.short_term_ref_pic_set_sps_flag());
+    }
+}
@@ -0,0 +1,109 @@
+// Copyright 2023 The ChromiumOS Authors
+// Use of this source code is governed by a BSD-style license that can be
+// found in the LICENSE file.
+
+use crate::decoders::h265::parser::NaluType;
+use crate::decoders::h265::parser::Slice;
+use crate::decoders::h265::parser::Sps;
+use crate::decoders::FrameInfo;
+use crate::Resolution;
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum Reference {
+    #[default]
+    None,
+    ShortTerm,
+    LongTerm,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct PictureData {
+    /// PicOrderCntVal in the specification.
+    pub pic_order_cnt_val: i32,
+    /// PicOrderCntMsb in the specification.
+    pub pic_order_cnt_msb: i32,
+    /// slice_pic_order_cnt_lsb of the picture.
+    pub pic_order_cnt_lsb: i32,
+
+    /// The NAL unit type of the slice segments of the picture.
+    pub nalu_type: NaluType,
+    /// The TemporalId of the picture.
+    pub temporal_id: u8,
+    /// NoRaslOutputFlag in the specification. Only meaningful for IRAP pictures.
+    pub no_rasl_output_flag: bool,
+    /// Whether the pictures preceding this IRAP picture in decoding order must be discarded
+    /// without being output.
+    pub no_output_of_prior_pics_flag: bool,
+    /// PicOutputFlag in the specification.
+    pub pic_output_flag: bool,
+
+    /// Whether the picture is waiting in the DPB to be output.
+    pub needed_for_output: bool,
+    /// PicLatencyCount in the specification.
+    pub pic_latency_count: u32,
+    reference: Reference,
+
+    pub coded_resolution: Resolution,
+    pub display_resolution: Resolution,
+
+    pub timestamp: u64,
+}
+
+impl PictureData {
+    pub fn new_from_slice(slice: &Slice<&[u8]>, sps: &Sps, timestamp: u64) -> Self {
+        let hdr = slice.header();
+        let nalu_hdr = slice.nalu().header();
+
+        let coded_resolution = Resolution {
+            width: sps.width(),
+            height: sps.height(),
+        };
+
+        let (_, _, width, height) = sps.visible_rectangle();
+        let display_resolution = Resolution { width, height };
+
+        PictureData {
+            pic_order_cnt_lsb: i32::from(hdr.pic_order_cnt_lsb()),
+            nalu_type: *nalu_hdr.nalu_type(),
+            temporal_id: nalu_hdr.temporal_id(),
+            no_output_of_prior_pics_flag: hdr.no_output_of_prior_pics_flag(),
+            pic_output_flag: hdr.pic_output_flag(),
+            coded_resolution,
+            display_resolution,
+            timestamp,
+            ..Default::default()
+        }
+    }
+
+    /// Whether the picture is an IRAP picture with NoRaslOutputFlag set, i.e. the first picture
+    /// of a coded video sequence.
+    pub fn starts_cvs(&self) -> bool {
+        self.nalu_type.is_irap() && self.no_rasl_output_flag
+    }
+
+    /// Whether the current picture is a reference, either ShortTerm or LongTerm.
+    pub fn is_ref(&self) -> bool {
+        !matches!(self.reference, Reference::None)
+    }
+
+    /// Returns a reference to the picture's Reference
+    pub fn reference(&self) -> &Reference {
+        &self.reference
+    }
+
+    /// Mark the picture as a reference picture.
+    pub fn set_reference(&mut self, reference: Reference) {
+        log::debug!(
+            "Set reference of POC {} to {:?}",
+            self.pic_order_cnt_val,
+            reference
+        );
+        self.reference = reference;
+    }
+}
+
+impl FrameInfo for PictureData {
+    fn display_resolution(&self) -> Resolution {
+        self.display_resolution
+    }
+}
@@ -2,6 +2,8 @@
 // Use of this source code is governed by a BSD-style license that can be
 // found in the LICENSE file.
 
+#[cfg(test)]
+pub(crate) mod bitwriter;
 #[cfg(test)]
 pub(crate) mod dummy;
 #[cfg(feature = "vaapi")]
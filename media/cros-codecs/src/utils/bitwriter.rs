@@ -0,0 +1,97 @@
+// Copyright 2023 The ChromiumOS Authors
+// Use of this source code is governed by a BSD-style license that can be
+// found in the LICENSE file.
+
+//! A simple MSB-first bit writer, used by the tests to synthesize bitstreams.
+
+#[derive(Debug, Default)]
+pub struct BitWriter {
+    data: Vec<u8>,
+    /// Number of bits used in the last byte of `data`. 0 means the last byte is full.
+    bit_pos: u8,
+}
+
+impl BitWriter {
+    /// Writes a single bit.
+    pub fn write_bit(&mut self, bit: bool) {
+        if self.bit_pos == 0 {
+            self.data.push(0);
+        }
+
+        if bit {
+            *self.data.last_mut().unwrap() |= 1 << (7 - self.bit_pos);
+        }
+
+        self.bit_pos = (self.bit_pos + 1) % 8;
+    }
+
+    /// Writes the `num_bits` least significant bits of `value`, MSB first.
+    pub fn write_bits(&mut self, value: u64, num_bits: usize) {
+        for i in (0..num_bits).rev() {
+            self.write_bit((value >> i) & 1 != 0);
+        }
+    }
+
+    /// Writes an unsigned Exp-Golomb code.
+    pub fn write_ue(&mut self, value: u32) {
+        let value = u64::from(value) + 1;
+        let num_bits = 64 - value.leading_zeros() as usize;
+
+        self.write_bits(0, num_bits - 1);
+        self.write_bits(value, num_bits);
+    }
+
+    /// Writes a signed Exp-Golomb code.
+    pub fn write_se(&mut self, value: i32) {
+        let mapped = if value > 0 {
+            2 * value as u32 - 1
+        } else {
+            2 * value.unsigned_abs()
+        };
+
+        self.write_ue(mapped);
+    }
+
+    /// Whether the writer is at a byte boundary.
+    pub fn byte_aligned(&self) -> bool {
+        self.bit_pos == 0
+    }
+
+    /// Pads the data with zero bits until the next byte boundary.
+    pub fn align_with_zeros(&mut self) {
+        self.bit_pos = 0;
+    }
+
+    /// Writes the rbsp_trailing_bits() syntax structure: a one bit followed by zero bits until
+    /// the next byte boundary.
+    pub fn write_trailing_bits(&mut self) {
+        self.write_bit(true);
+        self.align_with_zeros();
+    }
+
+    /// Returns the written data. Partially written bytes are padded with zeros.
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.data
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BitWriter;
+
+    #[test]
+    fn exp_golomb() {
+        let mut w = BitWriter::default();
+        // 1, 010, 011, 00100
+        w.write_ue(0);
+        w.write_ue(1);
+        w.write_ue(2);
+        w.write_ue(3);
+        // se(-1) is ue(2): 011
+        w.write_se(-1);
+        assert!(!w.byte_aligned());
+        w.write_trailing_bits();
+
+        assert_eq!(w.into_bytes(), vec![0b1010_0110, 0b0100_0111]);
+    }
+}
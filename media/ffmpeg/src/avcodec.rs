@@ -7,6 +7,7 @@
 //! low-level access as the libavcodec functions do.
 
 use std::ffi::CStr;
+use std::ffi::CString;
 use std::fmt::Debug;
 use std::fmt::Display;
 use std::marker::PhantomData;
@@ -124,6 +125,7 @@ impl AvCodec {
         Ok(EncoderContextBuilder {
             codec: self.0,
             context: self.alloc_context()?,
+            options: Default::default(),
         })
     }
 
@@ -178,7 +180,7 @@ impl DecoderContextBuilder {
 
     /// Build a decoder AvCodecContext from the configured options.
     pub fn build(mut self) -> Result<AvCodecContext, AvCodecOpenError> {
-        self.context.init(self.codec)?;
+        self.context.init(self.codec, None)?;
         Ok(self.context)
     }
 }
@@ -189,6 +191,7 @@ impl DecoderContextBuilder {
 pub struct EncoderContextBuilder {
     codec: *const ffi::AVCodec,
     context: AvCodecContext,
+    options: AvDictionary,
 }
 
 impl EncoderContextBuilder {
@@ -211,13 +214,95 @@ impl EncoderContextBuilder {
         context.pix_fmt = fmt.pix_fmt();
     }
 
+    /// Set the average bit rate for this encoding context.
+    pub fn set_bit_rate(&mut self, bit_rate: u64) {
+        let context = unsafe { &mut *(self.context.0) };
+        context.bit_rate = bit_rate as _;
+    }
+
+    /// Set the min bit rate (rc_min_rate) for this encoding context.
+    pub fn set_min_bit_rate(&mut self, bit_rate: u64) {
+        let context = unsafe { &mut *(self.context.0) };
+        context.rc_min_rate = bit_rate as _;
+    }
+
+    /// Set the max bit rate (rc_max_rate) for this encoding context.
+    pub fn set_max_bit_rate(&mut self, bit_rate: u64) {
+        let context = unsafe { &mut *(self.context.0) };
+        context.rc_max_rate = bit_rate as _;
+    }
+
+    /// Set the size of the rate control buffer, in bits.
+    pub fn set_rc_buffer_size(&mut self, size: u32) {
+        let context = unsafe { &mut *(self.context.0) };
+        context.rc_buffer_size = size as _;
+    }
+
+    /// Set the maximum number of frames between two keyframes.
+    pub fn set_gop_size(&mut self, gop_size: u32) {
+        let context = unsafe { &mut *(self.context.0) };
+        context.gop_size = gop_size as _;
+    }
+
+    /// Set the maximum number of consecutive B-frames. 0 disables frame reordering.
+    pub fn set_max_b_frames(&mut self, max_b_frames: u32) {
+        let context = unsafe { &mut *(self.context.0) };
+        context.max_b_frames = max_b_frames as _;
+    }
+
+    /// Set the profile to encode with, as one of the FF_PROFILE_* values.
+    pub fn set_profile(&mut self, profile: u32) {
+        let context = unsafe { &mut *(self.context.0) };
+        context.profile = profile as _;
+    }
+
+    /// Set the codec-specific level to encode with, e.g. the level_idc for H.264.
+    pub fn set_level(&mut self, level: u32) {
+        let context = unsafe { &mut *(self.context.0) };
+        context.level = level as _;
+    }
+
+    /// Set a codec-private option, which will be applied when the context is built.
+    pub fn set_option(&mut self, key: &str, value: &str) -> Result<(), AvError> {
+        self.options.set(key, value)
+    }
+
     /// Build a encoder AvCodecContext from the configured options.
     pub fn build(mut self) -> Result<AvCodecContext, AvCodecOpenError> {
-        self.context.init(self.codec)?;
+        self.context.init(self.codec, Some(&mut self.options))?;
         Ok(self.context)
     }
 }
 
+/// An owned `AVDictionary` of options, as taken by `avcodec_open2`.
+struct AvDictionary(*mut ffi::AVDictionary);
+
+impl Default for AvDictionary {
+    fn default() -> Self {
+        // A NULL dictionary is an empty one for libavutil.
+        Self(std::ptr::null_mut())
+    }
+}
+
+impl AvDictionary {
+    /// Set `key` to `value`, replacing any previous value.
+    fn set(&mut self, key: &str, value: &str) -> Result<(), AvError> {
+        let key = CString::new(key).map_err(|_| AvError(-libc::EINVAL))?;
+        let value = CString::new(value).map_err(|_| AvError(-libc::EINVAL))?;
+
+        // Safe because `self.0` is either NULL or a dictionary owned by us, and `key` and `value`
+        // are valid NUL-terminated strings that are copied by `av_dict_set`.
+        AvError::result(unsafe { ffi::av_dict_set(&mut self.0, key.as_ptr(), value.as_ptr(), 0) })
+    }
+}
+
+impl Drop for AvDictionary {
+    fn drop(&mut self) {
+        // Safe because `self.0` is either NULL or a dictionary owned by us.
+        unsafe { ffi::av_dict_free(&mut self.0) };
+    }
+}
+
 /// Lightweight abstraction over libavcodec's `av_codec_iterate` function that can be used to
 /// enumerate all the supported codecs.
 pub struct AvCodecIterator(*mut libc::c_void);
@@ -435,11 +520,22 @@ pub enum TryReceiveResult {
 }
 
 impl AvCodecContext {
-    /// Internal helper for [`DecoderContextBuilder`] to initialize the context.
-    fn init(&mut self, codec: *const ffi::AVCodec) -> Result<(), AvCodecOpenError> {
-        // Safe because `codec` is a valid static AVCodec reference, and `self.0` is a valid
-        // AVCodecContext allocation.
-        if unsafe { ffi::avcodec_open2(self.0, codec, std::ptr::null_mut()) } < 0 {
+    /// Internal helper for [`DecoderContextBuilder`] and [`EncoderContextBuilder`] to initialize
+    /// the context. Options of `options` that are not used by the codec are left in it.
+    fn init(
+        &mut self,
+        codec: *const ffi::AVCodec,
+        options: Option<&mut AvDictionary>,
+    ) -> Result<(), AvCodecOpenError> {
+        // `avcodec_open2` replaces the dictionary with the options that were not found.
+        let options = options.map_or(std::ptr::null_mut(), |options| &mut options.0 as *mut _);
+
+        // Safe because `codec` is a valid static AVCodec reference, `self.0` is a valid
+        // AVCodecContext allocation, and `options` is either NULL or points to a dictionary we
+        // own.
+        let ret = unsafe { ffi::avcodec_open2(self.0, codec, options) };
+
+        if ret < 0 {
             return Err(AvCodecOpenError::ContextOpen);
         }
 
@@ -565,6 +661,18 @@ impl AvCodecContext {
         let context = unsafe { &mut *(self.0) };
         context.rc_max_rate = bit_rate as _;
     }
+
+    /// Set the min bit rate (rc_min_rate) for this context.
+    pub fn set_min_bit_rate(&mut self, bit_rate: u64) {
+        let context = unsafe { &mut *(self.0) };
+        context.rc_min_rate = bit_rate as _;
+    }
+
+    /// Set the size of the rate control buffer, in bits.
+    pub fn set_rc_buffer_size(&mut self, size: u32) {
+        let context = unsafe { &mut *(self.0) };
+        context.rc_buffer_size = size as _;
+    }
 }
 
 /// Trait for types that can be used as data provider for a `AVBuffer`.
@@ -14,6 +14,7 @@ use crate::ffmpeg as ffi;
 pub mod swscale;
 
 pub use ffi::AVPictureType_AV_PICTURE_TYPE_I;
+pub use ffi::AVPixelFormat_AV_PIX_FMT_BGR0;
 pub use ffi::AVPixelFormat_AV_PIX_FMT_NV12;
 pub use ffi::AVPixelFormat_AV_PIX_FMT_YUV420P;
 pub use ffi::AVRational;
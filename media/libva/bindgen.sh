@@ -14,6 +14,8 @@ bindgen_generate \
     --constified-enum-module "VA.*" \
     --allowlist-function "va.*" \
     --allowlist-type ".*MPEG2.*|.*VP8.*|.*VP9.*|.*H264.*" \
+    --allowlist-type "VA(PictureParameter|SliceParameter|IQMatrix)BufferHEVC" \
+    --allowlist-type "VA(DecPictureParameter|SliceParameter)BufferAV1" \
     "media/libva/libva-wrapper.h" \
     > media/libva/src/bindings/va.rs
 
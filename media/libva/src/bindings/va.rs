@@ -1950,273 +1950,426 @@ pub struct _VABoolCoderContextVPX {
 }
 pub type VABoolCoderContextVPX = _VABoolCoderContextVPX;
 #[repr(C)]
-#[derive(Copy, Clone)]
-pub struct _VAPictureParameterBufferVP8 {
-    pub frame_width: u32,
-    pub frame_height: u32,
-    pub last_ref_frame: VASurfaceID,
-    pub golden_ref_frame: VASurfaceID,
-    pub alt_ref_frame: VASurfaceID,
-    pub out_of_loop_frame: VASurfaceID,
-    pub pic_fields: _VAPictureParameterBufferVP8__bindgen_ty_1,
-    pub mb_segment_tree_probs: [u8; 3usize],
-    pub loop_filter_level: [u8; 4usize],
-    pub loop_filter_deltas_ref_frame: [i8; 4usize],
-    pub loop_filter_deltas_mode: [i8; 4usize],
-    pub prob_skip_false: u8,
-    pub prob_intra: u8,
-    pub prob_last: u8,
-    pub prob_gf: u8,
-    pub y_mode_probs: [u8; 4usize],
-    pub uv_mode_probs: [u8; 3usize],
-    pub mv_probs: [[u8; 19usize]; 2usize],
-    pub bool_coder_ctx: VABoolCoderContextVPX,
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct _VAPictureHEVC {
+    pub picture_id: VASurfaceID,
+    pub pic_order_cnt: i32,
+    pub flags: u32,
     pub va_reserved: [u32; 4usize],
 }
+pub type VAPictureHEVC = _VAPictureHEVC;
 #[repr(C)]
 #[derive(Copy, Clone)]
-pub union _VAPictureParameterBufferVP8__bindgen_ty_1 {
-    pub bits: _VAPictureParameterBufferVP8__bindgen_ty_1__bindgen_ty_1,
+pub struct _VAPictureParameterBufferHEVC {
+    pub CurrPic: VAPictureHEVC,
+    pub ReferenceFrames: [VAPictureHEVC; 15usize],
+    pub pic_width_in_luma_samples: u16,
+    pub pic_height_in_luma_samples: u16,
+    pub pic_fields: _VAPictureParameterBufferHEVC__bindgen_ty_1,
+    pub sps_max_dec_pic_buffering_minus1: u8,
+    pub bit_depth_luma_minus8: u8,
+    pub bit_depth_chroma_minus8: u8,
+    pub pcm_sample_bit_depth_luma_minus1: u8,
+    pub pcm_sample_bit_depth_chroma_minus1: u8,
+    pub log2_min_luma_coding_block_size_minus3: u8,
+    pub log2_diff_max_min_luma_coding_block_size: u8,
+    pub log2_min_transform_block_size_minus2: u8,
+    pub log2_diff_max_min_transform_block_size: u8,
+    pub log2_min_pcm_luma_coding_block_size_minus3: u8,
+    pub log2_diff_max_min_pcm_luma_coding_block_size: u8,
+    pub max_transform_hierarchy_depth_intra: u8,
+    pub max_transform_hierarchy_depth_inter: u8,
+    pub init_qp_minus26: i8,
+    pub diff_cu_qp_delta_depth: u8,
+    pub pps_cb_qp_offset: i8,
+    pub pps_cr_qp_offset: i8,
+    pub log2_parallel_merge_level_minus2: u8,
+    pub num_tile_columns_minus1: u8,
+    pub num_tile_rows_minus1: u8,
+    pub column_width_minus1: [u16; 19usize],
+    pub row_height_minus1: [u16; 21usize],
+    pub slice_parsing_fields: _VAPictureParameterBufferHEVC__bindgen_ty_2,
+    pub log2_max_pic_order_cnt_lsb_minus4: u8,
+    pub num_short_term_ref_pic_sets: u8,
+    pub num_long_term_ref_pic_sps: u8,
+    pub num_ref_idx_l0_default_active_minus1: u8,
+    pub num_ref_idx_l1_default_active_minus1: u8,
+    pub pps_beta_offset_div2: i8,
+    pub pps_tc_offset_div2: i8,
+    pub num_extra_slice_header_bits: u8,
+    pub st_rps_bits: u32,
+    pub va_reserved: [u32; 8usize],
+}
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub union _VAPictureParameterBufferHEVC__bindgen_ty_1 {
+    pub bits: _VAPictureParameterBufferHEVC__bindgen_ty_1__bindgen_ty_1,
     pub value: u32,
 }
 #[repr(C)]
 #[repr(align(4))]
 #[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
-pub struct _VAPictureParameterBufferVP8__bindgen_ty_1__bindgen_ty_1 {
+pub struct _VAPictureParameterBufferHEVC__bindgen_ty_1__bindgen_ty_1 {
     pub _bitfield_align_1: [u8; 0],
-    pub _bitfield_1: __BindgenBitfieldUnit<[u8; 3usize]>,
-    pub __bindgen_padding_0: u8,
+    pub _bitfield_1: __BindgenBitfieldUnit<[u8; 4usize]>,
 }
-impl _VAPictureParameterBufferVP8__bindgen_ty_1__bindgen_ty_1 {
+impl _VAPictureParameterBufferHEVC__bindgen_ty_1__bindgen_ty_1 {
     #[inline]
-    pub fn key_frame(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(0usize, 1u8) as u32) }
+    pub fn chroma_format_idc(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(0usize, 2u8) as u32) }
     }
     #[inline]
-    pub fn set_key_frame(&mut self, val: u32) {
+    pub fn set_chroma_format_idc(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(0usize, 1u8, val as u64)
+            self._bitfield_1.set(0usize, 2u8, val as u64)
         }
     }
     #[inline]
-    pub fn version(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(1usize, 3u8) as u32) }
+    pub fn separate_colour_plane_flag(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(2usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_version(&mut self, val: u32) {
+    pub fn set_separate_colour_plane_flag(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(1usize, 3u8, val as u64)
+            self._bitfield_1.set(2usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn segmentation_enabled(&self) -> u32 {
+    pub fn pcm_enabled_flag(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(3usize, 1u8) as u32) }
+    }
+    #[inline]
+    pub fn set_pcm_enabled_flag(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(3usize, 1u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn scaling_list_enabled_flag(&self) -> u32 {
         unsafe { ::std::mem::transmute(self._bitfield_1.get(4usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_segmentation_enabled(&mut self, val: u32) {
+    pub fn set_scaling_list_enabled_flag(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
             self._bitfield_1.set(4usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn update_mb_segmentation_map(&self) -> u32 {
+    pub fn transform_skip_enabled_flag(&self) -> u32 {
         unsafe { ::std::mem::transmute(self._bitfield_1.get(5usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_update_mb_segmentation_map(&mut self, val: u32) {
+    pub fn set_transform_skip_enabled_flag(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
             self._bitfield_1.set(5usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn update_segment_feature_data(&self) -> u32 {
+    pub fn amp_enabled_flag(&self) -> u32 {
         unsafe { ::std::mem::transmute(self._bitfield_1.get(6usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_update_segment_feature_data(&mut self, val: u32) {
+    pub fn set_amp_enabled_flag(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
             self._bitfield_1.set(6usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn filter_type(&self) -> u32 {
+    pub fn strong_intra_smoothing_enabled_flag(&self) -> u32 {
         unsafe { ::std::mem::transmute(self._bitfield_1.get(7usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_filter_type(&mut self, val: u32) {
+    pub fn set_strong_intra_smoothing_enabled_flag(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
             self._bitfield_1.set(7usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn sharpness_level(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(8usize, 3u8) as u32) }
+    pub fn sign_data_hiding_enabled_flag(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(8usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_sharpness_level(&mut self, val: u32) {
+    pub fn set_sign_data_hiding_enabled_flag(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(8usize, 3u8, val as u64)
+            self._bitfield_1.set(8usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn loop_filter_adj_enable(&self) -> u32 {
+    pub fn constrained_intra_pred_flag(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(9usize, 1u8) as u32) }
+    }
+    #[inline]
+    pub fn set_constrained_intra_pred_flag(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(9usize, 1u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn cu_qp_delta_enabled_flag(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(10usize, 1u8) as u32) }
+    }
+    #[inline]
+    pub fn set_cu_qp_delta_enabled_flag(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(10usize, 1u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn weighted_pred_flag(&self) -> u32 {
         unsafe { ::std::mem::transmute(self._bitfield_1.get(11usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_loop_filter_adj_enable(&mut self, val: u32) {
+    pub fn set_weighted_pred_flag(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
             self._bitfield_1.set(11usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn mode_ref_lf_delta_update(&self) -> u32 {
+    pub fn weighted_bipred_flag(&self) -> u32 {
         unsafe { ::std::mem::transmute(self._bitfield_1.get(12usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_mode_ref_lf_delta_update(&mut self, val: u32) {
+    pub fn set_weighted_bipred_flag(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
             self._bitfield_1.set(12usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn sign_bias_golden(&self) -> u32 {
+    pub fn transquant_bypass_enabled_flag(&self) -> u32 {
         unsafe { ::std::mem::transmute(self._bitfield_1.get(13usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_sign_bias_golden(&mut self, val: u32) {
+    pub fn set_transquant_bypass_enabled_flag(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
             self._bitfield_1.set(13usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn sign_bias_alternate(&self) -> u32 {
+    pub fn tiles_enabled_flag(&self) -> u32 {
         unsafe { ::std::mem::transmute(self._bitfield_1.get(14usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_sign_bias_alternate(&mut self, val: u32) {
+    pub fn set_tiles_enabled_flag(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
             self._bitfield_1.set(14usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn mb_no_coeff_skip(&self) -> u32 {
+    pub fn entropy_coding_sync_enabled_flag(&self) -> u32 {
         unsafe { ::std::mem::transmute(self._bitfield_1.get(15usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_mb_no_coeff_skip(&mut self, val: u32) {
+    pub fn set_entropy_coding_sync_enabled_flag(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
             self._bitfield_1.set(15usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn loop_filter_disable(&self) -> u32 {
+    pub fn pps_loop_filter_across_slices_enabled_flag(&self) -> u32 {
         unsafe { ::std::mem::transmute(self._bitfield_1.get(16usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_loop_filter_disable(&mut self, val: u32) {
+    pub fn set_pps_loop_filter_across_slices_enabled_flag(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
             self._bitfield_1.set(16usize, 1u8, val as u64)
         }
     }
     #[inline]
+    pub fn loop_filter_across_tiles_enabled_flag(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(17usize, 1u8) as u32) }
+    }
+    #[inline]
+    pub fn set_loop_filter_across_tiles_enabled_flag(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(17usize, 1u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn pcm_loop_filter_disabled_flag(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(18usize, 1u8) as u32) }
+    }
+    #[inline]
+    pub fn set_pcm_loop_filter_disabled_flag(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(18usize, 1u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn NoPicReorderingFlag(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(19usize, 1u8) as u32) }
+    }
+    #[inline]
+    pub fn set_NoPicReorderingFlag(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(19usize, 1u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn NoBiPredFlag(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(20usize, 1u8) as u32) }
+    }
+    #[inline]
+    pub fn set_NoBiPredFlag(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(20usize, 1u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn ReservedBits(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(21usize, 11u8) as u32) }
+    }
+    #[inline]
+    pub fn set_ReservedBits(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(21usize, 11u8, val as u64)
+        }
+    }
+    #[inline]
     pub fn new_bitfield_1(
-        key_frame: u32,
-        version: u32,
-        segmentation_enabled: u32,
-        update_mb_segmentation_map: u32,
-        update_segment_feature_data: u32,
-        filter_type: u32,
-        sharpness_level: u32,
-        loop_filter_adj_enable: u32,
-        mode_ref_lf_delta_update: u32,
-        sign_bias_golden: u32,
-        sign_bias_alternate: u32,
-        mb_no_coeff_skip: u32,
-        loop_filter_disable: u32,
-    ) -> __BindgenBitfieldUnit<[u8; 3usize]> {
-        let mut __bindgen_bitfield_unit: __BindgenBitfieldUnit<[u8; 3usize]> = Default::default();
-        __bindgen_bitfield_unit.set(0usize, 1u8, {
-            let key_frame: u32 = unsafe { ::std::mem::transmute(key_frame) };
-            key_frame as u64
+        chroma_format_idc: u32,
+        separate_colour_plane_flag: u32,
+        pcm_enabled_flag: u32,
+        scaling_list_enabled_flag: u32,
+        transform_skip_enabled_flag: u32,
+        amp_enabled_flag: u32,
+        strong_intra_smoothing_enabled_flag: u32,
+        sign_data_hiding_enabled_flag: u32,
+        constrained_intra_pred_flag: u32,
+        cu_qp_delta_enabled_flag: u32,
+        weighted_pred_flag: u32,
+        weighted_bipred_flag: u32,
+        transquant_bypass_enabled_flag: u32,
+        tiles_enabled_flag: u32,
+        entropy_coding_sync_enabled_flag: u32,
+        pps_loop_filter_across_slices_enabled_flag: u32,
+        loop_filter_across_tiles_enabled_flag: u32,
+        pcm_loop_filter_disabled_flag: u32,
+        NoPicReorderingFlag: u32,
+        NoBiPredFlag: u32,
+        ReservedBits: u32,
+    ) -> __BindgenBitfieldUnit<[u8; 4usize]> {
+        let mut __bindgen_bitfield_unit: __BindgenBitfieldUnit<[u8; 4usize]> = Default::default();
+        __bindgen_bitfield_unit.set(0usize, 2u8, {
+            let chroma_format_idc: u32 = unsafe { ::std::mem::transmute(chroma_format_idc) };
+            chroma_format_idc as u64
         });
-        __bindgen_bitfield_unit.set(1usize, 3u8, {
-            let version: u32 = unsafe { ::std::mem::transmute(version) };
-            version as u64
+        __bindgen_bitfield_unit.set(2usize, 1u8, {
+            let separate_colour_plane_flag: u32 =
+                unsafe { ::std::mem::transmute(separate_colour_plane_flag) };
+            separate_colour_plane_flag as u64
+        });
+        __bindgen_bitfield_unit.set(3usize, 1u8, {
+            let pcm_enabled_flag: u32 = unsafe { ::std::mem::transmute(pcm_enabled_flag) };
+            pcm_enabled_flag as u64
         });
         __bindgen_bitfield_unit.set(4usize, 1u8, {
-            let segmentation_enabled: u32 = unsafe { ::std::mem::transmute(segmentation_enabled) };
-            segmentation_enabled as u64
+            let scaling_list_enabled_flag: u32 =
+                unsafe { ::std::mem::transmute(scaling_list_enabled_flag) };
+            scaling_list_enabled_flag as u64
         });
         __bindgen_bitfield_unit.set(5usize, 1u8, {
-            let update_mb_segmentation_map: u32 =
-                unsafe { ::std::mem::transmute(update_mb_segmentation_map) };
-            update_mb_segmentation_map as u64
+            let transform_skip_enabled_flag: u32 =
+                unsafe { ::std::mem::transmute(transform_skip_enabled_flag) };
+            transform_skip_enabled_flag as u64
         });
         __bindgen_bitfield_unit.set(6usize, 1u8, {
-            let update_segment_feature_data: u32 =
-                unsafe { ::std::mem::transmute(update_segment_feature_data) };
-            update_segment_feature_data as u64
+            let amp_enabled_flag: u32 = unsafe { ::std::mem::transmute(amp_enabled_flag) };
+            amp_enabled_flag as u64
         });
         __bindgen_bitfield_unit.set(7usize, 1u8, {
-            let filter_type: u32 = unsafe { ::std::mem::transmute(filter_type) };
-            filter_type as u64
+            let strong_intra_smoothing_enabled_flag: u32 =
+                unsafe { ::std::mem::transmute(strong_intra_smoothing_enabled_flag) };
+            strong_intra_smoothing_enabled_flag as u64
         });
-        __bindgen_bitfield_unit.set(8usize, 3u8, {
-            let sharpness_level: u32 = unsafe { ::std::mem::transmute(sharpness_level) };
-            sharpness_level as u64
+        __bindgen_bitfield_unit.set(8usize, 1u8, {
+            let sign_data_hiding_enabled_flag: u32 =
+                unsafe { ::std::mem::transmute(sign_data_hiding_enabled_flag) };
+            sign_data_hiding_enabled_flag as u64
+        });
+        __bindgen_bitfield_unit.set(9usize, 1u8, {
+            let constrained_intra_pred_flag: u32 =
+                unsafe { ::std::mem::transmute(constrained_intra_pred_flag) };
+            constrained_intra_pred_flag as u64
+        });
+        __bindgen_bitfield_unit.set(10usize, 1u8, {
+            let cu_qp_delta_enabled_flag: u32 =
+                unsafe { ::std::mem::transmute(cu_qp_delta_enabled_flag) };
+            cu_qp_delta_enabled_flag as u64
         });
         __bindgen_bitfield_unit.set(11usize, 1u8, {
-            let loop_filter_adj_enable: u32 =
-                unsafe { ::std::mem::transmute(loop_filter_adj_enable) };
-            loop_filter_adj_enable as u64
+            let weighted_pred_flag: u32 = unsafe { ::std::mem::transmute(weighted_pred_flag) };
+            weighted_pred_flag as u64
         });
         __bindgen_bitfield_unit.set(12usize, 1u8, {
-            let mode_ref_lf_delta_update: u32 =
-                unsafe { ::std::mem::transmute(mode_ref_lf_delta_update) };
-            mode_ref_lf_delta_update as u64
+            let weighted_bipred_flag: u32 = unsafe { ::std::mem::transmute(weighted_bipred_flag) };
+            weighted_bipred_flag as u64
         });
         __bindgen_bitfield_unit.set(13usize, 1u8, {
-            let sign_bias_golden: u32 = unsafe { ::std::mem::transmute(sign_bias_golden) };
-            sign_bias_golden as u64
+            let transquant_bypass_enabled_flag: u32 =
+                unsafe { ::std::mem::transmute(transquant_bypass_enabled_flag) };
+            transquant_bypass_enabled_flag as u64
         });
         __bindgen_bitfield_unit.set(14usize, 1u8, {
-            let sign_bias_alternate: u32 = unsafe { ::std::mem::transmute(sign_bias_alternate) };
-            sign_bias_alternate as u64
+            let tiles_enabled_flag: u32 = unsafe { ::std::mem::transmute(tiles_enabled_flag) };
+            tiles_enabled_flag as u64
         });
         __bindgen_bitfield_unit.set(15usize, 1u8, {
-            let mb_no_coeff_skip: u32 = unsafe { ::std::mem::transmute(mb_no_coeff_skip) };
-            mb_no_coeff_skip as u64
+            let entropy_coding_sync_enabled_flag: u32 =
+                unsafe { ::std::mem::transmute(entropy_coding_sync_enabled_flag) };
+            entropy_coding_sync_enabled_flag as u64
         });
         __bindgen_bitfield_unit.set(16usize, 1u8, {
-            let loop_filter_disable: u32 = unsafe { ::std::mem::transmute(loop_filter_disable) };
-            loop_filter_disable as u64
+            let pps_loop_filter_across_slices_enabled_flag: u32 =
+                unsafe { ::std::mem::transmute(pps_loop_filter_across_slices_enabled_flag) };
+            pps_loop_filter_across_slices_enabled_flag as u64
+        });
+        __bindgen_bitfield_unit.set(17usize, 1u8, {
+            let loop_filter_across_tiles_enabled_flag: u32 =
+                unsafe { ::std::mem::transmute(loop_filter_across_tiles_enabled_flag) };
+            loop_filter_across_tiles_enabled_flag as u64
+        });
+        __bindgen_bitfield_unit.set(18usize, 1u8, {
+            let pcm_loop_filter_disabled_flag: u32 =
+                unsafe { ::std::mem::transmute(pcm_loop_filter_disabled_flag) };
+            pcm_loop_filter_disabled_flag as u64
+        });
+        __bindgen_bitfield_unit.set(19usize, 1u8, {
+            let NoPicReorderingFlag: u32 = unsafe { ::std::mem::transmute(NoPicReorderingFlag) };
+            NoPicReorderingFlag as u64
+        });
+        __bindgen_bitfield_unit.set(20usize, 1u8, {
+            let NoBiPredFlag: u32 = unsafe { ::std::mem::transmute(NoBiPredFlag) };
+            NoBiPredFlag as u64
+        });
+        __bindgen_bitfield_unit.set(21usize, 11u8, {
+            let ReservedBits: u32 = unsafe { ::std::mem::transmute(ReservedBits) };
+            ReservedBits as u64
         });
         __bindgen_bitfield_unit
     }
 }
-impl Default for _VAPictureParameterBufferVP8__bindgen_ty_1 {
-    fn default() -> Self {
-        let mut s = ::std::mem::MaybeUninit::<Self>::uninit();
-        unsafe {
-            ::std::ptr::write_bytes(s.as_mut_ptr(), 0, 1);
-            s.assume_init()
-        }
-    }
-}
-impl Default for _VAPictureParameterBufferVP8 {
+impl Default for _VAPictureParameterBufferHEVC__bindgen_ty_1 {
     fn default() -> Self {
         let mut s = ::std::mem::MaybeUninit::<Self>::uninit();
         unsafe {
@@ -2225,434 +2378,567 @@ impl Default for _VAPictureParameterBufferVP8 {
         }
     }
 }
-pub type VAPictureParameterBufferVP8 = _VAPictureParameterBufferVP8;
 #[repr(C)]
-#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
-pub struct _VASliceParameterBufferVP8 {
-    pub slice_data_size: u32,
-    pub slice_data_offset: u32,
-    pub slice_data_flag: u32,
-    pub macroblock_offset: u32,
-    pub num_of_partitions: u8,
-    pub partition_size: [u32; 9usize],
-    pub va_reserved: [u32; 4usize],
+#[derive(Copy, Clone)]
+pub union _VAPictureParameterBufferHEVC__bindgen_ty_2 {
+    pub bits: _VAPictureParameterBufferHEVC__bindgen_ty_2__bindgen_ty_1,
+    pub value: u32,
 }
-pub type VASliceParameterBufferVP8 = _VASliceParameterBufferVP8;
 #[repr(C)]
+#[repr(align(4))]
 #[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
-pub struct _VAProbabilityDataBufferVP8 {
-    pub dct_coeff_probs: [[[[u8; 11usize]; 3usize]; 8usize]; 4usize],
-    pub va_reserved: [u32; 4usize],
-}
-pub type VAProbabilityDataBufferVP8 = _VAProbabilityDataBufferVP8;
-#[repr(C)]
-#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
-pub struct _VAIQMatrixBufferVP8 {
-    pub quantization_index: [[u16; 6usize]; 4usize],
-    pub va_reserved: [u32; 4usize],
-}
-pub type VAIQMatrixBufferVP8 = _VAIQMatrixBufferVP8;
-#[repr(C)]
-#[derive(Copy, Clone)]
-pub struct _VADecPictureParameterBufferVP9 {
-    pub frame_width: u16,
-    pub frame_height: u16,
-    pub reference_frames: [VASurfaceID; 8usize],
-    pub pic_fields: _VADecPictureParameterBufferVP9__bindgen_ty_1,
-    pub filter_level: u8,
-    pub sharpness_level: u8,
-    pub log2_tile_rows: u8,
-    pub log2_tile_columns: u8,
-    pub frame_header_length_in_bytes: u8,
-    pub first_partition_size: u16,
-    pub mb_segment_tree_probs: [u8; 7usize],
-    pub segment_pred_probs: [u8; 3usize],
-    pub profile: u8,
-    pub bit_depth: u8,
-    pub va_reserved: [u32; 8usize],
-}
-#[repr(C)]
-#[derive(Copy, Clone)]
-pub union _VADecPictureParameterBufferVP9__bindgen_ty_1 {
-    pub bits: _VADecPictureParameterBufferVP9__bindgen_ty_1__bindgen_ty_1,
-    pub value: u32,
-}
-#[repr(C)]
-#[repr(align(4))]
-#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
-pub struct _VADecPictureParameterBufferVP9__bindgen_ty_1__bindgen_ty_1 {
+pub struct _VAPictureParameterBufferHEVC__bindgen_ty_2__bindgen_ty_1 {
     pub _bitfield_align_1: [u8; 0],
     pub _bitfield_1: __BindgenBitfieldUnit<[u8; 4usize]>,
 }
-impl _VADecPictureParameterBufferVP9__bindgen_ty_1__bindgen_ty_1 {
+impl _VAPictureParameterBufferHEVC__bindgen_ty_2__bindgen_ty_1 {
     #[inline]
-    pub fn subsampling_x(&self) -> u32 {
+    pub fn lists_modification_present_flag(&self) -> u32 {
         unsafe { ::std::mem::transmute(self._bitfield_1.get(0usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_subsampling_x(&mut self, val: u32) {
+    pub fn set_lists_modification_present_flag(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
             self._bitfield_1.set(0usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn subsampling_y(&self) -> u32 {
+    pub fn long_term_ref_pics_present_flag(&self) -> u32 {
         unsafe { ::std::mem::transmute(self._bitfield_1.get(1usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_subsampling_y(&mut self, val: u32) {
+    pub fn set_long_term_ref_pics_present_flag(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
             self._bitfield_1.set(1usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn frame_type(&self) -> u32 {
+    pub fn sps_temporal_mvp_enabled_flag(&self) -> u32 {
         unsafe { ::std::mem::transmute(self._bitfield_1.get(2usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_frame_type(&mut self, val: u32) {
+    pub fn set_sps_temporal_mvp_enabled_flag(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
             self._bitfield_1.set(2usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn show_frame(&self) -> u32 {
+    pub fn cabac_init_present_flag(&self) -> u32 {
         unsafe { ::std::mem::transmute(self._bitfield_1.get(3usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_show_frame(&mut self, val: u32) {
+    pub fn set_cabac_init_present_flag(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
             self._bitfield_1.set(3usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn error_resilient_mode(&self) -> u32 {
+    pub fn output_flag_present_flag(&self) -> u32 {
         unsafe { ::std::mem::transmute(self._bitfield_1.get(4usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_error_resilient_mode(&mut self, val: u32) {
+    pub fn set_output_flag_present_flag(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
             self._bitfield_1.set(4usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn intra_only(&self) -> u32 {
+    pub fn dependent_slice_segments_enabled_flag(&self) -> u32 {
         unsafe { ::std::mem::transmute(self._bitfield_1.get(5usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_intra_only(&mut self, val: u32) {
+    pub fn set_dependent_slice_segments_enabled_flag(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
             self._bitfield_1.set(5usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn allow_high_precision_mv(&self) -> u32 {
+    pub fn pps_slice_chroma_qp_offsets_present_flag(&self) -> u32 {
         unsafe { ::std::mem::transmute(self._bitfield_1.get(6usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_allow_high_precision_mv(&mut self, val: u32) {
+    pub fn set_pps_slice_chroma_qp_offsets_present_flag(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
             self._bitfield_1.set(6usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn mcomp_filter_type(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(7usize, 3u8) as u32) }
+    pub fn sample_adaptive_offset_enabled_flag(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(7usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_mcomp_filter_type(&mut self, val: u32) {
+    pub fn set_sample_adaptive_offset_enabled_flag(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(7usize, 3u8, val as u64)
+            self._bitfield_1.set(7usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn frame_parallel_decoding_mode(&self) -> u32 {
+    pub fn deblocking_filter_override_enabled_flag(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(8usize, 1u8) as u32) }
+    }
+    #[inline]
+    pub fn set_deblocking_filter_override_enabled_flag(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(8usize, 1u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn pps_disable_deblocking_filter_flag(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(9usize, 1u8) as u32) }
+    }
+    #[inline]
+    pub fn set_pps_disable_deblocking_filter_flag(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(9usize, 1u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn slice_segment_header_extension_present_flag(&self) -> u32 {
         unsafe { ::std::mem::transmute(self._bitfield_1.get(10usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_frame_parallel_decoding_mode(&mut self, val: u32) {
+    pub fn set_slice_segment_header_extension_present_flag(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
             self._bitfield_1.set(10usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn reset_frame_context(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(11usize, 2u8) as u32) }
+    pub fn RapPicFlag(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(11usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_reset_frame_context(&mut self, val: u32) {
+    pub fn set_RapPicFlag(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(11usize, 2u8, val as u64)
+            self._bitfield_1.set(11usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn refresh_frame_context(&self) -> u32 {
+    pub fn IdrPicFlag(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(12usize, 1u8) as u32) }
+    }
+    #[inline]
+    pub fn set_IdrPicFlag(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(12usize, 1u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn IntraPicFlag(&self) -> u32 {
         unsafe { ::std::mem::transmute(self._bitfield_1.get(13usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_refresh_frame_context(&mut self, val: u32) {
+    pub fn set_IntraPicFlag(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
             self._bitfield_1.set(13usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn frame_context_idx(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(14usize, 2u8) as u32) }
+    pub fn ReservedBits(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(14usize, 18u8) as u32) }
     }
     #[inline]
-    pub fn set_frame_context_idx(&mut self, val: u32) {
+    pub fn set_ReservedBits(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(14usize, 2u8, val as u64)
+            self._bitfield_1.set(14usize, 18u8, val as u64)
         }
     }
     #[inline]
-    pub fn segmentation_enabled(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(16usize, 1u8) as u32) }
+    pub fn new_bitfield_1(
+        lists_modification_present_flag: u32,
+        long_term_ref_pics_present_flag: u32,
+        sps_temporal_mvp_enabled_flag: u32,
+        cabac_init_present_flag: u32,
+        output_flag_present_flag: u32,
+        dependent_slice_segments_enabled_flag: u32,
+        pps_slice_chroma_qp_offsets_present_flag: u32,
+        sample_adaptive_offset_enabled_flag: u32,
+        deblocking_filter_override_enabled_flag: u32,
+        pps_disable_deblocking_filter_flag: u32,
+        slice_segment_header_extension_present_flag: u32,
+        RapPicFlag: u32,
+        IdrPicFlag: u32,
+        IntraPicFlag: u32,
+        ReservedBits: u32,
+    ) -> __BindgenBitfieldUnit<[u8; 4usize]> {
+        let mut __bindgen_bitfield_unit: __BindgenBitfieldUnit<[u8; 4usize]> = Default::default();
+        __bindgen_bitfield_unit.set(0usize, 1u8, {
+            let lists_modification_present_flag: u32 =
+                unsafe { ::std::mem::transmute(lists_modification_present_flag) };
+            lists_modification_present_flag as u64
+        });
+        __bindgen_bitfield_unit.set(1usize, 1u8, {
+            let long_term_ref_pics_present_flag: u32 =
+                unsafe { ::std::mem::transmute(long_term_ref_pics_present_flag) };
+            long_term_ref_pics_present_flag as u64
+        });
+        __bindgen_bitfield_unit.set(2usize, 1u8, {
+            let sps_temporal_mvp_enabled_flag: u32 =
+                unsafe { ::std::mem::transmute(sps_temporal_mvp_enabled_flag) };
+            sps_temporal_mvp_enabled_flag as u64
+        });
+        __bindgen_bitfield_unit.set(3usize, 1u8, {
+            let cabac_init_present_flag: u32 =
+                unsafe { ::std::mem::transmute(cabac_init_present_flag) };
+            cabac_init_present_flag as u64
+        });
+        __bindgen_bitfield_unit.set(4usize, 1u8, {
+            let output_flag_present_flag: u32 =
+                unsafe { ::std::mem::transmute(output_flag_present_flag) };
+            output_flag_present_flag as u64
+        });
+        __bindgen_bitfield_unit.set(5usize, 1u8, {
+            let dependent_slice_segments_enabled_flag: u32 =
+                unsafe { ::std::mem::transmute(dependent_slice_segments_enabled_flag) };
+            dependent_slice_segments_enabled_flag as u64
+        });
+        __bindgen_bitfield_unit.set(6usize, 1u8, {
+            let pps_slice_chroma_qp_offsets_present_flag: u32 =
+                unsafe { ::std::mem::transmute(pps_slice_chroma_qp_offsets_present_flag) };
+            pps_slice_chroma_qp_offsets_present_flag as u64
+        });
+        __bindgen_bitfield_unit.set(7usize, 1u8, {
+            let sample_adaptive_offset_enabled_flag: u32 =
+                unsafe { ::std::mem::transmute(sample_adaptive_offset_enabled_flag) };
+            sample_adaptive_offset_enabled_flag as u64
+        });
+        __bindgen_bitfield_unit.set(8usize, 1u8, {
+            let deblocking_filter_override_enabled_flag: u32 =
+                unsafe { ::std::mem::transmute(deblocking_filter_override_enabled_flag) };
+            deblocking_filter_override_enabled_flag as u64
+        });
+        __bindgen_bitfield_unit.set(9usize, 1u8, {
+            let pps_disable_deblocking_filter_flag: u32 =
+                unsafe { ::std::mem::transmute(pps_disable_deblocking_filter_flag) };
+            pps_disable_deblocking_filter_flag as u64
+        });
+        __bindgen_bitfield_unit.set(10usize, 1u8, {
+            let slice_segment_header_extension_present_flag: u32 =
+                unsafe { ::std::mem::transmute(slice_segment_header_extension_present_flag) };
+            slice_segment_header_extension_present_flag as u64
+        });
+        __bindgen_bitfield_unit.set(11usize, 1u8, {
+            let RapPicFlag: u32 = unsafe { ::std::mem::transmute(RapPicFlag) };
+            RapPicFlag as u64
+        });
+        __bindgen_bitfield_unit.set(12usize, 1u8, {
+            let IdrPicFlag: u32 = unsafe { ::std::mem::transmute(IdrPicFlag) };
+            IdrPicFlag as u64
+        });
+        __bindgen_bitfield_unit.set(13usize, 1u8, {
+            let IntraPicFlag: u32 = unsafe { ::std::mem::transmute(IntraPicFlag) };
+            IntraPicFlag as u64
+        });
+        __bindgen_bitfield_unit.set(14usize, 18u8, {
+            let ReservedBits: u32 = unsafe { ::std::mem::transmute(ReservedBits) };
+            ReservedBits as u64
+        });
+        __bindgen_bitfield_unit
+    }
+}
+impl Default for _VAPictureParameterBufferHEVC__bindgen_ty_2 {
+    fn default() -> Self {
+        let mut s = ::std::mem::MaybeUninit::<Self>::uninit();
+        unsafe {
+            ::std::ptr::write_bytes(s.as_mut_ptr(), 0, 1);
+            s.assume_init()
+        }
+    }
+}
+impl Default for _VAPictureParameterBufferHEVC {
+    fn default() -> Self {
+        let mut s = ::std::mem::MaybeUninit::<Self>::uninit();
+        unsafe {
+            ::std::ptr::write_bytes(s.as_mut_ptr(), 0, 1);
+            s.assume_init()
+        }
+    }
+}
+pub type VAPictureParameterBufferHEVC = _VAPictureParameterBufferHEVC;
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct _VASliceParameterBufferHEVC {
+    pub slice_data_size: u32,
+    pub slice_data_offset: u32,
+    pub slice_data_flag: u32,
+    pub slice_data_byte_offset: u32,
+    pub slice_segment_address: u32,
+    pub RefPicList: [[u8; 15usize]; 2usize],
+    pub LongSliceFlags: _VASliceParameterBufferHEVC__bindgen_ty_1,
+    pub collocated_ref_idx: u8,
+    pub num_ref_idx_l0_active_minus1: u8,
+    pub num_ref_idx_l1_active_minus1: u8,
+    pub slice_qp_delta: i8,
+    pub slice_cb_qp_offset: i8,
+    pub slice_cr_qp_offset: i8,
+    pub slice_beta_offset_div2: i8,
+    pub slice_tc_offset_div2: i8,
+    pub luma_log2_weight_denom: u8,
+    pub delta_chroma_log2_weight_denom: i8,
+    pub delta_luma_weight_l0: [i8; 15usize],
+    pub luma_offset_l0: [i8; 15usize],
+    pub delta_chroma_weight_l0: [[i8; 2usize]; 15usize],
+    pub ChromaOffsetL0: [[i8; 2usize]; 15usize],
+    pub delta_luma_weight_l1: [i8; 15usize],
+    pub luma_offset_l1: [i8; 15usize],
+    pub delta_chroma_weight_l1: [[i8; 2usize]; 15usize],
+    pub ChromaOffsetL1: [[i8; 2usize]; 15usize],
+    pub five_minus_max_num_merge_cand: u8,
+    pub num_entry_point_offsets: u16,
+    pub entry_offset_to_subset_array: u16,
+    pub slice_data_num_emu_prevn_bytes: u16,
+    pub va_reserved: [u32; 2usize],
+}
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub union _VASliceParameterBufferHEVC__bindgen_ty_1 {
+    pub value: u32,
+    pub fields: _VASliceParameterBufferHEVC__bindgen_ty_1__bindgen_ty_1,
+}
+#[repr(C)]
+#[repr(align(4))]
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct _VASliceParameterBufferHEVC__bindgen_ty_1__bindgen_ty_1 {
+    pub _bitfield_align_1: [u8; 0],
+    pub _bitfield_1: __BindgenBitfieldUnit<[u8; 4usize]>,
+}
+impl _VASliceParameterBufferHEVC__bindgen_ty_1__bindgen_ty_1 {
+    #[inline]
+    pub fn LastSliceOfPic(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(0usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_segmentation_enabled(&mut self, val: u32) {
+    pub fn set_LastSliceOfPic(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(16usize, 1u8, val as u64)
+            self._bitfield_1.set(0usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn segmentation_temporal_update(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(17usize, 1u8) as u32) }
+    pub fn dependent_slice_segment_flag(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(1usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_segmentation_temporal_update(&mut self, val: u32) {
+    pub fn set_dependent_slice_segment_flag(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(17usize, 1u8, val as u64)
+            self._bitfield_1.set(1usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn segmentation_update_map(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(18usize, 1u8) as u32) }
+    pub fn slice_type(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(2usize, 2u8) as u32) }
     }
     #[inline]
-    pub fn set_segmentation_update_map(&mut self, val: u32) {
+    pub fn set_slice_type(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(18usize, 1u8, val as u64)
+            self._bitfield_1.set(2usize, 2u8, val as u64)
         }
     }
     #[inline]
-    pub fn last_ref_frame(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(19usize, 3u8) as u32) }
+    pub fn color_plane_id(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(4usize, 2u8) as u32) }
     }
     #[inline]
-    pub fn set_last_ref_frame(&mut self, val: u32) {
+    pub fn set_color_plane_id(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(19usize, 3u8, val as u64)
+            self._bitfield_1.set(4usize, 2u8, val as u64)
         }
     }
     #[inline]
-    pub fn last_ref_frame_sign_bias(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(22usize, 1u8) as u32) }
+    pub fn slice_sao_luma_flag(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(6usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_last_ref_frame_sign_bias(&mut self, val: u32) {
+    pub fn set_slice_sao_luma_flag(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(22usize, 1u8, val as u64)
+            self._bitfield_1.set(6usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn golden_ref_frame(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(23usize, 3u8) as u32) }
+    pub fn slice_sao_chroma_flag(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(7usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_golden_ref_frame(&mut self, val: u32) {
+    pub fn set_slice_sao_chroma_flag(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(23usize, 3u8, val as u64)
+            self._bitfield_1.set(7usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn golden_ref_frame_sign_bias(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(26usize, 1u8) as u32) }
+    pub fn mvd_l1_zero_flag(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(8usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_golden_ref_frame_sign_bias(&mut self, val: u32) {
+    pub fn set_mvd_l1_zero_flag(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(26usize, 1u8, val as u64)
+            self._bitfield_1.set(8usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn alt_ref_frame(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(27usize, 3u8) as u32) }
+    pub fn cabac_init_flag(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(9usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_alt_ref_frame(&mut self, val: u32) {
+    pub fn set_cabac_init_flag(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(27usize, 3u8, val as u64)
+            self._bitfield_1.set(9usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn alt_ref_frame_sign_bias(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(30usize, 1u8) as u32) }
+    pub fn slice_temporal_mvp_enabled_flag(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(10usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_alt_ref_frame_sign_bias(&mut self, val: u32) {
+    pub fn set_slice_temporal_mvp_enabled_flag(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(30usize, 1u8, val as u64)
+            self._bitfield_1.set(10usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn lossless_flag(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(31usize, 1u8) as u32) }
+    pub fn slice_deblocking_filter_disabled_flag(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(11usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_lossless_flag(&mut self, val: u32) {
+    pub fn set_slice_deblocking_filter_disabled_flag(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(31usize, 1u8, val as u64)
+            self._bitfield_1.set(11usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn new_bitfield_1(
-        subsampling_x: u32,
-        subsampling_y: u32,
-        frame_type: u32,
-        show_frame: u32,
-        error_resilient_mode: u32,
-        intra_only: u32,
-        allow_high_precision_mv: u32,
-        mcomp_filter_type: u32,
-        frame_parallel_decoding_mode: u32,
-        reset_frame_context: u32,
-        refresh_frame_context: u32,
-        frame_context_idx: u32,
-        segmentation_enabled: u32,
-        segmentation_temporal_update: u32,
-        segmentation_update_map: u32,
-        last_ref_frame: u32,
-        last_ref_frame_sign_bias: u32,
-        golden_ref_frame: u32,
-        golden_ref_frame_sign_bias: u32,
-        alt_ref_frame: u32,
-        alt_ref_frame_sign_bias: u32,
-        lossless_flag: u32,
+    pub fn collocated_from_l0_flag(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(12usize, 1u8) as u32) }
+    }
+    #[inline]
+    pub fn set_collocated_from_l0_flag(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(12usize, 1u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn slice_loop_filter_across_slices_enabled_flag(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(13usize, 1u8) as u32) }
+    }
+    #[inline]
+    pub fn set_slice_loop_filter_across_slices_enabled_flag(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(13usize, 1u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn reserved(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(14usize, 18u8) as u32) }
+    }
+    #[inline]
+    pub fn set_reserved(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(14usize, 18u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn new_bitfield_1(
+        LastSliceOfPic: u32,
+        dependent_slice_segment_flag: u32,
+        slice_type: u32,
+        color_plane_id: u32,
+        slice_sao_luma_flag: u32,
+        slice_sao_chroma_flag: u32,
+        mvd_l1_zero_flag: u32,
+        cabac_init_flag: u32,
+        slice_temporal_mvp_enabled_flag: u32,
+        slice_deblocking_filter_disabled_flag: u32,
+        collocated_from_l0_flag: u32,
+        slice_loop_filter_across_slices_enabled_flag: u32,
+        reserved: u32,
     ) -> __BindgenBitfieldUnit<[u8; 4usize]> {
         let mut __bindgen_bitfield_unit: __BindgenBitfieldUnit<[u8; 4usize]> = Default::default();
         __bindgen_bitfield_unit.set(0usize, 1u8, {
-            let subsampling_x: u32 = unsafe { ::std::mem::transmute(subsampling_x) };
-            subsampling_x as u64
+            let LastSliceOfPic: u32 = unsafe { ::std::mem::transmute(LastSliceOfPic) };
+            LastSliceOfPic as u64
         });
         __bindgen_bitfield_unit.set(1usize, 1u8, {
-            let subsampling_y: u32 = unsafe { ::std::mem::transmute(subsampling_y) };
-            subsampling_y as u64
-        });
-        __bindgen_bitfield_unit.set(2usize, 1u8, {
-            let frame_type: u32 = unsafe { ::std::mem::transmute(frame_type) };
-            frame_type as u64
-        });
-        __bindgen_bitfield_unit.set(3usize, 1u8, {
-            let show_frame: u32 = unsafe { ::std::mem::transmute(show_frame) };
-            show_frame as u64
+            let dependent_slice_segment_flag: u32 =
+                unsafe { ::std::mem::transmute(dependent_slice_segment_flag) };
+            dependent_slice_segment_flag as u64
         });
-        __bindgen_bitfield_unit.set(4usize, 1u8, {
-            let error_resilient_mode: u32 = unsafe { ::std::mem::transmute(error_resilient_mode) };
-            error_resilient_mode as u64
+        __bindgen_bitfield_unit.set(2usize, 2u8, {
+            let slice_type: u32 = unsafe { ::std::mem::transmute(slice_type) };
+            slice_type as u64
         });
-        __bindgen_bitfield_unit.set(5usize, 1u8, {
-            let intra_only: u32 = unsafe { ::std::mem::transmute(intra_only) };
-            intra_only as u64
+        __bindgen_bitfield_unit.set(4usize, 2u8, {
+            let color_plane_id: u32 = unsafe { ::std::mem::transmute(color_plane_id) };
+            color_plane_id as u64
         });
         __bindgen_bitfield_unit.set(6usize, 1u8, {
-            let allow_high_precision_mv: u32 =
-                unsafe { ::std::mem::transmute(allow_high_precision_mv) };
-            allow_high_precision_mv as u64
-        });
-        __bindgen_bitfield_unit.set(7usize, 3u8, {
-            let mcomp_filter_type: u32 = unsafe { ::std::mem::transmute(mcomp_filter_type) };
-            mcomp_filter_type as u64
-        });
-        __bindgen_bitfield_unit.set(10usize, 1u8, {
-            let frame_parallel_decoding_mode: u32 =
-                unsafe { ::std::mem::transmute(frame_parallel_decoding_mode) };
-            frame_parallel_decoding_mode as u64
-        });
-        __bindgen_bitfield_unit.set(11usize, 2u8, {
-            let reset_frame_context: u32 = unsafe { ::std::mem::transmute(reset_frame_context) };
-            reset_frame_context as u64
-        });
-        __bindgen_bitfield_unit.set(13usize, 1u8, {
-            let refresh_frame_context: u32 =
-                unsafe { ::std::mem::transmute(refresh_frame_context) };
-            refresh_frame_context as u64
-        });
-        __bindgen_bitfield_unit.set(14usize, 2u8, {
-            let frame_context_idx: u32 = unsafe { ::std::mem::transmute(frame_context_idx) };
-            frame_context_idx as u64
-        });
-        __bindgen_bitfield_unit.set(16usize, 1u8, {
-            let segmentation_enabled: u32 = unsafe { ::std::mem::transmute(segmentation_enabled) };
-            segmentation_enabled as u64
-        });
-        __bindgen_bitfield_unit.set(17usize, 1u8, {
-            let segmentation_temporal_update: u32 =
-                unsafe { ::std::mem::transmute(segmentation_temporal_update) };
-            segmentation_temporal_update as u64
+            let slice_sao_luma_flag: u32 = unsafe { ::std::mem::transmute(slice_sao_luma_flag) };
+            slice_sao_luma_flag as u64
         });
-        __bindgen_bitfield_unit.set(18usize, 1u8, {
-            let segmentation_update_map: u32 =
-                unsafe { ::std::mem::transmute(segmentation_update_map) };
-            segmentation_update_map as u64
+        __bindgen_bitfield_unit.set(7usize, 1u8, {
+            let slice_sao_chroma_flag: u32 =
+                unsafe { ::std::mem::transmute(slice_sao_chroma_flag) };
+            slice_sao_chroma_flag as u64
         });
-        __bindgen_bitfield_unit.set(19usize, 3u8, {
-            let last_ref_frame: u32 = unsafe { ::std::mem::transmute(last_ref_frame) };
-            last_ref_frame as u64
+        __bindgen_bitfield_unit.set(8usize, 1u8, {
+            let mvd_l1_zero_flag: u32 = unsafe { ::std::mem::transmute(mvd_l1_zero_flag) };
+            mvd_l1_zero_flag as u64
         });
-        __bindgen_bitfield_unit.set(22usize, 1u8, {
-            let last_ref_frame_sign_bias: u32 =
-                unsafe { ::std::mem::transmute(last_ref_frame_sign_bias) };
-            last_ref_frame_sign_bias as u64
+        __bindgen_bitfield_unit.set(9usize, 1u8, {
+            let cabac_init_flag: u32 = unsafe { ::std::mem::transmute(cabac_init_flag) };
+            cabac_init_flag as u64
         });
-        __bindgen_bitfield_unit.set(23usize, 3u8, {
-            let golden_ref_frame: u32 = unsafe { ::std::mem::transmute(golden_ref_frame) };
-            golden_ref_frame as u64
+        __bindgen_bitfield_unit.set(10usize, 1u8, {
+            let slice_temporal_mvp_enabled_flag: u32 =
+                unsafe { ::std::mem::transmute(slice_temporal_mvp_enabled_flag) };
+            slice_temporal_mvp_enabled_flag as u64
         });
-        __bindgen_bitfield_unit.set(26usize, 1u8, {
-            let golden_ref_frame_sign_bias: u32 =
-                unsafe { ::std::mem::transmute(golden_ref_frame_sign_bias) };
-            golden_ref_frame_sign_bias as u64
+        __bindgen_bitfield_unit.set(11usize, 1u8, {
+            let slice_deblocking_filter_disabled_flag: u32 =
+                unsafe { ::std::mem::transmute(slice_deblocking_filter_disabled_flag) };
+            slice_deblocking_filter_disabled_flag as u64
         });
-        __bindgen_bitfield_unit.set(27usize, 3u8, {
-            let alt_ref_frame: u32 = unsafe { ::std::mem::transmute(alt_ref_frame) };
-            alt_ref_frame as u64
+        __bindgen_bitfield_unit.set(12usize, 1u8, {
+            let collocated_from_l0_flag: u32 =
+                unsafe { ::std::mem::transmute(collocated_from_l0_flag) };
+            collocated_from_l0_flag as u64
         });
-        __bindgen_bitfield_unit.set(30usize, 1u8, {
-            let alt_ref_frame_sign_bias: u32 =
-                unsafe { ::std::mem::transmute(alt_ref_frame_sign_bias) };
-            alt_ref_frame_sign_bias as u64
+        __bindgen_bitfield_unit.set(13usize, 1u8, {
+            let slice_loop_filter_across_slices_enabled_flag: u32 =
+                unsafe { ::std::mem::transmute(slice_loop_filter_across_slices_enabled_flag) };
+            slice_loop_filter_across_slices_enabled_flag as u64
         });
-        __bindgen_bitfield_unit.set(31usize, 1u8, {
-            let lossless_flag: u32 = unsafe { ::std::mem::transmute(lossless_flag) };
-            lossless_flag as u64
+        __bindgen_bitfield_unit.set(14usize, 18u8, {
+            let reserved: u32 = unsafe { ::std::mem::transmute(reserved) };
+            reserved as u64
         });
         __bindgen_bitfield_unit
     }
 }
-impl Default for _VADecPictureParameterBufferVP9__bindgen_ty_1 {
+impl Default for _VASliceParameterBufferHEVC__bindgen_ty_1 {
     fn default() -> Self {
         let mut s = ::std::mem::MaybeUninit::<Self>::uninit();
         unsafe {
@@ -2661,7 +2947,7 @@ impl Default for _VADecPictureParameterBufferVP9__bindgen_ty_1 {
         }
     }
 }
-impl Default for _VADecPictureParameterBufferVP9 {
+impl Default for _VASliceParameterBufferHEVC {
     fn default() -> Self {
         let mut s = ::std::mem::MaybeUninit::<Self>::uninit();
         unsafe {
@@ -2670,308 +2956,205 @@ impl Default for _VADecPictureParameterBufferVP9 {
         }
     }
 }
-pub type VADecPictureParameterBufferVP9 = _VADecPictureParameterBufferVP9;
+pub type VASliceParameterBufferHEVC = _VASliceParameterBufferHEVC;
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct _VAIQMatrixBufferHEVC {
+    pub ScalingList4x4: [[u8; 16usize]; 6usize],
+    pub ScalingList8x8: [[u8; 64usize]; 6usize],
+    pub ScalingList16x16: [[u8; 64usize]; 6usize],
+    pub ScalingList32x32: [[u8; 64usize]; 2usize],
+    pub ScalingListDC16x16: [u8; 6usize],
+    pub ScalingListDC32x32: [u8; 2usize],
+    pub va_reserved: [u32; 4usize],
+}
+impl Default for _VAIQMatrixBufferHEVC {
+    fn default() -> Self {
+        let mut s = ::std::mem::MaybeUninit::<Self>::uninit();
+        unsafe {
+            ::std::ptr::write_bytes(s.as_mut_ptr(), 0, 1);
+            s.assume_init()
+        }
+    }
+}
+pub type VAIQMatrixBufferHEVC = _VAIQMatrixBufferHEVC;
 #[repr(C)]
 #[derive(Copy, Clone)]
-pub struct _VASegmentParameterVP9 {
-    pub segment_flags: _VASegmentParameterVP9__bindgen_ty_1,
-    pub filter_level: [[u8; 2usize]; 4usize],
-    pub luma_ac_quant_scale: i16,
-    pub luma_dc_quant_scale: i16,
-    pub chroma_ac_quant_scale: i16,
-    pub chroma_dc_quant_scale: i16,
+pub struct _VAPictureParameterBufferVP8 {
+    pub frame_width: u32,
+    pub frame_height: u32,
+    pub last_ref_frame: VASurfaceID,
+    pub golden_ref_frame: VASurfaceID,
+    pub alt_ref_frame: VASurfaceID,
+    pub out_of_loop_frame: VASurfaceID,
+    pub pic_fields: _VAPictureParameterBufferVP8__bindgen_ty_1,
+    pub mb_segment_tree_probs: [u8; 3usize],
+    pub loop_filter_level: [u8; 4usize],
+    pub loop_filter_deltas_ref_frame: [i8; 4usize],
+    pub loop_filter_deltas_mode: [i8; 4usize],
+    pub prob_skip_false: u8,
+    pub prob_intra: u8,
+    pub prob_last: u8,
+    pub prob_gf: u8,
+    pub y_mode_probs: [u8; 4usize],
+    pub uv_mode_probs: [u8; 3usize],
+    pub mv_probs: [[u8; 19usize]; 2usize],
+    pub bool_coder_ctx: VABoolCoderContextVPX,
     pub va_reserved: [u32; 4usize],
 }
 #[repr(C)]
 #[derive(Copy, Clone)]
-pub union _VASegmentParameterVP9__bindgen_ty_1 {
-    pub fields: _VASegmentParameterVP9__bindgen_ty_1__bindgen_ty_1,
-    pub value: u16,
+pub union _VAPictureParameterBufferVP8__bindgen_ty_1 {
+    pub bits: _VAPictureParameterBufferVP8__bindgen_ty_1__bindgen_ty_1,
+    pub value: u32,
 }
 #[repr(C)]
-#[repr(align(2))]
+#[repr(align(4))]
 #[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
-pub struct _VASegmentParameterVP9__bindgen_ty_1__bindgen_ty_1 {
+pub struct _VAPictureParameterBufferVP8__bindgen_ty_1__bindgen_ty_1 {
     pub _bitfield_align_1: [u8; 0],
-    pub _bitfield_1: __BindgenBitfieldUnit<[u8; 1usize]>,
+    pub _bitfield_1: __BindgenBitfieldUnit<[u8; 3usize]>,
     pub __bindgen_padding_0: u8,
 }
-impl _VASegmentParameterVP9__bindgen_ty_1__bindgen_ty_1 {
+impl _VAPictureParameterBufferVP8__bindgen_ty_1__bindgen_ty_1 {
     #[inline]
-    pub fn segment_reference_enabled(&self) -> u16 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(0usize, 1u8) as u16) }
+    pub fn key_frame(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(0usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_segment_reference_enabled(&mut self, val: u16) {
+    pub fn set_key_frame(&mut self, val: u32) {
         unsafe {
-            let val: u16 = ::std::mem::transmute(val);
+            let val: u32 = ::std::mem::transmute(val);
             self._bitfield_1.set(0usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn segment_reference(&self) -> u16 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(1usize, 2u8) as u16) }
+    pub fn version(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(1usize, 3u8) as u32) }
     }
     #[inline]
-    pub fn set_segment_reference(&mut self, val: u16) {
+    pub fn set_version(&mut self, val: u32) {
         unsafe {
-            let val: u16 = ::std::mem::transmute(val);
-            self._bitfield_1.set(1usize, 2u8, val as u64)
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(1usize, 3u8, val as u64)
         }
     }
     #[inline]
-    pub fn segment_reference_skipped(&self) -> u16 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(3usize, 1u8) as u16) }
+    pub fn segmentation_enabled(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(4usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_segment_reference_skipped(&mut self, val: u16) {
+    pub fn set_segmentation_enabled(&mut self, val: u32) {
         unsafe {
-            let val: u16 = ::std::mem::transmute(val);
-            self._bitfield_1.set(3usize, 1u8, val as u64)
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(4usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn new_bitfield_1(
-        segment_reference_enabled: u16,
-        segment_reference: u16,
-        segment_reference_skipped: u16,
-    ) -> __BindgenBitfieldUnit<[u8; 1usize]> {
-        let mut __bindgen_bitfield_unit: __BindgenBitfieldUnit<[u8; 1usize]> = Default::default();
-        __bindgen_bitfield_unit.set(0usize, 1u8, {
-            let segment_reference_enabled: u16 =
-                unsafe { ::std::mem::transmute(segment_reference_enabled) };
-            segment_reference_enabled as u64
-        });
-        __bindgen_bitfield_unit.set(1usize, 2u8, {
-            let segment_reference: u16 = unsafe { ::std::mem::transmute(segment_reference) };
-            segment_reference as u64
-        });
-        __bindgen_bitfield_unit.set(3usize, 1u8, {
-            let segment_reference_skipped: u16 =
-                unsafe { ::std::mem::transmute(segment_reference_skipped) };
-            segment_reference_skipped as u64
-        });
-        __bindgen_bitfield_unit
+    pub fn update_mb_segmentation_map(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(5usize, 1u8) as u32) }
     }
-}
-impl Default for _VASegmentParameterVP9__bindgen_ty_1 {
-    fn default() -> Self {
-        let mut s = ::std::mem::MaybeUninit::<Self>::uninit();
+    #[inline]
+    pub fn set_update_mb_segmentation_map(&mut self, val: u32) {
         unsafe {
-            ::std::ptr::write_bytes(s.as_mut_ptr(), 0, 1);
-            s.assume_init()
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(5usize, 1u8, val as u64)
         }
     }
-}
-impl Default for _VASegmentParameterVP9 {
-    fn default() -> Self {
-        let mut s = ::std::mem::MaybeUninit::<Self>::uninit();
+    #[inline]
+    pub fn update_segment_feature_data(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(6usize, 1u8) as u32) }
+    }
+    #[inline]
+    pub fn set_update_segment_feature_data(&mut self, val: u32) {
         unsafe {
-            ::std::ptr::write_bytes(s.as_mut_ptr(), 0, 1);
-            s.assume_init()
-        }
-    }
-}
-pub type VASegmentParameterVP9 = _VASegmentParameterVP9;
-#[repr(C)]
-#[derive(Copy, Clone)]
-pub struct _VASliceParameterBufferVP9 {
-    pub slice_data_size: u32,
-    pub slice_data_offset: u32,
-    pub slice_data_flag: u32,
-    pub seg_param: [VASegmentParameterVP9; 8usize],
-    pub va_reserved: [u32; 4usize],
-}
-impl Default for _VASliceParameterBufferVP9 {
-    fn default() -> Self {
-        let mut s = ::std::mem::MaybeUninit::<Self>::uninit();
-        unsafe {
-            ::std::ptr::write_bytes(s.as_mut_ptr(), 0, 1);
-            s.assume_init()
-        }
-    }
-}
-pub type VASliceParameterBufferVP9 = _VASliceParameterBufferVP9;
-#[repr(C)]
-#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
-pub struct _VAPictureStats {
-    pub picture_id: VASurfaceID,
-    pub flags: u32,
-}
-pub type VAPictureStats = _VAPictureStats;
-#[repr(C)]
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
-pub struct _VAStatsStatisticsParameter {
-    pub input: VAPictureStats,
-    pub past_references: *mut VAPictureStats,
-    pub num_past_references: u32,
-    pub past_ref_stat_buf: *mut VABufferID,
-    pub future_references: *mut VAPictureStats,
-    pub num_future_references: u32,
-    pub future_ref_stat_buf: *mut VABufferID,
-    pub outputs: *mut VABufferID,
-    pub mv_predictor: VABufferID,
-    pub qp: VABufferID,
-}
-impl Default for _VAStatsStatisticsParameter {
-    fn default() -> Self {
-        let mut s = ::std::mem::MaybeUninit::<Self>::uninit();
-        unsafe {
-            ::std::ptr::write_bytes(s.as_mut_ptr(), 0, 1);
-            s.assume_init()
-        }
-    }
-}
-pub type VAStatsStatisticsParameter = _VAStatsStatisticsParameter;
-pub mod VAEncPackedHeaderTypeH264 {
-    pub type Type = ::std::os::raw::c_uint;
-    pub const VAEncPackedHeaderH264_SPS: Type = 1;
-    pub const VAEncPackedHeaderH264_PPS: Type = 2;
-    pub const VAEncPackedHeaderH264_Slice: Type = 3;
-    pub const VAEncPackedHeaderH264_SEI: Type = 2147483649;
-}
-#[repr(C)]
-#[derive(Copy, Clone)]
-pub struct _VAEncSequenceParameterBufferH264 {
-    pub seq_parameter_set_id: u8,
-    pub level_idc: u8,
-    pub intra_period: u32,
-    pub intra_idr_period: u32,
-    pub ip_period: u32,
-    pub bits_per_second: u32,
-    pub max_num_ref_frames: u32,
-    pub picture_width_in_mbs: u16,
-    pub picture_height_in_mbs: u16,
-    pub seq_fields: _VAEncSequenceParameterBufferH264__bindgen_ty_1,
-    pub bit_depth_luma_minus8: u8,
-    pub bit_depth_chroma_minus8: u8,
-    pub num_ref_frames_in_pic_order_cnt_cycle: u8,
-    pub offset_for_non_ref_pic: i32,
-    pub offset_for_top_to_bottom_field: i32,
-    pub offset_for_ref_frame: [i32; 256usize],
-    pub frame_cropping_flag: u8,
-    pub frame_crop_left_offset: u32,
-    pub frame_crop_right_offset: u32,
-    pub frame_crop_top_offset: u32,
-    pub frame_crop_bottom_offset: u32,
-    pub vui_parameters_present_flag: u8,
-    pub vui_fields: _VAEncSequenceParameterBufferH264__bindgen_ty_2,
-    pub aspect_ratio_idc: u8,
-    pub sar_width: u32,
-    pub sar_height: u32,
-    pub num_units_in_tick: u32,
-    pub time_scale: u32,
-    pub va_reserved: [u32; 4usize],
-}
-#[repr(C)]
-#[derive(Copy, Clone)]
-pub union _VAEncSequenceParameterBufferH264__bindgen_ty_1 {
-    pub bits: _VAEncSequenceParameterBufferH264__bindgen_ty_1__bindgen_ty_1,
-    pub value: u32,
-}
-#[repr(C)]
-#[repr(align(4))]
-#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
-pub struct _VAEncSequenceParameterBufferH264__bindgen_ty_1__bindgen_ty_1 {
-    pub _bitfield_align_1: [u8; 0],
-    pub _bitfield_1: __BindgenBitfieldUnit<[u8; 3usize]>,
-    pub __bindgen_padding_0: u8,
-}
-impl _VAEncSequenceParameterBufferH264__bindgen_ty_1__bindgen_ty_1 {
-    #[inline]
-    pub fn chroma_format_idc(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(0usize, 2u8) as u32) }
-    }
-    #[inline]
-    pub fn set_chroma_format_idc(&mut self, val: u32) {
-        unsafe {
-            let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(0usize, 2u8, val as u64)
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(6usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn frame_mbs_only_flag(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(2usize, 1u8) as u32) }
+    pub fn filter_type(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(7usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_frame_mbs_only_flag(&mut self, val: u32) {
+    pub fn set_filter_type(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(2usize, 1u8, val as u64)
+            self._bitfield_1.set(7usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn mb_adaptive_frame_field_flag(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(3usize, 1u8) as u32) }
+    pub fn sharpness_level(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(8usize, 3u8) as u32) }
     }
     #[inline]
-    pub fn set_mb_adaptive_frame_field_flag(&mut self, val: u32) {
+    pub fn set_sharpness_level(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(3usize, 1u8, val as u64)
+            self._bitfield_1.set(8usize, 3u8, val as u64)
         }
     }
     #[inline]
-    pub fn seq_scaling_matrix_present_flag(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(4usize, 1u8) as u32) }
+    pub fn loop_filter_adj_enable(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(11usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_seq_scaling_matrix_present_flag(&mut self, val: u32) {
+    pub fn set_loop_filter_adj_enable(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(4usize, 1u8, val as u64)
+            self._bitfield_1.set(11usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn direct_8x8_inference_flag(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(5usize, 1u8) as u32) }
+    pub fn mode_ref_lf_delta_update(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(12usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_direct_8x8_inference_flag(&mut self, val: u32) {
+    pub fn set_mode_ref_lf_delta_update(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(5usize, 1u8, val as u64)
+            self._bitfield_1.set(12usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn log2_max_frame_num_minus4(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(6usize, 4u8) as u32) }
+    pub fn sign_bias_golden(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(13usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_log2_max_frame_num_minus4(&mut self, val: u32) {
+    pub fn set_sign_bias_golden(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(6usize, 4u8, val as u64)
+            self._bitfield_1.set(13usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn pic_order_cnt_type(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(10usize, 2u8) as u32) }
+    pub fn sign_bias_alternate(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(14usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_pic_order_cnt_type(&mut self, val: u32) {
+    pub fn set_sign_bias_alternate(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(10usize, 2u8, val as u64)
+            self._bitfield_1.set(14usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn log2_max_pic_order_cnt_lsb_minus4(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(12usize, 4u8) as u32) }
+    pub fn mb_no_coeff_skip(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(15usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_log2_max_pic_order_cnt_lsb_minus4(&mut self, val: u32) {
+    pub fn set_mb_no_coeff_skip(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(12usize, 4u8, val as u64)
+            self._bitfield_1.set(15usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn delta_pic_order_always_zero_flag(&self) -> u32 {
+    pub fn loop_filter_disable(&self) -> u32 {
         unsafe { ::std::mem::transmute(self._bitfield_1.get(16usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_delta_pic_order_always_zero_flag(&mut self, val: u32) {
+    pub fn set_loop_filter_disable(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
             self._bitfield_1.set(16usize, 1u8, val as u64)
@@ -2979,63 +3162,90 @@ impl _VAEncSequenceParameterBufferH264__bindgen_ty_1__bindgen_ty_1 {
     }
     #[inline]
     pub fn new_bitfield_1(
-        chroma_format_idc: u32,
-        frame_mbs_only_flag: u32,
-        mb_adaptive_frame_field_flag: u32,
-        seq_scaling_matrix_present_flag: u32,
-        direct_8x8_inference_flag: u32,
-        log2_max_frame_num_minus4: u32,
-        pic_order_cnt_type: u32,
-        log2_max_pic_order_cnt_lsb_minus4: u32,
-        delta_pic_order_always_zero_flag: u32,
+        key_frame: u32,
+        version: u32,
+        segmentation_enabled: u32,
+        update_mb_segmentation_map: u32,
+        update_segment_feature_data: u32,
+        filter_type: u32,
+        sharpness_level: u32,
+        loop_filter_adj_enable: u32,
+        mode_ref_lf_delta_update: u32,
+        sign_bias_golden: u32,
+        sign_bias_alternate: u32,
+        mb_no_coeff_skip: u32,
+        loop_filter_disable: u32,
     ) -> __BindgenBitfieldUnit<[u8; 3usize]> {
         let mut __bindgen_bitfield_unit: __BindgenBitfieldUnit<[u8; 3usize]> = Default::default();
-        __bindgen_bitfield_unit.set(0usize, 2u8, {
-            let chroma_format_idc: u32 = unsafe { ::std::mem::transmute(chroma_format_idc) };
-            chroma_format_idc as u64
-        });
-        __bindgen_bitfield_unit.set(2usize, 1u8, {
-            let frame_mbs_only_flag: u32 = unsafe { ::std::mem::transmute(frame_mbs_only_flag) };
-            frame_mbs_only_flag as u64
+        __bindgen_bitfield_unit.set(0usize, 1u8, {
+            let key_frame: u32 = unsafe { ::std::mem::transmute(key_frame) };
+            key_frame as u64
         });
-        __bindgen_bitfield_unit.set(3usize, 1u8, {
-            let mb_adaptive_frame_field_flag: u32 =
-                unsafe { ::std::mem::transmute(mb_adaptive_frame_field_flag) };
-            mb_adaptive_frame_field_flag as u64
+        __bindgen_bitfield_unit.set(1usize, 3u8, {
+            let version: u32 = unsafe { ::std::mem::transmute(version) };
+            version as u64
         });
         __bindgen_bitfield_unit.set(4usize, 1u8, {
-            let seq_scaling_matrix_present_flag: u32 =
-                unsafe { ::std::mem::transmute(seq_scaling_matrix_present_flag) };
-            seq_scaling_matrix_present_flag as u64
+            let segmentation_enabled: u32 = unsafe { ::std::mem::transmute(segmentation_enabled) };
+            segmentation_enabled as u64
         });
         __bindgen_bitfield_unit.set(5usize, 1u8, {
-            let direct_8x8_inference_flag: u32 =
-                unsafe { ::std::mem::transmute(direct_8x8_inference_flag) };
-            direct_8x8_inference_flag as u64
+            let update_mb_segmentation_map: u32 =
+                unsafe { ::std::mem::transmute(update_mb_segmentation_map) };
+            update_mb_segmentation_map as u64
         });
-        __bindgen_bitfield_unit.set(6usize, 4u8, {
-            let log2_max_frame_num_minus4: u32 =
-                unsafe { ::std::mem::transmute(log2_max_frame_num_minus4) };
-            log2_max_frame_num_minus4 as u64
+        __bindgen_bitfield_unit.set(6usize, 1u8, {
+            let update_segment_feature_data: u32 =
+                unsafe { ::std::mem::transmute(update_segment_feature_data) };
+            update_segment_feature_data as u64
         });
-        __bindgen_bitfield_unit.set(10usize, 2u8, {
-            let pic_order_cnt_type: u32 = unsafe { ::std::mem::transmute(pic_order_cnt_type) };
-            pic_order_cnt_type as u64
+        __bindgen_bitfield_unit.set(7usize, 1u8, {
+            let filter_type: u32 = unsafe { ::std::mem::transmute(filter_type) };
+            filter_type as u64
         });
-        __bindgen_bitfield_unit.set(12usize, 4u8, {
-            let log2_max_pic_order_cnt_lsb_minus4: u32 =
-                unsafe { ::std::mem::transmute(log2_max_pic_order_cnt_lsb_minus4) };
-            log2_max_pic_order_cnt_lsb_minus4 as u64
+        __bindgen_bitfield_unit.set(8usize, 3u8, {
+            let sharpness_level: u32 = unsafe { ::std::mem::transmute(sharpness_level) };
+            sharpness_level as u64
         });
-        __bindgen_bitfield_unit.set(16usize, 1u8, {
-            let delta_pic_order_always_zero_flag: u32 =
-                unsafe { ::std::mem::transmute(delta_pic_order_always_zero_flag) };
-            delta_pic_order_always_zero_flag as u64
+        __bindgen_bitfield_unit.set(11usize, 1u8, {
+            let loop_filter_adj_enable: u32 =
+                unsafe { ::std::mem::transmute(loop_filter_adj_enable) };
+            loop_filter_adj_enable as u64
         });
-        __bindgen_bitfield_unit
-    }
-}
-impl Default for _VAEncSequenceParameterBufferH264__bindgen_ty_1 {
+        __bindgen_bitfield_unit.set(12usize, 1u8, {
+            let mode_ref_lf_delta_update: u32 =
+                unsafe { ::std::mem::transmute(mode_ref_lf_delta_update) };
+            mode_ref_lf_delta_update as u64
+        });
+        __bindgen_bitfield_unit.set(13usize, 1u8, {
+            let sign_bias_golden: u32 = unsafe { ::std::mem::transmute(sign_bias_golden) };
+            sign_bias_golden as u64
+        });
+        __bindgen_bitfield_unit.set(14usize, 1u8, {
+            let sign_bias_alternate: u32 = unsafe { ::std::mem::transmute(sign_bias_alternate) };
+            sign_bias_alternate as u64
+        });
+        __bindgen_bitfield_unit.set(15usize, 1u8, {
+            let mb_no_coeff_skip: u32 = unsafe { ::std::mem::transmute(mb_no_coeff_skip) };
+            mb_no_coeff_skip as u64
+        });
+        __bindgen_bitfield_unit.set(16usize, 1u8, {
+            let loop_filter_disable: u32 = unsafe { ::std::mem::transmute(loop_filter_disable) };
+            loop_filter_disable as u64
+        });
+        __bindgen_bitfield_unit
+    }
+}
+impl Default for _VAPictureParameterBufferVP8__bindgen_ty_1 {
+    fn default() -> Self {
+        let mut s = ::std::mem::MaybeUninit::<Self>::uninit();
+        unsafe {
+            ::std::ptr::write_bytes(s.as_mut_ptr(), 0, 1);
+            s.assume_init()
+        }
+    }
+}
+impl Default for _VAPictureParameterBufferVP8 {
     fn default() -> Self {
         let mut s = ::std::mem::MaybeUninit::<Self>::uninit();
         unsafe {
@@ -3044,421 +3254,434 @@ impl Default for _VAEncSequenceParameterBufferH264__bindgen_ty_1 {
         }
     }
 }
+pub type VAPictureParameterBufferVP8 = _VAPictureParameterBufferVP8;
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct _VASliceParameterBufferVP8 {
+    pub slice_data_size: u32,
+    pub slice_data_offset: u32,
+    pub slice_data_flag: u32,
+    pub macroblock_offset: u32,
+    pub num_of_partitions: u8,
+    pub partition_size: [u32; 9usize],
+    pub va_reserved: [u32; 4usize],
+}
+pub type VASliceParameterBufferVP8 = _VASliceParameterBufferVP8;
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct _VAProbabilityDataBufferVP8 {
+    pub dct_coeff_probs: [[[[u8; 11usize]; 3usize]; 8usize]; 4usize],
+    pub va_reserved: [u32; 4usize],
+}
+pub type VAProbabilityDataBufferVP8 = _VAProbabilityDataBufferVP8;
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct _VAIQMatrixBufferVP8 {
+    pub quantization_index: [[u16; 6usize]; 4usize],
+    pub va_reserved: [u32; 4usize],
+}
+pub type VAIQMatrixBufferVP8 = _VAIQMatrixBufferVP8;
 #[repr(C)]
 #[derive(Copy, Clone)]
-pub union _VAEncSequenceParameterBufferH264__bindgen_ty_2 {
-    pub bits: _VAEncSequenceParameterBufferH264__bindgen_ty_2__bindgen_ty_1,
+pub struct _VADecPictureParameterBufferVP9 {
+    pub frame_width: u16,
+    pub frame_height: u16,
+    pub reference_frames: [VASurfaceID; 8usize],
+    pub pic_fields: _VADecPictureParameterBufferVP9__bindgen_ty_1,
+    pub filter_level: u8,
+    pub sharpness_level: u8,
+    pub log2_tile_rows: u8,
+    pub log2_tile_columns: u8,
+    pub frame_header_length_in_bytes: u8,
+    pub first_partition_size: u16,
+    pub mb_segment_tree_probs: [u8; 7usize],
+    pub segment_pred_probs: [u8; 3usize],
+    pub profile: u8,
+    pub bit_depth: u8,
+    pub va_reserved: [u32; 8usize],
+}
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub union _VADecPictureParameterBufferVP9__bindgen_ty_1 {
+    pub bits: _VADecPictureParameterBufferVP9__bindgen_ty_1__bindgen_ty_1,
     pub value: u32,
 }
 #[repr(C)]
 #[repr(align(4))]
 #[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
-pub struct _VAEncSequenceParameterBufferH264__bindgen_ty_2__bindgen_ty_1 {
-    pub _bitfield_align_1: [u16; 0],
+pub struct _VADecPictureParameterBufferVP9__bindgen_ty_1__bindgen_ty_1 {
+    pub _bitfield_align_1: [u8; 0],
     pub _bitfield_1: __BindgenBitfieldUnit<[u8; 4usize]>,
 }
-impl _VAEncSequenceParameterBufferH264__bindgen_ty_2__bindgen_ty_1 {
+impl _VADecPictureParameterBufferVP9__bindgen_ty_1__bindgen_ty_1 {
     #[inline]
-    pub fn aspect_ratio_info_present_flag(&self) -> u32 {
+    pub fn subsampling_x(&self) -> u32 {
         unsafe { ::std::mem::transmute(self._bitfield_1.get(0usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_aspect_ratio_info_present_flag(&mut self, val: u32) {
+    pub fn set_subsampling_x(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
             self._bitfield_1.set(0usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn timing_info_present_flag(&self) -> u32 {
+    pub fn subsampling_y(&self) -> u32 {
         unsafe { ::std::mem::transmute(self._bitfield_1.get(1usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_timing_info_present_flag(&mut self, val: u32) {
+    pub fn set_subsampling_y(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
             self._bitfield_1.set(1usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn bitstream_restriction_flag(&self) -> u32 {
+    pub fn frame_type(&self) -> u32 {
         unsafe { ::std::mem::transmute(self._bitfield_1.get(2usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_bitstream_restriction_flag(&mut self, val: u32) {
+    pub fn set_frame_type(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
             self._bitfield_1.set(2usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn log2_max_mv_length_horizontal(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(3usize, 5u8) as u32) }
+    pub fn show_frame(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(3usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_log2_max_mv_length_horizontal(&mut self, val: u32) {
+    pub fn set_show_frame(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(3usize, 5u8, val as u64)
+            self._bitfield_1.set(3usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn log2_max_mv_length_vertical(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(8usize, 5u8) as u32) }
+    pub fn error_resilient_mode(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(4usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_log2_max_mv_length_vertical(&mut self, val: u32) {
+    pub fn set_error_resilient_mode(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(8usize, 5u8, val as u64)
+            self._bitfield_1.set(4usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn fixed_frame_rate_flag(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(13usize, 1u8) as u32) }
+    pub fn intra_only(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(5usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_fixed_frame_rate_flag(&mut self, val: u32) {
+    pub fn set_intra_only(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(13usize, 1u8, val as u64)
+            self._bitfield_1.set(5usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn low_delay_hrd_flag(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(14usize, 1u8) as u32) }
+    pub fn allow_high_precision_mv(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(6usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_low_delay_hrd_flag(&mut self, val: u32) {
+    pub fn set_allow_high_precision_mv(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(14usize, 1u8, val as u64)
+            self._bitfield_1.set(6usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn motion_vectors_over_pic_boundaries_flag(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(15usize, 1u8) as u32) }
+    pub fn mcomp_filter_type(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(7usize, 3u8) as u32) }
     }
     #[inline]
-    pub fn set_motion_vectors_over_pic_boundaries_flag(&mut self, val: u32) {
+    pub fn set_mcomp_filter_type(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(15usize, 1u8, val as u64)
+            self._bitfield_1.set(7usize, 3u8, val as u64)
         }
     }
     #[inline]
-    pub fn reserved(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(16usize, 16u8) as u32) }
+    pub fn frame_parallel_decoding_mode(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(10usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_reserved(&mut self, val: u32) {
+    pub fn set_frame_parallel_decoding_mode(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(16usize, 16u8, val as u64)
+            self._bitfield_1.set(10usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn new_bitfield_1(
-        aspect_ratio_info_present_flag: u32,
-        timing_info_present_flag: u32,
-        bitstream_restriction_flag: u32,
-        log2_max_mv_length_horizontal: u32,
-        log2_max_mv_length_vertical: u32,
-        fixed_frame_rate_flag: u32,
-        low_delay_hrd_flag: u32,
-        motion_vectors_over_pic_boundaries_flag: u32,
-        reserved: u32,
-    ) -> __BindgenBitfieldUnit<[u8; 4usize]> {
-        let mut __bindgen_bitfield_unit: __BindgenBitfieldUnit<[u8; 4usize]> = Default::default();
-        __bindgen_bitfield_unit.set(0usize, 1u8, {
-            let aspect_ratio_info_present_flag: u32 =
-                unsafe { ::std::mem::transmute(aspect_ratio_info_present_flag) };
-            aspect_ratio_info_present_flag as u64
-        });
-        __bindgen_bitfield_unit.set(1usize, 1u8, {
-            let timing_info_present_flag: u32 =
-                unsafe { ::std::mem::transmute(timing_info_present_flag) };
-            timing_info_present_flag as u64
-        });
-        __bindgen_bitfield_unit.set(2usize, 1u8, {
-            let bitstream_restriction_flag: u32 =
-                unsafe { ::std::mem::transmute(bitstream_restriction_flag) };
-            bitstream_restriction_flag as u64
-        });
-        __bindgen_bitfield_unit.set(3usize, 5u8, {
-            let log2_max_mv_length_horizontal: u32 =
-                unsafe { ::std::mem::transmute(log2_max_mv_length_horizontal) };
-            log2_max_mv_length_horizontal as u64
-        });
-        __bindgen_bitfield_unit.set(8usize, 5u8, {
-            let log2_max_mv_length_vertical: u32 =
-                unsafe { ::std::mem::transmute(log2_max_mv_length_vertical) };
-            log2_max_mv_length_vertical as u64
-        });
-        __bindgen_bitfield_unit.set(13usize, 1u8, {
-            let fixed_frame_rate_flag: u32 =
-                unsafe { ::std::mem::transmute(fixed_frame_rate_flag) };
-            fixed_frame_rate_flag as u64
-        });
-        __bindgen_bitfield_unit.set(14usize, 1u8, {
-            let low_delay_hrd_flag: u32 = unsafe { ::std::mem::transmute(low_delay_hrd_flag) };
-            low_delay_hrd_flag as u64
-        });
-        __bindgen_bitfield_unit.set(15usize, 1u8, {
-            let motion_vectors_over_pic_boundaries_flag: u32 =
-                unsafe { ::std::mem::transmute(motion_vectors_over_pic_boundaries_flag) };
-            motion_vectors_over_pic_boundaries_flag as u64
-        });
-        __bindgen_bitfield_unit.set(16usize, 16u8, {
-            let reserved: u32 = unsafe { ::std::mem::transmute(reserved) };
-            reserved as u64
-        });
-        __bindgen_bitfield_unit
+    pub fn reset_frame_context(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(11usize, 2u8) as u32) }
     }
-}
-impl Default for _VAEncSequenceParameterBufferH264__bindgen_ty_2 {
-    fn default() -> Self {
-        let mut s = ::std::mem::MaybeUninit::<Self>::uninit();
+    #[inline]
+    pub fn set_reset_frame_context(&mut self, val: u32) {
         unsafe {
-            ::std::ptr::write_bytes(s.as_mut_ptr(), 0, 1);
-            s.assume_init()
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(11usize, 2u8, val as u64)
         }
     }
-}
-impl Default for _VAEncSequenceParameterBufferH264 {
-    fn default() -> Self {
-        let mut s = ::std::mem::MaybeUninit::<Self>::uninit();
+    #[inline]
+    pub fn refresh_frame_context(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(13usize, 1u8) as u32) }
+    }
+    #[inline]
+    pub fn set_refresh_frame_context(&mut self, val: u32) {
         unsafe {
-            ::std::ptr::write_bytes(s.as_mut_ptr(), 0, 1);
-            s.assume_init()
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(13usize, 1u8, val as u64)
         }
     }
-}
-pub type VAEncSequenceParameterBufferH264 = _VAEncSequenceParameterBufferH264;
-#[repr(C)]
-#[derive(Copy, Clone)]
-pub struct _VAEncPictureParameterBufferH264 {
-    pub CurrPic: VAPictureH264,
-    pub ReferenceFrames: [VAPictureH264; 16usize],
-    pub coded_buf: VABufferID,
-    pub pic_parameter_set_id: u8,
-    pub seq_parameter_set_id: u8,
-    pub last_picture: u8,
-    pub frame_num: u16,
-    pub pic_init_qp: u8,
-    pub num_ref_idx_l0_active_minus1: u8,
-    pub num_ref_idx_l1_active_minus1: u8,
-    pub chroma_qp_index_offset: i8,
-    pub second_chroma_qp_index_offset: i8,
-    pub pic_fields: _VAEncPictureParameterBufferH264__bindgen_ty_1,
-    pub va_reserved: [u32; 4usize],
-}
-#[repr(C)]
-#[derive(Copy, Clone)]
-pub union _VAEncPictureParameterBufferH264__bindgen_ty_1 {
-    pub bits: _VAEncPictureParameterBufferH264__bindgen_ty_1__bindgen_ty_1,
-    pub value: u32,
-}
-#[repr(C)]
-#[repr(align(4))]
-#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
-pub struct _VAEncPictureParameterBufferH264__bindgen_ty_1__bindgen_ty_1 {
-    pub _bitfield_align_1: [u8; 0],
-    pub _bitfield_1: __BindgenBitfieldUnit<[u8; 2usize]>,
-    pub __bindgen_padding_0: u16,
-}
-impl _VAEncPictureParameterBufferH264__bindgen_ty_1__bindgen_ty_1 {
     #[inline]
-    pub fn idr_pic_flag(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(0usize, 1u8) as u32) }
+    pub fn frame_context_idx(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(14usize, 2u8) as u32) }
     }
     #[inline]
-    pub fn set_idr_pic_flag(&mut self, val: u32) {
+    pub fn set_frame_context_idx(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(0usize, 1u8, val as u64)
+            self._bitfield_1.set(14usize, 2u8, val as u64)
         }
     }
     #[inline]
-    pub fn reference_pic_flag(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(1usize, 2u8) as u32) }
+    pub fn segmentation_enabled(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(16usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_reference_pic_flag(&mut self, val: u32) {
+    pub fn set_segmentation_enabled(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(1usize, 2u8, val as u64)
+            self._bitfield_1.set(16usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn entropy_coding_mode_flag(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(3usize, 1u8) as u32) }
+    pub fn segmentation_temporal_update(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(17usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_entropy_coding_mode_flag(&mut self, val: u32) {
+    pub fn set_segmentation_temporal_update(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(3usize, 1u8, val as u64)
+            self._bitfield_1.set(17usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn weighted_pred_flag(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(4usize, 1u8) as u32) }
+    pub fn segmentation_update_map(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(18usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_weighted_pred_flag(&mut self, val: u32) {
+    pub fn set_segmentation_update_map(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(4usize, 1u8, val as u64)
+            self._bitfield_1.set(18usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn weighted_bipred_idc(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(5usize, 2u8) as u32) }
+    pub fn last_ref_frame(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(19usize, 3u8) as u32) }
     }
     #[inline]
-    pub fn set_weighted_bipred_idc(&mut self, val: u32) {
+    pub fn set_last_ref_frame(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(5usize, 2u8, val as u64)
+            self._bitfield_1.set(19usize, 3u8, val as u64)
         }
     }
     #[inline]
-    pub fn constrained_intra_pred_flag(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(7usize, 1u8) as u32) }
+    pub fn last_ref_frame_sign_bias(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(22usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_constrained_intra_pred_flag(&mut self, val: u32) {
+    pub fn set_last_ref_frame_sign_bias(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(7usize, 1u8, val as u64)
+            self._bitfield_1.set(22usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn transform_8x8_mode_flag(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(8usize, 1u8) as u32) }
+    pub fn golden_ref_frame(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(23usize, 3u8) as u32) }
     }
     #[inline]
-    pub fn set_transform_8x8_mode_flag(&mut self, val: u32) {
+    pub fn set_golden_ref_frame(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(8usize, 1u8, val as u64)
+            self._bitfield_1.set(23usize, 3u8, val as u64)
         }
     }
     #[inline]
-    pub fn deblocking_filter_control_present_flag(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(9usize, 1u8) as u32) }
+    pub fn golden_ref_frame_sign_bias(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(26usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_deblocking_filter_control_present_flag(&mut self, val: u32) {
+    pub fn set_golden_ref_frame_sign_bias(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(9usize, 1u8, val as u64)
+            self._bitfield_1.set(26usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn redundant_pic_cnt_present_flag(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(10usize, 1u8) as u32) }
+    pub fn alt_ref_frame(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(27usize, 3u8) as u32) }
     }
     #[inline]
-    pub fn set_redundant_pic_cnt_present_flag(&mut self, val: u32) {
+    pub fn set_alt_ref_frame(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(10usize, 1u8, val as u64)
+            self._bitfield_1.set(27usize, 3u8, val as u64)
         }
     }
     #[inline]
-    pub fn pic_order_present_flag(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(11usize, 1u8) as u32) }
+    pub fn alt_ref_frame_sign_bias(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(30usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_pic_order_present_flag(&mut self, val: u32) {
+    pub fn set_alt_ref_frame_sign_bias(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(11usize, 1u8, val as u64)
+            self._bitfield_1.set(30usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn pic_scaling_matrix_present_flag(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(12usize, 1u8) as u32) }
+    pub fn lossless_flag(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(31usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_pic_scaling_matrix_present_flag(&mut self, val: u32) {
+    pub fn set_lossless_flag(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(12usize, 1u8, val as u64)
+            self._bitfield_1.set(31usize, 1u8, val as u64)
         }
     }
     #[inline]
     pub fn new_bitfield_1(
-        idr_pic_flag: u32,
-        reference_pic_flag: u32,
-        entropy_coding_mode_flag: u32,
-        weighted_pred_flag: u32,
-        weighted_bipred_idc: u32,
-        constrained_intra_pred_flag: u32,
-        transform_8x8_mode_flag: u32,
-        deblocking_filter_control_present_flag: u32,
-        redundant_pic_cnt_present_flag: u32,
-        pic_order_present_flag: u32,
-        pic_scaling_matrix_present_flag: u32,
-    ) -> __BindgenBitfieldUnit<[u8; 2usize]> {
-        let mut __bindgen_bitfield_unit: __BindgenBitfieldUnit<[u8; 2usize]> = Default::default();
+        subsampling_x: u32,
+        subsampling_y: u32,
+        frame_type: u32,
+        show_frame: u32,
+        error_resilient_mode: u32,
+        intra_only: u32,
+        allow_high_precision_mv: u32,
+        mcomp_filter_type: u32,
+        frame_parallel_decoding_mode: u32,
+        reset_frame_context: u32,
+        refresh_frame_context: u32,
+        frame_context_idx: u32,
+        segmentation_enabled: u32,
+        segmentation_temporal_update: u32,
+        segmentation_update_map: u32,
+        last_ref_frame: u32,
+        last_ref_frame_sign_bias: u32,
+        golden_ref_frame: u32,
+        golden_ref_frame_sign_bias: u32,
+        alt_ref_frame: u32,
+        alt_ref_frame_sign_bias: u32,
+        lossless_flag: u32,
+    ) -> __BindgenBitfieldUnit<[u8; 4usize]> {
+        let mut __bindgen_bitfield_unit: __BindgenBitfieldUnit<[u8; 4usize]> = Default::default();
         __bindgen_bitfield_unit.set(0usize, 1u8, {
-            let idr_pic_flag: u32 = unsafe { ::std::mem::transmute(idr_pic_flag) };
-            idr_pic_flag as u64
+            let subsampling_x: u32 = unsafe { ::std::mem::transmute(subsampling_x) };
+            subsampling_x as u64
         });
-        __bindgen_bitfield_unit.set(1usize, 2u8, {
-            let reference_pic_flag: u32 = unsafe { ::std::mem::transmute(reference_pic_flag) };
-            reference_pic_flag as u64
+        __bindgen_bitfield_unit.set(1usize, 1u8, {
+            let subsampling_y: u32 = unsafe { ::std::mem::transmute(subsampling_y) };
+            subsampling_y as u64
+        });
+        __bindgen_bitfield_unit.set(2usize, 1u8, {
+            let frame_type: u32 = unsafe { ::std::mem::transmute(frame_type) };
+            frame_type as u64
         });
         __bindgen_bitfield_unit.set(3usize, 1u8, {
-            let entropy_coding_mode_flag: u32 =
-                unsafe { ::std::mem::transmute(entropy_coding_mode_flag) };
-            entropy_coding_mode_flag as u64
+            let show_frame: u32 = unsafe { ::std::mem::transmute(show_frame) };
+            show_frame as u64
         });
         __bindgen_bitfield_unit.set(4usize, 1u8, {
-            let weighted_pred_flag: u32 = unsafe { ::std::mem::transmute(weighted_pred_flag) };
-            weighted_pred_flag as u64
-        });
-        __bindgen_bitfield_unit.set(5usize, 2u8, {
-            let weighted_bipred_idc: u32 = unsafe { ::std::mem::transmute(weighted_bipred_idc) };
-            weighted_bipred_idc as u64
+            let error_resilient_mode: u32 = unsafe { ::std::mem::transmute(error_resilient_mode) };
+            error_resilient_mode as u64
         });
-        __bindgen_bitfield_unit.set(7usize, 1u8, {
-            let constrained_intra_pred_flag: u32 =
-                unsafe { ::std::mem::transmute(constrained_intra_pred_flag) };
-            constrained_intra_pred_flag as u64
+        __bindgen_bitfield_unit.set(5usize, 1u8, {
+            let intra_only: u32 = unsafe { ::std::mem::transmute(intra_only) };
+            intra_only as u64
         });
-        __bindgen_bitfield_unit.set(8usize, 1u8, {
-            let transform_8x8_mode_flag: u32 =
-                unsafe { ::std::mem::transmute(transform_8x8_mode_flag) };
-            transform_8x8_mode_flag as u64
+        __bindgen_bitfield_unit.set(6usize, 1u8, {
+            let allow_high_precision_mv: u32 =
+                unsafe { ::std::mem::transmute(allow_high_precision_mv) };
+            allow_high_precision_mv as u64
         });
-        __bindgen_bitfield_unit.set(9usize, 1u8, {
-            let deblocking_filter_control_present_flag: u32 =
-                unsafe { ::std::mem::transmute(deblocking_filter_control_present_flag) };
-            deblocking_filter_control_present_flag as u64
+        __bindgen_bitfield_unit.set(7usize, 3u8, {
+            let mcomp_filter_type: u32 = unsafe { ::std::mem::transmute(mcomp_filter_type) };
+            mcomp_filter_type as u64
         });
         __bindgen_bitfield_unit.set(10usize, 1u8, {
-            let redundant_pic_cnt_present_flag: u32 =
-                unsafe { ::std::mem::transmute(redundant_pic_cnt_present_flag) };
-            redundant_pic_cnt_present_flag as u64
+            let frame_parallel_decoding_mode: u32 =
+                unsafe { ::std::mem::transmute(frame_parallel_decoding_mode) };
+            frame_parallel_decoding_mode as u64
         });
-        __bindgen_bitfield_unit.set(11usize, 1u8, {
-            let pic_order_present_flag: u32 =
-                unsafe { ::std::mem::transmute(pic_order_present_flag) };
-            pic_order_present_flag as u64
+        __bindgen_bitfield_unit.set(11usize, 2u8, {
+            let reset_frame_context: u32 = unsafe { ::std::mem::transmute(reset_frame_context) };
+            reset_frame_context as u64
         });
-        __bindgen_bitfield_unit.set(12usize, 1u8, {
-            let pic_scaling_matrix_present_flag: u32 =
-                unsafe { ::std::mem::transmute(pic_scaling_matrix_present_flag) };
-            pic_scaling_matrix_present_flag as u64
+        __bindgen_bitfield_unit.set(13usize, 1u8, {
+            let refresh_frame_context: u32 =
+                unsafe { ::std::mem::transmute(refresh_frame_context) };
+            refresh_frame_context as u64
+        });
+        __bindgen_bitfield_unit.set(14usize, 2u8, {
+            let frame_context_idx: u32 = unsafe { ::std::mem::transmute(frame_context_idx) };
+            frame_context_idx as u64
+        });
+        __bindgen_bitfield_unit.set(16usize, 1u8, {
+            let segmentation_enabled: u32 = unsafe { ::std::mem::transmute(segmentation_enabled) };
+            segmentation_enabled as u64
+        });
+        __bindgen_bitfield_unit.set(17usize, 1u8, {
+            let segmentation_temporal_update: u32 =
+                unsafe { ::std::mem::transmute(segmentation_temporal_update) };
+            segmentation_temporal_update as u64
+        });
+        __bindgen_bitfield_unit.set(18usize, 1u8, {
+            let segmentation_update_map: u32 =
+                unsafe { ::std::mem::transmute(segmentation_update_map) };
+            segmentation_update_map as u64
+        });
+        __bindgen_bitfield_unit.set(19usize, 3u8, {
+            let last_ref_frame: u32 = unsafe { ::std::mem::transmute(last_ref_frame) };
+            last_ref_frame as u64
+        });
+        __bindgen_bitfield_unit.set(22usize, 1u8, {
+            let last_ref_frame_sign_bias: u32 =
+                unsafe { ::std::mem::transmute(last_ref_frame_sign_bias) };
+            last_ref_frame_sign_bias as u64
+        });
+        __bindgen_bitfield_unit.set(23usize, 3u8, {
+            let golden_ref_frame: u32 = unsafe { ::std::mem::transmute(golden_ref_frame) };
+            golden_ref_frame as u64
+        });
+        __bindgen_bitfield_unit.set(26usize, 1u8, {
+            let golden_ref_frame_sign_bias: u32 =
+                unsafe { ::std::mem::transmute(golden_ref_frame_sign_bias) };
+            golden_ref_frame_sign_bias as u64
+        });
+        __bindgen_bitfield_unit.set(27usize, 3u8, {
+            let alt_ref_frame: u32 = unsafe { ::std::mem::transmute(alt_ref_frame) };
+            alt_ref_frame as u64
+        });
+        __bindgen_bitfield_unit.set(30usize, 1u8, {
+            let alt_ref_frame_sign_bias: u32 =
+                unsafe { ::std::mem::transmute(alt_ref_frame_sign_bias) };
+            alt_ref_frame_sign_bias as u64
+        });
+        __bindgen_bitfield_unit.set(31usize, 1u8, {
+            let lossless_flag: u32 = unsafe { ::std::mem::transmute(lossless_flag) };
+            lossless_flag as u64
         });
         __bindgen_bitfield_unit
     }
 }
-impl Default for _VAEncPictureParameterBufferH264__bindgen_ty_1 {
+impl Default for _VADecPictureParameterBufferVP9__bindgen_ty_1 {
     fn default() -> Self {
         let mut s = ::std::mem::MaybeUninit::<Self>::uninit();
         unsafe {
@@ -3467,7 +3690,7 @@ impl Default for _VAEncPictureParameterBufferH264__bindgen_ty_1 {
         }
     }
 }
-impl Default for _VAEncPictureParameterBufferH264 {
+impl Default for _VADecPictureParameterBufferVP9 {
     fn default() -> Self {
         let mut s = ::std::mem::MaybeUninit::<Self>::uninit();
         unsafe {
@@ -3476,122 +3699,91 @@ impl Default for _VAEncPictureParameterBufferH264 {
         }
     }
 }
-pub type VAEncPictureParameterBufferH264 = _VAEncPictureParameterBufferH264;
-#[repr(C)]
-#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
-pub struct _VAEncQPBufferH264 {
-    pub qp: u8,
-}
-pub type VAEncQPBufferH264 = _VAEncQPBufferH264;
-#[repr(C)]
-#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
-pub struct _VAEncSliceParameterBufferH264 {
-    pub macroblock_address: u32,
-    pub num_macroblocks: u32,
-    pub macroblock_info: VABufferID,
-    pub slice_type: u8,
-    pub pic_parameter_set_id: u8,
-    pub idr_pic_id: u16,
-    pub pic_order_cnt_lsb: u16,
-    pub delta_pic_order_cnt_bottom: i32,
-    pub delta_pic_order_cnt: [i32; 2usize],
-    pub direct_spatial_mv_pred_flag: u8,
-    pub num_ref_idx_active_override_flag: u8,
-    pub num_ref_idx_l0_active_minus1: u8,
-    pub num_ref_idx_l1_active_minus1: u8,
-    pub RefPicList0: [VAPictureH264; 32usize],
-    pub RefPicList1: [VAPictureH264; 32usize],
-    pub luma_log2_weight_denom: u8,
-    pub chroma_log2_weight_denom: u8,
-    pub luma_weight_l0_flag: u8,
-    pub luma_weight_l0: [::std::os::raw::c_short; 32usize],
-    pub luma_offset_l0: [::std::os::raw::c_short; 32usize],
-    pub chroma_weight_l0_flag: u8,
-    pub chroma_weight_l0: [[::std::os::raw::c_short; 2usize]; 32usize],
-    pub chroma_offset_l0: [[::std::os::raw::c_short; 2usize]; 32usize],
-    pub luma_weight_l1_flag: u8,
-    pub luma_weight_l1: [::std::os::raw::c_short; 32usize],
-    pub luma_offset_l1: [::std::os::raw::c_short; 32usize],
-    pub chroma_weight_l1_flag: u8,
-    pub chroma_weight_l1: [[::std::os::raw::c_short; 2usize]; 32usize],
-    pub chroma_offset_l1: [[::std::os::raw::c_short; 2usize]; 32usize],
-    pub cabac_init_idc: u8,
-    pub slice_qp_delta: i8,
-    pub disable_deblocking_filter_idc: u8,
-    pub slice_alpha_c0_offset_div2: i8,
-    pub slice_beta_offset_div2: i8,
-    pub va_reserved: [u32; 4usize],
-}
-pub type VAEncSliceParameterBufferH264 = _VAEncSliceParameterBufferH264;
+pub type VADecPictureParameterBufferVP9 = _VADecPictureParameterBufferVP9;
 #[repr(C)]
 #[derive(Copy, Clone)]
-pub struct _VAEncMacroblockParameterBufferH264 {
-    pub qp: u8,
-    pub info: _VAEncMacroblockParameterBufferH264__bindgen_ty_1,
+pub struct _VASegmentParameterVP9 {
+    pub segment_flags: _VASegmentParameterVP9__bindgen_ty_1,
+    pub filter_level: [[u8; 2usize]; 4usize],
+    pub luma_ac_quant_scale: i16,
+    pub luma_dc_quant_scale: i16,
+    pub chroma_ac_quant_scale: i16,
+    pub chroma_dc_quant_scale: i16,
     pub va_reserved: [u32; 4usize],
 }
 #[repr(C)]
 #[derive(Copy, Clone)]
-pub union _VAEncMacroblockParameterBufferH264__bindgen_ty_1 {
-    pub intra_fields: _VAEncMacroblockParameterBufferH264__bindgen_ty_1__bindgen_ty_1,
-    pub inter_fields: _VAEncMacroblockParameterBufferH264__bindgen_ty_1__bindgen_ty_2,
-}
-#[repr(C)]
-#[derive(Copy, Clone)]
-pub union _VAEncMacroblockParameterBufferH264__bindgen_ty_1__bindgen_ty_1 {
-    pub bits: _VAEncMacroblockParameterBufferH264__bindgen_ty_1__bindgen_ty_1__bindgen_ty_1,
-    pub value: u32,
+pub union _VASegmentParameterVP9__bindgen_ty_1 {
+    pub fields: _VASegmentParameterVP9__bindgen_ty_1__bindgen_ty_1,
+    pub value: u16,
 }
 #[repr(C)]
-#[repr(align(4))]
+#[repr(align(2))]
 #[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
-pub struct _VAEncMacroblockParameterBufferH264__bindgen_ty_1__bindgen_ty_1__bindgen_ty_1 {
+pub struct _VASegmentParameterVP9__bindgen_ty_1__bindgen_ty_1 {
     pub _bitfield_align_1: [u8; 0],
-    pub _bitfield_1: __BindgenBitfieldUnit<[u8; 2usize]>,
-    pub __bindgen_padding_0: u16,
+    pub _bitfield_1: __BindgenBitfieldUnit<[u8; 1usize]>,
+    pub __bindgen_padding_0: u8,
 }
-impl _VAEncMacroblockParameterBufferH264__bindgen_ty_1__bindgen_ty_1__bindgen_ty_1 {
+impl _VASegmentParameterVP9__bindgen_ty_1__bindgen_ty_1 {
     #[inline]
-    pub fn pred_avail_override_flag(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(0usize, 1u8) as u32) }
+    pub fn segment_reference_enabled(&self) -> u16 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(0usize, 1u8) as u16) }
     }
     #[inline]
-    pub fn set_pred_avail_override_flag(&mut self, val: u32) {
+    pub fn set_segment_reference_enabled(&mut self, val: u16) {
         unsafe {
-            let val: u32 = ::std::mem::transmute(val);
+            let val: u16 = ::std::mem::transmute(val);
             self._bitfield_1.set(0usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn pred_avail_flags(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(1usize, 8u8) as u32) }
+    pub fn segment_reference(&self) -> u16 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(1usize, 2u8) as u16) }
     }
     #[inline]
-    pub fn set_pred_avail_flags(&mut self, val: u32) {
+    pub fn set_segment_reference(&mut self, val: u16) {
         unsafe {
-            let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(1usize, 8u8, val as u64)
+            let val: u16 = ::std::mem::transmute(val);
+            self._bitfield_1.set(1usize, 2u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn segment_reference_skipped(&self) -> u16 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(3usize, 1u8) as u16) }
+    }
+    #[inline]
+    pub fn set_segment_reference_skipped(&mut self, val: u16) {
+        unsafe {
+            let val: u16 = ::std::mem::transmute(val);
+            self._bitfield_1.set(3usize, 1u8, val as u64)
         }
     }
     #[inline]
     pub fn new_bitfield_1(
-        pred_avail_override_flag: u32,
-        pred_avail_flags: u32,
-    ) -> __BindgenBitfieldUnit<[u8; 2usize]> {
-        let mut __bindgen_bitfield_unit: __BindgenBitfieldUnit<[u8; 2usize]> = Default::default();
+        segment_reference_enabled: u16,
+        segment_reference: u16,
+        segment_reference_skipped: u16,
+    ) -> __BindgenBitfieldUnit<[u8; 1usize]> {
+        let mut __bindgen_bitfield_unit: __BindgenBitfieldUnit<[u8; 1usize]> = Default::default();
         __bindgen_bitfield_unit.set(0usize, 1u8, {
-            let pred_avail_override_flag: u32 =
-                unsafe { ::std::mem::transmute(pred_avail_override_flag) };
-            pred_avail_override_flag as u64
+            let segment_reference_enabled: u16 =
+                unsafe { ::std::mem::transmute(segment_reference_enabled) };
+            segment_reference_enabled as u64
         });
-        __bindgen_bitfield_unit.set(1usize, 8u8, {
-            let pred_avail_flags: u32 = unsafe { ::std::mem::transmute(pred_avail_flags) };
-            pred_avail_flags as u64
+        __bindgen_bitfield_unit.set(1usize, 2u8, {
+            let segment_reference: u16 = unsafe { ::std::mem::transmute(segment_reference) };
+            segment_reference as u64
+        });
+        __bindgen_bitfield_unit.set(3usize, 1u8, {
+            let segment_reference_skipped: u16 =
+                unsafe { ::std::mem::transmute(segment_reference_skipped) };
+            segment_reference_skipped as u64
         });
         __bindgen_bitfield_unit
     }
 }
-impl Default for _VAEncMacroblockParameterBufferH264__bindgen_ty_1__bindgen_ty_1 {
+impl Default for _VASegmentParameterVP9__bindgen_ty_1 {
     fn default() -> Self {
         let mut s = ::std::mem::MaybeUninit::<Self>::uninit();
         unsafe {
@@ -3600,18 +3792,7 @@ impl Default for _VAEncMacroblockParameterBufferH264__bindgen_ty_1__bindgen_ty_1
         }
     }
 }
-#[repr(C)]
-#[derive(Copy, Clone)]
-pub union _VAEncMacroblockParameterBufferH264__bindgen_ty_1__bindgen_ty_2 {
-    pub bits: _VAEncMacroblockParameterBufferH264__bindgen_ty_1__bindgen_ty_2__bindgen_ty_1,
-    pub value: u32,
-}
-#[repr(C)]
-#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
-pub struct _VAEncMacroblockParameterBufferH264__bindgen_ty_1__bindgen_ty_2__bindgen_ty_1 {
-    pub reserved: u32,
-}
-impl Default for _VAEncMacroblockParameterBufferH264__bindgen_ty_1__bindgen_ty_2 {
+impl Default for _VASegmentParameterVP9 {
     fn default() -> Self {
         let mut s = ::std::mem::MaybeUninit::<Self>::uninit();
         unsafe {
@@ -3620,7 +3801,17 @@ impl Default for _VAEncMacroblockParameterBufferH264__bindgen_ty_1__bindgen_ty_2
         }
     }
 }
-impl Default for _VAEncMacroblockParameterBufferH264__bindgen_ty_1 {
+pub type VASegmentParameterVP9 = _VASegmentParameterVP9;
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct _VASliceParameterBufferVP9 {
+    pub slice_data_size: u32,
+    pub slice_data_offset: u32,
+    pub slice_data_flag: u32,
+    pub seg_param: [VASegmentParameterVP9; 8usize],
+    pub va_reserved: [u32; 4usize],
+}
+impl Default for _VASliceParameterBufferVP9 {
     fn default() -> Self {
         let mut s = ::std::mem::MaybeUninit::<Self>::uninit();
         unsafe {
@@ -3629,7 +3820,29 @@ impl Default for _VAEncMacroblockParameterBufferH264__bindgen_ty_1 {
         }
     }
 }
-impl Default for _VAEncMacroblockParameterBufferH264 {
+pub type VASliceParameterBufferVP9 = _VASliceParameterBufferVP9;
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct _VAPictureStats {
+    pub picture_id: VASurfaceID,
+    pub flags: u32,
+}
+pub type VAPictureStats = _VAPictureStats;
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct _VAStatsStatisticsParameter {
+    pub input: VAPictureStats,
+    pub past_references: *mut VAPictureStats,
+    pub num_past_references: u32,
+    pub past_ref_stat_buf: *mut VABufferID,
+    pub future_references: *mut VAPictureStats,
+    pub num_future_references: u32,
+    pub future_ref_stat_buf: *mut VABufferID,
+    pub outputs: *mut VABufferID,
+    pub mv_predictor: VABufferID,
+    pub qp: VABufferID,
+}
+impl Default for _VAStatsStatisticsParameter {
     fn default() -> Self {
         let mut s = ::std::mem::MaybeUninit::<Self>::uninit();
         unsafe {
@@ -3638,183 +3851,220 @@ impl Default for _VAEncMacroblockParameterBufferH264 {
         }
     }
 }
-pub type VAEncMacroblockParameterBufferH264 = _VAEncMacroblockParameterBufferH264;
+pub type VAStatsStatisticsParameter = _VAStatsStatisticsParameter;
+pub mod VAEncPackedHeaderTypeH264 {
+    pub type Type = ::std::os::raw::c_uint;
+    pub const VAEncPackedHeaderH264_SPS: Type = 1;
+    pub const VAEncPackedHeaderH264_PPS: Type = 2;
+    pub const VAEncPackedHeaderH264_Slice: Type = 3;
+    pub const VAEncPackedHeaderH264_SEI: Type = 2147483649;
+}
 #[repr(C)]
 #[derive(Copy, Clone)]
-pub struct _VAEncMiscParameterSubMbPartPelH264 {
-    pub disable_inter_sub_mb_partition: u32,
-    pub inter_sub_mb_partition_mask: _VAEncMiscParameterSubMbPartPelH264__bindgen_ty_1,
-    pub enable_sub_pel_mode: u32,
-    pub sub_pel_mode: u8,
-    pub reserved: [u8; 3usize],
+pub struct _VAEncSequenceParameterBufferH264 {
+    pub seq_parameter_set_id: u8,
+    pub level_idc: u8,
+    pub intra_period: u32,
+    pub intra_idr_period: u32,
+    pub ip_period: u32,
+    pub bits_per_second: u32,
+    pub max_num_ref_frames: u32,
+    pub picture_width_in_mbs: u16,
+    pub picture_height_in_mbs: u16,
+    pub seq_fields: _VAEncSequenceParameterBufferH264__bindgen_ty_1,
+    pub bit_depth_luma_minus8: u8,
+    pub bit_depth_chroma_minus8: u8,
+    pub num_ref_frames_in_pic_order_cnt_cycle: u8,
+    pub offset_for_non_ref_pic: i32,
+    pub offset_for_top_to_bottom_field: i32,
+    pub offset_for_ref_frame: [i32; 256usize],
+    pub frame_cropping_flag: u8,
+    pub frame_crop_left_offset: u32,
+    pub frame_crop_right_offset: u32,
+    pub frame_crop_top_offset: u32,
+    pub frame_crop_bottom_offset: u32,
+    pub vui_parameters_present_flag: u8,
+    pub vui_fields: _VAEncSequenceParameterBufferH264__bindgen_ty_2,
+    pub aspect_ratio_idc: u8,
+    pub sar_width: u32,
+    pub sar_height: u32,
+    pub num_units_in_tick: u32,
+    pub time_scale: u32,
+    pub va_reserved: [u32; 4usize],
 }
 #[repr(C)]
 #[derive(Copy, Clone)]
-pub union _VAEncMiscParameterSubMbPartPelH264__bindgen_ty_1 {
-    pub bits: _VAEncMiscParameterSubMbPartPelH264__bindgen_ty_1__bindgen_ty_1,
-    pub value: u8,
+pub union _VAEncSequenceParameterBufferH264__bindgen_ty_1 {
+    pub bits: _VAEncSequenceParameterBufferH264__bindgen_ty_1__bindgen_ty_1,
+    pub value: u32,
 }
 #[repr(C)]
 #[repr(align(4))]
 #[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
-pub struct _VAEncMiscParameterSubMbPartPelH264__bindgen_ty_1__bindgen_ty_1 {
+pub struct _VAEncSequenceParameterBufferH264__bindgen_ty_1__bindgen_ty_1 {
     pub _bitfield_align_1: [u8; 0],
-    pub _bitfield_1: __BindgenBitfieldUnit<[u8; 1usize]>,
-    pub __bindgen_padding_0: [u8; 3usize],
+    pub _bitfield_1: __BindgenBitfieldUnit<[u8; 3usize]>,
+    pub __bindgen_padding_0: u8,
 }
-impl _VAEncMiscParameterSubMbPartPelH264__bindgen_ty_1__bindgen_ty_1 {
+impl _VAEncSequenceParameterBufferH264__bindgen_ty_1__bindgen_ty_1 {
     #[inline]
-    pub fn disable_16x16_inter_mb_partition(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(0usize, 1u8) as u32) }
+    pub fn chroma_format_idc(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(0usize, 2u8) as u32) }
     }
     #[inline]
-    pub fn set_disable_16x16_inter_mb_partition(&mut self, val: u32) {
+    pub fn set_chroma_format_idc(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(0usize, 1u8, val as u64)
-        }
-    }
-    #[inline]
-    pub fn disable_16x8_inter_mb_partition(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(1usize, 1u8) as u32) }
-    }
-    #[inline]
-    pub fn set_disable_16x8_inter_mb_partition(&mut self, val: u32) {
-        unsafe {
-            let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(1usize, 1u8, val as u64)
+            self._bitfield_1.set(0usize, 2u8, val as u64)
         }
     }
     #[inline]
-    pub fn disable_8x16_inter_mb_partition(&self) -> u32 {
+    pub fn frame_mbs_only_flag(&self) -> u32 {
         unsafe { ::std::mem::transmute(self._bitfield_1.get(2usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_disable_8x16_inter_mb_partition(&mut self, val: u32) {
+    pub fn set_frame_mbs_only_flag(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
             self._bitfield_1.set(2usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn disable_8x8_inter_mb_partition(&self) -> u32 {
+    pub fn mb_adaptive_frame_field_flag(&self) -> u32 {
         unsafe { ::std::mem::transmute(self._bitfield_1.get(3usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_disable_8x8_inter_mb_partition(&mut self, val: u32) {
+    pub fn set_mb_adaptive_frame_field_flag(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
             self._bitfield_1.set(3usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn disable_8x4_inter_mb_partition(&self) -> u32 {
+    pub fn seq_scaling_matrix_present_flag(&self) -> u32 {
         unsafe { ::std::mem::transmute(self._bitfield_1.get(4usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_disable_8x4_inter_mb_partition(&mut self, val: u32) {
+    pub fn set_seq_scaling_matrix_present_flag(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
             self._bitfield_1.set(4usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn disable_4x8_inter_mb_partition(&self) -> u32 {
+    pub fn direct_8x8_inference_flag(&self) -> u32 {
         unsafe { ::std::mem::transmute(self._bitfield_1.get(5usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_disable_4x8_inter_mb_partition(&mut self, val: u32) {
+    pub fn set_direct_8x8_inference_flag(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
             self._bitfield_1.set(5usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn disable_4x4_inter_mb_partition(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(6usize, 1u8) as u32) }
+    pub fn log2_max_frame_num_minus4(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(6usize, 4u8) as u32) }
     }
     #[inline]
-    pub fn set_disable_4x4_inter_mb_partition(&mut self, val: u32) {
+    pub fn set_log2_max_frame_num_minus4(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(6usize, 1u8, val as u64)
+            self._bitfield_1.set(6usize, 4u8, val as u64)
         }
     }
     #[inline]
-    pub fn reserved(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(7usize, 1u8) as u32) }
+    pub fn pic_order_cnt_type(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(10usize, 2u8) as u32) }
     }
     #[inline]
-    pub fn set_reserved(&mut self, val: u32) {
+    pub fn set_pic_order_cnt_type(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(7usize, 1u8, val as u64)
+            self._bitfield_1.set(10usize, 2u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn log2_max_pic_order_cnt_lsb_minus4(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(12usize, 4u8) as u32) }
+    }
+    #[inline]
+    pub fn set_log2_max_pic_order_cnt_lsb_minus4(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(12usize, 4u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn delta_pic_order_always_zero_flag(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(16usize, 1u8) as u32) }
+    }
+    #[inline]
+    pub fn set_delta_pic_order_always_zero_flag(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(16usize, 1u8, val as u64)
         }
     }
     #[inline]
     pub fn new_bitfield_1(
-        disable_16x16_inter_mb_partition: u32,
-        disable_16x8_inter_mb_partition: u32,
-        disable_8x16_inter_mb_partition: u32,
-        disable_8x8_inter_mb_partition: u32,
-        disable_8x4_inter_mb_partition: u32,
-        disable_4x8_inter_mb_partition: u32,
-        disable_4x4_inter_mb_partition: u32,
-        reserved: u32,
-    ) -> __BindgenBitfieldUnit<[u8; 1usize]> {
-        let mut __bindgen_bitfield_unit: __BindgenBitfieldUnit<[u8; 1usize]> = Default::default();
-        __bindgen_bitfield_unit.set(0usize, 1u8, {
-            let disable_16x16_inter_mb_partition: u32 =
-                unsafe { ::std::mem::transmute(disable_16x16_inter_mb_partition) };
-            disable_16x16_inter_mb_partition as u64
-        });
-        __bindgen_bitfield_unit.set(1usize, 1u8, {
-            let disable_16x8_inter_mb_partition: u32 =
-                unsafe { ::std::mem::transmute(disable_16x8_inter_mb_partition) };
-            disable_16x8_inter_mb_partition as u64
+        chroma_format_idc: u32,
+        frame_mbs_only_flag: u32,
+        mb_adaptive_frame_field_flag: u32,
+        seq_scaling_matrix_present_flag: u32,
+        direct_8x8_inference_flag: u32,
+        log2_max_frame_num_minus4: u32,
+        pic_order_cnt_type: u32,
+        log2_max_pic_order_cnt_lsb_minus4: u32,
+        delta_pic_order_always_zero_flag: u32,
+    ) -> __BindgenBitfieldUnit<[u8; 3usize]> {
+        let mut __bindgen_bitfield_unit: __BindgenBitfieldUnit<[u8; 3usize]> = Default::default();
+        __bindgen_bitfield_unit.set(0usize, 2u8, {
+            let chroma_format_idc: u32 = unsafe { ::std::mem::transmute(chroma_format_idc) };
+            chroma_format_idc as u64
         });
         __bindgen_bitfield_unit.set(2usize, 1u8, {
-            let disable_8x16_inter_mb_partition: u32 =
-                unsafe { ::std::mem::transmute(disable_8x16_inter_mb_partition) };
-            disable_8x16_inter_mb_partition as u64
+            let frame_mbs_only_flag: u32 = unsafe { ::std::mem::transmute(frame_mbs_only_flag) };
+            frame_mbs_only_flag as u64
         });
         __bindgen_bitfield_unit.set(3usize, 1u8, {
-            let disable_8x8_inter_mb_partition: u32 =
-                unsafe { ::std::mem::transmute(disable_8x8_inter_mb_partition) };
-            disable_8x8_inter_mb_partition as u64
+            let mb_adaptive_frame_field_flag: u32 =
+                unsafe { ::std::mem::transmute(mb_adaptive_frame_field_flag) };
+            mb_adaptive_frame_field_flag as u64
         });
         __bindgen_bitfield_unit.set(4usize, 1u8, {
-            let disable_8x4_inter_mb_partition: u32 =
-                unsafe { ::std::mem::transmute(disable_8x4_inter_mb_partition) };
-            disable_8x4_inter_mb_partition as u64
+            let seq_scaling_matrix_present_flag: u32 =
+                unsafe { ::std::mem::transmute(seq_scaling_matrix_present_flag) };
+            seq_scaling_matrix_present_flag as u64
         });
         __bindgen_bitfield_unit.set(5usize, 1u8, {
-            let disable_4x8_inter_mb_partition: u32 =
-                unsafe { ::std::mem::transmute(disable_4x8_inter_mb_partition) };
-            disable_4x8_inter_mb_partition as u64
+            let direct_8x8_inference_flag: u32 =
+                unsafe { ::std::mem::transmute(direct_8x8_inference_flag) };
+            direct_8x8_inference_flag as u64
         });
-        __bindgen_bitfield_unit.set(6usize, 1u8, {
-            let disable_4x4_inter_mb_partition: u32 =
-                unsafe { ::std::mem::transmute(disable_4x4_inter_mb_partition) };
-            disable_4x4_inter_mb_partition as u64
+        __bindgen_bitfield_unit.set(6usize, 4u8, {
+            let log2_max_frame_num_minus4: u32 =
+                unsafe { ::std::mem::transmute(log2_max_frame_num_minus4) };
+            log2_max_frame_num_minus4 as u64
         });
-        __bindgen_bitfield_unit.set(7usize, 1u8, {
-            let reserved: u32 = unsafe { ::std::mem::transmute(reserved) };
-            reserved as u64
+        __bindgen_bitfield_unit.set(10usize, 2u8, {
+            let pic_order_cnt_type: u32 = unsafe { ::std::mem::transmute(pic_order_cnt_type) };
+            pic_order_cnt_type as u64
+        });
+        __bindgen_bitfield_unit.set(12usize, 4u8, {
+            let log2_max_pic_order_cnt_lsb_minus4: u32 =
+                unsafe { ::std::mem::transmute(log2_max_pic_order_cnt_lsb_minus4) };
+            log2_max_pic_order_cnt_lsb_minus4 as u64
+        });
+        __bindgen_bitfield_unit.set(16usize, 1u8, {
+            let delta_pic_order_always_zero_flag: u32 =
+                unsafe { ::std::mem::transmute(delta_pic_order_always_zero_flag) };
+            delta_pic_order_always_zero_flag as u64
         });
         __bindgen_bitfield_unit
     }
 }
-impl Default for _VAEncMiscParameterSubMbPartPelH264__bindgen_ty_1 {
-    fn default() -> Self {
-        let mut s = ::std::mem::MaybeUninit::<Self>::uninit();
-        unsafe {
-            ::std::ptr::write_bytes(s.as_mut_ptr(), 0, 1);
-            s.assume_init()
-        }
-    }
-}
-impl Default for _VAEncMiscParameterSubMbPartPelH264 {
+impl Default for _VAEncSequenceParameterBufferH264__bindgen_ty_1 {
     fn default() -> Self {
         let mut s = ::std::mem::MaybeUninit::<Self>::uninit();
         unsafe {
@@ -3823,230 +4073,179 @@ impl Default for _VAEncMiscParameterSubMbPartPelH264 {
         }
     }
 }
-pub type VAEncMiscParameterSubMbPartPelH264 = _VAEncMiscParameterSubMbPartPelH264;
-pub type VAQMatrixBufferMPEG2 = VAIQMatrixBufferMPEG2;
-pub mod VAEncPackedHeaderTypeMPEG2 {
-    pub type Type = ::std::os::raw::c_uint;
-    pub const VAEncPackedHeaderMPEG2_SPS: Type = 1;
-    pub const VAEncPackedHeaderMPEG2_PPS: Type = 2;
-    pub const VAEncPackedHeaderMPEG2_Slice: Type = 3;
-}
-#[repr(C)]
-#[derive(Copy, Clone)]
-pub struct _VAEncSequenceParameterBufferMPEG2 {
-    pub intra_period: u32,
-    pub ip_period: u32,
-    pub picture_width: u16,
-    pub picture_height: u16,
-    pub bits_per_second: u32,
-    pub frame_rate: f32,
-    pub aspect_ratio_information: u16,
-    pub vbv_buffer_size: u32,
-    pub sequence_extension: _VAEncSequenceParameterBufferMPEG2__bindgen_ty_1,
-    pub new_gop_header: u32,
-    pub gop_header: _VAEncSequenceParameterBufferMPEG2__bindgen_ty_2,
-    pub va_reserved: [u32; 4usize],
-}
 #[repr(C)]
 #[derive(Copy, Clone)]
-pub union _VAEncSequenceParameterBufferMPEG2__bindgen_ty_1 {
-    pub bits: _VAEncSequenceParameterBufferMPEG2__bindgen_ty_1__bindgen_ty_1,
+pub union _VAEncSequenceParameterBufferH264__bindgen_ty_2 {
+    pub bits: _VAEncSequenceParameterBufferH264__bindgen_ty_2__bindgen_ty_1,
     pub value: u32,
 }
 #[repr(C)]
 #[repr(align(4))]
 #[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
-pub struct _VAEncSequenceParameterBufferMPEG2__bindgen_ty_1__bindgen_ty_1 {
-    pub _bitfield_align_1: [u8; 0],
-    pub _bitfield_1: __BindgenBitfieldUnit<[u8; 3usize]>,
-    pub __bindgen_padding_0: u8,
+pub struct _VAEncSequenceParameterBufferH264__bindgen_ty_2__bindgen_ty_1 {
+    pub _bitfield_align_1: [u16; 0],
+    pub _bitfield_1: __BindgenBitfieldUnit<[u8; 4usize]>,
 }
-impl _VAEncSequenceParameterBufferMPEG2__bindgen_ty_1__bindgen_ty_1 {
+impl _VAEncSequenceParameterBufferH264__bindgen_ty_2__bindgen_ty_1 {
     #[inline]
-    pub fn profile_and_level_indication(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(0usize, 8u8) as u32) }
+    pub fn aspect_ratio_info_present_flag(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(0usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_profile_and_level_indication(&mut self, val: u32) {
+    pub fn set_aspect_ratio_info_present_flag(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(0usize, 8u8, val as u64)
+            self._bitfield_1.set(0usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn progressive_sequence(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(8usize, 1u8) as u32) }
+    pub fn timing_info_present_flag(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(1usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_progressive_sequence(&mut self, val: u32) {
+    pub fn set_timing_info_present_flag(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(8usize, 1u8, val as u64)
+            self._bitfield_1.set(1usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn chroma_format(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(9usize, 2u8) as u32) }
+    pub fn bitstream_restriction_flag(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(2usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_chroma_format(&mut self, val: u32) {
+    pub fn set_bitstream_restriction_flag(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(9usize, 2u8, val as u64)
+            self._bitfield_1.set(2usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn low_delay(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(11usize, 1u8) as u32) }
+    pub fn log2_max_mv_length_horizontal(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(3usize, 5u8) as u32) }
     }
     #[inline]
-    pub fn set_low_delay(&mut self, val: u32) {
+    pub fn set_log2_max_mv_length_horizontal(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(11usize, 1u8, val as u64)
+            self._bitfield_1.set(3usize, 5u8, val as u64)
         }
     }
     #[inline]
-    pub fn frame_rate_extension_n(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(12usize, 2u8) as u32) }
+    pub fn log2_max_mv_length_vertical(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(8usize, 5u8) as u32) }
     }
     #[inline]
-    pub fn set_frame_rate_extension_n(&mut self, val: u32) {
+    pub fn set_log2_max_mv_length_vertical(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(12usize, 2u8, val as u64)
+            self._bitfield_1.set(8usize, 5u8, val as u64)
         }
     }
     #[inline]
-    pub fn frame_rate_extension_d(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(14usize, 5u8) as u32) }
+    pub fn fixed_frame_rate_flag(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(13usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_frame_rate_extension_d(&mut self, val: u32) {
+    pub fn set_fixed_frame_rate_flag(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(14usize, 5u8, val as u64)
+            self._bitfield_1.set(13usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn new_bitfield_1(
-        profile_and_level_indication: u32,
-        progressive_sequence: u32,
-        chroma_format: u32,
-        low_delay: u32,
-        frame_rate_extension_n: u32,
-        frame_rate_extension_d: u32,
-    ) -> __BindgenBitfieldUnit<[u8; 3usize]> {
-        let mut __bindgen_bitfield_unit: __BindgenBitfieldUnit<[u8; 3usize]> = Default::default();
-        __bindgen_bitfield_unit.set(0usize, 8u8, {
-            let profile_and_level_indication: u32 =
-                unsafe { ::std::mem::transmute(profile_and_level_indication) };
-            profile_and_level_indication as u64
-        });
-        __bindgen_bitfield_unit.set(8usize, 1u8, {
-            let progressive_sequence: u32 = unsafe { ::std::mem::transmute(progressive_sequence) };
-            progressive_sequence as u64
-        });
-        __bindgen_bitfield_unit.set(9usize, 2u8, {
-            let chroma_format: u32 = unsafe { ::std::mem::transmute(chroma_format) };
-            chroma_format as u64
-        });
-        __bindgen_bitfield_unit.set(11usize, 1u8, {
-            let low_delay: u32 = unsafe { ::std::mem::transmute(low_delay) };
-            low_delay as u64
-        });
-        __bindgen_bitfield_unit.set(12usize, 2u8, {
-            let frame_rate_extension_n: u32 =
-                unsafe { ::std::mem::transmute(frame_rate_extension_n) };
-            frame_rate_extension_n as u64
-        });
-        __bindgen_bitfield_unit.set(14usize, 5u8, {
-            let frame_rate_extension_d: u32 =
-                unsafe { ::std::mem::transmute(frame_rate_extension_d) };
-            frame_rate_extension_d as u64
-        });
-        __bindgen_bitfield_unit
-    }
-}
-impl Default for _VAEncSequenceParameterBufferMPEG2__bindgen_ty_1 {
-    fn default() -> Self {
-        let mut s = ::std::mem::MaybeUninit::<Self>::uninit();
-        unsafe {
-            ::std::ptr::write_bytes(s.as_mut_ptr(), 0, 1);
-            s.assume_init()
-        }
-    }
-}
-#[repr(C)]
-#[derive(Copy, Clone)]
-pub union _VAEncSequenceParameterBufferMPEG2__bindgen_ty_2 {
-    pub bits: _VAEncSequenceParameterBufferMPEG2__bindgen_ty_2__bindgen_ty_1,
-    pub value: u32,
-}
-#[repr(C)]
-#[repr(align(4))]
-#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
-pub struct _VAEncSequenceParameterBufferMPEG2__bindgen_ty_2__bindgen_ty_1 {
-    pub _bitfield_align_1: [u32; 0],
-    pub _bitfield_1: __BindgenBitfieldUnit<[u8; 4usize]>,
-}
-impl _VAEncSequenceParameterBufferMPEG2__bindgen_ty_2__bindgen_ty_1 {
-    #[inline]
-    pub fn time_code(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(0usize, 25u8) as u32) }
+    pub fn low_delay_hrd_flag(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(14usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_time_code(&mut self, val: u32) {
+    pub fn set_low_delay_hrd_flag(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(0usize, 25u8, val as u64)
+            self._bitfield_1.set(14usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn closed_gop(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(25usize, 1u8) as u32) }
+    pub fn motion_vectors_over_pic_boundaries_flag(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(15usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_closed_gop(&mut self, val: u32) {
+    pub fn set_motion_vectors_over_pic_boundaries_flag(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(25usize, 1u8, val as u64)
+            self._bitfield_1.set(15usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn broken_link(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(26usize, 1u8) as u32) }
+    pub fn reserved(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(16usize, 16u8) as u32) }
     }
     #[inline]
-    pub fn set_broken_link(&mut self, val: u32) {
+    pub fn set_reserved(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(26usize, 1u8, val as u64)
+            self._bitfield_1.set(16usize, 16u8, val as u64)
         }
     }
     #[inline]
     pub fn new_bitfield_1(
-        time_code: u32,
-        closed_gop: u32,
-        broken_link: u32,
+        aspect_ratio_info_present_flag: u32,
+        timing_info_present_flag: u32,
+        bitstream_restriction_flag: u32,
+        log2_max_mv_length_horizontal: u32,
+        log2_max_mv_length_vertical: u32,
+        fixed_frame_rate_flag: u32,
+        low_delay_hrd_flag: u32,
+        motion_vectors_over_pic_boundaries_flag: u32,
+        reserved: u32,
     ) -> __BindgenBitfieldUnit<[u8; 4usize]> {
         let mut __bindgen_bitfield_unit: __BindgenBitfieldUnit<[u8; 4usize]> = Default::default();
-        __bindgen_bitfield_unit.set(0usize, 25u8, {
-            let time_code: u32 = unsafe { ::std::mem::transmute(time_code) };
-            time_code as u64
+        __bindgen_bitfield_unit.set(0usize, 1u8, {
+            let aspect_ratio_info_present_flag: u32 =
+                unsafe { ::std::mem::transmute(aspect_ratio_info_present_flag) };
+            aspect_ratio_info_present_flag as u64
         });
-        __bindgen_bitfield_unit.set(25usize, 1u8, {
-            let closed_gop: u32 = unsafe { ::std::mem::transmute(closed_gop) };
-            closed_gop as u64
+        __bindgen_bitfield_unit.set(1usize, 1u8, {
+            let timing_info_present_flag: u32 =
+                unsafe { ::std::mem::transmute(timing_info_present_flag) };
+            timing_info_present_flag as u64
         });
-        __bindgen_bitfield_unit.set(26usize, 1u8, {
-            let broken_link: u32 = unsafe { ::std::mem::transmute(broken_link) };
-            broken_link as u64
+        __bindgen_bitfield_unit.set(2usize, 1u8, {
+            let bitstream_restriction_flag: u32 =
+                unsafe { ::std::mem::transmute(bitstream_restriction_flag) };
+            bitstream_restriction_flag as u64
+        });
+        __bindgen_bitfield_unit.set(3usize, 5u8, {
+            let log2_max_mv_length_horizontal: u32 =
+                unsafe { ::std::mem::transmute(log2_max_mv_length_horizontal) };
+            log2_max_mv_length_horizontal as u64
+        });
+        __bindgen_bitfield_unit.set(8usize, 5u8, {
+            let log2_max_mv_length_vertical: u32 =
+                unsafe { ::std::mem::transmute(log2_max_mv_length_vertical) };
+            log2_max_mv_length_vertical as u64
+        });
+        __bindgen_bitfield_unit.set(13usize, 1u8, {
+            let fixed_frame_rate_flag: u32 =
+                unsafe { ::std::mem::transmute(fixed_frame_rate_flag) };
+            fixed_frame_rate_flag as u64
+        });
+        __bindgen_bitfield_unit.set(14usize, 1u8, {
+            let low_delay_hrd_flag: u32 = unsafe { ::std::mem::transmute(low_delay_hrd_flag) };
+            low_delay_hrd_flag as u64
+        });
+        __bindgen_bitfield_unit.set(15usize, 1u8, {
+            let motion_vectors_over_pic_boundaries_flag: u32 =
+                unsafe { ::std::mem::transmute(motion_vectors_over_pic_boundaries_flag) };
+            motion_vectors_over_pic_boundaries_flag as u64
+        });
+        __bindgen_bitfield_unit.set(16usize, 16u8, {
+            let reserved: u32 = unsafe { ::std::mem::transmute(reserved) };
+            reserved as u64
         });
         __bindgen_bitfield_unit
     }
 }
-impl Default for _VAEncSequenceParameterBufferMPEG2__bindgen_ty_2 {
+impl Default for _VAEncSequenceParameterBufferH264__bindgen_ty_2 {
     fn default() -> Self {
         let mut s = ::std::mem::MaybeUninit::<Self>::uninit();
         unsafe {
@@ -4055,7 +4254,7 @@ impl Default for _VAEncSequenceParameterBufferMPEG2__bindgen_ty_2 {
         }
     }
 }
-impl Default for _VAEncSequenceParameterBufferMPEG2 {
+impl Default for _VAEncSequenceParameterBufferH264 {
     fn default() -> Self {
         let mut s = ::std::mem::MaybeUninit::<Self>::uninit();
         unsafe {
@@ -4064,154 +4263,156 @@ impl Default for _VAEncSequenceParameterBufferMPEG2 {
         }
     }
 }
-pub type VAEncSequenceParameterBufferMPEG2 = _VAEncSequenceParameterBufferMPEG2;
+pub type VAEncSequenceParameterBufferH264 = _VAEncSequenceParameterBufferH264;
 #[repr(C)]
 #[derive(Copy, Clone)]
-pub struct _VAEncPictureParameterBufferMPEG2 {
-    pub forward_reference_picture: VASurfaceID,
-    pub backward_reference_picture: VASurfaceID,
-    pub reconstructed_picture: VASurfaceID,
+pub struct _VAEncPictureParameterBufferH264 {
+    pub CurrPic: VAPictureH264,
+    pub ReferenceFrames: [VAPictureH264; 16usize],
     pub coded_buf: VABufferID,
+    pub pic_parameter_set_id: u8,
+    pub seq_parameter_set_id: u8,
     pub last_picture: u8,
-    pub picture_type: VAEncPictureType::Type,
-    pub temporal_reference: u32,
-    pub vbv_delay: u32,
-    pub f_code: [[u8; 2usize]; 2usize],
-    pub picture_coding_extension: _VAEncPictureParameterBufferMPEG2__bindgen_ty_1,
-    pub composite_display: _VAEncPictureParameterBufferMPEG2__bindgen_ty_2,
+    pub frame_num: u16,
+    pub pic_init_qp: u8,
+    pub num_ref_idx_l0_active_minus1: u8,
+    pub num_ref_idx_l1_active_minus1: u8,
+    pub chroma_qp_index_offset: i8,
+    pub second_chroma_qp_index_offset: i8,
+    pub pic_fields: _VAEncPictureParameterBufferH264__bindgen_ty_1,
     pub va_reserved: [u32; 4usize],
 }
 #[repr(C)]
 #[derive(Copy, Clone)]
-pub union _VAEncPictureParameterBufferMPEG2__bindgen_ty_1 {
-    pub bits: _VAEncPictureParameterBufferMPEG2__bindgen_ty_1__bindgen_ty_1,
+pub union _VAEncPictureParameterBufferH264__bindgen_ty_1 {
+    pub bits: _VAEncPictureParameterBufferH264__bindgen_ty_1__bindgen_ty_1,
     pub value: u32,
 }
 #[repr(C)]
 #[repr(align(4))]
 #[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
-pub struct _VAEncPictureParameterBufferMPEG2__bindgen_ty_1__bindgen_ty_1 {
+pub struct _VAEncPictureParameterBufferH264__bindgen_ty_1__bindgen_ty_1 {
     pub _bitfield_align_1: [u8; 0],
     pub _bitfield_1: __BindgenBitfieldUnit<[u8; 2usize]>,
     pub __bindgen_padding_0: u16,
 }
-impl _VAEncPictureParameterBufferMPEG2__bindgen_ty_1__bindgen_ty_1 {
+impl _VAEncPictureParameterBufferH264__bindgen_ty_1__bindgen_ty_1 {
     #[inline]
-    pub fn intra_dc_precision(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(0usize, 2u8) as u32) }
+    pub fn idr_pic_flag(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(0usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_intra_dc_precision(&mut self, val: u32) {
+    pub fn set_idr_pic_flag(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(0usize, 2u8, val as u64)
+            self._bitfield_1.set(0usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn picture_structure(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(2usize, 2u8) as u32) }
+    pub fn reference_pic_flag(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(1usize, 2u8) as u32) }
     }
     #[inline]
-    pub fn set_picture_structure(&mut self, val: u32) {
+    pub fn set_reference_pic_flag(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(2usize, 2u8, val as u64)
+            self._bitfield_1.set(1usize, 2u8, val as u64)
         }
     }
     #[inline]
-    pub fn top_field_first(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(4usize, 1u8) as u32) }
+    pub fn entropy_coding_mode_flag(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(3usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_top_field_first(&mut self, val: u32) {
+    pub fn set_entropy_coding_mode_flag(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(4usize, 1u8, val as u64)
+            self._bitfield_1.set(3usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn frame_pred_frame_dct(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(5usize, 1u8) as u32) }
+    pub fn weighted_pred_flag(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(4usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_frame_pred_frame_dct(&mut self, val: u32) {
+    pub fn set_weighted_pred_flag(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(5usize, 1u8, val as u64)
+            self._bitfield_1.set(4usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn concealment_motion_vectors(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(6usize, 1u8) as u32) }
+    pub fn weighted_bipred_idc(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(5usize, 2u8) as u32) }
     }
     #[inline]
-    pub fn set_concealment_motion_vectors(&mut self, val: u32) {
+    pub fn set_weighted_bipred_idc(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(6usize, 1u8, val as u64)
+            self._bitfield_1.set(5usize, 2u8, val as u64)
         }
     }
     #[inline]
-    pub fn q_scale_type(&self) -> u32 {
+    pub fn constrained_intra_pred_flag(&self) -> u32 {
         unsafe { ::std::mem::transmute(self._bitfield_1.get(7usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_q_scale_type(&mut self, val: u32) {
+    pub fn set_constrained_intra_pred_flag(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
             self._bitfield_1.set(7usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn intra_vlc_format(&self) -> u32 {
+    pub fn transform_8x8_mode_flag(&self) -> u32 {
         unsafe { ::std::mem::transmute(self._bitfield_1.get(8usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_intra_vlc_format(&mut self, val: u32) {
+    pub fn set_transform_8x8_mode_flag(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
             self._bitfield_1.set(8usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn alternate_scan(&self) -> u32 {
+    pub fn deblocking_filter_control_present_flag(&self) -> u32 {
         unsafe { ::std::mem::transmute(self._bitfield_1.get(9usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_alternate_scan(&mut self, val: u32) {
+    pub fn set_deblocking_filter_control_present_flag(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
             self._bitfield_1.set(9usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn repeat_first_field(&self) -> u32 {
+    pub fn redundant_pic_cnt_present_flag(&self) -> u32 {
         unsafe { ::std::mem::transmute(self._bitfield_1.get(10usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_repeat_first_field(&mut self, val: u32) {
+    pub fn set_redundant_pic_cnt_present_flag(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
             self._bitfield_1.set(10usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn progressive_frame(&self) -> u32 {
+    pub fn pic_order_present_flag(&self) -> u32 {
         unsafe { ::std::mem::transmute(self._bitfield_1.get(11usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_progressive_frame(&mut self, val: u32) {
+    pub fn set_pic_order_present_flag(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
             self._bitfield_1.set(11usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn composite_display_flag(&self) -> u32 {
+    pub fn pic_scaling_matrix_present_flag(&self) -> u32 {
         unsafe { ::std::mem::transmute(self._bitfield_1.get(12usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_composite_display_flag(&mut self, val: u32) {
+    pub fn set_pic_scaling_matrix_present_flag(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
             self._bitfield_1.set(12usize, 1u8, val as u64)
@@ -4219,69 +4420,83 @@ impl _VAEncPictureParameterBufferMPEG2__bindgen_ty_1__bindgen_ty_1 {
     }
     #[inline]
     pub fn new_bitfield_1(
-        intra_dc_precision: u32,
-        picture_structure: u32,
-        top_field_first: u32,
-        frame_pred_frame_dct: u32,
-        concealment_motion_vectors: u32,
-        q_scale_type: u32,
-        intra_vlc_format: u32,
-        alternate_scan: u32,
-        repeat_first_field: u32,
-        progressive_frame: u32,
-        composite_display_flag: u32,
+        idr_pic_flag: u32,
+        reference_pic_flag: u32,
+        entropy_coding_mode_flag: u32,
+        weighted_pred_flag: u32,
+        weighted_bipred_idc: u32,
+        constrained_intra_pred_flag: u32,
+        transform_8x8_mode_flag: u32,
+        deblocking_filter_control_present_flag: u32,
+        redundant_pic_cnt_present_flag: u32,
+        pic_order_present_flag: u32,
+        pic_scaling_matrix_present_flag: u32,
     ) -> __BindgenBitfieldUnit<[u8; 2usize]> {
         let mut __bindgen_bitfield_unit: __BindgenBitfieldUnit<[u8; 2usize]> = Default::default();
-        __bindgen_bitfield_unit.set(0usize, 2u8, {
-            let intra_dc_precision: u32 = unsafe { ::std::mem::transmute(intra_dc_precision) };
-            intra_dc_precision as u64
+        __bindgen_bitfield_unit.set(0usize, 1u8, {
+            let idr_pic_flag: u32 = unsafe { ::std::mem::transmute(idr_pic_flag) };
+            idr_pic_flag as u64
         });
-        __bindgen_bitfield_unit.set(2usize, 2u8, {
-            let picture_structure: u32 = unsafe { ::std::mem::transmute(picture_structure) };
-            picture_structure as u64
+        __bindgen_bitfield_unit.set(1usize, 2u8, {
+            let reference_pic_flag: u32 = unsafe { ::std::mem::transmute(reference_pic_flag) };
+            reference_pic_flag as u64
         });
-        __bindgen_bitfield_unit.set(4usize, 1u8, {
-            let top_field_first: u32 = unsafe { ::std::mem::transmute(top_field_first) };
-            top_field_first as u64
+        __bindgen_bitfield_unit.set(3usize, 1u8, {
+            let entropy_coding_mode_flag: u32 =
+                unsafe { ::std::mem::transmute(entropy_coding_mode_flag) };
+            entropy_coding_mode_flag as u64
         });
-        __bindgen_bitfield_unit.set(5usize, 1u8, {
-            let frame_pred_frame_dct: u32 = unsafe { ::std::mem::transmute(frame_pred_frame_dct) };
-            frame_pred_frame_dct as u64
+        __bindgen_bitfield_unit.set(4usize, 1u8, {
+            let weighted_pred_flag: u32 = unsafe { ::std::mem::transmute(weighted_pred_flag) };
+            weighted_pred_flag as u64
         });
-        __bindgen_bitfield_unit.set(6usize, 1u8, {
-            let concealment_motion_vectors: u32 =
-                unsafe { ::std::mem::transmute(concealment_motion_vectors) };
-            concealment_motion_vectors as u64
+        __bindgen_bitfield_unit.set(5usize, 2u8, {
+            let weighted_bipred_idc: u32 = unsafe { ::std::mem::transmute(weighted_bipred_idc) };
+            weighted_bipred_idc as u64
         });
         __bindgen_bitfield_unit.set(7usize, 1u8, {
-            let q_scale_type: u32 = unsafe { ::std::mem::transmute(q_scale_type) };
-            q_scale_type as u64
+            let constrained_intra_pred_flag: u32 =
+                unsafe { ::std::mem::transmute(constrained_intra_pred_flag) };
+            constrained_intra_pred_flag as u64
         });
         __bindgen_bitfield_unit.set(8usize, 1u8, {
-            let intra_vlc_format: u32 = unsafe { ::std::mem::transmute(intra_vlc_format) };
-            intra_vlc_format as u64
+            let transform_8x8_mode_flag: u32 =
+                unsafe { ::std::mem::transmute(transform_8x8_mode_flag) };
+            transform_8x8_mode_flag as u64
         });
         __bindgen_bitfield_unit.set(9usize, 1u8, {
-            let alternate_scan: u32 = unsafe { ::std::mem::transmute(alternate_scan) };
-            alternate_scan as u64
+            let deblocking_filter_control_present_flag: u32 =
+                unsafe { ::std::mem::transmute(deblocking_filter_control_present_flag) };
+            deblocking_filter_control_present_flag as u64
         });
         __bindgen_bitfield_unit.set(10usize, 1u8, {
-            let repeat_first_field: u32 = unsafe { ::std::mem::transmute(repeat_first_field) };
-            repeat_first_field as u64
+            let redundant_pic_cnt_present_flag: u32 =
+                unsafe { ::std::mem::transmute(redundant_pic_cnt_present_flag) };
+            redundant_pic_cnt_present_flag as u64
         });
         __bindgen_bitfield_unit.set(11usize, 1u8, {
-            let progressive_frame: u32 = unsafe { ::std::mem::transmute(progressive_frame) };
-            progressive_frame as u64
+            let pic_order_present_flag: u32 =
+                unsafe { ::std::mem::transmute(pic_order_present_flag) };
+            pic_order_present_flag as u64
         });
         __bindgen_bitfield_unit.set(12usize, 1u8, {
-            let composite_display_flag: u32 =
-                unsafe { ::std::mem::transmute(composite_display_flag) };
-            composite_display_flag as u64
+            let pic_scaling_matrix_present_flag: u32 =
+                unsafe { ::std::mem::transmute(pic_scaling_matrix_present_flag) };
+            pic_scaling_matrix_present_flag as u64
         });
         __bindgen_bitfield_unit
     }
 }
-impl Default for _VAEncPictureParameterBufferMPEG2__bindgen_ty_1 {
+impl Default for _VAEncPictureParameterBufferH264__bindgen_ty_1 {
+    fn default() -> Self {
+        let mut s = ::std::mem::MaybeUninit::<Self>::uninit();
+        unsafe {
+            ::std::ptr::write_bytes(s.as_mut_ptr(), 0, 1);
+            s.assume_init()
+        }
+    }
+}
+impl Default for _VAEncPictureParameterBufferH264 {
     fn default() -> Self {
         let mut s = ::std::mem::MaybeUninit::<Self>::uninit();
         unsafe {
@@ -4290,109 +4505,151 @@ impl Default for _VAEncPictureParameterBufferMPEG2__bindgen_ty_1 {
         }
     }
 }
+pub type VAEncPictureParameterBufferH264 = _VAEncPictureParameterBufferH264;
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct _VAEncQPBufferH264 {
+    pub qp: u8,
+}
+pub type VAEncQPBufferH264 = _VAEncQPBufferH264;
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct _VAEncSliceParameterBufferH264 {
+    pub macroblock_address: u32,
+    pub num_macroblocks: u32,
+    pub macroblock_info: VABufferID,
+    pub slice_type: u8,
+    pub pic_parameter_set_id: u8,
+    pub idr_pic_id: u16,
+    pub pic_order_cnt_lsb: u16,
+    pub delta_pic_order_cnt_bottom: i32,
+    pub delta_pic_order_cnt: [i32; 2usize],
+    pub direct_spatial_mv_pred_flag: u8,
+    pub num_ref_idx_active_override_flag: u8,
+    pub num_ref_idx_l0_active_minus1: u8,
+    pub num_ref_idx_l1_active_minus1: u8,
+    pub RefPicList0: [VAPictureH264; 32usize],
+    pub RefPicList1: [VAPictureH264; 32usize],
+    pub luma_log2_weight_denom: u8,
+    pub chroma_log2_weight_denom: u8,
+    pub luma_weight_l0_flag: u8,
+    pub luma_weight_l0: [::std::os::raw::c_short; 32usize],
+    pub luma_offset_l0: [::std::os::raw::c_short; 32usize],
+    pub chroma_weight_l0_flag: u8,
+    pub chroma_weight_l0: [[::std::os::raw::c_short; 2usize]; 32usize],
+    pub chroma_offset_l0: [[::std::os::raw::c_short; 2usize]; 32usize],
+    pub luma_weight_l1_flag: u8,
+    pub luma_weight_l1: [::std::os::raw::c_short; 32usize],
+    pub luma_offset_l1: [::std::os::raw::c_short; 32usize],
+    pub chroma_weight_l1_flag: u8,
+    pub chroma_weight_l1: [[::std::os::raw::c_short; 2usize]; 32usize],
+    pub chroma_offset_l1: [[::std::os::raw::c_short; 2usize]; 32usize],
+    pub cabac_init_idc: u8,
+    pub slice_qp_delta: i8,
+    pub disable_deblocking_filter_idc: u8,
+    pub slice_alpha_c0_offset_div2: i8,
+    pub slice_beta_offset_div2: i8,
+    pub va_reserved: [u32; 4usize],
+}
+pub type VAEncSliceParameterBufferH264 = _VAEncSliceParameterBufferH264;
 #[repr(C)]
 #[derive(Copy, Clone)]
-pub union _VAEncPictureParameterBufferMPEG2__bindgen_ty_2 {
-    pub bits: _VAEncPictureParameterBufferMPEG2__bindgen_ty_2__bindgen_ty_1,
+pub struct _VAEncMacroblockParameterBufferH264 {
+    pub qp: u8,
+    pub info: _VAEncMacroblockParameterBufferH264__bindgen_ty_1,
+    pub va_reserved: [u32; 4usize],
+}
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub union _VAEncMacroblockParameterBufferH264__bindgen_ty_1 {
+    pub intra_fields: _VAEncMacroblockParameterBufferH264__bindgen_ty_1__bindgen_ty_1,
+    pub inter_fields: _VAEncMacroblockParameterBufferH264__bindgen_ty_1__bindgen_ty_2,
+}
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub union _VAEncMacroblockParameterBufferH264__bindgen_ty_1__bindgen_ty_1 {
+    pub bits: _VAEncMacroblockParameterBufferH264__bindgen_ty_1__bindgen_ty_1__bindgen_ty_1,
     pub value: u32,
 }
 #[repr(C)]
 #[repr(align(4))]
 #[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
-pub struct _VAEncPictureParameterBufferMPEG2__bindgen_ty_2__bindgen_ty_1 {
+pub struct _VAEncMacroblockParameterBufferH264__bindgen_ty_1__bindgen_ty_1__bindgen_ty_1 {
     pub _bitfield_align_1: [u8; 0],
-    pub _bitfield_1: __BindgenBitfieldUnit<[u8; 3usize]>,
-    pub __bindgen_padding_0: u8,
+    pub _bitfield_1: __BindgenBitfieldUnit<[u8; 2usize]>,
+    pub __bindgen_padding_0: u16,
 }
-impl _VAEncPictureParameterBufferMPEG2__bindgen_ty_2__bindgen_ty_1 {
+impl _VAEncMacroblockParameterBufferH264__bindgen_ty_1__bindgen_ty_1__bindgen_ty_1 {
     #[inline]
-    pub fn v_axis(&self) -> u32 {
+    pub fn pred_avail_override_flag(&self) -> u32 {
         unsafe { ::std::mem::transmute(self._bitfield_1.get(0usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_v_axis(&mut self, val: u32) {
+    pub fn set_pred_avail_override_flag(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
             self._bitfield_1.set(0usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn field_sequence(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(1usize, 3u8) as u32) }
-    }
-    #[inline]
-    pub fn set_field_sequence(&mut self, val: u32) {
-        unsafe {
-            let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(1usize, 3u8, val as u64)
-        }
-    }
-    #[inline]
-    pub fn sub_carrier(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(4usize, 1u8) as u32) }
+    pub fn pred_avail_flags(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(1usize, 8u8) as u32) }
     }
     #[inline]
-    pub fn set_sub_carrier(&mut self, val: u32) {
+    pub fn set_pred_avail_flags(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(4usize, 1u8, val as u64)
+            self._bitfield_1.set(1usize, 8u8, val as u64)
         }
     }
     #[inline]
-    pub fn burst_amplitude(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(5usize, 7u8) as u32) }
+    pub fn new_bitfield_1(
+        pred_avail_override_flag: u32,
+        pred_avail_flags: u32,
+    ) -> __BindgenBitfieldUnit<[u8; 2usize]> {
+        let mut __bindgen_bitfield_unit: __BindgenBitfieldUnit<[u8; 2usize]> = Default::default();
+        __bindgen_bitfield_unit.set(0usize, 1u8, {
+            let pred_avail_override_flag: u32 =
+                unsafe { ::std::mem::transmute(pred_avail_override_flag) };
+            pred_avail_override_flag as u64
+        });
+        __bindgen_bitfield_unit.set(1usize, 8u8, {
+            let pred_avail_flags: u32 = unsafe { ::std::mem::transmute(pred_avail_flags) };
+            pred_avail_flags as u64
+        });
+        __bindgen_bitfield_unit
     }
-    #[inline]
-    pub fn set_burst_amplitude(&mut self, val: u32) {
+}
+impl Default for _VAEncMacroblockParameterBufferH264__bindgen_ty_1__bindgen_ty_1 {
+    fn default() -> Self {
+        let mut s = ::std::mem::MaybeUninit::<Self>::uninit();
         unsafe {
-            let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(5usize, 7u8, val as u64)
+            ::std::ptr::write_bytes(s.as_mut_ptr(), 0, 1);
+            s.assume_init()
         }
     }
-    #[inline]
-    pub fn sub_carrier_phase(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(12usize, 8u8) as u32) }
-    }
-    #[inline]
-    pub fn set_sub_carrier_phase(&mut self, val: u32) {
+}
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub union _VAEncMacroblockParameterBufferH264__bindgen_ty_1__bindgen_ty_2 {
+    pub bits: _VAEncMacroblockParameterBufferH264__bindgen_ty_1__bindgen_ty_2__bindgen_ty_1,
+    pub value: u32,
+}
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct _VAEncMacroblockParameterBufferH264__bindgen_ty_1__bindgen_ty_2__bindgen_ty_1 {
+    pub reserved: u32,
+}
+impl Default for _VAEncMacroblockParameterBufferH264__bindgen_ty_1__bindgen_ty_2 {
+    fn default() -> Self {
+        let mut s = ::std::mem::MaybeUninit::<Self>::uninit();
         unsafe {
-            let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(12usize, 8u8, val as u64)
+            ::std::ptr::write_bytes(s.as_mut_ptr(), 0, 1);
+            s.assume_init()
         }
     }
-    #[inline]
-    pub fn new_bitfield_1(
-        v_axis: u32,
-        field_sequence: u32,
-        sub_carrier: u32,
-        burst_amplitude: u32,
-        sub_carrier_phase: u32,
-    ) -> __BindgenBitfieldUnit<[u8; 3usize]> {
-        let mut __bindgen_bitfield_unit: __BindgenBitfieldUnit<[u8; 3usize]> = Default::default();
-        __bindgen_bitfield_unit.set(0usize, 1u8, {
-            let v_axis: u32 = unsafe { ::std::mem::transmute(v_axis) };
-            v_axis as u64
-        });
-        __bindgen_bitfield_unit.set(1usize, 3u8, {
-            let field_sequence: u32 = unsafe { ::std::mem::transmute(field_sequence) };
-            field_sequence as u64
-        });
-        __bindgen_bitfield_unit.set(4usize, 1u8, {
-            let sub_carrier: u32 = unsafe { ::std::mem::transmute(sub_carrier) };
-            sub_carrier as u64
-        });
-        __bindgen_bitfield_unit.set(5usize, 7u8, {
-            let burst_amplitude: u32 = unsafe { ::std::mem::transmute(burst_amplitude) };
-            burst_amplitude as u64
-        });
-        __bindgen_bitfield_unit.set(12usize, 8u8, {
-            let sub_carrier_phase: u32 = unsafe { ::std::mem::transmute(sub_carrier_phase) };
-            sub_carrier_phase as u64
-        });
-        __bindgen_bitfield_unit
-    }
 }
-impl Default for _VAEncPictureParameterBufferMPEG2__bindgen_ty_2 {
+impl Default for _VAEncMacroblockParameterBufferH264__bindgen_ty_1 {
     fn default() -> Self {
         let mut s = ::std::mem::MaybeUninit::<Self>::uninit();
         unsafe {
@@ -4401,7 +4658,7 @@ impl Default for _VAEncPictureParameterBufferMPEG2__bindgen_ty_2 {
         }
     }
 }
-impl Default for _VAEncPictureParameterBufferMPEG2 {
+impl Default for _VAEncMacroblockParameterBufferH264 {
     fn default() -> Self {
         let mut s = ::std::mem::MaybeUninit::<Self>::uninit();
         unsafe {
@@ -4410,216 +4667,183 @@ impl Default for _VAEncPictureParameterBufferMPEG2 {
         }
     }
 }
-pub type VAEncPictureParameterBufferMPEG2 = _VAEncPictureParameterBufferMPEG2;
-#[repr(C)]
-#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
-pub struct _VAEncSliceParameterBufferMPEG2 {
-    pub macroblock_address: u32,
-    pub num_macroblocks: u32,
-    pub quantiser_scale_code: i32,
-    pub is_intra_slice: i32,
-    pub va_reserved: [u32; 4usize],
-}
-pub type VAEncSliceParameterBufferMPEG2 = _VAEncSliceParameterBufferMPEG2;
-#[repr(C)]
-#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
-pub struct _VAEncMiscParameterExtensionDataSeqDisplayMPEG2 {
-    pub extension_start_code_identifier: u8,
-    pub video_format: u8,
-    pub colour_description: u8,
-    pub colour_primaries: u8,
-    pub transfer_characteristics: u8,
-    pub matrix_coefficients: u8,
-    pub display_horizontal_size: u16,
-    pub display_vertical_size: u16,
-}
-pub type VAEncMiscParameterExtensionDataSeqDisplayMPEG2 =
-    _VAEncMiscParameterExtensionDataSeqDisplayMPEG2;
-#[repr(C)]
-#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
-pub struct _VAEncSequenceParameterBufferVP8 {
-    pub frame_width: u32,
-    pub frame_height: u32,
-    pub frame_width_scale: u32,
-    pub frame_height_scale: u32,
-    pub error_resilient: u32,
-    pub kf_auto: u32,
-    pub kf_min_dist: u32,
-    pub kf_max_dist: u32,
-    pub bits_per_second: u32,
-    pub intra_period: u32,
-    pub reference_frames: [VASurfaceID; 4usize],
-    pub va_reserved: [u32; 4usize],
-}
-pub type VAEncSequenceParameterBufferVP8 = _VAEncSequenceParameterBufferVP8;
+pub type VAEncMacroblockParameterBufferH264 = _VAEncMacroblockParameterBufferH264;
 #[repr(C)]
 #[derive(Copy, Clone)]
-pub struct _VAEncPictureParameterBufferVP8 {
-    pub reconstructed_frame: VASurfaceID,
-    pub ref_last_frame: VASurfaceID,
-    pub ref_gf_frame: VASurfaceID,
-    pub ref_arf_frame: VASurfaceID,
-    pub coded_buf: VABufferID,
-    pub ref_flags: _VAEncPictureParameterBufferVP8__bindgen_ty_1,
-    pub pic_flags: _VAEncPictureParameterBufferVP8__bindgen_ty_2,
-    pub loop_filter_level: [i8; 4usize],
-    pub ref_lf_delta: [i8; 4usize],
-    pub mode_lf_delta: [i8; 4usize],
-    pub sharpness_level: u8,
-    pub clamp_qindex_high: u8,
-    pub clamp_qindex_low: u8,
-    pub va_reserved: [u32; 4usize],
+pub struct _VAEncMiscParameterSubMbPartPelH264 {
+    pub disable_inter_sub_mb_partition: u32,
+    pub inter_sub_mb_partition_mask: _VAEncMiscParameterSubMbPartPelH264__bindgen_ty_1,
+    pub enable_sub_pel_mode: u32,
+    pub sub_pel_mode: u8,
+    pub reserved: [u8; 3usize],
 }
 #[repr(C)]
 #[derive(Copy, Clone)]
-pub union _VAEncPictureParameterBufferVP8__bindgen_ty_1 {
-    pub bits: _VAEncPictureParameterBufferVP8__bindgen_ty_1__bindgen_ty_1,
-    pub value: u32,
+pub union _VAEncMiscParameterSubMbPartPelH264__bindgen_ty_1 {
+    pub bits: _VAEncMiscParameterSubMbPartPelH264__bindgen_ty_1__bindgen_ty_1,
+    pub value: u8,
 }
 #[repr(C)]
 #[repr(align(4))]
 #[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
-pub struct _VAEncPictureParameterBufferVP8__bindgen_ty_1__bindgen_ty_1 {
-    pub _bitfield_align_1: [u16; 0],
-    pub _bitfield_1: __BindgenBitfieldUnit<[u8; 4usize]>,
+pub struct _VAEncMiscParameterSubMbPartPelH264__bindgen_ty_1__bindgen_ty_1 {
+    pub _bitfield_align_1: [u8; 0],
+    pub _bitfield_1: __BindgenBitfieldUnit<[u8; 1usize]>,
+    pub __bindgen_padding_0: [u8; 3usize],
 }
-impl _VAEncPictureParameterBufferVP8__bindgen_ty_1__bindgen_ty_1 {
+impl _VAEncMiscParameterSubMbPartPelH264__bindgen_ty_1__bindgen_ty_1 {
     #[inline]
-    pub fn force_kf(&self) -> u32 {
+    pub fn disable_16x16_inter_mb_partition(&self) -> u32 {
         unsafe { ::std::mem::transmute(self._bitfield_1.get(0usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_force_kf(&mut self, val: u32) {
+    pub fn set_disable_16x16_inter_mb_partition(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
             self._bitfield_1.set(0usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn no_ref_last(&self) -> u32 {
+    pub fn disable_16x8_inter_mb_partition(&self) -> u32 {
         unsafe { ::std::mem::transmute(self._bitfield_1.get(1usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_no_ref_last(&mut self, val: u32) {
+    pub fn set_disable_16x8_inter_mb_partition(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
             self._bitfield_1.set(1usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn no_ref_gf(&self) -> u32 {
+    pub fn disable_8x16_inter_mb_partition(&self) -> u32 {
         unsafe { ::std::mem::transmute(self._bitfield_1.get(2usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_no_ref_gf(&mut self, val: u32) {
+    pub fn set_disable_8x16_inter_mb_partition(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
             self._bitfield_1.set(2usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn no_ref_arf(&self) -> u32 {
+    pub fn disable_8x8_inter_mb_partition(&self) -> u32 {
         unsafe { ::std::mem::transmute(self._bitfield_1.get(3usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_no_ref_arf(&mut self, val: u32) {
+    pub fn set_disable_8x8_inter_mb_partition(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
             self._bitfield_1.set(3usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn temporal_id(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(4usize, 8u8) as u32) }
+    pub fn disable_8x4_inter_mb_partition(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(4usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_temporal_id(&mut self, val: u32) {
+    pub fn set_disable_8x4_inter_mb_partition(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(4usize, 8u8, val as u64)
+            self._bitfield_1.set(4usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn first_ref(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(12usize, 2u8) as u32) }
+    pub fn disable_4x8_inter_mb_partition(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(5usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_first_ref(&mut self, val: u32) {
+    pub fn set_disable_4x8_inter_mb_partition(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(12usize, 2u8, val as u64)
+            self._bitfield_1.set(5usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn second_ref(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(14usize, 2u8) as u32) }
+    pub fn disable_4x4_inter_mb_partition(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(6usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_second_ref(&mut self, val: u32) {
+    pub fn set_disable_4x4_inter_mb_partition(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(14usize, 2u8, val as u64)
+            self._bitfield_1.set(6usize, 1u8, val as u64)
         }
     }
     #[inline]
     pub fn reserved(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(16usize, 16u8) as u32) }
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(7usize, 1u8) as u32) }
     }
     #[inline]
     pub fn set_reserved(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(16usize, 16u8, val as u64)
+            self._bitfield_1.set(7usize, 1u8, val as u64)
         }
     }
     #[inline]
     pub fn new_bitfield_1(
-        force_kf: u32,
-        no_ref_last: u32,
-        no_ref_gf: u32,
-        no_ref_arf: u32,
-        temporal_id: u32,
-        first_ref: u32,
-        second_ref: u32,
+        disable_16x16_inter_mb_partition: u32,
+        disable_16x8_inter_mb_partition: u32,
+        disable_8x16_inter_mb_partition: u32,
+        disable_8x8_inter_mb_partition: u32,
+        disable_8x4_inter_mb_partition: u32,
+        disable_4x8_inter_mb_partition: u32,
+        disable_4x4_inter_mb_partition: u32,
         reserved: u32,
-    ) -> __BindgenBitfieldUnit<[u8; 4usize]> {
-        let mut __bindgen_bitfield_unit: __BindgenBitfieldUnit<[u8; 4usize]> = Default::default();
+    ) -> __BindgenBitfieldUnit<[u8; 1usize]> {
+        let mut __bindgen_bitfield_unit: __BindgenBitfieldUnit<[u8; 1usize]> = Default::default();
         __bindgen_bitfield_unit.set(0usize, 1u8, {
-            let force_kf: u32 = unsafe { ::std::mem::transmute(force_kf) };
-            force_kf as u64
+            let disable_16x16_inter_mb_partition: u32 =
+                unsafe { ::std::mem::transmute(disable_16x16_inter_mb_partition) };
+            disable_16x16_inter_mb_partition as u64
         });
         __bindgen_bitfield_unit.set(1usize, 1u8, {
-            let no_ref_last: u32 = unsafe { ::std::mem::transmute(no_ref_last) };
-            no_ref_last as u64
+            let disable_16x8_inter_mb_partition: u32 =
+                unsafe { ::std::mem::transmute(disable_16x8_inter_mb_partition) };
+            disable_16x8_inter_mb_partition as u64
         });
         __bindgen_bitfield_unit.set(2usize, 1u8, {
-            let no_ref_gf: u32 = unsafe { ::std::mem::transmute(no_ref_gf) };
-            no_ref_gf as u64
+            let disable_8x16_inter_mb_partition: u32 =
+                unsafe { ::std::mem::transmute(disable_8x16_inter_mb_partition) };
+            disable_8x16_inter_mb_partition as u64
         });
         __bindgen_bitfield_unit.set(3usize, 1u8, {
-            let no_ref_arf: u32 = unsafe { ::std::mem::transmute(no_ref_arf) };
-            no_ref_arf as u64
+            let disable_8x8_inter_mb_partition: u32 =
+                unsafe { ::std::mem::transmute(disable_8x8_inter_mb_partition) };
+            disable_8x8_inter_mb_partition as u64
         });
-        __bindgen_bitfield_unit.set(4usize, 8u8, {
-            let temporal_id: u32 = unsafe { ::std::mem::transmute(temporal_id) };
-            temporal_id as u64
+        __bindgen_bitfield_unit.set(4usize, 1u8, {
+            let disable_8x4_inter_mb_partition: u32 =
+                unsafe { ::std::mem::transmute(disable_8x4_inter_mb_partition) };
+            disable_8x4_inter_mb_partition as u64
         });
-        __bindgen_bitfield_unit.set(12usize, 2u8, {
-            let first_ref: u32 = unsafe { ::std::mem::transmute(first_ref) };
-            first_ref as u64
+        __bindgen_bitfield_unit.set(5usize, 1u8, {
+            let disable_4x8_inter_mb_partition: u32 =
+                unsafe { ::std::mem::transmute(disable_4x8_inter_mb_partition) };
+            disable_4x8_inter_mb_partition as u64
         });
-        __bindgen_bitfield_unit.set(14usize, 2u8, {
-            let second_ref: u32 = unsafe { ::std::mem::transmute(second_ref) };
-            second_ref as u64
+        __bindgen_bitfield_unit.set(6usize, 1u8, {
+            let disable_4x4_inter_mb_partition: u32 =
+                unsafe { ::std::mem::transmute(disable_4x4_inter_mb_partition) };
+            disable_4x4_inter_mb_partition as u64
         });
-        __bindgen_bitfield_unit.set(16usize, 16u8, {
+        __bindgen_bitfield_unit.set(7usize, 1u8, {
             let reserved: u32 = unsafe { ::std::mem::transmute(reserved) };
             reserved as u64
         });
         __bindgen_bitfield_unit
     }
 }
-impl Default for _VAEncPictureParameterBufferVP8__bindgen_ty_1 {
+impl Default for _VAEncMiscParameterSubMbPartPelH264__bindgen_ty_1 {
+    fn default() -> Self {
+        let mut s = ::std::mem::MaybeUninit::<Self>::uninit();
+        unsafe {
+            ::std::ptr::write_bytes(s.as_mut_ptr(), 0, 1);
+            s.assume_init()
+        }
+    }
+}
+impl Default for _VAEncMiscParameterSubMbPartPelH264 {
     fn default() -> Self {
         let mut s = ::std::mem::MaybeUninit::<Self>::uninit();
         unsafe {
@@ -4628,419 +4852,465 @@ impl Default for _VAEncPictureParameterBufferVP8__bindgen_ty_1 {
         }
     }
 }
+pub type VAEncMiscParameterSubMbPartPelH264 = _VAEncMiscParameterSubMbPartPelH264;
+pub type VAQMatrixBufferMPEG2 = VAIQMatrixBufferMPEG2;
+pub mod VAEncPackedHeaderTypeMPEG2 {
+    pub type Type = ::std::os::raw::c_uint;
+    pub const VAEncPackedHeaderMPEG2_SPS: Type = 1;
+    pub const VAEncPackedHeaderMPEG2_PPS: Type = 2;
+    pub const VAEncPackedHeaderMPEG2_Slice: Type = 3;
+}
 #[repr(C)]
 #[derive(Copy, Clone)]
-pub union _VAEncPictureParameterBufferVP8__bindgen_ty_2 {
-    pub bits: _VAEncPictureParameterBufferVP8__bindgen_ty_2__bindgen_ty_1,
+pub struct _VAEncSequenceParameterBufferMPEG2 {
+    pub intra_period: u32,
+    pub ip_period: u32,
+    pub picture_width: u16,
+    pub picture_height: u16,
+    pub bits_per_second: u32,
+    pub frame_rate: f32,
+    pub aspect_ratio_information: u16,
+    pub vbv_buffer_size: u32,
+    pub sequence_extension: _VAEncSequenceParameterBufferMPEG2__bindgen_ty_1,
+    pub new_gop_header: u32,
+    pub gop_header: _VAEncSequenceParameterBufferMPEG2__bindgen_ty_2,
+    pub va_reserved: [u32; 4usize],
+}
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub union _VAEncSequenceParameterBufferMPEG2__bindgen_ty_1 {
+    pub bits: _VAEncSequenceParameterBufferMPEG2__bindgen_ty_1__bindgen_ty_1,
     pub value: u32,
 }
 #[repr(C)]
 #[repr(align(4))]
 #[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
-pub struct _VAEncPictureParameterBufferVP8__bindgen_ty_2__bindgen_ty_1 {
+pub struct _VAEncSequenceParameterBufferMPEG2__bindgen_ty_1__bindgen_ty_1 {
     pub _bitfield_align_1: [u8; 0],
-    pub _bitfield_1: __BindgenBitfieldUnit<[u8; 4usize]>,
+    pub _bitfield_1: __BindgenBitfieldUnit<[u8; 3usize]>,
+    pub __bindgen_padding_0: u8,
 }
-impl _VAEncPictureParameterBufferVP8__bindgen_ty_2__bindgen_ty_1 {
+impl _VAEncSequenceParameterBufferMPEG2__bindgen_ty_1__bindgen_ty_1 {
     #[inline]
-    pub fn frame_type(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(0usize, 1u8) as u32) }
+    pub fn profile_and_level_indication(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(0usize, 8u8) as u32) }
     }
     #[inline]
-    pub fn set_frame_type(&mut self, val: u32) {
+    pub fn set_profile_and_level_indication(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(0usize, 1u8, val as u64)
+            self._bitfield_1.set(0usize, 8u8, val as u64)
         }
     }
     #[inline]
-    pub fn version(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(1usize, 3u8) as u32) }
+    pub fn progressive_sequence(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(8usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_version(&mut self, val: u32) {
+    pub fn set_progressive_sequence(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(1usize, 3u8, val as u64)
+            self._bitfield_1.set(8usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn show_frame(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(4usize, 1u8) as u32) }
+    pub fn chroma_format(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(9usize, 2u8) as u32) }
     }
     #[inline]
-    pub fn set_show_frame(&mut self, val: u32) {
+    pub fn set_chroma_format(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(4usize, 1u8, val as u64)
+            self._bitfield_1.set(9usize, 2u8, val as u64)
         }
     }
     #[inline]
-    pub fn color_space(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(5usize, 1u8) as u32) }
+    pub fn low_delay(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(11usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_color_space(&mut self, val: u32) {
+    pub fn set_low_delay(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(5usize, 1u8, val as u64)
+            self._bitfield_1.set(11usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn recon_filter_type(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(6usize, 2u8) as u32) }
+    pub fn frame_rate_extension_n(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(12usize, 2u8) as u32) }
     }
     #[inline]
-    pub fn set_recon_filter_type(&mut self, val: u32) {
+    pub fn set_frame_rate_extension_n(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(6usize, 2u8, val as u64)
+            self._bitfield_1.set(12usize, 2u8, val as u64)
         }
     }
     #[inline]
-    pub fn loop_filter_type(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(8usize, 2u8) as u32) }
+    pub fn frame_rate_extension_d(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(14usize, 5u8) as u32) }
     }
     #[inline]
-    pub fn set_loop_filter_type(&mut self, val: u32) {
+    pub fn set_frame_rate_extension_d(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(8usize, 2u8, val as u64)
+            self._bitfield_1.set(14usize, 5u8, val as u64)
         }
     }
     #[inline]
-    pub fn auto_partitions(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(10usize, 1u8) as u32) }
+    pub fn new_bitfield_1(
+        profile_and_level_indication: u32,
+        progressive_sequence: u32,
+        chroma_format: u32,
+        low_delay: u32,
+        frame_rate_extension_n: u32,
+        frame_rate_extension_d: u32,
+    ) -> __BindgenBitfieldUnit<[u8; 3usize]> {
+        let mut __bindgen_bitfield_unit: __BindgenBitfieldUnit<[u8; 3usize]> = Default::default();
+        __bindgen_bitfield_unit.set(0usize, 8u8, {
+            let profile_and_level_indication: u32 =
+                unsafe { ::std::mem::transmute(profile_and_level_indication) };
+            profile_and_level_indication as u64
+        });
+        __bindgen_bitfield_unit.set(8usize, 1u8, {
+            let progressive_sequence: u32 = unsafe { ::std::mem::transmute(progressive_sequence) };
+            progressive_sequence as u64
+        });
+        __bindgen_bitfield_unit.set(9usize, 2u8, {
+            let chroma_format: u32 = unsafe { ::std::mem::transmute(chroma_format) };
+            chroma_format as u64
+        });
+        __bindgen_bitfield_unit.set(11usize, 1u8, {
+            let low_delay: u32 = unsafe { ::std::mem::transmute(low_delay) };
+            low_delay as u64
+        });
+        __bindgen_bitfield_unit.set(12usize, 2u8, {
+            let frame_rate_extension_n: u32 =
+                unsafe { ::std::mem::transmute(frame_rate_extension_n) };
+            frame_rate_extension_n as u64
+        });
+        __bindgen_bitfield_unit.set(14usize, 5u8, {
+            let frame_rate_extension_d: u32 =
+                unsafe { ::std::mem::transmute(frame_rate_extension_d) };
+            frame_rate_extension_d as u64
+        });
+        __bindgen_bitfield_unit
     }
-    #[inline]
-    pub fn set_auto_partitions(&mut self, val: u32) {
+}
+impl Default for _VAEncSequenceParameterBufferMPEG2__bindgen_ty_1 {
+    fn default() -> Self {
+        let mut s = ::std::mem::MaybeUninit::<Self>::uninit();
         unsafe {
-            let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(10usize, 1u8, val as u64)
+            ::std::ptr::write_bytes(s.as_mut_ptr(), 0, 1);
+            s.assume_init()
         }
     }
+}
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub union _VAEncSequenceParameterBufferMPEG2__bindgen_ty_2 {
+    pub bits: _VAEncSequenceParameterBufferMPEG2__bindgen_ty_2__bindgen_ty_1,
+    pub value: u32,
+}
+#[repr(C)]
+#[repr(align(4))]
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct _VAEncSequenceParameterBufferMPEG2__bindgen_ty_2__bindgen_ty_1 {
+    pub _bitfield_align_1: [u32; 0],
+    pub _bitfield_1: __BindgenBitfieldUnit<[u8; 4usize]>,
+}
+impl _VAEncSequenceParameterBufferMPEG2__bindgen_ty_2__bindgen_ty_1 {
     #[inline]
-    pub fn num_token_partitions(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(11usize, 2u8) as u32) }
+    pub fn time_code(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(0usize, 25u8) as u32) }
     }
     #[inline]
-    pub fn set_num_token_partitions(&mut self, val: u32) {
+    pub fn set_time_code(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(11usize, 2u8, val as u64)
+            self._bitfield_1.set(0usize, 25u8, val as u64)
         }
     }
     #[inline]
-    pub fn clamping_type(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(13usize, 1u8) as u32) }
+    pub fn closed_gop(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(25usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_clamping_type(&mut self, val: u32) {
+    pub fn set_closed_gop(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(13usize, 1u8, val as u64)
+            self._bitfield_1.set(25usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn segmentation_enabled(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(14usize, 1u8) as u32) }
+    pub fn broken_link(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(26usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_segmentation_enabled(&mut self, val: u32) {
+    pub fn set_broken_link(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(14usize, 1u8, val as u64)
+            self._bitfield_1.set(26usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn update_mb_segmentation_map(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(15usize, 1u8) as u32) }
+    pub fn new_bitfield_1(
+        time_code: u32,
+        closed_gop: u32,
+        broken_link: u32,
+    ) -> __BindgenBitfieldUnit<[u8; 4usize]> {
+        let mut __bindgen_bitfield_unit: __BindgenBitfieldUnit<[u8; 4usize]> = Default::default();
+        __bindgen_bitfield_unit.set(0usize, 25u8, {
+            let time_code: u32 = unsafe { ::std::mem::transmute(time_code) };
+            time_code as u64
+        });
+        __bindgen_bitfield_unit.set(25usize, 1u8, {
+            let closed_gop: u32 = unsafe { ::std::mem::transmute(closed_gop) };
+            closed_gop as u64
+        });
+        __bindgen_bitfield_unit.set(26usize, 1u8, {
+            let broken_link: u32 = unsafe { ::std::mem::transmute(broken_link) };
+            broken_link as u64
+        });
+        __bindgen_bitfield_unit
     }
-    #[inline]
-    pub fn set_update_mb_segmentation_map(&mut self, val: u32) {
+}
+impl Default for _VAEncSequenceParameterBufferMPEG2__bindgen_ty_2 {
+    fn default() -> Self {
+        let mut s = ::std::mem::MaybeUninit::<Self>::uninit();
         unsafe {
-            let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(15usize, 1u8, val as u64)
+            ::std::ptr::write_bytes(s.as_mut_ptr(), 0, 1);
+            s.assume_init()
+        }
+    }
+}
+impl Default for _VAEncSequenceParameterBufferMPEG2 {
+    fn default() -> Self {
+        let mut s = ::std::mem::MaybeUninit::<Self>::uninit();
+        unsafe {
+            ::std::ptr::write_bytes(s.as_mut_ptr(), 0, 1);
+            s.assume_init()
         }
     }
+}
+pub type VAEncSequenceParameterBufferMPEG2 = _VAEncSequenceParameterBufferMPEG2;
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct _VAEncPictureParameterBufferMPEG2 {
+    pub forward_reference_picture: VASurfaceID,
+    pub backward_reference_picture: VASurfaceID,
+    pub reconstructed_picture: VASurfaceID,
+    pub coded_buf: VABufferID,
+    pub last_picture: u8,
+    pub picture_type: VAEncPictureType::Type,
+    pub temporal_reference: u32,
+    pub vbv_delay: u32,
+    pub f_code: [[u8; 2usize]; 2usize],
+    pub picture_coding_extension: _VAEncPictureParameterBufferMPEG2__bindgen_ty_1,
+    pub composite_display: _VAEncPictureParameterBufferMPEG2__bindgen_ty_2,
+    pub va_reserved: [u32; 4usize],
+}
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub union _VAEncPictureParameterBufferMPEG2__bindgen_ty_1 {
+    pub bits: _VAEncPictureParameterBufferMPEG2__bindgen_ty_1__bindgen_ty_1,
+    pub value: u32,
+}
+#[repr(C)]
+#[repr(align(4))]
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct _VAEncPictureParameterBufferMPEG2__bindgen_ty_1__bindgen_ty_1 {
+    pub _bitfield_align_1: [u8; 0],
+    pub _bitfield_1: __BindgenBitfieldUnit<[u8; 2usize]>,
+    pub __bindgen_padding_0: u16,
+}
+impl _VAEncPictureParameterBufferMPEG2__bindgen_ty_1__bindgen_ty_1 {
     #[inline]
-    pub fn update_segment_feature_data(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(16usize, 1u8) as u32) }
+    pub fn intra_dc_precision(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(0usize, 2u8) as u32) }
     }
     #[inline]
-    pub fn set_update_segment_feature_data(&mut self, val: u32) {
+    pub fn set_intra_dc_precision(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(16usize, 1u8, val as u64)
+            self._bitfield_1.set(0usize, 2u8, val as u64)
         }
     }
     #[inline]
-    pub fn loop_filter_adj_enable(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(17usize, 1u8) as u32) }
+    pub fn picture_structure(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(2usize, 2u8) as u32) }
     }
     #[inline]
-    pub fn set_loop_filter_adj_enable(&mut self, val: u32) {
+    pub fn set_picture_structure(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(17usize, 1u8, val as u64)
+            self._bitfield_1.set(2usize, 2u8, val as u64)
         }
     }
     #[inline]
-    pub fn refresh_entropy_probs(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(18usize, 1u8) as u32) }
+    pub fn top_field_first(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(4usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_refresh_entropy_probs(&mut self, val: u32) {
+    pub fn set_top_field_first(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(18usize, 1u8, val as u64)
+            self._bitfield_1.set(4usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn refresh_golden_frame(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(19usize, 1u8) as u32) }
+    pub fn frame_pred_frame_dct(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(5usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_refresh_golden_frame(&mut self, val: u32) {
+    pub fn set_frame_pred_frame_dct(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(19usize, 1u8, val as u64)
+            self._bitfield_1.set(5usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn refresh_alternate_frame(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(20usize, 1u8) as u32) }
+    pub fn concealment_motion_vectors(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(6usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_refresh_alternate_frame(&mut self, val: u32) {
+    pub fn set_concealment_motion_vectors(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(20usize, 1u8, val as u64)
+            self._bitfield_1.set(6usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn refresh_last(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(21usize, 1u8) as u32) }
+    pub fn q_scale_type(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(7usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_refresh_last(&mut self, val: u32) {
+    pub fn set_q_scale_type(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(21usize, 1u8, val as u64)
+            self._bitfield_1.set(7usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn copy_buffer_to_golden(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(22usize, 2u8) as u32) }
+    pub fn intra_vlc_format(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(8usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_copy_buffer_to_golden(&mut self, val: u32) {
+    pub fn set_intra_vlc_format(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(22usize, 2u8, val as u64)
+            self._bitfield_1.set(8usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn copy_buffer_to_alternate(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(24usize, 2u8) as u32) }
+    pub fn alternate_scan(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(9usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_copy_buffer_to_alternate(&mut self, val: u32) {
+    pub fn set_alternate_scan(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(24usize, 2u8, val as u64)
+            self._bitfield_1.set(9usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn sign_bias_golden(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(26usize, 1u8) as u32) }
+    pub fn repeat_first_field(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(10usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_sign_bias_golden(&mut self, val: u32) {
+    pub fn set_repeat_first_field(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(26usize, 1u8, val as u64)
+            self._bitfield_1.set(10usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn sign_bias_alternate(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(27usize, 1u8) as u32) }
-    }
-    #[inline]
-    pub fn set_sign_bias_alternate(&mut self, val: u32) {
-        unsafe {
-            let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(27usize, 1u8, val as u64)
-        }
-    }
-    #[inline]
-    pub fn mb_no_coeff_skip(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(28usize, 1u8) as u32) }
-    }
-    #[inline]
-    pub fn set_mb_no_coeff_skip(&mut self, val: u32) {
-        unsafe {
-            let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(28usize, 1u8, val as u64)
-        }
-    }
-    #[inline]
-    pub fn forced_lf_adjustment(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(29usize, 1u8) as u32) }
+    pub fn progressive_frame(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(11usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_forced_lf_adjustment(&mut self, val: u32) {
+    pub fn set_progressive_frame(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(29usize, 1u8, val as u64)
+            self._bitfield_1.set(11usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn reserved(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(30usize, 2u8) as u32) }
+    pub fn composite_display_flag(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(12usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_reserved(&mut self, val: u32) {
+    pub fn set_composite_display_flag(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(30usize, 2u8, val as u64)
+            self._bitfield_1.set(12usize, 1u8, val as u64)
         }
     }
     #[inline]
     pub fn new_bitfield_1(
-        frame_type: u32,
-        version: u32,
-        show_frame: u32,
-        color_space: u32,
-        recon_filter_type: u32,
-        loop_filter_type: u32,
-        auto_partitions: u32,
-        num_token_partitions: u32,
-        clamping_type: u32,
-        segmentation_enabled: u32,
-        update_mb_segmentation_map: u32,
-        update_segment_feature_data: u32,
-        loop_filter_adj_enable: u32,
-        refresh_entropy_probs: u32,
-        refresh_golden_frame: u32,
-        refresh_alternate_frame: u32,
-        refresh_last: u32,
-        copy_buffer_to_golden: u32,
-        copy_buffer_to_alternate: u32,
-        sign_bias_golden: u32,
-        sign_bias_alternate: u32,
-        mb_no_coeff_skip: u32,
-        forced_lf_adjustment: u32,
-        reserved: u32,
-    ) -> __BindgenBitfieldUnit<[u8; 4usize]> {
-        let mut __bindgen_bitfield_unit: __BindgenBitfieldUnit<[u8; 4usize]> = Default::default();
-        __bindgen_bitfield_unit.set(0usize, 1u8, {
-            let frame_type: u32 = unsafe { ::std::mem::transmute(frame_type) };
-            frame_type as u64
+        intra_dc_precision: u32,
+        picture_structure: u32,
+        top_field_first: u32,
+        frame_pred_frame_dct: u32,
+        concealment_motion_vectors: u32,
+        q_scale_type: u32,
+        intra_vlc_format: u32,
+        alternate_scan: u32,
+        repeat_first_field: u32,
+        progressive_frame: u32,
+        composite_display_flag: u32,
+    ) -> __BindgenBitfieldUnit<[u8; 2usize]> {
+        let mut __bindgen_bitfield_unit: __BindgenBitfieldUnit<[u8; 2usize]> = Default::default();
+        __bindgen_bitfield_unit.set(0usize, 2u8, {
+            let intra_dc_precision: u32 = unsafe { ::std::mem::transmute(intra_dc_precision) };
+            intra_dc_precision as u64
         });
-        __bindgen_bitfield_unit.set(1usize, 3u8, {
-            let version: u32 = unsafe { ::std::mem::transmute(version) };
-            version as u64
+        __bindgen_bitfield_unit.set(2usize, 2u8, {
+            let picture_structure: u32 = unsafe { ::std::mem::transmute(picture_structure) };
+            picture_structure as u64
         });
         __bindgen_bitfield_unit.set(4usize, 1u8, {
-            let show_frame: u32 = unsafe { ::std::mem::transmute(show_frame) };
-            show_frame as u64
+            let top_field_first: u32 = unsafe { ::std::mem::transmute(top_field_first) };
+            top_field_first as u64
         });
         __bindgen_bitfield_unit.set(5usize, 1u8, {
-            let color_space: u32 = unsafe { ::std::mem::transmute(color_space) };
-            color_space as u64
-        });
-        __bindgen_bitfield_unit.set(6usize, 2u8, {
-            let recon_filter_type: u32 = unsafe { ::std::mem::transmute(recon_filter_type) };
-            recon_filter_type as u64
-        });
-        __bindgen_bitfield_unit.set(8usize, 2u8, {
-            let loop_filter_type: u32 = unsafe { ::std::mem::transmute(loop_filter_type) };
-            loop_filter_type as u64
-        });
-        __bindgen_bitfield_unit.set(10usize, 1u8, {
-            let auto_partitions: u32 = unsafe { ::std::mem::transmute(auto_partitions) };
-            auto_partitions as u64
-        });
-        __bindgen_bitfield_unit.set(11usize, 2u8, {
-            let num_token_partitions: u32 = unsafe { ::std::mem::transmute(num_token_partitions) };
-            num_token_partitions as u64
-        });
-        __bindgen_bitfield_unit.set(13usize, 1u8, {
-            let clamping_type: u32 = unsafe { ::std::mem::transmute(clamping_type) };
-            clamping_type as u64
-        });
-        __bindgen_bitfield_unit.set(14usize, 1u8, {
-            let segmentation_enabled: u32 = unsafe { ::std::mem::transmute(segmentation_enabled) };
-            segmentation_enabled as u64
-        });
-        __bindgen_bitfield_unit.set(15usize, 1u8, {
-            let update_mb_segmentation_map: u32 =
-                unsafe { ::std::mem::transmute(update_mb_segmentation_map) };
-            update_mb_segmentation_map as u64
-        });
-        __bindgen_bitfield_unit.set(16usize, 1u8, {
-            let update_segment_feature_data: u32 =
-                unsafe { ::std::mem::transmute(update_segment_feature_data) };
-            update_segment_feature_data as u64
-        });
-        __bindgen_bitfield_unit.set(17usize, 1u8, {
-            let loop_filter_adj_enable: u32 =
-                unsafe { ::std::mem::transmute(loop_filter_adj_enable) };
-            loop_filter_adj_enable as u64
-        });
-        __bindgen_bitfield_unit.set(18usize, 1u8, {
-            let refresh_entropy_probs: u32 =
-                unsafe { ::std::mem::transmute(refresh_entropy_probs) };
-            refresh_entropy_probs as u64
-        });
-        __bindgen_bitfield_unit.set(19usize, 1u8, {
-            let refresh_golden_frame: u32 = unsafe { ::std::mem::transmute(refresh_golden_frame) };
-            refresh_golden_frame as u64
-        });
-        __bindgen_bitfield_unit.set(20usize, 1u8, {
-            let refresh_alternate_frame: u32 =
-                unsafe { ::std::mem::transmute(refresh_alternate_frame) };
-            refresh_alternate_frame as u64
-        });
-        __bindgen_bitfield_unit.set(21usize, 1u8, {
-            let refresh_last: u32 = unsafe { ::std::mem::transmute(refresh_last) };
-            refresh_last as u64
+            let frame_pred_frame_dct: u32 = unsafe { ::std::mem::transmute(frame_pred_frame_dct) };
+            frame_pred_frame_dct as u64
         });
-        __bindgen_bitfield_unit.set(22usize, 2u8, {
-            let copy_buffer_to_golden: u32 =
-                unsafe { ::std::mem::transmute(copy_buffer_to_golden) };
-            copy_buffer_to_golden as u64
+        __bindgen_bitfield_unit.set(6usize, 1u8, {
+            let concealment_motion_vectors: u32 =
+                unsafe { ::std::mem::transmute(concealment_motion_vectors) };
+            concealment_motion_vectors as u64
         });
-        __bindgen_bitfield_unit.set(24usize, 2u8, {
-            let copy_buffer_to_alternate: u32 =
-                unsafe { ::std::mem::transmute(copy_buffer_to_alternate) };
-            copy_buffer_to_alternate as u64
+        __bindgen_bitfield_unit.set(7usize, 1u8, {
+            let q_scale_type: u32 = unsafe { ::std::mem::transmute(q_scale_type) };
+            q_scale_type as u64
         });
-        __bindgen_bitfield_unit.set(26usize, 1u8, {
-            let sign_bias_golden: u32 = unsafe { ::std::mem::transmute(sign_bias_golden) };
-            sign_bias_golden as u64
+        __bindgen_bitfield_unit.set(8usize, 1u8, {
+            let intra_vlc_format: u32 = unsafe { ::std::mem::transmute(intra_vlc_format) };
+            intra_vlc_format as u64
         });
-        __bindgen_bitfield_unit.set(27usize, 1u8, {
-            let sign_bias_alternate: u32 = unsafe { ::std::mem::transmute(sign_bias_alternate) };
-            sign_bias_alternate as u64
+        __bindgen_bitfield_unit.set(9usize, 1u8, {
+            let alternate_scan: u32 = unsafe { ::std::mem::transmute(alternate_scan) };
+            alternate_scan as u64
         });
-        __bindgen_bitfield_unit.set(28usize, 1u8, {
-            let mb_no_coeff_skip: u32 = unsafe { ::std::mem::transmute(mb_no_coeff_skip) };
-            mb_no_coeff_skip as u64
+        __bindgen_bitfield_unit.set(10usize, 1u8, {
+            let repeat_first_field: u32 = unsafe { ::std::mem::transmute(repeat_first_field) };
+            repeat_first_field as u64
         });
-        __bindgen_bitfield_unit.set(29usize, 1u8, {
-            let forced_lf_adjustment: u32 = unsafe { ::std::mem::transmute(forced_lf_adjustment) };
-            forced_lf_adjustment as u64
+        __bindgen_bitfield_unit.set(11usize, 1u8, {
+            let progressive_frame: u32 = unsafe { ::std::mem::transmute(progressive_frame) };
+            progressive_frame as u64
         });
-        __bindgen_bitfield_unit.set(30usize, 2u8, {
-            let reserved: u32 = unsafe { ::std::mem::transmute(reserved) };
-            reserved as u64
+        __bindgen_bitfield_unit.set(12usize, 1u8, {
+            let composite_display_flag: u32 =
+                unsafe { ::std::mem::transmute(composite_display_flag) };
+            composite_display_flag as u64
         });
         __bindgen_bitfield_unit
     }
 }
-impl Default for _VAEncPictureParameterBufferVP8__bindgen_ty_2 {
+impl Default for _VAEncPictureParameterBufferMPEG2__bindgen_ty_1 {
     fn default() -> Self {
         let mut s = ::std::mem::MaybeUninit::<Self>::uninit();
         unsafe {
@@ -5049,114 +5319,200 @@ impl Default for _VAEncPictureParameterBufferVP8__bindgen_ty_2 {
         }
     }
 }
-impl Default for _VAEncPictureParameterBufferVP8 {
-    fn default() -> Self {
-        let mut s = ::std::mem::MaybeUninit::<Self>::uninit();
-        unsafe {
-            ::std::ptr::write_bytes(s.as_mut_ptr(), 0, 1);
-            s.assume_init()
-        }
-    }
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub union _VAEncPictureParameterBufferMPEG2__bindgen_ty_2 {
+    pub bits: _VAEncPictureParameterBufferMPEG2__bindgen_ty_2__bindgen_ty_1,
+    pub value: u32,
 }
-pub type VAEncPictureParameterBufferVP8 = _VAEncPictureParameterBufferVP8;
 #[repr(C)]
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
-pub struct _VAEncMBMapBufferVP8 {
-    pub num_mbs: u32,
-    pub mb_segment_id: *mut u8,
-    pub va_reserved: [u32; 4usize],
+#[repr(align(4))]
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct _VAEncPictureParameterBufferMPEG2__bindgen_ty_2__bindgen_ty_1 {
+    pub _bitfield_align_1: [u8; 0],
+    pub _bitfield_1: __BindgenBitfieldUnit<[u8; 3usize]>,
+    pub __bindgen_padding_0: u8,
 }
-impl Default for _VAEncMBMapBufferVP8 {
-    fn default() -> Self {
-        let mut s = ::std::mem::MaybeUninit::<Self>::uninit();
+impl _VAEncPictureParameterBufferMPEG2__bindgen_ty_2__bindgen_ty_1 {
+    #[inline]
+    pub fn v_axis(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(0usize, 1u8) as u32) }
+    }
+    #[inline]
+    pub fn set_v_axis(&mut self, val: u32) {
         unsafe {
-            ::std::ptr::write_bytes(s.as_mut_ptr(), 0, 1);
-            s.assume_init()
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(0usize, 1u8, val as u64)
         }
     }
-}
-pub type VAEncMBMapBufferVP8 = _VAEncMBMapBufferVP8;
-#[repr(C)]
-#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
-pub struct _VAQMatrixBufferVP8 {
-    pub quantization_index: [u16; 4usize],
-    pub quantization_index_delta: [i16; 5usize],
-    pub va_reserved: [u32; 4usize],
-}
-pub type VAQMatrixBufferVP8 = _VAQMatrixBufferVP8;
-#[repr(C)]
-#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
-pub struct _VACodedBufferVP9Status {
-    pub base_qp_index: u16,
-    pub loop_filter_level: u8,
-    pub long_term_indication: u8,
-    pub next_frame_width: u16,
-    pub next_frame_height: u16,
+    #[inline]
+    pub fn field_sequence(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(1usize, 3u8) as u32) }
+    }
+    #[inline]
+    pub fn set_field_sequence(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(1usize, 3u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn sub_carrier(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(4usize, 1u8) as u32) }
+    }
+    #[inline]
+    pub fn set_sub_carrier(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(4usize, 1u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn burst_amplitude(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(5usize, 7u8) as u32) }
+    }
+    #[inline]
+    pub fn set_burst_amplitude(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(5usize, 7u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn sub_carrier_phase(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(12usize, 8u8) as u32) }
+    }
+    #[inline]
+    pub fn set_sub_carrier_phase(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(12usize, 8u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn new_bitfield_1(
+        v_axis: u32,
+        field_sequence: u32,
+        sub_carrier: u32,
+        burst_amplitude: u32,
+        sub_carrier_phase: u32,
+    ) -> __BindgenBitfieldUnit<[u8; 3usize]> {
+        let mut __bindgen_bitfield_unit: __BindgenBitfieldUnit<[u8; 3usize]> = Default::default();
+        __bindgen_bitfield_unit.set(0usize, 1u8, {
+            let v_axis: u32 = unsafe { ::std::mem::transmute(v_axis) };
+            v_axis as u64
+        });
+        __bindgen_bitfield_unit.set(1usize, 3u8, {
+            let field_sequence: u32 = unsafe { ::std::mem::transmute(field_sequence) };
+            field_sequence as u64
+        });
+        __bindgen_bitfield_unit.set(4usize, 1u8, {
+            let sub_carrier: u32 = unsafe { ::std::mem::transmute(sub_carrier) };
+            sub_carrier as u64
+        });
+        __bindgen_bitfield_unit.set(5usize, 7u8, {
+            let burst_amplitude: u32 = unsafe { ::std::mem::transmute(burst_amplitude) };
+            burst_amplitude as u64
+        });
+        __bindgen_bitfield_unit.set(12usize, 8u8, {
+            let sub_carrier_phase: u32 = unsafe { ::std::mem::transmute(sub_carrier_phase) };
+            sub_carrier_phase as u64
+        });
+        __bindgen_bitfield_unit
+    }
+}
+impl Default for _VAEncPictureParameterBufferMPEG2__bindgen_ty_2 {
+    fn default() -> Self {
+        let mut s = ::std::mem::MaybeUninit::<Self>::uninit();
+        unsafe {
+            ::std::ptr::write_bytes(s.as_mut_ptr(), 0, 1);
+            s.assume_init()
+        }
+    }
+}
+impl Default for _VAEncPictureParameterBufferMPEG2 {
+    fn default() -> Self {
+        let mut s = ::std::mem::MaybeUninit::<Self>::uninit();
+        unsafe {
+            ::std::ptr::write_bytes(s.as_mut_ptr(), 0, 1);
+            s.assume_init()
+        }
+    }
+}
+pub type VAEncPictureParameterBufferMPEG2 = _VAEncPictureParameterBufferMPEG2;
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct _VAEncSliceParameterBufferMPEG2 {
+    pub macroblock_address: u32,
+    pub num_macroblocks: u32,
+    pub quantiser_scale_code: i32,
+    pub is_intra_slice: i32,
     pub va_reserved: [u32; 4usize],
 }
-pub type VACodedBufferVP9Status = _VACodedBufferVP9Status;
+pub type VAEncSliceParameterBufferMPEG2 = _VAEncSliceParameterBufferMPEG2;
 #[repr(C)]
 #[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
-pub struct _VAEncSequenceParameterBufferVP9 {
-    pub max_frame_width: u32,
-    pub max_frame_height: u32,
+pub struct _VAEncMiscParameterExtensionDataSeqDisplayMPEG2 {
+    pub extension_start_code_identifier: u8,
+    pub video_format: u8,
+    pub colour_description: u8,
+    pub colour_primaries: u8,
+    pub transfer_characteristics: u8,
+    pub matrix_coefficients: u8,
+    pub display_horizontal_size: u16,
+    pub display_vertical_size: u16,
+}
+pub type VAEncMiscParameterExtensionDataSeqDisplayMPEG2 =
+    _VAEncMiscParameterExtensionDataSeqDisplayMPEG2;
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct _VAEncSequenceParameterBufferVP8 {
+    pub frame_width: u32,
+    pub frame_height: u32,
+    pub frame_width_scale: u32,
+    pub frame_height_scale: u32,
+    pub error_resilient: u32,
     pub kf_auto: u32,
     pub kf_min_dist: u32,
     pub kf_max_dist: u32,
     pub bits_per_second: u32,
     pub intra_period: u32,
+    pub reference_frames: [VASurfaceID; 4usize],
     pub va_reserved: [u32; 4usize],
 }
-pub type VAEncSequenceParameterBufferVP9 = _VAEncSequenceParameterBufferVP9;
+pub type VAEncSequenceParameterBufferVP8 = _VAEncSequenceParameterBufferVP8;
 #[repr(C)]
 #[derive(Copy, Clone)]
-pub struct _VAEncPictureParameterBufferVP9 {
-    pub frame_width_src: u32,
-    pub frame_height_src: u32,
-    pub frame_width_dst: u32,
-    pub frame_height_dst: u32,
+pub struct _VAEncPictureParameterBufferVP8 {
     pub reconstructed_frame: VASurfaceID,
-    pub reference_frames: [VASurfaceID; 8usize],
+    pub ref_last_frame: VASurfaceID,
+    pub ref_gf_frame: VASurfaceID,
+    pub ref_arf_frame: VASurfaceID,
     pub coded_buf: VABufferID,
-    pub ref_flags: _VAEncPictureParameterBufferVP9__bindgen_ty_1,
-    pub pic_flags: _VAEncPictureParameterBufferVP9__bindgen_ty_2,
-    pub refresh_frame_flags: u8,
-    pub luma_ac_qindex: u8,
-    pub luma_dc_qindex_delta: i8,
-    pub chroma_ac_qindex_delta: i8,
-    pub chroma_dc_qindex_delta: i8,
-    pub filter_level: u8,
-    pub sharpness_level: u8,
+    pub ref_flags: _VAEncPictureParameterBufferVP8__bindgen_ty_1,
+    pub pic_flags: _VAEncPictureParameterBufferVP8__bindgen_ty_2,
+    pub loop_filter_level: [i8; 4usize],
     pub ref_lf_delta: [i8; 4usize],
-    pub mode_lf_delta: [i8; 2usize],
-    pub bit_offset_ref_lf_delta: u16,
-    pub bit_offset_mode_lf_delta: u16,
-    pub bit_offset_lf_level: u16,
-    pub bit_offset_qindex: u16,
-    pub bit_offset_first_partition_size: u16,
-    pub bit_offset_segmentation: u16,
-    pub bit_size_segmentation: u16,
-    pub log2_tile_rows: u8,
-    pub log2_tile_columns: u8,
-    pub skip_frame_flag: u8,
-    pub number_skip_frames: u8,
-    pub skip_frames_size: u32,
-    pub va_reserved: [u32; 8usize],
+    pub mode_lf_delta: [i8; 4usize],
+    pub sharpness_level: u8,
+    pub clamp_qindex_high: u8,
+    pub clamp_qindex_low: u8,
+    pub va_reserved: [u32; 4usize],
 }
 #[repr(C)]
 #[derive(Copy, Clone)]
-pub union _VAEncPictureParameterBufferVP9__bindgen_ty_1 {
-    pub bits: _VAEncPictureParameterBufferVP9__bindgen_ty_1__bindgen_ty_1,
+pub union _VAEncPictureParameterBufferVP8__bindgen_ty_1 {
+    pub bits: _VAEncPictureParameterBufferVP8__bindgen_ty_1__bindgen_ty_1,
     pub value: u32,
 }
 #[repr(C)]
 #[repr(align(4))]
 #[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
-pub struct _VAEncPictureParameterBufferVP9__bindgen_ty_1__bindgen_ty_1 {
-    pub _bitfield_align_1: [u8; 0],
+pub struct _VAEncPictureParameterBufferVP8__bindgen_ty_1__bindgen_ty_1 {
+    pub _bitfield_align_1: [u16; 0],
     pub _bitfield_1: __BindgenBitfieldUnit<[u8; 4usize]>,
 }
-impl _VAEncPictureParameterBufferVP9__bindgen_ty_1__bindgen_ty_1 {
+impl _VAEncPictureParameterBufferVP8__bindgen_ty_1__bindgen_ty_1 {
     #[inline]
     pub fn force_kf(&self) -> u32 {
         unsafe { ::std::mem::transmute(self._bitfield_1.get(0usize, 1u8) as u32) }
@@ -5169,1684 +5525,3620 @@ impl _VAEncPictureParameterBufferVP9__bindgen_ty_1__bindgen_ty_1 {
         }
     }
     #[inline]
-    pub fn ref_frame_ctrl_l0(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(1usize, 3u8) as u32) }
+    pub fn no_ref_last(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(1usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_ref_frame_ctrl_l0(&mut self, val: u32) {
+    pub fn set_no_ref_last(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(1usize, 3u8, val as u64)
+            self._bitfield_1.set(1usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn ref_frame_ctrl_l1(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(4usize, 3u8) as u32) }
+    pub fn no_ref_gf(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(2usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_ref_frame_ctrl_l1(&mut self, val: u32) {
+    pub fn set_no_ref_gf(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(4usize, 3u8, val as u64)
+            self._bitfield_1.set(2usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn ref_last_idx(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(7usize, 3u8) as u32) }
+    pub fn no_ref_arf(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(3usize, 1u8) as u32) }
+    }
+    #[inline]
+    pub fn set_no_ref_arf(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(3usize, 1u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn temporal_id(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(4usize, 8u8) as u32) }
+    }
+    #[inline]
+    pub fn set_temporal_id(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(4usize, 8u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn first_ref(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(12usize, 2u8) as u32) }
+    }
+    #[inline]
+    pub fn set_first_ref(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(12usize, 2u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn second_ref(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(14usize, 2u8) as u32) }
+    }
+    #[inline]
+    pub fn set_second_ref(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(14usize, 2u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn reserved(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(16usize, 16u8) as u32) }
+    }
+    #[inline]
+    pub fn set_reserved(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(16usize, 16u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn new_bitfield_1(
+        force_kf: u32,
+        no_ref_last: u32,
+        no_ref_gf: u32,
+        no_ref_arf: u32,
+        temporal_id: u32,
+        first_ref: u32,
+        second_ref: u32,
+        reserved: u32,
+    ) -> __BindgenBitfieldUnit<[u8; 4usize]> {
+        let mut __bindgen_bitfield_unit: __BindgenBitfieldUnit<[u8; 4usize]> = Default::default();
+        __bindgen_bitfield_unit.set(0usize, 1u8, {
+            let force_kf: u32 = unsafe { ::std::mem::transmute(force_kf) };
+            force_kf as u64
+        });
+        __bindgen_bitfield_unit.set(1usize, 1u8, {
+            let no_ref_last: u32 = unsafe { ::std::mem::transmute(no_ref_last) };
+            no_ref_last as u64
+        });
+        __bindgen_bitfield_unit.set(2usize, 1u8, {
+            let no_ref_gf: u32 = unsafe { ::std::mem::transmute(no_ref_gf) };
+            no_ref_gf as u64
+        });
+        __bindgen_bitfield_unit.set(3usize, 1u8, {
+            let no_ref_arf: u32 = unsafe { ::std::mem::transmute(no_ref_arf) };
+            no_ref_arf as u64
+        });
+        __bindgen_bitfield_unit.set(4usize, 8u8, {
+            let temporal_id: u32 = unsafe { ::std::mem::transmute(temporal_id) };
+            temporal_id as u64
+        });
+        __bindgen_bitfield_unit.set(12usize, 2u8, {
+            let first_ref: u32 = unsafe { ::std::mem::transmute(first_ref) };
+            first_ref as u64
+        });
+        __bindgen_bitfield_unit.set(14usize, 2u8, {
+            let second_ref: u32 = unsafe { ::std::mem::transmute(second_ref) };
+            second_ref as u64
+        });
+        __bindgen_bitfield_unit.set(16usize, 16u8, {
+            let reserved: u32 = unsafe { ::std::mem::transmute(reserved) };
+            reserved as u64
+        });
+        __bindgen_bitfield_unit
+    }
+}
+impl Default for _VAEncPictureParameterBufferVP8__bindgen_ty_1 {
+    fn default() -> Self {
+        let mut s = ::std::mem::MaybeUninit::<Self>::uninit();
+        unsafe {
+            ::std::ptr::write_bytes(s.as_mut_ptr(), 0, 1);
+            s.assume_init()
+        }
+    }
+}
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub union _VAEncPictureParameterBufferVP8__bindgen_ty_2 {
+    pub bits: _VAEncPictureParameterBufferVP8__bindgen_ty_2__bindgen_ty_1,
+    pub value: u32,
+}
+#[repr(C)]
+#[repr(align(4))]
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct _VAEncPictureParameterBufferVP8__bindgen_ty_2__bindgen_ty_1 {
+    pub _bitfield_align_1: [u8; 0],
+    pub _bitfield_1: __BindgenBitfieldUnit<[u8; 4usize]>,
+}
+impl _VAEncPictureParameterBufferVP8__bindgen_ty_2__bindgen_ty_1 {
+    #[inline]
+    pub fn frame_type(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(0usize, 1u8) as u32) }
+    }
+    #[inline]
+    pub fn set_frame_type(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(0usize, 1u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn version(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(1usize, 3u8) as u32) }
+    }
+    #[inline]
+    pub fn set_version(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(1usize, 3u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn show_frame(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(4usize, 1u8) as u32) }
+    }
+    #[inline]
+    pub fn set_show_frame(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(4usize, 1u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn color_space(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(5usize, 1u8) as u32) }
+    }
+    #[inline]
+    pub fn set_color_space(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(5usize, 1u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn recon_filter_type(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(6usize, 2u8) as u32) }
+    }
+    #[inline]
+    pub fn set_recon_filter_type(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(6usize, 2u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn loop_filter_type(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(8usize, 2u8) as u32) }
+    }
+    #[inline]
+    pub fn set_loop_filter_type(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(8usize, 2u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn auto_partitions(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(10usize, 1u8) as u32) }
+    }
+    #[inline]
+    pub fn set_auto_partitions(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(10usize, 1u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn num_token_partitions(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(11usize, 2u8) as u32) }
+    }
+    #[inline]
+    pub fn set_num_token_partitions(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(11usize, 2u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn clamping_type(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(13usize, 1u8) as u32) }
+    }
+    #[inline]
+    pub fn set_clamping_type(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(13usize, 1u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn segmentation_enabled(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(14usize, 1u8) as u32) }
+    }
+    #[inline]
+    pub fn set_segmentation_enabled(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(14usize, 1u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn update_mb_segmentation_map(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(15usize, 1u8) as u32) }
+    }
+    #[inline]
+    pub fn set_update_mb_segmentation_map(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(15usize, 1u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn update_segment_feature_data(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(16usize, 1u8) as u32) }
+    }
+    #[inline]
+    pub fn set_update_segment_feature_data(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(16usize, 1u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn loop_filter_adj_enable(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(17usize, 1u8) as u32) }
+    }
+    #[inline]
+    pub fn set_loop_filter_adj_enable(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(17usize, 1u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn refresh_entropy_probs(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(18usize, 1u8) as u32) }
+    }
+    #[inline]
+    pub fn set_refresh_entropy_probs(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(18usize, 1u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn refresh_golden_frame(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(19usize, 1u8) as u32) }
+    }
+    #[inline]
+    pub fn set_refresh_golden_frame(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(19usize, 1u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn refresh_alternate_frame(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(20usize, 1u8) as u32) }
+    }
+    #[inline]
+    pub fn set_refresh_alternate_frame(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(20usize, 1u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn refresh_last(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(21usize, 1u8) as u32) }
+    }
+    #[inline]
+    pub fn set_refresh_last(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(21usize, 1u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn copy_buffer_to_golden(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(22usize, 2u8) as u32) }
+    }
+    #[inline]
+    pub fn set_copy_buffer_to_golden(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(22usize, 2u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn copy_buffer_to_alternate(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(24usize, 2u8) as u32) }
+    }
+    #[inline]
+    pub fn set_copy_buffer_to_alternate(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(24usize, 2u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn sign_bias_golden(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(26usize, 1u8) as u32) }
+    }
+    #[inline]
+    pub fn set_sign_bias_golden(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(26usize, 1u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn sign_bias_alternate(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(27usize, 1u8) as u32) }
+    }
+    #[inline]
+    pub fn set_sign_bias_alternate(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(27usize, 1u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn mb_no_coeff_skip(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(28usize, 1u8) as u32) }
+    }
+    #[inline]
+    pub fn set_mb_no_coeff_skip(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(28usize, 1u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn forced_lf_adjustment(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(29usize, 1u8) as u32) }
+    }
+    #[inline]
+    pub fn set_forced_lf_adjustment(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(29usize, 1u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn reserved(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(30usize, 2u8) as u32) }
+    }
+    #[inline]
+    pub fn set_reserved(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(30usize, 2u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn new_bitfield_1(
+        frame_type: u32,
+        version: u32,
+        show_frame: u32,
+        color_space: u32,
+        recon_filter_type: u32,
+        loop_filter_type: u32,
+        auto_partitions: u32,
+        num_token_partitions: u32,
+        clamping_type: u32,
+        segmentation_enabled: u32,
+        update_mb_segmentation_map: u32,
+        update_segment_feature_data: u32,
+        loop_filter_adj_enable: u32,
+        refresh_entropy_probs: u32,
+        refresh_golden_frame: u32,
+        refresh_alternate_frame: u32,
+        refresh_last: u32,
+        copy_buffer_to_golden: u32,
+        copy_buffer_to_alternate: u32,
+        sign_bias_golden: u32,
+        sign_bias_alternate: u32,
+        mb_no_coeff_skip: u32,
+        forced_lf_adjustment: u32,
+        reserved: u32,
+    ) -> __BindgenBitfieldUnit<[u8; 4usize]> {
+        let mut __bindgen_bitfield_unit: __BindgenBitfieldUnit<[u8; 4usize]> = Default::default();
+        __bindgen_bitfield_unit.set(0usize, 1u8, {
+            let frame_type: u32 = unsafe { ::std::mem::transmute(frame_type) };
+            frame_type as u64
+        });
+        __bindgen_bitfield_unit.set(1usize, 3u8, {
+            let version: u32 = unsafe { ::std::mem::transmute(version) };
+            version as u64
+        });
+        __bindgen_bitfield_unit.set(4usize, 1u8, {
+            let show_frame: u32 = unsafe { ::std::mem::transmute(show_frame) };
+            show_frame as u64
+        });
+        __bindgen_bitfield_unit.set(5usize, 1u8, {
+            let color_space: u32 = unsafe { ::std::mem::transmute(color_space) };
+            color_space as u64
+        });
+        __bindgen_bitfield_unit.set(6usize, 2u8, {
+            let recon_filter_type: u32 = unsafe { ::std::mem::transmute(recon_filter_type) };
+            recon_filter_type as u64
+        });
+        __bindgen_bitfield_unit.set(8usize, 2u8, {
+            let loop_filter_type: u32 = unsafe { ::std::mem::transmute(loop_filter_type) };
+            loop_filter_type as u64
+        });
+        __bindgen_bitfield_unit.set(10usize, 1u8, {
+            let auto_partitions: u32 = unsafe { ::std::mem::transmute(auto_partitions) };
+            auto_partitions as u64
+        });
+        __bindgen_bitfield_unit.set(11usize, 2u8, {
+            let num_token_partitions: u32 = unsafe { ::std::mem::transmute(num_token_partitions) };
+            num_token_partitions as u64
+        });
+        __bindgen_bitfield_unit.set(13usize, 1u8, {
+            let clamping_type: u32 = unsafe { ::std::mem::transmute(clamping_type) };
+            clamping_type as u64
+        });
+        __bindgen_bitfield_unit.set(14usize, 1u8, {
+            let segmentation_enabled: u32 = unsafe { ::std::mem::transmute(segmentation_enabled) };
+            segmentation_enabled as u64
+        });
+        __bindgen_bitfield_unit.set(15usize, 1u8, {
+            let update_mb_segmentation_map: u32 =
+                unsafe { ::std::mem::transmute(update_mb_segmentation_map) };
+            update_mb_segmentation_map as u64
+        });
+        __bindgen_bitfield_unit.set(16usize, 1u8, {
+            let update_segment_feature_data: u32 =
+                unsafe { ::std::mem::transmute(update_segment_feature_data) };
+            update_segment_feature_data as u64
+        });
+        __bindgen_bitfield_unit.set(17usize, 1u8, {
+            let loop_filter_adj_enable: u32 =
+                unsafe { ::std::mem::transmute(loop_filter_adj_enable) };
+            loop_filter_adj_enable as u64
+        });
+        __bindgen_bitfield_unit.set(18usize, 1u8, {
+            let refresh_entropy_probs: u32 =
+                unsafe { ::std::mem::transmute(refresh_entropy_probs) };
+            refresh_entropy_probs as u64
+        });
+        __bindgen_bitfield_unit.set(19usize, 1u8, {
+            let refresh_golden_frame: u32 = unsafe { ::std::mem::transmute(refresh_golden_frame) };
+            refresh_golden_frame as u64
+        });
+        __bindgen_bitfield_unit.set(20usize, 1u8, {
+            let refresh_alternate_frame: u32 =
+                unsafe { ::std::mem::transmute(refresh_alternate_frame) };
+            refresh_alternate_frame as u64
+        });
+        __bindgen_bitfield_unit.set(21usize, 1u8, {
+            let refresh_last: u32 = unsafe { ::std::mem::transmute(refresh_last) };
+            refresh_last as u64
+        });
+        __bindgen_bitfield_unit.set(22usize, 2u8, {
+            let copy_buffer_to_golden: u32 =
+                unsafe { ::std::mem::transmute(copy_buffer_to_golden) };
+            copy_buffer_to_golden as u64
+        });
+        __bindgen_bitfield_unit.set(24usize, 2u8, {
+            let copy_buffer_to_alternate: u32 =
+                unsafe { ::std::mem::transmute(copy_buffer_to_alternate) };
+            copy_buffer_to_alternate as u64
+        });
+        __bindgen_bitfield_unit.set(26usize, 1u8, {
+            let sign_bias_golden: u32 = unsafe { ::std::mem::transmute(sign_bias_golden) };
+            sign_bias_golden as u64
+        });
+        __bindgen_bitfield_unit.set(27usize, 1u8, {
+            let sign_bias_alternate: u32 = unsafe { ::std::mem::transmute(sign_bias_alternate) };
+            sign_bias_alternate as u64
+        });
+        __bindgen_bitfield_unit.set(28usize, 1u8, {
+            let mb_no_coeff_skip: u32 = unsafe { ::std::mem::transmute(mb_no_coeff_skip) };
+            mb_no_coeff_skip as u64
+        });
+        __bindgen_bitfield_unit.set(29usize, 1u8, {
+            let forced_lf_adjustment: u32 = unsafe { ::std::mem::transmute(forced_lf_adjustment) };
+            forced_lf_adjustment as u64
+        });
+        __bindgen_bitfield_unit.set(30usize, 2u8, {
+            let reserved: u32 = unsafe { ::std::mem::transmute(reserved) };
+            reserved as u64
+        });
+        __bindgen_bitfield_unit
+    }
+}
+impl Default for _VAEncPictureParameterBufferVP8__bindgen_ty_2 {
+    fn default() -> Self {
+        let mut s = ::std::mem::MaybeUninit::<Self>::uninit();
+        unsafe {
+            ::std::ptr::write_bytes(s.as_mut_ptr(), 0, 1);
+            s.assume_init()
+        }
+    }
+}
+impl Default for _VAEncPictureParameterBufferVP8 {
+    fn default() -> Self {
+        let mut s = ::std::mem::MaybeUninit::<Self>::uninit();
+        unsafe {
+            ::std::ptr::write_bytes(s.as_mut_ptr(), 0, 1);
+            s.assume_init()
+        }
+    }
+}
+pub type VAEncPictureParameterBufferVP8 = _VAEncPictureParameterBufferVP8;
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct _VAEncMBMapBufferVP8 {
+    pub num_mbs: u32,
+    pub mb_segment_id: *mut u8,
+    pub va_reserved: [u32; 4usize],
+}
+impl Default for _VAEncMBMapBufferVP8 {
+    fn default() -> Self {
+        let mut s = ::std::mem::MaybeUninit::<Self>::uninit();
+        unsafe {
+            ::std::ptr::write_bytes(s.as_mut_ptr(), 0, 1);
+            s.assume_init()
+        }
+    }
+}
+pub type VAEncMBMapBufferVP8 = _VAEncMBMapBufferVP8;
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct _VAQMatrixBufferVP8 {
+    pub quantization_index: [u16; 4usize],
+    pub quantization_index_delta: [i16; 5usize],
+    pub va_reserved: [u32; 4usize],
+}
+pub type VAQMatrixBufferVP8 = _VAQMatrixBufferVP8;
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct _VACodedBufferVP9Status {
+    pub base_qp_index: u16,
+    pub loop_filter_level: u8,
+    pub long_term_indication: u8,
+    pub next_frame_width: u16,
+    pub next_frame_height: u16,
+    pub va_reserved: [u32; 4usize],
+}
+pub type VACodedBufferVP9Status = _VACodedBufferVP9Status;
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct _VAEncSequenceParameterBufferVP9 {
+    pub max_frame_width: u32,
+    pub max_frame_height: u32,
+    pub kf_auto: u32,
+    pub kf_min_dist: u32,
+    pub kf_max_dist: u32,
+    pub bits_per_second: u32,
+    pub intra_period: u32,
+    pub va_reserved: [u32; 4usize],
+}
+pub type VAEncSequenceParameterBufferVP9 = _VAEncSequenceParameterBufferVP9;
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct _VAEncPictureParameterBufferVP9 {
+    pub frame_width_src: u32,
+    pub frame_height_src: u32,
+    pub frame_width_dst: u32,
+    pub frame_height_dst: u32,
+    pub reconstructed_frame: VASurfaceID,
+    pub reference_frames: [VASurfaceID; 8usize],
+    pub coded_buf: VABufferID,
+    pub ref_flags: _VAEncPictureParameterBufferVP9__bindgen_ty_1,
+    pub pic_flags: _VAEncPictureParameterBufferVP9__bindgen_ty_2,
+    pub refresh_frame_flags: u8,
+    pub luma_ac_qindex: u8,
+    pub luma_dc_qindex_delta: i8,
+    pub chroma_ac_qindex_delta: i8,
+    pub chroma_dc_qindex_delta: i8,
+    pub filter_level: u8,
+    pub sharpness_level: u8,
+    pub ref_lf_delta: [i8; 4usize],
+    pub mode_lf_delta: [i8; 2usize],
+    pub bit_offset_ref_lf_delta: u16,
+    pub bit_offset_mode_lf_delta: u16,
+    pub bit_offset_lf_level: u16,
+    pub bit_offset_qindex: u16,
+    pub bit_offset_first_partition_size: u16,
+    pub bit_offset_segmentation: u16,
+    pub bit_size_segmentation: u16,
+    pub log2_tile_rows: u8,
+    pub log2_tile_columns: u8,
+    pub skip_frame_flag: u8,
+    pub number_skip_frames: u8,
+    pub skip_frames_size: u32,
+    pub va_reserved: [u32; 8usize],
+}
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub union _VAEncPictureParameterBufferVP9__bindgen_ty_1 {
+    pub bits: _VAEncPictureParameterBufferVP9__bindgen_ty_1__bindgen_ty_1,
+    pub value: u32,
+}
+#[repr(C)]
+#[repr(align(4))]
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct _VAEncPictureParameterBufferVP9__bindgen_ty_1__bindgen_ty_1 {
+    pub _bitfield_align_1: [u8; 0],
+    pub _bitfield_1: __BindgenBitfieldUnit<[u8; 4usize]>,
+}
+impl _VAEncPictureParameterBufferVP9__bindgen_ty_1__bindgen_ty_1 {
+    #[inline]
+    pub fn force_kf(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(0usize, 1u8) as u32) }
+    }
+    #[inline]
+    pub fn set_force_kf(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(0usize, 1u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn ref_frame_ctrl_l0(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(1usize, 3u8) as u32) }
+    }
+    #[inline]
+    pub fn set_ref_frame_ctrl_l0(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(1usize, 3u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn ref_frame_ctrl_l1(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(4usize, 3u8) as u32) }
+    }
+    #[inline]
+    pub fn set_ref_frame_ctrl_l1(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(4usize, 3u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn ref_last_idx(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(7usize, 3u8) as u32) }
+    }
+    #[inline]
+    pub fn set_ref_last_idx(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(7usize, 3u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn ref_last_sign_bias(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(10usize, 1u8) as u32) }
+    }
+    #[inline]
+    pub fn set_ref_last_sign_bias(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(10usize, 1u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn ref_gf_idx(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(11usize, 3u8) as u32) }
+    }
+    #[inline]
+    pub fn set_ref_gf_idx(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(11usize, 3u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn ref_gf_sign_bias(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(14usize, 1u8) as u32) }
+    }
+    #[inline]
+    pub fn set_ref_gf_sign_bias(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(14usize, 1u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn ref_arf_idx(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(15usize, 3u8) as u32) }
+    }
+    #[inline]
+    pub fn set_ref_arf_idx(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(15usize, 3u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn ref_arf_sign_bias(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(18usize, 1u8) as u32) }
+    }
+    #[inline]
+    pub fn set_ref_arf_sign_bias(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(18usize, 1u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn temporal_id(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(19usize, 8u8) as u32) }
+    }
+    #[inline]
+    pub fn set_temporal_id(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(19usize, 8u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn reserved(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(27usize, 5u8) as u32) }
+    }
+    #[inline]
+    pub fn set_reserved(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(27usize, 5u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn new_bitfield_1(
+        force_kf: u32,
+        ref_frame_ctrl_l0: u32,
+        ref_frame_ctrl_l1: u32,
+        ref_last_idx: u32,
+        ref_last_sign_bias: u32,
+        ref_gf_idx: u32,
+        ref_gf_sign_bias: u32,
+        ref_arf_idx: u32,
+        ref_arf_sign_bias: u32,
+        temporal_id: u32,
+        reserved: u32,
+    ) -> __BindgenBitfieldUnit<[u8; 4usize]> {
+        let mut __bindgen_bitfield_unit: __BindgenBitfieldUnit<[u8; 4usize]> = Default::default();
+        __bindgen_bitfield_unit.set(0usize, 1u8, {
+            let force_kf: u32 = unsafe { ::std::mem::transmute(force_kf) };
+            force_kf as u64
+        });
+        __bindgen_bitfield_unit.set(1usize, 3u8, {
+            let ref_frame_ctrl_l0: u32 = unsafe { ::std::mem::transmute(ref_frame_ctrl_l0) };
+            ref_frame_ctrl_l0 as u64
+        });
+        __bindgen_bitfield_unit.set(4usize, 3u8, {
+            let ref_frame_ctrl_l1: u32 = unsafe { ::std::mem::transmute(ref_frame_ctrl_l1) };
+            ref_frame_ctrl_l1 as u64
+        });
+        __bindgen_bitfield_unit.set(7usize, 3u8, {
+            let ref_last_idx: u32 = unsafe { ::std::mem::transmute(ref_last_idx) };
+            ref_last_idx as u64
+        });
+        __bindgen_bitfield_unit.set(10usize, 1u8, {
+            let ref_last_sign_bias: u32 = unsafe { ::std::mem::transmute(ref_last_sign_bias) };
+            ref_last_sign_bias as u64
+        });
+        __bindgen_bitfield_unit.set(11usize, 3u8, {
+            let ref_gf_idx: u32 = unsafe { ::std::mem::transmute(ref_gf_idx) };
+            ref_gf_idx as u64
+        });
+        __bindgen_bitfield_unit.set(14usize, 1u8, {
+            let ref_gf_sign_bias: u32 = unsafe { ::std::mem::transmute(ref_gf_sign_bias) };
+            ref_gf_sign_bias as u64
+        });
+        __bindgen_bitfield_unit.set(15usize, 3u8, {
+            let ref_arf_idx: u32 = unsafe { ::std::mem::transmute(ref_arf_idx) };
+            ref_arf_idx as u64
+        });
+        __bindgen_bitfield_unit.set(18usize, 1u8, {
+            let ref_arf_sign_bias: u32 = unsafe { ::std::mem::transmute(ref_arf_sign_bias) };
+            ref_arf_sign_bias as u64
+        });
+        __bindgen_bitfield_unit.set(19usize, 8u8, {
+            let temporal_id: u32 = unsafe { ::std::mem::transmute(temporal_id) };
+            temporal_id as u64
+        });
+        __bindgen_bitfield_unit.set(27usize, 5u8, {
+            let reserved: u32 = unsafe { ::std::mem::transmute(reserved) };
+            reserved as u64
+        });
+        __bindgen_bitfield_unit
+    }
+}
+impl Default for _VAEncPictureParameterBufferVP9__bindgen_ty_1 {
+    fn default() -> Self {
+        let mut s = ::std::mem::MaybeUninit::<Self>::uninit();
+        unsafe {
+            ::std::ptr::write_bytes(s.as_mut_ptr(), 0, 1);
+            s.assume_init()
+        }
+    }
+}
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub union _VAEncPictureParameterBufferVP9__bindgen_ty_2 {
+    pub bits: _VAEncPictureParameterBufferVP9__bindgen_ty_2__bindgen_ty_1,
+    pub value: u32,
+}
+#[repr(C)]
+#[repr(align(4))]
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct _VAEncPictureParameterBufferVP9__bindgen_ty_2__bindgen_ty_1 {
+    pub _bitfield_align_1: [u16; 0],
+    pub _bitfield_1: __BindgenBitfieldUnit<[u8; 4usize]>,
+}
+impl _VAEncPictureParameterBufferVP9__bindgen_ty_2__bindgen_ty_1 {
+    #[inline]
+    pub fn frame_type(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(0usize, 1u8) as u32) }
+    }
+    #[inline]
+    pub fn set_frame_type(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(0usize, 1u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn show_frame(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(1usize, 1u8) as u32) }
+    }
+    #[inline]
+    pub fn set_show_frame(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(1usize, 1u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn error_resilient_mode(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(2usize, 1u8) as u32) }
+    }
+    #[inline]
+    pub fn set_error_resilient_mode(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(2usize, 1u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn intra_only(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(3usize, 1u8) as u32) }
+    }
+    #[inline]
+    pub fn set_intra_only(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(3usize, 1u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn allow_high_precision_mv(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(4usize, 1u8) as u32) }
+    }
+    #[inline]
+    pub fn set_allow_high_precision_mv(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(4usize, 1u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn mcomp_filter_type(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(5usize, 3u8) as u32) }
+    }
+    #[inline]
+    pub fn set_mcomp_filter_type(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(5usize, 3u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn frame_parallel_decoding_mode(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(8usize, 1u8) as u32) }
+    }
+    #[inline]
+    pub fn set_frame_parallel_decoding_mode(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(8usize, 1u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn reset_frame_context(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(9usize, 2u8) as u32) }
+    }
+    #[inline]
+    pub fn set_reset_frame_context(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(9usize, 2u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn refresh_frame_context(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(11usize, 1u8) as u32) }
+    }
+    #[inline]
+    pub fn set_refresh_frame_context(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(11usize, 1u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn frame_context_idx(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(12usize, 2u8) as u32) }
+    }
+    #[inline]
+    pub fn set_frame_context_idx(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(12usize, 2u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn segmentation_enabled(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(14usize, 1u8) as u32) }
+    }
+    #[inline]
+    pub fn set_segmentation_enabled(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(14usize, 1u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn segmentation_temporal_update(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(15usize, 1u8) as u32) }
+    }
+    #[inline]
+    pub fn set_segmentation_temporal_update(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(15usize, 1u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn segmentation_update_map(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(16usize, 1u8) as u32) }
+    }
+    #[inline]
+    pub fn set_segmentation_update_map(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(16usize, 1u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn lossless_mode(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(17usize, 1u8) as u32) }
+    }
+    #[inline]
+    pub fn set_lossless_mode(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(17usize, 1u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn comp_prediction_mode(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(18usize, 2u8) as u32) }
+    }
+    #[inline]
+    pub fn set_comp_prediction_mode(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(18usize, 2u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn auto_segmentation(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(20usize, 1u8) as u32) }
+    }
+    #[inline]
+    pub fn set_auto_segmentation(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(20usize, 1u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn super_frame_flag(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(21usize, 1u8) as u32) }
+    }
+    #[inline]
+    pub fn set_super_frame_flag(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(21usize, 1u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn reserved(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(22usize, 10u8) as u32) }
+    }
+    #[inline]
+    pub fn set_reserved(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(22usize, 10u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn new_bitfield_1(
+        frame_type: u32,
+        show_frame: u32,
+        error_resilient_mode: u32,
+        intra_only: u32,
+        allow_high_precision_mv: u32,
+        mcomp_filter_type: u32,
+        frame_parallel_decoding_mode: u32,
+        reset_frame_context: u32,
+        refresh_frame_context: u32,
+        frame_context_idx: u32,
+        segmentation_enabled: u32,
+        segmentation_temporal_update: u32,
+        segmentation_update_map: u32,
+        lossless_mode: u32,
+        comp_prediction_mode: u32,
+        auto_segmentation: u32,
+        super_frame_flag: u32,
+        reserved: u32,
+    ) -> __BindgenBitfieldUnit<[u8; 4usize]> {
+        let mut __bindgen_bitfield_unit: __BindgenBitfieldUnit<[u8; 4usize]> = Default::default();
+        __bindgen_bitfield_unit.set(0usize, 1u8, {
+            let frame_type: u32 = unsafe { ::std::mem::transmute(frame_type) };
+            frame_type as u64
+        });
+        __bindgen_bitfield_unit.set(1usize, 1u8, {
+            let show_frame: u32 = unsafe { ::std::mem::transmute(show_frame) };
+            show_frame as u64
+        });
+        __bindgen_bitfield_unit.set(2usize, 1u8, {
+            let error_resilient_mode: u32 = unsafe { ::std::mem::transmute(error_resilient_mode) };
+            error_resilient_mode as u64
+        });
+        __bindgen_bitfield_unit.set(3usize, 1u8, {
+            let intra_only: u32 = unsafe { ::std::mem::transmute(intra_only) };
+            intra_only as u64
+        });
+        __bindgen_bitfield_unit.set(4usize, 1u8, {
+            let allow_high_precision_mv: u32 =
+                unsafe { ::std::mem::transmute(allow_high_precision_mv) };
+            allow_high_precision_mv as u64
+        });
+        __bindgen_bitfield_unit.set(5usize, 3u8, {
+            let mcomp_filter_type: u32 = unsafe { ::std::mem::transmute(mcomp_filter_type) };
+            mcomp_filter_type as u64
+        });
+        __bindgen_bitfield_unit.set(8usize, 1u8, {
+            let frame_parallel_decoding_mode: u32 =
+                unsafe { ::std::mem::transmute(frame_parallel_decoding_mode) };
+            frame_parallel_decoding_mode as u64
+        });
+        __bindgen_bitfield_unit.set(9usize, 2u8, {
+            let reset_frame_context: u32 = unsafe { ::std::mem::transmute(reset_frame_context) };
+            reset_frame_context as u64
+        });
+        __bindgen_bitfield_unit.set(11usize, 1u8, {
+            let refresh_frame_context: u32 =
+                unsafe { ::std::mem::transmute(refresh_frame_context) };
+            refresh_frame_context as u64
+        });
+        __bindgen_bitfield_unit.set(12usize, 2u8, {
+            let frame_context_idx: u32 = unsafe { ::std::mem::transmute(frame_context_idx) };
+            frame_context_idx as u64
+        });
+        __bindgen_bitfield_unit.set(14usize, 1u8, {
+            let segmentation_enabled: u32 = unsafe { ::std::mem::transmute(segmentation_enabled) };
+            segmentation_enabled as u64
+        });
+        __bindgen_bitfield_unit.set(15usize, 1u8, {
+            let segmentation_temporal_update: u32 =
+                unsafe { ::std::mem::transmute(segmentation_temporal_update) };
+            segmentation_temporal_update as u64
+        });
+        __bindgen_bitfield_unit.set(16usize, 1u8, {
+            let segmentation_update_map: u32 =
+                unsafe { ::std::mem::transmute(segmentation_update_map) };
+            segmentation_update_map as u64
+        });
+        __bindgen_bitfield_unit.set(17usize, 1u8, {
+            let lossless_mode: u32 = unsafe { ::std::mem::transmute(lossless_mode) };
+            lossless_mode as u64
+        });
+        __bindgen_bitfield_unit.set(18usize, 2u8, {
+            let comp_prediction_mode: u32 = unsafe { ::std::mem::transmute(comp_prediction_mode) };
+            comp_prediction_mode as u64
+        });
+        __bindgen_bitfield_unit.set(20usize, 1u8, {
+            let auto_segmentation: u32 = unsafe { ::std::mem::transmute(auto_segmentation) };
+            auto_segmentation as u64
+        });
+        __bindgen_bitfield_unit.set(21usize, 1u8, {
+            let super_frame_flag: u32 = unsafe { ::std::mem::transmute(super_frame_flag) };
+            super_frame_flag as u64
+        });
+        __bindgen_bitfield_unit.set(22usize, 10u8, {
+            let reserved: u32 = unsafe { ::std::mem::transmute(reserved) };
+            reserved as u64
+        });
+        __bindgen_bitfield_unit
+    }
+}
+impl Default for _VAEncPictureParameterBufferVP9__bindgen_ty_2 {
+    fn default() -> Self {
+        let mut s = ::std::mem::MaybeUninit::<Self>::uninit();
+        unsafe {
+            ::std::ptr::write_bytes(s.as_mut_ptr(), 0, 1);
+            s.assume_init()
+        }
+    }
+}
+impl Default for _VAEncPictureParameterBufferVP9 {
+    fn default() -> Self {
+        let mut s = ::std::mem::MaybeUninit::<Self>::uninit();
+        unsafe {
+            ::std::ptr::write_bytes(s.as_mut_ptr(), 0, 1);
+            s.assume_init()
+        }
+    }
+}
+pub type VAEncPictureParameterBufferVP9 = _VAEncPictureParameterBufferVP9;
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct _VAEncSegParamVP9 {
+    pub seg_flags: _VAEncSegParamVP9__bindgen_ty_1,
+    pub segment_lf_level_delta: i8,
+    pub segment_qindex_delta: i16,
+    pub va_reserved: [u32; 4usize],
+}
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub union _VAEncSegParamVP9__bindgen_ty_1 {
+    pub bits: _VAEncSegParamVP9__bindgen_ty_1__bindgen_ty_1,
+    pub value: u8,
+}
+#[repr(C, packed)]
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct _VAEncSegParamVP9__bindgen_ty_1__bindgen_ty_1 {
+    pub _bitfield_align_1: [u8; 0],
+    pub _bitfield_1: __BindgenBitfieldUnit<[u8; 1usize]>,
+}
+impl _VAEncSegParamVP9__bindgen_ty_1__bindgen_ty_1 {
+    #[inline]
+    pub fn segment_reference_enabled(&self) -> u8 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(0usize, 1u8) as u8) }
+    }
+    #[inline]
+    pub fn set_segment_reference_enabled(&mut self, val: u8) {
+        unsafe {
+            let val: u8 = ::std::mem::transmute(val);
+            self._bitfield_1.set(0usize, 1u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn segment_reference(&self) -> u8 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(1usize, 2u8) as u8) }
+    }
+    #[inline]
+    pub fn set_segment_reference(&mut self, val: u8) {
+        unsafe {
+            let val: u8 = ::std::mem::transmute(val);
+            self._bitfield_1.set(1usize, 2u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn segment_reference_skipped(&self) -> u8 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(3usize, 1u8) as u8) }
+    }
+    #[inline]
+    pub fn set_segment_reference_skipped(&mut self, val: u8) {
+        unsafe {
+            let val: u8 = ::std::mem::transmute(val);
+            self._bitfield_1.set(3usize, 1u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn reserved(&self) -> u8 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(4usize, 4u8) as u8) }
+    }
+    #[inline]
+    pub fn set_reserved(&mut self, val: u8) {
+        unsafe {
+            let val: u8 = ::std::mem::transmute(val);
+            self._bitfield_1.set(4usize, 4u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn new_bitfield_1(
+        segment_reference_enabled: u8,
+        segment_reference: u8,
+        segment_reference_skipped: u8,
+        reserved: u8,
+    ) -> __BindgenBitfieldUnit<[u8; 1usize]> {
+        let mut __bindgen_bitfield_unit: __BindgenBitfieldUnit<[u8; 1usize]> = Default::default();
+        __bindgen_bitfield_unit.set(0usize, 1u8, {
+            let segment_reference_enabled: u8 =
+                unsafe { ::std::mem::transmute(segment_reference_enabled) };
+            segment_reference_enabled as u64
+        });
+        __bindgen_bitfield_unit.set(1usize, 2u8, {
+            let segment_reference: u8 = unsafe { ::std::mem::transmute(segment_reference) };
+            segment_reference as u64
+        });
+        __bindgen_bitfield_unit.set(3usize, 1u8, {
+            let segment_reference_skipped: u8 =
+                unsafe { ::std::mem::transmute(segment_reference_skipped) };
+            segment_reference_skipped as u64
+        });
+        __bindgen_bitfield_unit.set(4usize, 4u8, {
+            let reserved: u8 = unsafe { ::std::mem::transmute(reserved) };
+            reserved as u64
+        });
+        __bindgen_bitfield_unit
+    }
+}
+impl Default for _VAEncSegParamVP9__bindgen_ty_1 {
+    fn default() -> Self {
+        let mut s = ::std::mem::MaybeUninit::<Self>::uninit();
+        unsafe {
+            ::std::ptr::write_bytes(s.as_mut_ptr(), 0, 1);
+            s.assume_init()
+        }
+    }
+}
+impl Default for _VAEncSegParamVP9 {
+    fn default() -> Self {
+        let mut s = ::std::mem::MaybeUninit::<Self>::uninit();
+        unsafe {
+            ::std::ptr::write_bytes(s.as_mut_ptr(), 0, 1);
+            s.assume_init()
+        }
+    }
+}
+pub type VAEncSegParamVP9 = _VAEncSegParamVP9;
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct _VAEncMiscParameterTypeVP9PerSegmantParam {
+    pub seg_data: [VAEncSegParamVP9; 8usize],
+    pub va_reserved: [u32; 4usize],
+}
+impl Default for _VAEncMiscParameterTypeVP9PerSegmantParam {
+    fn default() -> Self {
+        let mut s = ::std::mem::MaybeUninit::<Self>::uninit();
+        unsafe {
+            ::std::ptr::write_bytes(s.as_mut_ptr(), 0, 1);
+            s.assume_init()
+        }
+    }
+}
+pub type VAEncMiscParameterTypeVP9PerSegmantParam = _VAEncMiscParameterTypeVP9PerSegmantParam;
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct _VAEncMiscParameterFEIFrameControlH264 {
+    pub function: u32,
+    pub mb_ctrl: VABufferID,
+    pub distortion: VABufferID,
+    pub mv_data: VABufferID,
+    pub mb_code_data: VABufferID,
+    pub qp: VABufferID,
+    pub mv_predictor: VABufferID,
+    pub _bitfield_align_1: [u16; 0],
+    pub _bitfield_1: __BindgenBitfieldUnit<[u8; 16usize]>,
+    pub max_frame_size: u32,
+    pub num_passes: u32,
+    pub delta_qp: *mut u8,
+    pub reserved3: [u32; 4usize],
+}
+impl Default for _VAEncMiscParameterFEIFrameControlH264 {
+    fn default() -> Self {
+        let mut s = ::std::mem::MaybeUninit::<Self>::uninit();
+        unsafe {
+            ::std::ptr::write_bytes(s.as_mut_ptr(), 0, 1);
+            s.assume_init()
+        }
+    }
+}
+impl _VAEncMiscParameterFEIFrameControlH264 {
+    #[inline]
+    pub fn num_mv_predictors_l0(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(0usize, 16u8) as u32) }
+    }
+    #[inline]
+    pub fn set_num_mv_predictors_l0(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(0usize, 16u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn num_mv_predictors_l1(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(16usize, 16u8) as u32) }
+    }
+    #[inline]
+    pub fn set_num_mv_predictors_l1(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(16usize, 16u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn search_path(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(32usize, 8u8) as u32) }
+    }
+    #[inline]
+    pub fn set_search_path(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(32usize, 8u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn len_sp(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(40usize, 8u8) as u32) }
+    }
+    #[inline]
+    pub fn set_len_sp(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(40usize, 8u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn reserved0(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(48usize, 16u8) as u32) }
+    }
+    #[inline]
+    pub fn set_reserved0(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(48usize, 16u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn sub_mb_part_mask(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(64usize, 7u8) as u32) }
+    }
+    #[inline]
+    pub fn set_sub_mb_part_mask(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(64usize, 7u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn intra_part_mask(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(71usize, 5u8) as u32) }
+    }
+    #[inline]
+    pub fn set_intra_part_mask(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(71usize, 5u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn multi_pred_l0(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(76usize, 1u8) as u32) }
+    }
+    #[inline]
+    pub fn set_multi_pred_l0(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(76usize, 1u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn multi_pred_l1(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(77usize, 1u8) as u32) }
+    }
+    #[inline]
+    pub fn set_multi_pred_l1(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(77usize, 1u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn sub_pel_mode(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(78usize, 2u8) as u32) }
+    }
+    #[inline]
+    pub fn set_sub_pel_mode(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(78usize, 2u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn inter_sad(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(80usize, 2u8) as u32) }
+    }
+    #[inline]
+    pub fn set_inter_sad(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(80usize, 2u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn intra_sad(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(82usize, 2u8) as u32) }
+    }
+    #[inline]
+    pub fn set_intra_sad(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(82usize, 2u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn distortion_type(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(84usize, 1u8) as u32) }
+    }
+    #[inline]
+    pub fn set_distortion_type(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(84usize, 1u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn repartition_check_enable(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(85usize, 1u8) as u32) }
+    }
+    #[inline]
+    pub fn set_repartition_check_enable(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(85usize, 1u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn adaptive_search(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(86usize, 1u8) as u32) }
+    }
+    #[inline]
+    pub fn set_adaptive_search(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(86usize, 1u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn mv_predictor_enable(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(87usize, 1u8) as u32) }
+    }
+    #[inline]
+    pub fn set_mv_predictor_enable(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(87usize, 1u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn mb_qp(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(88usize, 1u8) as u32) }
+    }
+    #[inline]
+    pub fn set_mb_qp(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(88usize, 1u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn mb_input(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(89usize, 1u8) as u32) }
+    }
+    #[inline]
+    pub fn set_mb_input(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(89usize, 1u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn mb_size_ctrl(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(90usize, 1u8) as u32) }
+    }
+    #[inline]
+    pub fn set_mb_size_ctrl(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(90usize, 1u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn colocated_mb_distortion(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(91usize, 1u8) as u32) }
+    }
+    #[inline]
+    pub fn set_colocated_mb_distortion(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(91usize, 1u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn reserved1(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(92usize, 4u8) as u32) }
+    }
+    #[inline]
+    pub fn set_reserved1(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(92usize, 4u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn ref_width(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(96usize, 8u8) as u32) }
+    }
+    #[inline]
+    pub fn set_ref_width(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(96usize, 8u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn ref_height(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(104usize, 8u8) as u32) }
+    }
+    #[inline]
+    pub fn set_ref_height(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(104usize, 8u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn search_window(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(112usize, 4u8) as u32) }
+    }
+    #[inline]
+    pub fn set_search_window(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(112usize, 4u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn reserved2(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(116usize, 12u8) as u32) }
+    }
+    #[inline]
+    pub fn set_reserved2(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(116usize, 12u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn new_bitfield_1(
+        num_mv_predictors_l0: u32,
+        num_mv_predictors_l1: u32,
+        search_path: u32,
+        len_sp: u32,
+        reserved0: u32,
+        sub_mb_part_mask: u32,
+        intra_part_mask: u32,
+        multi_pred_l0: u32,
+        multi_pred_l1: u32,
+        sub_pel_mode: u32,
+        inter_sad: u32,
+        intra_sad: u32,
+        distortion_type: u32,
+        repartition_check_enable: u32,
+        adaptive_search: u32,
+        mv_predictor_enable: u32,
+        mb_qp: u32,
+        mb_input: u32,
+        mb_size_ctrl: u32,
+        colocated_mb_distortion: u32,
+        reserved1: u32,
+        ref_width: u32,
+        ref_height: u32,
+        search_window: u32,
+        reserved2: u32,
+    ) -> __BindgenBitfieldUnit<[u8; 16usize]> {
+        let mut __bindgen_bitfield_unit: __BindgenBitfieldUnit<[u8; 16usize]> = Default::default();
+        __bindgen_bitfield_unit.set(0usize, 16u8, {
+            let num_mv_predictors_l0: u32 = unsafe { ::std::mem::transmute(num_mv_predictors_l0) };
+            num_mv_predictors_l0 as u64
+        });
+        __bindgen_bitfield_unit.set(16usize, 16u8, {
+            let num_mv_predictors_l1: u32 = unsafe { ::std::mem::transmute(num_mv_predictors_l1) };
+            num_mv_predictors_l1 as u64
+        });
+        __bindgen_bitfield_unit.set(32usize, 8u8, {
+            let search_path: u32 = unsafe { ::std::mem::transmute(search_path) };
+            search_path as u64
+        });
+        __bindgen_bitfield_unit.set(40usize, 8u8, {
+            let len_sp: u32 = unsafe { ::std::mem::transmute(len_sp) };
+            len_sp as u64
+        });
+        __bindgen_bitfield_unit.set(48usize, 16u8, {
+            let reserved0: u32 = unsafe { ::std::mem::transmute(reserved0) };
+            reserved0 as u64
+        });
+        __bindgen_bitfield_unit.set(64usize, 7u8, {
+            let sub_mb_part_mask: u32 = unsafe { ::std::mem::transmute(sub_mb_part_mask) };
+            sub_mb_part_mask as u64
+        });
+        __bindgen_bitfield_unit.set(71usize, 5u8, {
+            let intra_part_mask: u32 = unsafe { ::std::mem::transmute(intra_part_mask) };
+            intra_part_mask as u64
+        });
+        __bindgen_bitfield_unit.set(76usize, 1u8, {
+            let multi_pred_l0: u32 = unsafe { ::std::mem::transmute(multi_pred_l0) };
+            multi_pred_l0 as u64
+        });
+        __bindgen_bitfield_unit.set(77usize, 1u8, {
+            let multi_pred_l1: u32 = unsafe { ::std::mem::transmute(multi_pred_l1) };
+            multi_pred_l1 as u64
+        });
+        __bindgen_bitfield_unit.set(78usize, 2u8, {
+            let sub_pel_mode: u32 = unsafe { ::std::mem::transmute(sub_pel_mode) };
+            sub_pel_mode as u64
+        });
+        __bindgen_bitfield_unit.set(80usize, 2u8, {
+            let inter_sad: u32 = unsafe { ::std::mem::transmute(inter_sad) };
+            inter_sad as u64
+        });
+        __bindgen_bitfield_unit.set(82usize, 2u8, {
+            let intra_sad: u32 = unsafe { ::std::mem::transmute(intra_sad) };
+            intra_sad as u64
+        });
+        __bindgen_bitfield_unit.set(84usize, 1u8, {
+            let distortion_type: u32 = unsafe { ::std::mem::transmute(distortion_type) };
+            distortion_type as u64
+        });
+        __bindgen_bitfield_unit.set(85usize, 1u8, {
+            let repartition_check_enable: u32 =
+                unsafe { ::std::mem::transmute(repartition_check_enable) };
+            repartition_check_enable as u64
+        });
+        __bindgen_bitfield_unit.set(86usize, 1u8, {
+            let adaptive_search: u32 = unsafe { ::std::mem::transmute(adaptive_search) };
+            adaptive_search as u64
+        });
+        __bindgen_bitfield_unit.set(87usize, 1u8, {
+            let mv_predictor_enable: u32 = unsafe { ::std::mem::transmute(mv_predictor_enable) };
+            mv_predictor_enable as u64
+        });
+        __bindgen_bitfield_unit.set(88usize, 1u8, {
+            let mb_qp: u32 = unsafe { ::std::mem::transmute(mb_qp) };
+            mb_qp as u64
+        });
+        __bindgen_bitfield_unit.set(89usize, 1u8, {
+            let mb_input: u32 = unsafe { ::std::mem::transmute(mb_input) };
+            mb_input as u64
+        });
+        __bindgen_bitfield_unit.set(90usize, 1u8, {
+            let mb_size_ctrl: u32 = unsafe { ::std::mem::transmute(mb_size_ctrl) };
+            mb_size_ctrl as u64
+        });
+        __bindgen_bitfield_unit.set(91usize, 1u8, {
+            let colocated_mb_distortion: u32 =
+                unsafe { ::std::mem::transmute(colocated_mb_distortion) };
+            colocated_mb_distortion as u64
+        });
+        __bindgen_bitfield_unit.set(92usize, 4u8, {
+            let reserved1: u32 = unsafe { ::std::mem::transmute(reserved1) };
+            reserved1 as u64
+        });
+        __bindgen_bitfield_unit.set(96usize, 8u8, {
+            let ref_width: u32 = unsafe { ::std::mem::transmute(ref_width) };
+            ref_width as u64
+        });
+        __bindgen_bitfield_unit.set(104usize, 8u8, {
+            let ref_height: u32 = unsafe { ::std::mem::transmute(ref_height) };
+            ref_height as u64
+        });
+        __bindgen_bitfield_unit.set(112usize, 4u8, {
+            let search_window: u32 = unsafe { ::std::mem::transmute(search_window) };
+            search_window as u64
+        });
+        __bindgen_bitfield_unit.set(116usize, 12u8, {
+            let reserved2: u32 = unsafe { ::std::mem::transmute(reserved2) };
+            reserved2 as u64
+        });
+        __bindgen_bitfield_unit
+    }
+}
+pub type VAEncMiscParameterFEIFrameControlH264 = _VAEncMiscParameterFEIFrameControlH264;
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct _VAEncFEIMBControlH264 {
+    pub _bitfield_align_1: [u32; 0],
+    pub _bitfield_1: __BindgenBitfieldUnit<[u8; 4usize]>,
+    pub reserved1: u32,
+    pub reserved2: u32,
+    pub _bitfield_align_2: [u16; 0],
+    pub _bitfield_2: __BindgenBitfieldUnit<[u8; 4usize]>,
+}
+impl _VAEncFEIMBControlH264 {
+    #[inline]
+    pub fn force_to_intra(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(0usize, 1u8) as u32) }
+    }
+    #[inline]
+    pub fn set_force_to_intra(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(0usize, 1u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn force_to_skip(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(1usize, 1u8) as u32) }
+    }
+    #[inline]
+    pub fn set_force_to_skip(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(1usize, 1u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn force_to_nonskip(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(2usize, 1u8) as u32) }
+    }
+    #[inline]
+    pub fn set_force_to_nonskip(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(2usize, 1u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn enable_direct_bias_adjustment(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(3usize, 1u8) as u32) }
+    }
+    #[inline]
+    pub fn set_enable_direct_bias_adjustment(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(3usize, 1u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn enable_motion_bias_adjustment(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(4usize, 1u8) as u32) }
+    }
+    #[inline]
+    pub fn set_enable_motion_bias_adjustment(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(4usize, 1u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn ext_mv_cost_scaling_factor(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(5usize, 3u8) as u32) }
+    }
+    #[inline]
+    pub fn set_ext_mv_cost_scaling_factor(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(5usize, 3u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn reserved0(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(8usize, 24u8) as u32) }
+    }
+    #[inline]
+    pub fn set_reserved0(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(8usize, 24u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn new_bitfield_1(
+        force_to_intra: u32,
+        force_to_skip: u32,
+        force_to_nonskip: u32,
+        enable_direct_bias_adjustment: u32,
+        enable_motion_bias_adjustment: u32,
+        ext_mv_cost_scaling_factor: u32,
+        reserved0: u32,
+    ) -> __BindgenBitfieldUnit<[u8; 4usize]> {
+        let mut __bindgen_bitfield_unit: __BindgenBitfieldUnit<[u8; 4usize]> = Default::default();
+        __bindgen_bitfield_unit.set(0usize, 1u8, {
+            let force_to_intra: u32 = unsafe { ::std::mem::transmute(force_to_intra) };
+            force_to_intra as u64
+        });
+        __bindgen_bitfield_unit.set(1usize, 1u8, {
+            let force_to_skip: u32 = unsafe { ::std::mem::transmute(force_to_skip) };
+            force_to_skip as u64
+        });
+        __bindgen_bitfield_unit.set(2usize, 1u8, {
+            let force_to_nonskip: u32 = unsafe { ::std::mem::transmute(force_to_nonskip) };
+            force_to_nonskip as u64
+        });
+        __bindgen_bitfield_unit.set(3usize, 1u8, {
+            let enable_direct_bias_adjustment: u32 =
+                unsafe { ::std::mem::transmute(enable_direct_bias_adjustment) };
+            enable_direct_bias_adjustment as u64
+        });
+        __bindgen_bitfield_unit.set(4usize, 1u8, {
+            let enable_motion_bias_adjustment: u32 =
+                unsafe { ::std::mem::transmute(enable_motion_bias_adjustment) };
+            enable_motion_bias_adjustment as u64
+        });
+        __bindgen_bitfield_unit.set(5usize, 3u8, {
+            let ext_mv_cost_scaling_factor: u32 =
+                unsafe { ::std::mem::transmute(ext_mv_cost_scaling_factor) };
+            ext_mv_cost_scaling_factor as u64
+        });
+        __bindgen_bitfield_unit.set(8usize, 24u8, {
+            let reserved0: u32 = unsafe { ::std::mem::transmute(reserved0) };
+            reserved0 as u64
+        });
+        __bindgen_bitfield_unit
+    }
+    #[inline]
+    pub fn reserved3(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_2.get(0usize, 16u8) as u32) }
+    }
+    #[inline]
+    pub fn set_reserved3(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_2.set(0usize, 16u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn target_size_in_word(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_2.get(16usize, 8u8) as u32) }
+    }
+    #[inline]
+    pub fn set_target_size_in_word(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_2.set(16usize, 8u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn max_size_in_word(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_2.get(24usize, 8u8) as u32) }
+    }
+    #[inline]
+    pub fn set_max_size_in_word(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_2.set(24usize, 8u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn new_bitfield_2(
+        reserved3: u32,
+        target_size_in_word: u32,
+        max_size_in_word: u32,
+    ) -> __BindgenBitfieldUnit<[u8; 4usize]> {
+        let mut __bindgen_bitfield_unit: __BindgenBitfieldUnit<[u8; 4usize]> = Default::default();
+        __bindgen_bitfield_unit.set(0usize, 16u8, {
+            let reserved3: u32 = unsafe { ::std::mem::transmute(reserved3) };
+            reserved3 as u64
+        });
+        __bindgen_bitfield_unit.set(16usize, 8u8, {
+            let target_size_in_word: u32 = unsafe { ::std::mem::transmute(target_size_in_word) };
+            target_size_in_word as u64
+        });
+        __bindgen_bitfield_unit.set(24usize, 8u8, {
+            let max_size_in_word: u32 = unsafe { ::std::mem::transmute(max_size_in_word) };
+            max_size_in_word as u64
+        });
+        __bindgen_bitfield_unit
+    }
+}
+pub type VAEncFEIMBControlH264 = _VAEncFEIMBControlH264;
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct _VAEncFEIMVPredictorH264 {
+    pub ref_idx: [_VAEncFEIMVPredictorH264__bindgen_ty_1; 4usize],
+    pub reserved: u32,
+    pub mv: [VAMotionVector; 4usize],
+}
+#[repr(C, packed)]
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct _VAEncFEIMVPredictorH264__bindgen_ty_1 {
+    pub _bitfield_align_1: [u8; 0],
+    pub _bitfield_1: __BindgenBitfieldUnit<[u8; 1usize]>,
+}
+impl _VAEncFEIMVPredictorH264__bindgen_ty_1 {
+    #[inline]
+    pub fn ref_idx_l0(&self) -> u8 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(0usize, 4u8) as u8) }
+    }
+    #[inline]
+    pub fn set_ref_idx_l0(&mut self, val: u8) {
+        unsafe {
+            let val: u8 = ::std::mem::transmute(val);
+            self._bitfield_1.set(0usize, 4u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn ref_idx_l1(&self) -> u8 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(4usize, 4u8) as u8) }
+    }
+    #[inline]
+    pub fn set_ref_idx_l1(&mut self, val: u8) {
+        unsafe {
+            let val: u8 = ::std::mem::transmute(val);
+            self._bitfield_1.set(4usize, 4u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn new_bitfield_1(ref_idx_l0: u8, ref_idx_l1: u8) -> __BindgenBitfieldUnit<[u8; 1usize]> {
+        let mut __bindgen_bitfield_unit: __BindgenBitfieldUnit<[u8; 1usize]> = Default::default();
+        __bindgen_bitfield_unit.set(0usize, 4u8, {
+            let ref_idx_l0: u8 = unsafe { ::std::mem::transmute(ref_idx_l0) };
+            ref_idx_l0 as u64
+        });
+        __bindgen_bitfield_unit.set(4usize, 4u8, {
+            let ref_idx_l1: u8 = unsafe { ::std::mem::transmute(ref_idx_l1) };
+            ref_idx_l1 as u64
+        });
+        __bindgen_bitfield_unit
+    }
+}
+pub type VAEncFEIMVPredictorH264 = _VAEncFEIMVPredictorH264;
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct _VAEncFEIMBCodeH264 {
+    pub reserved0: [u32; 3usize],
+    pub _bitfield_align_1: [u32; 0],
+    pub _bitfield_1: __BindgenBitfieldUnit<[u8; 16usize]>,
+    pub mb_mode: _VAEncFEIMBCodeH264__bindgen_ty_1,
+    pub _bitfield_align_2: [u16; 0],
+    pub _bitfield_2: __BindgenBitfieldUnit<[u8; 4usize]>,
+    pub reserved9: [u32; 4usize],
+    pub reserved10: u32,
+}
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub union _VAEncFEIMBCodeH264__bindgen_ty_1 {
+    pub intra_mb: _VAEncFEIMBCodeH264__bindgen_ty_1__bindgen_ty_1,
+    pub inter_mb: _VAEncFEIMBCodeH264__bindgen_ty_1__bindgen_ty_2,
+}
+#[repr(C)]
+#[repr(align(4))]
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct _VAEncFEIMBCodeH264__bindgen_ty_1__bindgen_ty_1 {
+    pub _bitfield_align_1: [u32; 0],
+    pub _bitfield_1: __BindgenBitfieldUnit<[u8; 12usize]>,
+}
+impl _VAEncFEIMBCodeH264__bindgen_ty_1__bindgen_ty_1 {
+    #[inline]
+    pub fn luma_intra_pred_modes0(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(0usize, 16u8) as u32) }
     }
     #[inline]
-    pub fn set_ref_last_idx(&mut self, val: u32) {
+    pub fn set_luma_intra_pred_modes0(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(7usize, 3u8, val as u64)
+            self._bitfield_1.set(0usize, 16u8, val as u64)
         }
     }
     #[inline]
-    pub fn ref_last_sign_bias(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(10usize, 1u8) as u32) }
+    pub fn luma_intra_pred_modes1(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(16usize, 16u8) as u32) }
     }
     #[inline]
-    pub fn set_ref_last_sign_bias(&mut self, val: u32) {
+    pub fn set_luma_intra_pred_modes1(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(10usize, 1u8, val as u64)
+            self._bitfield_1.set(16usize, 16u8, val as u64)
         }
     }
     #[inline]
-    pub fn ref_gf_idx(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(11usize, 3u8) as u32) }
+    pub fn luma_intra_pred_modes2(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(32usize, 16u8) as u32) }
     }
     #[inline]
-    pub fn set_ref_gf_idx(&mut self, val: u32) {
+    pub fn set_luma_intra_pred_modes2(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(11usize, 3u8, val as u64)
+            self._bitfield_1.set(32usize, 16u8, val as u64)
         }
     }
     #[inline]
-    pub fn ref_gf_sign_bias(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(14usize, 1u8) as u32) }
+    pub fn luma_intra_pred_modes3(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(48usize, 16u8) as u32) }
     }
     #[inline]
-    pub fn set_ref_gf_sign_bias(&mut self, val: u32) {
+    pub fn set_luma_intra_pred_modes3(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(14usize, 1u8, val as u64)
+            self._bitfield_1.set(48usize, 16u8, val as u64)
         }
     }
     #[inline]
-    pub fn ref_arf_idx(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(15usize, 3u8) as u32) }
+    pub fn chroma_intra_pred_mode(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(64usize, 2u8) as u32) }
     }
     #[inline]
-    pub fn set_ref_arf_idx(&mut self, val: u32) {
+    pub fn set_chroma_intra_pred_mode(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(15usize, 3u8, val as u64)
+            self._bitfield_1.set(64usize, 2u8, val as u64)
         }
     }
     #[inline]
-    pub fn ref_arf_sign_bias(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(18usize, 1u8) as u32) }
+    pub fn intra_pred_avail_flag(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(66usize, 5u8) as u32) }
     }
     #[inline]
-    pub fn set_ref_arf_sign_bias(&mut self, val: u32) {
+    pub fn set_intra_pred_avail_flag(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(18usize, 1u8, val as u64)
+            self._bitfield_1.set(66usize, 5u8, val as u64)
         }
     }
     #[inline]
-    pub fn temporal_id(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(19usize, 8u8) as u32) }
+    pub fn intra_pred_avail_flagF(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(71usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_temporal_id(&mut self, val: u32) {
+    pub fn set_intra_pred_avail_flagF(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(19usize, 8u8, val as u64)
+            self._bitfield_1.set(71usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn reserved(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(27usize, 5u8) as u32) }
+    pub fn reserved6(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(72usize, 24u8) as u32) }
     }
     #[inline]
-    pub fn set_reserved(&mut self, val: u32) {
+    pub fn set_reserved6(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(27usize, 5u8, val as u64)
+            self._bitfield_1.set(72usize, 24u8, val as u64)
         }
     }
     #[inline]
     pub fn new_bitfield_1(
-        force_kf: u32,
-        ref_frame_ctrl_l0: u32,
-        ref_frame_ctrl_l1: u32,
-        ref_last_idx: u32,
-        ref_last_sign_bias: u32,
-        ref_gf_idx: u32,
-        ref_gf_sign_bias: u32,
-        ref_arf_idx: u32,
-        ref_arf_sign_bias: u32,
-        temporal_id: u32,
-        reserved: u32,
-    ) -> __BindgenBitfieldUnit<[u8; 4usize]> {
-        let mut __bindgen_bitfield_unit: __BindgenBitfieldUnit<[u8; 4usize]> = Default::default();
-        __bindgen_bitfield_unit.set(0usize, 1u8, {
-            let force_kf: u32 = unsafe { ::std::mem::transmute(force_kf) };
-            force_kf as u64
-        });
-        __bindgen_bitfield_unit.set(1usize, 3u8, {
-            let ref_frame_ctrl_l0: u32 = unsafe { ::std::mem::transmute(ref_frame_ctrl_l0) };
-            ref_frame_ctrl_l0 as u64
-        });
-        __bindgen_bitfield_unit.set(4usize, 3u8, {
-            let ref_frame_ctrl_l1: u32 = unsafe { ::std::mem::transmute(ref_frame_ctrl_l1) };
-            ref_frame_ctrl_l1 as u64
-        });
-        __bindgen_bitfield_unit.set(7usize, 3u8, {
-            let ref_last_idx: u32 = unsafe { ::std::mem::transmute(ref_last_idx) };
-            ref_last_idx as u64
+        luma_intra_pred_modes0: u32,
+        luma_intra_pred_modes1: u32,
+        luma_intra_pred_modes2: u32,
+        luma_intra_pred_modes3: u32,
+        chroma_intra_pred_mode: u32,
+        intra_pred_avail_flag: u32,
+        intra_pred_avail_flagF: u32,
+        reserved6: u32,
+    ) -> __BindgenBitfieldUnit<[u8; 12usize]> {
+        let mut __bindgen_bitfield_unit: __BindgenBitfieldUnit<[u8; 12usize]> = Default::default();
+        __bindgen_bitfield_unit.set(0usize, 16u8, {
+            let luma_intra_pred_modes0: u32 =
+                unsafe { ::std::mem::transmute(luma_intra_pred_modes0) };
+            luma_intra_pred_modes0 as u64
         });
-        __bindgen_bitfield_unit.set(10usize, 1u8, {
-            let ref_last_sign_bias: u32 = unsafe { ::std::mem::transmute(ref_last_sign_bias) };
-            ref_last_sign_bias as u64
+        __bindgen_bitfield_unit.set(16usize, 16u8, {
+            let luma_intra_pred_modes1: u32 =
+                unsafe { ::std::mem::transmute(luma_intra_pred_modes1) };
+            luma_intra_pred_modes1 as u64
         });
-        __bindgen_bitfield_unit.set(11usize, 3u8, {
-            let ref_gf_idx: u32 = unsafe { ::std::mem::transmute(ref_gf_idx) };
-            ref_gf_idx as u64
+        __bindgen_bitfield_unit.set(32usize, 16u8, {
+            let luma_intra_pred_modes2: u32 =
+                unsafe { ::std::mem::transmute(luma_intra_pred_modes2) };
+            luma_intra_pred_modes2 as u64
         });
-        __bindgen_bitfield_unit.set(14usize, 1u8, {
-            let ref_gf_sign_bias: u32 = unsafe { ::std::mem::transmute(ref_gf_sign_bias) };
-            ref_gf_sign_bias as u64
+        __bindgen_bitfield_unit.set(48usize, 16u8, {
+            let luma_intra_pred_modes3: u32 =
+                unsafe { ::std::mem::transmute(luma_intra_pred_modes3) };
+            luma_intra_pred_modes3 as u64
         });
-        __bindgen_bitfield_unit.set(15usize, 3u8, {
-            let ref_arf_idx: u32 = unsafe { ::std::mem::transmute(ref_arf_idx) };
-            ref_arf_idx as u64
+        __bindgen_bitfield_unit.set(64usize, 2u8, {
+            let chroma_intra_pred_mode: u32 =
+                unsafe { ::std::mem::transmute(chroma_intra_pred_mode) };
+            chroma_intra_pred_mode as u64
         });
-        __bindgen_bitfield_unit.set(18usize, 1u8, {
-            let ref_arf_sign_bias: u32 = unsafe { ::std::mem::transmute(ref_arf_sign_bias) };
-            ref_arf_sign_bias as u64
+        __bindgen_bitfield_unit.set(66usize, 5u8, {
+            let intra_pred_avail_flag: u32 =
+                unsafe { ::std::mem::transmute(intra_pred_avail_flag) };
+            intra_pred_avail_flag as u64
         });
-        __bindgen_bitfield_unit.set(19usize, 8u8, {
-            let temporal_id: u32 = unsafe { ::std::mem::transmute(temporal_id) };
-            temporal_id as u64
+        __bindgen_bitfield_unit.set(71usize, 1u8, {
+            let intra_pred_avail_flagF: u32 =
+                unsafe { ::std::mem::transmute(intra_pred_avail_flagF) };
+            intra_pred_avail_flagF as u64
         });
-        __bindgen_bitfield_unit.set(27usize, 5u8, {
-            let reserved: u32 = unsafe { ::std::mem::transmute(reserved) };
-            reserved as u64
+        __bindgen_bitfield_unit.set(72usize, 24u8, {
+            let reserved6: u32 = unsafe { ::std::mem::transmute(reserved6) };
+            reserved6 as u64
         });
         __bindgen_bitfield_unit
     }
 }
-impl Default for _VAEncPictureParameterBufferVP9__bindgen_ty_1 {
-    fn default() -> Self {
-        let mut s = ::std::mem::MaybeUninit::<Self>::uninit();
-        unsafe {
-            ::std::ptr::write_bytes(s.as_mut_ptr(), 0, 1);
-            s.assume_init()
-        }
-    }
-}
-#[repr(C)]
-#[derive(Copy, Clone)]
-pub union _VAEncPictureParameterBufferVP9__bindgen_ty_2 {
-    pub bits: _VAEncPictureParameterBufferVP9__bindgen_ty_2__bindgen_ty_1,
-    pub value: u32,
-}
 #[repr(C)]
 #[repr(align(4))]
 #[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
-pub struct _VAEncPictureParameterBufferVP9__bindgen_ty_2__bindgen_ty_1 {
+pub struct _VAEncFEIMBCodeH264__bindgen_ty_1__bindgen_ty_2 {
     pub _bitfield_align_1: [u16; 0],
-    pub _bitfield_1: __BindgenBitfieldUnit<[u8; 4usize]>,
+    pub _bitfield_1: __BindgenBitfieldUnit<[u8; 12usize]>,
 }
-impl _VAEncPictureParameterBufferVP9__bindgen_ty_2__bindgen_ty_1 {
+impl _VAEncFEIMBCodeH264__bindgen_ty_1__bindgen_ty_2 {
     #[inline]
-    pub fn frame_type(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(0usize, 1u8) as u32) }
+    pub fn sub_mb_shapes(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(0usize, 8u8) as u32) }
     }
     #[inline]
-    pub fn set_frame_type(&mut self, val: u32) {
+    pub fn set_sub_mb_shapes(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(0usize, 1u8, val as u64)
+            self._bitfield_1.set(0usize, 8u8, val as u64)
         }
     }
     #[inline]
-    pub fn show_frame(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(1usize, 1u8) as u32) }
+    pub fn sub_mb_pred_modes(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(8usize, 8u8) as u32) }
     }
     #[inline]
-    pub fn set_show_frame(&mut self, val: u32) {
+    pub fn set_sub_mb_pred_modes(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(1usize, 1u8, val as u64)
+            self._bitfield_1.set(8usize, 8u8, val as u64)
         }
     }
     #[inline]
-    pub fn error_resilient_mode(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(2usize, 1u8) as u32) }
+    pub fn reserved7(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(16usize, 16u8) as u32) }
     }
     #[inline]
-    pub fn set_error_resilient_mode(&mut self, val: u32) {
+    pub fn set_reserved7(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(2usize, 1u8, val as u64)
+            self._bitfield_1.set(16usize, 16u8, val as u64)
         }
     }
     #[inline]
-    pub fn intra_only(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(3usize, 1u8) as u32) }
+    pub fn ref_idx_l0_0(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(32usize, 8u8) as u32) }
     }
     #[inline]
-    pub fn set_intra_only(&mut self, val: u32) {
+    pub fn set_ref_idx_l0_0(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(3usize, 1u8, val as u64)
+            self._bitfield_1.set(32usize, 8u8, val as u64)
         }
     }
     #[inline]
-    pub fn allow_high_precision_mv(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(4usize, 1u8) as u32) }
+    pub fn ref_idx_l0_1(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(40usize, 8u8) as u32) }
     }
     #[inline]
-    pub fn set_allow_high_precision_mv(&mut self, val: u32) {
+    pub fn set_ref_idx_l0_1(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(4usize, 1u8, val as u64)
+            self._bitfield_1.set(40usize, 8u8, val as u64)
         }
     }
     #[inline]
-    pub fn mcomp_filter_type(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(5usize, 3u8) as u32) }
+    pub fn ref_idx_l0_2(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(48usize, 8u8) as u32) }
     }
     #[inline]
-    pub fn set_mcomp_filter_type(&mut self, val: u32) {
+    pub fn set_ref_idx_l0_2(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(5usize, 3u8, val as u64)
+            self._bitfield_1.set(48usize, 8u8, val as u64)
         }
     }
     #[inline]
-    pub fn frame_parallel_decoding_mode(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(8usize, 1u8) as u32) }
+    pub fn ref_idx_l0_3(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(56usize, 8u8) as u32) }
     }
     #[inline]
-    pub fn set_frame_parallel_decoding_mode(&mut self, val: u32) {
+    pub fn set_ref_idx_l0_3(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(8usize, 1u8, val as u64)
+            self._bitfield_1.set(56usize, 8u8, val as u64)
         }
     }
     #[inline]
-    pub fn reset_frame_context(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(9usize, 2u8) as u32) }
+    pub fn ref_idx_l1_0(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(64usize, 8u8) as u32) }
     }
     #[inline]
-    pub fn set_reset_frame_context(&mut self, val: u32) {
+    pub fn set_ref_idx_l1_0(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(9usize, 2u8, val as u64)
+            self._bitfield_1.set(64usize, 8u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn ref_idx_l1_1(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(72usize, 8u8) as u32) }
+    }
+    #[inline]
+    pub fn set_ref_idx_l1_1(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(72usize, 8u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn ref_idx_l1_2(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(80usize, 8u8) as u32) }
+    }
+    #[inline]
+    pub fn set_ref_idx_l1_2(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(80usize, 8u8, val as u64)
         }
     }
     #[inline]
-    pub fn refresh_frame_context(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(11usize, 1u8) as u32) }
+    pub fn ref_idx_l1_3(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(88usize, 8u8) as u32) }
     }
     #[inline]
-    pub fn set_refresh_frame_context(&mut self, val: u32) {
+    pub fn set_ref_idx_l1_3(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(11usize, 1u8, val as u64)
+            self._bitfield_1.set(88usize, 8u8, val as u64)
         }
     }
     #[inline]
-    pub fn frame_context_idx(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(12usize, 2u8) as u32) }
+    pub fn new_bitfield_1(
+        sub_mb_shapes: u32,
+        sub_mb_pred_modes: u32,
+        reserved7: u32,
+        ref_idx_l0_0: u32,
+        ref_idx_l0_1: u32,
+        ref_idx_l0_2: u32,
+        ref_idx_l0_3: u32,
+        ref_idx_l1_0: u32,
+        ref_idx_l1_1: u32,
+        ref_idx_l1_2: u32,
+        ref_idx_l1_3: u32,
+    ) -> __BindgenBitfieldUnit<[u8; 12usize]> {
+        let mut __bindgen_bitfield_unit: __BindgenBitfieldUnit<[u8; 12usize]> = Default::default();
+        __bindgen_bitfield_unit.set(0usize, 8u8, {
+            let sub_mb_shapes: u32 = unsafe { ::std::mem::transmute(sub_mb_shapes) };
+            sub_mb_shapes as u64
+        });
+        __bindgen_bitfield_unit.set(8usize, 8u8, {
+            let sub_mb_pred_modes: u32 = unsafe { ::std::mem::transmute(sub_mb_pred_modes) };
+            sub_mb_pred_modes as u64
+        });
+        __bindgen_bitfield_unit.set(16usize, 16u8, {
+            let reserved7: u32 = unsafe { ::std::mem::transmute(reserved7) };
+            reserved7 as u64
+        });
+        __bindgen_bitfield_unit.set(32usize, 8u8, {
+            let ref_idx_l0_0: u32 = unsafe { ::std::mem::transmute(ref_idx_l0_0) };
+            ref_idx_l0_0 as u64
+        });
+        __bindgen_bitfield_unit.set(40usize, 8u8, {
+            let ref_idx_l0_1: u32 = unsafe { ::std::mem::transmute(ref_idx_l0_1) };
+            ref_idx_l0_1 as u64
+        });
+        __bindgen_bitfield_unit.set(48usize, 8u8, {
+            let ref_idx_l0_2: u32 = unsafe { ::std::mem::transmute(ref_idx_l0_2) };
+            ref_idx_l0_2 as u64
+        });
+        __bindgen_bitfield_unit.set(56usize, 8u8, {
+            let ref_idx_l0_3: u32 = unsafe { ::std::mem::transmute(ref_idx_l0_3) };
+            ref_idx_l0_3 as u64
+        });
+        __bindgen_bitfield_unit.set(64usize, 8u8, {
+            let ref_idx_l1_0: u32 = unsafe { ::std::mem::transmute(ref_idx_l1_0) };
+            ref_idx_l1_0 as u64
+        });
+        __bindgen_bitfield_unit.set(72usize, 8u8, {
+            let ref_idx_l1_1: u32 = unsafe { ::std::mem::transmute(ref_idx_l1_1) };
+            ref_idx_l1_1 as u64
+        });
+        __bindgen_bitfield_unit.set(80usize, 8u8, {
+            let ref_idx_l1_2: u32 = unsafe { ::std::mem::transmute(ref_idx_l1_2) };
+            ref_idx_l1_2 as u64
+        });
+        __bindgen_bitfield_unit.set(88usize, 8u8, {
+            let ref_idx_l1_3: u32 = unsafe { ::std::mem::transmute(ref_idx_l1_3) };
+            ref_idx_l1_3 as u64
+        });
+        __bindgen_bitfield_unit
     }
-    #[inline]
-    pub fn set_frame_context_idx(&mut self, val: u32) {
+}
+impl Default for _VAEncFEIMBCodeH264__bindgen_ty_1 {
+    fn default() -> Self {
+        let mut s = ::std::mem::MaybeUninit::<Self>::uninit();
         unsafe {
-            let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(12usize, 2u8, val as u64)
+            ::std::ptr::write_bytes(s.as_mut_ptr(), 0, 1);
+            s.assume_init()
         }
     }
+}
+impl Default for _VAEncFEIMBCodeH264 {
+    fn default() -> Self {
+        let mut s = ::std::mem::MaybeUninit::<Self>::uninit();
+        unsafe {
+            ::std::ptr::write_bytes(s.as_mut_ptr(), 0, 1);
+            s.assume_init()
+        }
+    }
+}
+impl _VAEncFEIMBCodeH264 {
     #[inline]
-    pub fn segmentation_enabled(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(14usize, 1u8) as u32) }
+    pub fn inter_mb_mode(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(0usize, 2u8) as u32) }
     }
     #[inline]
-    pub fn set_segmentation_enabled(&mut self, val: u32) {
+    pub fn set_inter_mb_mode(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(14usize, 1u8, val as u64)
+            self._bitfield_1.set(0usize, 2u8, val as u64)
         }
     }
     #[inline]
-    pub fn segmentation_temporal_update(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(15usize, 1u8) as u32) }
+    pub fn mb_skip_flag(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(2usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_segmentation_temporal_update(&mut self, val: u32) {
+    pub fn set_mb_skip_flag(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(15usize, 1u8, val as u64)
+            self._bitfield_1.set(2usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn segmentation_update_map(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(16usize, 1u8) as u32) }
+    pub fn reserved1(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(3usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_segmentation_update_map(&mut self, val: u32) {
+    pub fn set_reserved1(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(16usize, 1u8, val as u64)
+            self._bitfield_1.set(3usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn lossless_mode(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(17usize, 1u8) as u32) }
+    pub fn intra_mb_mode(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(4usize, 2u8) as u32) }
     }
     #[inline]
-    pub fn set_lossless_mode(&mut self, val: u32) {
+    pub fn set_intra_mb_mode(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(17usize, 1u8, val as u64)
+            self._bitfield_1.set(4usize, 2u8, val as u64)
         }
     }
     #[inline]
-    pub fn comp_prediction_mode(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(18usize, 2u8) as u32) }
+    pub fn reserved2(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(6usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_comp_prediction_mode(&mut self, val: u32) {
+    pub fn set_reserved2(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(18usize, 2u8, val as u64)
+            self._bitfield_1.set(6usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn auto_segmentation(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(20usize, 1u8) as u32) }
+    pub fn field_mb_polarity_flag(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(7usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_auto_segmentation(&mut self, val: u32) {
+    pub fn set_field_mb_polarity_flag(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(20usize, 1u8, val as u64)
+            self._bitfield_1.set(7usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn super_frame_flag(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(21usize, 1u8) as u32) }
+    pub fn mb_type(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(8usize, 5u8) as u32) }
     }
     #[inline]
-    pub fn set_super_frame_flag(&mut self, val: u32) {
+    pub fn set_mb_type(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(21usize, 1u8, val as u64)
+            self._bitfield_1.set(8usize, 5u8, val as u64)
         }
     }
     #[inline]
-    pub fn reserved(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(22usize, 10u8) as u32) }
+    pub fn intra_mb_flag(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(13usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_reserved(&mut self, val: u32) {
+    pub fn set_intra_mb_flag(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(22usize, 10u8, val as u64)
+            self._bitfield_1.set(13usize, 1u8, val as u64)
         }
     }
-    #[inline]
-    pub fn new_bitfield_1(
-        frame_type: u32,
-        show_frame: u32,
-        error_resilient_mode: u32,
-        intra_only: u32,
-        allow_high_precision_mv: u32,
-        mcomp_filter_type: u32,
-        frame_parallel_decoding_mode: u32,
-        reset_frame_context: u32,
-        refresh_frame_context: u32,
-        frame_context_idx: u32,
-        segmentation_enabled: u32,
-        segmentation_temporal_update: u32,
-        segmentation_update_map: u32,
-        lossless_mode: u32,
-        comp_prediction_mode: u32,
-        auto_segmentation: u32,
-        super_frame_flag: u32,
-        reserved: u32,
-    ) -> __BindgenBitfieldUnit<[u8; 4usize]> {
-        let mut __bindgen_bitfield_unit: __BindgenBitfieldUnit<[u8; 4usize]> = Default::default();
-        __bindgen_bitfield_unit.set(0usize, 1u8, {
-            let frame_type: u32 = unsafe { ::std::mem::transmute(frame_type) };
-            frame_type as u64
-        });
-        __bindgen_bitfield_unit.set(1usize, 1u8, {
-            let show_frame: u32 = unsafe { ::std::mem::transmute(show_frame) };
-            show_frame as u64
-        });
-        __bindgen_bitfield_unit.set(2usize, 1u8, {
-            let error_resilient_mode: u32 = unsafe { ::std::mem::transmute(error_resilient_mode) };
-            error_resilient_mode as u64
-        });
-        __bindgen_bitfield_unit.set(3usize, 1u8, {
-            let intra_only: u32 = unsafe { ::std::mem::transmute(intra_only) };
-            intra_only as u64
-        });
-        __bindgen_bitfield_unit.set(4usize, 1u8, {
-            let allow_high_precision_mv: u32 =
-                unsafe { ::std::mem::transmute(allow_high_precision_mv) };
-            allow_high_precision_mv as u64
-        });
-        __bindgen_bitfield_unit.set(5usize, 3u8, {
-            let mcomp_filter_type: u32 = unsafe { ::std::mem::transmute(mcomp_filter_type) };
-            mcomp_filter_type as u64
-        });
-        __bindgen_bitfield_unit.set(8usize, 1u8, {
-            let frame_parallel_decoding_mode: u32 =
-                unsafe { ::std::mem::transmute(frame_parallel_decoding_mode) };
-            frame_parallel_decoding_mode as u64
-        });
-        __bindgen_bitfield_unit.set(9usize, 2u8, {
-            let reset_frame_context: u32 = unsafe { ::std::mem::transmute(reset_frame_context) };
-            reset_frame_context as u64
-        });
-        __bindgen_bitfield_unit.set(11usize, 1u8, {
-            let refresh_frame_context: u32 =
-                unsafe { ::std::mem::transmute(refresh_frame_context) };
-            refresh_frame_context as u64
-        });
-        __bindgen_bitfield_unit.set(12usize, 2u8, {
-            let frame_context_idx: u32 = unsafe { ::std::mem::transmute(frame_context_idx) };
-            frame_context_idx as u64
-        });
-        __bindgen_bitfield_unit.set(14usize, 1u8, {
-            let segmentation_enabled: u32 = unsafe { ::std::mem::transmute(segmentation_enabled) };
-            segmentation_enabled as u64
-        });
-        __bindgen_bitfield_unit.set(15usize, 1u8, {
-            let segmentation_temporal_update: u32 =
-                unsafe { ::std::mem::transmute(segmentation_temporal_update) };
-            segmentation_temporal_update as u64
-        });
-        __bindgen_bitfield_unit.set(16usize, 1u8, {
-            let segmentation_update_map: u32 =
-                unsafe { ::std::mem::transmute(segmentation_update_map) };
-            segmentation_update_map as u64
-        });
-        __bindgen_bitfield_unit.set(17usize, 1u8, {
-            let lossless_mode: u32 = unsafe { ::std::mem::transmute(lossless_mode) };
-            lossless_mode as u64
-        });
-        __bindgen_bitfield_unit.set(18usize, 2u8, {
-            let comp_prediction_mode: u32 = unsafe { ::std::mem::transmute(comp_prediction_mode) };
-            comp_prediction_mode as u64
-        });
-        __bindgen_bitfield_unit.set(20usize, 1u8, {
-            let auto_segmentation: u32 = unsafe { ::std::mem::transmute(auto_segmentation) };
-            auto_segmentation as u64
-        });
-        __bindgen_bitfield_unit.set(21usize, 1u8, {
-            let super_frame_flag: u32 = unsafe { ::std::mem::transmute(super_frame_flag) };
-            super_frame_flag as u64
-        });
-        __bindgen_bitfield_unit.set(22usize, 10u8, {
-            let reserved: u32 = unsafe { ::std::mem::transmute(reserved) };
-            reserved as u64
-        });
-        __bindgen_bitfield_unit
-    }
-}
-impl Default for _VAEncPictureParameterBufferVP9__bindgen_ty_2 {
-    fn default() -> Self {
-        let mut s = ::std::mem::MaybeUninit::<Self>::uninit();
+    #[inline]
+    pub fn field_mb_flag(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(14usize, 1u8) as u32) }
+    }
+    #[inline]
+    pub fn set_field_mb_flag(&mut self, val: u32) {
         unsafe {
-            ::std::ptr::write_bytes(s.as_mut_ptr(), 0, 1);
-            s.assume_init()
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(14usize, 1u8, val as u64)
         }
     }
-}
-impl Default for _VAEncPictureParameterBufferVP9 {
-    fn default() -> Self {
-        let mut s = ::std::mem::MaybeUninit::<Self>::uninit();
+    #[inline]
+    pub fn transform8x8_flag(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(15usize, 1u8) as u32) }
+    }
+    #[inline]
+    pub fn set_transform8x8_flag(&mut self, val: u32) {
         unsafe {
-            ::std::ptr::write_bytes(s.as_mut_ptr(), 0, 1);
-            s.assume_init()
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(15usize, 1u8, val as u64)
         }
     }
-}
-pub type VAEncPictureParameterBufferVP9 = _VAEncPictureParameterBufferVP9;
-#[repr(C)]
-#[derive(Copy, Clone)]
-pub struct _VAEncSegParamVP9 {
-    pub seg_flags: _VAEncSegParamVP9__bindgen_ty_1,
-    pub segment_lf_level_delta: i8,
-    pub segment_qindex_delta: i16,
-    pub va_reserved: [u32; 4usize],
-}
-#[repr(C)]
-#[derive(Copy, Clone)]
-pub union _VAEncSegParamVP9__bindgen_ty_1 {
-    pub bits: _VAEncSegParamVP9__bindgen_ty_1__bindgen_ty_1,
-    pub value: u8,
-}
-#[repr(C, packed)]
-#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
-pub struct _VAEncSegParamVP9__bindgen_ty_1__bindgen_ty_1 {
-    pub _bitfield_align_1: [u8; 0],
-    pub _bitfield_1: __BindgenBitfieldUnit<[u8; 1usize]>,
-}
-impl _VAEncSegParamVP9__bindgen_ty_1__bindgen_ty_1 {
     #[inline]
-    pub fn segment_reference_enabled(&self) -> u8 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(0usize, 1u8) as u8) }
+    pub fn reserved3(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(16usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_segment_reference_enabled(&mut self, val: u8) {
+    pub fn set_reserved3(&mut self, val: u32) {
         unsafe {
-            let val: u8 = ::std::mem::transmute(val);
-            self._bitfield_1.set(0usize, 1u8, val as u64)
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(16usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn segment_reference(&self) -> u8 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(1usize, 2u8) as u8) }
+    pub fn dc_block_coded_cr_flag(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(17usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_segment_reference(&mut self, val: u8) {
+    pub fn set_dc_block_coded_cr_flag(&mut self, val: u32) {
         unsafe {
-            let val: u8 = ::std::mem::transmute(val);
-            self._bitfield_1.set(1usize, 2u8, val as u64)
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(17usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn segment_reference_skipped(&self) -> u8 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(3usize, 1u8) as u8) }
+    pub fn dc_block_coded_cb_flag(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(18usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_segment_reference_skipped(&mut self, val: u8) {
+    pub fn set_dc_block_coded_cb_flag(&mut self, val: u32) {
         unsafe {
-            let val: u8 = ::std::mem::transmute(val);
-            self._bitfield_1.set(3usize, 1u8, val as u64)
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(18usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn reserved(&self) -> u8 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(4usize, 4u8) as u8) }
+    pub fn dc_block_coded_y_flag(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(19usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_reserved(&mut self, val: u8) {
+    pub fn set_dc_block_coded_y_flag(&mut self, val: u32) {
         unsafe {
-            let val: u8 = ::std::mem::transmute(val);
-            self._bitfield_1.set(4usize, 4u8, val as u64)
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(19usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn new_bitfield_1(
-        segment_reference_enabled: u8,
-        segment_reference: u8,
-        segment_reference_skipped: u8,
-        reserved: u8,
-    ) -> __BindgenBitfieldUnit<[u8; 1usize]> {
-        let mut __bindgen_bitfield_unit: __BindgenBitfieldUnit<[u8; 1usize]> = Default::default();
-        __bindgen_bitfield_unit.set(0usize, 1u8, {
-            let segment_reference_enabled: u8 =
-                unsafe { ::std::mem::transmute(segment_reference_enabled) };
-            segment_reference_enabled as u64
-        });
-        __bindgen_bitfield_unit.set(1usize, 2u8, {
-            let segment_reference: u8 = unsafe { ::std::mem::transmute(segment_reference) };
-            segment_reference as u64
-        });
-        __bindgen_bitfield_unit.set(3usize, 1u8, {
-            let segment_reference_skipped: u8 =
-                unsafe { ::std::mem::transmute(segment_reference_skipped) };
-            segment_reference_skipped as u64
-        });
-        __bindgen_bitfield_unit.set(4usize, 4u8, {
-            let reserved: u8 = unsafe { ::std::mem::transmute(reserved) };
-            reserved as u64
-        });
-        __bindgen_bitfield_unit
+    pub fn reserved4(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(20usize, 12u8) as u32) }
     }
-}
-impl Default for _VAEncSegParamVP9__bindgen_ty_1 {
-    fn default() -> Self {
-        let mut s = ::std::mem::MaybeUninit::<Self>::uninit();
+    #[inline]
+    pub fn set_reserved4(&mut self, val: u32) {
         unsafe {
-            ::std::ptr::write_bytes(s.as_mut_ptr(), 0, 1);
-            s.assume_init()
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(20usize, 12u8, val as u64)
         }
     }
-}
-impl Default for _VAEncSegParamVP9 {
-    fn default() -> Self {
-        let mut s = ::std::mem::MaybeUninit::<Self>::uninit();
+    #[inline]
+    pub fn horz_origin(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(32usize, 8u8) as u32) }
+    }
+    #[inline]
+    pub fn set_horz_origin(&mut self, val: u32) {
         unsafe {
-            ::std::ptr::write_bytes(s.as_mut_ptr(), 0, 1);
-            s.assume_init()
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(32usize, 8u8, val as u64)
         }
     }
-}
-pub type VAEncSegParamVP9 = _VAEncSegParamVP9;
-#[repr(C)]
-#[derive(Copy, Clone)]
-pub struct _VAEncMiscParameterTypeVP9PerSegmantParam {
-    pub seg_data: [VAEncSegParamVP9; 8usize],
-    pub va_reserved: [u32; 4usize],
-}
-impl Default for _VAEncMiscParameterTypeVP9PerSegmantParam {
-    fn default() -> Self {
-        let mut s = ::std::mem::MaybeUninit::<Self>::uninit();
+    #[inline]
+    pub fn vert_origin(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(40usize, 8u8) as u32) }
+    }
+    #[inline]
+    pub fn set_vert_origin(&mut self, val: u32) {
         unsafe {
-            ::std::ptr::write_bytes(s.as_mut_ptr(), 0, 1);
-            s.assume_init()
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(40usize, 8u8, val as u64)
         }
     }
-}
-pub type VAEncMiscParameterTypeVP9PerSegmantParam = _VAEncMiscParameterTypeVP9PerSegmantParam;
-#[repr(C)]
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
-pub struct _VAEncMiscParameterFEIFrameControlH264 {
-    pub function: u32,
-    pub mb_ctrl: VABufferID,
-    pub distortion: VABufferID,
-    pub mv_data: VABufferID,
-    pub mb_code_data: VABufferID,
-    pub qp: VABufferID,
-    pub mv_predictor: VABufferID,
-    pub _bitfield_align_1: [u16; 0],
-    pub _bitfield_1: __BindgenBitfieldUnit<[u8; 16usize]>,
-    pub max_frame_size: u32,
-    pub num_passes: u32,
-    pub delta_qp: *mut u8,
-    pub reserved3: [u32; 4usize],
-}
-impl Default for _VAEncMiscParameterFEIFrameControlH264 {
-    fn default() -> Self {
-        let mut s = ::std::mem::MaybeUninit::<Self>::uninit();
+    #[inline]
+    pub fn cbp_y(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(48usize, 16u8) as u32) }
+    }
+    #[inline]
+    pub fn set_cbp_y(&mut self, val: u32) {
         unsafe {
-            ::std::ptr::write_bytes(s.as_mut_ptr(), 0, 1);
-            s.assume_init()
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(48usize, 16u8, val as u64)
         }
     }
-}
-impl _VAEncMiscParameterFEIFrameControlH264 {
     #[inline]
-    pub fn num_mv_predictors_l0(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(0usize, 16u8) as u32) }
+    pub fn cbp_cb(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(64usize, 16u8) as u32) }
     }
     #[inline]
-    pub fn set_num_mv_predictors_l0(&mut self, val: u32) {
+    pub fn set_cbp_cb(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(0usize, 16u8, val as u64)
+            self._bitfield_1.set(64usize, 16u8, val as u64)
         }
     }
     #[inline]
-    pub fn num_mv_predictors_l1(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(16usize, 16u8) as u32) }
+    pub fn cbp_cr(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(80usize, 16u8) as u32) }
     }
     #[inline]
-    pub fn set_num_mv_predictors_l1(&mut self, val: u32) {
+    pub fn set_cbp_cr(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(16usize, 16u8, val as u64)
+            self._bitfield_1.set(80usize, 16u8, val as u64)
         }
     }
     #[inline]
-    pub fn search_path(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(32usize, 8u8) as u32) }
+    pub fn qp_prime_y(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(96usize, 8u8) as u32) }
     }
     #[inline]
-    pub fn set_search_path(&mut self, val: u32) {
+    pub fn set_qp_prime_y(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(32usize, 8u8, val as u64)
+            self._bitfield_1.set(96usize, 8u8, val as u64)
         }
     }
     #[inline]
-    pub fn len_sp(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(40usize, 8u8) as u32) }
+    pub fn reserved5(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(104usize, 17u8) as u32) }
     }
     #[inline]
-    pub fn set_len_sp(&mut self, val: u32) {
+    pub fn set_reserved5(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(40usize, 8u8, val as u64)
+            self._bitfield_1.set(104usize, 17u8, val as u64)
         }
     }
     #[inline]
-    pub fn reserved0(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(48usize, 16u8) as u32) }
+    pub fn mb_skip_conv_disable(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(121usize, 1u8) as u32) }
+    }
+    #[inline]
+    pub fn set_mb_skip_conv_disable(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(121usize, 1u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn is_last_mb(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(122usize, 1u8) as u32) }
+    }
+    #[inline]
+    pub fn set_is_last_mb(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(122usize, 1u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn enable_coefficient_clamp(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(123usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_reserved0(&mut self, val: u32) {
+    pub fn set_enable_coefficient_clamp(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(48usize, 16u8, val as u64)
+            self._bitfield_1.set(123usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn sub_mb_part_mask(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(64usize, 7u8) as u32) }
+    pub fn direct8x8_pattern(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(124usize, 4u8) as u32) }
     }
     #[inline]
-    pub fn set_sub_mb_part_mask(&mut self, val: u32) {
+    pub fn set_direct8x8_pattern(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(64usize, 7u8, val as u64)
+            self._bitfield_1.set(124usize, 4u8, val as u64)
         }
     }
     #[inline]
-    pub fn intra_part_mask(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(71usize, 5u8) as u32) }
+    pub fn new_bitfield_1(
+        inter_mb_mode: u32,
+        mb_skip_flag: u32,
+        reserved1: u32,
+        intra_mb_mode: u32,
+        reserved2: u32,
+        field_mb_polarity_flag: u32,
+        mb_type: u32,
+        intra_mb_flag: u32,
+        field_mb_flag: u32,
+        transform8x8_flag: u32,
+        reserved3: u32,
+        dc_block_coded_cr_flag: u32,
+        dc_block_coded_cb_flag: u32,
+        dc_block_coded_y_flag: u32,
+        reserved4: u32,
+        horz_origin: u32,
+        vert_origin: u32,
+        cbp_y: u32,
+        cbp_cb: u32,
+        cbp_cr: u32,
+        qp_prime_y: u32,
+        reserved5: u32,
+        mb_skip_conv_disable: u32,
+        is_last_mb: u32,
+        enable_coefficient_clamp: u32,
+        direct8x8_pattern: u32,
+    ) -> __BindgenBitfieldUnit<[u8; 16usize]> {
+        let mut __bindgen_bitfield_unit: __BindgenBitfieldUnit<[u8; 16usize]> = Default::default();
+        __bindgen_bitfield_unit.set(0usize, 2u8, {
+            let inter_mb_mode: u32 = unsafe { ::std::mem::transmute(inter_mb_mode) };
+            inter_mb_mode as u64
+        });
+        __bindgen_bitfield_unit.set(2usize, 1u8, {
+            let mb_skip_flag: u32 = unsafe { ::std::mem::transmute(mb_skip_flag) };
+            mb_skip_flag as u64
+        });
+        __bindgen_bitfield_unit.set(3usize, 1u8, {
+            let reserved1: u32 = unsafe { ::std::mem::transmute(reserved1) };
+            reserved1 as u64
+        });
+        __bindgen_bitfield_unit.set(4usize, 2u8, {
+            let intra_mb_mode: u32 = unsafe { ::std::mem::transmute(intra_mb_mode) };
+            intra_mb_mode as u64
+        });
+        __bindgen_bitfield_unit.set(6usize, 1u8, {
+            let reserved2: u32 = unsafe { ::std::mem::transmute(reserved2) };
+            reserved2 as u64
+        });
+        __bindgen_bitfield_unit.set(7usize, 1u8, {
+            let field_mb_polarity_flag: u32 =
+                unsafe { ::std::mem::transmute(field_mb_polarity_flag) };
+            field_mb_polarity_flag as u64
+        });
+        __bindgen_bitfield_unit.set(8usize, 5u8, {
+            let mb_type: u32 = unsafe { ::std::mem::transmute(mb_type) };
+            mb_type as u64
+        });
+        __bindgen_bitfield_unit.set(13usize, 1u8, {
+            let intra_mb_flag: u32 = unsafe { ::std::mem::transmute(intra_mb_flag) };
+            intra_mb_flag as u64
+        });
+        __bindgen_bitfield_unit.set(14usize, 1u8, {
+            let field_mb_flag: u32 = unsafe { ::std::mem::transmute(field_mb_flag) };
+            field_mb_flag as u64
+        });
+        __bindgen_bitfield_unit.set(15usize, 1u8, {
+            let transform8x8_flag: u32 = unsafe { ::std::mem::transmute(transform8x8_flag) };
+            transform8x8_flag as u64
+        });
+        __bindgen_bitfield_unit.set(16usize, 1u8, {
+            let reserved3: u32 = unsafe { ::std::mem::transmute(reserved3) };
+            reserved3 as u64
+        });
+        __bindgen_bitfield_unit.set(17usize, 1u8, {
+            let dc_block_coded_cr_flag: u32 =
+                unsafe { ::std::mem::transmute(dc_block_coded_cr_flag) };
+            dc_block_coded_cr_flag as u64
+        });
+        __bindgen_bitfield_unit.set(18usize, 1u8, {
+            let dc_block_coded_cb_flag: u32 =
+                unsafe { ::std::mem::transmute(dc_block_coded_cb_flag) };
+            dc_block_coded_cb_flag as u64
+        });
+        __bindgen_bitfield_unit.set(19usize, 1u8, {
+            let dc_block_coded_y_flag: u32 =
+                unsafe { ::std::mem::transmute(dc_block_coded_y_flag) };
+            dc_block_coded_y_flag as u64
+        });
+        __bindgen_bitfield_unit.set(20usize, 12u8, {
+            let reserved4: u32 = unsafe { ::std::mem::transmute(reserved4) };
+            reserved4 as u64
+        });
+        __bindgen_bitfield_unit.set(32usize, 8u8, {
+            let horz_origin: u32 = unsafe { ::std::mem::transmute(horz_origin) };
+            horz_origin as u64
+        });
+        __bindgen_bitfield_unit.set(40usize, 8u8, {
+            let vert_origin: u32 = unsafe { ::std::mem::transmute(vert_origin) };
+            vert_origin as u64
+        });
+        __bindgen_bitfield_unit.set(48usize, 16u8, {
+            let cbp_y: u32 = unsafe { ::std::mem::transmute(cbp_y) };
+            cbp_y as u64
+        });
+        __bindgen_bitfield_unit.set(64usize, 16u8, {
+            let cbp_cb: u32 = unsafe { ::std::mem::transmute(cbp_cb) };
+            cbp_cb as u64
+        });
+        __bindgen_bitfield_unit.set(80usize, 16u8, {
+            let cbp_cr: u32 = unsafe { ::std::mem::transmute(cbp_cr) };
+            cbp_cr as u64
+        });
+        __bindgen_bitfield_unit.set(96usize, 8u8, {
+            let qp_prime_y: u32 = unsafe { ::std::mem::transmute(qp_prime_y) };
+            qp_prime_y as u64
+        });
+        __bindgen_bitfield_unit.set(104usize, 17u8, {
+            let reserved5: u32 = unsafe { ::std::mem::transmute(reserved5) };
+            reserved5 as u64
+        });
+        __bindgen_bitfield_unit.set(121usize, 1u8, {
+            let mb_skip_conv_disable: u32 = unsafe { ::std::mem::transmute(mb_skip_conv_disable) };
+            mb_skip_conv_disable as u64
+        });
+        __bindgen_bitfield_unit.set(122usize, 1u8, {
+            let is_last_mb: u32 = unsafe { ::std::mem::transmute(is_last_mb) };
+            is_last_mb as u64
+        });
+        __bindgen_bitfield_unit.set(123usize, 1u8, {
+            let enable_coefficient_clamp: u32 =
+                unsafe { ::std::mem::transmute(enable_coefficient_clamp) };
+            enable_coefficient_clamp as u64
+        });
+        __bindgen_bitfield_unit.set(124usize, 4u8, {
+            let direct8x8_pattern: u32 = unsafe { ::std::mem::transmute(direct8x8_pattern) };
+            direct8x8_pattern as u64
+        });
+        __bindgen_bitfield_unit
     }
     #[inline]
-    pub fn set_intra_part_mask(&mut self, val: u32) {
+    pub fn reserved8(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_2.get(0usize, 16u8) as u32) }
+    }
+    #[inline]
+    pub fn set_reserved8(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(71usize, 5u8, val as u64)
+            self._bitfield_2.set(0usize, 16u8, val as u64)
         }
     }
     #[inline]
-    pub fn multi_pred_l0(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(76usize, 1u8) as u32) }
+    pub fn target_size_in_word(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_2.get(16usize, 8u8) as u32) }
     }
     #[inline]
-    pub fn set_multi_pred_l0(&mut self, val: u32) {
+    pub fn set_target_size_in_word(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(76usize, 1u8, val as u64)
+            self._bitfield_2.set(16usize, 8u8, val as u64)
         }
     }
     #[inline]
-    pub fn multi_pred_l1(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(77usize, 1u8) as u32) }
+    pub fn max_size_in_word(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_2.get(24usize, 8u8) as u32) }
     }
     #[inline]
-    pub fn set_multi_pred_l1(&mut self, val: u32) {
+    pub fn set_max_size_in_word(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(77usize, 1u8, val as u64)
+            self._bitfield_2.set(24usize, 8u8, val as u64)
         }
     }
     #[inline]
-    pub fn sub_pel_mode(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(78usize, 2u8) as u32) }
+    pub fn new_bitfield_2(
+        reserved8: u32,
+        target_size_in_word: u32,
+        max_size_in_word: u32,
+    ) -> __BindgenBitfieldUnit<[u8; 4usize]> {
+        let mut __bindgen_bitfield_unit: __BindgenBitfieldUnit<[u8; 4usize]> = Default::default();
+        __bindgen_bitfield_unit.set(0usize, 16u8, {
+            let reserved8: u32 = unsafe { ::std::mem::transmute(reserved8) };
+            reserved8 as u64
+        });
+        __bindgen_bitfield_unit.set(16usize, 8u8, {
+            let target_size_in_word: u32 = unsafe { ::std::mem::transmute(target_size_in_word) };
+            target_size_in_word as u64
+        });
+        __bindgen_bitfield_unit.set(24usize, 8u8, {
+            let max_size_in_word: u32 = unsafe { ::std::mem::transmute(max_size_in_word) };
+            max_size_in_word as u64
+        });
+        __bindgen_bitfield_unit
+    }
+}
+pub type VAEncFEIMBCodeH264 = _VAEncFEIMBCodeH264;
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct _VAEncFEIDistortionH264 {
+    pub inter_distortion: [u16; 16usize],
+    pub _bitfield_align_1: [u16; 0],
+    pub _bitfield_1: __BindgenBitfieldUnit<[u8; 8usize]>,
+    pub reserved1: [u32; 2usize],
+}
+impl _VAEncFEIDistortionH264 {
+    #[inline]
+    pub fn best_inter_distortion(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(0usize, 16u8) as u32) }
     }
     #[inline]
-    pub fn set_sub_pel_mode(&mut self, val: u32) {
+    pub fn set_best_inter_distortion(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(78usize, 2u8, val as u64)
+            self._bitfield_1.set(0usize, 16u8, val as u64)
         }
     }
     #[inline]
-    pub fn inter_sad(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(80usize, 2u8) as u32) }
+    pub fn best_intra_distortion(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(16usize, 16u8) as u32) }
     }
     #[inline]
-    pub fn set_inter_sad(&mut self, val: u32) {
+    pub fn set_best_intra_distortion(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(80usize, 2u8, val as u64)
+            self._bitfield_1.set(16usize, 16u8, val as u64)
         }
     }
     #[inline]
-    pub fn intra_sad(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(82usize, 2u8) as u32) }
+    pub fn colocated_mb_distortion(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(32usize, 16u8) as u32) }
     }
     #[inline]
-    pub fn set_intra_sad(&mut self, val: u32) {
+    pub fn set_colocated_mb_distortion(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(82usize, 2u8, val as u64)
+            self._bitfield_1.set(32usize, 16u8, val as u64)
         }
     }
     #[inline]
-    pub fn distortion_type(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(84usize, 1u8) as u32) }
+    pub fn reserved0(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(48usize, 16u8) as u32) }
     }
     #[inline]
-    pub fn set_distortion_type(&mut self, val: u32) {
+    pub fn set_reserved0(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(84usize, 1u8, val as u64)
+            self._bitfield_1.set(48usize, 16u8, val as u64)
         }
     }
     #[inline]
-    pub fn repartition_check_enable(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(85usize, 1u8) as u32) }
+    pub fn new_bitfield_1(
+        best_inter_distortion: u32,
+        best_intra_distortion: u32,
+        colocated_mb_distortion: u32,
+        reserved0: u32,
+    ) -> __BindgenBitfieldUnit<[u8; 8usize]> {
+        let mut __bindgen_bitfield_unit: __BindgenBitfieldUnit<[u8; 8usize]> = Default::default();
+        __bindgen_bitfield_unit.set(0usize, 16u8, {
+            let best_inter_distortion: u32 =
+                unsafe { ::std::mem::transmute(best_inter_distortion) };
+            best_inter_distortion as u64
+        });
+        __bindgen_bitfield_unit.set(16usize, 16u8, {
+            let best_intra_distortion: u32 =
+                unsafe { ::std::mem::transmute(best_intra_distortion) };
+            best_intra_distortion as u64
+        });
+        __bindgen_bitfield_unit.set(32usize, 16u8, {
+            let colocated_mb_distortion: u32 =
+                unsafe { ::std::mem::transmute(colocated_mb_distortion) };
+            colocated_mb_distortion as u64
+        });
+        __bindgen_bitfield_unit.set(48usize, 16u8, {
+            let reserved0: u32 = unsafe { ::std::mem::transmute(reserved0) };
+            reserved0 as u64
+        });
+        __bindgen_bitfield_unit
     }
-    #[inline]
-    pub fn set_repartition_check_enable(&mut self, val: u32) {
+}
+pub type VAEncFEIDistortionH264 = _VAEncFEIDistortionH264;
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct _VAStatsStatisticsParameterH264 {
+    pub stats_params: VAStatsStatisticsParameter,
+    pub _bitfield_align_1: [u32; 0],
+    pub _bitfield_1: __BindgenBitfieldUnit<[u8; 16usize]>,
+    pub reserved4: [u32; 2usize],
+}
+impl Default for _VAStatsStatisticsParameterH264 {
+    fn default() -> Self {
+        let mut s = ::std::mem::MaybeUninit::<Self>::uninit();
         unsafe {
-            let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(85usize, 1u8, val as u64)
+            ::std::ptr::write_bytes(s.as_mut_ptr(), 0, 1);
+            s.assume_init()
         }
     }
+}
+impl _VAStatsStatisticsParameterH264 {
     #[inline]
-    pub fn adaptive_search(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(86usize, 1u8) as u32) }
+    pub fn frame_qp(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(0usize, 8u8) as u32) }
     }
     #[inline]
-    pub fn set_adaptive_search(&mut self, val: u32) {
+    pub fn set_frame_qp(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(86usize, 1u8, val as u64)
+            self._bitfield_1.set(0usize, 8u8, val as u64)
         }
     }
     #[inline]
-    pub fn mv_predictor_enable(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(87usize, 1u8) as u32) }
+    pub fn len_sp(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(8usize, 8u8) as u32) }
     }
     #[inline]
-    pub fn set_mv_predictor_enable(&mut self, val: u32) {
+    pub fn set_len_sp(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(87usize, 1u8, val as u64)
+            self._bitfield_1.set(8usize, 8u8, val as u64)
         }
     }
     #[inline]
-    pub fn mb_qp(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(88usize, 1u8) as u32) }
+    pub fn search_path(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(16usize, 8u8) as u32) }
     }
     #[inline]
-    pub fn set_mb_qp(&mut self, val: u32) {
+    pub fn set_search_path(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(88usize, 1u8, val as u64)
+            self._bitfield_1.set(16usize, 8u8, val as u64)
         }
     }
     #[inline]
-    pub fn mb_input(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(89usize, 1u8) as u32) }
+    pub fn reserved0(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(24usize, 8u8) as u32) }
     }
     #[inline]
-    pub fn set_mb_input(&mut self, val: u32) {
+    pub fn set_reserved0(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(89usize, 1u8, val as u64)
+            self._bitfield_1.set(24usize, 8u8, val as u64)
         }
     }
     #[inline]
-    pub fn mb_size_ctrl(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(90usize, 1u8) as u32) }
+    pub fn sub_mb_part_mask(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(32usize, 7u8) as u32) }
     }
     #[inline]
-    pub fn set_mb_size_ctrl(&mut self, val: u32) {
+    pub fn set_sub_mb_part_mask(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(90usize, 1u8, val as u64)
+            self._bitfield_1.set(32usize, 7u8, val as u64)
         }
     }
     #[inline]
-    pub fn colocated_mb_distortion(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(91usize, 1u8) as u32) }
+    pub fn sub_pel_mode(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(39usize, 2u8) as u32) }
     }
     #[inline]
-    pub fn set_colocated_mb_distortion(&mut self, val: u32) {
+    pub fn set_sub_pel_mode(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(91usize, 1u8, val as u64)
+            self._bitfield_1.set(39usize, 2u8, val as u64)
         }
     }
     #[inline]
-    pub fn reserved1(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(92usize, 4u8) as u32) }
+    pub fn inter_sad(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(41usize, 2u8) as u32) }
     }
     #[inline]
-    pub fn set_reserved1(&mut self, val: u32) {
+    pub fn set_inter_sad(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(92usize, 4u8, val as u64)
+            self._bitfield_1.set(41usize, 2u8, val as u64)
         }
     }
     #[inline]
-    pub fn ref_width(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(96usize, 8u8) as u32) }
+    pub fn intra_sad(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(43usize, 2u8) as u32) }
     }
     #[inline]
-    pub fn set_ref_width(&mut self, val: u32) {
+    pub fn set_intra_sad(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(96usize, 8u8, val as u64)
+            self._bitfield_1.set(43usize, 2u8, val as u64)
         }
     }
     #[inline]
-    pub fn ref_height(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(104usize, 8u8) as u32) }
+    pub fn adaptive_search(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(45usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_ref_height(&mut self, val: u32) {
+    pub fn set_adaptive_search(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(104usize, 8u8, val as u64)
+            self._bitfield_1.set(45usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn search_window(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(112usize, 4u8) as u32) }
+    pub fn mv_predictor_ctrl(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(46usize, 3u8) as u32) }
     }
     #[inline]
-    pub fn set_search_window(&mut self, val: u32) {
+    pub fn set_mv_predictor_ctrl(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(112usize, 4u8, val as u64)
+            self._bitfield_1.set(46usize, 3u8, val as u64)
         }
     }
     #[inline]
-    pub fn reserved2(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(116usize, 12u8) as u32) }
+    pub fn mb_qp(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(49usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_reserved2(&mut self, val: u32) {
+    pub fn set_mb_qp(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(116usize, 12u8, val as u64)
+            self._bitfield_1.set(49usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn new_bitfield_1(
-        num_mv_predictors_l0: u32,
-        num_mv_predictors_l1: u32,
-        search_path: u32,
-        len_sp: u32,
-        reserved0: u32,
-        sub_mb_part_mask: u32,
-        intra_part_mask: u32,
-        multi_pred_l0: u32,
-        multi_pred_l1: u32,
-        sub_pel_mode: u32,
-        inter_sad: u32,
-        intra_sad: u32,
-        distortion_type: u32,
-        repartition_check_enable: u32,
-        adaptive_search: u32,
-        mv_predictor_enable: u32,
-        mb_qp: u32,
-        mb_input: u32,
-        mb_size_ctrl: u32,
-        colocated_mb_distortion: u32,
-        reserved1: u32,
-        ref_width: u32,
-        ref_height: u32,
-        search_window: u32,
-        reserved2: u32,
-    ) -> __BindgenBitfieldUnit<[u8; 16usize]> {
-        let mut __bindgen_bitfield_unit: __BindgenBitfieldUnit<[u8; 16usize]> = Default::default();
-        __bindgen_bitfield_unit.set(0usize, 16u8, {
-            let num_mv_predictors_l0: u32 = unsafe { ::std::mem::transmute(num_mv_predictors_l0) };
-            num_mv_predictors_l0 as u64
-        });
-        __bindgen_bitfield_unit.set(16usize, 16u8, {
-            let num_mv_predictors_l1: u32 = unsafe { ::std::mem::transmute(num_mv_predictors_l1) };
-            num_mv_predictors_l1 as u64
-        });
-        __bindgen_bitfield_unit.set(32usize, 8u8, {
-            let search_path: u32 = unsafe { ::std::mem::transmute(search_path) };
-            search_path as u64
-        });
-        __bindgen_bitfield_unit.set(40usize, 8u8, {
-            let len_sp: u32 = unsafe { ::std::mem::transmute(len_sp) };
-            len_sp as u64
-        });
-        __bindgen_bitfield_unit.set(48usize, 16u8, {
-            let reserved0: u32 = unsafe { ::std::mem::transmute(reserved0) };
-            reserved0 as u64
-        });
-        __bindgen_bitfield_unit.set(64usize, 7u8, {
-            let sub_mb_part_mask: u32 = unsafe { ::std::mem::transmute(sub_mb_part_mask) };
-            sub_mb_part_mask as u64
-        });
-        __bindgen_bitfield_unit.set(71usize, 5u8, {
-            let intra_part_mask: u32 = unsafe { ::std::mem::transmute(intra_part_mask) };
-            intra_part_mask as u64
-        });
-        __bindgen_bitfield_unit.set(76usize, 1u8, {
-            let multi_pred_l0: u32 = unsafe { ::std::mem::transmute(multi_pred_l0) };
-            multi_pred_l0 as u64
-        });
-        __bindgen_bitfield_unit.set(77usize, 1u8, {
-            let multi_pred_l1: u32 = unsafe { ::std::mem::transmute(multi_pred_l1) };
-            multi_pred_l1 as u64
-        });
-        __bindgen_bitfield_unit.set(78usize, 2u8, {
-            let sub_pel_mode: u32 = unsafe { ::std::mem::transmute(sub_pel_mode) };
-            sub_pel_mode as u64
-        });
-        __bindgen_bitfield_unit.set(80usize, 2u8, {
-            let inter_sad: u32 = unsafe { ::std::mem::transmute(inter_sad) };
-            inter_sad as u64
-        });
-        __bindgen_bitfield_unit.set(82usize, 2u8, {
-            let intra_sad: u32 = unsafe { ::std::mem::transmute(intra_sad) };
-            intra_sad as u64
-        });
-        __bindgen_bitfield_unit.set(84usize, 1u8, {
-            let distortion_type: u32 = unsafe { ::std::mem::transmute(distortion_type) };
-            distortion_type as u64
-        });
-        __bindgen_bitfield_unit.set(85usize, 1u8, {
-            let repartition_check_enable: u32 =
-                unsafe { ::std::mem::transmute(repartition_check_enable) };
-            repartition_check_enable as u64
-        });
-        __bindgen_bitfield_unit.set(86usize, 1u8, {
-            let adaptive_search: u32 = unsafe { ::std::mem::transmute(adaptive_search) };
-            adaptive_search as u64
-        });
-        __bindgen_bitfield_unit.set(87usize, 1u8, {
-            let mv_predictor_enable: u32 = unsafe { ::std::mem::transmute(mv_predictor_enable) };
-            mv_predictor_enable as u64
-        });
-        __bindgen_bitfield_unit.set(88usize, 1u8, {
-            let mb_qp: u32 = unsafe { ::std::mem::transmute(mb_qp) };
-            mb_qp as u64
-        });
-        __bindgen_bitfield_unit.set(89usize, 1u8, {
-            let mb_input: u32 = unsafe { ::std::mem::transmute(mb_input) };
-            mb_input as u64
-        });
-        __bindgen_bitfield_unit.set(90usize, 1u8, {
-            let mb_size_ctrl: u32 = unsafe { ::std::mem::transmute(mb_size_ctrl) };
-            mb_size_ctrl as u64
-        });
-        __bindgen_bitfield_unit.set(91usize, 1u8, {
-            let colocated_mb_distortion: u32 =
-                unsafe { ::std::mem::transmute(colocated_mb_distortion) };
-            colocated_mb_distortion as u64
-        });
-        __bindgen_bitfield_unit.set(92usize, 4u8, {
-            let reserved1: u32 = unsafe { ::std::mem::transmute(reserved1) };
-            reserved1 as u64
-        });
-        __bindgen_bitfield_unit.set(96usize, 8u8, {
-            let ref_width: u32 = unsafe { ::std::mem::transmute(ref_width) };
-            ref_width as u64
-        });
-        __bindgen_bitfield_unit.set(104usize, 8u8, {
-            let ref_height: u32 = unsafe { ::std::mem::transmute(ref_height) };
-            ref_height as u64
-        });
-        __bindgen_bitfield_unit.set(112usize, 4u8, {
-            let search_window: u32 = unsafe { ::std::mem::transmute(search_window) };
-            search_window as u64
-        });
-        __bindgen_bitfield_unit.set(116usize, 12u8, {
-            let reserved2: u32 = unsafe { ::std::mem::transmute(reserved2) };
-            reserved2 as u64
-        });
-        __bindgen_bitfield_unit
-    }
-}
-pub type VAEncMiscParameterFEIFrameControlH264 = _VAEncMiscParameterFEIFrameControlH264;
-#[repr(C)]
-#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
-pub struct _VAEncFEIMBControlH264 {
-    pub _bitfield_align_1: [u32; 0],
-    pub _bitfield_1: __BindgenBitfieldUnit<[u8; 4usize]>,
-    pub reserved1: u32,
-    pub reserved2: u32,
-    pub _bitfield_align_2: [u16; 0],
-    pub _bitfield_2: __BindgenBitfieldUnit<[u8; 4usize]>,
-}
-impl _VAEncFEIMBControlH264 {
-    #[inline]
-    pub fn force_to_intra(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(0usize, 1u8) as u32) }
+    pub fn ft_enable(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(50usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_force_to_intra(&mut self, val: u32) {
+    pub fn set_ft_enable(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(0usize, 1u8, val as u64)
+            self._bitfield_1.set(50usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn force_to_skip(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(1usize, 1u8) as u32) }
+    pub fn intra_part_mask(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(51usize, 5u8) as u32) }
     }
     #[inline]
-    pub fn set_force_to_skip(&mut self, val: u32) {
+    pub fn set_intra_part_mask(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(1usize, 1u8, val as u64)
+            self._bitfield_1.set(51usize, 5u8, val as u64)
         }
     }
     #[inline]
-    pub fn force_to_nonskip(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(2usize, 1u8) as u32) }
+    pub fn reserved1(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(56usize, 8u8) as u32) }
     }
     #[inline]
-    pub fn set_force_to_nonskip(&mut self, val: u32) {
+    pub fn set_reserved1(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(2usize, 1u8, val as u64)
+            self._bitfield_1.set(56usize, 8u8, val as u64)
         }
     }
     #[inline]
-    pub fn enable_direct_bias_adjustment(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(3usize, 1u8) as u32) }
+    pub fn ref_width(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(64usize, 8u8) as u32) }
     }
     #[inline]
-    pub fn set_enable_direct_bias_adjustment(&mut self, val: u32) {
+    pub fn set_ref_width(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(3usize, 1u8, val as u64)
+            self._bitfield_1.set(64usize, 8u8, val as u64)
         }
     }
     #[inline]
-    pub fn enable_motion_bias_adjustment(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(4usize, 1u8) as u32) }
+    pub fn ref_height(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(72usize, 8u8) as u32) }
     }
     #[inline]
-    pub fn set_enable_motion_bias_adjustment(&mut self, val: u32) {
+    pub fn set_ref_height(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(4usize, 1u8, val as u64)
+            self._bitfield_1.set(72usize, 8u8, val as u64)
         }
     }
     #[inline]
-    pub fn ext_mv_cost_scaling_factor(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(5usize, 3u8) as u32) }
+    pub fn search_window(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(80usize, 4u8) as u32) }
     }
     #[inline]
-    pub fn set_ext_mv_cost_scaling_factor(&mut self, val: u32) {
+    pub fn set_search_window(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(5usize, 3u8, val as u64)
+            self._bitfield_1.set(80usize, 4u8, val as u64)
         }
     }
     #[inline]
-    pub fn reserved0(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(8usize, 24u8) as u32) }
+    pub fn reserved2(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(84usize, 12u8) as u32) }
     }
     #[inline]
-    pub fn set_reserved0(&mut self, val: u32) {
+    pub fn set_reserved2(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(8usize, 24u8, val as u64)
+            self._bitfield_1.set(84usize, 12u8, val as u64)
         }
     }
     #[inline]
-    pub fn new_bitfield_1(
-        force_to_intra: u32,
-        force_to_skip: u32,
-        force_to_nonskip: u32,
-        enable_direct_bias_adjustment: u32,
-        enable_motion_bias_adjustment: u32,
-        ext_mv_cost_scaling_factor: u32,
-        reserved0: u32,
-    ) -> __BindgenBitfieldUnit<[u8; 4usize]> {
-        let mut __bindgen_bitfield_unit: __BindgenBitfieldUnit<[u8; 4usize]> = Default::default();
-        __bindgen_bitfield_unit.set(0usize, 1u8, {
-            let force_to_intra: u32 = unsafe { ::std::mem::transmute(force_to_intra) };
-            force_to_intra as u64
-        });
-        __bindgen_bitfield_unit.set(1usize, 1u8, {
-            let force_to_skip: u32 = unsafe { ::std::mem::transmute(force_to_skip) };
-            force_to_skip as u64
-        });
-        __bindgen_bitfield_unit.set(2usize, 1u8, {
-            let force_to_nonskip: u32 = unsafe { ::std::mem::transmute(force_to_nonskip) };
-            force_to_nonskip as u64
-        });
-        __bindgen_bitfield_unit.set(3usize, 1u8, {
-            let enable_direct_bias_adjustment: u32 =
-                unsafe { ::std::mem::transmute(enable_direct_bias_adjustment) };
-            enable_direct_bias_adjustment as u64
-        });
-        __bindgen_bitfield_unit.set(4usize, 1u8, {
-            let enable_motion_bias_adjustment: u32 =
-                unsafe { ::std::mem::transmute(enable_motion_bias_adjustment) };
-            enable_motion_bias_adjustment as u64
-        });
-        __bindgen_bitfield_unit.set(5usize, 3u8, {
-            let ext_mv_cost_scaling_factor: u32 =
-                unsafe { ::std::mem::transmute(ext_mv_cost_scaling_factor) };
-            ext_mv_cost_scaling_factor as u64
-        });
-        __bindgen_bitfield_unit.set(8usize, 24u8, {
-            let reserved0: u32 = unsafe { ::std::mem::transmute(reserved0) };
-            reserved0 as u64
-        });
-        __bindgen_bitfield_unit
+    pub fn disable_mv_output(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(96usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn reserved3(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_2.get(0usize, 16u8) as u32) }
+    pub fn set_disable_mv_output(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(96usize, 1u8, val as u64)
+        }
     }
     #[inline]
-    pub fn set_reserved3(&mut self, val: u32) {
+    pub fn disable_statistics_output(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(97usize, 1u8) as u32) }
+    }
+    #[inline]
+    pub fn set_disable_statistics_output(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_2.set(0usize, 16u8, val as u64)
+            self._bitfield_1.set(97usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn target_size_in_word(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_2.get(16usize, 8u8) as u32) }
+    pub fn enable_8x8_statistics(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(98usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_target_size_in_word(&mut self, val: u32) {
+    pub fn set_enable_8x8_statistics(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_2.set(16usize, 8u8, val as u64)
+            self._bitfield_1.set(98usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn max_size_in_word(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_2.get(24usize, 8u8) as u32) }
+    pub fn reserved3(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(99usize, 29u8) as u32) }
     }
     #[inline]
-    pub fn set_max_size_in_word(&mut self, val: u32) {
+    pub fn set_reserved3(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_2.set(24usize, 8u8, val as u64)
+            self._bitfield_1.set(99usize, 29u8, val as u64)
         }
     }
     #[inline]
-    pub fn new_bitfield_2(
+    pub fn new_bitfield_1(
+        frame_qp: u32,
+        len_sp: u32,
+        search_path: u32,
+        reserved0: u32,
+        sub_mb_part_mask: u32,
+        sub_pel_mode: u32,
+        inter_sad: u32,
+        intra_sad: u32,
+        adaptive_search: u32,
+        mv_predictor_ctrl: u32,
+        mb_qp: u32,
+        ft_enable: u32,
+        intra_part_mask: u32,
+        reserved1: u32,
+        ref_width: u32,
+        ref_height: u32,
+        search_window: u32,
+        reserved2: u32,
+        disable_mv_output: u32,
+        disable_statistics_output: u32,
+        enable_8x8_statistics: u32,
         reserved3: u32,
-        target_size_in_word: u32,
-        max_size_in_word: u32,
-    ) -> __BindgenBitfieldUnit<[u8; 4usize]> {
-        let mut __bindgen_bitfield_unit: __BindgenBitfieldUnit<[u8; 4usize]> = Default::default();
-        __bindgen_bitfield_unit.set(0usize, 16u8, {
-            let reserved3: u32 = unsafe { ::std::mem::transmute(reserved3) };
-            reserved3 as u64
+    ) -> __BindgenBitfieldUnit<[u8; 16usize]> {
+        let mut __bindgen_bitfield_unit: __BindgenBitfieldUnit<[u8; 16usize]> = Default::default();
+        __bindgen_bitfield_unit.set(0usize, 8u8, {
+            let frame_qp: u32 = unsafe { ::std::mem::transmute(frame_qp) };
+            frame_qp as u64
+        });
+        __bindgen_bitfield_unit.set(8usize, 8u8, {
+            let len_sp: u32 = unsafe { ::std::mem::transmute(len_sp) };
+            len_sp as u64
+        });
+        __bindgen_bitfield_unit.set(16usize, 8u8, {
+            let search_path: u32 = unsafe { ::std::mem::transmute(search_path) };
+            search_path as u64
+        });
+        __bindgen_bitfield_unit.set(24usize, 8u8, {
+            let reserved0: u32 = unsafe { ::std::mem::transmute(reserved0) };
+            reserved0 as u64
+        });
+        __bindgen_bitfield_unit.set(32usize, 7u8, {
+            let sub_mb_part_mask: u32 = unsafe { ::std::mem::transmute(sub_mb_part_mask) };
+            sub_mb_part_mask as u64
+        });
+        __bindgen_bitfield_unit.set(39usize, 2u8, {
+            let sub_pel_mode: u32 = unsafe { ::std::mem::transmute(sub_pel_mode) };
+            sub_pel_mode as u64
+        });
+        __bindgen_bitfield_unit.set(41usize, 2u8, {
+            let inter_sad: u32 = unsafe { ::std::mem::transmute(inter_sad) };
+            inter_sad as u64
+        });
+        __bindgen_bitfield_unit.set(43usize, 2u8, {
+            let intra_sad: u32 = unsafe { ::std::mem::transmute(intra_sad) };
+            intra_sad as u64
+        });
+        __bindgen_bitfield_unit.set(45usize, 1u8, {
+            let adaptive_search: u32 = unsafe { ::std::mem::transmute(adaptive_search) };
+            adaptive_search as u64
+        });
+        __bindgen_bitfield_unit.set(46usize, 3u8, {
+            let mv_predictor_ctrl: u32 = unsafe { ::std::mem::transmute(mv_predictor_ctrl) };
+            mv_predictor_ctrl as u64
+        });
+        __bindgen_bitfield_unit.set(49usize, 1u8, {
+            let mb_qp: u32 = unsafe { ::std::mem::transmute(mb_qp) };
+            mb_qp as u64
+        });
+        __bindgen_bitfield_unit.set(50usize, 1u8, {
+            let ft_enable: u32 = unsafe { ::std::mem::transmute(ft_enable) };
+            ft_enable as u64
+        });
+        __bindgen_bitfield_unit.set(51usize, 5u8, {
+            let intra_part_mask: u32 = unsafe { ::std::mem::transmute(intra_part_mask) };
+            intra_part_mask as u64
+        });
+        __bindgen_bitfield_unit.set(56usize, 8u8, {
+            let reserved1: u32 = unsafe { ::std::mem::transmute(reserved1) };
+            reserved1 as u64
+        });
+        __bindgen_bitfield_unit.set(64usize, 8u8, {
+            let ref_width: u32 = unsafe { ::std::mem::transmute(ref_width) };
+            ref_width as u64
+        });
+        __bindgen_bitfield_unit.set(72usize, 8u8, {
+            let ref_height: u32 = unsafe { ::std::mem::transmute(ref_height) };
+            ref_height as u64
+        });
+        __bindgen_bitfield_unit.set(80usize, 4u8, {
+            let search_window: u32 = unsafe { ::std::mem::transmute(search_window) };
+            search_window as u64
+        });
+        __bindgen_bitfield_unit.set(84usize, 12u8, {
+            let reserved2: u32 = unsafe { ::std::mem::transmute(reserved2) };
+            reserved2 as u64
+        });
+        __bindgen_bitfield_unit.set(96usize, 1u8, {
+            let disable_mv_output: u32 = unsafe { ::std::mem::transmute(disable_mv_output) };
+            disable_mv_output as u64
+        });
+        __bindgen_bitfield_unit.set(97usize, 1u8, {
+            let disable_statistics_output: u32 =
+                unsafe { ::std::mem::transmute(disable_statistics_output) };
+            disable_statistics_output as u64
         });
-        __bindgen_bitfield_unit.set(16usize, 8u8, {
-            let target_size_in_word: u32 = unsafe { ::std::mem::transmute(target_size_in_word) };
-            target_size_in_word as u64
+        __bindgen_bitfield_unit.set(98usize, 1u8, {
+            let enable_8x8_statistics: u32 =
+                unsafe { ::std::mem::transmute(enable_8x8_statistics) };
+            enable_8x8_statistics as u64
         });
-        __bindgen_bitfield_unit.set(24usize, 8u8, {
-            let max_size_in_word: u32 = unsafe { ::std::mem::transmute(max_size_in_word) };
-            max_size_in_word as u64
+        __bindgen_bitfield_unit.set(99usize, 29u8, {
+            let reserved3: u32 = unsafe { ::std::mem::transmute(reserved3) };
+            reserved3 as u64
         });
         __bindgen_bitfield_unit
     }
 }
-pub type VAEncFEIMBControlH264 = _VAEncFEIMBControlH264;
+pub type VAStatsStatisticsParameterH264 = _VAStatsStatisticsParameterH264;
 #[repr(C)]
 #[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
-pub struct _VAEncFEIMVPredictorH264 {
-    pub ref_idx: [_VAEncFEIMVPredictorH264__bindgen_ty_1; 4usize],
-    pub reserved: u32,
-    pub mv: [VAMotionVector; 4usize],
-}
-#[repr(C, packed)]
-#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
-pub struct _VAEncFEIMVPredictorH264__bindgen_ty_1 {
-    pub _bitfield_align_1: [u8; 0],
-    pub _bitfield_1: __BindgenBitfieldUnit<[u8; 1usize]>,
-}
-impl _VAEncFEIMVPredictorH264__bindgen_ty_1 {
-    #[inline]
-    pub fn ref_idx_l0(&self) -> u8 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(0usize, 4u8) as u8) }
-    }
-    #[inline]
-    pub fn set_ref_idx_l0(&mut self, val: u8) {
-        unsafe {
-            let val: u8 = ::std::mem::transmute(val);
-            self._bitfield_1.set(0usize, 4u8, val as u64)
-        }
-    }
-    #[inline]
-    pub fn ref_idx_l1(&self) -> u8 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(4usize, 4u8) as u8) }
-    }
-    #[inline]
-    pub fn set_ref_idx_l1(&mut self, val: u8) {
-        unsafe {
-            let val: u8 = ::std::mem::transmute(val);
-            self._bitfield_1.set(4usize, 4u8, val as u64)
-        }
-    }
-    #[inline]
-    pub fn new_bitfield_1(ref_idx_l0: u8, ref_idx_l1: u8) -> __BindgenBitfieldUnit<[u8; 1usize]> {
-        let mut __bindgen_bitfield_unit: __BindgenBitfieldUnit<[u8; 1usize]> = Default::default();
-        __bindgen_bitfield_unit.set(0usize, 4u8, {
-            let ref_idx_l0: u8 = unsafe { ::std::mem::transmute(ref_idx_l0) };
-            ref_idx_l0 as u64
-        });
-        __bindgen_bitfield_unit.set(4usize, 4u8, {
-            let ref_idx_l1: u8 = unsafe { ::std::mem::transmute(ref_idx_l1) };
-            ref_idx_l1 as u64
-        });
-        __bindgen_bitfield_unit
-    }
-}
-pub type VAEncFEIMVPredictorH264 = _VAEncFEIMVPredictorH264;
-#[repr(C)]
-#[derive(Copy, Clone)]
-pub struct _VAEncFEIMBCodeH264 {
-    pub reserved0: [u32; 3usize],
-    pub _bitfield_align_1: [u32; 0],
+pub struct _VAStatsStatisticsH264 {
+    pub _bitfield_align_1: [u16; 0],
     pub _bitfield_1: __BindgenBitfieldUnit<[u8; 16usize]>,
-    pub mb_mode: _VAEncFEIMBCodeH264__bindgen_ty_1,
-    pub _bitfield_align_2: [u16; 0],
+    pub sum_coef: u32,
+    pub _bitfield_align_2: [u32; 0],
     pub _bitfield_2: __BindgenBitfieldUnit<[u8; 4usize]>,
-    pub reserved9: [u32; 4usize],
-    pub reserved10: u32,
-}
-#[repr(C)]
-#[derive(Copy, Clone)]
-pub union _VAEncFEIMBCodeH264__bindgen_ty_1 {
-    pub intra_mb: _VAEncFEIMBCodeH264__bindgen_ty_1__bindgen_ty_1,
-    pub inter_mb: _VAEncFEIMBCodeH264__bindgen_ty_1__bindgen_ty_2,
-}
-#[repr(C)]
-#[repr(align(4))]
-#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
-pub struct _VAEncFEIMBCodeH264__bindgen_ty_1__bindgen_ty_1 {
-    pub _bitfield_align_1: [u32; 0],
-    pub _bitfield_1: __BindgenBitfieldUnit<[u8; 12usize]>,
+    pub variance_16x16: u32,
+    pub variance_8x8: [u32; 4usize],
+    pub pixel_average_16x16: u32,
+    pub pixel_average_8x8: [u32; 4usize],
 }
-impl _VAEncFEIMBCodeH264__bindgen_ty_1__bindgen_ty_1 {
+impl _VAStatsStatisticsH264 {
     #[inline]
-    pub fn luma_intra_pred_modes0(&self) -> u32 {
+    pub fn best_inter_distortion0(&self) -> u32 {
         unsafe { ::std::mem::transmute(self._bitfield_1.get(0usize, 16u8) as u32) }
     }
     #[inline]
-    pub fn set_luma_intra_pred_modes0(&mut self, val: u32) {
+    pub fn set_best_inter_distortion0(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
             self._bitfield_1.set(0usize, 16u8, val as u64)
         }
     }
     #[inline]
-    pub fn luma_intra_pred_modes1(&self) -> u32 {
+    pub fn inter_mode0(&self) -> u32 {
         unsafe { ::std::mem::transmute(self._bitfield_1.get(16usize, 16u8) as u32) }
     }
     #[inline]
-    pub fn set_luma_intra_pred_modes1(&mut self, val: u32) {
+    pub fn set_inter_mode0(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
             self._bitfield_1.set(16usize, 16u8, val as u64)
         }
     }
     #[inline]
-    pub fn luma_intra_pred_modes2(&self) -> u32 {
+    pub fn best_inter_distortion1(&self) -> u32 {
         unsafe { ::std::mem::transmute(self._bitfield_1.get(32usize, 16u8) as u32) }
     }
     #[inline]
-    pub fn set_luma_intra_pred_modes2(&mut self, val: u32) {
+    pub fn set_best_inter_distortion1(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
             self._bitfield_1.set(32usize, 16u8, val as u64)
         }
     }
     #[inline]
-    pub fn luma_intra_pred_modes3(&self) -> u32 {
+    pub fn inter_mode1(&self) -> u32 {
         unsafe { ::std::mem::transmute(self._bitfield_1.get(48usize, 16u8) as u32) }
     }
     #[inline]
-    pub fn set_luma_intra_pred_modes3(&mut self, val: u32) {
+    pub fn set_inter_mode1(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
             self._bitfield_1.set(48usize, 16u8, val as u64)
         }
     }
     #[inline]
-    pub fn chroma_intra_pred_mode(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(64usize, 2u8) as u32) }
+    pub fn best_intra_distortion(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(64usize, 16u8) as u32) }
     }
     #[inline]
-    pub fn set_chroma_intra_pred_mode(&mut self, val: u32) {
+    pub fn set_best_intra_distortion(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(64usize, 2u8, val as u64)
+            self._bitfield_1.set(64usize, 16u8, val as u64)
         }
     }
     #[inline]
-    pub fn intra_pred_avail_flag(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(66usize, 5u8) as u32) }
+    pub fn intra_mode(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(80usize, 16u8) as u32) }
     }
     #[inline]
-    pub fn set_intra_pred_avail_flag(&mut self, val: u32) {
+    pub fn set_intra_mode(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(66usize, 5u8, val as u64)
+            self._bitfield_1.set(80usize, 16u8, val as u64)
         }
     }
     #[inline]
-    pub fn intra_pred_avail_flagF(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(71usize, 1u8) as u32) }
+    pub fn num_non_zero_coef(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(96usize, 16u8) as u32) }
     }
     #[inline]
-    pub fn set_intra_pred_avail_flagF(&mut self, val: u32) {
+    pub fn set_num_non_zero_coef(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(71usize, 1u8, val as u64)
+            self._bitfield_1.set(96usize, 16u8, val as u64)
         }
     }
     #[inline]
-    pub fn reserved6(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(72usize, 24u8) as u32) }
+    pub fn reserved0(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(112usize, 16u8) as u32) }
     }
     #[inline]
-    pub fn set_reserved6(&mut self, val: u32) {
+    pub fn set_reserved0(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(72usize, 24u8, val as u64)
+            self._bitfield_1.set(112usize, 16u8, val as u64)
         }
     }
     #[inline]
     pub fn new_bitfield_1(
-        luma_intra_pred_modes0: u32,
-        luma_intra_pred_modes1: u32,
-        luma_intra_pred_modes2: u32,
-        luma_intra_pred_modes3: u32,
-        chroma_intra_pred_mode: u32,
-        intra_pred_avail_flag: u32,
-        intra_pred_avail_flagF: u32,
-        reserved6: u32,
-    ) -> __BindgenBitfieldUnit<[u8; 12usize]> {
-        let mut __bindgen_bitfield_unit: __BindgenBitfieldUnit<[u8; 12usize]> = Default::default();
+        best_inter_distortion0: u32,
+        inter_mode0: u32,
+        best_inter_distortion1: u32,
+        inter_mode1: u32,
+        best_intra_distortion: u32,
+        intra_mode: u32,
+        num_non_zero_coef: u32,
+        reserved0: u32,
+    ) -> __BindgenBitfieldUnit<[u8; 16usize]> {
+        let mut __bindgen_bitfield_unit: __BindgenBitfieldUnit<[u8; 16usize]> = Default::default();
         __bindgen_bitfield_unit.set(0usize, 16u8, {
-            let luma_intra_pred_modes0: u32 =
-                unsafe { ::std::mem::transmute(luma_intra_pred_modes0) };
-            luma_intra_pred_modes0 as u64
+            let best_inter_distortion0: u32 =
+                unsafe { ::std::mem::transmute(best_inter_distortion0) };
+            best_inter_distortion0 as u64
         });
         __bindgen_bitfield_unit.set(16usize, 16u8, {
-            let luma_intra_pred_modes1: u32 =
-                unsafe { ::std::mem::transmute(luma_intra_pred_modes1) };
-            luma_intra_pred_modes1 as u64
+            let inter_mode0: u32 = unsafe { ::std::mem::transmute(inter_mode0) };
+            inter_mode0 as u64
         });
         __bindgen_bitfield_unit.set(32usize, 16u8, {
-            let luma_intra_pred_modes2: u32 =
-                unsafe { ::std::mem::transmute(luma_intra_pred_modes2) };
-            luma_intra_pred_modes2 as u64
+            let best_inter_distortion1: u32 =
+                unsafe { ::std::mem::transmute(best_inter_distortion1) };
+            best_inter_distortion1 as u64
         });
         __bindgen_bitfield_unit.set(48usize, 16u8, {
-            let luma_intra_pred_modes3: u32 =
-                unsafe { ::std::mem::transmute(luma_intra_pred_modes3) };
-            luma_intra_pred_modes3 as u64
+            let inter_mode1: u32 = unsafe { ::std::mem::transmute(inter_mode1) };
+            inter_mode1 as u64
         });
-        __bindgen_bitfield_unit.set(64usize, 2u8, {
-            let chroma_intra_pred_mode: u32 =
-                unsafe { ::std::mem::transmute(chroma_intra_pred_mode) };
-            chroma_intra_pred_mode as u64
+        __bindgen_bitfield_unit.set(64usize, 16u8, {
+            let best_intra_distortion: u32 =
+                unsafe { ::std::mem::transmute(best_intra_distortion) };
+            best_intra_distortion as u64
         });
-        __bindgen_bitfield_unit.set(66usize, 5u8, {
-            let intra_pred_avail_flag: u32 =
-                unsafe { ::std::mem::transmute(intra_pred_avail_flag) };
-            intra_pred_avail_flag as u64
+        __bindgen_bitfield_unit.set(80usize, 16u8, {
+            let intra_mode: u32 = unsafe { ::std::mem::transmute(intra_mode) };
+            intra_mode as u64
         });
-        __bindgen_bitfield_unit.set(71usize, 1u8, {
-            let intra_pred_avail_flagF: u32 =
-                unsafe { ::std::mem::transmute(intra_pred_avail_flagF) };
-            intra_pred_avail_flagF as u64
+        __bindgen_bitfield_unit.set(96usize, 16u8, {
+            let num_non_zero_coef: u32 = unsafe { ::std::mem::transmute(num_non_zero_coef) };
+            num_non_zero_coef as u64
         });
-        __bindgen_bitfield_unit.set(72usize, 24u8, {
-            let reserved6: u32 = unsafe { ::std::mem::transmute(reserved6) };
-            reserved6 as u64
+        __bindgen_bitfield_unit.set(112usize, 16u8, {
+            let reserved0: u32 = unsafe { ::std::mem::transmute(reserved0) };
+            reserved0 as u64
         });
         __bindgen_bitfield_unit
     }
-}
-#[repr(C)]
-#[repr(align(4))]
-#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
-pub struct _VAEncFEIMBCodeH264__bindgen_ty_1__bindgen_ty_2 {
-    pub _bitfield_align_1: [u16; 0],
-    pub _bitfield_1: __BindgenBitfieldUnit<[u8; 12usize]>,
-}
-impl _VAEncFEIMBCodeH264__bindgen_ty_1__bindgen_ty_2 {
-    #[inline]
-    pub fn sub_mb_shapes(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(0usize, 8u8) as u32) }
-    }
-    #[inline]
-    pub fn set_sub_mb_shapes(&mut self, val: u32) {
-        unsafe {
-            let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(0usize, 8u8, val as u64)
-        }
-    }
-    #[inline]
-    pub fn sub_mb_pred_modes(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(8usize, 8u8) as u32) }
-    }
-    #[inline]
-    pub fn set_sub_mb_pred_modes(&mut self, val: u32) {
-        unsafe {
-            let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(8usize, 8u8, val as u64)
-        }
-    }
-    #[inline]
-    pub fn reserved7(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(16usize, 16u8) as u32) }
-    }
-    #[inline]
-    pub fn set_reserved7(&mut self, val: u32) {
-        unsafe {
-            let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(16usize, 16u8, val as u64)
-        }
-    }
     #[inline]
-    pub fn ref_idx_l0_0(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(32usize, 8u8) as u32) }
+    pub fn mb_is_flat(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_2.get(0usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_ref_idx_l0_0(&mut self, val: u32) {
+    pub fn set_mb_is_flat(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(32usize, 8u8, val as u64)
+            self._bitfield_2.set(0usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn ref_idx_l0_1(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(40usize, 8u8) as u32) }
+    pub fn reserved1(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_2.get(1usize, 31u8) as u32) }
     }
     #[inline]
-    pub fn set_ref_idx_l0_1(&mut self, val: u32) {
+    pub fn set_reserved1(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(40usize, 8u8, val as u64)
+            self._bitfield_2.set(1usize, 31u8, val as u64)
         }
     }
     #[inline]
-    pub fn ref_idx_l0_2(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(48usize, 8u8) as u32) }
-    }
-    #[inline]
-    pub fn set_ref_idx_l0_2(&mut self, val: u32) {
-        unsafe {
-            let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(48usize, 8u8, val as u64)
-        }
+    pub fn new_bitfield_2(mb_is_flat: u32, reserved1: u32) -> __BindgenBitfieldUnit<[u8; 4usize]> {
+        let mut __bindgen_bitfield_unit: __BindgenBitfieldUnit<[u8; 4usize]> = Default::default();
+        __bindgen_bitfield_unit.set(0usize, 1u8, {
+            let mb_is_flat: u32 = unsafe { ::std::mem::transmute(mb_is_flat) };
+            mb_is_flat as u64
+        });
+        __bindgen_bitfield_unit.set(1usize, 31u8, {
+            let reserved1: u32 = unsafe { ::std::mem::transmute(reserved1) };
+            reserved1 as u64
+        });
+        __bindgen_bitfield_unit
     }
+}
+pub type VAStatsStatisticsH264 = _VAStatsStatisticsH264;
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct _VASegmentationStructAV1 {
+    pub segment_info_fields: _VASegmentationStructAV1__bindgen_ty_1,
+    pub feature_data: [[i16; 8usize]; 8usize],
+    pub feature_mask: [u8; 8usize],
+    pub va_reserved: [u32; 4usize],
+}
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub union _VASegmentationStructAV1__bindgen_ty_1 {
+    pub bits: _VASegmentationStructAV1__bindgen_ty_1__bindgen_ty_1,
+    pub value: u32,
+}
+#[repr(C)]
+#[repr(align(4))]
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct _VASegmentationStructAV1__bindgen_ty_1__bindgen_ty_1 {
+    pub _bitfield_align_1: [u8; 0],
+    pub _bitfield_1: __BindgenBitfieldUnit<[u8; 4usize]>,
+}
+impl _VASegmentationStructAV1__bindgen_ty_1__bindgen_ty_1 {
     #[inline]
-    pub fn ref_idx_l0_3(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(56usize, 8u8) as u32) }
+    pub fn enabled(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(0usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_ref_idx_l0_3(&mut self, val: u32) {
+    pub fn set_enabled(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(56usize, 8u8, val as u64)
+            self._bitfield_1.set(0usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn ref_idx_l1_0(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(64usize, 8u8) as u32) }
+    pub fn update_map(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(1usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_ref_idx_l1_0(&mut self, val: u32) {
+    pub fn set_update_map(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(64usize, 8u8, val as u64)
+            self._bitfield_1.set(1usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn ref_idx_l1_1(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(72usize, 8u8) as u32) }
+    pub fn temporal_update(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(2usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_ref_idx_l1_1(&mut self, val: u32) {
+    pub fn set_temporal_update(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(72usize, 8u8, val as u64)
+            self._bitfield_1.set(2usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn ref_idx_l1_2(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(80usize, 8u8) as u32) }
+    pub fn update_data(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(3usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_ref_idx_l1_2(&mut self, val: u32) {
+    pub fn set_update_data(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(80usize, 8u8, val as u64)
+            self._bitfield_1.set(3usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn ref_idx_l1_3(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(88usize, 8u8) as u32) }
+    pub fn reserved(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(4usize, 28u8) as u32) }
     }
     #[inline]
-    pub fn set_ref_idx_l1_3(&mut self, val: u32) {
+    pub fn set_reserved(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(88usize, 8u8, val as u64)
+            self._bitfield_1.set(4usize, 28u8, val as u64)
         }
     }
     #[inline]
     pub fn new_bitfield_1(
-        sub_mb_shapes: u32,
-        sub_mb_pred_modes: u32,
-        reserved7: u32,
-        ref_idx_l0_0: u32,
-        ref_idx_l0_1: u32,
-        ref_idx_l0_2: u32,
-        ref_idx_l0_3: u32,
-        ref_idx_l1_0: u32,
-        ref_idx_l1_1: u32,
-        ref_idx_l1_2: u32,
-        ref_idx_l1_3: u32,
-    ) -> __BindgenBitfieldUnit<[u8; 12usize]> {
-        let mut __bindgen_bitfield_unit: __BindgenBitfieldUnit<[u8; 12usize]> = Default::default();
-        __bindgen_bitfield_unit.set(0usize, 8u8, {
-            let sub_mb_shapes: u32 = unsafe { ::std::mem::transmute(sub_mb_shapes) };
-            sub_mb_shapes as u64
-        });
-        __bindgen_bitfield_unit.set(8usize, 8u8, {
-            let sub_mb_pred_modes: u32 = unsafe { ::std::mem::transmute(sub_mb_pred_modes) };
-            sub_mb_pred_modes as u64
-        });
-        __bindgen_bitfield_unit.set(16usize, 16u8, {
-            let reserved7: u32 = unsafe { ::std::mem::transmute(reserved7) };
-            reserved7 as u64
-        });
-        __bindgen_bitfield_unit.set(32usize, 8u8, {
-            let ref_idx_l0_0: u32 = unsafe { ::std::mem::transmute(ref_idx_l0_0) };
-            ref_idx_l0_0 as u64
-        });
-        __bindgen_bitfield_unit.set(40usize, 8u8, {
-            let ref_idx_l0_1: u32 = unsafe { ::std::mem::transmute(ref_idx_l0_1) };
-            ref_idx_l0_1 as u64
-        });
-        __bindgen_bitfield_unit.set(48usize, 8u8, {
-            let ref_idx_l0_2: u32 = unsafe { ::std::mem::transmute(ref_idx_l0_2) };
-            ref_idx_l0_2 as u64
-        });
-        __bindgen_bitfield_unit.set(56usize, 8u8, {
-            let ref_idx_l0_3: u32 = unsafe { ::std::mem::transmute(ref_idx_l0_3) };
-            ref_idx_l0_3 as u64
+        enabled: u32,
+        update_map: u32,
+        temporal_update: u32,
+        update_data: u32,
+        reserved: u32,
+    ) -> __BindgenBitfieldUnit<[u8; 4usize]> {
+        let mut __bindgen_bitfield_unit: __BindgenBitfieldUnit<[u8; 4usize]> = Default::default();
+        __bindgen_bitfield_unit.set(0usize, 1u8, {
+            let enabled: u32 = unsafe { ::std::mem::transmute(enabled) };
+            enabled as u64
         });
-        __bindgen_bitfield_unit.set(64usize, 8u8, {
-            let ref_idx_l1_0: u32 = unsafe { ::std::mem::transmute(ref_idx_l1_0) };
-            ref_idx_l1_0 as u64
+        __bindgen_bitfield_unit.set(1usize, 1u8, {
+            let update_map: u32 = unsafe { ::std::mem::transmute(update_map) };
+            update_map as u64
         });
-        __bindgen_bitfield_unit.set(72usize, 8u8, {
-            let ref_idx_l1_1: u32 = unsafe { ::std::mem::transmute(ref_idx_l1_1) };
-            ref_idx_l1_1 as u64
+        __bindgen_bitfield_unit.set(2usize, 1u8, {
+            let temporal_update: u32 = unsafe { ::std::mem::transmute(temporal_update) };
+            temporal_update as u64
         });
-        __bindgen_bitfield_unit.set(80usize, 8u8, {
-            let ref_idx_l1_2: u32 = unsafe { ::std::mem::transmute(ref_idx_l1_2) };
-            ref_idx_l1_2 as u64
+        __bindgen_bitfield_unit.set(3usize, 1u8, {
+            let update_data: u32 = unsafe { ::std::mem::transmute(update_data) };
+            update_data as u64
         });
-        __bindgen_bitfield_unit.set(88usize, 8u8, {
-            let ref_idx_l1_3: u32 = unsafe { ::std::mem::transmute(ref_idx_l1_3) };
-            ref_idx_l1_3 as u64
+        __bindgen_bitfield_unit.set(4usize, 28u8, {
+            let reserved: u32 = unsafe { ::std::mem::transmute(reserved) };
+            reserved as u64
         });
         __bindgen_bitfield_unit
     }
 }
-impl Default for _VAEncFEIMBCodeH264__bindgen_ty_1 {
+impl Default for _VASegmentationStructAV1__bindgen_ty_1 {
     fn default() -> Self {
         let mut s = ::std::mem::MaybeUninit::<Self>::uninit();
         unsafe {
@@ -6855,7 +9147,7 @@ impl Default for _VAEncFEIMBCodeH264__bindgen_ty_1 {
         }
     }
 }
-impl Default for _VAEncFEIMBCodeH264 {
+impl Default for _VASegmentationStructAV1 {
     fn default() -> Self {
         let mut s = ::std::mem::MaybeUninit::<Self>::uninit();
         unsafe {
@@ -6864,1142 +9156,1454 @@ impl Default for _VAEncFEIMBCodeH264 {
         }
     }
 }
-impl _VAEncFEIMBCodeH264 {
+pub type VASegmentationStructAV1 = _VASegmentationStructAV1;
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct _VAFilmGrainStructAV1 {
+    pub film_grain_info_fields: _VAFilmGrainStructAV1__bindgen_ty_1,
+    pub grain_seed: u16,
+    pub num_y_points: u8,
+    pub point_y_value: [u8; 14usize],
+    pub point_y_scaling: [u8; 14usize],
+    pub num_cb_points: u8,
+    pub point_cb_value: [u8; 10usize],
+    pub point_cb_scaling: [u8; 10usize],
+    pub num_cr_points: u8,
+    pub point_cr_value: [u8; 10usize],
+    pub point_cr_scaling: [u8; 10usize],
+    pub ar_coeffs_y: [i8; 24usize],
+    pub ar_coeffs_cb: [i8; 25usize],
+    pub ar_coeffs_cr: [i8; 25usize],
+    pub cb_mult: u8,
+    pub cb_luma_mult: u8,
+    pub cb_offset: u16,
+    pub cr_mult: u8,
+    pub cr_luma_mult: u8,
+    pub cr_offset: u16,
+    pub va_reserved: [u32; 4usize],
+}
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub union _VAFilmGrainStructAV1__bindgen_ty_1 {
+    pub bits: _VAFilmGrainStructAV1__bindgen_ty_1__bindgen_ty_1,
+    pub value: u32,
+}
+#[repr(C)]
+#[repr(align(4))]
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct _VAFilmGrainStructAV1__bindgen_ty_1__bindgen_ty_1 {
+    pub _bitfield_align_1: [u8; 0],
+    pub _bitfield_1: __BindgenBitfieldUnit<[u8; 4usize]>,
+}
+impl _VAFilmGrainStructAV1__bindgen_ty_1__bindgen_ty_1 {
     #[inline]
-    pub fn inter_mb_mode(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(0usize, 2u8) as u32) }
+    pub fn apply_grain(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(0usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_inter_mb_mode(&mut self, val: u32) {
+    pub fn set_apply_grain(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(0usize, 2u8, val as u64)
+            self._bitfield_1.set(0usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn mb_skip_flag(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(2usize, 1u8) as u32) }
+    pub fn chroma_scaling_from_luma(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(1usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_mb_skip_flag(&mut self, val: u32) {
+    pub fn set_chroma_scaling_from_luma(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(2usize, 1u8, val as u64)
+            self._bitfield_1.set(1usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn reserved1(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(3usize, 1u8) as u32) }
+    pub fn grain_scaling_minus_8(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(2usize, 2u8) as u32) }
     }
     #[inline]
-    pub fn set_reserved1(&mut self, val: u32) {
+    pub fn set_grain_scaling_minus_8(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(3usize, 1u8, val as u64)
+            self._bitfield_1.set(2usize, 2u8, val as u64)
         }
     }
     #[inline]
-    pub fn intra_mb_mode(&self) -> u32 {
+    pub fn ar_coeff_lag(&self) -> u32 {
         unsafe { ::std::mem::transmute(self._bitfield_1.get(4usize, 2u8) as u32) }
     }
     #[inline]
-    pub fn set_intra_mb_mode(&mut self, val: u32) {
+    pub fn set_ar_coeff_lag(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
             self._bitfield_1.set(4usize, 2u8, val as u64)
         }
     }
     #[inline]
-    pub fn reserved2(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(6usize, 1u8) as u32) }
+    pub fn ar_coeff_shift_minus_6(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(6usize, 2u8) as u32) }
     }
     #[inline]
-    pub fn set_reserved2(&mut self, val: u32) {
+    pub fn set_ar_coeff_shift_minus_6(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(6usize, 1u8, val as u64)
+            self._bitfield_1.set(6usize, 2u8, val as u64)
         }
     }
     #[inline]
-    pub fn field_mb_polarity_flag(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(7usize, 1u8) as u32) }
+    pub fn grain_scale_shift(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(8usize, 2u8) as u32) }
     }
     #[inline]
-    pub fn set_field_mb_polarity_flag(&mut self, val: u32) {
+    pub fn set_grain_scale_shift(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(7usize, 1u8, val as u64)
+            self._bitfield_1.set(8usize, 2u8, val as u64)
         }
     }
     #[inline]
-    pub fn mb_type(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(8usize, 5u8) as u32) }
+    pub fn overlap_flag(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(10usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_mb_type(&mut self, val: u32) {
+    pub fn set_overlap_flag(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(8usize, 5u8, val as u64)
+            self._bitfield_1.set(10usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn intra_mb_flag(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(13usize, 1u8) as u32) }
+    pub fn clip_to_restricted_range(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(11usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_intra_mb_flag(&mut self, val: u32) {
+    pub fn set_clip_to_restricted_range(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(13usize, 1u8, val as u64)
+            self._bitfield_1.set(11usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn field_mb_flag(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(14usize, 1u8) as u32) }
+    pub fn reserved(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(12usize, 20u8) as u32) }
     }
     #[inline]
-    pub fn set_field_mb_flag(&mut self, val: u32) {
+    pub fn set_reserved(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(14usize, 1u8, val as u64)
+            self._bitfield_1.set(12usize, 20u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn new_bitfield_1(
+        apply_grain: u32,
+        chroma_scaling_from_luma: u32,
+        grain_scaling_minus_8: u32,
+        ar_coeff_lag: u32,
+        ar_coeff_shift_minus_6: u32,
+        grain_scale_shift: u32,
+        overlap_flag: u32,
+        clip_to_restricted_range: u32,
+        reserved: u32,
+    ) -> __BindgenBitfieldUnit<[u8; 4usize]> {
+        let mut __bindgen_bitfield_unit: __BindgenBitfieldUnit<[u8; 4usize]> = Default::default();
+        __bindgen_bitfield_unit.set(0usize, 1u8, {
+            let apply_grain: u32 = unsafe { ::std::mem::transmute(apply_grain) };
+            apply_grain as u64
+        });
+        __bindgen_bitfield_unit.set(1usize, 1u8, {
+            let chroma_scaling_from_luma: u32 =
+                unsafe { ::std::mem::transmute(chroma_scaling_from_luma) };
+            chroma_scaling_from_luma as u64
+        });
+        __bindgen_bitfield_unit.set(2usize, 2u8, {
+            let grain_scaling_minus_8: u32 =
+                unsafe { ::std::mem::transmute(grain_scaling_minus_8) };
+            grain_scaling_minus_8 as u64
+        });
+        __bindgen_bitfield_unit.set(4usize, 2u8, {
+            let ar_coeff_lag: u32 = unsafe { ::std::mem::transmute(ar_coeff_lag) };
+            ar_coeff_lag as u64
+        });
+        __bindgen_bitfield_unit.set(6usize, 2u8, {
+            let ar_coeff_shift_minus_6: u32 =
+                unsafe { ::std::mem::transmute(ar_coeff_shift_minus_6) };
+            ar_coeff_shift_minus_6 as u64
+        });
+        __bindgen_bitfield_unit.set(8usize, 2u8, {
+            let grain_scale_shift: u32 = unsafe { ::std::mem::transmute(grain_scale_shift) };
+            grain_scale_shift as u64
+        });
+        __bindgen_bitfield_unit.set(10usize, 1u8, {
+            let overlap_flag: u32 = unsafe { ::std::mem::transmute(overlap_flag) };
+            overlap_flag as u64
+        });
+        __bindgen_bitfield_unit.set(11usize, 1u8, {
+            let clip_to_restricted_range: u32 =
+                unsafe { ::std::mem::transmute(clip_to_restricted_range) };
+            clip_to_restricted_range as u64
+        });
+        __bindgen_bitfield_unit.set(12usize, 20u8, {
+            let reserved: u32 = unsafe { ::std::mem::transmute(reserved) };
+            reserved as u64
+        });
+        __bindgen_bitfield_unit
+    }
+}
+impl Default for _VAFilmGrainStructAV1__bindgen_ty_1 {
+    fn default() -> Self {
+        let mut s = ::std::mem::MaybeUninit::<Self>::uninit();
+        unsafe {
+            ::std::ptr::write_bytes(s.as_mut_ptr(), 0, 1);
+            s.assume_init()
+        }
+    }
+}
+impl Default for _VAFilmGrainStructAV1 {
+    fn default() -> Self {
+        let mut s = ::std::mem::MaybeUninit::<Self>::uninit();
+        unsafe {
+            ::std::ptr::write_bytes(s.as_mut_ptr(), 0, 1);
+            s.assume_init()
         }
     }
+}
+pub type VAFilmGrainStructAV1 = _VAFilmGrainStructAV1;
+pub mod VAAV1TransformationType {
+    pub type Type = ::std::os::raw::c_uint;
+    pub const VAAV1TransformationIdentity: Type = 0;
+    pub const VAAV1TransformationTranslation: Type = 1;
+    pub const VAAV1TransformationRotzoom: Type = 2;
+    pub const VAAV1TransformationAffine: Type = 3;
+    pub const VAAV1TransformationCount: Type = 4;
+}
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct _VAWarpedMotionParamsAV1 {
+    pub wmtype: VAAV1TransformationType::Type,
+    pub wmmat: [i32; 8usize],
+    pub invalid: u8,
+    pub va_reserved: [u32; 4usize],
+}
+pub type VAWarpedMotionParamsAV1 = _VAWarpedMotionParamsAV1;
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct _VADecPictureParameterBufferAV1 {
+    pub profile: u8,
+    pub order_hint_bits_minus_1: u8,
+    pub bit_depth_idx: u8,
+    pub matrix_coefficients: u8,
+    pub seq_info_fields: _VADecPictureParameterBufferAV1__bindgen_ty_1,
+    pub current_frame: VASurfaceID,
+    pub current_display_picture: VASurfaceID,
+    pub anchor_frames_num: u8,
+    pub anchor_frames_list: *mut VASurfaceID,
+    pub frame_width_minus1: u16,
+    pub frame_height_minus1: u16,
+    pub output_frame_width_in_tiles_minus_1: u16,
+    pub output_frame_height_in_tiles_minus_1: u16,
+    pub ref_frame_map: [VASurfaceID; 8usize],
+    pub ref_frame_idx: [u8; 7usize],
+    pub primary_ref_frame: u8,
+    pub order_hint: u8,
+    pub seg_info: VASegmentationStructAV1,
+    pub film_grain_info: VAFilmGrainStructAV1,
+    pub tile_cols: u8,
+    pub tile_rows: u8,
+    pub width_in_sbs_minus_1: [u16; 63usize],
+    pub height_in_sbs_minus_1: [u16; 63usize],
+    pub tile_count_minus_1: u16,
+    pub context_update_tile_id: u16,
+    pub pic_info_fields: _VADecPictureParameterBufferAV1__bindgen_ty_2,
+    pub superres_scale_denominator: u8,
+    pub interp_filter: u8,
+    pub filter_level: [u8; 2usize],
+    pub filter_level_u: u8,
+    pub filter_level_v: u8,
+    pub loop_filter_info_fields: _VADecPictureParameterBufferAV1__bindgen_ty_3,
+    pub ref_deltas: [i8; 8usize],
+    pub mode_deltas: [i8; 2usize],
+    pub base_qindex: u8,
+    pub y_dc_delta_q: i8,
+    pub u_dc_delta_q: i8,
+    pub u_ac_delta_q: i8,
+    pub v_dc_delta_q: i8,
+    pub v_ac_delta_q: i8,
+    pub qmatrix_fields: _VADecPictureParameterBufferAV1__bindgen_ty_4,
+    pub mode_control_fields: _VADecPictureParameterBufferAV1__bindgen_ty_5,
+    pub cdef_damping_minus_3: u8,
+    pub cdef_bits: u8,
+    pub cdef_y_strengths: [u8; 8usize],
+    pub cdef_uv_strengths: [u8; 8usize],
+    pub loop_restoration_fields: _VADecPictureParameterBufferAV1__bindgen_ty_6,
+    pub wm: [VAWarpedMotionParamsAV1; 7usize],
+    pub va_reserved: [u32; 8usize],
+}
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub union _VADecPictureParameterBufferAV1__bindgen_ty_1 {
+    pub fields: _VADecPictureParameterBufferAV1__bindgen_ty_1__bindgen_ty_1,
+    pub value: u32,
+}
+#[repr(C)]
+#[repr(align(4))]
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct _VADecPictureParameterBufferAV1__bindgen_ty_1__bindgen_ty_1 {
+    pub _bitfield_align_1: [u8; 0],
+    pub _bitfield_1: __BindgenBitfieldUnit<[u8; 4usize]>,
+}
+impl _VADecPictureParameterBufferAV1__bindgen_ty_1__bindgen_ty_1 {
     #[inline]
-    pub fn transform8x8_flag(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(15usize, 1u8) as u32) }
+    pub fn still_picture(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(0usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_transform8x8_flag(&mut self, val: u32) {
+    pub fn set_still_picture(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(15usize, 1u8, val as u64)
+            self._bitfield_1.set(0usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn reserved3(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(16usize, 1u8) as u32) }
+    pub fn use_128x128_superblock(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(1usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_reserved3(&mut self, val: u32) {
+    pub fn set_use_128x128_superblock(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(16usize, 1u8, val as u64)
+            self._bitfield_1.set(1usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn dc_block_coded_cr_flag(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(17usize, 1u8) as u32) }
+    pub fn enable_filter_intra(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(2usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_dc_block_coded_cr_flag(&mut self, val: u32) {
+    pub fn set_enable_filter_intra(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(17usize, 1u8, val as u64)
+            self._bitfield_1.set(2usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn dc_block_coded_cb_flag(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(18usize, 1u8) as u32) }
+    pub fn enable_intra_edge_filter(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(3usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_dc_block_coded_cb_flag(&mut self, val: u32) {
+    pub fn set_enable_intra_edge_filter(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(18usize, 1u8, val as u64)
+            self._bitfield_1.set(3usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn dc_block_coded_y_flag(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(19usize, 1u8) as u32) }
+    pub fn enable_interintra_compound(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(4usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_dc_block_coded_y_flag(&mut self, val: u32) {
+    pub fn set_enable_interintra_compound(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(19usize, 1u8, val as u64)
+            self._bitfield_1.set(4usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn reserved4(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(20usize, 12u8) as u32) }
+    pub fn enable_masked_compound(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(5usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_reserved4(&mut self, val: u32) {
+    pub fn set_enable_masked_compound(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(20usize, 12u8, val as u64)
+            self._bitfield_1.set(5usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn horz_origin(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(32usize, 8u8) as u32) }
+    pub fn enable_dual_filter(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(6usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_horz_origin(&mut self, val: u32) {
+    pub fn set_enable_dual_filter(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(32usize, 8u8, val as u64)
+            self._bitfield_1.set(6usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn vert_origin(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(40usize, 8u8) as u32) }
+    pub fn enable_order_hint(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(7usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_vert_origin(&mut self, val: u32) {
+    pub fn set_enable_order_hint(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(40usize, 8u8, val as u64)
+            self._bitfield_1.set(7usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn cbp_y(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(48usize, 16u8) as u32) }
+    pub fn enable_jnt_comp(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(8usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_cbp_y(&mut self, val: u32) {
+    pub fn set_enable_jnt_comp(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(48usize, 16u8, val as u64)
+            self._bitfield_1.set(8usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn cbp_cb(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(64usize, 16u8) as u32) }
+    pub fn enable_cdef(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(9usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_cbp_cb(&mut self, val: u32) {
+    pub fn set_enable_cdef(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(64usize, 16u8, val as u64)
+            self._bitfield_1.set(9usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn cbp_cr(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(80usize, 16u8) as u32) }
+    pub fn mono_chrome(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(10usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_cbp_cr(&mut self, val: u32) {
+    pub fn set_mono_chrome(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(80usize, 16u8, val as u64)
+            self._bitfield_1.set(10usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn qp_prime_y(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(96usize, 8u8) as u32) }
+    pub fn color_range(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(11usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_qp_prime_y(&mut self, val: u32) {
+    pub fn set_color_range(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(96usize, 8u8, val as u64)
+            self._bitfield_1.set(11usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn reserved5(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(104usize, 17u8) as u32) }
+    pub fn subsampling_x(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(12usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_reserved5(&mut self, val: u32) {
+    pub fn set_subsampling_x(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(104usize, 17u8, val as u64)
+            self._bitfield_1.set(12usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn mb_skip_conv_disable(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(121usize, 1u8) as u32) }
+    pub fn subsampling_y(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(13usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_mb_skip_conv_disable(&mut self, val: u32) {
+    pub fn set_subsampling_y(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(121usize, 1u8, val as u64)
+            self._bitfield_1.set(13usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn is_last_mb(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(122usize, 1u8) as u32) }
+    pub fn chroma_sample_position(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(14usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_is_last_mb(&mut self, val: u32) {
+    pub fn set_chroma_sample_position(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(122usize, 1u8, val as u64)
+            self._bitfield_1.set(14usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn enable_coefficient_clamp(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(123usize, 1u8) as u32) }
+    pub fn film_grain_params_present(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(15usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_enable_coefficient_clamp(&mut self, val: u32) {
+    pub fn set_film_grain_params_present(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(123usize, 1u8, val as u64)
+            self._bitfield_1.set(15usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn direct8x8_pattern(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(124usize, 4u8) as u32) }
+    pub fn reserved(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(16usize, 16u8) as u32) }
     }
     #[inline]
-    pub fn set_direct8x8_pattern(&mut self, val: u32) {
+    pub fn set_reserved(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(124usize, 4u8, val as u64)
+            self._bitfield_1.set(16usize, 16u8, val as u64)
         }
     }
     #[inline]
     pub fn new_bitfield_1(
-        inter_mb_mode: u32,
-        mb_skip_flag: u32,
-        reserved1: u32,
-        intra_mb_mode: u32,
-        reserved2: u32,
-        field_mb_polarity_flag: u32,
-        mb_type: u32,
-        intra_mb_flag: u32,
-        field_mb_flag: u32,
-        transform8x8_flag: u32,
-        reserved3: u32,
-        dc_block_coded_cr_flag: u32,
-        dc_block_coded_cb_flag: u32,
-        dc_block_coded_y_flag: u32,
-        reserved4: u32,
-        horz_origin: u32,
-        vert_origin: u32,
-        cbp_y: u32,
-        cbp_cb: u32,
-        cbp_cr: u32,
-        qp_prime_y: u32,
-        reserved5: u32,
-        mb_skip_conv_disable: u32,
-        is_last_mb: u32,
-        enable_coefficient_clamp: u32,
-        direct8x8_pattern: u32,
-    ) -> __BindgenBitfieldUnit<[u8; 16usize]> {
-        let mut __bindgen_bitfield_unit: __BindgenBitfieldUnit<[u8; 16usize]> = Default::default();
-        __bindgen_bitfield_unit.set(0usize, 2u8, {
-            let inter_mb_mode: u32 = unsafe { ::std::mem::transmute(inter_mb_mode) };
-            inter_mb_mode as u64
+        still_picture: u32,
+        use_128x128_superblock: u32,
+        enable_filter_intra: u32,
+        enable_intra_edge_filter: u32,
+        enable_interintra_compound: u32,
+        enable_masked_compound: u32,
+        enable_dual_filter: u32,
+        enable_order_hint: u32,
+        enable_jnt_comp: u32,
+        enable_cdef: u32,
+        mono_chrome: u32,
+        color_range: u32,
+        subsampling_x: u32,
+        subsampling_y: u32,
+        chroma_sample_position: u32,
+        film_grain_params_present: u32,
+        reserved: u32,
+    ) -> __BindgenBitfieldUnit<[u8; 4usize]> {
+        let mut __bindgen_bitfield_unit: __BindgenBitfieldUnit<[u8; 4usize]> = Default::default();
+        __bindgen_bitfield_unit.set(0usize, 1u8, {
+            let still_picture: u32 = unsafe { ::std::mem::transmute(still_picture) };
+            still_picture as u64
+        });
+        __bindgen_bitfield_unit.set(1usize, 1u8, {
+            let use_128x128_superblock: u32 =
+                unsafe { ::std::mem::transmute(use_128x128_superblock) };
+            use_128x128_superblock as u64
         });
         __bindgen_bitfield_unit.set(2usize, 1u8, {
-            let mb_skip_flag: u32 = unsafe { ::std::mem::transmute(mb_skip_flag) };
-            mb_skip_flag as u64
+            let enable_filter_intra: u32 = unsafe { ::std::mem::transmute(enable_filter_intra) };
+            enable_filter_intra as u64
         });
         __bindgen_bitfield_unit.set(3usize, 1u8, {
-            let reserved1: u32 = unsafe { ::std::mem::transmute(reserved1) };
-            reserved1 as u64
+            let enable_intra_edge_filter: u32 =
+                unsafe { ::std::mem::transmute(enable_intra_edge_filter) };
+            enable_intra_edge_filter as u64
         });
-        __bindgen_bitfield_unit.set(4usize, 2u8, {
-            let intra_mb_mode: u32 = unsafe { ::std::mem::transmute(intra_mb_mode) };
-            intra_mb_mode as u64
+        __bindgen_bitfield_unit.set(4usize, 1u8, {
+            let enable_interintra_compound: u32 =
+                unsafe { ::std::mem::transmute(enable_interintra_compound) };
+            enable_interintra_compound as u64
+        });
+        __bindgen_bitfield_unit.set(5usize, 1u8, {
+            let enable_masked_compound: u32 =
+                unsafe { ::std::mem::transmute(enable_masked_compound) };
+            enable_masked_compound as u64
         });
         __bindgen_bitfield_unit.set(6usize, 1u8, {
-            let reserved2: u32 = unsafe { ::std::mem::transmute(reserved2) };
-            reserved2 as u64
+            let enable_dual_filter: u32 = unsafe { ::std::mem::transmute(enable_dual_filter) };
+            enable_dual_filter as u64
         });
         __bindgen_bitfield_unit.set(7usize, 1u8, {
-            let field_mb_polarity_flag: u32 =
-                unsafe { ::std::mem::transmute(field_mb_polarity_flag) };
-            field_mb_polarity_flag as u64
-        });
-        __bindgen_bitfield_unit.set(8usize, 5u8, {
-            let mb_type: u32 = unsafe { ::std::mem::transmute(mb_type) };
-            mb_type as u64
-        });
-        __bindgen_bitfield_unit.set(13usize, 1u8, {
-            let intra_mb_flag: u32 = unsafe { ::std::mem::transmute(intra_mb_flag) };
-            intra_mb_flag as u64
-        });
-        __bindgen_bitfield_unit.set(14usize, 1u8, {
-            let field_mb_flag: u32 = unsafe { ::std::mem::transmute(field_mb_flag) };
-            field_mb_flag as u64
-        });
-        __bindgen_bitfield_unit.set(15usize, 1u8, {
-            let transform8x8_flag: u32 = unsafe { ::std::mem::transmute(transform8x8_flag) };
-            transform8x8_flag as u64
-        });
-        __bindgen_bitfield_unit.set(16usize, 1u8, {
-            let reserved3: u32 = unsafe { ::std::mem::transmute(reserved3) };
-            reserved3 as u64
-        });
-        __bindgen_bitfield_unit.set(17usize, 1u8, {
-            let dc_block_coded_cr_flag: u32 =
-                unsafe { ::std::mem::transmute(dc_block_coded_cr_flag) };
-            dc_block_coded_cr_flag as u64
-        });
-        __bindgen_bitfield_unit.set(18usize, 1u8, {
-            let dc_block_coded_cb_flag: u32 =
-                unsafe { ::std::mem::transmute(dc_block_coded_cb_flag) };
-            dc_block_coded_cb_flag as u64
-        });
-        __bindgen_bitfield_unit.set(19usize, 1u8, {
-            let dc_block_coded_y_flag: u32 =
-                unsafe { ::std::mem::transmute(dc_block_coded_y_flag) };
-            dc_block_coded_y_flag as u64
-        });
-        __bindgen_bitfield_unit.set(20usize, 12u8, {
-            let reserved4: u32 = unsafe { ::std::mem::transmute(reserved4) };
-            reserved4 as u64
-        });
-        __bindgen_bitfield_unit.set(32usize, 8u8, {
-            let horz_origin: u32 = unsafe { ::std::mem::transmute(horz_origin) };
-            horz_origin as u64
+            let enable_order_hint: u32 = unsafe { ::std::mem::transmute(enable_order_hint) };
+            enable_order_hint as u64
         });
-        __bindgen_bitfield_unit.set(40usize, 8u8, {
-            let vert_origin: u32 = unsafe { ::std::mem::transmute(vert_origin) };
-            vert_origin as u64
-        });
-        __bindgen_bitfield_unit.set(48usize, 16u8, {
-            let cbp_y: u32 = unsafe { ::std::mem::transmute(cbp_y) };
-            cbp_y as u64
+        __bindgen_bitfield_unit.set(8usize, 1u8, {
+            let enable_jnt_comp: u32 = unsafe { ::std::mem::transmute(enable_jnt_comp) };
+            enable_jnt_comp as u64
         });
-        __bindgen_bitfield_unit.set(64usize, 16u8, {
-            let cbp_cb: u32 = unsafe { ::std::mem::transmute(cbp_cb) };
-            cbp_cb as u64
+        __bindgen_bitfield_unit.set(9usize, 1u8, {
+            let enable_cdef: u32 = unsafe { ::std::mem::transmute(enable_cdef) };
+            enable_cdef as u64
         });
-        __bindgen_bitfield_unit.set(80usize, 16u8, {
-            let cbp_cr: u32 = unsafe { ::std::mem::transmute(cbp_cr) };
-            cbp_cr as u64
+        __bindgen_bitfield_unit.set(10usize, 1u8, {
+            let mono_chrome: u32 = unsafe { ::std::mem::transmute(mono_chrome) };
+            mono_chrome as u64
         });
-        __bindgen_bitfield_unit.set(96usize, 8u8, {
-            let qp_prime_y: u32 = unsafe { ::std::mem::transmute(qp_prime_y) };
-            qp_prime_y as u64
+        __bindgen_bitfield_unit.set(11usize, 1u8, {
+            let color_range: u32 = unsafe { ::std::mem::transmute(color_range) };
+            color_range as u64
         });
-        __bindgen_bitfield_unit.set(104usize, 17u8, {
-            let reserved5: u32 = unsafe { ::std::mem::transmute(reserved5) };
-            reserved5 as u64
+        __bindgen_bitfield_unit.set(12usize, 1u8, {
+            let subsampling_x: u32 = unsafe { ::std::mem::transmute(subsampling_x) };
+            subsampling_x as u64
         });
-        __bindgen_bitfield_unit.set(121usize, 1u8, {
-            let mb_skip_conv_disable: u32 = unsafe { ::std::mem::transmute(mb_skip_conv_disable) };
-            mb_skip_conv_disable as u64
+        __bindgen_bitfield_unit.set(13usize, 1u8, {
+            let subsampling_y: u32 = unsafe { ::std::mem::transmute(subsampling_y) };
+            subsampling_y as u64
         });
-        __bindgen_bitfield_unit.set(122usize, 1u8, {
-            let is_last_mb: u32 = unsafe { ::std::mem::transmute(is_last_mb) };
-            is_last_mb as u64
+        __bindgen_bitfield_unit.set(14usize, 1u8, {
+            let chroma_sample_position: u32 =
+                unsafe { ::std::mem::transmute(chroma_sample_position) };
+            chroma_sample_position as u64
         });
-        __bindgen_bitfield_unit.set(123usize, 1u8, {
-            let enable_coefficient_clamp: u32 =
-                unsafe { ::std::mem::transmute(enable_coefficient_clamp) };
-            enable_coefficient_clamp as u64
+        __bindgen_bitfield_unit.set(15usize, 1u8, {
+            let film_grain_params_present: u32 =
+                unsafe { ::std::mem::transmute(film_grain_params_present) };
+            film_grain_params_present as u64
         });
-        __bindgen_bitfield_unit.set(124usize, 4u8, {
-            let direct8x8_pattern: u32 = unsafe { ::std::mem::transmute(direct8x8_pattern) };
-            direct8x8_pattern as u64
+        __bindgen_bitfield_unit.set(16usize, 16u8, {
+            let reserved: u32 = unsafe { ::std::mem::transmute(reserved) };
+            reserved as u64
         });
         __bindgen_bitfield_unit
     }
-    #[inline]
-    pub fn reserved8(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_2.get(0usize, 16u8) as u32) }
-    }
-    #[inline]
-    pub fn set_reserved8(&mut self, val: u32) {
+}
+impl Default for _VADecPictureParameterBufferAV1__bindgen_ty_1 {
+    fn default() -> Self {
+        let mut s = ::std::mem::MaybeUninit::<Self>::uninit();
         unsafe {
-            let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_2.set(0usize, 16u8, val as u64)
+            ::std::ptr::write_bytes(s.as_mut_ptr(), 0, 1);
+            s.assume_init()
         }
     }
+}
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub union _VADecPictureParameterBufferAV1__bindgen_ty_2 {
+    pub bits: _VADecPictureParameterBufferAV1__bindgen_ty_2__bindgen_ty_1,
+    pub value: u32,
+}
+#[repr(C)]
+#[repr(align(4))]
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct _VADecPictureParameterBufferAV1__bindgen_ty_2__bindgen_ty_1 {
+    pub _bitfield_align_1: [u8; 0],
+    pub _bitfield_1: __BindgenBitfieldUnit<[u8; 4usize]>,
+}
+impl _VADecPictureParameterBufferAV1__bindgen_ty_2__bindgen_ty_1 {
     #[inline]
-    pub fn target_size_in_word(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_2.get(16usize, 8u8) as u32) }
+    pub fn frame_type(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(0usize, 2u8) as u32) }
     }
     #[inline]
-    pub fn set_target_size_in_word(&mut self, val: u32) {
+    pub fn set_frame_type(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_2.set(16usize, 8u8, val as u64)
+            self._bitfield_1.set(0usize, 2u8, val as u64)
         }
     }
     #[inline]
-    pub fn max_size_in_word(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_2.get(24usize, 8u8) as u32) }
+    pub fn show_frame(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(2usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_max_size_in_word(&mut self, val: u32) {
+    pub fn set_show_frame(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_2.set(24usize, 8u8, val as u64)
+            self._bitfield_1.set(2usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn new_bitfield_2(
-        reserved8: u32,
-        target_size_in_word: u32,
-        max_size_in_word: u32,
-    ) -> __BindgenBitfieldUnit<[u8; 4usize]> {
-        let mut __bindgen_bitfield_unit: __BindgenBitfieldUnit<[u8; 4usize]> = Default::default();
-        __bindgen_bitfield_unit.set(0usize, 16u8, {
-            let reserved8: u32 = unsafe { ::std::mem::transmute(reserved8) };
-            reserved8 as u64
-        });
-        __bindgen_bitfield_unit.set(16usize, 8u8, {
-            let target_size_in_word: u32 = unsafe { ::std::mem::transmute(target_size_in_word) };
-            target_size_in_word as u64
-        });
-        __bindgen_bitfield_unit.set(24usize, 8u8, {
-            let max_size_in_word: u32 = unsafe { ::std::mem::transmute(max_size_in_word) };
-            max_size_in_word as u64
-        });
-        __bindgen_bitfield_unit
-    }
-}
-pub type VAEncFEIMBCodeH264 = _VAEncFEIMBCodeH264;
-#[repr(C)]
-#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
-pub struct _VAEncFEIDistortionH264 {
-    pub inter_distortion: [u16; 16usize],
-    pub _bitfield_align_1: [u16; 0],
-    pub _bitfield_1: __BindgenBitfieldUnit<[u8; 8usize]>,
-    pub reserved1: [u32; 2usize],
-}
-impl _VAEncFEIDistortionH264 {
-    #[inline]
-    pub fn best_inter_distortion(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(0usize, 16u8) as u32) }
+    pub fn showable_frame(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(3usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_best_inter_distortion(&mut self, val: u32) {
+    pub fn set_showable_frame(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(0usize, 16u8, val as u64)
+            self._bitfield_1.set(3usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn best_intra_distortion(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(16usize, 16u8) as u32) }
+    pub fn error_resilient_mode(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(4usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_best_intra_distortion(&mut self, val: u32) {
+    pub fn set_error_resilient_mode(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(16usize, 16u8, val as u64)
+            self._bitfield_1.set(4usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn colocated_mb_distortion(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(32usize, 16u8) as u32) }
+    pub fn disable_cdf_update(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(5usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_colocated_mb_distortion(&mut self, val: u32) {
+    pub fn set_disable_cdf_update(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(32usize, 16u8, val as u64)
+            self._bitfield_1.set(5usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn reserved0(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(48usize, 16u8) as u32) }
+    pub fn allow_screen_content_tools(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(6usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_reserved0(&mut self, val: u32) {
+    pub fn set_allow_screen_content_tools(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(48usize, 16u8, val as u64)
-        }
-    }
-    #[inline]
-    pub fn new_bitfield_1(
-        best_inter_distortion: u32,
-        best_intra_distortion: u32,
-        colocated_mb_distortion: u32,
-        reserved0: u32,
-    ) -> __BindgenBitfieldUnit<[u8; 8usize]> {
-        let mut __bindgen_bitfield_unit: __BindgenBitfieldUnit<[u8; 8usize]> = Default::default();
-        __bindgen_bitfield_unit.set(0usize, 16u8, {
-            let best_inter_distortion: u32 =
-                unsafe { ::std::mem::transmute(best_inter_distortion) };
-            best_inter_distortion as u64
-        });
-        __bindgen_bitfield_unit.set(16usize, 16u8, {
-            let best_intra_distortion: u32 =
-                unsafe { ::std::mem::transmute(best_intra_distortion) };
-            best_intra_distortion as u64
-        });
-        __bindgen_bitfield_unit.set(32usize, 16u8, {
-            let colocated_mb_distortion: u32 =
-                unsafe { ::std::mem::transmute(colocated_mb_distortion) };
-            colocated_mb_distortion as u64
-        });
-        __bindgen_bitfield_unit.set(48usize, 16u8, {
-            let reserved0: u32 = unsafe { ::std::mem::transmute(reserved0) };
-            reserved0 as u64
-        });
-        __bindgen_bitfield_unit
-    }
-}
-pub type VAEncFEIDistortionH264 = _VAEncFEIDistortionH264;
-#[repr(C)]
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
-pub struct _VAStatsStatisticsParameterH264 {
-    pub stats_params: VAStatsStatisticsParameter,
-    pub _bitfield_align_1: [u32; 0],
-    pub _bitfield_1: __BindgenBitfieldUnit<[u8; 16usize]>,
-    pub reserved4: [u32; 2usize],
-}
-impl Default for _VAStatsStatisticsParameterH264 {
-    fn default() -> Self {
-        let mut s = ::std::mem::MaybeUninit::<Self>::uninit();
-        unsafe {
-            ::std::ptr::write_bytes(s.as_mut_ptr(), 0, 1);
-            s.assume_init()
+            self._bitfield_1.set(6usize, 1u8, val as u64)
         }
     }
-}
-impl _VAStatsStatisticsParameterH264 {
     #[inline]
-    pub fn frame_qp(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(0usize, 8u8) as u32) }
+    pub fn force_integer_mv(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(7usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_frame_qp(&mut self, val: u32) {
+    pub fn set_force_integer_mv(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(0usize, 8u8, val as u64)
+            self._bitfield_1.set(7usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn len_sp(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(8usize, 8u8) as u32) }
+    pub fn allow_intrabc(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(8usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_len_sp(&mut self, val: u32) {
+    pub fn set_allow_intrabc(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(8usize, 8u8, val as u64)
+            self._bitfield_1.set(8usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn search_path(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(16usize, 8u8) as u32) }
+    pub fn use_superres(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(9usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_search_path(&mut self, val: u32) {
+    pub fn set_use_superres(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(16usize, 8u8, val as u64)
+            self._bitfield_1.set(9usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn reserved0(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(24usize, 8u8) as u32) }
+    pub fn allow_high_precision_mv(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(10usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_reserved0(&mut self, val: u32) {
+    pub fn set_allow_high_precision_mv(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(24usize, 8u8, val as u64)
+            self._bitfield_1.set(10usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn sub_mb_part_mask(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(32usize, 7u8) as u32) }
+    pub fn is_motion_mode_switchable(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(11usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_sub_mb_part_mask(&mut self, val: u32) {
+    pub fn set_is_motion_mode_switchable(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(32usize, 7u8, val as u64)
+            self._bitfield_1.set(11usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn sub_pel_mode(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(39usize, 2u8) as u32) }
+    pub fn use_ref_frame_mvs(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(12usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_sub_pel_mode(&mut self, val: u32) {
+    pub fn set_use_ref_frame_mvs(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(39usize, 2u8, val as u64)
+            self._bitfield_1.set(12usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn inter_sad(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(41usize, 2u8) as u32) }
+    pub fn disable_frame_end_update_cdf(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(13usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_inter_sad(&mut self, val: u32) {
+    pub fn set_disable_frame_end_update_cdf(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(41usize, 2u8, val as u64)
+            self._bitfield_1.set(13usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn intra_sad(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(43usize, 2u8) as u32) }
+    pub fn uniform_tile_spacing_flag(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(14usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_intra_sad(&mut self, val: u32) {
+    pub fn set_uniform_tile_spacing_flag(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(43usize, 2u8, val as u64)
+            self._bitfield_1.set(14usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn adaptive_search(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(45usize, 1u8) as u32) }
+    pub fn allow_warped_motion(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(15usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_adaptive_search(&mut self, val: u32) {
+    pub fn set_allow_warped_motion(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(45usize, 1u8, val as u64)
+            self._bitfield_1.set(15usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn mv_predictor_ctrl(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(46usize, 3u8) as u32) }
+    pub fn large_scale_tile(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(16usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_mv_predictor_ctrl(&mut self, val: u32) {
+    pub fn set_large_scale_tile(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(46usize, 3u8, val as u64)
+            self._bitfield_1.set(16usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn mb_qp(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(49usize, 1u8) as u32) }
+    pub fn reserved(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(17usize, 15u8) as u32) }
     }
     #[inline]
-    pub fn set_mb_qp(&mut self, val: u32) {
+    pub fn set_reserved(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(49usize, 1u8, val as u64)
+            self._bitfield_1.set(17usize, 15u8, val as u64)
         }
     }
     #[inline]
-    pub fn ft_enable(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(50usize, 1u8) as u32) }
+    pub fn new_bitfield_1(
+        frame_type: u32,
+        show_frame: u32,
+        showable_frame: u32,
+        error_resilient_mode: u32,
+        disable_cdf_update: u32,
+        allow_screen_content_tools: u32,
+        force_integer_mv: u32,
+        allow_intrabc: u32,
+        use_superres: u32,
+        allow_high_precision_mv: u32,
+        is_motion_mode_switchable: u32,
+        use_ref_frame_mvs: u32,
+        disable_frame_end_update_cdf: u32,
+        uniform_tile_spacing_flag: u32,
+        allow_warped_motion: u32,
+        large_scale_tile: u32,
+        reserved: u32,
+    ) -> __BindgenBitfieldUnit<[u8; 4usize]> {
+        let mut __bindgen_bitfield_unit: __BindgenBitfieldUnit<[u8; 4usize]> = Default::default();
+        __bindgen_bitfield_unit.set(0usize, 2u8, {
+            let frame_type: u32 = unsafe { ::std::mem::transmute(frame_type) };
+            frame_type as u64
+        });
+        __bindgen_bitfield_unit.set(2usize, 1u8, {
+            let show_frame: u32 = unsafe { ::std::mem::transmute(show_frame) };
+            show_frame as u64
+        });
+        __bindgen_bitfield_unit.set(3usize, 1u8, {
+            let showable_frame: u32 = unsafe { ::std::mem::transmute(showable_frame) };
+            showable_frame as u64
+        });
+        __bindgen_bitfield_unit.set(4usize, 1u8, {
+            let error_resilient_mode: u32 = unsafe { ::std::mem::transmute(error_resilient_mode) };
+            error_resilient_mode as u64
+        });
+        __bindgen_bitfield_unit.set(5usize, 1u8, {
+            let disable_cdf_update: u32 = unsafe { ::std::mem::transmute(disable_cdf_update) };
+            disable_cdf_update as u64
+        });
+        __bindgen_bitfield_unit.set(6usize, 1u8, {
+            let allow_screen_content_tools: u32 =
+                unsafe { ::std::mem::transmute(allow_screen_content_tools) };
+            allow_screen_content_tools as u64
+        });
+        __bindgen_bitfield_unit.set(7usize, 1u8, {
+            let force_integer_mv: u32 = unsafe { ::std::mem::transmute(force_integer_mv) };
+            force_integer_mv as u64
+        });
+        __bindgen_bitfield_unit.set(8usize, 1u8, {
+            let allow_intrabc: u32 = unsafe { ::std::mem::transmute(allow_intrabc) };
+            allow_intrabc as u64
+        });
+        __bindgen_bitfield_unit.set(9usize, 1u8, {
+            let use_superres: u32 = unsafe { ::std::mem::transmute(use_superres) };
+            use_superres as u64
+        });
+        __bindgen_bitfield_unit.set(10usize, 1u8, {
+            let allow_high_precision_mv: u32 =
+                unsafe { ::std::mem::transmute(allow_high_precision_mv) };
+            allow_high_precision_mv as u64
+        });
+        __bindgen_bitfield_unit.set(11usize, 1u8, {
+            let is_motion_mode_switchable: u32 =
+                unsafe { ::std::mem::transmute(is_motion_mode_switchable) };
+            is_motion_mode_switchable as u64
+        });
+        __bindgen_bitfield_unit.set(12usize, 1u8, {
+            let use_ref_frame_mvs: u32 = unsafe { ::std::mem::transmute(use_ref_frame_mvs) };
+            use_ref_frame_mvs as u64
+        });
+        __bindgen_bitfield_unit.set(13usize, 1u8, {
+            let disable_frame_end_update_cdf: u32 =
+                unsafe { ::std::mem::transmute(disable_frame_end_update_cdf) };
+            disable_frame_end_update_cdf as u64
+        });
+        __bindgen_bitfield_unit.set(14usize, 1u8, {
+            let uniform_tile_spacing_flag: u32 =
+                unsafe { ::std::mem::transmute(uniform_tile_spacing_flag) };
+            uniform_tile_spacing_flag as u64
+        });
+        __bindgen_bitfield_unit.set(15usize, 1u8, {
+            let allow_warped_motion: u32 = unsafe { ::std::mem::transmute(allow_warped_motion) };
+            allow_warped_motion as u64
+        });
+        __bindgen_bitfield_unit.set(16usize, 1u8, {
+            let large_scale_tile: u32 = unsafe { ::std::mem::transmute(large_scale_tile) };
+            large_scale_tile as u64
+        });
+        __bindgen_bitfield_unit.set(17usize, 15u8, {
+            let reserved: u32 = unsafe { ::std::mem::transmute(reserved) };
+            reserved as u64
+        });
+        __bindgen_bitfield_unit
     }
-    #[inline]
-    pub fn set_ft_enable(&mut self, val: u32) {
+}
+impl Default for _VADecPictureParameterBufferAV1__bindgen_ty_2 {
+    fn default() -> Self {
+        let mut s = ::std::mem::MaybeUninit::<Self>::uninit();
         unsafe {
-            let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(50usize, 1u8, val as u64)
+            ::std::ptr::write_bytes(s.as_mut_ptr(), 0, 1);
+            s.assume_init()
         }
     }
+}
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub union _VADecPictureParameterBufferAV1__bindgen_ty_3 {
+    pub bits: _VADecPictureParameterBufferAV1__bindgen_ty_3__bindgen_ty_1,
+    pub value: u8,
+}
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct _VADecPictureParameterBufferAV1__bindgen_ty_3__bindgen_ty_1 {
+    pub _bitfield_align_1: [u8; 0],
+    pub _bitfield_1: __BindgenBitfieldUnit<[u8; 1usize]>,
+}
+impl _VADecPictureParameterBufferAV1__bindgen_ty_3__bindgen_ty_1 {
     #[inline]
-    pub fn intra_part_mask(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(51usize, 5u8) as u32) }
+    pub fn sharpness_level(&self) -> u8 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(0usize, 3u8) as u8) }
     }
     #[inline]
-    pub fn set_intra_part_mask(&mut self, val: u32) {
+    pub fn set_sharpness_level(&mut self, val: u8) {
         unsafe {
-            let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(51usize, 5u8, val as u64)
+            let val: u8 = ::std::mem::transmute(val);
+            self._bitfield_1.set(0usize, 3u8, val as u64)
         }
     }
     #[inline]
-    pub fn reserved1(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(56usize, 8u8) as u32) }
+    pub fn mode_ref_delta_enabled(&self) -> u8 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(3usize, 1u8) as u8) }
     }
     #[inline]
-    pub fn set_reserved1(&mut self, val: u32) {
+    pub fn set_mode_ref_delta_enabled(&mut self, val: u8) {
         unsafe {
-            let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(56usize, 8u8, val as u64)
+            let val: u8 = ::std::mem::transmute(val);
+            self._bitfield_1.set(3usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn ref_width(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(64usize, 8u8) as u32) }
+    pub fn mode_ref_delta_update(&self) -> u8 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(4usize, 1u8) as u8) }
     }
     #[inline]
-    pub fn set_ref_width(&mut self, val: u32) {
+    pub fn set_mode_ref_delta_update(&mut self, val: u8) {
         unsafe {
-            let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(64usize, 8u8, val as u64)
+            let val: u8 = ::std::mem::transmute(val);
+            self._bitfield_1.set(4usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn ref_height(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(72usize, 8u8) as u32) }
+    pub fn reserved(&self) -> u8 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(5usize, 3u8) as u8) }
     }
     #[inline]
-    pub fn set_ref_height(&mut self, val: u32) {
+    pub fn set_reserved(&mut self, val: u8) {
         unsafe {
-            let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(72usize, 8u8, val as u64)
+            let val: u8 = ::std::mem::transmute(val);
+            self._bitfield_1.set(5usize, 3u8, val as u64)
         }
     }
     #[inline]
-    pub fn search_window(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(80usize, 4u8) as u32) }
+    pub fn new_bitfield_1(
+        sharpness_level: u8,
+        mode_ref_delta_enabled: u8,
+        mode_ref_delta_update: u8,
+        reserved: u8,
+    ) -> __BindgenBitfieldUnit<[u8; 1usize]> {
+        let mut __bindgen_bitfield_unit: __BindgenBitfieldUnit<[u8; 1usize]> = Default::default();
+        __bindgen_bitfield_unit.set(0usize, 3u8, {
+            let sharpness_level: u8 = unsafe { ::std::mem::transmute(sharpness_level) };
+            sharpness_level as u64
+        });
+        __bindgen_bitfield_unit.set(3usize, 1u8, {
+            let mode_ref_delta_enabled: u8 =
+                unsafe { ::std::mem::transmute(mode_ref_delta_enabled) };
+            mode_ref_delta_enabled as u64
+        });
+        __bindgen_bitfield_unit.set(4usize, 1u8, {
+            let mode_ref_delta_update: u8 = unsafe { ::std::mem::transmute(mode_ref_delta_update) };
+            mode_ref_delta_update as u64
+        });
+        __bindgen_bitfield_unit.set(5usize, 3u8, {
+            let reserved: u8 = unsafe { ::std::mem::transmute(reserved) };
+            reserved as u64
+        });
+        __bindgen_bitfield_unit
     }
-    #[inline]
-    pub fn set_search_window(&mut self, val: u32) {
+}
+impl Default for _VADecPictureParameterBufferAV1__bindgen_ty_3 {
+    fn default() -> Self {
+        let mut s = ::std::mem::MaybeUninit::<Self>::uninit();
         unsafe {
-            let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(80usize, 4u8, val as u64)
+            ::std::ptr::write_bytes(s.as_mut_ptr(), 0, 1);
+            s.assume_init()
         }
     }
+}
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub union _VADecPictureParameterBufferAV1__bindgen_ty_4 {
+    pub bits: _VADecPictureParameterBufferAV1__bindgen_ty_4__bindgen_ty_1,
+    pub value: u16,
+}
+#[repr(C)]
+#[repr(align(2))]
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct _VADecPictureParameterBufferAV1__bindgen_ty_4__bindgen_ty_1 {
+    pub _bitfield_align_1: [u8; 0],
+    pub _bitfield_1: __BindgenBitfieldUnit<[u8; 2usize]>,
+}
+impl _VADecPictureParameterBufferAV1__bindgen_ty_4__bindgen_ty_1 {
     #[inline]
-    pub fn reserved2(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(84usize, 12u8) as u32) }
+    pub fn using_qmatrix(&self) -> u16 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(0usize, 1u8) as u16) }
     }
     #[inline]
-    pub fn set_reserved2(&mut self, val: u32) {
+    pub fn set_using_qmatrix(&mut self, val: u16) {
         unsafe {
-            let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(84usize, 12u8, val as u64)
+            let val: u16 = ::std::mem::transmute(val);
+            self._bitfield_1.set(0usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn disable_mv_output(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(96usize, 1u8) as u32) }
+    pub fn qm_y(&self) -> u16 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(1usize, 4u8) as u16) }
     }
     #[inline]
-    pub fn set_disable_mv_output(&mut self, val: u32) {
+    pub fn set_qm_y(&mut self, val: u16) {
         unsafe {
-            let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(96usize, 1u8, val as u64)
+            let val: u16 = ::std::mem::transmute(val);
+            self._bitfield_1.set(1usize, 4u8, val as u64)
         }
     }
     #[inline]
-    pub fn disable_statistics_output(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(97usize, 1u8) as u32) }
+    pub fn qm_u(&self) -> u16 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(5usize, 4u8) as u16) }
     }
     #[inline]
-    pub fn set_disable_statistics_output(&mut self, val: u32) {
+    pub fn set_qm_u(&mut self, val: u16) {
         unsafe {
-            let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(97usize, 1u8, val as u64)
+            let val: u16 = ::std::mem::transmute(val);
+            self._bitfield_1.set(5usize, 4u8, val as u64)
         }
     }
     #[inline]
-    pub fn enable_8x8_statistics(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(98usize, 1u8) as u32) }
+    pub fn qm_v(&self) -> u16 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(9usize, 4u8) as u16) }
     }
     #[inline]
-    pub fn set_enable_8x8_statistics(&mut self, val: u32) {
+    pub fn set_qm_v(&mut self, val: u16) {
         unsafe {
-            let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(98usize, 1u8, val as u64)
+            let val: u16 = ::std::mem::transmute(val);
+            self._bitfield_1.set(9usize, 4u8, val as u64)
         }
     }
     #[inline]
-    pub fn reserved3(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(99usize, 29u8) as u32) }
+    pub fn reserved(&self) -> u16 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(13usize, 3u8) as u16) }
     }
     #[inline]
-    pub fn set_reserved3(&mut self, val: u32) {
+    pub fn set_reserved(&mut self, val: u16) {
         unsafe {
-            let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(99usize, 29u8, val as u64)
+            let val: u16 = ::std::mem::transmute(val);
+            self._bitfield_1.set(13usize, 3u8, val as u64)
         }
     }
     #[inline]
     pub fn new_bitfield_1(
-        frame_qp: u32,
-        len_sp: u32,
-        search_path: u32,
-        reserved0: u32,
-        sub_mb_part_mask: u32,
-        sub_pel_mode: u32,
-        inter_sad: u32,
-        intra_sad: u32,
-        adaptive_search: u32,
-        mv_predictor_ctrl: u32,
-        mb_qp: u32,
-        ft_enable: u32,
-        intra_part_mask: u32,
-        reserved1: u32,
-        ref_width: u32,
-        ref_height: u32,
-        search_window: u32,
-        reserved2: u32,
-        disable_mv_output: u32,
-        disable_statistics_output: u32,
-        enable_8x8_statistics: u32,
-        reserved3: u32,
-    ) -> __BindgenBitfieldUnit<[u8; 16usize]> {
-        let mut __bindgen_bitfield_unit: __BindgenBitfieldUnit<[u8; 16usize]> = Default::default();
-        __bindgen_bitfield_unit.set(0usize, 8u8, {
-            let frame_qp: u32 = unsafe { ::std::mem::transmute(frame_qp) };
-            frame_qp as u64
-        });
-        __bindgen_bitfield_unit.set(8usize, 8u8, {
-            let len_sp: u32 = unsafe { ::std::mem::transmute(len_sp) };
-            len_sp as u64
-        });
-        __bindgen_bitfield_unit.set(16usize, 8u8, {
-            let search_path: u32 = unsafe { ::std::mem::transmute(search_path) };
-            search_path as u64
-        });
-        __bindgen_bitfield_unit.set(24usize, 8u8, {
-            let reserved0: u32 = unsafe { ::std::mem::transmute(reserved0) };
-            reserved0 as u64
-        });
-        __bindgen_bitfield_unit.set(32usize, 7u8, {
-            let sub_mb_part_mask: u32 = unsafe { ::std::mem::transmute(sub_mb_part_mask) };
-            sub_mb_part_mask as u64
-        });
-        __bindgen_bitfield_unit.set(39usize, 2u8, {
-            let sub_pel_mode: u32 = unsafe { ::std::mem::transmute(sub_pel_mode) };
-            sub_pel_mode as u64
-        });
-        __bindgen_bitfield_unit.set(41usize, 2u8, {
-            let inter_sad: u32 = unsafe { ::std::mem::transmute(inter_sad) };
-            inter_sad as u64
-        });
-        __bindgen_bitfield_unit.set(43usize, 2u8, {
-            let intra_sad: u32 = unsafe { ::std::mem::transmute(intra_sad) };
-            intra_sad as u64
-        });
-        __bindgen_bitfield_unit.set(45usize, 1u8, {
-            let adaptive_search: u32 = unsafe { ::std::mem::transmute(adaptive_search) };
-            adaptive_search as u64
-        });
-        __bindgen_bitfield_unit.set(46usize, 3u8, {
-            let mv_predictor_ctrl: u32 = unsafe { ::std::mem::transmute(mv_predictor_ctrl) };
-            mv_predictor_ctrl as u64
-        });
-        __bindgen_bitfield_unit.set(49usize, 1u8, {
-            let mb_qp: u32 = unsafe { ::std::mem::transmute(mb_qp) };
-            mb_qp as u64
-        });
-        __bindgen_bitfield_unit.set(50usize, 1u8, {
-            let ft_enable: u32 = unsafe { ::std::mem::transmute(ft_enable) };
-            ft_enable as u64
-        });
-        __bindgen_bitfield_unit.set(51usize, 5u8, {
-            let intra_part_mask: u32 = unsafe { ::std::mem::transmute(intra_part_mask) };
-            intra_part_mask as u64
-        });
-        __bindgen_bitfield_unit.set(56usize, 8u8, {
-            let reserved1: u32 = unsafe { ::std::mem::transmute(reserved1) };
-            reserved1 as u64
-        });
-        __bindgen_bitfield_unit.set(64usize, 8u8, {
-            let ref_width: u32 = unsafe { ::std::mem::transmute(ref_width) };
-            ref_width as u64
-        });
-        __bindgen_bitfield_unit.set(72usize, 8u8, {
-            let ref_height: u32 = unsafe { ::std::mem::transmute(ref_height) };
-            ref_height as u64
-        });
-        __bindgen_bitfield_unit.set(80usize, 4u8, {
-            let search_window: u32 = unsafe { ::std::mem::transmute(search_window) };
-            search_window as u64
-        });
-        __bindgen_bitfield_unit.set(84usize, 12u8, {
-            let reserved2: u32 = unsafe { ::std::mem::transmute(reserved2) };
-            reserved2 as u64
+        using_qmatrix: u16,
+        qm_y: u16,
+        qm_u: u16,
+        qm_v: u16,
+        reserved: u16,
+    ) -> __BindgenBitfieldUnit<[u8; 2usize]> {
+        let mut __bindgen_bitfield_unit: __BindgenBitfieldUnit<[u8; 2usize]> = Default::default();
+        __bindgen_bitfield_unit.set(0usize, 1u8, {
+            let using_qmatrix: u16 = unsafe { ::std::mem::transmute(using_qmatrix) };
+            using_qmatrix as u64
         });
-        __bindgen_bitfield_unit.set(96usize, 1u8, {
-            let disable_mv_output: u32 = unsafe { ::std::mem::transmute(disable_mv_output) };
-            disable_mv_output as u64
+        __bindgen_bitfield_unit.set(1usize, 4u8, {
+            let qm_y: u16 = unsafe { ::std::mem::transmute(qm_y) };
+            qm_y as u64
         });
-        __bindgen_bitfield_unit.set(97usize, 1u8, {
-            let disable_statistics_output: u32 =
-                unsafe { ::std::mem::transmute(disable_statistics_output) };
-            disable_statistics_output as u64
+        __bindgen_bitfield_unit.set(5usize, 4u8, {
+            let qm_u: u16 = unsafe { ::std::mem::transmute(qm_u) };
+            qm_u as u64
         });
-        __bindgen_bitfield_unit.set(98usize, 1u8, {
-            let enable_8x8_statistics: u32 =
-                unsafe { ::std::mem::transmute(enable_8x8_statistics) };
-            enable_8x8_statistics as u64
+        __bindgen_bitfield_unit.set(9usize, 4u8, {
+            let qm_v: u16 = unsafe { ::std::mem::transmute(qm_v) };
+            qm_v as u64
         });
-        __bindgen_bitfield_unit.set(99usize, 29u8, {
-            let reserved3: u32 = unsafe { ::std::mem::transmute(reserved3) };
-            reserved3 as u64
+        __bindgen_bitfield_unit.set(13usize, 3u8, {
+            let reserved: u16 = unsafe { ::std::mem::transmute(reserved) };
+            reserved as u64
         });
         __bindgen_bitfield_unit
     }
 }
-pub type VAStatsStatisticsParameterH264 = _VAStatsStatisticsParameterH264;
+impl Default for _VADecPictureParameterBufferAV1__bindgen_ty_4 {
+    fn default() -> Self {
+        let mut s = ::std::mem::MaybeUninit::<Self>::uninit();
+        unsafe {
+            ::std::ptr::write_bytes(s.as_mut_ptr(), 0, 1);
+            s.assume_init()
+        }
+    }
+}
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub union _VADecPictureParameterBufferAV1__bindgen_ty_5 {
+    pub bits: _VADecPictureParameterBufferAV1__bindgen_ty_5__bindgen_ty_1,
+    pub value: u32,
+}
 #[repr(C)]
+#[repr(align(4))]
 #[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
-pub struct _VAStatsStatisticsH264 {
-    pub _bitfield_align_1: [u16; 0],
-    pub _bitfield_1: __BindgenBitfieldUnit<[u8; 16usize]>,
-    pub sum_coef: u32,
-    pub _bitfield_align_2: [u32; 0],
-    pub _bitfield_2: __BindgenBitfieldUnit<[u8; 4usize]>,
-    pub variance_16x16: u32,
-    pub variance_8x8: [u32; 4usize],
-    pub pixel_average_16x16: u32,
-    pub pixel_average_8x8: [u32; 4usize],
+pub struct _VADecPictureParameterBufferAV1__bindgen_ty_5__bindgen_ty_1 {
+    pub _bitfield_align_1: [u8; 0],
+    pub _bitfield_1: __BindgenBitfieldUnit<[u8; 4usize]>,
 }
-impl _VAStatsStatisticsH264 {
+impl _VADecPictureParameterBufferAV1__bindgen_ty_5__bindgen_ty_1 {
     #[inline]
-    pub fn best_inter_distortion0(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(0usize, 16u8) as u32) }
+    pub fn delta_q_present_flag(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(0usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_best_inter_distortion0(&mut self, val: u32) {
+    pub fn set_delta_q_present_flag(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(0usize, 16u8, val as u64)
+            self._bitfield_1.set(0usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn inter_mode0(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(16usize, 16u8) as u32) }
+    pub fn log2_delta_q_res(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(1usize, 2u8) as u32) }
     }
     #[inline]
-    pub fn set_inter_mode0(&mut self, val: u32) {
+    pub fn set_log2_delta_q_res(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(16usize, 16u8, val as u64)
+            self._bitfield_1.set(1usize, 2u8, val as u64)
         }
     }
     #[inline]
-    pub fn best_inter_distortion1(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(32usize, 16u8) as u32) }
+    pub fn delta_lf_present_flag(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(3usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_best_inter_distortion1(&mut self, val: u32) {
+    pub fn set_delta_lf_present_flag(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(32usize, 16u8, val as u64)
+            self._bitfield_1.set(3usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn inter_mode1(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(48usize, 16u8) as u32) }
+    pub fn log2_delta_lf_res(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(4usize, 2u8) as u32) }
     }
     #[inline]
-    pub fn set_inter_mode1(&mut self, val: u32) {
+    pub fn set_log2_delta_lf_res(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(48usize, 16u8, val as u64)
+            self._bitfield_1.set(4usize, 2u8, val as u64)
         }
     }
     #[inline]
-    pub fn best_intra_distortion(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(64usize, 16u8) as u32) }
+    pub fn delta_lf_multi(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(6usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_best_intra_distortion(&mut self, val: u32) {
+    pub fn set_delta_lf_multi(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(64usize, 16u8, val as u64)
+            self._bitfield_1.set(6usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn intra_mode(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(80usize, 16u8) as u32) }
+    pub fn tx_mode(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(7usize, 2u8) as u32) }
     }
     #[inline]
-    pub fn set_intra_mode(&mut self, val: u32) {
+    pub fn set_tx_mode(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(80usize, 16u8, val as u64)
+            self._bitfield_1.set(7usize, 2u8, val as u64)
         }
     }
     #[inline]
-    pub fn num_non_zero_coef(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(96usize, 16u8) as u32) }
+    pub fn reference_select(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(9usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_num_non_zero_coef(&mut self, val: u32) {
+    pub fn set_reference_select(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(96usize, 16u8, val as u64)
+            self._bitfield_1.set(9usize, 1u8, val as u64)
         }
     }
     #[inline]
-    pub fn reserved0(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(112usize, 16u8) as u32) }
+    pub fn reduced_tx_set(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(10usize, 1u8) as u32) }
     }
     #[inline]
-    pub fn set_reserved0(&mut self, val: u32) {
+    pub fn set_reduced_tx_set(&mut self, val: u32) {
         unsafe {
             let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_1.set(112usize, 16u8, val as u64)
+            self._bitfield_1.set(10usize, 1u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn skip_mode_present(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(11usize, 1u8) as u32) }
+    }
+    #[inline]
+    pub fn set_skip_mode_present(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(11usize, 1u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn reserved(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(12usize, 20u8) as u32) }
+    }
+    #[inline]
+    pub fn set_reserved(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(12usize, 20u8, val as u64)
         }
     }
     #[inline]
     pub fn new_bitfield_1(
-        best_inter_distortion0: u32,
-        inter_mode0: u32,
-        best_inter_distortion1: u32,
-        inter_mode1: u32,
-        best_intra_distortion: u32,
-        intra_mode: u32,
-        num_non_zero_coef: u32,
-        reserved0: u32,
-    ) -> __BindgenBitfieldUnit<[u8; 16usize]> {
-        let mut __bindgen_bitfield_unit: __BindgenBitfieldUnit<[u8; 16usize]> = Default::default();
-        __bindgen_bitfield_unit.set(0usize, 16u8, {
-            let best_inter_distortion0: u32 =
-                unsafe { ::std::mem::transmute(best_inter_distortion0) };
-            best_inter_distortion0 as u64
+        delta_q_present_flag: u32,
+        log2_delta_q_res: u32,
+        delta_lf_present_flag: u32,
+        log2_delta_lf_res: u32,
+        delta_lf_multi: u32,
+        tx_mode: u32,
+        reference_select: u32,
+        reduced_tx_set: u32,
+        skip_mode_present: u32,
+        reserved: u32,
+    ) -> __BindgenBitfieldUnit<[u8; 4usize]> {
+        let mut __bindgen_bitfield_unit: __BindgenBitfieldUnit<[u8; 4usize]> = Default::default();
+        __bindgen_bitfield_unit.set(0usize, 1u8, {
+            let delta_q_present_flag: u32 = unsafe { ::std::mem::transmute(delta_q_present_flag) };
+            delta_q_present_flag as u64
         });
-        __bindgen_bitfield_unit.set(16usize, 16u8, {
-            let inter_mode0: u32 = unsafe { ::std::mem::transmute(inter_mode0) };
-            inter_mode0 as u64
+        __bindgen_bitfield_unit.set(1usize, 2u8, {
+            let log2_delta_q_res: u32 = unsafe { ::std::mem::transmute(log2_delta_q_res) };
+            log2_delta_q_res as u64
         });
-        __bindgen_bitfield_unit.set(32usize, 16u8, {
-            let best_inter_distortion1: u32 =
-                unsafe { ::std::mem::transmute(best_inter_distortion1) };
-            best_inter_distortion1 as u64
+        __bindgen_bitfield_unit.set(3usize, 1u8, {
+            let delta_lf_present_flag: u32 =
+                unsafe { ::std::mem::transmute(delta_lf_present_flag) };
+            delta_lf_present_flag as u64
         });
-        __bindgen_bitfield_unit.set(48usize, 16u8, {
-            let inter_mode1: u32 = unsafe { ::std::mem::transmute(inter_mode1) };
-            inter_mode1 as u64
+        __bindgen_bitfield_unit.set(4usize, 2u8, {
+            let log2_delta_lf_res: u32 = unsafe { ::std::mem::transmute(log2_delta_lf_res) };
+            log2_delta_lf_res as u64
         });
-        __bindgen_bitfield_unit.set(64usize, 16u8, {
-            let best_intra_distortion: u32 =
-                unsafe { ::std::mem::transmute(best_intra_distortion) };
-            best_intra_distortion as u64
+        __bindgen_bitfield_unit.set(6usize, 1u8, {
+            let delta_lf_multi: u32 = unsafe { ::std::mem::transmute(delta_lf_multi) };
+            delta_lf_multi as u64
         });
-        __bindgen_bitfield_unit.set(80usize, 16u8, {
-            let intra_mode: u32 = unsafe { ::std::mem::transmute(intra_mode) };
-            intra_mode as u64
+        __bindgen_bitfield_unit.set(7usize, 2u8, {
+            let tx_mode: u32 = unsafe { ::std::mem::transmute(tx_mode) };
+            tx_mode as u64
         });
-        __bindgen_bitfield_unit.set(96usize, 16u8, {
-            let num_non_zero_coef: u32 = unsafe { ::std::mem::transmute(num_non_zero_coef) };
-            num_non_zero_coef as u64
+        __bindgen_bitfield_unit.set(9usize, 1u8, {
+            let reference_select: u32 = unsafe { ::std::mem::transmute(reference_select) };
+            reference_select as u64
         });
-        __bindgen_bitfield_unit.set(112usize, 16u8, {
-            let reserved0: u32 = unsafe { ::std::mem::transmute(reserved0) };
-            reserved0 as u64
+        __bindgen_bitfield_unit.set(10usize, 1u8, {
+            let reduced_tx_set: u32 = unsafe { ::std::mem::transmute(reduced_tx_set) };
+            reduced_tx_set as u64
+        });
+        __bindgen_bitfield_unit.set(11usize, 1u8, {
+            let skip_mode_present: u32 = unsafe { ::std::mem::transmute(skip_mode_present) };
+            skip_mode_present as u64
+        });
+        __bindgen_bitfield_unit.set(12usize, 20u8, {
+            let reserved: u32 = unsafe { ::std::mem::transmute(reserved) };
+            reserved as u64
         });
         __bindgen_bitfield_unit
     }
+}
+impl Default for _VADecPictureParameterBufferAV1__bindgen_ty_5 {
+    fn default() -> Self {
+        let mut s = ::std::mem::MaybeUninit::<Self>::uninit();
+        unsafe {
+            ::std::ptr::write_bytes(s.as_mut_ptr(), 0, 1);
+            s.assume_init()
+        }
+    }
+}
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub union _VADecPictureParameterBufferAV1__bindgen_ty_6 {
+    pub bits: _VADecPictureParameterBufferAV1__bindgen_ty_6__bindgen_ty_1,
+    pub value: u16,
+}
+#[repr(C)]
+#[repr(align(2))]
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct _VADecPictureParameterBufferAV1__bindgen_ty_6__bindgen_ty_1 {
+    pub _bitfield_align_1: [u8; 0],
+    pub _bitfield_1: __BindgenBitfieldUnit<[u8; 2usize]>,
+}
+impl _VADecPictureParameterBufferAV1__bindgen_ty_6__bindgen_ty_1 {
     #[inline]
-    pub fn mb_is_flat(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_2.get(0usize, 1u8) as u32) }
+    pub fn yframe_restoration_type(&self) -> u16 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(0usize, 2u8) as u16) }
     }
     #[inline]
-    pub fn set_mb_is_flat(&mut self, val: u32) {
+    pub fn set_yframe_restoration_type(&mut self, val: u16) {
         unsafe {
-            let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_2.set(0usize, 1u8, val as u64)
+            let val: u16 = ::std::mem::transmute(val);
+            self._bitfield_1.set(0usize, 2u8, val as u64)
         }
     }
     #[inline]
-    pub fn reserved1(&self) -> u32 {
-        unsafe { ::std::mem::transmute(self._bitfield_2.get(1usize, 31u8) as u32) }
+    pub fn cbframe_restoration_type(&self) -> u16 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(2usize, 2u8) as u16) }
     }
     #[inline]
-    pub fn set_reserved1(&mut self, val: u32) {
+    pub fn set_cbframe_restoration_type(&mut self, val: u16) {
         unsafe {
-            let val: u32 = ::std::mem::transmute(val);
-            self._bitfield_2.set(1usize, 31u8, val as u64)
+            let val: u16 = ::std::mem::transmute(val);
+            self._bitfield_1.set(2usize, 2u8, val as u64)
         }
     }
     #[inline]
-    pub fn new_bitfield_2(mb_is_flat: u32, reserved1: u32) -> __BindgenBitfieldUnit<[u8; 4usize]> {
-        let mut __bindgen_bitfield_unit: __BindgenBitfieldUnit<[u8; 4usize]> = Default::default();
-        __bindgen_bitfield_unit.set(0usize, 1u8, {
-            let mb_is_flat: u32 = unsafe { ::std::mem::transmute(mb_is_flat) };
-            mb_is_flat as u64
+    pub fn crframe_restoration_type(&self) -> u16 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(4usize, 2u8) as u16) }
+    }
+    #[inline]
+    pub fn set_crframe_restoration_type(&mut self, val: u16) {
+        unsafe {
+            let val: u16 = ::std::mem::transmute(val);
+            self._bitfield_1.set(4usize, 2u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn lr_unit_shift(&self) -> u16 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(6usize, 2u8) as u16) }
+    }
+    #[inline]
+    pub fn set_lr_unit_shift(&mut self, val: u16) {
+        unsafe {
+            let val: u16 = ::std::mem::transmute(val);
+            self._bitfield_1.set(6usize, 2u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn lr_uv_shift(&self) -> u16 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(8usize, 1u8) as u16) }
+    }
+    #[inline]
+    pub fn set_lr_uv_shift(&mut self, val: u16) {
+        unsafe {
+            let val: u16 = ::std::mem::transmute(val);
+            self._bitfield_1.set(8usize, 1u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn reserved(&self) -> u16 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(9usize, 7u8) as u16) }
+    }
+    #[inline]
+    pub fn set_reserved(&mut self, val: u16) {
+        unsafe {
+            let val: u16 = ::std::mem::transmute(val);
+            self._bitfield_1.set(9usize, 7u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn new_bitfield_1(
+        yframe_restoration_type: u16,
+        cbframe_restoration_type: u16,
+        crframe_restoration_type: u16,
+        lr_unit_shift: u16,
+        lr_uv_shift: u16,
+        reserved: u16,
+    ) -> __BindgenBitfieldUnit<[u8; 2usize]> {
+        let mut __bindgen_bitfield_unit: __BindgenBitfieldUnit<[u8; 2usize]> = Default::default();
+        __bindgen_bitfield_unit.set(0usize, 2u8, {
+            let yframe_restoration_type: u16 =
+                unsafe { ::std::mem::transmute(yframe_restoration_type) };
+            yframe_restoration_type as u64
         });
-        __bindgen_bitfield_unit.set(1usize, 31u8, {
-            let reserved1: u32 = unsafe { ::std::mem::transmute(reserved1) };
-            reserved1 as u64
+        __bindgen_bitfield_unit.set(2usize, 2u8, {
+            let cbframe_restoration_type: u16 =
+                unsafe { ::std::mem::transmute(cbframe_restoration_type) };
+            cbframe_restoration_type as u64
+        });
+        __bindgen_bitfield_unit.set(4usize, 2u8, {
+            let crframe_restoration_type: u16 =
+                unsafe { ::std::mem::transmute(crframe_restoration_type) };
+            crframe_restoration_type as u64
+        });
+        __bindgen_bitfield_unit.set(6usize, 2u8, {
+            let lr_unit_shift: u16 = unsafe { ::std::mem::transmute(lr_unit_shift) };
+            lr_unit_shift as u64
+        });
+        __bindgen_bitfield_unit.set(8usize, 1u8, {
+            let lr_uv_shift: u16 = unsafe { ::std::mem::transmute(lr_uv_shift) };
+            lr_uv_shift as u64
+        });
+        __bindgen_bitfield_unit.set(9usize, 7u8, {
+            let reserved: u16 = unsafe { ::std::mem::transmute(reserved) };
+            reserved as u64
         });
         __bindgen_bitfield_unit
     }
 }
-pub type VAStatsStatisticsH264 = _VAStatsStatisticsH264;
+impl Default for _VADecPictureParameterBufferAV1__bindgen_ty_6 {
+    fn default() -> Self {
+        let mut s = ::std::mem::MaybeUninit::<Self>::uninit();
+        unsafe {
+            ::std::ptr::write_bytes(s.as_mut_ptr(), 0, 1);
+            s.assume_init()
+        }
+    }
+}
+impl Default for _VADecPictureParameterBufferAV1 {
+    fn default() -> Self {
+        let mut s = ::std::mem::MaybeUninit::<Self>::uninit();
+        unsafe {
+            ::std::ptr::write_bytes(s.as_mut_ptr(), 0, 1);
+            s.assume_init()
+        }
+    }
+}
+pub type VADecPictureParameterBufferAV1 = _VADecPictureParameterBufferAV1;
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct _VASliceParameterBufferAV1 {
+    pub slice_data_size: u32,
+    pub slice_data_offset: u32,
+    pub slice_data_flag: u32,
+    pub tile_row: u16,
+    pub tile_column: u16,
+    pub tg_start: u16,
+    pub tg_end: u16,
+    pub anchor_frame_idx: u8,
+    pub tile_idx_in_tile_list: u16,
+    pub va_reserved: [u32; 4usize],
+}
+pub type VASliceParameterBufferAV1 = _VASliceParameterBufferAV1;
 pub const _VAProcFilterType_VAProcFilterNone: _VAProcFilterType = 0;
 pub const _VAProcFilterType_VAProcFilterNoiseReduction: _VAProcFilterType = 1;
 pub const _VAProcFilterType_VAProcFilterDeinterlacing: _VAProcFilterType = 2;
@@ -6,15 +6,16 @@ use crate::bindings;
 
 /// Abstraction over `VABufferType`s.
 pub enum BufferType {
-    /// Abstraction over `VAPictureParameterBufferType`. Needed for MPEG2, VP8, VP9, H264.
+    /// Abstraction over `VAPictureParameterBufferType`. Needed for MPEG2, VP8, VP9, H264, HEVC,
+    /// AV1.
     PictureParameter(PictureParameter),
-    /// Abstraction over `VASliceParameterBufferType`. Needed for MPEG2, VP8, VP9, H264.
+    /// Abstraction over `VASliceParameterBufferType`. Needed for MPEG2, VP8, VP9, H264, HEVC, AV1.
     SliceParameter(SliceParameter),
-    /// Abstraction over `VAIQMatrixBufferType`. Needed for VP8, H264.
+    /// Abstraction over `VAIQMatrixBufferType`. Needed for VP8, H264, HEVC.
     IQMatrix(IQMatrix),
     /// Abstraction over `VAProbabilityDataBufferType`. Needed for VP8.
     Probability(ProbabilityDataBufferVP8),
-    /// Abstraction over `VASliceDataBufferType`. Needed for VP9, H264.
+    /// Abstraction over `VASliceDataBufferType`. Needed for VP9, H264, HEVC, AV1.
     SliceData(Vec<u8>),
 }
 
@@ -550,6 +551,785 @@ impl PictureParameterBufferH264 {
     }
 }
 
+/// Wrapper over the `VAPictureHEVC` FFI type.
+pub struct PictureHEVC(bindings::VAPictureHEVC);
+
+impl PictureHEVC {
+    /// Creates the wrapper.
+    pub fn new(picture_id: bindings::VASurfaceID, pic_order_cnt: i32, flags: u32) -> Self {
+        Self(bindings::VAPictureHEVC {
+            picture_id,
+            pic_order_cnt,
+            flags,
+            va_reserved: Default::default(),
+        })
+    }
+}
+
+/// Wrapper over the `pic_fields` bindgen field in `VAPictureParameterBufferHEVC`.
+pub struct HEVCPicFields(bindings::_VAPictureParameterBufferHEVC__bindgen_ty_1);
+
+impl HEVCPicFields {
+    /// Creates the bindgen field.
+    pub fn new(
+        chroma_format_idc: u32,
+        separate_colour_plane_flag: u32,
+        pcm_enabled_flag: u32,
+        scaling_list_enabled_flag: u32,
+        transform_skip_enabled_flag: u32,
+        amp_enabled_flag: u32,
+        strong_intra_smoothing_enabled_flag: u32,
+        sign_data_hiding_enabled_flag: u32,
+        constrained_intra_pred_flag: u32,
+        cu_qp_delta_enabled_flag: u32,
+        weighted_pred_flag: u32,
+        weighted_bipred_flag: u32,
+        transquant_bypass_enabled_flag: u32,
+        tiles_enabled_flag: u32,
+        entropy_coding_sync_enabled_flag: u32,
+        pps_loop_filter_across_slices_enabled_flag: u32,
+        loop_filter_across_tiles_enabled_flag: u32,
+        pcm_loop_filter_disabled_flag: u32,
+        no_pic_reordering_flag: u32,
+        no_bi_pred_flag: u32,
+    ) -> Self {
+        let _bitfield_1 =
+            bindings::_VAPictureParameterBufferHEVC__bindgen_ty_1__bindgen_ty_1::new_bitfield_1(
+                chroma_format_idc,
+                separate_colour_plane_flag,
+                pcm_enabled_flag,
+                scaling_list_enabled_flag,
+                transform_skip_enabled_flag,
+                amp_enabled_flag,
+                strong_intra_smoothing_enabled_flag,
+                sign_data_hiding_enabled_flag,
+                constrained_intra_pred_flag,
+                cu_qp_delta_enabled_flag,
+                weighted_pred_flag,
+                weighted_bipred_flag,
+                transquant_bypass_enabled_flag,
+                tiles_enabled_flag,
+                entropy_coding_sync_enabled_flag,
+                pps_loop_filter_across_slices_enabled_flag,
+                loop_filter_across_tiles_enabled_flag,
+                pcm_loop_filter_disabled_flag,
+                no_pic_reordering_flag,
+                no_bi_pred_flag,
+                0,
+            );
+
+        Self(bindings::_VAPictureParameterBufferHEVC__bindgen_ty_1 {
+            bits: bindings::_VAPictureParameterBufferHEVC__bindgen_ty_1__bindgen_ty_1 {
+                _bitfield_align_1: Default::default(),
+                _bitfield_1,
+            },
+        })
+    }
+
+    /// Returns the inner FFI type. Useful for testing purposes.
+    pub fn inner(&mut self) -> &bindings::_VAPictureParameterBufferHEVC__bindgen_ty_1 {
+        &self.0
+    }
+}
+
+/// Wrapper over the `slice_parsing_fields` bindgen field in `VAPictureParameterBufferHEVC`.
+pub struct HEVCSliceParsingFields(bindings::_VAPictureParameterBufferHEVC__bindgen_ty_2);
+
+impl HEVCSliceParsingFields {
+    /// Creates the bindgen field.
+    pub fn new(
+        lists_modification_present_flag: u32,
+        long_term_ref_pics_present_flag: u32,
+        sps_temporal_mvp_enabled_flag: u32,
+        cabac_init_present_flag: u32,
+        output_flag_present_flag: u32,
+        dependent_slice_segments_enabled_flag: u32,
+        pps_slice_chroma_qp_offsets_present_flag: u32,
+        sample_adaptive_offset_enabled_flag: u32,
+        deblocking_filter_override_enabled_flag: u32,
+        pps_disable_deblocking_filter_flag: u32,
+        slice_segment_header_extension_present_flag: u32,
+        rap_pic_flag: u32,
+        idr_pic_flag: u32,
+        intra_pic_flag: u32,
+    ) -> Self {
+        let _bitfield_1 =
+            bindings::_VAPictureParameterBufferHEVC__bindgen_ty_2__bindgen_ty_1::new_bitfield_1(
+                lists_modification_present_flag,
+                long_term_ref_pics_present_flag,
+                sps_temporal_mvp_enabled_flag,
+                cabac_init_present_flag,
+                output_flag_present_flag,
+                dependent_slice_segments_enabled_flag,
+                pps_slice_chroma_qp_offsets_present_flag,
+                sample_adaptive_offset_enabled_flag,
+                deblocking_filter_override_enabled_flag,
+                pps_disable_deblocking_filter_flag,
+                slice_segment_header_extension_present_flag,
+                rap_pic_flag,
+                idr_pic_flag,
+                intra_pic_flag,
+                0,
+            );
+
+        Self(bindings::_VAPictureParameterBufferHEVC__bindgen_ty_2 {
+            bits: bindings::_VAPictureParameterBufferHEVC__bindgen_ty_2__bindgen_ty_1 {
+                _bitfield_align_1: Default::default(),
+                _bitfield_1,
+            },
+        })
+    }
+
+    /// Returns the inner FFI type. Useful for testing purposes.
+    pub fn inner(&mut self) -> &bindings::_VAPictureParameterBufferHEVC__bindgen_ty_2 {
+        &self.0
+    }
+}
+
+/// A wrapper over `VAPictureParameterBufferHEVC` FFI type
+pub struct PictureParameterBufferHEVC(Box<bindings::VAPictureParameterBufferHEVC>);
+
+impl PictureParameterBufferHEVC {
+    /// Creates the wrapper
+    pub fn new(
+        curr_pic: PictureHEVC,
+        reference_frames: [PictureHEVC; 15],
+        pic_width_in_luma_samples: u16,
+        pic_height_in_luma_samples: u16,
+        pic_fields: &HEVCPicFields,
+        sps_max_dec_pic_buffering_minus1: u8,
+        bit_depth_luma_minus8: u8,
+        bit_depth_chroma_minus8: u8,
+        pcm_sample_bit_depth_luma_minus1: u8,
+        pcm_sample_bit_depth_chroma_minus1: u8,
+        log2_min_luma_coding_block_size_minus3: u8,
+        log2_diff_max_min_luma_coding_block_size: u8,
+        log2_min_transform_block_size_minus2: u8,
+        log2_diff_max_min_transform_block_size: u8,
+        log2_min_pcm_luma_coding_block_size_minus3: u8,
+        log2_diff_max_min_pcm_luma_coding_block_size: u8,
+        max_transform_hierarchy_depth_intra: u8,
+        max_transform_hierarchy_depth_inter: u8,
+        init_qp_minus26: i8,
+        diff_cu_qp_delta_depth: u8,
+        pps_cb_qp_offset: i8,
+        pps_cr_qp_offset: i8,
+        log2_parallel_merge_level_minus2: u8,
+        num_tile_columns_minus1: u8,
+        num_tile_rows_minus1: u8,
+        column_width_minus1: [u16; 19usize],
+        row_height_minus1: [u16; 21usize],
+        slice_parsing_fields: &HEVCSliceParsingFields,
+        log2_max_pic_order_cnt_lsb_minus4: u8,
+        num_short_term_ref_pic_sets: u8,
+        num_long_term_ref_pic_sps: u8,
+        num_ref_idx_l0_default_active_minus1: u8,
+        num_ref_idx_l1_default_active_minus1: u8,
+        pps_beta_offset_div2: i8,
+        pps_tc_offset_div2: i8,
+        num_extra_slice_header_bits: u8,
+        st_rps_bits: u32,
+    ) -> Self {
+        let reference_frames = reference_frames.map(|pic| pic.0);
+        let pic_fields = pic_fields.0;
+        let slice_parsing_fields = slice_parsing_fields.0;
+
+        Self(Box::new(bindings::VAPictureParameterBufferHEVC {
+            CurrPic: curr_pic.0,
+            ReferenceFrames: reference_frames,
+            pic_width_in_luma_samples,
+            pic_height_in_luma_samples,
+            pic_fields,
+            sps_max_dec_pic_buffering_minus1,
+            bit_depth_luma_minus8,
+            bit_depth_chroma_minus8,
+            pcm_sample_bit_depth_luma_minus1,
+            pcm_sample_bit_depth_chroma_minus1,
+            log2_min_luma_coding_block_size_minus3,
+            log2_diff_max_min_luma_coding_block_size,
+            log2_min_transform_block_size_minus2,
+            log2_diff_max_min_transform_block_size,
+            log2_min_pcm_luma_coding_block_size_minus3,
+            log2_diff_max_min_pcm_luma_coding_block_size,
+            max_transform_hierarchy_depth_intra,
+            max_transform_hierarchy_depth_inter,
+            init_qp_minus26,
+            diff_cu_qp_delta_depth,
+            pps_cb_qp_offset,
+            pps_cr_qp_offset,
+            log2_parallel_merge_level_minus2,
+            num_tile_columns_minus1,
+            num_tile_rows_minus1,
+            column_width_minus1,
+            row_height_minus1,
+            slice_parsing_fields,
+            log2_max_pic_order_cnt_lsb_minus4,
+            num_short_term_ref_pic_sets,
+            num_long_term_ref_pic_sps,
+            num_ref_idx_l0_default_active_minus1,
+            num_ref_idx_l1_default_active_minus1,
+            pps_beta_offset_div2,
+            pps_tc_offset_div2,
+            num_extra_slice_header_bits,
+            st_rps_bits,
+            va_reserved: Default::default(),
+        }))
+    }
+
+    pub(crate) fn inner_mut(&mut self) -> &mut bindings::VAPictureParameterBufferHEVC {
+        self.0.as_mut()
+    }
+
+    /// Returns the inner FFI type. Useful for testing purposes.
+    pub fn inner(&self) -> &bindings::VAPictureParameterBufferHEVC {
+        self.0.as_ref()
+    }
+}
+
+/// Wrapper over the `segment_info_fields` bindgen field in `VASegmentationStructAV1`.
+pub struct AV1SegmentInfoFields(bindings::_VASegmentationStructAV1__bindgen_ty_1);
+
+impl AV1SegmentInfoFields {
+    /// Creates the bindgen field.
+    pub fn new(enabled: u32, update_map: u32, temporal_update: u32, update_data: u32) -> Self {
+        let _bitfield_1 =
+            bindings::_VASegmentationStructAV1__bindgen_ty_1__bindgen_ty_1::new_bitfield_1(
+                enabled,
+                update_map,
+                temporal_update,
+                update_data,
+                0,
+            );
+
+        Self(bindings::_VASegmentationStructAV1__bindgen_ty_1 {
+            bits: bindings::_VASegmentationStructAV1__bindgen_ty_1__bindgen_ty_1 {
+                _bitfield_align_1: Default::default(),
+                _bitfield_1,
+            },
+        })
+    }
+
+    /// Returns the inner FFI type. Useful for testing purposes.
+    pub fn inner(&mut self) -> &bindings::_VASegmentationStructAV1__bindgen_ty_1 {
+        &self.0
+    }
+}
+
+/// Wrapper over the `VASegmentationStructAV1` FFI type.
+pub struct SegmentationStructAV1(bindings::VASegmentationStructAV1);
+
+impl SegmentationStructAV1 {
+    /// Creates the wrapper.
+    pub fn new(
+        segment_info_fields: &AV1SegmentInfoFields,
+        feature_data: [[i16; 8usize]; 8usize],
+        feature_mask: [u8; 8usize],
+    ) -> Self {
+        let segment_info_fields = segment_info_fields.0;
+
+        Self(bindings::VASegmentationStructAV1 {
+            segment_info_fields,
+            feature_data,
+            feature_mask,
+            va_reserved: Default::default(),
+        })
+    }
+}
+
+/// Wrapper over the `film_grain_info_fields` bindgen field in `VAFilmGrainStructAV1`.
+pub struct AV1FilmGrainInfoFields(bindings::_VAFilmGrainStructAV1__bindgen_ty_1);
+
+impl AV1FilmGrainInfoFields {
+    /// Creates the bindgen field.
+    pub fn new(
+        apply_grain: u32,
+        chroma_scaling_from_luma: u32,
+        grain_scaling_minus_8: u32,
+        ar_coeff_lag: u32,
+        ar_coeff_shift_minus_6: u32,
+        grain_scale_shift: u32,
+        overlap_flag: u32,
+        clip_to_restricted_range: u32,
+    ) -> Self {
+        let _bitfield_1 =
+            bindings::_VAFilmGrainStructAV1__bindgen_ty_1__bindgen_ty_1::new_bitfield_1(
+                apply_grain,
+                chroma_scaling_from_luma,
+                grain_scaling_minus_8,
+                ar_coeff_lag,
+                ar_coeff_shift_minus_6,
+                grain_scale_shift,
+                overlap_flag,
+                clip_to_restricted_range,
+                0,
+            );
+
+        Self(bindings::_VAFilmGrainStructAV1__bindgen_ty_1 {
+            bits: bindings::_VAFilmGrainStructAV1__bindgen_ty_1__bindgen_ty_1 {
+                _bitfield_align_1: Default::default(),
+                _bitfield_1,
+            },
+        })
+    }
+
+    /// Returns the inner FFI type. Useful for testing purposes.
+    pub fn inner(&mut self) -> &bindings::_VAFilmGrainStructAV1__bindgen_ty_1 {
+        &self.0
+    }
+}
+
+/// Wrapper over the `VAFilmGrainStructAV1` FFI type.
+pub struct FilmGrainStructAV1(bindings::VAFilmGrainStructAV1);
+
+impl FilmGrainStructAV1 {
+    /// Creates the wrapper.
+    pub fn new(
+        film_grain_info_fields: &AV1FilmGrainInfoFields,
+        grain_seed: u16,
+        num_y_points: u8,
+        point_y_value: [u8; 14usize],
+        point_y_scaling: [u8; 14usize],
+        num_cb_points: u8,
+        point_cb_value: [u8; 10usize],
+        point_cb_scaling: [u8; 10usize],
+        num_cr_points: u8,
+        point_cr_value: [u8; 10usize],
+        point_cr_scaling: [u8; 10usize],
+        ar_coeffs_y: [i8; 24usize],
+        ar_coeffs_cb: [i8; 25usize],
+        ar_coeffs_cr: [i8; 25usize],
+        cb_mult: u8,
+        cb_luma_mult: u8,
+        cb_offset: u16,
+        cr_mult: u8,
+        cr_luma_mult: u8,
+        cr_offset: u16,
+    ) -> Self {
+        let film_grain_info_fields = film_grain_info_fields.0;
+
+        Self(bindings::VAFilmGrainStructAV1 {
+            film_grain_info_fields,
+            grain_seed,
+            num_y_points,
+            point_y_value,
+            point_y_scaling,
+            num_cb_points,
+            point_cb_value,
+            point_cb_scaling,
+            num_cr_points,
+            point_cr_value,
+            point_cr_scaling,
+            ar_coeffs_y,
+            ar_coeffs_cb,
+            ar_coeffs_cr,
+            cb_mult,
+            cb_luma_mult,
+            cb_offset,
+            cr_mult,
+            cr_luma_mult,
+            cr_offset,
+            va_reserved: Default::default(),
+        })
+    }
+}
+
+/// Wrapper over the `VAWarpedMotionParamsAV1` FFI type.
+pub struct WarpedMotionParamsAV1(bindings::VAWarpedMotionParamsAV1);
+
+impl WarpedMotionParamsAV1 {
+    /// Creates the wrapper.
+    pub fn new(
+        wmtype: bindings::VAAV1TransformationType::Type,
+        wmmat: [i32; 8usize],
+        invalid: u8,
+    ) -> Self {
+        Self(bindings::VAWarpedMotionParamsAV1 {
+            wmtype,
+            wmmat,
+            invalid,
+            va_reserved: Default::default(),
+        })
+    }
+}
+
+/// Wrapper over the `seq_info_fields` bindgen field in `VADecPictureParameterBufferAV1`.
+pub struct AV1SeqInfoFields(bindings::_VADecPictureParameterBufferAV1__bindgen_ty_1);
+
+impl AV1SeqInfoFields {
+    /// Creates the bindgen field.
+    pub fn new(
+        still_picture: u32,
+        use_128x128_superblock: u32,
+        enable_filter_intra: u32,
+        enable_intra_edge_filter: u32,
+        enable_interintra_compound: u32,
+        enable_masked_compound: u32,
+        enable_dual_filter: u32,
+        enable_order_hint: u32,
+        enable_jnt_comp: u32,
+        enable_cdef: u32,
+        mono_chrome: u32,
+        color_range: u32,
+        subsampling_x: u32,
+        subsampling_y: u32,
+        chroma_sample_position: u32,
+        film_grain_params_present: u32,
+    ) -> Self {
+        let _bitfield_1 =
+            bindings::_VADecPictureParameterBufferAV1__bindgen_ty_1__bindgen_ty_1::new_bitfield_1(
+                still_picture,
+                use_128x128_superblock,
+                enable_filter_intra,
+                enable_intra_edge_filter,
+                enable_interintra_compound,
+                enable_masked_compound,
+                enable_dual_filter,
+                enable_order_hint,
+                enable_jnt_comp,
+                enable_cdef,
+                mono_chrome,
+                color_range,
+                subsampling_x,
+                subsampling_y,
+                chroma_sample_position,
+                film_grain_params_present,
+                0,
+            );
+
+        Self(bindings::_VADecPictureParameterBufferAV1__bindgen_ty_1 {
+            fields: bindings::_VADecPictureParameterBufferAV1__bindgen_ty_1__bindgen_ty_1 {
+                _bitfield_align_1: Default::default(),
+                _bitfield_1,
+            },
+        })
+    }
+
+    /// Returns the inner FFI type. Useful for testing purposes.
+    pub fn inner(&mut self) -> &bindings::_VADecPictureParameterBufferAV1__bindgen_ty_1 {
+        &self.0
+    }
+}
+
+/// Wrapper over the `pic_info_fields` bindgen field in `VADecPictureParameterBufferAV1`.
+pub struct AV1PicInfoFields(bindings::_VADecPictureParameterBufferAV1__bindgen_ty_2);
+
+impl AV1PicInfoFields {
+    /// Creates the bindgen field.
+    pub fn new(
+        frame_type: u32,
+        show_frame: u32,
+        showable_frame: u32,
+        error_resilient_mode: u32,
+        disable_cdf_update: u32,
+        allow_screen_content_tools: u32,
+        force_integer_mv: u32,
+        allow_intrabc: u32,
+        use_superres: u32,
+        allow_high_precision_mv: u32,
+        is_motion_mode_switchable: u32,
+        use_ref_frame_mvs: u32,
+        disable_frame_end_update_cdf: u32,
+        uniform_tile_spacing_flag: u32,
+        allow_warped_motion: u32,
+        large_scale_tile: u32,
+    ) -> Self {
+        let _bitfield_1 =
+            bindings::_VADecPictureParameterBufferAV1__bindgen_ty_2__bindgen_ty_1::new_bitfield_1(
+                frame_type,
+                show_frame,
+                showable_frame,
+                error_resilient_mode,
+                disable_cdf_update,
+                allow_screen_content_tools,
+                force_integer_mv,
+                allow_intrabc,
+                use_superres,
+                allow_high_precision_mv,
+                is_motion_mode_switchable,
+                use_ref_frame_mvs,
+                disable_frame_end_update_cdf,
+                uniform_tile_spacing_flag,
+                allow_warped_motion,
+                large_scale_tile,
+                0,
+            );
+
+        Self(bindings::_VADecPictureParameterBufferAV1__bindgen_ty_2 {
+            bits: bindings::_VADecPictureParameterBufferAV1__bindgen_ty_2__bindgen_ty_1 {
+                _bitfield_align_1: Default::default(),
+                _bitfield_1,
+            },
+        })
+    }
+
+    /// Returns the inner FFI type. Useful for testing purposes.
+    pub fn inner(&mut self) -> &bindings::_VADecPictureParameterBufferAV1__bindgen_ty_2 {
+        &self.0
+    }
+}
+
+/// Wrapper over the `loop_filter_info_fields` bindgen field in `VADecPictureParameterBufferAV1`.
+pub struct AV1LoopFilterInfoFields(bindings::_VADecPictureParameterBufferAV1__bindgen_ty_3);
+
+impl AV1LoopFilterInfoFields {
+    /// Creates the bindgen field.
+    pub fn new(sharpness_level: u8, mode_ref_delta_enabled: u8, mode_ref_delta_update: u8) -> Self {
+        let _bitfield_1 =
+            bindings::_VADecPictureParameterBufferAV1__bindgen_ty_3__bindgen_ty_1::new_bitfield_1(
+                sharpness_level,
+                mode_ref_delta_enabled,
+                mode_ref_delta_update,
+                0,
+            );
+
+        Self(bindings::_VADecPictureParameterBufferAV1__bindgen_ty_3 {
+            bits: bindings::_VADecPictureParameterBufferAV1__bindgen_ty_3__bindgen_ty_1 {
+                _bitfield_align_1: Default::default(),
+                _bitfield_1,
+            },
+        })
+    }
+
+    /// Returns the inner FFI type. Useful for testing purposes.
+    pub fn inner(&mut self) -> &bindings::_VADecPictureParameterBufferAV1__bindgen_ty_3 {
+        &self.0
+    }
+}
+
+/// Wrapper over the `qmatrix_fields` bindgen field in `VADecPictureParameterBufferAV1`.
+pub struct AV1QMatrixFields(bindings::_VADecPictureParameterBufferAV1__bindgen_ty_4);
+
+impl AV1QMatrixFields {
+    /// Creates the bindgen field.
+    pub fn new(using_qmatrix: u16, qm_y: u16, qm_u: u16, qm_v: u16) -> Self {
+        let _bitfield_1 =
+            bindings::_VADecPictureParameterBufferAV1__bindgen_ty_4__bindgen_ty_1::new_bitfield_1(
+                using_qmatrix,
+                qm_y,
+                qm_u,
+                qm_v,
+                0,
+            );
+
+        Self(bindings::_VADecPictureParameterBufferAV1__bindgen_ty_4 {
+            bits: bindings::_VADecPictureParameterBufferAV1__bindgen_ty_4__bindgen_ty_1 {
+                _bitfield_align_1: Default::default(),
+                _bitfield_1,
+            },
+        })
+    }
+
+    /// Returns the inner FFI type. Useful for testing purposes.
+    pub fn inner(&mut self) -> &bindings::_VADecPictureParameterBufferAV1__bindgen_ty_4 {
+        &self.0
+    }
+}
+
+/// Wrapper over the `mode_control_fields` bindgen field in `VADecPictureParameterBufferAV1`.
+pub struct AV1ModeControlFields(bindings::_VADecPictureParameterBufferAV1__bindgen_ty_5);
+
+impl AV1ModeControlFields {
+    /// Creates the bindgen field.
+    pub fn new(
+        delta_q_present_flag: u32,
+        log2_delta_q_res: u32,
+        delta_lf_present_flag: u32,
+        log2_delta_lf_res: u32,
+        delta_lf_multi: u32,
+        tx_mode: u32,
+        reference_select: u32,
+        reduced_tx_set: u32,
+        skip_mode_present: u32,
+    ) -> Self {
+        let _bitfield_1 =
+            bindings::_VADecPictureParameterBufferAV1__bindgen_ty_5__bindgen_ty_1::new_bitfield_1(
+                delta_q_present_flag,
+                log2_delta_q_res,
+                delta_lf_present_flag,
+                log2_delta_lf_res,
+                delta_lf_multi,
+                tx_mode,
+                reference_select,
+                reduced_tx_set,
+                skip_mode_present,
+                0,
+            );
+
+        Self(bindings::_VADecPictureParameterBufferAV1__bindgen_ty_5 {
+            bits: bindings::_VADecPictureParameterBufferAV1__bindgen_ty_5__bindgen_ty_1 {
+                _bitfield_align_1: Default::default(),
+                _bitfield_1,
+            },
+        })
+    }
+
+    /// Returns the inner FFI type. Useful for testing purposes.
+    pub fn inner(&mut self) -> &bindings::_VADecPictureParameterBufferAV1__bindgen_ty_5 {
+        &self.0
+    }
+}
+
+/// Wrapper over the `loop_restoration_fields` bindgen field in `VADecPictureParameterBufferAV1`.
+pub struct AV1LoopRestorationFields(bindings::_VADecPictureParameterBufferAV1__bindgen_ty_6);
+
+impl AV1LoopRestorationFields {
+    /// Creates the bindgen field.
+    pub fn new(
+        yframe_restoration_type: u16,
+        cbframe_restoration_type: u16,
+        crframe_restoration_type: u16,
+        lr_unit_shift: u16,
+        lr_uv_shift: u16,
+    ) -> Self {
+        let _bitfield_1 =
+            bindings::_VADecPictureParameterBufferAV1__bindgen_ty_6__bindgen_ty_1::new_bitfield_1(
+                yframe_restoration_type,
+                cbframe_restoration_type,
+                crframe_restoration_type,
+                lr_unit_shift,
+                lr_uv_shift,
+                0,
+            );
+
+        Self(bindings::_VADecPictureParameterBufferAV1__bindgen_ty_6 {
+            bits: bindings::_VADecPictureParameterBufferAV1__bindgen_ty_6__bindgen_ty_1 {
+                _bitfield_align_1: Default::default(),
+                _bitfield_1,
+            },
+        })
+    }
+
+    /// Returns the inner FFI type. Useful for testing purposes.
+    pub fn inner(&mut self) -> &bindings::_VADecPictureParameterBufferAV1__bindgen_ty_6 {
+        &self.0
+    }
+}
+
+/// Wrapper over the `VADecPictureParameterBufferAV1` FFI type.
+pub struct PictureParameterBufferAV1(Box<bindings::VADecPictureParameterBufferAV1>);
+
+impl PictureParameterBufferAV1 {
+    /// Creates the wrapper. Large scale tile decoding is not supported, so there are no anchor
+    /// frames.
+    pub fn new(
+        profile: u8,
+        order_hint_bits_minus_1: u8,
+        bit_depth_idx: u8,
+        matrix_coefficients: u8,
+        seq_info_fields: &AV1SeqInfoFields,
+        current_frame: bindings::VASurfaceID,
+        current_display_picture: bindings::VASurfaceID,
+        frame_width_minus1: u16,
+        frame_height_minus1: u16,
+        ref_frame_map: [bindings::VASurfaceID; 8usize],
+        ref_frame_idx: [u8; 7usize],
+        primary_ref_frame: u8,
+        order_hint: u8,
+        seg_info: &SegmentationStructAV1,
+        film_grain_info: &FilmGrainStructAV1,
+        tile_cols: u8,
+        tile_rows: u8,
+        width_in_sbs_minus_1: [u16; 63usize],
+        height_in_sbs_minus_1: [u16; 63usize],
+        tile_count_minus_1: u16,
+        context_update_tile_id: u16,
+        pic_info_fields: &AV1PicInfoFields,
+        superres_scale_denominator: u8,
+        interp_filter: u8,
+        filter_level: [u8; 2usize],
+        filter_level_u: u8,
+        filter_level_v: u8,
+        loop_filter_info_fields: &AV1LoopFilterInfoFields,
+        ref_deltas: [i8; 8usize],
+        mode_deltas: [i8; 2usize],
+        base_qindex: u8,
+        y_dc_delta_q: i8,
+        u_dc_delta_q: i8,
+        u_ac_delta_q: i8,
+        v_dc_delta_q: i8,
+        v_ac_delta_q: i8,
+        qmatrix_fields: &AV1QMatrixFields,
+        mode_control_fields: &AV1ModeControlFields,
+        cdef_damping_minus_3: u8,
+        cdef_bits: u8,
+        cdef_y_strengths: [u8; 8usize],
+        cdef_uv_strengths: [u8; 8usize],
+        loop_restoration_fields: &AV1LoopRestorationFields,
+        wm: [WarpedMotionParamsAV1; 7usize],
+    ) -> Self {
+        let seq_info_fields = seq_info_fields.0;
+        let seg_info = seg_info.0;
+        let film_grain_info = film_grain_info.0;
+        let pic_info_fields = pic_info_fields.0;
+        let loop_filter_info_fields = loop_filter_info_fields.0;
+        let qmatrix_fields = qmatrix_fields.0;
+        let mode_control_fields = mode_control_fields.0;
+        let loop_restoration_fields = loop_restoration_fields.0;
+        let wm = wm.map(|params| params.0);
+
+        Self(Box::new(bindings::VADecPictureParameterBufferAV1 {
+            profile,
+            order_hint_bits_minus_1,
+            bit_depth_idx,
+            matrix_coefficients,
+            seq_info_fields,
+            current_frame,
+            current_display_picture,
+            anchor_frames_num: 0,
+            anchor_frames_list: std::ptr::null_mut(),
+            frame_width_minus1,
+            frame_height_minus1,
+            output_frame_width_in_tiles_minus_1: 0,
+            output_frame_height_in_tiles_minus_1: 0,
+            ref_frame_map,
+            ref_frame_idx,
+            primary_ref_frame,
+            order_hint,
+            seg_info,
+            film_grain_info,
+            tile_cols,
+            tile_rows,
+            width_in_sbs_minus_1,
+            height_in_sbs_minus_1,
+            tile_count_minus_1,
+            context_update_tile_id,
+            pic_info_fields,
+            superres_scale_denominator,
+            interp_filter,
+            filter_level,
+            filter_level_u,
+            filter_level_v,
+            loop_filter_info_fields,
+            ref_deltas,
+            mode_deltas,
+            base_qindex,
+            y_dc_delta_q,
+            u_dc_delta_q,
+            u_ac_delta_q,
+            v_dc_delta_q,
+            v_ac_delta_q,
+            qmatrix_fields,
+            mode_control_fields,
+            cdef_damping_minus_3,
+            cdef_bits,
+            cdef_y_strengths,
+            cdef_uv_strengths,
+            loop_restoration_fields,
+            wm,
+            va_reserved: Default::default(),
+        }))
+    }
+
+    pub(crate) fn inner_mut(&mut self) -> &mut bindings::VADecPictureParameterBufferAV1 {
+        self.0.as_mut()
+    }
+
+    /// Returns the inner FFI type. Useful for testing purposes.
+    pub fn inner(&self) -> &bindings::VADecPictureParameterBufferAV1 {
+        self.0.as_ref()
+    }
+}
+
 /// Abstraction over the `PictureParameterBuffer` types we support.
 pub enum PictureParameter {
     /// Wrapper over VAPictureParameterBufferMPEG2.
@@ -560,6 +1340,10 @@ pub enum PictureParameter {
     VP9(PictureParameterBufferVP9),
     /// Wrapper over VAPictureParameterBufferH264.
     H264(PictureParameterBufferH264),
+    /// Wrapper over VAPictureParameterBufferHEVC.
+    HEVC(PictureParameterBufferHEVC),
+    /// Wrapper over VADecPictureParameterBufferAV1.
+    AV1(PictureParameterBufferAV1),
 }
 
 /// Wrapper over the `VASliceParameterBufferMPEG2` FFI type.
@@ -610,6 +1394,10 @@ pub enum SliceParameter {
     VP9(SliceParameterBufferVP9),
     /// Wrapper over VASliceParameterBufferH264
     H264(SliceParameterBufferH264),
+    /// Wrapper over VASliceParameterBufferHEVC
+    HEVC(SliceParameterBufferHEVC),
+    /// Wrapper over VASliceParameterBufferAV1
+    AV1(SliceParameterBufferAV1),
 }
 
 /// Wrapper over the `VASliceParameterBufferVP8` FFI type.
@@ -822,6 +1610,185 @@ impl SliceParameterBufferH264 {
     }
 }
 
+/// Wrapper over the `LongSliceFlags` bindgen field in `VASliceParameterBufferHEVC`.
+pub struct HEVCLongSliceFlags(bindings::_VASliceParameterBufferHEVC__bindgen_ty_1);
+
+impl HEVCLongSliceFlags {
+    /// Creates the bindgen field.
+    pub fn new(
+        last_slice_of_pic: u32,
+        dependent_slice_segment_flag: u32,
+        slice_type: u32,
+        color_plane_id: u32,
+        slice_sao_luma_flag: u32,
+        slice_sao_chroma_flag: u32,
+        mvd_l1_zero_flag: u32,
+        cabac_init_flag: u32,
+        slice_temporal_mvp_enabled_flag: u32,
+        slice_deblocking_filter_disabled_flag: u32,
+        collocated_from_l0_flag: u32,
+        slice_loop_filter_across_slices_enabled_flag: u32,
+    ) -> Self {
+        let _bitfield_1 =
+            bindings::_VASliceParameterBufferHEVC__bindgen_ty_1__bindgen_ty_1::new_bitfield_1(
+                last_slice_of_pic,
+                dependent_slice_segment_flag,
+                slice_type,
+                color_plane_id,
+                slice_sao_luma_flag,
+                slice_sao_chroma_flag,
+                mvd_l1_zero_flag,
+                cabac_init_flag,
+                slice_temporal_mvp_enabled_flag,
+                slice_deblocking_filter_disabled_flag,
+                collocated_from_l0_flag,
+                slice_loop_filter_across_slices_enabled_flag,
+                0,
+            );
+
+        Self(bindings::_VASliceParameterBufferHEVC__bindgen_ty_1 {
+            fields: bindings::_VASliceParameterBufferHEVC__bindgen_ty_1__bindgen_ty_1 {
+                _bitfield_align_1: Default::default(),
+                _bitfield_1,
+            },
+        })
+    }
+
+    /// Returns the inner FFI type. Useful for testing purposes.
+    pub fn inner(&mut self) -> &bindings::_VASliceParameterBufferHEVC__bindgen_ty_1 {
+        &self.0
+    }
+}
+
+/// Wrapper over the `VASliceParameterBufferHEVC` FFI type.
+pub struct SliceParameterBufferHEVC(Box<bindings::VASliceParameterBufferHEVC>);
+
+impl SliceParameterBufferHEVC {
+    /// Creates the wrapper.
+    pub fn new(
+        slice_data_size: u32,
+        slice_data_offset: u32,
+        slice_data_flag: u32,
+        slice_data_byte_offset: u32,
+        slice_segment_address: u32,
+        ref_pic_list: [[u8; 15usize]; 2usize],
+        long_slice_flags: &HEVCLongSliceFlags,
+        collocated_ref_idx: u8,
+        num_ref_idx_l0_active_minus1: u8,
+        num_ref_idx_l1_active_minus1: u8,
+        slice_qp_delta: i8,
+        slice_cb_qp_offset: i8,
+        slice_cr_qp_offset: i8,
+        slice_beta_offset_div2: i8,
+        slice_tc_offset_div2: i8,
+        luma_log2_weight_denom: u8,
+        delta_chroma_log2_weight_denom: i8,
+        delta_luma_weight_l0: [i8; 15usize],
+        luma_offset_l0: [i8; 15usize],
+        delta_chroma_weight_l0: [[i8; 2usize]; 15usize],
+        chroma_offset_l0: [[i8; 2usize]; 15usize],
+        delta_luma_weight_l1: [i8; 15usize],
+        luma_offset_l1: [i8; 15usize],
+        delta_chroma_weight_l1: [[i8; 2usize]; 15usize],
+        chroma_offset_l1: [[i8; 2usize]; 15usize],
+        five_minus_max_num_merge_cand: u8,
+        num_entry_point_offsets: u16,
+        entry_offset_to_subset_array: u16,
+        slice_data_num_emu_prevn_bytes: u16,
+    ) -> Self {
+        let long_slice_flags = long_slice_flags.0;
+
+        Self(Box::new(bindings::VASliceParameterBufferHEVC {
+            slice_data_size,
+            slice_data_offset,
+            slice_data_flag,
+            slice_data_byte_offset,
+            slice_segment_address,
+            RefPicList: ref_pic_list,
+            LongSliceFlags: long_slice_flags,
+            collocated_ref_idx,
+            num_ref_idx_l0_active_minus1,
+            num_ref_idx_l1_active_minus1,
+            slice_qp_delta,
+            slice_cb_qp_offset,
+            slice_cr_qp_offset,
+            slice_beta_offset_div2,
+            slice_tc_offset_div2,
+            luma_log2_weight_denom,
+            delta_chroma_log2_weight_denom,
+            delta_luma_weight_l0,
+            luma_offset_l0,
+            delta_chroma_weight_l0,
+            ChromaOffsetL0: chroma_offset_l0,
+            delta_luma_weight_l1,
+            luma_offset_l1,
+            delta_chroma_weight_l1,
+            ChromaOffsetL1: chroma_offset_l1,
+            five_minus_max_num_merge_cand,
+            num_entry_point_offsets,
+            entry_offset_to_subset_array,
+            slice_data_num_emu_prevn_bytes,
+            va_reserved: Default::default(),
+        }))
+    }
+
+    /// Marks this slice as the last one of the picture. The decoder only learns this once the
+    /// next slice or the end of the picture is seen, hence the setter.
+    pub fn set_as_last(&mut self) {
+        // Safe because both members of the union are plain integers of the same size.
+        unsafe { self.0.LongSliceFlags.fields.set_LastSliceOfPic(1) };
+    }
+
+    pub(crate) fn inner_mut(&mut self) -> &mut bindings::VASliceParameterBufferHEVC {
+        self.0.as_mut()
+    }
+
+    /// Returns the inner FFI type. Useful for testing purposes.
+    pub fn inner(&self) -> &bindings::VASliceParameterBufferHEVC {
+        self.0.as_ref()
+    }
+}
+
+/// Wrapper over the `VASliceParameterBufferAV1` FFI type.
+pub struct SliceParameterBufferAV1(Box<bindings::VASliceParameterBufferAV1>);
+
+impl SliceParameterBufferAV1 {
+    /// Creates the wrapper.
+    pub fn new(
+        slice_data_size: u32,
+        slice_data_offset: u32,
+        slice_data_flag: u32,
+        tile_row: u16,
+        tile_column: u16,
+        tg_start: u16,
+        tg_end: u16,
+        anchor_frame_idx: u8,
+        tile_idx_in_tile_list: u16,
+    ) -> Self {
+        Self(Box::new(bindings::VASliceParameterBufferAV1 {
+            slice_data_size,
+            slice_data_offset,
+            slice_data_flag,
+            tile_row,
+            tile_column,
+            tg_start,
+            tg_end,
+            anchor_frame_idx,
+            tile_idx_in_tile_list,
+            va_reserved: Default::default(),
+        }))
+    }
+
+    pub(crate) fn inner_mut(&mut self) -> &mut bindings::VASliceParameterBufferAV1 {
+        self.0.as_mut()
+    }
+
+    /// Returns the inner FFI type. Useful for testing purposes.
+    pub fn inner(&self) -> &bindings::VASliceParameterBufferAV1 {
+        self.0.as_ref()
+    }
+}
+
 /// Wrapper over the `VAIQMatrixBufferMPEG2` FFI type.
 pub struct IQMatrixBufferMPEG2(Box<bindings::VAIQMatrixBufferMPEG2>);
 
@@ -908,6 +1875,40 @@ impl IQMatrixBufferH264 {
     }
 }
 
+/// Wrapper over the `VAIQMatrixBufferHEVC` FFI type
+pub struct IQMatrixBufferHEVC(Box<bindings::VAIQMatrixBufferHEVC>);
+
+impl IQMatrixBufferHEVC {
+    /// Creates the wrapper.
+    pub fn new(
+        scaling_list4x4: [[u8; 16usize]; 6usize],
+        scaling_list8x8: [[u8; 64usize]; 6usize],
+        scaling_list16x16: [[u8; 64usize]; 6usize],
+        scaling_list32x32: [[u8; 64usize]; 2usize],
+        scaling_list_dc16x16: [u8; 6usize],
+        scaling_list_dc32x32: [u8; 2usize],
+    ) -> Self {
+        Self(Box::new(bindings::VAIQMatrixBufferHEVC {
+            ScalingList4x4: scaling_list4x4,
+            ScalingList8x8: scaling_list8x8,
+            ScalingList16x16: scaling_list16x16,
+            ScalingList32x32: scaling_list32x32,
+            ScalingListDC16x16: scaling_list_dc16x16,
+            ScalingListDC32x32: scaling_list_dc32x32,
+            va_reserved: Default::default(),
+        }))
+    }
+
+    pub(crate) fn inner_mut(&mut self) -> &mut bindings::VAIQMatrixBufferHEVC {
+        self.0.as_mut()
+    }
+
+    /// Returns the inner FFI type. Useful for testing purposes.
+    pub fn inner(&self) -> &bindings::VAIQMatrixBufferHEVC {
+        self.0.as_ref()
+    }
+}
+
 /// Abstraction over the `IQMatrixBuffer` types we support.
 pub enum IQMatrix {
     /// Abstraction over `VAIQMatrixBufferMPEG2`
@@ -916,6 +1917,8 @@ pub enum IQMatrix {
     VP8(IQMatrixBufferVP8),
     /// Abstraction over `VAIQMatrixBufferH264`
     H264(IQMatrixBufferH264),
+    /// Abstraction over `VAIQMatrixBufferHEVC`
+    HEVC(IQMatrixBufferHEVC),
 }
 
 /// Wrapper over the VAProbabilityDataBufferVP8 FFI type.
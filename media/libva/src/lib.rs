@@ -24,6 +24,7 @@ mod surface;
 mod usage_hint;
 
 pub use bindings::constants;
+pub use bindings::VAAV1TransformationType;
 pub use bindings::VAConfigAttrib;
 pub use bindings::VAConfigAttribType;
 pub use bindings::VAEntrypoint;
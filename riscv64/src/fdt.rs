@@ -2,6 +2,10 @@
 // Use of this source code is governed by a BSD-style license that can be
 // found in the LICENSE file.
 
+use arch::numa::numa_distance;
+use arch::numa::numa_node_of_cpu;
+use arch::NumaMemoryRange;
+use arch::NumaNode;
 use cros_fdt::Error;
 use cros_fdt::FdtWriter;
 use cros_fdt::Result;
@@ -26,7 +30,15 @@ const PHANDLE_AIA_APLIC: u32 = 2;
 const PHANDLE_AIA_IMSIC: u32 = 3;
 const PHANDLE_CPU_INTC_BASE: u32 = 4;
 
-fn create_memory_node(fdt: &mut FdtWriter, guest_mem: &GuestMemory) -> Result<()> {
+fn create_memory_node(
+    fdt: &mut FdtWriter,
+    guest_mem: &GuestMemory,
+    numa_ranges: &[NumaMemoryRange],
+) -> Result<()> {
+    if !numa_ranges.is_empty() {
+        return create_numa_memory_nodes(fdt, numa_ranges);
+    }
+
     let mut mem_reg_prop = Vec::new();
     let mut previous_memory_region_end = None;
     let mut regions = guest_mem.guest_memory_regions();
@@ -56,7 +68,43 @@ fn create_memory_node(fdt: &mut FdtWriter, guest_mem: &GuestMemory) -> Result<()
     Ok(())
 }
 
-fn create_cpu_nodes(fdt: &mut FdtWriter, num_cpus: u32, timebase_frequency: u32) -> Result<()> {
+// With NUMA, each memory range gets its own node to hold the id of the NUMA node it belongs to.
+fn create_numa_memory_nodes(fdt: &mut FdtWriter, numa_ranges: &[NumaMemoryRange]) -> Result<()> {
+    for range in numa_ranges {
+        let memory_node = fdt.begin_node(&format!("memory@{:x}", range.address.offset()))?;
+        fdt.property_string("device_type", "memory")?;
+        fdt.property_array_u64("reg", &[range.address.offset(), range.size])?;
+        fdt.property_u32("numa-node-id", range.node as u32)?;
+        fdt.end_node(memory_node)?;
+    }
+
+    Ok(())
+}
+
+fn create_distance_map_node(fdt: &mut FdtWriter, numa_nodes: &[NumaNode]) -> Result<()> {
+    let mut distance_matrix = Vec::new();
+    for from in 0..numa_nodes.len() {
+        for to in 0..numa_nodes.len() {
+            distance_matrix.push(from as u32);
+            distance_matrix.push(to as u32);
+            distance_matrix.push(numa_distance(numa_nodes, from, to) as u32);
+        }
+    }
+
+    let distance_map_node = fdt.begin_node("distance-map")?;
+    fdt.property_string("compatible", "numa-distance-map-v1")?;
+    fdt.property_array_u32("distance-matrix", &distance_matrix)?;
+    fdt.end_node(distance_map_node)?;
+
+    Ok(())
+}
+
+fn create_cpu_nodes(
+    fdt: &mut FdtWriter,
+    num_cpus: u32,
+    numa_nodes: &[NumaNode],
+    timebase_frequency: u32,
+) -> Result<()> {
     let cpus_node = fdt.begin_node("cpus")?;
     fdt.property_u32("#address-cells", 0x1)?;
     fdt.property_u32("#size-cells", 0x0)?;
@@ -72,6 +120,9 @@ fn create_cpu_nodes(fdt: &mut FdtWriter, num_cpus: u32, timebase_frequency: u32)
         fdt.property_string("status", "okay")?;
         fdt.property_u32("reg", cpu_id)?;
         fdt.property_u32("phandle", PHANDLE_CPU0 + cpu_id)?;
+        if let Some(node) = numa_node_of_cpu(numa_nodes, cpu_id as usize) {
+            fdt.property_u32("numa-node-id", node as u32)?;
+        }
 
         // Add interrupt controller node
         let intc_node = fdt.begin_node("interrupt-controller")?;
@@ -304,6 +355,8 @@ fn create_pci_nodes(
 /// * `pci_cfg` - Location of the memory-mapped PCI configuration space.
 /// * `pci_ranges` - Memory ranges accessible via the PCI host controller.
 /// * `num_cpus` - Number of virtual CPUs the guest will have
+/// * `numa_nodes` - NUMA nodes of the guest, empty for a single node
+/// * `numa_ranges` - Guest memory ranges of the NUMA nodes
 /// * `fdt_load_offset` - The offset into physical memory for the device tree
 /// * `cmdline` - The kernel commandline
 /// * `initrd` - An optional tuple of initrd guest physical address and size
@@ -315,6 +368,8 @@ pub fn create_fdt(
     pci_cfg: PciConfigRegion,
     pci_ranges: &[PciRange],
     num_cpus: u32,
+    numa_nodes: &[NumaNode],
+    numa_ranges: &[NumaMemoryRange],
     fdt_load_offset: u64,
     aia_num_ids: usize,
     aia_num_sources: usize,
@@ -330,8 +385,11 @@ pub fn create_fdt(
     fdt.property_u32("#address-cells", 0x2)?;
     fdt.property_u32("#size-cells", 0x2)?;
     create_chosen_node(&mut fdt, cmdline, initrd)?;
-    create_memory_node(&mut fdt, guest_mem)?;
-    create_cpu_nodes(&mut fdt, num_cpus, timebase_frequency)?;
+    create_memory_node(&mut fdt, guest_mem, numa_ranges)?;
+    create_cpu_nodes(&mut fdt, num_cpus, numa_nodes, timebase_frequency)?;
+    if !numa_nodes.is_empty() {
+        create_distance_map_node(&mut fdt, numa_nodes)?;
+    }
     create_aia_node(&mut fdt, num_cpus as usize, aia_num_ids, aia_num_sources)?;
     create_pci_nodes(&mut fdt, pci_irqs, pci_cfg, pci_ranges)?;
 
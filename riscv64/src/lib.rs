@@ -91,6 +91,9 @@ const RISCV64_IRQ_BASE: u32 = 1;
 #[sorted]
 #[derive(Error, Debug)]
 pub enum Error {
+    #[cfg(unix)]
+    #[error("failed to bind guest memory to host NUMA node: {0}")]
+    BindNumaMemory(vm_memory::GuestMemoryError),
     #[error("unable to clone an Event: {0}")]
     CloneEvent(base::Error),
     #[error("failed to clone IRQ chip: {0}")]
@@ -200,6 +203,14 @@ impl arch::LinuxArch for Riscv64 {
 
         let mem = vm.get_memory().clone();
 
+        let numa_ranges = arch::numa::numa_memory_ranges(
+            &components.numa_nodes,
+            &[(GuestAddress(RISCV64_PHYS_MEM_START), components.memory_size)],
+        );
+        #[cfg(unix)]
+        arch::numa::bind_numa_memory(&mem, &components.numa_nodes, &numa_ranges)
+            .map_err(Error::BindNumaMemory)?;
+
         let mmio_bus = Arc::new(Bus::new());
 
         // Riscv doesn't really use the io bus like x86, so just create an empty bus.
@@ -369,6 +380,8 @@ impl arch::LinuxArch for Riscv64 {
             pci_cfg,
             &pci_ranges,
             components.vcpu_count as u32,
+            &components.numa_nodes,
+            &numa_ranges,
             fdt_offset,
             aia_num_ids,
             aia_num_sources,
@@ -386,6 +399,7 @@ impl arch::LinuxArch for Riscv64 {
         Ok(RunnableLinuxVm {
             vm,
             vcpu_count: components.vcpu_count,
+            vcpu_hotplug: None,
             vcpus: Some(vcpus),
             vcpu_init,
             vcpu_affinity: components.vcpu_affinity,
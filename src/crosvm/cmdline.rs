@@ -10,6 +10,7 @@ cfg_if::cfg_if! {
         use devices::virtio::vhost::user::device::parse_wayland_sock;
 
         use super::sys::config::VfioOption;
+        use super::sys::config::VfioUserOption;
         use super::config::SharedDir;
     }
 }
@@ -51,6 +52,8 @@ use devices::PflashParameters;
 use devices::SerialHardware;
 use devices::SerialParameters;
 use devices::StubPciParameters;
+#[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), unix))]
+use devices::ThermalConfig;
 #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
 use hypervisor::CpuHybridType;
 use hypervisor::ProtectionType;
@@ -64,6 +67,8 @@ use serde::Deserializer;
 use serde::Serialize;
 #[cfg(feature = "gpu")]
 use serde_keyvalue::FromKeyValues;
+#[cfg(feature = "gpu")]
+use vm_control::gpu::RecordingFormat;
 
 #[cfg(feature = "gpu")]
 use super::gpu_config::fixup_gpu_display_options;
@@ -106,6 +111,7 @@ use crate::crosvm::config::HostPcieRootPortParameters;
 use crate::crosvm::config::HypervisorKind;
 use crate::crosvm::config::IrqChipKind;
 use crate::crosvm::config::MemOptions;
+use crate::crosvm::config::NumaNodeOption;
 use crate::crosvm::config::TouchDeviceOption;
 use crate::crosvm::config::VhostUserFsOption;
 use crate::crosvm::config::VhostUserOption;
@@ -134,6 +140,7 @@ pub struct CrosvmCmdlineArgs {
 #[derive(FromArgs)]
 #[argh(subcommand)]
 pub enum CrossPlatformCommands {
+    Aer(AerCommand),
     #[cfg(feature = "balloon")]
     Balloon(BalloonCommand),
     #[cfg(feature = "balloon")]
@@ -152,15 +159,20 @@ pub enum CrossPlatformCommands {
     MakeRT(MakeRTCommand),
     Resume(ResumeCommand),
     Run(RunCommand),
+    Snd(SndCommand),
     Stop(StopCommand),
     Suspend(SuspendCommand),
     Swap(SwapCommand),
+    Thermal(ThermalCommand),
     Powerbtn(PowerbtnCommand),
     Sleepbtn(SleepCommand),
     Gpe(GpeCommand),
+    Input(InputCommand),
     Usb(UsbCommand),
+    Vcpu(VcpuCommand),
     Version(VersionCommand),
     Vfio(VfioCrosvmCommand),
+    Virtio(VirtioCommand),
     Snapshot(SnapshotCommand),
 }
 
@@ -298,6 +310,49 @@ pub struct ResumeCommand {
     pub socket_path: String,
 }
 
+#[derive(FromArgs)]
+#[argh(subcommand)]
+pub enum SndSubcommand {
+    Jack(JackSndSubcommand),
+}
+
+fn parse_jack_state(s: &str) -> Result<bool, String> {
+    match s {
+        "connected" => Ok(true),
+        "disconnected" => Ok(false),
+        _ => Err(format!(
+            "invalid jack state {}, expected connected or disconnected",
+            s
+        )),
+    }
+}
+
+#[derive(FromArgs)]
+/// plug or unplug a jack
+#[argh(subcommand, name = "jack")]
+pub struct JackSndSubcommand {
+    #[argh(positional, arg_name = "SND_INDEX")]
+    /// sound device index
+    pub snd_index: usize,
+    #[argh(positional, arg_name = "JACK_ID")]
+    /// jack id, in the order of the devices configured with a jack
+    pub jack_id: u32,
+    #[argh(positional, arg_name = "STATE", from_str_fn(parse_jack_state))]
+    /// jack state, connected or disconnected
+    pub connected: bool,
+    #[argh(positional, arg_name = "VM_SOCKET")]
+    /// VM Socket path
+    pub socket_path: String,
+}
+
+#[derive(FromArgs)]
+#[argh(subcommand, name = "snd")]
+/// Manage attached virtio sound devices
+pub struct SndCommand {
+    #[argh(subcommand)]
+    pub command: SndSubcommand,
+}
+
 #[derive(FromArgs)]
 #[argh(subcommand, name = "stop")]
 /// Stops crosvm instances via their control sockets
@@ -409,6 +464,24 @@ pub struct GpeCommand {
     pub socket_path: String,
 }
 
+#[derive(FromArgs)]
+#[argh(subcommand, name = "aer")]
+/// Injects a PCIe Advanced Error Reporting error at a device behind a PCIe root port. The error
+/// is logged and signaled by the root port. Only root ports have an AER capability, so the guest
+/// sees the root port as the source of the error, whichever device behind it is given.
+pub struct AerCommand {
+    #[argh(positional, arg_name = "DEVICE")]
+    /// PCI address of the device, as BUS:DEVICE.FUNCTION. The error is attributed to the root
+    /// port the device is behind.
+    pub address: String,
+    #[argh(positional, arg_name = "ERROR")]
+    /// error to inject: correctable, non-fatal or fatal
+    pub error: String,
+    #[argh(positional, arg_name = "VM_SOCKET")]
+    /// VM Socket path
+    pub socket_path: String,
+}
+
 #[derive(FromArgs)]
 #[argh(subcommand, name = "usb")]
 /// Manage attached virtual USB devices.
@@ -431,6 +504,198 @@ pub struct GpuCommand {
 /// Show package version.
 pub struct VersionCommand {}
 
+#[derive(FromArgs)]
+#[argh(subcommand, name = "add")]
+/// Hotplug a virtio-input device reading events from a unix socket. The socket carries 8 byte
+/// little-endian virtio_input_event structs (u16 type, u16 code, u32 value).
+pub struct InputAddSubCommand {
+    #[argh(positional, arg_name = "KIND")]
+    /// device kind: keyboard, mouse, switches, gamepad, single-touch, multi-touch or trackpad
+    pub kind: String,
+    #[argh(positional, arg_name = "EVENT_SOCKET")]
+    /// path to the unix socket the device reads input events from
+    pub event_socket: PathBuf,
+    #[argh(positional, arg_name = "VM_SOCKET")]
+    /// VM Socket path
+    pub socket_path: String,
+    #[argh(option, arg_name = "WIDTH")]
+    /// width of touch devices, defaults to 1280
+    pub width: Option<u32>,
+    #[argh(option, arg_name = "HEIGHT")]
+    /// height of touch devices, defaults to 1024
+    pub height: Option<u32>,
+}
+
+#[derive(FromArgs)]
+#[argh(subcommand, name = "remove")]
+/// Unplug a virtio-input device previously added with `crosvm input add`
+pub struct InputRemoveSubCommand {
+    #[argh(positional, arg_name = "EVENT_SOCKET")]
+    /// path to the unix socket the device was added with
+    pub event_socket: PathBuf,
+    #[argh(positional, arg_name = "VM_SOCKET")]
+    /// VM Socket path
+    pub socket_path: String,
+}
+
+#[derive(FromArgs)]
+#[argh(subcommand)]
+pub enum InputSubCommand {
+    Add(InputAddSubCommand),
+    Remove(InputRemoveSubCommand),
+}
+
+#[derive(FromArgs)]
+#[argh(subcommand, name = "input")]
+/// add/remove virtio-input devices
+pub struct InputCommand {
+    #[argh(subcommand)]
+    pub command: InputSubCommand,
+}
+
+#[derive(FromArgs)]
+#[argh(subcommand, name = "temperature")]
+/// Set the temperature of the thermal zone
+pub struct ThermalTemperatureSubCommand {
+    #[argh(positional, arg_name = "CELSIUS")]
+    /// temperature in degrees Celsius
+    pub celsius: i32,
+    #[argh(positional, arg_name = "VM_SOCKET")]
+    /// VM Socket path
+    pub socket_path: String,
+}
+
+#[derive(FromArgs)]
+#[argh(subcommand, name = "trip-points")]
+/// Set the passive cooling and critical trip points of the thermal zone
+pub struct ThermalTripPointsSubCommand {
+    #[argh(positional, arg_name = "PASSIVE")]
+    /// passive cooling trip point in degrees Celsius
+    pub passive: i32,
+    #[argh(positional, arg_name = "CRITICAL")]
+    /// critical trip point in degrees Celsius
+    pub critical: i32,
+    #[argh(positional, arg_name = "VM_SOCKET")]
+    /// VM Socket path
+    pub socket_path: String,
+}
+
+#[derive(FromArgs)]
+#[argh(subcommand, name = "frequency-limit")]
+/// Limit the vCPUs to the performance states at or below a frequency
+pub struct ThermalFrequencyLimitSubCommand {
+    #[argh(positional, arg_name = "MHZ")]
+    /// highest allowed frequency in MHz
+    pub mhz: u32,
+    #[argh(positional, arg_name = "VM_SOCKET")]
+    /// VM Socket path
+    pub socket_path: String,
+}
+
+#[derive(FromArgs)]
+#[argh(subcommand)]
+pub enum ThermalSubCommand {
+    Temperature(ThermalTemperatureSubCommand),
+    TripPoints(ThermalTripPointsSubCommand),
+    FrequencyLimit(ThermalFrequencyLimitSubCommand),
+}
+
+#[derive(FromArgs)]
+#[argh(subcommand, name = "thermal")]
+/// change the thermal zone of a VM started with `--thermal`
+pub struct ThermalCommand {
+    #[argh(subcommand)]
+    pub command: ThermalSubCommand,
+}
+
+#[derive(FromArgs)]
+#[argh(subcommand, name = "add")]
+/// Hotplug a vCPU that was not online at boot
+pub struct VcpuAddSubCommand {
+    #[argh(positional, arg_name = "CPU_ID")]
+    /// index of the vCPU, smaller than the number of cores given to `--cpus`
+    pub cpu_id: usize,
+    #[argh(positional, arg_name = "VM_SOCKET")]
+    /// VM Socket path
+    pub socket_path: String,
+}
+
+#[derive(FromArgs)]
+#[argh(subcommand, name = "remove")]
+/// Unplug a vCPU. vCPU 0 can't be unplugged.
+pub struct VcpuRemoveSubCommand {
+    #[argh(positional, arg_name = "CPU_ID")]
+    /// index of the vCPU
+    pub cpu_id: usize,
+    #[argh(positional, arg_name = "VM_SOCKET")]
+    /// VM Socket path
+    pub socket_path: String,
+}
+
+#[derive(FromArgs)]
+#[argh(subcommand)]
+pub enum VcpuSubCommand {
+    Add(VcpuAddSubCommand),
+    Remove(VcpuRemoveSubCommand),
+}
+
+#[derive(FromArgs)]
+#[argh(subcommand, name = "vcpu")]
+/// add/remove vCPUs of a VM started with `--cpus boot-cores=N`
+pub struct VcpuCommand {
+    #[argh(subcommand)]
+    pub command: VcpuSubCommand,
+}
+
+#[derive(FromArgs)]
+#[argh(subcommand, name = "add")]
+/// Hotplug a virtio-pci device behind a free PCIe hotplug root port
+pub struct VirtioAddSubCommand {
+    #[argh(positional, arg_name = "KIND")]
+    /// device kind: block, net, shared-dir, vhost-user-blk, vhost-user-fs or vhost-user-net
+    pub kind: String,
+    #[argh(positional, arg_name = "ID")]
+    /// name of the device, used to remove it
+    pub id: String,
+    #[argh(positional, arg_name = "PARAMS")]
+    /// device parameters, as given to the `crosvm run` option of the same name. Paths must be
+    /// absolute.
+    pub params: String,
+    #[argh(positional, arg_name = "VM_SOCKET")]
+    /// VM Socket path
+    pub socket_path: String,
+}
+
+#[derive(FromArgs)]
+#[argh(subcommand, name = "remove")]
+/// Unplug a virtio-pci device previously added with `crosvm virtio add`
+pub struct VirtioRemoveSubCommand {
+    #[argh(positional, arg_name = "KIND")]
+    /// device kind the device was added with
+    pub kind: String,
+    #[argh(positional, arg_name = "ID")]
+    /// name the device was added with
+    pub id: String,
+    #[argh(positional, arg_name = "VM_SOCKET")]
+    /// VM Socket path
+    pub socket_path: String,
+}
+
+#[derive(FromArgs)]
+#[argh(subcommand)]
+pub enum VirtioSubCommand {
+    Add(VirtioAddSubCommand),
+    Remove(VirtioRemoveSubCommand),
+}
+
+#[derive(FromArgs)]
+#[argh(subcommand, name = "virtio")]
+/// add/remove virtio-pci devices of a running VM
+pub struct VirtioCommand {
+    #[argh(subcommand)]
+    pub command: VirtioSubCommand,
+}
+
 #[derive(FromArgs)]
 #[argh(subcommand, name = "add")]
 /// ADD
@@ -508,6 +773,9 @@ pub enum GpuSubCommand {
     AddDisplays(GpuAddDisplaysCommand),
     ListDisplays(GpuListDisplaysCommand),
     RemoveDisplays(GpuRemoveDisplaysCommand),
+    Screenshot(GpuScreenshotCommand),
+    StartRecording(GpuStartRecordingCommand),
+    StopRecording(GpuStopRecordingCommand),
 }
 
 #[cfg(feature = "gpu")]
@@ -547,10 +815,60 @@ pub struct GpuRemoveDisplaysCommand {
     pub socket_path: String,
 }
 
+#[cfg(feature = "gpu")]
+#[derive(FromArgs)]
+/// Save the current contents of a display as a PNG image.
+#[argh(subcommand, name = "screenshot")]
+pub struct GpuScreenshotCommand {
+    #[argh(option, default = "0")]
+    /// display id (default: 0)
+    pub display_id: u32,
+    #[argh(positional, arg_name = "FILE")]
+    /// path of the PNG file to write
+    pub file_path: PathBuf,
+    #[argh(positional, arg_name = "VM_SOCKET")]
+    /// VM Socket path
+    pub socket_path: String,
+}
+
+#[cfg(feature = "gpu")]
+#[derive(FromArgs)]
+/// Start recording every frame shown on a display to a file.
+#[argh(subcommand, name = "start-recording")]
+pub struct GpuStartRecordingCommand {
+    #[argh(option, default = "0")]
+    /// display id (default: 0)
+    pub display_id: u32,
+    #[argh(option)]
+    /// file format, `apng` or `ivf` (default: ivf if ffmpeg supports VP8, apng otherwise)
+    pub format: Option<RecordingFormat>,
+    #[argh(positional, arg_name = "FILE")]
+    /// path of the file to write
+    pub file_path: PathBuf,
+    #[argh(positional, arg_name = "VM_SOCKET")]
+    /// VM Socket path
+    pub socket_path: String,
+}
+
+#[cfg(feature = "gpu")]
+#[derive(FromArgs)]
+/// Stop recording a display and finalize the file.
+#[argh(subcommand, name = "stop-recording")]
+pub struct GpuStopRecordingCommand {
+    #[argh(option, default = "0")]
+    /// display id (default: 0)
+    pub display_id: u32,
+    #[argh(positional, arg_name = "VM_SOCKET")]
+    /// VM Socket path
+    pub socket_path: String,
+}
+
 #[derive(FromArgs)]
 #[argh(subcommand)]
 pub enum UsbSubCommand {
     Attach(UsbAttachCommand),
+    AttachHid(UsbAttachHidCommand),
+    AttachStorage(UsbAttachStorageCommand),
     Detach(UsbDetachCommand),
     List(UsbListCommand),
 }
@@ -566,13 +884,72 @@ pub struct UsbAttachCommand {
     )]
     pub addr: (u8, u8, u16, u16),
     #[argh(positional)]
-    /// usb device path
+    /// usb device path, or usbip://HOST:PORT/BUSID to import a device from a usbip server
     pub dev_path: String,
     #[argh(positional, arg_name = "VM_SOCKET")]
     /// VM Socket path
     pub socket_path: String,
 }
 
+/// Kind of emulated usb HID device.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UsbHidDeviceKind {
+    Keyboard,
+    Mouse,
+    Tablet,
+}
+
+fn parse_usb_hid_kind(s: &str) -> Result<UsbHidDeviceKind, String> {
+    match s {
+        "keyboard" => Ok(UsbHidDeviceKind::Keyboard),
+        "mouse" => Ok(UsbHidDeviceKind::Mouse),
+        "tablet" => Ok(UsbHidDeviceKind::Tablet),
+        _ => Err(format!(
+            "invalid usb hid device kind {}, expected keyboard, mouse or tablet",
+            s
+        )),
+    }
+}
+
+#[derive(FromArgs)]
+/// Attach an emulated usb HID device fed by input events
+#[argh(subcommand, name = "attach-hid")]
+pub struct UsbAttachHidCommand {
+    #[argh(positional, arg_name = "KIND", from_str_fn(parse_usb_hid_kind))]
+    /// kind of device: keyboard, mouse or tablet
+    pub kind: UsbHidDeviceKind,
+    #[argh(positional, arg_name = "EVENT_SOURCE")]
+    /// path to a socket sending virtio input events, or to an evdev device with --evdev
+    pub source_path: String,
+    #[argh(positional, arg_name = "VM_SOCKET")]
+    /// VM Socket path
+    pub socket_path: String,
+    #[argh(switch)]
+    /// the event source is a host evdev device, grabbed while the device is attached
+    pub evdev: bool,
+    #[argh(option, arg_name = "WIDTH")]
+    /// width of the tablet coordinates, defaults to 1280
+    pub width: Option<u32>,
+    #[argh(option, arg_name = "HEIGHT")]
+    /// height of the tablet coordinates, defaults to 1024
+    pub height: Option<u32>,
+}
+
+#[derive(FromArgs)]
+/// Attach an emulated usb mass storage device backed by a disk image
+#[argh(subcommand, name = "attach-storage")]
+pub struct UsbAttachStorageCommand {
+    #[argh(positional, arg_name = "IMAGE")]
+    /// disk image path
+    pub image_path: String,
+    #[argh(positional, arg_name = "VM_SOCKET")]
+    /// VM Socket path
+    pub socket_path: String,
+    #[argh(switch)]
+    /// expose the disk image as write protected
+    pub read_only: bool,
+}
+
 #[derive(FromArgs)]
 /// Detach usb device
 #[argh(subcommand, name = "detach")]
@@ -1020,6 +1397,11 @@ pub struct RunCommand {
     /// cpu parameters.
     /// Possible key values:
     ///     num-cores=NUM - number of VCPUs. (default: 1)
+    ///     boot-cores=NUM - number of VCPUs online at boot. The
+    ///       others can be hot-plugged later with `crosvm vcpu`.
+    ///       On x86 the guest is notified through ACPI, on
+    ///       aarch64 the guest has to bring the plugged VCPUs
+    ///       online itself. (default: all VCPUs)
     ///     clusters=[[CLUSTER],...] - CPU clusters (default: None)
     ///       Each CLUSTER is a set containing a list of CPUs
     ///       that should belong to the same cluster. Individual
@@ -1273,6 +1655,11 @@ pub struct RunCommand {
     ///     cache-size=SIZE - The maximum size of the shader cache.
     ///     pci-bar-size=SIZE - The size for the PCI BAR in bytes
     ///        (default 8gb).
+    ///     vnc-port=PORT - Serve the displays to VNC clients on
+    ///        127.0.0.1, display N on port PORT+N (Linux only).
+    ///     headless-socket=PATH - Keep the displays in memory and
+    ///        serve them and accept input on the unix socket at
+    ///        PATH (Linux only).
     ///
     /// Possible key values for GpuDisplayParameters:
     ///     mode=(borderless_full_screen|windowed[width,height]) -
@@ -1561,6 +1948,25 @@ pub struct RunCommand {
     /// don't use usb devices in the guest
     pub no_usb: Option<bool>,
 
+    #[argh(
+        option,
+        arg_name = "cpus=[CPUSET],size=NUM[,host-node=NODE][,distances=[DISTANCES]]"
+    )]
+    #[serde(default)]
+    #[merge(strategy = append)]
+    /// add a NUMA node to the guest. Nodes are numbered in the
+    ///     order they are given, and together must have all the
+    ///     vCPUs and guest memory.
+    /// Possible key values:
+    ///     cpus=[CPUSET] - vCPUs of the node.
+    ///     size=NUM - amount of guest memory of the node in MiB.
+    ///     host-node=NODE - allocate the memory of the node from
+    ///        this host NUMA node. (default: no binding)
+    ///     distances=[DISTANCES] - distance to each node, in node
+    ///        order. The distance to the node itself must be 10.
+    ///        (default: 10 to itself, 20 to the other nodes)
+    pub numa: Vec<NumaNodeOption>,
+
     #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
     #[argh(option, arg_name = "OEM_STRING")]
     #[serde(skip)] // TODO(b/255223604)
@@ -1574,6 +1980,14 @@ pub struct RunCommand {
     /// extra kernel or plugin command line arguments. Can be given more than once
     pub params: Vec<String>,
 
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    #[argh(option, arg_name = "N")]
+    #[serde(skip)] // TODO(b/255223604)
+    #[merge(strategy = overwrite_option)]
+    /// number of empty PCIe root ports to create for hotplugged
+    /// devices. Each port takes one device. (default: 1)
+    pub pci_hotplug_slots: Option<u8>,
+
     #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
     #[argh(option, arg_name = "pci_low_mmio_start")]
     #[serde(skip)] // TODO(b/255223604)
@@ -1925,6 +2339,27 @@ pub struct RunCommand {
     ///        supports POSIX ACLs.  This should only be enabled
     ///        when the underlying file system supports POSIX ACLs.
     ///        The default value for this option is "true".
+    ///     guest_uid_map=MAP - Translation of uids between the VM
+    ///        and the device process for type "fs", in the format
+    ///        "guest host count[,guest host count]". Applied to
+    ///        file ownership and to the credentials used for
+    ///        creating files. Unmapped uids are passed through
+    ///        unchanged (default: empty).
+    ///     guest_gid_map=MAP - Translation of gids between the VM
+    ///        and the device process for type "fs", in the same
+    ///        format as guest_uid_map (default: empty).
+    ///     read_only=BOOL - Reject every modification of the
+    ///        shared directory with EROFS for type "fs".
+    ///        (default: false)
+    ///     allow=PATH - Path relative to the shared directory
+    ///        that the VM may access for type "fs". May be given
+    ///        multiple times. Everything else is hidden except
+    ///        for the directories leading to the allowed paths.
+    ///        (default: everything is allowed)
+    ///     deny=PATH - Path relative to the shared directory
+    ///        that the VM may not access for type "fs". May be
+    ///        given multiple times and takes precedence over
+    ///        allow. Denied requests are logged.
     ///     uid=UID - uid of the device process in the user
     ///        namespace created by minijail. (default: 0)
     ///     gid=GID - gid of the device process in the user
@@ -1956,6 +2391,15 @@ pub struct RunCommand {
     /// Redirects slirp network packets to the supplied log file rather than the current directory as `slirp_capture_packets.pcap`
     pub slirp_capture_file: Option<String>,
 
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    #[argh(option, arg_name = "PATH")]
+    #[serde(skip)] // TODO(b/255223604)
+    #[merge(strategy = overwrite_option)]
+    /// JSON file with SMBIOS structures to add to the DMI tables,
+    ///     or to replace the generated BIOS and System Information
+    ///     with. See `arch::smbios::SmbiosConfig` for the format.
+    pub smbios: Option<PathBuf>,
+
     #[argh(option, short = 's', arg_name = "PATH")]
     #[merge(strategy = overwrite_option)]
     /// path to put the control socket. If PATH is a directory, a name will be generated
@@ -2053,6 +2497,26 @@ pub struct RunCommand {
     /// comma-separated names of the task profiles to apply to all threads in crosvm including the vCPU threads
     pub task_profiles: Vec<String>,
 
+    #[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), unix))]
+    #[argh(
+        option,
+        arg_name = "[temperature=C][,passive=C][,critical=C][,frequencies=[MHZ]]"
+    )]
+    #[serde(skip)] // TODO(b/255223604)
+    #[merge(strategy = overwrite_option)]
+    /// emulate an ACPI thermal zone and processor performance
+    ///     states, controlled with `crosvm thermal`.
+    /// Possible key values:
+    ///     temperature=C - temperature of the thermal zone in
+    ///        degrees Celsius. (default: 40)
+    ///     passive=C - passive cooling trip point. (default: 90)
+    ///     critical=C - critical trip point. (default: 105)
+    ///     frequencies=[MHZ] - frequencies of the processor
+    ///        performance states, from the fastest. Required for
+    ///        passive cooling and frequency limits.
+    ///        (default: no performance states)
+    pub thermal: Option<ThermalConfig>,
+
     #[argh(option, arg_name = "PATH:WIDTH:HEIGHT")]
     #[serde(skip)] // TODO(b/255223604)
     #[merge(strategy = append)]
@@ -2134,6 +2598,16 @@ pub struct RunCommand {
     /// path to sysfs of platform pass through
     pub vfio_platform: Vec<VfioOption>,
 
+    #[cfg(unix)]
+    #[argh(option, arg_name = "PATH[,guest-address=<BUS:DEVICE.FUNCTION>]")]
+    #[serde(default)]
+    #[merge(strategy = append)]
+    /// path to the socket of a vfio-user server emulating a PCI
+    ///     device in another process.
+    ///     guest-address=<BUS:DEVICE.FUNCTION> - PCI address
+    ///        that the device will be assigned in the guest.
+    pub vfio_user: Vec<VfioUserOption>,
+
     #[cfg(unix)]
     #[argh(switch)]
     #[serde(skip)] // Deprecated - use `net` instead.
@@ -2251,8 +2725,9 @@ pub struct RunCommand {
     /// Possible key values:
     ///     capture=(false,true) - Disable/enable audio capture.
     ///         Default is false.
-    ///     backend=(null,file,[cras]) - Which backend to use for
-    ///         virtio-snd.
+    ///     backend=(null,file,[cras],[pulse]) - Which backend to
+    ///         use for virtio-snd. pulse requires the audio_pulse
+    ///         feature.
     ///     client_type=(crosvm,arcvm,borealis) - Set specific
     ///         client type for cras backend. Default is crosvm.
     ///     socket_type=(legacy,unified) Set specific socket type
@@ -2260,7 +2735,17 @@ pub struct RunCommand {
     ///     playback_path=STR - Set directory of output streams
     ///         for file backend.
     ///     playback_size=INT - Set size of the output streams
-    ///         from file backend.
+    ///         from file backend. The streams are written as WAV
+    ///         files named stream-N.out.
+    ///     capture_path=STR - Set directory of input streams for
+    ///         file backend. Input stream N reads its samples from
+    ///         the stream-N.wav file, whose format must match the
+    ///         one requested by the guest. Capture provides silence
+    ///         when unset.
+    ///     capture_loop=(false,true) - Whether input streams of
+    ///         file backend start over once the end of the file is
+    ///         reached, instead of providing silence. Default is
+    ///         false.
     ///     num_output_devices=INT - Set number of output PCM
     ///         devices.
     ///     num_input_devices=INT - Set number of input PCM devices.
@@ -2268,9 +2753,15 @@ pub struct RunCommand {
     ///         streams per device.
     ///     num_input_streams=INT - Set number of input PCM streams
     ///         per device.
+    ///     output_device_config=[[jack=(connected,disconnected)],
+    ///         ...] - Give output PCM devices a headphone jack in
+    ///         the given initial state, which can be changed with
+    ///         `crosvm snd jack`.
+    ///     input_device_config=[[jack=(connected,disconnected)],
+    ///         ...] - Give input PCM devices a microphone jack.
     pub virtio_snd: Vec<SndParameters>,
 
-    #[argh(option, arg_name = "cid=CID[,device=VHOST_DEVICE]")]
+    #[argh(option, arg_name = "cid=CID[,device=VHOST_DEVICE][,uds_path=PATH]")]
     #[serde(default)]
     #[merge(strategy = overwrite_option)]
     /// add a vsock device. Since a guest can only have one CID,
@@ -2278,6 +2769,11 @@ pub struct RunCommand {
     ///     cid=CID - CID to use for the device.
     ///     device=VHOST_DEVICE - path to the vhost-vsock device to
     ///         use (Linux only). Defaults to /dev/vhost-vsock.
+    ///     uds_path=PATH - use a userspace device instead of
+    ///         vhost-vsock (Linux only). Guest connections to host
+    ///         port P are forwarded to the unix socket PATH_P, and
+    ///         host applications connect to the guest through the
+    ///         unix socket PATH by writing "CONNECT <port>\n".
     pub vsock: Option<VsockConfig>,
 
     #[cfg(all(feature = "vtpm", target_arch = "x86_64"))]
@@ -2402,6 +2898,7 @@ impl TryFrom<RunCommand> for super::config::Config {
         {
             let cpus = cmd.cpus.unwrap_or_default();
             cfg.vcpu_count = cpus.num_cores;
+            cfg.boot_vcpu_count = cpus.boot_cores;
 
             // Only allow deprecated `--cpu-cluster` option only if `--cpu clusters=[...]` is not
             // used.
@@ -2460,6 +2957,7 @@ impl TryFrom<RunCommand> for super::config::Config {
 
         let mem = cmd.mem.unwrap_or_default();
         cfg.memory = mem.size;
+        cfg.numa = cmd.numa;
 
         #[cfg(target_arch = "aarch64")]
         {
@@ -3000,6 +3498,7 @@ impl TryFrom<RunCommand> for super::config::Config {
         #[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), unix))]
         {
             cfg.ac_adapter = cmd.ac_adapter.unwrap_or_default();
+            cfg.thermal = cmd.thermal;
         }
 
         #[cfg(feature = "gdb")]
@@ -3014,6 +3513,7 @@ impl TryFrom<RunCommand> for super::config::Config {
             cfg.enable_hwp = cmd.enable_hwp.unwrap_or_default();
             cfg.force_s2idle = cmd.s2idle.unwrap_or_default();
             cfg.pcie_ecam = cmd.pcie_ecam;
+            cfg.pci_hotplug_slots = cmd.pci_hotplug_slots.unwrap_or(1);
             cfg.pci_low_start = cmd.pci_start;
             cfg.no_i8042 = cmd.no_i8042.unwrap_or_default();
             cfg.no_rtc = cmd.no_rtc.unwrap_or_default();
@@ -3022,6 +3522,15 @@ impl TryFrom<RunCommand> for super::config::Config {
             if !cfg.oem_strings.is_empty() && cfg.dmi_path.is_some() {
                 return Err("unable to use oem-strings and dmi-path together".to_string());
             }
+            if let Some(path) = cmd.smbios {
+                if cmd.dmi.is_some() {
+                    return Err("unable to use smbios and dmi-path together".to_string());
+                }
+                let smbios = std::fs::read_to_string(&path)
+                    .map_err(|e| format!("failed to read {}: {}", path.display(), e))?;
+                cfg.smbios = serde_json::from_str(&smbios)
+                    .map_err(|e| format!("invalid SMBIOS file {}: {}", path.display(), e))?;
+            }
             for (index, msr_config) in cmd.userspace_msr {
                 if cfg.userspace_msr.insert(index, msr_config).is_some() {
                     return Err(String::from("msr must be unique"));
@@ -3111,6 +3620,7 @@ impl TryFrom<RunCommand> for super::config::Config {
             cfg.vfio.extend(cmd.vfio);
             cfg.vfio.extend(cmd.vfio_platform);
             cfg.vfio_isolate_hotplug = cmd.vfio_isolate_hotplug.unwrap_or_default();
+            cfg.vfio_user.extend(cmd.vfio_user);
         }
 
         // `--disable-sandbox` has the effect of disabling sandboxing altogether, so make sure
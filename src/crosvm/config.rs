@@ -24,6 +24,8 @@ use arch::MsrRWType;
 #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
 use arch::MsrValueFrom;
 use arch::Pstore;
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+use arch::SmbiosConfig;
 use arch::VcpuAffinity;
 use base::debug;
 use base::pagesize;
@@ -54,6 +56,8 @@ use devices::BusRange;
 use devices::PciAddress;
 use devices::PflashParameters;
 use devices::StubPciParameters;
+#[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), unix))]
+use devices::ThermalConfig;
 #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
 use hypervisor::CpuHybridType;
 use hypervisor::ProtectionType;
@@ -134,6 +138,9 @@ pub struct CpuOptions {
     /// Number of CPU cores.
     #[serde(default)]
     pub num_cores: Option<usize>,
+    /// Number of CPU cores online at boot, the others can be hot-plugged.
+    #[serde(default)]
+    pub boot_cores: Option<usize>,
     /// Vector of CPU ids to be grouped into the same cluster.
     #[serde(default)]
     pub clusters: Vec<CpuSet>,
@@ -150,6 +157,22 @@ pub struct MemOptions {
     pub size: Option<u64>,
 }
 
+/// A NUMA node of the guest. Nodes are numbered in the order they are given.
+#[derive(Debug, Deserialize, Serialize, FromKeyValues, PartialEq, Eq)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+pub struct NumaNodeOption {
+    /// vCPUs of the node.
+    pub cpus: CpuSet,
+    /// Amount of guest memory of the node in MiB.
+    pub size: u64,
+    /// Host NUMA node to allocate the memory of the node from.
+    #[serde(default)]
+    pub host_node: Option<u32>,
+    /// Distance to each node, in node order.
+    #[serde(default)]
+    pub distances: Vec<u8>,
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct VhostUserOption {
     pub socket: PathBuf,
@@ -463,6 +486,15 @@ impl FromStr for SharedDir {
         //   and directory contents should be considered valid (default: 5)
         // * cache=CACHE - one of "never", "always", or "auto" (default: auto)
         // * writeback=BOOL - indicates whether writeback caching should be enabled (default: false)
+        // * guest_uid_map=MAP - a uid translation between the VM and the fs device process in the
+        //   format "guest host count[,guest host count]" (default: empty)
+        // * guest_gid_map=MAP - a gid translation in the same format as guest_uid_map
+        //   (default: empty)
+        // * read_only=BOOL - reject every modification of the shared directory (default: false)
+        // * allow=PATH - a path relative to the shared directory that the VM may access. Can be
+        //   repeated. When given, everything else is hidden (default: everything is allowed)
+        // * deny=PATH - a path relative to the shared directory that the VM may not access. Can
+        //   be repeated and takes precedence over allow
         // * uid=UID - uid of the device process in the user namespace created by minijail.
         //   (default: 0)
         // * gid=GID - gid of the device process in the user namespace created by minijail.
@@ -1059,6 +1091,7 @@ pub struct Config {
     pub block_control_tube: Vec<Tube>,
     #[cfg(windows)]
     pub block_vhost_user_tube: Vec<Tube>,
+    pub boot_vcpu_count: Option<usize>,
     #[cfg(windows)]
     pub broker_shutdown_event: Option<Event>,
     #[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), unix))]
@@ -1142,10 +1175,13 @@ pub struct Config {
     pub no_i8042: bool,
     pub no_rtc: bool,
     pub no_smt: bool,
+    pub numa: Vec<NumaNodeOption>,
     #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
     pub oem_strings: Vec<String>,
     pub params: Vec<String>,
     #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    pub pci_hotplug_slots: u8,
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
     pub pci_low_start: Option<u64>,
     #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
     pub pcie_ecam: Option<AddressRange>,
@@ -1186,6 +1222,8 @@ pub struct Config {
     pub shared_dirs: Vec<SharedDir>,
     #[cfg(feature = "slirp-ring-capture")]
     pub slirp_capture_file: Option<String>,
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    pub smbios: SmbiosConfig,
     #[cfg(all(windows, feature = "audio"))]
     pub snd_split_config: Option<SndSplitConfig>,
     pub socket_path: Option<PathBuf>,
@@ -1199,6 +1237,8 @@ pub struct Config {
     pub swiotlb: Option<u64>,
     #[cfg(target_os = "android")]
     pub task_profiles: Vec<String>,
+    #[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), unix))]
+    pub thermal: Option<ThermalConfig>,
     #[cfg(unix)]
     pub unmap_guest_memory_on_fork: bool,
     pub usb: bool,
@@ -1214,6 +1254,8 @@ pub struct Config {
     #[cfg(unix)]
     pub vfio_isolate_hotplug: bool,
     #[cfg(unix)]
+    pub vfio_user: Vec<super::sys::config::VfioUserOption>,
+    #[cfg(unix)]
     pub vhost_net_device_path: PathBuf,
     pub vhost_user_blk: Vec<VhostUserOption>,
     pub vhost_user_console: Vec<VhostUserOption>,
@@ -1267,6 +1309,7 @@ impl Default for Config {
             block_control_tube: Vec::new(),
             #[cfg(windows)]
             block_vhost_user_tube: Vec::new(),
+            boot_vcpu_count: None,
             #[cfg(windows)]
             broker_shutdown_event: None,
             #[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), unix))]
@@ -1358,10 +1401,13 @@ impl Default for Config {
             no_i8042: false,
             no_rtc: false,
             no_smt: false,
+            numa: Vec::new(),
             #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
             oem_strings: Vec::new(),
             params: Vec::new(),
             #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+            pci_hotplug_slots: 1,
+            #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
             pci_low_start: None,
             #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
             pcie_ecam: None,
@@ -1395,6 +1441,8 @@ impl Default for Config {
             shared_dirs: Vec::new(),
             #[cfg(feature = "slirp-ring-capture")]
             slirp_capture_file: None,
+            #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+            smbios: Default::default(),
             #[cfg(all(windows, feature = "audio"))]
             snd_split_config: None,
             swap_dir: None,
@@ -1408,6 +1456,8 @@ impl Default for Config {
             swiotlb: None,
             #[cfg(target_os = "android")]
             task_profiles: Vec::new(),
+            #[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), unix))]
+            thermal: None,
             #[cfg(unix)]
             unmap_guest_memory_on_fork: false,
             usb: true,
@@ -1423,6 +1473,8 @@ impl Default for Config {
             #[cfg(unix)]
             vfio_isolate_hotplug: false,
             #[cfg(unix)]
+            vfio_user: Vec::new(),
+            #[cfg(unix)]
             vhost_net_device_path: PathBuf::from(VHOST_NET_PATH),
             vhost_user_blk: Vec::new(),
             vhost_user_console: Vec::new(),
@@ -1628,6 +1680,38 @@ pub fn validate_config(cfg: &mut Config) -> std::result::Result<(), String> {
         validate_file_backed_mapping(mapping)?;
     }
 
+    if !cfg.numa.is_empty() {
+        validate_numa_nodes(cfg)?;
+    }
+
+    if let Some(boot_vcpu_count) = cfg.boot_vcpu_count {
+        if cfg!(any(windows, target_arch = "riscv64")) {
+            return Err("`boot-cores` is not supported on this platform".to_string());
+        }
+        let vcpu_count = cfg.vcpu_count.unwrap_or(1);
+        if boot_vcpu_count == 0 || boot_vcpu_count > vcpu_count {
+            return Err(format!(
+                "`boot-cores` must be between 1 and the number of cores ({})",
+                vcpu_count
+            ));
+        }
+        // The hot-pluggable vCPUs are described with the default APIC IDs.
+        if cfg.host_cpu_topology {
+            return Err("`boot-cores` cannot be used with `host-cpu-topology`".to_string());
+        }
+        // Their _MAT returns a Processor Local APIC structure, whose APIC ID is 8 bits wide and
+        // 0xff is the broadcast ID.
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        if vcpu_count > 255 {
+            return Err("`boot-cores` supports at most 255 cores".to_string());
+        }
+    }
+
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    if cfg.pci_hotplug_slots == 0 {
+        return Err("`pci-hotplug-slots` must be at least 1".to_string());
+    }
+
     // Validate platform specific things
     super::sys::config::validate_config(cfg)
 }
@@ -1650,6 +1734,82 @@ fn validate_file_backed_mapping(mapping: &mut FileBackedMappingParameters) -> Re
     Ok(())
 }
 
+fn validate_numa_nodes(cfg: &mut Config) -> Result<(), String> {
+    let vcpu_count = cfg.vcpu_count.unwrap_or(1);
+    let mut vcpu_nodes = vec![None; vcpu_count];
+    let mut memory: u64 = 0;
+    for (node_id, node) in cfg.numa.iter().enumerate() {
+        if node.size == 0 {
+            return Err(format!("`numa` node {} has no memory", node_id));
+        }
+        memory = memory
+            .checked_add(node.size)
+            .ok_or_else(|| "`numa` memory size too large".to_string())?;
+
+        for cpu in node.cpus.iter() {
+            match vcpu_nodes.get_mut(*cpu) {
+                Some(Some(other)) => {
+                    return Err(format!(
+                        "vCPU {} is in both `numa` nodes {} and {}",
+                        cpu, other, node_id
+                    ))
+                }
+                Some(vcpu_node) => *vcpu_node = Some(node_id),
+                None => {
+                    return Err(format!(
+                        "`numa` node {} has vCPU {}, but there are only {} vCPUs",
+                        node_id, cpu, vcpu_count
+                    ))
+                }
+            }
+        }
+
+        if !node.distances.is_empty() {
+            if node.distances.len() != cfg.numa.len() {
+                return Err(format!(
+                    "`numa` node {} must have a distance to each of the {} nodes",
+                    node_id,
+                    cfg.numa.len()
+                ));
+            }
+            for (other, distance) in node.distances.iter().enumerate() {
+                let valid = if other == node_id {
+                    *distance == arch::numa::NUMA_LOCAL_DISTANCE
+                } else {
+                    *distance > arch::numa::NUMA_LOCAL_DISTANCE
+                };
+                if !valid {
+                    return Err(format!(
+                        "invalid `numa` distance {} from node {} to node {}",
+                        distance, node_id, other
+                    ));
+                }
+            }
+        }
+
+        #[cfg(windows)]
+        if node.host_node.is_some() {
+            return Err("`numa` host-node is not supported on Windows".to_string());
+        }
+    }
+
+    if let Some(cpu) = vcpu_nodes.iter().position(|node| node.is_none()) {
+        return Err(format!("vCPU {} is not in any `numa` node", cpu));
+    }
+
+    match cfg.memory {
+        Some(size) if size != memory => Err(format!(
+            "the `numa` nodes have {} MiB of memory, but the guest has {} MiB",
+            memory, size
+        )),
+        Some(_) => Ok(()),
+        None => {
+            cfg.memory = Some(memory);
+            Ok(())
+        }
+    }
+}
+
 #[cfg(test)]
 #[allow(clippy::needless_update)]
 mod tests {
@@ -1760,6 +1920,196 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parse_numa_opts() {
+        let res: NumaNodeOption = from_key_values("cpus=[0-3],size=1024").unwrap();
+        assert_eq!(
+            res,
+            NumaNodeOption {
+                cpus: CpuSet::new([0, 1, 2, 3]),
+                size: 1024,
+                host_node: None,
+                distances: Vec::new(),
+            }
+        );
+
+        let res: NumaNodeOption =
+            from_key_values("cpus=[4,6],size=512,host-node=1,distances=[21,10]").unwrap();
+        assert_eq!(
+            res,
+            NumaNodeOption {
+                cpus: CpuSet::new([4, 6]),
+                size: 512,
+                host_node: Some(1),
+                distances: vec![21, 10],
+            }
+        );
+
+        from_key_values::<NumaNodeOption>("cpus=[0]").expect_err("parse should have failed");
+    }
+
+    #[test]
+    fn numa_nodes_set_memory_size() {
+        let cfg = TryInto::<Config>::try_into(
+            crate::crosvm::cmdline::RunCommand::from_args(
+                &[],
+                &[
+                    "--cpus",
+                    "3",
+                    "--numa",
+                    "cpus=[0,1],size=256",
+                    "--numa",
+                    "cpus=[2],size=512",
+                    "/dev/null",
+                ],
+            )
+            .unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(cfg.numa.len(), 2);
+        assert_eq!(cfg.memory, Some(768));
+    }
+
+    #[test]
+    fn numa_nodes_invalid() {
+        // Memory size mismatch.
+        TryInto::<Config>::try_into(
+            crate::crosvm::cmdline::RunCommand::from_args(
+                &[],
+                &["--mem", "1024", "--numa", "cpus=[0],size=512", "/dev/null"],
+            )
+            .unwrap(),
+        )
+        .expect_err("config should have been rejected");
+
+        // vCPU 1 isn't in any node.
+        TryInto::<Config>::try_into(
+            crate::crosvm::cmdline::RunCommand::from_args(
+                &[],
+                &["--cpus", "2", "--numa", "cpus=[0],size=512", "/dev/null"],
+            )
+            .unwrap(),
+        )
+        .expect_err("config should have been rejected");
+
+        // Invalid distance to itself.
+        TryInto::<Config>::try_into(
+            crate::crosvm::cmdline::RunCommand::from_args(
+                &[],
+                &[
+                    "--cpus",
+                    "2",
+                    "--numa",
+                    "cpus=[0],size=512,distances=[20,20]",
+                    "--numa",
+                    "cpus=[1],size=512",
+                    "/dev/null",
+                ],
+            )
+            .unwrap(),
+        )
+        .expect_err("config should have been rejected");
+    }
+
+    #[cfg(all(unix, not(target_arch = "riscv64")))]
+    #[test]
+    fn boot_cores() {
+        let cfg: Config = crate::crosvm::cmdline::RunCommand::from_args(
+            &[],
+            &["--cpus", "num-cores=4,boot-cores=2", "/dev/null"],
+        )
+        .unwrap()
+        .try_into()
+        .unwrap();
+        assert_eq!(cfg.vcpu_count, Some(4));
+        assert_eq!(cfg.boot_vcpu_count, Some(2));
+
+        // More boot vCPUs than vCPUs.
+        TryInto::<Config>::try_into(
+            crate::crosvm::cmdline::RunCommand::from_args(
+                &[],
+                &["--cpus", "num-cores=2,boot-cores=3", "/dev/null"],
+            )
+            .unwrap(),
+        )
+        .expect_err("config should have been rejected");
+
+        // No boot vCPU.
+        TryInto::<Config>::try_into(
+            crate::crosvm::cmdline::RunCommand::from_args(
+                &[],
+                &["--cpus", "num-cores=2,boot-cores=0", "/dev/null"],
+            )
+            .unwrap(),
+        )
+        .expect_err("config should have been rejected");
+
+        // APIC IDs past 254.
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        TryInto::<Config>::try_into(
+            crate::crosvm::cmdline::RunCommand::from_args(
+                &[],
+                &["--cpus", "num-cores=256,boot-cores=1", "/dev/null"],
+            )
+            .unwrap(),
+        )
+        .expect_err("config should have been rejected");
+    }
+
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    #[test]
+    fn pci_hotplug_slots() {
+        let cfg: Config = crate::crosvm::cmdline::RunCommand::from_args(&[], &["/dev/null"])
+            .unwrap()
+            .try_into()
+            .unwrap();
+        assert_eq!(cfg.pci_hotplug_slots, 1);
+
+        let cfg: Config = crate::crosvm::cmdline::RunCommand::from_args(
+            &[],
+            &["--pci-hotplug-slots", "4", "/dev/null"],
+        )
+        .unwrap()
+        .try_into()
+        .unwrap();
+        assert_eq!(cfg.pci_hotplug_slots, 4);
+
+        TryInto::<Config>::try_into(
+            crate::crosvm::cmdline::RunCommand::from_args(
+                &[],
+                &["--pci-hotplug-slots", "0", "/dev/null"],
+            )
+            .unwrap(),
+        )
+        .expect_err("config should have been rejected");
+    }
+
+    #[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), unix))]
+    #[test]
+    fn parse_thermal() {
+        let cfg: Config = crate::crosvm::cmdline::RunCommand::from_args(
+            &[],
+            &[
+                "--thermal",
+                "temperature=50,critical=100,frequencies=[3000,2000]",
+                "/dev/null",
+            ],
+        )
+        .unwrap()
+        .try_into()
+        .unwrap();
+        assert_eq!(
+            cfg.thermal,
+            Some(ThermalConfig {
+                temperature: 50,
+                passive: 90,
+                critical: 100,
+                frequencies: vec![3000, 2000],
+            })
+        );
+    }
+
     #[test]
     fn parse_cpu_set_single() {
         assert_eq!(
@@ -2339,6 +2689,42 @@ mod tests {
         assert_eq!(shared_dir.ugid, (None, None));
     }
 
+    #[cfg(unix)]
+    #[test]
+    fn parse_shared_dir_guest_id_map() {
+        let shared_dir: SharedDir =
+            "/:tag:type=fs:guest_uid_map=1000 0 1:guest_gid_map=1000 0 1,2000 100 10"
+                .parse()
+                .unwrap();
+        assert_eq!(shared_dir.fs_cfg.uid_map.to_host(1000), 0);
+        assert_eq!(shared_dir.fs_cfg.uid_map.to_guest(0), 1000);
+        assert_eq!(shared_dir.fs_cfg.gid_map.to_host(2005), 105);
+        assert_eq!(shared_dir.fs_cfg.gid_map.to_guest(109), 2009);
+
+        assert!("/:tag:type=fs:guest_uid_map=1000 0"
+            .parse::<SharedDir>()
+            .is_err());
+    }
+
+    #[test]
+    fn parse_shared_dir_access_rules() {
+        let shared_dir: SharedDir =
+            "/:tag:type=fs:read_only=true:allow=a/b:allow=c:deny=a/b/secret"
+                .parse()
+                .unwrap();
+        assert!(shared_dir.fs_cfg.read_only);
+        assert_eq!(
+            shared_dir.fs_cfg.allow_paths,
+            vec![PathBuf::from("a/b"), PathBuf::from("c")]
+        );
+        assert_eq!(
+            shared_dir.fs_cfg.deny_paths,
+            vec![PathBuf::from("a/b/secret")]
+        );
+
+        assert!("/:tag:type=fs:read_only=yes".parse::<SharedDir>().is_err());
+    }
+
     #[cfg(unix)]
     #[test]
     fn parse_shared_dir_oem() {
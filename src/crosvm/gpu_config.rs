@@ -57,6 +57,15 @@ pub(crate) fn fixup_gpu_options(
         ));
     }
 
+    #[cfg(windows)]
+    if gpu_params.vnc_port.is_some() {
+        return Err("'vnc-port' is not supported on Windows".to_string());
+    }
+    #[cfg(windows)]
+    if gpu_params.headless_socket.is_some() {
+        return Err("'headless-socket' is not supported on Windows".to_string());
+    }
+
     Ok(FixedGpuParameters(gpu_params))
 }
 
@@ -322,6 +331,23 @@ mod tests {
         assert_eq!(gpu_params.pci_bar_size, 0x100000);
     }
 
+    #[cfg(unix)]
+    #[test]
+    fn parse_gpu_options_vnc_port() {
+        assert_eq!(parse_gpu_options("").unwrap().vnc_port, None);
+        let gpu_params = parse_gpu_options("vnc-port=5900").unwrap();
+        assert_eq!(gpu_params.vnc_port, Some(5900));
+        assert!(parse_gpu_options("vnc-port=65536").is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn parse_gpu_options_headless_socket() {
+        assert_eq!(parse_gpu_options("").unwrap().headless_socket, None);
+        let gpu_params = parse_gpu_options("headless-socket=/run/display.sock").unwrap();
+        assert_eq!(gpu_params.headless_socket, Some("/run/display.sock".into()));
+    }
+
     #[test]
     fn parse_gpu_options_no_display_specified() {
         let display_params = parse_gpu_options("").unwrap().display_params;
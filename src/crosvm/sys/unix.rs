@@ -30,6 +30,8 @@ use std::ops::RangeInclusive;
 use std::os::unix::prelude::OpenOptionsExt;
 use std::os::unix::process::ExitStatusExt;
 use std::path::Path;
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+use std::path::PathBuf;
 use std::process;
 use std::rc::Rc;
 use std::sync::mpsc;
@@ -68,6 +70,8 @@ use devices::vfio::VfioCommonSetup;
 use devices::vfio::VfioCommonTrait;
 #[cfg(feature = "gpu")]
 use devices::virtio;
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+use devices::virtio::block::block::DiskOption;
 use devices::virtio::device_constants::video::VideoDeviceType;
 #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
 use devices::virtio::memory_mapper::MemoryMapper;
@@ -80,6 +84,9 @@ use devices::virtio::BalloonFeatures;
 use devices::virtio::BalloonMode;
 #[cfg(feature = "gpu")]
 use devices::virtio::EventDevice;
+use devices::virtio::NetParameters;
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+use devices::virtio::NetParametersMode;
 use devices::virtio::VirtioTransportType;
 #[cfg(feature = "audio")]
 use devices::Ac97Dev;
@@ -175,6 +182,8 @@ use x86_64::msr::get_override_msr_list;
 #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
 use x86_64::X8664arch as Arch;
 
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+use crate::crosvm::config::from_key_values;
 use crate::crosvm::config::Config;
 use crate::crosvm::config::Executable;
 use crate::crosvm::config::FileBackedMappingParameters;
@@ -184,6 +193,12 @@ use crate::crosvm::config::HypervisorKind;
 use crate::crosvm::config::IrqChipKind;
 use crate::crosvm::config::SharedDir;
 use crate::crosvm::config::SharedDirKind;
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+use crate::crosvm::config::TouchDeviceOption;
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+use crate::crosvm::config::VhostUserFsOption;
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+use crate::crosvm::config::VhostUserOption;
 #[cfg(feature = "gdb")]
 use crate::crosvm::gdb::gdb_thread;
 #[cfg(feature = "gdb")]
@@ -210,6 +225,7 @@ fn create_virtio_devices(
     disk_device_tubes: &mut Vec<Tube>,
     pmem_device_tubes: &mut Vec<Tube>,
     fs_device_tubes: &mut Vec<Tube>,
+    #[cfg_attr(not(feature = "audio"), allow(unused_variables))] snd_device_tubes: &mut Vec<Tube>,
     #[cfg(feature = "gpu")] gpu_control_tube: Tube,
     #[cfg(feature = "gpu")] render_server_fd: Option<SafeDescriptor>,
     vvu_proxy_device_tubes: &mut Vec<Tube>,
@@ -502,31 +518,7 @@ fn create_virtio_devices(
     }
 
     for opt in &cfg.net {
-        let vq_pairs = opt.vq_pairs.unwrap_or(1);
-        let vcpu_count = cfg.vcpu_count.unwrap_or(1);
-        let multi_vq = vq_pairs > 1 && !opt.vhost_net;
-        let (tap, mac) = create_tap_for_net_device(&opt.mode, multi_vq)?;
-        let dev = if opt.vhost_net {
-            create_virtio_vhost_net_device_from_tap(
-                cfg.protection_type,
-                &cfg.jail_config,
-                vq_pairs,
-                vcpu_count,
-                cfg.vhost_net_device_path.clone(),
-                tap,
-                mac,
-            )
-        } else {
-            create_virtio_net_device_from_tap(
-                cfg.protection_type,
-                &cfg.jail_config,
-                vq_pairs,
-                vcpu_count,
-                tap,
-                mac,
-            )
-        }?;
-        devs.push(dev);
+        devs.push(create_net_device_from_params(cfg, opt)?);
     }
 
     for net in &cfg.vhost_user_net {
@@ -548,6 +540,7 @@ fn create_virtio_devices(
                 cfg.protection_type,
                 &cfg.jail_config,
                 virtio_snd.clone(),
+                snd_device_tubes.remove(0),
             )?);
         }
     }
@@ -608,44 +601,12 @@ fn create_virtio_devices(
     }
 
     for shared_dir in &cfg.shared_dirs {
-        let SharedDir {
-            src,
-            tag,
-            kind,
-            ugid,
-            uid_map,
-            gid_map,
-            fs_cfg,
-            p9_cfg,
-        } = shared_dir;
-
-        let dev = match kind {
-            SharedDirKind::FS => {
-                let device_tube = fs_device_tubes.remove(0);
-                create_fs_device(
-                    cfg.protection_type,
-                    &cfg.jail_config,
-                    *ugid,
-                    uid_map,
-                    gid_map,
-                    src,
-                    tag,
-                    fs_cfg.clone(),
-                    device_tube,
-                )?
-            }
-            SharedDirKind::P9 => create_9p_device(
-                cfg.protection_type,
-                &cfg.jail_config,
-                *ugid,
-                uid_map,
-                gid_map,
-                src,
-                tag,
-                p9_cfg.clone(),
-            )?,
+        let fs_device_tube = if shared_dir.kind == SharedDirKind::FS {
+            Some(fs_device_tubes.remove(0))
+        } else {
+            None
         };
-        devs.push(dev);
+        devs.push(create_shared_dir_device(cfg, shared_dir, fs_device_tube)?);
     }
 
     if let Some(vhost_user_mac80211_hwsim) = &cfg.vhost_user_mac80211_hwsim {
@@ -667,6 +628,79 @@ fn create_virtio_devices(
     Ok(devs)
 }
 
+fn create_net_device_from_params(cfg: &Config, opt: &NetParameters) -> DeviceResult {
+    let vq_pairs = opt.vq_pairs.unwrap_or(1);
+    let vcpu_count = cfg.vcpu_count.unwrap_or(1);
+    let multi_vq = vq_pairs > 1 && !opt.vhost_net;
+    let (tap, mac) = create_tap_for_net_device(&opt.mode, multi_vq)?;
+    if opt.vhost_net {
+        create_virtio_vhost_net_device_from_tap(
+            cfg.protection_type,
+            &cfg.jail_config,
+            vq_pairs,
+            vcpu_count,
+            cfg.vhost_net_device_path.clone(),
+            tap,
+            mac,
+        )
+    } else {
+        create_virtio_net_device_from_tap(
+            cfg.protection_type,
+            &cfg.jail_config,
+            vq_pairs,
+            vcpu_count,
+            tap,
+            mac,
+        )
+    }
+}
+
+/// Creates the virtio-fs or virtio-9p device of `shared_dir`. `fs_device_tube` is required for
+/// virtio-fs.
+fn create_shared_dir_device(
+    cfg: &Config,
+    shared_dir: &SharedDir,
+    fs_device_tube: Option<Tube>,
+) -> DeviceResult {
+    let SharedDir {
+        src,
+        tag,
+        kind,
+        ugid,
+        uid_map,
+        gid_map,
+        fs_cfg,
+        p9_cfg,
+    } = shared_dir;
+
+    match kind {
+        SharedDirKind::FS => {
+            let device_tube = fs_device_tube.context("missing control tube for virtio-fs")?;
+            create_fs_device(
+                cfg.protection_type,
+                &cfg.jail_config,
+                *ugid,
+                uid_map,
+                gid_map,
+                src,
+                tag,
+                fs_cfg.clone(),
+                device_tube,
+            )
+        }
+        SharedDirKind::P9 => create_9p_device(
+            cfg.protection_type,
+            &cfg.jail_config,
+            *ugid,
+            uid_map,
+            gid_map,
+            src,
+            tag,
+            p9_cfg.clone(),
+        ),
+    }
+}
+
 fn create_devices(
     cfg: &Config,
     vm: &mut impl Vm,
@@ -680,6 +714,7 @@ fn create_devices(
     disk_device_tubes: &mut Vec<Tube>,
     pmem_device_tubes: &mut Vec<Tube>,
     fs_device_tubes: &mut Vec<Tube>,
+    snd_device_tubes: &mut Vec<Tube>,
     #[cfg(feature = "usb")] usb_provider: HostBackendDeviceProvider,
     #[cfg(feature = "gpu")] gpu_control_tube: Tube,
     #[cfg(feature = "gpu")] render_server_fd: Option<SafeDescriptor>,
@@ -811,6 +846,7 @@ fn create_devices(
         disk_device_tubes,
         pmem_device_tubes,
         fs_device_tubes,
+        snd_device_tubes,
         #[cfg(feature = "gpu")]
         gpu_control_tube,
         #[cfg(feature = "gpu")]
@@ -884,6 +920,17 @@ fn create_devices(
         ));
     }
 
+    for vfio_user in &cfg.vfio_user {
+        let (dev, jail) = create_vfio_user_device(
+            &cfg.jail_config,
+            vm,
+            irq_control_tubes,
+            &vfio_user.path,
+            vfio_user.guest_address,
+        )?;
+        devices.push((Box::new(dev), jail));
+    }
+
     for params in &cfg.stub_pci_devices {
         // Stub devices don't need jailing since they don't do anything.
         devices.push((Box::new(StubPciDevice::new(params)), None));
@@ -955,6 +1002,7 @@ fn create_file_backed_mappings(
 #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
 fn create_pcie_root_port(
     host_pcie_rp: Vec<HostPcieRootPortParameters>,
+    hotplug_slots: u8,
     sys_allocator: &mut SystemAllocator,
     irq_control_tubes: &mut Vec<Tube>,
     control_tubes: &mut Vec<TaggedControlTube>,
@@ -968,14 +1016,14 @@ fn create_pcie_root_port(
 ) -> Result<()> {
     if host_pcie_rp.is_empty() {
         // user doesn't specify host pcie root port which link to this virtual pcie rp,
-        // find the empty bus and create a total virtual pcie rp
-        let mut hp_sec_bus = 0u8;
+        // find the empty buses and create total virtual pcie rps
+        let mut hp_sec_buses = Vec::new();
         // Create Pcie Root Port for non-root buses, each non-root bus device will be
         // connected behind a virtual pcie root port.
         for i in 1..255 {
             if sys_allocator.pci_bus_empty(i) {
-                if hp_sec_bus == 0 {
-                    hp_sec_bus = i;
+                if hp_sec_buses.len() < hotplug_slots as usize {
+                    hp_sec_buses.push(i);
                 }
                 continue;
             }
@@ -988,36 +1036,38 @@ fn create_pcie_root_port(
             devices.push((pci_bridge, None));
         }
 
-        // Create Pcie Root Port for hot-plug
-        if hp_sec_bus == 0 {
+        // Create Pcie Root Ports for hot-plug
+        if hp_sec_buses.len() < hotplug_slots as usize {
             return Err(anyhow!("no more addresses are available"));
         }
-        let pcie_root_port = Arc::new(Mutex::new(PcieRootPort::new(hp_sec_bus, true)));
-        pme_notify_devs.push((
-            hp_sec_bus,
-            pcie_root_port.clone() as Arc<Mutex<dyn PmeNotify>>,
-        ));
-        let (msi_host_tube, msi_device_tube) = Tube::pair().context("failed to create tube")?;
-        irq_control_tubes.push(msi_host_tube);
-        let pci_bridge = Box::new(PciBridge::new(pcie_root_port.clone(), msi_device_tube));
+        for hp_sec_bus in hp_sec_buses {
+            let pcie_root_port = Arc::new(Mutex::new(PcieRootPort::new(hp_sec_bus, true)));
+            pme_notify_devs.push((
+                hp_sec_bus,
+                pcie_root_port.clone() as Arc<Mutex<dyn PmeNotify>>,
+            ));
+            let (msi_host_tube, msi_device_tube) = Tube::pair().context("failed to create tube")?;
+            irq_control_tubes.push(msi_host_tube);
+            let pci_bridge = Box::new(PciBridge::new(pcie_root_port.clone(), msi_device_tube));
 
-        hp_endpoints_ranges.push(RangeInclusive::new(
-            PciAddress {
-                bus: pci_bridge.get_secondary_num(),
-                dev: 0,
-                func: 0,
-            }
-            .to_u32(),
-            PciAddress {
-                bus: pci_bridge.get_subordinate_num(),
-                dev: 32,
-                func: 8,
-            }
-            .to_u32(),
-        ));
+            hp_endpoints_ranges.push(RangeInclusive::new(
+                PciAddress {
+                    bus: pci_bridge.get_secondary_num(),
+                    dev: 0,
+                    func: 0,
+                }
+                .to_u32(),
+                PciAddress {
+                    bus: pci_bridge.get_subordinate_num(),
+                    dev: 32,
+                    func: 8,
+                }
+                .to_u32(),
+            ));
 
-        devices.push((pci_bridge, None));
-        hp_vec.push((hp_sec_bus, pcie_root_port as Arc<Mutex<dyn HotPlugBus>>));
+            devices.push((pci_bridge, None));
+            hp_vec.push((hp_sec_bus, pcie_root_port as Arc<Mutex<dyn HotPlugBus>>));
+        }
     } else {
         // user specify host pcie root port which link to this virtual pcie rp,
         // reserve the host pci BDF and create a virtual pcie RP with some attrs same as host
@@ -1168,6 +1218,16 @@ fn setup_vm_components(cfg: &Config) -> Result<VmComponents> {
         #[cfg(feature = "direct")]
         direct_fixed_evts: cfg.direct_fixed_evts.clone(),
         no_smt: cfg.no_smt,
+        numa_nodes: cfg
+            .numa
+            .iter()
+            .map(|node| arch::NumaNode {
+                cpus: node.cpus.clone(),
+                memory_size: node.size * 1024 * 1024,
+                host_node: node.host_node,
+                distances: node.distances.clone(),
+            })
+            .collect(),
         hugepages: cfg.hugepages,
         hv_cfg: hypervisor::Config {
             #[cfg(target_arch = "aarch64")]
@@ -1183,6 +1243,7 @@ fn setup_vm_components(cfg: &Config) -> Result<VmComponents> {
                     .with_context(|| format!("failed to open android fstab file {}", x.display()))
             })
             .map_or(Ok(None), |v| v.map(Some))?,
+        boot_vcpu_count: cfg.boot_vcpu_count,
         pstore: cfg.pstore.clone(),
         pflash_block_size,
         pflash_image,
@@ -1205,6 +1266,8 @@ fn setup_vm_components(cfg: &Config) -> Result<VmComponents> {
         no_rtc: cfg.no_rtc,
         #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
         oem_strings: cfg.oem_strings.clone(),
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        smbios: cfg.smbios.clone(),
         host_cpu_topology: cfg.host_cpu_topology,
         itmt: cfg.itmt,
         #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
@@ -1214,6 +1277,8 @@ fn setup_vm_components(cfg: &Config) -> Result<VmComponents> {
         pcie_ecam: cfg.pcie_ecam,
         #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
         pci_low_start: cfg.pci_low_start,
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        thermal: cfg.thermal.clone(),
         dynamic_power_coefficient: cfg.dynamic_power_coefficient.clone(),
     })
 }
@@ -1655,6 +1720,19 @@ where
         disk_device_tubes.push(disk_device_tube);
     }
 
+    // Create one control socket per sound device.
+    let mut snd_device_tubes = Vec::new();
+    let mut snd_host_tubes = Vec::new();
+    #[cfg(feature = "audio")]
+    let snd_count = cfg.virtio_snds.len();
+    #[cfg(not(feature = "audio"))]
+    let snd_count = 0;
+    for _ in 0..snd_count {
+        let (snd_host_tube, snd_device_tube) = Tube::pair().context("failed to create tube")?;
+        snd_host_tubes.push(snd_host_tube);
+        snd_device_tubes.push(snd_device_tube);
+    }
+
     let mut pmem_device_tubes = Vec::new();
     let pmem_count = cfg.pmem_devices.len();
     for _ in 0..pmem_count {
@@ -1850,6 +1928,7 @@ where
         &mut disk_device_tubes,
         &mut pmem_device_tubes,
         &mut fs_device_tubes,
+        &mut snd_device_tubes,
         #[cfg(feature = "usb")]
         usb_provider,
         #[cfg(feature = "gpu")]
@@ -1882,6 +1961,7 @@ where
         // Create Pcie Root Port
         create_pcie_root_port(
             rp_host,
+            cfg.pci_hotplug_slots,
             &mut sys_allocator,
             &mut irq_control_tubes,
             &mut control_tubes,
@@ -2058,6 +2138,7 @@ where
         #[cfg(feature = "balloon")]
         balloon_host_tube,
         &disk_host_tubes,
+        &snd_host_tubes,
         #[cfg(feature = "gpu")]
         gpu_control_host_tube,
         #[cfg(feature = "usb")]
@@ -2191,6 +2272,7 @@ fn add_hotplug_device<V: VmArch, Vcpu: VcpuArch>(
 
             (host_key, pci_address)
         }
+        HotPlugDeviceType::Virtio { .. } => bail!("virtio devices aren't host devices"),
         HotPlugDeviceType::EndPoint => {
             let host_key = HostHotPlugKey::Vfio { host_addr };
             let (vfio_device, jail, viommu_mapper) = create_vfio_device(
@@ -2303,6 +2385,7 @@ fn remove_hotplug_device<V: VmArch, Vcpu: VcpuArch>(
         HotPlugDeviceType::UpstreamPort => HostHotPlugKey::UpstreamPort { host_addr },
         HotPlugDeviceType::DownstreamPort => HostHotPlugKey::DownstreamPort { host_addr },
         HotPlugDeviceType::EndPoint => HostHotPlugKey::Vfio { host_addr },
+        HotPlugDeviceType::Virtio { .. } => bail!("virtio devices aren't host devices"),
     };
 
     let hp_bus = linux
@@ -2457,10 +2540,29 @@ fn handle_hotplug_command<V: VmArch, Vcpu: VcpuArch>(
     add_tubes: &mut Vec<TaggedControlTube>,
     hp_control_tube: &mpsc::Sender<PciRootCommand>,
     iommu_host_tube: &Option<Tube>,
+    hotplugged_virtio_devices: &mut HotPluggedVirtioDevices,
     device: &HotPlugDeviceInfo,
     add: bool,
     #[cfg(feature = "swap")] swap_controller: Option<&SwapController>,
 ) -> VmResponse {
+    if let HotPlugDeviceType::Virtio { kind, params } = &device.device_type {
+        return handle_virtio_hotplug_command(
+            linux,
+            sys_allocator,
+            cfg,
+            add_irq_control_tubes,
+            add_tubes,
+            hp_control_tube,
+            hotplugged_virtio_devices,
+            &device.path,
+            *kind,
+            params,
+            add,
+            #[cfg(feature = "swap")]
+            swap_controller,
+        );
+    }
+
     let iommu_host_tube = if cfg.vfio_isolate_hotplug {
         iommu_host_tube
     } else {
@@ -2494,6 +2596,518 @@ fn handle_hotplug_command<V: VmArch, Vcpu: VcpuArch>(
     }
 }
 
+/// Virtio-input devices hotplugged at runtime, keyed by the socket feeding them events.
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[derive(Default)]
+struct HotPluggedInputs {
+    devices: BTreeMap<PathBuf, PciAddress>,
+    // Monotonic counter so that hotplugged devices never reuse a name.
+    next_idx: u32,
+}
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+impl HotPluggedInputs {
+    /// Returns the index naming a new device fed from `socket_path`, unless one already is.
+    fn next_idx(&self, socket_path: &Path) -> base::Result<u32> {
+        if self.devices.contains_key(socket_path) {
+            error!(
+                "input device fed from {} is already plugged in",
+                socket_path.display()
+            );
+            return Err(base::Error::new(libc::EEXIST));
+        }
+        Ok(self.next_idx)
+    }
+
+    /// Records the device fed from `socket_path` plugged in at `pci_address`.
+    fn insert(&mut self, socket_path: PathBuf, pci_address: PciAddress) {
+        self.next_idx += 1;
+        self.devices.insert(socket_path, pci_address);
+    }
+
+    /// Forgets the device fed from `socket_path`, returning its address. Only the devices
+    /// hotplugged with `InputHotPlugCommand::Add` can be removed.
+    fn remove(&mut self, socket_path: &Path) -> base::Result<PciAddress> {
+        self.devices.remove(socket_path).ok_or_else(|| {
+            error!(
+                "no hotplugged input device fed from {}",
+                socket_path.display()
+            );
+            base::Error::new(libc::ENODEV)
+        })
+    }
+}
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+fn create_hotplug_input_device(
+    cfg: &Config,
+    kind: VirtioInputKind,
+    socket_path: &Path,
+    idx: u32,
+) -> DeviceResult {
+    let touch_spec = |width, height| {
+        let mut spec = TouchDeviceOption::new(socket_path.to_path_buf());
+        spec.set_width(width);
+        spec.set_height(height);
+        spec
+    };
+    match kind {
+        VirtioInputKind::Keyboard => {
+            create_keyboard_device(cfg.protection_type, &cfg.jail_config, socket_path, idx)
+        }
+        VirtioInputKind::Mouse => {
+            create_mouse_device(cfg.protection_type, &cfg.jail_config, socket_path, idx)
+        }
+        VirtioInputKind::Switches => {
+            create_switches_device(cfg.protection_type, &cfg.jail_config, socket_path, idx)
+        }
+        VirtioInputKind::Gamepad => {
+            create_gamepad_device(cfg.protection_type, &cfg.jail_config, socket_path, idx)
+        }
+        VirtioInputKind::SingleTouch { width, height } => create_single_touch_device(
+            cfg.protection_type,
+            &cfg.jail_config,
+            &touch_spec(width, height),
+            idx,
+        ),
+        VirtioInputKind::MultiTouch { width, height } => create_multi_touch_device(
+            cfg.protection_type,
+            &cfg.jail_config,
+            &touch_spec(width, height),
+            idx,
+        ),
+        VirtioInputKind::Trackpad { width, height } => create_trackpad_device(
+            cfg.protection_type,
+            &cfg.jail_config,
+            &touch_spec(width, height),
+            idx,
+        ),
+    }
+}
+
+/// Returns the secondary bus number and the hotplug bus of an empty root port that can take an
+/// emulated device.
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+fn find_free_virtio_hotplug_port(
+    hotplug_bus: &BTreeMap<u8, Arc<Mutex<dyn HotPlugBus>>>,
+) -> Result<(u8, Arc<Mutex<dyn HotPlugBus>>)> {
+    // Only virtual root ports accept emulated devices. Those match any host address, while ports
+    // linked to a host root port never match bus 0. A root port reports all of its downstream
+    // devices as removed on unplug, so each hotplugged device gets a port of its own.
+    hotplug_bus
+        .values()
+        .find_map(|hp_bus| {
+            let hp_bus_lock = hp_bus.lock();
+            match hp_bus_lock.is_match(PciAddress::default()) {
+                Some(secondary_bus) if hp_bus_lock.is_empty() => {
+                    Some((secondary_bus, hp_bus.clone()))
+                }
+                _ => None,
+            }
+        })
+        .context("no free hotplug root port for virtio device")
+}
+
+/// Returns the hotplug bus the emulated device at `guest_addr` was plugged in.
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+fn find_virtio_hotplug_port(
+    hotplug_bus: &BTreeMap<u8, Arc<Mutex<dyn HotPlugBus>>>,
+    guest_addr: PciAddress,
+) -> Result<Arc<Mutex<dyn HotPlugBus>>> {
+    let host_key = HostHotPlugKey::Virtio { guest_addr };
+    hotplug_bus
+        .values()
+        .find(|hp_bus| hp_bus.lock().get_hotplug_device(host_key).is_some())
+        .cloned()
+        .with_context(|| format!("Can not find device {:?} on hotplug buses", host_key))
+}
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+fn add_hotplug_virtio_device<V: VmArch, Vcpu: VcpuArch>(
+    linux: &mut RunnableLinuxVm<V, Vcpu>,
+    sys_allocator: &mut SystemAllocator,
+    cfg: &Config,
+    irq_control_tubes: &mut Vec<Tube>,
+    control_tubes: &mut Vec<TaggedControlTube>,
+    hp_control_tube: &mpsc::Sender<PciRootCommand>,
+    stub: VirtioDeviceStub,
+    #[cfg(feature = "swap")] swap_controller: Option<&SwapController>,
+) -> Result<PciAddress> {
+    let (secondary_bus, hp_bus) = find_free_virtio_hotplug_port(&linux.hotplug_bus)?;
+
+    let (msi_host_tube, msi_device_tube) = Tube::pair().context("failed to create tube")?;
+    irq_control_tubes.push(msi_host_tube);
+    let (ioevent_host_tube, ioevent_device_tube) =
+        Tube::pair().context("failed to create ioevent tube")?;
+    control_tubes.push(TaggedControlTube::VmMemory {
+        tube: ioevent_host_tube,
+        expose_with_viommu: false,
+    });
+    let mut dev = VirtioPciDevice::new(
+        linux.vm.get_memory().clone(),
+        stub.dev,
+        msi_device_tube,
+        cfg.disable_virtio_intx,
+        None,
+        ioevent_device_tube,
+    )
+    .context("failed to create virtio pci dev")?;
+    dev.set_preferred_address(PciAddress {
+        bus: secondary_bus,
+        dev: 0,
+        func: 0,
+    });
+
+    let pci_address = Arch::register_pci_device(
+        linux,
+        Box::new(dev),
+        stub.jail,
+        sys_allocator,
+        hp_control_tube,
+        #[cfg(feature = "swap")]
+        swap_controller,
+    )?;
+
+    let mut hp_bus_lock = hp_bus.lock();
+    hp_bus_lock.add_hotplug_device(
+        HostHotPlugKey::Virtio {
+            guest_addr: pci_address,
+        },
+        pci_address,
+    );
+    hp_bus_lock.hot_plug(pci_address);
+    Ok(pci_address)
+}
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+fn remove_hotplug_virtio_device<V: VmArch, Vcpu: VcpuArch>(
+    linux: &RunnableLinuxVm<V, Vcpu>,
+    sys_allocator: &mut SystemAllocator,
+    guest_addr: PciAddress,
+) -> Result<()> {
+    let hp_bus = find_virtio_hotplug_port(&linux.hotplug_bus, guest_addr)?;
+    hp_bus.lock().hot_unplug(guest_addr);
+    sys_allocator.release_pci(guest_addr.bus, guest_addr.dev, guest_addr.func);
+    Ok(())
+}
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+fn handle_input_hotplug_command<V: VmArch, Vcpu: VcpuArch>(
+    linux: &mut RunnableLinuxVm<V, Vcpu>,
+    sys_allocator: &mut SystemAllocator,
+    cfg: &Config,
+    add_irq_control_tubes: &mut Vec<Tube>,
+    add_tubes: &mut Vec<TaggedControlTube>,
+    hp_control_tube: &mpsc::Sender<PciRootCommand>,
+    hotplugged_inputs: &mut HotPluggedInputs,
+    command: InputHotPlugCommand,
+    #[cfg(feature = "swap")] swap_controller: Option<&SwapController>,
+) -> VmResponse {
+    let ret = match command {
+        InputHotPlugCommand::Add { kind, socket_path } => {
+            let idx = match hotplugged_inputs.next_idx(&socket_path) {
+                Ok(idx) => idx,
+                Err(e) => return VmResponse::Err(e),
+            };
+            create_hotplug_input_device(cfg, kind, &socket_path, idx)
+                .and_then(|stub| {
+                    add_hotplug_virtio_device(
+                        linux,
+                        sys_allocator,
+                        cfg,
+                        add_irq_control_tubes,
+                        add_tubes,
+                        hp_control_tube,
+                        stub,
+                        #[cfg(feature = "swap")]
+                        swap_controller,
+                    )
+                })
+                .map(|pci_address| {
+                    info!(
+                        "hotplugged {:?} input device fed from {} at {}",
+                        kind,
+                        socket_path.display(),
+                        pci_address
+                    );
+                    hotplugged_inputs.insert(socket_path, pci_address);
+                })
+        }
+        InputHotPlugCommand::Remove { socket_path } => {
+            match hotplugged_inputs.remove(&socket_path) {
+                Ok(pci_address) => remove_hotplug_virtio_device(linux, sys_allocator, pci_address),
+                Err(e) => return VmResponse::Err(e),
+            }
+        }
+    };
+
+    match ret {
+        Ok(()) => VmResponse::Ok,
+        Err(e) => {
+            error!("handle_input_hotplug_command failure: {:#}", e);
+            add_tubes.clear();
+            add_irq_control_tubes.clear();
+            VmResponse::Err(base::Error::new(libc::EINVAL))
+        }
+    }
+}
+
+/// Virtio-pci devices hotplugged with `HotPlugDeviceType::Virtio`, keyed by the id given when
+/// adding them.
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[derive(Default)]
+struct HotPluggedVirtioDevices {
+    devices: BTreeMap<PathBuf, (VirtioHotPlugKind, PciAddress)>,
+}
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+impl HotPluggedVirtioDevices {
+    /// Fails if a device named `id` is already plugged in.
+    fn check_id(&self, id: &Path) -> base::Result<()> {
+        if self.devices.contains_key(id) {
+            error!("virtio device {} is already plugged in", id.display());
+            return Err(base::Error::new(libc::EEXIST));
+        }
+        Ok(())
+    }
+
+    /// Records the `kind` device named `id` plugged in at `pci_address`.
+    fn insert(&mut self, id: PathBuf, kind: VirtioHotPlugKind, pci_address: PciAddress) {
+        self.devices.insert(id, (kind, pci_address));
+    }
+
+    /// Forgets the `kind` device named `id`, returning its address. Only the devices hotplugged
+    /// with `HotPlugDeviceType::Virtio` can be removed.
+    fn remove(&mut self, id: &Path, kind: VirtioHotPlugKind) -> base::Result<PciAddress> {
+        match self.devices.get(id) {
+            Some(&(device_kind, pci_address)) if device_kind == kind => {
+                self.devices.remove(id);
+                Ok(pci_address)
+            }
+            _ => {
+                error!("no hotplugged {:?} device {}", kind, id.display());
+                Err(base::Error::new(libc::ENODEV))
+            }
+        }
+    }
+}
+
+/// Creates the device of a `HotPlugDeviceType::Virtio` being added. `params` is parsed like the
+/// argument of the matching `crosvm run` option, and the control tubes of the device are pushed
+/// to `control_tubes`.
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+fn create_hotplug_virtio_device(
+    cfg: &Config,
+    kind: VirtioHotPlugKind,
+    params: &str,
+    control_tubes: &mut Vec<TaggedControlTube>,
+) -> DeviceResult {
+    match kind {
+        VirtioHotPlugKind::Block => {
+            let disk: DiskOption =
+                from_key_values(params).map_err(|e| anyhow!("invalid block parameters: {}", e))?;
+            // No control tube: disks added at runtime can't be resized.
+            DiskConfig::new(&disk, None)
+                .create_virtio_device_and_jail(cfg.protection_type, &cfg.jail_config)
+        }
+        VirtioHotPlugKind::Net => {
+            let opt: NetParameters = params
+                .parse()
+                .map_err(|e| anyhow!("invalid net parameters: {}", e))?;
+            if let NetParametersMode::TapFd { .. } = opt.mode {
+                bail!("`tap-fd` can't be used to hotplug a net device");
+            }
+            create_net_device_from_params(cfg, &opt)
+        }
+        VirtioHotPlugKind::SharedDir => {
+            let shared_dir: SharedDir = params
+                .parse()
+                .map_err(|e| anyhow!("invalid shared-dir parameters: {}", e))?;
+            let fs_device_tube = if shared_dir.kind == SharedDirKind::FS {
+                let (fs_host_tube, fs_device_tube) =
+                    Tube::pair().context("failed to create tube")?;
+                control_tubes.push(TaggedControlTube::Fs(fs_host_tube));
+                Some(fs_device_tube)
+            } else {
+                None
+            };
+            create_shared_dir_device(cfg, &shared_dir, fs_device_tube)
+        }
+        VirtioHotPlugKind::VhostUserBlk => {
+            let opt: VhostUserOption = params
+                .parse()
+                .map_err(|e| anyhow!("invalid vhost-user-blk parameters: {}", e))?;
+            create_vhost_user_block_device(cfg.protection_type, &opt)
+        }
+        VirtioHotPlugKind::VhostUserFs => {
+            let opt: VhostUserFsOption = params
+                .parse()
+                .map_err(|e| anyhow!("invalid vhost-user-fs parameters: {}", e))?;
+            create_vhost_user_fs_device(cfg.protection_type, &opt)
+        }
+        VirtioHotPlugKind::VhostUserNet => {
+            let opt: VhostUserOption = params
+                .parse()
+                .map_err(|e| anyhow!("invalid vhost-user-net parameters: {}", e))?;
+            create_vhost_user_net_device(cfg.protection_type, &opt)
+        }
+    }
+}
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+fn handle_virtio_hotplug_command<V: VmArch, Vcpu: VcpuArch>(
+    linux: &mut RunnableLinuxVm<V, Vcpu>,
+    sys_allocator: &mut SystemAllocator,
+    cfg: &Config,
+    add_irq_control_tubes: &mut Vec<Tube>,
+    add_tubes: &mut Vec<TaggedControlTube>,
+    hp_control_tube: &mpsc::Sender<PciRootCommand>,
+    hotplugged_devices: &mut HotPluggedVirtioDevices,
+    id: &Path,
+    kind: VirtioHotPlugKind,
+    params: &str,
+    add: bool,
+    #[cfg(feature = "swap")] swap_controller: Option<&SwapController>,
+) -> VmResponse {
+    let ret = if add {
+        if let Err(e) = hotplugged_devices.check_id(id) {
+            return VmResponse::Err(e);
+        }
+        create_hotplug_virtio_device(cfg, kind, params, add_tubes)
+            .and_then(|stub| {
+                add_hotplug_virtio_device(
+                    linux,
+                    sys_allocator,
+                    cfg,
+                    add_irq_control_tubes,
+                    add_tubes,
+                    hp_control_tube,
+                    stub,
+                    #[cfg(feature = "swap")]
+                    swap_controller,
+                )
+            })
+            .map(|pci_address| {
+                info!(
+                    "hotplugged {:?} device {} at {}",
+                    kind,
+                    id.display(),
+                    pci_address
+                );
+                hotplugged_devices.insert(id.to_path_buf(), kind, pci_address);
+            })
+    } else {
+        match hotplugged_devices.remove(id, kind) {
+            Ok(pci_address) => remove_hotplug_virtio_device(linux, sys_allocator, pci_address),
+            Err(e) => return VmResponse::Err(e),
+        }
+    };
+
+    match ret {
+        Ok(()) => VmResponse::Ok,
+        Err(e) => {
+            error!("handle_virtio_hotplug_command failure: {:#}", e);
+            add_tubes.clear();
+            add_irq_control_tubes.clear();
+            VmResponse::Err(base::Error::new(libc::EINVAL))
+        }
+    }
+}
+
+/// Plugs or unplugs vCPU `cpu_id` and notifies the guest through the GPE of the vCPU hotplug
+/// controller, if any. Without ACPI (aarch64), this gates whether the guest can power on the vCPU
+/// with PSCI CPU_ON, and only the vCPUs powered off with CPU_OFF can be unplugged.
+fn handle_vcpu_hotplug_command<V: VmArch, Vcpu: VcpuArch>(
+    linux: &RunnableLinuxVm<V, Vcpu>,
+    #[cfg(any(target_arch = "arm", target_arch = "aarch64"))] vcpu_power_control: Option<
+        &vcpu::VcpuPowerControl,
+    >,
+    cpu_id: usize,
+    add: bool,
+) -> VmResponse {
+    let hotplug = match &linux.vcpu_hotplug {
+        Some(hotplug) => hotplug,
+        None => {
+            error!("vcpu hotplug is not enabled, use `--cpus boot-cores=N`");
+            return VmResponse::Err(base::Error::new(libc::ENOTSUP));
+        }
+    };
+
+    let action = if add { "plug" } else { "unplug" };
+    let unplug = || hotplug.lock().unplug(cpu_id).map_err(anyhow::Error::from);
+    let res = if add {
+        hotplug.lock().plug(cpu_id).map_err(anyhow::Error::from)
+    } else {
+        // On ARM the guest powers the vCPUs on and off with PSCI and isn't told about removals,
+        // so only the vCPUs it powered off can be unplugged.
+        #[cfg(any(target_arch = "arm", target_arch = "aarch64"))]
+        {
+            vcpu_power_control.map_or_else(unplug, |power_control| power_control.unplug(cpu_id))
+        }
+        #[cfg(not(any(target_arch = "arm", target_arch = "aarch64")))]
+        {
+            unplug()
+        }
+    };
+    if let Err(e) = res {
+        error!("failed to {} vcpu {}: {:#}", action, cpu_id, e);
+        return VmResponse::ErrString(format!("{:#}", e));
+    }
+
+    let gpe = hotplug.lock().gpe();
+    if let (Some(gpe), Some(pm)) = (gpe, &linux.pm) {
+        pm.lock().gpe_evt(gpe);
+    }
+    if add {
+        info!("plugged vcpu {}", cpu_id);
+    } else {
+        info!("unplugged vcpu {}", cpu_id);
+    }
+    VmResponse::Ok
+}
+
+/// Changes the emulated thermal zone or processor frequency limit and notifies the guest through
+/// the GPE of the thermal controller.
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+fn handle_thermal_command<V: VmArch, Vcpu: VcpuArch>(
+    linux: &RunnableLinuxVm<V, Vcpu>,
+    command: ThermalCommand,
+) -> VmResponse {
+    let thermal = match &linux.thermal {
+        Some(thermal) => thermal,
+        None => {
+            error!("thermal emulation is not enabled, use `--thermal`");
+            return VmResponse::Err(base::Error::new(libc::ENOTSUP));
+        }
+    };
+
+    let gpe = {
+        let mut thermal = thermal.lock();
+        let res = match command {
+            ThermalCommand::SetTemperature(celsius) => thermal.set_temperature(celsius),
+            ThermalCommand::SetTripPoints { passive, critical } => {
+                thermal.set_trip_points(passive, critical)
+            }
+            ThermalCommand::SetFrequencyLimit(mhz) => thermal
+                .set_frequency_limit(mhz)
+                .map(|limit| info!("limited vcpu frequency to {} MHz", limit)),
+        };
+        if let Err(e) = res {
+            error!("failed to apply {:?}: {}", command, e);
+            return VmResponse::ErrString(e.to_string());
+        }
+        if !thermal.has_pending_events() {
+            return VmResponse::Ok;
+        }
+        thermal.gpe()
+    };
+
+    if let (Some(gpe), Some(pm)) = (gpe, &linux.pm) {
+        pm.lock().gpe_evt(gpe);
+    }
+    VmResponse::Ok
+}
+
 fn run_control<V: VmArch + 'static, Vcpu: VcpuArch + 'static>(
     mut linux: RunnableLinuxVm<V, Vcpu>,
     sys_allocator: SystemAllocator,
@@ -2503,6 +3117,7 @@ fn run_control<V: VmArch + 'static, Vcpu: VcpuArch + 'static>(
     mut control_tubes: Vec<TaggedControlTube>,
     #[cfg(feature = "balloon")] balloon_host_tube: Option<Tube>,
     disk_host_tubes: &[Tube],
+    snd_host_tubes: &[Tube],
     #[cfg(feature = "gpu")] gpu_control_tube: Tube,
     #[cfg(feature = "usb")] usb_control_tube: Tube,
     vm_evt_rdtube: RecvTube,
@@ -2734,6 +3349,14 @@ fn run_control<V: VmArch + 'static, Vcpu: VcpuArch + 'static>(
     // Architecture-specific code must supply a vcpu_init element for each VCPU.
     assert_eq!(vcpus.len(), linux.vcpu_init.len());
 
+    #[cfg(any(target_arch = "arm", target_arch = "aarch64"))]
+    let vcpu_power_control = linux.vcpu_hotplug.as_ref().map(|hotplug| {
+        Arc::new(vcpu::VcpuPowerControl::new(
+            hotplug.clone(),
+            linux.vcpu_count,
+        ))
+    });
+
     for ((cpu_id, vcpu), vcpu_init) in vcpus.into_iter().enumerate().zip(linux.vcpu_init.drain(..))
     {
         let (to_vcpu_channel, from_main_channel) = mpsc::channel();
@@ -2765,6 +3388,10 @@ fn run_control<V: VmArch + 'static, Vcpu: VcpuArch + 'static>(
 
         #[cfg(any(target_arch = "arm", target_arch = "aarch64"))]
         let cpu_config = None;
+        #[cfg(any(target_arch = "arm", target_arch = "aarch64"))]
+        if let Some(power_control) = &vcpu_power_control {
+            power_control.register_vcpu(cpu_id, to_vcpu_channel.clone());
+        }
 
         #[cfg(target_arch = "riscv64")]
         let cpu_config = Some(CpuConfigRiscv64::new(vcpu_init.fdt_address));
@@ -2809,6 +3436,8 @@ fn run_control<V: VmArch + 'static, Vcpu: VcpuArch + 'static>(
             #[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), unix))]
             bus_lock_ratelimit_ctrl,
             run_mode,
+            #[cfg(any(target_arch = "arm", target_arch = "aarch64"))]
+            vcpu_power_control.clone(),
         )?;
         vcpu_handles.push((handle, to_vcpu_channel));
     }
@@ -2876,6 +3505,10 @@ fn run_control<V: VmArch + 'static, Vcpu: VcpuArch + 'static>(
     #[cfg(feature = "balloon")]
     let mut balloon_wss_id: u64 = 0;
     let mut registered_evt_tubes: HashMap<RegisteredEvent, HashSet<AddressedTube>> = HashMap::new();
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    let mut hotplugged_inputs = HotPluggedInputs::default();
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    let mut hotplugged_virtio_devices = HotPluggedVirtioDevices::default();
     let mut region_state = VmMemoryRegionState::new();
 
     'wait: loop {
@@ -3043,6 +3676,7 @@ fn run_control<V: VmArch + 'static, Vcpu: VcpuArch + 'static>(
                                                     &mut add_tubes,
                                                     &hp_control_tube,
                                                     &iommu_host_tube,
+                                                    &mut hotplugged_virtio_devices,
                                                     &device,
                                                     add,
                                                     #[cfg(feature = "swap")]
@@ -3060,6 +3694,75 @@ fn run_control<V: VmArch + 'static, Vcpu: VcpuArch + 'static>(
                                                 VmResponse::Ok
                                             }
                                         }
+                                        VmRequest::InputHotPlugCommand(command) => {
+                                            #[cfg(any(
+                                                target_arch = "x86",
+                                                target_arch = "x86_64"
+                                            ))]
+                                            {
+                                                handle_input_hotplug_command(
+                                                    &mut linux,
+                                                    &mut sys_allocator_mutex.lock(),
+                                                    &cfg,
+                                                    &mut add_irq_control_tubes,
+                                                    &mut add_tubes,
+                                                    &hp_control_tube,
+                                                    &mut hotplugged_inputs,
+                                                    command,
+                                                    #[cfg(feature = "swap")]
+                                                    swap_controller.as_ref(),
+                                                )
+                                            }
+
+                                            #[cfg(not(any(
+                                                target_arch = "x86",
+                                                target_arch = "x86_64"
+                                            )))]
+                                            {
+                                                let _ = command;
+                                                VmResponse::Err(base::Error::new(libc::ENOTSUP))
+                                            }
+                                        }
+                                        VmRequest::VcpuHotPlugCommand { cpu_id, add } => {
+                                            handle_vcpu_hotplug_command(
+                                                &linux,
+                                                #[cfg(any(
+                                                    target_arch = "arm",
+                                                    target_arch = "aarch64"
+                                                ))]
+                                                vcpu_power_control.as_deref(),
+                                                cpu_id,
+                                                add,
+                                            )
+                                        }
+                                        VmRequest::ThermalCommand(command) => {
+                                            #[cfg(any(
+                                                target_arch = "x86",
+                                                target_arch = "x86_64"
+                                            ))]
+                                            {
+                                                handle_thermal_command(&linux, command)
+                                            }
+
+                                            #[cfg(not(any(
+                                                target_arch = "x86",
+                                                target_arch = "x86_64"
+                                            )))]
+                                            {
+                                                let _ = command;
+                                                VmResponse::Err(base::Error::new(libc::ENOTSUP))
+                                            }
+                                        }
+                                        VmRequest::PcieAerInject {
+                                            requester_id,
+                                            error,
+                                        } => match devices::inject_aer_error(requester_id, error) {
+                                            Ok(()) => VmResponse::Ok,
+                                            Err(e) => {
+                                                error!("failed to inject {:?} error: {}", error, e);
+                                                VmResponse::ErrString(e.to_string())
+                                            }
+                                        },
                                         VmRequest::RegisterListener { socket_addr, event } => {
                                             let (registered_tube, already_registered) =
                                                 find_registered_tube(
@@ -3115,6 +3818,7 @@ fn run_control<V: VmArch + 'static, Vcpu: VcpuArch + 'static>(
                                                 #[cfg(feature = "balloon")]
                                                 &mut balloon_wss_id,
                                                 disk_host_tubes,
+                                                snd_host_tubes,
                                                 &mut linux.pm,
                                                 #[cfg(feature = "gpu")]
                                                 &gpu_control_tube,
@@ -4162,4 +4866,130 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    fn hotplugged_inputs() {
+        let mut inputs = HotPluggedInputs::default();
+        let keyboard = Path::new("/run/keyboard.sock");
+        let mouse = Path::new("/run/mouse.sock");
+        let address = |bus| PciAddress {
+            bus,
+            dev: 0,
+            func: 0,
+        };
+
+        assert_eq!(inputs.next_idx(keyboard), Ok(0));
+        inputs.insert(keyboard.to_path_buf(), address(1));
+        // The same socket can't feed two devices.
+        assert_eq!(
+            inputs.next_idx(keyboard),
+            Err(base::Error::new(libc::EEXIST))
+        );
+        assert_eq!(inputs.next_idx(mouse), Ok(1));
+        inputs.insert(mouse.to_path_buf(), address(2));
+
+        assert_eq!(inputs.remove(keyboard), Ok(address(1)));
+        // Removed devices and devices given on the command line aren't known.
+        assert_eq!(inputs.remove(keyboard), Err(base::Error::new(libc::ENODEV)));
+        assert_eq!(
+            inputs.remove(Path::new("/run/boot-touch.sock")),
+            Err(base::Error::new(libc::ENODEV))
+        );
+        // Names are never reused.
+        assert_eq!(inputs.next_idx(keyboard), Ok(2));
+        assert_eq!(inputs.remove(mouse), Ok(address(2)));
+    }
+
+    #[test]
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    fn hotplugged_virtio_devices() {
+        let mut devices = HotPluggedVirtioDevices::default();
+        let scratch = Path::new("scratch");
+        let address = PciAddress {
+            bus: 1,
+            dev: 0,
+            func: 0,
+        };
+
+        assert_eq!(devices.check_id(scratch), Ok(()));
+        devices.insert(scratch.to_path_buf(), VirtioHotPlugKind::Block, address);
+        // Ids are unique across kinds.
+        assert_eq!(
+            devices.check_id(scratch),
+            Err(base::Error::new(libc::EEXIST))
+        );
+
+        // Unknown ids and ids of another kind aren't removed.
+        assert_eq!(
+            devices.remove(Path::new("nic1"), VirtioHotPlugKind::Net),
+            Err(base::Error::new(libc::ENODEV))
+        );
+        assert_eq!(
+            devices.remove(scratch, VirtioHotPlugKind::Net),
+            Err(base::Error::new(libc::ENODEV))
+        );
+
+        assert_eq!(
+            devices.remove(scratch, VirtioHotPlugKind::Block),
+            Ok(address)
+        );
+        assert_eq!(
+            devices.remove(scratch, VirtioHotPlugKind::Block),
+            Err(base::Error::new(libc::ENODEV))
+        );
+        // The id can be reused once the device is removed.
+        assert_eq!(devices.check_id(scratch), Ok(()));
+    }
+
+    #[test]
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    fn virtio_hotplug_ports() {
+        let mut hotplug_bus: BTreeMap<u8, Arc<Mutex<dyn HotPlugBus>>> = BTreeMap::new();
+        assert!(find_free_virtio_hotplug_port(&hotplug_bus).is_err());
+
+        hotplug_bus.insert(1, Arc::new(Mutex::new(PcieRootPort::new(1, true))));
+        let (secondary_bus, hp_bus) = find_free_virtio_hotplug_port(&hotplug_bus).unwrap();
+        assert_eq!(secondary_bus, 1);
+        let guest_addr = PciAddress {
+            bus: secondary_bus,
+            dev: 0,
+            func: 0,
+        };
+        hp_bus
+            .lock()
+            .add_hotplug_device(HostHotPlugKey::Virtio { guest_addr }, guest_addr);
+
+        // The only root port is taken.
+        assert!(find_free_virtio_hotplug_port(&hotplug_bus).is_err());
+        assert!(find_virtio_hotplug_port(&hotplug_bus, guest_addr).is_ok());
+
+        find_virtio_hotplug_port(&hotplug_bus, guest_addr)
+            .unwrap()
+            .lock()
+            .hot_unplug(guest_addr);
+        assert!(find_virtio_hotplug_port(&hotplug_bus, guest_addr).is_err());
+        assert_eq!(find_free_virtio_hotplug_port(&hotplug_bus).unwrap().0, 1);
+    }
+
+    #[test]
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    fn hotplug_virtio_device_invalid_params() {
+        let cfg = Config::default();
+        let mut control_tubes = Vec::new();
+        for (kind, params) in [
+            (VirtioHotPlugKind::Block, "foo=bar"),
+            (VirtioHotPlugKind::Net, "foo=bar"),
+            (VirtioHotPlugKind::SharedDir, "/tmp"),
+            (VirtioHotPlugKind::VhostUserFs, "/run/fs.sock"),
+        ] {
+            assert!(
+                create_hotplug_virtio_device(&cfg, kind, params, &mut control_tubes).is_err(),
+                "{:?} {}",
+                kind,
+                params
+            );
+        }
+        assert!(control_tubes.is_empty());
+    }
 }
@@ -79,6 +79,17 @@ pub struct VfioOption {
     pub intel_lpss: bool,
 }
 
+/// vfio-user device structure for creating a new instance based on command line options.
+#[derive(Serialize, Deserialize, FromKeyValues)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+pub struct VfioUserOption {
+    /// Path to the socket of the vfio-user server.
+    pub path: PathBuf,
+
+    /// PCI address to use for the device in the guest.
+    pub guest_address: Option<PciAddress>,
+}
+
 #[cfg(test)]
 mod tests {
     use std::path::PathBuf;
@@ -350,6 +361,29 @@ mod tests {
         assert_eq!(vfio.guest_address, None);
     }
 
+    #[test]
+    fn vfio_user_path() {
+        let config: Config = crate::crosvm::cmdline::RunCommand::from_args(
+            &[],
+            &[
+                "--vfio-user",
+                "/path/to/socket,guest-address=00:05.0",
+                "/dev/null",
+            ],
+        )
+        .unwrap()
+        .try_into()
+        .unwrap();
+
+        let vfio_user = config.vfio_user.first().unwrap();
+
+        assert_eq!(vfio_user.path, PathBuf::from("/path/to/socket"));
+        assert_eq!(
+            vfio_user.guest_address,
+            Some(PciAddress::new(0, 0, 5, 0).unwrap())
+        );
+    }
+
     #[test]
     fn vfio_pci_path_coiommu() {
         let config: Config = crate::crosvm::cmdline::RunCommand::from_args(
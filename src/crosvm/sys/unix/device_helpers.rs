@@ -58,6 +58,7 @@ use devices::VfioDevice;
 use devices::VfioDeviceType;
 use devices::VfioPciDevice;
 use devices::VfioPlatformDevice;
+use devices::VfioUserPciDevice;
 #[cfg(all(feature = "vtpm", target_arch = "x86_64"))]
 use devices::VtpmProxy;
 use hypervisor::ProtectionType;
@@ -427,11 +428,13 @@ pub fn create_virtio_snd_device(
     protection_type: ProtectionType,
     jail_config: &Option<JailConfig>,
     snd_params: SndParameters,
+    control_tube: Tube,
 ) -> DeviceResult {
     let backend = snd_params.backend;
     let dev = virtio::snd::common_backend::VirtioSnd::new(
         virtio::base_features(protection_type),
         snd_params,
+        Some(control_tube),
     )
     .context("failed to create cras sound device")?;
 
@@ -441,7 +444,9 @@ pub fn create_virtio_snd_device(
         Backend::NULL | Backend::FILE => "snd_null_device",
         #[cfg(feature = "audio_cras")]
         Backend::Sys(virtio::snd::sys::StreamSourceBackend::CRAS) => "snd_cras_device",
-        #[cfg(not(feature = "audio_cras"))]
+        #[cfg(feature = "audio_pulse")]
+        Backend::Sys(virtio::snd::sys::StreamSourceBackend::PULSE) => "snd_pulse_device",
+        #[cfg(not(any(feature = "audio_cras", feature = "audio_pulse")))]
         _ => unreachable!(),
     };
 
@@ -451,6 +456,10 @@ pub fn create_virtio_snd_device(
         if backend == Backend::Sys(virtio::snd::sys::StreamSourceBackend::CRAS) {
             config.bind_mounts = true;
         }
+        #[cfg(feature = "audio_pulse")]
+        if backend == Backend::Sys(virtio::snd::sys::StreamSourceBackend::PULSE) {
+            config.bind_mounts = true;
+        }
         // TODO(b/267574679): running as current_user may not be required for snd device.
         config.run_as = RunAsUser::CurrentUser;
         #[allow(unused_mut)]
@@ -461,6 +470,14 @@ pub fn create_virtio_snd_device(
             let run_cras_path = Path::new("/run/cras");
             jail.mount_bind(run_cras_path, run_cras_path, true)?;
         }
+        #[cfg(feature = "audio_pulse")]
+        if backend == Backend::Sys(virtio::snd::sys::StreamSourceBackend::PULSE) {
+            // Only the socket of the server is reachable, the policy can't check the paths given
+            // to open and connect.
+            if let Some(socket_path) = virtio::snd::sys::pulse_server_socket_path() {
+                jail.mount_bind(&socket_path, &socket_path, true)?;
+            }
+        }
         Some(jail)
     } else {
         None
@@ -678,6 +695,25 @@ pub fn create_switches_device<T: IntoUnixStream>(
     })
 }
 
+pub fn create_gamepad_device<T: IntoUnixStream>(
+    protection_type: ProtectionType,
+    jail_config: &Option<JailConfig>,
+    gamepad_socket: T,
+    idx: u32,
+) -> DeviceResult {
+    let socket = gamepad_socket
+        .into_unix_stream()
+        .context("failed configuring virtio gamepad")?;
+
+    let dev = virtio::new_gamepad(idx, socket, virtio::base_features(protection_type))
+        .context("failed to set up input device")?;
+
+    Ok(VirtioDeviceStub {
+        dev: Box::new(dev),
+        jail: simple_jail(jail_config, "input_device")?,
+    })
+}
+
 pub fn create_vinput_device(
     protection_type: ProtectionType,
     jail_config: &Option<JailConfig>,
@@ -1060,6 +1096,12 @@ impl VirtioDeviceBuilder for &VsockConfig {
     ) -> anyhow::Result<Box<dyn VirtioDevice>> {
         let features = virtio::base_features(protection_type);
 
+        if let Some(uds_path) = &self.uds_path {
+            let dev = virtio::vsock::UserspaceVsock::new(features, self.cid, uds_path)
+                .context("failed to set up userspace virtual socket device")?;
+            return Ok(Box::new(dev));
+        }
+
         let dev = virtio::vhost::Vsock::new(features, self)
             .context("failed to set up virtual socket device")?;
 
@@ -1070,12 +1112,48 @@ impl VirtioDeviceBuilder for &VsockConfig {
         self,
         keep_rds: &mut Vec<RawDescriptor>,
     ) -> anyhow::Result<Box<dyn VhostUserDevice>> {
+        if self.uds_path.is_some() {
+            bail!("uds_path is not supported by the vhost-user vsock device");
+        }
         let vsock_device = VhostUserVsockDevice::new(self.cid, &self.vhost_device)?;
 
         keep_rds.push(vsock_device.as_raw_descriptor());
 
         Ok(Box::new(vsock_device))
     }
+
+    fn create_jail(
+        &self,
+        jail_config: &Option<JailConfig>,
+        virtio_transport: VirtioDeviceType,
+    ) -> anyhow::Result<Option<Minijail>> {
+        let uds_path = match &self.uds_path {
+            Some(uds_path) => uds_path,
+            None => {
+                return simple_jail(
+                    jail_config,
+                    &virtio_transport.seccomp_policy_file(Self::NAME),
+                )
+            }
+        };
+        let uds_dir = uds_path
+            .parent()
+            .ok_or_else(|| anyhow!("vsock socket path has no parent"))?;
+
+        if let Some(jail_config) = jail_config {
+            let policy = virtio_transport.seccomp_policy_file("vsock");
+            let mut config = SandboxConfig::new(jail_config, &policy);
+            config.bind_mounts = true;
+            let mut jail =
+                create_sandbox_minijail(&jail_config.pivot_root, MAX_OPEN_FILES_DEFAULT, &config)?;
+            // Guest connections are forwarded to sockets next to `uds_path`, which may be created
+            // after the device is started.
+            jail.mount_bind(uds_dir, uds_dir, true)?;
+            Ok(Some(jail))
+        } else {
+            Ok(None)
+        }
+    }
 }
 
 pub fn create_fs_device(
@@ -1492,6 +1570,31 @@ pub fn create_vfio_device(
     }
 }
 
+pub fn create_vfio_user_device(
+    jail_config: &Option<JailConfig>,
+    vm: &impl Vm,
+    irq_control_tubes: &mut Vec<Tube>,
+    socket_path: &Path,
+    guest_address: Option<PciAddress>,
+) -> DeviceResult<(VfioUserPciDevice, Option<Minijail>)> {
+    let (host_tube_msi, device_tube_msi) = Tube::pair().context("failed to create tube")?;
+    irq_control_tubes.push(host_tube_msi);
+
+    let (host_tube_msix, device_tube_msix) = Tube::pair().context("failed to create tube")?;
+    irq_control_tubes.push(host_tube_msix);
+
+    let dev = VfioUserPciDevice::new(
+        socket_path,
+        guest_address,
+        vm.get_memory(),
+        device_tube_msi,
+        device_tube_msix,
+    )
+    .context("failed to create vfio-user device")?;
+
+    Ok((dev, simple_jail(jail_config, "vfio_user_device")?))
+}
+
 /// Setup for devices with virtio-iommu
 pub fn setup_virtio_access_platform(
     resources: &mut SystemAllocator,
@@ -105,6 +105,21 @@ pub fn create_gpu_device(
         );
     }
 
+    let gpu_parameters = cfg.gpu_parameters.as_ref().unwrap();
+    if let Some(port) = gpu_parameters.vnc_port {
+        // The listeners are bound before entering the jail, whose network namespace would make
+        // them unreachable from the host.
+        let backend = virtio::DisplayBackend::vnc(port, gpu_parameters.display_params.len())
+            .with_context(|| format!("failed to bind VNC server to port {}", port))?;
+        display_backends.insert(0, backend);
+    }
+    if let Some(path) = &gpu_parameters.headless_socket {
+        let backend = virtio::DisplayBackend::headless(path).with_context(|| {
+            format!("failed to bind headless display socket {}", path.display())
+        })?;
+        display_backends.insert(0, backend);
+    }
+
     let dev = virtio::Gpu::new(
         exit_evt_wrtube
             .try_clone()
@@ -112,7 +127,7 @@ pub fn create_gpu_device(
         gpu_control_tube,
         resource_bridges,
         display_backends,
-        cfg.gpu_parameters.as_ref().unwrap(),
+        gpu_parameters,
         render_server_fd,
         event_devices,
         /*external_blob=*/ cfg.jail_config.is_some(),
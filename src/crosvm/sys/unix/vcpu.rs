@@ -14,6 +14,26 @@ use std::time::Duration;
 
 #[cfg(any(target_arch = "arm", target_arch = "aarch64"))]
 use aarch64::AArch64 as Arch;
+#[cfg(any(target_arch = "arm", target_arch = "aarch64"))]
+use aarch64::PSCI_ALREADY_ON;
+#[cfg(any(target_arch = "arm", target_arch = "aarch64"))]
+use aarch64::PSCI_CPU_OFF;
+#[cfg(any(target_arch = "arm", target_arch = "aarch64"))]
+use aarch64::PSCI_CPU_ON_32;
+#[cfg(any(target_arch = "arm", target_arch = "aarch64"))]
+use aarch64::PSCI_CPU_ON_64;
+#[cfg(any(target_arch = "arm", target_arch = "aarch64"))]
+use aarch64::PSCI_DENIED;
+#[cfg(any(target_arch = "arm", target_arch = "aarch64"))]
+use aarch64::PSCI_INTERNAL_FAILURE;
+#[cfg(any(target_arch = "arm", target_arch = "aarch64"))]
+use aarch64::PSCI_INVALID_PARAMETERS;
+#[cfg(any(target_arch = "arm", target_arch = "aarch64"))]
+use aarch64::PSCI_NOT_SUPPORTED;
+#[cfg(any(target_arch = "arm", target_arch = "aarch64"))]
+use aarch64::PSCI_SUCCESS;
+#[cfg(any(target_arch = "arm", target_arch = "aarch64"))]
+use anyhow::bail;
 use anyhow::Context;
 use anyhow::Result;
 use arch::CpuConfigArch;
@@ -26,16 +46,26 @@ use arch::VmArch;
 use base::*;
 use devices::Bus;
 use devices::IrqChip;
+#[cfg(any(target_arch = "arm", target_arch = "aarch64"))]
+use devices::VcpuHotPlugState;
 use devices::VcpuRunState;
 use hypervisor::IoOperation;
 use hypervisor::IoParams;
 use hypervisor::Vcpu;
 use hypervisor::VcpuExit;
+#[cfg(any(target_arch = "arm", target_arch = "aarch64"))]
+use hypervisor::VcpuFeature;
+#[cfg(any(target_arch = "arm", target_arch = "aarch64"))]
+use hypervisor::VcpuRegAArch64;
 use hypervisor::VcpuRunHandle;
 use libc::c_int;
 #[cfg(target_arch = "riscv64")]
 use riscv64::Riscv64 as Arch;
-#[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), unix))]
+#[cfg(any(
+    target_arch = "arm",
+    target_arch = "aarch64",
+    all(any(target_arch = "x86", target_arch = "x86_64"), unix)
+))]
 use sync::Mutex;
 use vm_control::*;
 #[cfg(feature = "gdb")]
@@ -138,6 +168,137 @@ pub fn set_vcpu_thread_scheduling(
     Ok(())
 }
 
+#[cfg(any(target_arch = "arm", target_arch = "aarch64"))]
+struct VcpuPower {
+    on: bool,
+    control: Option<mpsc::Sender<VcpuControl>>,
+}
+
+/// Power state of the vCPUs, used to handle the PSCI CPU_ON and CPU_OFF calls in the VMM when vCPU
+/// hotplug is enabled so that only the plugged vCPUs can be powered on.
+#[cfg(any(target_arch = "arm", target_arch = "aarch64"))]
+pub struct VcpuPowerControl {
+    hotplug: Arc<Mutex<VcpuHotPlugState>>,
+    vcpus: Mutex<Vec<VcpuPower>>,
+}
+
+#[cfg(any(target_arch = "arm", target_arch = "aarch64"))]
+impl VcpuPowerControl {
+    /// Creates the power state of `vcpu_count` vCPUs, of which only the boot vCPU is on.
+    pub fn new(hotplug: Arc<Mutex<VcpuHotPlugState>>, vcpu_count: usize) -> VcpuPowerControl {
+        VcpuPowerControl {
+            hotplug,
+            vcpus: Mutex::new(
+                (0..vcpu_count)
+                    .map(|cpu_id| VcpuPower {
+                        on: cpu_id == 0,
+                        control: None,
+                    })
+                    .collect(),
+            ),
+        }
+    }
+
+    /// Sets the channel used to send `VcpuControl::PowerOn` to vCPU `cpu_id`.
+    pub fn register_vcpu(&self, cpu_id: usize, control: mpsc::Sender<VcpuControl>) {
+        if let Some(vcpu) = self.vcpus.lock().get_mut(cpu_id) {
+            vcpu.control = Some(control);
+        }
+    }
+
+    /// Handles a CPU_ON call, returning the PSCI status for the caller.
+    fn cpu_on(&self, target_cpu: u64, entry: u64, context_id: u64) -> i32 {
+        // The device tree describes each vCPU with its index in `reg`, which is what the guest
+        // passes as the target.
+        let cpu_id = (target_cpu & 0xff_ffff) as usize;
+        let mut vcpus = self.vcpus.lock();
+        let vcpu = match vcpus.get_mut(cpu_id) {
+            Some(vcpu) => vcpu,
+            None => return PSCI_INVALID_PARAMETERS,
+        };
+        if vcpu.on {
+            return PSCI_ALREADY_ON;
+        }
+        if !self.hotplug.lock().is_plugged(cpu_id) {
+            return PSCI_DENIED;
+        }
+        let control = match &vcpu.control {
+            Some(control) => control,
+            None => return PSCI_INTERNAL_FAILURE,
+        };
+        if let Err(e) = control.send(VcpuControl::PowerOn { entry, context_id }) {
+            error!("failed to power on vcpu {}: {}", cpu_id, e);
+            return PSCI_INTERNAL_FAILURE;
+        }
+        vcpu.on = true;
+        PSCI_SUCCESS
+    }
+
+    /// Handles a CPU_OFF call from vCPU `cpu_id`.
+    fn cpu_off(&self, cpu_id: usize) {
+        if let Some(vcpu) = self.vcpus.lock().get_mut(cpu_id) {
+            vcpu.on = false;
+        }
+    }
+
+    /// Unplugs vCPU `cpu_id`. The guest has no way to be told about the removal, so the vCPU must
+    /// have been powered off with CPU_OFF first.
+    pub fn unplug(&self, cpu_id: usize) -> Result<()> {
+        // The vCPU can't be powered on while `vcpus` is locked.
+        let vcpus = self.vcpus.lock();
+        if vcpus.get(cpu_id).map_or(false, |vcpu| vcpu.on) {
+            bail!("vCPU {} is powered on", cpu_id);
+        }
+        self.hotplug.lock().unplug(cpu_id)?;
+        Ok(())
+    }
+}
+
+/// Handles the PSCI call of a `VcpuExit::Hypercall` exit. Returns true if the vCPU powered itself
+/// off, in which case it is reset and must not run until it is powered on again.
+#[cfg(any(target_arch = "arm", target_arch = "aarch64"))]
+fn handle_psci_call<V: VcpuArch>(
+    vcpu: &V,
+    cpu_id: usize,
+    features: &[VcpuFeature],
+    power_control: &VcpuPowerControl,
+) -> bool {
+    let mut cpu_off = false;
+    let res = vcpu.handle_smccc_call(&mut |function_id, args| {
+        let ret = match function_id {
+            PSCI_CPU_ON_32 => power_control.cpu_on(
+                args[0] & 0xffff_ffff,
+                args[1] & 0xffff_ffff,
+                args[2] & 0xffff_ffff,
+            ),
+            PSCI_CPU_ON_64 => power_control.cpu_on(args[0], args[1], args[2]),
+            PSCI_CPU_OFF => {
+                cpu_off = true;
+                PSCI_SUCCESS
+            }
+            _ => PSCI_NOT_SUPPORTED,
+        };
+        ret as i64 as u64
+    });
+    if let Err(e) = res {
+        error!("failed to handle psci call on vcpu {}: {}", cpu_id, e);
+        return false;
+    }
+    if !cpu_off {
+        return false;
+    }
+
+    power_control.cpu_off(cpu_id);
+    // Reset the vCPU so that it starts from a clean state when powered on again.
+    if let Err(e) = vcpu
+        .init(features)
+        .and_then(|_| vcpu.set_power_state(false))
+    {
+        error!("failed to power off vcpu {}: {}", cpu_id, e);
+    }
+    true
+}
+
 // Sets up a vcpu and converts it into a runnable vcpu.
 pub fn runnable_vcpu<V>(
     cpu_id: usize,
@@ -219,22 +380,34 @@ fn vcpu_loop<V>(
     #[cfg(any(target_arch = "x86", target_arch = "x86_64"))] msr_handlers: MsrHandlers,
     #[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), unix))]
     bus_lock_ratelimit_ctrl: Arc<Mutex<Ratelimit>>,
+    #[cfg(any(target_arch = "arm", target_arch = "aarch64"))] vcpu_features: Vec<VcpuFeature>,
+    #[cfg(any(target_arch = "arm", target_arch = "aarch64"))] power_control: Option<
+        Arc<VcpuPowerControl>,
+    >,
 ) -> ExitState
 where
     V: VcpuArch + 'static,
 {
     let mut interrupted_by_signal = false;
+    // A vCPU powered off by PSCI only processes messages until it is powered on again. Only the
+    // boot vCPU is initially on when the VMM handles the PSCI calls.
+    #[cfg(any(target_arch = "arm", target_arch = "aarch64"))]
+    let mut powered_off = cpu_id != 0 && power_control.is_some();
+    #[cfg(not(any(target_arch = "arm", target_arch = "aarch64")))]
+    let powered_off = false;
 
     loop {
         // Start by checking for messages to process and the run state of the CPU.
         // An extra check here for Running so there isn't a need to call recv unless a
         // message is likely to be ready because a signal was sent.
-        if interrupted_by_signal || run_mode != VmRunMode::Running {
+        if interrupted_by_signal || run_mode != VmRunMode::Running || powered_off {
             'state_loop: loop {
                 // Tries to get a pending message without blocking first.
                 let msg = match from_main_tube.try_recv() {
                     Ok(m) => m,
-                    Err(mpsc::TryRecvError::Empty) if run_mode == VmRunMode::Running => {
+                    Err(mpsc::TryRecvError::Empty)
+                        if run_mode == VmRunMode::Running && !powered_off =>
+                    {
                         // If the VM is running and no message is pending, the state won't
                         // change.
                         break 'state_loop;
@@ -264,7 +437,8 @@ where
                         VcpuControl::RunState(new_mode) => {
                             run_mode = new_mode;
                             match run_mode {
-                                VmRunMode::Running => break 'state_loop,
+                                VmRunMode::Running if !powered_off => break 'state_loop,
+                                VmRunMode::Running => {}
                                 VmRunMode::Suspending => {
                                     // On KVM implementations that use a paravirtualized
                                     // clock (e.g. x86), a flag must be set to indicate to
@@ -331,6 +505,18 @@ where
                                 error!("Failed to send restore response: {}", e);
                             }
                         }
+                        #[cfg(any(target_arch = "arm", target_arch = "aarch64"))]
+                        VcpuControl::PowerOn { entry, context_id } => {
+                            if let Err(e) = vcpu
+                                .set_one_reg(VcpuRegAArch64::Pc, entry)
+                                .and_then(|_| vcpu.set_one_reg(VcpuRegAArch64::X(0), context_id))
+                                .and_then(|_| vcpu.set_power_state(true))
+                            {
+                                error!("failed to power on vcpu {}: {}", cpu_id, e);
+                                return ExitState::Crash;
+                            }
+                            powered_off = false;
+                        }
                     }
                 }
             }
@@ -422,6 +608,13 @@ where
                     let delay_ns: u64 = bus_lock_ratelimit_ctrl.lock().ratelimit_calculate_delay(1);
                     thread::sleep(Duration::from_nanos(delay_ns));
                 }
+                #[cfg(any(target_arch = "arm", target_arch = "aarch64"))]
+                Ok(VcpuExit::Hypercall) => match power_control.as_deref() {
+                    Some(power_control) => {
+                        powered_off = handle_psci_call(&vcpu, cpu_id, &vcpu_features, power_control)
+                    }
+                    None => warn!("unexpected vcpu exit: {:?}", VcpuExit::Hypercall),
+                },
                 Ok(VcpuExit::Sbi {
                     extension_id: _,
                     function_id: _,
@@ -502,6 +695,9 @@ pub fn run_vcpu<V>(
     #[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), unix))]
     bus_lock_ratelimit_ctrl: Arc<Mutex<Ratelimit>>,
     run_mode: VmRunMode,
+    #[cfg(any(target_arch = "arm", target_arch = "aarch64"))] power_control: Option<
+        Arc<VcpuPowerControl>,
+    >,
 ) -> Result<JoinHandle<()>>
 where
     V: VcpuArch + 'static,
@@ -526,6 +722,10 @@ where
                 #[cfg(feature = "gdb")]
                 let guest_mem = vm.get_memory().clone();
 
+                // The features are needed to reset the vCPU when it powers itself off.
+                #[cfg(any(target_arch = "arm", target_arch = "aarch64"))]
+                let vcpu_features = vcpu_init.features.clone();
+
                 let runnable_vcpu = runnable_vcpu(
                     cpu_id,
                     vcpu_id,
@@ -587,6 +787,10 @@ where
                     msr_handlers,
                     #[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), unix))]
                     bus_lock_ratelimit_ctrl,
+                    #[cfg(any(target_arch = "arm", target_arch = "aarch64"))]
+                    vcpu_features,
+                    #[cfg(any(target_arch = "arm", target_arch = "aarch64"))]
+                    power_control,
                 )
             };
 
@@ -643,3 +847,62 @@ pub fn kick_vcpu(
     }
     irq_chip.kick_halted_vcpus();
 }
+
+#[cfg(all(test, any(target_arch = "arm", target_arch = "aarch64")))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cpu_on_gating() {
+        let hotplug = Arc::new(Mutex::new(VcpuHotPlugState::new(3, 1)));
+        let power_control = VcpuPowerControl::new(hotplug.clone(), 3);
+        let (tx, rx) = mpsc::channel();
+        power_control.register_vcpu(2, tx);
+
+        assert_eq!(power_control.cpu_on(3, 0, 0), PSCI_INVALID_PARAMETERS);
+        // The boot vCPU is on from the start.
+        assert_eq!(power_control.cpu_on(0, 0, 0), PSCI_ALREADY_ON);
+        // vCPU 2 isn't plugged yet.
+        assert_eq!(power_control.cpu_on(2, 0x8000, 42), PSCI_DENIED);
+
+        hotplug.lock().plug(1).unwrap();
+        hotplug.lock().plug(2).unwrap();
+        // vCPU 1 has no control channel.
+        assert_eq!(power_control.cpu_on(1, 0, 0), PSCI_INTERNAL_FAILURE);
+
+        // The affinity bits above Aff0-Aff2 are ignored.
+        assert_eq!(
+            power_control.cpu_on(0xff_0000_0002, 0x8000, 42),
+            PSCI_SUCCESS
+        );
+        match rx.try_recv() {
+            Ok(VcpuControl::PowerOn { entry, context_id }) => {
+                assert_eq!(entry, 0x8000);
+                assert_eq!(context_id, 42);
+            }
+            other => panic!("unexpected vcpu control {:?}", other),
+        }
+        assert_eq!(power_control.cpu_on(2, 0x8000, 42), PSCI_ALREADY_ON);
+    }
+
+    #[test]
+    fn cpu_off_and_unplug() {
+        let hotplug = Arc::new(Mutex::new(VcpuHotPlugState::new(2, 1)));
+        let power_control = VcpuPowerControl::new(hotplug.clone(), 2);
+        let (tx, _rx) = mpsc::channel();
+        power_control.register_vcpu(1, tx);
+
+        hotplug.lock().plug(1).unwrap();
+        assert_eq!(power_control.cpu_on(1, 0, 0), PSCI_SUCCESS);
+
+        // A running vCPU can't be unplugged.
+        assert!(power_control.unplug(1).is_err());
+        assert!(hotplug.lock().is_plugged(1));
+
+        power_control.cpu_off(1);
+        power_control.unplug(1).unwrap();
+        assert!(!hotplug.lock().is_plugged(1));
+        // Once unplugged, it can't be powered on again.
+        assert_eq!(power_control.cpu_on(1, 0, 0), PSCI_DENIED);
+    }
+}
@@ -10,6 +10,8 @@
 #[cfg(any(feature = "composite-disk", feature = "qcow"))]
 use std::fs::OpenOptions;
 use std::path::Path;
+use std::path::PathBuf;
+use std::str::FromStr;
 
 use anyhow::anyhow;
 use anyhow::Context;
@@ -26,12 +28,15 @@ use crosvm::cmdline;
 #[cfg(feature = "plugin")]
 use crosvm::config::executable_is_plugin;
 use crosvm::config::Config;
+use crosvm::config::DEFAULT_TOUCH_DEVICE_HEIGHT;
+use crosvm::config::DEFAULT_TOUCH_DEVICE_WIDTH;
 use devices::virtio::vhost::user::device::run_block_device;
 #[cfg(feature = "gpu")]
 use devices::virtio::vhost::user::device::run_gpu_device;
 use devices::virtio::vhost::user::device::run_net_device;
 #[cfg(feature = "audio")]
 use devices::virtio::vhost::user::device::run_snd_device;
+use devices::PciAddress;
 #[cfg(feature = "composite-disk")]
 use disk::create_composite_disk;
 #[cfg(feature = "composite-disk")]
@@ -56,9 +61,17 @@ use vm_control::client::do_gpu_display_add;
 use vm_control::client::do_gpu_display_list;
 #[cfg(feature = "gpu")]
 use vm_control::client::do_gpu_display_remove;
+#[cfg(feature = "gpu")]
+use vm_control::client::do_gpu_screenshot;
+#[cfg(feature = "gpu")]
+use vm_control::client::do_gpu_start_recording;
+#[cfg(feature = "gpu")]
+use vm_control::client::do_gpu_stop_recording;
 use vm_control::client::do_modify_battery;
 use vm_control::client::do_swap_status;
 use vm_control::client::do_usb_attach;
+use vm_control::client::do_usb_attach_hid;
+use vm_control::client::do_usb_attach_storage;
 use vm_control::client::do_usb_detach;
 use vm_control::client::do_usb_list;
 #[cfg(feature = "balloon")]
@@ -72,10 +85,17 @@ use vm_control::BalloonControlCommand;
 use vm_control::DiskControlCommand;
 use vm_control::HotPlugDeviceInfo;
 use vm_control::HotPlugDeviceType;
+use vm_control::InputHotPlugCommand;
+use vm_control::PcieAerErrorType;
 use vm_control::RestoreCommand;
 use vm_control::SnapshotCommand;
+use vm_control::SndControlCommand;
 use vm_control::SwapCommand;
+use vm_control::ThermalCommand;
 use vm_control::UsbControlResult;
+use vm_control::UsbHidKind;
+use vm_control::VirtioHotPlugKind;
+use vm_control::VirtioInputKind;
 use vm_control::VmRequest;
 #[cfg(feature = "balloon")]
 use vm_control::VmResponse;
@@ -299,7 +319,6 @@ fn modify_vfio(cmd: cmdline::VfioCrosvmCommand) -> std::result::Result<(), ()> {
 #[cfg(feature = "composite-disk")]
 fn create_composite(cmd: cmdline::CreateCompositeCommand) -> std::result::Result<(), ()> {
     use std::fs::File;
-    use std::path::PathBuf;
 
     let composite_image_path = &cmd.path;
     let zero_filler_path = format!("{}.filler", composite_image_path);
@@ -477,6 +496,151 @@ fn disk_cmd(cmd: cmdline::DiskCommand) -> std::result::Result<(), ()> {
     }
 }
 
+fn snd_cmd(cmd: cmdline::SndCommand) -> std::result::Result<(), ()> {
+    match cmd.command {
+        cmdline::SndSubcommand::Jack(cmd) => {
+            let request = VmRequest::SndCommand {
+                snd_index: cmd.snd_index,
+                command: SndControlCommand::SetJackConnected {
+                    jack_id: cmd.jack_id,
+                    connected: cmd.connected,
+                },
+            };
+            vms_request(&request, cmd.socket_path)
+        }
+    }
+}
+
+/// Builds the `InputHotPlugCommand` of a `crosvm input` subcommand, returned along with the path of
+/// the VM socket to send it to.
+fn input_hotplug_command(
+    command: cmdline::InputSubCommand,
+) -> std::result::Result<(InputHotPlugCommand, String), ()> {
+    match command {
+        cmdline::InputSubCommand::Add(cmd) => {
+            let width = cmd.width.unwrap_or(DEFAULT_TOUCH_DEVICE_WIDTH);
+            let height = cmd.height.unwrap_or(DEFAULT_TOUCH_DEVICE_HEIGHT);
+            let kind = match cmd.kind.as_str() {
+                "keyboard" => VirtioInputKind::Keyboard,
+                "mouse" => VirtioInputKind::Mouse,
+                "switches" => VirtioInputKind::Switches,
+                "gamepad" => VirtioInputKind::Gamepad,
+                "single-touch" => VirtioInputKind::SingleTouch { width, height },
+                "multi-touch" => VirtioInputKind::MultiTouch { width, height },
+                "trackpad" => VirtioInputKind::Trackpad { width, height },
+                kind => {
+                    error!("invalid input device kind: {}", kind);
+                    return Err(());
+                }
+            };
+            // The socket is opened by the VM process, so make the path independent of our cwd.
+            let socket_path = cmd.event_socket.canonicalize().map_err(|e| {
+                error!("invalid event socket {}: {}", cmd.event_socket.display(), e);
+            })?;
+            Ok((
+                InputHotPlugCommand::Add { kind, socket_path },
+                cmd.socket_path,
+            ))
+        }
+        cmdline::InputSubCommand::Remove(cmd) => {
+            let socket_path = cmd.event_socket.canonicalize().map_err(|e| {
+                error!("invalid event socket {}: {}", cmd.event_socket.display(), e);
+            })?;
+            Ok((InputHotPlugCommand::Remove { socket_path }, cmd.socket_path))
+        }
+    }
+}
+
+fn input_cmd(cmd: cmdline::InputCommand) -> std::result::Result<(), ()> {
+    let (command, socket_path) = input_hotplug_command(cmd.command)?;
+    vms_request(&VmRequest::InputHotPlugCommand(command), socket_path)
+}
+
+fn vcpu_cmd(cmd: cmdline::VcpuCommand) -> std::result::Result<(), ()> {
+    let (cpu_id, add, socket_path) = match cmd.command {
+        cmdline::VcpuSubCommand::Add(cmd) => (cmd.cpu_id, true, cmd.socket_path),
+        cmdline::VcpuSubCommand::Remove(cmd) => (cmd.cpu_id, false, cmd.socket_path),
+    };
+    vms_request(&VmRequest::VcpuHotPlugCommand { cpu_id, add }, socket_path)
+}
+
+fn thermal_cmd(cmd: cmdline::ThermalCommand) -> std::result::Result<(), ()> {
+    let (command, socket_path) = match cmd.command {
+        cmdline::ThermalSubCommand::Temperature(cmd) => {
+            (ThermalCommand::SetTemperature(cmd.celsius), cmd.socket_path)
+        }
+        cmdline::ThermalSubCommand::TripPoints(cmd) => (
+            ThermalCommand::SetTripPoints {
+                passive: cmd.passive,
+                critical: cmd.critical,
+            },
+            cmd.socket_path,
+        ),
+        cmdline::ThermalSubCommand::FrequencyLimit(cmd) => {
+            (ThermalCommand::SetFrequencyLimit(cmd.mhz), cmd.socket_path)
+        }
+    };
+    vms_request(&VmRequest::ThermalCommand(command), socket_path)
+}
+
+fn virtio_hotplug_kind(kind: &str) -> std::result::Result<VirtioHotPlugKind, ()> {
+    match kind {
+        "block" => Ok(VirtioHotPlugKind::Block),
+        "net" => Ok(VirtioHotPlugKind::Net),
+        "shared-dir" => Ok(VirtioHotPlugKind::SharedDir),
+        "vhost-user-blk" => Ok(VirtioHotPlugKind::VhostUserBlk),
+        "vhost-user-fs" => Ok(VirtioHotPlugKind::VhostUserFs),
+        "vhost-user-net" => Ok(VirtioHotPlugKind::VhostUserNet),
+        kind => {
+            error!("invalid virtio device kind: {}", kind);
+            Err(())
+        }
+    }
+}
+
+fn virtio_cmd(cmd: cmdline::VirtioCommand) -> std::result::Result<(), ()> {
+    let (kind, id, params, add, socket_path) = match cmd.command {
+        cmdline::VirtioSubCommand::Add(cmd) => {
+            (cmd.kind, cmd.id, cmd.params, true, cmd.socket_path)
+        }
+        cmdline::VirtioSubCommand::Remove(cmd) => {
+            (cmd.kind, cmd.id, String::new(), false, cmd.socket_path)
+        }
+    };
+    let request = VmRequest::HotPlugCommand {
+        device: HotPlugDeviceInfo {
+            device_type: HotPlugDeviceType::Virtio {
+                kind: virtio_hotplug_kind(&kind)?,
+                params,
+            },
+            path: PathBuf::from(id),
+            hp_interrupt: true,
+        },
+        add,
+    };
+    vms_request(&request, socket_path)
+}
+
+fn inject_aer(cmd: cmdline::AerCommand) -> std::result::Result<(), ()> {
+    let address = PciAddress::from_str(&cmd.address).map_err(|e| {
+        error!("invalid PCI address {}: {}", cmd.address, e);
+    })?;
+    let error = match cmd.error.as_str() {
+        "correctable" => PcieAerErrorType::Correctable,
+        "non-fatal" => PcieAerErrorType::NonFatal,
+        "fatal" => PcieAerErrorType::Fatal,
+        error => {
+            error!("invalid AER error: {}", error);
+            return Err(());
+        }
+    };
+    let request = VmRequest::PcieAerInject {
+        requester_id: address.pme_requester_id(),
+        error,
+    };
+    vms_request(&request, cmd.socket_path)
+}
+
 fn make_rt(cmd: cmdline::MakeRTCommand) -> std::result::Result<(), ()> {
     vms_request(&VmRequest::MakeRT, cmd.socket_path)
 }
@@ -496,12 +660,30 @@ fn gpu_display_remove(cmd: cmdline::GpuRemoveDisplaysCommand) -> ModifyGpuResult
     do_gpu_display_remove(cmd.socket_path, cmd.display_id)
 }
 
+#[cfg(feature = "gpu")]
+fn gpu_screenshot(cmd: cmdline::GpuScreenshotCommand) -> ModifyGpuResult {
+    do_gpu_screenshot(cmd.socket_path, cmd.display_id, &cmd.file_path)
+}
+
+#[cfg(feature = "gpu")]
+fn gpu_start_recording(cmd: cmdline::GpuStartRecordingCommand) -> ModifyGpuResult {
+    do_gpu_start_recording(cmd.socket_path, cmd.display_id, &cmd.file_path, cmd.format)
+}
+
+#[cfg(feature = "gpu")]
+fn gpu_stop_recording(cmd: cmdline::GpuStopRecordingCommand) -> ModifyGpuResult {
+    do_gpu_stop_recording(cmd.socket_path, cmd.display_id)
+}
+
 #[cfg(feature = "gpu")]
 fn modify_gpu(cmd: cmdline::GpuCommand) -> std::result::Result<(), ()> {
     let result = match cmd.command {
         cmdline::GpuSubCommand::AddDisplays(cmd) => gpu_display_add(cmd),
         cmdline::GpuSubCommand::ListDisplays(cmd) => gpu_display_list(cmd),
         cmdline::GpuSubCommand::RemoveDisplays(cmd) => gpu_display_remove(cmd),
+        cmdline::GpuSubCommand::Screenshot(cmd) => gpu_screenshot(cmd),
+        cmdline::GpuSubCommand::StartRecording(cmd) => gpu_start_recording(cmd),
+        cmdline::GpuSubCommand::StopRecording(cmd) => gpu_stop_recording(cmd),
     };
     match result {
         Ok(response) => {
@@ -521,6 +703,27 @@ fn usb_attach(cmd: UsbAttachCommand) -> ModifyUsbResult<UsbControlResult> {
     do_usb_attach(cmd.socket_path, dev_path)
 }
 
+fn usb_attach_hid(cmd: cmdline::UsbAttachHidCommand) -> ModifyUsbResult<UsbControlResult> {
+    let kind = match cmd.kind {
+        cmdline::UsbHidDeviceKind::Keyboard => UsbHidKind::Keyboard,
+        cmdline::UsbHidDeviceKind::Mouse => UsbHidKind::Mouse,
+        cmdline::UsbHidDeviceKind::Tablet => UsbHidKind::Tablet {
+            width: cmd.width.unwrap_or(DEFAULT_TOUCH_DEVICE_WIDTH),
+            height: cmd.height.unwrap_or(DEFAULT_TOUCH_DEVICE_HEIGHT),
+        },
+    };
+    do_usb_attach_hid(
+        cmd.socket_path,
+        kind,
+        Path::new(&cmd.source_path),
+        cmd.evdev,
+    )
+}
+
+fn usb_attach_storage(cmd: cmdline::UsbAttachStorageCommand) -> ModifyUsbResult<UsbControlResult> {
+    do_usb_attach_storage(cmd.socket_path, Path::new(&cmd.image_path), cmd.read_only)
+}
+
 fn usb_detach(cmd: cmdline::UsbDetachCommand) -> ModifyUsbResult<UsbControlResult> {
     do_usb_detach(cmd.socket_path, cmd.port)
 }
@@ -532,6 +735,8 @@ fn usb_list(cmd: cmdline::UsbListCommand) -> ModifyUsbResult<UsbControlResult> {
 fn modify_usb(cmd: cmdline::UsbCommand) -> std::result::Result<(), ()> {
     let result = match cmd.command {
         cmdline::UsbSubCommand::Attach(cmd) => usb_attach(cmd),
+        cmdline::UsbSubCommand::AttachHid(cmd) => usb_attach_hid(cmd),
+        cmdline::UsbSubCommand::AttachStorage(cmd) => usb_attach_storage(cmd),
         cmdline::UsbSubCommand::Detach(cmd) => usb_detach(cmd),
         cmdline::UsbSubCommand::List(cmd) => usb_list(cmd),
     };
@@ -724,6 +929,12 @@ fn crosvm_main<I: IntoIterator<Item = String>>(args: I) -> Result<CommandStatus>
                         resume_vms(cmd).map_err(|_| anyhow!("resume subcommand failed"))
                     }
                     CrossPlatformCommands::Run(_) => unreachable!(),
+                    CrossPlatformCommands::Input(cmd) => {
+                        input_cmd(cmd).map_err(|_| anyhow!("input subcommand failed"))
+                    }
+                    CrossPlatformCommands::Snd(cmd) => {
+                        snd_cmd(cmd).map_err(|_| anyhow!("snd subcommand failed"))
+                    }
                     CrossPlatformCommands::Stop(cmd) => {
                         stop_vms(cmd).map_err(|_| anyhow!("stop subcommand failed"))
                     }
@@ -733,24 +944,36 @@ fn crosvm_main<I: IntoIterator<Item = String>>(args: I) -> Result<CommandStatus>
                     CrossPlatformCommands::Swap(cmd) => {
                         swap_vms(cmd).map_err(|_| anyhow!("swap subcommand failed"))
                     }
+                    CrossPlatformCommands::Thermal(cmd) => {
+                        thermal_cmd(cmd).map_err(|_| anyhow!("thermal subcommand failed"))
+                    }
                     CrossPlatformCommands::Powerbtn(cmd) => {
                         powerbtn_vms(cmd).map_err(|_| anyhow!("powerbtn subcommand failed"))
                     }
                     CrossPlatformCommands::Sleepbtn(cmd) => {
                         sleepbtn_vms(cmd).map_err(|_| anyhow!("sleepbtn subcommand failed"))
                     }
+                    CrossPlatformCommands::Aer(cmd) => {
+                        inject_aer(cmd).map_err(|_| anyhow!("aer subcommand failed"))
+                    }
                     CrossPlatformCommands::Gpe(cmd) => {
                         inject_gpe(cmd).map_err(|_| anyhow!("gpe subcommand failed"))
                     }
                     CrossPlatformCommands::Usb(cmd) => {
                         modify_usb(cmd).map_err(|_| anyhow!("usb subcommand failed"))
                     }
+                    CrossPlatformCommands::Vcpu(cmd) => {
+                        vcpu_cmd(cmd).map_err(|_| anyhow!("vcpu subcommand failed"))
+                    }
                     CrossPlatformCommands::Version(_) => {
                         pkg_version().map_err(|_| anyhow!("version subcommand failed"))
                     }
                     CrossPlatformCommands::Vfio(cmd) => {
                         modify_vfio(cmd).map_err(|_| anyhow!("vfio subcommand failed"))
                     }
+                    CrossPlatformCommands::Virtio(cmd) => {
+                        virtio_cmd(cmd).map_err(|_| anyhow!("virtio subcommand failed"))
+                    }
                     CrossPlatformCommands::Snapshot(cmd) => {
                         snapshot_vm(cmd).map_err(|_| anyhow!("snapshot subcommand failed"))
                     }
@@ -903,4 +1126,88 @@ mod tests {
         let status = res.expect("arg parsing should succeed");
         assert_eq!(status, CommandStatus::InvalidArgs);
     }
+
+    fn parse_input_command(
+        args: &[&str],
+    ) -> std::result::Result<(InputHotPlugCommand, String), ()> {
+        let cmd = cmdline::InputCommand::from_args(&["crosvm", "input"], args).unwrap();
+        input_hotplug_command(cmd.command)
+    }
+
+    #[test]
+    fn input_add() {
+        // The event socket path is made absolute.
+        let cwd = std::env::current_dir().unwrap().canonicalize().unwrap();
+        let (command, socket_path) =
+            parse_input_command(&["add", "keyboard", ".", "/run/vm.sock"]).unwrap();
+        assert_eq!(socket_path, "/run/vm.sock");
+        match command {
+            InputHotPlugCommand::Add { kind, socket_path } => {
+                assert_eq!(kind, VirtioInputKind::Keyboard);
+                assert_eq!(socket_path, cwd);
+            }
+            command => panic!("unexpected command: {:?}", command),
+        }
+    }
+
+    #[test]
+    fn input_add_touch_size() {
+        let (command, _) = parse_input_command(&[
+            "add",
+            "multi-touch",
+            "/",
+            "/run/vm.sock",
+            "--width",
+            "800",
+            "--height",
+            "600",
+        ])
+        .unwrap();
+        assert!(matches!(
+            command,
+            InputHotPlugCommand::Add {
+                kind: VirtioInputKind::MultiTouch {
+                    width: 800,
+                    height: 600
+                },
+                ..
+            }
+        ));
+
+        let (command, _) =
+            parse_input_command(&["add", "trackpad", "/", "/run/vm.sock", "--width", "800"])
+                .unwrap();
+        match command {
+            InputHotPlugCommand::Add {
+                kind: VirtioInputKind::Trackpad { width, height },
+                ..
+            } => {
+                assert_eq!(width, 800);
+                assert_eq!(height, DEFAULT_TOUCH_DEVICE_HEIGHT);
+            }
+            command => panic!("unexpected command: {:?}", command),
+        }
+    }
+
+    #[test]
+    fn input_add_invalid() {
+        assert!(parse_input_command(&["add", "joystick", "/", "/run/vm.sock"]).is_err());
+        assert!(
+            parse_input_command(&["add", "mouse", "/nonexistent/event.sock", "/run/vm.sock"])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn input_remove() {
+        let (command, socket_path) = parse_input_command(&["remove", "/", "/run/vm.sock"]).unwrap();
+        assert_eq!(socket_path, "/run/vm.sock");
+        match command {
+            InputHotPlugCommand::Remove { socket_path } => assert_eq!(socket_path, Path::new("/")),
+            command => panic!("unexpected command: {:?}", command),
+        }
+        assert!(
+            parse_input_command(&["remove", "/nonexistent/event.sock", "/run/vm.sock"]).is_err()
+        );
+    }
 }
@@ -323,7 +323,7 @@ fn create_snd_device(
     _product_args: SndBackendConfigProduct,
 ) -> DeviceResult {
     let features = virtio::base_features(cfg.protection_type);
-    let dev = VirtioSnd::new(features, parameters)
+    let dev = VirtioSnd::new(features, parameters, None)
         .exit_context(Exit::VirtioSoundDeviceNew, "failed to create snd device")?;
 
     Ok(VirtioDeviceStub {
@@ -1449,6 +1449,16 @@ fn setup_vm_components(cfg: &Config) -> Result<VmComponents> {
         cpu_clusters: cfg.cpu_clusters.clone(),
         cpu_capacity: cfg.cpu_capacity.clone(),
         no_smt: cfg.no_smt,
+        numa_nodes: cfg
+            .numa
+            .iter()
+            .map(|node| arch::NumaNode {
+                cpus: node.cpus.clone(),
+                memory_size: node.size * 1024 * 1024,
+                host_node: node.host_node,
+                distances: node.distances.clone(),
+            })
+            .collect(),
         hugepages: cfg.hugepages,
         hv_cfg: hypervisor::Config {
             protection_type: cfg.protection_type,
@@ -1463,6 +1473,7 @@ fn setup_vm_components(cfg: &Config) -> Result<VmComponents> {
                 })
             })
             .map_or(Ok(None), |v| v.map(Some))?,
+        boot_vcpu_count: None,
         pstore: cfg.pstore.clone(),
         pflash_block_size,
         pflash_image,
@@ -1493,6 +1504,8 @@ fn setup_vm_components(cfg: &Config) -> Result<VmComponents> {
         pcie_ecam: cfg.pcie_ecam,
         #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
         oem_strings: cfg.oem_strings.clone(),
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        smbios: cfg.smbios.clone(),
         dynamic_power_coefficient: cfg.dynamic_power_coefficient.clone(),
     })
 }
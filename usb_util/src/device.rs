@@ -61,12 +61,13 @@ pub struct TransferHandle {
     fd: std::sync::Weak<File>,
 }
 
-#[derive(PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq)]
 pub enum TransferStatus {
     Completed,
     Error,
     Cancelled,
     NoDevice,
+    Stalled,
 }
 
 impl Device {
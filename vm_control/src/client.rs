@@ -12,6 +12,8 @@ use thiserror::Error;
 
 #[cfg(feature = "gpu")]
 pub use crate::gpu::*;
+use crate::sys::connect_event_socket;
+use crate::sys::connect_usbip_server;
 pub use crate::sys::handle_request;
 pub use crate::*;
 
@@ -27,6 +29,8 @@ enum ModifyBatError {
 pub enum ModifyUsbError {
     #[error("failed to open device {0}: {1}")]
     FailedToOpenDevice(PathBuf, base::Error),
+    #[error("invalid usbip url {0}, expected usbip://HOST:PORT/BUSID")]
+    InvalidUsbipUrl(String),
     #[error("socket failed")]
     SocketFailed,
     #[error("unexpected response: {0}")]
@@ -53,14 +57,88 @@ pub fn vms_request<T: AsRef<Path> + std::fmt::Debug>(
     }
 }
 
+const USBIP_URL_SCHEME: &str = "usbip://";
+
+/// Attaches the host USB device at `dev_path`, or imports a device from a usbip server if
+/// `dev_path` is a `usbip://HOST:PORT/BUSID` url.
 pub fn do_usb_attach<T: AsRef<Path> + std::fmt::Debug>(
     socket_path: T,
     dev_path: &Path,
 ) -> ModifyUsbResult<UsbControlResult> {
-    let usb_file = open_file(dev_path, OpenOptions::new().read(true).write(true))
-        .map_err(|e| ModifyUsbError::FailedToOpenDevice(dev_path.into(), e))?;
+    let request = match dev_path
+        .to_str()
+        .and_then(|p| p.strip_prefix(USBIP_URL_SCHEME))
+    {
+        Some(url) => {
+            let (addr, busid) = parse_usbip_url(url)
+                .ok_or_else(|| ModifyUsbError::InvalidUsbipUrl(dev_path.display().to_string()))?;
+            let socket = connect_usbip_server(addr)
+                .map_err(|e| ModifyUsbError::FailedToOpenDevice(dev_path.into(), e.into()))?;
+            UsbControlCommand::AttachUsbip {
+                socket,
+                busid: busid.to_string(),
+            }
+        }
+        None => {
+            let file = open_file(dev_path, OpenOptions::new().read(true).write(true))
+                .map_err(|e| ModifyUsbError::FailedToOpenDevice(dev_path.into(), e))?;
+            UsbControlCommand::AttachDevice { file }
+        }
+    };
+
+    let request = VmRequest::UsbCommand(request);
+    let response =
+        handle_request(&request, socket_path).map_err(|_| ModifyUsbError::SocketFailed)?;
+    match response {
+        VmResponse::UsbResponse(usb_resp) => Ok(usb_resp),
+        r => Err(ModifyUsbError::UnexpectedResponse(r)),
+    }
+}
+
+// Splits the `HOST:PORT/BUSID` part of a usbip url.
+fn parse_usbip_url(url: &str) -> Option<(&str, &str)> {
+    let (addr, busid) = url.rsplit_once('/')?;
+    if addr.is_empty() || busid.is_empty() {
+        return None;
+    }
+    Some((addr, busid))
+}
+
+pub fn do_usb_attach_storage<T: AsRef<Path> + std::fmt::Debug>(
+    socket_path: T,
+    image_path: &Path,
+    read_only: bool,
+) -> ModifyUsbResult<UsbControlResult> {
+    let file = open_file(image_path, OpenOptions::new().read(true).write(!read_only))
+        .map_err(|e| ModifyUsbError::FailedToOpenDevice(image_path.into(), e))?;
+
+    let request = VmRequest::UsbCommand(UsbControlCommand::AttachStorage { file, read_only });
+    let response =
+        handle_request(&request, socket_path).map_err(|_| ModifyUsbError::SocketFailed)?;
+    match response {
+        VmResponse::UsbResponse(usb_resp) => Ok(usb_resp),
+        r => Err(ModifyUsbError::UnexpectedResponse(r)),
+    }
+}
+
+pub fn do_usb_attach_hid<T: AsRef<Path> + std::fmt::Debug>(
+    socket_path: T,
+    kind: UsbHidKind,
+    source_path: &Path,
+    evdev: bool,
+) -> ModifyUsbResult<UsbControlResult> {
+    let source = if evdev {
+        open_file(source_path, OpenOptions::new().read(true).write(true))
+    } else {
+        connect_event_socket(source_path).map_err(base::Error::from)
+    }
+    .map_err(|e| ModifyUsbError::FailedToOpenDevice(source_path.into(), e))?;
 
-    let request = VmRequest::UsbCommand(UsbControlCommand::AttachDevice { file: usb_file });
+    let request = VmRequest::UsbCommand(UsbControlCommand::AttachHid {
+        kind,
+        source,
+        evdev,
+    });
     let response =
         handle_request(&request, socket_path).map_err(|_| ModifyUsbError::SocketFailed)?;
     match response {
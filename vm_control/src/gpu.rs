@@ -5,8 +5,14 @@
 use std::collections::BTreeMap as Map;
 use std::fmt;
 use std::fmt::Display;
+use std::fs::File;
+use std::fs::OpenOptions;
+use std::io;
 use std::path::Path;
+use std::path::PathBuf;
+use std::str::FromStr;
 
+use base::with_as_descriptor;
 use serde::Deserialize;
 use serde::Serialize;
 use serde_keyvalue::FromKeyValues;
@@ -106,11 +112,64 @@ impl Default for DisplayParameters {
     }
 }
 
+/// Container format of a display recording.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum RecordingFormat {
+    /// Animated PNG with uncompressed frames.
+    Apng,
+    /// VP8 video in an IVF container. Requires the ffmpeg feature.
+    Ivf,
+}
+
+impl FromStr for RecordingFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "apng" => Ok(RecordingFormat::Apng),
+            "ivf" => Ok(RecordingFormat::Ivf),
+            _ => Err(format!("unknown recording format `{}`", s)),
+        }
+    }
+}
+
+impl Display for RecordingFormat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RecordingFormat::Apng => write!(f, "apng"),
+            RecordingFormat::Ivf => write!(f, "ivf"),
+        }
+    }
+}
+
+/// Writes the current contents of the display to `file` as a PNG image.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct DisplayScreenshot {
+    pub display_id: u32,
+    #[serde(with = "with_as_descriptor")]
+    pub file: File,
+}
+
+/// Starts writing every frame flushed to the display to `file`, until
+/// `GpuControlCommand::StopRecording` finalizes the file. The device picks the best available
+/// format if `format` is `None`.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct DisplayRecording {
+    pub display_id: u32,
+    #[serde(with = "with_as_descriptor")]
+    pub file: File,
+    pub format: Option<RecordingFormat>,
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub enum GpuControlCommand {
     AddDisplays { displays: Vec<DisplayParameters> },
     ListDisplays,
     RemoveDisplays { display_ids: Vec<u32> },
+    Screenshot(DisplayScreenshot),
+    StartRecording(DisplayRecording),
+    StopRecording { display_id: u32 },
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -123,6 +182,18 @@ pub enum GpuControlResult {
     NoSuchDisplay {
         display_id: u32,
     },
+    ScreenshotTaken {
+        width: u32,
+        height: u32,
+    },
+    ScreenshotFailed(String),
+    RecordingStarted {
+        format: RecordingFormat,
+    },
+    RecordingStopped {
+        frames: u64,
+    },
+    RecordingFailed(String),
 }
 
 impl Display for GpuControlResult {
@@ -141,6 +212,13 @@ impl Display for GpuControlResult {
             }
             TooManyDisplays(n) => write!(f, "too_many_displays {}", n),
             NoSuchDisplay { display_id } => write!(f, "no_such_display {}", display_id),
+            ScreenshotTaken { width, height } => {
+                write!(f, "screenshot_taken {}x{}", width, height)
+            }
+            ScreenshotFailed(e) => write!(f, "screenshot_failed {}", e),
+            RecordingStarted { format } => write!(f, "recording_started {}", format),
+            RecordingStopped { frames } => write!(f, "recording_stopped {}", frames),
+            RecordingFailed(e) => write!(f, "recording_failed {}", e),
         }
     }
 }
@@ -150,6 +228,7 @@ pub enum ModifyGpuError {
     UnexpectedResponse(VmResponse),
     UnknownCommand(String),
     GpuControl(GpuControlResult),
+    FailedToOpenFile(PathBuf, io::Error),
 }
 
 impl fmt::Display for ModifyGpuError {
@@ -161,6 +240,7 @@ impl fmt::Display for ModifyGpuError {
             UnexpectedResponse(r) => write!(f, "unexpected response: {}", r),
             UnknownCommand(c) => write!(f, "unknown display command: `{}`", c),
             GpuControl(e) => write!(f, "{}", e),
+            FailedToOpenFile(path, e) => write!(f, "failed to open {}: {}", path.display(), e),
         }
     }
 }
@@ -204,3 +284,55 @@ pub fn do_gpu_display_remove<T: AsRef<Path> + std::fmt::Debug>(
         .map_err(|_| ModifyGpuError::SocketFailed)?
         .into()
 }
+
+pub fn do_gpu_screenshot<T: AsRef<Path> + std::fmt::Debug>(
+    control_socket_path: T,
+    display_id: u32,
+    out_path: &Path,
+) -> ModifyGpuResult {
+    let file = OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(out_path)
+        .map_err(|e| ModifyGpuError::FailedToOpenFile(out_path.into(), e))?;
+    let request = VmRequest::GpuCommand(GpuControlCommand::Screenshot(DisplayScreenshot {
+        display_id,
+        file,
+    }));
+    handle_request(&request, control_socket_path)
+        .map_err(|_| ModifyGpuError::SocketFailed)?
+        .into()
+}
+
+pub fn do_gpu_start_recording<T: AsRef<Path> + std::fmt::Debug>(
+    control_socket_path: T,
+    display_id: u32,
+    out_path: &Path,
+    format: Option<RecordingFormat>,
+) -> ModifyGpuResult {
+    let file = OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(out_path)
+        .map_err(|e| ModifyGpuError::FailedToOpenFile(out_path.into(), e))?;
+    let request = VmRequest::GpuCommand(GpuControlCommand::StartRecording(DisplayRecording {
+        display_id,
+        file,
+        format,
+    }));
+    handle_request(&request, control_socket_path)
+        .map_err(|_| ModifyGpuError::SocketFailed)?
+        .into()
+}
+
+pub fn do_gpu_stop_recording<T: AsRef<Path> + std::fmt::Debug>(
+    control_socket_path: T,
+    display_id: u32,
+) -> ModifyGpuResult {
+    let request = VmRequest::GpuCommand(GpuControlCommand::StopRecording { display_id });
+    handle_request(&request, control_socket_path)
+        .map_err(|_| ModifyGpuError::SocketFailed)?
+        .into()
+}
@@ -129,6 +129,12 @@ pub enum VcpuControl {
     GetStates(mpsc::Sender<VmRunMode>),
     Snapshot(mpsc::Sender<anyhow::Result<VcpuSnapshot>>),
     Restore(mpsc::Sender<anyhow::Result<()>>, Box<VcpuSnapshot>),
+    /// Powers on the vCPU at `entry` with `context_id` in x0, on behalf of a PSCI CPU_ON call.
+    #[cfg(any(target_arch = "arm", target_arch = "aarch64"))]
+    PowerOn {
+        entry: u64,
+        context_id: u64,
+    },
 }
 
 /// Mode of execution for the VM.
@@ -233,12 +239,68 @@ pub enum DiskControlResult {
     Err(SysError),
 }
 
+#[derive(Serialize, Deserialize, Debug)]
+pub enum SndControlCommand {
+    /// Plug (`connected`) or unplug the jack `jack_id`.
+    SetJackConnected { jack_id: u32, connected: bool },
+}
+
+impl Display for SndControlCommand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use self::SndControlCommand::*;
+
+        match self {
+            SetJackConnected { jack_id, connected } => {
+                write!(f, "snd_set_jack_connected {} {}", jack_id, connected)
+            }
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub enum SndControlResult {
+    Ok,
+    Err(SysError),
+}
+
+/// Kind of an emulated USB HID device.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UsbHidKind {
+    Keyboard,
+    Mouse,
+    /// Absolute pointing device. The event source reports coordinates within `width` by `height`.
+    Tablet {
+        width: u32,
+        height: u32,
+    },
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub enum UsbControlCommand {
     AttachDevice {
         #[serde(with = "with_as_descriptor")]
         file: File,
     },
+    /// Attaches an emulated mass storage device backed by the disk image in `file`.
+    AttachStorage {
+        #[serde(with = "with_as_descriptor")]
+        file: File,
+        read_only: bool,
+    },
+    /// Attaches an emulated HID device fed by the input events read from `source`, either an
+    /// event device node when `evdev` is set or a socket sending virtio input events.
+    AttachHid {
+        kind: UsbHidKind,
+        #[serde(with = "with_as_descriptor")]
+        source: File,
+        evdev: bool,
+    },
+    /// Imports the device `busid` from the usbip server connected to `socket`.
+    AttachUsbip {
+        #[serde(with = "with_as_descriptor")]
+        socket: File,
+        busid: String,
+    },
     DetachDevice {
         port: u8,
     },
@@ -1147,16 +1209,88 @@ pub enum HotPlugDeviceType {
     UpstreamPort,
     DownstreamPort,
     EndPoint,
+    /// Virtio-pci device emulated by crosvm behind one of the PCIe hotplug root ports.
+    ///
+    /// `params` has the same syntax as the argument of the matching `crosvm run` option
+    /// (`--block`, `--net`, `--shared-dir`, `--vhost-user-blk`, `--vhost-user-fs` and
+    /// `--vhost-user-net`) and is parsed by the VM process, so paths in it must be absolute. It is
+    /// ignored when removing the device.
+    Virtio {
+        kind: VirtioHotPlugKind,
+        params: String,
+    },
 }
 
 // Used for VM to hotplug pci devices
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct HotPlugDeviceInfo {
     pub device_type: HotPlugDeviceType,
+    /// Sysfs path of the host device, or for `HotPlugDeviceType::Virtio`, the id chosen by the
+    /// caller to remove the device with.
     pub path: PathBuf,
     pub hp_interrupt: bool,
 }
 
+/// Severity of a PCIe error injected with `VmRequest::PcieAerInject`.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PcieAerErrorType {
+    Correctable,
+    NonFatal,
+    Fatal,
+}
+
+/// Kind of virtio-input device to create when hotplugging one.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VirtioInputKind {
+    Keyboard,
+    Mouse,
+    Switches,
+    Gamepad,
+    SingleTouch { width: u32, height: u32 },
+    MultiTouch { width: u32, height: u32 },
+    Trackpad { width: u32, height: u32 },
+}
+
+/// Commands to hotplug virtio-input devices whose events are read from a unix socket.
+///
+/// The socket carries raw `virtio_input_event` structs: 8 bytes each, made of a little-endian
+/// `u16` type, `u16` code and `u32` value, exactly like the sockets passed to `--keyboard`,
+/// `--mouse` and friends. The socket path doubles as the identifier of the device for removal.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub enum InputHotPlugCommand {
+    Add {
+        kind: VirtioInputKind,
+        socket_path: PathBuf,
+    },
+    Remove {
+        socket_path: PathBuf,
+    },
+}
+
+/// Kind of virtio-pci device to create when hotplugging one behind a PCIe root port.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VirtioHotPlugKind {
+    Block,
+    Net,
+    SharedDir,
+    VhostUserBlk,
+    VhostUserFs,
+    VhostUserNet,
+}
+
+/// Commands to change the state of the emulated ACPI thermal zone and processor performance
+/// states. Temperatures are in degrees Celsius.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThermalCommand {
+    SetTemperature(i32),
+    SetTripPoints {
+        passive: i32,
+        critical: i32,
+    },
+    /// Limit the processors to the performance states running at this frequency (MHz) at most.
+    SetFrequencyLimit(u32),
+}
+
 /// Message for communicating a suspend or resume to the virtio-pvclock device.
 #[derive(Serialize, Deserialize, Debug)]
 pub enum PvClockCommand {
@@ -1205,6 +1339,12 @@ pub enum VmRequest {
     Gpe(u32),
     /// Inject a PCI PME
     PciPme(u16),
+    /// Inject a PCIe AER error at the device with the requester ID `requester_id`. The error is
+    /// logged and signaled by the root port the device is behind.
+    PcieAerInject {
+        requester_id: u16,
+        error: PcieAerErrorType,
+    },
     /// Make the VM's RT VCPU real-time.
     MakeRT,
     /// Command for balloon driver.
@@ -1215,6 +1355,12 @@ pub enum VmRequest {
         disk_index: usize,
         command: DiskControlCommand,
     },
+    /// Send a command to a sound device chosen by `snd_index`.
+    /// `snd_index` is a 0-based count of `--virtio-snd` command-line options.
+    SndCommand {
+        snd_index: usize,
+        command: SndControlCommand,
+    },
     /// Command to use controller.
     UsbCommand(UsbControlCommand),
     #[cfg(feature = "gpu")]
@@ -1227,6 +1373,12 @@ pub enum VmRequest {
         device: HotPlugDeviceInfo,
         add: bool,
     },
+    /// Command to add/remove a virtio-input device fed from a socket
+    InputHotPlugCommand(InputHotPlugCommand),
+    /// Command to plug/unplug a vCPU
+    VcpuHotPlugCommand { cpu_id: usize, add: bool },
+    /// Command to change the thermal zone and the processor frequency limit
+    ThermalCommand(ThermalCommand),
     /// Command to Snapshot devices
     Snapshot(SnapshotCommand),
     /// Command to Restore devices
@@ -1272,6 +1424,23 @@ pub fn handle_disk_command(command: &DiskControlCommand, disk_host_tube: &Tube)
     }
 }
 
+pub fn handle_snd_command(command: &SndControlCommand, snd_host_tube: &Tube) -> VmResponse {
+    // Forward the request to the sound device process via its control socket.
+    if let Err(e) = snd_host_tube.send(command) {
+        error!("snd socket send failed: {}", e);
+        return VmResponse::Err(SysError::new(EINVAL));
+    }
+
+    match snd_host_tube.recv() {
+        Ok(SndControlResult::Ok) => VmResponse::Ok,
+        Ok(SndControlResult::Err(e)) => VmResponse::Err(e),
+        Err(e) => {
+            error!("snd socket recv failed: {}", e);
+            VmResponse::Err(SysError::new(EINVAL))
+        }
+    }
+}
+
 /// WARNING: descriptor must be a mapping handle on Windows.
 fn map_descriptor(
     descriptor: &dyn AsRawDescriptor,
@@ -1429,6 +1598,7 @@ impl VmRequest {
         #[cfg(feature = "balloon")] balloon_stats_id: &mut u64,
         #[cfg(feature = "balloon")] balloon_wss_id: &mut u64,
         disk_host_tubes: &[Tube],
+        snd_host_tubes: &[Tube],
         pm: &mut Option<Arc<Mutex<dyn PmResource + Send>>>,
         #[cfg(feature = "gpu")] gpu_control_tube: &Tube,
         usb_control_tube: Option<&Tube>,
@@ -1746,6 +1916,13 @@ impl VmRequest {
                 Some(tube) => handle_disk_command(command, tube),
                 None => VmResponse::Err(SysError::new(ENODEV)),
             },
+            VmRequest::SndCommand {
+                snd_index,
+                ref command,
+            } => match &snd_host_tubes.get(snd_index) {
+                Some(tube) => handle_snd_command(command, tube),
+                None => VmResponse::Err(SysError::new(ENODEV)),
+            },
             #[cfg(feature = "gpu")]
             VmRequest::GpuCommand(ref cmd) => {
                 let res = gpu_control_tube.send(cmd);
@@ -1808,6 +1985,13 @@ impl VmRequest {
                 }
             }
             VmRequest::HotPlugCommand { device: _, add: _ } => VmResponse::Ok,
+            VmRequest::InputHotPlugCommand(_) => VmResponse::Ok,
+            VmRequest::VcpuHotPlugCommand { cpu_id: _, add: _ } => VmResponse::Ok,
+            VmRequest::ThermalCommand(_) => VmResponse::Ok,
+            VmRequest::PcieAerInject {
+                requester_id: _,
+                error: _,
+            } => VmResponse::Ok,
             VmRequest::Snapshot(SnapshotCommand::Take { ref snapshot_path }) => {
                 let f = || -> anyhow::Result<VmResponse> {
                     let _vcpu_guard = VcpuSuspendGuard::new(&kick_vcpus, vcpu_size)?;
@@ -2140,6 +2324,72 @@ mod tests {
         recv_event.signal().unwrap();
         e1.wait().unwrap();
     }
+
+    #[test]
+    fn input_hotplug_request_serde() {
+        let (req, res) = Tube::pair().unwrap();
+        req.send(&VmRequest::InputHotPlugCommand(InputHotPlugCommand::Add {
+            kind: VirtioInputKind::SingleTouch {
+                width: 800,
+                height: 600,
+            },
+            socket_path: PathBuf::from("/run/touch.sock"),
+        }))
+        .unwrap();
+        req.send(&VmRequest::InputHotPlugCommand(
+            InputHotPlugCommand::Remove {
+                socket_path: PathBuf::from("/run/touch.sock"),
+            },
+        ))
+        .unwrap();
+
+        match res.recv::<VmRequest>().unwrap() {
+            VmRequest::InputHotPlugCommand(InputHotPlugCommand::Add { kind, socket_path }) => {
+                assert_eq!(
+                    kind,
+                    VirtioInputKind::SingleTouch {
+                        width: 800,
+                        height: 600
+                    }
+                );
+                assert_eq!(socket_path, PathBuf::from("/run/touch.sock"));
+            }
+            request => panic!("unexpected request: {:?}", request),
+        }
+        match res.recv::<VmRequest>().unwrap() {
+            VmRequest::InputHotPlugCommand(InputHotPlugCommand::Remove { socket_path }) => {
+                assert_eq!(socket_path, PathBuf::from("/run/touch.sock"));
+            }
+            request => panic!("unexpected request: {:?}", request),
+        }
+    }
+
+    #[test]
+    fn input_kind_serde() {
+        let (req, res) = Tube::pair().unwrap();
+        let kinds = [
+            VirtioInputKind::Keyboard,
+            VirtioInputKind::Mouse,
+            VirtioInputKind::Switches,
+            VirtioInputKind::Gamepad,
+            VirtioInputKind::SingleTouch {
+                width: 1,
+                height: 2,
+            },
+            VirtioInputKind::MultiTouch {
+                width: 3,
+                height: 4,
+            },
+            VirtioInputKind::Trackpad {
+                width: 5,
+                height: 6,
+            },
+        ];
+        for kind in kinds {
+            req.send(&kind).unwrap();
+            assert_eq!(res.recv::<VirtioInputKind>().unwrap(), kind);
+        }
+    }
 }
 
 #[sorted]
@@ -19,4 +19,7 @@ cfg_if::cfg_if! {
     }
 }
 
-pub use platform::{handle_request, prepare_shared_memory_region, should_prepare_memory_region};
+pub use platform::{
+    connect_event_socket, connect_usbip_server, handle_request, prepare_shared_memory_region,
+    should_prepare_memory_region,
+};
@@ -5,6 +5,10 @@
 #[cfg(feature = "gpu")]
 pub(crate) mod gpu;
 
+use std::fs::File;
+use std::net::TcpStream;
+use std::os::unix::io::OwnedFd;
+use std::os::unix::net::UnixStream;
 use std::path::Path;
 
 use base::error;
@@ -64,6 +68,18 @@ pub fn handle_request<T: AsRef<Path> + std::fmt::Debug>(
     }
 }
 
+/// Connects to the unix socket at `path`, which sends input events to an emulated device.
+pub fn connect_event_socket(path: &Path) -> std::io::Result<File> {
+    let stream = UnixStream::connect(path)?;
+    Ok(File::from(OwnedFd::from(stream)))
+}
+
+/// Connects to the usbip server at `addr`, in the `host:port` form.
+pub fn connect_usbip_server(addr: &str) -> std::io::Result<File> {
+    let stream = TcpStream::connect(addr)?;
+    Ok(File::from(OwnedFd::from(stream)))
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub enum VmMsyncRequest {
     /// Flush the content of a memory mapping to its backing file.
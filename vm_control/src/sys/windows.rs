@@ -5,6 +5,7 @@
 #[cfg(feature = "gpu")]
 pub(crate) mod gpu;
 
+use std::fs::File;
 use std::io::Result;
 use std::mem::size_of;
 use std::path::Path;
@@ -31,6 +32,14 @@ pub fn handle_request<T: AsRef<Path> + std::fmt::Debug>(
     Err(())
 }
 
+pub fn connect_event_socket(_path: &Path) -> Result<File> {
+    Err(std::io::Error::from(std::io::ErrorKind::Unsupported))
+}
+
+pub fn connect_usbip_server(_addr: &str) -> Result<File> {
+    Err(std::io::Error::from(std::io::ErrorKind::Unsupported))
+}
+
 /// Send the size header first and then the protbuf message.
 ///
 /// A helper function to keep communication with service consistent across crosvm code.
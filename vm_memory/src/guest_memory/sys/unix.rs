@@ -46,6 +46,16 @@ impl GuestMemory {
             .map_err(|e| Error::MemoryAccess(addr, e))
     }
 
+    /// Allocates the host memory backing the given guest range from host NUMA node `node`.
+    ///
+    /// The range must be within a single memory region.
+    pub fn bind_range_to_host_node(&self, addr: GuestAddress, count: u64, node: u32) -> Result<()> {
+        let (mapping, offset, _) = self.find_region(addr)?;
+        mapping
+            .bind_to_host_node(offset, count as usize, node)
+            .map_err(|e| Error::MemoryAccess(addr, e))
+    }
+
     /// Handles guest memory policy hints/advices.
     pub fn set_memory_policy(&self, mem_policy: MemoryPolicy) {
         if mem_policy.is_empty() {
@@ -12,7 +12,10 @@ use acpi_tables::aml;
 use acpi_tables::facs::FACS;
 use acpi_tables::rsdp::RSDP;
 use acpi_tables::sdt::SDT;
+use arch::numa::numa_distance;
 use arch::CpuSet;
+use arch::NumaMemoryRange;
+use arch::NumaNode;
 use arch::VcpuAffinity;
 use base::error;
 use base::warn;
@@ -87,6 +90,46 @@ struct Localx2Apic {
     _processor_id: u32,
 }
 
+#[repr(C, packed)]
+#[derive(Clone, Copy, Default, AsBytes)]
+struct SratLocalApicAffinity {
+    _type: u8,
+    _length: u8,
+    _proximity_domain_lo: u8,
+    _apic_id: u8,
+    _flags: u32,
+    _sapic_eid: u8,
+    _proximity_domain_hi: [u8; 3],
+    _clock_domain: u32,
+}
+
+#[repr(C, packed)]
+#[derive(Clone, Copy, Default, AsBytes)]
+struct SratMemoryAffinity {
+    _type: u8,
+    _length: u8,
+    _proximity_domain: u32,
+    _reserved1: u16,
+    _base_address: u64,
+    _address_length: u64,
+    _reserved2: u32,
+    _flags: u32,
+    _reserved3: u64,
+}
+
+#[repr(C, packed)]
+#[derive(Clone, Copy, Default, AsBytes)]
+struct SratX2ApicAffinity {
+    _type: u8,
+    _length: u8,
+    _reserved1: u16,
+    _proximity_domain: u32,
+    _x2apic_id: u32,
+    _flags: u32,
+    _clock_domain: u32,
+    _reserved2: u32,
+}
+
 // Space ID for GenericAddress
 const ADR_SPACE_SYSTEM_IO: u8 = 1;
 
@@ -158,6 +201,7 @@ const MADT_TYPE_INTERRUPT_SOURCE_OVERRIDE: u8 = 2;
 const MADT_TYPE_LOCAL_X2APIC: u8 = 9;
 // MADT flags
 const MADT_ENABLED: u32 = 1;
+const MADT_ONLINE_CAPABLE: u32 = 1 << 1;
 const MADT_INT_POLARITY_ACTIVE_LOW: u16 = 0b11;
 const MADT_INT_TRIGGER_LEVEL: u16 = 0b11 << 2;
 // MADT compatibility
@@ -174,6 +218,22 @@ const MCFG_FIELD_BASE_ADDRESS: usize = 44;
 const MCFG_FIELD_START_BUS_NUMBER: usize = 54;
 const MCFG_FIELD_END_BUS_NUMBER: usize = 55;
 
+// SRAT
+const SRAT_LEN: u32 = 48;
+const SRAT_REVISION: u8 = 3;
+const SRAT_FIELD_TABLE_REVISION: usize = 36;
+// SRAT types
+const SRAT_TYPE_LOCAL_APIC_AFFINITY: u8 = 0;
+const SRAT_TYPE_MEMORY_AFFINITY: u8 = 1;
+const SRAT_TYPE_X2APIC_AFFINITY: u8 = 2;
+// SRAT flags
+const SRAT_ENABLED: u32 = 1;
+
+// SLIT
+const SLIT_LEN: u32 = 44;
+const SLIT_REVISION: u8 = 1;
+const SLIT_FIELD_NUMBER_OF_LOCALITIES: usize = 36;
+
 const SSDT_REVISION: u8 = 2;
 pub fn create_customize_ssdt(
     pci_root: Arc<Mutex<PciRoot>>,
@@ -409,6 +469,91 @@ fn write_facp_overrides(
     facp.write(FADT_FIELD_RESET_VALUE, reset_value);
 }
 
+/// Creates the SRAT assigning the vCPUs, by their apic ids, and the memory ranges to the proximity
+/// domains of the NUMA nodes.
+fn create_srat_table(
+    numa_nodes: &[NumaNode],
+    numa_ranges: &[NumaMemoryRange],
+    apic_ids: &[usize],
+) -> SDT {
+    let mut srat = SDT::new(
+        *b"SRAT",
+        SRAT_LEN,
+        SRAT_REVISION,
+        *b"CROSVM",
+        *b"CROSVMDT",
+        OEM_REVISION,
+    );
+    // Reserved field that must be 1 for backward compatibility.
+    srat.write(SRAT_FIELD_TABLE_REVISION, 1u32);
+
+    for (node_id, node) in numa_nodes.iter().enumerate() {
+        for cpu in node.cpus.iter() {
+            let apic_id = match apic_ids.get(*cpu) {
+                Some(apic_id) => *apic_id as u32,
+                None => continue,
+            };
+            if apic_id < MADT_MIN_LOCAL_APIC_ID {
+                srat.append(SratLocalApicAffinity {
+                    _type: SRAT_TYPE_LOCAL_APIC_AFFINITY,
+                    _length: std::mem::size_of::<SratLocalApicAffinity>() as u8,
+                    _proximity_domain_lo: node_id as u8,
+                    _apic_id: apic_id as u8,
+                    _flags: SRAT_ENABLED,
+                    _proximity_domain_hi: [
+                        (node_id >> 8) as u8,
+                        (node_id >> 16) as u8,
+                        (node_id >> 24) as u8,
+                    ],
+                    ..Default::default()
+                });
+            } else {
+                srat.append(SratX2ApicAffinity {
+                    _type: SRAT_TYPE_X2APIC_AFFINITY,
+                    _length: std::mem::size_of::<SratX2ApicAffinity>() as u8,
+                    _proximity_domain: node_id as u32,
+                    _x2apic_id: apic_id,
+                    _flags: SRAT_ENABLED,
+                    ..Default::default()
+                });
+            }
+        }
+    }
+
+    for range in numa_ranges {
+        srat.append(SratMemoryAffinity {
+            _type: SRAT_TYPE_MEMORY_AFFINITY,
+            _length: std::mem::size_of::<SratMemoryAffinity>() as u8,
+            _proximity_domain: range.node as u32,
+            _base_address: range.address.offset(),
+            _address_length: range.size,
+            _flags: SRAT_ENABLED,
+            ..Default::default()
+        });
+    }
+
+    srat
+}
+
+/// Creates the SLIT with the distances between the NUMA nodes.
+fn create_slit_table(numa_nodes: &[NumaNode]) -> SDT {
+    let mut slit = SDT::new(
+        *b"SLIT",
+        SLIT_LEN,
+        SLIT_REVISION,
+        *b"CROSVM",
+        *b"CROSVMDT",
+        OEM_REVISION,
+    );
+    slit.write(SLIT_FIELD_NUMBER_OF_LOCALITIES, numa_nodes.len() as u64);
+    for from in 0..numa_nodes.len() {
+        for to in 0..numa_nodes.len() {
+            slit.append(numa_distance(numa_nodes, from, to));
+        }
+    }
+    slit
+}
+
 fn next_offset(offset: GuestAddress, len: u64) -> Option<GuestAddress> {
     // Enforce 64-byte allocation alignment.
     match len % 64 {
@@ -514,6 +659,8 @@ fn sync_acpi_id_from_cpuid(
 ///
 /// * `guest_mem` - The guest memory where the tables will be stored.
 /// * `num_cpus` - Used to construct the MADT.
+/// * `num_boot_cpus` - Number of CPUs enabled at boot in the MADT when the others can be
+///                     hot-plugged.
 /// * `sci_irq` - Used to fill the FACP SCI_INTERRUPT field, which
 ///               is going to be used by the ACPI drivers to register
 ///               sci handler.
@@ -527,11 +674,14 @@ fn sync_acpi_id_from_cpuid(
 ///               interrupt pin assignment).
 /// * `pcie_cfg_mmio` - Base address for the pcie enhanced configuration access mechanism
 /// *  `max_bus` - Max bus number in MCFG table
+/// * `numa_nodes` - NUMA nodes of the guest, used to construct the SRAT and SLIT.
+/// * `numa_ranges` - Guest memory ranges of the NUMA nodes.
 ///
 
 pub fn create_acpi_tables(
     guest_mem: &GuestMemory,
     num_cpus: u8,
+    num_boot_cpus: Option<u8>,
     sci_irq: u32,
     reset_port: u32,
     reset_value: u8,
@@ -542,6 +692,8 @@ pub fn create_acpi_tables(
     pcie_cfg_mmio: u64,
     max_bus: u8,
     force_s2idle: bool,
+    numa_nodes: &[NumaNode],
+    numa_ranges: &[NumaMemoryRange],
 ) -> Option<GuestAddress> {
     // RSDP is at the HI RSDP WINDOW
     let rsdp_offset = GuestAddress(super::ACPI_HI_RSDP_WINDOW_BASE);
@@ -643,7 +795,10 @@ pub fn create_acpi_tables(
                     _length: std::mem::size_of::<LocalApic>() as u8,
                     _processor_id: cpu,
                     _apic_id: cpu,
-                    _flags: MADT_ENABLED,
+                    _flags: match num_boot_cpus {
+                        Some(num_boot_cpus) if cpu >= num_boot_cpus => MADT_ONLINE_CAPABLE,
+                        _ => MADT_ENABLED,
+                    },
                 };
                 madt.append(apic);
                 apic_ids.push(cpu as usize);
@@ -695,6 +850,20 @@ pub fn create_acpi_tables(
     tables.push(offset.0);
     offset = next_offset(offset, madt.len() as u64)?;
 
+    if !numa_nodes.is_empty() {
+        // SRAT
+        let srat = create_srat_table(numa_nodes, numa_ranges, apic_ids);
+        guest_mem.write_at_addr(srat.as_slice(), offset).ok()?;
+        tables.push(offset.0);
+        offset = next_offset(offset, srat.len() as u64)?;
+
+        // SLIT
+        let slit = create_slit_table(numa_nodes);
+        guest_mem.write_at_addr(slit.as_slice(), offset).ok()?;
+        tables.push(offset.0);
+        offset = next_offset(offset, slit.len() as u64)?;
+    }
+
     // MCFG
     let mut mcfg = SDT::new(
         *b"MCFG",
@@ -764,4 +933,52 @@ mod tests {
             devices::cmos::RTC_REG_ALARM_MONTH
         );
     }
+
+    #[test]
+    fn srat_slit_table_creation() {
+        let numa_nodes = [
+            NumaNode {
+                cpus: CpuSet::new([0, 1]),
+                memory_size: 0x1000,
+                ..Default::default()
+            },
+            NumaNode {
+                cpus: CpuSet::new([2]),
+                memory_size: 0x1000,
+                distances: vec![30, 10],
+                ..Default::default()
+            },
+        ];
+        let numa_ranges = [
+            NumaMemoryRange {
+                node: 0,
+                address: GuestAddress(0x0),
+                size: 0x1000,
+            },
+            NumaMemoryRange {
+                node: 1,
+                address: GuestAddress(0x1000),
+                size: 0x1000,
+            },
+        ];
+        // vCPU 2 needs an x2APIC affinity structure.
+        let apic_ids = [0, 1, 300];
+
+        let srat = create_srat_table(&numa_nodes, &numa_ranges, &apic_ids);
+        assert_eq!(
+            srat.len(),
+            SRAT_LEN as usize
+                + 2 * std::mem::size_of::<SratLocalApicAffinity>()
+                + std::mem::size_of::<SratX2ApicAffinity>()
+                + 2 * std::mem::size_of::<SratMemoryAffinity>()
+        );
+        // Proximity domain of the second memory range.
+        let second_range = srat.len() - std::mem::size_of::<SratMemoryAffinity>();
+        assert_eq!(srat.read::<u32>(second_range + 2), 1);
+        assert_eq!(srat.read::<u64>(second_range + 8), 0x1000);
+
+        let slit = create_slit_table(&numa_nodes);
+        assert_eq!(slit.read::<u64>(SLIT_FIELD_NUMBER_OF_LOCALITIES), 2);
+        assert_eq!(&slit.as_slice()[SLIT_LEN as usize..], &[10, 20, 30, 10]);
+    }
 }
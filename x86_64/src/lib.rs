@@ -80,6 +80,7 @@ pub use cpuid::CpuIdContext;
 use devices::BusDevice;
 use devices::BusDeviceObj;
 use devices::BusResumeDevice;
+use devices::CpuHotPlugController;
 use devices::Debugcon;
 use devices::IrqChip;
 use devices::IrqChipX86_64;
@@ -98,6 +99,11 @@ use devices::Serial;
 use devices::SerialHardware;
 use devices::SerialParameters;
 #[cfg(unix)]
+use devices::ThermalController;
+#[cfg(unix)]
+use devices::ThermalState;
+use devices::VcpuHotPlugState;
+#[cfg(unix)]
 use devices::VirtualPmc;
 #[cfg(feature = "gdb")]
 use gdbstub_arch::x86::reg::id::X86_64CoreRegId;
@@ -159,6 +165,9 @@ pub enum Error {
     AllocateIOResouce(resources::Error),
     #[error("error allocating a single irq")]
     AllocateIrq,
+    #[cfg(unix)]
+    #[error("failed to bind guest memory to host NUMA node: {0}")]
+    BindNumaMemory(GuestMemoryError),
     #[error("unable to clone an Event: {0}")]
     CloneEvent(base::Error),
     #[error("failed to clone IRQ chip: {0}")]
@@ -201,6 +210,9 @@ pub enum Error {
     CreateSerialDevices(arch::DeviceRegistrationError),
     #[error("failed to create socket: {0}")]
     CreateSocket(io::Error),
+    #[cfg(unix)]
+    #[error("invalid thermal configuration: {0}")]
+    CreateThermal(devices::ThermalError),
     #[error("failed to create VCPU: {0}")]
     CreateVcpu(base::Error),
     #[error("failed to create Virtio MMIO bus: {0}")]
@@ -710,6 +722,17 @@ impl arch::LinuxArch for X8664arch {
 
         let mem = vm.get_memory().clone();
 
+        // The NUMA nodes share the guest RAM, leaving out the BIOS region.
+        let ram_regions: Vec<(GuestAddress, u64)> =
+            arch_memory_regions(components.memory_size, None)
+                .into_iter()
+                .map(|(addr, size, _)| (addr, size))
+                .collect();
+        let numa_ranges = arch::numa::numa_memory_ranges(&components.numa_nodes, &ram_regions);
+        #[cfg(unix)]
+        arch::numa::bind_numa_memory(&mem, &components.numa_nodes, &numa_ranges)
+            .map_err(Error::BindNumaMemory)?;
+
         let vcpu_count = components.vcpu_count;
 
         let tss_addr = GuestAddress(TSS_ADDR);
@@ -876,6 +899,22 @@ impl arch::LinuxArch for X8664arch {
 
         // each bus occupy 1MB mmio for pcie enhanced configuration
         let max_bus = (pcie_cfg_mmio_len / 0x100000 - 1) as u8;
+        let vcpu_hotplug = components.boot_vcpu_count.map(|boot_vcpu_count| {
+            Arc::new(Mutex::new(VcpuHotPlugState::new(
+                vcpu_count,
+                boot_vcpu_count,
+            )))
+        });
+        #[cfg(unix)]
+        let thermal = components
+            .thermal
+            .as_ref()
+            .map(|config| {
+                ThermalState::new(config, vcpu_count).map(|state| Arc::new(Mutex::new(state)))
+            })
+            .transpose()
+            .map_err(Error::CreateThermal)?;
+
         let (mut acpi_dev_resource, bat_control) = Self::setup_acpi_devices(
             pci.clone(),
             &mem,
@@ -900,6 +939,9 @@ impl arch::LinuxArch for X8664arch {
             components.ac_adapter,
             #[cfg(unix)]
             guest_suspended_cvar,
+            vcpu_hotplug.as_ref(),
+            #[cfg(unix)]
+            thermal.as_ref(),
         )?;
 
         // Create customized SSDT table
@@ -925,8 +967,13 @@ impl arch::LinuxArch for X8664arch {
             mptable::setup_mptable(&mem, vcpu_count as u8, &pci_irqs)
                 .map_err(Error::SetupMptable)?;
         }
-        smbios::setup_smbios(&mem, components.dmi_path, &components.oem_strings)
-            .map_err(Error::SetupSmbios)?;
+        smbios::setup_smbios(
+            &mem,
+            components.dmi_path,
+            &components.oem_strings,
+            &components.smbios,
+        )
+        .map_err(Error::SetupSmbios)?;
 
         let host_cpus = if components.host_cpu_topology {
             components.vcpu_affinity.clone()
@@ -938,6 +985,7 @@ impl arch::LinuxArch for X8664arch {
         acpi::create_acpi_tables(
             &mem,
             vcpu_count as u8,
+            components.boot_vcpu_count.map(|count| count as u8),
             sci_irq,
             0xcf9,
             6, // RST_CPU|SYS_RST
@@ -948,6 +996,8 @@ impl arch::LinuxArch for X8664arch {
             pcie_cfg_mmio_range.start,
             max_bus,
             components.force_s2idle,
+            &components.numa_nodes,
+            &numa_ranges,
         )
         .ok_or(Error::CreateAcpi)?;
 
@@ -1025,6 +1075,7 @@ impl arch::LinuxArch for X8664arch {
         Ok(RunnableLinuxVm {
             vm,
             vcpu_count,
+            vcpu_hotplug,
             vcpus: None,
             vcpu_affinity: components.vcpu_affinity,
             vcpu_init,
@@ -1036,6 +1087,8 @@ impl arch::LinuxArch for X8664arch {
             pid_debug_label_map,
             suspend_evt,
             resume_notify_devices,
+            #[cfg(unix)]
+            thermal,
             rt_cpus: components.rt_cpus,
             delay_rt: components.delay_rt,
             bat_control,
@@ -1420,7 +1473,11 @@ const PCI_HB_OSC_CONTROL_PCIE_AER: u32 = 0x8;
 #[allow(dead_code)]
 const PCI_HB_OSC_CONTROL_PCIE_CAP: u32 = 0x10;
 
-struct PciRootOSC {}
+struct PciRootOSC {
+    // Whether the root ports implement the AER capability, so that the guest can take native
+    // control of AER.
+    aer: bool,
+}
 
 // Method (_OSC, 4, NotSerialized)  // _OSC: Operating System Capabilities
 // {
@@ -1430,7 +1487,7 @@ struct PciRootOSC {}
 //         CreateDWordField (Arg3, 8, CDW3) // control field
 //         if ( 0 == (CDW1 & 0x01))  // Query flag ?
 //         {
-//              CDW3 &= !(SHPC_HP | AER)
+//              CDW3 &= !(SHPC_HP | AER) // AER is kept if the root ports implement it
 //         }
 //     } Else {
 //         CDW1 |= UNSUPPORT_UUID
@@ -1440,9 +1497,13 @@ struct PciRootOSC {}
 impl Aml for PciRootOSC {
     fn to_aml_bytes(&self, aml: &mut Vec<u8>) {
         let osc_uuid = "33DB4D5B-1FF7-401C-9657-7441C03DD766";
-        // virtual pcie root port supports hotplug, pme, and pcie cap register, clear all
-        // the other bits.
-        let mask = !(PCI_HB_OSC_CONTROL_SHPC_HP | PCI_HB_OSC_CONTROL_PCIE_AER);
+        // virtual pcie root port supports hotplug, pme, pcie cap register and, if the root ports
+        // have the capability, aer. Clear all the other bits.
+        let mask = if self.aer {
+            !PCI_HB_OSC_CONTROL_SHPC_HP
+        } else {
+            !(PCI_HB_OSC_CONTROL_SHPC_HP | PCI_HB_OSC_CONTROL_PCIE_AER)
+        };
         aml::Method::new(
             "_OSC".into(),
             4,
@@ -1792,6 +1853,8 @@ impl X8664arch {
     /// * - `irq_chip` the IrqChip object for registering irq events
     /// * - `battery` indicate whether to create the battery
     /// * - `mmio_bus` the MMIO bus to add the devices to
+    /// * - `vcpu_hotplug` the plugged state of the vCPUs, if vCPU hotplug is enabled
+    /// * - `thermal` the state of the emulated thermal zone, if enabled
     pub fn setup_acpi_devices(
         pci_root: Arc<Mutex<PciRoot>>,
         mem: &GuestMemory,
@@ -1811,6 +1874,8 @@ impl X8664arch {
         #[cfg(feature = "swap")] swap_controller: Option<&swap::SwapController>,
         #[cfg(unix)] ac_adapter: bool,
         #[cfg(unix)] guest_suspended_cvar: Option<Arc<(Mutex<bool>, Condvar)>>,
+        vcpu_hotplug: Option<&Arc<Mutex<VcpuHotPlugState>>>,
+        #[cfg(unix)] thermal: Option<&Arc<Mutex<ThermalState>>>,
     ) -> Result<(acpi::AcpiDevResource, Option<BatControl>)> {
         // The AML data for the acpi devices
         let mut amls = Vec::new();
@@ -1950,6 +2015,69 @@ impl X8664arch {
             pmc_virtio_mmio.lock().to_aml_bytes(&mut amls);
         }
 
+        if let Some(vcpu_hotplug) = vcpu_hotplug {
+            // Allocate GPE for vCPU hotplug notification
+            let gpe = resources.allocate_gpe().ok_or(Error::AllocateGpe)?;
+            vcpu_hotplug.lock().set_gpe(gpe);
+
+            let alloc = resources.get_anon_alloc();
+            let mmio_base = resources
+                .allocate_mmio(
+                    devices::cpu_hotplug::CPU_HOTPLUG_MMIO_SIZE,
+                    alloc,
+                    "CpuHotPlugController".to_string(),
+                    resources::AllocOptions::new()
+                        .align(devices::cpu_hotplug::CPU_HOTPLUG_MMIO_SIZE),
+                )
+                .map_err(Error::AllocateIOResouce)?;
+            let controller = Arc::new(Mutex::new(CpuHotPlugController::new(
+                vcpu_hotplug.clone(),
+                mmio_base,
+                gpe,
+            )));
+            mmio_bus
+                .insert(
+                    controller.clone(),
+                    mmio_base,
+                    devices::cpu_hotplug::CPU_HOTPLUG_MMIO_SIZE,
+                )
+                .map_err(Error::InsertBus)?;
+            controller.lock().to_aml_bytes(&mut amls);
+        }
+
+        #[cfg(unix)]
+        if let Some(thermal) = thermal {
+            // Allocate GPE for thermal and performance state notification
+            let gpe = resources.allocate_gpe().ok_or(Error::AllocateGpe)?;
+            thermal.lock().set_gpe(gpe);
+
+            let alloc = resources.get_anon_alloc();
+            let io_base = resources
+                .io_allocator()
+                .ok_or(Error::AllocateIOResouce(resources::Error::NoIoAllocator))?
+                .allocate_with_align(
+                    devices::thermal::THERMAL_IO_SIZE,
+                    alloc,
+                    "ThermalController".to_string(),
+                    4, // must be 32-bit aligned
+                )
+                .map_err(Error::AllocateIOResouce)?;
+            let controller = Arc::new(Mutex::new(ThermalController::new(
+                thermal.clone(),
+                io_base,
+                gpe,
+                vcpu_hotplug.is_some(),
+            )));
+            io_bus
+                .insert(
+                    controller.clone(),
+                    io_base,
+                    devices::thermal::THERMAL_IO_SIZE,
+                )
+                .unwrap();
+            controller.lock().to_aml_bytes(&mut amls);
+        }
+
         let mut pmresource = devices::ACPIPMResource::new(
             pm_sci_evt.try_clone().map_err(Error::CloneEvent)?,
             #[cfg(feature = "direct")]
@@ -1968,6 +2096,11 @@ impl X8664arch {
             .map_err(Error::RegisterIrqfd)?;
         pmresource.start();
 
+        #[cfg(unix)]
+        let pcie_aer = devices::has_pcie_aer_root_ports();
+        #[cfg(windows)]
+        let pcie_aer = false;
+
         let mut crs_entries: Vec<Box<dyn Aml>> = vec![
             Box::new(aml::AddressSpace::new_bus_number(0x0u16, max_bus as u16)),
             Box::new(aml::IO::new(0xcf8, 0xcf8, 1, 0x8)),
@@ -2003,7 +2136,7 @@ impl X8664arch {
                     "_CRS".into(),
                     &aml::ResourceTemplate::new(crs_entries.iter().map(|b| b.as_ref()).collect()),
                 ),
-                &PciRootOSC {},
+                &PciRootOSC { aer: pcie_aer },
             ],
         )
         .to_aml_bytes(&mut amls);
@@ -10,6 +10,15 @@ use std::path::PathBuf;
 use std::result;
 use std::slice;
 
+use arch::smbios::SmbiosBaseboardConfig;
+use arch::smbios::SmbiosBiosConfig;
+use arch::smbios::SmbiosChassisConfig;
+use arch::smbios::SmbiosEntryPoint;
+use arch::smbios::SmbiosMemoryDeviceConfig;
+use arch::smbios::SmbiosProcessorConfig;
+use arch::smbios::SmbiosRawStructure;
+use arch::smbios::SmbiosSystemConfig;
+use arch::SmbiosConfig;
 use remain::sorted;
 use thiserror::Error;
 use vm_memory::GuestAddress;
@@ -32,6 +41,9 @@ pub enum Error {
     /// Incorrect or not readable host SMBIOS data
     #[error("Failure to read host SMBIOS data")]
     InvalidInput,
+    /// A raw SMBIOS structure is too long, has an empty string, or is an end-of-table structure
+    #[error("Invalid raw SMBIOS structure of type {0}")]
+    InvalidRawStructure(u8),
     /// Failure while reading SMBIOS data file
     #[error("Failure while reading SMBIOS data file")]
     IoFailed,
@@ -44,9 +56,18 @@ pub enum Error {
     /// Failure while opening SMBIOS data file
     #[error("Failure while opening SMBIOS data file {1}: {0}")]
     OpenFailed(std::io::Error, PathBuf),
+    /// A provided SMBIOS string contained a null character
+    #[error("SMBIOS string {0:?} contains a null character")]
+    StringHasNullCharacter(String),
+    /// The structure table is larger than what a 2.x entry point can describe
+    #[error("The SMBIOS table is too large for a 2.x entry point")]
+    TableTooLarge,
     /// Too many OEM strings provided
     #[error("Too many OEM strings were provided, limited to 255")]
     TooManyOemStrings,
+    /// Too many strings provided for a structure
+    #[error("Too many strings in the SMBIOS structure of type {0}, limited to 255")]
+    TooManyStrings(u8),
     /// Failure to write additional data to memory
     #[error("Failure to write additional data to memory")]
     WriteData,
@@ -61,15 +82,43 @@ const SMBIOS_START: u64 = 0xf0000; // First possible location per the spec.
 
 // Constants sourced from SMBIOS Spec 2.3.1.
 const SM2_MAGIC_IDENT: &[u8; 4usize] = b"_SM_";
+const DMI_MAGIC_IDENT: &[u8; 5usize] = b"_DMI_";
 
 // Constants sourced from SMBIOS Spec 3.2.0.
 const SM3_MAGIC_IDENT: &[u8; 5usize] = b"_SM3_";
 const BIOS_INFORMATION: u8 = 0;
 const SYSTEM_INFORMATION: u8 = 1;
+const BASEBOARD_INFORMATION: u8 = 2;
+const CHASSIS_INFORMATION: u8 = 3;
+const PROCESSOR_INFORMATION: u8 = 4;
 const OEM_STRING: u8 = 11;
+const PHYSICAL_MEMORY_ARRAY: u8 = 16;
+const MEMORY_DEVICE: u8 = 17;
 const END_OF_TABLE: u8 = 127;
 const PCI_SUPPORTED: u64 = 1 << 7;
 const IS_VIRTUAL_MACHINE: u8 = 1 << 4;
+const NO_HANDLE: u16 = 0xffff;
+const NO_ERROR_INFORMATION: u16 = 0xfffe;
+const BOARD_IS_HOSTING_BOARD: u8 = 1 << 0;
+const BOARD_TYPE_MOTHERBOARD: u8 = 0x0a;
+const CHASSIS_TYPE_OTHER: u8 = 0x01;
+const CHASSIS_STATE_SAFE: u8 = 0x03;
+const CHASSIS_SECURITY_NONE: u8 = 0x03;
+const PROCESSOR_TYPE_CENTRAL: u8 = 0x03;
+const PROCESSOR_FAMILY_UNKNOWN: u16 = 0x02;
+const PROCESSOR_FAMILY_USE_FAMILY2: u8 = 0xfe;
+const PROCESSOR_STATUS_ENABLED: u8 = 0x41; // Socket populated, CPU enabled
+const PROCESSOR_UPGRADE_UNKNOWN: u8 = 0x02;
+const PROCESSOR_64BIT_CAPABLE: u16 = 1 << 2;
+const MEMORY_ARRAY_LOCATION_SYSTEM_BOARD: u8 = 0x03;
+const MEMORY_ARRAY_USE_SYSTEM_MEMORY: u8 = 0x03;
+const MEMORY_ARRAY_ECC_NONE: u8 = 0x03;
+const MEMORY_ARRAY_USE_EXTENDED_CAPACITY: u32 = 0x8000_0000;
+const MEMORY_DEVICE_USE_EXTENDED_SIZE: u16 = 0x7fff;
+const UNKNOWN_WIDTH: u16 = 0xffff;
+const MEMORY_FORM_FACTOR_UNKNOWN: u8 = 0x02;
+const MEMORY_TYPE_UNKNOWN: u8 = 0x02;
+const MEMORY_TYPE_DETAIL_UNKNOWN: u16 = 1 << 2;
 
 fn compute_checksum<T: Copy>(v: &T) -> u8 {
     // Safe because we are only reading the bytes within the size of the `T` reference `v`.
@@ -162,6 +211,135 @@ pub struct SmbiosOemStrings {
     pub count: u8,
 }
 
+#[repr(C, packed)]
+#[derive(Default, Clone, Copy, FromBytes, AsBytes)]
+pub struct SmbiosBaseboardInfo {
+    pub typ: u8,
+    pub length: u8,
+    pub handle: u16,
+    pub manufacturer: u8,
+    pub product: u8,
+    pub version: u8,
+    pub serial_number: u8,
+    pub asset_tag: u8,
+    pub feature_flags: u8,
+    pub location_in_chassis: u8,
+    pub chassis_handle: u16,
+    pub board_type: u8,
+    pub contained_object_handles: u8,
+}
+
+#[repr(C, packed)]
+#[derive(Default, Clone, Copy, FromBytes, AsBytes)]
+pub struct SmbiosChassisInfo {
+    pub typ: u8,
+    pub length: u8,
+    pub handle: u16,
+    pub manufacturer: u8,
+    pub chassis_type: u8,
+    pub version: u8,
+    pub serial_number: u8,
+    pub asset_tag: u8,
+    pub bootup_state: u8,
+    pub power_supply_state: u8,
+    pub thermal_state: u8,
+    pub security_status: u8,
+    pub oem_defined: u32,
+    pub height: u8,
+    pub power_cords: u8,
+    pub contained_element_count: u8,
+    pub contained_element_record_length: u8,
+    pub sku: u8,
+}
+
+#[repr(C, packed)]
+#[derive(Default, Clone, Copy, FromBytes, AsBytes)]
+pub struct SmbiosProcessorInfo {
+    pub typ: u8,
+    pub length: u8,
+    pub handle: u16,
+    pub socket_designation: u8,
+    pub processor_type: u8,
+    pub family: u8,
+    pub manufacturer: u8,
+    pub id: u64,
+    pub version: u8,
+    pub voltage: u8,
+    pub external_clock: u16,
+    pub max_speed: u16,
+    pub current_speed: u16,
+    pub status: u8,
+    pub upgrade: u8,
+    pub l1_cache_handle: u16,
+    pub l2_cache_handle: u16,
+    pub l3_cache_handle: u16,
+    pub serial_number: u8,
+    pub asset_tag: u8,
+    pub part_number: u8,
+    pub core_count: u8,
+    pub core_enabled: u8,
+    pub thread_count: u8,
+    pub characteristics: u16,
+    pub family2: u16,
+    pub core_count2: u16,
+    pub core_enabled2: u16,
+    pub thread_count2: u16,
+}
+
+#[repr(C, packed)]
+#[derive(Default, Clone, Copy, FromBytes, AsBytes)]
+pub struct SmbiosMemoryArray {
+    pub typ: u8,
+    pub length: u8,
+    pub handle: u16,
+    pub location: u8,
+    pub usage: u8,
+    pub error_correction: u8,
+    pub maximum_capacity: u32,
+    pub error_information_handle: u16,
+    pub number_of_devices: u16,
+    pub extended_maximum_capacity: u64,
+}
+
+#[repr(C, packed)]
+#[derive(Default, Clone, Copy, FromBytes, AsBytes)]
+pub struct SmbiosMemoryDevice {
+    pub typ: u8,
+    pub length: u8,
+    pub handle: u16,
+    pub memory_array_handle: u16,
+    pub error_information_handle: u16,
+    pub total_width: u16,
+    pub data_width: u16,
+    pub size: u16,
+    pub form_factor: u8,
+    pub device_set: u8,
+    pub locator: u8,
+    pub bank_locator: u8,
+    pub memory_type: u8,
+    pub type_detail: u16,
+    pub speed: u16,
+    pub manufacturer: u8,
+    pub serial_number: u8,
+    pub asset_tag: u8,
+    pub part_number: u8,
+    pub attributes: u8,
+    pub extended_size: u32,
+    pub configured_speed: u16,
+    pub minimum_voltage: u16,
+    pub maximum_voltage: u16,
+    pub configured_voltage: u16,
+}
+
+/// Header of every structure, and whole end-of-table structure.
+#[repr(C, packed)]
+#[derive(Default, Clone, Copy, FromBytes, AsBytes)]
+pub struct SmbiosHeader {
+    pub typ: u8,
+    pub length: u8,
+    pub handle: u16,
+}
+
 fn write_and_incr<T: AsBytes + FromBytes>(
     mem: &GuestMemory,
     val: T,
@@ -255,105 +433,429 @@ fn setup_smbios_from_file(mem: &GuestMemory, path: &Path) -> Result<()> {
     Err(Error::InvalidInput)
 }
 
+/// Builds the structure table of the generated SMBIOS data in guest memory.
+struct SmbiosTable<'a> {
+    mem: &'a GuestMemory,
+    start: GuestAddress,
+    curptr: GuestAddress,
+    handle: u16,
+    count: u16,
+    max_structure_size: u16,
+}
+
+impl<'a> SmbiosTable<'a> {
+    fn new(mem: &'a GuestMemory, start: GuestAddress) -> Self {
+        SmbiosTable {
+            mem,
+            start,
+            curptr: start,
+            handle: 0,
+            count: 0,
+            max_structure_size: 0,
+        }
+    }
+
+    fn next_handle(&mut self) -> u16 {
+        self.handle += 1;
+        self.handle
+    }
+
+    /// Writes a structure made of the formatted area `formatted` and of `strings`.
+    fn add_structure(&mut self, formatted: &[u8], strings: &[&str]) -> Result<()> {
+        let start = self.curptr;
+        self.mem
+            .write_all_at_addr(formatted, self.curptr)
+            .map_err(|_| Error::WriteData)?;
+        self.curptr = self
+            .curptr
+            .checked_add(formatted.len() as u64)
+            .ok_or(Error::NotEnoughMemory)?;
+        for string in strings {
+            self.curptr = write_string(self.mem, string, self.curptr)?;
+        }
+        // The string set ends with a null byte, and is made of two null bytes when empty.
+        if strings.is_empty() {
+            self.curptr = write_and_incr(self.mem, 0_u8, self.curptr)?;
+        }
+        self.curptr = write_and_incr(self.mem, 0_u8, self.curptr)?;
+
+        let size = u16::try_from(self.curptr.offset_from(start)).unwrap_or(u16::MAX);
+        self.max_structure_size = self.max_structure_size.max(size);
+        self.count += 1;
+        Ok(())
+    }
+
+    fn size(&self) -> u64 {
+        self.curptr.offset_from(self.start)
+    }
+}
+
+/// Strings of a structure, referenced by their index from 1. Index 0 means no string.
+struct SmbiosStrings<'a> {
+    typ: u8,
+    strings: Vec<&'a str>,
+}
+
+impl<'a> SmbiosStrings<'a> {
+    fn new(typ: u8) -> Self {
+        SmbiosStrings {
+            typ,
+            strings: Vec::new(),
+        }
+    }
+
+    fn add(&mut self, string: Option<&'a str>) -> Result<u8> {
+        let string = match string {
+            Some(string) if !string.is_empty() => string,
+            _ => return Ok(0),
+        };
+        if string.contains('\0') {
+            return Err(Error::StringHasNullCharacter(string.to_owned()));
+        }
+        if self.strings.len() >= u8::MAX.into() {
+            return Err(Error::TooManyStrings(self.typ));
+        }
+        self.strings.push(string);
+        Ok(self.strings.len() as u8)
+    }
+}
+
+fn add_bios_info(table: &mut SmbiosTable, config: Option<&SmbiosBiosConfig>) -> Result<()> {
+    let config = config.cloned().unwrap_or_default();
+    let mut strings = SmbiosStrings::new(BIOS_INFORMATION);
+    let smbios_biosinfo = SmbiosBiosInfo {
+        typ: BIOS_INFORMATION,
+        length: mem::size_of::<SmbiosBiosInfo>() as u8,
+        handle: table.next_handle(),
+        vendor: strings.add(Some(config.vendor.as_deref().unwrap_or("crosvm")))?,
+        version: strings.add(Some(config.version.as_deref().unwrap_or("0")))?,
+        release_date: strings.add(config.release_date.as_deref())?,
+        characteristics: PCI_SUPPORTED,
+        characteristics_ext2: IS_VIRTUAL_MACHINE,
+        ..Default::default()
+    };
+    table.add_structure(smbios_biosinfo.as_bytes(), &strings.strings)
+}
+
+fn add_system_info(table: &mut SmbiosTable, config: Option<&SmbiosSystemConfig>) -> Result<()> {
+    let config = config.cloned().unwrap_or_default();
+    let mut strings = SmbiosStrings::new(SYSTEM_INFORMATION);
+    let smbios_sysinfo = SmbiosSysInfo {
+        typ: SYSTEM_INFORMATION,
+        length: mem::size_of::<SmbiosSysInfo>() as u8,
+        handle: table.next_handle(),
+        manufacturer: strings.add(Some(config.manufacturer.as_deref().unwrap_or("ChromiumOS")))?,
+        product_name: strings.add(Some(config.product_name.as_deref().unwrap_or("crosvm")))?,
+        version: strings.add(config.version.as_deref())?,
+        serial_number: strings.add(config.serial_number.as_deref())?,
+        // The first three fields of the UUID are little-endian since SMBIOS 2.6.
+        uuid: config
+            .uuid
+            .map(|uuid| uuid.to_bytes_le())
+            .unwrap_or_default(),
+        sku: strings.add(config.sku.as_deref())?,
+        family: strings.add(config.family.as_deref())?,
+        ..Default::default()
+    };
+    table.add_structure(smbios_sysinfo.as_bytes(), &strings.strings)
+}
+
+fn add_baseboard_info(
+    table: &mut SmbiosTable,
+    config: &SmbiosBaseboardConfig,
+    chassis_handle: Option<u16>,
+) -> Result<()> {
+    let mut strings = SmbiosStrings::new(BASEBOARD_INFORMATION);
+    let smbios_baseboard = SmbiosBaseboardInfo {
+        typ: BASEBOARD_INFORMATION,
+        length: mem::size_of::<SmbiosBaseboardInfo>() as u8,
+        handle: table.next_handle(),
+        manufacturer: strings.add(config.manufacturer.as_deref())?,
+        product: strings.add(config.product.as_deref())?,
+        version: strings.add(config.version.as_deref())?,
+        serial_number: strings.add(config.serial_number.as_deref())?,
+        asset_tag: strings.add(config.asset_tag.as_deref())?,
+        feature_flags: BOARD_IS_HOSTING_BOARD,
+        chassis_handle: chassis_handle.unwrap_or(0),
+        board_type: BOARD_TYPE_MOTHERBOARD,
+        ..Default::default()
+    };
+    table.add_structure(smbios_baseboard.as_bytes(), &strings.strings)
+}
+
+fn add_chassis_info(
+    table: &mut SmbiosTable,
+    config: &SmbiosChassisConfig,
+    handle: u16,
+) -> Result<()> {
+    let mut strings = SmbiosStrings::new(CHASSIS_INFORMATION);
+    let smbios_chassis = SmbiosChassisInfo {
+        typ: CHASSIS_INFORMATION,
+        length: mem::size_of::<SmbiosChassisInfo>() as u8,
+        handle,
+        manufacturer: strings.add(config.manufacturer.as_deref())?,
+        chassis_type: config.chassis_type.unwrap_or(CHASSIS_TYPE_OTHER),
+        version: strings.add(config.version.as_deref())?,
+        serial_number: strings.add(config.serial_number.as_deref())?,
+        asset_tag: strings.add(config.asset_tag.as_deref())?,
+        bootup_state: CHASSIS_STATE_SAFE,
+        power_supply_state: CHASSIS_STATE_SAFE,
+        thermal_state: CHASSIS_STATE_SAFE,
+        security_status: CHASSIS_SECURITY_NONE,
+        sku: strings.add(config.sku.as_deref())?,
+        ..Default::default()
+    };
+    table.add_structure(smbios_chassis.as_bytes(), &strings.strings)
+}
+
+fn add_processor_info(table: &mut SmbiosTable, config: &SmbiosProcessorConfig) -> Result<()> {
+    let mut strings = SmbiosStrings::new(PROCESSOR_INFORMATION);
+    // Values that don't fit in the original byte fields are only given in the SMBIOS 3.0 ones.
+    let family = config.family.unwrap_or(PROCESSOR_FAMILY_UNKNOWN);
+    let core_count = config.core_count.unwrap_or(0);
+    let thread_count = config.thread_count.unwrap_or(0);
+    let smbios_processor = SmbiosProcessorInfo {
+        typ: PROCESSOR_INFORMATION,
+        length: mem::size_of::<SmbiosProcessorInfo>() as u8,
+        handle: table.next_handle(),
+        socket_designation: strings.add(config.socket_designation.as_deref())?,
+        processor_type: PROCESSOR_TYPE_CENTRAL,
+        family: u8::try_from(family)
+            .ok()
+            .filter(|f| *f < PROCESSOR_FAMILY_USE_FAMILY2)
+            .unwrap_or(PROCESSOR_FAMILY_USE_FAMILY2),
+        manufacturer: strings.add(config.manufacturer.as_deref())?,
+        version: strings.add(config.version.as_deref())?,
+        max_speed: config.max_speed.unwrap_or(0),
+        current_speed: config.current_speed.unwrap_or(0),
+        status: PROCESSOR_STATUS_ENABLED,
+        upgrade: PROCESSOR_UPGRADE_UNKNOWN,
+        l1_cache_handle: NO_HANDLE,
+        l2_cache_handle: NO_HANDLE,
+        l3_cache_handle: NO_HANDLE,
+        serial_number: strings.add(config.serial_number.as_deref())?,
+        asset_tag: strings.add(config.asset_tag.as_deref())?,
+        part_number: strings.add(config.part_number.as_deref())?,
+        core_count: u8::try_from(core_count).unwrap_or(u8::MAX),
+        core_enabled: u8::try_from(core_count).unwrap_or(u8::MAX),
+        thread_count: u8::try_from(thread_count).unwrap_or(u8::MAX),
+        characteristics: PROCESSOR_64BIT_CAPABLE,
+        family2: family,
+        core_count2: core_count,
+        core_enabled2: core_count,
+        thread_count2: thread_count,
+        ..Default::default()
+    };
+    table.add_structure(smbios_processor.as_bytes(), &strings.strings)
+}
+
+fn add_memory_devices(table: &mut SmbiosTable, devices: &[SmbiosMemoryDeviceConfig]) -> Result<()> {
+    let capacity_kib: u64 = devices.iter().map(|d| u64::from(d.size) * 1024).sum();
+    let array_handle = table.next_handle();
+    let smbios_memory_array = SmbiosMemoryArray {
+        typ: PHYSICAL_MEMORY_ARRAY,
+        length: mem::size_of::<SmbiosMemoryArray>() as u8,
+        handle: array_handle,
+        location: MEMORY_ARRAY_LOCATION_SYSTEM_BOARD,
+        usage: MEMORY_ARRAY_USE_SYSTEM_MEMORY,
+        error_correction: MEMORY_ARRAY_ECC_NONE,
+        maximum_capacity: match u32::try_from(capacity_kib) {
+            Ok(kib) if kib < MEMORY_ARRAY_USE_EXTENDED_CAPACITY => kib,
+            _ => MEMORY_ARRAY_USE_EXTENDED_CAPACITY,
+        },
+        error_information_handle: NO_ERROR_INFORMATION,
+        number_of_devices: devices.len() as u16,
+        extended_maximum_capacity: capacity_kib * 1024,
+    };
+    table.add_structure(smbios_memory_array.as_bytes(), &[])?;
+
+    for device in devices {
+        let mut strings = SmbiosStrings::new(MEMORY_DEVICE);
+        let speed = device.speed.unwrap_or(0);
+        let size = match u16::try_from(device.size) {
+            Ok(mib) if mib < MEMORY_DEVICE_USE_EXTENDED_SIZE => mib,
+            _ => MEMORY_DEVICE_USE_EXTENDED_SIZE,
+        };
+        let smbios_memory_device = SmbiosMemoryDevice {
+            typ: MEMORY_DEVICE,
+            length: mem::size_of::<SmbiosMemoryDevice>() as u8,
+            handle: table.next_handle(),
+            memory_array_handle: array_handle,
+            error_information_handle: NO_ERROR_INFORMATION,
+            total_width: UNKNOWN_WIDTH,
+            data_width: UNKNOWN_WIDTH,
+            size,
+            form_factor: MEMORY_FORM_FACTOR_UNKNOWN,
+            locator: strings.add(device.locator.as_deref())?,
+            bank_locator: strings.add(device.bank_locator.as_deref())?,
+            memory_type: device.memory_type.unwrap_or(MEMORY_TYPE_UNKNOWN),
+            type_detail: MEMORY_TYPE_DETAIL_UNKNOWN,
+            speed,
+            manufacturer: strings.add(device.manufacturer.as_deref())?,
+            serial_number: strings.add(device.serial_number.as_deref())?,
+            asset_tag: strings.add(device.asset_tag.as_deref())?,
+            part_number: strings.add(device.part_number.as_deref())?,
+            extended_size: if size == MEMORY_DEVICE_USE_EXTENDED_SIZE {
+                device.size
+            } else {
+                0
+            },
+            configured_speed: speed,
+            ..Default::default()
+        };
+        table.add_structure(smbios_memory_device.as_bytes(), &strings.strings)?;
+    }
+    Ok(())
+}
+
+fn add_oem_strings(table: &mut SmbiosTable, oem_strings: &[&str]) -> Result<()> {
+    // AFAIK nothing prevents us from creating multiple OEM string tables
+    // if we have more than 255 strings, but 255 already seems pretty
+    // excessive.
+    if oem_strings.len() > u8::MAX.into() {
+        return Err(Error::TooManyOemStrings);
+    }
+    if oem_strings.iter().any(|s| s.contains('\0')) {
+        return Err(Error::OemStringHasNullCharacter);
+    }
+    let smbios_oemstring = SmbiosOemStrings {
+        typ: OEM_STRING,
+        length: mem::size_of::<SmbiosOemStrings>() as u8,
+        handle: table.next_handle(),
+        count: oem_strings.len() as u8,
+    };
+    table.add_structure(smbios_oemstring.as_bytes(), oem_strings)
+}
+
+fn add_raw_structure(table: &mut SmbiosTable, raw: &SmbiosRawStructure) -> Result<()> {
+    let length = u8::try_from(mem::size_of::<SmbiosHeader>() + raw.data.len())
+        .map_err(|_| Error::InvalidRawStructure(raw.typ))?;
+    if raw.typ == END_OF_TABLE {
+        return Err(Error::InvalidRawStructure(raw.typ));
+    }
+    let mut strings = SmbiosStrings::new(raw.typ);
+    for string in &raw.strings {
+        if strings.add(Some(string))? == 0 {
+            // Empty strings can't be referenced and would end the string set.
+            return Err(Error::InvalidRawStructure(raw.typ));
+        }
+    }
+    let header = SmbiosHeader {
+        typ: raw.typ,
+        length,
+        handle: table.next_handle(),
+    };
+    let mut formatted = header.as_bytes().to_vec();
+    formatted.extend_from_slice(&raw.data);
+    table.add_structure(&formatted, &strings.strings)
+}
+
 pub fn setup_smbios(
     mem: &GuestMemory,
     dmi_path: Option<PathBuf>,
     oem_strings: &[String],
+    config: &SmbiosConfig,
 ) -> Result<()> {
     if let Some(dmi_path) = dmi_path {
         return setup_smbios_from_file(mem, &dmi_path);
     }
 
+    let entry_point_size = match config.entry_point {
+        SmbiosEntryPoint::Smbios2 => mem::size_of::<Smbios23Entrypoint>(),
+        SmbiosEntryPoint::Smbios3 => mem::size_of::<Smbios30Entrypoint>(),
+    };
     let physptr = GuestAddress(SMBIOS_START)
-        .checked_add(mem::size_of::<Smbios30Entrypoint>() as u64)
+        .checked_add(entry_point_size as u64)
         .ok_or(Error::NotEnoughMemory)?;
-    let mut curptr = physptr;
-    let mut handle = 0;
+    let mut table = SmbiosTable::new(mem, physptr);
 
-    {
-        handle += 1;
-        let smbios_biosinfo = SmbiosBiosInfo {
-            typ: BIOS_INFORMATION,
-            length: mem::size_of::<SmbiosBiosInfo>() as u8,
-            handle,
-            vendor: 1,  // First string written in this section
-            version: 2, // Second string written in this section
-            characteristics: PCI_SUPPORTED,
-            characteristics_ext2: IS_VIRTUAL_MACHINE,
-            ..Default::default()
-        };
-        curptr = write_and_incr(mem, smbios_biosinfo, curptr)?;
-        curptr = write_string(mem, "crosvm", curptr)?;
-        curptr = write_string(mem, "0", curptr)?;
-        curptr = write_and_incr(mem, 0_u8, curptr)?;
+    // Raw structures of type 0 or 1 replace the generated ones.
+    if !config.raw.iter().any(|raw| raw.typ == BIOS_INFORMATION) {
+        add_bios_info(&mut table, config.bios.as_ref())?;
+    }
+    if !config.raw.iter().any(|raw| raw.typ == SYSTEM_INFORMATION) {
+        add_system_info(&mut table, config.system.as_ref())?;
     }
 
-    {
-        handle += 1;
-        let smbios_sysinfo = SmbiosSysInfo {
-            typ: SYSTEM_INFORMATION,
-            length: mem::size_of::<SmbiosSysInfo>() as u8,
-            handle,
-            manufacturer: 1, // First string written in this section
-            product_name: 2, // Second string written in this section
-            ..Default::default()
-        };
-        curptr = write_and_incr(mem, smbios_sysinfo, curptr)?;
-        curptr = write_string(mem, "ChromiumOS", curptr)?;
-        curptr = write_string(mem, "crosvm", curptr)?;
-        curptr = write_and_incr(mem, 0u8, curptr)?;
+    // The chassis handle is referenced by the baseboard.
+    let chassis_handle = config.chassis.as_ref().map(|_| table.next_handle());
+    if let Some(baseboard) = &config.baseboard {
+        add_baseboard_info(&mut table, baseboard, chassis_handle)?;
+    }
+    if let (Some(chassis), Some(handle)) = (&config.chassis, chassis_handle) {
+        add_chassis_info(&mut table, chassis, handle)?;
+    }
+
+    for processor in &config.processors {
+        add_processor_info(&mut table, processor)?;
     }
 
+    let oem_strings: Vec<&str> = oem_strings
+        .iter()
+        .chain(config.oem_strings.iter())
+        .map(String::as_str)
+        .collect();
     if !oem_strings.is_empty() {
-        // AFAIK nothing prevents us from creating multiple OEM string tables
-        // if we have more than 255 strings, but 255 already seems pretty
-        // excessive.
-        if oem_strings.len() > u8::MAX.into() {
-            return Err(Error::TooManyOemStrings);
-        }
-        handle += 1;
-        let smbios_oemstring = SmbiosOemStrings {
-            typ: OEM_STRING,
-            length: mem::size_of::<SmbiosOemStrings>() as u8,
-            handle,
-            count: oem_strings.len() as u8,
-        };
-        curptr = write_and_incr(mem, smbios_oemstring, curptr)?;
-        for oem_string in oem_strings {
-            if oem_string.contains("\0") {
-                return Err(Error::OemStringHasNullCharacter);
-            }
-            curptr = write_string(mem, oem_string, curptr)?;
-        }
-        curptr = write_and_incr(mem, 0u8, curptr)?;
+        add_oem_strings(&mut table, &oem_strings)?;
+    }
+
+    if !config.memory_devices.is_empty() {
+        add_memory_devices(&mut table, &config.memory_devices)?;
+    }
+
+    for raw in &config.raw {
+        add_raw_structure(&mut table, raw)?;
     }
 
     {
-        handle += 1;
-        let smbios_sysinfo = SmbiosSysInfo {
+        let smbios_end = SmbiosHeader {
             typ: END_OF_TABLE,
-            length: mem::size_of::<SmbiosSysInfo>() as u8,
-            handle,
-            ..Default::default()
+            length: mem::size_of::<SmbiosHeader>() as u8,
+            handle: table.next_handle(),
         };
-        curptr = write_and_incr(mem, smbios_sysinfo, curptr)?;
-        curptr = write_and_incr(mem, 0_u8, curptr)?;
+        table.add_structure(smbios_end.as_bytes(), &[])?;
     }
 
-    {
-        let mut smbios_ep = Smbios30Entrypoint::default();
-        smbios_ep.signature = *SM3_MAGIC_IDENT;
-        smbios_ep.length = mem::size_of::<Smbios30Entrypoint>() as u8;
-        // SMBIOS rev 3.2.0
-        smbios_ep.majorver = 0x03;
-        smbios_ep.minorver = 0x02;
-        smbios_ep.docrev = 0x00;
-        smbios_ep.revision = 0x01; // SMBIOS 3.0
-        smbios_ep.max_size = curptr.offset_from(physptr) as u32;
-        smbios_ep.physptr = physptr.offset();
-        smbios_ep.checksum = compute_checksum(&smbios_ep);
-        mem.write_obj_at_addr(smbios_ep, GuestAddress(SMBIOS_START))
-            .map_err(|_| Error::WriteSmbiosEp)?;
+    match config.entry_point {
+        SmbiosEntryPoint::Smbios2 => {
+            let mut dmi = Smbios23Intermediate {
+                signature: *DMI_MAGIC_IDENT,
+                length: u16::try_from(table.size()).map_err(|_| Error::TableTooLarge)?,
+                address: physptr.offset() as u32,
+                count: table.count,
+                revision: 0x28, // SMBIOS 2.8
+                ..Default::default()
+            };
+            dmi.checksum = compute_checksum(&dmi);
+            let mut smbios_ep = Smbios23Entrypoint {
+                signature: *SM2_MAGIC_IDENT,
+                length: mem::size_of::<Smbios23Entrypoint>() as u8,
+                // SMBIOS rev 2.8
+                majorver: 0x02,
+                minorver: 0x08,
+                max_size: table.max_structure_size,
+                dmi,
+                ..Default::default()
+            };
+            smbios_ep.checksum = compute_checksum(&smbios_ep);
+            mem.write_obj_at_addr(smbios_ep, GuestAddress(SMBIOS_START))
+                .map_err(|_| Error::WriteSmbiosEp)?;
+        }
+        SmbiosEntryPoint::Smbios3 => {
+            let mut smbios_ep = Smbios30Entrypoint::default();
+            smbios_ep.signature = *SM3_MAGIC_IDENT;
+            smbios_ep.length = mem::size_of::<Smbios30Entrypoint>() as u8;
+            // SMBIOS rev 3.2.0
+            smbios_ep.majorver = 0x03;
+            smbios_ep.minorver = 0x02;
+            smbios_ep.docrev = 0x00;
+            smbios_ep.revision = 0x01; // SMBIOS 3.0
+            smbios_ep.max_size = table.size() as u32;
+            smbios_ep.physptr = physptr.offset();
+            smbios_ep.checksum = compute_checksum(&smbios_ep);
+            mem.write_obj_at_addr(smbios_ep, GuestAddress(SMBIOS_START))
+                .map_err(|_| Error::WriteSmbiosEp)?;
+        }
     }
 
     Ok(())
@@ -389,6 +891,31 @@ mod tests {
             mem::size_of::<SmbiosOemStrings>(),
             0x5usize,
             concat!("Size of: ", stringify!(SmbiosOemStrings))
+        );
+        assert_eq!(
+            mem::size_of::<SmbiosBaseboardInfo>(),
+            0xfusize,
+            concat!("Size of: ", stringify!(SmbiosBaseboardInfo))
+        );
+        assert_eq!(
+            mem::size_of::<SmbiosChassisInfo>(),
+            0x16usize,
+            concat!("Size of: ", stringify!(SmbiosChassisInfo))
+        );
+        assert_eq!(
+            mem::size_of::<SmbiosProcessorInfo>(),
+            0x30usize,
+            concat!("Size of: ", stringify!(SmbiosProcessorInfo))
+        );
+        assert_eq!(
+            mem::size_of::<SmbiosMemoryArray>(),
+            0x17usize,
+            concat!("Size of: ", stringify!(SmbiosMemoryArray))
+        );
+        assert_eq!(
+            mem::size_of::<SmbiosMemoryDevice>(),
+            0x28usize,
+            concat!("Size of: ", stringify!(SmbiosMemoryDevice))
         )
     }
 
@@ -397,11 +924,150 @@ mod tests {
         let mem = GuestMemory::new(&[(GuestAddress(SMBIOS_START), 4096)]).unwrap();
 
         // Use default 3.0 SMBIOS format.
-        setup_smbios(&mem, None, &Vec::new()).unwrap();
+        setup_smbios(&mem, None, &Vec::new(), &Default::default()).unwrap();
 
         let smbios_ep: Smbios30Entrypoint =
             mem.read_obj_from_addr(GuestAddress(SMBIOS_START)).unwrap();
 
         assert_eq!(compute_checksum(&smbios_ep), 0);
     }
+
+    /// Returns the type, address and strings of the structures of the table at `address`.
+    fn read_table(
+        mem: &GuestMemory,
+        mut address: GuestAddress,
+    ) -> Vec<(u8, GuestAddress, Vec<String>)> {
+        let mut structures = Vec::new();
+        loop {
+            let start = address;
+            let header: SmbiosHeader = mem.read_obj_from_addr(address).unwrap();
+            address = address.unchecked_add(header.length.into());
+            let mut strings = Vec::new();
+            loop {
+                let mut string = Vec::new();
+                loop {
+                    let c: u8 = mem.read_obj_from_addr(address).unwrap();
+                    address = address.unchecked_add(1);
+                    if c == 0 {
+                        break;
+                    }
+                    string.push(c);
+                }
+                if string.is_empty() {
+                    break;
+                }
+                strings.push(String::from_utf8(string).unwrap());
+            }
+            if strings.is_empty() {
+                // Skip the second null byte of an empty string set.
+                address = address.unchecked_add(1);
+            }
+            structures.push((header.typ, start, strings));
+            if header.typ == END_OF_TABLE {
+                return structures;
+            }
+        }
+    }
+
+    #[test]
+    fn custom_structures() {
+        let mem = GuestMemory::new(&[(GuestAddress(SMBIOS_START), 4096)]).unwrap();
+        let config = SmbiosConfig {
+            entry_point: SmbiosEntryPoint::Smbios2,
+            system: Some(SmbiosSystemConfig {
+                product_name: Some("Test Machine".to_owned()),
+                ..Default::default()
+            }),
+            baseboard: Some(Default::default()),
+            chassis: Some(SmbiosChassisConfig {
+                manufacturer: Some("Test Vendor".to_owned()),
+                ..Default::default()
+            }),
+            processors: vec![SmbiosProcessorConfig {
+                version: Some("Test CPU".to_owned()),
+                core_count: Some(300),
+                ..Default::default()
+            }],
+            oem_strings: vec!["from config".to_owned()],
+            memory_devices: vec![SmbiosMemoryDeviceConfig {
+                size: 65536,
+                locator: Some("DIMM 0".to_owned()),
+                ..Default::default()
+            }],
+            raw: vec![
+                SmbiosRawStructure {
+                    typ: BIOS_INFORMATION,
+                    data: vec![1, 0],
+                    strings: vec!["Raw BIOS".to_owned()],
+                },
+                SmbiosRawStructure {
+                    typ: 200,
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+        setup_smbios(&mem, None, &["from command line".to_owned()], &config).unwrap();
+
+        let smbios_ep: Smbios23Entrypoint =
+            mem.read_obj_from_addr(GuestAddress(SMBIOS_START)).unwrap();
+        assert_eq!(compute_checksum(&smbios_ep), 0);
+        assert_eq!(compute_checksum(&smbios_ep.dmi), 0);
+        let address = smbios_ep.dmi.address;
+        let count = smbios_ep.dmi.count;
+
+        let structures = read_table(&mem, GuestAddress(address.into()));
+        assert_eq!(structures.len(), count as usize);
+        let types: Vec<u8> = structures.iter().map(|(typ, _, _)| *typ).collect();
+        assert_eq!(
+            types,
+            vec![
+                SYSTEM_INFORMATION,
+                BASEBOARD_INFORMATION,
+                CHASSIS_INFORMATION,
+                PROCESSOR_INFORMATION,
+                OEM_STRING,
+                PHYSICAL_MEMORY_ARRAY,
+                MEMORY_DEVICE,
+                BIOS_INFORMATION,
+                200,
+                END_OF_TABLE
+            ]
+        );
+        assert_eq!(structures[0].2, vec!["ChromiumOS", "Test Machine"]);
+        assert_eq!(structures[2].2, vec!["Test Vendor"]);
+        assert_eq!(structures[4].2, vec!["from command line", "from config"]);
+        assert_eq!(structures[7].2, vec!["Raw BIOS"]);
+
+        // The baseboard is in the chassis.
+        let baseboard: SmbiosBaseboardInfo = mem.read_obj_from_addr(structures[1].1).unwrap();
+        let chassis: SmbiosChassisInfo = mem.read_obj_from_addr(structures[2].1).unwrap();
+        assert_eq!({ baseboard.chassis_handle }, { chassis.handle });
+
+        // Counts above 255 are only in the SMBIOS 3.0 fields.
+        let processor: SmbiosProcessorInfo = mem.read_obj_from_addr(structures[3].1).unwrap();
+        assert_eq!(processor.core_count, 0xff);
+        assert_eq!({ processor.core_count2 }, 300);
+
+        // Sizes above 32 GiB are in the extended size.
+        let device: SmbiosMemoryDevice = mem.read_obj_from_addr(structures[6].1).unwrap();
+        assert_eq!({ device.size }, MEMORY_DEVICE_USE_EXTENDED_SIZE);
+        assert_eq!({ device.extended_size }, 65536);
+    }
+
+    #[test]
+    fn invalid_raw_structure() {
+        let mem = GuestMemory::new(&[(GuestAddress(SMBIOS_START), 4096)]).unwrap();
+        let config = SmbiosConfig {
+            raw: vec![SmbiosRawStructure {
+                typ: END_OF_TABLE,
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        assert!(matches!(
+            setup_smbios(&mem, None, &[], &config),
+            Err(Error::InvalidRawStructure(END_OF_TABLE))
+        ));
+    }
 }
@@ -223,6 +223,9 @@ where
         #[cfg(unix)]
         false,
         Default::default(),
+        None,
+        #[cfg(unix)]
+        None,
     )
     .unwrap();
 
@@ -239,12 +242,14 @@ where
 
     // Note that this puts the mptable at 0x9FC00 in guest physical memory.
     mptable::setup_mptable(&guest_mem, 1, &pci_irqs).expect("failed to setup mptable");
-    smbios::setup_smbios(&guest_mem, None, &Vec::new()).expect("failed to setup smbios");
+    smbios::setup_smbios(&guest_mem, None, &Vec::new(), &Default::default())
+        .expect("failed to setup smbios");
 
     let mut apic_ids = Vec::new();
     acpi::create_acpi_tables(
         &guest_mem,
         1,
+        None,
         X86_64_SCI_IRQ,
         0xcf9,
         6,
@@ -255,6 +260,8 @@ where
         read_pcie_cfg_mmio().start,
         max_bus,
         false,
+        &[],
+        &[],
     );
 
     let guest_mem2 = guest_mem.clone();